@@ -0,0 +1,12 @@
+-- Migration 0016: National ID Deduplication
+-- EmpleosInclusivos duplicate account detection and admin merge support
+
+-- ============================================================================
+-- INDEXES
+-- ============================================================================
+
+-- Lookups by national_id (RUT) when detecting duplicate accounts.
+-- Not unique: existing duplicates must be merged by an admin first.
+CREATE INDEX IF NOT EXISTS idx_job_seeker_profiles_national_id
+    ON job_seeker_profiles(national_id)
+    WHERE national_id IS NOT NULL;
@@ -0,0 +1,33 @@
+-- Migration 0017: Scheduled Job Publication
+-- EmpleosInclusivos jobs that go live automatically at a chosen time
+
+-- ============================================================================
+-- JOB STATUS
+-- ============================================================================
+
+-- Approved jobs with a future publish_at wait in 'scheduled' until the
+-- publication task activates them.
+ALTER TYPE job_status ADD VALUE IF NOT EXISTS 'scheduled' AFTER 'pending_approval';
+
+-- ============================================================================
+-- JOB COLUMNS
+-- ============================================================================
+
+ALTER TABLE jobs
+    ADD COLUMN IF NOT EXISTS publish_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS published_at TIMESTAMP WITH TIME ZONE;
+
+COMMENT ON COLUMN jobs.publish_at IS 'Requested go-live time; the job stays scheduled until then';
+COMMENT ON COLUMN jobs.published_at IS 'When the job last became publicly visible (active)';
+
+-- Enum values added in this migration cannot be referenced as literals in the
+-- same transaction, so compare on the text representation.
+ALTER TABLE jobs
+    ADD CONSTRAINT check_scheduled_has_approval
+    CHECK (status::text != 'scheduled' OR (approved_at IS NOT NULL AND approved_by IS NOT NULL AND publish_at IS NOT NULL));
+
+-- Backfill for jobs that were already live
+UPDATE jobs SET published_at = COALESCE(approved_at, created_at) WHERE status = 'active';
+
+-- Publication task scans due jobs by publish_at
+CREATE INDEX IF NOT EXISTS idx_jobs_publish_at ON jobs(publish_at) WHERE publish_at IS NOT NULL;
@@ -0,0 +1,60 @@
+-- Migration 0018: OMIL inter-organization transfers
+-- Moves a managed job seeker from one OMIL to another, keeping shared history
+
+-- ============================================================================
+-- TRANSFER REQUESTS
+-- ============================================================================
+
+CREATE TYPE omil_transfer_status AS ENUM (
+    'pending',
+    'accepted',
+    'declined',
+    'cancelled'
+);
+
+CREATE TABLE omil_transfers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_seeker_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source_omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    target_omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    source_managed_id UUID NOT NULL REFERENCES omil_managed_job_seekers(id) ON DELETE CASCADE,
+
+    reason TEXT,
+    status omil_transfer_status NOT NULL DEFAULT 'pending',
+
+    requested_by UUID NOT NULL REFERENCES users(id),
+    responded_by UUID REFERENCES users(id),
+    responded_at TIMESTAMP WITH TIME ZONE,
+
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_transfer_distinct_omils CHECK (source_omil_id <> target_omil_id)
+);
+
+COMMENT ON TABLE omil_transfers IS 'Requests to hand a managed job seeker over to another OMIL';
+
+-- Only one open transfer per managed record
+CREATE UNIQUE INDEX idx_omil_transfers_one_pending
+    ON omil_transfers(source_managed_id)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_omil_transfers_source ON omil_transfers(source_omil_id, created_at DESC);
+CREATE INDEX idx_omil_transfers_target ON omil_transfers(target_omil_id, created_at DESC);
+
+CREATE TRIGGER update_omil_transfers_updated_at
+    BEFORE UPDATE ON omil_transfers
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- ============================================================================
+-- TRANSFER TRACKING ON EXISTING TABLES
+-- ============================================================================
+
+-- Set on the source record once the seeker has moved; stats skip these rows
+ALTER TABLE omil_managed_job_seekers
+    ADD COLUMN transferred_to_omil_id UUID REFERENCES omil_organizations(id) ON DELETE SET NULL,
+    ADD COLUMN transferred_at TIMESTAMP WITH TIME ZONE;
+
+-- Followups copied to the receiving OMIL point back at their original
+ALTER TABLE job_seeker_followups
+    ADD COLUMN copied_from_id UUID REFERENCES job_seeker_followups(id) ON DELETE SET NULL;
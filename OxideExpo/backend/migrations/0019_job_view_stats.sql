@@ -0,0 +1,18 @@
+-- Migration 0019: Daily job view statistics
+-- jobs.views_count only holds a lifetime total; benchmarks need views per period
+
+-- ============================================================================
+-- JOB VIEW STATS
+-- ============================================================================
+
+CREATE TABLE job_view_stats (
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    view_date DATE NOT NULL DEFAULT CURRENT_DATE,
+    views INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (job_id, view_date)
+);
+
+COMMENT ON TABLE job_view_stats IS 'Public job detail views per job and day';
+
+CREATE INDEX idx_job_view_stats_date ON job_view_stats(view_date);
@@ -0,0 +1,7 @@
+-- Migration 0020: Per-user token versioning
+-- Bumping token_version invalidates every access token issued to the user,
+-- including ones never seen by the Redis blacklist
+
+ALTER TABLE users ADD COLUMN token_version INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN users.token_version IS 'Embedded in access tokens; incremented to revoke all of a user''s tokens';
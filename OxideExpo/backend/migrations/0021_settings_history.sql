@@ -0,0 +1,16 @@
+-- Migration 0021: System settings history
+-- Every change made through the admin settings endpoint is recorded here
+
+CREATE TABLE settings_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    key VARCHAR(100) NOT NULL,
+    old_value JSONB,
+    new_value JSONB NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_settings_history_key ON settings_history(key, changed_at DESC);
+
+COMMENT ON TABLE settings_history IS 'Audit trail of system_settings changes';
+COMMENT ON COLUMN settings_history.old_value IS 'NULL when the setting was first stored over its default';
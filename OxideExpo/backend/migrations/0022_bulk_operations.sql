@@ -0,0 +1,36 @@
+-- Migration 0022: Asynchronous bulk operations
+-- Very large applicant selections are processed by a background worker one
+-- chunk at a time; next_chunk is advanced in the same transaction as each
+-- chunk's update so a crashed worker resumes where it stopped
+
+CREATE TYPE bulk_operation_status AS ENUM ('queued', 'running', 'done', 'failed');
+
+CREATE TABLE bulk_operations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id),
+    target_status application_status NOT NULL,
+    status bulk_operation_status NOT NULL DEFAULT 'queued',
+
+    -- Applications that passed validation when the operation was queued
+    application_ids UUID[] NOT NULL,
+    total INTEGER NOT NULL,
+    next_chunk INTEGER NOT NULL DEFAULT 0,
+    processed INTEGER NOT NULL DEFAULT 0,
+    updated_count INTEGER NOT NULL DEFAULT 0,
+    -- Missing at queue time, or changed by someone else before their chunk ran
+    failed_ids UUID[] NOT NULL DEFAULT '{}',
+    skipped_count INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMP WITH TIME ZONE,
+    heartbeat_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_bulk_operations_pending ON bulk_operations(created_at)
+    WHERE status IN ('queued', 'running');
+CREATE INDEX idx_bulk_operations_job ON bulk_operations(job_id, created_at DESC);
+
+COMMENT ON COLUMN bulk_operations.heartbeat_at IS 'Touched after every chunk; running operations with a stale heartbeat are reclaimed';
@@ -0,0 +1,29 @@
+-- Migration 0023: Review of sensitive company profile changes
+-- Once a company is approved, edits to its identity (name, legal name, RUT)
+-- wait here for an admin instead of being applied immediately
+
+CREATE TYPE profile_change_status AS ENUM ('pending', 'approved', 'rejected');
+
+CREATE TABLE pending_profile_changes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    field_name VARCHAR(50) NOT NULL CHECK (field_name IN ('company_name', 'legal_name', 'tax_id')),
+    old_value TEXT,
+    new_value TEXT NOT NULL,
+    status profile_change_status NOT NULL DEFAULT 'pending',
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    rejection_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- A later edit to the same field replaces the pending value
+CREATE UNIQUE INDEX idx_pending_profile_changes_open
+    ON pending_profile_changes(company_id, field_name)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_pending_profile_changes_company
+    ON pending_profile_changes(company_id, created_at DESC);
+
+COMMENT ON TABLE pending_profile_changes IS 'Sensitive company profile edits awaiting admin review';
@@ -0,0 +1,140 @@
+-- Migration 0024: Municipality coordinates for commute-distance filtering
+-- Coordinates are the comuna's urban centre; distances are great-circle
+-- estimates, good enough to answer "within 30 km of me"
+
+ALTER TABLE municipalities
+    ADD COLUMN latitude DOUBLE PRECISION,
+    ADD COLUMN longitude DOUBLE PRECISION;
+
+ALTER TABLE job_seeker_preferences
+    ADD COLUMN preferred_max_commute_km INTEGER CHECK (preferred_max_commute_km > 0);
+
+COMMENT ON COLUMN job_seeker_preferences.preferred_max_commute_km IS 'Recommendations skip on-site jobs farther than this from the seeker''s municipality';
+
+-- Great-circle distance in kilometres between two points
+CREATE OR REPLACE FUNCTION haversine_km(
+    lat1 DOUBLE PRECISION, lon1 DOUBLE PRECISION,
+    lat2 DOUBLE PRECISION, lon2 DOUBLE PRECISION
+) RETURNS DOUBLE PRECISION AS $$
+    SELECT 2 * 6371.0 * asin(sqrt(
+        power(sin(radians(lat2 - lat1) / 2), 2)
+        + cos(radians(lat1)) * cos(radians(lat2)) * power(sin(radians(lon2 - lon1) / 2), 2)
+    ))
+$$ LANGUAGE sql IMMUTABLE STRICT;
+
+UPDATE municipalities m
+SET latitude = v.latitude, longitude = v.longitude
+FROM (VALUES
+    -- Región Metropolitana
+    ('RM', 'Santiago', -33.4372, -70.6506),
+    ('RM', 'Providencia', -33.4314, -70.6093),
+    ('RM', 'Las Condes', -33.4117, -70.5678),
+    ('RM', 'Vitacura', -33.3897, -70.5714),
+    ('RM', 'La Reina', -33.4500, -70.5417),
+    ('RM', 'Ñuñoa', -33.4569, -70.5977),
+    ('RM', 'Macul', -33.4869, -70.5994),
+    ('RM', 'Peñalolén', -33.4858, -70.5425),
+    ('RM', 'La Florida', -33.5227, -70.5980),
+    ('RM', 'Puente Alto', -33.6117, -70.5758),
+    ('RM', 'San Bernardo', -33.5922, -70.6996),
+    ('RM', 'Maipú', -33.5106, -70.7572),
+    ('RM', 'Cerrillos', -33.5000, -70.7167),
+    ('RM', 'Estación Central', -33.4586, -70.6845),
+    ('RM', 'Quinta Normal', -33.4283, -70.6983),
+    ('RM', 'Renca', -33.4033, -70.7275),
+    ('RM', 'Quilicura', -33.3600, -70.7289),
+    ('RM', 'Huechuraba', -33.3667, -70.6333),
+    ('RM', 'Conchalí', -33.3833, -70.6750),
+    ('RM', 'Independencia', -33.4167, -70.6667),
+    ('RM', 'Recoleta', -33.4064, -70.6414),
+    ('RM', 'San Miguel', -33.4967, -70.6511),
+    ('RM', 'San Joaquín', -33.4950, -70.6283),
+    ('RM', 'La Granja', -33.5333, -70.6250),
+    ('RM', 'La Pintana', -33.5833, -70.6333),
+    ('RM', 'El Bosque', -33.5667, -70.6750),
+    ('RM', 'Pedro Aguirre Cerda', -33.4906, -70.6783),
+    ('RM', 'Lo Espejo', -33.5222, -70.6914),
+    ('RM', 'Cerro Navia', -33.4250, -70.7350),
+    ('RM', 'Pudahuel', -33.4400, -70.7600),
+    ('RM', 'Lo Prado', -33.4444, -70.7253),
+    ('RM', 'Lo Barnechea', -33.3500, -70.5167),
+    ('RM', 'San Ramón', -33.5333, -70.6417),
+    ('RM', 'Colina', -33.2000, -70.6833),
+    ('RM', 'Lampa', -33.2833, -70.8833),
+    ('RM', 'Buin', -33.7333, -70.7333),
+    ('RM', 'Paine', -33.8167, -70.7333),
+    ('RM', 'Melipilla', -33.6833, -71.2167),
+    ('RM', 'Talagante', -33.6667, -70.9333),
+    ('RM', 'Peñaflor', -33.6167, -70.8833),
+    ('RM', 'El Monte', -33.6833, -71.0167),
+    ('RM', 'Padre Hurtado', -33.5667, -70.8167),
+    ('RM', 'Isla de Maipo', -33.7500, -70.9000),
+    -- Valparaíso
+    ('V', 'Valparaíso', -33.0472, -71.6127),
+    ('V', 'Viña del Mar', -33.0246, -71.5518),
+    ('V', 'Concón', -32.9306, -71.5186),
+    ('V', 'Quilpué', -33.0472, -71.4425),
+    ('V', 'Villa Alemana', -33.0422, -71.3733),
+    ('V', 'Quillota', -32.8833, -71.2500),
+    ('V', 'La Calera', -32.7833, -71.2000),
+    ('V', 'San Antonio', -33.5933, -71.6217),
+    ('V', 'Cartagena', -33.5531, -71.6069),
+    ('V', 'El Quisco', -33.3972, -71.6972),
+    ('V', 'Algarrobo', -33.3667, -71.6667),
+    ('V', 'Casablanca', -33.3167, -71.4167),
+    ('V', 'Los Andes', -32.8333, -70.6000),
+    ('V', 'San Felipe', -32.7500, -70.7167),
+    -- Biobío
+    ('VIII', 'Concepción', -36.8270, -73.0503),
+    ('VIII', 'Talcahuano', -36.7167, -73.1167),
+    ('VIII', 'Hualpén', -36.7833, -73.0833),
+    ('VIII', 'San Pedro de la Paz', -36.8333, -73.1000),
+    ('VIII', 'Chiguayante', -36.9167, -73.0167),
+    ('VIII', 'Coronel', -37.0167, -73.1333),
+    ('VIII', 'Lota', -37.0833, -73.1500),
+    ('VIII', 'Tomé', -36.6167, -72.9500),
+    ('VIII', 'Penco', -36.7333, -72.9833),
+    ('VIII', 'Los Ángeles', -37.4667, -72.3500),
+    ('VIII', 'Mulchén', -37.7167, -72.2333),
+    ('VIII', 'Nacimiento', -37.5000, -72.6667),
+    -- La Araucanía
+    ('IX', 'Temuco', -38.7359, -72.5904),
+    ('IX', 'Padre Las Casas', -38.7667, -72.6000),
+    ('IX', 'Villarrica', -39.2833, -72.2167),
+    ('IX', 'Pucón', -39.2833, -71.9500),
+    ('IX', 'Angol', -37.8000, -72.7167),
+    ('IX', 'Victoria', -38.2333, -72.3333),
+    -- Coquimbo
+    ('IV', 'La Serena', -29.9027, -71.2519),
+    ('IV', 'Coquimbo', -29.9533, -71.3436),
+    ('IV', 'Ovalle', -30.6000, -71.2000),
+    ('IV', 'Illapel', -31.6333, -71.1667),
+    ('IV', 'Vicuña', -30.0333, -70.7167),
+    -- O'Higgins
+    ('VI', 'Rancagua', -34.1708, -70.7444),
+    ('VI', 'Machalí', -34.1833, -70.6500),
+    ('VI', 'San Fernando', -34.5833, -70.9833),
+    ('VI', 'Santa Cruz', -34.6333, -71.3667),
+    ('VI', 'Rengo', -34.4167, -70.8667),
+    -- Maule
+    ('VII', 'Talca', -35.4264, -71.6554),
+    ('VII', 'Curicó', -34.9828, -71.2394),
+    ('VII', 'Linares', -35.8500, -71.6000),
+    ('VII', 'Constitución', -35.3333, -72.4167),
+    ('VII', 'Cauquenes', -35.9667, -72.3167),
+    -- Los Lagos
+    ('X', 'Puerto Montt', -41.4693, -72.9424),
+    ('X', 'Puerto Varas', -41.3167, -72.9833),
+    ('X', 'Osorno', -40.5725, -73.1353),
+    ('X', 'Castro', -42.4833, -73.7667),
+    ('X', 'Ancud', -41.8667, -73.8333),
+    ('X', 'Frutillar', -41.1167, -73.0500),
+    -- Antofagasta
+    ('II', 'Antofagasta', -23.6500, -70.4000),
+    ('II', 'Calama', -22.4667, -68.9333),
+    ('II', 'Tocopilla', -22.0833, -70.2000),
+    ('II', 'Mejillones', -23.1000, -70.4500),
+    ('II', 'San Pedro de Atacama', -22.9167, -68.2000)
+) AS v(region_code, name, latitude, longitude)
+JOIN regions r ON r.code = v.region_code
+WHERE m.region_id = r.id AND m.name = v.name;
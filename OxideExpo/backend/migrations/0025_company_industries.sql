@@ -0,0 +1,42 @@
+-- Migration 0025: Companies can belong to up to three industries
+-- company_profiles.industry_id keeps mirroring the primary industry until
+-- clients have moved to the industries list
+
+CREATE TABLE company_industries (
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    industry_id UUID NOT NULL REFERENCES industries(id),
+    is_primary BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (company_id, industry_id)
+);
+
+-- At most one primary industry per company
+CREATE UNIQUE INDEX idx_company_industries_primary
+    ON company_industries(company_id) WHERE is_primary;
+
+CREATE INDEX idx_company_industries_industry ON company_industries(industry_id);
+
+-- Checked at commit so a whole set can be replaced within a transaction
+CREATE OR REPLACE FUNCTION check_company_industry_limit()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF (SELECT COUNT(*) FROM company_industries WHERE company_id = NEW.company_id) > 3 THEN
+        RAISE EXCEPTION 'A company can have at most 3 industries'
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE CONSTRAINT TRIGGER company_industries_limit
+    AFTER INSERT OR UPDATE ON company_industries
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION check_company_industry_limit();
+
+-- Existing single industries become the primary one
+INSERT INTO company_industries (company_id, industry_id, is_primary)
+SELECT id, industry_id, true
+FROM company_profiles
+WHERE industry_id IS NOT NULL;
+
+COMMENT ON COLUMN company_profiles.industry_id IS 'Deprecated: mirrors the primary row in company_industries';
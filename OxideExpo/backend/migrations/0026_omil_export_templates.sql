@@ -0,0 +1,30 @@
+-- Migration 0026: OMIL export templates and organization logos
+-- Each OMIL reports to its municipality in its own format; templates pick
+-- and order the exported columns and brand the sheet with the OMIL's logo
+
+ALTER TYPE file_type ADD VALUE IF NOT EXISTS 'omil_logo';
+
+ALTER TABLE omil_organizations
+    ADD COLUMN logo_file_id UUID REFERENCES uploaded_files(id) ON DELETE SET NULL;
+
+CREATE TABLE omil_export_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- Keys from OMIL_EXPORT_COLUMNS, in output order
+    columns TEXT[] NOT NULL CHECK (cardinality(columns) > 0),
+    header_title VARCHAR(200),
+    include_logo BOOLEAN NOT NULL DEFAULT false,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_omil_export_template_name UNIQUE (omil_id, name)
+);
+
+CREATE TRIGGER update_omil_export_templates_updated_at
+    BEFORE UPDATE ON omil_export_templates
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE omil_export_templates IS 'Column layouts for OMIL managed job seeker exports';
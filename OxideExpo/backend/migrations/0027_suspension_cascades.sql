@@ -0,0 +1,22 @@
+-- Migration 0027: Account suspension cascades
+-- Suspending a user pauses what they own; each change is recorded with the
+-- status it replaced so reactivation can put things back exactly
+
+ALTER TABLE job_applications
+    ADD COLUMN is_on_hold BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN job_applications.is_on_hold IS 'Applicant account is suspended; shown to companies as on hold';
+
+CREATE TABLE suspension_cascades (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    entity_type VARCHAR(20) NOT NULL CHECK (entity_type IN ('company', 'job', 'application')),
+    entity_id UUID NOT NULL,
+    -- Status before suspension; NULL for applications, which are only flagged
+    prior_status TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_suspension_cascade_entity UNIQUE (user_id, entity_type, entity_id)
+);
+
+COMMENT ON TABLE suspension_cascades IS 'Resources changed by a user suspension, reverted on reactivation';
@@ -0,0 +1,49 @@
+-- Migration 0028: Skill endorsements
+-- OMIL advisors vouch for skills of the job seekers they manage; an endorsed
+-- skill is shown as verified instead of self-reported
+
+CREATE TYPE endorser_type AS ENUM (
+    'omil_member'
+);
+
+CREATE TABLE skill_endorsements (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_skill_id UUID NOT NULL REFERENCES user_skills(id) ON DELETE CASCADE,
+    endorsed_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    endorser_type endorser_type NOT NULL DEFAULT 'omil_member',
+    -- Organization whose management relationship backs the endorsement
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_skill_endorsement UNIQUE (user_skill_id, endorsed_by)
+);
+
+CREATE INDEX idx_skill_endorsements_user_skill ON skill_endorsements(user_skill_id);
+CREATE INDEX idx_skill_endorsements_omil ON skill_endorsements(omil_id);
+
+-- Endorsements only stand while the OMIL still manages the job seeker
+CREATE OR REPLACE FUNCTION remove_endorsements_on_unmanage()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM skill_endorsements e
+    USING user_skills s
+    WHERE e.user_skill_id = s.id
+      AND s.user_id = OLD.job_seeker_id
+      AND e.omil_id = OLD.omil_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER omil_managed_job_seekers_deactivated
+    AFTER UPDATE OF is_active ON omil_managed_job_seekers
+    FOR EACH ROW
+    WHEN (OLD.is_active AND NOT NEW.is_active)
+    EXECUTE FUNCTION remove_endorsements_on_unmanage();
+
+CREATE TRIGGER omil_managed_job_seekers_deleted
+    AFTER DELETE ON omil_managed_job_seekers
+    FOR EACH ROW
+    EXECUTE FUNCTION remove_endorsements_on_unmanage();
+
+COMMENT ON TABLE skill_endorsements IS 'Third-party confirmations of job seeker skills';
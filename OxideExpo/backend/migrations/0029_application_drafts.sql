@@ -0,0 +1,20 @@
+-- Migration 0029: Application drafts
+-- Autosaved cover letters and screening answers, kept until the seeker
+-- submits or the job stops accepting applications
+
+CREATE TABLE application_drafts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    cover_letter TEXT,
+    answers JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_application_draft UNIQUE (user_id, job_id)
+);
+
+CREATE INDEX idx_application_drafts_user ON application_drafts(user_id, updated_at DESC);
+CREATE INDEX idx_application_drafts_job ON application_drafts(job_id);
+
+COMMENT ON TABLE application_drafts IS 'Unsubmitted job applications, private to the job seeker';
@@ -0,0 +1,35 @@
+-- Migration 0030: Content pre-screening for job postings
+-- Admin-maintained rules are checked when a company submits a job for
+-- approval; blocking findings keep the job in draft, warnings are kept for
+-- the moderation queue
+
+CREATE TYPE moderation_rule_type AS ENUM ('banned_phrase', 'regex');
+CREATE TYPE moderation_severity AS ENUM ('warn', 'block');
+
+CREATE TABLE moderation_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    rule_type moderation_rule_type NOT NULL,
+    pattern VARCHAR(500) NOT NULL,
+    severity moderation_severity NOT NULL,
+    description VARCHAR(500),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_moderation_rule UNIQUE (rule_type, pattern)
+);
+
+CREATE TRIGGER update_moderation_rules_updated_at
+    BEFORE UPDATE ON moderation_rules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Warnings found at the latest submission, shown in the pending queue
+CREATE TABLE job_screening_findings (
+    job_id UUID PRIMARY KEY REFERENCES jobs(id) ON DELETE CASCADE,
+    findings JSONB NOT NULL,
+    screened_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE moderation_rules IS 'Banned phrases and patterns checked before job moderation';
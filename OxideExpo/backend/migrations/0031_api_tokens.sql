@@ -0,0 +1,21 @@
+-- Migration 0031: Personal API tokens
+-- Let integrations (e.g. municipal dashboards) read OMIL data without a
+-- user's password. Only the SHA256 of a token is stored.
+
+CREATE TABLE api_tokens (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- First characters of the token, so users can tell their tokens apart
+    token_prefix VARCHAR(12) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL CHECK (cardinality(scopes) > 0),
+    last_used_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_tokens_user ON api_tokens(user_id);
+
+COMMENT ON TABLE api_tokens IS 'Scoped personal access tokens for data integrations';
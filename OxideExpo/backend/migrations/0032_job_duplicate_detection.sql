@@ -0,0 +1,25 @@
+-- Migration 0032: Duplicate job posting detection
+-- New and resubmitted postings are compared with the company's open jobs by
+-- title and by trigram similarity of their descriptions
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Case and whitespace don't make two descriptions different
+CREATE OR REPLACE FUNCTION normalize_job_description(description TEXT)
+RETURNS TEXT AS $$
+    SELECT regexp_replace(lower(btrim(description)), '\s+', ' ', 'g')
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX idx_jobs_description_trgm
+    ON jobs USING GIN (normalize_job_description(description) gin_trgm_ops);
+
+-- Submissions that went ahead despite matching other jobs
+CREATE TABLE job_duplicate_overrides (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    duplicate_job_ids UUID[] NOT NULL,
+    overridden_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_job_duplicate_overrides_job ON job_duplicate_overrides(job_id);
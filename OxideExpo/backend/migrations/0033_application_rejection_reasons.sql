@@ -0,0 +1,20 @@
+-- Migration 0033: Structured rejection reasons
+-- Companies say why a candidate was not selected and may share feedback
+-- with them; the managing OMIL always sees the reason code for coaching
+
+CREATE TYPE rejection_reason_code AS ENUM (
+    'profile_mismatch',
+    'experience',
+    'location',
+    'position_filled',
+    'other'
+);
+
+ALTER TABLE job_applications
+    ADD COLUMN rejection_reason_code rejection_reason_code,
+    ADD COLUMN rejection_feedback TEXT,
+    ADD COLUMN rejection_feedback_shared BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_job_applications_rejection_reason
+    ON job_applications(job_id, rejection_reason_code)
+    WHERE status = 'rejected';
@@ -0,0 +1,15 @@
+-- Migration 0034: Job completeness scored by the application
+-- services::completeness now computes jobs.completeness_percentage (and the
+-- checklist of what is missing) after every job or junction-table change.
+-- The old triggers would overwrite its scores with a different formula.
+
+DROP TRIGGER IF EXISTS calculate_job_completeness_trigger ON jobs;
+DROP TRIGGER IF EXISTS update_job_completeness_on_required_skills ON job_required_skills;
+DROP TRIGGER IF EXISTS update_job_completeness_on_required_languages ON job_required_languages;
+DROP TRIGGER IF EXISTS update_job_completeness_on_accommodations ON job_disability_accommodations;
+
+DROP FUNCTION IF EXISTS trigger_calculate_job_completeness();
+DROP FUNCTION IF EXISTS trigger_update_job_completeness_on_junction_change();
+DROP FUNCTION IF EXISTS calculate_job_completeness(UUID);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_completeness ON jobs(completeness_percentage);
@@ -0,0 +1,40 @@
+-- Migration 0035: Job snapshots on invitations
+-- Seekers see the terms they were invited for even after the company edits
+-- the job, and invitations outlive the job instead of cascading away
+
+ALTER TYPE invitation_status ADD VALUE IF NOT EXISTS 'job_no_longer_available';
+
+-- The invitation-relevant terms of a job. Also used to tell whether a job
+-- changed since an invitation was sent, so both sides must come from here.
+CREATE OR REPLACE FUNCTION job_invitation_snapshot(p_job_id UUID)
+RETURNS JSONB AS $$
+    SELECT jsonb_build_object(
+        'title', j.title,
+        'description', left(j.description, 300),
+        'salary_min', j.salary_min::text,
+        'salary_max', j.salary_max::text,
+        'salary_currency', j.salary_currency,
+        'salary_period', j.salary_period,
+        'work_modality', j.work_modality,
+        'region_id', j.region_id,
+        'region_name', r.name
+    )
+    FROM jobs j
+    LEFT JOIN regions r ON r.id = j.region_id
+    WHERE j.id = p_job_id
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE job_invitations ADD COLUMN job_snapshot JSONB;
+
+-- Pending invitations are the ones seekers can still act on; answered ones
+-- are backfilled too so every invitation renders the same way
+UPDATE job_invitations SET job_snapshot = job_invitation_snapshot(job_id);
+
+ALTER TABLE job_invitations ALTER COLUMN job_snapshot SET NOT NULL;
+
+-- Deleting a job keeps its invitations, detached from it
+ALTER TABLE job_invitations ALTER COLUMN job_id DROP NOT NULL;
+ALTER TABLE job_invitations DROP CONSTRAINT job_invitations_job_id_fkey;
+ALTER TABLE job_invitations
+    ADD CONSTRAINT job_invitations_job_id_fkey
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE SET NULL;
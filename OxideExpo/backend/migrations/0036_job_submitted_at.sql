@@ -0,0 +1,10 @@
+-- Migration 0036: Track when a job last entered the moderation queue
+-- Rejected or drafted jobs can be resubmitted; moderation throughput is
+-- measured from the latest submission rather than from created_at.
+
+ALTER TABLE jobs ADD COLUMN submitted_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE jobs SET submitted_at = created_at;
+
+COMMENT ON COLUMN jobs.submitted_at IS 'Latest transition into pending_approval (created_at until then)';
+
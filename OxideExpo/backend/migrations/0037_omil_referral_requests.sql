@@ -0,0 +1,57 @@
+-- Migration 0037: Company referral requests to OMILs
+-- Companies ask an OMIL to refer candidates for one of their active jobs.
+-- Every status change is kept so both sides can follow the request.
+
+CREATE TYPE referral_request_status AS ENUM (
+    'pending',
+    'accepted',
+    'declined'
+);
+
+CREATE TABLE omil_referral_requests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+
+    message TEXT NOT NULL,
+    status referral_request_status NOT NULL DEFAULT 'pending',
+    response_message TEXT,
+
+    requested_by UUID NOT NULL REFERENCES users(id),
+    responded_by UUID REFERENCES users(id),
+    responded_at TIMESTAMP WITH TIME ZONE,
+
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE omil_referral_requests IS 'Company requests for an OMIL to refer candidates to a job';
+
+-- One open request per job and OMIL
+CREATE UNIQUE INDEX idx_omil_referral_requests_one_pending
+    ON omil_referral_requests(omil_id, job_id)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_omil_referral_requests_omil ON omil_referral_requests(omil_id, created_at DESC);
+CREATE INDEX idx_omil_referral_requests_company ON omil_referral_requests(company_id, created_at DESC);
+
+CREATE TRIGGER update_omil_referral_requests_updated_at
+    BEFORE UPDATE ON omil_referral_requests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- ============================================================================
+-- STATUS HISTORY
+-- ============================================================================
+
+CREATE TABLE omil_referral_request_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    request_id UUID NOT NULL REFERENCES omil_referral_requests(id) ON DELETE CASCADE,
+    status referral_request_status NOT NULL,
+    changed_by UUID NOT NULL REFERENCES users(id),
+    note TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_omil_referral_request_history_request
+    ON omil_referral_request_history(request_id, created_at);
@@ -0,0 +1,46 @@
+-- Migration 0038: Application document attachments
+-- Certificates, portfolios and other files a seeker attaches to a single
+-- application, on top of the CV on their profile
+
+CREATE TABLE application_documents (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    application_id UUID NOT NULL REFERENCES job_applications(id) ON DELETE CASCADE,
+    file_key TEXT NOT NULL UNIQUE,
+    label VARCHAR(100) NOT NULL,
+    original_filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size BIGINT NOT NULL,
+    uploaded_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_application_documents_application
+    ON application_documents(application_id, created_at);
+
+COMMENT ON TABLE application_documents IS 'Extra files attached to a job application';
+
+-- ============================================================================
+-- STORAGE CLEANUP
+-- ============================================================================
+
+-- Rows also disappear through cascades (application or job deleted, users
+-- merged), so their objects are queued here and removed from storage by the
+-- scheduler
+CREATE TABLE application_document_deletions (
+    file_key TEXT PRIMARY KEY,
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE OR REPLACE FUNCTION queue_application_document_deletion()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO application_document_deletions (file_key)
+    VALUES (OLD.file_key)
+    ON CONFLICT (file_key) DO NOTHING;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER application_documents_queue_deletion
+    AFTER DELETE ON application_documents
+    FOR EACH ROW EXECUTE FUNCTION queue_application_document_deletion();
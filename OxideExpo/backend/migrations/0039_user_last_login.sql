@@ -0,0 +1,20 @@
+-- Migration 0039: Last login tracking
+-- Lets admins tell companies whose owner never came back after registering
+-- apart from ones that are simply waiting for review
+
+ALTER TABLE users ADD COLUMN last_login_at TIMESTAMPTZ;
+
+-- Backfill from issued refresh tokens (logins and refreshes); the one issued
+-- at registration doesn't count
+UPDATE users u
+SET last_login_at = t.last_issued_at
+FROM (
+    SELECT rt.user_id, MAX(rt.created_at) AS last_issued_at
+    FROM refresh_tokens rt
+    JOIN users ru ON ru.id = rt.user_id
+    WHERE rt.created_at > ru.created_at + INTERVAL '1 minute'
+    GROUP BY rt.user_id
+) t
+WHERE t.user_id = u.id;
+
+COMMENT ON COLUMN users.last_login_at IS 'Last successful password login';
@@ -0,0 +1,33 @@
+-- Migration 0040: Applicant tags
+-- Company-defined labels recruiters put on applications ("bilingual",
+-- "2nd round pool") on top of the status. Internal to the company: never
+-- shown to the seeker or their OMIL.
+
+CREATE TABLE application_tags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    color VARCHAR(7) NOT NULL DEFAULT '#6B7280' CHECK (color ~ '^#[0-9A-Fa-f]{6}$'),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE application_tags IS 'Tag definitions a company uses to label its applicants';
+
+CREATE UNIQUE INDEX idx_application_tags_company_name
+    ON application_tags(company_id, LOWER(name));
+
+CREATE TRIGGER update_application_tags_updated_at
+    BEFORE UPDATE ON application_tags
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE application_tag_assignments (
+    application_id UUID NOT NULL REFERENCES job_applications(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES application_tags(id) ON DELETE CASCADE,
+    assigned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (application_id, tag_id)
+);
+
+CREATE INDEX idx_application_tag_assignments_tag ON application_tag_assignments(tag_id);
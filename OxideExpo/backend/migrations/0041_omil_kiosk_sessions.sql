@@ -0,0 +1,26 @@
+-- Migration 0041: OMIL kiosk sessions
+-- Restricted tokens for shared tablets at job fairs: they can only register
+-- job seekers on behalf of the OMIL and read reference data. The token itself
+-- is revoked through the JWT blacklist; this table is for listing and
+-- attribution.
+
+CREATE TABLE omil_kiosk_sessions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    label VARCHAR(100),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    token_jti UUID NOT NULL UNIQUE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    revoked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_omil_kiosk_sessions_omil ON omil_kiosk_sessions(omil_id, created_at DESC);
+
+-- Kiosk a seeker was registered through, if any
+ALTER TABLE omil_managed_job_seekers
+    ADD COLUMN kiosk_session_id UUID REFERENCES omil_kiosk_sessions(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_omil_managed_kiosk_session ON omil_managed_job_seekers(kiosk_session_id)
+    WHERE kiosk_session_id IS NOT NULL;
@@ -0,0 +1,13 @@
+-- Migration 0042: Match score visibility and recalculation
+-- Companies can hide match scores from their applicant views, and ask for
+-- every applicant of a job to be rescored by the background worker.
+
+ALTER TABLE company_profiles
+    ADD COLUMN show_match_scores BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- One pending request per job; asking again only moves it back in the queue
+CREATE TABLE match_score_recalculations (
+    job_id UUID PRIMARY KEY REFERENCES jobs(id) ON DELETE CASCADE,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    requested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,70 @@
+-- Migration 0043: Data retention policies
+-- Old applications are anonymized, OMIL followups deleted and admin audit
+-- logs archived by a monthly background job, each after a configurable age.
+
+CREATE TYPE retention_entity AS ENUM ('applications', 'omil_followups', 'audit_logs');
+CREATE TYPE retention_action AS ENUM ('anonymize', 'delete', 'archive');
+
+CREATE TABLE retention_policies (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    entity_type retention_entity NOT NULL UNIQUE,
+    action retention_action NOT NULL,
+    age_months INTEGER NOT NULL CHECK (age_months > 0),
+    is_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO retention_policies (entity_type, action, age_months) VALUES
+    ('applications', 'anonymize', 36),
+    ('omil_followups', 'delete', 60),
+    ('audit_logs', 'archive', 84);
+
+COMMENT ON TABLE retention_policies IS 'How long each kind of record is kept before the retention job acts on it';
+
+-- One row per policy applied; dry runs are not recorded
+CREATE TABLE retention_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    policy_id UUID REFERENCES retention_policies(id) ON DELETE SET NULL,
+    entity_type retention_entity NOT NULL,
+    action retention_action NOT NULL,
+    age_months INTEGER NOT NULL,
+    affected_rows BIGINT NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    -- Set when a batch failed; rows from earlier batches stay applied
+    error TEXT,
+    -- NULL for the scheduled run
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_retention_runs_started ON retention_runs(started_at DESC);
+
+-- Anonymized applications keep their status and dates for statistics
+ALTER TABLE job_applications
+    ADD COLUMN anonymized_at TIMESTAMPTZ;
+
+CREATE INDEX idx_job_applications_retention
+    ON job_applications(applied_at)
+    WHERE anonymized_at IS NULL;
+
+CREATE INDEX idx_job_seeker_followups_created ON job_seeker_followups(created_at);
+
+-- Status history has no updated_at column, so this trigger made every update
+-- fail; anonymization is the first thing to update these rows
+DROP TRIGGER update_application_status_history_updated_at ON application_status_history;
+
+-- Archived audit rows no longer reference their admin, who may be gone by then
+CREATE TABLE audit_logs_archive (
+    id UUID PRIMARY KEY,
+    admin_id UUID NOT NULL,
+    action_type VARCHAR(50) NOT NULL,
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id UUID NOT NULL,
+    details JSONB,
+    ip_address VARCHAR(45),
+    created_at TIMESTAMPTZ NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE audit_logs_archive IS 'Admin audit log entries past their retention age';
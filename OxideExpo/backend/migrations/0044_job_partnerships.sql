@@ -0,0 +1,11 @@
+-- Migration 0044: Jobs co-published with a partner OMIL
+-- A company can name an OMIL that accepted a referral request for one of its
+-- jobs as the job's partner. The OMIL sees its own seekers' applications to
+-- the job and may withdraw from the partnership at any time.
+
+ALTER TYPE referral_request_status ADD VALUE IF NOT EXISTS 'withdrawn';
+
+ALTER TABLE jobs
+    ADD COLUMN partner_omil_id UUID REFERENCES omil_organizations(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_jobs_partner_omil ON jobs(partner_omil_id) WHERE partner_omil_id IS NOT NULL;
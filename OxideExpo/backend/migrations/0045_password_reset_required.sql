@@ -0,0 +1,5 @@
+-- Migration 0045: Admin-forced password resets
+-- Accounts an admin forced through a password reset cannot log in until the
+-- reset is completed.
+
+ALTER TYPE account_status ADD VALUE IF NOT EXISTS 'password_reset_required';
@@ -0,0 +1,45 @@
+-- Migration 0046: Structured job schedules
+-- work_schedule was free text ("part time mañanas", "media jornada AM"), which
+-- cannot be searched. Jobs now carry a shift type and weekly hours, and the
+-- free text moves to schedule_details as extra detail.
+
+CREATE TYPE shift_type AS ENUM ('full_time', 'part_time', 'shifts', 'flexible');
+
+ALTER TABLE jobs RENAME COLUMN work_schedule TO schedule_details;
+ALTER TABLE jobs ALTER COLUMN schedule_details TYPE VARCHAR(200);
+
+ALTER TABLE jobs
+    ADD COLUMN shift_type shift_type,
+    -- Chile's ordinary working week is at most 45 hours
+    ADD COLUMN weekly_hours INTEGER CHECK (weekly_hours BETWEEN 1 AND 45);
+
+CREATE INDEX idx_jobs_shift_type ON jobs(shift_type) WHERE shift_type IS NOT NULL;
+
+ALTER TABLE job_seeker_preferences
+    ADD COLUMN preferred_shift_types shift_type[] NOT NULL DEFAULT '{}';
+
+-- Best guess at the shift type behind a legacy schedule text, or NULL when
+-- the text does not say. Rotating and night shifts win over everything else,
+-- then part time, flexible and full time, in that order.
+CREATE OR REPLACE FUNCTION infer_shift_type(schedule TEXT)
+RETURNS shift_type AS $$
+DECLARE
+    t TEXT := translate(lower(COALESCE(schedule, '')), 'áéíóúñ', 'aeioun');
+BEGIN
+    IF t ~ '(turno|rotativ|nocturn|\m\d+\s*x\s*\d+\M)' THEN
+        RETURN 'shifts';
+    ELSIF t ~ '(part[ -]?time|media jornada|medio tiempo|jornada parcial|tiempo parcial)' THEN
+        RETURN 'part_time';
+    ELSIF t ~ '(flexib|a convenir)' THEN
+        RETURN 'flexible';
+    ELSIF t ~ '(full[ -]?time|jornada completa|tiempo completo|\m4[0-5]\s*(h|hrs|horas)\M|lunes a viernes)' THEN
+        RETURN 'full_time';
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+COMMENT ON FUNCTION infer_shift_type(TEXT) IS 'Heuristic shift type of a free-text job schedule, used to backfill jobs.shift_type';
+
+UPDATE jobs SET shift_type = infer_shift_type(schedule_details)
+WHERE schedule_details IS NOT NULL;
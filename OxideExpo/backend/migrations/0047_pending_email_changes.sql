@@ -0,0 +1,29 @@
+-- Migration 0047: Email change requests
+-- A login email only changes once the new address confirms it; the old
+-- address gets a link to cancel. Confirmed rows are kept as the record of
+-- the change.
+
+CREATE TABLE pending_email_changes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    old_email VARCHAR(255) NOT NULL,
+    new_email VARCHAR(255) NOT NULL,
+
+    -- Tokens (stored as hash for security)
+    confirm_token_hash VARCHAR(64) NOT NULL UNIQUE,
+    cancel_token_hash VARCHAR(64) NOT NULL UNIQUE,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    confirmed_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (confirmed_at IS NULL OR cancelled_at IS NULL)
+);
+
+-- One open request per user; expired rows count until a new request replaces them
+CREATE UNIQUE INDEX idx_pending_email_changes_open
+    ON pending_email_changes(user_id)
+    WHERE confirmed_at IS NULL AND cancelled_at IS NULL;
+
+COMMENT ON TABLE pending_email_changes IS 'Login email changes awaiting confirmation from the new address';
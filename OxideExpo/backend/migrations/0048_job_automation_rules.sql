@@ -0,0 +1,43 @@
+-- Migration 0048: Applicant funnel automation
+-- Per-job rules a company sets up ("reject anyone below 30% match") that run
+-- when an application is submitted. Each action taken leaves an application
+-- note naming the rule. The job_automation_enabled setting turns all of them
+-- off at once.
+
+CREATE TYPE automation_trigger AS ENUM ('on_application_submitted');
+CREATE TYPE automation_action AS ENUM ('set_status', 'add_tag', 'notify_member');
+
+CREATE TABLE job_automation_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    trigger automation_trigger NOT NULL DEFAULT 'on_application_submitted',
+    -- Every criterion present must hold; see AutomationCondition
+    condition JSONB NOT NULL DEFAULT '{}'::jsonb,
+    action automation_action NOT NULL,
+
+    -- Exactly the parameter the action needs is set
+    target_status application_status,
+    tag_id UUID REFERENCES application_tags(id) ON DELETE CASCADE,
+    notify_user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+
+    is_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT automation_action_params CHECK (
+        (action = 'set_status') = (target_status IS NOT NULL)
+        AND (action = 'add_tag') = (tag_id IS NOT NULL)
+        AND (action = 'notify_member') = (notify_user_id IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_job_automation_rules_job ON job_automation_rules(job_id);
+
+CREATE TRIGGER update_job_automation_rules_updated_at
+    BEFORE UPDATE ON job_automation_rules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE job_automation_rules IS 'Company rules applied automatically to new applications for a job';
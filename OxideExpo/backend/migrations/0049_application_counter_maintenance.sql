@@ -0,0 +1,23 @@
+-- Migration 0049: Explicit applications_count maintenance
+-- The counter is now updated by the application code in the same
+-- transaction as the application insert, withdrawal or delete, and only
+-- counts applications that are not withdrawn. The old trigger is dropped so
+-- nothing counts twice, and existing drift is corrected.
+
+DROP TRIGGER IF EXISTS update_applications_count_trigger ON job_applications;
+DROP FUNCTION IF EXISTS trigger_update_applications_count();
+
+UPDATE jobs j
+SET applications_count = actual.count
+FROM (
+    SELECT j2.id, COUNT(ja.id) FILTER (WHERE ja.status <> 'withdrawn') as count
+    FROM jobs j2
+    LEFT JOIN job_applications ja ON ja.job_id = j2.id
+    GROUP BY j2.id
+) actual
+WHERE j.id = actual.id AND j.applications_count <> actual.count;
+
+ALTER TABLE jobs
+    ADD CONSTRAINT jobs_applications_count_non_negative CHECK (applications_count >= 0);
+
+COMMENT ON COLUMN jobs.applications_count IS 'Applications that are not withdrawn; maintained by services::counters';
@@ -0,0 +1,14 @@
+-- Migration 0050: Seeker availability
+-- Seekers say whether they are looking for work. Companies only see the
+-- coarse status; the date from which a seeker is available is shown to them
+-- only when the seeker chooses to share it.
+
+CREATE TYPE seeker_availability AS ENUM ('actively_looking', 'open_to_offers', 'not_available');
+
+ALTER TABLE job_seeker_profiles
+    ADD COLUMN availability seeker_availability NOT NULL DEFAULT 'open_to_offers',
+    ADD COLUMN available_from DATE,
+    ADD COLUMN share_available_from BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN job_seeker_profiles.availability IS 'Whether the seeker is looking for work; not_available hides them from candidate search';
+COMMENT ON COLUMN job_seeker_profiles.available_from IS 'Date the seeker can start; shown to companies only when share_available_from is set';
@@ -0,0 +1,8 @@
+-- Migration 0051: Job syndication feed opt-out
+-- Companies are included in the public XML/JSON job feeds unless they opt out
+
+ALTER TABLE company_profiles
+    ADD COLUMN syndication_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+
+COMMENT ON COLUMN company_profiles.syndication_enabled IS
+    'Whether the company''s active jobs are included in the public syndication feeds';
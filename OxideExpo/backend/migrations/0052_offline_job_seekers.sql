@@ -0,0 +1,35 @@
+-- Migration 0052: Job seekers managed offline by an OMIL
+-- Beneficiaries without an email address are registered by their OMIL as
+-- 'managed_offline' users with no email and no usable password. Once they
+-- get an address, the OMIL sends an invitation to claim the account, which
+-- keeps the record and its history.
+
+ALTER TYPE account_status ADD VALUE IF NOT EXISTS 'managed_offline';
+
+ALTER TABLE users ALTER COLUMN email DROP NOT NULL;
+
+-- The unique constraint becomes a partial index so any number of users can
+-- be without an email
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+CREATE UNIQUE INDEX users_email_key ON users(email) WHERE email IS NOT NULL;
+
+COMMENT ON COLUMN users.email IS 'Login email; NULL only for job seekers managed offline by an OMIL';
+
+CREATE TABLE account_claim_invitations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    email VARCHAR(255) NOT NULL,
+
+    -- Token (stored as hash for security)
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    claimed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_account_claim_invitations_user ON account_claim_invitations(user_id);
+
+COMMENT ON TABLE account_claim_invitations IS 'Invitations for offline job seekers to take over the account their OMIL registered';
@@ -0,0 +1,44 @@
+-- Followup types become admin-maintained reference data instead of a fixed
+-- enum, so new intervention categories don't need a deploy. Codes keep the
+-- old enum values, so existing followups and API clients are unaffected.
+
+CREATE TABLE followup_types (
+    code VARCHAR(50) PRIMARY KEY,
+    label_es VARCHAR(255) NOT NULL,
+    -- Inactive types stay on old followups but can't be chosen for new ones
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    -- Counted as an intervention in OMIL statistics and the monthly report
+    counts_as_intervention BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT followup_type_code_format CHECK (code ~ '^[a-z][a-z0-9_]*$')
+);
+
+CREATE TRIGGER update_followup_types_updated_at
+    BEFORE UPDATE ON followup_types
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+INSERT INTO followup_types (code, label_es, counts_as_intervention) VALUES
+    ('initial_registration', 'Registro inicial', false),
+    ('profile_update', 'Actualización de perfil', false),
+    ('job_application', 'Postulación a empleo', true),
+    ('interview_scheduled', 'Entrevista agendada', true),
+    ('interview_completed', 'Entrevista realizada', true),
+    ('placement', 'Colocación', true),
+    ('follow_up_call', 'Llamada de seguimiento', true),
+    ('general_note', 'Nota general', false);
+
+ALTER TABLE job_seeker_followups
+    ALTER COLUMN followup_type TYPE VARCHAR(50) USING followup_type::text;
+
+ALTER TABLE job_seeker_followups
+    ADD CONSTRAINT job_seeker_followups_followup_type_fkey
+    FOREIGN KEY (followup_type) REFERENCES followup_types(code);
+
+CREATE INDEX idx_job_seeker_followups_type ON job_seeker_followups(followup_type);
+
+DROP TYPE followup_type;
+
+COMMENT ON TABLE followup_types IS 'Categories of OMIL followups, maintained by admins';
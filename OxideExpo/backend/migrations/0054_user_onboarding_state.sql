@@ -0,0 +1,24 @@
+-- Job seeker onboarding checklist. The steps are defined in code
+-- (models::onboarding::OnboardingStep) and whether each one is done is
+-- computed from the profile, so only the seeker's choices are stored here.
+
+CREATE TABLE user_onboarding_state (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    -- Optional steps the seeker chose to skip
+    dismissed_steps VARCHAR(50)[] NOT NULL DEFAULT '{}',
+    -- Set once, when every required step was first found done
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_user_onboarding_state_completed
+    ON user_onboarding_state(completed_at)
+    WHERE completed_at IS NOT NULL;
+
+CREATE TRIGGER update_user_onboarding_state_updated_at
+    BEFORE UPDATE ON user_onboarding_state
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE user_onboarding_state IS 'Dismissed optional steps and completion of the job seeker onboarding checklist';
@@ -0,0 +1,37 @@
+-- Migration 0055: Self-service company data exports
+-- A company owner queues an export of the company's jobs, applicants and
+-- profile; the background worker writes it as a ZIP to storage, where it can
+-- be downloaded through a signed URL until expires_at
+
+CREATE TYPE data_export_status AS ENUM ('queued', 'running', 'done', 'failed');
+
+CREATE TABLE company_data_exports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id),
+    status data_export_status NOT NULL DEFAULT 'queued',
+
+    -- Progress: jobs whose applicants have been written
+    total_jobs INTEGER,
+    processed_jobs INTEGER NOT NULL DEFAULT 0,
+
+    -- Cleared once the file is removed after expiring
+    storage_path VARCHAR(500),
+    file_size BIGINT,
+    error TEXT,
+
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMP WITH TIME ZONE,
+    heartbeat_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    expires_at TIMESTAMP WITH TIME ZONE
+);
+
+-- One export in progress per company
+CREATE UNIQUE INDEX idx_company_data_exports_active ON company_data_exports(company_id)
+    WHERE status IN ('queued', 'running');
+CREATE INDEX idx_company_data_exports_pending ON company_data_exports(created_at)
+    WHERE status IN ('queued', 'running');
+CREATE INDEX idx_company_data_exports_company ON company_data_exports(company_id, created_at DESC);
+
+COMMENT ON COLUMN company_data_exports.heartbeat_at IS 'Touched after every job; running exports with a stale heartbeat are reclaimed';
@@ -0,0 +1,27 @@
+-- Migration 0056: Applying straight from an invitation
+-- Seekers can accept an invitation and apply in the same step, or accept
+-- now and apply later. Applications record whether an invitation led to them.
+
+CREATE TYPE application_source AS ENUM ('direct', 'invitation');
+
+ALTER TABLE job_applications
+    ADD COLUMN source application_source NOT NULL DEFAULT 'direct';
+
+-- Accepted without an application
+ALTER TYPE invitation_status ADD VALUE IF NOT EXISTS 'accepted' AFTER 'viewed';
+
+ALTER TABLE job_invitations
+    ADD COLUMN application_id UUID REFERENCES job_applications(id) ON DELETE SET NULL;
+
+-- Accepting used to always apply, so applied invitations have an application
+UPDATE job_invitations i
+SET application_id = a.id
+FROM job_applications a
+WHERE i.status = 'applied'
+  AND a.job_id = i.job_id
+  AND a.applicant_id = i.job_seeker_id;
+
+UPDATE job_applications a
+SET source = 'invitation'
+FROM job_invitations i
+WHERE i.application_id = a.id;
@@ -0,0 +1,21 @@
+-- Migration 0057: Cover letter templates
+-- Reusable cover letters a job seeker fills in per job. Applications always
+-- store the expanded text, never a reference to the template, so editing or
+-- deleting a template leaves past applications as they were.
+
+CREATE TABLE cover_letter_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    body TEXT NOT NULL,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE cover_letter_templates IS 'Cover letters a job seeker reuses across applications';
+COMMENT ON COLUMN cover_letter_templates.updated_at IS 'Last edit of the name or body; using the template does not change it';
+
+CREATE UNIQUE INDEX idx_cover_letter_templates_user_name
+    ON cover_letter_templates(user_id, LOWER(name));
@@ -0,0 +1,34 @@
+-- Migration: Linking education records to reference institutions
+-- Seekers mostly type the institution name instead of picking it, so the
+-- same institution shows up under many spellings. Records whose name
+-- matches an institution exactly are linked to it, and admins merge
+-- duplicate institution entries into a canonical one.
+
+-- Where a merged duplicate now points; merged duplicates are inactive
+ALTER TABLE institutions
+    ADD COLUMN merged_into_id UUID REFERENCES institutions(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_institutions_name_lower ON institutions(LOWER(name));
+
+-- Institution an education record's free-text name stands for: an exact,
+-- case-insensitive match, following merges to the canonical entry
+CREATE OR REPLACE FUNCTION match_institution(institution_name TEXT)
+RETURNS UUID AS $$
+    SELECT COALESCE(i.merged_into_id, i.id)
+    FROM institutions i
+    WHERE LOWER(i.name) = LOWER(btrim(institution_name))
+    AND (i.is_active OR i.merged_into_id IS NOT NULL)
+    ORDER BY i.is_active DESC, i.created_at
+    LIMIT 1
+$$ LANGUAGE sql STABLE;
+
+-- Names still waiting for a match, for the curation report
+CREATE INDEX idx_education_records_unmatched_institution
+    ON education_records(LOWER(btrim(institution_name)))
+    WHERE institution_id IS NULL AND institution_name IS NOT NULL;
+
+UPDATE education_records
+SET institution_id = match_institution(institution_name)
+WHERE institution_id IS NULL
+AND institution_name IS NOT NULL
+AND match_institution(institution_name) IS NOT NULL;
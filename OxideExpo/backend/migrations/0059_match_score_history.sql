@@ -0,0 +1,30 @@
+-- Migration: Match score history
+-- Seekers who improve their profile can follow their match for a job over
+-- time. A point is recorded whenever a computed score moves by more than a
+-- few points, and saved jobs remember the score the job had when saved.
+
+CREATE TABLE job_match_score_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Scores as in job_match_scores at the time
+    total_score INTEGER NOT NULL,
+    skills_score INTEGER NOT NULL,
+    languages_score INTEGER NOT NULL,
+    location_score INTEGER NOT NULL,
+    experience_score INTEGER NOT NULL,
+    education_score INTEGER NOT NULL,
+    preferred_skills_score INTEGER NOT NULL,
+    accommodations_score INTEGER NOT NULL,
+
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE job_match_score_history IS 'Append-only match score points per job and seeker, capped per pair';
+
+CREATE INDEX idx_job_match_score_history_pair
+ON job_match_score_history(job_id, user_id, recorded_at DESC);
+
+-- Score when the job was saved, for the "score improved" badge
+ALTER TABLE saved_jobs ADD COLUMN match_score_at_save INTEGER;
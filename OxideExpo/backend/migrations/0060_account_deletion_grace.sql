@@ -0,0 +1,22 @@
+-- Migration: Deferred account deletion
+-- Deleting an account only schedules it. During the grace period the account
+-- is hidden and its applications are on hold, and the user can restore it;
+-- the scheduler scrubs it once the period is over.
+
+ALTER TYPE account_status ADD VALUE IF NOT EXISTS 'pending_deletion';
+
+-- One row per account awaiting deletion, removed on restore or scrub
+CREATE TABLE account_deletions (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Status to go back to on restore
+    prior_status account_status NOT NULL,
+    restore_token_hash VARCHAR(255) NOT NULL UNIQUE,
+
+    requested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    scheduled_for TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+COMMENT ON TABLE account_deletions IS 'Accounts in their deletion grace period';
+
+CREATE INDEX idx_account_deletions_scheduled_for ON account_deletions(scheduled_for);
@@ -0,0 +1,30 @@
+-- Migration: First password setup for accounts registered on someone's behalf
+-- Job seekers an OMIL registers with an email used to get an empty password
+-- hash. They now have none at all and stay 'invited' until they set their
+-- first password through a link their advisor sends them.
+
+ALTER TYPE account_status ADD VALUE IF NOT EXISTS 'invited';
+
+ALTER TABLE users ALTER COLUMN password_hash DROP NOT NULL;
+
+UPDATE users SET password_hash = NULL WHERE password_hash = '';
+
+COMMENT ON COLUMN users.password_hash IS 'Argon2 hash; NULL until the user sets a first password';
+
+CREATE TABLE password_setup_tokens (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    -- Token (stored as hash for security)
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_password_setup_tokens_user ON password_setup_tokens(user_id);
+
+COMMENT ON TABLE password_setup_tokens IS 'One-time links for invited users to set their first password';
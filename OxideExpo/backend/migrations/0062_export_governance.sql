@@ -0,0 +1,43 @@
+-- Migration: Export governance
+-- Spreadsheets with seekers' contact details get forwarded around with no
+-- way to tell where they came from. Every export is now stamped with who
+-- made it and recorded here, and organizations can stop contact details
+-- from being exported at all.
+
+CREATE TYPE export_type AS ENUM ('managed_seekers', 'applicants');
+
+CREATE TABLE export_audit (
+    -- Also the export ID printed in the file, shortened to 8 characters
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    export_type export_type NOT NULL,
+
+    exported_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    omil_id UUID REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    company_id UUID REFERENCES company_profiles(id) ON DELETE CASCADE,
+
+    -- Names as printed in the file, kept when the user or organization changes
+    exported_by_name VARCHAR(255) NOT NULL,
+    organization_name VARCHAR(255) NOT NULL,
+
+    filters JSONB NOT NULL DEFAULT '{}',
+    row_count INTEGER NOT NULL,
+    included_contact BOOLEAN NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT export_audit_one_owner CHECK ((omil_id IS NULL) <> (company_id IS NULL))
+);
+
+COMMENT ON TABLE export_audit IS 'One row per generated spreadsheet export, matching the watermark in the file';
+
+CREATE INDEX idx_export_audit_omil ON export_audit(omil_id, created_at DESC);
+CREATE INDEX idx_export_audit_company ON export_audit(company_id, created_at DESC);
+CREATE INDEX idx_export_audit_created ON export_audit(created_at DESC);
+
+ALTER TABLE omil_organizations
+    ADD COLUMN allow_contact_export BOOLEAN NOT NULL DEFAULT true;
+ALTER TABLE company_profiles
+    ADD COLUMN allow_contact_export BOOLEAN NOT NULL DEFAULT true;
+
+COMMENT ON COLUMN omil_organizations.allow_contact_export IS 'When false, exports never include emails or phone numbers; set by the director';
+COMMENT ON COLUMN company_profiles.allow_contact_export IS 'When false, exports never include emails or phone numbers; set by administrators';
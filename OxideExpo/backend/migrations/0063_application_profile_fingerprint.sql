@@ -0,0 +1,9 @@
+-- Migration: Profile fingerprint on applications
+-- Recruiters reading an application weeks later can tell whether the
+-- seeker's profile changed since they applied. Only a fingerprint is kept
+-- (entry counts and last edit per section, plus completeness), enough to
+-- flag the sections that moved without storing a copy of the profile.
+
+ALTER TABLE job_applications ADD COLUMN profile_fingerprint JSONB;
+
+COMMENT ON COLUMN job_applications.profile_fingerprint IS 'Profile fingerprint at apply time; NULL for applications made before it was recorded';
@@ -0,0 +1,48 @@
+-- Migration: Four-eyes approval of sensitive OMIL actions
+-- Placements feed official statistics, so an organization can require an
+-- advisor's 'placed' outcome or deactivation of a managed record to be
+-- approved by a coordinator or director before it takes effect.
+
+CREATE TYPE omil_action_type AS ENUM (
+    'placement',
+    'deactivation'
+);
+
+CREATE TYPE pending_action_status AS ENUM (
+    'pending',
+    'approved',
+    'rejected'
+);
+
+CREATE TABLE pending_omil_actions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    managed_id UUID NOT NULL REFERENCES omil_managed_job_seekers(id) ON DELETE CASCADE,
+
+    action_type omil_action_type NOT NULL,
+    -- The request as the advisor sent it, replayed on approval
+    payload JSONB NOT NULL,
+
+    status pending_action_status NOT NULL DEFAULT 'pending',
+    requested_by UUID NOT NULL REFERENCES users(id),
+    reviewed_by UUID REFERENCES users(id),
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    review_notes TEXT,
+
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE pending_omil_actions IS 'Advisor actions waiting for a coordinator or director to approve them';
+
+-- Only one open request per action on a record
+CREATE UNIQUE INDEX idx_pending_omil_actions_one_pending
+    ON pending_omil_actions(managed_id, action_type)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_pending_omil_actions_queue
+    ON pending_omil_actions(omil_id, created_at)
+    WHERE status = 'pending';
+
+-- Off keeps advisors' placements applying right away
+ALTER TABLE omil_organizations
+    ADD COLUMN require_placement_approval BOOLEAN NOT NULL DEFAULT false;
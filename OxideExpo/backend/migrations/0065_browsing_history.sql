@@ -0,0 +1,34 @@
+-- Migration: Recently viewed jobs and recent searches
+-- Feeds the app's "recently viewed" rail and "continue your search"
+-- suggestions. Seekers can turn both off with track_browsing, which also
+-- clears what was kept.
+
+CREATE TABLE job_view_history (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    -- Last view; viewing again moves the job to the front
+    viewed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, job_id)
+);
+
+COMMENT ON TABLE job_view_history IS 'Jobs a seeker viewed, capped to the most recent per seeker';
+
+CREATE INDEX idx_job_view_history_recent ON job_view_history(user_id, viewed_at DESC);
+
+CREATE TABLE recent_job_searches (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Filters of GET /api/jobs as sent, without pagination
+    filters JSONB NOT NULL,
+    searched_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_recent_search UNIQUE (user_id, filters)
+);
+
+COMMENT ON TABLE recent_job_searches IS 'Distinct job search filter combinations a seeker used last';
+
+CREATE INDEX idx_recent_job_searches_recent ON recent_job_searches(user_id, searched_at DESC);
+
+ALTER TABLE job_seeker_preferences
+    ADD COLUMN track_browsing BOOLEAN NOT NULL DEFAULT true;
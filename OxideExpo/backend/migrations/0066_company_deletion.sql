@@ -0,0 +1,41 @@
+-- Migration: Two-phase company deletion
+-- Winding a company down closes its jobs and deactivates its members but can
+-- be undone; purging it later anonymizes the profile and archives its jobs,
+-- which stay readable to the seekers who applied to them.
+
+ALTER TYPE organization_status ADD VALUE IF NOT EXISTS 'closing';
+ALTER TYPE organization_status ADD VALUE IF NOT EXISTS 'deleted';
+
+-- Jobs of a purged company; their company shows as "Empresa eliminada"
+ALTER TYPE job_status ADD VALUE IF NOT EXISTS 'archived_deleted';
+
+CREATE TABLE company_deletions (
+    company_id UUID PRIMARY KEY REFERENCES company_profiles(id) ON DELETE CASCADE,
+    -- Restored when the company is reopened
+    prior_status organization_status NOT NULL,
+    closed_job_ids UUID[] NOT NULL DEFAULT '{}',
+    deactivated_member_ids UUID[] NOT NULL DEFAULT '{}',
+
+    requested_by UUID NOT NULL REFERENCES users(id),
+    wound_down_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    purged_by UUID REFERENCES users(id),
+    purged_at TIMESTAMP WITH TIME ZONE
+);
+
+COMMENT ON TABLE company_deletions IS 'Companies being deleted, with what winding them down changed';
+
+-- Owners delete their own company, so not every step is an admin action
+CREATE TABLE company_deletion_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    -- 'wind_down', 'reopen' or 'purge'
+    action VARCHAR(20) NOT NULL,
+    performed_by UUID NOT NULL REFERENCES users(id),
+    -- The company row before the step
+    snapshot JSONB NOT NULL,
+    details JSONB,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_company_deletion_events_company
+    ON company_deletion_events(company_id, created_at);
@@ -0,0 +1,51 @@
+-- Migration: Per-event notification preferences
+-- Replaces the one-row-per-user email settings with one row per user and
+-- event, each with an email and an in-app switch. Rows are created the first
+-- time a user reads their preferences; until then the defaults for their
+-- user type apply.
+
+DROP TRIGGER IF EXISTS auto_create_notification_preferences ON users;
+DROP FUNCTION IF EXISTS create_default_notification_preferences();
+
+ALTER TABLE notification_preferences RENAME TO legacy_notification_preferences;
+ALTER TABLE legacy_notification_preferences
+    RENAME CONSTRAINT notification_preferences_pkey TO legacy_notification_preferences_pkey;
+ALTER TABLE legacy_notification_preferences
+    RENAME CONSTRAINT notification_preferences_user_id_fkey TO legacy_notification_preferences_user_id_fkey;
+
+CREATE TABLE notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- NotificationEvent code, e.g. 'application_status'
+    event_type VARCHAR(50) NOT NULL,
+    email BOOLEAN NOT NULL,
+    in_app BOOLEAN NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, event_type)
+);
+
+COMMENT ON TABLE notification_preferences IS 'Email and in-app switches per user and notification event';
+
+CREATE TRIGGER update_notification_preferences_updated_at
+    BEFORE UPDATE ON notification_preferences
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Keep the choices users already made where they differ from the defaults:
+-- opting out of seeker emails, and opting into announcements
+INSERT INTO notification_preferences (user_id, event_type, email, in_app)
+SELECT l.user_id, e.event_type, e.email, true
+FROM legacy_notification_preferences l
+JOIN users u ON u.id = l.user_id
+CROSS JOIN LATERAL (
+    VALUES
+        ('application_status', l.email_application_updates, true),
+        ('job_invitation', l.email_invitations, true),
+        ('digest', l.email_job_alerts, true),
+        ('new_message', l.email_messages, false),
+        ('announcement', l.email_marketing, false)
+) AS e(event_type, email, seeker_only)
+WHERE e.email <> (e.event_type <> 'announcement')
+  AND (u.user_type = 'job_seeker' OR NOT e.seeker_only);
+
+DROP TABLE legacy_notification_preferences;
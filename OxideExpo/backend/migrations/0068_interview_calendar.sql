@@ -0,0 +1,21 @@
+-- Calendar feeds of scheduled interviews. Calendar apps subscribe to a URL
+-- and can't send a JWT, so company members get a secret calendar token to
+-- put in it instead.
+
+-- Office address or video call link
+ALTER TABLE job_applications ADD COLUMN interview_location VARCHAR(500);
+
+-- At most one per member; revoking deletes it
+CREATE TABLE calendar_tokens (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    token_prefix VARCHAR(12) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMP WITH TIME ZONE
+);
+
+COMMENT ON TABLE calendar_tokens IS 'Secret tokens for subscribing to interview calendar feeds';
+
+CREATE INDEX idx_job_applications_interview_date
+    ON job_applications(interview_date)
+    WHERE interview_date IS NOT NULL;
@@ -0,0 +1,40 @@
+-- Jobs whose company stopped reviewing applicants: the company is nudged
+-- weekly and, once enough nudges went unanswered, the job is paused.
+
+ALTER TABLE jobs
+    -- Last time the company viewed, moved or annotated the job's applicants
+    ADD COLUMN last_company_activity_at TIMESTAMP WITH TIME ZONE,
+    -- Nudges since that activity
+    ADD COLUMN stale_nudge_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN last_stale_nudge_at TIMESTAMP WITH TIME ZONE,
+    -- Set while the job is paused for inactivity
+    ADD COLUMN stale_paused_at TIMESTAMP WITH TIME ZONE;
+
+-- Existing jobs start from their latest review or note, or their
+-- publication. Backfilling isn't an edit, so updated_at is left alone.
+ALTER TABLE jobs DISABLE TRIGGER update_jobs_updated_at;
+
+UPDATE jobs j
+SET last_company_activity_at = GREATEST(
+    j.published_at,
+    (SELECT MAX(ja.reviewed_at) FROM job_applications ja WHERE ja.job_id = j.id),
+    (
+        SELECT MAX(n.created_at)
+        FROM application_notes n
+        JOIN job_applications ja ON ja.id = n.application_id
+        WHERE ja.job_id = j.id
+    )
+);
+
+ALTER TABLE jobs ENABLE TRIGGER update_jobs_updated_at;
+
+-- What each run did, for the admin report
+CREATE TABLE stale_job_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    ran_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    nudged_jobs INTEGER NOT NULL,
+    paused_jobs INTEGER NOT NULL,
+    notified_applicants INTEGER NOT NULL
+);
+
+CREATE INDEX idx_stale_job_runs_ran_at ON stale_job_runs(ran_at DESC);
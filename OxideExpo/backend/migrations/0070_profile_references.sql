@@ -0,0 +1,55 @@
+-- Profile references
+-- Recommendation letters and referees from previous employers or training
+-- programs, kept on the profile and shown with every application. The OMIL
+-- managing the job seeker can mark a reference as verified after calling the
+-- referee.
+
+CREATE TABLE profile_references (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    referee_name VARCHAR(200) NOT NULL,
+    referee_organization VARCHAR(200),
+    relationship VARCHAR(100) NOT NULL,
+    -- Only the job seeker and their OMIL see it, never companies
+    contact_email VARCHAR(255),
+    letter_file_key TEXT,
+    letter_filename VARCHAR(255),
+    is_verified BOOLEAN NOT NULL DEFAULT false,
+    verified_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_profile_references_user ON profile_references(user_id);
+
+CREATE TRIGGER update_profile_references_updated_at
+    BEFORE UPDATE ON profile_references
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Letters go through the application documents' deletion queue, whether the
+-- reference is deleted, its letter replaced or the user removed
+CREATE OR REPLACE FUNCTION queue_reference_letter_deletion()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.letter_file_key IS NOT NULL
+       AND (TG_OP = 'DELETE' OR NEW.letter_file_key IS DISTINCT FROM OLD.letter_file_key) THEN
+        INSERT INTO application_document_deletions (file_key)
+        VALUES (OLD.letter_file_key)
+        ON CONFLICT (file_key) DO NOTHING;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER profile_references_queue_letter_deletion
+    AFTER DELETE OR UPDATE OF letter_file_key ON profile_references
+    FOR EACH ROW EXECUTE FUNCTION queue_reference_letter_deletion();
+
+-- Per-section visibility, like show_disability_info
+ALTER TABLE job_seeker_preferences
+    ADD COLUMN show_references BOOLEAN NOT NULL DEFAULT true;
+
+COMMENT ON TABLE profile_references IS 'Referees and recommendation letters on job seeker profiles';
+COMMENT ON COLUMN job_seeker_preferences.show_references IS 'Companies see the references of the job seeker''s applications';
@@ -0,0 +1,10 @@
+-- Maintenance mode levels
+-- maintenance_mode used to be an on/off flag that nothing enforced. It now
+-- takes 'off', 'read_only' (writes rejected for everyone but admins) or
+-- 'full' (the API answers 503 except for health checks and the settings
+-- endpoint). A stored true meant blocking non-admin access altogether.
+
+UPDATE system_settings
+SET value = CASE WHEN value = 'true'::jsonb THEN '"full"'::jsonb ELSE '"off"'::jsonb END,
+    description = 'Maintenance mode: off, read_only (only admins can write) or full'
+WHERE key = 'maintenance_mode';
@@ -0,0 +1,8 @@
+-- Salary expectation visibility
+-- Companies comparing shortlisted candidates see the salary a job seeker
+-- expects only when the seeker chooses to show it.
+
+ALTER TABLE job_seeker_preferences
+    ADD COLUMN show_salary_expectation BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN job_seeker_preferences.show_salary_expectation IS 'Companies see the salary expectation of the job seeker''s applications';
@@ -0,0 +1,32 @@
+-- OMIL branches
+-- Large municipalities run several attention points under one OMIL. Members
+-- and managed job seekers can belong to one of them, so directors can follow
+-- each branch's numbers.
+
+CREATE TABLE omil_branches (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    omil_id UUID NOT NULL REFERENCES omil_organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    address VARCHAR(255),
+    municipality_id UUID REFERENCES municipalities(id),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (omil_id, name)
+);
+
+CREATE TRIGGER update_omil_branches_updated_at
+    BEFORE UPDATE ON omil_branches
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Unassigned when the branch is deleted
+ALTER TABLE omil_members
+    ADD COLUMN branch_id UUID REFERENCES omil_branches(id) ON DELETE SET NULL;
+
+ALTER TABLE omil_managed_job_seekers
+    ADD COLUMN branch_id UUID REFERENCES omil_branches(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_omil_members_branch ON omil_members(branch_id) WHERE branch_id IS NOT NULL;
+CREATE INDEX idx_omil_managed_job_seekers_branch
+    ON omil_managed_job_seekers(branch_id) WHERE branch_id IS NOT NULL;
@@ -0,0 +1,37 @@
+-- Domain events
+-- An append-only log of what happened to applications, jobs, invitations,
+-- managed job seekers and users. Each event is written in the transaction of
+-- the change it describes, so support can replay one entity's history.
+
+CREATE TYPE aggregate_type AS ENUM (
+    'application',
+    'job',
+    'invitation',
+    'managed_job_seeker',
+    'user'
+);
+
+CREATE TABLE domain_events (
+    id BIGSERIAL PRIMARY KEY,
+    aggregate_type aggregate_type NOT NULL,
+    aggregate_id UUID NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    -- NULL for events raised by the platform itself. Not a foreign key, so
+    -- the log outlives the users it mentions.
+    actor_id UUID,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_domain_events_aggregate ON domain_events(aggregate_type, aggregate_id, id);
+
+CREATE FUNCTION reject_domain_event_changes() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'domain_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER domain_events_append_only
+    BEFORE UPDATE OR DELETE ON domain_events
+    FOR EACH ROW
+    EXECUTE FUNCTION reject_domain_event_changes();
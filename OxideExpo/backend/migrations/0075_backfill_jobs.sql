@@ -0,0 +1,26 @@
+-- Backfill jobs
+-- Data migrations over large tables run out of band, a batch at a time, so
+-- deploys don't wait on them. Each registered backfill has one row here with
+-- its progress; the cursor is committed with every batch so a restarted
+-- worker picks up where the last one stopped.
+
+CREATE TYPE backfill_status AS ENUM ('pending', 'running', 'paused', 'completed', 'failed');
+
+CREATE TABLE backfill_jobs (
+    name VARCHAR(100) PRIMARY KEY,
+    status backfill_status NOT NULL DEFAULT 'pending',
+    -- Opaque to the runner; each backfill decides what it holds
+    cursor TEXT,
+    batch_size INTEGER NOT NULL DEFAULT 1000 CHECK (batch_size > 0),
+    processed BIGINT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_backfill_jobs_updated_at
+    BEFORE UPDATE ON backfill_jobs
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,5 @@
+-- Job activity feed
+-- Each member's unread count covers what colleagues did after they last
+-- opened a job's activity feed.
+
+ALTER TABLE company_members ADD COLUMN last_seen_activity_at TIMESTAMPTZ;
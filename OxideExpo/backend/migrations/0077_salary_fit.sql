@@ -0,0 +1,38 @@
+-- Salary fit
+-- How a job seeker's salary expectation compares with a job's salary range.
+-- Job salaries can be quoted per hour up to per year; expectations are
+-- monthly, so job amounts are brought to a month before comparing.
+
+CREATE TYPE salary_fit AS ENUM ('below', 'within', 'above');
+
+-- Monthly equivalent of a salary quoted per `period`, taking a 42-hour,
+-- five-day week. Amounts without a period are taken as monthly.
+CREATE OR REPLACE FUNCTION monthly_salary(amount NUMERIC, period VARCHAR)
+RETURNS NUMERIC AS $$
+    SELECT amount * CASE period
+        WHEN 'hourly' THEN 42 * 52 / 12.0
+        WHEN 'daily' THEN 5 * 52 / 12.0
+        WHEN 'weekly' THEN 52 / 12.0
+        WHEN 'biweekly' THEN 26 / 12.0
+        WHEN 'yearly' THEN 1 / 12.0
+        ELSE 1
+    END
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Where the expectation falls against the job's range: above when even its
+-- minimum is more than the job pays at most, below when even its maximum is
+-- less than the job's minimum. NULL when either side has no amounts or the
+-- currencies differ.
+CREATE OR REPLACE FUNCTION salary_expectation_fit(
+    job_min NUMERIC, job_max NUMERIC, job_currency VARCHAR, job_period VARCHAR,
+    expected_min NUMERIC, expected_max NUMERIC, expected_currency VARCHAR
+) RETURNS salary_fit AS $$
+    SELECT CASE
+        WHEN UPPER(job_currency) IS DISTINCT FROM UPPER(expected_currency) THEN NULL
+        WHEN COALESCE(job_min, job_max) IS NULL
+            OR COALESCE(expected_min, expected_max) IS NULL THEN NULL
+        WHEN expected_min > monthly_salary(job_max, job_period) THEN 'above'
+        WHEN expected_max < monthly_salary(job_min, job_period) THEN 'below'
+        ELSE 'within'
+    END::salary_fit
+$$ LANGUAGE sql IMMUTABLE;
@@ -0,0 +1,8 @@
+-- Cohort retention report
+-- When a user last made an authenticated request. The auth middleware writes
+-- it at most once an hour per user, debounced in Redis, so it is only as
+-- precise as that.
+
+ALTER TABLE users ADD COLUMN last_active_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.last_active_at IS 'Last authenticated request, written at most hourly';
@@ -0,0 +1,11 @@
+-- Job reposts
+-- A job may be a repost of an earlier, closed posting of the same vacancy,
+-- so applicants who already applied to it can be recognized.
+
+ALTER TABLE jobs
+    ADD COLUMN reposted_from_job_id UUID REFERENCES jobs(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_jobs_reposted_from ON jobs(reposted_from_job_id)
+    WHERE reposted_from_job_id IS NOT NULL;
+
+COMMENT ON COLUMN jobs.reposted_from_job_id IS 'Earlier posting of the same vacancy, of the same company and closed or rejected when linked';
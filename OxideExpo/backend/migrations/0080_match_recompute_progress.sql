@@ -0,0 +1,15 @@
+-- Match score recomputation after requirement changes
+-- Editing what a job requires queues a rescore ahead of the ones companies
+-- ask for, covering seekers who saved the job or were scored for it before
+-- as well as its applicants. The worker goes through it in batches and
+-- records how far it got, so companies can see that scores are updating.
+
+ALTER TABLE match_score_recalculations
+    ADD COLUMN requirements_changed BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Seekers to rescore, counted when the worker starts on the request
+    ADD COLUMN total_seekers INTEGER,
+    ADD COLUMN processed_seekers INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN started_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_match_score_recalculations_queue
+    ON match_score_recalculations(requirements_changed DESC, requested_at);
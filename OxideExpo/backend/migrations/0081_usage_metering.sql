@@ -0,0 +1,35 @@
+-- Company usage metering
+-- What each company uses the platform for, kept for future cost-sharing.
+-- Handlers record one row per metered action; a nightly task totals them per
+-- calendar month in the platform time zone, rewriting the month each time.
+
+CREATE TYPE usage_event_type AS ENUM (
+    'job_published',
+    'candidate_search',
+    'invitation_sent',
+    'cv_download'
+);
+
+CREATE TABLE usage_events (
+    id BIGSERIAL PRIMARY KEY,
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    event_type usage_event_type NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1 CHECK (quantity > 0),
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    metadata JSONB NOT NULL DEFAULT '{}'
+);
+
+CREATE INDEX idx_usage_events_occurred ON usage_events(occurred_at);
+CREATE INDEX idx_usage_events_company ON usage_events(company_id, occurred_at);
+
+CREATE TABLE usage_monthly_summaries (
+    company_id UUID NOT NULL REFERENCES company_profiles(id) ON DELETE CASCADE,
+    -- First day of the month
+    month DATE NOT NULL,
+    event_type usage_event_type NOT NULL,
+    total BIGINT NOT NULL,
+    aggregated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (company_id, month, event_type)
+);
+
+CREATE INDEX idx_usage_monthly_summaries_month ON usage_monthly_summaries(month);
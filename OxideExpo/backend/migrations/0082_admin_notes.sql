@@ -0,0 +1,44 @@
+-- Internal admin notes
+-- Support and moderation staff annotate companies, users, jobs and OMILs.
+-- Notes are only ever read through admin endpoints. Mentioning another admin
+-- leaves them an unread mention, their in-app notification of the note.
+
+CREATE TYPE admin_note_entity AS ENUM (
+    'company',
+    'user',
+    'job',
+    'omil'
+);
+
+CREATE TABLE admin_notes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    entity_type admin_note_entity NOT NULL,
+    -- Not a foreign key: it points into the table of entity_type
+    entity_id UUID NOT NULL,
+    -- Kept when the author stops being an admin
+    author_admin_id UUID REFERENCES admins(id) ON DELETE SET NULL,
+    body TEXT NOT NULL,
+    is_pinned BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_admin_notes_entity ON admin_notes(entity_type, entity_id);
+
+CREATE TRIGGER update_admin_notes_updated_at
+    BEFORE UPDATE ON admin_notes
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE admin_notes IS 'Internal notes of admins on companies, users, jobs and OMILs; never shown outside the admin panel';
+
+CREATE TABLE admin_note_mentions (
+    note_id UUID NOT NULL REFERENCES admin_notes(id) ON DELETE CASCADE,
+    admin_id UUID NOT NULL REFERENCES admins(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    read_at TIMESTAMPTZ,
+    PRIMARY KEY (note_id, admin_id)
+);
+
+CREATE INDEX idx_admin_note_mentions_unread ON admin_note_mentions(admin_id, created_at DESC)
+    WHERE read_at IS NULL;
@@ -0,0 +1,46 @@
+-- Application deadline changes
+-- Jobs are closed once their deadline passes. Rather than reposting a job
+-- that underperformed, a company can move its deadline; extending the
+-- deadline of a job closed that way reopens it.
+
+ALTER TABLE jobs
+    -- Set while the job is closed because its deadline passed
+    ADD COLUMN deadline_closed_at TIMESTAMP WITH TIME ZONE;
+
+-- A deadline can't be set in the past, but the job must stay writable once
+-- its deadline passes, to be closed among others. The CHECK held on every
+-- write of the row, so it's replaced by a trigger on setting the deadline.
+ALTER TABLE jobs DROP CONSTRAINT check_application_deadline;
+
+CREATE OR REPLACE FUNCTION check_application_deadline()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_OP = 'INSERT' OR NEW.application_deadline IS DISTINCT FROM OLD.application_deadline)
+        AND NEW.application_deadline < CURRENT_DATE THEN
+        RAISE EXCEPTION 'The application deadline can''t be in the past'
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER jobs_application_deadline
+    BEFORE INSERT OR UPDATE OF application_deadline ON jobs
+    FOR EACH ROW EXECUTE FUNCTION check_application_deadline();
+
+CREATE TABLE job_deadline_changes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    previous_deadline DATE NOT NULL,
+    new_deadline DATE NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- The job had been closed by its deadline and the change reopened it
+    reopened BOOLEAN NOT NULL DEFAULT false,
+    -- Seekers told about an extension
+    notified_seekers INTEGER NOT NULL DEFAULT 0,
+    changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE job_deadline_changes IS 'Log of application deadline changes of published jobs';
+
+CREATE INDEX idx_job_deadline_changes_job ON job_deadline_changes(job_id, changed_at DESC);
@@ -0,0 +1,21 @@
+-- Job alert emails
+-- Seekers with email_job_alerts on get the new jobs that match them, as
+-- often as their alert_frequency says.
+
+ALTER TABLE job_seeker_preferences
+    -- Jobs published up to this moment were considered for the seeker's
+    -- alerts. Set when a digest went out or there was nothing to send.
+    ADD COLUMN last_alert_sent_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_job_seeker_preferences_alerts
+    ON job_seeker_preferences(last_alert_sent_at NULLS FIRST)
+    WHERE email_job_alerts AND alert_frequency <> 'never';
+
+-- updated_at tells the seeker when they last changed their preferences, which
+-- stamping an alert doesn't
+DROP TRIGGER update_job_seeker_preferences_updated_at ON job_seeker_preferences;
+CREATE TRIGGER update_job_seeker_preferences_updated_at
+    BEFORE UPDATE ON job_seeker_preferences
+    FOR EACH ROW
+    WHEN (OLD.last_alert_sent_at IS NOT DISTINCT FROM NEW.last_alert_sent_at)
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,10 @@
+-- Cursor pagination of the public job listing
+-- GET /api/jobs orders active jobs featured first, then newest published,
+-- then by id, and continues after a cursor with a row comparison on the
+-- same keys.
+
+CREATE INDEX idx_jobs_public_listing ON jobs (
+    COALESCE(is_featured, false) DESC,
+    COALESCE(published_at, created_at) DESC,
+    id DESC
+) WHERE status = 'active';
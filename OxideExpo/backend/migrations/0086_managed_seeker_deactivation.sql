@@ -0,0 +1,30 @@
+-- Deactivation time of managed job seekers
+-- The monthly report counts the seekers an OMIL was managing when the month
+-- ended, which needs to know when a record stopped being active rather than
+-- only whether it is active now.
+
+ALTER TABLE omil_managed_job_seekers
+    -- Set while the record is inactive, cleared when it's reactivated
+    ADD COLUMN deactivated_at TIMESTAMP WITH TIME ZONE;
+
+-- Best estimate for existing records: the transfer, or the last change
+UPDATE omil_managed_job_seekers
+SET deactivated_at = COALESCE(transferred_at, updated_at)
+WHERE is_active = false;
+
+-- Kept up to date on every path that flips is_active, unless the update
+-- sets the time itself
+CREATE OR REPLACE FUNCTION track_managed_seeker_deactivation()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.is_active IS DISTINCT FROM OLD.is_active
+        AND NEW.deactivated_at IS NOT DISTINCT FROM OLD.deactivated_at THEN
+        NEW.deactivated_at := CASE WHEN NEW.is_active THEN NULL ELSE NOW() END;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER omil_managed_job_seekers_deactivation
+    BEFORE UPDATE OF is_active ON omil_managed_job_seekers
+    FOR EACH ROW EXECUTE FUNCTION track_managed_seeker_deactivation();
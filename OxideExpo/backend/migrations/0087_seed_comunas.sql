@@ -0,0 +1,376 @@
+-- Seed of all of Chile's comunas
+-- The reference data only held the larger comunas of some regions, so
+-- seekers, companies and OMILs elsewhere couldn't pick their own. Adds the
+-- rest of the 346 comunas with the coordinates of their urban centre for
+-- commute filtering; comunas already present keep their row and coordinates.
+
+INSERT INTO municipalities (region_id, name, latitude, longitude)
+SELECT r.id, v.name, v.latitude, v.longitude
+FROM (VALUES
+    -- Arica y Parinacota
+    ('XV', 'Arica', -18.4783, -70.3126),
+    ('XV', 'Camarones', -19.0167, -69.8667),
+    ('XV', 'Putre', -18.1967, -69.5594),
+    ('XV', 'General Lagos', -17.5950, -69.4780),
+    -- Tarapacá
+    ('I', 'Iquique', -20.2141, -70.1522),
+    ('I', 'Alto Hospicio', -20.2681, -70.1003),
+    ('I', 'Pozo Almonte', -20.2597, -69.7864),
+    ('I', 'Camiña', -19.3125, -69.4253),
+    ('I', 'Colchane', -19.2767, -68.6381),
+    ('I', 'Huara', -19.9953, -69.7717),
+    ('I', 'Pica', -20.4892, -69.3297),
+    -- Antofagasta
+    ('II', 'Antofagasta', -23.6500, -70.4000),
+    ('II', 'Mejillones', -23.1000, -70.4500),
+    ('II', 'Sierra Gorda', -22.8911, -69.3214),
+    ('II', 'Taltal', -25.4053, -70.4856),
+    ('II', 'Calama', -22.4667, -68.9333),
+    ('II', 'Ollagüe', -21.2231, -68.2536),
+    ('II', 'San Pedro de Atacama', -22.9167, -68.2000),
+    ('II', 'Tocopilla', -22.0833, -70.2000),
+    ('II', 'María Elena', -22.3456, -69.6614),
+    -- Atacama
+    ('III', 'Copiapó', -27.3668, -70.3314),
+    ('III', 'Caldera', -27.0667, -70.8167),
+    ('III', 'Tierra Amarilla', -27.4833, -70.2667),
+    ('III', 'Chañaral', -26.3479, -70.6224),
+    ('III', 'Diego de Almagro', -26.3667, -70.0500),
+    ('III', 'Vallenar', -28.5708, -70.7581),
+    ('III', 'Alto del Carmen', -28.7500, -70.4833),
+    ('III', 'Freirina', -28.5000, -71.0833),
+    ('III', 'Huasco', -28.4667, -71.2167),
+    -- Coquimbo
+    ('IV', 'La Serena', -29.9027, -71.2519),
+    ('IV', 'Coquimbo', -29.9533, -71.3436),
+    ('IV', 'Andacollo', -30.2333, -71.0833),
+    ('IV', 'La Higuera', -29.5000, -71.2667),
+    ('IV', 'Paihuano', -30.0167, -70.5167),
+    ('IV', 'Vicuña', -30.0333, -70.7167),
+    ('IV', 'Illapel', -31.6333, -71.1667),
+    ('IV', 'Canela', -31.4000, -71.4500),
+    ('IV', 'Los Vilos', -31.9167, -71.5000),
+    ('IV', 'Salamanca', -31.7667, -70.9667),
+    ('IV', 'Ovalle', -30.6000, -71.2000),
+    ('IV', 'Combarbalá', -31.1833, -71.0000),
+    ('IV', 'Monte Patria', -30.6833, -70.9500),
+    ('IV', 'Punitaqui', -30.8333, -71.2667),
+    ('IV', 'Río Hurtado', -30.2667, -70.6667),
+    -- Valparaíso
+    ('V', 'Valparaíso', -33.0472, -71.6127),
+    ('V', 'Casablanca', -33.3167, -71.4167),
+    ('V', 'Concón', -32.9306, -71.5186),
+    ('V', 'Juan Fernández', -33.6167, -78.8333),
+    ('V', 'Puchuncaví', -32.7167, -71.4167),
+    ('V', 'Quintero', -32.7833, -71.5333),
+    ('V', 'Viña del Mar', -33.0246, -71.5518),
+    ('V', 'Isla de Pascua', -27.1500, -109.4333),
+    ('V', 'Los Andes', -32.8333, -70.6000),
+    ('V', 'Calle Larga', -32.8500, -70.6333),
+    ('V', 'Rinconada', -32.8333, -70.7000),
+    ('V', 'San Esteban', -32.8000, -70.5833),
+    ('V', 'La Ligua', -32.4500, -71.2333),
+    ('V', 'Cabildo', -32.4333, -71.0667),
+    ('V', 'Papudo', -32.5167, -71.4500),
+    ('V', 'Petorca', -32.2500, -70.9333),
+    ('V', 'Zapallar', -32.5500, -71.4667),
+    ('V', 'Quillota', -32.8833, -71.2500),
+    ('V', 'La Calera', -32.7833, -71.2000),
+    ('V', 'Hijuelas', -32.8000, -71.1667),
+    ('V', 'La Cruz', -32.8167, -71.2333),
+    ('V', 'Nogales', -32.7333, -71.2000),
+    ('V', 'San Antonio', -33.5933, -71.6217),
+    ('V', 'Algarrobo', -33.3667, -71.6667),
+    ('V', 'Cartagena', -33.5531, -71.6069),
+    ('V', 'El Quisco', -33.3972, -71.6972),
+    ('V', 'El Tabo', -33.4500, -71.6667),
+    ('V', 'Santo Domingo', -33.6333, -71.6333),
+    ('V', 'San Felipe', -32.7500, -70.7167),
+    ('V', 'Catemu', -32.7833, -70.9667),
+    ('V', 'Llaillay', -32.8500, -70.9667),
+    ('V', 'Panquehue', -32.8000, -70.8333),
+    ('V', 'Putaendo', -32.6333, -70.7167),
+    ('V', 'Santa María', -32.7500, -70.6667),
+    ('V', 'Quilpué', -33.0472, -71.4425),
+    ('V', 'Limache', -33.0167, -71.2667),
+    ('V', 'Olmué', -33.0000, -71.1833),
+    ('V', 'Villa Alemana', -33.0422, -71.3733),
+    -- Metropolitana de Santiago
+    ('RM', 'Santiago', -33.4372, -70.6506),
+    ('RM', 'Cerrillos', -33.5000, -70.7167),
+    ('RM', 'Cerro Navia', -33.4250, -70.7350),
+    ('RM', 'Conchalí', -33.3833, -70.6750),
+    ('RM', 'El Bosque', -33.5667, -70.6750),
+    ('RM', 'Estación Central', -33.4586, -70.6845),
+    ('RM', 'Huechuraba', -33.3667, -70.6333),
+    ('RM', 'Independencia', -33.4167, -70.6667),
+    ('RM', 'La Cisterna', -33.5297, -70.6642),
+    ('RM', 'La Florida', -33.5227, -70.5980),
+    ('RM', 'La Granja', -33.5333, -70.6250),
+    ('RM', 'La Pintana', -33.5833, -70.6333),
+    ('RM', 'La Reina', -33.4500, -70.5417),
+    ('RM', 'Las Condes', -33.4117, -70.5678),
+    ('RM', 'Lo Barnechea', -33.3500, -70.5167),
+    ('RM', 'Lo Espejo', -33.5222, -70.6914),
+    ('RM', 'Lo Prado', -33.4444, -70.7253),
+    ('RM', 'Macul', -33.4869, -70.5994),
+    ('RM', 'Maipú', -33.5106, -70.7572),
+    ('RM', 'Ñuñoa', -33.4569, -70.5977),
+    ('RM', 'Pedro Aguirre Cerda', -33.4906, -70.6783),
+    ('RM', 'Peñalolén', -33.4858, -70.5425),
+    ('RM', 'Providencia', -33.4314, -70.6093),
+    ('RM', 'Pudahuel', -33.4400, -70.7600),
+    ('RM', 'Quilicura', -33.3600, -70.7289),
+    ('RM', 'Quinta Normal', -33.4283, -70.6983),
+    ('RM', 'Recoleta', -33.4064, -70.6414),
+    ('RM', 'Renca', -33.4033, -70.7275),
+    ('RM', 'San Joaquín', -33.4950, -70.6283),
+    ('RM', 'San Miguel', -33.4967, -70.6511),
+    ('RM', 'San Ramón', -33.5333, -70.6417),
+    ('RM', 'Vitacura', -33.3897, -70.5714),
+    ('RM', 'Puente Alto', -33.6117, -70.5758),
+    ('RM', 'Pirque', -33.6333, -70.5500),
+    ('RM', 'San José de Maipo', -33.6333, -70.3500),
+    ('RM', 'Colina', -33.2000, -70.6833),
+    ('RM', 'Lampa', -33.2833, -70.8833),
+    ('RM', 'Tiltil', -33.0833, -70.9333),
+    ('RM', 'San Bernardo', -33.5922, -70.6996),
+    ('RM', 'Buin', -33.7333, -70.7333),
+    ('RM', 'Calera de Tango', -33.6333, -70.7833),
+    ('RM', 'Paine', -33.8167, -70.7333),
+    ('RM', 'Melipilla', -33.6833, -71.2167),
+    ('RM', 'Alhué', -34.0333, -71.1000),
+    ('RM', 'Curacaví', -33.4000, -71.1500),
+    ('RM', 'María Pinto', -33.5167, -71.1333),
+    ('RM', 'San Pedro', -33.9000, -71.4667),
+    ('RM', 'Talagante', -33.6667, -70.9333),
+    ('RM', 'El Monte', -33.6833, -71.0167),
+    ('RM', 'Isla de Maipo', -33.7500, -70.9000),
+    ('RM', 'Padre Hurtado', -33.5667, -70.8167),
+    ('RM', 'Peñaflor', -33.6167, -70.8833),
+    -- O'Higgins
+    ('VI', 'Rancagua', -34.1708, -70.7444),
+    ('VI', 'Codegua', -34.0333, -70.6667),
+    ('VI', 'Coinco', -34.2667, -70.9667),
+    ('VI', 'Coltauco', -34.2833, -71.0833),
+    ('VI', 'Doñihue', -34.2333, -70.9667),
+    ('VI', 'Graneros', -34.0667, -70.7333),
+    ('VI', 'Las Cabras', -34.2833, -71.3167),
+    ('VI', 'Machalí', -34.1833, -70.6500),
+    ('VI', 'Malloa', -34.4500, -70.9333),
+    ('VI', 'Mostazal', -33.9833, -70.7000),
+    ('VI', 'Olivar', -34.2167, -70.8167),
+    ('VI', 'Peumo', -34.4000, -71.1667),
+    ('VI', 'Pichidegua', -34.3500, -71.2833),
+    ('VI', 'Quinta de Tilcoco', -34.3500, -70.9667),
+    ('VI', 'Rengo', -34.4167, -70.8667),
+    ('VI', 'Requínoa', -34.2833, -70.8167),
+    ('VI', 'San Vicente', -34.4333, -71.0833),
+    ('VI', 'Pichilemu', -34.3833, -72.0000),
+    ('VI', 'La Estrella', -34.2000, -71.6667),
+    ('VI', 'Litueche', -34.1167, -71.7333),
+    ('VI', 'Marchigüe', -34.4000, -71.6167),
+    ('VI', 'Navidad', -33.9333, -71.8333),
+    ('VI', 'Paredones', -34.6500, -71.9000),
+    ('VI', 'San Fernando', -34.5833, -70.9833),
+    ('VI', 'Chépica', -34.7333, -71.2833),
+    ('VI', 'Chimbarongo', -34.7000, -71.0500),
+    ('VI', 'Lolol', -34.7667, -71.6500),
+    ('VI', 'Nancagua', -34.6667, -71.2167),
+    ('VI', 'Palmilla', -34.6000, -71.3667),
+    ('VI', 'Peralillo', -34.4833, -71.4833),
+    ('VI', 'Placilla', -34.6167, -71.1167),
+    ('VI', 'Pumanque', -34.6000, -71.6667),
+    ('VI', 'Santa Cruz', -34.6333, -71.3667),
+    -- Maule
+    ('VII', 'Talca', -35.4264, -71.6554),
+    ('VII', 'Constitución', -35.3333, -72.4167),
+    ('VII', 'Curepto', -35.0833, -72.0167),
+    ('VII', 'Empedrado', -35.6000, -72.2833),
+    ('VII', 'Maule', -35.5333, -71.7000),
+    ('VII', 'Pelarco', -35.3667, -71.4500),
+    ('VII', 'Pencahue', -35.4000, -71.8167),
+    ('VII', 'Río Claro', -35.2833, -71.2667),
+    ('VII', 'San Clemente', -35.5500, -71.4833),
+    ('VII', 'San Rafael', -35.3167, -71.5333),
+    ('VII', 'Cauquenes', -35.9667, -72.3167),
+    ('VII', 'Chanco', -35.7333, -72.5333),
+    ('VII', 'Pelluhue', -35.8167, -72.5667),
+    ('VII', 'Curicó', -34.9828, -71.2394),
+    ('VII', 'Hualañé', -34.9667, -71.8000),
+    ('VII', 'Licantén', -34.9833, -72.0000),
+    ('VII', 'Molina', -35.1167, -71.2833),
+    ('VII', 'Rauco', -34.9333, -71.3167),
+    ('VII', 'Romeral', -34.9667, -71.1333),
+    ('VII', 'Sagrada Familia', -34.9833, -71.3833),
+    ('VII', 'Teno', -34.8667, -71.1667),
+    ('VII', 'Vichuquén', -34.8833, -72.0000),
+    ('VII', 'Linares', -35.8500, -71.6000),
+    ('VII', 'Colbún', -35.7000, -71.4167),
+    ('VII', 'Longaví', -35.9667, -71.6833),
+    ('VII', 'Parral', -36.1500, -71.8333),
+    ('VII', 'Retiro', -36.0500, -71.7667),
+    ('VII', 'San Javier', -35.6000, -71.7333),
+    ('VII', 'Villa Alegre', -35.6833, -71.7500),
+    ('VII', 'Yerbas Buenas', -35.7500, -71.5833),
+    -- Ñuble
+    ('XVI', 'Chillán', -36.6066, -72.1034),
+    ('XVI', 'Bulnes', -36.7333, -72.3000),
+    ('XVI', 'Chillán Viejo', -36.6333, -72.1333),
+    ('XVI', 'El Carmen', -36.9000, -72.0333),
+    ('XVI', 'Pemuco', -36.9667, -72.1000),
+    ('XVI', 'Pinto', -36.7000, -71.9000),
+    ('XVI', 'Quillón', -36.7333, -72.4667),
+    ('XVI', 'San Ignacio', -36.8000, -71.9833),
+    ('XVI', 'Yungay', -37.1167, -72.0167),
+    ('XVI', 'Quirihue', -36.2833, -72.5333),
+    ('XVI', 'Cobquecura', -36.1333, -72.7833),
+    ('XVI', 'Coelemu', -36.4833, -72.7000),
+    ('XVI', 'Ninhue', -36.4000, -72.4000),
+    ('XVI', 'Portezuelo', -36.5333, -72.4333),
+    ('XVI', 'Ránquil', -36.6500, -72.6000),
+    ('XVI', 'Treguaco', -36.4333, -72.6667),
+    ('XVI', 'San Carlos', -36.4167, -71.9500),
+    ('XVI', 'Coihueco', -36.6167, -71.8333),
+    ('XVI', 'Ñiquén', -36.3000, -71.9000),
+    ('XVI', 'San Fabián', -36.5500, -71.5500),
+    ('XVI', 'San Nicolás', -36.5000, -72.2167),
+    -- Biobío
+    ('VIII', 'Concepción', -36.8270, -73.0503),
+    ('VIII', 'Coronel', -37.0167, -73.1333),
+    ('VIII', 'Chiguayante', -36.9167, -73.0167),
+    ('VIII', 'Florida', -36.8167, -72.6667),
+    ('VIII', 'Hualqui', -36.9667, -72.9333),
+    ('VIII', 'Lota', -37.0833, -73.1500),
+    ('VIII', 'Penco', -36.7333, -72.9833),
+    ('VIII', 'San Pedro de la Paz', -36.8333, -73.1000),
+    ('VIII', 'Santa Juana', -37.1667, -72.9333),
+    ('VIII', 'Talcahuano', -36.7167, -73.1167),
+    ('VIII', 'Tomé', -36.6167, -72.9500),
+    ('VIII', 'Hualpén', -36.7833, -73.0833),
+    ('VIII', 'Lebu', -37.6167, -73.6500),
+    ('VIII', 'Arauco', -37.2500, -73.3167),
+    ('VIII', 'Cañete', -37.8000, -73.4000),
+    ('VIII', 'Contulmo', -38.0000, -73.2333),
+    ('VIII', 'Curanilahue', -37.4667, -73.3500),
+    ('VIII', 'Los Álamos', -37.6167, -73.4667),
+    ('VIII', 'Tirúa', -38.3333, -73.5000),
+    ('VIII', 'Los Ángeles', -37.4667, -72.3500),
+    ('VIII', 'Antuco', -37.3333, -71.6833),
+    ('VIII', 'Cabrero', -37.0333, -72.4000),
+    ('VIII', 'Laja', -37.2833, -72.7167),
+    ('VIII', 'Mulchén', -37.7167, -72.2333),
+    ('VIII', 'Nacimiento', -37.5000, -72.6667),
+    ('VIII', 'Negrete', -37.5833, -72.5333),
+    ('VIII', 'Quilaco', -37.6833, -71.9833),
+    ('VIII', 'Quilleco', -37.4667, -71.8833),
+    ('VIII', 'San Rosendo', -37.2667, -72.7167),
+    ('VIII', 'Santa Bárbara', -37.6667, -72.0167),
+    ('VIII', 'Tucapel', -37.2833, -71.9500),
+    ('VIII', 'Yumbel', -37.1000, -72.5667),
+    ('VIII', 'Alto Biobío', -37.8667, -71.6167),
+    -- La Araucanía
+    ('IX', 'Temuco', -38.7359, -72.5904),
+    ('IX', 'Carahue', -38.7167, -73.1667),
+    ('IX', 'Cunco', -38.9333, -72.0333),
+    ('IX', 'Curarrehue', -39.3500, -71.5833),
+    ('IX', 'Freire', -38.9500, -72.6333),
+    ('IX', 'Galvarino', -38.4000, -72.7833),
+    ('IX', 'Gorbea', -39.1000, -72.6833),
+    ('IX', 'Lautaro', -38.5333, -72.4333),
+    ('IX', 'Loncoche', -39.3667, -72.6333),
+    ('IX', 'Melipeuco', -38.8500, -71.7000),
+    ('IX', 'Nueva Imperial', -38.7333, -72.9500),
+    ('IX', 'Padre Las Casas', -38.7667, -72.6000),
+    ('IX', 'Perquenco', -38.4167, -72.3833),
+    ('IX', 'Pitrufquén', -38.9833, -72.6500),
+    ('IX', 'Pucón', -39.2833, -71.9500),
+    ('IX', 'Saavedra', -38.7833, -73.4000),
+    ('IX', 'Teodoro Schmidt', -38.9667, -73.0667),
+    ('IX', 'Toltén', -39.2167, -73.2167),
+    ('IX', 'Vilcún', -38.6667, -72.2333),
+    ('IX', 'Villarrica', -39.2833, -72.2167),
+    ('IX', 'Cholchol', -38.6000, -72.8500),
+    ('IX', 'Angol', -37.8000, -72.7167),
+    ('IX', 'Collipulli', -37.9500, -72.4333),
+    ('IX', 'Curacautín', -38.4333, -71.8833),
+    ('IX', 'Ercilla', -38.0500, -72.3833),
+    ('IX', 'Lonquimay', -38.4500, -71.3667),
+    ('IX', 'Los Sauces', -37.9667, -72.8333),
+    ('IX', 'Lumaco', -38.1500, -72.9000),
+    ('IX', 'Purén', -38.0333, -73.0667),
+    ('IX', 'Renaico', -37.6667, -72.5667),
+    ('IX', 'Traiguén', -38.2500, -72.6667),
+    ('IX', 'Victoria', -38.2333, -72.3333),
+    -- Los Ríos
+    ('XIV', 'Valdivia', -39.8142, -73.2459),
+    ('XIV', 'Corral', -39.8833, -73.4333),
+    ('XIV', 'Lanco', -39.4500, -72.7667),
+    ('XIV', 'Los Lagos', -39.8500, -72.8167),
+    ('XIV', 'Máfil', -39.6500, -72.9500),
+    ('XIV', 'Mariquina', -39.5333, -72.9667),
+    ('XIV', 'Paillaco', -40.0667, -72.8667),
+    ('XIV', 'Panguipulli', -39.6333, -72.3333),
+    ('XIV', 'La Unión', -40.2833, -73.0833),
+    ('XIV', 'Futrono', -40.1333, -72.3833),
+    ('XIV', 'Lago Ranco', -40.3167, -72.5000),
+    ('XIV', 'Río Bueno', -40.3333, -72.9500),
+    -- Los Lagos
+    ('X', 'Puerto Montt', -41.4693, -72.9424),
+    ('X', 'Calbuco', -41.7667, -73.1333),
+    ('X', 'Cochamó', -41.5000, -72.3167),
+    ('X', 'Fresia', -41.1500, -73.4167),
+    ('X', 'Frutillar', -41.1167, -73.0500),
+    ('X', 'Los Muermos', -41.4000, -73.4667),
+    ('X', 'Llanquihue', -41.2500, -73.0167),
+    ('X', 'Maullín', -41.6167, -73.6000),
+    ('X', 'Puerto Varas', -41.3167, -72.9833),
+    ('X', 'Castro', -42.4833, -73.7667),
+    ('X', 'Ancud', -41.8667, -73.8333),
+    ('X', 'Chonchi', -42.6167, -73.7667),
+    ('X', 'Curaco de Vélez', -42.4333, -73.6000),
+    ('X', 'Dalcahue', -42.3667, -73.6500),
+    ('X', 'Puqueldón', -42.6000, -73.6667),
+    ('X', 'Queilén', -42.9000, -73.4833),
+    ('X', 'Quellón', -43.1167, -73.6167),
+    ('X', 'Quemchi', -42.1333, -73.4667),
+    ('X', 'Quinchao', -42.5333, -73.4167),
+    ('X', 'Osorno', -40.5725, -73.1353),
+    ('X', 'Puerto Octay', -40.9667, -72.8833),
+    ('X', 'Purranque', -40.9167, -73.1667),
+    ('X', 'Puyehue', -40.6833, -72.6000),
+    ('X', 'Río Negro', -40.7833, -73.2333),
+    ('X', 'San Juan de la Costa', -40.5167, -73.4000),
+    ('X', 'San Pablo', -40.4000, -73.0167),
+    ('X', 'Chaitén', -42.9167, -72.7167),
+    ('X', 'Futaleufú', -43.1833, -71.8667),
+    ('X', 'Hualaihué', -42.0167, -72.6833),
+    ('X', 'Palena', -43.6167, -71.8000),
+    -- Aysén
+    ('XI', 'Coyhaique', -45.5712, -72.0685),
+    ('XI', 'Lago Verde', -44.2167, -71.8333),
+    ('XI', 'Aysén', -45.4000, -72.7000),
+    ('XI', 'Cisnes', -44.7333, -72.7000),
+    ('XI', 'Guaitecas', -43.8833, -73.7500),
+    ('XI', 'Cochrane', -47.2500, -72.5833),
+    ('XI', 'O''Higgins', -48.4667, -72.5667),
+    ('XI', 'Tortel', -47.8000, -73.5333),
+    ('XI', 'Chile Chico', -46.5500, -71.7333),
+    ('XI', 'Río Ibáñez', -46.3000, -71.9333),
+    -- Magallanes
+    ('XII', 'Punta Arenas', -53.1638, -70.9171),
+    ('XII', 'Laguna Blanca', -52.2500, -71.9167),
+    ('XII', 'Río Verde', -52.6500, -71.4667),
+    ('XII', 'San Gregorio', -52.3167, -69.6833),
+    ('XII', 'Cabo de Hornos', -54.9333, -67.6167),
+    ('XII', 'Antártica', -62.2000, -58.9667),
+    ('XII', 'Porvenir', -53.3000, -70.3667),
+    ('XII', 'Primavera', -52.7167, -69.2500),
+    ('XII', 'Timaukel', -53.6667, -69.9000),
+    ('XII', 'Natales', -51.7236, -72.5064),
+    ('XII', 'Torres del Paine', -51.2667, -72.3333)
+) AS v(region_code, name, latitude, longitude)
+JOIN regions r ON r.code = v.region_code
+ON CONFLICT (region_id, name) DO UPDATE
+SET latitude = COALESCE(municipalities.latitude, EXCLUDED.latitude),
+    longitude = COALESCE(municipalities.longitude, EXCLUDED.longitude);
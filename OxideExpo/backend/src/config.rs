@@ -1,5 +1,13 @@
 use std::env;
 
+use crate::models::user::CaptchaMode;
+use crate::services::storage::StorageDriver;
+use crate::utils::redact::Redactor;
+
+/// Longest lifetime, in seconds, accepted for access and refresh tokens, so
+/// expiry timestamps computed from them can't overflow
+pub const MAX_TOKEN_EXPIRY_SECS: i64 = 366 * 24 * 60 * 60;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     // Application
@@ -17,6 +25,8 @@ pub struct Config {
 
     // JWT
     pub jwt_secret: String,
+    /// Retired secrets still accepted for verification while old tokens expire
+    pub jwt_previous_secrets: Vec<String>,
     pub jwt_access_expiry: i64,
     pub jwt_refresh_expiry: i64,
 
@@ -33,6 +43,10 @@ pub struct Config {
     pub s3_bucket: String,
     pub s3_region: String,
     pub s3_public_url: String,
+    // Driver behind every file feature; the S3 settings above only apply to
+    // the s3 driver and the directory only to the local one
+    pub storage_driver: StorageDriver,
+    pub storage_local_dir: String,
 
     // Email
     pub smtp_host: String,
@@ -40,10 +54,55 @@ pub struct Config {
     pub smtp_user: Option<String>,
     pub smtp_password: Option<String>,
     pub smtp_from: String,
+
+    // Public landing page statistics; false turns GET /api/stats/public off
+    pub public_stats_enabled: bool,
+
+    // IANA zone that calendar months and days are counted in for reports
+    pub platform_timezone: String,
+
+    // Job alert emails: seekers handled per run, and the match score (0-100)
+    // a new job needs to be included in an alert
+    pub job_alert_batch_size: i64,
+    pub job_alert_min_score: i32,
+
+    // Overload protection: requests served at once before further ones are
+    // shed with a 503, and seconds one may run before it is answered with 408
+    pub max_concurrent_requests: usize,
+    pub request_timeout_secs: u64,
+
+    // Logging: personal data (emails, RUTs, sensitive query parameters) is
+    // masked unless log_pii is set
+    pub log_pii: bool,
+    pub log_redacted_params: Vec<String>,
+
+    // Abuse protection on registration and password reset. Provider modes
+    // need the secret; the verify URL overrides the provider's siteverify
+    // endpoint, and proof-of-work solutions need pow_difficulty zero bits.
+    pub captcha_mode: CaptchaMode,
+    pub captcha_secret: Option<String>,
+    pub captcha_verify_url: Option<String>,
+    pub pow_difficulty: u32,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self, ConfigError> {
+        let database_max_connections: u32 = env::var("DATABASE_MAX_CONNECTIONS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .map_err(|_| ConfigError::InvalidValue("DATABASE_MAX_CONNECTIONS".to_string()))?;
+
+        let captcha_mode: CaptchaMode = env::var("CAPTCHA_MODE")
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| ConfigError::InvalidValue("CAPTCHA_MODE".to_string()))?;
+        let captcha_secret = env::var("CAPTCHA_SECRET").ok().filter(|s| !s.is_empty());
+        if matches!(captcha_mode, CaptchaMode::Hcaptcha | CaptchaMode::Turnstile)
+            && captcha_secret.is_none()
+        {
+            return Err(ConfigError::Missing("CAPTCHA_SECRET".to_string()));
+        }
+
         Ok(Config {
             // Application
             app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
@@ -59,10 +118,7 @@ impl Config {
             // Database
             database_url: env::var("DATABASE_URL")
                 .map_err(|_| ConfigError::Missing("DATABASE_URL".to_string()))?,
-            database_max_connections: env::var("DATABASE_MAX_CONNECTIONS")
-                .unwrap_or_else(|_| "10".to_string())
-                .parse()
-                .map_err(|_| ConfigError::InvalidValue("DATABASE_MAX_CONNECTIONS".to_string()))?,
+            database_max_connections,
 
             // Redis
             redis_url: env::var("REDIS_URL")
@@ -71,14 +127,26 @@ impl Config {
             // JWT
             jwt_secret: env::var("JWT_SECRET")
                 .map_err(|_| ConfigError::Missing("JWT_SECRET".to_string()))?,
+            jwt_previous_secrets: env::var("JWT_PREVIOUS_SECRETS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
             jwt_access_expiry: env::var("JWT_ACCESS_EXPIRY")
                 .unwrap_or_else(|_| "900".to_string())
                 .parse()
-                .map_err(|_| ConfigError::InvalidValue("JWT_ACCESS_EXPIRY".to_string()))?,
+                .ok()
+                .filter(|secs| (1..=MAX_TOKEN_EXPIRY_SECS).contains(secs))
+                .ok_or_else(|| ConfigError::InvalidValue("JWT_ACCESS_EXPIRY".to_string()))?,
             jwt_refresh_expiry: env::var("JWT_REFRESH_EXPIRY")
                 .unwrap_or_else(|_| "604800".to_string())
                 .parse()
-                .map_err(|_| ConfigError::InvalidValue("JWT_REFRESH_EXPIRY".to_string()))?,
+                .ok()
+                .filter(|secs| (1..=MAX_TOKEN_EXPIRY_SECS).contains(secs))
+                .ok_or_else(|| ConfigError::InvalidValue("JWT_REFRESH_EXPIRY".to_string()))?,
 
             // OAuth (optional)
             google_client_id: env::var("GOOGLE_CLIENT_ID").ok().filter(|s| !s.is_empty()),
@@ -99,6 +167,12 @@ impl Config {
                 .unwrap_or_else(|_| "us-east-1".to_string()),
             s3_public_url: env::var("S3_PUBLIC_URL")
                 .unwrap_or_else(|_| "http://localhost:9000/empleos-inclusivos".to_string()),
+            storage_driver: env::var("STORAGE_DRIVER")
+                .unwrap_or_default()
+                .parse()
+                .map_err(|_| ConfigError::InvalidValue("STORAGE_DRIVER".to_string()))?,
+            storage_local_dir: env::var("STORAGE_LOCAL_DIR")
+                .unwrap_or_else(|_| "./storage".to_string()),
 
             // Email
             smtp_host: env::var("SMTP_HOST")
@@ -111,9 +185,77 @@ impl Config {
             smtp_password: env::var("SMTP_PASSWORD").ok().filter(|s| !s.is_empty()),
             smtp_from: env::var("SMTP_FROM")
                 .unwrap_or_else(|_| "noreply@empleosinclusivos.cl".to_string()),
+
+            // Public statistics
+            public_stats_enabled: env::var("PUBLIC_STATS_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidValue("PUBLIC_STATS_ENABLED".to_string()))?,
+
+            // Reporting
+            platform_timezone: env::var("PLATFORM_TIMEZONE")
+                .unwrap_or_else(|_| "America/Santiago".to_string()),
+
+            // Job alerts
+            job_alert_batch_size: env::var("JOB_ALERT_BATCH_SIZE")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .ok()
+                .filter(|&size| size > 0)
+                .ok_or_else(|| ConfigError::InvalidValue("JOB_ALERT_BATCH_SIZE".to_string()))?,
+            job_alert_min_score: env::var("JOB_ALERT_MIN_SCORE")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .ok()
+                .filter(|score| (0..=100).contains(score))
+                .ok_or_else(|| ConfigError::InvalidValue("JOB_ALERT_MIN_SCORE".to_string()))?,
+
+            // Overload protection. Most requests hold a database connection
+            // for only part of their lifetime, so the default admits several
+            // requests per pooled connection.
+            max_concurrent_requests: match env::var("MAX_CONCURRENT_REQUESTS") {
+                Ok(v) => v
+                    .parse()
+                    .ok()
+                    .filter(|&limit| limit > 0)
+                    .ok_or_else(|| ConfigError::InvalidValue("MAX_CONCURRENT_REQUESTS".to_string()))?,
+                Err(_) => database_max_connections as usize * 10,
+            },
+            request_timeout_secs: env::var("REQUEST_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidValue("REQUEST_TIMEOUT_SECS".to_string()))?,
+
+            // Logging
+            log_pii: env::var("LOG_PII")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidValue("LOG_PII".to_string()))?,
+            log_redacted_params: env::var("LOG_REDACTED_PARAMS")
+                .unwrap_or_else(|_| "email,search,q,national_id".to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+
+            // Abuse protection
+            captcha_mode,
+            captcha_secret,
+            captcha_verify_url: env::var("CAPTCHA_VERIFY_URL").ok().filter(|s| !s.is_empty()),
+            pow_difficulty: env::var("POW_DIFFICULTY")
+                .unwrap_or_else(|_| "18".to_string())
+                .parse()
+                .ok()
+                .filter(|&bits| bits <= 32)
+                .ok_or_else(|| ConfigError::InvalidValue("POW_DIFFICULTY".to_string()))?,
         })
     }
 
+    /// Masks personal data in logs unless `log_pii` is set
+    pub fn redactor(&self) -> Redactor {
+        Redactor::new(self.log_pii)
+    }
+
     pub fn is_development(&self) -> bool {
         self.app_env == "development"
     }
@@ -1,11 +1,15 @@
 use axum::{
-    http::StatusCode,
+    extract::rejection::{PathRejection, QueryRejection},
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, Utc};
 use serde_json::json;
+use std::fmt;
+
+use crate::{models::application::ApplicationStatus, utils::redact};
 
-#[derive(Debug)]
 pub enum AppError {
     /// Database operation failed
     DatabaseError(sqlx::Error),
@@ -15,19 +19,163 @@ pub enum AppError {
     AuthenticationError(String),
     /// User is authenticated but not authorized for this action (403)
     ForbiddenError(String),
+    /// Bot check missing or failed (403, code `captcha_failed`)
+    CaptchaFailed(String),
+    /// The account has no password yet (403, code `password_setup_required`)
+    PasswordSetupRequired(String),
+    /// Registration was rolled back and no account exists (500, code
+    /// `registration_failed`), so the client can offer to try again
+    RegistrationFailed(String),
+    /// The user would act on both sides of an application (403 with `code`)
+    ConflictOfInterest { code: &'static str, message: String },
     /// Resource not found (404)
     NotFound(String),
     /// Resource already exists - e.g., duplicate email (409)
     ConflictError(String),
+    /// Conflict the client can inspect and possibly override (409)
+    ConflictWithDetails {
+        message: String,
+        details: serde_json::Value,
+    },
+    /// Request ran longer than the server allows (408)
+    RequestTimeout(String),
+    /// Caller is sending requests faster than allowed (429)
+    TooManyRequests(String),
+    /// A usage quota is used up until `resets_at` (429 with `Retry-After`)
+    QuotaExceeded {
+        message: String,
+        resets_at: DateTime<Utc>,
+    },
+    /// Request is well-formed but not allowed in the resource's current state (422)
+    UnprocessableEntity {
+        message: String,
+        details: serde_json::Value,
+    },
     /// Internal server error (500)
     InternalError(String),
+    /// A dependency or generated resource is not ready yet (503)
+    ServiceUnavailable(String),
+    /// The platform is in maintenance mode (503, code `MAINTENANCE`), with
+    /// the operator's message when there is one
+    Maintenance {
+        mode: &'static str,
+        message: Option<String>,
+    },
+}
+
+impl AppError {
+    /// 422 for an application status change outside the allowed transition graph
+    pub fn invalid_status_transition(
+        current: ApplicationStatus,
+        requested: ApplicationStatus,
+        allowed: &[ApplicationStatus],
+    ) -> Self {
+        AppError::UnprocessableEntity {
+            message: format!(
+                "Cannot change application status from {:?} to {:?}",
+                current, requested
+            ),
+            details: json!({
+                "current_status": current,
+                "requested_status": requested,
+                "allowed_statuses": allowed,
+            }),
+        }
+    }
+}
+
+/// Database errors are described without the values Postgres echoes back,
+/// see `redact::db_error`
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::DatabaseError(err) => write!(f, "Database error: {}", redact::db_error(err)),
+            AppError::ValidationError(msg)
+            | AppError::AuthenticationError(msg)
+            | AppError::ForbiddenError(msg)
+            | AppError::CaptchaFailed(msg)
+            | AppError::PasswordSetupRequired(msg)
+            | AppError::RegistrationFailed(msg)
+            | AppError::NotFound(msg)
+            | AppError::ConflictError(msg)
+            | AppError::RequestTimeout(msg)
+            | AppError::TooManyRequests(msg)
+            | AppError::InternalError(msg)
+            | AppError::ServiceUnavailable(msg) => f.write_str(msg),
+            AppError::ConflictWithDetails { message, .. }
+            | AppError::UnprocessableEntity { message, .. }
+            | AppError::QuotaExceeded { message, .. }
+            | AppError::ConflictOfInterest { message, .. } => f.write_str(message),
+            AppError::Maintenance { mode, .. } => write!(f, "Maintenance mode {}", mode),
+        }
+    }
+}
+
+/// Written by hand so a database error is described as in `Display`, not
+/// with the values sqlx's own Debug output carries
+impl fmt::Debug for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::DatabaseError(err) => f
+                .debug_tuple("DatabaseError")
+                .field(&redact::db_error(err))
+                .finish(),
+            AppError::ValidationError(msg) => f.debug_tuple("ValidationError").field(msg).finish(),
+            AppError::AuthenticationError(msg) => {
+                f.debug_tuple("AuthenticationError").field(msg).finish()
+            }
+            AppError::ForbiddenError(msg) => f.debug_tuple("ForbiddenError").field(msg).finish(),
+            AppError::CaptchaFailed(msg) => f.debug_tuple("CaptchaFailed").field(msg).finish(),
+            AppError::PasswordSetupRequired(msg) => {
+                f.debug_tuple("PasswordSetupRequired").field(msg).finish()
+            }
+            AppError::RegistrationFailed(msg) => {
+                f.debug_tuple("RegistrationFailed").field(msg).finish()
+            }
+            AppError::ConflictOfInterest { code, message } => f
+                .debug_struct("ConflictOfInterest")
+                .field("code", code)
+                .field("message", message)
+                .finish(),
+            AppError::NotFound(msg) => f.debug_tuple("NotFound").field(msg).finish(),
+            AppError::ConflictError(msg) => f.debug_tuple("ConflictError").field(msg).finish(),
+            AppError::ConflictWithDetails { message, details } => f
+                .debug_struct("ConflictWithDetails")
+                .field("message", message)
+                .field("details", details)
+                .finish(),
+            AppError::RequestTimeout(msg) => f.debug_tuple("RequestTimeout").field(msg).finish(),
+            AppError::TooManyRequests(msg) => f.debug_tuple("TooManyRequests").field(msg).finish(),
+            AppError::QuotaExceeded { message, resets_at } => f
+                .debug_struct("QuotaExceeded")
+                .field("message", message)
+                .field("resets_at", resets_at)
+                .finish(),
+            AppError::UnprocessableEntity { message, details } => f
+                .debug_struct("UnprocessableEntity")
+                .field("message", message)
+                .field("details", details)
+                .finish(),
+            AppError::InternalError(msg) => f.debug_tuple("InternalError").field(msg).finish(),
+            AppError::ServiceUnavailable(msg) => {
+                f.debug_tuple("ServiceUnavailable").field(msg).finish()
+            }
+            AppError::Maintenance { mode, message } => f
+                .debug_struct("Maintenance")
+                .field("mode", mode)
+                .field("message", message)
+                .finish(),
+        }
+    }
 }
 
+impl std::error::Error for AppError {}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             AppError::DatabaseError(err) => {
-                tracing::error!("Database error: {:?}", err);
+                tracing::error!("Database error: {}", redact::db_error(&err));
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Database error occurred".to_string(),
@@ -36,12 +184,81 @@ impl IntoResponse for AppError {
             AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::AuthenticationError(msg) => (StatusCode::UNAUTHORIZED, msg),
             AppError::ForbiddenError(msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::CaptchaFailed(msg) => {
+                let body = Json(json!({
+                    "error": msg,
+                    "code": "captcha_failed"
+                }));
+                return (StatusCode::FORBIDDEN, body).into_response();
+            }
+            AppError::PasswordSetupRequired(msg) => {
+                let body = Json(json!({
+                    "error": msg,
+                    "code": "password_setup_required"
+                }));
+                return (StatusCode::FORBIDDEN, body).into_response();
+            }
+            AppError::RegistrationFailed(msg) => {
+                let body = Json(json!({
+                    "error": msg,
+                    "code": "registration_failed"
+                }));
+                return (StatusCode::INTERNAL_SERVER_ERROR, body).into_response();
+            }
+            AppError::ConflictOfInterest { code, message } => {
+                let body = Json(json!({
+                    "error": message,
+                    "code": code
+                }));
+                return (StatusCode::FORBIDDEN, body).into_response();
+            }
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             AppError::ConflictError(msg) => (StatusCode::CONFLICT, msg),
+            AppError::RequestTimeout(msg) => (StatusCode::REQUEST_TIMEOUT, msg),
+            AppError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg),
+            AppError::QuotaExceeded { message, resets_at } => {
+                let retry_after = (resets_at - Utc::now()).num_seconds().max(0);
+                let body = Json(json!({
+                    "error": message,
+                    "resets_at": resets_at
+                }));
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after.to_string())],
+                    body,
+                )
+                    .into_response();
+            }
+            AppError::UnprocessableEntity { message, details } => {
+                let body = Json(json!({
+                    "error": message,
+                    "details": details
+                }));
+                return (StatusCode::UNPROCESSABLE_ENTITY, body).into_response();
+            }
+            AppError::ConflictWithDetails { message, details } => {
+                let body = Json(json!({
+                    "error": message,
+                    "details": details
+                }));
+                return (StatusCode::CONFLICT, body).into_response();
+            }
             AppError::InternalError(msg) => {
                 tracing::error!("Internal error: {}", msg);
                 (StatusCode::INTERNAL_SERVER_ERROR, msg)
             }
+            AppError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            AppError::Maintenance { mode, message } => {
+                let mut body = json!({
+                    "error": "The platform is under maintenance",
+                    "code": "MAINTENANCE",
+                    "mode": mode
+                });
+                if let Some(message) = message {
+                    body["message"] = json!(message);
+                }
+                return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+            }
         };
 
         let body = Json(json!({
@@ -77,18 +294,99 @@ impl From<validator::ValidationErrors> for AppError {
     }
 }
 
+impl From<PathRejection> for AppError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            // A malformed ID is reported like a missing one
+            PathRejection::FailedToDeserializePathParams(err) => {
+                tracing::debug!("Path rejected: {}", err.body_text());
+                AppError::NotFound("Resource not found".to_string())
+            }
+            other => AppError::InternalError(other.body_text()),
+        }
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        let body_text = rejection.body_text();
+        let (parameter, reason) = query_rejection_parts(&body_text);
+
+        let message = match parameter {
+            Some(name) => format!("Invalid value for query parameter '{}'", name),
+            None => "Invalid query parameters".to_string(),
+        };
+
+        AppError::UnprocessableEntity {
+            message,
+            details: json!({
+                "parameter": parameter,
+                "reason": reason,
+            }),
+        }
+    }
+}
+
+/// Splits axum's query rejection text into the offending parameter, when
+/// it names one, and the deserializer's reason
+fn query_rejection_parts(body_text: &str) -> (Option<&str>, &str) {
+    let detail = body_text
+        .split_once(": ")
+        .map(|(_, detail)| detail)
+        .unwrap_or(body_text);
+
+    if let Some(rest) = detail.strip_prefix("missing field `") {
+        return (rest.split('`').next(), detail);
+    }
+
+    match detail.split_once(": ") {
+        Some((parameter, reason)) if !parameter.contains(' ') => (Some(parameter), reason),
+        _ => (None, detail),
+    }
+}
+
 impl From<argon2::password_hash::Error> for AppError {
     fn from(err: argon2::password_hash::Error) -> Self {
-        tracing::error!("Password hash error: {:?}", err);
+        tracing::error!("Password hash error: {}", err);
         AppError::InternalError("Password processing error".to_string())
     }
 }
 
 impl From<jsonwebtoken::errors::Error> for AppError {
     fn from(err: jsonwebtoken::errors::Error) -> Self {
-        tracing::debug!("JWT error: {:?}", err);
+        tracing::debug!("JWT error: {}", err);
         AppError::AuthenticationError("Invalid or expired token".to_string())
     }
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_rejection_parts_names_parameter() {
+        let (parameter, reason) = query_rejection_parts(
+            "Failed to deserialize query string: admin_id: UUID parsing failed: invalid length",
+        );
+        assert_eq!(parameter, Some("admin_id"));
+        assert_eq!(reason, "UUID parsing failed: invalid length");
+    }
+
+    #[test]
+    fn test_query_rejection_parts_missing_field() {
+        let (parameter, reason) =
+            query_rejection_parts("Failed to deserialize query string: missing field `year`");
+        assert_eq!(parameter, Some("year"));
+        assert_eq!(reason, "missing field `year`");
+    }
+
+    #[test]
+    fn test_query_rejection_parts_without_parameter() {
+        let (parameter, reason) =
+            query_rejection_parts("Failed to deserialize query string: invalid type: map");
+        assert_eq!(parameter, None);
+        assert_eq!(reason, "invalid type: map");
+    }
+}
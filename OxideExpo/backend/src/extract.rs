@@ -0,0 +1,22 @@
+//! Drop-in replacements for axum's `Path` and `Query` extractors.
+//!
+//! They behave exactly like axum's, but a rejected request answers with the
+//! usual `AppError` JSON body instead of axum's plain-text 400:
+//!
+//! - a path parameter that fails to parse (e.g. `/api/jobs/not-a-uuid`) is a
+//!   404, the same as a well-formed ID that doesn't exist
+//! - a query string that fails to deserialize is a 422 naming the parameter
+//!
+//! Handlers import these instead of `axum::extract::{Path, Query}`.
+
+use axum::extract::FromRequestParts;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(AppError))]
+pub struct Path<T>(pub T);
+
+#[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(AppError))]
+pub struct Query<T>(pub T);
@@ -1,32 +1,78 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
 use axum::{
     body::Body,
-    extract::{Path, Query, State},
+    extract::State,
     http::header,
-    response::Response,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
     Extension, Json,
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
+use futures::Stream;
 use rust_xlsxwriter::{Format, Workbook};
 use serde_json::json;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::error::AppError;
-use crate::middleware::auth::AuthUser;
+use crate::extract::{Path, Query};
+use crate::handlers::auth::{create_password_reset_token_with, create_verification_token_with};
+use crate::middleware::auth::{
+    forget_token_version, recent_key_usage, AuthUser, KEY_USAGE_WINDOW_HOURS,
+};
 use crate::models::admin::{
-    Admin, AdminAuditLog, AdminDashboardStats, AdminImpersonationResponse, ApplicationStatusCount,
-    ApplicationTrendsReport, ApproveCompanyRequest, ApproveJobRequest, ApproveOmilRequest,
-    AuditLogFilterParams, CompanyTrendsReport, JobTrendsReport,
-    PaginatedResponse, RejectCompanyRequest, RejectJobRequest, RejectOmilRequest,
-    ReportDateRangeParams, SystemSetting, TrendDataPoint,
-    UpdateSettingsRequest, UpdateUserStatusRequest, UserDetail, UserFilterParams, UserListItem,
-    UserTrendsReport, UserTypeCount,
+    Admin, AdminAuditLog, AdminCompanyDetail, AdminDashboardStats, AdminImpersonationResponse,
+    AdminNote, AdminNoteEntity, AdminNoteMention, AdminNoteMentionsQuery, AdminNoteQuery,
+    AdminRole, AggregateType, ApplicationStatusCount, ApplicationTrendsReport,
+    ApproveCompanyRequest, ApproveJobRequest, ApproveOmilRequest, AuditLogFilterParams,
+    BackfillJob, BackfillStatus, CohortRetentionReport, CohortRetentionRow, CohortRetentionTotals,
+    CompanyTrendsReport, CreateAdminNoteRequest, CreateFollowupTypeRequest,
+    CreateModerationRuleRequest, DismissOrphanedCompaniesRequest, DismissOrphanedCompaniesResponse,
+    DomainEvent, DomainEventsQuery, DuplicateUserEntry, DuplicateUserGroup,
+    ExportAuditFilterParams, InclusionFunnelRow, InclusionReport, IndustryCompanyCount,
+    IssueUserTokenRequest, IssuedUserToken, JobReview, JobReviewCompany, JobReviewRequirement,
+    JobTransferResponse, JobTrendsReport, JwtKeyInfo, JwtKeysResponse, MergeUsersRequest,
+    MergeUsersResponse, ModerationQueueAge, ModerationReport, ModerationRule, ModerationRuleType,
+    ModerationSeverity, ModerationTurnaround, OrphanedCompany, OrphanedCompanyParams,
+    PaginatedResponse, PaginationParams, PendingJob, RecountApplicationsResponse,
+    RejectCompanyRequest, RejectJobRequest, RejectOmilRequest, RejectionReasonCount,
+    ReportDateRangeParams, RetentionAction, RetentionEntity, RetentionPolicy, RetentionRun,
+    RetentionRunParams, RetentionRunResponse, ReviewerDecisionCount, RunRetentionRequest,
+    ScreeningFinding, SettingDefinition, SettingHistoryEntry, StaleJobRun, SuppressedCount,
+    SystemSetting, TokenDelivery, TransferJobRequest, TrendDataPoint, UpdateAdminNoteRequest,
+    UpdateFollowupTypeRequest, UpdateModerationRuleRequest, UpdateRetentionPolicyRequest,
+    UpdateSettingsRequest, UpdateUserStatusRequest, UsageReport, UserDetail, UserFilterParams,
+    UserListItem, UserTrendsReport, UserTypeCount, ADMIN_PASSWORD_RESET_HOURS,
+    COHORT_RETENTION_MONTHS, COHORT_RETENTION_WINDOWS, DEFAULT_ORPHANED_COMPANY_DAYS,
+    MIN_REPORTABLE_CELL, ORPHANED_COMPANY_REJECTION_REASON, SETTING_DEFINITIONS,
+};
+use crate::models::application::RejectionReasonCode;
+use crate::models::company::{
+    CompanyDeletionResponse, CompanyProfile, DeleteCompanyQuery, OrganizationStatus,
+    PendingProfileChange, ProfileChangeStatus, RejectProfileChangeRequest, ReopenCompanyResponse,
+    UsageEventType, UsageQuery,
+};
+use crate::models::export::{ExportAuditEntry, ExportSettings, UpdateExportSettingsRequest};
+use crate::models::job::{Job, JobStatus, JobType, ShiftType, WorkModality};
+use crate::models::notification::NotificationEvent;
+use crate::models::omil::{FollowupType, OmilOrganization, SYSTEM_FOLLOWUP_TYPES};
+use crate::models::profile::DisabilityCategory;
+use crate::models::user::{AccountStatus, UserResponse, UserType};
+use crate::models::reference::{
+    MergeInstitutionsReport, MergeInstitutionsRequest, ReferenceSeed, ReferenceSeedReport,
+    UnmatchedInstitutionName, UnmatchedInstitutionsQuery,
 };
-use crate::models::company::{CompanyProfile, OrganizationStatus};
-use crate::models::job::{Job, JobStatus, JobType, WorkModality};
-use crate::models::omil::OmilOrganization;
-use crate::models::user::{AccountStatus, UserType};
-use crate::utils::jwt::create_impersonation_token;
+use crate::services::{
+    admin_events, admin_notes, backfill, company_deletion, content_screening, counters, events, exports,
+    institutions, job_transfer, metering, notifications, reference_seed, retention, settings,
+    suspension,
+};
+use crate::utils::jwt::{create_impersonation_token, create_refresh_token, key_id};
+use crate::utils::password::hash_password;
 use crate::AppState;
 
 // ============================================================================
@@ -140,6 +186,31 @@ pub async fn get_dashboard_stats(
     }))
 }
 
+// ============================================================================
+// LIVE QUEUE COUNTS
+// ============================================================================
+
+/// Seconds between keep-alive comments, inside common proxy idle timeouts
+const ADMIN_EVENTS_KEEP_ALIVE_SECS: u64 = 25;
+
+/// GET /api/admin/events
+/// Server-sent events with the moderation queue counts: a `snapshot` on
+/// connect, then `queue_counts` whenever they change. Reconnecting clients
+/// get a fresh snapshot, so `Last-Event-ID` needs no replay.
+pub async fn stream_admin_events(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>, AppError> {
+    // Subscribe before counting so no change falls between the two
+    let receiver = state.admin_events.subscribe();
+    let snapshot_id = state.admin_events.last_id();
+    let snapshot = admin_events::pending_counts(&state.db).await?;
+
+    Ok(Sse::new(admin_events::event_stream(snapshot_id, snapshot, receiver)).keep_alive(
+        KeepAlive::new().interval(std::time::Duration::from_secs(ADMIN_EVENTS_KEEP_ALIVE_SECS)),
+    ))
+}
+
 // ============================================================================
 // COMPANY MANAGEMENT
 // ============================================================================
@@ -195,6 +266,140 @@ pub async fn list_pending_companies(
     Ok(Json(companies))
 }
 
+/// Threshold in days for the orphaned company view, defaulted and bounded
+fn orphaned_company_days(older_than_days: Option<i64>) -> Result<i32, AppError> {
+    let days = older_than_days.unwrap_or(DEFAULT_ORPHANED_COMPANY_DAYS);
+    if !(1..=365).contains(&days) {
+        return Err(AppError::ValidationError(
+            "older_than_days must be between 1 and 365".to_string(),
+        ));
+    }
+    Ok(days as i32)
+}
+
+/// GET /api/admin/companies/orphaned
+/// Pending companies left behind by registrations that were never completed:
+/// older than the threshold and none of their members ever verified their
+/// email or logged in
+pub async fn list_orphaned_companies(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<OrphanedCompanyParams>,
+) -> Result<Json<Vec<OrphanedCompany>>, AppError> {
+    let days = orphaned_company_days(params.older_than_days)?;
+
+    let companies = sqlx::query_as!(
+        OrphanedCompany,
+        r#"
+        SELECT
+            c.id as company_id,
+            c.company_name,
+            c.created_at as registered_at,
+            o.id as "owner_id?",
+            o.email as "owner_email?",
+            (o.first_name || ' ' || o.last_name) as "owner_name?"
+        FROM company_profiles c
+        LEFT JOIN LATERAL (
+            SELECT u.id, u.email, u.first_name, u.last_name
+            FROM company_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.company_id = c.id AND m.role = 'owner'
+            ORDER BY m.joined_at
+            LIMIT 1
+        ) o ON true
+        WHERE c.status = 'pending_approval'
+        AND c.created_at < NOW() - make_interval(days => $1)
+        AND NOT EXISTS (
+            SELECT 1
+            FROM company_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.company_id = c.id
+            AND (u.email_verified_at IS NOT NULL OR u.last_login_at IS NOT NULL)
+        )
+        ORDER BY c.created_at ASC
+        "#,
+        days
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(companies))
+}
+
+/// POST /api/admin/companies/orphaned/dismiss
+/// Reject orphaned companies with the standard reason. Companies that no
+/// longer match the orphaned criteria are skipped.
+pub async fn dismiss_orphaned_companies(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<DismissOrphanedCompaniesRequest>,
+) -> Result<Json<DismissOrphanedCompaniesResponse>, AppError> {
+    payload.validate()?;
+    let days = orphaned_company_days(payload.older_than_days)?;
+
+    let mut tx = state.db.begin().await?;
+
+    // The criteria are checked again here, so a company whose owner has
+    // turned up since the list was loaded is left for normal review
+    let dismissed = sqlx::query!(
+        r#"
+        UPDATE company_profiles c
+        SET
+            status = 'rejected'::organization_status,
+            rejection_reason = $3,
+            approved_by = $4,
+            updated_at = NOW()
+        WHERE c.id = ANY($1)
+        AND c.status = 'pending_approval'
+        AND c.created_at < NOW() - make_interval(days => $2)
+        AND NOT EXISTS (
+            SELECT 1
+            FROM company_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.company_id = c.id
+            AND (u.email_verified_at IS NOT NULL OR u.last_login_at IS NOT NULL)
+        )
+        RETURNING c.id, c.company_name
+        "#,
+        &payload.company_ids,
+        days,
+        ORPHANED_COMPANY_REJECTION_REASON,
+        auth_user.id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for company in &dismissed {
+        log_admin_action(
+            &mut *tx,
+            admin.id,
+            "dismiss_orphaned_company",
+            "company",
+            company.id,
+            Some(json!({
+                "rejection_reason": ORPHANED_COMPANY_REJECTION_REASON,
+                "company_name": company.company_name,
+                "older_than_days": days,
+            })),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let dismissed: Vec<Uuid> = dismissed.into_iter().map(|c| c.id).collect();
+    let mut skipped: Vec<Uuid> = payload
+        .company_ids
+        .into_iter()
+        .filter(|id| !dismissed.contains(id))
+        .collect();
+    skipped.sort();
+    skipped.dedup();
+
+    Ok(Json(DismissOrphanedCompaniesResponse { dismissed, skipped }))
+}
+
 /// PATCH /api/admin/companies/{id}/approve
 /// Approve a company registration
 pub async fn approve_company(
@@ -285,6 +490,8 @@ pub async fn approve_company(
     )
     .await?;
 
+    admin_events::publish(&state).await;
+
     Ok(Json(company))
 }
 
@@ -379,1266 +586,4421 @@ pub async fn reject_company(
     )
     .await?;
 
+    admin_events::publish(&state).await;
+
     Ok(Json(company))
 }
 
+/// GET /api/admin/companies/{id}
+/// A company's profile with the internal notes staff keep on it
+pub async fn get_company_detail(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Path(company_id): Path<Uuid>,
+) -> Result<Json<AdminCompanyDetail>, AppError> {
+    let profile = fetch_company_profile(&state.db, company_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
+    let notes = admin_notes::notes_for(&state.db, AdminNoteEntity::Company, company_id).await?;
+
+    Ok(Json(AdminCompanyDetail { profile, notes }))
+}
+
 // ============================================================================
-// JOB MODERATION
+// V13: COMPANY PROFILE CHANGE REVIEW
 // ============================================================================
 
-/// GET /api/admin/jobs/pending
-/// List jobs pending approval
-pub async fn list_pending_jobs(
+async fn fetch_pending_change(
+    db: impl sqlx::PgExecutor<'_>,
+    company_id: Uuid,
+    change_id: Uuid,
+) -> Result<PendingProfileChange, AppError> {
+    sqlx::query_as!(
+        PendingProfileChange,
+        r#"
+        SELECT id, company_id, field_name, old_value, new_value,
+               status as "status: ProfileChangeStatus",
+               requested_by, reviewed_by, reviewed_at, rejection_reason, created_at
+        FROM pending_profile_changes
+        WHERE id = $1 AND company_id = $2 AND status = 'pending'
+        FOR UPDATE
+        "#,
+        change_id,
+        company_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending change not found".to_string()))
+}
+
+/// GET /api/admin/companies/{id}/pending-changes
+/// List sensitive profile changes awaiting review for a company
+pub async fn list_company_pending_changes(
     State(state): State<AppState>,
     Extension(_admin): Extension<Admin>,
-) -> Result<Json<Vec<Job>>, AppError> {
-    let jobs = sqlx::query_as!(
-        Job,
+    Path(company_id): Path<Uuid>,
+) -> Result<Json<Vec<PendingProfileChange>>, AppError> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM company_profiles WHERE id = $1) as "exists!""#,
+        company_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !exists {
+        return Err(AppError::NotFound("Company not found".to_string()));
+    }
+
+    let changes = sqlx::query_as!(
+        PendingProfileChange,
         r#"
-        SELECT
-            id,
-            company_id,
-            posted_by,
-            title,
-            description,
-            responsibilities,
-            job_type as "job_type: JobType",
-            industry_id,
-            work_area_id,
-            position_level_id,
-            work_modality as "work_modality: WorkModality",
-            work_schedule,
-            region_id,
-            municipality_id,
-            is_remote_allowed,
-            education_level,
-            years_experience_min,
-            years_experience_max,
-            age_min,
-            age_max,
-            salary_min as "salary_min: rust_decimal::Decimal",
-            salary_max as "salary_max: rust_decimal::Decimal",
-            salary_currency,
-            salary_period,
-            benefits,
-            application_deadline,
-            contact_email,
-            application_url,
-            vacancies,
-            applications_count,
-            status as "status: JobStatus",
-            approved_at,
-            approved_by,
-            rejection_reason,
-            completeness_percentage,
-            is_featured,
-            views_count,
-            created_at,
-            updated_at
-        FROM jobs
-        WHERE status = 'pending_approval'
-        ORDER BY created_at ASC
-        "#
+        SELECT id, company_id, field_name, old_value, new_value,
+               status as "status: ProfileChangeStatus",
+               requested_by, reviewed_by, reviewed_at, rejection_reason, created_at
+        FROM pending_profile_changes
+        WHERE company_id = $1 AND status = 'pending'
+        ORDER BY created_at
+        "#,
+        company_id
     )
     .fetch_all(&state.db)
     .await?;
 
-    Ok(Json(jobs))
+    Ok(Json(changes))
 }
 
-/// PATCH /api/admin/jobs/{id}/approve
-/// Approve a job posting
-pub async fn approve_job(
+/// PATCH /api/admin/companies/{id}/pending-changes/{change_id}/approve
+/// Apply a pending sensitive profile change
+pub async fn approve_company_profile_change(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(admin): Extension<Admin>,
-    Path(job_id): Path<Uuid>,
-    Json(payload): Json<ApproveJobRequest>,
-) -> Result<Json<Job>, AppError> {
-    // Validate payload
-    payload.validate()?;
+    Path((company_id, change_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<PendingProfileChange>, AppError> {
+    let mut tx = state.db.begin().await?;
+    let change = fetch_pending_change(&mut *tx, company_id, change_id).await?;
+
+    match change.field_name.as_str() {
+        "company_name" => {
+            sqlx::query!(
+                "UPDATE company_profiles SET company_name = $2, updated_at = NOW() WHERE id = $1",
+                company_id,
+                change.new_value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        "legal_name" => {
+            sqlx::query!(
+                "UPDATE company_profiles SET legal_name = $2, updated_at = NOW() WHERE id = $1",
+                company_id,
+                change.new_value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        "tax_id" => {
+            let taken = sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM company_profiles WHERE tax_id = $1 AND id <> $2) as "exists!""#,
+                change.new_value,
+                company_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
 
-    // Check if job exists and is pending
-    let existing_job = sqlx::query!("SELECT status::text as status FROM jobs WHERE id = $1", job_id)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+            if taken {
+                return Err(AppError::ConflictError(
+                    "Another company is already registered with this RUT".to_string(),
+                ));
+            }
 
-    if existing_job.status.as_deref() != Some("pending_approval") {
-        return Err(AppError::ValidationError(
-            "Job is not pending approval".to_string(),
-        ));
+            sqlx::query!(
+                "UPDATE company_profiles SET tax_id = $2, updated_at = NOW() WHERE id = $1",
+                company_id,
+                change.new_value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        other => {
+            return Err(AppError::InternalError(format!(
+                "Unexpected profile change field {}",
+                other
+            )))
+        }
     }
 
-    // Update job status to active (MUST set both approved_at and approved_by)
-    let job = sqlx::query_as!(
-        Job,
+    let change = sqlx::query_as!(
+        PendingProfileChange,
         r#"
-        UPDATE jobs
-        SET
-            status = 'active'::job_status,
-            approved_at = NOW(),
-            approved_by = $1,
-            updated_at = NOW()
-        WHERE id = $2
-        RETURNING
-            id,
-            company_id,
-            posted_by,
-            title,
-            description,
-            responsibilities,
-            job_type as "job_type: JobType",
-            industry_id,
-            work_area_id,
-            position_level_id,
-            work_modality as "work_modality: WorkModality",
-            work_schedule,
-            region_id,
-            municipality_id,
-            is_remote_allowed,
-            education_level,
-            years_experience_min,
-            years_experience_max,
-            age_min,
-            age_max,
-            salary_min as "salary_min: rust_decimal::Decimal",
-            salary_max as "salary_max: rust_decimal::Decimal",
-            salary_currency,
-            salary_period,
-            benefits,
-            application_deadline,
-            contact_email,
-            application_url,
-            vacancies,
-            applications_count,
-            status as "status: JobStatus",
-            approved_at,
-            approved_by,
-            rejection_reason,
-            completeness_percentage,
-            is_featured,
-            views_count,
-            created_at,
-            updated_at
+        UPDATE pending_profile_changes
+        SET status = 'approved', reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = $1
+        RETURNING id, company_id, field_name, old_value, new_value,
+                  status as "status: ProfileChangeStatus",
+                  requested_by, reviewed_by, reviewed_at, rejection_reason, created_at
         "#,
-        auth_user.id,
-        job_id
+        change_id,
+        auth_user.id
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    // Log admin action
     log_admin_action(
-        &state.db,
+        &mut *tx,
         admin.id,
-        "approve_job",
-        "job",
-        job_id,
+        "approve_company_profile_change",
+        "company",
+        company_id,
         Some(json!({
-            "approval_notes": payload.approval_notes,
-            "job_title": job.title,
+            "change_id": change.id,
+            "field_name": change.field_name,
+            "old_value": change.old_value,
+            "new_value": change.new_value,
         })),
     )
     .await?;
 
-    Ok(Json(job))
+    tx.commit().await?;
+
+    Ok(Json(change))
 }
 
-/// PATCH /api/admin/jobs/{id}/reject
-/// Reject a job posting
-pub async fn reject_job(
+/// PATCH /api/admin/companies/{id}/pending-changes/{change_id}/reject
+/// Reject a pending sensitive profile change and notify the company
+pub async fn reject_company_profile_change(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(admin): Extension<Admin>,
-    Path(job_id): Path<Uuid>,
-    Json(payload): Json<RejectJobRequest>,
-) -> Result<Json<Job>, AppError> {
-    // Validate payload
+    Path((company_id, change_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<RejectProfileChangeRequest>,
+) -> Result<Json<PendingProfileChange>, AppError> {
     payload.validate()?;
 
-    // Check if job exists and is pending
-    let existing_job = sqlx::query!("SELECT status::text as status FROM jobs WHERE id = $1", job_id)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
-
-    if existing_job.status.as_deref() != Some("pending_approval") {
-        return Err(AppError::ValidationError(
-            "Job is not pending approval".to_string(),
-        ));
-    }
+    let mut tx = state.db.begin().await?;
+    fetch_pending_change(&mut *tx, company_id, change_id).await?;
 
-    // Update job status to rejected
-    let job = sqlx::query_as!(
-        Job,
+    let change = sqlx::query_as!(
+        PendingProfileChange,
         r#"
-        UPDATE jobs
-        SET
-            status = 'rejected'::job_status,
-            rejection_reason = $1,
-            approved_by = $2,
-            updated_at = NOW()
-        WHERE id = $3
-        RETURNING
-            id,
-            company_id,
-            posted_by,
-            title,
-            description,
-            responsibilities,
-            job_type as "job_type: JobType",
-            industry_id,
-            work_area_id,
-            position_level_id,
-            work_modality as "work_modality: WorkModality",
-            work_schedule,
-            region_id,
-            municipality_id,
-            is_remote_allowed,
-            education_level,
-            years_experience_min,
-            years_experience_max,
-            age_min,
-            age_max,
-            salary_min as "salary_min: rust_decimal::Decimal",
-            salary_max as "salary_max: rust_decimal::Decimal",
-            salary_currency,
-            salary_period,
-            benefits,
-            application_deadline,
-            contact_email,
-            application_url,
-            vacancies,
-            applications_count,
-            status as "status: JobStatus",
-            approved_at,
-            approved_by,
-            rejection_reason,
-            completeness_percentage,
-            is_featured,
-            views_count,
-            created_at,
-            updated_at
+        UPDATE pending_profile_changes
+        SET status = 'rejected', reviewed_by = $2, reviewed_at = NOW(), rejection_reason = $3
+        WHERE id = $1
+        RETURNING id, company_id, field_name, old_value, new_value,
+                  status as "status: ProfileChangeStatus",
+                  requested_by, reviewed_by, reviewed_at, rejection_reason, created_at
         "#,
-        payload.rejection_reason,
+        change_id,
         auth_user.id,
-        job_id
+        payload.rejection_reason
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    // Log admin action
     log_admin_action(
-        &state.db,
+        &mut *tx,
         admin.id,
-        "reject_job",
-        "job",
-        job_id,
+        "reject_company_profile_change",
+        "company",
+        company_id,
         Some(json!({
+            "change_id": change.id,
+            "field_name": change.field_name,
+            "new_value": change.new_value,
             "rejection_reason": payload.rejection_reason,
-            "job_title": job.title,
         })),
     )
     .await?;
 
-    Ok(Json(job))
-}
-
-// ============================================================================
-// AUDIT LOGGING HELPER
-// ============================================================================
+    tx.commit().await?;
 
-/// Internal helper to log admin actions to audit table
-async fn log_admin_action(
-    db: &sqlx::PgPool,
-    admin_id: Uuid,
-    action_type: &str,
-    entity_type: &str,
-    entity_id: Uuid,
-    details: Option<serde_json::Value>,
-) -> Result<(), AppError> {
-    sqlx::query!(
+    // Let the company's owners and admins know (async, don't wait)
+    let recipients = sqlx::query!(
         r#"
-        INSERT INTO admin_audit_logs (admin_id, action_type, entity_type, entity_id, details)
-        VALUES ($1, $2, $3, $4, $5)
+        SELECT u.id, u.email as "email!", u.first_name, c.company_name
+        FROM company_members m
+        JOIN users u ON u.id = m.user_id
+        JOIN company_profiles c ON c.id = m.company_id
+        WHERE m.company_id = $1 AND m.role IN ('owner', 'admin') AND m.is_active = true
         "#,
-        admin_id,
-        action_type,
-        entity_type,
-        entity_id,
-        details
+        company_id
     )
-    .execute(db)
+    .fetch_all(&state.db)
     .await?;
 
-    Ok(())
+    for recipient in recipients {
+        let field_name = change.field_name.clone();
+        let new_value = change.new_value.clone();
+        let reason = payload.rejection_reason.clone();
+        notifications::email(
+            &state,
+            recipient.id,
+            NotificationEvent::ProfileReview,
+            move |email| async move {
+                email
+                    .send_company_change_rejected_email(
+                        &recipient.email,
+                        &recipient.first_name,
+                        &recipient.company_name,
+                        &field_name,
+                        &new_value,
+                        &reason,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(Json(change))
 }
 
 // ============================================================================
-// V11: USER MANAGEMENT
+// COMPANY DELETION
 // ============================================================================
 
-/// GET /api/admin/users
-/// List users with filters and pagination
-pub async fn list_users(
+/// DELETE /api/admin/companies/{id}
+/// Wind a company down, or purge it once it has been wound down. Purging is
+/// refused during the waiting period unless `force` is set.
+pub async fn delete_company(
     State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Query(params): Query<UserFilterParams>,
-) -> Result<Json<PaginatedResponse<UserListItem>>, AppError> {
-    let limit = params.limit.unwrap_or(50).min(100);
-    let offset = params.offset.unwrap_or(0);
-
-    // Build dynamic query for filtering
-    let total: i64 = sqlx::query_scalar!(
-        r#"
-        SELECT COUNT(*)
-        FROM users
-        WHERE ($1::text IS NULL OR user_type::text = $1)
-        AND ($2::text IS NULL OR account_status::text = $2)
-        AND ($3::text IS NULL OR email ILIKE '%' || $3 || '%' OR first_name ILIKE '%' || $3 || '%' OR last_name ILIKE '%' || $3 || '%')
-        "#,
-        params.user_type,
-        params.status,
-        params.search
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Path(company_id): Path<Uuid>,
+    Query(query): Query<DeleteCompanyQuery>,
+) -> Result<Json<CompanyDeletionResponse>, AppError> {
+    let status = sqlx::query_scalar!(
+        r#"SELECT status as "status: OrganizationStatus" FROM company_profiles WHERE id = $1"#,
+        company_id
     )
-    .fetch_one(&state.db)
+    .fetch_optional(&state.db)
     .await?
-    .unwrap_or(0);
+    .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
 
-    let users = sqlx::query!(
-        r#"
-        SELECT
-            id,
-            email,
-            first_name,
-            last_name,
-            user_type as "user_type: UserType",
-            account_status as "account_status: AccountStatus",
-            email_verified_at,
-            created_at
-        FROM users
-        WHERE ($1::text IS NULL OR user_type::text = $1)
-        AND ($2::text IS NULL OR account_status::text = $2)
-        AND ($3::text IS NULL OR email ILIKE '%' || $3 || '%' OR first_name ILIKE '%' || $3 || '%' OR last_name ILIKE '%' || $3 || '%')
-        ORDER BY created_at DESC
-        LIMIT $4 OFFSET $5
-        "#,
-        params.user_type,
-        params.status,
-        params.search,
-        limit,
-        offset
-    )
-    .fetch_all(&state.db)
-    .await?;
+    let mut tx = state.db.begin().await?;
 
-    let data: Vec<UserListItem> = users
-        .into_iter()
-        .map(|u| UserListItem {
-            id: u.id,
-            email: u.email,
-            first_name: u.first_name,
-            last_name: u.last_name,
-            user_type: u.user_type,
-            account_status: u.account_status,
-            email_verified_at: u.email_verified_at,
-            created_at: u.created_at,
-        })
-        .collect();
+    if status != OrganizationStatus::Closing {
+        let wind_down = company_deletion::wind_down(&mut tx, company_id, auth_user.id).await?;
+        let response = CompanyDeletionResponse {
+            company_id,
+            status: OrganizationStatus::Closing,
+            purge_after: Some(wind_down.purge_after),
+            jobs_closed: wind_down.jobs_closed,
+            members_deactivated: wind_down.members_deactivated,
+            applicants_notified: wind_down.applicants.len() as i64,
+            jobs_archived: 0,
+        };
+
+        log_admin_action(
+            &mut *tx,
+            admin.id,
+            "wind_down_company",
+            "company",
+            company_id,
+            Some(json!({
+                "company_name": wind_down.company_name,
+                "jobs_closed": response.jobs_closed,
+                "members_deactivated": response.members_deactivated,
+            })),
+        )
+        .await?;
+        tx.commit().await?;
 
-    Ok(Json(PaginatedResponse {
-        data,
-        total,
-        limit,
-        offset,
-    }))
-}
+        company_deletion::notify_applicants(
+            &state,
+            &wind_down.company_name,
+            wind_down.applicants,
+        );
+        admin_events::publish(&state).await;
 
-/// GET /api/admin/users/{id}
-/// Get user detail
-pub async fn get_user_detail(
-    State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Path(user_id): Path<Uuid>,
-) -> Result<Json<UserDetail>, AppError> {
-    let user = sqlx::query!(
-        r#"
-        SELECT
-            u.id,
-            u.email,
-            u.first_name,
-            u.last_name,
-            u.user_type as "user_type: UserType",
-            u.account_status as "account_status: AccountStatus",
-            u.email_verified_at,
-            u.created_at,
-            u.updated_at,
-            cm.company_id as "company_id?",
-            cp.company_name as "company_name?",
-            om.omil_id as "omil_id?",
-            oo.organization_name as "omil_name?"
-        FROM users u
-        LEFT JOIN company_members cm ON cm.user_id = u.id AND cm.is_active = true
-        LEFT JOIN company_profiles cp ON cp.id = cm.company_id
-        LEFT JOIN omil_members om ON om.user_id = u.id AND om.is_active = true
-        LEFT JOIN omil_organizations oo ON oo.id = om.omil_id
-        WHERE u.id = $1
-        "#,
-        user_id
+        return Ok(Json(response));
+    }
+
+    let force = query.force.unwrap_or(false);
+    let purge = company_deletion::purge(&mut tx, company_id, auth_user.id, force).await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "purge_company",
+        "company",
+        company_id,
+        Some(json!({
+            "forced": force,
+            "jobs_archived": purge.jobs_archived,
+        })),
     )
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    .await?;
+    tx.commit().await?;
 
-    Ok(Json(UserDetail {
-        id: user.id,
-        email: user.email,
-        first_name: user.first_name,
-        last_name: user.last_name,
-        user_type: user.user_type,
-        account_status: user.account_status,
-        email_verified_at: user.email_verified_at,
-        created_at: user.created_at,
-        updated_at: user.updated_at,
-        company_id: user.company_id,
-        company_name: user.company_name,
-        omil_id: user.omil_id,
-        omil_name: user.omil_name,
+    for path in &purge.storage_paths {
+        if let Err(e) = state.storage.delete(path).await {
+            tracing::error!("Failed to delete company file {}: {}", path, e);
+        }
+    }
+
+    Ok(Json(CompanyDeletionResponse {
+        company_id,
+        status: OrganizationStatus::Deleted,
+        purge_after: None,
+        jobs_closed: 0,
+        members_deactivated: 0,
+        applicants_notified: 0,
+        jobs_archived: purge.jobs_archived,
     }))
 }
 
-/// PATCH /api/admin/users/{id}/status
-/// Update user status (suspend/activate)
-pub async fn update_user_status(
+/// POST /api/admin/companies/{id}/reopen
+/// Undo a wind-down before the company is purged
+pub async fn reopen_company(
     State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
     Extension(admin): Extension<Admin>,
-    Path(user_id): Path<Uuid>,
-    Json(payload): Json<UpdateUserStatusRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    payload.validate()?;
+    Path(company_id): Path<Uuid>,
+) -> Result<Json<ReopenCompanyResponse>, AppError> {
+    let mut tx = state.db.begin().await?;
 
-    // Update user status
-    sqlx::query!(
-        r#"
-        UPDATE users
-        SET account_status = $1, updated_at = NOW()
-        WHERE id = $2
-        "#,
-        payload.status as AccountStatus,
-        user_id
-    )
-    .execute(&state.db)
-    .await?;
+    let reopened = company_deletion::reopen(&mut tx, company_id, auth_user.id).await?;
 
-    // Log admin action
     log_admin_action(
-        &state.db,
+        &mut *tx,
         admin.id,
-        if payload.status == AccountStatus::Suspended {
-            "suspend_user"
-        } else {
-            "activate_user"
-        },
-        "user",
-        user_id,
+        "reopen_company",
+        "company",
+        company_id,
         Some(json!({
-            "new_status": format!("{:?}", payload.status),
-            "reason": payload.reason
+            "jobs_reopened": reopened.jobs_reopened,
+            "members_reactivated": reopened.members_reactivated,
         })),
     )
     .await?;
+    tx.commit().await?;
 
-    Ok(Json(json!({ "message": "User status updated successfully" })))
+    admin_events::publish(&state).await;
+
+    Ok(Json(ReopenCompanyResponse {
+        company_id,
+        status: reopened.status,
+        jobs_reopened: reopened.jobs_reopened,
+        members_reactivated: reopened.members_reactivated,
+    }))
 }
 
-/// GET /api/admin/users/{id}/impersonate
-/// Generate impersonation token for admin to act as user
-pub async fn impersonate_user(
-    State(state): State<AppState>,
-    Extension(admin): Extension<Admin>,
-    Path(user_id): Path<Uuid>,
-) -> Result<Json<AdminImpersonationResponse>, AppError> {
-    // Get user info
-    let user = sqlx::query!(
-        "SELECT id, email FROM users WHERE id = $1",
-        user_id
+// ============================================================================
+// JOB MODERATION
+// ============================================================================
+
+/// Screening findings stored for each of the given jobs
+async fn screening_findings_by_job(
+    db: &sqlx::PgPool,
+    job_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<ScreeningFinding>>, AppError> {
+    let findings = sqlx::query!(
+        "SELECT job_id, findings FROM job_screening_findings WHERE job_id = ANY($1)",
+        job_ids
     )
-    .fetch_optional(&state.db)
+    .fetch_all(db)
     .await?
-    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    .into_iter()
+    .filter_map(|row| match serde_json::from_value(row.findings) {
+        Ok(findings) => Some((row.job_id, findings)),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable screening findings for {}: {}", row.job_id, e);
+            None
+        }
+    })
+    .collect();
 
-    // Generate impersonation token (reuse OMIL impersonation token with admin as actor)
-    let (token, jti, expires_at) = create_impersonation_token(
-        user.id,
-        admin.user_id, // Admin is the impersonator
-        Uuid::nil(),   // No OMIL organization
-        &state.config,
-    )
-    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    Ok(findings)
+}
 
-    // Log admin action
-    log_admin_action(
-        &state.db,
-        admin.id,
-        "impersonate_user",
-        "user",
-        user_id,
-        Some(json!({ "token_jti": jti.to_string() })),
+/// Jobs each of the given jobs matched when its company overrode the
+/// duplicate check
+async fn duplicate_overrides_by_job(
+    db: &sqlx::PgPool,
+    job_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<Uuid>>, AppError> {
+    let duplicates = sqlx::query!(
+        r#"
+        SELECT o.job_id, array_agg(DISTINCT d) as "duplicate_ids!"
+        FROM job_duplicate_overrides o, unnest(o.duplicate_job_ids) d
+        WHERE o.job_id = ANY($1)
+        GROUP BY o.job_id
+        "#,
+        job_ids
     )
-    .await?;
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| (row.job_id, row.duplicate_ids))
+    .collect();
 
-    Ok(Json(AdminImpersonationResponse {
-        impersonation_token: token,
-        expires_at,
-        user_id: user.id,
-        user_email: user.email,
-    }))
+    Ok(duplicates)
 }
 
-// ============================================================================
-// V11: OMIL APPROVALS
-// ============================================================================
-
-/// GET /api/admin/omils/pending
-/// List OMIL organizations pending approval
-pub async fn list_pending_omils(
+/// GET /api/admin/jobs/pending
+/// List jobs pending approval with their pre-screening warnings and any
+/// duplicates the company chose to override, longest waiting first
+pub async fn list_pending_jobs(
     State(state): State<AppState>,
     Extension(_admin): Extension<Admin>,
-) -> Result<Json<Vec<OmilOrganization>>, AppError> {
-    let omils = sqlx::query_as!(
-        OmilOrganization,
+) -> Result<Json<Vec<PendingJob>>, AppError> {
+    let jobs = sqlx::query!(
+        r#"
+        SELECT
+            j.id,
+            j.title,
+            j.company_id,
+            c.company_name,
+            j.created_at,
+            j.completeness_percentage,
+            (EXTRACT(EPOCH FROM NOW() - j.submitted_at)::BIGINT / 3600) as "waiting_hours!"
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        WHERE j.status = 'pending_approval'
+        ORDER BY j.submitted_at ASC, j.id
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let job_ids: Vec<Uuid> = jobs.iter().map(|j| j.id).collect();
+    let mut findings = screening_findings_by_job(&state.db, &job_ids).await?;
+    let mut duplicates = duplicate_overrides_by_job(&state.db, &job_ids).await?;
+
+    let jobs = jobs
+        .into_iter()
+        .map(|job| PendingJob {
+            screening_findings: findings.remove(&job.id).unwrap_or_default(),
+            possible_duplicate_of: duplicates.remove(&job.id).unwrap_or_default(),
+            id: job.id,
+            title: job.title,
+            company_id: job.company_id,
+            company_name: job.company_name,
+            created_at: job.created_at,
+            completeness_percentage: job.completeness_percentage,
+            waiting_hours: job.waiting_hours,
+        })
+        .collect();
+
+    Ok(Json(jobs))
+}
+
+/// A company's full profile, for the admin views
+async fn fetch_company_profile(
+    db: &sqlx::PgPool,
+    company_id: Uuid,
+) -> Result<Option<CompanyProfile>, AppError> {
+    let profile = sqlx::query_as!(
+        CompanyProfile,
         r#"
         SELECT
             id,
-            organization_name,
-            municipality_id,
+            company_name,
+            legal_name,
+            tax_id,
+            industry_id,
+            company_size,
+            founded_year,
             region_id,
+            municipality_id,
             address,
             phone,
-            email,
             website_url,
+            linkedin_url,
+            video_url,
+            logo_url,
+            cover_image_url,
+            description,
+            mission,
+            vision,
+            culture,
+            benefits,
             status as "status: OrganizationStatus",
             approved_at,
             approved_by,
+            rejection_reason,
+            is_featured,
+            can_search_candidates,
+            completeness_percentage,
             created_at,
             updated_at
-        FROM omil_organizations
-        WHERE status = 'pending_approval'
-        ORDER BY created_at ASC
-        "#
+        FROM company_profiles
+        WHERE id = $1
+        "#,
+        company_id
     )
-    .fetch_all(&state.db)
+    .fetch_optional(db)
     .await?;
 
-    Ok(Json(omils))
+    Ok(profile)
 }
 
-/// PATCH /api/admin/omils/{id}/approve
-/// Approve an OMIL organization
-pub async fn approve_omil(
+/// GET /api/admin/jobs/{id}/review
+/// A job with its requirements, company and poster for the moderation
+/// screen. Jobs that already left the queue are returned for audit with
+/// pending_approval set to false.
+pub async fn get_job_review(
     State(state): State<AppState>,
-    Extension(auth_user): Extension<AuthUser>,
-    Extension(admin): Extension<Admin>,
-    Path(omil_id): Path<Uuid>,
-    Json(payload): Json<ApproveOmilRequest>,
-) -> Result<Json<OmilOrganization>, AppError> {
-    payload.validate()?;
-
-    // Check if OMIL exists and is pending
-    let existing = sqlx::query!(
-        "SELECT status::text as status FROM omil_organizations WHERE id = $1",
-        omil_id
+    Extension(_admin): Extension<Admin>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobReview>, AppError> {
+    let job = sqlx::query_as!(
+        Job,
+        r#"
+        SELECT
+            id, company_id, posted_by,
+            title, description, responsibilities,
+            job_type as "job_type: JobType",
+            industry_id, work_area_id, position_level_id,
+            work_modality as "work_modality: WorkModality",
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
+            region_id, municipality_id, is_remote_allowed,
+            education_level, years_experience_min, years_experience_max,
+            age_min, age_max,
+            salary_min as "salary_min: rust_decimal::Decimal",
+            salary_max as "salary_max: rust_decimal::Decimal",
+            salary_currency, salary_period, benefits,
+            application_deadline, contact_email, application_url,
+            vacancies, applications_count,
+            status as "status: JobStatus",
+            approved_at, approved_by, rejection_reason,
+            completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
+            created_at, updated_at
+        FROM jobs
+        WHERE id = $1
+        "#,
+        job_id
     )
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::NotFound("OMIL organization not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
 
-    if existing.status.as_deref() != Some("pending_approval") {
-        return Err(AppError::ValidationError(
-            "OMIL organization is not pending approval".to_string(),
-        ));
-    }
+    let required_skills = sqlx::query_as!(
+        JobReviewRequirement,
+        r#"
+        SELECT s.id, s.name, rs.minimum_proficiency as "minimum_proficiency?"
+        FROM job_required_skills rs
+        JOIN skills s ON s.id = rs.skill_id
+        WHERE rs.job_id = $1
+        ORDER BY s.name
+        "#,
+        job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
 
-    // Update status
-    let omil = sqlx::query_as!(
-        OmilOrganization,
+    let preferred_skills = sqlx::query_as!(
+        JobReviewRequirement,
         r#"
-        UPDATE omil_organizations
-        SET
-            status = 'active'::organization_status,
-            approved_at = NOW(),
-            approved_by = $1,
-            updated_at = NOW()
-        WHERE id = $2
-        RETURNING
-            id,
-            organization_name,
-            municipality_id,
-            region_id,
-            address,
-            phone,
-            email,
-            website_url,
-            status as "status: OrganizationStatus",
-            approved_at,
-            approved_by,
-            created_at,
-            updated_at
+        SELECT s.id, s.name, NULL::INT as "minimum_proficiency?"
+        FROM job_preferred_skills ps
+        JOIN skills s ON s.id = ps.skill_id
+        WHERE ps.job_id = $1
+        ORDER BY s.name
         "#,
-        auth_user.id,
-        omil_id
+        job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let required_languages = sqlx::query_as!(
+        JobReviewRequirement,
+        r#"
+        SELECT l.id, l.name, rl.minimum_proficiency as "minimum_proficiency?"
+        FROM job_required_languages rl
+        JOIN languages l ON l.id = rl.language_id
+        WHERE rl.job_id = $1
+        ORDER BY l.name
+        "#,
+        job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let disability_accommodations = sqlx::query_scalar!(
+        r#"
+        SELECT disability_category as "disability_category: DisabilityCategory"
+        FROM job_disability_accommodations
+        WHERE job_id = $1
+        ORDER BY disability_category
+        "#,
+        job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let profile = fetch_company_profile(&state.db, job.company_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
+
+    let prior_job_rejections = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM admin_audit_logs l
+        JOIN jobs j ON j.id = l.entity_id
+        WHERE l.entity_type = 'job'
+          AND l.action_type = 'reject_job'
+          AND j.company_id = $1
+        "#,
+        job.company_id
     )
     .fetch_one(&state.db)
     .await?;
 
-    // Log admin action
-    log_admin_action(
-        &state.db,
-        admin.id,
-        "approve_omil",
-        "omil",
-        omil_id,
-        Some(json!({
-            "approval_notes": payload.approval_notes,
-            "organization_name": omil.organization_name,
-        })),
+    let posted_by = sqlx::query_as!(
+        UserResponse,
+        r#"
+        SELECT
+            id,
+            email as "email!",
+            first_name,
+            last_name,
+            user_type as "user_type: UserType",
+            account_status as "account_status: AccountStatus",
+            email_verified_at IS NOT NULL as "email_verified!",
+            created_at
+        FROM users
+        WHERE id = $1
+        "#,
+        job.posted_by
     )
+    .fetch_one(&state.db)
     .await?;
 
-    Ok(Json(omil))
+    let screening_findings = screening_findings_by_job(&state.db, &[job_id])
+        .await?
+        .remove(&job_id)
+        .unwrap_or_default();
+    let possible_duplicate_of = duplicate_overrides_by_job(&state.db, &[job_id])
+        .await?
+        .remove(&job_id)
+        .unwrap_or_default();
+    let notes = admin_notes::notes_for(&state.db, AdminNoteEntity::Job, job_id).await?;
+
+    Ok(Json(JobReview {
+        pending_approval: job.status == JobStatus::PendingApproval,
+        job,
+        required_skills,
+        preferred_skills,
+        required_languages,
+        disability_accommodations,
+        company: JobReviewCompany {
+            profile,
+            prior_job_rejections,
+        },
+        posted_by,
+        screening_findings,
+        possible_duplicate_of,
+        notes,
+    }))
 }
 
-/// PATCH /api/admin/omils/{id}/reject
-/// Reject an OMIL organization
-pub async fn reject_omil(
+/// PATCH /api/admin/jobs/{id}/approve
+/// Approve a job posting
+pub async fn approve_job(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(admin): Extension<Admin>,
-    Path(omil_id): Path<Uuid>,
-    Json(payload): Json<RejectOmilRequest>,
-) -> Result<Json<OmilOrganization>, AppError> {
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<ApproveJobRequest>,
+) -> Result<Json<Job>, AppError> {
+    // Validate payload
     payload.validate()?;
 
-    // Check if OMIL exists and is pending
-    let existing = sqlx::query!(
-        "SELECT status::text as status FROM omil_organizations WHERE id = $1",
-        omil_id
-    )
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or_else(|| AppError::NotFound("OMIL organization not found".to_string()))?;
+    // Check if job exists and is pending
+    let existing_job = sqlx::query!("SELECT status::text as status FROM jobs WHERE id = $1", job_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
 
-    if existing.status.as_deref() != Some("pending_approval") {
+    if existing_job.status.as_deref() != Some("pending_approval") {
         return Err(AppError::ValidationError(
-            "OMIL organization is not pending approval".to_string(),
+            "Job is not pending approval".to_string(),
         ));
     }
 
-    // Update status - note: omil_organizations doesn't have rejection_reason column
-    // We'll add the reason in the audit log
-    let omil = sqlx::query_as!(
-        OmilOrganization,
+    let mut tx = state.db.begin().await?;
+
+    // Update job status to active (MUST set both approved_at and approved_by).
+    // Jobs with a future publish_at wait as scheduled until the publication task runs.
+    let job = sqlx::query_as!(
+        Job,
         r#"
-        UPDATE omil_organizations
+        UPDATE jobs
         SET
-            status = 'rejected'::organization_status,
+            status = CASE
+                WHEN publish_at > NOW() THEN 'scheduled'::job_status
+                ELSE 'active'::job_status
+            END,
+            published_at = CASE WHEN publish_at > NOW() THEN NULL ELSE NOW() END,
+            approved_at = NOW(),
             approved_by = $1,
             updated_at = NOW()
         WHERE id = $2
         RETURNING
             id,
-            organization_name,
-            municipality_id,
+            company_id,
+            posted_by,
+            title,
+            description,
+            responsibilities,
+            job_type as "job_type: JobType",
+            industry_id,
+            work_area_id,
+            position_level_id,
+            work_modality as "work_modality: WorkModality",
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
             region_id,
-            address,
-            phone,
-            email,
-            website_url,
-            status as "status: OrganizationStatus",
+            municipality_id,
+            is_remote_allowed,
+            education_level,
+            years_experience_min,
+            years_experience_max,
+            age_min,
+            age_max,
+            salary_min as "salary_min: rust_decimal::Decimal",
+            salary_max as "salary_max: rust_decimal::Decimal",
+            salary_currency,
+            salary_period,
+            benefits,
+            application_deadline,
+            contact_email,
+            application_url,
+            vacancies,
+            applications_count,
+            status as "status: JobStatus",
             approved_at,
             approved_by,
+            rejection_reason,
+            completeness_percentage,
+            is_featured,
+            views_count,
+            publish_at,
+            published_at,
             created_at,
             updated_at
         "#,
         auth_user.id,
-        omil_id
+        job_id
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    // Log admin action with rejection reason
+    // Log admin action
     log_admin_action(
-        &state.db,
+        &mut *tx,
         admin.id,
-        "reject_omil",
-        "omil",
-        omil_id,
+        "approve_job",
+        "job",
+        job_id,
         Some(json!({
-            "rejection_reason": payload.rejection_reason,
-            "organization_name": omil.organization_name,
+            "approval_notes": payload.approval_notes,
+            "job_title": job.title,
         })),
     )
     .await?;
 
-    Ok(Json(omil))
-}
-
-// ============================================================================
-// V11: AUDIT LOG VIEWER
-// ============================================================================
-
-/// GET /api/admin/audit-logs
-/// View audit logs with filters
-pub async fn list_audit_logs(
-    State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Query(params): Query<AuditLogFilterParams>,
-) -> Result<Json<PaginatedResponse<AdminAuditLog>>, AppError> {
-    let limit = params.limit.unwrap_or(50).min(100);
-    let offset = params.offset.unwrap_or(0);
-
-    let total: i64 = sqlx::query_scalar!(
-        r#"
-        SELECT COUNT(*)
-        FROM admin_audit_logs
-        WHERE ($1::uuid IS NULL OR admin_id = $1)
-        AND ($2::text IS NULL OR action_type = $2)
-        AND ($3::text IS NULL OR entity_type = $3)
-        AND ($4::uuid IS NULL OR entity_id = $4)
-        AND ($5::timestamptz IS NULL OR created_at >= $5)
-        AND ($6::timestamptz IS NULL OR created_at <= $6)
-        "#,
-        params.admin_id,
-        params.action_type,
-        params.entity_type,
-        params.entity_id,
-        params.from_date,
-        params.to_date
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
-
-    let logs = sqlx::query_as!(
-        AdminAuditLog,
-        r#"
-        SELECT
-            id,
-            admin_id,
-            action_type,
-            entity_type,
-            entity_id,
-            details,
-            ip_address,
-            created_at
-        FROM admin_audit_logs
-        WHERE ($1::uuid IS NULL OR admin_id = $1)
-        AND ($2::text IS NULL OR action_type = $2)
-        AND ($3::text IS NULL OR entity_type = $3)
-        AND ($4::uuid IS NULL OR entity_id = $4)
-        AND ($5::timestamptz IS NULL OR created_at >= $5)
-        AND ($6::timestamptz IS NULL OR created_at <= $6)
-        ORDER BY created_at DESC
-        LIMIT $7 OFFSET $8
-        "#,
-        params.admin_id,
-        params.action_type,
-        params.entity_type,
-        params.entity_id,
-        params.from_date,
-        params.to_date,
-        limit,
-        offset
+    events::emit(
+        &mut tx,
+        AggregateType::Job,
+        job_id,
+        "approved",
+        Some(auth_user.id),
+        json!({ "status": job.status, "publish_at": job.publish_at }),
     )
-    .fetch_all(&state.db)
     .await?;
 
-    Ok(Json(PaginatedResponse {
-        data: logs,
-        total,
-        limit,
-        offset,
-    }))
-}
-
-// ============================================================================
-// V11: SYSTEM SETTINGS
-// ============================================================================
+    tx.commit().await?;
 
-/// GET /api/admin/settings
-/// Get all system settings
-pub async fn get_settings(
-    State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-) -> Result<Json<Vec<SystemSetting>>, AppError> {
-    let settings = sqlx::query!(
-        "SELECT key, value, description, updated_at FROM system_settings ORDER BY key"
-    )
-    .fetch_all(&state.db)
-    .await?;
+    // Scheduled jobs are metered when the publication task activates them
+    if job.status == JobStatus::Active {
+        metering::record(
+            &state.db,
+            job.company_id,
+            UsageEventType::JobPublished,
+            1,
+            json!({ "job_id": job.id }),
+        )
+        .await;
+    }
 
-    let result: Vec<SystemSetting> = settings
-        .into_iter()
-        .map(|s| SystemSetting {
-            key: s.key,
-            value: s.value,
-            description: s.description,
-            updated_at: s.updated_at,
-        })
-        .collect();
+    admin_events::publish(&state).await;
 
-    Ok(Json(result))
+    Ok(Json(job))
 }
 
-/// PUT /api/admin/settings
-/// Update system settings
-pub async fn update_settings(
+/// PATCH /api/admin/jobs/{id}/reject
+/// Reject a job posting
+pub async fn reject_job(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(admin): Extension<Admin>,
-    Json(payload): Json<UpdateSettingsRequest>,
-) -> Result<Json<Vec<SystemSetting>>, AppError> {
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<RejectJobRequest>,
+) -> Result<Json<Job>, AppError> {
+    // Validate payload
     payload.validate()?;
 
-    for setting in &payload.settings {
-        sqlx::query!(
-            r#"
-            UPDATE system_settings
-            SET value = $1, updated_by = $2, updated_at = NOW()
-            WHERE key = $3
-            "#,
-            setting.value,
-            auth_user.id,
-            setting.key
-        )
-        .execute(&state.db)
-        .await?;
+    // Check if job exists and is pending
+    let existing_job = sqlx::query!("SELECT status::text as status FROM jobs WHERE id = $1", job_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    if existing_job.status.as_deref() != Some("pending_approval") {
+        return Err(AppError::ValidationError(
+            "Job is not pending approval".to_string(),
+        ));
     }
 
+    // Update job status to rejected
+    let job = sqlx::query_as!(
+        Job,
+        r#"
+        UPDATE jobs
+        SET
+            status = 'rejected'::job_status,
+            rejection_reason = $1,
+            approved_by = $2,
+            updated_at = NOW()
+        WHERE id = $3
+        RETURNING
+            id,
+            company_id,
+            posted_by,
+            title,
+            description,
+            responsibilities,
+            job_type as "job_type: JobType",
+            industry_id,
+            work_area_id,
+            position_level_id,
+            work_modality as "work_modality: WorkModality",
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
+            region_id,
+            municipality_id,
+            is_remote_allowed,
+            education_level,
+            years_experience_min,
+            years_experience_max,
+            age_min,
+            age_max,
+            salary_min as "salary_min: rust_decimal::Decimal",
+            salary_max as "salary_max: rust_decimal::Decimal",
+            salary_currency,
+            salary_period,
+            benefits,
+            application_deadline,
+            contact_email,
+            application_url,
+            vacancies,
+            applications_count,
+            status as "status: JobStatus",
+            approved_at,
+            approved_by,
+            rejection_reason,
+            completeness_percentage,
+            is_featured,
+            views_count,
+            publish_at,
+            published_at,
+            created_at,
+            updated_at
+        "#,
+        payload.rejection_reason,
+        auth_user.id,
+        job_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
     // Log admin action
     log_admin_action(
         &state.db,
         admin.id,
-        "update_settings",
-        "settings",
-        Uuid::nil(),
+        "reject_job",
+        "job",
+        job_id,
         Some(json!({
-            "updated_keys": payload.settings.iter().map(|s| &s.key).collect::<Vec<_>>()
+            "rejection_reason": payload.rejection_reason,
+            "job_title": job.title,
         })),
     )
     .await?;
 
-    // Return updated settings
-    get_settings(State(state), Extension(admin)).await
-}
+    admin_events::publish(&state).await;
 
-// ============================================================================
-// V12: REPORTING
-// ============================================================================
+    Ok(Json(job))
+}
 
-/// GET /api/admin/reports/users
-/// User registration trends report
-pub async fn report_users(
+/// POST /api/admin/jobs/{id}/transfer
+/// Move a job and its applicants to another company, e.g. when it was posted
+/// under the wrong legal entity
+pub async fn transfer_job(
     State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Query(params): Query<ReportDateRangeParams>,
-) -> Result<Json<UserTrendsReport>, AppError> {
-    let from_date = params.from_date.unwrap_or_else(|| {
-        Utc::now() - chrono::Duration::days(30)
-    });
-    let to_date = params.to_date.unwrap_or_else(Utc::now);
+    Extension(admin): Extension<Admin>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<TransferJobRequest>,
+) -> Result<Json<JobTransferResponse>, AppError> {
+    let mut tx = state.db.begin().await?;
 
-    let total_users: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM users")
-        .fetch_one(&state.db)
-        .await?
-        .unwrap_or(0);
+    let transfer = job_transfer::transfer(&mut tx, job_id, payload.company_id).await?;
 
-    let new_users_period: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM users WHERE created_at >= $1 AND created_at <= $2",
-        from_date,
-        to_date
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "transfer_job",
+        "job",
+        job_id,
+        Some(transfer.audit_details.clone()),
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    // Users by type
-    let by_type = sqlx::query!(
+    tx.commit().await?;
+
+    let response = JobTransferResponse {
+        job_id,
+        from_company_id: transfer.from_company_id,
+        to_company_id: payload.company_id,
+        applications_moved: transfer.applications_moved,
+        applicants_notified: transfer.applicants.len() as i64,
+    };
+
+    // Applicants agreed to share their profile with the old company, so they
+    // are told who holds it now
+    for applicant in transfer.applicants {
+        let job_title = transfer.job_title.clone();
+        let from_company_name = transfer.from_company_name.clone();
+        let to_company_name = transfer.to_company_name.clone();
+        notifications::email(
+            &state,
+            applicant.user_id,
+            NotificationEvent::ApplicationStatus,
+            move |email| async move {
+                email
+                    .send_job_transferred_email(
+                        &applicant.email,
+                        &applicant.first_name,
+                        &job_title,
+                        &from_company_name,
+                        &to_company_name,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// AUDIT LOGGING HELPER
+// ============================================================================
+
+/// Internal helper to log admin actions to audit table
+async fn log_admin_action(
+    db: impl sqlx::PgExecutor<'_>,
+    admin_id: Uuid,
+    action_type: &str,
+    entity_type: &str,
+    entity_id: Uuid,
+    details: Option<serde_json::Value>,
+) -> Result<(), AppError> {
+    sqlx::query!(
         r#"
-        SELECT user_type::text as "user_type!", COUNT(*) as "count!"
-        FROM users
-        GROUP BY user_type
-        "#
+        INSERT INTO admin_audit_logs (admin_id, action_type, entity_type, entity_id, details)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        admin_id,
+        action_type,
+        entity_type,
+        entity_id,
+        details
     )
-    .fetch_all(&state.db)
-    .await?
-    .into_iter()
-    .map(|r| UserTypeCount {
-        user_type: r.user_type,
-        count: r.count,
-    })
-    .collect();
+    .execute(db)
+    .await?;
 
-    // Trend data (daily)
-    let trend = sqlx::query!(
+    Ok(())
+}
+
+// ============================================================================
+// V13: MODERATION RULES
+// ============================================================================
+
+fn check_rule_pattern(rule_type: ModerationRuleType, pattern: &str) -> Result<(), AppError> {
+    content_screening::compile_rule(rule_type, pattern)
+        .map(|_| ())
+        .map_err(|e| AppError::ValidationError(format!("Invalid pattern: {}", e)))
+}
+
+fn map_rule_conflict(e: sqlx::Error) -> AppError {
+    if e.as_database_error().is_some_and(|d| d.is_unique_violation()) {
+        AppError::ConflictError("A rule with this pattern already exists".to_string())
+    } else {
+        AppError::DatabaseError(e)
+    }
+}
+
+/// GET /api/admin/moderation-rules
+/// List content screening rules
+pub async fn list_moderation_rules(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<ModerationRule>>, AppError> {
+    let rules = sqlx::query_as!(
+        ModerationRule,
         r#"
-        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-        FROM users
-        WHERE created_at >= $1 AND created_at <= $2
-        GROUP BY DATE(created_at)
-        ORDER BY DATE(created_at)
-        "#,
-        from_date,
-        to_date
+        SELECT
+            id,
+            rule_type as "rule_type: ModerationRuleType",
+            pattern,
+            severity as "severity: ModerationSeverity",
+            description,
+            is_active,
+            created_by,
+            created_at,
+            updated_at
+        FROM moderation_rules
+        ORDER BY created_at ASC
+        "#
     )
     .fetch_all(&state.db)
-    .await?
-    .into_iter()
-    .map(|r| TrendDataPoint {
-        date: r.date,
-        count: r.count,
-    })
-    .collect();
+    .await?;
 
-    Ok(Json(UserTrendsReport {
-        total_users,
-        new_users_period,
-        by_type,
-        trend,
-    }))
+    Ok(Json(rules))
 }
 
-/// GET /api/admin/reports/companies
-/// Company statistics report
-pub async fn report_companies(
+/// POST /api/admin/moderation-rules
+/// Create a banned phrase or regex rule
+pub async fn create_moderation_rule(
     State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Query(params): Query<ReportDateRangeParams>,
-) -> Result<Json<CompanyTrendsReport>, AppError> {
-    let from_date = params.from_date.unwrap_or_else(|| {
-        Utc::now() - chrono::Duration::days(30)
-    });
-    let to_date = params.to_date.unwrap_or_else(Utc::now);
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<CreateModerationRuleRequest>,
+) -> Result<Json<ModerationRule>, AppError> {
+    payload.validate()?;
+    check_rule_pattern(payload.rule_type, &payload.pattern)?;
 
-    let total_companies: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM company_profiles")
-        .fetch_one(&state.db)
-        .await?
-        .unwrap_or(0);
+    let mut tx = state.db.begin().await?;
 
-    let active_companies: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM company_profiles WHERE status = 'active'"
+    let rule = sqlx::query_as!(
+        ModerationRule,
+        r#"
+        INSERT INTO moderation_rules
+            (rule_type, pattern, severity, description, is_active, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id,
+            rule_type as "rule_type: ModerationRuleType",
+            pattern,
+            severity as "severity: ModerationSeverity",
+            description,
+            is_active,
+            created_by,
+            created_at,
+            updated_at
+        "#,
+        payload.rule_type as ModerationRuleType,
+        payload.pattern,
+        payload.severity as ModerationSeverity,
+        payload.description,
+        payload.is_active.unwrap_or(true),
+        auth_user.id,
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(map_rule_conflict)?;
 
-    let pending_companies: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM company_profiles WHERE status = 'pending_approval'"
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "create_moderation_rule",
+        "moderation_rule",
+        rule.id,
+        Some(json!({ "pattern": rule.pattern, "severity": rule.severity })),
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    let new_companies_period: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM company_profiles WHERE created_at >= $1 AND created_at <= $2",
-        from_date,
-        to_date
+    tx.commit().await?;
+
+    Ok(Json(rule))
+}
+
+/// PUT /api/admin/moderation-rules/{id}
+/// Update a content screening rule
+pub async fn update_moderation_rule(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(rule_id): Path<Uuid>,
+    Json(payload): Json<UpdateModerationRuleRequest>,
+) -> Result<Json<ModerationRule>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let rule_type = sqlx::query_scalar!(
+        r#"
+        SELECT rule_type as "rule_type: ModerationRuleType"
+        FROM moderation_rules
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        rule_id
     )
-    .fetch_one(&state.db)
+    .fetch_optional(&mut *tx)
     .await?
-    .unwrap_or(0);
+    .ok_or_else(|| AppError::NotFound("Moderation rule not found".to_string()))?;
 
-    let trend = sqlx::query!(
+    if let Some(pattern) = &payload.pattern {
+        check_rule_pattern(rule_type, pattern)?;
+    }
+
+    let rule = sqlx::query_as!(
+        ModerationRule,
         r#"
-        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-        FROM company_profiles
-        WHERE created_at >= $1 AND created_at <= $2
-        GROUP BY DATE(created_at)
-        ORDER BY DATE(created_at)
+        UPDATE moderation_rules
+        SET
+            pattern = COALESCE($2, pattern),
+            severity = COALESCE($3, severity),
+            description = COALESCE($4, description),
+            is_active = COALESCE($5, is_active)
+        WHERE id = $1
+        RETURNING
+            id,
+            rule_type as "rule_type: ModerationRuleType",
+            pattern,
+            severity as "severity: ModerationSeverity",
+            description,
+            is_active,
+            created_by,
+            created_at,
+            updated_at
         "#,
-        from_date,
-        to_date
+        rule_id,
+        payload.pattern,
+        payload.severity as Option<ModerationSeverity>,
+        payload.description,
+        payload.is_active,
     )
-    .fetch_all(&state.db)
-    .await?
-    .into_iter()
-    .map(|r| TrendDataPoint {
-        date: r.date,
-        count: r.count,
-    })
-    .collect();
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(map_rule_conflict)?;
 
-    Ok(Json(CompanyTrendsReport {
-        total_companies,
-        active_companies,
-        pending_companies,
-        new_companies_period,
-        trend,
-    }))
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "update_moderation_rule",
+        "moderation_rule",
+        rule.id,
+        Some(json!({
+            "pattern": rule.pattern,
+            "severity": rule.severity,
+            "is_active": rule.is_active,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(rule))
 }
 
-/// GET /api/admin/reports/jobs
-/// Job posting trends report
-pub async fn report_jobs(
+/// DELETE /api/admin/moderation-rules/{id}
+/// Delete a content screening rule
+pub async fn delete_moderation_rule(
     State(state): State<AppState>,
-    Extension(_admin): Extension<Admin>,
-    Query(params): Query<ReportDateRangeParams>,
-) -> Result<Json<JobTrendsReport>, AppError> {
-    let from_date = params.from_date.unwrap_or_else(|| {
-        Utc::now() - chrono::Duration::days(30)
-    });
-    let to_date = params.to_date.unwrap_or_else(Utc::now);
-
-    let total_jobs: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM jobs")
-        .fetch_one(&state.db)
-        .await?
-        .unwrap_or(0);
+    Extension(admin): Extension<Admin>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let mut tx = state.db.begin().await?;
 
-    let active_jobs: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM jobs WHERE status = 'active'"
+    let pattern = sqlx::query_scalar!(
+        "DELETE FROM moderation_rules WHERE id = $1 RETURNING pattern",
+        rule_id
     )
-    .fetch_one(&state.db)
+    .fetch_optional(&mut *tx)
     .await?
-    .unwrap_or(0);
+    .ok_or_else(|| AppError::NotFound("Moderation rule not found".to_string()))?;
 
-    let pending_jobs: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM jobs WHERE status = 'pending_approval'"
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "delete_moderation_rule",
+        "moderation_rule",
+        rule_id,
+        Some(json!({ "pattern": pattern })),
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    let new_jobs_period: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM jobs WHERE created_at >= $1 AND created_at <= $2",
-        from_date,
-        to_date
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    tx.commit().await?;
 
-    let trend = sqlx::query!(
-        r#"
-        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-        FROM jobs
-        WHERE created_at >= $1 AND created_at <= $2
-        GROUP BY DATE(created_at)
-        ORDER BY DATE(created_at)
+    Ok(Json(json!({ "message": "Moderation rule deleted successfully" })))
+}
+
+// ============================================================================
+// FOLLOWUP TYPE REFERENCE DATA
+// ============================================================================
+
+/// GET /api/admin/reference/followup-types
+/// List followup types, inactive ones included
+pub async fn list_followup_types(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<FollowupType>>, AppError> {
+    let types = sqlx::query_as!(
+        FollowupType,
+        r#"
+        SELECT code, label_es, is_active, counts_as_intervention, created_at, updated_at
+        FROM followup_types
+        ORDER BY is_active DESC, label_es
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(types))
+}
+
+/// POST /api/admin/reference/followup-types
+/// Add a followup type
+pub async fn create_followup_type(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<CreateFollowupTypeRequest>,
+) -> Result<Json<FollowupType>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let followup_type = sqlx::query_as!(
+        FollowupType,
+        r#"
+        INSERT INTO followup_types (code, label_es, is_active, counts_as_intervention)
+        VALUES ($1, $2, $3, $4)
+        RETURNING code, label_es, is_active, counts_as_intervention, created_at, updated_at
         "#,
-        from_date,
-        to_date
+        payload.code,
+        payload.label_es.trim(),
+        payload.is_active.unwrap_or(true),
+        payload.counts_as_intervention.unwrap_or(false),
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        if e.as_database_error().is_some_and(|d| d.is_unique_violation()) {
+            AppError::ConflictError("A followup type with this code already exists".to_string())
+        } else {
+            AppError::DatabaseError(e)
+        }
+    })?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "create_followup_type",
+        "followup_type",
+        Uuid::nil(),
+        Some(json!({
+            "code": followup_type.code,
+            "label_es": followup_type.label_es,
+            "counts_as_intervention": followup_type.counts_as_intervention,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(followup_type))
+}
+
+/// PUT /api/admin/reference/followup-types/{code}
+/// Rename, retire or reclassify a followup type
+pub async fn update_followup_type(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(code): Path<String>,
+    Json(payload): Json<UpdateFollowupTypeRequest>,
+) -> Result<Json<FollowupType>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let followup_type = sqlx::query_as!(
+        FollowupType,
+        r#"
+        UPDATE followup_types
+        SET
+            label_es = COALESCE($2, label_es),
+            is_active = COALESCE($3, is_active),
+            counts_as_intervention = COALESCE($4, counts_as_intervention)
+        WHERE code = $1
+        RETURNING code, label_es, is_active, counts_as_intervention, created_at, updated_at
+        "#,
+        code,
+        payload.label_es.as_deref().map(str::trim),
+        payload.is_active,
+        payload.counts_as_intervention,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Followup type not found".to_string()))?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "update_followup_type",
+        "followup_type",
+        Uuid::nil(),
+        Some(json!({
+            "code": followup_type.code,
+            "label_es": followup_type.label_es,
+            "is_active": followup_type.is_active,
+            "counts_as_intervention": followup_type.counts_as_intervention,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(followup_type))
+}
+
+/// DELETE /api/admin/reference/followup-types/{code}
+/// Delete a followup type no followup uses; used ones can only be retired
+pub async fn delete_followup_type(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(code): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if SYSTEM_FOLLOWUP_TYPES.contains(&code.as_str()) {
+        return Err(AppError::ValidationError(
+            "This followup type is recorded by the system and can't be deleted".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let deleted = sqlx::query!("DELETE FROM followup_types WHERE code = $1", code)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if e.as_database_error().is_some_and(|d| d.is_foreign_key_violation()) {
+                AppError::ConflictError(
+                    "Followups use this type; deactivate it instead".to_string(),
+                )
+            } else {
+                AppError::DatabaseError(e)
+            }
+        })?
+        .rows_affected();
+
+    if deleted == 0 {
+        return Err(AppError::NotFound("Followup type not found".to_string()));
+    }
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "delete_followup_type",
+        "followup_type",
+        Uuid::nil(),
+        Some(json!({ "code": code })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(json!({ "message": "Followup type deleted successfully" })))
+}
+
+// ============================================================================
+// V11: USER MANAGEMENT
+// ============================================================================
+
+/// GET /api/admin/users
+/// List users with filters and pagination
+pub async fn list_users(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<UserFilterParams>,
+) -> Result<Json<PaginatedResponse<UserListItem>>, AppError> {
+    let limit = params.limit.unwrap_or(50).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    // Build dynamic query for filtering
+    let total: i64 = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        WHERE ($1::text IS NULL OR user_type::text = $1)
+        AND ($2::text IS NULL OR account_status::text = $2)
+        AND ($3::text IS NULL OR email ILIKE '%' || $3 || '%' OR first_name ILIKE '%' || $3 || '%' OR last_name ILIKE '%' || $3 || '%')
+        "#,
+        params.user_type,
+        params.status,
+        params.search
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let users = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            email,
+            first_name,
+            last_name,
+            user_type as "user_type: UserType",
+            account_status as "account_status: AccountStatus",
+            email_verified_at,
+            created_at
+        FROM users
+        WHERE ($1::text IS NULL OR user_type::text = $1)
+        AND ($2::text IS NULL OR account_status::text = $2)
+        AND ($3::text IS NULL OR email ILIKE '%' || $3 || '%' OR first_name ILIKE '%' || $3 || '%' OR last_name ILIKE '%' || $3 || '%')
+        ORDER BY created_at DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        params.user_type,
+        params.status,
+        params.search,
+        limit,
+        offset
     )
     .fetch_all(&state.db)
+    .await?;
+
+    let data: Vec<UserListItem> = users
+        .into_iter()
+        .map(|u| UserListItem {
+            id: u.id,
+            email: u.email,
+            first_name: u.first_name,
+            last_name: u.last_name,
+            user_type: u.user_type,
+            account_status: u.account_status,
+            email_verified_at: u.email_verified_at,
+            created_at: u.created_at,
+        })
+        .collect();
+
+    Ok(Json(PaginatedResponse {
+        data,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// GET /api/admin/users/{id}
+/// Get user detail
+pub async fn get_user_detail(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserDetail>, AppError> {
+    let user = sqlx::query!(
+        r#"
+        SELECT
+            u.id,
+            u.email,
+            u.first_name,
+            u.last_name,
+            u.user_type as "user_type: UserType",
+            u.account_status as "account_status: AccountStatus",
+            u.email_verified_at,
+            u.created_at,
+            u.updated_at,
+            cm.company_id as "company_id?",
+            cp.company_name as "company_name?",
+            om.omil_id as "omil_id?",
+            oo.organization_name as "omil_name?"
+        FROM users u
+        LEFT JOIN company_members cm ON cm.user_id = u.id AND cm.is_active = true
+        LEFT JOIN company_profiles cp ON cp.id = cm.company_id
+        LEFT JOIN omil_members om ON om.user_id = u.id AND om.is_active = true
+        LEFT JOIN omil_organizations oo ON oo.id = om.omil_id
+        WHERE u.id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(&state.db)
     .await?
-    .into_iter()
-    .map(|r| TrendDataPoint {
-        date: r.date,
-        count: r.count,
-    })
-    .collect();
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let notes = admin_notes::notes_for(&state.db, AdminNoteEntity::User, user_id).await?;
+
+    Ok(Json(UserDetail {
+        id: user.id,
+        email: user.email,
+        first_name: user.first_name,
+        last_name: user.last_name,
+        user_type: user.user_type,
+        account_status: user.account_status,
+        email_verified_at: user.email_verified_at,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+        company_id: user.company_id,
+        company_name: user.company_name,
+        omil_id: user.omil_id,
+        omil_name: user.omil_name,
+        notes,
+    }))
+}
+
+/// PATCH /api/admin/users/{id}/status
+/// Update user status (suspend/activate)
+pub async fn update_user_status(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<UpdateUserStatusRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT account_status as "account_status: AccountStatus",
+               user_type as "user_type: UserType"
+        FROM users WHERE id = $1
+        FOR UPDATE
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    // Update user status; leaving the active state revokes every issued token
+    let revoke_tokens = payload.status != AccountStatus::Active;
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET account_status = $1,
+            token_version = token_version + CASE WHEN $3 THEN 1 ELSE 0 END,
+            updated_at = NOW()
+        WHERE id = $2
+        "#,
+        payload.status as AccountStatus,
+        user_id,
+        revoke_tokens
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if revoke_tokens {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Suspension pauses what the user owns; reactivation restores it
+    let cascade = match (user.account_status, payload.status) {
+        (previous, AccountStatus::Suspended) if previous != AccountStatus::Suspended => {
+            Some(suspension::suspend(&mut tx, user_id, user.user_type).await?)
+        }
+        (AccountStatus::Suspended, AccountStatus::Active) => {
+            Some(suspension::reactivate(&mut tx, user_id).await?)
+        }
+        _ => None,
+    };
+
+    if payload.status == AccountStatus::Suspended && user.account_status != AccountStatus::Suspended
+    {
+        events::emit(
+            &mut tx,
+            AggregateType::User,
+            user_id,
+            "suspended",
+            Some(admin.user_id),
+            json!({ "reason": payload.reason, "cascade": cascade }),
+        )
+        .await?;
+    }
+
+    // Log admin action
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        if payload.status == AccountStatus::Suspended {
+            "suspend_user"
+        } else {
+            "activate_user"
+        },
+        "user",
+        user_id,
+        Some(json!({
+            "new_status": format!("{:?}", payload.status),
+            "reason": payload.reason,
+            "cascade": cascade
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if revoke_tokens {
+        forget_token_version(&mut state.redis.clone(), user_id).await;
+    }
+
+    Ok(Json(json!({ "message": "User status updated successfully" })))
+}
+
+/// GET /api/admin/users/{id}/impersonate
+/// Generate impersonation token for admin to act as user
+pub async fn impersonate_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<AdminImpersonationResponse>, AppError> {
+    // Get user info
+    let user = sqlx::query!(
+        "SELECT id, email FROM users WHERE id = $1",
+        user_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    // Generate impersonation token (reuse OMIL impersonation token with admin as actor)
+    let (token, jti, expires_at) = create_impersonation_token(
+        user.id,
+        admin.user_id, // Admin is the impersonator
+        Uuid::nil(),   // No OMIL organization
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+
+    // Log admin action
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "impersonate_user",
+        "user",
+        user_id,
+        Some(json!({ "token_jti": jti.to_string() })),
+    )
+    .await?;
+
+    Ok(Json(AdminImpersonationResponse {
+        impersonation_token: token,
+        expires_at,
+        user_id: user.id,
+        user_email: user.email,
+    }))
+}
+
+/// User whose credentials an admin is about to reset
+struct CredentialTarget {
+    email: String,
+    first_name: String,
+    account_status: AccountStatus,
+    email_verified_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Lock a user for a credential change. Only super admins may change
+/// another admin's credentials.
+async fn lock_credential_target(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    admin: &Admin,
+    user_id: Uuid,
+) -> Result<CredentialTarget, AppError> {
+    let user = sqlx::query!(
+        r#"
+        SELECT email, first_name,
+               user_type as "user_type: UserType",
+               account_status as "account_status: AccountStatus",
+               email_verified_at
+        FROM users
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if user.user_type == UserType::Admin && admin.admin_role != AdminRole::SuperAdmin {
+        return Err(AppError::ForbiddenError(
+            "Only super admins can reset another admin's credentials".to_string(),
+        ));
+    }
+
+    // Seekers managed offline have no credentials until they claim the account
+    let Some(email) = user.email else {
+        return Err(AppError::ValidationError(
+            "Account is managed offline by an OMIL and has no credentials".to_string(),
+        ));
+    };
+
+    Ok(CredentialTarget {
+        email,
+        first_name: user.first_name,
+        account_status: user.account_status,
+        email_verified_at: user.email_verified_at,
+    })
+}
+
+fn token_delivery(payload: &IssueUserTokenRequest) -> TokenDelivery {
+    if payload.deliver.unwrap_or(true) {
+        TokenDelivery::Email
+    } else {
+        TokenDelivery::OutOfBand
+    }
+}
+
+/// POST /api/admin/users/{id}/force-password-reset
+/// Lock a possibly compromised account out until its password is reset.
+/// The current password stops working, every session is revoked and a reset
+/// token is either emailed or returned once for out-of-band delivery.
+pub async fn force_password_reset(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<IssueUserTokenRequest>,
+) -> Result<Json<IssuedUserToken>, AppError> {
+    let delivery = token_delivery(&payload);
+
+    // Nobody knows this password, so the old one stops working right away
+    let password_hash = hash_password(&create_refresh_token())
+        .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
+
+    let mut tx = state.db.begin().await?;
+    let user = lock_credential_target(&mut tx, &admin, user_id).await?;
+
+    if matches!(
+        user.account_status,
+        AccountStatus::Suspended | AccountStatus::Deactivated
+    ) {
+        return Err(AppError::ValidationError(
+            "Suspended or deactivated accounts cannot be reset".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET password_hash = $1,
+            account_status = $2,
+            token_version = token_version + 1,
+            updated_at = NOW()
+        WHERE id = $3
+        "#,
+        password_hash,
+        AccountStatus::PasswordResetRequired as AccountStatus,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let (token, expires_at) = create_password_reset_token_with(
+        &mut tx,
+        user_id,
+        Duration::hours(ADMIN_PASSWORD_RESET_HOURS),
+    )
+    .await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "force_password_reset",
+        "user",
+        user_id,
+        Some(json!({ "delivery": delivery })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    forget_token_version(&mut state.redis.clone(), user_id).await;
+
+    let token = match delivery {
+        TokenDelivery::Email => {
+            let email_service = state.email.clone();
+            tokio::spawn(async move {
+                if let Err(e) = email_service
+                    .send_password_reset_email(&user.email, &user.first_name, &token)
+                    .await
+                {
+                    tracing::error!("Failed to send password reset email: {}", e);
+                }
+            });
+            None
+        }
+        TokenDelivery::OutOfBand => Some(token),
+    };
+
+    Ok(Json(IssuedUserToken {
+        user_id,
+        delivery,
+        token,
+        expires_at,
+    }))
+}
+
+/// POST /api/admin/users/{id}/resend-verification
+/// Issue a new email verification token, emailed or returned once for
+/// out-of-band delivery
+pub async fn admin_resend_verification(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<IssueUserTokenRequest>,
+) -> Result<Json<IssuedUserToken>, AppError> {
+    let delivery = token_delivery(&payload);
+
+    let mut tx = state.db.begin().await?;
+    let user = lock_credential_target(&mut tx, &admin, user_id).await?;
+
+    if user.email_verified_at.is_some() {
+        return Err(AppError::ValidationError(
+            "Email is already verified".to_string(),
+        ));
+    }
+
+    let (token, expires_at) = create_verification_token_with(&mut tx, user_id).await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "resend_verification",
+        "user",
+        user_id,
+        Some(json!({ "delivery": delivery })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let token = match delivery {
+        TokenDelivery::Email => {
+            let email_service = state.email.clone();
+            tokio::spawn(async move {
+                if let Err(e) = email_service
+                    .send_verification_email(&user.email, &user.first_name, &token)
+                    .await
+                {
+                    tracing::error!("Failed to send verification email: {}", e);
+                }
+            });
+            None
+        }
+        TokenDelivery::OutOfBand => Some(token),
+    };
+
+    Ok(Json(IssuedUserToken {
+        user_id,
+        delivery,
+        token,
+        expires_at,
+    }))
+}
+
+// ============================================================================
+// V13: DUPLICATE ACCOUNTS
+// ============================================================================
+
+/// GET /api/admin/users/duplicates
+/// List job seeker accounts that share a national ID, grouped by national ID
+pub async fn list_duplicate_users(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<DuplicateUserGroup>>, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            jsp.national_id as "national_id!",
+            u.id,
+            u.email,
+            u.first_name,
+            u.last_name,
+            u.account_status as "account_status: AccountStatus",
+            u.created_at,
+            (SELECT COUNT(*) FROM job_applications ja WHERE ja.applicant_id = u.id) as "applications_count!"
+        FROM job_seeker_profiles jsp
+        JOIN users u ON u.id = jsp.user_id
+        WHERE jsp.national_id IN (
+            SELECT national_id
+            FROM job_seeker_profiles
+            WHERE national_id IS NOT NULL AND national_id <> ''
+            GROUP BY national_id
+            HAVING COUNT(*) > 1
+        )
+        ORDER BY jsp.national_id, u.created_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut groups: Vec<DuplicateUserGroup> = Vec::new();
+    for row in rows {
+        let entry = DuplicateUserEntry {
+            id: row.id,
+            email: row.email,
+            first_name: row.first_name,
+            last_name: row.last_name,
+            account_status: row.account_status,
+            applications_count: row.applications_count,
+            created_at: row.created_at,
+        };
+
+        match groups.last_mut() {
+            Some(group) if group.national_id == row.national_id => group.users.push(entry),
+            _ => groups.push(DuplicateUserGroup {
+                national_id: row.national_id,
+                users: vec![entry],
+            }),
+        }
+    }
+
+    Ok(Json(groups))
+}
+
+/// POST /api/admin/users/merge
+/// Merge a duplicate job seeker account into a primary account
+pub async fn merge_users(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<MergeUsersRequest>,
+) -> Result<Json<MergeUsersResponse>, AppError> {
+    payload.validate()?;
+
+    let primary_id = payload.primary_user_id;
+    let duplicate_id = payload.duplicate_user_id;
+
+    if primary_id == duplicate_id {
+        return Err(AppError::ValidationError(
+            "Primary and duplicate accounts must be different".to_string(),
+        ));
+    }
+
+    let users = sqlx::query!(
+        r#"
+        SELECT id, user_type as "user_type: UserType"
+        FROM users
+        WHERE id = ANY($1)
+        "#,
+        &[primary_id, duplicate_id][..]
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    if users.len() != 2 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+    if users.iter().any(|u| u.user_type != UserType::JobSeeker) {
+        return Err(AppError::ValidationError(
+            "Only job seeker accounts can be merged".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Both accounts applied to the same job: keep the earlier application
+    let conflicts = sqlx::query!(
+        r#"
+        SELECT
+            p.id as primary_app_id,
+            p.applied_at as primary_applied_at,
+            d.id as duplicate_app_id,
+            d.applied_at as duplicate_applied_at
+        FROM job_applications p
+        JOIN job_applications d ON d.job_id = p.job_id
+        WHERE p.applicant_id = $1 AND d.applicant_id = $2
+        "#,
+        primary_id,
+        duplicate_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let applications_removed: Vec<Uuid> = conflicts
+        .iter()
+        .map(|c| {
+            if c.duplicate_applied_at < c.primary_applied_at {
+                c.primary_app_id
+            } else {
+                c.duplicate_app_id
+            }
+        })
+        .collect();
+
+    let counted_job_ids = sqlx::query_scalar!(
+        r#"
+        DELETE FROM job_applications WHERE id = ANY($1)
+        RETURNING CASE WHEN status <> 'withdrawn' THEN job_id END as job_id
+        "#,
+        &applications_removed[..]
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let counted_job_ids: Vec<Uuid> = counted_job_ids.into_iter().flatten().collect();
+    counters::applications_removed(&mut tx, &counted_job_ids).await?;
+
+    let applications_moved = sqlx::query_scalar!(
+        r#"
+        UPDATE job_applications
+        SET applicant_id = $1
+        WHERE applicant_id = $2
+        RETURNING id
+        "#,
+        primary_id,
+        duplicate_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Rows covered by a unique constraint are skipped when the primary already has them
+    let saved_jobs_moved = sqlx::query!(
+        r#"
+        UPDATE saved_jobs
+        SET user_id = $1
+        WHERE user_id = $2
+        AND job_id NOT IN (SELECT job_id FROM saved_jobs WHERE user_id = $1)
+        "#,
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let followups_moved = sqlx::query!(
+        "UPDATE job_seeker_followups SET job_seeker_id = $1 WHERE job_seeker_id = $2",
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let managed_records_moved = sqlx::query!(
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET job_seeker_id = $1
+        WHERE job_seeker_id = $2
+        AND omil_id NOT IN (SELECT omil_id FROM omil_managed_job_seekers WHERE job_seeker_id = $1)
+        "#,
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let skills_moved = sqlx::query!(
+        r#"
+        UPDATE user_skills
+        SET user_id = $1
+        WHERE user_id = $2
+        AND skill_id NOT IN (SELECT skill_id FROM user_skills WHERE user_id = $1)
+        "#,
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let education_moved = sqlx::query!(
+        "UPDATE education_records SET user_id = $1 WHERE user_id = $2",
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let experience_moved = sqlx::query!(
+        "UPDATE work_experiences SET user_id = $1 WHERE user_id = $2",
+        primary_id,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    // Carry the national ID over so the pair no longer shows up as duplicates
+    let national_id = sqlx::query_scalar!(
+        "SELECT national_id FROM job_seeker_profiles WHERE user_id = $1",
+        duplicate_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .flatten();
+
+    sqlx::query!(
+        "UPDATE job_seeker_profiles SET national_id = NULL WHERE user_id = $1",
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_profiles (user_id, national_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE
+        SET national_id = COALESCE(job_seeker_profiles.national_id, EXCLUDED.national_id)
+        "#,
+        primary_id,
+        national_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET account_status = 'deactivated', token_version = token_version + 1, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        duplicate_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let response = MergeUsersResponse {
+        primary_user_id: primary_id,
+        duplicate_user_id: duplicate_id,
+        applications_moved,
+        applications_removed,
+        saved_jobs_moved: saved_jobs_moved as i64,
+        followups_moved: followups_moved as i64,
+        managed_records_moved: managed_records_moved as i64,
+        skills_moved: skills_moved as i64,
+        education_moved: education_moved as i64,
+        experience_moved: experience_moved as i64,
+    };
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "merge_users",
+        "user",
+        primary_id,
+        Some(json!({
+            "duplicate_user_id": duplicate_id,
+            "national_id": national_id,
+            "applications_moved": response.applications_moved,
+            "applications_removed": response.applications_removed,
+            "saved_jobs_moved": response.saved_jobs_moved,
+            "followups_moved": response.followups_moved,
+            "managed_records_moved": response.managed_records_moved,
+            "skills_moved": response.skills_moved,
+            "education_moved": response.education_moved,
+            "experience_moved": response.experience_moved,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    forget_token_version(&mut state.redis.clone(), duplicate_id).await;
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// V11: OMIL APPROVALS
+// ============================================================================
+
+/// GET /api/admin/omils/pending
+/// List OMIL organizations pending approval
+pub async fn list_pending_omils(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<OmilOrganization>>, AppError> {
+    let omils = sqlx::query_as!(
+        OmilOrganization,
+        r#"
+        SELECT
+            id,
+            organization_name,
+            municipality_id,
+            region_id,
+            address,
+            phone,
+            email,
+            website_url,
+            status as "status: OrganizationStatus",
+            approved_at,
+            approved_by,
+            created_at,
+            updated_at
+        FROM omil_organizations
+        WHERE status = 'pending_approval'
+        ORDER BY created_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(omils))
+}
+
+/// PATCH /api/admin/omils/{id}/approve
+/// Approve an OMIL organization
+pub async fn approve_omil(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Path(omil_id): Path<Uuid>,
+    Json(payload): Json<ApproveOmilRequest>,
+) -> Result<Json<OmilOrganization>, AppError> {
+    payload.validate()?;
+
+    // Check if OMIL exists and is pending
+    let existing = sqlx::query!(
+        "SELECT status::text as status FROM omil_organizations WHERE id = $1",
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("OMIL organization not found".to_string()))?;
+
+    if existing.status.as_deref() != Some("pending_approval") {
+        return Err(AppError::ValidationError(
+            "OMIL organization is not pending approval".to_string(),
+        ));
+    }
+
+    // Update status
+    let omil = sqlx::query_as!(
+        OmilOrganization,
+        r#"
+        UPDATE omil_organizations
+        SET
+            status = 'active'::organization_status,
+            approved_at = NOW(),
+            approved_by = $1,
+            updated_at = NOW()
+        WHERE id = $2
+        RETURNING
+            id,
+            organization_name,
+            municipality_id,
+            region_id,
+            address,
+            phone,
+            email,
+            website_url,
+            status as "status: OrganizationStatus",
+            approved_at,
+            approved_by,
+            created_at,
+            updated_at
+        "#,
+        auth_user.id,
+        omil_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    // Log admin action
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "approve_omil",
+        "omil",
+        omil_id,
+        Some(json!({
+            "approval_notes": payload.approval_notes,
+            "organization_name": omil.organization_name,
+        })),
+    )
+    .await?;
+
+    admin_events::publish(&state).await;
+
+    Ok(Json(omil))
+}
+
+/// PATCH /api/admin/omils/{id}/reject
+/// Reject an OMIL organization
+pub async fn reject_omil(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Path(omil_id): Path<Uuid>,
+    Json(payload): Json<RejectOmilRequest>,
+) -> Result<Json<OmilOrganization>, AppError> {
+    payload.validate()?;
+
+    // Check if OMIL exists and is pending
+    let existing = sqlx::query!(
+        "SELECT status::text as status FROM omil_organizations WHERE id = $1",
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("OMIL organization not found".to_string()))?;
+
+    if existing.status.as_deref() != Some("pending_approval") {
+        return Err(AppError::ValidationError(
+            "OMIL organization is not pending approval".to_string(),
+        ));
+    }
+
+    // Update status - note: omil_organizations doesn't have rejection_reason column
+    // We'll add the reason in the audit log
+    let omil = sqlx::query_as!(
+        OmilOrganization,
+        r#"
+        UPDATE omil_organizations
+        SET
+            status = 'rejected'::organization_status,
+            approved_by = $1,
+            updated_at = NOW()
+        WHERE id = $2
+        RETURNING
+            id,
+            organization_name,
+            municipality_id,
+            region_id,
+            address,
+            phone,
+            email,
+            website_url,
+            status as "status: OrganizationStatus",
+            approved_at,
+            approved_by,
+            created_at,
+            updated_at
+        "#,
+        auth_user.id,
+        omil_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    // Log admin action with rejection reason
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "reject_omil",
+        "omil",
+        omil_id,
+        Some(json!({
+            "rejection_reason": payload.rejection_reason,
+            "organization_name": omil.organization_name,
+        })),
+    )
+    .await?;
+
+    admin_events::publish(&state).await;
+
+    Ok(Json(omil))
+}
+
+// ============================================================================
+// V11: AUDIT LOG VIEWER
+// ============================================================================
+
+/// GET /api/admin/audit-logs
+/// View audit logs with filters
+pub async fn list_audit_logs(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<AuditLogFilterParams>,
+) -> Result<Json<PaginatedResponse<AdminAuditLog>>, AppError> {
+    let limit = params.limit.unwrap_or(50).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let total: i64 = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*)
+        FROM admin_audit_logs
+        WHERE ($1::uuid IS NULL OR admin_id = $1)
+        AND ($2::text IS NULL OR action_type = $2)
+        AND ($3::text IS NULL OR entity_type = $3)
+        AND ($4::uuid IS NULL OR entity_id = $4)
+        AND ($5::timestamptz IS NULL OR created_at >= $5)
+        AND ($6::timestamptz IS NULL OR created_at <= $6)
+        "#,
+        params.admin_id,
+        params.action_type,
+        params.entity_type,
+        params.entity_id,
+        params.from_date,
+        params.to_date
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let logs = sqlx::query_as!(
+        AdminAuditLog,
+        r#"
+        SELECT
+            id,
+            admin_id,
+            action_type,
+            entity_type,
+            entity_id,
+            details,
+            ip_address,
+            created_at
+        FROM admin_audit_logs
+        WHERE ($1::uuid IS NULL OR admin_id = $1)
+        AND ($2::text IS NULL OR action_type = $2)
+        AND ($3::text IS NULL OR entity_type = $3)
+        AND ($4::uuid IS NULL OR entity_id = $4)
+        AND ($5::timestamptz IS NULL OR created_at >= $5)
+        AND ($6::timestamptz IS NULL OR created_at <= $6)
+        ORDER BY created_at DESC
+        LIMIT $7 OFFSET $8
+        "#,
+        params.admin_id,
+        params.action_type,
+        params.entity_type,
+        params.entity_id,
+        params.from_date,
+        params.to_date,
+        limit,
+        offset
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: logs,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// GET /api/admin/domain-events?aggregate_type=&aggregate_id=
+/// The events of one entity in the order they happened, personal data redacted
+pub async fn list_domain_events(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<DomainEventsQuery>,
+) -> Result<Json<Vec<DomainEvent>>, AppError> {
+    let events = sqlx::query_as!(
+        DomainEvent,
+        r#"
+        SELECT
+            id,
+            aggregate_type as "aggregate_type: AggregateType",
+            aggregate_id,
+            event_type,
+            payload,
+            actor_id,
+            occurred_at
+        FROM domain_events
+        WHERE aggregate_type = $1 AND aggregate_id = $2
+        ORDER BY id
+        "#,
+        params.aggregate_type as AggregateType,
+        params.aggregate_id
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|event| DomainEvent {
+        payload: events::redact_payload(event.payload),
+        ..event
+    })
+    .collect();
+
+    Ok(Json(events))
+}
+
+/// GET /api/admin/exports
+/// Spreadsheet exports across all organizations, newest first
+pub async fn list_exports(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ExportAuditFilterParams>,
+) -> Result<Json<PaginatedResponse<ExportAuditEntry>>, AppError> {
+    let limit = params.limit.unwrap_or(50).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let filter = exports::AuditFilter {
+        omil_id: params.omil_id,
+        company_id: params.company_id,
+        exported_by: params.exported_by,
+        export_type: params.export_type,
+        export_id: params.export_id,
+    };
+    let (data, total) = exports::list(&state.db, &filter, limit, offset).await?;
+
+    Ok(Json(PaginatedResponse {
+        data,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// PUT /api/admin/companies/{id}/export-settings
+/// Allow or forbid applicants' contact details in a company's exports
+pub async fn update_company_export_settings(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(company_id): Path<Uuid>,
+    Json(payload): Json<UpdateExportSettingsRequest>,
+) -> Result<Json<ExportSettings>, AppError> {
+    let settings = sqlx::query_as!(
+        ExportSettings,
+        r#"
+        UPDATE company_profiles
+        SET allow_contact_export = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING allow_contact_export
+        "#,
+        company_id,
+        payload.allow_contact_export
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
+
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "update_company_export_settings",
+        "company",
+        company_id,
+        Some(json!({ "allow_contact_export": settings.allow_contact_export })),
+    )
+    .await?;
+
+    Ok(Json(settings))
+}
+
+// ============================================================================
+// V11: SYSTEM SETTINGS
+// ============================================================================
+
+/// GET /api/admin/settings
+/// Get all system settings with their definitions
+pub async fn get_settings(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<SystemSetting>>, AppError> {
+    let stored = sqlx::query!("SELECT key, value, updated_at FROM system_settings")
+        .fetch_all(&state.db)
+        .await?;
+
+    let result: Vec<SystemSetting> = SETTING_DEFINITIONS
+        .iter()
+        .map(|definition| {
+            let row = stored.iter().find(|row| row.key == definition.key);
+            SystemSetting {
+                key: definition.key.to_string(),
+                value: row
+                    .map(|row| row.value.clone())
+                    .unwrap_or_else(|| definition.default_value()),
+                definition: *definition,
+                updated_at: row.map(|row| row.updated_at),
+            }
+        })
+        .collect();
+
+    Ok(Json(result))
+}
+
+/// PUT /api/admin/settings
+/// Update system settings; every value must match its setting definition
+pub async fn update_settings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<UpdateSettingsRequest>,
+) -> Result<Json<Vec<SystemSetting>>, AppError> {
+    payload.validate()?;
+
+    // Validate the whole batch before writing anything
+    for setting in &payload.settings {
+        let definition = SettingDefinition::find(&setting.key).ok_or_else(|| {
+            AppError::ValidationError(format!("Unknown setting: {}", setting.key))
+        })?;
+        definition
+            .validate_value(&setting.value)
+            .map_err(AppError::ValidationError)?;
+    }
+
+    let mut tx = state.db.begin().await?;
+    let mut changed_keys = Vec::new();
+
+    for setting in &payload.settings {
+        let old_value = sqlx::query_scalar!(
+            "SELECT value FROM system_settings WHERE key = $1 FOR UPDATE",
+            setting.key
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if old_value.as_ref() == Some(&setting.value) {
+            continue;
+        }
+
+        let description = SettingDefinition::find(&setting.key).map(|d| d.description);
+        sqlx::query!(
+            r#"
+            INSERT INTO system_settings (key, value, description, updated_by, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (key) DO UPDATE
+            SET value = EXCLUDED.value, updated_by = EXCLUDED.updated_by, updated_at = NOW()
+            "#,
+            setting.key,
+            setting.value,
+            description,
+            auth_user.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO settings_history (key, old_value, new_value, changed_by)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            setting.key,
+            old_value,
+            setting.value,
+            auth_user.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        changed_keys.push(setting.key.as_str());
+    }
+
+    // Log admin action
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "update_settings",
+        "settings",
+        Uuid::nil(),
+        Some(json!({ "updated_keys": changed_keys })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let mut redis = state.redis.clone();
+    settings::invalidate(&mut redis, &changed_keys).await;
+
+    // Return updated settings
+    get_settings(State(state), Extension(admin)).await
+}
+
+/// GET /api/admin/settings/{key}/history
+/// Get the change history of a system setting, newest first
+pub async fn get_setting_history(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Path(key): Path<String>,
+) -> Result<Json<Vec<SettingHistoryEntry>>, AppError> {
+    if SettingDefinition::find(&key).is_none() {
+        return Err(AppError::NotFound(format!("Unknown setting: {}", key)));
+    }
+
+    let history = sqlx::query_as!(
+        SettingHistoryEntry,
+        r#"
+        SELECT
+            h.id, h.key, h.old_value, h.new_value, h.changed_by,
+            u.first_name || ' ' || u.last_name AS changed_by_name,
+            h.changed_at
+        FROM settings_history h
+        LEFT JOIN users u ON u.id = h.changed_by
+        WHERE h.key = $1
+        ORDER BY h.changed_at DESC
+        "#,
+        key
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(history))
+}
+
+// ============================================================================
+// V13: JWT KEYS
+// ============================================================================
+
+/// GET /api/admin/security/jwt-keys
+/// Configured signing keys and how many recent tokens still use each
+pub async fn list_jwt_keys(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<JwtKeysResponse>, AppError> {
+    let mut redis_conn = state.redis.clone();
+    let redis_err = |e: redis::RedisError| AppError::InternalError(format!("Redis error: {}", e));
+
+    let secrets = std::iter::once(&state.config.jwt_secret)
+        .chain(state.config.jwt_previous_secrets.iter());
+
+    let mut keys = Vec::new();
+    for (i, secret) in secrets.enumerate() {
+        let kid = key_id(secret);
+        let recent_tokens = recent_key_usage(&mut redis_conn, &kid)
+            .await
+            .map_err(redis_err)?;
+
+        keys.push(JwtKeyInfo {
+            kid,
+            primary: i == 0,
+            recent_tokens,
+        });
+    }
+
+    let tokens_without_kid = recent_key_usage(&mut redis_conn, "none")
+        .await
+        .map_err(redis_err)?;
+
+    Ok(Json(JwtKeysResponse {
+        window_hours: KEY_USAGE_WINDOW_HOURS,
+        keys,
+        tokens_without_kid,
+    }))
+}
+
+// ============================================================================
+// V12: REPORTING
+// ============================================================================
+
+/// GET /api/admin/reports/users
+/// User registration trends report
+pub async fn report_users(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<UserTrendsReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    let total_users: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM users")
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(0);
+
+    let new_users_period: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM users WHERE created_at >= $1 AND created_at <= $2",
+        from_date,
+        to_date
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    // Users by type
+    let by_type = sqlx::query!(
+        r#"
+        SELECT user_type::text as "user_type!", COUNT(*) as "count!"
+        FROM users
+        GROUP BY user_type
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| UserTypeCount {
+        user_type: r.user_type,
+        count: r.count,
+    })
+    .collect();
+
+    // Trend data (daily)
+    let trend = sqlx::query!(
+        r#"
+        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+        FROM users
+        WHERE created_at >= $1 AND created_at <= $2
+        GROUP BY DATE(created_at)
+        ORDER BY DATE(created_at)
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| TrendDataPoint {
+        date: r.date,
+        count: r.count,
+    })
+    .collect();
+
+    Ok(Json(UserTrendsReport {
+        total_users,
+        new_users_period,
+        by_type,
+        trend,
+    }))
+}
+
+/// GET /api/admin/reports/companies
+/// Company statistics report
+pub async fn report_companies(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<CompanyTrendsReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    let total_companies: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM company_profiles")
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(0);
+
+    let active_companies: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM company_profiles WHERE status = 'active'"
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let pending_companies: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM company_profiles WHERE status = 'pending_approval'"
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let new_companies_period: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM company_profiles WHERE created_at >= $1 AND created_at <= $2",
+        from_date,
+        to_date
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    // Secondary industries are ignored so no company is counted twice
+    let by_primary_industry = sqlx::query_as!(
+        IndustryCompanyCount,
+        r#"
+        SELECT ci.industry_id as "industry_id?", i.name as "industry_name?", COUNT(*) as "count!"
+        FROM company_profiles cp
+        LEFT JOIN company_industries ci ON ci.company_id = cp.id AND ci.is_primary
+        LEFT JOIN industries i ON i.id = ci.industry_id
+        GROUP BY ci.industry_id, i.name
+        ORDER BY COUNT(*) DESC, i.name
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let trend = sqlx::query!(
+        r#"
+        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+        FROM company_profiles
+        WHERE created_at >= $1 AND created_at <= $2
+        GROUP BY DATE(created_at)
+        ORDER BY DATE(created_at)
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| TrendDataPoint {
+        date: r.date,
+        count: r.count,
+    })
+    .collect();
+
+    Ok(Json(CompanyTrendsReport {
+        total_companies,
+        active_companies,
+        pending_companies,
+        new_companies_period,
+        by_primary_industry,
+        trend,
+    }))
+}
+
+/// GET /api/admin/reports/jobs
+/// Job posting trends report
+pub async fn report_jobs(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<JobTrendsReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    let total_jobs: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM jobs")
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(0);
+
+    let active_jobs: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM jobs WHERE status = 'active'"
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let pending_jobs: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM jobs WHERE status = 'pending_approval'"
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let new_jobs_period: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM jobs WHERE created_at >= $1 AND created_at <= $2",
+        from_date,
+        to_date
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let trend = sqlx::query!(
+        r#"
+        SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+        FROM jobs
+        WHERE created_at >= $1 AND created_at <= $2
+        GROUP BY DATE(created_at)
+        ORDER BY DATE(created_at)
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| TrendDataPoint {
+        date: r.date,
+        count: r.count,
+    })
+    .collect();
+
+    Ok(Json(JobTrendsReport {
+        total_jobs,
+        active_jobs,
+        pending_jobs,
+        new_jobs_period,
+        trend,
+    }))
+}
+
+/// GET /api/admin/reports/applications
+/// Application trends report
+pub async fn report_applications(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<ApplicationTrendsReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    let total_applications: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM job_applications")
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(0);
+
+    let new_applications_period: i64 = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM job_applications WHERE applied_at >= $1 AND applied_at <= $2",
+        from_date,
+        to_date
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let by_status = sqlx::query!(
+        r#"
+        SELECT status::text as "status!", COUNT(*) as "count!"
+        FROM job_applications
+        GROUP BY status
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| ApplicationStatusCount {
+        status: r.status,
+        count: r.count,
+    })
+    .collect();
+
+    let trend = sqlx::query!(
+        r#"
+        SELECT DATE(applied_at)::text as "date!", COUNT(*) as "count!"
+        FROM job_applications
+        WHERE applied_at >= $1 AND applied_at <= $2
+        GROUP BY DATE(applied_at)
+        ORDER BY DATE(applied_at)
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| TrendDataPoint {
+        date: r.date,
+        count: r.count,
+    })
+    .collect();
+
+    let rejection_reasons = sqlx::query!(
+        r#"
+        SELECT
+            rejection_reason_code as "reason: RejectionReasonCode",
+            COUNT(*) as "count!"
+        FROM job_applications
+        WHERE status = 'rejected'
+        GROUP BY rejection_reason_code
+        ORDER BY COUNT(*) DESC, rejection_reason_code
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|r| RejectionReasonCount {
+        reason: r.reason,
+        count: r.count,
+    })
+    .collect();
+
+    Ok(Json(ApplicationTrendsReport {
+        total_applications,
+        new_applications_period,
+        by_status,
+        trend,
+        rejection_reasons,
+    }))
+}
+
+/// GET /api/admin/reports/inclusion
+/// Hiring funnel for seekers with a registered disability, by category and region
+pub async fn report_inclusion(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<InclusionReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    Ok(Json(build_inclusion_report(&state.db, from_date, to_date).await?))
+}
+
+/// Aggregate the inclusion funnel. Only counts leave the database and every
+/// people count goes through SuppressedCount before it is reported.
+async fn build_inclusion_report(
+    db: &sqlx::PgPool,
+    from_date: chrono::DateTime<Utc>,
+    to_date: chrono::DateTime<Utc>,
+) -> Result<InclusionReport, AppError> {
+    let mut by_category = Vec::new();
+    let mut by_region = Vec::new();
+    let mut total_seekers = 0;
+    let mut total_applicants = 0;
+    let mut total_applications = 0;
+
+    for dimension in ["category", "region"] {
+        // A stage counts as reached if the application is there now or passed through it
+        let rows = sqlx::query!(
+            r#"
+            WITH seekers AS (
+                SELECT
+                    jsd.user_id,
+                    jsd.category::text AS category,
+                    COALESCE(r.name, 'Sin región') AS region
+                FROM job_seeker_disabilities jsd
+                JOIN users u ON u.id = jsd.user_id
+                LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = jsd.user_id
+                LEFT JOIN regions r ON r.id = jsp.region_id
+                WHERE u.user_type = 'job_seeker' AND u.created_at <= $2
+            ),
+            apps AS (
+                SELECT
+                    ja.id,
+                    ja.applicant_id,
+                    ARRAY_APPEND(
+                        ARRAY(
+                            SELECT h.new_status::text
+                            FROM application_status_history h
+                            WHERE h.application_id = ja.id
+                        ),
+                        ja.status::text
+                    ) AS statuses
+                FROM job_applications ja
+                WHERE ja.applied_at >= $1 AND ja.applied_at <= $2
+            )
+            SELECT
+                CASE WHEN $3 = 'region' THEN s.region ELSE s.category END AS "group_name!",
+                COUNT(DISTINCT s.user_id) AS "seekers!",
+                COUNT(a.id) AS "applications!",
+                COUNT(DISTINCT a.applicant_id) AS "applied!",
+                COUNT(DISTINCT a.applicant_id) FILTER (
+                    WHERE a.statuses && ARRAY['interview_scheduled', 'offer_extended', 'hired']
+                ) AS "interviewing!",
+                COUNT(DISTINCT a.applicant_id) FILTER (
+                    WHERE a.statuses && ARRAY['offer_extended', 'hired']
+                ) AS "offered!",
+                COUNT(DISTINCT a.applicant_id) FILTER (
+                    WHERE a.statuses && ARRAY['hired']
+                ) AS "hired!"
+            FROM seekers s
+            LEFT JOIN apps a ON a.applicant_id = s.user_id
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            from_date,
+            to_date,
+            dimension
+        )
+        .fetch_all(db)
+        .await?;
+
+        if dimension == "category" {
+            for row in &rows {
+                total_seekers += row.seekers;
+                total_applicants += row.applied;
+                total_applications += row.applications;
+            }
+        }
+
+        // Each column adds up to a reported total, or to the same column of
+        // the other breakdown
+        let column = |count: fn(&_) -> i64| {
+            SuppressedCount::column(&rows.iter().map(count).collect::<Vec<_>>())
+        };
+        let seekers = column(|row| row.seekers);
+        let applied = column(|row| row.applied);
+        let interviewing = column(|row| row.interviewing);
+        let offered = column(|row| row.offered);
+        let hired = column(|row| row.hired);
+
+        let mut funnel = Vec::with_capacity(rows.len());
+        for (i, row) in rows.into_iter().enumerate() {
+            let (applied, interviewing, offered, hired) =
+                (applied[i], interviewing[i], offered[i], hired[i]);
+
+            // Application totals are hidden along with the people behind them
+            let applications = if applied.suppressed {
+                applied
+            } else {
+                SuppressedCount {
+                    value: Some(row.applications),
+                    suppressed: false,
+                }
+            };
+
+            funnel.push(InclusionFunnelRow {
+                group: row.group_name,
+                seekers: seekers[i],
+                applications,
+                interview_rate: interviewing.rate_over(&applied),
+                offer_rate: offered.rate_over(&interviewing),
+                hire_rate: hired.rate_over(&offered),
+                applied,
+                interviewing,
+                offered,
+                hired,
+            });
+        }
+
+        if dimension == "category" {
+            by_category = funnel;
+        } else {
+            by_region = funnel;
+        }
+    }
+
+    let applications = if total_applicants < MIN_REPORTABLE_CELL {
+        SuppressedCount::from_count(total_applicants)
+    } else {
+        SuppressedCount {
+            value: Some(total_applications),
+            suppressed: false,
+        }
+    };
+
+    // Jobs are not people: no suppression needed
+    let jobs = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) AS "active_jobs!",
+            COUNT(*) FILTER (
+                WHERE EXISTS (
+                    SELECT 1 FROM job_disability_accommodations jda WHERE jda.job_id = j.id
+                )
+            ) AS "with_accommodation!"
+        FROM jobs j
+        WHERE j.status = 'active'
+        "#
+    )
+    .fetch_one(db)
+    .await?;
+
+    let accommodation_share = if jobs.active_jobs > 0 {
+        Some((jobs.with_accommodation as f64 / jobs.active_jobs as f64 * 10_000.0).round() / 10_000.0)
+    } else {
+        None
+    };
+
+    Ok(InclusionReport {
+        from_date,
+        to_date,
+        seekers_with_disability: SuppressedCount::from_count(total_seekers),
+        applications,
+        by_category,
+        by_region,
+        active_jobs: jobs.active_jobs,
+        active_jobs_with_accommodation: jobs.with_accommodation,
+        accommodation_share,
+    })
+}
+
+/// GET /api/admin/reports/moderation
+/// Approval turnaround, reviewer load and current queue age
+pub async fn report_moderation(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Json<ModerationReport>, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    Ok(Json(build_moderation_report(&state.db, from_date, to_date).await?))
+}
+
+fn round_hours(hours: Option<f64>) -> Option<f64> {
+    hours.map(|h| (h * 100.0).round() / 100.0)
+}
+
+/// Audit logs only carry the decision time; submission times come from the
+/// entity tables. Jobs use their latest resubmission when it preceded the
+/// decision, companies and OMILs are never resubmitted.
+async fn build_moderation_report(
+    db: &sqlx::PgPool,
+    from_date: chrono::DateTime<Utc>,
+    to_date: chrono::DateTime<Utc>,
+) -> Result<ModerationReport, AppError> {
+    let turnaround = sqlx::query!(
+        r#"
+        WITH decisions AS (
+            SELECT
+                l.entity_type,
+                l.action_type LIKE 'approve_%' AS approved,
+                EXTRACT(EPOCH FROM l.created_at - (
+                    CASE l.entity_type
+                        WHEN 'company' THEN c.created_at
+                        WHEN 'job' THEN
+                            CASE WHEN j.submitted_at <= l.created_at THEN j.submitted_at ELSE j.created_at END
+                        ELSE o.created_at
+                    END
+                ))::float8 / 3600.0 AS wait_hours
+            FROM admin_audit_logs l
+            LEFT JOIN company_profiles c ON l.entity_type = 'company' AND c.id = l.entity_id
+            LEFT JOIN jobs j ON l.entity_type = 'job' AND j.id = l.entity_id
+            LEFT JOIN omil_organizations o ON l.entity_type = 'omil' AND o.id = l.entity_id
+            WHERE l.action_type IN (
+                'approve_company', 'reject_company',
+                'approve_job', 'reject_job',
+                'approve_omil', 'reject_omil'
+            )
+            AND l.created_at >= $1 AND l.created_at <= $2
+        )
+        SELECT
+            e.entity_type AS "entity_type!",
+            COUNT(d.entity_type) AS "decisions!",
+            COUNT(d.entity_type) FILTER (WHERE d.approved) AS "approvals!",
+            COUNT(d.entity_type) FILTER (WHERE NOT d.approved) AS "rejections!",
+            PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY d.wait_hours) AS median_hours,
+            PERCENTILE_CONT(0.9) WITHIN GROUP (ORDER BY d.wait_hours) AS p90_hours
+        FROM (VALUES ('company'), ('job'), ('omil')) AS e(entity_type)
+        LEFT JOIN decisions d ON d.entity_type = e.entity_type
+        GROUP BY e.entity_type
+        ORDER BY e.entity_type
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|r| ModerationTurnaround {
+        entity_type: r.entity_type,
+        decisions: r.decisions,
+        approvals: r.approvals,
+        rejections: r.rejections,
+        median_hours: round_hours(r.median_hours),
+        p90_hours: round_hours(r.p90_hours),
+    })
+    .collect();
+
+    let by_admin = sqlx::query_as!(
+        ReviewerDecisionCount,
+        r#"
+        SELECT
+            a.id AS "admin_id!",
+            u.first_name || ' ' || u.last_name AS "admin_name!",
+            COUNT(*) FILTER (WHERE l.action_type LIKE 'approve_%') AS "approvals!",
+            COUNT(*) FILTER (WHERE l.action_type LIKE 'reject_%') AS "rejections!"
+        FROM admin_audit_logs l
+        JOIN admins a ON a.id = l.admin_id
+        JOIN users u ON u.id = a.user_id
+        WHERE l.action_type IN (
+            'approve_company', 'reject_company',
+            'approve_job', 'reject_job',
+            'approve_omil', 'reject_omil'
+        )
+        AND l.created_at >= $1 AND l.created_at <= $2
+        GROUP BY a.id, u.first_name, u.last_name
+        ORDER BY COUNT(*) DESC, u.last_name, u.first_name
+        "#,
+        from_date,
+        to_date
+    )
+    .fetch_all(db)
+    .await?;
+
+    let queue = sqlx::query!(
+        r#"
+        WITH pending AS (
+            SELECT 'company' AS entity_type, created_at AS submitted_at
+            FROM company_profiles WHERE status = 'pending_approval'
+            UNION ALL
+            SELECT 'job', submitted_at
+            FROM jobs WHERE status = 'pending_approval'
+            UNION ALL
+            SELECT 'omil', created_at
+            FROM omil_organizations WHERE status = 'pending_approval'
+        ),
+        waits AS (
+            SELECT entity_type, EXTRACT(EPOCH FROM NOW() - submitted_at)::float8 / 3600.0 AS hours
+            FROM pending
+        )
+        SELECT
+            e.entity_type AS "entity_type!",
+            COUNT(w.entity_type) AS "pending!",
+            COUNT(w.entity_type) FILTER (WHERE w.hours < 24) AS "under_1_day!",
+            COUNT(w.entity_type) FILTER (WHERE w.hours >= 24 AND w.hours < 72) AS "from_1_to_3_days!",
+            COUNT(w.entity_type) FILTER (WHERE w.hours >= 72 AND w.hours < 168) AS "from_3_to_7_days!",
+            COUNT(w.entity_type) FILTER (WHERE w.hours >= 168) AS "over_7_days!",
+            MAX(w.hours) AS oldest_hours
+        FROM (VALUES ('company'), ('job'), ('omil')) AS e(entity_type)
+        LEFT JOIN waits w ON w.entity_type = e.entity_type
+        GROUP BY e.entity_type
+        ORDER BY e.entity_type
+        "#
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|r| ModerationQueueAge {
+        entity_type: r.entity_type,
+        pending: r.pending,
+        under_1_day: r.under_1_day,
+        from_1_to_3_days: r.from_1_to_3_days,
+        from_3_to_7_days: r.from_3_to_7_days,
+        over_7_days: r.over_7_days,
+        oldest_hours: round_hours(r.oldest_hours),
+    })
+    .collect();
+
+    Ok(ModerationReport {
+        from_date,
+        to_date,
+        turnaround,
+        by_admin,
+        queue,
+    })
+}
+
+// ============================================================================
+// V13: COHORT RETENTION
+// ============================================================================
+
+/// GET /api/admin/reports/retention
+/// Monthly job seeker registration cohorts with activity retention at 30, 60
+/// and 90 days and the share placed in a job
+pub async fn report_cohort_retention(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<CohortRetentionReport>, AppError> {
+    Ok(Json(
+        build_cohort_retention_report(&state.db, &state.config.platform_timezone).await?,
+    ))
+}
+
+fn share(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| (part as f64 / whole as f64 * 10_000.0).round() / 10_000.0)
+}
+
+/// A seeker's last activity is the latest of their last authenticated
+/// request, refresh token, application and profile update; they count as
+/// retained at a window when it came at least that many days after
+/// registering. Months are calendar months in `timezone`.
+async fn build_cohort_retention_report(
+    db: &sqlx::PgPool,
+    timezone: &str,
+) -> Result<CohortRetentionReport, AppError> {
+    let [w30, w60, w90] = COHORT_RETENTION_WINDOWS;
+
+    let rows = sqlx::query!(
+        r#"
+        WITH months AS (
+            SELECT generate_series(
+                date_trunc('month', NOW() AT TIME ZONE $1) - make_interval(months => $2 - 1),
+                date_trunc('month', NOW() AT TIME ZONE $1),
+                INTERVAL '1 month'
+            ) AS month
+        ),
+        seekers AS (
+            SELECT
+                date_trunc('month', u.created_at AT TIME ZONE $1) AS month,
+                GREATEST(
+                    u.last_active_at,
+                    (SELECT MAX(rt.created_at) FROM refresh_tokens rt WHERE rt.user_id = u.id),
+                    (SELECT MAX(ja.applied_at) FROM job_applications ja WHERE ja.applicant_id = u.id),
+                    (SELECT p.updated_at FROM job_seeker_profiles p WHERE p.user_id = u.id)
+                ) - u.created_at AS active_for,
+                EXISTS (
+                    SELECT 1 FROM job_applications ja
+                    WHERE ja.applicant_id = u.id AND ja.status = 'hired'
+                ) OR EXISTS (
+                    SELECT 1 FROM omil_managed_job_seekers m
+                    WHERE m.job_seeker_id = u.id AND m.placement_outcome = 'placed'
+                ) AS placed
+            FROM users u
+            WHERE u.user_type = 'job_seeker'
+            AND u.created_at >= (SELECT MIN(month) FROM months) AT TIME ZONE $1
+        )
+        SELECT
+            to_char(m.month, 'YYYY-MM') AS "cohort!",
+            (m.month + INTERVAL '1 month') AT TIME ZONE $1 AS "ends_at!",
+            COUNT(s.month) AS "registered!",
+            COUNT(s.month) FILTER (WHERE s.active_for >= make_interval(days => $3)) AS "retained_30!",
+            COUNT(s.month) FILTER (WHERE s.active_for >= make_interval(days => $4)) AS "retained_60!",
+            COUNT(s.month) FILTER (WHERE s.active_for >= make_interval(days => $5)) AS "retained_90!",
+            COUNT(s.month) FILTER (WHERE s.placed) AS "placed!"
+        FROM months m
+        LEFT JOIN seekers s ON s.month = m.month
+        GROUP BY m.month
+        ORDER BY m.month
+        "#,
+        timezone,
+        COHORT_RETENTION_MONTHS,
+        w30,
+        w60,
+        w90,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let now = Utc::now();
+    let mut totals = CohortRetentionTotals {
+        registered: 0,
+        retained: vec![0; COHORT_RETENTION_WINDOWS.len()],
+        retention_rates: Vec::new(),
+        placed: 0,
+        placement_rate: None,
+    };
+    // Registrations of the cohorts that reached each window
+    let mut reached = vec![0; COHORT_RETENTION_WINDOWS.len()];
+
+    let mut cohorts = Vec::with_capacity(rows.len());
+    for row in rows {
+        let counts = [row.retained_30, row.retained_60, row.retained_90];
+        let retained: Vec<Option<i64>> = COHORT_RETENTION_WINDOWS
+            .iter()
+            .zip(counts)
+            .map(|(&days, count)| {
+                (row.ends_at + Duration::days(days as i64) <= now).then_some(count)
+            })
+            .collect();
+
+        for (i, count) in retained.iter().enumerate() {
+            if let Some(count) = count {
+                totals.retained[i] += count;
+                reached[i] += row.registered;
+            }
+        }
+        totals.registered += row.registered;
+        totals.placed += row.placed;
+
+        cohorts.push(CohortRetentionRow {
+            cohort: row.cohort,
+            registered: row.registered,
+            retention_rates: retained
+                .iter()
+                .map(|count| count.and_then(|count| share(count, row.registered)))
+                .collect(),
+            retained,
+            placed: row.placed,
+            placement_rate: share(row.placed, row.registered),
+        });
+    }
+
+    totals.retention_rates = totals
+        .retained
+        .iter()
+        .zip(&reached)
+        .map(|(&count, &registered)| share(count, registered))
+        .collect();
+    totals.placement_rate = share(totals.placed, totals.registered);
+
+    Ok(CohortRetentionReport {
+        windows: COHORT_RETENTION_WINDOWS.to_vec(),
+        cohorts,
+        totals,
+    })
+}
+
+/// GET /api/admin/reports/export/{type}
+/// Export report to Excel
+pub async fn export_report(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Path(report_type): Path<String>,
+    Query(params): Query<ReportDateRangeParams>,
+) -> Result<Response, AppError> {
+    let from_date = params.from_date.unwrap_or_else(|| {
+        Utc::now() - chrono::Duration::days(30)
+    });
+    let to_date = params.to_date.unwrap_or_else(Utc::now);
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    let header_format = Format::new().set_bold();
+    let xlsx_err = |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+    match report_type.as_str() {
+        "users" => {
+            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
+            worksheet.write_string_with_format(0, 1, "New Users", &header_format).map_err(xlsx_err)?;
+
+            let data = sqlx::query!(
+                r#"
+                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+                FROM users
+                WHERE created_at >= $1 AND created_at <= $2
+                GROUP BY DATE(created_at)
+                ORDER BY DATE(created_at)
+                "#,
+                from_date, to_date
+            )
+            .fetch_all(&state.db)
+            .await?;
+
+            for (i, row) in data.iter().enumerate() {
+                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
+                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
+            }
+        }
+        "companies" => {
+            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
+            worksheet.write_string_with_format(0, 1, "New Companies", &header_format).map_err(xlsx_err)?;
+
+            let data = sqlx::query!(
+                r#"
+                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+                FROM company_profiles
+                WHERE created_at >= $1 AND created_at <= $2
+                GROUP BY DATE(created_at)
+                ORDER BY DATE(created_at)
+                "#,
+                from_date, to_date
+            )
+            .fetch_all(&state.db)
+            .await?;
+
+            for (i, row) in data.iter().enumerate() {
+                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
+                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
+            }
+        }
+        "jobs" => {
+            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
+            worksheet.write_string_with_format(0, 1, "New Jobs", &header_format).map_err(xlsx_err)?;
+
+            let data = sqlx::query!(
+                r#"
+                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
+                FROM jobs
+                WHERE created_at >= $1 AND created_at <= $2
+                GROUP BY DATE(created_at)
+                ORDER BY DATE(created_at)
+                "#,
+                from_date, to_date
+            )
+            .fetch_all(&state.db)
+            .await?;
+
+            for (i, row) in data.iter().enumerate() {
+                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
+                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
+            }
+        }
+        "applications" => {
+            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
+            worksheet.write_string_with_format(0, 1, "New Applications", &header_format).map_err(xlsx_err)?;
+
+            let data = sqlx::query!(
+                r#"
+                SELECT DATE(applied_at)::text as "date!", COUNT(*) as "count!"
+                FROM job_applications
+                WHERE applied_at >= $1 AND applied_at <= $2
+                GROUP BY DATE(applied_at)
+                ORDER BY DATE(applied_at)
+                "#,
+                from_date, to_date
+            )
+            .fetch_all(&state.db)
+            .await?;
+
+            for (i, row) in data.iter().enumerate() {
+                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
+                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
+            }
+        }
+        "inclusion" => {
+            let report = build_inclusion_report(&state.db, from_date, to_date).await?;
+
+            let headers = [
+                "Dimension", "Group", "Seekers", "Applications", "Applied",
+                "Interviewing", "Offered", "Hired", "Interview Rate", "Offer Rate", "Hire Rate",
+            ];
+            for (col, title) in headers.iter().enumerate() {
+                worksheet.write_string_with_format(0, col as u16, *title, &header_format).map_err(xlsx_err)?;
+            }
+
+            let rows = report
+                .by_category
+                .iter()
+                .map(|r| ("Category", r))
+                .chain(report.by_region.iter().map(|r| ("Region", r)));
+
+            for (i, (dimension, row)) in rows.enumerate() {
+                let line = (i + 1) as u32;
+                worksheet.write_string(line, 0, dimension).map_err(xlsx_err)?;
+                worksheet.write_string(line, 1, &row.group).map_err(xlsx_err)?;
+
+                let counts = [row.seekers, row.applications, row.applied, row.interviewing, row.offered, row.hired];
+                for (j, count) in counts.iter().enumerate() {
+                    let col = (j + 2) as u16;
+                    match count.value {
+                        Some(v) => worksheet.write_number(line, col, v as f64).map_err(xlsx_err)?,
+                        None => worksheet.write_string(line, col, "<5").map_err(xlsx_err)?,
+                    };
+                }
+
+                for (j, rate) in [row.interview_rate, row.offer_rate, row.hire_rate].iter().enumerate() {
+                    if let Some(rate) = rate {
+                        worksheet.write_number(line, (j + 8) as u16, *rate).map_err(xlsx_err)?;
+                    }
+                }
+            }
+        }
+        "moderation" => {
+            let report = build_moderation_report(&state.db, from_date, to_date).await?;
+            let mut line = 0u32;
+
+            let headers = ["Entity", "Decisions", "Approvals", "Rejections", "Median Hours", "P90 Hours"];
+            for (col, title) in headers.iter().enumerate() {
+                worksheet.write_string_with_format(line, col as u16, *title, &header_format).map_err(xlsx_err)?;
+            }
+            for row in &report.turnaround {
+                line += 1;
+                worksheet.write_string(line, 0, &row.entity_type).map_err(xlsx_err)?;
+                for (j, count) in [row.decisions, row.approvals, row.rejections].iter().enumerate() {
+                    worksheet.write_number(line, (j + 1) as u16, *count as f64).map_err(xlsx_err)?;
+                }
+                for (j, hours) in [row.median_hours, row.p90_hours].iter().enumerate() {
+                    if let Some(hours) = hours {
+                        worksheet.write_number(line, (j + 4) as u16, *hours).map_err(xlsx_err)?;
+                    }
+                }
+            }
+
+            line += 2;
+            for (col, title) in ["Admin", "Approvals", "Rejections"].iter().enumerate() {
+                worksheet.write_string_with_format(line, col as u16, *title, &header_format).map_err(xlsx_err)?;
+            }
+            for row in &report.by_admin {
+                line += 1;
+                worksheet.write_string(line, 0, &row.admin_name).map_err(xlsx_err)?;
+                worksheet.write_number(line, 1, row.approvals as f64).map_err(xlsx_err)?;
+                worksheet.write_number(line, 2, row.rejections as f64).map_err(xlsx_err)?;
+            }
+
+            line += 2;
+            let headers = ["Queue", "Pending", "< 1 Day", "1-3 Days", "3-7 Days", "> 7 Days", "Oldest Hours"];
+            for (col, title) in headers.iter().enumerate() {
+                worksheet.write_string_with_format(line, col as u16, *title, &header_format).map_err(xlsx_err)?;
+            }
+            for row in &report.queue {
+                line += 1;
+                worksheet.write_string(line, 0, &row.entity_type).map_err(xlsx_err)?;
+                let counts = [row.pending, row.under_1_day, row.from_1_to_3_days, row.from_3_to_7_days, row.over_7_days];
+                for (j, count) in counts.iter().enumerate() {
+                    worksheet.write_number(line, (j + 1) as u16, *count as f64).map_err(xlsx_err)?;
+                }
+                if let Some(hours) = row.oldest_hours {
+                    worksheet.write_number(line, 6, hours).map_err(xlsx_err)?;
+                }
+            }
+        }
+        "retention" => {
+            let report =
+                build_cohort_retention_report(&state.db, &state.config.platform_timezone).await?;
+
+            let mut headers = vec!["Cohort".to_string(), "Registered".to_string()];
+            for days in &report.windows {
+                headers.push(format!("Active {}d", days));
+                headers.push(format!("Active {}d Rate", days));
+            }
+            headers.push("Placed".to_string());
+            headers.push("Placement Rate".to_string());
+            for (col, title) in headers.iter().enumerate() {
+                worksheet.write_string_with_format(0, col as u16, title, &header_format).map_err(xlsx_err)?;
+            }
+
+            let totals = CohortRetentionRow {
+                cohort: "Total".to_string(),
+                registered: report.totals.registered,
+                retained: report.totals.retained.iter().copied().map(Some).collect(),
+                retention_rates: report.totals.retention_rates.clone(),
+                placed: report.totals.placed,
+                placement_rate: report.totals.placement_rate,
+            };
+
+            for (i, row) in report.cohorts.iter().chain([&totals]).enumerate() {
+                let line = (i + 1) as u32;
+                worksheet.write_string(line, 0, &row.cohort).map_err(xlsx_err)?;
+                worksheet.write_number(line, 1, row.registered as f64).map_err(xlsx_err)?;
+
+                for (j, (count, rate)) in row.retained.iter().zip(&row.retention_rates).enumerate() {
+                    let col = (2 + j * 2) as u16;
+                    if let Some(count) = count {
+                        worksheet.write_number(line, col, *count as f64).map_err(xlsx_err)?;
+                    }
+                    if let Some(rate) = rate {
+                        worksheet.write_number(line, col + 1, *rate).map_err(xlsx_err)?;
+                    }
+                }
+
+                let col = (2 + row.retained.len() * 2) as u16;
+                worksheet.write_number(line, col, row.placed as f64).map_err(xlsx_err)?;
+                if let Some(rate) = row.placement_rate {
+                    worksheet.write_number(line, col + 1, rate).map_err(xlsx_err)?;
+                }
+            }
+        }
+        _ => {
+            return Err(AppError::ValidationError(format!("Unknown report type: {}", report_type)));
+        }
+    }
+
+    let buffer = workbook.save_to_buffer()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))?;
+
+    let filename = format!("{}-report-{}.xlsx", report_type, Utc::now().format("%Y%m%d"));
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename))
+        .body(Body::from(buffer))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+
+    Ok(response)
+}
+
+// ============================================================================
+// V13: DATA RETENTION
+// ============================================================================
+
+/// GET /api/admin/retention/policies
+/// List the retention policy of every entity type
+pub async fn list_retention_policies(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+) -> Result<Json<Vec<RetentionPolicy>>, AppError> {
+    let policies = sqlx::query_as!(
+        RetentionPolicy,
+        r#"
+        SELECT
+            id,
+            entity_type as "entity_type: RetentionEntity",
+            action as "action: RetentionAction",
+            age_months,
+            is_enabled,
+            updated_by,
+            updated_at
+        FROM retention_policies
+        ORDER BY entity_type
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(policies))
+}
+
+/// PUT /api/admin/retention/policies/{id}
+/// Change a policy's action, age or enabled flag
+pub async fn update_retention_policy(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Path(policy_id): Path<Uuid>,
+    Json(payload): Json<UpdateRetentionPolicyRequest>,
+) -> Result<Json<RetentionPolicy>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let entity_type = sqlx::query_scalar!(
+        r#"
+        SELECT entity_type as "entity_type: RetentionEntity"
+        FROM retention_policies
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        policy_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Retention policy not found".to_string()))?;
+
+    if let Some(action) = payload.action {
+        if !entity_type.allowed_actions().contains(&action) {
+            return Err(AppError::ValidationError(
+                "This action cannot be applied to the policy's records".to_string(),
+            ));
+        }
+    }
+
+    let policy = sqlx::query_as!(
+        RetentionPolicy,
+        r#"
+        UPDATE retention_policies
+        SET
+            action = COALESCE($2, action),
+            age_months = COALESCE($3, age_months),
+            is_enabled = COALESCE($4, is_enabled),
+            updated_by = $5,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING
+            id,
+            entity_type as "entity_type: RetentionEntity",
+            action as "action: RetentionAction",
+            age_months,
+            is_enabled,
+            updated_by,
+            updated_at
+        "#,
+        policy_id,
+        payload.action as Option<RetentionAction>,
+        payload.age_months,
+        payload.is_enabled,
+        auth_user.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "update_retention_policy",
+        "retention_policy",
+        policy.id,
+        Some(json!({
+            "entity_type": policy.entity_type,
+            "action": policy.action,
+            "age_months": policy.age_months,
+            "is_enabled": policy.is_enabled,
+        })),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(policy))
+}
+
+/// POST /api/admin/retention/runs
+/// Apply the enabled policies now, or with dry_run only count what they
+/// would touch
+pub async fn run_retention(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<RunRetentionRequest>,
+) -> Result<Json<RetentionRunResponse>, AppError> {
+    let outcomes = retention::apply_policies(&state.db, payload.dry_run, Some(auth_user.id)).await?;
+
+    if !payload.dry_run {
+        for outcome in &outcomes {
+            log_admin_action(
+                &state.db,
+                admin.id,
+                "run_retention_policy",
+                "retention_policy",
+                outcome.policy_id,
+                Some(json!({ "affected_rows": outcome.affected_rows })),
+            )
+            .await?;
+        }
+    }
+
+    Ok(Json(RetentionRunResponse {
+        dry_run: payload.dry_run,
+        outcomes,
+    }))
+}
+
+/// POST /api/admin/maintenance/recount-applications
+/// Fix the jobs whose applications_count no longer matches their applications
+/// that are not withdrawn
+pub async fn recount_applications(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+) -> Result<Json<RecountApplicationsResponse>, AppError> {
+    let corrected = counters::recount_applications(&state.db).await?;
+
+    for drift in &corrected {
+        log_admin_action(
+            &state.db,
+            admin.id,
+            "recount_applications",
+            "job",
+            drift.job_id,
+            Some(json!({ "stored": drift.stored, "actual": drift.actual })),
+        )
+        .await?;
+    }
+
+    Ok(Json(RecountApplicationsResponse { corrected }))
+}
+
+/// GET /api/admin/retention/runs
+/// Recorded policy runs, newest first
+pub async fn list_retention_runs(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<RetentionRunParams>,
+) -> Result<Json<PaginatedResponse<RetentionRun>>, AppError> {
+    let limit = params.limit.unwrap_or(50).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) FROM retention_runs
+        WHERE ($1::retention_entity IS NULL OR entity_type = $1)
+        "#,
+        params.entity_type as Option<RetentionEntity>
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(0);
+
+    let runs = sqlx::query_as!(
+        RetentionRun,
+        r#"
+        SELECT
+            id,
+            policy_id,
+            entity_type as "entity_type: RetentionEntity",
+            action as "action: RetentionAction",
+            age_months,
+            affected_rows,
+            duration_ms,
+            error,
+            triggered_by,
+            started_at
+        FROM retention_runs
+        WHERE ($1::retention_entity IS NULL OR entity_type = $1)
+        ORDER BY started_at DESC, entity_type
+        LIMIT $2 OFFSET $3
+        "#,
+        params.entity_type as Option<RetentionEntity>,
+        limit,
+        offset
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: runs,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+// ============================================================================
+// STALE JOBS
+// ============================================================================
+
+/// GET /api/admin/stale-jobs/runs
+/// Weekly stale job runs with how many jobs were nudged and paused, newest
+/// first
+pub async fn list_stale_job_runs(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<PaginatedResponse<StaleJobRun>>, AppError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let total = sqlx::query_scalar!("SELECT COUNT(*) FROM stale_job_runs")
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(0);
+
+    let runs = sqlx::query_as!(
+        StaleJobRun,
+        r#"
+        SELECT id, ran_at, nudged_jobs, paused_jobs, notified_applicants
+        FROM stale_job_runs
+        ORDER BY ran_at DESC
+        LIMIT $1 OFFSET $2
+        "#,
+        limit,
+        offset
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse {
+        data: runs,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+// ============================================================================
+// REFERENCE DATA SEEDING
+// ============================================================================
+
+/// POST /api/admin/reference/seed
+/// Create or update reference data from a seed document, matched by natural
+/// key. Existing rows missing from the document are kept.
+pub async fn seed_reference_data(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<ReferenceSeed>,
+) -> Result<Json<ReferenceSeedReport>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let report = reference_seed::seed(&mut tx, &payload).await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "seed_reference_data",
+        "reference_data",
+        Uuid::nil(),
+        Some(json!(report)),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(report))
+}
+
+// ============================================================================
+// INSTITUTION CURATION
+// ============================================================================
+
+/// POST /api/admin/reference/institutions/merge
+/// Fold duplicate institutions into a canonical one, repointing education
+/// records and deactivating the duplicates
+pub async fn merge_institutions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<MergeInstitutionsRequest>,
+) -> Result<Json<MergeInstitutionsReport>, AppError> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let report = institutions::merge(&mut tx, &payload).await?;
+
+    log_admin_action(
+        &mut *tx,
+        admin.id,
+        "merge_institutions",
+        "institution",
+        payload.canonical_id,
+        Some(json!({
+            "duplicate_ids": payload.duplicate_ids,
+            "report": report,
+        })),
+    )
+    .await?;
 
-    Ok(Json(JobTrendsReport {
-        total_jobs,
-        active_jobs,
-        pending_jobs,
-        new_jobs_period,
-        trend,
-    }))
+    tx.commit().await?;
+
+    Ok(Json(report))
 }
 
-/// GET /api/admin/reports/applications
-/// Application trends report
-pub async fn report_applications(
+/// GET /api/admin/reference/institutions/unmatched
+/// Most common free-text institution names no reference entry matches
+pub async fn list_unmatched_institutions(
+    State(state): State<AppState>,
+    Query(query): Query<UnmatchedInstitutionsQuery>,
+) -> Result<Json<Vec<UnmatchedInstitutionName>>, AppError> {
+    let limit = query
+        .limit
+        .unwrap_or(institutions::DEFAULT_UNMATCHED_LIMIT)
+        .clamp(1, institutions::MAX_UNMATCHED_LIMIT);
+
+    Ok(Json(institutions::unmatched_names(&state.db, limit).await?))
+}
+
+// ============================================================================
+// BACKFILLS
+// ============================================================================
+
+/// GET /api/admin/backfills
+/// Registered data migrations and their progress
+pub async fn list_backfills(
     State(state): State<AppState>,
     Extension(_admin): Extension<Admin>,
-    Query(params): Query<ReportDateRangeParams>,
-) -> Result<Json<ApplicationTrendsReport>, AppError> {
-    let from_date = params.from_date.unwrap_or_else(|| {
-        Utc::now() - chrono::Duration::days(30)
-    });
-    let to_date = params.to_date.unwrap_or_else(Utc::now);
+) -> Result<Json<Vec<BackfillJob>>, AppError> {
+    Ok(Json(backfill::list(&state.db).await?))
+}
 
-    let total_applications: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM job_applications")
-        .fetch_one(&state.db)
-        .await?
-        .unwrap_or(0);
+/// POST /api/admin/backfills/{name}/start
+/// Start a backfill, or resume a paused or failed one from its cursor
+pub async fn start_backfill(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(name): Path<String>,
+) -> Result<Json<BackfillJob>, AppError> {
+    let job = backfill::transition(
+        &state.db,
+        &name,
+        &[
+            BackfillStatus::Pending,
+            BackfillStatus::Paused,
+            BackfillStatus::Failed,
+        ],
+        BackfillStatus::Running,
+        "Backfill is already running or completed",
+    )
+    .await?;
 
-    let new_applications_period: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM job_applications WHERE applied_at >= $1 AND applied_at <= $2",
-        from_date,
-        to_date
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "start_backfill",
+        "backfill",
+        Uuid::nil(),
+        Some(json!({ "backfill": job.name, "cursor": job.cursor })),
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    let by_status = sqlx::query!(
-        r#"
-        SELECT status::text as "status!", COUNT(*) as "count!"
-        FROM job_applications
-        GROUP BY status
-        "#
+    Ok(Json(job))
+}
+
+/// POST /api/admin/backfills/{name}/pause
+/// Stop a running backfill after its current batch
+pub async fn pause_backfill(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(name): Path<String>,
+) -> Result<Json<BackfillJob>, AppError> {
+    let job = backfill::transition(
+        &state.db,
+        &name,
+        &[BackfillStatus::Running],
+        BackfillStatus::Paused,
+        "Backfill is not running",
     )
-    .fetch_all(&state.db)
-    .await?
-    .into_iter()
-    .map(|r| ApplicationStatusCount {
-        status: r.status,
-        count: r.count,
-    })
-    .collect();
+    .await?;
 
-    let trend = sqlx::query!(
-        r#"
-        SELECT DATE(applied_at)::text as "date!", COUNT(*) as "count!"
-        FROM job_applications
-        WHERE applied_at >= $1 AND applied_at <= $2
-        GROUP BY DATE(applied_at)
-        ORDER BY DATE(applied_at)
-        "#,
-        from_date,
-        to_date
+    log_admin_action(
+        &state.db,
+        admin.id,
+        "pause_backfill",
+        "backfill",
+        Uuid::nil(),
+        Some(json!({ "backfill": job.name, "cursor": job.cursor })),
     )
-    .fetch_all(&state.db)
-    .await?
-    .into_iter()
-    .map(|r| TrendDataPoint {
-        date: r.date,
-        count: r.count,
-    })
-    .collect();
+    .await?;
 
-    Ok(Json(ApplicationTrendsReport {
-        total_applications,
-        new_applications_period,
-        by_status,
-        trend,
-    }))
+    Ok(Json(job))
 }
 
-/// GET /api/admin/reports/export/{type}
-/// Export report to Excel
-pub async fn export_report(
+// ============================================================================
+// USAGE METERING
+// ============================================================================
+
+/// GET /api/admin/usage
+/// Metered usage of every company in a month (?month=YYYY-MM, the current
+/// one by default), as of the last nightly aggregation
+pub async fn get_usage_report(
     State(state): State<AppState>,
     Extension(_admin): Extension<Admin>,
-    Path(report_type): Path<String>,
-    Query(params): Query<ReportDateRangeParams>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<UsageReport>, AppError> {
+    let month = metering::parse_month(
+        &state.db,
+        query.month.as_deref(),
+        &state.config.platform_timezone,
+    )
+    .await?;
+
+    Ok(Json(metering::usage_report(&state.db, month).await?))
+}
+
+/// GET /api/admin/usage/export
+/// The monthly usage report as a spreadsheet
+pub async fn export_usage_report(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(query): Query<UsageQuery>,
 ) -> Result<Response, AppError> {
-    let from_date = params.from_date.unwrap_or_else(|| {
-        Utc::now() - chrono::Duration::days(30)
-    });
-    let to_date = params.to_date.unwrap_or_else(Utc::now);
+    let month = metering::parse_month(
+        &state.db,
+        query.month.as_deref(),
+        &state.config.platform_timezone,
+    )
+    .await?;
+    let report = metering::usage_report(&state.db, month).await?;
+    let buffer = metering::render_usage_report(&report)?;
 
-    let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
-    let header_format = Format::new().set_bold();
-    let xlsx_err = |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+    let response = Response::builder()
+        .header(
+            header::CONTENT_TYPE,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"usage-{}.xlsx\"", report.month),
+        )
+        .body(Body::from(buffer))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
 
-    match report_type.as_str() {
-        "users" => {
-            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
-            worksheet.write_string_with_format(0, 1, "New Users", &header_format).map_err(xlsx_err)?;
+    Ok(response)
+}
 
-            let data = sqlx::query!(
-                r#"
-                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-                FROM users
-                WHERE created_at >= $1 AND created_at <= $2
-                GROUP BY DATE(created_at)
-                ORDER BY DATE(created_at)
-                "#,
-                from_date, to_date
-            )
-            .fetch_all(&state.db)
-            .await?;
+// ============================================================================
+// INTERNAL NOTES
+// ============================================================================
 
-            for (i, row) in data.iter().enumerate() {
-                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
-                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
-            }
-        }
-        "companies" => {
-            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
-            worksheet.write_string_with_format(0, 1, "New Companies", &header_format).map_err(xlsx_err)?;
+/// GET /api/admin/notes?entity_type=&entity_id=
+/// Internal notes on an entity, pinned first
+pub async fn list_notes(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<Admin>,
+    Query(query): Query<AdminNoteQuery>,
+) -> Result<Json<Vec<AdminNote>>, AppError> {
+    let notes = admin_notes::notes_for(&state.db, query.entity_type, query.entity_id).await?;
 
-            let data = sqlx::query!(
-                r#"
-                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-                FROM company_profiles
-                WHERE created_at >= $1 AND created_at <= $2
-                GROUP BY DATE(created_at)
-                ORDER BY DATE(created_at)
-                "#,
-                from_date, to_date
-            )
-            .fetch_all(&state.db)
-            .await?;
+    Ok(Json(notes))
+}
 
-            for (i, row) in data.iter().enumerate() {
-                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
-                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
-            }
-        }
-        "jobs" => {
-            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
-            worksheet.write_string_with_format(0, 1, "New Jobs", &header_format).map_err(xlsx_err)?;
+/// POST /api/admin/notes
+/// Add an internal note, notifying the admins it mentions
+pub async fn create_note(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Json(payload): Json<CreateAdminNoteRequest>,
+) -> Result<Json<AdminNote>, AppError> {
+    payload.validate()?;
 
-            let data = sqlx::query!(
-                r#"
-                SELECT DATE(created_at)::text as "date!", COUNT(*) as "count!"
-                FROM jobs
-                WHERE created_at >= $1 AND created_at <= $2
-                GROUP BY DATE(created_at)
-                ORDER BY DATE(created_at)
-                "#,
-                from_date, to_date
-            )
-            .fetch_all(&state.db)
-            .await?;
+    let note = admin_notes::create(&state.db, &admin, payload).await?;
 
-            for (i, row) in data.iter().enumerate() {
-                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
-                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
-            }
-        }
-        "applications" => {
-            worksheet.write_string_with_format(0, 0, "Date", &header_format).map_err(xlsx_err)?;
-            worksheet.write_string_with_format(0, 1, "New Applications", &header_format).map_err(xlsx_err)?;
+    Ok(Json(note))
+}
 
-            let data = sqlx::query!(
-                r#"
-                SELECT DATE(applied_at)::text as "date!", COUNT(*) as "count!"
-                FROM job_applications
-                WHERE applied_at >= $1 AND applied_at <= $2
-                GROUP BY DATE(applied_at)
-                ORDER BY DATE(applied_at)
-                "#,
-                from_date, to_date
-            )
-            .fetch_all(&state.db)
-            .await?;
+/// PATCH /api/admin/notes/{id}
+/// Edit or (un)pin a note; only its author or a super admin can
+pub async fn update_note(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(note_id): Path<Uuid>,
+    Json(payload): Json<UpdateAdminNoteRequest>,
+) -> Result<Json<AdminNote>, AppError> {
+    payload.validate()?;
 
-            for (i, row) in data.iter().enumerate() {
-                worksheet.write_string((i + 1) as u32, 0, &row.date).map_err(xlsx_err)?;
-                worksheet.write_number((i + 1) as u32, 1, row.count as f64).map_err(xlsx_err)?;
-            }
-        }
-        _ => {
-            return Err(AppError::ValidationError(format!("Unknown report type: {}", report_type)));
-        }
-    }
+    let note = admin_notes::update(&state.db, &admin, note_id, payload).await?;
 
-    let buffer = workbook.save_to_buffer()
-        .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))?;
+    Ok(Json(note))
+}
 
-    let filename = format!("{}-report-{}.xlsx", report_type, Utc::now().format("%Y%m%d"));
+/// DELETE /api/admin/notes/{id}
+/// Delete a note; only its author or a super admin can
+pub async fn delete_note(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(note_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    admin_notes::delete(&state.db, &admin, note_id).await?;
 
-    let response = Response::builder()
-        .header(header::CONTENT_TYPE, "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
-        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename))
-        .body(Body::from(buffer))
-        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+    Ok(Json(json!({ "message": "Note deleted successfully" })))
+}
 
-    Ok(response)
+/// GET /api/admin/notes/mentions
+/// Notes the current admin was mentioned in, newest first
+pub async fn list_note_mentions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Query(query): Query<AdminNoteMentionsQuery>,
+) -> Result<Json<Vec<AdminNoteMention>>, AppError> {
+    let mentions =
+        admin_notes::mentions_for(&state.db, admin.id, query.unread_only.unwrap_or(false)).await?;
+
+    Ok(Json(mentions))
+}
+
+/// POST /api/admin/notes/{id}/read
+/// Mark the current admin's mention in a note read
+pub async fn mark_note_mention_read(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Admin>,
+    Path(note_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    admin_notes::mark_mention_read(&state.db, admin.id, note_id).await?;
+
+    Ok(Json(json!({ "message": "Mention marked as read" })))
 }
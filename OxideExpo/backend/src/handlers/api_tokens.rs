@@ -0,0 +1,117 @@
+use axum::{
+    extract::State,
+    Extension, Json,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{AppError, Result},
+    extract::Path,
+    middleware::AuthUser,
+    models::api_token::*,
+    utils::jwt,
+    AppState,
+};
+
+/// Characters of the token kept in clear for display ("oxp_" plus 8)
+const TOKEN_PREFIX_LEN: usize = 12;
+
+/// POST /api/me/api-tokens
+/// Create a personal API token; the plaintext token is only returned here
+pub async fn create_api_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreatedApiToken>> {
+    payload.validate()?;
+
+    if payload.scopes.iter().any(|s| s == SCOPE_OMIL_READ) && auth_user.user_type != "omil_member" {
+        return Err(AppError::ForbiddenError(format!(
+            "Only OMIL members can create tokens with the {} scope",
+            SCOPE_OMIL_READ
+        )));
+    }
+
+    let token = jwt::create_api_token();
+    let expires_at = payload
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::days(days));
+
+    let mut scopes = payload.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let api_token = sqlx::query_as!(
+        ApiToken,
+        r#"
+        INSERT INTO api_tokens (user_id, name, token_prefix, token_hash, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id, user_id, name, token_prefix, scopes,
+            last_used_at, expires_at, revoked_at, created_at
+        "#,
+        auth_user.id,
+        payload.name.trim(),
+        &token[..TOKEN_PREFIX_LEN],
+        jwt::hash_token(&token),
+        &scopes,
+        expires_at,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(CreatedApiToken { api_token, token }))
+}
+
+/// GET /api/me/api-tokens
+/// List the current user's API tokens, newest first
+pub async fn list_api_tokens(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<ApiToken>>> {
+    let tokens = sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT
+            id, user_id, name, token_prefix, scopes,
+            last_used_at, expires_at, revoked_at, created_at
+        FROM api_tokens
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        auth_user.id,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(tokens))
+}
+
+/// DELETE /api/me/api-tokens/{id}
+/// Revoke an API token; it stops working on the next request
+pub async fn revoke_api_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(token_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE api_tokens SET revoked_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        token_id,
+        auth_user.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("API token not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "API token revoked successfully"
+    })))
+}
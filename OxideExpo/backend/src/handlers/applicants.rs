@@ -1,22 +1,44 @@
+use std::collections::HashMap;
+
 use axum::{
-    extract::{Path, Query, State},
-    http::header,
+    extract::State,
+    http::{header, StatusCode},
     response::IntoResponse,
     Extension, Json,
 };
+use chrono::{DateTime, Utc};
 use rust_xlsxwriter::{Workbook, Format};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
-    middleware::AuthUser,
+    extract::{Path, Query},
+    handlers::profile::fetch_user_skills,
+    middleware::{
+        omil_auth::{require_job_partner, OmilContext},
+        AuthUser,
+    },
     models::{
         applicant::*,
         application::ApplicationStatus,
-        company::MemberRole,
-        profile::JobSeekerProfile,
+        company::UsageEventType,
+        export::ExportType,
+        matching::SalaryFit,
+        omil::{PartnerJobApplicant, PartnerJobApplicants, PartnerJobStatusCount},
+        permissions::Capability,
+        profile::{JobSeekerProfile, SeekerAvailability},
+    },
+    services::{
+        application_documents, authz, availability, bulk_operations,
+        exports::{self, ExportOwner},
+        job_reposts,
+        matching::{self, MatchingService},
+        metering,
+        profile_fingerprint::{self, ProfileFingerprint},
+        profile_references, stale_jobs,
     },
+    utils::normalize::Normalize,
     AppState,
 };
 
@@ -24,27 +46,6 @@ use crate::{
 // HELPER FUNCTIONS
 // ============================================================================
 
-async fn get_user_company_membership(
-    db: &sqlx::PgPool,
-    user_id: Uuid,
-) -> Result<(Uuid, MemberRole)> {
-    let member = sqlx::query!(
-        r#"
-        SELECT company_id, role as "role: MemberRole"
-        FROM company_members
-        WHERE user_id = $1 AND is_active = true
-        "#,
-        user_id,
-    )
-    .fetch_optional(db)
-    .await?
-    .ok_or_else(|| {
-        AppError::ForbiddenError("User is not a member of any company".to_string())
-    })?;
-
-    Ok((member.company_id, member.role))
-}
-
 async fn verify_job_belongs_to_company(
     db: &sqlx::PgPool,
     job_id: Uuid,
@@ -64,8 +65,33 @@ async fn verify_job_belongs_to_company(
     Ok(())
 }
 
-fn is_owner_or_admin(role: MemberRole) -> bool {
-    matches!(role, MemberRole::Owner | MemberRole::Admin)
+/// Whether the company wants match scores in its applicant views
+async fn company_shows_match_scores(db: &sqlx::PgPool, company_id: Uuid) -> Result<bool> {
+    let show = sqlx::query_scalar!(
+        r#"SELECT show_match_scores FROM company_profiles WHERE id = $1"#,
+        company_id,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(show)
+}
+
+/// A cached score as the company sees it: hidden when the company turned
+/// scores off, and flagged when it is out of date
+fn visible_score(
+    show_match_scores: bool,
+    score: Option<i32>,
+    computed_at: Option<DateTime<Utc>>,
+    is_stale: Option<bool>,
+) -> (Option<i32>, Option<DateTime<Utc>>, bool) {
+    match (show_match_scores, score, computed_at) {
+        (true, Some(score), Some(computed_at)) => {
+            let is_stale = matching::is_score_stale(is_stale.unwrap_or(true), computed_at, Utc::now());
+            (Some(score), Some(computed_at), is_stale)
+        }
+        _ => (None, None, false),
+    }
 }
 
 // ============================================================================
@@ -80,27 +106,47 @@ pub async fn list_applicants(
     Path(job_id): Path<Uuid>,
     Query(query): Query<ApplicantFilterQuery>,
 ) -> Result<Json<PaginatedApplicants>> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
     verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+    stale_jobs::record_activity(&state.db, job_id).await?;
+    let show_match_scores = company_shows_match_scores(&state.db, company_id).await?;
 
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
+    let tag_ids = query
+        .tags
+        .as_deref()
+        .map(parse_tag_filter)
+        .transpose()
+        .map_err(AppError::ValidationError)?
+        .filter(|ids| !ids.is_empty());
+    let earlier_postings = job_reposts::predecessors(&state.db, job_id).await?;
 
     // Get total count
     let total: i64 = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!" FROM job_applications WHERE job_id = $1"#,
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM job_applications ja
+        WHERE ja.job_id = $1
+        AND ($2::uuid[] IS NULL OR (
+            SELECT COUNT(*) FROM application_tag_assignments ata
+            WHERE ata.application_id = ja.id AND ata.tag_id = ANY($2)
+        ) = cardinality($2))
+        AND ($4::bool IS NULL OR EXISTS(
+            SELECT 1 FROM job_applications p
+            WHERE p.applicant_id = ja.applicant_id AND p.job_id = ANY($3)
+        ) = $4)
+        "#,
         job_id,
+        tag_ids.as_deref(),
+        &earlier_postings,
+        query.applied_to_previous,
     )
     .fetch_one(&state.db)
     .await?;
 
-    // Get paginated results
+    // Get paginated results; tags are aggregated per row to avoid a query per applicant
     let rows = sqlx::query!(
         r#"
         SELECT
@@ -108,39 +154,92 @@ pub async fn list_applicants(
             ja.applicant_id,
             ja.status as "status: ApplicationStatus",
             ja.applied_at,
+            ja.is_on_hold,
             CONCAT(u.first_name, ' ', u.last_name) as "applicant_name!",
             u.email as applicant_email,
-            (ja.resume_url IS NOT NULL OR jsp.cv_file_id IS NOT NULL) as "has_cv!"
+            (ja.resume_url IS NOT NULL OR jsp.cv_file_id IS NOT NULL) as "has_cv!",
+            t.tag_ids as "tag_ids!",
+            t.tag_names as "tag_names!",
+            t.tag_colors as "tag_colors!",
+            ms.total_score as "match_score?",
+            ms.computed_at as "score_computed_at?",
+            ms.is_stale as "score_is_stale?",
+            EXISTS(
+                SELECT 1 FROM job_applications p
+                WHERE p.applicant_id = ja.applicant_id AND p.job_id = ANY($5)
+            ) as "applied_to_previous!"
         FROM job_applications ja
         JOIN users u ON u.id = ja.applicant_id
         LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = ja.applicant_id
+        LEFT JOIN job_match_scores ms ON ms.job_id = ja.job_id AND ms.user_id = ja.applicant_id
+        CROSS JOIN LATERAL (
+            SELECT
+                COALESCE(array_agg(at.id ORDER BY at.name), '{}') as tag_ids,
+                COALESCE(array_agg(at.name ORDER BY at.name), '{}') as tag_names,
+                COALESCE(array_agg(at.color ORDER BY at.name), '{}') as tag_colors
+            FROM application_tag_assignments ata
+            JOIN application_tags at ON at.id = ata.tag_id
+            WHERE ata.application_id = ja.id
+        ) t
         WHERE ja.job_id = $1
+        AND ($4::uuid[] IS NULL OR (
+            SELECT COUNT(*) FROM application_tag_assignments ata
+            WHERE ata.application_id = ja.id AND ata.tag_id = ANY($4)
+        ) = cardinality($4))
+        AND ($6::bool IS NULL OR EXISTS(
+            SELECT 1 FROM job_applications p
+            WHERE p.applicant_id = ja.applicant_id AND p.job_id = ANY($5)
+        ) = $6)
         ORDER BY ja.applied_at DESC
         LIMIT $2 OFFSET $3
         "#,
         job_id,
         limit,
         offset,
+        tag_ids.as_deref(),
+        &earlier_postings,
+        query.applied_to_previous,
     )
     .fetch_all(&state.db)
     .await?;
 
     let applicants: Vec<ApplicantListItem> = rows
         .into_iter()
-        .map(|row| ApplicantListItem {
-            application_id: row.application_id,
-            applicant_id: row.applicant_id,
-            status: row.status,
-            applied_at: row.applied_at,
-            applicant_name: row.applicant_name,
-            applicant_email: row.applicant_email,
-            match_score: None,
-            has_cv: row.has_cv,
+        .map(|row| {
+            let (match_score, match_score_computed_at, match_score_is_stale) = visible_score(
+                show_match_scores,
+                row.match_score,
+                row.score_computed_at,
+                row.score_is_stale,
+            );
+
+            ApplicantListItem {
+                application_id: row.application_id,
+                applicant_id: row.applicant_id,
+                status: row.status,
+                applied_at: row.applied_at,
+                applicant_name: row.applicant_name,
+                applicant_email: row.applicant_email,
+                match_score,
+                match_score_computed_at,
+                match_score_is_stale,
+                has_cv: row.has_cv,
+                is_on_hold: row.is_on_hold,
+                applied_to_previous: row.applied_to_previous,
+                tags: row
+                    .tag_ids
+                    .into_iter()
+                    .zip(row.tag_names)
+                    .zip(row.tag_colors)
+                    .map(|((id, name), color)| AssignedTag { id, name, color })
+                    .collect(),
+            }
         })
         .collect();
 
     Ok(Json(PaginatedApplicants {
         applicants,
+        show_match_scores,
         total,
         limit,
         offset,
@@ -153,15 +252,13 @@ pub async fn get_applicant_detail(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path((job_id, app_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<ApplicantDetailQuery>,
 ) -> Result<Json<ApplicantDetailResponse>> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
     verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+    stale_jobs::record_activity(&state.db, job_id).await?;
+    let show_match_scores = company_shows_match_scores(&state.db, company_id).await?;
 
     // Get application
     let app = sqlx::query!(
@@ -170,8 +267,9 @@ pub async fn get_applicant_detail(
             ja.id, ja.job_id, ja.applicant_id,
             ja.status as "status: ApplicationStatus",
             ja.cover_letter, ja.resume_url, ja.applied_at,
-            ja.reviewed_at, ja.interview_date, ja.interview_notes,
-            ja.offer_date, ja.offer_details
+            ja.reviewed_at, ja.interview_date, ja.interview_notes, ja.interview_location,
+            ja.offer_date, ja.offer_details, ja.is_on_hold,
+            ja.profile_fingerprint
         FROM job_applications ja
         WHERE ja.id = $1 AND ja.job_id = $2
         "#,
@@ -182,6 +280,56 @@ pub async fn get_applicant_detail(
     .await?
     .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
 
+    // Recalculating is skipped when scores are hidden, so it costs nothing
+    if show_match_scores && query.recalculate.unwrap_or(false) {
+        if !matching::take_recalculation_slot(&mut state.redis.clone(), company_id).await {
+            return Err(AppError::TooManyRequests(format!(
+                "At most {} match score recalculations per hour; recalculate the whole job instead",
+                matching::RECALCULATIONS_PER_HOUR
+            )));
+        }
+        let breakdown =
+            MatchingService::calculate_match_score(&state.db, job_id, app.applicant_id).await?;
+        MatchingService::save_match_score(&state.db, job_id, app.applicant_id, &breakdown).await?;
+    }
+
+    let cached = sqlx::query!(
+        r#"
+        SELECT total_score, computed_at, is_stale
+        FROM job_match_scores
+        WHERE job_id = $1 AND user_id = $2
+        "#,
+        job_id,
+        app.applicant_id,
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    let (match_score, match_score_computed_at, match_score_is_stale) = visible_score(
+        show_match_scores,
+        cached.as_ref().map(|c| c.total_score),
+        cached.as_ref().map(|c| c.computed_at),
+        cached.as_ref().map(|c| c.is_stale),
+    );
+
+    // Only the coarse indicator; the seeker's amounts never leave here
+    let salary_expectation_vs_range = sqlx::query_scalar!(
+        r#"
+        SELECT salary_expectation_fit(
+            j.salary_min, j.salary_max, j.salary_currency, j.salary_period,
+            p.salary_expectation_min, p.salary_expectation_max,
+            COALESCE(p.salary_currency, 'CLP')
+        ) as "fit: SalaryFit"
+        FROM jobs j
+        JOIN job_seeker_preferences p ON p.user_id = $2
+        WHERE j.id = $1 AND p.show_salary_expectation
+        "#,
+        job_id,
+        app.applicant_id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .flatten();
+
     // Get profile
     let profile = sqlx::query_as!(
         JobSeekerProfile,
@@ -195,6 +343,9 @@ pub async fn get_applicant_detail(
             bio, professional_headline,
             profile_image_url, cv_url,
             completeness_percentage,
+            availability as "availability: crate::models::profile::SeekerAvailability",
+            CASE WHEN share_available_from THEN available_from END as available_from,
+            share_available_from,
             created_at, updated_at
         FROM job_seeker_profiles
         WHERE user_id = $1
@@ -204,6 +355,11 @@ pub async fn get_applicant_detail(
     .fetch_optional(&state.db)
     .await?;
 
+    let skills = fetch_user_skills(&state.db, &[app.applicant_id])
+        .await?
+        .remove(&app.applicant_id)
+        .unwrap_or_default();
+
     // Get status history
     let history_rows = sqlx::query!(
         r#"
@@ -254,6 +410,30 @@ pub async fn get_applicant_detail(
     .await?
     .map(|path| format!("/api/files/download/{}", path));
 
+    let documents = application_documents::list_with_download_urls(
+        &state.db,
+        state.storage.as_ref(),
+        app.id,
+    )
+    .await?;
+
+    let references = profile_references::for_company(
+        &state.db,
+        state.storage.as_ref(),
+        app.applicant_id,
+    )
+    .await?;
+
+    // Only a diff signal against the fingerprint taken when they applied
+    let profile_changes = match ProfileFingerprint::from_json(app.profile_fingerprint) {
+        Some(then) => {
+            let mut conn = state.db.acquire().await?;
+            let now = profile_fingerprint::take(&mut conn, app.applicant_id).await?;
+            Some(profile_fingerprint::compare(&then, &now))
+        }
+        None => None,
+    };
+
     Ok(Json(ApplicantDetailResponse {
         application_id: app.id,
         job_id: app.job_id,
@@ -265,12 +445,252 @@ pub async fn get_applicant_detail(
         reviewed_at: app.reviewed_at,
         interview_date: app.interview_date,
         interview_notes: app.interview_notes,
+        interview_location: app.interview_location,
         offer_date: app.offer_date,
         offer_details: app.offer_details,
+        is_on_hold: app.is_on_hold,
         profile,
-        match_score: None,
+        skills,
+        match_score,
+        match_score_computed_at,
+        match_score_is_stale,
+        salary_expectation_vs_range,
         cv_url,
+        documents,
+        references,
         status_history,
+        profile_changed_since_application: profile_changes.as_ref().is_some_and(|c| c.any()),
+        profile_changes,
+    }))
+}
+
+/// GET /api/me/jobs/{id}/applicants/compare?ids=a,b,c
+/// Compare 2-5 applications of the job side by side. The queries are batched
+/// over the whole shortlist, so their number doesn't grow with it.
+pub async fn compare_applicants(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Query(query): Query<CompareApplicantsQuery>,
+) -> Result<Json<ApplicantComparison>> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
+
+    let ids = parse_compared_ids(&query.ids).map_err(AppError::ValidationError)?;
+
+    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+    stale_jobs::record_activity(&state.db, job_id).await?;
+    let show_match_scores = company_shows_match_scores(&state.db, company_id).await?;
+
+    // Sections the seeker keeps private come back NULL
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            ja.id as application_id,
+            ja.applicant_id,
+            ja.status as "status: ApplicationStatus",
+            ja.applied_at,
+            CONCAT(u.first_name, ' ', u.last_name) as "applicant_name!",
+            ms.total_score as "total_score?",
+            ms.skills_score as "skills_score?",
+            ms.preferred_skills_score as "preferred_skills_score?",
+            ms.languages_score as "languages_score?",
+            ms.location_score as "location_score?",
+            ms.experience_score as "experience_score?",
+            ms.education_score as "education_score?",
+            ms.accommodations_score as "accommodations_score?",
+            ms.computed_at as "score_computed_at?",
+            ms.is_stale as "score_is_stale?",
+            exp.total_years as "years_of_experience!",
+            edu.level as "highest_education?",
+            jsp.availability as "availability?: SeekerAvailability",
+            CASE WHEN jsp.share_available_from THEN jsp.available_from END as available_from,
+            COALESCE(p.show_salary_expectation, false) as "show_salary_expectation!",
+            p.salary_expectation_min as "salary_expectation_min?",
+            p.salary_expectation_max as "salary_expectation_max?",
+            p.salary_currency as "salary_currency?"
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = ja.applicant_id
+        LEFT JOIN job_seeker_preferences p ON p.user_id = ja.applicant_id
+        LEFT JOIN job_match_scores ms ON ms.job_id = ja.job_id AND ms.user_id = ja.applicant_id
+        CROSS JOIN LATERAL (
+            SELECT COALESCE(
+                SUM(EXTRACT(YEAR FROM AGE(COALESCE(we.end_date, CURRENT_DATE), we.start_date)))::INTEGER,
+                0
+            ) as total_years
+            FROM work_experiences we
+            WHERE we.user_id = ja.applicant_id
+        ) exp
+        LEFT JOIN LATERAL (
+            SELECT er.level::text as level
+            FROM education_records er
+            WHERE er.user_id = ja.applicant_id
+            ORDER BY
+                CASE er.level
+                    WHEN 'postgraduate' THEN 7
+                    WHEN 'graduate' THEN 6
+                    WHEN 'undergraduate' THEN 5
+                    WHEN 'technical' THEN 4
+                    WHEN 'secondary' THEN 3
+                    WHEN 'primary' THEN 2
+                    WHEN 'none' THEN 1
+                    ELSE 0
+                END DESC
+            LIMIT 1
+        ) edu ON true
+        WHERE ja.job_id = $1 AND ja.id = ANY($2)
+        "#,
+        job_id,
+        &ids,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    if rows.len() != ids.len() {
+        return Err(AppError::NotFound(
+            "Some applications were not found for this job".to_string(),
+        ));
+    }
+    let applicant_ids: Vec<Uuid> = rows.iter().map(|r| r.applicant_id).collect();
+
+    // Each required skill once per candidate, with the candidate's level if any
+    let skill_rows = sqlx::query!(
+        r#"
+        SELECT
+            a.applicant_id as "applicant_id!",
+            rs.skill_id,
+            s.name,
+            rs.minimum_proficiency,
+            us.proficiency_level as "proficiency_level?",
+            EXISTS(
+                SELECT 1 FROM skill_endorsements e WHERE e.user_skill_id = us.id
+            ) as "is_endorsed!"
+        FROM job_required_skills rs
+        JOIN skills s ON s.id = rs.skill_id
+        CROSS JOIN UNNEST($2::uuid[]) AS a(applicant_id)
+        LEFT JOIN user_skills us ON us.user_id = a.applicant_id AND us.skill_id = rs.skill_id
+        WHERE rs.job_id = $1
+        ORDER BY s.name
+        "#,
+        job_id,
+        &applicant_ids,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let language_rows = sqlx::query!(
+        r#"
+        SELECT ul.user_id, l.name, ul.proficiency as "proficiency: String"
+        FROM user_languages ul
+        JOIN languages l ON l.id = ul.language_id
+        WHERE ul.user_id = ANY($1)
+        ORDER BY ul.proficiency DESC, l.name
+        "#,
+        &applicant_ids,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut required_skills = Vec::new();
+    let mut skills: HashMap<Uuid, (Vec<String>, Vec<String>)> = HashMap::new();
+    for row in skill_rows {
+        if row.applicant_id == applicant_ids[0] {
+            required_skills.push(ComparedSkillRequirement {
+                skill_id: row.skill_id,
+                name: row.name.clone(),
+                minimum_proficiency: row.minimum_proficiency,
+            });
+        }
+        let meets = row.proficiency_level.is_some_and(|level| {
+            let bonus = if row.is_endorsed {
+                matching::ENDORSEMENT_PROFICIENCY_BONUS
+            } else {
+                0
+            };
+            level + bonus >= row.minimum_proficiency
+        });
+        let (matched, missing) = skills.entry(row.applicant_id).or_default();
+        if meets {
+            matched.push(row.name);
+        } else {
+            missing.push(row.name);
+        }
+    }
+
+    let mut languages: HashMap<Uuid, Vec<ComparedLanguage>> = HashMap::new();
+    for row in language_rows {
+        languages
+            .entry(row.user_id)
+            .or_default()
+            .push(ComparedLanguage {
+                name: row.name,
+                proficiency: row.proficiency,
+            });
+    }
+
+    let mut candidates: Vec<ComparedCandidate> = rows
+        .into_iter()
+        .map(|row| {
+            let (match_score, _, _) = visible_score(
+                show_match_scores,
+                row.total_score,
+                row.score_computed_at,
+                row.score_is_stale,
+            );
+            let match_score = match (match_score, row.score_computed_at) {
+                (Some(total_score), Some(computed_at)) => Some(ComparedMatchScore {
+                    total_score,
+                    skills_score: row.skills_score.unwrap_or_default(),
+                    preferred_skills_score: row.preferred_skills_score.unwrap_or_default(),
+                    languages_score: row.languages_score.unwrap_or_default(),
+                    location_score: row.location_score.unwrap_or_default(),
+                    experience_score: row.experience_score.unwrap_or_default(),
+                    education_score: row.education_score.unwrap_or_default(),
+                    accommodations_score: row.accommodations_score.unwrap_or_default(),
+                    computed_at,
+                    is_stale: matching::is_score_stale(
+                        row.score_is_stale.unwrap_or(true),
+                        computed_at,
+                        Utc::now(),
+                    ),
+                }),
+                _ => None,
+            };
+            let (matched_required_skills, missing_required_skills) =
+                skills.remove(&row.applicant_id).unwrap_or_default();
+
+            ComparedCandidate {
+                application_id: row.application_id,
+                applicant_id: row.applicant_id,
+                applicant_name: row.applicant_name,
+                status: row.status,
+                applied_at: row.applied_at,
+                match_score,
+                years_of_experience: row.years_of_experience,
+                highest_education: row.highest_education,
+                matched_required_skills,
+                missing_required_skills,
+                languages: languages.remove(&row.applicant_id).unwrap_or_default(),
+                salary_expectation: row.show_salary_expectation.then(|| {
+                    ComparedSalaryExpectation {
+                        min: row.salary_expectation_min,
+                        max: row.salary_expectation_max,
+                        currency: row.salary_currency.unwrap_or_else(|| "CLP".to_string()),
+                    }
+                }),
+                availability: row.availability,
+                available_from: row.available_from,
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|c| ids.iter().position(|id| *id == c.application_id));
+
+    Ok(Json(ApplicantComparison {
+        job_id,
+        required_skills,
+        show_match_scores,
+        candidates,
     }))
 }
 
@@ -281,13 +701,8 @@ pub async fn get_applicant_cv(
     Extension(auth_user): Extension<AuthUser>,
     Path((job_id, app_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<CvDownloadResponse>> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
     verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
 
     // Get applicant_id from application
@@ -314,6 +729,15 @@ pub async fn get_applicant_cv(
     .await?
     .ok_or_else(|| AppError::NotFound("CV not found for this applicant".to_string()))?;
 
+    metering::record(
+        &state.db,
+        company_id,
+        UsageEventType::CvDownload,
+        1,
+        serde_json::json!({ "job_id": job_id, "application_id": app_id }),
+    )
+    .await;
+
     Ok(Json(CvDownloadResponse {
         download_url: format!("/api/files/download/{}", cv.storage_path),
         filename: cv.original_filename,
@@ -328,13 +752,8 @@ pub async fn get_applicant_history(
     Extension(auth_user): Extension<AuthUser>,
     Path((job_id, app_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Vec<StatusHistoryWithUser>>> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
     verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
 
     // Verify application belongs to job
@@ -389,63 +808,217 @@ pub async fn get_applicant_history(
 }
 
 /// POST /api/me/jobs/{id}/applicants/bulk-status
-/// Update status of multiple applications at once
+/// Update status of multiple applications at once. Selections above
+/// ASYNC_THRESHOLD are queued and reported as a bulk operation.
 pub async fn bulk_status_update(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
     Json(payload): Json<BulkStatusUpdateRequest>,
-) -> Result<Json<BulkStatusUpdateResponse>> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
+) -> Result<(StatusCode, Json<BulkStatusUpdateResponse>)> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ManageApplicants).await?;
 
     payload.validate()?;
 
-    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
+    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+    stale_jobs::record_activity(&state.db, job_id).await?;
 
-    if !is_owner_or_admin(role) {
+    if payload.status == ApplicationStatus::Withdrawn {
         return Err(AppError::ForbiddenError(
-            "Only owners and admins can perform bulk updates".to_string(),
+            "Only the applicant can withdraw an application".to_string(),
         ));
     }
 
-    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+    let current: HashMap<Uuid, ApplicationStatus> = sqlx::query!(
+        r#"
+        SELECT id, status as "status: ApplicationStatus"
+        FROM job_applications
+        WHERE id = ANY($1) AND job_id = $2
+        "#,
+        &payload.application_ids,
+        job_id,
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.status))
+    .collect();
 
-    let mut updated_count = 0;
+    // Validate every transition up front; illegal moves are reported, not fatal
+    let mut eligible = Vec::new();
     let mut failed_ids = Vec::new();
+    let mut skipped = Vec::new();
 
     for app_id in &payload.application_ids {
-        let result = sqlx::query!(
-            r#"
-            UPDATE job_applications
-            SET status = $1, reviewed_by = $2, reviewed_at = NOW()
-            WHERE id = $3 AND job_id = $4
-            "#,
-            payload.status as ApplicationStatus,
-            auth_user.id,
-            app_id,
+        match current.get(app_id) {
+            None => failed_ids.push(*app_id),
+            Some(current_status) if !current_status.can_transition_to(payload.status) => {
+                skipped.push(SkippedStatusUpdate {
+                    application_id: *app_id,
+                    current_status: *current_status,
+                    allowed_statuses: current_status.company_transitions(),
+                });
+            }
+            Some(_) => eligible.push(*app_id),
+        }
+    }
+
+    if eligible.len() > bulk_operations::ASYNC_THRESHOLD {
+        let operation = bulk_operations::enqueue(
+            &state.db,
             job_id,
+            auth_user.id,
+            payload.status,
+            &eligible,
+            &failed_ids,
+            skipped.len() as i32,
         )
-        .execute(&state.db)
-        .await;
+        .await?;
 
-        match result {
-            Ok(res) if res.rows_affected() > 0 => {
-                updated_count += 1;
-            }
-            _ => {
-                failed_ids.push(*app_id);
-            }
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(BulkStatusUpdateResponse {
+                updated_count: 0,
+                failed_ids,
+                skipped,
+                chunks: Vec::new(),
+                operation: Some(operation),
+            }),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let mut updated_ids = Vec::new();
+    let mut chunks = Vec::new();
+
+    for (index, chunk) in eligible.chunks(bulk_operations::BULK_CHUNK_SIZE).enumerate() {
+        let updated =
+            bulk_operations::update_chunk(&mut tx, job_id, chunk, payload.status, auth_user.id)
+                .await?;
+
+        // Changed by someone else since validation
+        failed_ids.extend(chunk.iter().filter(|id| !updated.contains(id)));
+        chunks.push(BulkChunkSummary {
+            chunk: index as i32,
+            requested: chunk.len() as i32,
+            updated: updated.len() as i32,
+        });
+        updated_ids.extend(updated);
+    }
+
+    tx.commit().await?;
+
+    if payload.status == ApplicationStatus::Hired {
+        if let Err(e) = availability::prompt_after_hire(&state, &updated_ids).await {
+            tracing::error!("Failed to prompt availability update: {}", e);
         }
     }
+    let updated_count = updated_ids.len() as i32;
 
-    Ok(Json(BulkStatusUpdateResponse {
-        updated_count,
-        failed_ids,
-    }))
+    Ok((
+        StatusCode::OK,
+        Json(BulkStatusUpdateResponse {
+            updated_count,
+            failed_ids,
+            skipped,
+            chunks,
+            operation: None,
+        }),
+    ))
+}
+
+/// GET /api/me/jobs/{id}/bulk-operations/{op_id}
+/// Get the progress of a queued bulk status update
+pub async fn get_bulk_operation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, operation_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<BulkOperation>> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
+    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+
+    let operation = bulk_operations::get_operation(&state.db, job_id, operation_id).await?;
+    Ok(Json(operation))
+}
+
+/// POST /api/me/jobs/{id}/match-scores/recalculate
+/// Queue a rescore of every applicant of the job for the background worker
+pub async fn recalculate_match_scores(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<MatchScoreRecalculation>)> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
+    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+
+    if !company_shows_match_scores(&state.db, company_id).await? {
+        return Err(AppError::ValidationError(
+            "Match scores are turned off for this company".to_string(),
+        ));
+    }
+
+    let requested_at = matching::request_job_recalculation(&state.db, job_id, auth_user.id).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(MatchScoreRecalculation {
+            job_id,
+            requested_at,
+        }),
+    ))
+}
+
+/// GET /api/me/jobs/{id}/match-recompute-status
+/// Progress of the background rescore of the job's match scores
+pub async fn get_match_recompute_status(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<MatchRecomputeStatus>> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
+    verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
+
+    let request = sqlx::query!(
+        r#"
+        SELECT requirements_changed, total_seekers, processed_seekers, requested_at, started_at
+        FROM match_score_recalculations
+        WHERE job_id = $1
+        "#,
+        job_id,
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let status = match request {
+        Some(request) => MatchRecomputeStatus {
+            job_id,
+            state: if request.started_at.is_some() {
+                MatchRecomputeState::Running
+            } else {
+                MatchRecomputeState::Queued
+            },
+            requirements_changed: request.requirements_changed,
+            total_seekers: request.total_seekers,
+            processed_seekers: request.processed_seekers,
+            requested_at: Some(request.requested_at),
+            started_at: request.started_at,
+        },
+        None => MatchRecomputeStatus {
+            job_id,
+            state: MatchRecomputeState::Idle,
+            requirements_changed: false,
+            total_seekers: None,
+            processed_seekers: 0,
+            requested_at: None,
+            started_at: None,
+        },
+    };
+
+    Ok(Json(status))
 }
 
 /// GET /api/me/jobs/{id}/applicants/export
@@ -456,13 +1029,8 @@ pub async fn export_applicants(
     Path(job_id): Path<Uuid>,
     Query(query): Query<ExportApplicantsQuery>,
 ) -> Result<impl IntoResponse> {
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ExportData).await?;
     verify_job_belongs_to_company(&state.db, job_id, company_id).await?;
 
     // Get job title for filename
@@ -480,18 +1048,35 @@ pub async fn export_applicants(
             ja.id, ja.status as "status: ApplicationStatus",
             ja.applied_at, ja.cover_letter,
             u.first_name, u.last_name, u.email,
-            jsp.phone, jsp.professional_headline
+            jsp.phone, jsp.professional_headline,
+            ARRAY(
+                SELECT at.name
+                FROM application_tag_assignments ata
+                JOIN application_tags at ON at.id = ata.tag_id
+                WHERE ata.application_id = ja.id
+                ORDER BY at.name
+            ) as "tags!"
         FROM job_applications ja
         JOIN users u ON u.id = ja.applicant_id
         LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = ja.applicant_id
         WHERE ja.job_id = $1
+        AND ($2::application_status IS NULL OR ja.status = $2)
         ORDER BY ja.applied_at DESC
         "#,
         job_id,
+        query.status as Option<ApplicationStatus>,
     )
     .fetch_all(&state.db)
     .await?;
 
+    let export = exports::begin(
+        &state.db,
+        ExportOwner::Company(company_id),
+        auth_user.id,
+        query.include_contact,
+    )
+    .await?;
+
     // Create Excel workbook
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
@@ -503,7 +1088,7 @@ pub async fn export_applicants(
     let xlsx_err = |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
 
     // Write headers
-    let include_contact = query.include_contact.unwrap_or(true);
+    let include_contact = export.include_contact;
     let mut col = 0u16;
     worksheet.write_string_with_format(0, col, "Name", &header_format).map_err(xlsx_err)?;
     col += 1;
@@ -518,6 +1103,8 @@ pub async fn export_applicants(
     worksheet.write_string_with_format(0, col, "Applied At", &header_format).map_err(xlsx_err)?;
     col += 1;
     worksheet.write_string_with_format(0, col, "Headline", &header_format).map_err(xlsx_err)?;
+    col += 1;
+    worksheet.write_string_with_format(0, col, "Tags", &header_format).map_err(xlsx_err)?;
 
     // Write data rows
     for (row_idx, app) in applicants.iter().enumerate() {
@@ -529,7 +1116,7 @@ pub async fn export_applicants(
         col += 1;
 
         if include_contact {
-            worksheet.write_string(row, col, &app.email).map_err(xlsx_err)?;
+            worksheet.write_string(row, col, app.email.as_deref().unwrap_or("")).map_err(xlsx_err)?;
             col += 1;
             worksheet.write_string(row, col, app.phone.as_deref().unwrap_or("")).map_err(xlsx_err)?;
             col += 1;
@@ -539,16 +1126,34 @@ pub async fn export_applicants(
         worksheet.write_string(row, col, &status_str).map_err(xlsx_err)?;
         col += 1;
 
-        worksheet.write_string(row, col, &app.applied_at.format("%Y-%m-%d %H:%M").to_string()).map_err(xlsx_err)?;
+        worksheet.write_string(row, col, app.applied_at.format("%Y-%m-%d %H:%M").to_string()).map_err(xlsx_err)?;
         col += 1;
 
         worksheet.write_string(row, col, app.professional_headline.as_deref().unwrap_or("")).map_err(xlsx_err)?;
+        col += 1;
+
+        worksheet.write_string(row, col, app.tags.join(", ")).map_err(xlsx_err)?;
     }
 
+    export.watermark.stamp(&mut workbook, applicants.len() as u32 + 2)?;
+
     // Generate Excel file
     let buffer = workbook.save_to_buffer()
         .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))?;
 
+    export
+        .record(
+            &state.db,
+            ExportType::Applicants,
+            serde_json::json!({
+                "job_id": job_id,
+                "status": query.status,
+                "include_contact": query.include_contact,
+            }),
+            applicants.len(),
+        )
+        .await?;
+
     // Sanitize filename
     let safe_title: String = job_title
         .chars()
@@ -566,3 +1171,327 @@ pub async fn export_applicants(
         buffer,
     ))
 }
+
+// ============================================================================
+// V13: APPLICANT TAGS
+// ============================================================================
+
+/// Company of the calling company member
+async fn require_company_member(state: &AppState, auth_user: &AuthUser) -> Result<Uuid> {
+    authz::require_company(&state.db, auth_user, Capability::ViewCompanyJobs).await
+}
+
+fn tag_name_conflict(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::ConflictError("A tag with this name already exists".to_string())
+        }
+        _ => AppError::DatabaseError(e),
+    }
+}
+
+/// Tags currently on an application
+async fn fetch_assigned_tags(db: &sqlx::PgPool, application_id: Uuid) -> Result<Vec<AssignedTag>> {
+    let tags = sqlx::query_as!(
+        AssignedTag,
+        r#"
+        SELECT at.id, at.name, at.color
+        FROM application_tag_assignments ata
+        JOIN application_tags at ON at.id = ata.tag_id
+        WHERE ata.application_id = $1
+        ORDER BY at.name
+        "#,
+        application_id,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(tags)
+}
+
+/// Checks the application is on one of the company's jobs and the tag is the company's own
+async fn verify_tag_target(
+    db: &sqlx::PgPool,
+    company_id: Uuid,
+    job_id: Uuid,
+    app_id: Uuid,
+    tag_id: Uuid,
+) -> Result<()> {
+    verify_job_belongs_to_company(db, job_id, company_id).await?;
+
+    let application_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM job_applications WHERE id = $1 AND job_id = $2) as "exists!""#,
+        app_id,
+        job_id,
+    )
+    .fetch_one(db)
+    .await?;
+
+    if !application_exists {
+        return Err(AppError::NotFound("Application not found".to_string()));
+    }
+
+    let tag_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM application_tags WHERE id = $1 AND company_id = $2) as "exists!""#,
+        tag_id,
+        company_id,
+    )
+    .fetch_one(db)
+    .await?;
+
+    if !tag_exists {
+        return Err(AppError::NotFound("Tag not found".to_string()));
+    }
+
+    Ok(())
+}
+
+/// GET /api/me/company/tags
+/// List the company's applicant tags
+pub async fn list_tags(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<ApplicationTag>>> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let tags = sqlx::query_as!(
+        ApplicationTag,
+        r#"
+        SELECT id, company_id, name, color, created_at, updated_at
+        FROM application_tags
+        WHERE company_id = $1
+        ORDER BY name
+        "#,
+        company_id,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(tags))
+}
+
+/// POST /api/me/company/tags
+/// Define a new applicant tag
+pub async fn create_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<CreateApplicationTagRequest>,
+) -> Result<Json<ApplicationTag>> {
+    payload.normalize();
+    payload.validate()?;
+
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM application_tags WHERE company_id = $1"#,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if count >= MAX_APPLICATION_TAGS {
+        return Err(AppError::ValidationError(format!(
+            "A company can have at most {} tags",
+            MAX_APPLICATION_TAGS
+        )));
+    }
+
+    let tag = sqlx::query_as!(
+        ApplicationTag,
+        r#"
+        INSERT INTO application_tags (company_id, name, color, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, company_id, name, color, created_at, updated_at
+        "#,
+        company_id,
+        payload.name,
+        payload.color.as_deref().unwrap_or(DEFAULT_TAG_COLOR),
+        auth_user.id,
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(tag_name_conflict)?;
+
+    Ok(Json(tag))
+}
+
+/// PUT /api/me/company/tags/{id}
+/// Rename or recolor a tag
+pub async fn update_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(tag_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateApplicationTagRequest>,
+) -> Result<Json<ApplicationTag>> {
+    payload.normalize();
+    payload.validate()?;
+
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let tag = sqlx::query_as!(
+        ApplicationTag,
+        r#"
+        UPDATE application_tags
+        SET name = COALESCE($3, name), color = COALESCE($4, color)
+        WHERE id = $1 AND company_id = $2
+        RETURNING id, company_id, name, color, created_at, updated_at
+        "#,
+        tag_id,
+        company_id,
+        payload.name,
+        payload.color,
+    )
+    .fetch_optional(&state.db)
+    .await
+    .map_err(tag_name_conflict)?
+    .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))?;
+
+    Ok(Json(tag))
+}
+
+/// DELETE /api/me/company/tags/{id}
+/// Delete a tag; it is removed from every applicant carrying it
+pub async fn delete_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(tag_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM application_tags WHERE id = $1 AND company_id = $2",
+        tag_id,
+        company_id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Tag not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Tag deleted successfully" })))
+}
+
+/// POST /api/me/jobs/{job_id}/applicants/{app_id}/tags/{tag_id}
+/// Tag an applicant; returns the application's tags
+pub async fn assign_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, app_id, tag_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<Vec<AssignedTag>>> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+    verify_tag_target(&state.db, company_id, job_id, app_id, tag_id).await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO application_tag_assignments (application_id, tag_id, assigned_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (application_id, tag_id) DO NOTHING
+        "#,
+        app_id,
+        tag_id,
+        auth_user.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(fetch_assigned_tags(&state.db, app_id).await?))
+}
+
+/// DELETE /api/me/jobs/{job_id}/applicants/{app_id}/tags/{tag_id}
+/// Remove a tag from an applicant; returns the application's tags
+pub async fn unassign_tag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, app_id, tag_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<Vec<AssignedTag>>> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+    verify_tag_target(&state.db, company_id, job_id, app_id, tag_id).await?;
+
+    sqlx::query!(
+        "DELETE FROM application_tag_assignments WHERE application_id = $1 AND tag_id = $2",
+        app_id,
+        tag_id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(fetch_assigned_tags(&state.db, app_id).await?))
+}
+
+// ============================================================================
+// V13: PARTNER OMIL VIEW
+// ============================================================================
+
+/// GET /api/me/omil/partner-jobs/{id}/applicants
+/// Applicants of a job co-published with the caller's OMIL. Only the OMIL's
+/// own managed seekers are listed; counts cover every applicant.
+pub async fn list_partner_applicants(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<PartnerJobApplicants>> {
+    require_job_partner(&state.db, &omil_ctx, job_id).await?;
+
+    let job = sqlx::query!(
+        r#"
+        SELECT j.title, c.company_name
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        WHERE j.id = $1
+        "#,
+        job_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let status_counts = sqlx::query_as!(
+        PartnerJobStatusCount,
+        r#"
+        SELECT status as "status: ApplicationStatus", COUNT(*) as "count!"
+        FROM job_applications
+        WHERE job_id = $1
+        GROUP BY status
+        ORDER BY status
+        "#,
+        job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Seekers transferred out now belong to the receiving OMIL
+    let applicants = sqlx::query_as!(
+        PartnerJobApplicant,
+        r#"
+        SELECT
+            ja.id as application_id,
+            mjs.id as managed_id,
+            ja.applicant_id as job_seeker_id,
+            (u.first_name || ' ' || u.last_name) as "job_seeker_name!",
+            ja.status as "status: ApplicationStatus",
+            ja.applied_at
+        FROM job_applications ja
+        JOIN omil_managed_job_seekers mjs
+            ON mjs.job_seeker_id = ja.applicant_id
+            AND mjs.omil_id = $2
+            AND mjs.transferred_at IS NULL
+        JOIN users u ON u.id = ja.applicant_id
+        WHERE ja.job_id = $1
+        ORDER BY ja.applied_at DESC
+        "#,
+        job_id,
+        omil_ctx.organization.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PartnerJobApplicants {
+        job_id,
+        job_title: job.title,
+        company_name: job.company_name,
+        total_applications: status_counts.iter().map(|c| c.count).sum(),
+        status_counts,
+        applicants,
+    }))
+}
@@ -1,5 +1,6 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Multipart, State},
+    http::HeaderMap,
     Extension, Json,
 };
 use chrono::Utc;
@@ -9,8 +10,16 @@ use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
+    handlers::referrals::fetch_job_partner,
     middleware::AuthUser,
-    models::{application::*, job::*},
+    models::{admin::AggregateType, application::*, file::FileDeleteResponse, job::*},
+    services::{
+        application_documents,
+        applications::{self, NewApplication},
+        browsing, counters, cover_letters, events, job_reposts,
+    },
+    utils::{fields::FieldSelection, normalize::Normalize},
     AppState,
 };
 
@@ -23,8 +32,8 @@ use crate::{
 pub async fn submit_application(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(payload): Json<CreateApplicationRequest>,
-) -> Result<Json<JobApplication>> {
+    Json(mut payload): Json<CreateApplicationRequest>,
+) -> Result<Json<SubmitApplicationResponse>> {
     // Only job seekers can apply
     if auth_user.user_type != "job_seeker" {
         return Err(AppError::ForbiddenError(
@@ -34,113 +43,78 @@ pub async fn submit_application(
 
     payload.validate()?;
 
-    // Check if job exists and is active
-    let job = sqlx::query_as!(
-        Job,
-        r#"
-        SELECT
-            id, company_id, posted_by,
-            title, description, responsibilities,
-            job_type as "job_type: JobType",
-            industry_id, work_area_id, position_level_id,
-            work_modality as "work_modality: WorkModality",
-            work_schedule,
-            region_id, municipality_id, is_remote_allowed,
-            education_level, years_experience_min, years_experience_max,
-            age_min, age_max,
-            salary_min as "salary_min: _",
-            salary_max as "salary_max: _",
-            salary_currency, salary_period, benefits,
-            application_deadline, contact_email, application_url,
-            vacancies, applications_count,
-            status as "status: JobStatus",
-            approved_at, approved_by, rejection_reason,
-            completeness_percentage, is_featured, views_count,
-            created_at, updated_at
-        FROM jobs
-        WHERE id = $1 AND status = 'active'
-        "#,
-        payload.job_id,
-    )
-    .fetch_optional(&state.db)
-    .await?
-    .ok_or_else(|| {
-        AppError::ValidationError("Job not found or not active".to_string())
-    })?;
-
-    // Check application deadline
-    if job.application_deadline < Utc::now().date_naive() {
+    if payload.template_id.is_some() && payload.cover_letter.is_some() {
         return Err(AppError::ValidationError(
-            "Application deadline has passed".to_string(),
+            "Send either a cover letter or a template, not both".to_string(),
         ));
     }
 
-    // Check if user already applied
-    let already_applied = sqlx::query_scalar!(
-        r#"
-        SELECT EXISTS(SELECT 1 FROM job_applications WHERE job_id = $1 AND applicant_id = $2)
-        "#,
-        payload.job_id,
-        auth_user.id,
-    )
-    .fetch_one(&state.db)
-    .await?;
+    let draft = fetch_draft(&state.db, auth_user.id, payload.job_id).await?;
 
-    if already_applied.unwrap_or(false) {
-        return Err(AppError::ValidationError(
-            "You have already applied to this job".to_string(),
-        ));
-    }
+    // Taken up front so concurrent submissions can't overshoot the cap
+    let slots = applications::take_daily_slots(&state, auth_user.id, None, Utc::now()).await?;
+    let submitted = async {
+        let mut tx = state.db.begin().await?;
 
-    // Check profile completeness (must be >= 50%)
-    let profile_completeness = sqlx::query_scalar!(
-        r#"
-        SELECT completeness_percentage
-        FROM job_seeker_profiles
-        WHERE user_id = $1
-        "#,
-        auth_user.id,
-    )
-    .fetch_optional(&state.db)
-    .await?;
+        let mut warnings = Vec::new();
+        if let Some(notice) = applications::mismatch_notice(&mut tx, auth_user.id).await? {
+            warnings.push(notice);
+        }
+        if let Some(notice) =
+            applications::salary_notice(&mut tx, payload.job_id, auth_user.id).await?
+        {
+            warnings.push(notice);
+        }
+        if let Some(notice) = job_reposts::previous_application_notice(
+            &mut tx,
+            payload.job_id,
+            auth_user.id,
+            &state.config.platform_timezone,
+        )
+        .await?
+        {
+            warnings.push(notice);
+        }
+        if let Some(template_id) = payload.template_id {
+            let expanded =
+                cover_letters::use_template(&mut tx, auth_user.id, template_id, payload.job_id)
+                    .await?;
+            warnings.extend(expanded.warnings());
+            payload.cover_letter = Some(expanded.text);
+        }
 
-    if let Some(completeness) = profile_completeness {
-        if completeness < 50 {
-            return Err(AppError::ValidationError(
-                "Your profile must be at least 50% complete to apply for jobs".to_string(),
-            ));
+        // Anything left out of the submission comes from the autosaved draft
+        if let Some(ref draft) = draft {
+            payload.fill_from_draft(draft);
         }
-    } else {
-        return Err(AppError::ValidationError(
-            "You must complete your profile before applying for jobs".to_string(),
-        ));
-    }
 
-    // Create application
-    let application = sqlx::query_as!(
-        JobApplication,
-        r#"
-        INSERT INTO job_applications (job_id, applicant_id, cover_letter, resume_url, status)
-        VALUES ($1, $2, $3, $4, 'submitted')
-        RETURNING
-            id, job_id, applicant_id,
-            status as "status: ApplicationStatus",
-            cover_letter, resume_url, applied_at,
-            reviewed_at, reviewed_by,
-            interview_date, interview_notes,
-            offer_date, offer_details, response_date,
-            withdrawal_reason,
-            created_at, updated_at
-        "#,
-        payload.job_id,
-        auth_user.id,
-        payload.cover_letter,
-        payload.resume_url,
-    )
-    .fetch_one(&state.db)
-    .await?;
+        let application = applications::submit(
+            &mut tx,
+            &NewApplication {
+                job_id: payload.job_id,
+                applicant_id: auth_user.id,
+                cover_letter: payload.cover_letter,
+                resume_url: payload.resume_url,
+                source: ApplicationSource::Direct,
+            },
+        )
+        .await?;
+        tx.commit().await?;
+        Ok::<_, AppError>((application, warnings))
+    }
+    .await;
+    let (application, warnings) = match submitted {
+        Ok(submitted) => submitted,
+        Err(e) => {
+            slots.give_back(&mut state.redis.clone()).await;
+            return Err(e);
+        }
+    };
 
-    Ok(Json(application))
+    Ok(Json(SubmitApplicationResponse {
+        application: applications::run_automation(&state, application).await?,
+        warnings,
+    }))
 }
 
 /// GET /api/me/applications
@@ -165,9 +139,11 @@ pub async fn list_my_applications(
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         FROM job_applications
         WHERE applicant_id = $1
@@ -179,7 +155,9 @@ pub async fn list_my_applications(
     .await?;
 
     let mut result = Vec::new();
-    for application in applications {
+    for mut application in applications {
+        application.hide_unshared_rejection();
+
         // Get job details (public view)
         let job = sqlx::query!(
             r#"
@@ -188,7 +166,9 @@ pub async fn list_my_applications(
                 j.job_type as "job_type: JobType",
                 j.industry_id, j.work_area_id, j.position_level_id,
                 j.work_modality as "work_modality: WorkModality",
-                j.work_schedule,
+                j.schedule_details,
+                j.shift_type as "shift_type: ShiftType",
+                j.weekly_hours,
                 j.region_id, j.municipality_id, j.is_remote_allowed,
                 j.education_level, j.years_experience_min, j.years_experience_max,
                 j.benefits, j.application_deadline, j.contact_email, j.application_url,
@@ -213,7 +193,14 @@ pub async fn list_my_applications(
             work_area_id: job.work_area_id,
             position_level_id: job.position_level_id,
             work_modality: job.work_modality,
-            work_schedule: job.work_schedule,
+            schedule_display: schedule_display(
+                job.shift_type,
+                job.weekly_hours,
+                job.schedule_details.as_deref(),
+            ),
+            schedule_details: job.schedule_details,
+            shift_type: job.shift_type,
+            weekly_hours: job.weekly_hours,
             region_id: job.region_id,
             municipality_id: job.municipality_id,
             is_remote_allowed: job.is_remote_allowed.unwrap_or(false),
@@ -255,7 +242,7 @@ pub async fn get_application(
     }
 
     // Get application and verify ownership
-    let application = sqlx::query_as!(
+    let mut application = sqlx::query_as!(
         JobApplication,
         r#"
         SELECT
@@ -263,9 +250,11 @@ pub async fn get_application(
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         FROM job_applications
         WHERE id = $1 AND applicant_id = $2
@@ -276,6 +265,7 @@ pub async fn get_application(
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
+    application.hide_unshared_rejection();
 
     // Get job details (public view)
     let job = sqlx::query!(
@@ -285,7 +275,9 @@ pub async fn get_application(
             j.job_type as "job_type: JobType",
             j.industry_id, j.work_area_id, j.position_level_id,
             j.work_modality as "work_modality: WorkModality",
-            j.work_schedule,
+            j.schedule_details,
+            j.shift_type as "shift_type: ShiftType",
+            j.weekly_hours,
             j.region_id, j.municipality_id, j.is_remote_allowed,
             j.education_level, j.years_experience_min, j.years_experience_max,
             j.benefits, j.application_deadline, j.contact_email, j.application_url,
@@ -310,7 +302,14 @@ pub async fn get_application(
         work_area_id: job.work_area_id,
         position_level_id: job.position_level_id,
         work_modality: job.work_modality,
-        work_schedule: job.work_schedule,
+        schedule_display: schedule_display(
+            job.shift_type,
+            job.weekly_hours,
+            job.schedule_details.as_deref(),
+        ),
+        schedule_details: job.schedule_details,
+        shift_type: job.shift_type,
+        weekly_hours: job.weekly_hours,
         region_id: job.region_id,
         municipality_id: job.municipality_id,
         is_remote_allowed: job.is_remote_allowed.unwrap_or(false),
@@ -335,7 +334,7 @@ pub async fn get_application(
 }
 
 /// PATCH /api/me/applications/{id}/withdraw
-/// Withdraw application (only if status is submitted/under_review/shortlisted)
+/// Withdraw application (any status before hired/rejected)
 pub async fn withdraw_application(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -360,9 +359,11 @@ pub async fn withdraw_application(
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         FROM job_applications
         WHERE id = $1 AND applicant_id = $2
@@ -374,62 +375,673 @@ pub async fn withdraw_application(
     .await?
     .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
 
-    // Check if withdrawal is allowed (only for submitted/under_review/shortlisted)
-    match application.status {
-        ApplicationStatus::Submitted
-        | ApplicationStatus::UnderReview
-        | ApplicationStatus::Shortlisted => {}
-        _ => {
-            return Err(AppError::ValidationError(
-                "Cannot withdraw application in current status".to_string(),
-            ))
-        }
+    // Withdrawal is possible from any non-terminal status
+    if !application.status.can_transition_to(ApplicationStatus::Withdrawn) {
+        return Err(AppError::invalid_status_transition(
+            application.status,
+            ApplicationStatus::Withdrawn,
+            &[],
+        ));
     }
 
+    let mut tx = state.db.begin().await?;
+
     // Update to withdrawn
     let updated_application = sqlx::query_as!(
         JobApplication,
         r#"
         UPDATE job_applications
         SET status = 'withdrawn', withdrawal_reason = $1
-        WHERE id = $2 AND applicant_id = $3
+        WHERE id = $2 AND applicant_id = $3 AND status = $4
         RETURNING
             id, job_id, applicant_id,
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         "#,
         payload.withdrawal_reason,
         app_id,
         auth_user.id,
+        application.status as ApplicationStatus,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::ConflictError("Application status changed, please reload".to_string())
+    })?;
+
+    counters::applications_removed(&mut tx, &[updated_application.job_id]).await?;
+    events::emit(
+        &mut tx,
+        AggregateType::Application,
+        updated_application.id,
+        "withdrawn",
+        Some(auth_user.id),
+        serde_json::json!({
+            "from": application.status,
+            "reason": updated_application.withdrawal_reason,
+        }),
     )
-    .fetch_one(&state.db)
     .await?;
 
+    tx.commit().await?;
+
     Ok(Json(updated_application))
 }
 
+/// GET /api/me/applications/{id}/timeline
+/// Status changes of one of the seeker's applications, oldest first
+pub async fn get_application_timeline(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(app_id): Path<Uuid>,
+) -> Result<Json<Vec<ApplicationTimelineEntry>>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let rejection = sqlx::query!(
+        r#"
+        SELECT
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback,
+            rejection_feedback_shared
+        FROM job_applications
+        WHERE id = $1 AND applicant_id = $2
+        "#,
+        app_id,
+        auth_user.id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
+
+    let history = sqlx::query!(
+        r#"
+        SELECT
+            previous_status as "previous_status: ApplicationStatus",
+            new_status as "new_status: ApplicationStatus",
+            created_at
+        FROM application_status_history
+        WHERE application_id = $1
+        ORDER BY created_at, id
+        "#,
+        app_id,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let timeline = history
+        .into_iter()
+        .map(|row| {
+            let show_rejection = rejection.rejection_feedback_shared
+                && row.new_status == ApplicationStatus::Rejected;
+            ApplicationTimelineEntry {
+                previous_status: row.previous_status,
+                status: row.new_status,
+                rejection_reason_code: rejection
+                    .rejection_reason_code
+                    .filter(|_| show_rejection),
+                rejection_feedback: rejection
+                    .rejection_feedback
+                    .clone()
+                    .filter(|_| show_rejection),
+                created_at: row.created_at,
+            }
+        })
+        .collect();
+
+    Ok(Json(timeline))
+}
+
+// ============================================================================
+// V13: APPLICATION DRAFTS
+// ============================================================================
+
+pub(crate) async fn fetch_draft(
+    db: &sqlx::PgPool,
+    user_id: Uuid,
+    job_id: Uuid,
+) -> Result<Option<ApplicationDraft>> {
+    let draft = sqlx::query_as!(
+        ApplicationDraft,
+        r#"
+        SELECT id, user_id, job_id, cover_letter, answers, created_at, updated_at
+        FROM application_drafts
+        WHERE user_id = $1 AND job_id = $2
+        "#,
+        user_id,
+        job_id,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(draft)
+}
+
+fn require_job_seeker(auth_user: &AuthUser) -> Result<()> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// GET /api/me/jobs/{job_id}/application-draft
+/// Get the current user's draft application for a job
+pub async fn get_application_draft(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<ApplicationDraft>> {
+    require_job_seeker(&auth_user)?;
+
+    let draft = fetch_draft(&state.db, auth_user.id, job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Draft not found".to_string()))?;
+
+    Ok(Json(draft))
+}
+
+/// PUT /api/me/jobs/{job_id}/application-draft
+/// Autosave a draft application, replacing the previous version.
+/// A paused job keeps its drafts readable and deletable, but refuses saves
+/// with a conflict until it is reactivated
+pub async fn save_application_draft(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<SaveApplicationDraftRequest>,
+) -> Result<Json<ApplicationDraft>> {
+    require_job_seeker(&auth_user)?;
+    payload.validate()?;
+
+    // Drafts only make sense while the job takes applications
+    let job_status = sqlx::query_scalar!(
+        r#"
+        SELECT status as "status: JobStatus" FROM jobs
+        WHERE id = $1 AND application_deadline >= CURRENT_DATE
+        "#,
+        job_id,
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    match job_status {
+        Some(JobStatus::Active) => {}
+        Some(JobStatus::Paused) => {
+            return Err(AppError::ConflictError(
+                "The job is paused; drafts can be saved again once it reopens".to_string(),
+            ));
+        }
+        _ => {
+            return Err(AppError::NotFound(
+                "Job not found or not active".to_string(),
+            ))
+        }
+    }
+
+    let already_applied = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM job_applications WHERE job_id = $1 AND applicant_id = $2
+        ) as "exists!"
+        "#,
+        job_id,
+        auth_user.id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if already_applied {
+        return Err(AppError::ConflictError(
+            "You have already applied to this job".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Autosave fires on every pause in typing; saves closer together than the
+    // interval are refused so the client keeps its latest copy and retries
+    let draft = sqlx::query_as!(
+        ApplicationDraft,
+        r#"
+        INSERT INTO application_drafts (user_id, job_id, cover_letter, answers)
+        VALUES ($1, $2, $3, COALESCE($4, '{}'::jsonb))
+        ON CONFLICT (user_id, job_id) DO UPDATE
+        SET cover_letter = EXCLUDED.cover_letter,
+            answers = EXCLUDED.answers,
+            updated_at = NOW()
+        WHERE application_drafts.updated_at <= NOW() - make_interval(secs => $5)
+        RETURNING id, user_id, job_id, cover_letter, answers, created_at, updated_at
+        "#,
+        auth_user.id,
+        job_id,
+        payload.cover_letter,
+        payload.answers,
+        DRAFT_SAVE_INTERVAL_SECONDS as f64,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::TooManyRequests(format!(
+            "Draft was saved less than {} seconds ago",
+            DRAFT_SAVE_INTERVAL_SECONDS
+        ))
+    })?;
+
+    // Evict the oldest drafts beyond the per-user limit
+    sqlx::query!(
+        r#"
+        DELETE FROM application_drafts
+        WHERE user_id = $1 AND id <> $2
+        AND id NOT IN (
+            SELECT id FROM application_drafts
+            WHERE user_id = $1 AND id <> $2
+            ORDER BY updated_at DESC
+            LIMIT $3
+        )
+        "#,
+        auth_user.id,
+        draft.id,
+        MAX_APPLICATION_DRAFTS - 1,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(draft))
+}
+
+/// DELETE /api/me/jobs/{job_id}/application-draft
+/// Discard a draft application
+pub async fn delete_application_draft(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    require_job_seeker(&auth_user)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM application_drafts WHERE user_id = $1 AND job_id = $2",
+        auth_user.id,
+        job_id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Draft not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Draft deleted successfully" })))
+}
+
+// ============================================================================
+// V13: COVER LETTER TEMPLATES
+// ============================================================================
+
+fn template_name_conflict(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::ConflictError("A template with this name already exists".to_string())
+        }
+        _ => AppError::DatabaseError(e),
+    }
+}
+
+/// GET /api/me/cover-letter-templates
+/// List the seeker's cover letter templates, most used first
+pub async fn list_cover_letter_templates(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<CoverLetterTemplate>>> {
+    require_job_seeker(&auth_user)?;
+
+    Ok(Json(
+        cover_letters::list_templates(&state.db, auth_user.id).await?,
+    ))
+}
+
+/// POST /api/me/cover-letter-templates
+/// Save a new cover letter template
+pub async fn create_cover_letter_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<CreateCoverLetterTemplateRequest>,
+) -> Result<Json<CoverLetterTemplate>> {
+    require_job_seeker(&auth_user)?;
+    payload.normalize();
+    payload.validate()?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM cover_letter_templates WHERE user_id = $1"#,
+        auth_user.id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if count >= MAX_COVER_LETTER_TEMPLATES {
+        return Err(AppError::ValidationError(format!(
+            "You can have at most {} cover letter templates",
+            MAX_COVER_LETTER_TEMPLATES
+        )));
+    }
+
+    let template = sqlx::query_as!(
+        CoverLetterTemplate,
+        r#"
+        INSERT INTO cover_letter_templates (user_id, name, body)
+        VALUES ($1, $2, $3)
+        RETURNING id, user_id, name, body, use_count, last_used_at, created_at, updated_at
+        "#,
+        auth_user.id,
+        payload.name,
+        payload.body,
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(template_name_conflict)?;
+
+    Ok(Json(template))
+}
+
+/// PUT /api/me/cover-letter-templates/{id}
+/// Rename or rewrite a template; applications already sent keep their text
+pub async fn update_cover_letter_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(template_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateCoverLetterTemplateRequest>,
+) -> Result<Json<CoverLetterTemplate>> {
+    require_job_seeker(&auth_user)?;
+    payload.normalize();
+    payload.validate()?;
+
+    let template = sqlx::query_as!(
+        CoverLetterTemplate,
+        r#"
+        UPDATE cover_letter_templates
+        SET name = COALESCE($3, name), body = COALESCE($4, body), updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, name, body, use_count, last_used_at, created_at, updated_at
+        "#,
+        template_id,
+        auth_user.id,
+        payload.name,
+        payload.body,
+    )
+    .fetch_optional(&state.db)
+    .await
+    .map_err(template_name_conflict)?
+    .ok_or_else(|| AppError::NotFound("Cover letter template not found".to_string()))?;
+
+    Ok(Json(template))
+}
+
+/// DELETE /api/me/cover-letter-templates/{id}
+/// Delete a template
+pub async fn delete_cover_letter_template(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    require_job_seeker(&auth_user)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM cover_letter_templates WHERE id = $1 AND user_id = $2",
+        template_id,
+        auth_user.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            "Cover letter template not found".to_string(),
+        ));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Template deleted successfully" })))
+}
+
+// ============================================================================
+// V13: APPLICATION DOCUMENTS
+// ============================================================================
+
+/// Status of one of the seeker's own applications
+async fn own_application_status(
+    db: &sqlx::PgPool,
+    auth_user: &AuthUser,
+    app_id: Uuid,
+) -> Result<ApplicationStatus> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can manage application documents".to_string(),
+        ));
+    }
+
+    sqlx::query_scalar!(
+        r#"
+        SELECT status as "status: ApplicationStatus"
+        FROM job_applications
+        WHERE id = $1 AND applicant_id = $2
+        "#,
+        app_id,
+        auth_user.id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Application not found".to_string()))
+}
+
+/// GET /api/me/applications/{id}/documents
+/// Documents attached to one of the seeker's applications
+pub async fn list_application_documents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(app_id): Path<Uuid>,
+) -> Result<Json<Vec<ApplicationDocument>>> {
+    own_application_status(&state.db, &auth_user, app_id).await?;
+
+    let documents = application_documents::list(&state.db, app_id).await?;
+
+    Ok(Json(documents))
+}
+
+/// POST /api/me/applications/{id}/documents
+/// Attach a document (multipart `file` and optional `label`) while the
+/// application is submitted or under review
+pub async fn upload_application_document(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(app_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<ApplicationDocument>> {
+    let status = own_application_status(&state.db, &auth_user, app_id).await?;
+
+    let document =
+        application_documents::attach(&state, app_id, status, auth_user.id, &mut multipart)
+            .await?;
+
+    Ok(Json(document))
+}
+
+/// DELETE /api/me/applications/{id}/documents/{document_id}
+/// Remove a document from one of the seeker's applications
+pub async fn delete_application_document(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((app_id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<FileDeleteResponse>> {
+    own_application_status(&state.db, &auth_user, app_id).await?;
+
+    application_documents::delete(&state.db, app_id, document_id).await?;
+
+    Ok(Json(FileDeleteResponse {
+        message: "Document deleted successfully".to_string(),
+    }))
+}
+
 // ============================================================================
 // PUBLIC JOB LISTING ENDPOINTS
 // ============================================================================
 
+/// Radius used when a location filter is given without radius_km
+const DEFAULT_COMMUTE_RADIUS_KM: f64 = 30.0;
+const MAX_COMMUTE_RADIUS_KM: f64 = 500.0;
+
+/// Origin and radius of an active commute filter
+struct CommuteFilter {
+    latitude: f64,
+    longitude: f64,
+    radius_km: f64,
+}
+
+/// Resolves the commute filter of a job search, if any. `near_me` uses the
+/// signed-in seeker's profile municipality.
+async fn resolve_commute_filter(
+    state: &AppState,
+    params: &PublicJobListQuery,
+    auth_user: Option<&AuthUser>,
+) -> Result<Option<CommuteFilter>> {
+    let municipality_id = if params.near_me.unwrap_or(false) {
+        let auth_user = auth_user.ok_or_else(|| {
+            AppError::AuthenticationError("Sign in to search jobs near you".to_string())
+        })?;
+        if auth_user.user_type != "job_seeker" {
+            return Err(AppError::ForbiddenError(
+                "Only job seekers can search jobs near them".to_string(),
+            ));
+        }
+
+        sqlx::query_scalar!(
+            "SELECT municipality_id FROM job_seeker_profiles WHERE user_id = $1",
+            auth_user.id
+        )
+        .fetch_optional(&state.db)
+        .await?
+        .flatten()
+        .ok_or_else(|| {
+            AppError::ValidationError(
+                "Set your municipality in your profile to search jobs near you".to_string(),
+            )
+        })?
+    } else {
+        match params.near_municipality_id {
+            Some(id) => id,
+            None => return Ok(None),
+        }
+    };
+
+    let radius_km = params.radius_km.unwrap_or(DEFAULT_COMMUTE_RADIUS_KM);
+    if !(radius_km > 0.0 && radius_km <= MAX_COMMUTE_RADIUS_KM) {
+        return Err(AppError::ValidationError(format!(
+            "radius_km must be greater than 0 and at most {}",
+            MAX_COMMUTE_RADIUS_KM
+        )));
+    }
+
+    let origin = sqlx::query!(
+        "SELECT latitude, longitude FROM municipalities WHERE id = $1",
+        municipality_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Municipality not found".to_string()))?;
+
+    match (origin.latitude, origin.longitude) {
+        (Some(latitude), Some(longitude)) => Ok(Some(CommuteFilter {
+            latitude,
+            longitude,
+            radius_km,
+        })),
+        _ => Err(AppError::ValidationError(
+            "Distance search is not available for this municipality".to_string(),
+        )),
+    }
+}
+
 /// GET /api/jobs
 /// List active jobs (no authentication required)
+///
+/// Pass the response's `next_cursor` back as `cursor` for the next page.
+/// Unlike page and offset, a cursor never repeats or skips jobs when others
+/// are published in between. Distance searches page by offset only.
 pub async fn list_public_jobs(
     State(state): State<AppState>,
+    auth_user: Option<Extension<AuthUser>>,
     Query(params): Query<PublicJobListQuery>,
-) -> Result<Json<PublicJobListResponse>> {
+) -> Result<(HeaderMap, Json<serde_json::Value>)> {
+    let fields = FieldSelection::parse(params.fields.as_deref(), PUBLIC_JOB_LIST_SECTIONS);
+
     // Support both page/per_page and limit/offset pagination
     let per_page = params.per_page.or(params.limit).unwrap_or(20).min(100);
     let page = params.page.unwrap_or(1).max(1);
     let offset = params.offset.unwrap_or_else(|| (page - 1) * per_page);
+    let cursor = params
+        .cursor
+        .as_deref()
+        .map(|token| {
+            PublicJobCursor::decode(token)
+                .ok_or_else(|| AppError::ValidationError("Invalid cursor".to_string()))
+        })
+        .transpose()?;
+
+    let commute =
+        resolve_commute_filter(&state, &params, auth_user.as_ref().map(|u| &u.0)).await?;
+    if cursor.is_some() && commute.is_some() {
+        return Err(AppError::ValidationError(
+            "Distance searches page with page or offset, not cursor".to_string(),
+        ));
+    }
+
+    // Remember the seeker's search for GET /api/me/recent-searches
+    if let (Some(Extension(user)), Some(filters)) =
+        (auth_user.as_ref(), browsing::search_filters(&params))
+    {
+        if user.user_type == "job_seeker" {
+            let db = state.db.clone();
+            let user_id = user.id;
+            tokio::spawn(async move {
+                if let Err(e) = browsing::record_search(&db, user_id, filters).await {
+                    tracing::error!("Failed to record job search: {}", e);
+                }
+            });
+        }
+    }
+
+    // Great-circle distance from the filter origin to the job's municipality
+    let push_distance =
+        |query_builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>, filter: &CommuteFilter| {
+            query_builder.push("haversine_km(");
+            query_builder.push_bind(filter.latitude);
+            query_builder.push(", ");
+            query_builder.push_bind(filter.longitude);
+            query_builder.push(", jm.latitude, jm.longitude)");
+        };
 
     // Helper to build WHERE clause conditions
     let build_where_clause = |query_builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>| {
+        // Remote-allowed jobs are reachable from anywhere
+        if let Some(ref filter) = commute {
+            query_builder.push(" AND (COALESCE(j.is_remote_allowed, false) OR ");
+            push_distance(query_builder, filter);
+            query_builder.push(" <= ");
+            query_builder.push_bind(filter.radius_km);
+            query_builder.push(")");
+        }
         if let Some(region_id) = params.region_id {
             query_builder.push(" AND j.region_id = ");
             query_builder.push_bind(region_id);
@@ -450,6 +1062,14 @@ pub async fn list_public_jobs(
             query_builder.push(" AND j.work_modality = ");
             query_builder.push_bind(work_modality);
         }
+        if let Some(shift_type) = params.shift_type {
+            query_builder.push(" AND j.shift_type = ");
+            query_builder.push_bind(shift_type);
+        }
+        if let Some(weekly_hours_max) = params.weekly_hours_max {
+            query_builder.push(" AND j.weekly_hours <= ");
+            query_builder.push_bind(weekly_hours_max);
+        }
         if let Some(is_remote) = params.is_remote_allowed {
             query_builder.push(" AND j.is_remote_allowed = ");
             query_builder.push_bind(is_remote);
@@ -469,15 +1089,21 @@ pub async fn list_public_jobs(
         SELECT COUNT(*)
         FROM jobs j
         INNER JOIN company_profiles c ON j.company_id = c.id
+        LEFT JOIN municipalities jm ON jm.id = j.municipality_id
         WHERE j.status = 'active' AND j.application_deadline >= CURRENT_DATE
         "#,
     );
     build_where_clause(&mut count_builder);
 
-    let total: i64 = count_builder
-        .build_query_scalar::<i64>()
-        .fetch_one(&state.db)
-        .await?;
+    // Skipped when neither count is wanted
+    let total: i64 = if fields.includes("total") || fields.includes("total_pages") {
+        count_builder
+            .build_query_scalar::<i64>()
+            .fetch_one(&state.db)
+            .await?
+    } else {
+        0
+    };
 
     // Main query
     let mut query_builder = sqlx::QueryBuilder::new(
@@ -485,33 +1111,76 @@ pub async fn list_public_jobs(
         SELECT
             j.id, j.title, j.description, j.responsibilities,
             j.job_type, j.industry_id, j.work_area_id, j.position_level_id,
-            j.work_modality, j.work_schedule,
+            j.work_modality, j.schedule_details, j.shift_type, j.weekly_hours,
             j.region_id, j.municipality_id, j.is_remote_allowed,
             j.education_level, j.years_experience_min, j.years_experience_max,
             j.benefits, j.application_deadline, j.contact_email, j.application_url,
             j.vacancies, j.is_featured, j.created_at,
-            c.company_name, c.logo_url as company_logo_url
+            COALESCE(j.published_at, j.created_at) AS sort_published_at,
+            c.company_name, c.logo_url as company_logo_url,
+        "#,
+    );
+    match commute {
+        Some(ref filter) => {
+            query_builder.push("CASE WHEN COALESCE(j.is_remote_allowed, false) THEN NULL ELSE ");
+            push_distance(&mut query_builder, filter);
+            query_builder.push(" END AS distance_km");
+        }
+        None => {
+            query_builder.push("NULL::float8 AS distance_km");
+        }
+    }
+    query_builder.push(
+        r#"
         FROM jobs j
         INNER JOIN company_profiles c ON j.company_id = c.id
+        LEFT JOIN municipalities jm ON jm.id = j.municipality_id
         WHERE j.status = 'active' AND j.application_deadline >= CURRENT_DATE
         "#,
     );
     build_where_clause(&mut query_builder);
 
-    query_builder.push(" ORDER BY j.is_featured DESC, j.created_at DESC");
+    // Jobs after the cursor in the listing order, which
+    // idx_jobs_public_listing serves
+    if let Some(ref cursor) = cursor {
+        query_builder.push(
+            " AND (COALESCE(j.is_featured, false), COALESCE(j.published_at, j.created_at), j.id) < (",
+        );
+        query_builder.push_bind(cursor.is_featured);
+        query_builder.push(", ");
+        query_builder.push_bind(cursor.published_at);
+        query_builder.push(", ");
+        query_builder.push_bind(cursor.id);
+        query_builder.push(")");
+    }
+
+    if commute.is_some() {
+        query_builder.push(" ORDER BY distance_km ASC NULLS LAST,");
+        query_builder.push(" j.is_featured DESC, j.created_at DESC");
+    } else {
+        query_builder.push(" ORDER BY COALESCE(j.is_featured, false) DESC,");
+        query_builder.push(" COALESCE(j.published_at, j.created_at) DESC, j.id DESC");
+    }
+    // One more than a page tells whether there is a next one
     query_builder.push(" LIMIT ");
-    query_builder.push_bind(per_page);
-    query_builder.push(" OFFSET ");
-    query_builder.push_bind(offset);
+    query_builder.push_bind(per_page + 1);
+    if cursor.is_none() {
+        query_builder.push(" OFFSET ");
+        query_builder.push_bind(offset);
+    }
 
-    let jobs = query_builder
-        .build()
-        .fetch_all(&state.db)
-        .await?;
+    let mut jobs = if fields.includes("jobs") || fields.includes("next_cursor") {
+        query_builder.build().fetch_all(&state.db).await?
+    } else {
+        Vec::new()
+    };
+    let has_more = jobs.len() as i64 > per_page;
+    jobs.truncate(per_page.max(0) as usize);
 
     let mut result = Vec::new();
+    let mut next_cursor = None;
     for row in jobs {
-        result.push(PublicJobListing {
+        let mut job = PublicJobListing {
             id: row.try_get("id")?,
             title: row.try_get("title")?,
             description: row.try_get("description")?,
@@ -521,7 +1190,9 @@ pub async fn list_public_jobs(
             work_area_id: row.try_get("work_area_id")?,
             position_level_id: row.try_get("position_level_id")?,
             work_modality: row.try_get("work_modality")?,
-            work_schedule: row.try_get("work_schedule")?,
+            schedule_details: row.try_get("schedule_details")?,
+            shift_type: row.try_get("shift_type")?,
+            weekly_hours: row.try_get("weekly_hours")?,
             region_id: row.try_get("region_id")?,
             municipality_id: row.try_get("municipality_id")?,
             is_remote_allowed: row.try_get("is_remote_allowed")?,
@@ -537,26 +1208,48 @@ pub async fn list_public_jobs(
             created_at: row.try_get("created_at")?,
             company_name: row.try_get("company_name")?,
             company_logo_url: row.try_get("company_logo_url")?,
+            schedule_display: None,
+        };
+        job.schedule_display = schedule_display(
+            job.shift_type,
+            job.weekly_hours,
+            job.schedule_details.as_deref(),
+        );
+        if has_more && commute.is_none() {
+            next_cursor = Some(PublicJobCursor {
+                is_featured: job.is_featured,
+                published_at: row.try_get("sort_published_at")?,
+                id: job.id,
+            });
+        }
+        result.push(PublicJobSearchResult {
+            job,
+            distance_km: row.try_get("distance_km")?,
         });
     }
 
     let total_pages = (total as f64 / per_page as f64).ceil() as i64;
 
-    Ok(Json(PublicJobListResponse {
-        jobs: result,
-        total,
-        page,
-        per_page,
-        total_pages,
-    }))
+    Ok((
+        fields.warning_headers(),
+        Json(fields.retain(&PublicJobListResponse {
+            jobs: result,
+            total,
+            page,
+            per_page,
+            total_pages,
+            next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        })),
+    ))
 }
 
 /// GET /api/jobs/{id}
 /// Get single job public details (no authentication required)
 pub async fn get_public_job(
     State(state): State<AppState>,
+    auth_user: Option<Extension<AuthUser>>,
     Path(job_id): Path<Uuid>,
-) -> Result<Json<PublicJobListing>> {
+) -> Result<Json<PublicJobDetail>> {
     // Get job and verify it's active
     let job = sqlx::query!(
         r#"
@@ -565,7 +1258,9 @@ pub async fn get_public_job(
             j.job_type as "job_type: JobType",
             j.industry_id, j.work_area_id, j.position_level_id,
             j.work_modality as "work_modality: WorkModality",
-            j.work_schedule,
+            j.schedule_details,
+            j.shift_type as "shift_type: ShiftType",
+            j.weekly_hours,
             j.region_id, j.municipality_id, j.is_remote_allowed,
             j.education_level, j.years_experience_min, j.years_experience_max,
             j.benefits, j.application_deadline, j.contact_email, j.application_url,
@@ -589,6 +1284,27 @@ pub async fn get_public_job(
     .execute(&state.db)
     .await?;
 
+    sqlx::query!(
+        r#"
+        INSERT INTO job_view_stats (job_id, view_date, views)
+        VALUES ($1, CURRENT_DATE, 1)
+        ON CONFLICT (job_id, view_date) DO UPDATE SET views = job_view_stats.views + 1
+        "#,
+        job_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    // Remember the view for GET /api/me/recently-viewed-jobs
+    if let Some(Extension(user)) = auth_user.filter(|u| u.user_type == "job_seeker") {
+        let db = state.db.clone();
+        tokio::spawn(async move {
+            if let Err(e) = browsing::record_view(&db, user.id, job_id).await {
+                tracing::error!("Failed to record job view: {}", e);
+            }
+        });
+    }
+
     let public_job = PublicJobListing {
         id: job.id,
         title: job.title,
@@ -599,7 +1315,14 @@ pub async fn get_public_job(
         work_area_id: job.work_area_id,
         position_level_id: job.position_level_id,
         work_modality: job.work_modality,
-        work_schedule: job.work_schedule,
+        schedule_display: schedule_display(
+            job.shift_type,
+            job.weekly_hours,
+            job.schedule_details.as_deref(),
+        ),
+        schedule_details: job.schedule_details,
+        shift_type: job.shift_type,
+        weekly_hours: job.weekly_hours,
         region_id: job.region_id,
         municipality_id: job.municipality_id,
         is_remote_allowed: job.is_remote_allowed.unwrap_or(false),
@@ -617,5 +1340,8 @@ pub async fn get_public_job(
         company_logo_url: job.company_logo_url,
     };
 
-    Ok(Json(public_job))
+    Ok(Json(PublicJobDetail {
+        job: public_job,
+        partner_omil: fetch_job_partner(&state.db, job_id).await?,
+    }))
 }
@@ -1,18 +1,30 @@
 use axum::{extract::State, Extension, Json};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::PgConnection;
 use validator::Validate;
 
 use crate::{
+    config::Config,
     error::{AppError, Result},
-    middleware::{blacklist_token, AuthUser},
+    handlers::profile::ensure_national_id_available,
+    middleware::{blacklist_token, forget_token_version, AuthUser},
+    models::admin::AggregateType,
+    models::permissions::Capability,
     models::user::{
-        AccountStatus, AuthResponse, ForgotPasswordRequest, LoginRequest, MessageResponse,
-        RefreshRequest, RegisterCompanyRequest, RegisterJobSeekerRequest, RegisterOmilRequest,
-        ResetPasswordRequest, ResendVerificationRequest, TokenResponse, User, UserResponse,
-        UserType, VerifyEmailRequest,
+        AccountDeletionScheduledResponse, AccountStatus, AuthChallenge, AuthResponse,
+        ClaimAccountRequest, DeleteAccountRequest, EmailChangeRequestedResponse,
+        EmailChangeTokenRequest,
+        ForgotPasswordRequest, LoginRequest, MeResponse, MessageResponse, RefreshRequest,
+        RegisterCompanyRequest, RegisterJobSeekerRequest, RegisterOmilRequest,
+        RequestEmailChangeRequest, ResetPasswordRequest, ResendVerificationRequest,
+        RestoreAccountRequest, SetupPasswordRequest, TokenResponse, User, UserType,
+        VerifyEmailRequest,
     },
+    services::{account_deletion, admin_events, authz, captcha, events, settings},
     utils::{
         jwt::{create_access_token, create_refresh_token, hash_token},
+        normalize::Normalize,
         password::{hash_password, verify_password},
     },
     AppState,
@@ -22,26 +34,45 @@ use crate::{
 // REGISTRATION ENDPOINTS
 // ============================================================================
 
+/// GET /api/auth/challenge
+/// Bot check the registration and password reset forms must pass, with a
+/// fresh proof-of-work challenge in that mode
+pub async fn get_challenge(State(state): State<AppState>) -> Result<Json<AuthChallenge>> {
+    Ok(Json(captcha::issue_challenge(&state).await?))
+}
+
 /// POST /api/auth/register
 /// Register a new job seeker account
 pub async fn register_job_seeker(
     State(state): State<AppState>,
-    Json(payload): Json<RegisterJobSeekerRequest>,
+    Json(mut payload): Json<RegisterJobSeekerRequest>,
 ) -> Result<Json<AuthResponse>> {
+    payload.normalize();
     payload.validate()?;
+    captcha::verify(&state, payload.captcha_token.as_deref()).await?;
 
     let password_hash = hash_password(&payload.password)
         .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
 
+    let mut tx = state.db.begin().await?;
+
+    // A national ID already on file conflicts, including one of a person an
+    // OMIL manages offline: that account is taken over only through the
+    // claim invitation the OMIL sends, never by registering with the RUT
+    let national_id = payload.national_id.as_deref().filter(|n| !n.is_empty());
+    if let Some(national_id) = national_id {
+        ensure_national_id_available(&mut *tx, national_id, None).await?;
+    }
+
     let user = sqlx::query_as!(
         User,
         r#"
         INSERT INTO users (email, password_hash, first_name, last_name, user_type, account_status)
         VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, email, password_hash, first_name, last_name,
+        RETURNING id, email as "email!", password_hash, first_name, last_name,
                   user_type as "user_type: UserType",
                   account_status as "account_status: AccountStatus",
-                  email_verified_at, created_at, updated_at
+                  email_verified_at, created_at, updated_at, token_version
         "#,
         payload.email.to_lowercase(),
         password_hash,
@@ -50,20 +81,31 @@ pub async fn register_job_seeker(
         UserType::JobSeeker as UserType,
         AccountStatus::PendingVerification as AccountStatus,
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        if e.to_string().contains("unique") || e.to_string().contains("duplicate") {
-            AppError::ConflictError("Email already registered".to_string())
-        } else {
-            AppError::DatabaseError(e)
-        }
-    })?;
+    .map_err(email_conflict)?;
+
+    if let Some(national_id) = national_id {
+        sqlx::query!(
+            "INSERT INTO job_seeker_profiles (user_id, national_id) VALUES ($1, $2)",
+            user.id,
+            national_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
 
     // Create tokens
-    let (access_token, expires_in) =
-        create_access_token(user.id, &user.email, user.user_type, &state.config)
-            .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
 
     let refresh_token = create_refresh_token();
     store_refresh_token(&state, user.id, &refresh_token).await?;
@@ -78,7 +120,7 @@ pub async fn register_job_seeker(
             .send_verification_email(&user_email, &user_name, &verification_token)
             .await
         {
-            tracing::error!("Failed to send verification email: {:?}", e);
+            tracing::error!("Failed to send verification email: {}", e);
         }
     });
 
@@ -95,15 +137,18 @@ pub async fn register_job_seeker(
 /// Register a new company member account (pending approval)
 pub async fn register_company(
     State(state): State<AppState>,
-    Json(payload): Json<RegisterCompanyRequest>,
+    Json(mut payload): Json<RegisterCompanyRequest>,
 ) -> Result<Json<AuthResponse>> {
+    payload.normalize();
     payload.validate()?;
+    captcha::verify(&state, payload.captcha_token.as_deref()).await?;
 
     let password_hash = hash_password(&payload.password)
         .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
 
-    // Start transaction to create user + company profile + membership atomically
-    let mut tx = state.db.begin().await?;
+    // One transaction for the user, company profile, membership and
+    // credentials: any failure up to the commit leaves nothing behind
+    let mut tx = state.db.begin().await.map_err(registration_failed)?;
 
     // Create user
     let user = sqlx::query_as!(
@@ -111,10 +156,10 @@ pub async fn register_company(
         r#"
         INSERT INTO users (email, password_hash, first_name, last_name, user_type, account_status)
         VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, email, password_hash, first_name, last_name,
+        RETURNING id, email as "email!", password_hash, first_name, last_name,
                   user_type as "user_type: UserType",
                   account_status as "account_status: AccountStatus",
-                  email_verified_at, created_at, updated_at
+                  email_verified_at, created_at, updated_at, token_version
         "#,
         payload.email.to_lowercase(),
         password_hash,
@@ -126,10 +171,12 @@ pub async fn register_company(
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
-        if e.to_string().contains("unique") || e.to_string().contains("duplicate") {
+        if e.as_database_error()
+            .is_some_and(|d| d.is_unique_violation())
+        {
             AppError::ConflictError("Email already registered".to_string())
         } else {
-            AppError::DatabaseError(e)
+            registration_failed(e)
         }
     })?;
 
@@ -143,7 +190,8 @@ pub async fn register_company(
         payload.company_name,
     )
     .fetch_one(&mut *tx)
-    .await?;
+    .await
+    .map_err(registration_failed)?;
 
     // Create company membership (user becomes owner)
     sqlx::query!(
@@ -155,33 +203,45 @@ pub async fn register_company(
         user.id,
     )
     .execute(&mut *tx)
-    .await?;
+    .await
+    .map_err(registration_failed)?;
 
-    // Commit transaction
-    tx.commit().await?;
+    // Credentials are issued before the commit, so a failure here leaves no
+    // pending company behind whose owner never got to sign in
+    let refresh_token = create_refresh_token();
+    store_refresh_token_with(&mut *tx, &state.config, user.id, &refresh_token)
+        .await
+        .map_err(registration_failed)?;
+    let (verification_token, _) = create_verification_token_with(&mut tx, user.id)
+        .await
+        .map_err(registration_failed)?;
 
-    // Create tokens
-    let (access_token, expires_in) =
-        create_access_token(user.id, &user.email, user.user_type, &state.config)
-            .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(registration_failed)?;
 
-    let refresh_token = create_refresh_token();
-    store_refresh_token(&state, user.id, &refresh_token).await?;
+    tx.commit().await.map_err(registration_failed)?;
 
     // Send verification email
     let email_service = state.email.clone();
     let user_email = user.email.clone();
     let user_name = user.first_name.clone();
-    let verification_token = create_verification_token(&state, user.id).await?;
     tokio::spawn(async move {
         if let Err(e) = email_service
             .send_verification_email(&user_email, &user_name, &verification_token)
             .await
         {
-            tracing::error!("Failed to send verification email: {:?}", e);
+            tracing::error!("Failed to send verification email: {}", e);
         }
     });
 
+    admin_events::publish(&state).await;
+
     Ok(Json(AuthResponse {
         user: user.into(),
         access_token,
@@ -195,9 +255,11 @@ pub async fn register_company(
 /// Register a new OMIL member account with organization (pending approval)
 pub async fn register_omil(
     State(state): State<AppState>,
-    Json(payload): Json<RegisterOmilRequest>,
+    Json(mut payload): Json<RegisterOmilRequest>,
 ) -> Result<Json<AuthResponse>> {
+    payload.normalize();
     payload.validate()?;
+    captcha::verify(&state, payload.captcha_token.as_deref()).await?;
 
     let password_hash = hash_password(&payload.password)
         .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
@@ -208,10 +270,10 @@ pub async fn register_omil(
         r#"
         INSERT INTO users (email, password_hash, first_name, last_name, user_type, account_status)
         VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, email, password_hash, first_name, last_name,
+        RETURNING id, email as "email!", password_hash, first_name, last_name,
                   user_type as "user_type: UserType",
                   account_status as "account_status: AccountStatus",
-                  email_verified_at, created_at, updated_at
+                  email_verified_at, created_at, updated_at, token_version
         "#,
         payload.email.to_lowercase(),
         password_hash,
@@ -255,9 +317,14 @@ pub async fn register_omil(
     .await?;
 
     // Create tokens
-    let (access_token, expires_in) =
-        create_access_token(user.id, &user.email, user.user_type, &state.config)
-            .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
 
     let refresh_token = create_refresh_token();
     store_refresh_token(&state, user.id, &refresh_token).await?;
@@ -272,10 +339,211 @@ pub async fn register_omil(
             .send_verification_email(&user_email, &user_name, &verification_token)
             .await
         {
-            tracing::error!("Failed to send verification email: {:?}", e);
+            tracing::error!("Failed to send verification email: {}", e);
         }
     });
 
+    admin_events::publish(&state).await;
+
+    Ok(Json(AuthResponse {
+        user: user.into(),
+        access_token,
+        refresh_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+    }))
+}
+
+/// POST /api/auth/claim-account
+/// Take over an account an OMIL managed offline, using the emailed
+/// invitation. The invited email becomes the verified login email and the
+/// account keeps all of its history.
+pub async fn claim_account(
+    State(state): State<AppState>,
+    Json(payload): Json<ClaimAccountRequest>,
+) -> Result<Json<AuthResponse>> {
+    payload.validate()?;
+
+    let password_hash = hash_password(&payload.password)
+        .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
+
+    let token_hash = hash_token(&payload.token);
+    let mut tx = state.db.begin().await?;
+
+    let invitation = sqlx::query!(
+        r#"
+        SELECT id, user_id, email, expires_at, claimed_at
+        FROM account_claim_invitations
+        WHERE token_hash = $1
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))?;
+
+    if invitation.claimed_at.is_some() {
+        return Err(AppError::ValidationError(
+            "This invitation has already been used".to_string(),
+        ));
+    }
+
+    if invitation.expires_at < Utc::now() {
+        return Err(AppError::ValidationError("Invitation has expired".to_string()));
+    }
+
+    ensure_email_unused(&mut *tx, &invitation.email).await?;
+
+    // Only still-offline accounts can be claimed; the person may have
+    // registered on their own since the invitation was sent
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users
+        SET email = $1, password_hash = $2, email_verified_at = NOW(),
+            account_status = $3, updated_at = NOW()
+        WHERE id = $4 AND account_status = $5
+        RETURNING id, email as "email!", password_hash, first_name, last_name,
+                  user_type as "user_type: UserType",
+                  account_status as "account_status: AccountStatus",
+                  email_verified_at, created_at, updated_at, token_version
+        "#,
+        invitation.email,
+        password_hash,
+        AccountStatus::Active as AccountStatus,
+        invitation.user_id,
+        AccountStatus::ManagedOffline as AccountStatus
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(email_conflict)?
+    .ok_or_else(|| {
+        AppError::ValidationError("This account has already been claimed".to_string())
+    })?;
+
+    sqlx::query!(
+        "UPDATE account_claim_invitations SET claimed_at = NOW() WHERE id = $1",
+        invitation.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    end_offline_management(
+        &mut tx,
+        user.id,
+        &format!("La persona activó su cuenta con el correo {}", invitation.email),
+    )
+    .await?;
+
+    let refresh_token = create_refresh_token();
+    store_refresh_token_with(&mut *tx, &state.config, user.id, &refresh_token).await?;
+
+    tx.commit().await?;
+
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+
+    Ok(Json(AuthResponse {
+        user: user.into(),
+        access_token,
+        refresh_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+    }))
+}
+
+/// POST /api/auth/setup-password
+/// Set the first password of an account an OMIL registered with an email,
+/// using the link the advisor sent. Following the link proves the email, so
+/// the account is verified and activated.
+pub async fn setup_password(
+    State(state): State<AppState>,
+    Json(payload): Json<SetupPasswordRequest>,
+) -> Result<Json<AuthResponse>> {
+    payload.validate()?;
+
+    let password_hash = hash_password(&payload.password)
+        .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
+
+    let token_hash = hash_token(&payload.token);
+    let mut tx = state.db.begin().await?;
+
+    let setup = sqlx::query!(
+        r#"
+        SELECT id, user_id, expires_at, used_at
+        FROM password_setup_tokens
+        WHERE token_hash = $1
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))?;
+
+    if setup.used_at.is_some() {
+        return Err(AppError::ValidationError(
+            "This link has already been used".to_string(),
+        ));
+    }
+
+    if setup.expires_at < Utc::now() {
+        return Err(AppError::ValidationError("Link has expired".to_string()));
+    }
+
+    // Accounts registered before invites existed are still pending
+    // verification; suspended or deleted accounts can't be activated here
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users
+        SET password_hash = $1, email_verified_at = NOW(),
+            account_status = $2, updated_at = NOW()
+        WHERE id = $3 AND password_hash IS NULL AND email IS NOT NULL
+          AND account_status IN ($4, $5)
+        RETURNING id, email as "email!", password_hash, first_name, last_name,
+                  user_type as "user_type: UserType",
+                  account_status as "account_status: AccountStatus",
+                  email_verified_at, created_at, updated_at, token_version
+        "#,
+        password_hash,
+        AccountStatus::Active as AccountStatus,
+        setup.user_id,
+        AccountStatus::Invited as AccountStatus,
+        AccountStatus::PendingVerification as AccountStatus
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("This account already has a password".to_string()))?;
+
+    sqlx::query!(
+        "UPDATE password_setup_tokens SET used_at = NOW() WHERE id = $1",
+        setup.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let refresh_token = create_refresh_token();
+    store_refresh_token_with(&mut *tx, &state.config, user.id, &refresh_token).await?;
+
+    tx.commit().await?;
+
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+
     Ok(Json(AuthResponse {
         user: user.into(),
         access_token,
@@ -300,10 +568,10 @@ pub async fn login(
     let user = sqlx::query_as!(
         User,
         r#"
-        SELECT id, email, password_hash, first_name, last_name,
+        SELECT id, email as "email!", password_hash, first_name, last_name,
                user_type as "user_type: UserType",
                account_status as "account_status: AccountStatus",
-               email_verified_at, created_at, updated_at
+               email_verified_at, created_at, updated_at, token_version
         FROM users
         WHERE email = $1
         "#,
@@ -311,39 +579,62 @@ pub async fn login(
     )
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::AuthenticationError("Invalid email or password".to_string()))?;
+    .ok_or_else(|| {
+        tracing::info!(
+            "Failed login for {}: unknown email",
+            state.config.redactor().email(&payload.email)
+        );
+        AppError::AuthenticationError("Invalid email or password".to_string())
+    })?;
+
+    // Accounts registered on someone's behalf have no password until the
+    // person sets one from their advisor's invite
+    if user.password_hash.is_none() {
+        tracing::info!(
+            "Failed login for {}: no password set",
+            state.config.redactor().email(&payload.email)
+        );
+        return Err(AppError::PasswordSetupRequired(
+            "A password must be set for this account before logging in".to_string(),
+        ));
+    }
 
     // Verify password
-    let is_valid = verify_password(&payload.password, &user.password_hash)
+    let is_valid = verify_password(&payload.password, user.password_hash.as_deref())
         .map_err(|e| AppError::InternalError(format!("Failed to verify password: {}", e)))?;
 
     if !is_valid {
+        tracing::info!(
+            "Failed login for {}: wrong password",
+            state.config.redactor().email(&payload.email)
+        );
         return Err(AppError::AuthenticationError(
             "Invalid email or password".to_string(),
         ));
     }
 
-    // Check account status
-    match user.account_status {
-        AccountStatus::Suspended => {
-            return Err(AppError::ForbiddenError("Account is suspended".to_string()));
-        }
-        AccountStatus::Deactivated => {
-            return Err(AppError::ForbiddenError(
-                "Account is deactivated".to_string(),
-            ));
-        }
-        _ => {}
-    }
+    ensure_can_sign_in(user.account_status)?;
 
     // Create tokens
-    let (access_token, expires_in) =
-        create_access_token(user.id, &user.email, user.user_type, &state.config)
-            .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    let (access_token, expires_in) = create_access_token(
+        user.id,
+        &user.email,
+        user.user_type,
+        user.token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
 
     let refresh_token = create_refresh_token();
     store_refresh_token(&state, user.id, &refresh_token).await?;
 
+    sqlx::query!(
+        "UPDATE users SET last_login_at = NOW() WHERE id = $1",
+        user.id
+    )
+    .execute(&state.db)
+    .await?;
+
     Ok(Json(AuthResponse {
         user: user.into(),
         access_token,
@@ -384,6 +675,26 @@ pub async fn logout(
 // TOKEN REFRESH ENDPOINT
 // ============================================================================
 
+/// Rejects statuses that may not hold a session. Login and refresh both go
+/// through here so a suspended account cannot keep renewing its tokens.
+fn ensure_can_sign_in(status: AccountStatus) -> Result<()> {
+    match status {
+        AccountStatus::Suspended => {
+            Err(AppError::ForbiddenError("Account is suspended".to_string()))
+        }
+        AccountStatus::Deactivated => Err(AppError::ForbiddenError(
+            "Account is deactivated".to_string(),
+        )),
+        AccountStatus::PasswordResetRequired => Err(AppError::ForbiddenError(
+            "Password must be reset before logging in".to_string(),
+        )),
+        AccountStatus::ManagedOffline => Err(AppError::ForbiddenError(
+            "Account is managed by an OMIL and must be claimed before logging in".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// POST /api/auth/refresh
 /// Exchange a refresh token for new access and refresh tokens
 pub async fn refresh(
@@ -398,7 +709,8 @@ pub async fn refresh(
     let stored_token = sqlx::query!(
         r#"
         SELECT rt.id, rt.user_id, rt.expires_at, rt.revoked_at,
-               u.email, u.user_type as "user_type: UserType"
+               u.email as "email!", u.user_type as "user_type: UserType", u.token_version,
+               u.account_status as "account_status: AccountStatus"
         FROM refresh_tokens rt
         JOIN users u ON u.id = rt.user_id
         WHERE rt.token_hash = $1
@@ -423,6 +735,8 @@ pub async fn refresh(
         ));
     }
 
+    ensure_can_sign_in(stored_token.account_status)?;
+
     // Revoke old refresh token (rotation)
     sqlx::query!(
         "UPDATE refresh_tokens SET revoked_at = NOW() WHERE id = $1",
@@ -436,6 +750,7 @@ pub async fn refresh(
         stored_token.user_id,
         &stored_token.email,
         stored_token.user_type,
+        stored_token.token_version,
         &state.config,
     )
     .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
@@ -456,18 +771,19 @@ pub async fn refresh(
 // ============================================================================
 
 /// GET /api/auth/me
-/// Get the current authenticated user's information
+/// Get the current authenticated user's information, with any scheduled deletion
 pub async fn me(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-) -> Result<Json<UserResponse>> {
+) -> Result<Json<MeResponse>> {
+    // Seekers managed offline have no email yet; staff impersonating them see it blank
     let user = sqlx::query_as!(
         User,
         r#"
-        SELECT id, email, password_hash, first_name, last_name,
+        SELECT id, COALESCE(email, '') as "email!", password_hash, first_name, last_name,
                user_type as "user_type: UserType",
                account_status as "account_status: AccountStatus",
-               email_verified_at, created_at, updated_at
+               email_verified_at, created_at, updated_at, token_version
         FROM users
         WHERE id = $1
         "#,
@@ -477,7 +793,16 @@ pub async fn me(
     .await?
     .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
-    Ok(Json(user.into()))
+    let deletion_scheduled_at = if user.account_status == AccountStatus::PendingDeletion {
+        account_deletion::scheduled_for(&state.db, auth_user.id).await?
+    } else {
+        None
+    };
+
+    Ok(Json(MeResponse {
+        user: user.into(),
+        deletion_scheduled_at,
+    }))
 }
 
 // ============================================================================
@@ -492,41 +817,25 @@ pub async fn forgot_password(
     Json(payload): Json<ForgotPasswordRequest>,
 ) -> Result<Json<MessageResponse>> {
     payload.validate()?;
+    captcha::verify(&state, payload.captcha_token.as_deref()).await?;
 
-    // Find user (but don't reveal if they exist)
+    // Find user (but don't reveal if they exist). Accounts without a password
+    // get their first one through the advisor's setup invite instead.
     let user = sqlx::query!(
-        "SELECT id, email, first_name FROM users WHERE email = $1",
+        r#"
+        SELECT id, email as "email!", first_name
+        FROM users
+        WHERE email = $1 AND password_hash IS NOT NULL
+        "#,
         payload.email.to_lowercase()
     )
     .fetch_optional(&state.db)
     .await?;
 
     if let Some(user) = user {
-        // Create password reset token
-        let token = create_refresh_token(); // Reuse secure token generation
-        let token_hash = hash_token(&token);
-        let expires_at = Utc::now() + Duration::hours(1);
-
-        // Delete any existing tokens for this user
-        sqlx::query!(
-            "DELETE FROM password_reset_tokens WHERE user_id = $1",
-            user.id
-        )
-        .execute(&state.db)
-        .await?;
-
-        // Store new token
-        sqlx::query!(
-            r#"
-            INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
-            VALUES ($1, $2, $3)
-            "#,
-            user.id,
-            token_hash,
-            expires_at
-        )
-        .execute(&state.db)
-        .await?;
+        let mut conn = state.db.acquire().await?;
+        let (token, _) =
+            create_password_reset_token_with(&mut conn, user.id, Duration::hours(1)).await?;
 
         // Send email (async)
         let email_service = state.email.clone();
@@ -537,7 +846,7 @@ pub async fn forgot_password(
                 .send_password_reset_email(&user_email, &user_name, &token)
                 .await
             {
-                tracing::error!("Failed to send password reset email: {:?}", e);
+                tracing::error!("Failed to send password reset email: {}", e);
             }
         });
     }
@@ -587,11 +896,25 @@ pub async fn reset_password(
     let password_hash = hash_password(&payload.new_password)
         .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))?;
 
-    // Update password
+    // Update password, lifting a reset an admin forced on the account
     sqlx::query!(
-        "UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2",
+        r#"
+        UPDATE users
+        SET password_hash = $1,
+            token_version = token_version + 1,
+            account_status = CASE
+                WHEN account_status <> $3 THEN account_status
+                WHEN email_verified_at IS NULL THEN $4
+                ELSE $5
+            END,
+            updated_at = NOW()
+        WHERE id = $2
+        "#,
         password_hash,
-        token_record.user_id
+        token_record.user_id,
+        AccountStatus::PasswordResetRequired as AccountStatus,
+        AccountStatus::PendingVerification as AccountStatus,
+        AccountStatus::Active as AccountStatus
     )
     .execute(&state.db)
     .await?;
@@ -612,6 +935,9 @@ pub async fn reset_password(
     .execute(&state.db)
     .await?;
 
+    // Access tokens were invalidated by the token_version bump above
+    forget_token_version(&mut state.redis.clone(), token_record.user_id).await;
+
     Ok(Json(MessageResponse::new(
         "Password has been reset successfully",
     )))
@@ -656,16 +982,17 @@ pub async fn verify_email(
         return Err(AppError::ValidationError("Token has expired".to_string()));
     }
 
-    // Update user's email verification status and activate account
+    // Update user's email verification status and activate a pending account
     sqlx::query!(
         r#"
         UPDATE users
         SET email_verified_at = NOW(),
-            account_status = $1,
+            account_status = CASE WHEN account_status = $2 THEN $1 ELSE account_status END,
             updated_at = NOW()
-        WHERE id = $2
+        WHERE id = $3
         "#,
         AccountStatus::Active as AccountStatus,
+        AccountStatus::PendingVerification as AccountStatus,
         token_record.user_id
     )
     .execute(&state.db)
@@ -694,7 +1021,7 @@ pub async fn resend_verification(
     // Find user
     let user = sqlx::query!(
         r#"
-        SELECT id, email, first_name, email_verified_at
+        SELECT id, email as "email!", first_name, email_verified_at
         FROM users
         WHERE email = $1
         "#,
@@ -716,7 +1043,7 @@ pub async fn resend_verification(
                     .send_verification_email(&user_email, &user_name, &verification_token)
                     .await
                 {
-                    tracing::error!("Failed to send verification email: {:?}", e);
+                    tracing::error!("Failed to send verification email: {}", e);
                 }
             });
         }
@@ -729,33 +1056,445 @@ pub async fn resend_verification(
 }
 
 // ============================================================================
-// HELPER FUNCTIONS
+// EMAIL CHANGE ENDPOINTS
 // ============================================================================
 
-async fn store_refresh_token(
-    state: &AppState,
-    user_id: uuid::Uuid,
-    token: &str,
-) -> Result<()> {
-    let token_hash = hash_token(token);
-    let expires_at = Utc::now() + Duration::seconds(state.config.jwt_refresh_expiry);
+/// How long an email change request can be confirmed or cancelled
+const EMAIL_CHANGE_VALID_HOURS: i64 = 24;
 
-    sqlx::query!(
-        r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
-        "#,
-        user_id,
-        token_hash,
-        expires_at
-    )
-    .execute(&state.db)
-    .await?;
+/// POST /api/me/email/change-request
+/// Start changing the login email; the new address must confirm it and the
+/// old one is told how to cancel. Replaces any request still open.
+pub async fn request_email_change(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<RequestEmailChangeRequest>,
+) -> Result<Json<EmailChangeRequestedResponse>> {
+    payload.normalize();
+    payload.validate()?;
 
-    Ok(())
-}
+    let user = sqlx::query!(
+        "SELECT email, password_hash, first_name FROM users WHERE id = $1",
+        auth_user.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    // Seekers managed offline get their first email by claiming the account
+    let Some(current_email) = user.email else {
+        return Err(AppError::ValidationError(
+            "This account has no email yet; ask your OMIL for an invitation to claim it"
+                .to_string(),
+        ));
+    };
+
+    let password_valid = verify_password(&payload.current_password, user.password_hash.as_deref())
+        .map_err(|e| AppError::InternalError(format!("Password verification failed: {}", e)))?;
+    if !password_valid {
+        return Err(AppError::AuthenticationError(
+            "Current password is incorrect".to_string(),
+        ));
+    }
+
+    if payload.new_email == current_email {
+        return Err(AppError::ValidationError(
+            "The new email is the same as the current one".to_string(),
+        ));
+    }
+    ensure_email_unused(&state.db, &payload.new_email).await?;
+
+    let confirm_token = create_refresh_token(); // Reuse secure token generation
+    let cancel_token = create_refresh_token();
+    let expires_at = Utc::now() + Duration::hours(EMAIL_CHANGE_VALID_HOURS);
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE pending_email_changes
+        SET cancelled_at = NOW()
+        WHERE user_id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL
+        "#,
+        auth_user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO pending_email_changes
+            (user_id, old_email, new_email, confirm_token_hash, cancel_token_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        auth_user.id,
+        current_email,
+        payload.new_email,
+        hash_token(&confirm_token),
+        hash_token(&cancel_token),
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let email_service = state.email.clone();
+    let old_email = current_email;
+    let new_email = payload.new_email.clone();
+    let user_name = user.first_name;
+    tokio::spawn(async move {
+        if let Err(e) = email_service
+            .send_email_change_confirmation_email(&new_email, &user_name, &confirm_token)
+            .await
+        {
+            tracing::error!("Failed to send email change confirmation: {}", e);
+        }
+        if let Err(e) = email_service
+            .send_email_change_requested_email(&old_email, &user_name, &new_email, &cancel_token)
+            .await
+        {
+            tracing::error!("Failed to send email change notice: {}", e);
+        }
+    });
+
+    Ok(Json(EmailChangeRequestedResponse {
+        new_email: payload.new_email,
+        expires_at,
+    }))
+}
+
+/// POST /api/auth/email/confirm-change
+/// Confirm an email change from the new address; the new email counts as
+/// verified and every session has to log in again
+pub async fn confirm_email_change(
+    State(state): State<AppState>,
+    Json(payload): Json<EmailChangeTokenRequest>,
+) -> Result<Json<MessageResponse>> {
+    payload.validate()?;
+
+    let token_hash = hash_token(&payload.token);
+    let mut tx = state.db.begin().await?;
+
+    let change = sqlx::query!(
+        r#"
+        SELECT id, user_id, old_email, new_email, expires_at, confirmed_at, cancelled_at
+        FROM pending_email_changes
+        WHERE confirm_token_hash = $1
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))?;
+
+    if change.confirmed_at.is_some() {
+        return Err(AppError::ValidationError(
+            "This token has already been used".to_string(),
+        ));
+    }
+    if change.cancelled_at.is_some() {
+        return Err(AppError::ValidationError(
+            "This email change was cancelled".to_string(),
+        ));
+    }
+    if change.expires_at < Utc::now() {
+        return Err(AppError::ValidationError("Token has expired".to_string()));
+    }
+
+    // Someone may have registered the address since the request was made
+    ensure_email_unused(&mut *tx, &change.new_email).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET email = $1,
+            email_verified_at = NOW(),
+            account_status = CASE WHEN account_status = $2 THEN $3 ELSE account_status END,
+            token_version = token_version + 1,
+            updated_at = NOW()
+        WHERE id = $4
+        "#,
+        change.new_email,
+        AccountStatus::PendingVerification as AccountStatus,
+        AccountStatus::Active as AccountStatus,
+        change.user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(email_conflict)?;
+
+    sqlx::query!(
+        "UPDATE pending_email_changes SET confirmed_at = NOW() WHERE id = $1",
+        change.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Outstanding verification links point at the old address
+    sqlx::query!(
+        "DELETE FROM email_verification_tokens WHERE user_id = $1",
+        change.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Revoke all refresh tokens (force re-login)
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        change.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // The addresses stay on the change request, which is scrubbed with the
+    // account; the append-only log only points at it
+    events::emit(
+        &mut tx,
+        AggregateType::User,
+        change.user_id,
+        "email_changed",
+        Some(change.user_id),
+        json!({ "email_change_id": change.id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    // Access tokens were invalidated by the token_version bump above
+    forget_token_version(&mut state.redis.clone(), change.user_id).await;
+
+    tracing::info!(
+        user_id = %change.user_id,
+        old_email = %change.old_email,
+        new_email = %change.new_email,
+        "Login email changed"
+    );
+
+    Ok(Json(MessageResponse::new("Email changed successfully")))
+}
+
+/// POST /api/auth/email/cancel-change
+/// Void a pending email change from the link sent to the old address
+pub async fn cancel_email_change(
+    State(state): State<AppState>,
+    Json(payload): Json<EmailChangeTokenRequest>,
+) -> Result<Json<MessageResponse>> {
+    payload.validate()?;
+
+    let token_hash = hash_token(&payload.token);
+
+    let change = sqlx::query!(
+        r#"
+        UPDATE pending_email_changes
+        SET cancelled_at = COALESCE(cancelled_at, NOW())
+        WHERE cancel_token_hash = $1 AND confirmed_at IS NULL
+        RETURNING id
+        "#,
+        token_hash
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    if change.is_none() {
+        return Err(AppError::ValidationError(
+            "Invalid token or the email change was already confirmed".to_string(),
+        ));
+    }
+
+    Ok(Json(MessageResponse::new("Email change cancelled")))
+}
+
+// ============================================================================
+// ACCOUNT DELETION ENDPOINTS
+// ============================================================================
+
+/// DELETE /api/me/account
+/// Schedule the account for deletion after the grace period. It is hidden
+/// and paused at once, every other session is signed out, and the user is
+/// emailed a restore link.
+pub async fn delete_account(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<DeleteAccountRequest>,
+) -> Result<Json<AccountDeletionScheduledResponse>> {
+    // Not while impersonating
+    let capabilities = authz::capabilities(&state.db, &auth_user).await?;
+    authz::require(&capabilities, Capability::ManageOwnAccount)?;
+
+    payload.validate()?;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT email as "email!", password_hash, first_name, user_type as "user_type: UserType"
+        FROM users WHERE id = $1
+        "#,
+        auth_user.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if user.user_type == UserType::Admin {
+        return Err(AppError::ForbiddenError(
+            "Admin accounts are removed by another admin".to_string(),
+        ));
+    }
+
+    let password_valid = verify_password(&payload.current_password, user.password_hash.as_deref())
+        .map_err(|e| AppError::InternalError(format!("Password verification failed: {}", e)))?;
+    if !password_valid {
+        return Err(AppError::AuthenticationError(
+            "Current password is incorrect".to_string(),
+        ));
+    }
+
+    let grace_days = settings::get_int(
+        &state.db,
+        &mut state.redis.clone(),
+        account_deletion::GRACE_DAYS_SETTING,
+    )
+    .await?;
+
+    let mut tx = state.db.begin().await?;
+    let deletion =
+        account_deletion::schedule(&mut tx, auth_user.id, user.user_type, grace_days).await?;
+
+    // Scheduling revoked every token; this session carries on with new ones
+    let token_version = sqlx::query_scalar!(
+        "SELECT token_version FROM users WHERE id = $1",
+        auth_user.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let (access_token, expires_in) = create_access_token(
+        auth_user.id,
+        &user.email,
+        user.user_type,
+        token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+    let refresh_token = create_refresh_token();
+    store_refresh_token_with(&mut *tx, &state.config, auth_user.id, &refresh_token).await?;
+
+    tx.commit().await?;
+
+    forget_token_version(&mut state.redis.clone(), auth_user.id).await;
+
+    tracing::info!(
+        user_id = %auth_user.id,
+        scheduled_for = %deletion.scheduled_for,
+        "Account deletion scheduled"
+    );
+
+    let email_service = state.email.clone();
+    let scheduled_for = deletion.scheduled_for;
+    tokio::spawn(async move {
+        if let Err(e) = email_service
+            .send_account_deletion_scheduled_email(
+                &user.email,
+                &user.first_name,
+                scheduled_for,
+                &deletion.restore_token,
+            )
+            .await
+        {
+            tracing::error!("Failed to send account deletion email: {}", e);
+        }
+    });
+
+    Ok(Json(AccountDeletionScheduledResponse {
+        deletion_scheduled_at: scheduled_for,
+        tokens: TokenResponse {
+            access_token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+        },
+    }))
+}
+
+/// POST /api/auth/account/restore
+/// Cancel a scheduled deletion with the emailed token, or as the signed-in
+/// user. The account goes back to how it was before the request.
+pub async fn restore_account(
+    State(state): State<AppState>,
+    auth_user: Option<Extension<AuthUser>>,
+    Json(payload): Json<RestoreAccountRequest>,
+) -> Result<Json<MessageResponse>> {
+    payload.validate()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let user_id = match (&payload.token, auth_user) {
+        (Some(token), _) => account_deletion::user_for_restore_token(&mut tx, token).await?,
+        (None, Some(Extension(auth_user))) => auth_user.id,
+        (None, None) => {
+            return Err(AppError::AuthenticationError(
+                "Sign in or use the link from the email to restore the account".to_string(),
+            ));
+        }
+    };
+
+    account_deletion::restore(&mut tx, user_id).await?;
+    tx.commit().await?;
+
+    tracing::info!(user_id = %user_id, "Account deletion cancelled");
+
+    Ok(Json(MessageResponse::new("Account restored")))
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+async fn store_refresh_token(
+    state: &AppState,
+    user_id: uuid::Uuid,
+    token: &str,
+) -> Result<()> {
+    store_refresh_token_with(&state.db, &state.config, user_id, token).await
+}
+
+/// Stores a refresh token through `db`, e.g. inside a registration transaction
+async fn store_refresh_token_with(
+    db: impl sqlx::PgExecutor<'_>,
+    config: &Config,
+    user_id: uuid::Uuid,
+    token: &str,
+) -> Result<()> {
+    let token_hash = hash_token(token);
+    let expires_at = Utc::now() + Duration::seconds(config.jwt_refresh_expiry);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        user_id,
+        token_hash,
+        expires_at
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
 
 async fn create_verification_token(state: &AppState, user_id: uuid::Uuid) -> Result<String> {
+    let mut conn = state.db.acquire().await?;
+    let (token, _) = create_verification_token_with(&mut conn, user_id).await?;
+    Ok(token)
+}
+
+/// Replaces the user's email verification token, returning the new token
+/// and when it expires
+pub(crate) async fn create_verification_token_with(
+    conn: &mut PgConnection,
+    user_id: uuid::Uuid,
+) -> Result<(String, DateTime<Utc>)> {
     let token = create_refresh_token(); // Reuse secure token generation
     let token_hash = hash_token(&token);
     let expires_at = Utc::now() + Duration::hours(24);
@@ -765,7 +1504,7 @@ async fn create_verification_token(state: &AppState, user_id: uuid::Uuid) -> Res
         "DELETE FROM email_verification_tokens WHERE user_id = $1",
         user_id
     )
-    .execute(&state.db)
+    .execute(&mut *conn)
     .await?;
 
     // Store new token
@@ -778,8 +1517,113 @@ async fn create_verification_token(state: &AppState, user_id: uuid::Uuid) -> Res
         token_hash,
         expires_at
     )
-    .execute(&state.db)
+    .execute(&mut *conn)
     .await?;
 
-    Ok(token)
+    Ok((token, expires_at))
+}
+
+/// Replaces the user's password reset token with one valid for `valid_for`,
+/// returning the new token and when it expires
+pub(crate) async fn create_password_reset_token_with(
+    conn: &mut PgConnection,
+    user_id: uuid::Uuid,
+    valid_for: Duration,
+) -> Result<(String, DateTime<Utc>)> {
+    let token = create_refresh_token(); // Reuse secure token generation
+    let token_hash = hash_token(&token);
+    let expires_at = Utc::now() + valid_for;
+
+    // Delete any existing tokens for this user
+    sqlx::query!(
+        "DELETE FROM password_reset_tokens WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Store new token
+    sqlx::query!(
+        r#"
+        INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        user_id,
+        token_hash,
+        expires_at
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok((token, expires_at))
+}
+
+/// The person behind an account managed offline now runs it themselves:
+/// open claim invitations are dropped and every OMIL managing them gets `note`
+async fn end_offline_management(
+    conn: &mut PgConnection,
+    user_id: uuid::Uuid,
+    note: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM account_claim_invitations WHERE user_id = $1 AND claimed_at IS NULL",
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, title, content)
+        SELECT job_seeker_id, job_seeker_id, omil_id, 'general_note', 'Cuenta activada', $2
+        FROM omil_managed_job_seekers
+        WHERE job_seeker_id = $1 AND is_active = true
+        "#,
+        user_id,
+        note
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Fails with a conflict if `email` already belongs to an account
+async fn ensure_email_unused(db: impl sqlx::PgExecutor<'_>, email: &str) -> Result<()> {
+    let taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE email = $1) as "taken!""#,
+        email
+    )
+    .fetch_one(db)
+    .await?;
+
+    if taken {
+        return Err(email_taken());
+    }
+    Ok(())
+}
+
+fn email_taken() -> AppError {
+    AppError::ConflictError("Email already registered".to_string())
+}
+
+/// A write that set an email another request took after
+/// `ensure_email_unused` checked it fails on the unique index; that is the
+/// same conflict
+fn email_conflict(e: sqlx::Error) -> AppError {
+    if e.as_database_error()
+        .is_some_and(|d| d.is_unique_violation())
+    {
+        email_taken()
+    } else {
+        AppError::DatabaseError(e)
+    }
+}
+
+/// A registration step failed before commit, so nothing was saved
+fn registration_failed(e: impl Into<AppError>) -> AppError {
+    tracing::error!("Registration rolled back: {}", e.into());
+    AppError::RegistrationFailed(
+        "Registration failed and no account was created. Please try again.".to_string(),
+    )
 }
@@ -0,0 +1,95 @@
+use axum::{extract::State, Extension, Json};
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, Result},
+    extract::Path,
+    middleware::AuthUser,
+    models::browsing::{RecentSearch, RecentlyViewedJob},
+    services::browsing,
+    AppState,
+};
+
+fn require_job_seeker(auth_user: &AuthUser) -> Result<()> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers have a browsing history".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// ENDPOINTS
+// ============================================================================
+
+/// GET /api/me/recently-viewed-jobs
+/// The last jobs the seeker viewed, most recent first
+pub async fn list_recently_viewed_jobs(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<RecentlyViewedJob>>> {
+    require_job_seeker(&auth_user)?;
+
+    let jobs = browsing::recently_viewed(&state.db, auth_user.id).await?;
+    Ok(Json(jobs))
+}
+
+/// GET /api/me/recent-searches
+/// The seeker's last distinct job searches, most recent first
+pub async fn list_recent_searches(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<RecentSearch>>> {
+    require_job_seeker(&auth_user)?;
+
+    let searches = browsing::recent_searches(&state.db, auth_user.id).await?;
+    Ok(Json(searches))
+}
+
+/// DELETE /api/me/recent-searches/{id}
+/// Forget one search
+pub async fn delete_recent_search(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(search_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    require_job_seeker(&auth_user)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM recent_job_searches WHERE id = $1 AND user_id = $2",
+        search_id,
+        auth_user.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Recent search not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "Search removed from history"
+    })))
+}
+
+/// DELETE /api/me/recent-searches
+/// Forget every search
+pub async fn clear_recent_searches(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>> {
+    require_job_seeker(&auth_user)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM recent_job_searches WHERE user_id = $1",
+        auth_user.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Search history cleared",
+        "deleted": result.rows_affected()
+    })))
+}
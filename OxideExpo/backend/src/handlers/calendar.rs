@@ -0,0 +1,146 @@
+use axum::{extract::State, http::header, response::IntoResponse, Extension, Json};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, Result},
+    extract::Query,
+    middleware::AuthUser,
+    models::calendar::{CalendarFeedQuery, CalendarToken},
+    services::calendar::{self, CalendarEvent},
+    AppState,
+};
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// The user's company and its name, while they are an active member
+async fn active_company(db: &sqlx::PgPool, user_id: Uuid) -> Result<(Uuid, String)> {
+    let company = sqlx::query!(
+        r#"
+        SELECT cm.company_id, cp.company_name
+        FROM company_members cm
+        JOIN company_profiles cp ON cp.id = cm.company_id
+        JOIN users u ON u.id = cm.user_id
+        WHERE cm.user_id = $1 AND cm.is_active = true
+          AND u.account_status = 'active'
+        "#,
+        user_id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::ForbiddenError("User is not a member of any company".to_string()))?;
+
+    Ok((company.company_id, company.company_name))
+}
+
+fn require_company_member(auth_user: &AuthUser) -> Result<()> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// An iCalendar document; calendar apps poll it, so it's never cached
+fn ics_response(name: &str, events: &[CalendarEvent]) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "private, no-cache"),
+        ],
+        calendar::render(name, events, Utc::now()),
+    )
+}
+
+// ============================================================================
+// FEEDS
+// ============================================================================
+
+/// GET /api/me/company/interviews.ics
+/// Upcoming interviews of the company's applicants as an iCalendar feed.
+/// Calendar apps pass the member's calendar token instead of signing in.
+pub async fn get_company_interviews_feed(
+    State(state): State<AppState>,
+    auth_user: Option<Extension<AuthUser>>,
+    Query(query): Query<CalendarFeedQuery>,
+) -> Result<impl IntoResponse> {
+    let user_id = match (query.token.as_deref(), auth_user) {
+        (Some(token), _) => calendar::user_for_token(&state.db, token)
+            .await?
+            .ok_or_else(|| {
+                AppError::AuthenticationError("Invalid or revoked calendar token".to_string())
+            })?,
+        (None, Some(Extension(auth_user))) => {
+            require_company_member(&auth_user)?;
+            auth_user.id
+        }
+        (None, None) => {
+            return Err(AppError::AuthenticationError(
+                "Sign in or use the calendar link".to_string(),
+            ));
+        }
+    };
+
+    // Checked on every read, so removed members' links stop working
+    let (company_id, company_name) = active_company(&state.db, user_id).await?;
+    let events = calendar::company_interviews(&state.db, company_id).await?;
+
+    Ok(ics_response(
+        &format!("Entrevistas - {}", company_name),
+        &events,
+    ))
+}
+
+/// GET /api/me/interviews.ics
+/// The job seeker's upcoming interviews as an iCalendar feed
+pub async fn get_seeker_interviews_feed(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let events = calendar::seeker_interviews(&state.db, auth_user.id).await?;
+    Ok(ics_response("Mis entrevistas", &events))
+}
+
+// ============================================================================
+// CALENDAR TOKENS
+// ============================================================================
+
+/// GET /api/me/company/calendar-token
+/// The member's calendar token, created on first request. The token and the
+/// feed URL are only shown then; revoke the token to get a new one.
+pub async fn get_calendar_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<CalendarToken>> {
+    require_company_member(&auth_user)?;
+    active_company(&state.db, auth_user.id).await?;
+
+    let token = calendar::get_or_create_token(&state.db, &state.config, auth_user.id).await?;
+    Ok(Json(token))
+}
+
+/// DELETE /api/me/company/calendar-token
+/// Revoke the member's calendar token; subscribed calendars stop updating
+pub async fn revoke_calendar_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<serde_json::Value>> {
+    require_company_member(&auth_user)?;
+
+    if !calendar::revoke_token(&state.db, auth_user.id).await? {
+        return Err(AppError::NotFound("Calendar token not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "Calendar token revoked successfully"
+    })))
+}
@@ -1,18 +1,27 @@
 use axum::{
-    extract::{Path, State},
+    extract::State,
+    http::StatusCode,
     Extension, Json,
 };
+use std::collections::HashMap;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
     middleware::AuthUser,
     models::{
-        admin::{ApplicationStatusCount, CompanyDashboard, TopJobPerformance, TrendDataPoint},
+        admin::{
+            ApplicationStatusCount, CompanyDashboard, JobRejectionReasons, RejectionReasonCount,
+            TopJobPerformance, TrendDataPoint,
+        },
+        application::RejectionReasonCode,
         company::*,
         user::{MessageResponse, UserResponse},
     },
+    services::{benchmarks, company_deletion, company_export, completeness, job_reposts, metering},
+    utils::normalize::Normalize,
     AppState,
 };
 
@@ -47,26 +56,87 @@ fn is_owner_or_admin(role: MemberRole) -> bool {
     matches!(role, MemberRole::Owner | MemberRole::Admin)
 }
 
-// ============================================================================
-// AUTHENTICATED COMPANY ENDPOINTS
-// ============================================================================
+/// Industries of each of the given companies, primary first
+async fn fetch_company_industries(
+    db: &sqlx::PgPool,
+    company_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<CompanyIndustry>>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT ci.company_id, ci.industry_id, i.name as industry_name, ci.is_primary
+        FROM company_industries ci
+        JOIN industries i ON i.id = ci.industry_id
+        WHERE ci.company_id = ANY($1)
+        ORDER BY ci.is_primary DESC, i.sort_order, i.name
+        "#,
+        company_ids,
+    )
+    .fetch_all(db)
+    .await?;
 
-/// GET /api/me/company/profile
-/// Get current user's company profile
-pub async fn get_company_profile(
-    State(state): State<AppState>,
-    Extension(auth_user): Extension<AuthUser>,
-) -> Result<Json<CompanyProfile>> {
-    // Only company members can access this
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
+    let mut by_company: HashMap<Uuid, Vec<CompanyIndustry>> = HashMap::new();
+    for row in rows {
+        by_company
+            .entry(row.company_id)
+            .or_default()
+            .push(CompanyIndustry {
+                industry_id: row.industry_id,
+                industry_name: row.industry_name,
+                is_primary: row.is_primary,
+            });
     }
 
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    Ok(by_company)
+}
+
+/// Replace the company's industries, keeping the deprecated
+/// company_profiles.industry_id in step with the primary one
+async fn replace_company_industries(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    company_id: Uuid,
+    industries: &[CompanyIndustryInput],
+) -> Result<()> {
+    let ids: Vec<Uuid> = industries.iter().map(|i| i.industry_id).collect();
+    let primaries: Vec<bool> = industries.iter().map(|i| i.is_primary).collect();
+
+    let known = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM industries WHERE id = ANY($1) AND is_active = true"#,
+        &ids,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    if known != ids.len() as i64 {
+        return Err(AppError::ValidationError("Unknown industry".to_string()));
+    }
+
+    sqlx::query!(
+        "DELETE FROM company_industries WHERE company_id = $1",
+        company_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO company_industries (company_id, industry_id, is_primary)
+        SELECT $1, industry_id, is_primary
+        FROM UNNEST($2::uuid[], $3::bool[]) AS t(industry_id, is_primary)
+        "#,
+        company_id,
+        &ids,
+        &primaries,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
 
-    // Get company profile
+/// The company's profile as its members see it, with pending changes applied
+async fn fetch_own_profile(
+    db: &sqlx::PgPool,
+    company_id: Uuid,
+) -> Result<OwnCompanyProfile> {
     let profile = sqlx::query_as!(
         CompanyProfile,
         r#"
@@ -85,19 +155,65 @@ pub async fn get_company_profile(
         "#,
         company_id,
     )
-    .fetch_one(&state.db)
+    .fetch_one(db)
+    .await?;
+
+    let pending_changes = sqlx::query_as!(
+        PendingProfileChange,
+        r#"
+        SELECT id, company_id, field_name, old_value, new_value,
+               status as "status: ProfileChangeStatus",
+               requested_by, reviewed_by, reviewed_at, rejection_reason, created_at
+        FROM pending_profile_changes
+        WHERE company_id = $1 AND status = 'pending'
+        ORDER BY created_at
+        "#,
+        company_id,
+    )
+    .fetch_all(db)
     .await?;
 
+    let industries = fetch_company_industries(db, &[company_id])
+        .await?
+        .remove(&company_id)
+        .unwrap_or_default();
+
+    Ok(OwnCompanyProfile::new(profile, industries, pending_changes))
+}
+
+// ============================================================================
+// AUTHENTICATED COMPANY ENDPOINTS
+// ============================================================================
+
+/// GET /api/me/company/profile
+/// Get current user's company profile, including changes awaiting review
+pub async fn get_company_profile(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<OwnCompanyProfile>> {
+    // Only company members can access this
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    let profile = fetch_own_profile(&state.db, company_id).await?;
+
     Ok(Json(profile))
 }
 
 /// PUT /api/me/company/profile
-/// Update current user's company profile (owner/admin only)
+/// Update current user's company profile (owner/admin only). Once the company
+/// is active, changes to the name, legal name and RUT are held for admin review.
 pub async fn update_company_profile(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(payload): Json<UpdateCompanyProfileRequest>,
-) -> Result<Json<CompanyProfile>> {
+    Json(mut payload): Json<UpdateCompanyProfileRequest>,
+) -> Result<Json<OwnCompanyProfile>> {
+    payload.normalize();
     payload.validate()?;
 
     if auth_user.user_type != "company_member" {
@@ -115,9 +231,114 @@ pub async fn update_company_profile(
         ));
     }
 
+    let mut tx = state.db.begin().await?;
+
+    let current = sqlx::query!(
+        r#"
+        SELECT company_name, legal_name, tax_id,
+               status as "status: crate::models::company::OrganizationStatus"
+        FROM company_profiles
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        company_id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if current.status == OrganizationStatus::Active {
+        let requested = [
+            ("company_name", payload.company_name.take(), Some(current.company_name)),
+            ("legal_name", payload.legal_name.take(), current.legal_name),
+            ("tax_id", payload.tax_id.take(), current.tax_id),
+        ];
+
+        for (field, new_value, old_value) in requested {
+            let Some(new_value) = new_value else {
+                continue;
+            };
+
+            // Reverting to the approved value withdraws the pending change
+            if Some(&new_value) == old_value.as_ref() {
+                sqlx::query!(
+                    r#"
+                    DELETE FROM pending_profile_changes
+                    WHERE company_id = $1 AND field_name = $2 AND status = 'pending'
+                    "#,
+                    company_id,
+                    field,
+                )
+                .execute(&mut *tx)
+                .await?;
+                continue;
+            }
+
+            sqlx::query!(
+                r#"
+                INSERT INTO pending_profile_changes
+                    (company_id, field_name, old_value, new_value, requested_by)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (company_id, field_name) WHERE status = 'pending'
+                DO UPDATE SET
+                    old_value = EXCLUDED.old_value,
+                    new_value = EXCLUDED.new_value,
+                    requested_by = EXCLUDED.requested_by,
+                    created_at = NOW()
+                "#,
+                company_id,
+                field,
+                old_value,
+                new_value,
+                auth_user.id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    // The industries list takes precedence over the deprecated single
+    // industry_id, which now only swaps out the primary industry
+    let primary_industry_id = match payload.industries {
+        Some(ref industries) => {
+            replace_company_industries(&mut tx, company_id, industries).await?;
+            industries
+                .iter()
+                .find(|i| i.is_primary)
+                .map(|i| i.industry_id)
+        }
+        None => {
+            if let Some(industry_id) = payload.industry_id {
+                let mut industries: Vec<CompanyIndustryInput> = sqlx::query!(
+                    r#"
+                    SELECT industry_id FROM company_industries
+                    WHERE company_id = $1 AND NOT is_primary AND industry_id <> $2
+                    "#,
+                    company_id,
+                    industry_id,
+                )
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .map(|row| CompanyIndustryInput {
+                    industry_id: row.industry_id,
+                    is_primary: false,
+                })
+                .collect();
+                industries.insert(
+                    0,
+                    CompanyIndustryInput {
+                        industry_id,
+                        is_primary: true,
+                    },
+                );
+                replace_company_industries(&mut tx, company_id, &industries).await?;
+            }
+            payload.industry_id
+        }
+    };
+
     // Update the profile using COALESCE pattern
-    let profile = sqlx::query_as!(
-        CompanyProfile,
+    sqlx::query!(
         r#"
         UPDATE company_profiles
         SET company_name = COALESCE($2, company_name),
@@ -142,22 +363,12 @@ pub async fn update_company_profile(
             benefits = COALESCE($21, benefits),
             updated_at = NOW()
         WHERE id = $1
-        RETURNING id, company_name, legal_name, tax_id,
-                  industry_id, company_size, founded_year,
-                  region_id, municipality_id, address, phone,
-                  website_url, linkedin_url, video_url,
-                  logo_url, cover_image_url,
-                  description, mission, vision, culture, benefits,
-                  status as "status: crate::models::company::OrganizationStatus",
-                  approved_at, approved_by, rejection_reason,
-                  is_featured, can_search_candidates,
-                  completeness_percentage, created_at, updated_at
         "#,
         company_id,
         payload.company_name,
         payload.legal_name,
         payload.tax_id,
-        payload.industry_id,
+        primary_industry_id,
         payload.company_size,
         payload.founded_year,
         payload.region_id,
@@ -175,9 +386,13 @@ pub async fn update_company_profile(
         payload.culture,
         payload.benefits,
     )
-    .fetch_one(&state.db)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
+    let profile = fetch_own_profile(&state.db, company_id).await?;
+
     Ok(Json(profile))
 }
 
@@ -196,34 +411,14 @@ pub async fn get_full_company_profile(
     let (company_id, current_user_role) =
         get_user_company_membership(&state.db, auth_user.id).await?;
 
-    // Get company profile
-    let profile = sqlx::query_as!(
-        CompanyProfile,
-        r#"
-        SELECT id, company_name, legal_name, tax_id,
-               industry_id, company_size, founded_year,
-               region_id, municipality_id, address, phone,
-               website_url, linkedin_url, video_url,
-               logo_url, cover_image_url,
-               description, mission, vision, culture, benefits,
-               status as "status: crate::models::company::OrganizationStatus",
-               approved_at, approved_by, rejection_reason,
-               is_featured, can_search_candidates,
-               completeness_percentage, created_at, updated_at
-        FROM company_profiles
-        WHERE id = $1
-        "#,
-        company_id,
-    )
-    .fetch_one(&state.db)
-    .await?;
+    let profile = fetch_own_profile(&state.db, company_id).await?;
 
     // Get all company members with user details
     let members_data = sqlx::query!(
         r#"
         SELECT cm.id, cm.company_id, cm.role as "role: crate::models::company::MemberRole",
                cm.job_title, cm.is_active, cm.joined_at,
-               u.id as user_id, u.email, u.first_name, u.last_name,
+               u.id as user_id, u.email as "email!", u.first_name, u.last_name,
                u.user_type as "user_type: crate::models::user::UserType",
                u.account_status as "account_status: crate::models::user::AccountStatus",
                u.email_verified_at, u.created_at
@@ -290,7 +485,7 @@ pub async fn list_members(
         r#"
         SELECT cm.id, cm.company_id, cm.role as "role: crate::models::company::MemberRole",
                cm.job_title, cm.is_active, cm.joined_at,
-               u.id as user_id, u.email, u.first_name, u.last_name,
+               u.id as user_id, u.email as "email!", u.first_name, u.last_name,
                u.user_type as "user_type: crate::models::user::UserType",
                u.account_status as "account_status: crate::models::user::AccountStatus",
                u.email_verified_at, u.created_at
@@ -495,6 +690,7 @@ pub async fn remove_member(
 /// List active companies (public)
 pub async fn list_public_companies(
     State(state): State<AppState>,
+    Query(params): Query<PublicCompanyListQuery>,
 ) -> Result<Json<Vec<PublicCompanyProfile>>> {
     // Get active companies only
     let companies = sqlx::query_as!(
@@ -510,19 +706,34 @@ pub async fn list_public_companies(
                approved_at, approved_by, rejection_reason,
                is_featured, can_search_candidates,
                completeness_percentage, created_at, updated_at
-        FROM company_profiles
+        FROM company_profiles cp
         WHERE status = 'active'
+          AND (
+            $1::uuid IS NULL
+            OR EXISTS (
+                SELECT 1 FROM company_industries ci
+                WHERE ci.company_id = cp.id AND ci.industry_id = $1
+            )
+          )
         ORDER BY is_featured DESC, company_name ASC
         LIMIT 100
         "#,
+        params.industry_id,
     )
     .fetch_all(&state.db)
     .await?;
 
+    let ids: Vec<Uuid> = companies.iter().map(|c| c.id).collect();
+    let mut industries = fetch_company_industries(&state.db, &ids).await?;
+
     // Convert to public profiles
     let public_profiles = companies
         .into_iter()
-        .map(PublicCompanyProfile::from)
+        .map(|company| {
+            let mut profile = PublicCompanyProfile::from(company);
+            profile.industries = industries.remove(&profile.id).unwrap_or_default();
+            profile
+        })
         .collect();
 
     Ok(Json(public_profiles))
@@ -557,7 +768,13 @@ pub async fn get_public_company(
     .await?
     .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
 
-    Ok(Json(PublicCompanyProfile::from(company)))
+    let mut profile = PublicCompanyProfile::from(company);
+    profile.industries = fetch_company_industries(&state.db, &[company_id])
+        .await?
+        .remove(&company_id)
+        .unwrap_or_default();
+
+    Ok(Json(profile))
 }
 
 // ============================================================================
@@ -661,15 +878,74 @@ pub async fn get_company_dashboard(
     .fetch_all(&state.db)
     .await?;
 
-    let top_jobs_list: Vec<TopJobPerformance> = top_jobs
-        .into_iter()
-        .map(|row| TopJobPerformance {
+    let mut top_jobs_list = Vec::with_capacity(top_jobs.len());
+    for row in top_jobs {
+        top_jobs_list.push(TopJobPerformance {
+            repeat_applicants_count: job_reposts::count_repeat_applicants(&state.db, row.id)
+                .await?,
             job_id: row.id,
             title: row.title,
             applications_count: row.applications_count,
             status: row.status,
-        })
-        .collect();
+        });
+    }
+
+    // Rejection reasons per job, most used first
+    let rejection_rows = sqlx::query!(
+        r#"
+        SELECT
+            j.id,
+            j.title,
+            ja.rejection_reason_code AS "reason: RejectionReasonCode",
+            COUNT(*) AS "count!"
+        FROM job_applications ja
+        JOIN jobs j ON ja.job_id = j.id
+        WHERE j.company_id = $1 AND ja.status = 'rejected'
+        GROUP BY j.id, j.title, ja.rejection_reason_code
+        ORDER BY j.title, j.id, COUNT(*) DESC, ja.rejection_reason_code
+        "#,
+        company_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut rejection_reasons: Vec<JobRejectionReasons> = Vec::new();
+    for row in rejection_rows {
+        let reason = RejectionReasonCount {
+            reason: row.reason,
+            count: row.count,
+        };
+        match rejection_reasons.last_mut() {
+            Some(job) if job.job_id == row.id => job.reasons.push(reason),
+            _ => rejection_reasons.push(JobRejectionReasons {
+                job_id: row.id,
+                title: row.title,
+                reasons: vec![reason],
+            }),
+        }
+    }
+
+    // Checklists for open jobs that are missing too much, lowest score first
+    let low_scoring_ids = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM jobs
+        WHERE company_id = $1
+          AND status NOT IN ('closed', 'rejected')
+          AND completeness_percentage < $2
+        "#,
+        company_id,
+        completeness::INCOMPLETE_JOB_THRESHOLD,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut incomplete_jobs = Vec::new();
+    if !low_scoring_ids.is_empty() {
+        let mut conn = state.db.acquire().await?;
+        incomplete_jobs = completeness::score(&mut conn, &low_scoring_ids).await?;
+        incomplete_jobs.retain(|job| job.score < completeness::INCOMPLETE_JOB_THRESHOLD);
+        incomplete_jobs.sort_by_key(|job| job.score);
+    }
 
     Ok(Json(CompanyDashboard {
         active_jobs,
@@ -677,5 +953,312 @@ pub async fn get_company_dashboard(
         applications_by_status,
         trend,
         top_jobs: top_jobs_list,
+        rejection_reasons,
+        incomplete_jobs,
     }))
 }
+
+// ============================================================================
+// USAGE METERING
+// ============================================================================
+
+/// GET /api/me/company/usage
+/// The company's metered usage in a month (?month=YYYY-MM, the current one
+/// by default), as of the last nightly aggregation
+pub async fn get_company_usage(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<CompanyUsage>> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let month = metering::parse_month(
+        &state.db,
+        query.month.as_deref(),
+        &state.config.platform_timezone,
+    )
+    .await?;
+
+    Ok(Json(
+        metering::company_usage(&state.db, company_id, month).await?,
+    ))
+}
+
+// ============================================================================
+// V13: COMPANY BENCHMARKS
+// ============================================================================
+
+/// GET /api/me/company/benchmarks
+/// Compare each active job's last 30 days against anonymous platform percentiles
+pub async fn get_company_benchmarks(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<CompanyBenchmarksResponse>> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    let jobs = sqlx::query!(
+        r#"
+        SELECT
+            j.id,
+            j.title,
+            j.industry_id,
+            j.region_id,
+            COALESCE((
+                SELECT SUM(v.views) FROM job_view_stats v
+                WHERE v.job_id = j.id AND v.view_date > CURRENT_DATE - $2::int
+            ), 0)::BIGINT AS "views!",
+            (
+                SELECT COUNT(*) FROM job_applications a
+                WHERE a.job_id = j.id AND a.applied_at >= NOW() - make_interval(days => $2)
+            ) AS "applications!"
+        FROM jobs j
+        WHERE j.company_id = $1 AND j.status = 'active'
+        ORDER BY j.created_at DESC
+        "#,
+        company_id,
+        benchmarks::PERIOD_DAYS,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Jobs in the same bucket share one benchmark
+    let mut by_bucket: HashMap<(Option<Uuid>, Option<Uuid>), PlatformBenchmark> = HashMap::new();
+    let mut redis = state.redis.clone();
+    let mut result = Vec::with_capacity(jobs.len());
+
+    for job in jobs {
+        let bucket = (job.industry_id, job.region_id);
+        let benchmark = match by_bucket.get(&bucket) {
+            Some(benchmark) => benchmark.clone(),
+            None => {
+                let benchmark =
+                    benchmarks::benchmark_for(&state.db, &mut redis, bucket.0, bucket.1).await?;
+                by_bucket.insert(bucket, benchmark.clone());
+                benchmark
+            }
+        };
+
+        let conversion_rate = (job.views > 0).then(|| {
+            (job.applications as f64 / job.views as f64 * 10_000.0).round() / 10_000.0
+        });
+
+        result.push(JobBenchmark {
+            job_id: job.id,
+            title: job.title,
+            industry_id: job.industry_id,
+            region_id: job.region_id,
+            views: job.views,
+            applications: job.applications,
+            conversion_rate,
+            benchmark,
+        });
+    }
+
+    Ok(Json(CompanyBenchmarksResponse {
+        period_days: benchmarks::PERIOD_DAYS as i64,
+        jobs: result,
+    }))
+}
+
+// ============================================================================
+// V13: COMPANY SETTINGS
+// ============================================================================
+
+/// GET /api/me/company/settings
+/// Get the company's view preferences
+pub async fn get_company_settings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<CompanySettings>> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    let settings = sqlx::query_as!(
+        CompanySettings,
+        r#"
+        SELECT show_match_scores, syndication_enabled, allow_contact_export
+        FROM company_profiles
+        WHERE id = $1
+        "#,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+/// PUT /api/me/company/settings
+/// Update the company's view preferences (owner/admin only)
+pub async fn update_company_settings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<UpdateCompanySettingsRequest>,
+) -> Result<Json<CompanySettings>> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    if !is_owner_or_admin(role) {
+        return Err(AppError::ForbiddenError(
+            "Only company owners or admins can change settings".to_string(),
+        ));
+    }
+
+    let settings = sqlx::query_as!(
+        CompanySettings,
+        r#"
+        UPDATE company_profiles
+        SET show_match_scores = COALESCE($2, show_match_scores),
+            syndication_enabled = COALESCE($3, syndication_enabled)
+        WHERE id = $1
+        RETURNING show_match_scores, syndication_enabled, allow_contact_export
+        "#,
+        company_id,
+        payload.show_match_scores,
+        payload.syndication_enabled,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+// ============================================================================
+// V13: COMPANY DATA EXPORTS
+// ============================================================================
+
+/// Company of the calling member, who must be its owner
+async fn require_company_owner(state: &AppState, auth_user: &AuthUser) -> Result<Uuid> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    if role != MemberRole::Owner {
+        return Err(AppError::ForbiddenError(
+            "Only the company owner can export the company's data".to_string(),
+        ));
+    }
+
+    Ok(company_id)
+}
+
+/// POST /api/me/company/data-export
+/// Queue an export of all the company's jobs, applicants and profile (owner only)
+pub async fn request_data_export(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<(StatusCode, Json<CompanyDataExport>)> {
+    let company_id = require_company_owner(&state, &auth_user).await?;
+
+    let export_id = company_export::enqueue(&state.db, company_id, auth_user.id).await?;
+    let export =
+        company_export::get_export(&state.db, state.storage.as_ref(), company_id, export_id)
+            .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(export)))
+}
+
+/// GET /api/me/company/data-export/{id}
+/// Progress of a data export, with its download link once ready (owner only)
+pub async fn get_data_export(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(export_id): Path<Uuid>,
+) -> Result<Json<CompanyDataExport>> {
+    let company_id = require_company_owner(&state, &auth_user).await?;
+
+    let export =
+        company_export::get_export(&state.db, state.storage.as_ref(), company_id, export_id)
+            .await?;
+
+    Ok(Json(export))
+}
+
+// ============================================================================
+// V13: COMPANY DELETION
+// ============================================================================
+
+/// DELETE /api/me/company
+/// Wind the company down (owner only, once it has no active jobs). Every
+/// member, the owner included, loses access; an admin can reopen it until it
+/// is purged.
+pub async fn delete_company(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<CompanyDeletionResponse>> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
+
+    if role != MemberRole::Owner {
+        return Err(AppError::ForbiddenError(
+            "Only the company owner can delete the company".to_string(),
+        ));
+    }
+
+    let active_jobs = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM jobs WHERE company_id = $1 AND status = 'active'"#,
+        company_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if active_jobs > 0 {
+        return Err(AppError::ValidationError(format!(
+            "Close the company's {} active job(s) before deleting it",
+            active_jobs
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let wind_down = company_deletion::wind_down(&mut tx, company_id, auth_user.id).await?;
+    tx.commit().await?;
+
+    let response = CompanyDeletionResponse {
+        company_id,
+        status: OrganizationStatus::Closing,
+        purge_after: Some(wind_down.purge_after),
+        jobs_closed: wind_down.jobs_closed,
+        members_deactivated: wind_down.members_deactivated,
+        applicants_notified: wind_down.applicants.len() as i64,
+        jobs_archived: 0,
+    };
+
+    // A job activated since the check above was closed with the rest
+    company_deletion::notify_applicants(
+        &state,
+        &wind_down.company_name,
+        wind_down.applicants,
+    );
+
+    Ok(Json(response))
+}
@@ -0,0 +1,49 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::{
+    error::{AppError, Result},
+    services::job_feed,
+    AppState,
+};
+
+/// A feed exactly as the scheduler last stored it
+async fn serve_feed(
+    state: &AppState,
+    key: &str,
+    content_type: &'static str,
+) -> Result<impl IntoResponse> {
+    let mut redis = state.redis.clone();
+    let Some(feed) = job_feed::cached(&mut redis, key).await else {
+        return Err(AppError::ServiceUnavailable(
+            "The job feed is being generated, try again later".to_string(),
+        ));
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CACHE_CONTROL,
+                format!("public, max-age={}", job_feed::REFRESH_INTERVAL_SECONDS),
+            ),
+        ],
+        feed,
+    ))
+}
+
+/// GET /api/feeds/jobs.xml
+/// Active jobs for aggregators in the common job-board XML format
+pub async fn get_jobs_xml_feed(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    serve_feed(
+        &state,
+        job_feed::XML_CACHE_KEY,
+        "application/xml; charset=utf-8",
+    )
+    .await
+}
+
+/// GET /api/feeds/jobs.json
+/// Active jobs as schema.org JobPosting objects
+pub async fn get_jobs_json_feed(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    serve_feed(&state, job_feed::JSON_CACHE_KEY, "application/json").await
+}
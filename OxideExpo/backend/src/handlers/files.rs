@@ -1,20 +1,25 @@
 use axum::{
     body::Body,
-    extract::{Multipart, Path, State},
+    extract::{FromRequest, Multipart, Request, State},
     http::{header, StatusCode},
     response::Response,
     Extension, Json,
 };
 use bytes::Bytes;
+use chrono::Utc;
 use uuid::Uuid;
 
 use crate::{
     error::{AppError, Result},
-    middleware::AuthUser,
+    extract::{Path, Query},
+    middleware::{AuthUser, OmilContext},
     models::{
         company::MemberRole,
         file::*,
+        profile::{CvParseFailure, CvSuggestions},
     },
+    services::cv_parse,
+    utils::jwt,
     AppState,
 };
 
@@ -101,12 +106,9 @@ async fn upload_file_internal(
     content_type: String,
     data: Bytes,
 ) -> Result<UploadedFile> {
-    let storage = state.storage.as_ref().ok_or_else(|| {
-        AppError::InternalError("Storage service not configured".to_string())
-    })?;
-
     // Upload to storage
-    let result = storage
+    let result = state
+        .storage
         .upload(file_type.storage_folder(), &filename, &content_type, data.clone())
         .await?;
 
@@ -136,10 +138,6 @@ async fn delete_file_internal(
     file_id: Uuid,
     user_id: Uuid,
 ) -> Result<()> {
-    let storage = state.storage.as_ref().ok_or_else(|| {
-        AppError::InternalError("Storage service not configured".to_string())
-    })?;
-
     // Get file info
     let file = sqlx::query!(
         r#"SELECT storage_path FROM uploaded_files WHERE id = $1 AND user_id = $2"#,
@@ -151,7 +149,7 @@ async fn delete_file_internal(
     .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
 
     // Delete from storage
-    storage.delete(&file.storage_path).await?;
+    state.storage.delete(&file.storage_path).await?;
 
     // Delete from database
     sqlx::query!(
@@ -259,6 +257,71 @@ pub async fn delete_cv(
     }))
 }
 
+/// POST /api/me/profile/cv/parse
+/// Suggest profile data from a CV: a file sent as multipart, or else the
+/// stored CV. Nothing is saved; a CV that cannot be read gives an empty set
+/// of suggestions with the reason.
+pub async fn parse_cv(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    request: Request,
+) -> Result<Json<CvSuggestions>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can parse CVs".to_string(),
+        ));
+    }
+
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let data = if is_multipart {
+        let mut multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|e| AppError::ValidationError(format!("Failed to read upload: {}", e)))?;
+        let (_, content_type, data) = validate_and_extract_file(&mut multipart, FileType::Cv).await?;
+        if content_type != "application/pdf" {
+            return Ok(Json(CvSuggestions::failed(CvParseFailure::UnsupportedFormat)));
+        }
+        data
+    } else {
+        let file = sqlx::query!(
+            r#"
+            SELECT f.storage_path, f.content_type
+            FROM job_seeker_profiles p
+            JOIN uploaded_files f ON f.id = p.cv_file_id
+            WHERE p.user_id = $1
+            "#,
+            auth_user.id,
+        )
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No CV uploaded".to_string()))?;
+
+        // Checked before downloading, as only PDFs are read
+        if file.content_type.as_deref() != Some("application/pdf") {
+            return Ok(Json(CvSuggestions::failed(CvParseFailure::UnsupportedFormat)));
+        }
+
+        state.storage.get(&file.storage_path).await?
+    };
+
+    // Text extraction is CPU-bound and can take a while on long CVs
+    let text = tokio::task::spawn_blocking(move || cv_parse::extract_text(&data))
+        .await
+        .map_err(|e| AppError::InternalError(format!("CV parsing task failed: {}", e)))?;
+
+    let suggestions = match text {
+        Ok(text) => cv_parse::suggest(&text, Utc::now().date_naive()),
+        Err(reason) => CvSuggestions::failed(reason),
+    };
+
+    Ok(Json(suggestions))
+}
+
 /// PUT /api/me/profile/image
 /// Upload profile image for job seeker
 pub async fn upload_profile_image(
@@ -566,6 +629,104 @@ pub async fn delete_company_cover(
     }))
 }
 
+// ============================================================================
+// OMIL FILE ENDPOINTS
+// ============================================================================
+
+/// Remove an OMIL's current logo file. Any director may replace a logo, so
+/// the file is deleted on behalf of whoever uploaded it.
+async fn delete_omil_logo_file(state: &AppState, file_id: Uuid) -> Result<()> {
+    let uploader = sqlx::query_scalar!(
+        r#"SELECT user_id FROM uploaded_files WHERE id = $1"#,
+        file_id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+    delete_file_internal(state, file_id, uploader).await
+}
+
+/// PUT /api/me/omil/logo
+/// Upload the OMIL logo used in branded exports (director only)
+pub async fn upload_omil_logo(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    mut multipart: Multipart,
+) -> Result<Json<FileUploadResponse>> {
+    let (filename, content_type, data) =
+        validate_and_extract_file(&mut multipart, FileType::OmilLogo).await?;
+
+    // Delete existing logo if any
+    let existing = sqlx::query_scalar!(
+        r#"SELECT logo_file_id FROM omil_organizations WHERE id = $1"#,
+        omil_ctx.organization.id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if let Some(file_id) = existing {
+        let _ = delete_omil_logo_file(&state, file_id).await;
+    }
+
+    let file = upload_file_internal(
+        &state,
+        auth_user.id,
+        FileType::OmilLogo,
+        filename,
+        content_type,
+        data,
+    )
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE omil_organizations SET logo_file_id = $1 WHERE id = $2"#,
+        file.id,
+        omil_ctx.organization.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    let download_url = format!("/api/files/{}", file.id);
+
+    Ok(Json(FileUploadResponse {
+        file_id: file.id,
+        file_type: file.file_type,
+        original_filename: file.original_filename,
+        file_size_bytes: file.file_size_bytes.unwrap_or(0),
+        download_url,
+    }))
+}
+
+/// DELETE /api/me/omil/logo
+/// Delete the OMIL logo (director only)
+pub async fn delete_omil_logo(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<FileDeleteResponse>> {
+    let file_id = sqlx::query_scalar!(
+        r#"SELECT logo_file_id FROM omil_organizations WHERE id = $1"#,
+        omil_ctx.organization.id,
+    )
+    .fetch_one(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No OMIL logo uploaded".to_string()))?;
+
+    // Clear organization reference first
+    sqlx::query!(
+        r#"UPDATE omil_organizations SET logo_file_id = NULL WHERE id = $1"#,
+        omil_ctx.organization.id,
+    )
+    .execute(&state.db)
+    .await?;
+
+    delete_omil_logo_file(&state, file_id).await?;
+
+    Ok(Json(FileDeleteResponse {
+        message: "OMIL logo deleted successfully".to_string(),
+    }))
+}
+
 // ============================================================================
 // FILE DOWNLOAD ENDPOINT
 // ============================================================================
@@ -577,10 +738,6 @@ pub async fn download_file(
     Extension(_auth_user): Extension<AuthUser>,
     Path(file_id): Path<Uuid>,
 ) -> Result<Response> {
-    let storage = state.storage.as_ref().ok_or_else(|| {
-        AppError::InternalError("Storage service not configured".to_string())
-    })?;
-
     // Get file info
     let file = sqlx::query!(
         r#"
@@ -595,7 +752,7 @@ pub async fn download_file(
     .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
 
     // Get file content
-    let data = storage.get(&file.storage_path).await?;
+    let data = state.storage.get(&file.storage_path).await?;
 
     let content_type = file
         .content_type
@@ -613,3 +770,31 @@ pub async fn download_file(
 
     Ok(response)
 }
+
+/// GET /api/files/download?token=
+/// Streams a stored object through a link from `StorageBackend::presign_get`,
+/// for storage drivers that cannot presign URLs themselves (local and
+/// in-memory). The signed token names the object, so no session is needed.
+pub async fn download_stored_file(
+    State(state): State<AppState>,
+    Query(query): Query<FileDownloadQuery>,
+) -> Result<Response> {
+    let claims = jwt::verify_file_download_token(&query.token, &state.config).map_err(|_| {
+        AppError::ValidationError("Invalid or expired download link".to_string())
+    })?;
+
+    let data = state.storage.get(&claims.sub).await?;
+    let filename = claims.sub.rsplit('/').next().unwrap_or_default();
+
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from(data))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+
+    Ok(response)
+}
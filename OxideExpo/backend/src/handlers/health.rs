@@ -1,3 +1,4 @@
+use crate::services::maintenance;
 use crate::AppState;
 use axum::{extract::State, http::StatusCode, Json};
 use serde::Serialize;
@@ -13,6 +14,8 @@ pub struct ReadinessResponse {
     pub db: String,
     pub redis: String,
     pub s3: String,
+    /// off, read_only or full
+    pub maintenance_mode: String,
 }
 
 /// Basic health check endpoint for load balancers
@@ -31,6 +34,7 @@ pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<Readi
         db: "ok".to_string(),
         redis: "ok".to_string(),
         s3: "ok".to_string(),
+        maintenance_mode: maintenance::current(&state).await.as_str().to_string(),
     };
 
     let mut has_error = false;
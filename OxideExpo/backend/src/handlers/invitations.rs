@@ -1,19 +1,31 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     Extension, Json,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::Acquire;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::error::AppError;
+use crate::extract::{Path, Query};
+use crate::handlers::applications::fetch_draft;
 use crate::middleware::auth::AuthUser;
-use crate::models::company::MemberRole;
-use crate::models::job::{JobType, PublicJobListing, WorkModality};
+use crate::models::admin::AggregateType;
+use crate::models::application::ApplicationSource;
+use crate::models::company::{MemberRole, UsageEventType};
+use crate::models::job::JobStatus;
+use crate::models::notification::NotificationEvent;
 use crate::models::omil::{
-    InvitationStatus, InvitationsQuery, JobInvitation, JobInvitationWithDetails,
-    RespondToInvitationRequest, SendJobInvitationRequest,
+    InvitationJobSnapshot, InvitationSort, InvitationStatus, InvitationStatusCounts,
+    InvitationWarning, InvitationsQuery, JobInvitation, JobInvitationForCompany,
+    JobInvitationWithDetails, JobInvitationsResponse, MyInvitationsResponse,
+    RespondToInvitationRequest, RespondToInvitationResponse, SendJobInvitationRequest,
+    SendJobInvitationResponse,
 };
+use crate::models::profile::SeekerAvailability;
+use crate::services::applications::{self, NewApplication};
+use crate::services::{events, metering, notifications};
 use crate::AppState;
 
 // ============================================================================
@@ -42,6 +54,59 @@ async fn get_user_company_membership(
     Ok((member.company_id, member.role))
 }
 
+/// An invitation as the job seeker sees it
+struct InvitationDetailsRow {
+    id: Uuid,
+    job_id: Option<Uuid>,
+    job_seeker_id: Uuid,
+    invited_by: Uuid,
+    company_id: Uuid,
+    message: Option<String>,
+    status: InvitationStatus,
+    viewed_at: Option<DateTime<Utc>>,
+    responded_at: Option<DateTime<Utc>>,
+    expires_at: DateTime<Utc>,
+    application_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    job_snapshot: sqlx::types::Json<InvitationJobSnapshot>,
+    job_changed: bool,
+    company_name: String,
+}
+
+impl From<InvitationDetailsRow> for JobInvitationWithDetails {
+    fn from(row: InvitationDetailsRow) -> Self {
+        JobInvitationWithDetails {
+            invitation: JobInvitation {
+                id: row.id,
+                job_id: row.job_id,
+                job_seeker_id: row.job_seeker_id,
+                invited_by: row.invited_by,
+                company_id: row.company_id,
+                message: row.message,
+                status: row.status,
+                viewed_at: row.viewed_at,
+                responded_at: row.responded_at,
+                expires_at: row.expires_at,
+                application_id: row.application_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            job: row.job_snapshot.0,
+            job_changed: row.job_changed,
+            company_name: row.company_name,
+        }
+    }
+}
+
+/// Limit and offset of an invitation list page
+fn page(query: &InvitationsQuery) -> (i64, i64) {
+    (
+        query.limit.unwrap_or(50).clamp(1, 100),
+        query.offset.unwrap_or(0).max(0),
+    )
+}
+
 // ============================================================================
 // COMPANY ENDPOINTS - SEND INVITATIONS
 // ============================================================================
@@ -53,7 +118,7 @@ pub async fn send_job_invitation(
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
     Json(payload): Json<SendJobInvitationRequest>,
-) -> Result<Json<JobInvitation>, AppError> {
+) -> Result<Json<SendJobInvitationResponse>, AppError> {
     payload.validate()?;
 
     // Verify user is company member
@@ -86,9 +151,13 @@ pub async fn send_job_invitation(
         ));
     }
 
-    // Verify job seeker exists and is a job_seeker type
+    // Verify job seeker exists and is a job_seeker type; accounts being
+    // deleted are hidden like in search
     let job_seeker = sqlx::query!(
-        "SELECT id, user_type::text as user_type FROM users WHERE id = $1",
+        r#"
+        SELECT id, user_type::text as user_type FROM users
+        WHERE id = $1 AND account_status <> 'pending_deletion'
+        "#,
         payload.job_seeker_id
     )
     .fetch_optional(&state.db)
@@ -120,12 +189,16 @@ pub async fn send_job_invitation(
     let expires_in_days = payload.expires_in_days.unwrap_or(30);
     let expires_at = Utc::now() + Duration::days(expires_in_days as i64);
 
+    let mut tx = state.db.begin().await?;
+
     // Create invitation
     let invitation = sqlx::query_as!(
         JobInvitation,
         r#"
-        INSERT INTO job_invitations (job_id, job_seeker_id, invited_by, company_id, message, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO job_invitations (
+            job_id, job_seeker_id, invited_by, company_id, message, expires_at, job_snapshot
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, job_invitation_snapshot($1))
         RETURNING
             id,
             job_id,
@@ -137,6 +210,7 @@ pub async fn send_job_invitation(
             viewed_at,
             responded_at,
             expires_at,
+            application_id,
             created_at,
             updated_at
         "#,
@@ -147,10 +221,92 @@ pub async fn send_job_invitation(
         payload.message,
         expires_at
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    events::emit(
+        &mut tx,
+        AggregateType::Invitation,
+        invitation.id,
+        "sent",
+        Some(auth_user.id),
+        serde_json::json!({
+            "job_id": job_id,
+            "job_seeker_id": payload.job_seeker_id,
+            "message": invitation.message,
+        }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    metering::record(
+        &state.db,
+        company_id,
+        UsageEventType::InvitationSent,
+        1,
+        serde_json::json!({ "job_id": job_id, "invitation_id": invitation.id }),
+    )
+    .await;
+
+    // Inviting a seeker who isn't looking is allowed, but the company is told
+    let availability = sqlx::query_scalar!(
+        r#"
+        SELECT availability as "availability: SeekerAvailability"
+        FROM job_seeker_profiles
+        WHERE user_id = $1
+        "#,
+        payload.job_seeker_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .unwrap_or_default();
+
+    let mut warnings = Vec::new();
+    if availability == SeekerAvailability::NotAvailable {
+        warnings.push(InvitationWarning::SeekerNotAvailable);
+    }
+
+    // Seekers managed offline have no email to invite
+    let recipient = sqlx::query!(
+        r#"
+        SELECT u.email as "email!", u.first_name, j.title, cp.company_name
+        FROM users u
+        JOIN jobs j ON j.id = $2
+        JOIN company_profiles cp ON cp.id = j.company_id
+        WHERE u.id = $1 AND u.email IS NOT NULL
+        "#,
+        payload.job_seeker_id,
+        job_id
+    )
+    .fetch_optional(&state.db)
     .await?;
 
-    Ok(Json(invitation))
+    if let Some(recipient) = recipient {
+        let message = invitation.message.clone();
+        notifications::email(
+            &state,
+            payload.job_seeker_id,
+            NotificationEvent::JobInvitation,
+            move |email| async move {
+                email
+                    .send_job_invitation_email(
+                        &recipient.email,
+                        &recipient.first_name,
+                        &recipient.company_name,
+                        job_id,
+                        &recipient.title,
+                        message.as_deref(),
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(Json(SendJobInvitationResponse {
+        invitation,
+        warnings,
+    }))
 }
 
 /// GET /api/me/jobs/{job_id}/invitations
@@ -160,7 +316,7 @@ pub async fn list_job_invitations(
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
     Query(query): Query<InvitationsQuery>,
-) -> Result<Json<Vec<JobInvitation>>, AppError> {
+) -> Result<Json<JobInvitationsResponse>, AppError> {
     // Verify user is company member
     if auth_user.user_type != "company_member" {
         return Err(AppError::ForbiddenError(
@@ -182,40 +338,94 @@ pub async fn list_job_invitations(
         ));
     }
 
-    let limit = query.limit.unwrap_or(50).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let (limit, offset) = page(&query);
+    let expiry_first = query.sort.unwrap_or_default() == InvitationSort::ExpiresAt;
 
-    let invitations = sqlx::query_as!(
-        JobInvitation,
+    let rows = sqlx::query!(
         r#"
         SELECT
-            id,
-            job_id,
-            job_seeker_id,
-            invited_by,
-            company_id,
-            message,
-            status as "status: InvitationStatus",
-            viewed_at,
-            responded_at,
-            expires_at,
-            created_at,
-            updated_at
-        FROM job_invitations
-        WHERE job_id = $1
-        AND ($2::invitation_status IS NULL OR status = $2)
-        ORDER BY created_at DESC
+            i.id,
+            i.job_id,
+            i.job_seeker_id,
+            i.invited_by,
+            i.company_id,
+            i.message,
+            i.status as "status: InvitationStatus",
+            i.viewed_at,
+            i.responded_at,
+            i.expires_at,
+            i.application_id,
+            i.created_at,
+            i.updated_at,
+            u.first_name || ' ' || u.last_name as "seeker_name!",
+            j.title as job_title
+        FROM job_invitations i
+        JOIN users u ON u.id = i.job_seeker_id
+        JOIN jobs j ON j.id = i.job_id
+        WHERE i.job_id = $1
+        AND ($2::invitation_status IS NULL OR i.status = $2)
+        AND ($5::boolean IS NULL OR (i.application_id IS NOT NULL) = $5)
+        ORDER BY CASE WHEN $6 THEN i.expires_at END ASC, i.created_at DESC
         LIMIT $3 OFFSET $4
         "#,
         job_id,
         query.status as Option<InvitationStatus>,
         limit,
-        offset
+        offset,
+        query.has_application,
+        expiry_first
     )
     .fetch_all(&state.db)
     .await?;
 
-    Ok(Json(invitations))
+    let counts = InvitationStatusCounts::from_rows(
+        sqlx::query!(
+            r#"
+            SELECT status as "status: InvitationStatus", COUNT(*) as "count!"
+            FROM job_invitations
+            WHERE job_id = $1
+            AND ($2::boolean IS NULL OR (application_id IS NOT NULL) = $2)
+            GROUP BY status
+            "#,
+            job_id,
+            query.has_application
+        )
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .map(|row| (row.status, row.count)),
+    );
+
+    let data = rows
+        .into_iter()
+        .map(|row| JobInvitationForCompany {
+            invitation: JobInvitation {
+                id: row.id,
+                job_id: row.job_id,
+                job_seeker_id: row.job_seeker_id,
+                invited_by: row.invited_by,
+                company_id: row.company_id,
+                message: row.message,
+                status: row.status,
+                viewed_at: row.viewed_at,
+                responded_at: row.responded_at,
+                expires_at: row.expires_at,
+                application_id: row.application_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            seeker_name: row.seeker_name,
+            job_title: row.job_title,
+        })
+        .collect();
+
+    Ok(Json(JobInvitationsResponse {
+        data,
+        total: counts.matching(query.status),
+        limit,
+        offset,
+        counts,
+    }))
 }
 
 // ============================================================================
@@ -228,7 +438,7 @@ pub async fn list_my_invitations(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Query(query): Query<InvitationsQuery>,
-) -> Result<Json<Vec<JobInvitationWithDetails>>, AppError> {
+) -> Result<Json<MyInvitationsResponse>, AppError> {
     // Verify user is job seeker
     if auth_user.user_type != "job_seeker" {
         return Err(AppError::ForbiddenError(
@@ -236,10 +446,11 @@ pub async fn list_my_invitations(
         ));
     }
 
-    let limit = query.limit.unwrap_or(50).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let (limit, offset) = page(&query);
+    let expiry_first = query.sort.unwrap_or_default() == InvitationSort::ExpiresAt;
 
-    let invitations = sqlx::query!(
+    let invitations = sqlx::query_as!(
+        InvitationDetailsRow,
         r#"
         SELECT
             i.id,
@@ -252,105 +463,51 @@ pub async fn list_my_invitations(
             i.viewed_at,
             i.responded_at,
             i.expires_at,
+            i.application_id,
             i.created_at,
             i.updated_at,
-            -- Job details (matching PublicJobListing struct)
-            j.title as job_title,
-            j.description as job_description,
-            j.responsibilities as job_responsibilities,
-            j.job_type as "job_type: JobType",
-            j.industry_id as job_industry_id,
-            j.work_area_id as job_work_area_id,
-            j.position_level_id as job_position_level_id,
-            j.work_modality as "work_modality: WorkModality",
-            j.work_schedule as job_work_schedule,
-            j.region_id as job_region_id,
-            j.municipality_id as job_municipality_id,
-            j.is_remote_allowed as job_is_remote_allowed,
-            j.education_level as job_education_level,
-            j.years_experience_min as job_years_experience_min,
-            j.years_experience_max as job_years_experience_max,
-            j.benefits as job_benefits,
-            j.application_deadline as job_application_deadline,
-            j.contact_email as job_contact_email,
-            j.application_url as job_application_url,
-            j.vacancies as job_vacancies,
-            j.is_featured as job_is_featured,
-            j.created_at as job_created_at,
-            -- Company info
-            c.company_name,
-            c.logo_url as company_logo_url
+            i.job_snapshot as "job_snapshot: sqlx::types::Json<InvitationJobSnapshot>",
+            COALESCE(job_invitation_snapshot(i.job_id) <> i.job_snapshot, false) as "job_changed!",
+            c.company_name
         FROM job_invitations i
-        JOIN jobs j ON j.id = i.job_id
         JOIN company_profiles c ON c.id = i.company_id
         WHERE i.job_seeker_id = $1
         AND ($2::invitation_status IS NULL OR i.status = $2)
-        ORDER BY i.created_at DESC
+        ORDER BY CASE WHEN $5 THEN i.expires_at END ASC, i.created_at DESC
         LIMIT $3 OFFSET $4
         "#,
         auth_user.id,
         query.status as Option<InvitationStatus>,
         limit,
-        offset
+        offset,
+        expiry_first
     )
     .fetch_all(&state.db)
     .await?;
 
-    let result: Vec<JobInvitationWithDetails> = invitations
+    let counts = InvitationStatusCounts::from_rows(
+        sqlx::query!(
+            r#"
+            SELECT status as "status: InvitationStatus", COUNT(*) as "count!"
+            FROM job_invitations
+            WHERE job_seeker_id = $1
+            GROUP BY status
+            "#,
+            auth_user.id
+        )
+        .fetch_all(&state.db)
+        .await?
         .into_iter()
-        .map(|row| {
-            let invitation = JobInvitation {
-                id: row.id,
-                job_id: row.job_id,
-                job_seeker_id: row.job_seeker_id,
-                invited_by: row.invited_by,
-                company_id: row.company_id,
-                message: row.message,
-                status: row.status,
-                viewed_at: row.viewed_at,
-                responded_at: row.responded_at,
-                expires_at: row.expires_at,
-                created_at: row.created_at,
-                updated_at: row.updated_at,
-            };
-
-            let job = PublicJobListing {
-                id: row.job_id,
-                company_name: row.company_name.clone(),
-                company_logo_url: row.company_logo_url.clone(),
-                title: row.job_title,
-                description: row.job_description,
-                responsibilities: row.job_responsibilities,
-                job_type: row.job_type,
-                industry_id: row.job_industry_id,
-                work_area_id: row.job_work_area_id,
-                position_level_id: row.job_position_level_id,
-                work_modality: row.work_modality,
-                work_schedule: row.job_work_schedule,
-                region_id: row.job_region_id,
-                municipality_id: row.job_municipality_id,
-                is_remote_allowed: row.job_is_remote_allowed.unwrap_or(false),
-                education_level: row.job_education_level,
-                years_experience_min: row.job_years_experience_min,
-                years_experience_max: row.job_years_experience_max,
-                benefits: row.job_benefits,
-                application_deadline: row.job_application_deadline,
-                contact_email: row.job_contact_email,
-                application_url: row.job_application_url,
-                vacancies: row.job_vacancies,
-                is_featured: row.job_is_featured.unwrap_or(false),
-                created_at: row.job_created_at,
-            };
-
-            JobInvitationWithDetails {
-                invitation,
-                job,
-                company_name: row.company_name,
-            }
-        })
-        .collect();
+        .map(|row| (row.status, row.count)),
+    );
 
-    Ok(Json(result))
+    Ok(Json(MyInvitationsResponse {
+        data: invitations.into_iter().map(Into::into).collect(),
+        total: counts.matching(query.status),
+        limit,
+        offset,
+        counts,
+    }))
 }
 
 /// GET /api/me/invitations/{id}
@@ -367,7 +524,8 @@ pub async fn get_invitation(
         ));
     }
 
-    let row = sqlx::query!(
+    let mut details: JobInvitationWithDetails = sqlx::query_as!(
+        InvitationDetailsRow,
         r#"
         SELECT
             i.id,
@@ -380,36 +538,13 @@ pub async fn get_invitation(
             i.viewed_at,
             i.responded_at,
             i.expires_at,
+            i.application_id,
             i.created_at,
             i.updated_at,
-            -- Job details (matching PublicJobListing struct)
-            j.title as job_title,
-            j.description as job_description,
-            j.responsibilities as job_responsibilities,
-            j.job_type as "job_type: JobType",
-            j.industry_id as job_industry_id,
-            j.work_area_id as job_work_area_id,
-            j.position_level_id as job_position_level_id,
-            j.work_modality as "work_modality: WorkModality",
-            j.work_schedule as job_work_schedule,
-            j.region_id as job_region_id,
-            j.municipality_id as job_municipality_id,
-            j.is_remote_allowed as job_is_remote_allowed,
-            j.education_level as job_education_level,
-            j.years_experience_min as job_years_experience_min,
-            j.years_experience_max as job_years_experience_max,
-            j.benefits as job_benefits,
-            j.application_deadline as job_application_deadline,
-            j.contact_email as job_contact_email,
-            j.application_url as job_application_url,
-            j.vacancies as job_vacancies,
-            j.is_featured as job_is_featured,
-            j.created_at as job_created_at,
-            -- Company info
-            c.company_name,
-            c.logo_url as company_logo_url
+            i.job_snapshot as "job_snapshot: sqlx::types::Json<InvitationJobSnapshot>",
+            COALESCE(job_invitation_snapshot(i.job_id) <> i.job_snapshot, false) as "job_changed!",
+            c.company_name
         FROM job_invitations i
-        JOIN jobs j ON j.id = i.job_id
         JOIN company_profiles c ON c.id = i.company_id
         WHERE i.id = $1 AND i.job_seeker_id = $2
         "#,
@@ -418,80 +553,35 @@ pub async fn get_invitation(
     )
     .fetch_optional(&state.db)
     .await?
-    .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?
+    .into();
 
     // Mark as viewed if pending
-    if row.status == InvitationStatus::Pending {
+    if details.invitation.status == InvitationStatus::Pending {
         sqlx::query!(
             "UPDATE job_invitations SET status = 'viewed', viewed_at = NOW() WHERE id = $1",
             invitation_id
         )
         .execute(&state.db)
         .await?;
-    }
-
-    let invitation = JobInvitation {
-        id: row.id,
-        job_id: row.job_id,
-        job_seeker_id: row.job_seeker_id,
-        invited_by: row.invited_by,
-        company_id: row.company_id,
-        message: row.message,
-        status: if row.status == InvitationStatus::Pending {
-            InvitationStatus::Viewed
-        } else {
-            row.status
-        },
-        viewed_at: row.viewed_at.or(Some(Utc::now())),
-        responded_at: row.responded_at,
-        expires_at: row.expires_at,
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-    };
 
-    let job = PublicJobListing {
-        id: row.job_id,
-        company_name: row.company_name.clone(),
-        company_logo_url: row.company_logo_url.clone(),
-        title: row.job_title,
-        description: row.job_description,
-        responsibilities: row.job_responsibilities,
-        job_type: row.job_type,
-        industry_id: row.job_industry_id,
-        work_area_id: row.job_work_area_id,
-        position_level_id: row.job_position_level_id,
-        work_modality: row.work_modality,
-        work_schedule: row.job_work_schedule,
-        region_id: row.job_region_id,
-        municipality_id: row.job_municipality_id,
-        is_remote_allowed: row.job_is_remote_allowed.unwrap_or(false),
-        education_level: row.job_education_level,
-        years_experience_min: row.job_years_experience_min,
-        years_experience_max: row.job_years_experience_max,
-        benefits: row.job_benefits,
-        application_deadline: row.job_application_deadline,
-        contact_email: row.job_contact_email,
-        application_url: row.job_application_url,
-        vacancies: row.job_vacancies,
-        is_featured: row.job_is_featured.unwrap_or(false),
-        created_at: row.job_created_at,
-    };
+        details.invitation.status = InvitationStatus::Viewed;
+        details.invitation.viewed_at = details.invitation.viewed_at.or(Some(Utc::now()));
+    }
 
-    Ok(Json(JobInvitationWithDetails {
-        invitation,
-        job,
-        company_name: row.company_name,
-    }))
+    Ok(Json(details))
 }
 
 /// POST /api/me/invitations/{id}/respond
-/// Respond to an invitation (accept/decline)
+/// Respond to an invitation (accept/decline). Accepting with
+/// `apply_immediately` also applies to the job; if the application is
+/// blocked, the invitation is still accepted and the reason returned.
 pub async fn respond_to_invitation(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(invitation_id): Path<Uuid>,
     Json(payload): Json<RespondToInvitationRequest>,
-) -> Result<Json<JobInvitation>, AppError> {
+) -> Result<Json<RespondToInvitationResponse>, AppError> {
     // Verify user is job seeker
     if auth_user.user_type != "job_seeker" {
         return Err(AppError::ForbiddenError(
@@ -499,16 +589,20 @@ pub async fn respond_to_invitation(
         ));
     }
 
+    payload.validate()?;
+
     // Get invitation
     let existing = sqlx::query!(
         r#"
         SELECT
-            id,
-            job_id,
-            status as "status: InvitationStatus",
-            expires_at
-        FROM job_invitations
-        WHERE id = $1 AND job_seeker_id = $2
+            i.id,
+            i.job_id,
+            i.status as "status: InvitationStatus",
+            i.expires_at,
+            j.status as "job_status?: JobStatus"
+        FROM job_invitations i
+        LEFT JOIN jobs j ON j.id = i.job_id
+        WHERE i.id = $1 AND i.job_seeker_id = $2
         "#,
         invitation_id,
         auth_user.id
@@ -517,14 +611,26 @@ pub async fn respond_to_invitation(
     .await?
     .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
 
-    // Check if already responded
-    if existing.status == InvitationStatus::Applied || existing.status == InvitationStatus::Declined
-    {
+    // Check if already responded. An accepted invitation can still be used
+    // to apply.
+    let applying = payload.accept && payload.apply_immediately;
+    let responded = match existing.status {
+        InvitationStatus::Applied | InvitationStatus::Declined => true,
+        InvitationStatus::Accepted => !applying,
+        _ => false,
+    };
+    if responded {
         return Err(AppError::ValidationError(
             "Already responded to this invitation".to_string(),
         ));
     }
 
+    if existing.status == InvitationStatus::JobNoLongerAvailable {
+        return Err(AppError::ValidationError(
+            "The job for this invitation is no longer available".to_string(),
+        ));
+    }
+
     // Check if expired
     if existing.expires_at < Utc::now() {
         return Err(AppError::ValidationError(
@@ -532,42 +638,59 @@ pub async fn respond_to_invitation(
         ));
     }
 
-    let new_status = if payload.accept {
-        InvitationStatus::Applied
-    } else {
-        InvitationStatus::Declined
-    };
+    let mut tx = state.db.begin().await?;
+    let mut application = None;
+    let mut application_error = None;
 
-    // If accepting, create application
-    if payload.accept {
-        // Check if already applied
-        let already_applied = sqlx::query_scalar!(
-            "SELECT id FROM job_applications WHERE job_id = $1 AND applicant_id = $2",
-            existing.job_id,
-            auth_user.id
-        )
-        .fetch_optional(&state.db)
-        .await?;
+    let new_status = if payload.accept {
+        // Closed, paused or deleted jobs can still be declined, not accepted
+        let job_id = match (existing.job_id, existing.job_status) {
+            (Some(job_id), Some(JobStatus::Active)) => job_id,
+            _ => {
+                return Err(AppError::ValidationError(
+                    "This job is no longer accepting applications".to_string(),
+                ))
+            }
+        };
+
+        if applying {
+            let mut cover_letter = payload.cover_letter.clone();
+            if cover_letter.is_none() {
+                cover_letter = fetch_draft(&state.db, auth_user.id, job_id)
+                    .await?
+                    .and_then(|draft| draft.cover_letter);
+            }
+            let new_application = NewApplication {
+                job_id,
+                applicant_id: auth_user.id,
+                cover_letter,
+                resume_url: None,
+                source: ApplicationSource::Invitation,
+            };
 
-        if already_applied.is_some() {
-            return Err(AppError::ValidationError(
-                "Already applied to this job".to_string(),
-            ));
+            // A blocked application leaves the acceptance in place
+            let mut savepoint = (&mut tx).begin().await?;
+            match applications::submit(&mut savepoint, &new_application).await {
+                Ok(created) => {
+                    savepoint.commit().await?;
+                    application = Some(created);
+                }
+                Err(AppError::ValidationError(reason)) => {
+                    savepoint.rollback().await?;
+                    application_error = Some(reason);
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        // Create application
-        sqlx::query!(
-            r#"
-            INSERT INTO job_applications (job_id, applicant_id, cover_letter, status)
-            VALUES ($1, $2, $3, 'submitted')
-            "#,
-            existing.job_id,
-            auth_user.id,
-            payload.cover_letter
-        )
-        .execute(&state.db)
-        .await?;
-    }
+        if application.is_some() {
+            InvitationStatus::Applied
+        } else {
+            InvitationStatus::Accepted
+        }
+    } else {
+        InvitationStatus::Declined
+    };
 
     // Update invitation
     let invitation = sqlx::query_as!(
@@ -576,9 +699,10 @@ pub async fn respond_to_invitation(
         UPDATE job_invitations
         SET
             status = $1,
-            responded_at = NOW(),
+            application_id = $2,
+            responded_at = COALESCE(responded_at, NOW()),
             updated_at = NOW()
-        WHERE id = $2
+        WHERE id = $3
         RETURNING
             id,
             job_id,
@@ -590,14 +714,38 @@ pub async fn respond_to_invitation(
             viewed_at,
             responded_at,
             expires_at,
+            application_id,
             created_at,
             updated_at
         "#,
         new_status as InvitationStatus,
+        application.as_ref().map(|a| a.id),
         invitation_id
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    events::emit(
+        &mut tx,
+        AggregateType::Invitation,
+        invitation.id,
+        "responded",
+        Some(auth_user.id),
+        serde_json::json!({
+            "status": invitation.status,
+            "application_id": invitation.application_id,
+        }),
+    )
     .await?;
 
-    Ok(Json(invitation))
+    tx.commit().await?;
+
+    if let Some(application) = application {
+        applications::run_automation(&state, application).await?;
+    }
+
+    Ok(Json(RespondToInvitationResponse {
+        invitation,
+        application_error,
+    }))
 }
@@ -1,20 +1,37 @@
 use axum::{
-    extract::{Path, State},
+    extract::State,
     Extension, Json,
 };
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgConnection;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
     middleware::AuthUser,
     models::{
+        admin::{AggregateType, ScreeningFinding},
+        applicant::{AutomationRuleRequest, JobAutomationRule},
         application::*,
-        company::{MemberRole, OrganizationStatus},
+        company::{OrganizationStatus, UsageEventType},
         job::*,
+        permissions::Capability,
         profile::JobSeekerProfile,
     },
+    services::{
+        admin_events, authz, automation, availability, completeness,
+        content_screening::{self, JobContent},
+        events, job_deadlines,
+        job_duplicates::{self, JobFingerprint},
+        job_reposts,
+        matching::{self, JobRequirements},
+        metering, settings, stale_jobs,
+    },
+    utils::normalize::Normalize,
     AppState,
 };
 
@@ -22,33 +39,6 @@ use crate::{
 // HELPER FUNCTIONS
 // ============================================================================
 
-/// Get the user's company membership (company_id and role)
-async fn get_user_company_membership(
-    db: &sqlx::PgPool,
-    user_id: Uuid,
-) -> Result<(Uuid, MemberRole)> {
-    let member = sqlx::query!(
-        r#"
-        SELECT company_id, role as "role: crate::models::company::MemberRole"
-        FROM company_members
-        WHERE user_id = $1 AND is_active = true
-        "#,
-        user_id,
-    )
-    .fetch_optional(db)
-    .await?
-    .ok_or_else(|| {
-        AppError::ForbiddenError("User is not a member of any company".to_string())
-    })?;
-
-    Ok((member.company_id, member.role))
-}
-
-/// Check if user is owner or admin
-fn is_owner_or_admin(role: MemberRole) -> bool {
-    matches!(role, MemberRole::Owner | MemberRole::Admin)
-}
-
 /// Check if company is active
 async fn check_company_active(db: &sqlx::PgPool, company_id: Uuid) -> Result<()> {
     let company = sqlx::query!(
@@ -82,38 +72,45 @@ pub async fn create_job(
     Extension(auth_user): Extension<AuthUser>,
     Json(payload): Json<CreateJobRequest>,
 ) -> Result<Json<Job>> {
-    // Only company members can create jobs
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can create jobs".to_string(),
-        ));
-    }
+    let company_id = authz::require_company(&state.db, &auth_user, Capability::PostJobs).await?;
 
     payload.validate()?;
 
-    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
-
-    // Only owner/admin can create jobs
-    if !is_owner_or_admin(role) {
-        return Err(AppError::ForbiddenError(
-            "Only owners and admins can create jobs".to_string(),
-        ));
-    }
-
     // Only active companies can post jobs
     check_company_active(&state.db, company_id).await?;
 
     // Begin transaction
     let mut tx = state.db.begin().await?;
 
+    if let Some(predecessor_id) = payload.reposted_from_job_id {
+        job_reposts::validate_predecessor(&mut tx, company_id, predecessor_id).await?;
+    }
+
+    // Catch accidental double submissions before they split the applicant pool
+    let duplicates = job_duplicates::find_duplicates(
+        &mut tx,
+        &JobFingerprint {
+            company_id,
+            job_id: None,
+            title: &payload.title,
+            description: &payload.description,
+            region_id: payload.region_id,
+            job_type: payload.job_type,
+        },
+    )
+    .await?;
+    if !duplicates.is_empty() && !payload.force.unwrap_or(false) {
+        return Err(job_duplicates::duplicate_conflict(&duplicates));
+    }
+
     // Insert job
-    let job = sqlx::query_as!(
+    let mut job = sqlx::query_as!(
         Job,
         r#"
         INSERT INTO jobs (
             company_id, posted_by, title, description, responsibilities,
             job_type, industry_id, work_area_id, position_level_id,
-            work_modality, work_schedule,
+            work_modality, schedule_details, shift_type, weekly_hours,
             region_id, municipality_id, is_remote_allowed,
             education_level, years_experience_min, years_experience_max,
             age_min, age_max,
@@ -121,16 +118,22 @@ pub async fn create_job(
             salary_max,
             salary_currency, salary_period, benefits,
             application_deadline, contact_email, application_url, vacancies,
-            status
+            publish_at, status, reposted_from_job_id
         ) VALUES (
             $1, $2, $3, $4, $5,
-            $6, $7, $8, $9,
-            $10, $11,
-            $12, $13, $14,
-            $15, $16, $17, $18, $19,
-            $20, $21, $22, $23, $24,
-            $25, $26, $27, $28,
-            'draft'
+            $6,
+            -- Jobs default to the company's primary industry
+            COALESCE(
+                $7,
+                (SELECT industry_id FROM company_industries WHERE company_id = $1 AND is_primary)
+            ),
+            $8, $9,
+            $10, $11, $12, $13,
+            $14, $15, $16,
+            $17, $18, $19, $20, $21,
+            $22, $23, $24, $25, $26,
+            $27, $28, $29, $30,
+            $31, 'draft', $32
         )
         RETURNING
             id, company_id, posted_by,
@@ -138,7 +141,9 @@ pub async fn create_job(
             job_type as "job_type: JobType",
             industry_id, work_area_id, position_level_id,
             work_modality as "work_modality: WorkModality",
-            work_schedule,
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
             region_id, municipality_id, is_remote_allowed,
             education_level, years_experience_min, years_experience_max,
             age_min, age_max,
@@ -150,6 +155,7 @@ pub async fn create_job(
             status as "status: JobStatus",
             approved_at, approved_by, rejection_reason,
             completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
             created_at, updated_at
         "#,
         company_id,
@@ -162,7 +168,9 @@ pub async fn create_job(
         payload.work_area_id,
         payload.position_level_id,
         payload.work_modality as WorkModality,
-        payload.work_schedule,
+        payload.schedule_details,
+        payload.shift_type as Option<ShiftType>,
+        payload.weekly_hours,
         payload.region_id,
         payload.municipality_id,
         payload.is_remote_allowed.unwrap_or(false),
@@ -180,10 +188,16 @@ pub async fn create_job(
         payload.contact_email,
         payload.application_url,
         payload.vacancies,
+        payload.publish_at,
+        payload.reposted_from_job_id,
     )
     .fetch_one(&mut *tx)
     .await?;
 
+    if !duplicates.is_empty() {
+        job_duplicates::record_override(&mut tx, job.id, &duplicates, auth_user.id).await?;
+    }
+
     // Insert required skills
     if let Some(required_skills) = payload.required_skills {
         for skill in required_skills {
@@ -250,6 +264,20 @@ pub async fn create_job(
         }
     }
 
+    if let Some(scored) = completeness::recalculate(&mut tx, &[job.id]).await?.first() {
+        job.completeness_percentage = scored.score;
+    }
+
+    events::emit(
+        &mut tx,
+        AggregateType::Job,
+        job.id,
+        "created",
+        Some(auth_user.id),
+        json!({ "company_id": company_id, "title": job.title }),
+    )
+    .await?;
+
     tx.commit().await?;
 
     Ok(Json(job))
@@ -261,14 +289,8 @@ pub async fn list_company_jobs(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
 ) -> Result<Json<Vec<Job>>> {
-    // Only company members can access this
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
 
     let jobs = sqlx::query_as!(
         Job,
@@ -279,7 +301,9 @@ pub async fn list_company_jobs(
             job_type as "job_type: JobType",
             industry_id, work_area_id, position_level_id,
             work_modality as "work_modality: WorkModality",
-            work_schedule,
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
             region_id, municipality_id, is_remote_allowed,
             education_level, years_experience_min, years_experience_max,
             age_min, age_max,
@@ -291,6 +315,7 @@ pub async fn list_company_jobs(
             status as "status: JobStatus",
             approved_at, approved_by, rejection_reason,
             completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
             created_at, updated_at
         FROM jobs
         WHERE company_id = $1
@@ -311,14 +336,8 @@ pub async fn get_job_with_applications(
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
 ) -> Result<Json<FullJobResponse>> {
-    // Only company members can access this
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
 
     // Get job and verify it belongs to company
     let job = sqlx::query_as!(
@@ -330,7 +349,9 @@ pub async fn get_job_with_applications(
             job_type as "job_type: JobType",
             industry_id, work_area_id, position_level_id,
             work_modality as "work_modality: WorkModality",
-            work_schedule,
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
             region_id, municipality_id, is_remote_allowed,
             education_level, years_experience_min, years_experience_max,
             age_min, age_max,
@@ -342,6 +363,7 @@ pub async fn get_job_with_applications(
             status as "status: JobStatus",
             approved_at, approved_by, rejection_reason,
             completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
             created_at, updated_at
         FROM jobs
         WHERE id = $1 AND company_id = $2
@@ -353,6 +375,8 @@ pub async fn get_job_with_applications(
     .await?
     .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
 
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
     // Get required skills
     let required_skills = sqlx::query_as!(
         JobRequiredSkill,
@@ -414,21 +438,145 @@ pub async fn get_job_with_applications(
     }))
 }
 
+/// GET /api/me/jobs/{id}/completeness
+/// Completeness score of a job with a checklist of what it is missing
+pub async fn get_job_completeness(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobCompleteness>> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
+
+    let job_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1 AND company_id = $2)
+        "#,
+        job_id,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !job_exists.unwrap_or(false) {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    // Scored fresh; the stored percentage is corrected on the next write or
+    // by the nightly recalculation
+    let mut conn = state.db.acquire().await?;
+    let completeness = completeness::score(&mut conn, &[job_id])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    Ok(Json(completeness))
+}
+
 /// PUT /api/me/jobs/{id}
-/// Update job posting (owner/admin only)
-/// TODO: Fix SQLx type inference issues with complex COALESCE query
+/// Update job posting (owner/admin only). Omitted fields and lists are left
+/// as they are; an empty list clears it. When what the job requires of
+/// seekers changes, its match scores are flagged stale and queued for the
+/// background worker.
 pub async fn update_job(
-    _state: State<AppState>,
-    _auth_user: Extension<AuthUser>,
-    _job_id: Path<Uuid>,
-    _payload: Json<UpdateJobRequest>,
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<UpdateJobRequest>,
 ) -> Result<Json<Job>> {
-    return Err(AppError::InternalError(
-        "Update job endpoint temporarily disabled due to SQLx compilation issues".to_string()
-    ));
+    let company_id = authz::require_company(&state.db, &auth_user, Capability::PostJobs).await?;
 
-    /* TODO: Fix the complex COALESCE query below
-    let job = sqlx::query_as!(
+    payload.validate()?;
+
+    let current = load_edited_job(&state.db, job_id, company_id).await?;
+    validate_merged_job(&current, &payload)?;
+
+    // With review_edited_jobs on, any change to an active job sends it back to
+    // the moderation queue
+    let review_edits = current.status == JobStatus::Active
+        && settings::get_bool(
+            &state.db,
+            &mut state.redis.clone(),
+            content_screening::REVIEW_EDITED_JOBS_SETTING,
+        )
+        .await?;
+
+    // Content waiting for moderation is screened again whenever it changes,
+    // as is that of an active job going back to review. Drafts are screened
+    // when submitted.
+    let content = JobContent {
+        title: payload.title.as_deref().unwrap_or(&current.title),
+        description: payload
+            .description
+            .as_deref()
+            .unwrap_or(&current.description),
+        responsibilities: payload
+            .responsibilities
+            .as_deref()
+            .or(current.responsibilities.as_deref()),
+        benefits: payload.benefits.as_deref().or(current.benefits.as_deref()),
+        age_min: payload.age_min.or(current.age_min),
+        age_max: payload.age_max.or(current.age_max),
+    };
+    let approved_content = JobContent {
+        title: &current.title,
+        description: &current.description,
+        responsibilities: current.responsibilities.as_deref(),
+        benefits: current.benefits.as_deref(),
+        age_min: current.age_min,
+        age_max: current.age_max,
+    };
+    let screen = content != approved_content
+        && (current.status == JobStatus::PendingApproval || review_edits);
+    let findings = if screen {
+        Some(screen_submission(&state, &content).await?)
+    } else {
+        None
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    // Locks the job; an edit that landed since it was read above would make
+    // the screening and checks stale
+    sqlx::query!("SELECT id FROM jobs WHERE id = $1 FOR UPDATE", job_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if load_edited_job(&mut *tx, job_id, company_id).await? != current {
+        return Err(AppError::ConflictError(
+            "The job was changed by another edit; reload it and try again".to_string(),
+        ));
+    }
+
+    // A changed title, description, region or type is checked for duplicates
+    // as on creation, whatever the job's status
+    let fingerprint = JobFingerprint {
+        company_id,
+        job_id: Some(job_id),
+        title: content.title,
+        description: content.description,
+        region_id: payload.region_id.or(current.region_id),
+        job_type: payload.job_type.unwrap_or(current.job_type),
+    };
+    let fingerprint_changed = fingerprint.title != current.title
+        || fingerprint.description != current.description
+        || fingerprint.region_id != current.region_id
+        || fingerprint.job_type != current.job_type;
+    let mut duplicates = Vec::new();
+    if fingerprint_changed {
+        duplicates = job_duplicates::find_duplicates(&mut tx, &fingerprint).await?;
+        if !duplicates.is_empty() && !payload.force.unwrap_or(false) {
+            return Err(job_duplicates::duplicate_conflict(&duplicates));
+        }
+    }
+
+    let requirements_before = JobRequirements::load(&mut tx, job_id).await?;
+    let snapshot_before = if review_edits {
+        Some(job_snapshot(&mut tx, job_id).await?)
+    } else {
+        None
+    };
+
+    let mut job = sqlx::query_as!(
         Job,
         r#"
         UPDATE jobs
@@ -441,32 +589,37 @@ pub async fn update_job(
             work_area_id = COALESCE($6, work_area_id),
             position_level_id = COALESCE($7, position_level_id),
             work_modality = COALESCE($8, work_modality),
-            work_schedule = COALESCE($9, work_schedule),
-            region_id = COALESCE($10, region_id),
-            municipality_id = COALESCE($11, municipality_id),
-            is_remote_allowed = COALESCE($12, is_remote_allowed),
-            education_level = COALESCE($13, education_level),
-            years_experience_min = COALESCE($14, years_experience_min),
-            years_experience_max = COALESCE($15, years_experience_max),
-            age_min = COALESCE($16, age_min),
-            age_max = COALESCE($17, age_max),
-            salary_min = COALESCE($18, salary_min),
-            salary_max = COALESCE($19, salary_max),
-            salary_currency = COALESCE($20, salary_currency),
-            salary_period = COALESCE($21, salary_period),
-            benefits = COALESCE($22, benefits),
-            application_deadline = COALESCE($23, application_deadline),
-            contact_email = COALESCE($24, contact_email),
-            application_url = COALESCE($25, application_url),
-            vacancies = COALESCE($26, vacancies)
-        WHERE id = $27 AND company_id = $28
+            schedule_details = COALESCE($9, schedule_details),
+            shift_type = COALESCE($10, shift_type),
+            weekly_hours = COALESCE($11, weekly_hours),
+            region_id = COALESCE($12, region_id),
+            municipality_id = COALESCE($13, municipality_id),
+            is_remote_allowed = COALESCE($14, is_remote_allowed),
+            education_level = COALESCE($15, education_level),
+            years_experience_min = COALESCE($16, years_experience_min),
+            years_experience_max = COALESCE($17, years_experience_max),
+            age_min = COALESCE($18, age_min),
+            age_max = COALESCE($19, age_max),
+            salary_min = COALESCE($20, salary_min),
+            salary_max = COALESCE($21, salary_max),
+            salary_currency = COALESCE($22, salary_currency),
+            salary_period = COALESCE($23, salary_period),
+            benefits = COALESCE($24, benefits),
+            application_deadline = COALESCE($25, application_deadline),
+            contact_email = COALESCE($26, contact_email),
+            application_url = COALESCE($27, application_url),
+            vacancies = COALESCE($28, vacancies),
+            publish_at = COALESCE($29, publish_at)
+        WHERE id = $30 AND company_id = $31
         RETURNING
             id, company_id, posted_by,
             title, description, responsibilities,
-            job_type as "job_type!: JobType",
+            job_type as "job_type: JobType",
             industry_id, work_area_id, position_level_id,
-            work_modality as "work_modality!: WorkModality",
-            work_schedule,
+            work_modality as "work_modality: WorkModality",
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
             region_id, municipality_id, is_remote_allowed,
             education_level, years_experience_min, years_experience_max,
             age_min, age_max,
@@ -475,20 +628,23 @@ pub async fn update_job(
             salary_currency, salary_period, benefits,
             application_deadline, contact_email, application_url,
             vacancies, applications_count,
-            status as "status!: JobStatus",
+            status as "status: JobStatus",
             approved_at, approved_by, rejection_reason,
             completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
             created_at, updated_at
         "#,
         payload.title,
         payload.description,
         payload.responsibilities,
-        payload.job_type.map(|t| t as JobType),
+        payload.job_type as Option<JobType>,
         payload.industry_id,
         payload.work_area_id,
         payload.position_level_id,
-        payload.work_modality.map(|m| m as WorkModality),
-        payload.work_schedule,
+        payload.work_modality as Option<WorkModality>,
+        payload.schedule_details,
+        payload.shift_type as Option<ShiftType>,
+        payload.weekly_hours,
         payload.region_id,
         payload.municipality_id,
         payload.is_remote_allowed,
@@ -500,40 +656,56 @@ pub async fn update_job(
         payload.salary_min,
         payload.salary_max,
         payload.salary_currency,
-        payload.salary_period.map(|p| format!("{:?}", p).to_lowercase()),
+        payload
+            .salary_period
+            .map(|p| format!("{:?}", p).to_lowercase()),
         payload.benefits,
         payload.application_deadline,
         payload.contact_email,
         payload.application_url,
         payload.vacancies,
+        payload.publish_at,
         job_id,
         company_id,
     )
-    .fetch_optional(&mut *tx)
-    .await?
-    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+    .fetch_one(&mut *tx)
+    .await?;
 
-    // Update junction tables if provided
+    // Update junction tables if provided. Required skills and languages are
+    // synced row by row, since any write to them marks the job's scores stale.
     if let Some(required_skills) = payload.required_skills {
-        // Delete existing
-        sqlx::query!("DELETE FROM job_required_skills WHERE job_id = $1", job_id)
-            .execute(&mut *tx)
-            .await?;
+        let (skill_ids, proficiencies): (Vec<Uuid>, Vec<i32>) = required_skills
+            .iter()
+            .map(|s| (s.skill_id, s.minimum_proficiency))
+            .unzip();
 
-        // Insert new
-        for skill in required_skills {
-            sqlx::query!(
-                r#"
-                INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency)
-                VALUES ($1, $2, $3)
-                "#,
-                job_id,
-                skill.skill_id,
-                skill.minimum_proficiency,
+        sqlx::query!(
+            r#"
+            DELETE FROM job_required_skills
+            WHERE job_id = $1
+            AND (skill_id, minimum_proficiency) NOT IN (
+                SELECT * FROM UNNEST($2::uuid[], $3::int[])
             )
-            .execute(&mut *tx)
-            .await?;
-        }
+            "#,
+            job_id,
+            &skill_ids,
+            &proficiencies,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency)
+            SELECT $1, * FROM UNNEST($2::uuid[], $3::int[])
+            ON CONFLICT (job_id, skill_id) DO NOTHING
+            "#,
+            job_id,
+            &skill_ids,
+            &proficiencies,
+        )
+        .execute(&mut *tx)
+        .await?;
     }
 
     if let Some(preferred_skills) = payload.preferred_skills {
@@ -556,23 +728,38 @@ pub async fn update_job(
     }
 
     if let Some(required_languages) = payload.required_languages {
-        sqlx::query!("DELETE FROM job_required_languages WHERE job_id = $1", job_id)
-            .execute(&mut *tx)
-            .await?;
+        let (language_ids, proficiencies): (Vec<Uuid>, Vec<i32>) = required_languages
+            .iter()
+            .map(|l| (l.language_id, l.minimum_proficiency))
+            .unzip();
 
-        for language in required_languages {
-            sqlx::query!(
-                r#"
-                INSERT INTO job_required_languages (job_id, language_id, minimum_proficiency)
-                VALUES ($1, $2, $3)
-                "#,
-                job_id,
-                language.language_id,
-                language.minimum_proficiency,
+        sqlx::query!(
+            r#"
+            DELETE FROM job_required_languages
+            WHERE job_id = $1
+            AND (language_id, minimum_proficiency) NOT IN (
+                SELECT * FROM UNNEST($2::uuid[], $3::int[])
             )
-            .execute(&mut *tx)
-            .await?;
-        }
+            "#,
+            job_id,
+            &language_ids,
+            &proficiencies,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO job_required_languages (job_id, language_id, minimum_proficiency)
+            SELECT $1, * FROM UNNEST($2::uuid[], $3::int[])
+            ON CONFLICT (job_id, language_id) DO NOTHING
+            "#,
+            job_id,
+            &language_ids,
+            &proficiencies,
+        )
+        .execute(&mut *tx)
+        .await?;
     }
 
     if let Some(accommodations) = payload.disability_accommodations {
@@ -597,10 +784,47 @@ pub async fn update_job(
         }
     }
 
+    let resubmitted = match snapshot_before {
+        Some(before) => job_snapshot(&mut tx, job_id).await? != before,
+        None => false,
+    };
+
+    // Typos in the title or a new benefits text leave the scores as they are
+    matching::queue_if_requirements_changed(&mut tx, job_id, requirements_before, auth_user.id)
+        .await?;
+
+    if let Some(scored) = completeness::recalculate(&mut tx, &[job_id]).await?.first() {
+        job.completeness_percentage = scored.score;
+    }
+
+    if !duplicates.is_empty() {
+        job_duplicates::record_override(&mut tx, job_id, &duplicates, auth_user.id).await?;
+    }
+
+    if let Some(findings) = findings {
+        store_findings(&mut tx, job_id, &findings).await?;
+    }
+    if resubmitted {
+        sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = 'pending_approval', submitted_at = NOW()
+            WHERE id = $1
+            "#,
+            job_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        job.status = JobStatus::PendingApproval;
+    }
+
     tx.commit().await?;
 
+    if resubmitted {
+        admin_events::publish(&state).await;
+    }
+
     Ok(Json(job))
-    */
 }
 
 /// DELETE /api/me/jobs/{id}
@@ -610,21 +834,7 @@ pub async fn delete_job(
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>> {
-    // Only company members can delete jobs
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can delete jobs".to_string(),
-        ));
-    }
-
-    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
-
-    // Only owner/admin can delete jobs
-    if !is_owner_or_admin(role) {
-        return Err(AppError::ForbiddenError(
-            "Only owners and admins can delete jobs".to_string(),
-        ));
-    }
+    let company_id = authz::require_company(&state.db, &auth_user, Capability::PostJobs).await?;
 
     // Check if job has applications
     let applications_count: i64 = sqlx::query_scalar!(
@@ -645,6 +855,21 @@ pub async fn delete_job(
         ));
     }
 
+    let mut tx = state.db.begin().await?;
+
+    // Open invitations stay readable, keeping their snapshot of the job
+    sqlx::query!(
+        r#"
+        UPDATE job_invitations
+        SET status = 'job_no_longer_available', updated_at = NOW()
+        WHERE job_id = $1 AND company_id = $2 AND status IN ('pending', 'viewed', 'accepted')
+        "#,
+        job_id,
+        company_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
     // Delete job (CASCADE will handle junction tables)
     let result = sqlx::query!(
         r#"
@@ -654,91 +879,432 @@ pub async fn delete_job(
         job_id,
         company_id,
     )
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
     if result.rows_affected() == 0 {
         return Err(AppError::NotFound("Job not found".to_string()));
     }
 
+    tx.commit().await?;
+
+    admin_events::publish(&state).await;
+
     Ok(Json(serde_json::json!({
         "message": "Job deleted successfully"
     })))
 }
 
-/// PATCH /api/me/jobs/{id}/status
-/// Change job status (owner/admin only)
-pub async fn update_job_status(
-    State(state): State<AppState>,
-    Extension(auth_user): Extension<AuthUser>,
-    Path(job_id): Path<Uuid>,
-    Json(payload): Json<UpdateJobStatusRequest>,
-) -> Result<Json<Job>> {
-    // Only company members can update job status
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can update job status".to_string(),
-        ));
-    }
-
-    payload.validate()?;
-
-    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
-
-    // Only owner/admin can update job status
-    if !is_owner_or_admin(role) {
-        return Err(AppError::ForbiddenError(
-            "Only owners and admins can update job status".to_string(),
-        ));
-    }
-
-    // Validate rejection_reason if status is rejected
-    if payload.status == JobStatus::Rejected && payload.rejection_reason.is_none() {
-        return Err(AppError::ValidationError(
-            "Rejection reason is required when rejecting a job".to_string(),
-        ));
-    }
+/// The fields of a job an edit is screened and validated against
+#[derive(PartialEq)]
+struct EditedJob {
+    status: JobStatus,
+    title: String,
+    description: String,
+    responsibilities: Option<String>,
+    benefits: Option<String>,
+    region_id: Option<Uuid>,
+    job_type: JobType,
+    age_min: Option<i32>,
+    age_max: Option<i32>,
+    years_experience_min: Option<i32>,
+    years_experience_max: Option<i32>,
+    salary_min: Option<Decimal>,
+    salary_max: Option<Decimal>,
+    application_deadline: NaiveDate,
+    published_at: Option<DateTime<Utc>>,
+}
 
-    let job = sqlx::query_as!(
-        Job,
+async fn load_edited_job(
+    db: impl sqlx::PgExecutor<'_>,
+    job_id: Uuid,
+    company_id: Uuid,
+) -> Result<EditedJob> {
+    sqlx::query_as!(
+        EditedJob,
         r#"
-        UPDATE jobs
-        SET
-            status = $1,
-            rejection_reason = $2
-        WHERE id = $3 AND company_id = $4
-        RETURNING
-            id, company_id, posted_by,
-            title, description, responsibilities,
-            job_type as "job_type: JobType",
-            industry_id, work_area_id, position_level_id,
-            work_modality as "work_modality: WorkModality",
-            work_schedule,
-            region_id, municipality_id, is_remote_allowed,
-            education_level, years_experience_min, years_experience_max,
-            age_min, age_max,
+        SELECT
+            status as "status: JobStatus",
+            title, description, responsibilities, benefits,
+            region_id, job_type as "job_type: JobType",
+            age_min, age_max, years_experience_min, years_experience_max,
             salary_min as "salary_min: _",
             salary_max as "salary_max: _",
-            salary_currency, salary_period, benefits,
-            application_deadline, contact_email, application_url,
-            vacancies, applications_count,
-            status as "status: JobStatus",
-            approved_at, approved_by, rejection_reason,
-            completeness_percentage, is_featured, views_count,
-            created_at, updated_at
+            application_deadline, published_at
+        FROM jobs
+        WHERE id = $1 AND company_id = $2
         "#,
-        payload.status as JobStatus,
-        payload.rejection_reason,
         job_id,
         company_id,
     )
-    .fetch_optional(&state.db)
+    .fetch_optional(db)
     .await?
-    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))
+}
+
+/// Everything an edit can change about a job, its lists included, to tell
+/// whether the edit changed anything at all
+async fn job_snapshot(conn: &mut PgConnection, job_id: Uuid) -> Result<serde_json::Value> {
+    let snapshot = sqlx::query_scalar!(
+        r#"
+        SELECT jsonb_build_object(
+            'job', to_jsonb(j) - 'updated_at' - 'completeness_percentage',
+            'required_skills', (
+                SELECT jsonb_agg(jsonb_build_array(skill_id, minimum_proficiency) ORDER BY skill_id)
+                FROM job_required_skills WHERE job_id = j.id
+            ),
+            'preferred_skills', (
+                SELECT jsonb_agg(skill_id ORDER BY skill_id)
+                FROM job_preferred_skills WHERE job_id = j.id
+            ),
+            'required_languages', (
+                SELECT jsonb_agg(jsonb_build_array(language_id, minimum_proficiency) ORDER BY language_id)
+                FROM job_required_languages WHERE job_id = j.id
+            ),
+            'disability_accommodations', (
+                SELECT jsonb_agg(disability_category ORDER BY disability_category)
+                FROM job_disability_accommodations WHERE job_id = j.id
+            )
+        ) as "snapshot!"
+        FROM jobs j
+        WHERE j.id = $1
+        "#,
+        job_id,
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(snapshot)
+}
+
+/// Checks the ranges of the job as it will be after the edit, since an edit
+/// may set only one end of a range
+fn validate_merged_job(current: &EditedJob, payload: &UpdateJobRequest) -> Result<()> {
+    fn ordered<T: PartialOrd>(min: Option<T>, max: Option<T>) -> bool {
+        match (min, max) {
+            (Some(min), Some(max)) => min <= max,
+            _ => true,
+        }
+    }
+
+    if !ordered(
+        payload.salary_min.or(current.salary_min),
+        payload.salary_max.or(current.salary_max),
+    ) {
+        return Err(AppError::ValidationError(
+            "Minimum salary cannot exceed the maximum".to_string(),
+        ));
+    }
+    if !ordered(
+        payload.age_min.or(current.age_min),
+        payload.age_max.or(current.age_max),
+    ) {
+        return Err(AppError::ValidationError(
+            "Minimum age cannot exceed the maximum".to_string(),
+        ));
+    }
+    if !ordered(
+        payload
+            .years_experience_min
+            .or(current.years_experience_min),
+        payload
+            .years_experience_max
+            .or(current.years_experience_max),
+    ) {
+        return Err(AppError::ValidationError(
+            "Minimum experience cannot exceed the maximum".to_string(),
+        ));
+    }
+    // An unchanged deadline may already have passed on a closed job
+    if let Some(deadline) = payload
+        .application_deadline
+        .filter(|&deadline| deadline != current.application_deadline)
+    {
+        // Once published, the deadline has its own rules, log and notices
+        let published = current.published_at.is_some()
+            || !matches!(
+                current.status,
+                JobStatus::Draft
+                    | JobStatus::PendingApproval
+                    | JobStatus::Scheduled
+                    | JobStatus::Rejected
+            );
+        if published {
+            return Err(AppError::ValidationError(
+                "The deadline of a published job is changed with PATCH /api/me/jobs/{id}/deadline"
+                    .to_string(),
+            ));
+        }
+        if deadline < Utc::now().date_naive() {
+            return Err(AppError::ValidationError(
+                "The application deadline cannot be in the past".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Screens content submitted for moderation, refusing it when a finding
+/// blocks submission
+async fn screen_submission(
+    state: &AppState,
+    content: &JobContent<'_>,
+) -> Result<Vec<ScreeningFinding>> {
+    let mut redis = state.redis.clone();
+    let findings = content_screening::screen_job(&state.db, &mut redis, content).await?;
+
+    let violations: Vec<&ScreeningFinding> =
+        findings.iter().filter(|f| content_screening::is_blocking(f)).collect();
+    if !violations.is_empty() {
+        return Err(AppError::UnprocessableEntity {
+            message: "Job content must be corrected before it can be submitted".to_string(),
+            details: serde_json::json!({ "violations": violations }),
+        });
+    }
+    Ok(findings)
+}
+
+/// Warnings from a submission replace those of any earlier one
+async fn store_findings(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    findings: &[ScreeningFinding],
+) -> Result<()> {
+    if findings.is_empty() {
+        sqlx::query!("DELETE FROM job_screening_findings WHERE job_id = $1", job_id)
+            .execute(conn)
+            .await?;
+    } else {
+        sqlx::query!(
+            r#"
+            INSERT INTO job_screening_findings (job_id, findings)
+            VALUES ($1, $2)
+            ON CONFLICT (job_id) DO UPDATE
+            SET findings = EXCLUDED.findings, screened_at = NOW()
+            "#,
+            job_id,
+            serde_json::json!(findings),
+        )
+        .execute(conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// PATCH /api/me/jobs/{id}/status
+/// Change job status (owner/admin only)
+pub async fn update_job_status(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<UpdateJobStatusRequest>,
+) -> Result<Json<Job>> {
+    let company_id = authz::require_company(&state.db, &auth_user, Capability::PostJobs).await?;
+
+    payload.validate()?;
+
+    // Validate rejection_reason if status is rejected
+    if payload.status == JobStatus::Rejected && payload.rejection_reason.is_none() {
+        return Err(AppError::ValidationError(
+            "Rejection reason is required when rejecting a job".to_string(),
+        ));
+    }
+
+    if payload.status == JobStatus::ArchivedDeleted {
+        return Err(AppError::ValidationError(
+            "Jobs are archived only when their company is deleted".to_string(),
+        ));
+    }
+
+    // Scheduling is driven by approval and publish_at, never set by hand
+    if payload.status == JobStatus::Scheduled {
+        return Err(AppError::ValidationError(
+            "Jobs are scheduled automatically when approved with a publish time".to_string(),
+        ));
+    }
+
+    let current = sqlx::query!(
+        r#"
+        SELECT
+            status as "status: JobStatus", publish_at,
+            title, description, responsibilities, benefits, age_min, age_max,
+            region_id, job_type as "job_type: JobType"
+        FROM jobs
+        WHERE id = $1 AND company_id = $2
+        "#,
+        job_id,
+        company_id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    let clear_publish_at = payload.clear_publish_at.unwrap_or(false);
+
+    // A scheduled job can't go live early unless its publish time is dropped
+    if current.status == JobStatus::Scheduled
+        && payload.status == JobStatus::Active
+        && !clear_publish_at
+    {
+        if let Some(publish_at) = current.publish_at.filter(|p| *p > Utc::now()) {
+            return Err(AppError::ValidationError(format!(
+                "Job is scheduled to publish at {}; clear publish_at to activate it now",
+                publish_at.to_rfc3339()
+            )));
+        }
+    }
+
+    // Pre-screen content before it reaches the moderation queue; blocking
+    // findings keep the job where it is
+    let findings = if payload.status == JobStatus::PendingApproval {
+        let content = JobContent {
+            title: &current.title,
+            description: &current.description,
+            responsibilities: current.responsibilities.as_deref(),
+            benefits: current.benefits.as_deref(),
+            age_min: current.age_min,
+            age_max: current.age_max,
+        };
+        Some(screen_submission(&state, &content).await?)
+    } else {
+        None
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    let mut duplicates = Vec::new();
+    if payload.status == JobStatus::PendingApproval {
+        duplicates = job_duplicates::find_duplicates(
+            &mut tx,
+            &JobFingerprint {
+                company_id,
+                job_id: Some(job_id),
+                title: &current.title,
+                description: &current.description,
+                region_id: current.region_id,
+                job_type: current.job_type,
+            },
+        )
+        .await?;
+        if !duplicates.is_empty() && !payload.force.unwrap_or(false) {
+            return Err(job_duplicates::duplicate_conflict(&duplicates));
+        }
+    }
+
+    let job = sqlx::query_as!(
+        Job,
+        r#"
+        UPDATE jobs
+        SET
+            status = $1,
+            rejection_reason = $2,
+            publish_at = CASE WHEN $5 THEN NULL ELSE publish_at END,
+            deadline_closed_at = NULL,
+            published_at = CASE
+                WHEN $1 = 'active'::job_status AND status <> 'active'::job_status THEN NOW()
+                ELSE published_at
+            END,
+            submitted_at = CASE
+                WHEN $1 = 'pending_approval'::job_status AND status <> 'pending_approval'::job_status THEN NOW()
+                ELSE submitted_at
+            END
+        WHERE id = $3 AND company_id = $4
+        RETURNING
+            id, company_id, posted_by,
+            title, description, responsibilities,
+            job_type as "job_type: JobType",
+            industry_id, work_area_id, position_level_id,
+            work_modality as "work_modality: WorkModality",
+            schedule_details,
+            shift_type as "shift_type: ShiftType",
+            weekly_hours,
+            region_id, municipality_id, is_remote_allowed,
+            education_level, years_experience_min, years_experience_max,
+            age_min, age_max,
+            salary_min as "salary_min: _",
+            salary_max as "salary_max: _",
+            salary_currency, salary_period, benefits,
+            application_deadline, contact_email, application_url,
+            vacancies, applications_count,
+            status as "status: JobStatus",
+            approved_at, approved_by, rejection_reason,
+            completeness_percentage, is_featured, views_count,
+            publish_at, published_at,
+            created_at, updated_at
+        "#,
+        payload.status as JobStatus,
+        payload.rejection_reason,
+        job_id,
+        company_id,
+        clear_publish_at,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    if !duplicates.is_empty() {
+        job_duplicates::record_override(&mut tx, job.id, &duplicates, auth_user.id).await?;
+    }
+
+    if let Some(findings) = findings {
+        store_findings(&mut tx, job.id, &findings).await?;
+    }
+
+    if job.status == JobStatus::Closed && current.status != JobStatus::Closed {
+        events::emit(
+            &mut tx,
+            AggregateType::Job,
+            job.id,
+            "closed",
+            Some(auth_user.id),
+            json!({ "from": current.status }),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    // Reactivating a job paused for inactivity answers the nudges too
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
+    if job.status == JobStatus::Active && current.status != JobStatus::Active {
+        metering::record(
+            &state.db,
+            company_id,
+            UsageEventType::JobPublished,
+            1,
+            json!({ "job_id": job.id }),
+        )
+        .await;
+    }
+
+    admin_events::publish(&state).await;
 
     Ok(Json(job))
 }
 
+/// PATCH /api/me/jobs/{id}/deadline
+/// Move the application deadline of an active job, or extend that of one
+/// closed by its deadline to reopen it (owner/admin only)
+pub async fn update_job_deadline(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<UpdateJobDeadlineRequest>,
+) -> Result<Json<JobDeadlineChange>> {
+    let company_id = authz::require_company(&state.db, &auth_user, Capability::PostJobs).await?;
+
+    let change = job_deadlines::change_deadline(
+        &state,
+        company_id,
+        job_id,
+        auth_user.id,
+        payload.application_deadline,
+    )
+    .await?;
+
+    Ok(Json(change))
+}
+
 /// GET /api/me/jobs/{id}/applications
 /// List all applications for a job (company members only)
 pub async fn list_job_applications(
@@ -746,14 +1312,8 @@ pub async fn list_job_applications(
     Extension(auth_user): Extension<AuthUser>,
     Path(job_id): Path<Uuid>,
 ) -> Result<Json<Vec<ApplicationWithApplicantDetails>>> {
-    // Only company members can access this
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
-
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
 
     // Verify job belongs to company
     let job_exists = sqlx::query_scalar!(
@@ -770,6 +1330,8 @@ pub async fn list_job_applications(
         return Err(AppError::NotFound("Job not found".to_string()));
     }
 
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
     // Get applications with applicant profiles
     let applications = sqlx::query_as!(
         JobApplication,
@@ -779,9 +1341,11 @@ pub async fn list_job_applications(
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         FROM job_applications
         WHERE job_id = $1
@@ -807,6 +1371,9 @@ pub async fn list_job_applications(
                 bio, professional_headline,
                 profile_image_url, cv_url,
                 completeness_percentage,
+                availability as "availability: crate::models::profile::SeekerAvailability",
+                CASE WHEN share_available_from THEN available_from END as available_from,
+                share_available_from,
                 created_at, updated_at
             FROM job_seeker_profiles
             WHERE user_id = $1
@@ -833,24 +1400,11 @@ pub async fn update_application_status(
     Path((job_id, app_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateApplicationStatusRequest>,
 ) -> Result<Json<JobApplication>> {
-    // Only company members can update applications
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can update applications".to_string(),
-        ));
-    }
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ManageApplicants).await?;
 
     payload.validate()?;
 
-    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
-
-    // Only owner/admin can update applications
-    if !is_owner_or_admin(role) {
-        return Err(AppError::ForbiddenError(
-            "Only owners and admins can update application status".to_string(),
-        ));
-    }
-
     // Verify job belongs to company
     let job_exists = sqlx::query_scalar!(
         r#"
@@ -866,13 +1420,55 @@ pub async fn update_application_status(
         return Err(AppError::NotFound("Job not found".to_string()));
     }
 
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
+    if payload.status == ApplicationStatus::Withdrawn {
+        return Err(AppError::ForbiddenError(
+            "Only the applicant can withdraw an application".to_string(),
+        ));
+    }
+
+    let current_status = sqlx::query_scalar!(
+        r#"
+        SELECT status as "status: ApplicationStatus"
+        FROM job_applications
+        WHERE id = $1 AND job_id = $2
+        "#,
+        app_id,
+        job_id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
+
+    if !current_status.can_transition_to(payload.status) {
+        return Err(AppError::invalid_status_transition(
+            current_status,
+            payload.status,
+            &current_status.company_transitions(),
+        ));
+    }
+
+    if payload.status != ApplicationStatus::Rejected && payload.has_rejection_details() {
+        return Err(AppError::ValidationError(
+            "A rejection reason and feedback can only be given when rejecting".to_string(),
+        ));
+    }
+    let feedback_text = payload
+        .feedback_text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+
     // Update application
-    let offer_date = if payload.status == ApplicationStatus::Offered {
+    let offer_date = if payload.status == ApplicationStatus::OfferExtended {
         Some(Utc::now())
     } else {
         None
     };
 
+    let mut tx = state.db.begin().await?;
+
     let application = sqlx::query_as!(
         JobApplication,
         r#"
@@ -883,17 +1479,23 @@ pub async fn update_application_status(
             reviewed_by = $3,
             interview_date = COALESCE($4, interview_date),
             interview_notes = COALESCE($5, interview_notes),
+            interview_location = COALESCE($14, interview_location),
             offer_date = COALESCE($6, offer_date),
-            offer_details = COALESCE($7, offer_details)
-        WHERE id = $8 AND job_id = $9
+            offer_details = COALESCE($7, offer_details),
+            rejection_reason_code = COALESCE($11, rejection_reason_code),
+            rejection_feedback = COALESCE($12, rejection_feedback),
+            rejection_feedback_shared = COALESCE($13, rejection_feedback_shared)
+        WHERE id = $8 AND job_id = $9 AND status = $10
         RETURNING
             id, job_id, applicant_id,
             status as "status: ApplicationStatus",
             cover_letter, resume_url, applied_at,
             reviewed_at, reviewed_by,
-            interview_date, interview_notes,
+            interview_date, interview_notes, interview_location,
             offer_date, offer_details, response_date,
             withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
             created_at, updated_at
         "#,
         payload.status as ApplicationStatus,
@@ -905,11 +1507,114 @@ pub async fn update_application_status(
         payload.offer_details,
         app_id,
         job_id,
+        current_status as ApplicationStatus,
+        payload.rejection_reason_code as Option<RejectionReasonCode>,
+        feedback_text,
+        payload.share_feedback_with_candidate,
+        payload.interview_location,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::ConflictError("Application status changed, please reload".to_string())
+    })?;
+
+    events::emit(
+        &mut tx,
+        AggregateType::Application,
+        application.id,
+        "status_changed",
+        Some(auth_user.id),
+        json!({
+            "from": current_status,
+            "to": application.status,
+            "rejection_reason_code": payload.rejection_reason_code,
+            "feedback": feedback_text,
+        }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if application.status == ApplicationStatus::Hired {
+        if let Err(e) = availability::prompt_after_hire(&state, &[application.id]).await {
+            tracing::error!("Failed to prompt availability update: {}", e);
+        }
+    }
+
+    Ok(Json(application))
+}
+
+/// PUT /api/me/jobs/{job_id}/applications/{app_id}/interview
+/// Reschedule the interview of an application in interview_scheduled
+/// (owner/admin only)
+pub async fn reschedule_interview(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, app_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<RescheduleInterviewRequest>,
+) -> Result<Json<JobApplication>> {
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ManageApplicants).await?;
+
+    payload.validate()?;
+
+    let current_status = sqlx::query_scalar!(
+        r#"
+        SELECT ja.status as "status: ApplicationStatus"
+        FROM job_applications ja
+        JOIN jobs j ON j.id = ja.job_id
+        WHERE ja.id = $1 AND ja.job_id = $2 AND j.company_id = $3
+        "#,
+        app_id,
+        job_id,
+        company_id,
     )
     .fetch_optional(&state.db)
     .await?
     .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
 
+    if current_status != ApplicationStatus::InterviewScheduled {
+        return Err(AppError::ValidationError(
+            "Only scheduled interviews can be rescheduled".to_string(),
+        ));
+    }
+
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
+    let application = sqlx::query_as!(
+        JobApplication,
+        r#"
+        UPDATE job_applications
+        SET
+            interview_date = $1,
+            interview_location = COALESCE($2, interview_location),
+            interview_notes = COALESCE($3, interview_notes)
+        WHERE id = $4 AND job_id = $5 AND status = 'interview_scheduled'
+        RETURNING
+            id, job_id, applicant_id,
+            status as "status: ApplicationStatus",
+            cover_letter, resume_url, applied_at,
+            reviewed_at, reviewed_by,
+            interview_date, interview_notes, interview_location,
+            offer_date, offer_details, response_date,
+            withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
+            created_at, updated_at
+        "#,
+        payload.interview_date,
+        payload.interview_location,
+        payload.interview_notes,
+        app_id,
+        job_id,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::ConflictError("Application status changed, please reload".to_string())
+    })?;
+
     Ok(Json(application))
 }
 
@@ -921,17 +1626,11 @@ pub async fn add_application_note(
     Path((job_id, app_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<CreateApplicationNoteRequest>,
 ) -> Result<Json<ApplicationNote>> {
-    // Only company members can add notes
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can add application notes".to_string(),
-        ));
-    }
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
 
     payload.validate()?;
 
-    let (company_id, _) = get_user_company_membership(&state.db, auth_user.id).await?;
-
     // Verify job belongs to company
     let job_exists = sqlx::query_scalar!(
         r#"
@@ -962,6 +1661,8 @@ pub async fn add_application_note(
         return Err(AppError::NotFound("Application not found".to_string()));
     }
 
+    stale_jobs::record_activity(&state.db, job_id).await?;
+
     // Create note
     let note = sqlx::query_as!(
         ApplicationNote,
@@ -980,3 +1681,270 @@ pub async fn add_application_note(
 
     Ok(Json(note))
 }
+
+// ============================================================================
+// V13: FUNNEL AUTOMATION RULES
+// ============================================================================
+
+/// Company of an owner/admin managing the job's automation rules
+async fn automation_rule_company(
+    state: &AppState,
+    auth_user: &AuthUser,
+    job_id: Uuid,
+) -> Result<Uuid> {
+    let company_id = authz::require_company(&state.db, auth_user, Capability::PostJobs).await?;
+
+    let job_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1 AND company_id = $2) as "exists!""#,
+        job_id,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !job_exists {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    Ok(company_id)
+}
+
+/// GET /api/me/jobs/{id}/automation-rules
+/// List the job's automation rules in the order they run (owner/admin only)
+pub async fn list_automation_rules(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Vec<JobAutomationRule>>> {
+    automation_rule_company(&state, &auth_user, job_id).await?;
+
+    Ok(Json(automation::list_rules(&state.db, job_id).await?))
+}
+
+/// POST /api/me/jobs/{id}/automation-rules
+/// Add an automation rule to the job (owner/admin only)
+pub async fn create_automation_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(mut payload): Json<AutomationRuleRequest>,
+) -> Result<Json<JobAutomationRule>> {
+    payload.normalize();
+    payload.validate()?;
+
+    let company_id = automation_rule_company(&state, &auth_user, job_id).await?;
+    let rule =
+        automation::create_rule(&state.db, company_id, job_id, auth_user.id, &payload).await?;
+
+    Ok(Json(rule))
+}
+
+/// PUT /api/me/jobs/{job_id}/automation-rules/{rule_id}
+/// Replace an automation rule (owner/admin only)
+pub async fn update_automation_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(mut payload): Json<AutomationRuleRequest>,
+) -> Result<Json<JobAutomationRule>> {
+    payload.normalize();
+    payload.validate()?;
+
+    let company_id = automation_rule_company(&state, &auth_user, job_id).await?;
+    let rule = automation::update_rule(&state.db, company_id, job_id, rule_id, &payload).await?;
+
+    Ok(Json(rule))
+}
+
+/// DELETE /api/me/jobs/{job_id}/automation-rules/{rule_id}
+/// Delete an automation rule (owner/admin only)
+pub async fn delete_automation_rule(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((job_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>> {
+    automation_rule_company(&state, &auth_user, job_id).await?;
+    automation::delete_rule(&state.db, job_id, rule_id).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Automation rule deleted successfully" })))
+}
+
+// ============================================================================
+// V13: JOB ACTIVITY FEED
+// ============================================================================
+
+/// Company of a member reading the job's activity feed; any role may
+async fn activity_feed_company(
+    state: &AppState,
+    auth_user: &AuthUser,
+    job_id: Uuid,
+) -> Result<Uuid> {
+    let company_id =
+        authz::require_company(&state.db, auth_user, Capability::ViewCompanyJobs).await?;
+
+    let job_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1 AND company_id = $2) as "exists!""#,
+        job_id,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !job_exists {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    Ok(company_id)
+}
+
+/// GET /api/me/jobs/{id}/activity
+/// What the company's members did on the job, newest first: status changes,
+/// interviews, notes, invitations and bulk updates (all company members)
+pub async fn get_job_activity(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Query(query): Query<JobActivityQuery>,
+) -> Result<Json<JobActivityResponse>> {
+    let company_id = activity_feed_company(&state, &auth_user, job_id).await?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    // The counts come back even when the page is empty, with NULL entry
+    // columns. Status changes count only when made by one of the company's
+    // members; withdrawals are the candidate's own.
+    let rows = sqlx::query!(
+        r#"
+        WITH activity AS (
+            SELECT
+                h.changed_by as actor_id,
+                CASE
+                    WHEN h.new_status = 'interview_scheduled' THEN 'interview_scheduled'
+                    ELSE 'status_changed'
+                END as action,
+                a.applicant_id as target_id,
+                h.created_at as occurred_at,
+                COALESCE(h.previous_status::text || ' → ', '') || h.new_status::text as summary
+            FROM application_status_history h
+            JOIN job_applications a ON a.id = h.application_id
+            WHERE a.job_id = $1
+            AND h.new_status <> 'withdrawn'
+            AND EXISTS (
+                SELECT 1 FROM company_members cm
+                WHERE cm.user_id = h.changed_by AND cm.company_id = $2
+            )
+            UNION ALL
+            SELECT n.created_by, 'note_added', a.applicant_id, n.created_at, LEFT(n.note_text, 140)
+            FROM application_notes n
+            JOIN job_applications a ON a.id = n.application_id
+            WHERE a.job_id = $1
+            UNION ALL
+            SELECT i.invited_by, 'invitation_sent', i.job_seeker_id, i.created_at, NULL
+            FROM job_invitations i
+            WHERE i.job_id = $1
+            UNION ALL
+            SELECT
+                b.requested_by, 'bulk_update', NULL, b.created_at,
+                b.total || ' → ' || b.target_status::text
+            FROM bulk_operations b
+            WHERE b.job_id = $1
+        ),
+        counts AS (
+            SELECT
+                COUNT(*) FILTER (
+                    WHERE $3::timestamptz IS NULL OR occurred_at > $3
+                ) as total,
+                COUNT(*) FILTER (
+                    WHERE actor_id <> $4 AND occurred_at > (
+                        SELECT COALESCE(last_seen_activity_at, joined_at)
+                        FROM company_members WHERE user_id = $4
+                    )
+                ) as unseen_count
+            FROM activity
+        )
+        SELECT
+            c.total as "total!",
+            c.unseen_count as "unseen_count!",
+            e.actor_id as "actor_id?",
+            e.actor as "actor?",
+            e.action as "action?",
+            e.target_id as "target_applicant_id?",
+            e.target_applicant as "target_applicant?",
+            e.occurred_at as "occurred_at?",
+            e.summary as "summary?"
+        FROM counts c
+        LEFT JOIN LATERAL (
+            SELECT
+                act.actor_id, u.first_name || ' ' || u.last_name as actor, act.action,
+                act.target_id, t.first_name || ' ' || t.last_name as target_applicant,
+                act.occurred_at, act.summary
+            FROM activity act
+            JOIN users u ON u.id = act.actor_id
+            LEFT JOIN users t ON t.id = act.target_id
+            WHERE $3::timestamptz IS NULL OR act.occurred_at > $3
+            ORDER BY act.occurred_at DESC, act.action
+            LIMIT $5 OFFSET $6
+        ) e ON true
+        "#,
+        job_id,
+        company_id,
+        query.since,
+        auth_user.id,
+        limit,
+        offset,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let total = rows.first().map_or(0, |r| r.total);
+    let unseen_count = rows.first().map_or(0, |r| r.unseen_count);
+    let entries = rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(JobActivityEntry {
+                actor_id: r.actor_id?,
+                actor: r.actor?,
+                action: r.action?,
+                target_applicant_id: r.target_applicant_id,
+                target_applicant: r.target_applicant,
+                occurred_at: r.occurred_at?,
+                summary: r.summary,
+            })
+        })
+        .collect();
+
+    Ok(Json(JobActivityResponse {
+        entries,
+        total,
+        limit,
+        offset,
+        unseen_count,
+    }))
+}
+
+/// POST /api/me/jobs/{id}/activity/seen
+/// Marks everything in the activity feed as seen by the caller (all company
+/// members). The mark is kept per member, so it covers all their jobs.
+pub async fn mark_job_activity_seen(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let company_id = activity_feed_company(&state, &auth_user, job_id).await?;
+
+    let seen_at = sqlx::query_scalar!(
+        r#"
+        UPDATE company_members
+        SET last_seen_activity_at = NOW()
+        WHERE user_id = $1 AND company_id = $2
+        RETURNING last_seen_activity_at as "last_seen_activity_at!"
+        "#,
+        auth_user.id,
+        company_id,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(json!({ "last_seen_activity_at": seen_at })))
+}
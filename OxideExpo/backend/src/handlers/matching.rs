@@ -1,18 +1,29 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     Extension, Json,
 };
+use std::collections::HashMap;
+
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
+    handlers::profile::fetch_user_skills,
     middleware::AuthUser,
     models::{
-        job::PublicJobListing,
+        company::UsageEventType,
+        job::{schedule_display, PublicJobListing, ShiftType},
         matching::*,
+        permissions::Capability,
+        profile::SeekerAvailability,
+    },
+    services::{
+        authz, browsing, job_reposts,
+        matching::{diversify_top, generate_match_tips, MatchingService},
+        metering, skill_gap,
     },
-    services::matching::MatchingService,
     AppState,
 };
 
@@ -38,6 +49,40 @@ pub async fn get_recommended_jobs(
     let offset = query.offset.unwrap_or(0);
     let min_score = query.min_score.unwrap_or(0);
     let exclude_applied = query.exclude_applied.unwrap_or(true);
+    let diversify = query.diversify.unwrap_or(false);
+
+    // A commute limit keeps on-site jobs beyond reach of the seeker's
+    // municipality out of the recommendations
+    let commute = sqlx::query!(
+        r#"
+        SELECT
+            m.latitude as "latitude!",
+            m.longitude as "longitude!",
+            p.preferred_max_commute_km::float8 as "radius_km!"
+        FROM job_seeker_preferences p
+        JOIN job_seeker_profiles sp ON sp.user_id = p.user_id
+        JOIN municipalities m ON m.id = sp.municipality_id
+        WHERE p.user_id = $1
+          AND p.preferred_max_commute_km IS NOT NULL
+          AND m.latitude IS NOT NULL
+          AND m.longitude IS NOT NULL
+        "#,
+        auth_user.id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let preferred_shift_types: Vec<ShiftType> = sqlx::query_scalar!(
+        r#"
+        SELECT preferred_shift_types as "preferred_shift_types: Vec<ShiftType>"
+        FROM job_seeker_preferences
+        WHERE user_id = $1
+        "#,
+        auth_user.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .unwrap_or_default();
 
     // Get active jobs
     let active_jobs = sqlx::query!(
@@ -52,7 +97,9 @@ pub async fn get_recommended_jobs(
             j.work_area_id,
             j.position_level_id,
             j.work_modality as "work_modality: String",
-            j.work_schedule,
+            j.schedule_details,
+            j.shift_type as "shift_type: ShiftType",
+            j.weekly_hours,
             j.region_id,
             j.municipality_id,
             COALESCE(j.is_remote_allowed, false) as "is_remote_allowed!",
@@ -66,19 +113,42 @@ pub async fn get_recommended_jobs(
             j.vacancies,
             COALESCE(j.is_featured, false) as "is_featured!",
             j.created_at,
+            j.published_at,
+            j.company_id,
             c.company_name,
             c.logo_url as company_logo_url
         FROM jobs j
         JOIN company_profiles c ON j.company_id = c.id
+        LEFT JOIN municipalities jm ON jm.id = j.municipality_id
+        LEFT JOIN job_seeker_preferences pref ON pref.user_id = $5
         WHERE j.status = 'active'
           AND j.application_deadline >= CURRENT_DATE
-        ORDER BY j.is_featured DESC, j.created_at DESC
+          AND (
+            $1::float8 IS NULL
+            OR COALESCE(j.is_remote_allowed, false)
+            OR haversine_km($1, $2, jm.latitude, jm.longitude) <= $3
+          )
+          AND (
+            $4::salary_fit IS NULL
+            OR salary_expectation_fit(
+                j.salary_min, j.salary_max, j.salary_currency, j.salary_period,
+                pref.salary_expectation_min, pref.salary_expectation_max,
+                COALESCE(pref.salary_currency, 'CLP')
+            ) = $4
+          )
+        ORDER BY j.is_featured DESC, j.created_at DESC, j.id
         LIMIT 200
-        "#
+        "#,
+        commute.as_ref().map(|c| c.latitude),
+        commute.as_ref().map(|c| c.longitude),
+        commute.as_ref().map(|c| c.radius_km),
+        query.salary_fit as Option<SalaryFit>,
+        auth_user.id,
     )
     .fetch_all(&state.db)
     .await?;
 
+    // Jobs with the keys they are ranked by
     let mut recommended_jobs = Vec::new();
 
     for job in active_jobs {
@@ -129,7 +199,14 @@ pub async fn get_recommended_jobs(
             work_area_id: job.work_area_id,
             position_level_id: job.position_level_id,
             work_modality,
-            work_schedule: job.work_schedule,
+            schedule_display: schedule_display(
+                job.shift_type,
+                job.weekly_hours,
+                job.schedule_details.as_deref(),
+            ),
+            schedule_details: job.schedule_details,
+            shift_type: job.shift_type,
+            weekly_hours: job.weekly_hours,
             region_id: job.region_id,
             municipality_id: job.municipality_id,
             is_remote_allowed: job.is_remote_allowed,
@@ -147,16 +224,44 @@ pub async fn get_recommended_jobs(
             company_logo_url: job.company_logo_url,
         };
 
-        recommended_jobs.push(RecommendedJob {
-            job: public_job,
-            match_score: score_breakdown.total_score,
-            score_breakdown,
-            already_applied,
-        });
+        // A preferred shift type nudges the job up without changing its score
+        let rank = match public_job.shift_type {
+            Some(shift_type) if preferred_shift_types.contains(&shift_type) => {
+                score_breakdown.total_score + SHIFT_PREFERENCE_BOOST
+            }
+            _ => score_breakdown.total_score,
+        };
+
+        recommended_jobs.push((
+            RecommendedJob {
+                job: public_job,
+                match_score: score_breakdown.total_score,
+                score_breakdown,
+                already_applied,
+            },
+            rank,
+            job.published_at,
+            job.company_id,
+        ));
     }
 
-    // Sort by match score descending
-    recommended_jobs.sort_by(|a, b| b.match_score.cmp(&a.match_score));
+    // Rank descending; ties go to the most recently published job, then the
+    // lowest ID, so every request sees the same order and offsets page cleanly
+    recommended_jobs.sort_by(|(a, a_rank, a_published, _), (b, b_rank, b_published, _)| {
+        b_rank
+            .cmp(a_rank)
+            .then_with(|| b_published.cmp(a_published))
+            .then_with(|| a.job.id.cmp(&b.job.id))
+    });
+
+    let (recommended_jobs, ordering) = if diversify {
+        (
+            diversify_top(recommended_jobs, |(_, _, _, company_id)| *company_id),
+            RECOMMENDED_JOBS_DIVERSIFIED_ORDERING,
+        )
+    } else {
+        (recommended_jobs, RECOMMENDED_JOBS_ORDERING)
+    };
 
     let total_count = recommended_jobs.len() as i64;
     let has_more = (offset + limit) < total_count;
@@ -166,12 +271,14 @@ pub async fn get_recommended_jobs(
         .into_iter()
         .skip(offset as usize)
         .take(limit as usize)
+        .map(|(job, _, _, _)| job)
         .collect();
 
     Ok(Json(RecommendedJobsResponse {
         jobs,
         total_count,
         has_more,
+        ordering: ordering.to_string(),
     }))
 }
 
@@ -226,6 +333,177 @@ pub async fn get_job_match_score(
     }))
 }
 
+/// GET /api/jobs/{id}/match-score/tips
+/// Get the changes that would raise the job seeker's match score for a job the most
+pub async fn get_job_match_tips(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<MatchTipsResponse>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let job_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM jobs
+            WHERE id = $1 AND status = 'active'
+        ) as "exists!"
+        "#,
+        job_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !job_exists {
+        return Err(AppError::NotFound("Job not found or not active".to_string()));
+    }
+
+    let breakdown = MatchingService::calculate_match_score(&state.db, job_id, auth_user.id).await?;
+
+    // Resolve names for the skills and languages the tips mention
+    let skill_ids: Vec<Uuid> = breakdown
+        .skills
+        .missing_required
+        .iter()
+        .map(|s| s.skill_id)
+        .collect();
+    let language_ids: Vec<Uuid> = breakdown
+        .languages
+        .missing
+        .iter()
+        .map(|l| l.language_id)
+        .collect();
+
+    let skill_names: HashMap<Uuid, String> = sqlx::query!(
+        "SELECT id, name FROM skills WHERE id = ANY($1)",
+        &skill_ids
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.name))
+    .collect();
+
+    let language_names: HashMap<Uuid, String> = sqlx::query!(
+        "SELECT id, name FROM languages WHERE id = ANY($1)",
+        &language_ids
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.name))
+    .collect();
+
+    Ok(Json(MatchTipsResponse {
+        job_id,
+        match_score: breakdown.total_score,
+        tips: generate_match_tips(&breakdown, &skill_names, &language_names),
+    }))
+}
+
+/// GET /api/jobs/{id}/match-score/history
+/// How the job seeker's own match score for a job changed over time
+pub async fn get_job_match_score_history(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<MatchScoreHistoryResponse>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    // History stays readable after the job closes
+    let job_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1) as "exists!""#,
+        job_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if !job_exists {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    let points = MatchingService::get_score_history(&state.db, job_id, auth_user.id).await?;
+
+    Ok(Json(MatchScoreHistoryResponse { job_id, points }))
+}
+
+/// GET /api/me/skill-gap
+/// Skills the active jobs of a work area ask for that the job seeker lacks,
+/// ranked by how many jobs they keep out of reach
+pub async fn get_skill_gap(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<SkillGapQuery>,
+) -> Result<Json<SkillGapResponse>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let work_area_id = match query.work_area_id {
+        Some(work_area_id) => {
+            let exists = sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM work_areas WHERE id = $1) as "exists!""#,
+                work_area_id
+            )
+            .fetch_one(&state.db)
+            .await?;
+            if !exists {
+                return Err(AppError::NotFound("Work area not found".to_string()));
+            }
+            work_area_id
+        }
+        None => sqlx::query_scalar!(
+            r#"
+            SELECT work_area_id as "work_area_id!"
+            FROM work_experiences
+            WHERE user_id = $1 AND work_area_id IS NOT NULL
+            ORDER BY is_current DESC, start_date DESC
+            LIMIT 1
+            "#,
+            auth_user.id
+        )
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| {
+            AppError::ValidationError(
+                "Choose a work area: none of your work experience has one".to_string(),
+            )
+        })?,
+    };
+
+    let region_id = if query.in_my_region.unwrap_or(false) {
+        let region_id = sqlx::query_scalar!(
+            "SELECT region_id FROM job_seeker_profiles WHERE user_id = $1",
+            auth_user.id
+        )
+        .fetch_optional(&state.db)
+        .await?
+        .flatten()
+        .ok_or_else(|| {
+            AppError::ValidationError("Add your region to your profile first".to_string())
+        })?;
+        Some(region_id)
+    } else {
+        None
+    };
+
+    let mut redis = state.redis.clone();
+    let gap =
+        skill_gap::skill_gap(&state.db, &mut redis, auth_user.id, work_area_id, region_id).await?;
+
+    Ok(Json(gap))
+}
+
 // ============================================================================
 // PREFERENCES ENDPOINTS
 // ============================================================================
@@ -255,10 +533,15 @@ pub async fn get_preferences(
             salary_expectation_min,
             salary_expectation_max,
             COALESCE(salary_currency, 'CLP') as "salary_currency!",
+            preferred_max_commute_km,
             profile_visibility as "profile_visibility: ProfileVisibility",
             show_disability_info,
+            show_references,
+            show_salary_expectation,
             email_job_alerts,
             alert_frequency as "alert_frequency: AlertFrequency",
+            track_browsing,
+            preferred_shift_types as "preferred_shift_types: Vec<ShiftType>",
             created_at,
             updated_at
         "#,
@@ -273,14 +556,19 @@ pub async fn get_preferences(
         preferred_job_types: Vec::new(),
         preferred_region_ids: Vec::new(),
         preferred_industry_ids: Vec::new(),
+        preferred_shift_types: preferences.preferred_shift_types,
         willing_to_relocate: preferences.willing_to_relocate.unwrap_or(false),
         salary_expectation_min: preferences.salary_expectation_min,
         salary_expectation_max: preferences.salary_expectation_max,
         salary_currency: preferences.salary_currency,
+        preferred_max_commute_km: preferences.preferred_max_commute_km,
         profile_visibility: preferences.profile_visibility,
         show_disability_info: preferences.show_disability_info,
+        show_references: preferences.show_references,
+        show_salary_expectation: preferences.show_salary_expectation,
         email_job_alerts: preferences.email_job_alerts,
         alert_frequency: preferences.alert_frequency,
+        track_browsing: preferences.track_browsing,
         created_at: preferences.created_at,
         updated_at: preferences.updated_at,
     }))
@@ -322,6 +610,11 @@ pub async fn update_preferences(
             show_disability_info = COALESCE($7, show_disability_info),
             email_job_alerts = COALESCE($8, email_job_alerts),
             alert_frequency = COALESCE($9, alert_frequency),
+            preferred_max_commute_km = COALESCE($10, preferred_max_commute_km),
+            preferred_shift_types = COALESCE($11, preferred_shift_types),
+            track_browsing = COALESCE($12, track_browsing),
+            show_references = COALESCE($13, show_references),
+            show_salary_expectation = COALESCE($14, show_salary_expectation),
             updated_at = NOW()
         WHERE user_id = $1
         RETURNING
@@ -330,10 +623,15 @@ pub async fn update_preferences(
             salary_expectation_min,
             salary_expectation_max,
             COALESCE(salary_currency, 'CLP') as "salary_currency!",
+            preferred_max_commute_km,
             profile_visibility as "profile_visibility: ProfileVisibility",
             show_disability_info,
+            show_references,
+            show_salary_expectation,
             email_job_alerts,
             alert_frequency as "alert_frequency: AlertFrequency",
+            track_browsing,
+            preferred_shift_types as "preferred_shift_types: Vec<ShiftType>",
             created_at,
             updated_at
         "#,
@@ -346,24 +644,38 @@ pub async fn update_preferences(
         payload.show_disability_info,
         payload.email_job_alerts,
         payload.alert_frequency as Option<AlertFrequency>,
+        payload.preferred_max_commute_km,
+        payload.preferred_shift_types.as_deref() as Option<&[ShiftType]>,
+        payload.track_browsing,
+        payload.show_references,
+        payload.show_salary_expectation,
     )
     .fetch_one(&state.db)
     .await?;
 
+    if payload.track_browsing == Some(false) {
+        browsing::clear(&state.db, auth_user.id).await?;
+    }
+
     Ok(Json(JobSeekerPreferences {
         user_id: preferences.user_id,
         preferred_work_modalities: Vec::new(),
         preferred_job_types: Vec::new(),
         preferred_region_ids: Vec::new(),
         preferred_industry_ids: Vec::new(),
+        preferred_shift_types: preferences.preferred_shift_types,
         willing_to_relocate: preferences.willing_to_relocate.unwrap_or(false),
         salary_expectation_min: preferences.salary_expectation_min,
         salary_expectation_max: preferences.salary_expectation_max,
         salary_currency: preferences.salary_currency,
+        preferred_max_commute_km: preferences.preferred_max_commute_km,
         profile_visibility: preferences.profile_visibility,
         show_disability_info: preferences.show_disability_info,
+        show_references: preferences.show_references,
+        show_salary_expectation: preferences.show_salary_expectation,
         email_job_alerts: preferences.email_job_alerts,
         alert_frequency: preferences.alert_frequency,
+        track_browsing: preferences.track_browsing,
         created_at: preferences.created_at,
         updated_at: preferences.updated_at,
     }))
@@ -381,23 +693,18 @@ pub async fn get_recommended_candidates(
     Path(job_id): Path<Uuid>,
     Query(query): Query<RecommendedCandidatesQuery>,
 ) -> Result<Json<RecommendedCandidatesResponse>> {
-    // Only company members can access this
-    if auth_user.user_type != "company_member" {
-        return Err(AppError::ForbiddenError(
-            "Only company members can access this endpoint".to_string(),
-        ));
-    }
+    let company_id =
+        authz::require_company(&state.db, &auth_user, Capability::ViewCompanyJobs).await?;
 
     // Verify job belongs to user's company
-    let _job = sqlx::query!(
+    let job = sqlx::query!(
         r#"
-        SELECT j.id, j.company_id
-        FROM jobs j
-        JOIN company_members cm ON j.company_id = cm.company_id
-        WHERE j.id = $1 AND cm.user_id = $2
+        SELECT id, company_id
+        FROM jobs
+        WHERE id = $1 AND company_id = $2
         "#,
         job_id,
-        auth_user.id
+        company_id
     )
     .fetch_optional(&state.db)
     .await?
@@ -409,6 +716,7 @@ pub async fn get_recommended_candidates(
     let offset = query.offset.unwrap_or(0);
     let min_score = query.min_score.unwrap_or(0);
     let include_applied_only = query.include_applied_only.unwrap_or(false);
+    let earlier_postings = job_reposts::predecessors(&state.db, job_id).await?;
 
     // Get job seekers with visible profiles
     let candidates = sqlx::query!(
@@ -430,6 +738,9 @@ pub async fn get_recommended_candidates(
             p.profile_image_url,
             p.cv_url,
             p.completeness_percentage,
+            p.availability as "availability: SeekerAvailability",
+            CASE WHEN p.share_available_from THEN p.available_from END as available_from,
+            p.share_available_from,
             p.created_at as profile_created_at,
             p.updated_at as profile_updated_at,
             COALESCE(pref.profile_visibility::text, 'visible') as "visibility!",
@@ -437,13 +748,19 @@ pub async fn get_recommended_candidates(
             EXISTS(
                 SELECT 1 FROM job_applications ja
                 WHERE ja.job_id = $1 AND ja.applicant_id = u.id
-            ) as "has_applied!"
+            ) as "has_applied!",
+            EXISTS(
+                SELECT 1 FROM job_applications ja
+                WHERE ja.job_id = ANY($3) AND ja.applicant_id = u.id AND ja.status = 'rejected'
+            ) as "rejected_before!"
         FROM users u
         JOIN job_seeker_profiles p ON u.id = p.user_id
         LEFT JOIN job_seeker_preferences pref ON u.id = pref.user_id
+        JOIN jobs j ON j.id = $1
         WHERE u.user_type = 'job_seeker'
           AND u.account_status = 'active'
           AND p.completeness_percentage >= 50
+          AND p.availability <> 'not_available'
           AND (
               COALESCE(pref.profile_visibility::text, 'visible') = 'visible'
               OR (
@@ -454,10 +771,24 @@ pub async fn get_recommended_candidates(
                   )
               )
           )
-        ORDER BY p.completeness_percentage DESC
+          -- Expectations the seeker keeps to themselves are never matched on
+          AND (
+              $2::salary_fit IS NULL
+              OR (
+                  COALESCE(pref.show_salary_expectation, false)
+                  AND salary_expectation_fit(
+                      j.salary_min, j.salary_max, j.salary_currency, j.salary_period,
+                      pref.salary_expectation_min, pref.salary_expectation_max,
+                      COALESCE(pref.salary_currency, 'CLP')
+                  ) = $2
+              )
+          )
+        ORDER BY p.completeness_percentage DESC, u.id
         LIMIT 500
         "#,
-        job_id
+        job_id,
+        query.salary_fit as Option<SalaryFit>,
+        &earlier_postings,
     )
     .fetch_all(&state.db)
     .await?;
@@ -511,13 +842,16 @@ pub async fn get_recommended_candidates(
             profile_image_url: candidate.profile_image_url,
             cv_url: candidate.cv_url,
             completeness_percentage: candidate.completeness_percentage,
+            availability: candidate.availability,
+            available_from: candidate.available_from,
+            share_available_from: candidate.share_available_from,
             created_at: candidate.profile_created_at,
             updated_at: candidate.profile_updated_at,
         };
 
         // Only show email if candidate has applied
         let user_email = if candidate.has_applied {
-            Some(candidate.user_email)
+            candidate.user_email
         } else {
             None
         };
@@ -529,30 +863,65 @@ pub async fn get_recommended_candidates(
             match_score: score_breakdown.total_score,
             score_breakdown,
             has_applied: candidate.has_applied,
+            rejected_before: candidate.rejected_before,
+            skills: Vec::new(),
         });
     }
 
-    // Sort by match score descending, then by whether they've applied
+    // Applicants first, then by match score descending, with seekers actively
+    // looking nudged up and those rejected for an earlier posting nudged
+    // down; ties go to the most recently updated profile, then the lowest
+    // user ID
+    let rank = |candidate: &RecommendedCandidate| {
+        let boost = match candidate.profile.availability {
+            SeekerAvailability::ActivelyLooking => ACTIVELY_LOOKING_BOOST,
+            _ => 0,
+        };
+        let penalty = if candidate.rejected_before {
+            EARLIER_REJECTION_PENALTY
+        } else {
+            0
+        };
+        candidate.match_score + boost - penalty
+    };
     recommended_candidates.sort_by(|a, b| {
-        match b.has_applied.cmp(&a.has_applied) {
-            std::cmp::Ordering::Equal => b.match_score.cmp(&a.match_score),
-            other => other,
-        }
+        b.has_applied
+            .cmp(&a.has_applied)
+            .then_with(|| rank(b).cmp(&rank(a)))
+            .then_with(|| b.profile.updated_at.cmp(&a.profile.updated_at))
+            .then_with(|| a.profile.user_id.cmp(&b.profile.user_id))
     });
 
     let total_count = recommended_candidates.len() as i64;
     let has_more = (offset + limit) < total_count;
 
     // Apply pagination
-    let candidates: Vec<RecommendedCandidate> = recommended_candidates
+    let mut candidates: Vec<RecommendedCandidate> = recommended_candidates
         .into_iter()
         .skip(offset as usize)
         .take(limit as usize)
         .collect();
 
+    // Skills, with endorsement status, for the returned page only
+    let user_ids: Vec<Uuid> = candidates.iter().map(|c| c.profile.user_id).collect();
+    let mut skills = fetch_user_skills(&state.db, &user_ids).await?;
+    for candidate in &mut candidates {
+        candidate.skills = skills.remove(&candidate.profile.user_id).unwrap_or_default();
+    }
+
+    metering::record(
+        &state.db,
+        job.company_id,
+        UsageEventType::CandidateSearch,
+        1,
+        serde_json::json!({ "job_id": job_id }),
+    )
+    .await;
+
     Ok(Json(RecommendedCandidatesResponse {
         candidates,
         total_count,
         has_more,
+        ordering: RECOMMENDED_CANDIDATES_ORDERING.to_string(),
     }))
 }
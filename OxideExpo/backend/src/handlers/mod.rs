@@ -32,3 +32,27 @@ pub mod invitations;
 pub mod applicants;
 pub mod saved_jobs;
 pub mod files;
+
+// V13 Handlers: Personal API tokens
+pub mod api_tokens;
+
+// V13 Handlers: Public platform statistics
+pub mod stats;
+
+// V13 Handlers: Job syndication feeds
+pub mod feeds;
+
+// V13 Handlers: OMIL directory and company referral requests
+pub mod referrals;
+
+// V13 Handlers: Job seeker onboarding checklist
+pub mod onboarding;
+
+// V13 Handlers: Recently viewed jobs and recent searches
+pub mod browsing;
+
+// V13 Handlers: Notification preferences and unsubscribe links
+pub mod notifications;
+
+// V13 Handlers: Interview calendar feeds
+pub mod calendar;
@@ -0,0 +1,60 @@
+use axum::{extract::State, Extension, Json};
+
+use crate::{
+    error::Result,
+    extract::Query,
+    middleware::AuthUser,
+    models::notification::{
+        NotificationPreference, UnsubscribeQuery, UnsubscribeResponse,
+        UpdateNotificationPreferencesRequest,
+    },
+    services::notifications,
+    AppState,
+};
+
+// ============================================================================
+// PREFERENCES
+// ============================================================================
+
+/// GET /api/me/notification-preferences
+/// Email and in-app switches for every event the user can be notified of,
+/// including the ones they never changed
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<Vec<NotificationPreference>>> {
+    let preferences = notifications::preferences(&state.db, auth_user.id).await?;
+    Ok(Json(preferences))
+}
+
+/// PUT /api/me/notification-preferences
+/// Change some of the switches; the rest stay as they are
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<Vec<NotificationPreference>>> {
+    let preferences =
+        notifications::update(&state.db, auth_user.id, &payload.preferences).await?;
+    Ok(Json(preferences))
+}
+
+// ============================================================================
+// UNSUBSCRIBE (PUBLIC)
+// ============================================================================
+
+/// POST /api/notifications/unsubscribe?token=
+/// One-click unsubscribe from the emails of one event. The signed token in
+/// the email identifies the user and the event, so no session is needed.
+pub async fn unsubscribe(
+    State(state): State<AppState>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> Result<Json<UnsubscribeResponse>> {
+    let event = notifications::unsubscribe(&state.db, &state.config, &query.token).await?;
+
+    Ok(Json(UnsubscribeResponse {
+        event,
+        title: event.title().to_string(),
+        email: false,
+    }))
+}
@@ -1,31 +1,66 @@
 use axum::{
     body::Body,
-    extract::{Path, Query, State},
-    http::header,
-    response::Response,
+    extract::{Multipart, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
-use chrono::Utc;
+use chrono::{Datelike, Duration, TimeZone, Utc};
 use rust_xlsxwriter::{Format, Workbook};
+use sqlx::PgConnection;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::error::AppError;
+use crate::extract::{Path, Query};
+use crate::handlers::profile::{ensure_national_id_available, fetch_user_skills};
+use crate::middleware::auth::{blacklist_token, AuthUser};
 use crate::middleware::omil_auth::OmilContext;
-use crate::models::application::ApplicationStatus;
+use crate::models::admin::{AggregateType, PaginatedResponse};
+use crate::models::application::{
+    ApplicationDocument, ApplicationStatus, CoverLetterTemplate, RejectionReasonCode,
+};
 use crate::models::company::OrganizationStatus;
+use crate::models::export::{
+    ExportAuditEntry, ExportAuditQuery, ExportSettings, ExportType, UpdateExportSettingsRequest,
+};
+use crate::models::notification::NotificationEvent;
 use crate::models::omil::{
-    AddOmilMemberRequest, ApplyOnBehalfRequest, CreateFollowupRequest,
-    ExportManagedSeekersQuery, FollowupType, FollowupWithCreator, FollowupsQuery,
-    ImpersonationResponse, JobSeekerFollowup, ManagedJobSeekerDetail, ManagedJobSeekerSummary,
-    ManagedJobSeekersQuery, OmilApplicationWithDetails, OmilApplicationsQuery,
-    OmilApplicationsResponse, OmilDashboardStats, OmilManagedJobSeeker, OmilMember,
-    OmilMemberWithUser, OmilOrganization, OmilOrganizationWithMembers, OmilRole, PlacementOutcome,
-    RegisterJobSeekerOnBehalfRequest, UpdateFollowupRequest, UpdateOmilMemberRequest,
-    UpdateOmilOrganizationRequest, UpdatePlacementRequest,
+    AcceptOmilTransferResponse, AddOmilMemberRequest, AddOmilMemberResponse, ApplyOnBehalfRequest,
+    ApprovalSettings, AssignBranchRequest, CreateOmilBranchRequest, OmilBranch,
+    UpdateOmilBranchRequest, branch_in_use_message, ApprovePendingActionResponse, ClaimInviteRequest,
+    CompanyInsightsResponse,
+    ClaimInviteResponse, CreateExportTemplateRequest, CreateKioskSessionRequest, CreateFollowupRequest, CreateOmilTransferRequest,
+    DeactivateManagedJobSeekerRequest, EndorseSkillRequest, ManagedProfileReference, EndorserType, ExportColumnInfo, ExportManagedSeekersQuery, FollowupType,
+    FollowupWithCreator, FollowupsQuery, ImpersonationResponse, JobSeekerFollowup,
+    KioskSessionResponse, OmilKioskSession,
+    ManagedJobSeekerDetail, ManagedJobSeekerSummary, ManagedJobSeekersQuery, MonthlyReportQuery,
+    OmilApplicationWithDetails, OmilApplicationsQuery, OmilApplicationsResponse,
+    OmilDashboardStats, OmilExportTemplate, OmilStatsTrends, OmilStatsTrendsQuery, OmilManagedJobSeeker, OmilMember, OmilMemberWithUser,
+    OmilMonthlyReport,
+    OmilActionType, OmilOrganization, OmilOrganizationWithMembers, OmilRole, OmilTransfer,
+    OmilTransferStatus, OmilTransferWithDetails, OmilTransfersQuery, PendingActionStatus,
+    PendingOmilAction, PendingOmilActionWithDetails, PendingOmilActionsQuery, PlacementOutcome,
+    RegisterJobSeekerOnBehalfRequest, RejectPendingActionRequest, SkillEndorsement, UpdateExportTemplateRequest,
+    UpdateApprovalSettingsRequest, UpdateFollowupRequest, UpdateOmilMemberRequest, UpdateOmilOrganizationRequest,
+    UpdatePlacementRequest, DEFAULT_TREND_MONTHS, MAX_TREND_MONTHS, OMIL_EXPORT_COLUMNS,
+};
+use crate::models::permissions::Capability;
+use crate::models::profile::{
+    Gender, JobSeekerProfile, MaritalStatus, ProfileReference, SeekerAvailability,
+};
+use crate::models::user::AccountStatus;
+use crate::services::{
+    application_documents, applications, automation, company_insights, counters, cover_letters,
+    events,
+    exports::{self, Export, ExportOwner},
+    memberships, notifications, omil_export,
+    omil_monthly_report, omil_stats, profile_fingerprint, profile_references,
 };
-use crate::models::profile::{Gender, JobSeekerProfile, MaritalStatus};
-use crate::utils::jwt::create_impersonation_token;
+use crate::utils::jwt::{
+    create_impersonation_token, create_kiosk_token, create_refresh_token, hash_token,
+};
+use crate::utils::normalize::Normalize;
 use crate::AppState;
 
 // ============================================================================
@@ -46,6 +81,7 @@ pub async fn get_omil_organization(
             m.omil_id,
             m.user_id,
             m.role as "role: OmilRole",
+            m.branch_id,
             m.is_active,
             m.joined_at,
             m.left_at,
@@ -71,6 +107,7 @@ pub async fn get_omil_organization(
                 omil_id: row.omil_id,
                 user_id: row.user_id,
                 role: row.role,
+                branch_id: row.branch_id,
                 is_active: row.is_active,
                 joined_at: row.joined_at,
                 left_at: row.left_at,
@@ -149,92 +186,117 @@ pub async fn get_omil_stats(
 ) -> Result<Json<OmilDashboardStats>, AppError> {
     let omil_id = omil_ctx.organization.id;
 
-    // Total managed seekers
-    let total_managed_seekers: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1",
-        omil_id
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    // Seekers transferred out count at the receiving OMIL, except for
+    // placements, which stay with the OMIL that made them
+    let (seekers, total_applications_submitted, interventions_this_month) = tokio::try_join!(
+        sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE transferred_at IS NULL) AS "total_managed_seekers!",
+                COUNT(*) FILTER (WHERE is_active) AS "active_seekers!",
+                COUNT(*) FILTER (
+                    WHERE placement_outcome = 'placed'
+                      AND placed_at >= DATE_TRUNC('month', CURRENT_DATE)
+                ) AS "placed_this_month!",
+                COUNT(*) FILTER (
+                    WHERE placement_outcome = 'placed'
+                      AND placed_at >= DATE_TRUNC('year', CURRENT_DATE)
+                ) AS "placed_this_year!",
+                COUNT(*) FILTER (
+                    WHERE placement_outcome = 'pending' AND transferred_at IS NULL
+                ) AS "pending_placements!",
+                COUNT(*) FILTER (
+                    WHERE transferred_at IS NULL
+                      AND registered_at >= DATE_TRUNC('month', CURRENT_DATE)
+                ) AS "new_registrations_this_month!"
+            FROM omil_managed_job_seekers
+            WHERE omil_id = $1
+            "#,
+            omil_id
+        )
+        .fetch_one(&state.db),
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM omil_applications WHERE omil_id = $1"#,
+            omil_id
+        )
+        .fetch_one(&state.db),
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM job_seeker_followups f
+            JOIN followup_types t ON t.code = f.followup_type
+            WHERE f.omil_id = $1
+              AND t.counts_as_intervention
+              AND f.followup_date >= DATE_TRUNC('month', CURRENT_DATE)
+            "#,
+            omil_id
+        )
+        .fetch_one(&state.db),
+    )?;
 
-    // Active seekers
-    let active_seekers: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND is_active = true",
-        omil_id
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    let now = Utc::now();
+    let year_start = Utc
+        .with_ymd_and_hms(now.year(), 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now);
+    let branches = omil_stats::branch_breakdown(&state.db, omil_id, year_start, now).await?;
 
-    // Placed this month
-    let placed_this_month: i64 = sqlx::query_scalar!(
-        r#"
-        SELECT COUNT(*) FROM omil_managed_job_seekers
-        WHERE omil_id = $1
-        AND placement_outcome = 'placed'
-        AND placed_at >= DATE_TRUNC('month', CURRENT_DATE)
-        "#,
-        omil_id
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    Ok(Json(OmilDashboardStats {
+        total_managed_seekers: seekers.total_managed_seekers,
+        active_seekers: seekers.active_seekers,
+        placed_this_month: seekers.placed_this_month,
+        placed_this_year: seekers.placed_this_year,
+        pending_placements: seekers.pending_placements,
+        new_registrations_this_month: seekers.new_registrations_this_month,
+        total_applications_submitted,
+        interventions_this_month,
+        branches,
+    }))
+}
 
-    // Placed this year
-    let placed_this_year: i64 = sqlx::query_scalar!(
-        r#"
-        SELECT COUNT(*) FROM omil_managed_job_seekers
-        WHERE omil_id = $1
-        AND placement_outcome = 'placed'
-        AND placed_at >= DATE_TRUNC('year', CURRENT_DATE)
-        "#,
-        omil_id
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+/// GET /api/me/omil/stats/trends
+/// Monthly registrations, placements and applications submitted over the
+/// last `months` months (12 by default), cached for 10 minutes
+pub async fn get_omil_stats_trends(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<OmilStatsTrendsQuery>,
+) -> Result<Json<OmilStatsTrends>, AppError> {
+    let months = query.months.unwrap_or(DEFAULT_TREND_MONTHS);
+    if !(1..=MAX_TREND_MONTHS).contains(&months) {
+        return Err(AppError::ValidationError(format!(
+            "months must be between 1 and {}",
+            MAX_TREND_MONTHS
+        )));
+    }
 
-    // Pending placements
-    let pending_placements: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND placement_outcome = 'pending'",
-        omil_id
+    let trends = omil_stats::trends(
+        &state.db,
+        &mut state.redis.clone(),
+        omil_ctx.organization.id,
+        months,
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    // New registrations this month
-    let new_registrations_this_month: i64 = sqlx::query_scalar!(
-        r#"
-        SELECT COUNT(*) FROM omil_managed_job_seekers
-        WHERE omil_id = $1
-        AND registered_at >= DATE_TRUNC('month', CURRENT_DATE)
-        "#,
-        omil_id
-    )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    Ok(Json(trends))
+}
 
-    // Total applications submitted by OMIL
-    let total_applications_submitted: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM omil_applications WHERE omil_id = $1",
-        omil_id
+/// GET /api/me/omil/companies/insights
+/// Hiring signals of the active companies in the OMIL's region, to focus
+/// outreach on those that hire people with disabilities
+pub async fn get_company_insights(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<CompanyInsightsResponse>, AppError> {
+    let insights = company_insights::insights(
+        &state.db,
+        &mut state.redis.clone(),
+        omil_ctx.organization.id,
+        Utc::now(),
     )
-    .fetch_one(&state.db)
-    .await?
-    .unwrap_or(0);
+    .await?;
 
-    Ok(Json(OmilDashboardStats {
-        total_managed_seekers,
-        active_seekers,
-        placed_this_month,
-        placed_this_year,
-        pending_placements,
-        new_registrations_this_month,
-        total_applications_submitted,
-    }))
+    Ok(Json(insights))
 }
 
 // ============================================================================
@@ -254,6 +316,7 @@ pub async fn list_omil_members(
             m.omil_id,
             m.user_id,
             m.role as "role: OmilRole",
+            m.branch_id,
             m.is_active,
             m.joined_at,
             m.left_at,
@@ -279,6 +342,7 @@ pub async fn list_omil_members(
                 omil_id: row.omil_id,
                 user_id: row.user_id,
                 role: row.role,
+                branch_id: row.branch_id,
                 is_active: row.is_active,
                 joined_at: row.joined_at,
                 left_at: row.left_at,
@@ -299,12 +363,12 @@ pub async fn add_omil_member(
     State(state): State<AppState>,
     Extension(omil_ctx): Extension<OmilContext>,
     Json(payload): Json<AddOmilMemberRequest>,
-) -> Result<Json<OmilMemberWithUser>, AppError> {
+) -> Result<Json<AddOmilMemberResponse>, AppError> {
     payload.validate()?;
 
     // Find user by email
     let user = sqlx::query!(
-        "SELECT id, first_name, last_name, email FROM users WHERE email = $1",
+        r#"SELECT id, first_name, last_name, email as "email!" FROM users WHERE email = $1"#,
         payload.email
     )
     .fetch_optional(&state.db)
@@ -328,7 +392,7 @@ pub async fn add_omil_member(
 
     // Only directors can add coordinators/directors
     if (payload.role == OmilRole::Coordinator || payload.role == OmilRole::Director)
-        && omil_ctx.member.role != OmilRole::Director
+        && !omil_ctx.can(Capability::AdministerOmil)
     {
         return Err(AppError::ForbiddenError(
             "Only directors can add coordinators or directors".to_string(),
@@ -346,6 +410,7 @@ pub async fn add_omil_member(
             omil_id,
             user_id,
             role as "role: OmilRole",
+            branch_id,
             is_active,
             joined_at,
             left_at,
@@ -359,12 +424,24 @@ pub async fn add_omil_member(
     .fetch_one(&state.db)
     .await?;
 
+    let warnings = memberships::omil_membership_warning(
+        &state.db,
+        user.id,
+        omil_ctx.organization.id,
+    )
+    .await?
+    .into_iter()
+    .collect();
+
     let user_name = format!("{} {}", user.first_name, user.last_name);
 
-    Ok(Json(OmilMemberWithUser {
-        member,
-        user_name,
-        user_email: user.email,
+    Ok(Json(AddOmilMemberResponse {
+        member: OmilMemberWithUser {
+            member,
+            user_name,
+            user_email: user.email,
+        },
+        warnings,
     }))
 }
 
@@ -392,7 +469,7 @@ pub async fn update_omil_member(
             (*new_role == OmilRole::Coordinator || *new_role == OmilRole::Director)
                 || (existing.role == OmilRole::Coordinator || existing.role == OmilRole::Director);
 
-        if role_change_requires_director && omil_ctx.member.role != OmilRole::Director {
+        if role_change_requires_director && !omil_ctx.can(Capability::AdministerOmil) {
             return Err(AppError::ForbiddenError(
                 "Only directors can change coordinator/director roles".to_string(),
             ));
@@ -414,6 +491,7 @@ pub async fn update_omil_member(
             omil_id,
             user_id,
             role as "role: OmilRole",
+            branch_id,
             is_active,
             joined_at,
             left_at,
@@ -480,9 +558,11 @@ pub async fn list_managed_job_seekers(
             mjs.id,
             mjs.job_seeker_id,
             (u.first_name || ' ' || u.last_name) as "user_name!",
-            u.email as "user_email!",
+            u.email as user_email,
             mjs.placement_outcome as "placement_outcome: PlacementOutcome",
             (SELECT (first_name || ' ' || last_name) FROM users WHERE id = mjs.assigned_advisor_id) as assigned_advisor_name,
+            mjs.branch_id,
+            b.name as "branch_name?",
             (SELECT COUNT(*) FROM job_seeker_followups f WHERE f.job_seeker_id = mjs.job_seeker_id AND f.omil_id = mjs.omil_id) as "followups_count!",
             (SELECT COUNT(*) FROM job_applications ja WHERE ja.applicant_id = mjs.job_seeker_id) as "applications_count!",
             mjs.registered_at,
@@ -490,9 +570,11 @@ pub async fn list_managed_job_seekers(
         FROM omil_managed_job_seekers mjs
         JOIN users u ON u.id = mjs.job_seeker_id
         LEFT JOIN job_seeker_profiles p ON p.user_id = mjs.job_seeker_id
+        LEFT JOIN omil_branches b ON b.id = mjs.branch_id
         WHERE mjs.omil_id = $1
         AND ($2::placement_outcome IS NULL OR mjs.placement_outcome = $2)
         AND ($3::boolean IS NULL OR $3 = false OR mjs.assigned_advisor_id = $4)
+        AND ($8::uuid IS NULL OR mjs.branch_id = $8)
         AND (
             $5::text IS NULL
             OR u.first_name ILIKE '%' || $5 || '%'
@@ -509,7 +591,8 @@ pub async fn list_managed_job_seekers(
         omil_ctx.member.user_id,
         query.search,
         limit,
-        offset
+        offset,
+        query.branch_id
     )
     .fetch_all(&state.db)
     .await?;
@@ -523,6 +606,8 @@ pub async fn list_managed_job_seekers(
             user_email: row.user_email,
             placement_outcome: row.placement_outcome,
             assigned_advisor_name: row.assigned_advisor_name,
+            branch_id: row.branch_id,
+            branch_name: row.branch_name,
             followups_count: row.followups_count,
             applications_count: row.applications_count,
             registered_at: row.registered_at,
@@ -537,25 +622,45 @@ pub async fn list_managed_job_seekers(
 /// Register job seeker on behalf (any OMIL member)
 pub async fn register_job_seeker_on_behalf(
     State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
     Extension(omil_ctx): Extension<OmilContext>,
-    Json(payload): Json<RegisterJobSeekerOnBehalfRequest>,
+    Json(mut payload): Json<RegisterJobSeekerOnBehalfRequest>,
 ) -> Result<Json<OmilManagedJobSeeker>, AppError> {
+    payload.normalize();
     payload.validate()?;
 
-    // Check if user already exists
-    let existing_user = sqlx::query!(
-        "SELECT id FROM users WHERE email = $1",
-        payload.email
-    )
-    .fetch_optional(&state.db)
-    .await?;
+    let national_id = payload.national_id.as_deref().filter(|n| !n.is_empty());
+
+    // Check if user already exists. Without an email, the national ID is
+    // what identifies someone registered before.
+    let existing_user_id = match (&payload.email, national_id) {
+        (Some(email), _) => {
+            sqlx::query_scalar!("SELECT id FROM users WHERE email = $1", email)
+                .fetch_optional(&state.db)
+                .await?
+        }
+        (None, Some(national_id)) => {
+            sqlx::query_scalar!(
+                "SELECT user_id FROM job_seeker_profiles WHERE national_id = $1",
+                national_id
+            )
+            .fetch_optional(&state.db)
+            .await?
+        }
+        (None, None) => None,
+    };
+
+    // Refuse to register a second account for a national ID we already know
+    if let Some(national_id) = national_id {
+        ensure_national_id_available(&state.db, national_id, existing_user_id).await?;
+    }
 
-    let job_seeker_id = if let Some(user) = existing_user {
+    let job_seeker_id = if let Some(user_id) = existing_user_id {
         // Check if already managed by this OMIL
         let already_managed = sqlx::query_scalar!(
             "SELECT id FROM omil_managed_job_seekers WHERE omil_id = $1 AND job_seeker_id = $2",
             omil_ctx.organization.id,
-            user.id
+            user_id
         )
         .fetch_optional(&state.db)
         .await?;
@@ -566,27 +671,54 @@ pub async fn register_job_seeker_on_behalf(
             ));
         }
 
-        user.id
+        // Fill in the national ID if the existing profile doesn't have one yet
+        if let Some(national_id) = national_id {
+            sqlx::query!(
+                r#"
+                INSERT INTO job_seeker_profiles (user_id, national_id)
+                VALUES ($1, $2)
+                ON CONFLICT (user_id) DO UPDATE
+                SET national_id = COALESCE(job_seeker_profiles.national_id, EXCLUDED.national_id)
+                "#,
+                user_id,
+                national_id
+            )
+            .execute(&state.db)
+            .await?;
+        }
+
+        user_id
     } else {
+        // Nobody has a password yet. Beneficiaries with an email are invited
+        // to set one; those without can't log in, so the OMIL manages them
+        // offline until they claim the account
+        let account_status = if payload.email.is_some() {
+            AccountStatus::Invited
+        } else {
+            AccountStatus::ManagedOffline
+        };
+
         // Create new user with job_seeker type (phone is stored in profile, not user)
         let new_user = sqlx::query!(
             r#"
-            INSERT INTO users (email, first_name, last_name, user_type, password_hash)
-            VALUES ($1, $2, $3, 'job_seeker', '')
+            INSERT INTO users (email, first_name, last_name, user_type, password_hash, account_status)
+            VALUES ($1, $2, $3, 'job_seeker', NULL, $4)
             RETURNING id
             "#,
             payload.email,
             payload.first_name,
-            payload.last_name
+            payload.last_name,
+            account_status as AccountStatus
         )
         .fetch_one(&state.db)
         .await?;
 
-        // Create profile for the job seeker with phone if provided
+        // Create profile for the job seeker with phone and national ID if provided
         sqlx::query!(
-            "INSERT INTO job_seeker_profiles (user_id, phone) VALUES ($1, $2)",
+            "INSERT INTO job_seeker_profiles (user_id, phone, national_id) VALUES ($1, $2, $3)",
             new_user.id,
-            payload.phone
+            payload.phone,
+            national_id
         )
         .execute(&state.db)
         .await?;
@@ -601,17 +733,19 @@ pub async fn register_job_seeker_on_behalf(
         None
     };
 
-    // Create managed job seeker record
+    // Create managed job seeker record, attributed to the kiosk it came through
     let managed = sqlx::query_as!(
         OmilManagedJobSeeker,
         r#"
-        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by, assigned_advisor_id, notes)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO omil_managed_job_seekers
+            (omil_id, job_seeker_id, registered_by, assigned_advisor_id, notes, kiosk_session_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
         RETURNING
             id,
             omil_id,
             job_seeker_id,
             assigned_advisor_id,
+            branch_id,
             registered_by,
             placement_outcome as "placement_outcome: PlacementOutcome",
             placed_at,
@@ -625,7 +759,8 @@ pub async fn register_job_seeker_on_behalf(
         job_seeker_id,
         omil_ctx.member.user_id,
         assigned_advisor_id,
-        payload.notes
+        payload.notes,
+        auth_user.kiosk.as_ref().map(|k| k.session_id)
     )
     .fetch_one(&state.db)
     .await?;
@@ -644,9 +779,232 @@ pub async fn register_job_seeker_on_behalf(
     .execute(&state.db)
     .await?;
 
+    let redactor = state.config.redactor();
+    tracing::info!(
+        "OMIL {} registered job seeker {} (email: {}, RUT: {})",
+        omil_ctx.organization.id,
+        job_seeker_id,
+        payload
+            .email
+            .as_deref()
+            .map(|email| redactor.email(email))
+            .unwrap_or_else(|| "none".to_string()),
+        national_id
+            .map(|rut| redactor.rut(rut))
+            .unwrap_or_else(|| "none".to_string())
+    );
+
     Ok(Json(managed))
 }
 
+/// How long an invitation to claim an offline account stays valid
+const CLAIM_INVITE_VALID_DAYS: i64 = 14;
+
+/// POST /api/me/omil/job-seekers/{id}/claim-invite
+/// Invite a job seeker managed offline to take over their account at an
+/// email they now have. Replaces any invitation still open.
+pub async fn send_claim_invite(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+    Json(mut payload): Json<ClaimInviteRequest>,
+) -> Result<Json<ClaimInviteResponse>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let seeker = sqlx::query!(
+        r#"
+        SELECT mjs.job_seeker_id, u.first_name,
+               u.account_status as "account_status: AccountStatus"
+        FROM omil_managed_job_seekers mjs
+        JOIN users u ON u.id = mjs.job_seeker_id
+        WHERE mjs.id = $1 AND mjs.omil_id = $2 AND mjs.is_active = true
+        "#,
+        managed_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    if seeker.account_status != AccountStatus::ManagedOffline {
+        return Err(AppError::ValidationError(
+            "Job seeker already has an account of their own".to_string(),
+        ));
+    }
+
+    let email_taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE email = $1) as "taken!""#,
+        payload.email
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if email_taken {
+        return Err(AppError::ConflictError("Email already registered".to_string()));
+    }
+
+    let token = create_refresh_token(); // Reuse secure token generation
+    let expires_at = Utc::now() + Duration::days(CLAIM_INVITE_VALID_DAYS);
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM account_claim_invitations WHERE user_id = $1 AND claimed_at IS NULL",
+        seeker.job_seeker_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO account_claim_invitations
+            (user_id, omil_id, invited_by, email, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        seeker.job_seeker_id,
+        omil_ctx.organization.id,
+        omil_ctx.member.user_id,
+        payload.email,
+        hash_token(&token),
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, title, content)
+        VALUES ($1, $2, $3, 'general_note', 'Invitación a activar cuenta', $4)
+        "#,
+        seeker.job_seeker_id,
+        omil_ctx.member.user_id,
+        omil_ctx.organization.id,
+        format!("Invitación para activar la cuenta enviada a {}", payload.email)
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let email_service = state.email.clone();
+    let email = payload.email.clone();
+    let name = seeker.first_name;
+    let omil_name = omil_ctx.organization.organization_name.clone();
+    tokio::spawn(async move {
+        if let Err(e) = email_service
+            .send_account_claim_invitation_email(
+                &email,
+                &name,
+                &omil_name,
+                &token,
+                CLAIM_INVITE_VALID_DAYS,
+            )
+            .await
+        {
+            tracing::error!("Failed to send account claim invitation: {}", e);
+        }
+    });
+
+    Ok(Json(ClaimInviteResponse {
+        email: payload.email,
+        expires_at,
+    }))
+}
+
+/// How long a link to set a first password stays valid
+const PASSWORD_SETUP_VALID_DAYS: i64 = 7;
+
+/// POST /api/me/omil/job-seekers/{id}/send-credentials-invite
+/// Email a job seeker registered with an email a one-time link to set their
+/// first password. Replaces any link still open.
+pub async fn send_credentials_invite(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+) -> Result<Json<ClaimInviteResponse>, AppError> {
+    let seeker = sqlx::query!(
+        r#"
+        SELECT mjs.job_seeker_id, u.email, u.first_name,
+               u.password_hash IS NOT NULL as "has_password!"
+        FROM omil_managed_job_seekers mjs
+        JOIN users u ON u.id = mjs.job_seeker_id
+        WHERE mjs.id = $1 AND mjs.omil_id = $2 AND mjs.is_active = true
+        "#,
+        managed_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    if seeker.has_password {
+        return Err(AppError::ValidationError(
+            "Job seeker already has a password".to_string(),
+        ));
+    }
+    let email = seeker.email.ok_or_else(|| {
+        AppError::ValidationError(
+            "Job seeker has no email; send a claim invite instead".to_string(),
+        )
+    })?;
+
+    let token = create_refresh_token(); // Reuse secure token generation
+    let expires_at = Utc::now() + Duration::days(PASSWORD_SETUP_VALID_DAYS);
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM password_setup_tokens WHERE user_id = $1 AND used_at IS NULL",
+        seeker.job_seeker_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO password_setup_tokens (user_id, omil_id, invited_by, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        seeker.job_seeker_id,
+        omil_ctx.organization.id,
+        omil_ctx.member.user_id,
+        hash_token(&token),
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, title, content)
+        VALUES ($1, $2, $3, 'general_note', 'Invitación a crear contraseña', $4)
+        "#,
+        seeker.job_seeker_id,
+        omil_ctx.member.user_id,
+        omil_ctx.organization.id,
+        format!("Enlace para crear la contraseña enviado a {}", email)
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let email_service = state.email.clone();
+    let to = email.clone();
+    let name = seeker.first_name;
+    let omil_name = omil_ctx.organization.organization_name.clone();
+    tokio::spawn(async move {
+        if let Err(e) = email_service
+            .send_password_setup_email(&to, &name, &omil_name, &token, PASSWORD_SETUP_VALID_DAYS)
+            .await
+        {
+            tracing::error!("Failed to send password setup email: {}", e);
+        }
+    });
+
+    Ok(Json(ClaimInviteResponse { email, expires_at }))
+}
+
 /// GET /api/me/omil/job-seekers/{id}
 /// Get managed job seeker detail
 pub async fn get_managed_job_seeker(
@@ -663,6 +1021,7 @@ pub async fn get_managed_job_seeker(
             omil_id,
             job_seeker_id,
             assigned_advisor_id,
+            branch_id,
             registered_by,
             placement_outcome as "placement_outcome: PlacementOutcome",
             placed_at,
@@ -709,6 +1068,9 @@ pub async fn get_managed_job_seeker(
             profile_image_url,
             cv_url,
             completeness_percentage,
+            availability as "availability: SeekerAvailability",
+            available_from,
+            share_available_from,
             created_at,
             updated_at
         FROM job_seeker_profiles
@@ -719,6 +1081,11 @@ pub async fn get_managed_job_seeker(
     .fetch_optional(&state.db)
     .await?;
 
+    let skills = fetch_user_skills(&state.db, &[managed.job_seeker_id])
+        .await?
+        .remove(&managed.job_seeker_id)
+        .unwrap_or_default();
+
     // Fetch recent followups
     let followups = sqlx::query_as!(
         JobSeekerFollowup,
@@ -729,7 +1096,7 @@ pub async fn get_managed_job_seeker(
             created_by,
             omil_id,
             application_id,
-            followup_type as "followup_type: FollowupType",
+            followup_type,
             title,
             content,
             is_private,
@@ -753,25 +1120,61 @@ pub async fn get_managed_job_seeker(
         user_name: format!("{} {}", user.first_name, user.last_name),
         user_email: user.email,
         recent_followups: followups,
+        skills,
     }))
 }
 
 /// PUT /api/me/omil/job-seekers/{id}/placement
-/// Update placement outcome
+/// Update placement outcome. When the organization requires approval, an
+/// advisor's 'placed' is queued instead and answered with 202.
 pub async fn update_placement(
     State(state): State<AppState>,
     Extension(omil_ctx): Extension<OmilContext>,
     Path(managed_id): Path<Uuid>,
     Json(payload): Json<UpdatePlacementRequest>,
-) -> Result<Json<OmilManagedJobSeeker>, AppError> {
+) -> Result<Response, AppError> {
     payload.validate()?;
 
     // Verify exists
-    let _existing = sqlx::query!("SELECT id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2", managed_id, omil_ctx.organization.id)
+    let _existing = sqlx::query!("SELECT id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true", managed_id, omil_ctx.organization.id)
         .fetch_optional(&state.db)
         .await?
         .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
 
+    if payload.outcome == PlacementOutcome::Placed && needs_approval(&state, &omil_ctx).await? {
+        let action = request_approval(
+            &state,
+            &omil_ctx,
+            managed_id,
+            OmilActionType::Placement,
+            serde_json::to_value(&payload).unwrap_or_default(),
+        )
+        .await?;
+        return Ok((StatusCode::ACCEPTED, Json(action)).into_response());
+    }
+
+    let mut tx = state.db.begin().await?;
+    let managed = apply_placement(
+        &mut tx,
+        managed_id,
+        &payload,
+        omil_ctx.member.user_id,
+        format!("Estado actualizado a: {}", payload.outcome.label()),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(Json(managed).into_response())
+}
+
+/// Sets the placement outcome and records a followup by `actor_id`
+async fn apply_placement(
+    conn: &mut PgConnection,
+    managed_id: Uuid,
+    payload: &UpdatePlacementRequest,
+    actor_id: Uuid,
+    followup_content: String,
+) -> Result<OmilManagedJobSeeker, AppError> {
     let placed_at = if payload.outcome == PlacementOutcome::Placed {
         Some(Utc::now())
     } else {
@@ -794,6 +1197,7 @@ pub async fn update_placement(
             omil_id,
             job_seeker_id,
             assigned_advisor_id,
+            branch_id,
             registered_by,
             placement_outcome as "placement_outcome: PlacementOutcome",
             placed_at,
@@ -809,7 +1213,7 @@ pub async fn update_placement(
         payload.notes,
         managed_id
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *conn)
     .await?;
 
     // Create followup for placement update
@@ -819,38 +1223,143 @@ pub async fn update_placement(
         VALUES ($1, $2, $3, 'placement', 'Actualización de colocación', $4)
         "#,
         managed.job_seeker_id,
-        omil_ctx.member.user_id,
-        omil_ctx.organization.id,
-        format!("Estado actualizado a: {:?}", payload.outcome)
+        actor_id,
+        managed.omil_id,
+        followup_content
     )
-    .execute(&state.db)
+    .execute(&mut *conn)
     .await?;
 
-    Ok(Json(managed))
+    events::emit(
+        conn,
+        AggregateType::ManagedJobSeeker,
+        managed.id,
+        "placement_updated",
+        Some(actor_id),
+        serde_json::json!({
+            "outcome": payload.outcome,
+            "job_id": payload.job_id,
+            "notes": payload.notes,
+        }),
+    )
+    .await?;
+
+    Ok(managed)
 }
 
-/// PUT /api/me/omil/job-seekers/{id}/advisor
-/// Assign advisor to job seeker (coordinator+ only)
-pub async fn assign_advisor(
+/// POST /api/me/omil/job-seekers/{id}/deactivate
+/// Deactivate a managed record. When the organization requires approval,
+/// an advisor's deactivation is queued instead and answered with 202.
+pub async fn deactivate_managed_job_seeker(
     State(state): State<AppState>,
     Extension(omil_ctx): Extension<OmilContext>,
     Path(managed_id): Path<Uuid>,
-    Json(payload): Json<serde_json::Value>,
-) -> Result<Json<OmilManagedJobSeeker>, AppError> {
-    let advisor_id: Option<Uuid> = payload.get("advisor_id").and_then(|v| {
-        v.as_str()
-            .and_then(|s| Uuid::parse_str(s).ok())
-    });
+    Json(mut payload): Json<DeactivateManagedJobSeekerRequest>,
+) -> Result<Response, AppError> {
+    payload.normalize();
+    payload.validate()?;
 
-    // If advisor_id provided, verify they're a member of this OMIL
-    if let Some(aid) = advisor_id {
-        let is_member = sqlx::query_scalar!(
-            "SELECT id FROM omil_members WHERE omil_id = $1 AND user_id = $2 AND is_active = true",
-            omil_ctx.organization.id,
-            aid
-        )
-        .fetch_optional(&state.db)
-        .await?;
+    sqlx::query!(
+        "SELECT id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true",
+        managed_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    if needs_approval(&state, &omil_ctx).await? {
+        let action = request_approval(
+            &state,
+            &omil_ctx,
+            managed_id,
+            OmilActionType::Deactivation,
+            serde_json::to_value(&payload).unwrap_or_default(),
+        )
+        .await?;
+        return Ok((StatusCode::ACCEPTED, Json(action)).into_response());
+    }
+
+    let content = payload.reason.clone().unwrap_or_else(|| "Registro desactivado".to_string());
+    let mut tx = state.db.begin().await?;
+    let managed = apply_deactivation(&mut tx, managed_id, omil_ctx.member.user_id, content).await?;
+    tx.commit().await?;
+
+    Ok(Json(managed).into_response())
+}
+
+/// Deactivates the record and records a followup by `actor_id`
+async fn apply_deactivation(
+    conn: &mut PgConnection,
+    managed_id: Uuid,
+    actor_id: Uuid,
+    followup_content: String,
+) -> Result<OmilManagedJobSeeker, AppError> {
+    let managed = sqlx::query_as!(
+        OmilManagedJobSeeker,
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET is_active = false, updated_at = NOW()
+        WHERE id = $1 AND is_active = true
+        RETURNING
+            id,
+            omil_id,
+            job_seeker_id,
+            assigned_advisor_id,
+            branch_id,
+            registered_by,
+            placement_outcome as "placement_outcome: PlacementOutcome",
+            placed_at,
+            placed_job_id,
+            is_active,
+            notes,
+            registered_at,
+            updated_at
+        "#,
+        managed_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("The job seeker is no longer active".to_string()))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, title, content)
+        VALUES ($1, $2, $3, 'general_note', 'Registro desactivado', $4)
+        "#,
+        managed.job_seeker_id,
+        actor_id,
+        managed.omil_id,
+        followup_content
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(managed)
+}
+
+/// PUT /api/me/omil/job-seekers/{id}/advisor
+/// Assign advisor to job seeker (coordinator+ only)
+pub async fn assign_advisor(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+    Json(payload): Json<serde_json::Value>,
+) -> Result<Json<OmilManagedJobSeeker>, AppError> {
+    let advisor_id: Option<Uuid> = payload.get("advisor_id").and_then(|v| {
+        v.as_str()
+            .and_then(|s| Uuid::parse_str(s).ok())
+    });
+
+    // If advisor_id provided, verify they're a member of this OMIL
+    if let Some(aid) = advisor_id {
+        let is_member = sqlx::query_scalar!(
+            "SELECT id FROM omil_members WHERE omil_id = $1 AND user_id = $2 AND is_active = true",
+            omil_ctx.organization.id,
+            aid
+        )
+        .fetch_optional(&state.db)
+        .await?;
 
         if is_member.is_none() {
             return Err(AppError::ValidationError(
@@ -872,6 +1381,7 @@ pub async fn assign_advisor(
             omil_id,
             job_seeker_id,
             assigned_advisor_id,
+            branch_id,
             registered_by,
             placement_outcome as "placement_outcome: PlacementOutcome",
             placed_at,
@@ -892,19 +1402,46 @@ pub async fn assign_advisor(
     Ok(Json(managed))
 }
 
+/// GET /api/me/omil/job-seekers/{id}/cover-letter-templates
+/// Cover letter templates of a managed job seeker, to apply on their behalf
+pub async fn list_managed_cover_letter_templates(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+) -> Result<Json<Vec<CoverLetterTemplate>>, AppError> {
+    let job_seeker_id = sqlx::query_scalar!(
+        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true",
+        managed_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    Ok(Json(
+        cover_letters::list_templates(&state.db, job_seeker_id).await?,
+    ))
+}
+
 /// POST /api/me/omil/job-seekers/{id}/apply
 /// Apply to job on behalf of job seeker
 pub async fn apply_on_behalf(
     State(state): State<AppState>,
     Extension(omil_ctx): Extension<OmilContext>,
     Path(managed_id): Path<Uuid>,
-    Json(payload): Json<ApplyOnBehalfRequest>,
+    Json(mut payload): Json<ApplyOnBehalfRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     payload.validate()?;
 
+    if payload.template_id.is_some() && payload.cover_letter.is_some() {
+        return Err(AppError::ValidationError(
+            "Send either a cover letter or a template, not both".to_string(),
+        ));
+    }
+
     // Get the managed job seeker
     let managed = sqlx::query!(
-        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2",
+        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true",
         managed_id,
         omil_ctx.organization.id
     )
@@ -912,6 +1449,9 @@ pub async fn apply_on_behalf(
     .await?
     .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
 
+    memberships::check_not_own_behalf(omil_ctx.member.user_id, managed.job_seeker_id)?;
+    memberships::check_not_self_dealing(&state.db, managed.job_seeker_id, payload.job_id).await?;
+
     // Verify job exists and is active
     let job = sqlx::query!(
         "SELECT id, status::text as status FROM jobs WHERE id = $1",
@@ -942,58 +1482,195 @@ pub async fn apply_on_behalf(
         ));
     }
 
-    // Create application
-    let application = sqlx::query!(
-        r#"
-        INSERT INTO job_applications (job_id, applicant_id, cover_letter, status)
-        VALUES ($1, $2, $3, 'submitted')
-        RETURNING id
-        "#,
-        payload.job_id,
+    // Counts against the seeker's own cap as well as the organization's
+    let slots = applications::take_daily_slots(
+        &state,
         managed.job_seeker_id,
-        payload.cover_letter
+        Some(omil_ctx.organization.id),
+        Utc::now(),
     )
-    .fetch_one(&state.db)
     .await?;
+    let submitted = async {
+        let mut tx = state.db.begin().await?;
+
+        // The managed seeker's own templates can be used on their behalf
+        let mut warnings = Vec::new();
+        if let Some(template_id) = payload.template_id {
+            let expanded = cover_letters::use_template(
+                &mut tx,
+                managed.job_seeker_id,
+                template_id,
+                payload.job_id,
+            )
+            .await?;
+            warnings = expanded.warnings();
+            payload.cover_letter = Some(expanded.text);
+        }
 
-    // Track in omil_applications
-    sqlx::query!(
+        // Create application
+        let fingerprint = profile_fingerprint::take(&mut tx, managed.job_seeker_id).await?;
+        let application = sqlx::query!(
+            r#"
+            INSERT INTO job_applications (job_id, applicant_id, cover_letter, status, profile_fingerprint)
+            VALUES ($1, $2, $3, 'submitted', $4)
+            RETURNING id
+            "#,
+            payload.job_id,
+            managed.job_seeker_id,
+            payload.cover_letter,
+            fingerprint.to_json()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Track in omil_applications
+        sqlx::query!(
+            r#"
+            INSERT INTO omil_applications (application_id, omil_id, submitted_by, internal_notes)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            application.id,
+            omil_ctx.organization.id,
+            omil_ctx.member.user_id,
+            payload.internal_notes
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Create followup
+        sqlx::query!(
+            r#"
+            INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, application_id, followup_type, title, content)
+            VALUES ($1, $2, $3, $4, 'job_application', 'Postulación enviada', 'Postulación enviada en representación del usuario')
+            "#,
+            managed.job_seeker_id,
+            omil_ctx.member.user_id,
+            omil_ctx.organization.id,
+            application.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        counters::application_added(&mut tx, payload.job_id).await?;
+        events::emit(
+            &mut tx,
+            AggregateType::Application,
+            application.id,
+            "submitted",
+            Some(omil_ctx.member.user_id),
+            serde_json::json!({ "job_id": payload.job_id, "omil_id": omil_ctx.organization.id }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok::<_, AppError>((application.id, warnings))
+    }
+    .await;
+    let (application_id, warnings) = match submitted {
+        Ok(submitted) => submitted,
+        Err(e) => {
+            slots.give_back(&mut state.redis.clone()).await;
+            return Err(e);
+        }
+    };
+
+    // Same as a seeker's own submission: rule failures don't undo the application
+    if let Err(e) = automation::on_application_submitted(&state, application_id).await {
+        tracing::error!(
+            "Automation rules failed for application {}: {}",
+            application_id,
+            e
+        );
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "Application submitted successfully",
+        "application_id": application_id,
+        "warnings": warnings
+    })))
+}
+
+/// POST /api/me/omil/job-seekers/{id}/applications/{app_id}/documents
+/// Attach a document to a managed job seeker's application
+pub async fn attach_document_on_behalf(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path((managed_id, app_id)): Path<(Uuid, Uuid)>,
+    mut multipart: Multipart,
+) -> Result<Json<ApplicationDocument>, AppError> {
+    let status = sqlx::query_scalar!(
         r#"
-        INSERT INTO omil_applications (application_id, omil_id, submitted_by, internal_notes)
-        VALUES ($1, $2, $3, $4)
+        SELECT ja.status as "status: ApplicationStatus"
+        FROM omil_managed_job_seekers mjs
+        JOIN job_applications ja ON ja.applicant_id = mjs.job_seeker_id
+        WHERE mjs.id = $1 AND mjs.omil_id = $2 AND ja.id = $3
         "#,
-        application.id,
+        managed_id,
         omil_ctx.organization.id,
-        omil_ctx.member.user_id,
-        payload.internal_notes
+        app_id
     )
-    .execute(&state.db)
-    .await?;
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
 
-    // Create followup
-    sqlx::query!(
-        r#"
-        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, application_id, followup_type, title, content)
-        VALUES ($1, $2, $3, $4, 'job_application', 'Postulación enviada', 'Postulación enviada en representación del usuario')
-        "#,
-        managed.job_seeker_id,
+    let document = application_documents::attach(
+        &state,
+        app_id,
+        status,
         omil_ctx.member.user_id,
-        omil_ctx.organization.id,
-        application.id
+        &mut multipart,
     )
-    .execute(&state.db)
     .await?;
 
-    Ok(Json(serde_json::json!({
-        "message": "Application submitted successfully",
-        "application_id": application.id
-    })))
+    Ok(Json(document))
 }
 
 // ============================================================================
 // FOLLOWUPS
 // ============================================================================
 
+/// Rejects codes that aren't an active followup type
+async fn ensure_active_followup_type(db: &sqlx::PgPool, code: &str) -> Result<(), AppError> {
+    let is_active = sqlx::query_scalar!(
+        "SELECT is_active FROM followup_types WHERE code = $1",
+        code
+    )
+    .fetch_optional(db)
+    .await?;
+
+    match is_active {
+        Some(true) => Ok(()),
+        Some(false) => Err(AppError::ValidationError(format!(
+            "Followup type '{}' is no longer in use",
+            code
+        ))),
+        None => Err(AppError::ValidationError(format!(
+            "Unknown followup type '{}'",
+            code
+        ))),
+    }
+}
+
+/// GET /api/me/omil/followup-types
+/// Followup types staff can choose from
+pub async fn list_followup_types(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<FollowupType>>, AppError> {
+    let types = sqlx::query_as!(
+        FollowupType,
+        r#"
+        SELECT code, label_es, is_active, counts_as_intervention, created_at, updated_at
+        FROM followup_types
+        WHERE is_active = true
+        ORDER BY label_es
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(types))
+}
+
 /// GET /api/me/omil/job-seekers/{id}/followups
 /// List followups for a job seeker
 pub async fn list_followups(
@@ -1004,7 +1681,7 @@ pub async fn list_followups(
 ) -> Result<Json<Vec<FollowupWithCreator>>, AppError> {
     // Get job_seeker_id from managed record
     let managed = sqlx::query!(
-        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2",
+        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true",
         managed_id,
         omil_ctx.organization.id
     )
@@ -1024,7 +1701,7 @@ pub async fn list_followups(
             f.created_by,
             f.omil_id,
             f.application_id,
-            f.followup_type as "followup_type: FollowupType",
+            f.followup_type,
             f.title,
             f.content,
             f.is_private,
@@ -1036,14 +1713,14 @@ pub async fn list_followups(
         JOIN users u ON u.id = f.created_by
         WHERE f.job_seeker_id = $1
         AND f.omil_id = $2
-        AND ($3::followup_type IS NULL OR f.followup_type = $3)
+        AND ($3::varchar IS NULL OR f.followup_type = $3)
         AND ($4::boolean = true OR f.is_private = false)
         ORDER BY f.followup_date DESC
         LIMIT $5 OFFSET $6
         "#,
         managed.job_seeker_id,
         omil_ctx.organization.id,
-        query.followup_type as Option<FollowupType>,
+        query.followup_type,
         include_private,
         limit,
         offset
@@ -1084,10 +1761,11 @@ pub async fn create_followup(
     Json(payload): Json<CreateFollowupRequest>,
 ) -> Result<Json<JobSeekerFollowup>, AppError> {
     payload.validate()?;
+    ensure_active_followup_type(&state.db, &payload.followup_type).await?;
 
     // Get job_seeker_id from managed record
     let managed = sqlx::query!(
-        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2",
+        "SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1 AND omil_id = $2 AND is_active = true",
         managed_id,
         omil_ctx.organization.id
     )
@@ -1106,7 +1784,7 @@ pub async fn create_followup(
             created_by,
             omil_id,
             application_id,
-            followup_type as "followup_type: FollowupType",
+            followup_type,
             title,
             content,
             is_private,
@@ -1118,7 +1796,7 @@ pub async fn create_followup(
         omil_ctx.member.user_id,
         omil_ctx.organization.id,
         payload.application_id,
-        payload.followup_type as FollowupType,
+        payload.followup_type,
         payload.title,
         payload.content,
         payload.is_private.unwrap_or(false)
@@ -1141,7 +1819,7 @@ pub async fn update_followup(
 
     // Verify followup belongs to this OMIL and user is creator
     let existing = sqlx::query!(
-        "SELECT created_by FROM job_seeker_followups WHERE id = $1 AND omil_id = $2",
+        "SELECT created_by, followup_type FROM job_seeker_followups WHERE id = $1 AND omil_id = $2",
         followup_id,
         omil_ctx.organization.id
     )
@@ -1155,6 +1833,13 @@ pub async fn update_followup(
         ));
     }
 
+    // Keeping a type that has since been retired is fine; switching to one isn't
+    if let Some(code) = &payload.followup_type {
+        if *code != existing.followup_type {
+            ensure_active_followup_type(&state.db, code).await?;
+        }
+    }
+
     let followup = sqlx::query_as!(
         JobSeekerFollowup,
         r#"
@@ -1163,6 +1848,7 @@ pub async fn update_followup(
             title = COALESCE($1, title),
             content = COALESCE($2, content),
             is_private = COALESCE($3, is_private),
+            followup_type = COALESCE($5, followup_type),
             updated_at = NOW()
         WHERE id = $4
         RETURNING
@@ -1171,7 +1857,7 @@ pub async fn update_followup(
             created_by,
             omil_id,
             application_id,
-            followup_type as "followup_type: FollowupType",
+            followup_type,
             title,
             content,
             is_private,
@@ -1182,7 +1868,8 @@ pub async fn update_followup(
         payload.title,
         payload.content,
         payload.is_private,
-        followup_id
+        followup_id,
+        payload.followup_type
     )
     .fetch_one(&state.db)
     .await?;
@@ -1208,9 +1895,8 @@ pub async fn delete_followup(
     .ok_or_else(|| AppError::NotFound("Followup not found".to_string()))?;
 
     // Only creator or coordinator+ can delete
-    let can_delete = existing.created_by == omil_ctx.member.user_id
-        || omil_ctx.member.role == OmilRole::Coordinator
-        || omil_ctx.member.role == OmilRole::Director;
+    let can_delete =
+        existing.created_by == omil_ctx.member.user_id || omil_ctx.can(Capability::SuperviseOmil);
 
     if !can_delete {
         return Err(AppError::ForbiddenError(
@@ -1289,20 +1975,87 @@ pub async fn export_managed_seekers(
     Extension(omil_ctx): Extension<OmilContext>,
     Query(query): Query<ExportManagedSeekersQuery>,
 ) -> Result<Response, AppError> {
+    let export = exports::begin(
+        &state.db,
+        ExportOwner::Omil(omil_ctx.organization.id),
+        omil_ctx.member.user_id,
+        query.include_contact,
+    )
+    .await?;
+
+    let (buffer, row_count) = match query.template_id {
+        Some(template_id) => {
+            export_with_template(
+                &state,
+                omil_ctx.organization.id,
+                template_id,
+                query.placement_outcome,
+                &export,
+            )
+            .await?
+        }
+        None => default_export(&state, &omil_ctx, &query, &export).await?,
+    };
+
+    export
+        .record(
+            &state.db,
+            ExportType::ManagedSeekers,
+            serde_json::json!({
+                "placement_outcome": query.placement_outcome,
+                "template_id": query.template_id,
+                "include_contact": query.include_contact,
+            }),
+            row_count,
+        )
+        .await?;
+
+    // Sanitize filename
+    let safe_name: String = omil_ctx
+        .organization
+        .organization_name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .take(30)
+        .collect();
+    let filename = format!("managed-seekers-{}.xlsx", safe_name);
+    let content_disposition = format!("attachment; filename=\"{}\"", filename);
+
+    let response = Response::builder()
+        .header(
+            header::CONTENT_TYPE,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        )
+        .header(header::CONTENT_DISPOSITION, content_disposition)
+        .body(Body::from(buffer))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+
+    Ok(response)
+}
+
+/// The fixed-column export used when no template is chosen, with its row count
+async fn default_export(
+    state: &AppState,
+    omil_ctx: &OmilContext,
+    query: &ExportManagedSeekersQuery,
+    export: &Export,
+) -> Result<(Vec<u8>, usize), AppError> {
     // Fetch all managed seekers with details
     let seekers = sqlx::query!(
         r#"
         SELECT
             (u.first_name || ' ' || u.last_name) as "user_name!",
-            u.email as "user_email!",
+            u.email as user_email,
             p.phone,
             mjs.placement_outcome as "placement_outcome: PlacementOutcome",
             (SELECT (first_name || ' ' || last_name) FROM users WHERE id = mjs.assigned_advisor_id) as assigned_advisor_name,
+            b.name as "branch_name?",
             mjs.registered_at,
             mjs.is_active
         FROM omil_managed_job_seekers mjs
         JOIN users u ON u.id = mjs.job_seeker_id
         LEFT JOIN job_seeker_profiles p ON p.user_id = mjs.job_seeker_id
+        LEFT JOIN omil_branches b ON b.id = mjs.branch_id
         WHERE mjs.omil_id = $1
         AND ($2::placement_outcome IS NULL OR mjs.placement_outcome = $2)
         ORDER BY mjs.registered_at DESC
@@ -1325,7 +2078,7 @@ pub async fn export_managed_seekers(
         |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
 
     // Write headers
-    let include_contact = query.include_contact.unwrap_or(true);
+    let include_contact = export.include_contact;
     let mut col = 0u16;
     worksheet
         .write_string_with_format(0, col, "Name", &header_format)
@@ -1349,6 +2102,10 @@ pub async fn export_managed_seekers(
         .write_string_with_format(0, col, "Advisor", &header_format)
         .map_err(xlsx_err)?;
     col += 1;
+    worksheet
+        .write_string_with_format(0, col, "Branch", &header_format)
+        .map_err(xlsx_err)?;
+    col += 1;
     worksheet
         .write_string_with_format(0, col, "Registered At", &header_format)
         .map_err(xlsx_err)?;
@@ -1369,7 +2126,7 @@ pub async fn export_managed_seekers(
 
         if include_contact {
             worksheet
-                .write_string(row, col, &seeker.user_email)
+                .write_string(row, col, seeker.user_email.as_deref().unwrap_or(""))
                 .map_err(xlsx_err)?;
             col += 1;
             worksheet
@@ -1393,11 +2150,16 @@ pub async fn export_managed_seekers(
             .map_err(xlsx_err)?;
         col += 1;
 
+        worksheet
+            .write_string(row, col, seeker.branch_name.as_deref().unwrap_or(""))
+            .map_err(xlsx_err)?;
+        col += 1;
+
         worksheet
             .write_string(
                 row,
                 col,
-                &seeker.registered_at.format("%Y-%m-%d %H:%M").to_string(),
+                seeker.registered_at.format("%Y-%m-%d %H:%M").to_string(),
             )
             .map_err(xlsx_err)?;
         col += 1;
@@ -1407,46 +2169,159 @@ pub async fn export_managed_seekers(
             .map_err(xlsx_err)?;
     }
 
+    export
+        .watermark
+        .stamp(&mut workbook, seekers.len() as u32 + 2)?;
+
     // Generate Excel file
     let buffer = workbook
         .save_to_buffer()
         .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))?;
 
-    // Sanitize filename
-    let safe_name: String = omil_ctx
-        .organization
-        .organization_name
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
-        .take(30)
-        .collect();
-    let filename = format!("managed-seekers-{}.xlsx", safe_name);
-    let content_disposition = format!("attachment; filename=\"{}\"", filename);
+    Ok((buffer, seekers.len()))
+}
 
-    let response = Response::builder()
-        .header(
-            header::CONTENT_TYPE,
-            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
-        )
-        .header(header::CONTENT_DISPOSITION, content_disposition)
-        .body(Body::from(buffer))
-        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+/// GET /api/me/omil/exports
+/// Exports made by the OMIL's members, newest first (director only)
+pub async fn list_exports(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<ExportAuditQuery>,
+) -> Result<Json<PaginatedResponse<ExportAuditEntry>>, AppError> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let filter = exports::AuditFilter {
+        omil_id: Some(omil_ctx.organization.id),
+        export_type: query.export_type,
+        export_id: query.export_id,
+        ..Default::default()
+    };
+    let (data, total) = exports::list(&state.db, &filter, limit, offset).await?;
 
-    Ok(response)
+    Ok(Json(PaginatedResponse {
+        data,
+        total,
+        limit,
+        offset,
+    }))
 }
 
-/// GET /api/me/omil/applications
-/// List all applications submitted by this OMIL
-pub async fn list_omil_applications(
+/// GET /api/me/omil/export-settings
+pub async fn get_export_settings(
     State(state): State<AppState>,
     Extension(omil_ctx): Extension<OmilContext>,
-    Query(query): Query<OmilApplicationsQuery>,
-) -> Result<Json<OmilApplicationsResponse>, AppError> {
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+) -> Result<Json<ExportSettings>, AppError> {
+    let settings = sqlx::query_as!(
+        ExportSettings,
+        "SELECT allow_contact_export FROM omil_organizations WHERE id = $1",
+        omil_ctx.organization.id
+    )
+    .fetch_one(&state.db)
+    .await?;
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar!(
+    Ok(Json(settings))
+}
+
+/// PUT /api/me/omil/export-settings
+/// Allow or forbid contact details in the OMIL's exports (director only)
+pub async fn update_export_settings(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Json(payload): Json<UpdateExportSettingsRequest>,
+) -> Result<Json<ExportSettings>, AppError> {
+    if !omil_ctx.can(Capability::AdministerOmil) {
+        return Err(AppError::ForbiddenError(
+            "Only directors can change export settings".to_string(),
+        ));
+    }
+
+    let settings = sqlx::query_as!(
+        ExportSettings,
+        r#"
+        UPDATE omil_organizations
+        SET allow_contact_export = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING allow_contact_export
+        "#,
+        omil_ctx.organization.id,
+        payload.allow_contact_export
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+// ============================================================================
+// MONTHLY SENCE REPORT
+// ============================================================================
+
+/// GET /api/me/omil/reports/monthly
+/// Monthly counters reported to SENCE
+pub async fn get_monthly_report(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<MonthlyReportQuery>,
+) -> Result<Json<OmilMonthlyReport>, AppError> {
+    let report = omil_monthly_report::build(
+        &state.db,
+        &omil_ctx.organization,
+        query.year,
+        query.month,
+        &state.config.platform_timezone,
+    )
+    .await?;
+
+    Ok(Json(report))
+}
+
+/// GET /api/me/omil/reports/monthly/export
+/// Monthly SENCE report in the official spreadsheet layout
+pub async fn export_monthly_report(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<MonthlyReportQuery>,
+) -> Result<Response, AppError> {
+    let report = omil_monthly_report::build(
+        &state.db,
+        &omil_ctx.organization,
+        query.year,
+        query.month,
+        &state.config.platform_timezone,
+    )
+    .await?;
+    let buffer = omil_monthly_report::render(&report)?;
+
+    let content_disposition = format!(
+        "attachment; filename=\"informe-sence-{}-{:02}.xlsx\"",
+        report.year, report.month
+    );
+
+    let response = Response::builder()
+        .header(
+            header::CONTENT_TYPE,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        )
+        .header(header::CONTENT_DISPOSITION, content_disposition)
+        .body(Body::from(buffer))
+        .map_err(|e| AppError::InternalError(format!("Failed to build response: {}", e)))?;
+
+    Ok(response)
+}
+
+/// GET /api/me/omil/applications
+/// List all applications submitted by this OMIL
+pub async fn list_omil_applications(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<OmilApplicationsQuery>,
+) -> Result<Json<OmilApplicationsResponse>, AppError> {
+    let limit = query.limit.unwrap_or(20).min(100);
+    let offset = query.offset.unwrap_or(0);
+
+    // Get total count
+    let total: i64 = sqlx::query_scalar!(
         r#"
         SELECT COUNT(*)
         FROM omil_applications oa
@@ -1454,10 +2329,17 @@ pub async fn list_omil_applications(
         WHERE oa.omil_id = $1
         AND ($2::uuid IS NULL OR ja.applicant_id = $2)
         AND ($3::application_status IS NULL OR ja.status = $3)
+        AND ($4::uuid IS NULL OR EXISTS (
+            SELECT 1 FROM omil_managed_job_seekers mjs
+            WHERE mjs.omil_id = oa.omil_id
+            AND mjs.job_seeker_id = ja.applicant_id
+            AND mjs.branch_id = $4
+        ))
         "#,
         omil_ctx.organization.id,
         query.job_seeker_id,
         query.status as Option<ApplicationStatus>,
+        query.branch_id,
     )
     .fetch_one(&state.db)
     .await?
@@ -1475,7 +2357,10 @@ pub async fn list_omil_applications(
             (u.first_name || ' ' || u.last_name) as "job_seeker_name!",
             ja.status as "status: ApplicationStatus",
             ja.applied_at,
-            oa.submitted_by
+            oa.submitted_by,
+            ja.rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            CASE WHEN ja.rejection_feedback_shared THEN ja.rejection_feedback END
+                as rejection_feedback
         FROM omil_applications oa
         JOIN job_applications ja ON ja.id = oa.application_id
         JOIN jobs j ON j.id = ja.job_id
@@ -1484,6 +2369,12 @@ pub async fn list_omil_applications(
         WHERE oa.omil_id = $1
         AND ($2::uuid IS NULL OR ja.applicant_id = $2)
         AND ($3::application_status IS NULL OR ja.status = $3)
+        AND ($6::uuid IS NULL OR EXISTS (
+            SELECT 1 FROM omil_managed_job_seekers mjs
+            WHERE mjs.omil_id = oa.omil_id
+            AND mjs.job_seeker_id = ja.applicant_id
+            AND mjs.branch_id = $6
+        ))
         ORDER BY ja.applied_at DESC
         LIMIT $4 OFFSET $5
         "#,
@@ -1492,6 +2383,7 @@ pub async fn list_omil_applications(
         query.status as Option<ApplicationStatus>,
         limit,
         offset,
+        query.branch_id,
     )
     .fetch_all(&state.db)
     .await?;
@@ -1508,6 +2400,8 @@ pub async fn list_omil_applications(
             status: row.status,
             applied_at: row.applied_at,
             submitted_by: row.submitted_by,
+            rejection_reason_code: row.rejection_reason_code,
+            rejection_feedback: row.rejection_feedback,
         })
         .collect();
 
@@ -1516,3 +2410,1588 @@ pub async fn list_omil_applications(
         total,
     }))
 }
+
+// ============================================================================
+// KIOSK SESSIONS
+// ============================================================================
+
+async fn fetch_kiosk_session(
+    db: &sqlx::PgPool,
+    session_id: Uuid,
+    omil_id: Uuid,
+) -> Result<OmilKioskSession, AppError> {
+    sqlx::query_as!(
+        OmilKioskSession,
+        r#"
+        SELECT
+            k.id, k.omil_id, k.label, k.created_by,
+            (u.first_name || ' ' || u.last_name) as created_by_name,
+            (SELECT COUNT(*) FROM omil_managed_job_seekers mjs
+             WHERE mjs.kiosk_session_id = k.id) as "registrations!",
+            k.expires_at, k.created_at
+        FROM omil_kiosk_sessions k
+        LEFT JOIN users u ON u.id = k.created_by
+        WHERE k.id = $1 AND k.omil_id = $2
+        "#,
+        session_id,
+        omil_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Kiosk session not found".to_string()))
+}
+
+/// POST /api/me/omil/kiosk-sessions
+/// Open a kiosk on a shared device. The token can only register job seekers
+/// for this OMIL and read reference data, and lasts 8 hours.
+pub async fn create_kiosk_session(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Json(mut payload): Json<CreateKioskSessionRequest>,
+) -> Result<Json<KioskSessionResponse>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let token_version = sqlx::query_scalar!(
+        "SELECT token_version FROM users WHERE id = $1",
+        omil_ctx.member.user_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let session_id = Uuid::new_v4();
+    let (token, jti, expires_at) = create_kiosk_token(
+        omil_ctx.member.user_id,
+        omil_ctx.organization.id,
+        session_id,
+        token_version,
+        &state.config,
+    )
+    .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO omil_kiosk_sessions (id, omil_id, label, created_by, token_jti, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        session_id,
+        omil_ctx.organization.id,
+        payload.label,
+        omil_ctx.member.user_id,
+        jti,
+        expires_at
+    )
+    .execute(&state.db)
+    .await?;
+
+    let session = fetch_kiosk_session(&state.db, session_id, omil_ctx.organization.id).await?;
+
+    Ok(Json(KioskSessionResponse {
+        kiosk_token: token,
+        session,
+    }))
+}
+
+/// GET /api/me/omil/kiosk-sessions
+/// List the OMIL's kiosk sessions that are neither revoked nor expired
+pub async fn list_kiosk_sessions(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<Vec<OmilKioskSession>>, AppError> {
+    let sessions = sqlx::query_as!(
+        OmilKioskSession,
+        r#"
+        SELECT
+            k.id, k.omil_id, k.label, k.created_by,
+            (u.first_name || ' ' || u.last_name) as created_by_name,
+            (SELECT COUNT(*) FROM omil_managed_job_seekers mjs
+             WHERE mjs.kiosk_session_id = k.id) as "registrations!",
+            k.expires_at, k.created_at
+        FROM omil_kiosk_sessions k
+        LEFT JOIN users u ON u.id = k.created_by
+        WHERE k.omil_id = $1 AND k.revoked_at IS NULL AND k.expires_at > NOW()
+        ORDER BY k.created_at DESC
+        "#,
+        omil_ctx.organization.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(sessions))
+}
+
+/// DELETE /api/me/omil/kiosk-sessions/{id}
+/// Revoke a kiosk session; its token is blacklisted and stops working at once
+pub async fn revoke_kiosk_session(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let session = sqlx::query!(
+        r#"
+        SELECT token_jti, expires_at
+        FROM omil_kiosk_sessions
+        WHERE id = $1 AND omil_id = $2 AND revoked_at IS NULL AND expires_at > NOW()
+        "#,
+        session_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Kiosk session not found".to_string()))?;
+
+    // Blacklist first: a session marked revoked must not keep a working token
+    let ttl_seconds = (session.expires_at - Utc::now()).num_seconds().max(1);
+    let mut redis_conn = state.redis.clone();
+    blacklist_token(&mut redis_conn, &session.token_jti.to_string(), ttl_seconds)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to blacklist token: {}", e)))?;
+
+    sqlx::query!(
+        "UPDATE omil_kiosk_sessions SET revoked_at = NOW(), revoked_by = $2 WHERE id = $1",
+        session_id,
+        omil_ctx.member.user_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Kiosk session revoked successfully"
+    })))
+}
+
+// ============================================================================
+// V13: INTER-OMIL TRANSFERS
+// ============================================================================
+
+/// POST /api/me/omil/job-seekers/{id}/transfer
+/// Request handing a managed job seeker over to another OMIL (director only)
+pub async fn request_transfer(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+    Json(payload): Json<CreateOmilTransferRequest>,
+) -> Result<Json<OmilTransfer>, AppError> {
+    payload.validate()?;
+
+    let omil_id = omil_ctx.organization.id;
+
+    if payload.target_omil_id == omil_id {
+        return Err(AppError::ValidationError(
+            "Cannot transfer a job seeker to your own OMIL".to_string(),
+        ));
+    }
+
+    let managed = sqlx::query!(
+        r#"
+        SELECT mjs.job_seeker_id, (u.first_name || ' ' || u.last_name) as "job_seeker_name!"
+        FROM omil_managed_job_seekers mjs
+        JOIN users u ON u.id = mjs.job_seeker_id
+        WHERE mjs.id = $1 AND mjs.omil_id = $2 AND mjs.is_active = true
+        "#,
+        managed_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    let target_active = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM omil_organizations WHERE id = $1 AND status = 'active')",
+        payload.target_omil_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if !target_active {
+        return Err(AppError::NotFound("Target OMIL not found".to_string()));
+    }
+
+    let already_managed = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM omil_managed_job_seekers
+            WHERE omil_id = $1 AND job_seeker_id = $2 AND is_active = true
+        )
+        "#,
+        payload.target_omil_id,
+        managed.job_seeker_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if already_managed {
+        return Err(AppError::ConflictError(
+            "Job seeker is already managed by the target OMIL".to_string(),
+        ));
+    }
+
+    let pending_exists = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM omil_transfers WHERE source_managed_id = $1 AND status = 'pending')",
+        managed_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if pending_exists {
+        return Err(AppError::ConflictError(
+            "A transfer is already pending for this job seeker".to_string(),
+        ));
+    }
+
+    let transfer = sqlx::query_as!(
+        OmilTransfer,
+        r#"
+        INSERT INTO omil_transfers (job_seeker_id, source_omil_id, target_omil_id, source_managed_id, reason, requested_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id,
+            job_seeker_id,
+            source_omil_id,
+            target_omil_id,
+            source_managed_id,
+            reason,
+            status as "status: OmilTransferStatus",
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        managed.job_seeker_id,
+        omil_id,
+        payload.target_omil_id,
+        managed_id,
+        payload.reason,
+        omil_ctx.member.user_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    // Let the receiving directors know (async, don't wait)
+    let directors = sqlx::query!(
+        r#"
+        SELECT u.id, u.email as "email!", u.first_name
+        FROM omil_members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.omil_id = $1 AND m.role = 'director' AND m.is_active = true
+        "#,
+        payload.target_omil_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for director in directors {
+        let job_seeker_name = managed.job_seeker_name.clone();
+        let source_omil_name = omil_ctx.organization.organization_name.clone();
+        notifications::email(
+            &state,
+            director.id,
+            NotificationEvent::OmilTransfer,
+            move |email| async move {
+                email
+                    .send_omil_transfer_request_email(
+                        &director.email,
+                        &director.first_name,
+                        &job_seeker_name,
+                        &source_omil_name,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(Json(transfer))
+}
+
+/// GET /api/me/omil/transfers
+/// List incoming and outgoing transfers for the current OMIL
+pub async fn list_transfers(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<OmilTransfersQuery>,
+) -> Result<Json<Vec<OmilTransferWithDetails>>, AppError> {
+    let omil_id = omil_ctx.organization.id;
+
+    let (incoming, outgoing) = match query.direction.as_deref() {
+        None => (true, true),
+        Some("incoming") => (true, false),
+        Some("outgoing") => (false, true),
+        Some(other) => {
+            return Err(AppError::ValidationError(format!(
+                "Invalid direction: {}",
+                other
+            )))
+        }
+    };
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            t.id,
+            t.job_seeker_id,
+            t.source_omil_id,
+            t.target_omil_id,
+            t.source_managed_id,
+            t.reason,
+            t.status as "status: OmilTransferStatus",
+            t.requested_by,
+            t.responded_by,
+            t.responded_at,
+            t.created_at,
+            t.updated_at,
+            (js.first_name || ' ' || js.last_name) as "job_seeker_name!",
+            so.organization_name as source_omil_name,
+            tgt.organization_name as target_omil_name,
+            (rb.first_name || ' ' || rb.last_name) as "requested_by_name!"
+        FROM omil_transfers t
+        JOIN users js ON js.id = t.job_seeker_id
+        JOIN users rb ON rb.id = t.requested_by
+        JOIN omil_organizations so ON so.id = t.source_omil_id
+        JOIN omil_organizations tgt ON tgt.id = t.target_omil_id
+        WHERE (($2 AND t.target_omil_id = $1) OR ($3 AND t.source_omil_id = $1))
+        AND ($4::omil_transfer_status IS NULL OR t.status = $4)
+        ORDER BY t.created_at DESC
+        "#,
+        omil_id,
+        incoming,
+        outgoing,
+        query.status as Option<OmilTransferStatus>
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let transfers = rows
+        .into_iter()
+        .map(|row| OmilTransferWithDetails {
+            direction: if row.target_omil_id == omil_id {
+                "incoming".to_string()
+            } else {
+                "outgoing".to_string()
+            },
+            transfer: OmilTransfer {
+                id: row.id,
+                job_seeker_id: row.job_seeker_id,
+                source_omil_id: row.source_omil_id,
+                target_omil_id: row.target_omil_id,
+                source_managed_id: row.source_managed_id,
+                reason: row.reason,
+                status: row.status,
+                requested_by: row.requested_by,
+                responded_by: row.responded_by,
+                responded_at: row.responded_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            job_seeker_name: row.job_seeker_name,
+            source_omil_name: row.source_omil_name,
+            target_omil_name: row.target_omil_name,
+            requested_by_name: row.requested_by_name,
+        })
+        .collect();
+
+    Ok(Json(transfers))
+}
+
+/// POST /api/me/omil/transfers/{id}/accept
+/// Accept an incoming transfer (receiving director only)
+pub async fn accept_transfer(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<AcceptOmilTransferResponse>, AppError> {
+    let omil_id = omil_ctx.organization.id;
+    let mut tx = state.db.begin().await?;
+
+    let pending = sqlx::query!(
+        r#"
+        SELECT t.job_seeker_id, t.source_omil_id, t.source_managed_id, mjs.registered_at, mjs.is_active
+        FROM omil_transfers t
+        JOIN omil_managed_job_seekers mjs ON mjs.id = t.source_managed_id
+        WHERE t.id = $1 AND t.target_omil_id = $2 AND t.status = 'pending'
+        FOR UPDATE
+        "#,
+        transfer_id,
+        omil_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending transfer not found".to_string()))?;
+
+    if !pending.is_active {
+        return Err(AppError::ConflictError(
+            "Job seeker is no longer managed by the source OMIL".to_string(),
+        ));
+    }
+
+    // Source keeps its record (and any placement it made) but stops counting the seeker
+    sqlx::query!(
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET is_active = false, transferred_to_omil_id = $1, transferred_at = NOW(), updated_at = NOW()
+        WHERE id = $2
+        "#,
+        omil_id,
+        pending.source_managed_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // New record at the target starts a fresh placement but keeps the original registration date.
+    // A seeker returning to an OMIL it previously transferred out gets that record back,
+    // unassigned like a new one since its old advisor or branch may be gone.
+    let managed = sqlx::query_as!(
+        OmilManagedJobSeeker,
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by, registered_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (omil_id, job_seeker_id) DO UPDATE
+        SET is_active = true, transferred_to_omil_id = NULL, transferred_at = NULL,
+            assigned_advisor_id = NULL, branch_id = NULL, updated_at = NOW()
+        RETURNING
+            id,
+            omil_id,
+            job_seeker_id,
+            assigned_advisor_id,
+            branch_id,
+            registered_by,
+            placement_outcome as "placement_outcome: PlacementOutcome",
+            placed_at,
+            placed_job_id,
+            is_active,
+            notes,
+            registered_at,
+            updated_at
+        "#,
+        omil_id,
+        pending.job_seeker_id,
+        omil_ctx.member.user_id,
+        pending.registered_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Shared history travels; private notes stay with the source OMIL
+    let followups_copied = sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_followups (
+            job_seeker_id, created_by, omil_id, application_id, followup_type,
+            title, content, is_private, followup_date, created_at, copied_from_id
+        )
+        SELECT
+            f.job_seeker_id, f.created_by, $3, f.application_id, f.followup_type,
+            f.title, f.content, false, f.followup_date, f.created_at, f.id
+        FROM job_seeker_followups f
+        WHERE f.job_seeker_id = $1
+        AND f.omil_id = $2
+        AND f.is_private = false
+        AND NOT EXISTS (
+            SELECT 1 FROM job_seeker_followups c
+            WHERE c.copied_from_id = f.id AND c.omil_id = $3
+        )
+        "#,
+        pending.job_seeker_id,
+        pending.source_omil_id,
+        omil_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected() as i64;
+
+    let transfer = sqlx::query_as!(
+        OmilTransfer,
+        r#"
+        UPDATE omil_transfers
+        SET status = 'accepted', responded_by = $1, responded_at = NOW()
+        WHERE id = $2
+        RETURNING
+            id,
+            job_seeker_id,
+            source_omil_id,
+            target_omil_id,
+            source_managed_id,
+            reason,
+            status as "status: OmilTransferStatus",
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        omil_ctx.member.user_id,
+        transfer_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(AcceptOmilTransferResponse {
+        transfer,
+        managed_job_seeker: managed,
+        followups_copied,
+    }))
+}
+
+/// POST /api/me/omil/transfers/{id}/decline
+/// Decline an incoming transfer (receiving director only)
+pub async fn decline_transfer(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<OmilTransfer>, AppError> {
+    let transfer = sqlx::query_as!(
+        OmilTransfer,
+        r#"
+        UPDATE omil_transfers
+        SET status = 'declined', responded_by = $1, responded_at = NOW()
+        WHERE id = $2 AND target_omil_id = $3 AND status = 'pending'
+        RETURNING
+            id,
+            job_seeker_id,
+            source_omil_id,
+            target_omil_id,
+            source_managed_id,
+            reason,
+            status as "status: OmilTransferStatus",
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        omil_ctx.member.user_id,
+        transfer_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending transfer not found".to_string()))?;
+
+    Ok(Json(transfer))
+}
+
+/// POST /api/me/omil/transfers/{id}/cancel
+/// Cancel a pending outgoing transfer (initiator only)
+pub async fn cancel_transfer(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(transfer_id): Path<Uuid>,
+) -> Result<Json<OmilTransfer>, AppError> {
+    let existing = sqlx::query!(
+        "SELECT requested_by FROM omil_transfers WHERE id = $1 AND source_omil_id = $2 AND status = 'pending'",
+        transfer_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending transfer not found".to_string()))?;
+
+    if existing.requested_by != omil_ctx.member.user_id {
+        return Err(AppError::ForbiddenError(
+            "Only the director who requested the transfer can cancel it".to_string(),
+        ));
+    }
+
+    let transfer = sqlx::query_as!(
+        OmilTransfer,
+        r#"
+        UPDATE omil_transfers
+        SET status = 'cancelled', responded_by = $1, responded_at = NOW()
+        WHERE id = $2 AND status = 'pending'
+        RETURNING
+            id,
+            job_seeker_id,
+            source_omil_id,
+            target_omil_id,
+            source_managed_id,
+            reason,
+            status as "status: OmilTransferStatus",
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        omil_ctx.member.user_id,
+        transfer_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending transfer not found".to_string()))?;
+
+    Ok(Json(transfer))
+}
+
+// ============================================================================
+// V13: EXPORT TEMPLATES
+// ============================================================================
+
+fn template_name_conflict(e: sqlx::Error) -> AppError {
+    if e.as_database_error()
+        .is_some_and(|db_err| db_err.is_unique_violation())
+    {
+        AppError::ConflictError("An export template with this name already exists".to_string())
+    } else {
+        AppError::DatabaseError(e)
+    }
+}
+
+async fn fetch_export_template(
+    state: &AppState,
+    omil_id: Uuid,
+    template_id: Uuid,
+) -> Result<OmilExportTemplate, AppError> {
+    sqlx::query_as!(
+        OmilExportTemplate,
+        r#"
+        SELECT id, omil_id, name, columns, header_title, include_logo,
+               created_by, created_at, updated_at
+        FROM omil_export_templates
+        WHERE id = $1 AND omil_id = $2
+        "#,
+        template_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Export template not found".to_string()))
+}
+
+/// GET /api/me/omil/export-templates/columns
+/// List the columns available to export templates
+pub async fn list_export_columns() -> Json<Vec<ExportColumnInfo>> {
+    Json(
+        OMIL_EXPORT_COLUMNS
+            .iter()
+            .map(|(key, header)| ExportColumnInfo {
+                key: key.to_string(),
+                header: header.to_string(),
+            })
+            .collect(),
+    )
+}
+
+/// GET /api/me/omil/export-templates
+/// List the organization's export templates
+pub async fn list_export_templates(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<Vec<OmilExportTemplate>>, AppError> {
+    let templates = sqlx::query_as!(
+        OmilExportTemplate,
+        r#"
+        SELECT id, omil_id, name, columns, header_title, include_logo,
+               created_by, created_at, updated_at
+        FROM omil_export_templates
+        WHERE omil_id = $1
+        ORDER BY name
+        "#,
+        omil_ctx.organization.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(templates))
+}
+
+/// POST /api/me/omil/export-templates
+/// Create an export template (coordinator+)
+pub async fn create_export_template(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Json(mut payload): Json<CreateExportTemplateRequest>,
+) -> Result<Json<OmilExportTemplate>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let template = sqlx::query_as!(
+        OmilExportTemplate,
+        r#"
+        INSERT INTO omil_export_templates
+            (omil_id, name, columns, header_title, include_logo, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, omil_id, name, columns, header_title, include_logo,
+                  created_by, created_at, updated_at
+        "#,
+        omil_ctx.organization.id,
+        payload.name,
+        &payload.columns,
+        payload.header_title,
+        payload.include_logo.unwrap_or(false),
+        omil_ctx.member.user_id
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(template_name_conflict)?;
+
+    Ok(Json(template))
+}
+
+/// GET /api/me/omil/export-templates/{id}
+/// Get an export template
+pub async fn get_export_template(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<OmilExportTemplate>, AppError> {
+    let template = fetch_export_template(&state, omil_ctx.organization.id, template_id).await?;
+    Ok(Json(template))
+}
+
+/// PUT /api/me/omil/export-templates/{id}
+/// Update an export template (coordinator+)
+pub async fn update_export_template(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(template_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateExportTemplateRequest>,
+) -> Result<Json<OmilExportTemplate>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let template = sqlx::query_as!(
+        OmilExportTemplate,
+        r#"
+        UPDATE omil_export_templates
+        SET
+            name = COALESCE($3, name),
+            columns = COALESCE($4, columns),
+            header_title = COALESCE($5, header_title),
+            include_logo = COALESCE($6, include_logo)
+        WHERE id = $1 AND omil_id = $2
+        RETURNING id, omil_id, name, columns, header_title, include_logo,
+                  created_by, created_at, updated_at
+        "#,
+        template_id,
+        omil_ctx.organization.id,
+        payload.name,
+        payload.columns.as_deref(),
+        payload.header_title,
+        payload.include_logo
+    )
+    .fetch_optional(&state.db)
+    .await
+    .map_err(template_name_conflict)?
+    .ok_or_else(|| AppError::NotFound("Export template not found".to_string()))?;
+
+    Ok(Json(template))
+}
+
+/// DELETE /api/me/omil/export-templates/{id}
+/// Delete an export template (coordinator+)
+pub async fn delete_export_template(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(template_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query!(
+        "DELETE FROM omil_export_templates WHERE id = $1 AND omil_id = $2",
+        template_id,
+        omil_ctx.organization.id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Export template not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Export template deleted successfully" })))
+}
+
+/// Render the managed seekers export through a saved template
+async fn export_with_template(
+    state: &AppState,
+    omil_id: Uuid,
+    template_id: Uuid,
+    placement_outcome: Option<PlacementOutcome>,
+    export: &Export,
+) -> Result<(Vec<u8>, usize), AppError> {
+    let template = fetch_export_template(state, omil_id, template_id).await?;
+    let rows = omil_export::fetch_rows(&state.db, omil_id, placement_outcome).await?;
+
+    let mut logo = None;
+    if template.include_logo {
+        let storage_path = sqlx::query_scalar!(
+            r#"
+            SELECT f.storage_path
+            FROM omil_organizations o
+            JOIN uploaded_files f ON f.id = o.logo_file_id
+            WHERE o.id = $1
+            "#,
+            omil_id
+        )
+        .fetch_optional(&state.db)
+        .await?;
+
+        if let Some(path) = storage_path {
+            match state.storage.get(&path).await {
+                Ok(data) => logo = Some(data),
+                Err(e) => tracing::warn!("Failed to load OMIL logo for export: {}", e),
+            }
+        }
+    }
+
+    let layout = omil_export::build_layout(&template, &rows, export.include_contact);
+    let buffer = omil_export::render(&layout, logo.as_deref(), &export.watermark)?;
+    Ok((buffer, rows.len()))
+}
+
+// ============================================================================
+// V13: SKILL ENDORSEMENTS
+// ============================================================================
+
+/// The skill, when it belongs to a job seeker this OMIL actively manages
+async fn find_endorsable_skill(
+    state: &AppState,
+    omil_id: Uuid,
+    managed_id: Uuid,
+    user_skill_id: Uuid,
+) -> Result<Uuid, AppError> {
+    let managed = sqlx::query!(
+        r#"
+        SELECT job_seeker_id
+        FROM omil_managed_job_seekers
+        WHERE id = $1 AND omil_id = $2 AND is_active = true
+        "#,
+        managed_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    sqlx::query_scalar!(
+        "SELECT id FROM user_skills WHERE id = $1 AND user_id = $2",
+        user_skill_id,
+        managed.job_seeker_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Skill not found".to_string()))
+}
+
+/// POST /api/me/omil/job-seekers/{id}/skills/{user_skill_id}/endorse
+/// Endorse a managed job seeker's skill; endorsing again updates the comment
+pub async fn endorse_skill(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path((managed_id, user_skill_id)): Path<(Uuid, Uuid)>,
+    Json(mut payload): Json<EndorseSkillRequest>,
+) -> Result<Json<SkillEndorsement>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let user_skill_id =
+        find_endorsable_skill(&state, omil_ctx.organization.id, managed_id, user_skill_id).await?;
+
+    let endorsement = sqlx::query_as!(
+        SkillEndorsement,
+        r#"
+        INSERT INTO skill_endorsements (user_skill_id, endorsed_by, endorser_type, omil_id, comment)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_skill_id, endorsed_by)
+        DO UPDATE SET comment = EXCLUDED.comment, omil_id = EXCLUDED.omil_id
+        RETURNING id, user_skill_id, endorsed_by,
+                  endorser_type as "endorser_type: EndorserType",
+                  omil_id, comment, created_at
+        "#,
+        user_skill_id,
+        omil_ctx.member.user_id,
+        EndorserType::OmilMember as EndorserType,
+        omil_ctx.organization.id,
+        payload.comment
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(endorsement))
+}
+
+/// DELETE /api/me/omil/job-seekers/{id}/skills/{user_skill_id}/endorse
+/// Withdraw this OMIL's endorsements of a skill
+pub async fn remove_skill_endorsement(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path((managed_id, user_skill_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user_skill_id =
+        find_endorsable_skill(&state, omil_ctx.organization.id, managed_id, user_skill_id).await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM skill_endorsements WHERE user_skill_id = $1 AND omil_id = $2",
+        user_skill_id,
+        omil_ctx.organization.id
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Endorsement not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Endorsement removed successfully" })))
+}
+
+// ============================================================================
+// PROFILE REFERENCES
+// ============================================================================
+
+/// The job seeker of a record this OMIL actively manages
+async fn active_managed_seeker(
+    state: &AppState,
+    omil_id: Uuid,
+    managed_id: Uuid,
+) -> Result<Uuid, AppError> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT job_seeker_id
+        FROM omil_managed_job_seekers
+        WHERE id = $1 AND omil_id = $2 AND is_active = true
+        "#,
+        managed_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))
+}
+
+/// GET /api/me/omil/job-seekers/{id}/references
+/// A managed job seeker's references with the referees' contact emails and
+/// who verified each one
+pub async fn list_managed_references(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+) -> Result<Json<Vec<ManagedProfileReference>>, AppError> {
+    let job_seeker_id =
+        active_managed_seeker(&state, omil_ctx.organization.id, managed_id).await?;
+
+    let references =
+        profile_references::for_omil(&state.db, state.storage.as_ref(), job_seeker_id).await?;
+
+    Ok(Json(references))
+}
+
+/// POST /api/me/omil/job-seekers/{id}/references/{reference_id}/verify
+/// Mark a reference as verified after calling the referee
+pub async fn verify_reference(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path((managed_id, reference_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ProfileReference>, AppError> {
+    let job_seeker_id =
+        active_managed_seeker(&state, omil_ctx.organization.id, managed_id).await?;
+
+    let reference = sqlx::query_as!(
+        ProfileReference,
+        r#"
+        UPDATE profile_references
+        SET is_verified = true, verified_by = $3, verified_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, referee_name, referee_organization, relationship, contact_email,
+                  letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+                  created_at, updated_at
+        "#,
+        reference_id,
+        job_seeker_id,
+        omil_ctx.member.user_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Reference not found".to_string()))?;
+
+    Ok(Json(reference))
+}
+
+// ============================================================================
+// PENDING ACTIONS (FOUR-EYES APPROVAL)
+// ============================================================================
+
+/// An advisor's placement or deactivation waits for approval when the
+/// organization requires it; coordinators and directors act directly
+async fn needs_approval(state: &AppState, omil_ctx: &OmilContext) -> Result<bool, AppError> {
+    if omil_ctx.can(Capability::SuperviseOmil) {
+        return Ok(false);
+    }
+
+    let required = sqlx::query_scalar!(
+        "SELECT require_placement_approval FROM omil_organizations WHERE id = $1",
+        omil_ctx.organization.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(required)
+}
+
+async fn request_approval(
+    state: &AppState,
+    omil_ctx: &OmilContext,
+    managed_id: Uuid,
+    action_type: OmilActionType,
+    payload: serde_json::Value,
+) -> Result<PendingOmilAction, AppError> {
+    sqlx::query_as!(
+        PendingOmilAction,
+        r#"
+        INSERT INTO pending_omil_actions (omil_id, managed_id, action_type, payload, requested_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING
+            id,
+            omil_id,
+            managed_id,
+            action_type as "action_type: OmilActionType",
+            payload,
+            status as "status: PendingActionStatus",
+            requested_by,
+            reviewed_by,
+            reviewed_at,
+            review_notes,
+            created_at
+        "#,
+        omil_ctx.organization.id,
+        managed_id,
+        action_type as OmilActionType,
+        payload,
+        omil_ctx.member.user_id
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => AppError::ValidationError(
+            "This action is already waiting for approval".to_string(),
+        ),
+        e => e.into(),
+    })
+}
+
+/// Closes a pending action of this OMIL, or fails if it was already reviewed
+async fn review_pending_action(
+    conn: &mut PgConnection,
+    omil_ctx: &OmilContext,
+    action_id: Uuid,
+    status: PendingActionStatus,
+    notes: Option<String>,
+) -> Result<PendingOmilAction, AppError> {
+    sqlx::query_as!(
+        PendingOmilAction,
+        r#"
+        UPDATE pending_omil_actions
+        SET status = $1, reviewed_by = $2, reviewed_at = NOW(), review_notes = $3
+        WHERE id = $4 AND omil_id = $5 AND status = 'pending'
+        RETURNING
+            id,
+            omil_id,
+            managed_id,
+            action_type as "action_type: OmilActionType",
+            payload,
+            status as "status: PendingActionStatus",
+            requested_by,
+            reviewed_by,
+            reviewed_at,
+            review_notes,
+            created_at
+        "#,
+        status as PendingActionStatus,
+        omil_ctx.member.user_id,
+        notes,
+        action_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending action not found".to_string()))
+}
+
+/// GET /api/me/omil/pending-actions
+/// Advisor actions waiting for approval, oldest first (coordinator+ only)
+pub async fn list_pending_actions(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<PendingOmilActionsQuery>,
+) -> Result<Json<Vec<PendingOmilActionWithDetails>>, AppError> {
+    let status = query.status.unwrap_or(PendingActionStatus::Pending);
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            a.id,
+            a.omil_id,
+            a.managed_id,
+            a.action_type as "action_type: OmilActionType",
+            a.payload,
+            a.status as "status: PendingActionStatus",
+            a.requested_by,
+            a.reviewed_by,
+            a.reviewed_at,
+            a.review_notes,
+            a.created_at,
+            (js.first_name || ' ' || js.last_name) as "job_seeker_name!",
+            (rb.first_name || ' ' || rb.last_name) as "requested_by_name!"
+        FROM pending_omil_actions a
+        JOIN omil_managed_job_seekers mjs ON mjs.id = a.managed_id
+        JOIN users js ON js.id = mjs.job_seeker_id
+        JOIN users rb ON rb.id = a.requested_by
+        WHERE a.omil_id = $1 AND a.status = $2
+        ORDER BY a.created_at
+        "#,
+        omil_ctx.organization.id,
+        status as PendingActionStatus
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let actions = rows
+        .into_iter()
+        .map(|row| PendingOmilActionWithDetails {
+            action: PendingOmilAction {
+                id: row.id,
+                omil_id: row.omil_id,
+                managed_id: row.managed_id,
+                action_type: row.action_type,
+                payload: row.payload,
+                status: row.status,
+                requested_by: row.requested_by,
+                reviewed_by: row.reviewed_by,
+                reviewed_at: row.reviewed_at,
+                review_notes: row.review_notes,
+                created_at: row.created_at,
+            },
+            job_seeker_name: row.job_seeker_name,
+            requested_by_name: row.requested_by_name,
+        })
+        .collect();
+
+    Ok(Json(actions))
+}
+
+/// POST /api/me/omil/pending-actions/{id}/approve
+/// Carry out an advisor's action as the approver (coordinator+ only). The
+/// followup names both the advisor and the approver. Fails with 409 when the
+/// record changed after the request, leaving the action pending.
+pub async fn approve_pending_action(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(action_id): Path<Uuid>,
+) -> Result<Json<ApprovePendingActionResponse>, AppError> {
+    let mut tx = state.db.begin().await?;
+    let action = review_pending_action(
+        &mut tx,
+        &omil_ctx,
+        action_id,
+        PendingActionStatus::Approved,
+        None,
+    )
+    .await?;
+
+    // The request was made against the record as it was then; if it was
+    // changed or deactivated since, the approver has to look at it again
+    let unchanged = sqlx::query_scalar!(
+        r#"
+        SELECT (is_active AND updated_at <= $2) as "unchanged!"
+        FROM omil_managed_job_seekers
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        action.managed_id,
+        action.created_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !unchanged {
+        return Err(AppError::ConflictError(
+            "The job seeker's record changed after this action was requested".to_string(),
+        ));
+    }
+
+    let names = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT first_name || ' ' || last_name FROM users WHERE id = $1) as "requested_by!",
+            (SELECT first_name || ' ' || last_name FROM users WHERE id = $2) as "approved_by!"
+        "#,
+        action.requested_by,
+        omil_ctx.member.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let actors = format!(
+        "Solicitado por {}, aprobado por {}",
+        names.requested_by, names.approved_by
+    );
+    let invalid_payload =
+        |e: serde_json::Error| AppError::InternalError(format!("Invalid pending action: {}", e));
+
+    let managed = match action.action_type {
+        OmilActionType::Placement => {
+            let request: UpdatePlacementRequest =
+                serde_json::from_value(action.payload.clone()).map_err(invalid_payload)?;
+            let content = format!(
+                "Estado actualizado a: {}. {}",
+                request.outcome.label(),
+                actors
+            );
+            apply_placement(&mut tx, action.managed_id, &request, omil_ctx.member.user_id, content)
+                .await?
+        }
+        OmilActionType::Deactivation => {
+            let request: DeactivateManagedJobSeekerRequest =
+                serde_json::from_value(action.payload.clone()).map_err(invalid_payload)?;
+            let content = match request.reason {
+                Some(reason) => format!("{}. {}", reason, actors),
+                None => actors,
+            };
+            apply_deactivation(&mut tx, action.managed_id, omil_ctx.member.user_id, content).await?
+        }
+    };
+    tx.commit().await?;
+
+    Ok(Json(ApprovePendingActionResponse {
+        action,
+        managed_job_seeker: managed,
+    }))
+}
+
+/// POST /api/me/omil/pending-actions/{id}/reject
+/// Turn down an advisor's action; nothing changes on the record (coordinator+ only)
+pub async fn reject_pending_action(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(action_id): Path<Uuid>,
+    Json(payload): Json<RejectPendingActionRequest>,
+) -> Result<Json<PendingOmilAction>, AppError> {
+    payload.validate()?;
+
+    let mut conn = state.db.acquire().await?;
+    let action = review_pending_action(
+        &mut conn,
+        &omil_ctx,
+        action_id,
+        PendingActionStatus::Rejected,
+        payload.reason,
+    )
+    .await?;
+
+    Ok(Json(action))
+}
+
+/// GET /api/me/omil/approval-settings
+pub async fn get_approval_settings(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<ApprovalSettings>, AppError> {
+    let settings = sqlx::query_as!(
+        ApprovalSettings,
+        "SELECT require_placement_approval FROM omil_organizations WHERE id = $1",
+        omil_ctx.organization.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+/// PUT /api/me/omil/approval-settings
+/// Require approval of advisors' placements and deactivations (director only)
+pub async fn update_approval_settings(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Json(payload): Json<UpdateApprovalSettingsRequest>,
+) -> Result<Json<ApprovalSettings>, AppError> {
+    if !omil_ctx.can(Capability::AdministerOmil) {
+        return Err(AppError::ForbiddenError(
+            "Only directors can change approval settings".to_string(),
+        ));
+    }
+
+    let settings = sqlx::query_as!(
+        ApprovalSettings,
+        r#"
+        UPDATE omil_organizations
+        SET require_placement_approval = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING require_placement_approval
+        "#,
+        omil_ctx.organization.id,
+        payload.require_placement_approval
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+// ============================================================================
+// BRANCHES
+// ============================================================================
+
+fn branch_name_conflict(e: sqlx::Error) -> AppError {
+    if e.as_database_error()
+        .is_some_and(|db_err| db_err.is_unique_violation())
+    {
+        AppError::ConflictError("A branch with this name already exists".to_string())
+    } else {
+        AppError::DatabaseError(e)
+    }
+}
+
+/// Refuses to take a branch out of use while active job seekers or members
+/// are still assigned to it
+async fn ensure_branch_unused(state: &AppState, branch_id: Uuid) -> Result<(), AppError> {
+    let in_use = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM omil_managed_job_seekers
+             WHERE branch_id = $1 AND is_active = true) as "seekers!",
+            (SELECT COUNT(*) FROM omil_members
+             WHERE branch_id = $1 AND is_active = true) as "members!"
+        "#,
+        branch_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    match branch_in_use_message(in_use.seekers, in_use.members) {
+        Some(message) => Err(AppError::ValidationError(message)),
+        None => Ok(()),
+    }
+}
+
+/// The branch to assign, which must be an active branch of the OMIL
+async fn ensure_assignable_branch(
+    state: &AppState,
+    omil_id: Uuid,
+    branch_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let Some(branch_id) = branch_id else {
+        return Ok(());
+    };
+    let is_active = sqlx::query_scalar!(
+        "SELECT is_active FROM omil_branches WHERE id = $1 AND omil_id = $2",
+        branch_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Branch not found".to_string()))?;
+
+    if !is_active {
+        return Err(AppError::ValidationError(
+            "Cannot assign an inactive branch".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /api/me/omil/branches
+/// List the organization's branches, active ones first
+pub async fn list_branches(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+) -> Result<Json<Vec<OmilBranch>>, AppError> {
+    let branches = sqlx::query_as!(
+        OmilBranch,
+        r#"
+        SELECT id, omil_id, name, address, municipality_id, is_active, created_at, updated_at
+        FROM omil_branches
+        WHERE omil_id = $1
+        ORDER BY is_active DESC, name
+        "#,
+        omil_ctx.organization.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(branches))
+}
+
+/// POST /api/me/omil/branches
+/// Create a branch (director only)
+pub async fn create_branch(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Json(mut payload): Json<CreateOmilBranchRequest>,
+) -> Result<Json<OmilBranch>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let branch = sqlx::query_as!(
+        OmilBranch,
+        r#"
+        INSERT INTO omil_branches (omil_id, name, address, municipality_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, omil_id, name, address, municipality_id, is_active, created_at, updated_at
+        "#,
+        omil_ctx.organization.id,
+        payload.name,
+        payload.address,
+        payload.municipality_id
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(branch_name_conflict)?;
+
+    Ok(Json(branch))
+}
+
+/// PUT /api/me/omil/branches/{id}
+/// Update or deactivate a branch (director only)
+pub async fn update_branch(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(branch_id): Path<Uuid>,
+    Json(mut payload): Json<UpdateOmilBranchRequest>,
+) -> Result<Json<OmilBranch>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    sqlx::query_scalar!(
+        "SELECT id FROM omil_branches WHERE id = $1 AND omil_id = $2",
+        branch_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Branch not found".to_string()))?;
+
+    if payload.is_active == Some(false) {
+        ensure_branch_unused(&state, branch_id).await?;
+    }
+
+    let branch = sqlx::query_as!(
+        OmilBranch,
+        r#"
+        UPDATE omil_branches
+        SET
+            name = COALESCE($2, name),
+            address = COALESCE($3, address),
+            municipality_id = COALESCE($4, municipality_id),
+            is_active = COALESCE($5, is_active)
+        WHERE id = $1
+        RETURNING id, omil_id, name, address, municipality_id, is_active, created_at, updated_at
+        "#,
+        branch_id,
+        payload.name,
+        payload.address,
+        payload.municipality_id,
+        payload.is_active
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(branch_name_conflict)?;
+
+    Ok(Json(branch))
+}
+
+/// DELETE /api/me/omil/branches/{id}
+/// Delete a branch (director only). Inactive records assigned to it are
+/// left unassigned.
+pub async fn delete_branch(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(branch_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    sqlx::query_scalar!(
+        "SELECT id FROM omil_branches WHERE id = $1 AND omil_id = $2",
+        branch_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Branch not found".to_string()))?;
+
+    ensure_branch_unused(&state, branch_id).await?;
+
+    sqlx::query!("DELETE FROM omil_branches WHERE id = $1", branch_id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(Json(serde_json::json!({ "message": "Branch deleted successfully" })))
+}
+
+/// PUT /api/me/omil/members/{id}/branch
+/// Assign a member to a branch, or unassign them (coordinator+ only)
+pub async fn assign_member_branch(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(member_id): Path<Uuid>,
+    Json(payload): Json<AssignBranchRequest>,
+) -> Result<Json<OmilMember>, AppError> {
+    ensure_assignable_branch(&state, omil_ctx.organization.id, payload.branch_id).await?;
+
+    let member = sqlx::query_as!(
+        OmilMember,
+        r#"
+        UPDATE omil_members
+        SET branch_id = $1, updated_at = NOW()
+        WHERE id = $2 AND omil_id = $3
+        RETURNING
+            id,
+            omil_id,
+            user_id,
+            role as "role: OmilRole",
+            branch_id,
+            is_active,
+            joined_at,
+            left_at,
+            created_at,
+            updated_at
+        "#,
+        payload.branch_id,
+        member_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+
+    Ok(Json(member))
+}
+
+/// PUT /api/me/omil/job-seekers/{id}/branch
+/// Assign a managed job seeker to a branch, or unassign them (coordinator+ only)
+pub async fn assign_job_seeker_branch(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(managed_id): Path<Uuid>,
+    Json(payload): Json<AssignBranchRequest>,
+) -> Result<Json<OmilManagedJobSeeker>, AppError> {
+    ensure_assignable_branch(&state, omil_ctx.organization.id, payload.branch_id).await?;
+
+    let managed = sqlx::query_as!(
+        OmilManagedJobSeeker,
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET branch_id = $1, updated_at = NOW()
+        WHERE id = $2 AND omil_id = $3
+        RETURNING
+            id,
+            omil_id,
+            job_seeker_id,
+            assigned_advisor_id,
+            branch_id,
+            registered_by,
+            placement_outcome as "placement_outcome: PlacementOutcome",
+            placed_at,
+            placed_job_id,
+            is_active,
+            notes,
+            registered_at,
+            updated_at
+        "#,
+        payload.branch_id,
+        managed_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Managed job seeker not found".to_string()))?;
+
+    Ok(Json(managed))
+}
@@ -0,0 +1,43 @@
+use axum::{extract::State, Extension, Json};
+
+use crate::error::{AppError, Result};
+use crate::extract::Path;
+use crate::middleware::auth::AuthUser;
+use crate::models::onboarding::{OnboardingChecklist, OnboardingStep};
+use crate::services::onboarding;
+use crate::AppState;
+
+fn ensure_job_seeker(auth_user: &AuthUser) -> Result<()> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /api/me/onboarding
+/// Onboarding checklist with each step's state computed from the profile
+pub async fn get_onboarding(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<OnboardingChecklist>> {
+    ensure_job_seeker(&auth_user)?;
+
+    Ok(Json(onboarding::checklist(&state, auth_user.id).await?))
+}
+
+/// POST /api/me/onboarding/{step}/dismiss
+/// Skip an optional onboarding step
+pub async fn dismiss_onboarding_step(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(step): Path<String>,
+) -> Result<Json<OnboardingChecklist>> {
+    ensure_job_seeker(&auth_user)?;
+
+    let step = OnboardingStep::from_code(&step)
+        .ok_or_else(|| AppError::NotFound("Onboarding step not found".to_string()))?;
+
+    Ok(Json(onboarding::dismiss(&state, auth_user.id, step).await?))
+}
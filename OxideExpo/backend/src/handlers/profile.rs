@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use axum::{
-    extract::{Path, State},
+    extract::{Multipart, State},
+    http::HeaderMap,
     Extension, Json,
 };
 use uuid::Uuid;
@@ -7,14 +10,174 @@ use validator::Validate;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
     middleware::AuthUser,
     models::{
         profile::*,
         user::MessageResponse,
     },
+    services::profile_references,
+    utils::{fields::FieldSelection, normalize::Normalize, validation::mask_email},
     AppState,
 };
 
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Ensure no other account already holds this national ID (RUT).
+/// Returns 409 with the masked email of the existing account so the
+/// user or OMIL staff can recognise the duplicate without leaking it.
+/// Accounts managed offline have no email and are named as such.
+pub(crate) async fn ensure_national_id_available(
+    db: impl sqlx::PgExecutor<'_>,
+    national_id: &str,
+    user_id: Option<Uuid>,
+) -> Result<()> {
+    let existing = sqlx::query_scalar!(
+        r#"
+        SELECT u.email
+        FROM job_seeker_profiles jsp
+        JOIN users u ON u.id = jsp.user_id
+        WHERE jsp.national_id = $1
+        AND ($2::uuid IS NULL OR jsp.user_id <> $2)
+        LIMIT 1
+        "#,
+        national_id,
+        user_id,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    match existing {
+        Some(Some(email)) => Err(AppError::ConflictError(format!(
+            "National ID is already registered to account {}",
+            mask_email(&email)
+        ))),
+        Some(None) => Err(AppError::ConflictError(
+            "National ID is already registered to an account managed by an OMIL; ask the OMIL for an invitation to claim it".to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Skills of each of `user_ids`, strongest first, with endorsement status
+pub(crate) async fn fetch_user_skills(
+    db: &sqlx::PgPool,
+    user_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<UserSkill>>> {
+    let skills = sqlx::query_as!(
+        UserSkill,
+        r#"
+        SELECT id, user_id, skill_id, proficiency_level, years_of_experience,
+               EXISTS(
+                   SELECT 1 FROM skill_endorsements e WHERE e.user_skill_id = user_skills.id
+               ) as "is_verified!",
+               created_at, updated_at
+        FROM user_skills
+        WHERE user_id = ANY($1)
+        ORDER BY proficiency_level DESC, created_at DESC
+        "#,
+        user_ids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut by_user: HashMap<Uuid, Vec<UserSkill>> = HashMap::new();
+    for skill in skills {
+        by_user.entry(skill.user_id).or_default().push(skill);
+    }
+
+    Ok(by_user)
+}
+
+/// Inserts a validated education record for the user. Without an
+/// institution_id, a name matching a reference institution links to it.
+pub(crate) async fn insert_education(
+    db: impl sqlx::PgExecutor<'_>,
+    user_id: Uuid,
+    payload: &CreateEducationRequest,
+) -> Result<EducationRecord> {
+    let record = sqlx::query_as!(
+        EducationRecord,
+        r#"
+        INSERT INTO education_records (
+            user_id, institution_id, institution_name, level, field_of_study_id,
+            field_of_study_name, degree_title, status, start_date, end_date,
+            description, achievements
+        )
+        VALUES (
+            $1, COALESCE($2, match_institution($3)), $3, $4, $5, $6, $7, $8, $9, $10,
+            $11, $12
+        )
+        RETURNING id, user_id, institution_id, institution_name,
+                  level as "level: EducationLevel",
+                  field_of_study_id, field_of_study_name, degree_title,
+                  status as "status: EducationStatus",
+                  start_date, end_date, description, achievements, display_order,
+                  created_at, updated_at
+        "#,
+        user_id,
+        payload.institution_id,
+        payload.institution_name,
+        payload.level as EducationLevel,
+        payload.field_of_study_id,
+        payload.field_of_study_name,
+        payload.degree_title,
+        payload.status as EducationStatus,
+        payload.start_date,
+        payload.end_date,
+        payload.description,
+        payload.achievements,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(record)
+}
+
+/// Inserts a validated work experience for the user
+pub(crate) async fn insert_experience(
+    db: impl sqlx::PgExecutor<'_>,
+    user_id: Uuid,
+    payload: &CreateWorkExperienceRequest,
+) -> Result<WorkExperience> {
+    let experience = sqlx::query_as!(
+        WorkExperience,
+        r#"
+        INSERT INTO work_experiences (
+            user_id, company_name, industry_id, position_title, work_area_id,
+            position_level_id, employment_type, is_current, start_date, end_date,
+            region_id, municipality_id, description, achievements
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        RETURNING id, user_id, company_name, industry_id, position_title,
+                  work_area_id, position_level_id,
+                  employment_type as "employment_type: JobType",
+                  is_current, start_date, end_date, region_id, municipality_id,
+                  description, achievements, display_order, created_at, updated_at
+        "#,
+        user_id,
+        payload.company_name,
+        payload.industry_id,
+        payload.position_title,
+        payload.work_area_id,
+        payload.position_level_id,
+        payload.employment_type as Option<JobType>,
+        payload.is_current,
+        payload.start_date,
+        payload.end_date,
+        payload.region_id,
+        payload.municipality_id,
+        payload.description,
+        payload.achievements,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(experience)
+}
+
 // ============================================================================
 // PROFILE ENDPOINTS
 // ============================================================================
@@ -44,7 +207,10 @@ pub async fn get_profile(
                   marital_status as "marital_status: MaritalStatus",
                   nationality, national_id, region_id, municipality_id,
                   address, bio, professional_headline, profile_image_url, cv_url,
-                  completeness_percentage, created_at, updated_at
+                  completeness_percentage,
+                  availability as "availability: SeekerAvailability",
+                  available_from, share_available_from,
+                  created_at, updated_at
         "#,
         auth_user.id,
     )
@@ -59,8 +225,9 @@ pub async fn get_profile(
 pub async fn update_profile(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(payload): Json<UpdateProfileRequest>,
+    Json(mut payload): Json<UpdateProfileRequest>,
 ) -> Result<Json<JobSeekerProfile>> {
+    payload.normalize();
     payload.validate()?;
 
     if auth_user.user_type != "job_seeker" {
@@ -69,15 +236,23 @@ pub async fn update_profile(
         ));
     }
 
+    if let Some(national_id) = payload.national_id.as_deref().filter(|n| !n.is_empty()) {
+        ensure_national_id_available(&state.db, national_id, Some(auth_user.id)).await?;
+    }
+
     // Upsert the profile
     sqlx::query!(
         r#"
         INSERT INTO job_seeker_profiles (
             user_id, phone, date_of_birth, gender, marital_status,
             nationality, national_id, region_id, municipality_id,
-            address, bio, professional_headline
+            address, bio, professional_headline,
+            availability, available_from, share_available_from
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12,
+            COALESCE($13, 'open_to_offers'::seeker_availability), $14, COALESCE($16, false)
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         ON CONFLICT (user_id) DO UPDATE SET
             phone = EXCLUDED.phone,
             date_of_birth = EXCLUDED.date_of_birth,
@@ -89,7 +264,13 @@ pub async fn update_profile(
             municipality_id = EXCLUDED.municipality_id,
             address = EXCLUDED.address,
             bio = EXCLUDED.bio,
-            professional_headline = EXCLUDED.professional_headline
+            professional_headline = EXCLUDED.professional_headline,
+            availability = COALESCE($13, job_seeker_profiles.availability),
+            available_from = CASE
+                WHEN $15 THEN NULL
+                ELSE COALESCE($14, job_seeker_profiles.available_from)
+            END,
+            share_available_from = COALESCE($16, job_seeker_profiles.share_available_from)
         "#,
         auth_user.id,
         payload.phone,
@@ -103,6 +284,10 @@ pub async fn update_profile(
         payload.address,
         payload.bio,
         payload.professional_headline,
+        payload.availability as Option<SeekerAvailability>,
+        payload.available_from,
+        payload.clear_available_from.unwrap_or(false),
+        payload.share_available_from,
     )
     .execute(&state.db)
     .await?;
@@ -116,7 +301,10 @@ pub async fn update_profile(
                marital_status as "marital_status: MaritalStatus",
                nationality, national_id, region_id, municipality_id,
                address, bio, professional_headline, profile_image_url, cv_url,
-               completeness_percentage, created_at, updated_at
+               completeness_percentage,
+               availability as "availability: SeekerAvailability",
+               available_from, share_available_from,
+               created_at, updated_at
         FROM job_seeker_profiles
         WHERE user_id = $1
         "#,
@@ -128,6 +316,73 @@ pub async fn update_profile(
     Ok(Json(profile))
 }
 
+/// POST /api/me/profile/apply-suggestions
+/// Save the CV suggestions the user accepted. Everything is saved or nothing is.
+pub async fn apply_suggestions(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<ApplySuggestionsRequest>,
+) -> Result<Json<ApplySuggestionsResponse>> {
+    payload.normalize();
+    payload.validate()?;
+
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    if payload.work_experiences.len() > MAX_APPLIED_SUGGESTIONS
+        || payload.education.len() > MAX_APPLIED_SUGGESTIONS
+    {
+        return Err(AppError::ValidationError(format!(
+            "At most {} work experiences and {} education entries at a time",
+            MAX_APPLIED_SUGGESTIONS, MAX_APPLIED_SUGGESTIONS
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Fields left out keep their current value
+    let profile = sqlx::query!(
+        r#"
+        INSERT INTO job_seeker_profiles (user_id, phone, professional_headline)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO UPDATE SET
+            phone = COALESCE(EXCLUDED.phone, job_seeker_profiles.phone),
+            professional_headline = COALESCE(
+                EXCLUDED.professional_headline,
+                job_seeker_profiles.professional_headline
+            )
+        RETURNING phone, professional_headline
+        "#,
+        auth_user.id,
+        payload.phone,
+        payload.professional_headline,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let mut work_experiences = Vec::with_capacity(payload.work_experiences.len());
+    for experience in &payload.work_experiences {
+        work_experiences.push(insert_experience(&mut *tx, auth_user.id, experience).await?);
+    }
+
+    let mut education = Vec::with_capacity(payload.education.len());
+    for record in &payload.education {
+        education.push(insert_education(&mut *tx, auth_user.id, record).await?);
+    }
+
+    tx.commit().await?;
+
+    Ok(Json(ApplySuggestionsResponse {
+        phone: profile.phone,
+        professional_headline: profile.professional_headline,
+        work_experiences,
+        education,
+    }))
+}
+
 // ============================================================================
 // DISABILITY INFO ENDPOINTS
 // ============================================================================
@@ -264,37 +519,7 @@ pub async fn create_education(
         ));
     }
 
-    let record = sqlx::query_as!(
-        EducationRecord,
-        r#"
-        INSERT INTO education_records (
-            user_id, institution_id, institution_name, level, field_of_study_id,
-            field_of_study_name, degree_title, status, start_date, end_date,
-            description, achievements
-        )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-        RETURNING id, user_id, institution_id, institution_name,
-                  level as "level: EducationLevel",
-                  field_of_study_id, field_of_study_name, degree_title,
-                  status as "status: EducationStatus",
-                  start_date, end_date, description, achievements, display_order,
-                  created_at, updated_at
-        "#,
-        auth_user.id,
-        payload.institution_id,
-        payload.institution_name,
-        payload.level as EducationLevel,
-        payload.field_of_study_id,
-        payload.field_of_study_name,
-        payload.degree_title,
-        payload.status as EducationStatus,
-        payload.start_date,
-        payload.end_date,
-        payload.description,
-        payload.achievements,
-    )
-    .fetch_one(&state.db)
-    .await?;
+    let record = insert_education(&state.db, auth_user.id, &payload).await?;
 
     Ok(Json(record))
 }
@@ -320,7 +545,7 @@ pub async fn update_education(
         r#"
         UPDATE education_records
         SET
-            institution_id = $3,
+            institution_id = COALESCE($3, match_institution($4)),
             institution_name = $4,
             level = $5,
             field_of_study_id = $6,
@@ -444,38 +669,7 @@ pub async fn create_experience(
         ));
     }
 
-    let experience = sqlx::query_as!(
-        WorkExperience,
-        r#"
-        INSERT INTO work_experiences (
-            user_id, company_name, industry_id, position_title, work_area_id,
-            position_level_id, employment_type, is_current, start_date, end_date,
-            region_id, municipality_id, description, achievements
-        )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
-        RETURNING id, user_id, company_name, industry_id, position_title,
-                  work_area_id, position_level_id,
-                  employment_type as "employment_type: JobType",
-                  is_current, start_date, end_date, region_id, municipality_id,
-                  description, achievements, display_order, created_at, updated_at
-        "#,
-        auth_user.id,
-        payload.company_name,
-        payload.industry_id,
-        payload.position_title,
-        payload.work_area_id,
-        payload.position_level_id,
-        payload.employment_type as Option<JobType>,
-        payload.is_current,
-        payload.start_date,
-        payload.end_date,
-        payload.region_id,
-        payload.municipality_id,
-        payload.description,
-        payload.achievements,
-    )
-    .fetch_one(&state.db)
-    .await?;
+    let experience = insert_experience(&state.db, auth_user.id, &payload).await?;
 
     Ok(Json(experience))
 }
@@ -593,19 +787,10 @@ pub async fn list_skills(
         ));
     }
 
-    let skills = sqlx::query_as!(
-        UserSkill,
-        r#"
-        SELECT id, user_id, skill_id, proficiency_level, years_of_experience,
-               created_at, updated_at
-        FROM user_skills
-        WHERE user_id = $1
-        ORDER BY proficiency_level DESC, created_at DESC
-        "#,
-        auth_user.id,
-    )
-    .fetch_all(&state.db)
-    .await?;
+    let skills = fetch_user_skills(&state.db, &[auth_user.id])
+        .await?
+        .remove(&auth_user.id)
+        .unwrap_or_default();
 
     Ok(Json(skills))
 }
@@ -631,7 +816,7 @@ pub async fn create_skill(
         INSERT INTO user_skills (user_id, skill_id, proficiency_level, years_of_experience)
         VALUES ($1, $2, $3, $4)
         RETURNING id, user_id, skill_id, proficiency_level, years_of_experience,
-                  created_at, updated_at
+                  false as "is_verified!", created_at, updated_at
         "#,
         auth_user.id,
         payload.skill_id,
@@ -676,6 +861,9 @@ pub async fn update_skill(
             years_of_experience = $4
         WHERE id = $1 AND user_id = $2
         RETURNING id, user_id, skill_id, proficiency_level, years_of_experience,
+                  EXISTS(
+                      SELECT 1 FROM skill_endorsements e WHERE e.user_skill_id = user_skills.id
+                  ) as "is_verified!",
                   created_at, updated_at
         "#,
         id,
@@ -1024,141 +1212,389 @@ pub async fn delete_portfolio(
 }
 
 // ============================================================================
-// FULL PROFILE ENDPOINT
+// REFERENCES ENDPOINTS
 // ============================================================================
 
-/// GET /api/me/profile/full
-/// Get complete profile with all related data
-pub async fn get_full_profile(
+/// GET /api/me/references
+/// List the current user's references
+pub async fn list_references(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-) -> Result<Json<FullProfileResponse>> {
+) -> Result<Json<Vec<ProfileReference>>> {
     if auth_user.user_type != "job_seeker" {
         return Err(AppError::ForbiddenError(
             "Only job seekers can access this endpoint".to_string(),
         ));
     }
 
-    // Get or create profile
-    let profile = sqlx::query_as!(
-        JobSeekerProfile,
+    let references = sqlx::query_as!(
+        ProfileReference,
         r#"
-        INSERT INTO job_seeker_profiles (user_id)
-        VALUES ($1)
-        ON CONFLICT (user_id) DO UPDATE SET user_id = $1
-        RETURNING user_id, phone, date_of_birth,
-                  gender as "gender: Gender",
-                  marital_status as "marital_status: MaritalStatus",
-                  nationality, national_id, region_id, municipality_id,
-                  address, bio, professional_headline, profile_image_url, cv_url,
-                  completeness_percentage, created_at, updated_at
+        SELECT id, user_id, referee_name, referee_organization, relationship, contact_email,
+               letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+               created_at, updated_at
+        FROM profile_references
+        WHERE user_id = $1
+        ORDER BY created_at
         "#,
         auth_user.id,
     )
-    .fetch_one(&state.db)
+    .fetch_all(&state.db)
     .await?;
 
-    // Get disability info
-    let disability = sqlx::query_as!(
-        JobSeekerDisability,
-        r#"
-        SELECT id, user_id,
-               category as "category: DisabilityCategory",
-               description, has_disability_certificate, disability_percentage,
-               requires_accommodations, accommodation_details, created_at, updated_at
-        FROM job_seeker_disabilities
-        WHERE user_id = $1
-        LIMIT 1
-        "#,
+    Ok(Json(references))
+}
+
+/// POST /api/me/references
+/// Add a reference, up to five
+pub async fn create_reference(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<ProfileReferenceRequest>,
+) -> Result<Json<ProfileReference>> {
+    payload.normalize();
+    payload.validate()?;
+
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM profile_references WHERE user_id = $1"#,
         auth_user.id,
     )
-    .fetch_optional(&state.db)
+    .fetch_one(&state.db)
     .await?;
 
-    // Get education records
-    let education = sqlx::query_as!(
-        EducationRecord,
+    if count >= MAX_PROFILE_REFERENCES {
+        return Err(AppError::ValidationError(format!(
+            "A profile can have at most {} references",
+            MAX_PROFILE_REFERENCES
+        )));
+    }
+
+    let reference = sqlx::query_as!(
+        ProfileReference,
         r#"
-        SELECT id, user_id, institution_id, institution_name,
-               level as "level: EducationLevel",
-               field_of_study_id, field_of_study_name, degree_title,
-               status as "status: EducationStatus",
-               start_date, end_date, description, achievements, display_order,
-               created_at, updated_at
-        FROM education_records
-        WHERE user_id = $1
-        ORDER BY start_date DESC, display_order
+        INSERT INTO profile_references (
+            user_id, referee_name, referee_organization, relationship, contact_email
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, user_id, referee_name, referee_organization, relationship, contact_email,
+                  letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+                  created_at, updated_at
         "#,
         auth_user.id,
+        payload.referee_name,
+        payload.referee_organization,
+        payload.relationship,
+        payload.contact_email,
     )
-    .fetch_all(&state.db)
+    .fetch_one(&state.db)
     .await?;
 
-    // Get work experiences
-    let experience = sqlx::query_as!(
-        WorkExperience,
+    Ok(Json(reference))
+}
+
+/// PUT /api/me/references/:id
+/// Replace a reference. A verification stands only while the referee's
+/// details stay the same.
+pub async fn update_reference(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    Json(mut payload): Json<ProfileReferenceRequest>,
+) -> Result<Json<ProfileReference>> {
+    payload.normalize();
+    payload.validate()?;
+
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let reference = sqlx::query_as!(
+        ProfileReference,
         r#"
-        SELECT id, user_id, company_name, industry_id, position_title,
-               work_area_id, position_level_id,
-               employment_type as "employment_type: JobType",
-               is_current, start_date, end_date, region_id, municipality_id,
-               description, achievements, display_order, created_at, updated_at
-        FROM work_experiences
-        WHERE user_id = $1
-        ORDER BY is_current DESC, start_date DESC, display_order
+        WITH current AS (
+            SELECT
+                id,
+                (referee_name, referee_organization, relationship, contact_email)
+                    IS NOT DISTINCT FROM ($3, $4, $5, $6) as unchanged
+            FROM profile_references
+            WHERE id = $1 AND user_id = $2
+        )
+        UPDATE profile_references r
+        SET
+            referee_name = $3,
+            referee_organization = $4,
+            relationship = $5,
+            contact_email = $6,
+            is_verified = r.is_verified AND c.unchanged,
+            verified_by = CASE WHEN c.unchanged THEN r.verified_by END,
+            verified_at = CASE WHEN c.unchanged THEN r.verified_at END
+        FROM current c
+        WHERE r.id = c.id
+        RETURNING r.id, r.user_id, r.referee_name, r.referee_organization, r.relationship,
+                  r.contact_email, r.letter_file_key, r.letter_filename, r.is_verified,
+                  r.verified_by, r.verified_at, r.created_at, r.updated_at
         "#,
+        id,
         auth_user.id,
+        payload.referee_name,
+        payload.referee_organization,
+        payload.relationship,
+        payload.contact_email,
     )
-    .fetch_all(&state.db)
-    .await?;
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Reference not found".to_string()))?;
 
-    // Get skills
-    let skills = sqlx::query_as!(
-        UserSkill,
-        r#"
-        SELECT id, user_id, skill_id, proficiency_level, years_of_experience,
-               created_at, updated_at
-        FROM user_skills
-        WHERE user_id = $1
-        ORDER BY proficiency_level DESC, created_at DESC
-        "#,
+    Ok(Json(reference))
+}
+
+/// DELETE /api/me/references/:id
+/// Delete a reference; its letter is queued for deletion from storage
+pub async fn delete_reference(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MessageResponse>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let result = sqlx::query!(
+        "DELETE FROM profile_references WHERE id = $1 AND user_id = $2",
+        id,
         auth_user.id,
     )
-    .fetch_all(&state.db)
+    .execute(&state.db)
     .await?;
 
-    // Get languages
-    let languages = sqlx::query_as!(
-        UserLanguage,
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Reference not found".to_string()));
+    }
+
+    Ok(Json(MessageResponse::new("Reference deleted")))
+}
+
+/// POST /api/me/references/:id/letter
+/// Upload the recommendation letter (multipart `file`, PDF up to 5 MB),
+/// replacing any earlier one
+pub async fn upload_reference_letter(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<ProfileReference>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let reference = profile_references::find(&state.db, auth_user.id, id).await?;
+    let reference = profile_references::attach_letter(&state, &reference, &mut multipart).await?;
+
+    Ok(Json(reference))
+}
+
+/// DELETE /api/me/references/:id/letter
+/// Remove the recommendation letter and keep the reference
+pub async fn delete_reference_letter(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProfileReference>> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let reference = sqlx::query_as!(
+        ProfileReference,
         r#"
-        SELECT id, user_id, language_id,
-               proficiency as "proficiency: LanguageProficiency",
-               created_at, updated_at
-        FROM user_languages
-        WHERE user_id = $1
-        ORDER BY created_at DESC
+        UPDATE profile_references
+        SET letter_file_key = NULL, letter_filename = NULL
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, referee_name, referee_organization, relationship, contact_email,
+                  letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+                  created_at, updated_at
         "#,
+        id,
         auth_user.id,
     )
-    .fetch_all(&state.db)
-    .await?;
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Reference not found".to_string()))?;
 
-    // Get portfolio
-    let portfolio = sqlx::query_as!(
-        PortfolioItem,
+    Ok(Json(reference))
+}
+
+// ============================================================================
+// FULL PROFILE ENDPOINT
+// ============================================================================
+
+/// GET /api/me/profile/full
+/// Get complete profile with all related data
+pub async fn get_full_profile(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<FullProfileQuery>,
+) -> Result<(HeaderMap, Json<serde_json::Value>)> {
+    if auth_user.user_type != "job_seeker" {
+        return Err(AppError::ForbiddenError(
+            "Only job seekers can access this endpoint".to_string(),
+        ));
+    }
+
+    let fields = FieldSelection::parse(params.fields.as_deref(), FULL_PROFILE_SECTIONS);
+
+    // Get or create profile, also when it's left out of the response
+    let profile = sqlx::query_as!(
+        JobSeekerProfile,
         r#"
-        SELECT id, user_id, title, description, url, file_url, category,
-               completion_date, display_order, created_at, updated_at
-        FROM portfolio_items
-        WHERE user_id = $1
-        ORDER BY display_order, created_at DESC
+        INSERT INTO job_seeker_profiles (user_id)
+        VALUES ($1)
+        ON CONFLICT (user_id) DO UPDATE SET user_id = $1
+        RETURNING user_id, phone, date_of_birth,
+                  gender as "gender: Gender",
+                  marital_status as "marital_status: MaritalStatus",
+                  nationality, national_id, region_id, municipality_id,
+                  address, bio, professional_headline, profile_image_url, cv_url,
+                  completeness_percentage,
+                  availability as "availability: SeekerAvailability",
+                  available_from, share_available_from,
+                  created_at, updated_at
         "#,
         auth_user.id,
     )
-    .fetch_all(&state.db)
+    .fetch_one(&state.db)
     .await?;
 
-    Ok(Json(FullProfileResponse {
+    // Get disability info
+    let disability = if fields.includes("disability") {
+        sqlx::query_as!(
+            JobSeekerDisability,
+            r#"
+            SELECT id, user_id,
+                   category as "category: DisabilityCategory",
+                   description, has_disability_certificate, disability_percentage,
+                   requires_accommodations, accommodation_details, created_at, updated_at
+            FROM job_seeker_disabilities
+            WHERE user_id = $1
+            LIMIT 1
+            "#,
+            auth_user.id,
+        )
+        .fetch_optional(&state.db)
+        .await?
+    } else {
+        None
+    };
+
+    // Get education records
+    let education = if fields.includes("education") {
+        sqlx::query_as!(
+            EducationRecord,
+            r#"
+            SELECT id, user_id, institution_id, institution_name,
+                   level as "level: EducationLevel",
+                   field_of_study_id, field_of_study_name, degree_title,
+                   status as "status: EducationStatus",
+                   start_date, end_date, description, achievements, display_order,
+                   created_at, updated_at
+            FROM education_records
+            WHERE user_id = $1
+            ORDER BY start_date DESC, display_order
+            "#,
+            auth_user.id,
+        )
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    // Get work experiences
+    let experience = if fields.includes("experience") {
+        sqlx::query_as!(
+            WorkExperience,
+            r#"
+            SELECT id, user_id, company_name, industry_id, position_title,
+                   work_area_id, position_level_id,
+                   employment_type as "employment_type: JobType",
+                   is_current, start_date, end_date, region_id, municipality_id,
+                   description, achievements, display_order, created_at, updated_at
+            FROM work_experiences
+            WHERE user_id = $1
+            ORDER BY is_current DESC, start_date DESC, display_order
+            "#,
+            auth_user.id,
+        )
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    // Get skills
+    let skills = if fields.includes("skills") {
+        fetch_user_skills(&state.db, &[auth_user.id])
+            .await?
+            .remove(&auth_user.id)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // Get languages
+    let languages = if fields.includes("languages") {
+        sqlx::query_as!(
+            UserLanguage,
+            r#"
+            SELECT id, user_id, language_id,
+                   proficiency as "proficiency: LanguageProficiency",
+                   created_at, updated_at
+            FROM user_languages
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            auth_user.id,
+        )
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    // Get portfolio
+    let portfolio = if fields.includes("portfolio") {
+        sqlx::query_as!(
+            PortfolioItem,
+            r#"
+            SELECT id, user_id, title, description, url, file_url, category,
+                   completion_date, display_order, created_at, updated_at
+            FROM portfolio_items
+            WHERE user_id = $1
+            ORDER BY display_order, created_at DESC
+            "#,
+            auth_user.id,
+        )
+        .fetch_all(&state.db)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    let response = FullProfileResponse {
         profile,
         disability,
         education,
@@ -1166,5 +1602,7 @@ pub async fn get_full_profile(
         skills,
         languages,
         portfolio,
-    }))
+    };
+
+    Ok((fields.warning_headers(), Json(fields.retain(&response))))
 }
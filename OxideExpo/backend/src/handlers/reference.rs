@@ -1,7 +1,8 @@
+use crate::extract::Query;
 use crate::models::reference::*;
 use crate::AppState;
 use axum::{
-    extract::{Query, State},
+    extract::State,
     http::StatusCode,
     Json,
 };
@@ -34,6 +35,20 @@ pub struct MunicipalityQuery {
     pub region_id: Option<Uuid>,
 }
 
+/// Query parameters for institution autocomplete
+#[derive(Deserialize)]
+pub struct InstitutionQuery {
+    /// Text typed so far; without it every active institution is listed
+    pub q: Option<String>,
+}
+
+/// Most institutions an autocomplete search returns
+const INSTITUTION_SEARCH_LIMIT: i64 = 15;
+
+/// Word similarity a name needs to match a search it doesn't start with;
+/// lower than pg_trgm's default so a mistyped letter still matches
+const INSTITUTION_SIMILARITY_THRESHOLD: f32 = 0.4;
+
 /// Query parameters for skills filtering
 #[derive(Deserialize)]
 pub struct SkillQuery {
@@ -206,17 +221,46 @@ pub async fn list_career_fields(
 /// GET /api/reference/institutions
 pub async fn list_institutions(
     State(state): State<AppState>,
+    Query(query): Query<InstitutionQuery>,
 ) -> Result<Json<ListResponse<Institution>>, (StatusCode, String)> {
-    let institutions = sqlx::query_as::<_, Institution>(
-        r#"
-        SELECT id, name, country_id, institution_type, is_active, created_at
-        FROM institutions
-        WHERE is_active = true
-        ORDER BY name
-        "#,
-    )
-    .fetch_all(&state.db)
-    .await
+    let search = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+    let institutions = if let Some(search) = search {
+        // Names starting with the text first, then names with a word like it;
+        // accents and case are ignored
+        sqlx::query_as::<_, Institution>(
+            r#"
+            SELECT id, name, country_id, institution_type, is_active, created_at
+            FROM institutions
+            WHERE is_active = true
+            AND (
+                starts_with(unaccent(LOWER(name)), unaccent(LOWER($1)))
+                OR word_similarity(unaccent(LOWER($1)), unaccent(LOWER(name))) >= $3
+            )
+            ORDER BY
+                starts_with(unaccent(LOWER(name)), unaccent(LOWER($1))) DESC,
+                word_similarity(unaccent(LOWER($1)), unaccent(LOWER(name))) DESC,
+                name
+            LIMIT $2
+            "#,
+        )
+        .bind(search)
+        .bind(INSTITUTION_SEARCH_LIMIT)
+        .bind(INSTITUTION_SIMILARITY_THRESHOLD)
+        .fetch_all(&state.db)
+        .await
+    } else {
+        sqlx::query_as::<_, Institution>(
+            r#"
+            SELECT id, name, country_id, institution_type, is_active, created_at
+            FROM institutions
+            WHERE is_active = true
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&state.db)
+        .await
+    }
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     Ok(Json(ListResponse::new(institutions)))
@@ -0,0 +1,843 @@
+use axum::{
+    extract::State,
+    Extension, Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::AppError;
+use crate::extract::{Path, Query};
+use crate::middleware::auth::AuthUser;
+use crate::middleware::omil_auth::OmilContext;
+use crate::models::company::MemberRole;
+use crate::models::notification::NotificationEvent;
+use crate::models::omil::{
+    seeker_count_bucket, CreateReferralRequest, JobPartnerOmil, JobPartnership,
+    OmilDirectoryEntry, OmilDirectoryQuery,
+    ReferralCandidate, ReferralRequest, ReferralRequestDetail, ReferralRequestHistoryEntry,
+    ReferralRequestStatus, ReferralRequestWithDetails, ReferralRequestsQuery,
+    RespondReferralRequest, SetJobPartnerRequest,
+};
+use crate::services::matching::MatchingService;
+use crate::services::notifications;
+use crate::utils::normalize::Normalize;
+use crate::AppState;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Get the user's company membership
+async fn get_user_company_membership(
+    db: &sqlx::PgPool,
+    user_id: Uuid,
+) -> Result<(Uuid, MemberRole), AppError> {
+    let member = sqlx::query!(
+        r#"
+        SELECT company_id, role as "role: MemberRole"
+        FROM company_members
+        WHERE user_id = $1 AND is_active = true
+        "#,
+        user_id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::ForbiddenError("User is not a member of any company".to_string()))?;
+
+    Ok((member.company_id, member.role))
+}
+
+/// Company of the calling company member
+async fn require_company_member(state: &AppState, auth_user: &AuthUser) -> Result<Uuid, AppError> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, _role) = get_user_company_membership(&state.db, auth_user.id).await?;
+    Ok(company_id)
+}
+
+/// Company of the calling company owner or admin
+async fn require_company_admin(state: &AppState, auth_user: &AuthUser) -> Result<Uuid, AppError> {
+    if auth_user.user_type != "company_member" {
+        return Err(AppError::ForbiddenError(
+            "Only company members can access this endpoint".to_string(),
+        ));
+    }
+
+    let (company_id, role) = get_user_company_membership(&state.db, auth_user.id).await?;
+    if !matches!(role, MemberRole::Owner | MemberRole::Admin) {
+        return Err(AppError::ForbiddenError(
+            "Only company owners or admins can do this".to_string(),
+        ));
+    }
+    Ok(company_id)
+}
+
+/// Referral requests seen from one company or one OMIL, newest first
+async fn fetch_referral_requests(
+    db: &sqlx::PgPool,
+    company_id: Option<Uuid>,
+    omil_id: Option<Uuid>,
+    request_id: Option<Uuid>,
+    status: Option<ReferralRequestStatus>,
+) -> Result<Vec<ReferralRequestWithDetails>, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            r.id,
+            r.company_id,
+            r.omil_id,
+            r.job_id,
+            r.message,
+            r.status as "status: ReferralRequestStatus",
+            r.response_message,
+            r.requested_by,
+            r.responded_by,
+            r.responded_at,
+            r.created_at,
+            r.updated_at,
+            c.company_name,
+            o.organization_name as omil_name,
+            j.title as job_title,
+            (rb.first_name || ' ' || rb.last_name) as "requested_by_name!"
+        FROM omil_referral_requests r
+        JOIN company_profiles c ON c.id = r.company_id
+        JOIN omil_organizations o ON o.id = r.omil_id
+        JOIN jobs j ON j.id = r.job_id
+        JOIN users rb ON rb.id = r.requested_by
+        WHERE ($1::uuid IS NULL OR r.company_id = $1)
+        AND ($2::uuid IS NULL OR r.omil_id = $2)
+        AND ($3::uuid IS NULL OR r.id = $3)
+        AND ($4::referral_request_status IS NULL OR r.status = $4)
+        ORDER BY r.created_at DESC
+        "#,
+        company_id,
+        omil_id,
+        request_id,
+        status as Option<ReferralRequestStatus>
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ReferralRequestWithDetails {
+            request: ReferralRequest {
+                id: row.id,
+                company_id: row.company_id,
+                omil_id: row.omil_id,
+                job_id: row.job_id,
+                message: row.message,
+                status: row.status,
+                response_message: row.response_message,
+                requested_by: row.requested_by,
+                responded_by: row.responded_by,
+                responded_at: row.responded_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            company_name: row.company_name,
+            omil_name: row.omil_name,
+            job_title: row.job_title,
+            requested_by_name: row.requested_by_name,
+        })
+        .collect())
+}
+
+/// One request with its status history, if the caller's organization is a party to it
+async fn fetch_referral_request_detail(
+    db: &sqlx::PgPool,
+    company_id: Option<Uuid>,
+    omil_id: Option<Uuid>,
+    request_id: Uuid,
+) -> Result<ReferralRequestDetail, AppError> {
+    let details = fetch_referral_requests(db, company_id, omil_id, Some(request_id), None)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound("Referral request not found".to_string()))?;
+
+    let history = sqlx::query_as!(
+        ReferralRequestHistoryEntry,
+        r#"
+        SELECT
+            h.status as "status: ReferralRequestStatus",
+            (u.first_name || ' ' || u.last_name) as "changed_by_name!",
+            h.note,
+            h.created_at
+        FROM omil_referral_request_history h
+        JOIN users u ON u.id = h.changed_by
+        WHERE h.request_id = $1
+        ORDER BY h.created_at, h.id
+        "#,
+        request_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(ReferralRequestDetail { details, history })
+}
+
+// ============================================================================
+// COMPANY ENDPOINTS
+// ============================================================================
+
+/// GET /api/omils
+/// Directory of active OMILs for company members, with aggregate figures only
+pub async fn list_omil_directory(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<OmilDirectoryQuery>,
+) -> Result<Json<Vec<OmilDirectoryEntry>>, AppError> {
+    require_company_member(&state, &auth_user).await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            o.id,
+            o.organization_name,
+            o.region_id,
+            r.name as "region_name?",
+            o.municipality_id,
+            m.name as "municipality_name?",
+            o.email,
+            o.phone,
+            (
+                SELECT COUNT(*) FROM omil_managed_job_seekers mjs
+                WHERE mjs.omil_id = o.id AND mjs.is_active = true AND mjs.transferred_at IS NULL
+            ) as "active_seekers!",
+            (
+                SELECT COUNT(*) FROM omil_managed_job_seekers mjs
+                WHERE mjs.omil_id = o.id
+                AND mjs.placement_outcome = 'placed'
+                AND mjs.placed_at >= DATE_TRUNC('year', CURRENT_DATE)
+            ) as "placements_this_year!"
+        FROM omil_organizations o
+        LEFT JOIN regions r ON r.id = o.region_id
+        LEFT JOIN municipalities m ON m.id = o.municipality_id
+        WHERE o.status = 'active'
+        AND ($1::uuid IS NULL OR o.region_id = $1)
+        AND ($2::uuid IS NULL OR o.municipality_id = $2)
+        ORDER BY r.name NULLS LAST, m.name NULLS LAST, o.organization_name
+        "#,
+        query.region_id,
+        query.municipality_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let directory = rows
+        .into_iter()
+        .map(|row| OmilDirectoryEntry {
+            id: row.id,
+            organization_name: row.organization_name,
+            region_id: row.region_id,
+            region_name: row.region_name,
+            municipality_id: row.municipality_id,
+            municipality_name: row.municipality_name,
+            email: row.email,
+            phone: row.phone,
+            active_seekers: seeker_count_bucket(row.active_seekers).to_string(),
+            placements_this_year: row.placements_this_year,
+        })
+        .collect();
+
+    Ok(Json(directory))
+}
+
+/// POST /api/me/company/referral-requests
+/// Ask an OMIL to refer candidates for one of the company's active jobs
+pub async fn create_referral_request(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(mut payload): Json<CreateReferralRequest>,
+) -> Result<Json<ReferralRequest>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let job = sqlx::query!(
+        r#"
+        SELECT j.title, j.status::text as status, c.company_name
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        WHERE j.id = $1 AND j.company_id = $2
+        "#,
+        payload.job_id,
+        company_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    if job.status.as_deref() != Some("active") {
+        return Err(AppError::ValidationError(
+            "Referrals can only be requested for active jobs".to_string(),
+        ));
+    }
+
+    let omil_active = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM omil_organizations WHERE id = $1 AND status = 'active')",
+        payload.omil_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if !omil_active {
+        return Err(AppError::NotFound("OMIL not found".to_string()));
+    }
+
+    let pending_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM omil_referral_requests
+            WHERE omil_id = $1 AND job_id = $2 AND status = 'pending'
+        )
+        "#,
+        payload.omil_id,
+        payload.job_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if pending_exists {
+        return Err(AppError::ConflictError(
+            "A referral request for this job is already pending with this OMIL".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let request = sqlx::query_as!(
+        ReferralRequest,
+        r#"
+        INSERT INTO omil_referral_requests (company_id, omil_id, job_id, message, requested_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING
+            id,
+            company_id,
+            omil_id,
+            job_id,
+            message,
+            status as "status: ReferralRequestStatus",
+            response_message,
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        company_id,
+        payload.omil_id,
+        payload.job_id,
+        payload.message,
+        auth_user.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO omil_referral_request_history (request_id, status, changed_by)
+        VALUES ($1, 'pending', $2)
+        "#,
+        request.id,
+        auth_user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // Let the OMIL's directors and coordinators know (async, don't wait)
+    let recipients = sqlx::query!(
+        r#"
+        SELECT u.id, u.email as "email!", u.first_name
+        FROM omil_members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.omil_id = $1 AND m.role IN ('director', 'coordinator') AND m.is_active = true
+        "#,
+        payload.omil_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for recipient in recipients {
+        let company_name = job.company_name.clone();
+        let job_title = job.title.clone();
+        notifications::email(
+            &state,
+            recipient.id,
+            NotificationEvent::ReferralRequest,
+            move |email| async move {
+                email
+                    .send_referral_request_email(
+                        &recipient.email,
+                        &recipient.first_name,
+                        &company_name,
+                        &job_title,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(Json(request))
+}
+
+/// GET /api/me/company/referral-requests
+/// Referral requests sent by the company
+pub async fn list_company_referral_requests(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ReferralRequestsQuery>,
+) -> Result<Json<Vec<ReferralRequestWithDetails>>, AppError> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let requests =
+        fetch_referral_requests(&state.db, Some(company_id), None, None, query.status).await?;
+
+    Ok(Json(requests))
+}
+
+/// GET /api/me/company/referral-requests/{id}
+/// A referral request sent by the company, with its status history
+pub async fn get_company_referral_request(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<ReferralRequestDetail>, AppError> {
+    let company_id = require_company_member(&state, &auth_user).await?;
+
+    let detail =
+        fetch_referral_request_detail(&state.db, Some(company_id), None, request_id).await?;
+
+    Ok(Json(detail))
+}
+
+// ============================================================================
+// OMIL ENDPOINTS
+// ============================================================================
+
+/// GET /api/me/omil/referral-requests
+/// Referral requests received by the current OMIL
+pub async fn list_omil_referral_requests(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Query(query): Query<ReferralRequestsQuery>,
+) -> Result<Json<Vec<ReferralRequestWithDetails>>, AppError> {
+    let requests = fetch_referral_requests(
+        &state.db,
+        None,
+        Some(omil_ctx.organization.id),
+        None,
+        query.status,
+    )
+    .await?;
+
+    Ok(Json(requests))
+}
+
+/// GET /api/me/omil/referral-requests/{id}
+/// A received referral request, with its status history
+pub async fn get_omil_referral_request(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<ReferralRequestDetail>, AppError> {
+    let detail =
+        fetch_referral_request_detail(&state.db, None, Some(omil_ctx.organization.id), request_id)
+            .await?;
+
+    Ok(Json(detail))
+}
+
+/// Move a pending request to accepted or declined and tell the requester
+async fn answer_referral_request(
+    state: &AppState,
+    omil_ctx: &OmilContext,
+    request_id: Uuid,
+    status: ReferralRequestStatus,
+    message: Option<String>,
+) -> Result<ReferralRequest, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let request = sqlx::query_as!(
+        ReferralRequest,
+        r#"
+        UPDATE omil_referral_requests
+        SET status = $1, response_message = $2, responded_by = $3, responded_at = NOW()
+        WHERE id = $4 AND omil_id = $5 AND status = 'pending'
+        RETURNING
+            id,
+            company_id,
+            omil_id,
+            job_id,
+            message,
+            status as "status: ReferralRequestStatus",
+            response_message,
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        status as ReferralRequestStatus,
+        message,
+        omil_ctx.member.user_id,
+        request_id,
+        omil_ctx.organization.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Pending referral request not found".to_string()))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO omil_referral_request_history (request_id, status, changed_by, note)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        request.id,
+        status as ReferralRequestStatus,
+        omil_ctx.member.user_id,
+        request.response_message
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let requester = sqlx::query!(
+        r#"
+        SELECT u.email as "email!", u.first_name, j.title as job_title
+        FROM users u
+        JOIN jobs j ON j.id = $2
+        WHERE u.id = $1
+        "#,
+        request.requested_by,
+        request.job_id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    if let Some(requester) = requester {
+        let omil_name = omil_ctx.organization.organization_name.clone();
+        let message = request.response_message.clone();
+        notifications::email(
+            state,
+            request.requested_by,
+            NotificationEvent::ReferralRequest,
+            move |email| async move {
+                email
+                    .send_referral_request_answered_email(
+                        &requester.email,
+                        &requester.first_name,
+                        &omil_name,
+                        &requester.job_title,
+                        status == ReferralRequestStatus::Accepted,
+                        message.as_deref(),
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(request)
+}
+
+/// POST /api/me/omil/referral-requests/{id}/accept
+/// Accept a referral request (coordinator or director)
+pub async fn accept_referral_request(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(request_id): Path<Uuid>,
+    Json(mut payload): Json<RespondReferralRequest>,
+) -> Result<Json<ReferralRequest>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let request = answer_referral_request(
+        &state,
+        &omil_ctx,
+        request_id,
+        ReferralRequestStatus::Accepted,
+        payload.message,
+    )
+    .await?;
+
+    Ok(Json(request))
+}
+
+/// POST /api/me/omil/referral-requests/{id}/decline
+/// Decline a referral request (coordinator or director)
+pub async fn decline_referral_request(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(request_id): Path<Uuid>,
+    Json(mut payload): Json<RespondReferralRequest>,
+) -> Result<Json<ReferralRequest>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let request = answer_referral_request(
+        &state,
+        &omil_ctx,
+        request_id,
+        ReferralRequestStatus::Declined,
+        payload.message,
+    )
+    .await?;
+
+    Ok(Json(request))
+}
+
+/// POST /api/me/omil/referral-requests/{id}/withdraw
+/// Withdraw from an accepted request (coordinator or director). A job
+/// co-published with the OMIL loses its partner; applications are kept.
+pub async fn withdraw_referral_request(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(request_id): Path<Uuid>,
+    Json(mut payload): Json<RespondReferralRequest>,
+) -> Result<Json<ReferralRequest>, AppError> {
+    payload.normalize();
+    payload.validate()?;
+
+    let omil_id = omil_ctx.organization.id;
+    let mut tx = state.db.begin().await?;
+
+    let request = sqlx::query_as!(
+        ReferralRequest,
+        r#"
+        UPDATE omil_referral_requests
+        SET status = 'withdrawn'
+        WHERE id = $1 AND omil_id = $2 AND status = 'accepted'
+        RETURNING
+            id,
+            company_id,
+            omil_id,
+            job_id,
+            message,
+            status as "status: ReferralRequestStatus",
+            response_message,
+            requested_by,
+            responded_by,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        request_id,
+        omil_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Accepted referral request not found".to_string()))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO omil_referral_request_history (request_id, status, changed_by, note)
+        VALUES ($1, 'withdrawn', $2, $3)
+        "#,
+        request.id,
+        omil_ctx.member.user_id,
+        payload.message
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Another accepted request for the same job keeps the partnership
+    sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET partner_omil_id = NULL
+        WHERE id = $1 AND partner_omil_id = $2
+        AND NOT EXISTS (
+            SELECT 1 FROM omil_referral_requests
+            WHERE job_id = $1 AND omil_id = $2 AND status = 'accepted'
+        )
+        "#,
+        request.job_id,
+        omil_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(request))
+}
+
+/// GET /api/me/omil/referral-requests/{id}/candidates
+/// The OMIL's own managed seekers ranked against the job of an accepted request
+pub async fn list_referral_candidates(
+    State(state): State<AppState>,
+    Extension(omil_ctx): Extension<OmilContext>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<Vec<ReferralCandidate>>, AppError> {
+    let omil_id = omil_ctx.organization.id;
+
+    let request = sqlx::query!(
+        r#"
+        SELECT job_id, status as "status: ReferralRequestStatus"
+        FROM omil_referral_requests
+        WHERE id = $1 AND omil_id = $2
+        "#,
+        request_id,
+        omil_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Referral request not found".to_string()))?;
+
+    if request.status != ReferralRequestStatus::Accepted {
+        return Err(AppError::ValidationError(
+            "Candidates are only available for accepted referral requests".to_string(),
+        ));
+    }
+
+    // Seekers transferred out now belong to the receiving OMIL
+    let seekers = sqlx::query!(
+        r#"
+        SELECT
+            mjs.id as managed_id,
+            mjs.job_seeker_id,
+            (u.first_name || ' ' || u.last_name) as "job_seeker_name!",
+            EXISTS(
+                SELECT 1 FROM job_applications ja
+                WHERE ja.job_id = $2 AND ja.applicant_id = mjs.job_seeker_id
+            ) as "has_applied!"
+        FROM omil_managed_job_seekers mjs
+        JOIN users u ON u.id = mjs.job_seeker_id
+        WHERE mjs.omil_id = $1
+        AND mjs.is_active = true
+        AND mjs.transferred_at IS NULL
+        AND u.account_status IN ('active', 'managed_offline')
+        "#,
+        omil_id,
+        request.job_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let seeker_ids: Vec<Uuid> = seekers.iter().map(|s| s.job_seeker_id).collect();
+    let mut scores =
+        MatchingService::calculate_match_scores(&state.db, request.job_id, &seeker_ids).await?;
+
+    let mut candidates = Vec::with_capacity(seekers.len());
+    for seeker in seekers {
+        let Some(score_breakdown) = scores.remove(&seeker.job_seeker_id) else {
+            continue;
+        };
+
+        candidates.push(ReferralCandidate {
+            managed_id: seeker.managed_id,
+            job_seeker_id: seeker.job_seeker_id,
+            job_seeker_name: seeker.job_seeker_name,
+            match_score: score_breakdown.total_score,
+            score_breakdown,
+            has_applied: seeker.has_applied,
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        b.match_score
+            .cmp(&a.match_score)
+            .then_with(|| a.job_seeker_name.cmp(&b.job_seeker_name))
+    });
+
+    Ok(Json(candidates))
+}
+
+// ============================================================================
+// V13: CO-PUBLISHED JOBS
+// ============================================================================
+
+/// Partner OMIL of a job, if it has an active one
+pub async fn fetch_job_partner(
+    db: &sqlx::PgPool,
+    job_id: Uuid,
+) -> Result<Option<JobPartnerOmil>, AppError> {
+    let partner = sqlx::query_as!(
+        JobPartnerOmil,
+        r#"
+        SELECT o.id as omil_id, o.organization_name
+        FROM jobs j
+        JOIN omil_organizations o ON o.id = j.partner_omil_id
+        WHERE j.id = $1 AND o.status = 'active'
+        "#,
+        job_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(partner)
+}
+
+/// PUT /api/me/jobs/{id}/partner-omil
+/// Co-publish a job with an OMIL that accepted a referral request for it, or
+/// end the partnership (owner/admin only)
+pub async fn set_job_partner(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<Uuid>,
+    Json(payload): Json<SetJobPartnerRequest>,
+) -> Result<Json<JobPartnership>, AppError> {
+    let company_id = require_company_admin(&state, &auth_user).await?;
+
+    let owns_job = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1 AND company_id = $2)",
+        job_id,
+        company_id
+    )
+    .fetch_one(&state.db)
+    .await?
+    .unwrap_or(false);
+
+    if !owns_job {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    if let Some(omil_id) = payload.omil_id {
+        let accepted = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM omil_referral_requests r
+                JOIN omil_organizations o ON o.id = r.omil_id
+                WHERE r.job_id = $1 AND r.omil_id = $2
+                AND r.status = 'accepted' AND o.status = 'active'
+            )
+            "#,
+            job_id,
+            omil_id
+        )
+        .fetch_one(&state.db)
+        .await?
+        .unwrap_or(false);
+
+        if !accepted {
+            return Err(AppError::ValidationError(
+                "The OMIL must accept a referral request for this job before partnering on it"
+                    .to_string(),
+            ));
+        }
+    }
+
+    sqlx::query!(
+        "UPDATE jobs SET partner_omil_id = $2 WHERE id = $1",
+        job_id,
+        payload.omil_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(JobPartnership {
+        job_id,
+        partner_omil: fetch_job_partner(&state.db, job_id).await?,
+    }))
+}
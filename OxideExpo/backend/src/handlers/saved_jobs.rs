@@ -1,16 +1,18 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     Extension, Json,
 };
 use uuid::Uuid;
 
 use crate::{
     error::{AppError, Result},
+    extract::{Path, Query},
     middleware::AuthUser,
     models::{
-        job::{JobType, WorkModality, PublicJobListing},
+        job::{schedule_display, JobType, PublicJobListing, ShiftType, WorkModality},
         saved_job::*,
     },
+    services::matching::MatchingService,
     AppState,
 };
 
@@ -51,11 +53,15 @@ pub async fn list_saved_jobs(
             sj.user_id,
             sj.job_id,
             sj.created_at as saved_at,
+            sj.match_score_at_save,
+            ms.total_score as "current_match_score?",
             j.id, j.title, j.description, j.responsibilities,
             j.job_type as "job_type: JobType",
             j.industry_id, j.work_area_id, j.position_level_id,
             j.work_modality as "work_modality: WorkModality",
-            j.work_schedule,
+            j.schedule_details,
+            j.shift_type as "shift_type: ShiftType",
+            j.weekly_hours,
             j.region_id, j.municipality_id,
             COALESCE(j.is_remote_allowed, false) as "is_remote_allowed!",
             j.education_level, j.years_experience_min, j.years_experience_max,
@@ -68,6 +74,7 @@ pub async fn list_saved_jobs(
         FROM saved_jobs sj
         JOIN jobs j ON j.id = sj.job_id
         JOIN company_profiles cp ON cp.id = j.company_id
+        LEFT JOIN job_match_scores ms ON ms.job_id = sj.job_id AND ms.user_id = sj.user_id
         WHERE sj.user_id = $1 AND j.status = 'active'
         ORDER BY sj.created_at DESC
         LIMIT $2 OFFSET $3
@@ -82,6 +89,12 @@ pub async fn list_saved_jobs(
     let saved_jobs: Vec<SavedJobWithDetails> = rows
         .into_iter()
         .map(|row| SavedJobWithDetails {
+            score_improved: matches!(
+                (row.match_score_at_save, row.current_match_score),
+                (Some(at_save), Some(current)) if current > at_save
+            ),
+            match_score_at_save: row.match_score_at_save,
+            current_match_score: row.current_match_score,
             saved_job: SavedJob {
                 id: row.saved_job_id,
                 user_id: row.user_id,
@@ -98,7 +111,14 @@ pub async fn list_saved_jobs(
                 work_area_id: row.work_area_id,
                 position_level_id: row.position_level_id,
                 work_modality: row.work_modality,
-                work_schedule: row.work_schedule,
+                schedule_display: schedule_display(
+                    row.shift_type,
+                    row.weekly_hours,
+                    row.schedule_details.as_deref(),
+                ),
+                schedule_details: row.schedule_details,
+                shift_type: row.shift_type,
+                weekly_hours: row.weekly_hours,
                 region_id: row.region_id,
                 municipality_id: row.municipality_id,
                 is_remote_allowed: row.is_remote_allowed,
@@ -160,15 +180,21 @@ pub async fn save_job(
         return Err(AppError::ValidationError("Job already saved".to_string()));
     }
 
+    // The score at save time is what the "score improved" badge compares
+    // the latest score with
+    let breakdown = MatchingService::calculate_match_score(&state.db, job_id, auth_user.id).await?;
+    MatchingService::save_match_score(&state.db, job_id, auth_user.id, &breakdown).await?;
+
     // Save the job
     let saved_job = sqlx::query!(
         r#"
-        INSERT INTO saved_jobs (user_id, job_id)
-        VALUES ($1, $2)
+        INSERT INTO saved_jobs (user_id, job_id, match_score_at_save)
+        VALUES ($1, $2, $3)
         RETURNING id
         "#,
         auth_user.id,
         job_id,
+        breakdown.total_score,
     )
     .fetch_one(&state.db)
     .await?;
@@ -0,0 +1,43 @@
+use axum::{extract::State, http::header, response::IntoResponse, Json};
+
+use crate::{
+    error::{AppError, Result},
+    services::{omil_coverage, public_stats},
+    AppState,
+};
+
+/// GET /api/stats/public
+/// Aggregate platform numbers for the landing page, cached for 15 minutes
+pub async fn get_public_stats(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    if !state.config.public_stats_enabled {
+        return Err(AppError::NotFound("Not found".to_string()));
+    }
+
+    let mut redis = state.redis.clone();
+    let stats = public_stats::public_stats(&state.db, &mut redis).await?;
+
+    Ok((
+        [(
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", public_stats::CACHE_TTL_SECONDS),
+        )],
+        Json(stats),
+    ))
+}
+
+/// GET /api/stats/omil-coverage
+/// Municipalities with an OMIL on the platform, active jobs and a coarse
+/// count of job seekers, grouped by region for the landing page map.
+/// Cached for an hour.
+pub async fn get_omil_coverage(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    let mut redis = state.redis.clone();
+    let coverage = omil_coverage::omil_coverage(&state.db, &mut redis).await?;
+
+    Ok((
+        [(
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", omil_coverage::CACHE_TTL_SECONDS),
+        )],
+        Json(coverage),
+    ))
+}
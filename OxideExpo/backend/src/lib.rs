@@ -6,14 +6,18 @@ pub mod services;
 
 // V2: Authentication
 pub mod error;
+pub mod extract;
 pub mod middleware;
 pub mod utils;
 
+pub mod routes;
+
 use aws_sdk_s3::Client as S3Client;
 use config::Config;
 use redis::aio::ConnectionManager;
+use services::admin_events::AdminEvents;
 use services::email::EmailService;
-use services::storage::StorageService;
+use services::storage::StorageBackend;
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -32,11 +36,14 @@ pub struct AppState {
     /// Email service for sending transactional emails
     pub email: EmailService,
 
-    /// V9: Storage service using object_store for S3/MinIO/R2 compatibility
-    pub storage: Option<StorageService>,
+    /// V9: File storage, with the driver selected by `STORAGE_DRIVER`
+    pub storage: Arc<dyn StorageBackend>,
 
     /// Application configuration
     pub config: Arc<Config>,
+
+    /// Live moderation queue counts for connected admin dashboards
+    pub admin_events: AdminEvents,
 }
 
 impl AppState {
@@ -52,6 +59,26 @@ impl AppState {
         tracing::info!("Running database migrations...");
         sqlx::migrate!("./migrations").run(&db).await?;
 
+        Self::from_pool(config, db).await
+    }
+
+    /// Builds the state around an already migrated database pool.
+    /// Integration tests use this with the per-test database from `#[sqlx::test]`.
+    pub async fn from_pool(config: Config, db: PgPool) -> Result<Self, Box<dyn std::error::Error>> {
+        // Reports convert times with `AT TIME ZONE`, which would only fail on
+        // an unknown zone once a report is requested
+        let timezone_known = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+        )
+        .bind(&config.platform_timezone)
+        .fetch_one(&db)
+        .await?;
+        if !timezone_known {
+            return Err(Box::new(config::ConfigError::InvalidValue(
+                "PLATFORM_TIMEZONE".to_string(),
+            )));
+        }
+
         // Initialize Redis connection
         tracing::info!("Connecting to Redis...");
         let redis_client = redis::Client::open(config.redis_url.clone())?;
@@ -79,28 +106,12 @@ impl AppState {
         tracing::info!("Initializing email service...");
         let email = EmailService::new(&config)?;
 
-        // Initialize storage service (object_store based)
-        tracing::info!("Initializing storage service...");
-        let storage = match StorageService::new(
-            &config.s3_endpoint,
-            &config.s3_bucket,
-            &config.s3_access_key,
-            &config.s3_secret_key,
-            &config.s3_region,
-            Some(config.s3_public_url.clone()),
-        ) {
-            Ok(s) => {
-                tracing::info!("Storage service initialized successfully");
-                Some(s)
-            }
-            Err(e) => {
-                tracing::warn!("Storage service initialization failed: {:?}. File uploads will be unavailable.", e);
-                None
-            }
-        };
-
         let config = Arc::new(config);
 
+        // Initialize file storage
+        tracing::info!("Initializing {:?} storage...", config.storage_driver);
+        let storage = services::storage::from_config(&config)?;
+
         Ok(Self {
             db,
             redis,
@@ -108,6 +119,7 @@ impl AppState {
             email,
             storage,
             config,
+            admin_events: AdminEvents::new(),
         })
     }
 }
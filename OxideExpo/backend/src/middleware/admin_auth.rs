@@ -7,6 +7,7 @@ use axum::{
 
 use crate::middleware::auth::AuthUser;
 use crate::models::admin::{Admin, AdminRole};
+use crate::utils::redact;
 use crate::AppState;
 
 /// Middleware that requires the authenticated user to be an admin (any role)
@@ -46,7 +47,10 @@ pub async fn require_admin(
     .fetch_optional(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Database error checking admin status: {:?}", e);
+        tracing::error!(
+            "Database error checking admin status: {}",
+            redact::db_error(&e)
+        );
         StatusCode::INTERNAL_SERVER_ERROR
     })?
     .ok_or_else(|| {
@@ -97,7 +101,10 @@ pub async fn require_moderator_or_above(
     .fetch_optional(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Database error checking admin status: {:?}", e);
+        tracing::error!(
+            "Database error checking admin status: {}",
+            redact::db_error(&e)
+        );
         StatusCode::INTERNAL_SERVER_ERROR
     })?
     .ok_or_else(|| {
@@ -158,7 +165,10 @@ pub async fn require_super_admin(
     .fetch_optional(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Database error checking admin status: {:?}", e);
+        tracing::error!(
+            "Database error checking admin status: {}",
+            redact::db_error(&e)
+        );
         StatusCode::INTERNAL_SERVER_ERROR
     })?
     .ok_or_else(|| {
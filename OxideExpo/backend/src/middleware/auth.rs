@@ -1,13 +1,15 @@
 use axum::{
     extract::{Request, State},
-    http::{header, StatusCode},
+    http::{header, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use chrono::{Duration, Utc};
 use redis::AsyncCommands;
 use uuid::Uuid;
 
-use crate::utils::jwt;
+use crate::models::api_token::SCOPE_OMIL_READ;
+use crate::utils::{jwt, redact};
 use crate::AppState;
 
 /// Authenticated user information extracted from JWT
@@ -20,6 +22,46 @@ pub struct AuthUser {
     pub jti: String,
     /// If this is an impersonation session, contains the OMIL actor ID
     pub impersonator_id: Option<Uuid>,
+    /// Scopes of the personal API token used; None for session tokens, which
+    /// are not limited
+    pub scopes: Option<Vec<String>>,
+    /// Set when the request comes from an OMIL kiosk token
+    pub kiosk: Option<KioskBinding>,
+}
+
+/// The kiosk session and organization a kiosk token was issued for
+#[derive(Clone, Debug)]
+pub struct KioskBinding {
+    pub session_id: Uuid,
+    pub omil_id: Uuid,
+}
+
+impl AuthUser {
+    /// Whether the credentials used may call this endpoint
+    pub fn may_access(&self, method: &Method, path: &str) -> bool {
+        match &self.scopes {
+            None => true,
+            Some(scopes) => scopes_allow(scopes, method, path),
+        }
+    }
+}
+
+/// `omil:read` covers the GET endpoints under /api/me/omil, exports included,
+/// except minting impersonation tokens. `omil:kiosk` covers registering job
+/// seekers on behalf of the OMIL and reading reference data. Nothing else
+/// accepts restricted tokens.
+fn scopes_allow(scopes: &[String], method: &Method, path: &str) -> bool {
+    let omil_read = *method == Method::GET
+        && (path == "/api/me/omil" || path.starts_with("/api/me/omil/"))
+        && !path.ends_with("/impersonate");
+    let kiosk = (*method == Method::POST && path == "/api/me/omil/job-seekers")
+        || (*method == Method::GET && path.starts_with("/api/reference/"));
+
+    scopes.iter().any(|scope| match scope.as_str() {
+        SCOPE_OMIL_READ => omil_read,
+        jwt::KIOSK_SCOPE => kiosk,
+        _ => false,
+    })
 }
 
 /// Middleware that requires a valid JWT token
@@ -30,52 +72,106 @@ pub async fn require_auth(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extract Bearer token from Authorization header
-    let auth_header = request
+    let Some(token) = bearer_token(&request) else {
+        tracing::debug!("Missing or invalid Authorization header");
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let auth_user = authenticate(&state, &token).await?;
+    if !auth_user.may_access(request.method(), request.uri().path()) {
+        tracing::debug!("API token {} lacks the scope for {}", auth_user.jti, request.uri());
+        return Err(StatusCode::FORBIDDEN);
+    }
+    // Requests made on someone's behalf are not their activity
+    if auth_user.impersonator_id.is_none() && auth_user.kiosk.is_none() {
+        touch_last_active(&state, auth_user.id).await;
+    }
+    request.extensions_mut().insert(auth_user);
+
+    Ok(next.run(request).await)
+}
+
+/// Middleware for public routes that personalize results when the caller is
+/// signed in. Requests without a valid token continue anonymously.
+pub async fn optional_auth(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(token) = bearer_token(&request) {
+        if let Ok(auth_user) = authenticate(&state, &token).await {
+            if auth_user.may_access(request.method(), request.uri().path()) {
+                request.extensions_mut().insert(auth_user);
+            }
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Extract the Bearer token from the Authorization header
+pub(crate) fn bearer_token(request: &Request) -> Option<String> {
+    request
         .headers()
         .get(header::AUTHORIZATION)
-        .and_then(|header| header.to_str().ok());
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .map(str::to_string)
+}
 
-    let token = match auth_header {
-        Some(header) if header.starts_with("Bearer ") => header.trim_start_matches("Bearer "),
-        _ => {
-            tracing::debug!("Missing or invalid Authorization header");
-            return Err(StatusCode::UNAUTHORIZED);
-        }
-    };
+/// Resolve a token to the user it was issued for
+pub(crate) async fn authenticate(state: &AppState, token: &str) -> Result<AuthUser, StatusCode> {
+    if token.starts_with(jwt::API_TOKEN_PREFIX) {
+        return authenticate_api_token(state, token).await;
+    }
 
     // First, try to verify as a regular access token
     if let Ok(claims) = jwt::verify_access_token(token, &state.config) {
         // Check if token is blacklisted in Redis
-        let blacklist_key = format!("token:blacklist:{}", claims.jti);
         let mut redis_conn = state.redis.clone();
 
-        let is_blacklisted: bool = redis_conn.exists(&blacklist_key).await.unwrap_or(false);
-
-        if is_blacklisted {
+        if is_blacklisted(&mut redis_conn, &claims.jti).await {
             tracing::debug!("Token {} is blacklisted", claims.jti);
             return Err(StatusCode::UNAUTHORIZED);
         }
 
+        record_key_usage(&mut redis_conn, token, &claims.jti).await;
+
         // Parse user ID as UUID
         let user_id = match claims.user_id() {
             Ok(id) => id,
             Err(e) => {
-                tracing::error!("Invalid user ID in JWT: {:?}", e);
+                tracing::error!("Invalid user ID in JWT: {}", e);
                 return Err(StatusCode::UNAUTHORIZED);
             }
         };
 
-        // Insert AuthUser into request extensions for handlers to access
-        request.extensions_mut().insert(AuthUser {
+        // Tokens issued before the user's last global revocation are rejected
+        let current_version = current_token_version(&state.db, &mut redis_conn, user_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "Failed to load token version for {}: {}",
+                    user_id,
+                    redact::db_error(&e)
+                );
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        if current_version != Some(claims.ver) {
+            tracing::debug!("Token {} has a stale token version", claims.jti);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+
+        return Ok(AuthUser {
             id: user_id,
             email: claims.email,
             user_type: claims.user_type,
             jti: claims.jti,
             impersonator_id: None,
+            scopes: None,
+            kiosk: None,
         });
-
-        return Ok(next.run(request).await);
     }
 
     // If regular token verification failed, try impersonation token (V10)
@@ -106,7 +202,7 @@ pub async fn require_auth(
             return Err(StatusCode::UNAUTHORIZED);
         }
 
-        // Get the job seeker's email
+        // Get the job seeker's email, blank for seekers managed offline
         let job_seeker_id = match impersonation_claims.job_seeker_id() {
             Ok(id) => id,
             Err(_) => {
@@ -124,18 +220,12 @@ pub async fn require_auth(
         .ok()
         .flatten();
 
-        let email = user.map(|u| u.email).unwrap_or_default();
+        let email = user.and_then(|u| u.email).unwrap_or_default();
 
         let impersonator_id = impersonation_claims.omil_actor_id().ok();
 
-        // Insert AuthUser as the job seeker, with impersonator tracked
-        request.extensions_mut().insert(AuthUser {
-            id: job_seeker_id,
-            email,
-            user_type: "job_seeker".to_string(),
-            jti: impersonation_claims.jti,
-            impersonator_id,
-        });
+        let mut redis_conn = state.redis.clone();
+        record_key_usage(&mut redis_conn, token, &impersonation_claims.jti).await;
 
         tracing::info!(
             "OMIL impersonation: actor {:?} acting as job seeker {}",
@@ -143,14 +233,147 @@ pub async fn require_auth(
             job_seeker_id
         );
 
-        return Ok(next.run(request).await);
+        // Authenticated as the job seeker, with impersonator tracked
+        return Ok(AuthUser {
+            id: job_seeker_id,
+            email,
+            user_type: "job_seeker".to_string(),
+            jti: impersonation_claims.jti,
+            impersonator_id,
+            scopes: None,
+            kiosk: None,
+        });
+    }
+
+    if let Ok(kiosk_claims) = jwt::verify_kiosk_token(token, &state.config) {
+        return authenticate_kiosk_token(state, token, kiosk_claims).await;
     }
 
-    tracing::debug!("JWT verification failed for both regular and impersonation tokens");
+    tracing::debug!("JWT verification failed for regular, impersonation and kiosk tokens");
     Err(StatusCode::UNAUTHORIZED)
 }
 
-/// Blacklists a JWT token by storing its JTI in Redis with TTL
+/// Whether a JWT ID has been blacklisted. Redis errors count as not blacklisted.
+async fn is_blacklisted(redis: &mut redis::aio::ConnectionManager, jti: &str) -> bool {
+    let blacklist_key = format!("token:blacklist:{}", jti);
+    redis.exists(&blacklist_key).await.unwrap_or(false)
+}
+
+/// Resolve a kiosk token to the OMIL member who opened the kiosk. Revoking the
+/// kiosk blacklists its jti, and revoking all of the opener's sessions bumps
+/// the token version, so both apply to the kiosk at once.
+async fn authenticate_kiosk_token(
+    state: &AppState,
+    token: &str,
+    claims: jwt::KioskClaims,
+) -> Result<AuthUser, StatusCode> {
+    let (Ok(user_id), Ok(omil_id), Ok(session_id)) = (
+        claims.user_id(),
+        claims.omil_id(),
+        claims.kiosk_session_id(),
+    ) else {
+        tracing::debug!("Malformed IDs in kiosk token {}", claims.jti);
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let mut redis_conn = state.redis.clone();
+    if is_blacklisted(&mut redis_conn, &claims.jti).await {
+        tracing::debug!("Kiosk token {} is blacklisted", claims.jti);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    record_key_usage(&mut redis_conn, token, &claims.jti).await;
+
+    let current_version = current_token_version(&state.db, &mut redis_conn, user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to load token version for {}: {}",
+                user_id,
+                redact::db_error(&e)
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if current_version != Some(claims.ver) {
+        tracing::debug!("Kiosk token {} has a stale token version", claims.jti);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let email = sqlx::query_scalar!(r#"SELECT email as "email!" FROM users WHERE id = $1"#, user_id)
+        .fetch_optional(&state.db)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    Ok(AuthUser {
+        id: user_id,
+        email,
+        user_type: "omil_member".to_string(),
+        jti: claims.jti,
+        impersonator_id: None,
+        scopes: Some(vec![jwt::KIOSK_SCOPE.to_string()]),
+        kiosk: Some(KioskBinding { session_id, omil_id }),
+    })
+}
+
+/// How stale last_used_at may get before a request refreshes it
+const API_TOKEN_USAGE_RESOLUTION_HOURS: i64 = 1;
+
+/// Resolve a personal API token. There is no jti to blacklist: revocation and
+/// expiry are checked in the database on every request, so they apply at once.
+async fn authenticate_api_token(state: &AppState, token: &str) -> Result<AuthUser, StatusCode> {
+    let api_token = sqlx::query!(
+        r#"
+        SELECT t.id, t.user_id, t.scopes, t.last_used_at, u.email as "email!", u.user_type::text as "user_type!"
+        FROM api_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND (t.expires_at IS NULL OR t.expires_at > NOW())
+          AND u.account_status = 'active'
+        "#,
+        jwt::hash_token(token)
+    )
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to look up API token: {}", redact::db_error(&e));
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or_else(|| {
+        tracing::debug!("Unknown, revoked or expired API token");
+        StatusCode::UNAUTHORIZED
+    })?;
+
+    // Integrations poll; recording every request would mean a write per call
+    let stale_before = Utc::now() - Duration::hours(API_TOKEN_USAGE_RESOLUTION_HOURS);
+    if api_token.last_used_at.is_none_or(|t| t < stale_before) {
+        let result = sqlx::query!(
+            "UPDATE api_tokens SET last_used_at = NOW() WHERE id = $1",
+            api_token.id
+        )
+        .execute(&state.db)
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to record API token usage for {}: {}", api_token.id, e);
+        }
+    }
+
+    Ok(AuthUser {
+        id: api_token.user_id,
+        email: api_token.email,
+        user_type: api_token.user_type,
+        jti: api_token.id.to_string(),
+        impersonator_id: None,
+        scopes: Some(api_token.scopes),
+        kiosk: None,
+    })
+}
+
+/// Blacklists a single JWT token by storing its JTI in Redis with TTL.
+/// To revoke every token of a user, bump users.token_version instead.
 pub async fn blacklist_token(
     redis: &mut redis::aio::ConnectionManager,
     jti: &str,
@@ -159,3 +382,169 @@ pub async fn blacklist_token(
     let key = format!("token:blacklist:{}", jti);
     redis.set_ex(&key, "1", ttl_seconds as u64).await
 }
+
+/// Seconds a user's token version is cached before it is re-read from the database
+pub const TOKEN_VERSION_CACHE_SECONDS: u64 = 60;
+
+pub fn token_version_key(user_id: Uuid) -> String {
+    format!("user:token_version:{}", user_id)
+}
+
+/// The user's current token_version, cached in Redis. The database stays the
+/// source of truth, so a Redis flush cannot bring revoked tokens back.
+/// Returns None when the user no longer exists.
+async fn current_token_version(
+    db: &sqlx::PgPool,
+    redis: &mut redis::aio::ConnectionManager,
+    user_id: Uuid,
+) -> Result<Option<i32>, sqlx::Error> {
+    let key = token_version_key(user_id);
+
+    match redis.get::<_, Option<i32>>(&key).await {
+        Ok(Some(version)) => return Ok(Some(version)),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read cached token version: {}", e),
+    }
+
+    let version = sqlx::query_scalar!("SELECT token_version FROM users WHERE id = $1", user_id)
+        .fetch_optional(db)
+        .await?;
+
+    if let Some(version) = version {
+        let result: Result<(), redis::RedisError> = redis
+            .set_ex(&key, version, TOKEN_VERSION_CACHE_SECONDS)
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache token version: {}", e);
+        }
+    }
+
+    Ok(version)
+}
+
+/// Drops the cached token version after a bump so revocation applies at once
+/// instead of when the cache expires. Failures are only logged.
+pub async fn forget_token_version(redis: &mut redis::aio::ConnectionManager, user_id: Uuid) {
+    let result: Result<(), redis::RedisError> = redis.del(token_version_key(user_id)).await;
+    if let Err(e) = result {
+        tracing::warn!("Failed to clear cached token version for {}: {}", user_id, e);
+    }
+}
+
+/// Seconds between writes of a user's last_active_at
+pub const LAST_ACTIVE_DEBOUNCE_SECONDS: u64 = 3600;
+
+pub fn last_active_key(user_id: Uuid) -> String {
+    format!("user:last_active:{}", user_id)
+}
+
+/// Stamps users.last_active_at, at most once per debounce window. The window
+/// is claimed with SET NX in Redis, so further requests in it cost a Redis
+/// round trip but no database write. Failures are only logged.
+async fn touch_last_active(state: &AppState, user_id: Uuid) {
+    let mut redis_conn = state.redis.clone();
+    let claimed: Result<Option<String>, redis::RedisError> = redis::cmd("SET")
+        .arg(last_active_key(user_id))
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(LAST_ACTIVE_DEBOUNCE_SECONDS)
+        .query_async(&mut redis_conn)
+        .await;
+
+    match claimed {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to debounce last activity of {}: {}", user_id, e);
+            return;
+        }
+    }
+
+    let result = sqlx::query!(
+        "UPDATE users SET last_active_at = NOW() WHERE id = $1",
+        user_id
+    )
+    .execute(&state.db)
+    .await;
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to record last activity of {}: {}",
+            user_id,
+            redact::db_error(&e)
+        );
+    }
+}
+
+/// Hours of token usage kept per signing key
+pub const KEY_USAGE_WINDOW_HOURS: i64 = 24;
+
+fn key_usage_key(kid: &str, hour: i64) -> String {
+    format!("jwt:kid:{}:{}", kid, hour)
+}
+
+/// Counts the token under its signing key in an hourly HyperLogLog, so operators
+/// can tell when a retired key is no longer in use. Failures are only logged.
+async fn record_key_usage(redis: &mut redis::aio::ConnectionManager, token: &str, jti: &str) {
+    let kid = jwt::token_key_id(token).unwrap_or_else(|| "none".to_string());
+    let key = key_usage_key(&kid, chrono::Utc::now().timestamp() / 3600);
+
+    let result: Result<(), redis::RedisError> = redis::pipe()
+        .pfadd(&key, jti)
+        .ignore()
+        .expire(&key, KEY_USAGE_WINDOW_HOURS * 3600)
+        .ignore()
+        .query_async(redis)
+        .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to record JWT key usage: {}", e);
+    }
+}
+
+/// Approximate number of distinct tokens seen for a key in the usage window
+pub async fn recent_key_usage(
+    redis: &mut redis::aio::ConnectionManager,
+    kid: &str,
+) -> Result<i64, redis::RedisError> {
+    let current_hour = chrono::Utc::now().timestamp() / 3600;
+    let keys: Vec<String> = (0..KEY_USAGE_WINDOW_HOURS)
+        .map(|offset| key_usage_key(kid, current_hour - offset))
+        .collect();
+
+    redis.pfcount(keys).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_omil_read_scope() {
+        let scopes = vec![SCOPE_OMIL_READ.to_string()];
+
+        assert!(scopes_allow(&scopes, &Method::GET, "/api/me/omil"));
+        assert!(scopes_allow(&scopes, &Method::GET, "/api/me/omil/job-seekers/export"));
+        assert!(!scopes_allow(&scopes, &Method::PUT, "/api/me/omil"));
+        assert!(!scopes_allow(&scopes, &Method::GET, "/api/me/omilx"));
+        assert!(!scopes_allow(&scopes, &Method::GET, "/api/me/profile"));
+        assert!(!scopes_allow(
+            &scopes,
+            &Method::GET,
+            "/api/me/omil/job-seekers/00000000-0000-0000-0000-000000000000/impersonate"
+        ));
+        assert!(!scopes_allow(&[], &Method::GET, "/api/me/omil"));
+    }
+
+    #[test]
+    fn test_kiosk_scope() {
+        let scopes = vec![jwt::KIOSK_SCOPE.to_string()];
+
+        assert!(scopes_allow(&scopes, &Method::POST, "/api/me/omil/job-seekers"));
+        assert!(scopes_allow(&scopes, &Method::GET, "/api/reference/municipalities"));
+        assert!(!scopes_allow(&scopes, &Method::GET, "/api/me/omil/job-seekers"));
+        assert!(!scopes_allow(&scopes, &Method::GET, "/api/me/omil"));
+        assert!(!scopes_allow(&scopes, &Method::POST, "/api/me/omil/kiosk-sessions"));
+        assert!(!scopes_allow(&scopes, &Method::GET, "/api/auth/me"));
+    }
+}
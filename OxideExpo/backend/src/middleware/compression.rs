@@ -0,0 +1,38 @@
+use axum::{
+    http::{Extensions, HeaderMap, StatusCode, Version},
+    response::Response,
+};
+use tower_http::compression::{
+    predicate::{DefaultPredicate, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+/// Responses smaller than this many bytes are sent as they are; compressing
+/// them saves less than it costs
+pub const COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// Marks a response the compression layer must leave alone
+#[derive(Debug, Clone, Copy)]
+struct SkipCompression;
+
+/// Compresses responses with gzip or brotli, whichever the client prefers.
+/// Small bodies, images, event streams and routes wrapped in
+/// `skip_compression` are left as they are.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    let predicate = DefaultPredicate::new()
+        .and(SizeAbove::new(COMPRESSION_MIN_SIZE))
+        .and(
+            |_: StatusCode, _: Version, _: &HeaderMap, extensions: &Extensions| {
+                extensions.get::<SkipCompression>().is_none()
+            },
+        );
+
+    CompressionLayer::new().compress_when(predicate)
+}
+
+/// Opts a route out of compression, for file downloads and Excel exports
+/// whose content is compressed already. Use with `middleware::map_response`.
+pub async fn skip_compression(mut response: Response) -> Response {
+    response.extensions_mut().insert(SkipCompression);
+    response
+}
@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::AppError;
+use crate::middleware::auth::{authenticate, bearer_token};
+use crate::services::maintenance::{self, MaintenanceMode};
+use crate::AppState;
+
+/// Admins need it to turn maintenance mode off again
+const SETTINGS_PATH: &str = "/api/admin/settings";
+
+/// Reachable in every mode, so sessions can be started, renewed and ended.
+/// Without them an admin whose token expired could not get back in to turn
+/// maintenance mode off.
+const SESSION_PATHS: &[&str] = &["/api/auth/login", "/api/auth/refresh", "/api/auth/logout"];
+
+/// Middleware that applies the maintenance mode to the API routes. Health
+/// checks are routed outside it.
+///
+/// In read_only mode only reads, session requests and admins' requests go
+/// through; in full mode nothing but session requests and the admin settings
+/// endpoint does. The rest get 503 with code `MAINTENANCE`.
+pub async fn enforce_maintenance_mode(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mode = maintenance::current(&state).await;
+    let path = request.uri().path();
+
+    let allowed = match mode {
+        MaintenanceMode::Off => true,
+        _ if path == SETTINGS_PATH || SESSION_PATHS.contains(&path) => true,
+        MaintenanceMode::Full => false,
+        MaintenanceMode::ReadOnly => {
+            matches!(
+                *request.method(),
+                Method::GET | Method::HEAD | Method::OPTIONS
+            ) || is_admin(&state, bearer_token(&request)).await
+        }
+    };
+    if allowed {
+        return next.run(request).await;
+    }
+
+    AppError::Maintenance {
+        mode: mode.as_str(),
+        message: maintenance::message(&state).await,
+    }
+    .into_response()
+}
+
+/// Whether the token is a valid admin token
+async fn is_admin(state: &AppState, token: Option<String>) -> bool {
+    let Some(token) = token else {
+        return false;
+    };
+    matches!(
+        authenticate(state, &token).await,
+        Ok(user) if user.user_type == "admin"
+    )
+}
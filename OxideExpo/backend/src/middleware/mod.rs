@@ -1,7 +1,15 @@
 pub mod auth;
+pub mod compression;
 pub mod admin_auth;
+pub mod maintenance;
 pub mod omil_auth;
+pub mod overload;
+pub mod request_log;
 
 pub use auth::*;
+pub use compression::*;
 pub use admin_auth::*;
+pub use maintenance::*;
 pub use omil_auth::*;
+pub use overload::*;
+pub use request_log::*;
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use axum::{
     extract::{Request, State},
     http::StatusCode,
@@ -6,9 +8,13 @@ use axum::{
 };
 use uuid::Uuid;
 
+use crate::error::AppError;
 use crate::middleware::auth::AuthUser;
 use crate::models::company::OrganizationStatus;
 use crate::models::omil::{OmilMember, OmilOrganization, OmilRole};
+use crate::models::permissions::{self, AuthContext, Capability, OmilMembership};
+use crate::models::user::UserType;
+use crate::utils::redact;
 use crate::AppState;
 
 /// OMIL member context available to handlers after middleware validation
@@ -16,6 +22,14 @@ use crate::AppState;
 pub struct OmilContext {
     pub member: OmilMember,
     pub organization: OmilOrganization,
+    /// What the member's role allows within the OMIL
+    pub capabilities: HashSet<Capability>,
+}
+
+impl OmilContext {
+    pub fn can(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
 }
 
 /// Middleware that requires the authenticated user to be an OMIL member (any role)
@@ -23,153 +37,141 @@ pub struct OmilContext {
 /// Must be used after require_auth middleware.
 pub async fn require_omil(
     State(state): State<AppState>,
-    mut request: Request,
+    request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extract AuthUser from request extensions (set by require_auth)
-    let auth_user = request
-        .extensions()
-        .get::<AuthUser>()
-        .cloned()
-        .ok_or_else(|| {
-            tracing::error!("require_omil called without require_auth");
-            StatusCode::UNAUTHORIZED
-        })?;
-
-    // Query OMIL membership with organization details
-    let context = fetch_omil_context(&state, auth_user.id).await?;
-
-    // Verify organization is active
-    if context.organization.status != OrganizationStatus::Active {
-        tracing::debug!(
-            "OMIL organization {} is not active (status: {:?})",
-            context.organization.id,
-            context.organization.status
-        );
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Verify member is active
-    if !context.member.is_active {
-        tracing::debug!(
-            "OMIL member {} is not active",
-            context.member.id
-        );
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Insert OmilContext into request extensions for handlers to access
-    request.extensions_mut().insert(context);
-
-    Ok(next.run(request).await)
+    authorize(
+        &state,
+        request,
+        next,
+        Capability::ManageOmilSeekers,
+        "require_omil",
+    )
+    .await
 }
 
 /// Middleware that requires the authenticated user to be an OMIL coordinator or director
 /// Must be used after require_auth middleware.
 pub async fn require_omil_coordinator_or_above(
     State(state): State<AppState>,
-    mut request: Request,
+    request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extract AuthUser from request extensions
-    let auth_user = request
-        .extensions()
-        .get::<AuthUser>()
-        .cloned()
-        .ok_or_else(|| {
-            tracing::error!("require_omil_coordinator_or_above called without require_auth");
-            StatusCode::UNAUTHORIZED
-        })?;
-
-    // Query OMIL membership with organization details
-    let context = fetch_omil_context(&state, auth_user.id).await?;
-
-    // Verify organization is active
-    if context.organization.status != OrganizationStatus::Active {
-        tracing::debug!(
-            "OMIL organization {} is not active (status: {:?})",
-            context.organization.id,
-            context.organization.status
-        );
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Verify member is active
-    if !context.member.is_active {
-        tracing::debug!("OMIL member {} is not active", context.member.id);
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Check role (must be coordinator or director)
-    if context.member.role != OmilRole::Coordinator && context.member.role != OmilRole::Director {
-        tracing::debug!(
-            "User {} has insufficient OMIL permissions (role: {:?})",
-            auth_user.id,
-            context.member.role
-        );
-        return Err(StatusCode::FORBIDDEN);
-    }
-
-    // Insert OmilContext into request extensions
-    request.extensions_mut().insert(context);
-
-    Ok(next.run(request).await)
+    authorize(
+        &state,
+        request,
+        next,
+        Capability::SuperviseOmil,
+        "require_omil_coordinator_or_above",
+    )
+    .await
 }
 
 /// Middleware that requires the authenticated user to be an OMIL director
 /// Must be used after require_auth middleware.
 pub async fn require_omil_director(
     State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    authorize(
+        &state,
+        request,
+        next,
+        Capability::AdministerOmil,
+        "require_omil_director",
+    )
+    .await
+}
+
+/// Lets the request through when the user is an active member of an active
+/// OMIL whose role grants `capability`
+async fn authorize(
+    state: &AppState,
     mut request: Request,
     next: Next,
+    capability: Capability,
+    middleware: &str,
 ) -> Result<Response, StatusCode> {
-    // Extract AuthUser from request extensions
+    // Extract AuthUser from request extensions (set by require_auth)
     let auth_user = request
         .extensions()
         .get::<AuthUser>()
         .cloned()
         .ok_or_else(|| {
-            tracing::error!("require_omil_director called without require_auth");
+            tracing::error!("{} called without require_auth", middleware);
             StatusCode::UNAUTHORIZED
         })?;
 
     // Query OMIL membership with organization details
-    let context = fetch_omil_context(&state, auth_user.id).await?;
+    let (member, organization, user_type) = fetch_omil_context(state, auth_user.id).await?;
 
     // Verify organization is active
-    if context.organization.status != OrganizationStatus::Active {
+    if organization.status != OrganizationStatus::Active {
         tracing::debug!(
             "OMIL organization {} is not active (status: {:?})",
-            context.organization.id,
-            context.organization.status
+            organization.id,
+            organization.status
         );
         return Err(StatusCode::FORBIDDEN);
     }
 
     // Verify member is active
-    if !context.member.is_active {
-        tracing::debug!("OMIL member {} is not active", context.member.id);
+    if !member.is_active {
+        tracing::debug!("OMIL member {} is not active", member.id);
         return Err(StatusCode::FORBIDDEN);
     }
 
-    // Check role (must be director)
-    if context.member.role != OmilRole::Director {
+    // Kiosk tokens only act for the organization they were opened for
+    if let Some(kiosk) = &auth_user.kiosk {
+        if kiosk.omil_id != organization.id {
+            tracing::debug!(
+                "Kiosk session {} is bound to another OMIL",
+                kiosk.session_id
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    // Only the OMIL membership matters to OMIL capabilities
+    let capabilities = permissions::resolve(&AuthContext {
+        user_id: auth_user.id,
+        user_type,
+        company: None,
+        omil: Some(OmilMembership {
+            omil_id: organization.id,
+            role: member.role,
+        }),
+        admin_role: None,
+        impersonated: auth_user.impersonator_id.is_some(),
+        kiosk: auth_user.kiosk.is_some(),
+    });
+
+    if !capabilities.contains(&capability) {
         tracing::debug!(
-            "User {} is not an OMIL director (role: {:?})",
+            "User {} has insufficient OMIL permissions (role: {:?}, needs {:?})",
             auth_user.id,
-            context.member.role
+            member.role,
+            capability
         );
         return Err(StatusCode::FORBIDDEN);
     }
 
-    // Insert OmilContext into request extensions
-    request.extensions_mut().insert(context);
+    // Insert OmilContext into request extensions for handlers to access
+    request.extensions_mut().insert(OmilContext {
+        member,
+        organization,
+        capabilities,
+    });
 
     Ok(next.run(request).await)
 }
 
 /// Helper function to fetch OMIL member context from database
-async fn fetch_omil_context(state: &AppState, user_id: Uuid) -> Result<OmilContext, StatusCode> {
+async fn fetch_omil_context(
+    state: &AppState,
+    user_id: Uuid,
+) -> Result<(OmilMember, OmilOrganization, UserType), StatusCode> {
     // Query OMIL member and organization in one query using JOIN
     let row = sqlx::query!(
         r#"
@@ -178,6 +180,7 @@ async fn fetch_omil_context(state: &AppState, user_id: Uuid) -> Result<OmilConte
             m.omil_id,
             m.user_id,
             m.role as "role: OmilRole",
+            m.branch_id,
             m.is_active as member_is_active,
             m.joined_at,
             m.left_at,
@@ -195,17 +198,25 @@ async fn fetch_omil_context(state: &AppState, user_id: Uuid) -> Result<OmilConte
             o.approved_at,
             o.approved_by,
             o.created_at as org_created_at,
-            o.updated_at as org_updated_at
+            o.updated_at as org_updated_at,
+            u.user_type as "user_type: UserType"
         FROM omil_members m
         JOIN omil_organizations o ON o.id = m.omil_id
+        JOIN users u ON u.id = m.user_id
         WHERE m.user_id = $1
+        -- Members of several OMILs act for the active one where they rank highest
+        ORDER BY (m.is_active AND o.status = 'active') DESC, m.role ASC, m.joined_at ASC, m.id ASC
+        LIMIT 1
         "#,
         user_id
     )
     .fetch_optional(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Database error fetching OMIL context: {:?}", e);
+        tracing::error!(
+            "Database error fetching OMIL context: {}",
+            redact::db_error(&e)
+        );
         StatusCode::INTERNAL_SERVER_ERROR
     })?
     .ok_or_else(|| {
@@ -218,6 +229,7 @@ async fn fetch_omil_context(state: &AppState, user_id: Uuid) -> Result<OmilConte
         omil_id: row.omil_id,
         user_id: row.user_id,
         role: row.role,
+        branch_id: row.branch_id,
         is_active: row.member_is_active,
         joined_at: row.joined_at,
         left_at: row.left_at,
@@ -241,5 +253,29 @@ async fn fetch_omil_context(state: &AppState, user_id: Uuid) -> Result<OmilConte
         updated_at: row.org_updated_at,
     };
 
-    Ok(OmilContext { member, organization })
+    Ok((member, organization, row.user_type))
+}
+
+/// Check that a job is co-published with the member's OMIL.
+/// Jobs the OMIL is no partner of, including ones it withdrew from, are not
+/// found, so their applicants stay hidden.
+pub async fn require_job_partner(
+    db: &sqlx::PgPool,
+    context: &OmilContext,
+    job_id: Uuid,
+) -> Result<(), AppError> {
+    let is_partner = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1 AND partner_omil_id = $2)",
+        job_id,
+        context.organization.id
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(false);
+
+    if !is_partner {
+        return Err(AppError::NotFound("Job not found".to_string()));
+    }
+
+    Ok(())
 }
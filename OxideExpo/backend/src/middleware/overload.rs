@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::{error_handling::HandleErrorLayer, BoxError, Router};
+use tower::{
+    limit::GlobalConcurrencyLimitLayer, load_shed::error::Overloaded, timeout::error::Elapsed,
+    ServiceBuilder,
+};
+
+use crate::{config::Config, error::AppError, AppState};
+
+/// Seconds between repeated shedding warnings while an overload lasts
+const SHED_WARNING_INTERVAL_SECS: u64 = 60;
+
+/// Unix time of the last shedding warning
+static LAST_SHED_WARNING: AtomicU64 = AtomicU64::new(0);
+
+/// Bounds how many requests run at once and for how long. Requests beyond
+/// the limit are rejected with 503 straight away instead of queueing for a
+/// database connection, and requests running past the timeout get a 408.
+///
+/// The limit is shared by every route of `router` (a plain
+/// `ConcurrencyLimitLayer` would give each route its own), so routes that
+/// must stay responsive under load, like health checks, are merged in
+/// after this is applied.
+pub fn with_overload_protection(router: Router<AppState>, config: &Config) -> Router<AppState> {
+    router.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(handle_overload_error))
+            .load_shed()
+            .layer(GlobalConcurrencyLimitLayer::new(
+                config.max_concurrent_requests,
+            ))
+            .timeout(Duration::from_secs(config.request_timeout_secs)),
+    )
+}
+
+async fn handle_overload_error(err: BoxError) -> AppError {
+    if err.is::<Overloaded>() {
+        warn_shedding();
+        AppError::ServiceUnavailable("Server is busy, try again shortly".to_string())
+    } else if err.is::<Elapsed>() {
+        AppError::RequestTimeout("Request took too long to complete".to_string())
+    } else {
+        AppError::InternalError(format!("Unhandled middleware error: {}", err))
+    }
+}
+
+/// Logs once when shedding starts, then at most once a minute while it goes on
+fn warn_shedding() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let last = LAST_SHED_WARNING.load(Ordering::Relaxed);
+    if now.saturating_sub(last) >= SHED_WARNING_INTERVAL_SECS
+        && LAST_SHED_WARNING
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    {
+        tracing::warn!("Concurrency limit reached, shedding requests");
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use axum::http::Request;
+use tower_http::{
+    classify::{ServerErrorsAsFailures, SharedClassifier},
+    trace::{MakeSpan, TraceLayer},
+};
+use tracing::Span;
+
+use crate::{config::Config, utils::redact::Redactor};
+
+/// Request span like tower-http's default, with the values of sensitive query
+/// parameters masked unless PII logging is on
+#[derive(Debug, Clone)]
+pub struct RedactingMakeSpan {
+    redactor: Redactor,
+    redacted_params: Arc<[String]>,
+}
+
+impl<B> MakeSpan<B> for RedactingMakeSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        tracing::debug_span!(
+            "request",
+            method = %request.method(),
+            uri = %self.redactor.uri(request.uri(), &self.redacted_params),
+            version = ?request.version(),
+        )
+    }
+}
+
+/// Logs each request and response, see `RedactingMakeSpan`
+pub fn trace_layer(
+    config: &Config,
+) -> TraceLayer<SharedClassifier<ServerErrorsAsFailures>, RedactingMakeSpan> {
+    TraceLayer::new_for_http().make_span_with(RedactingMakeSpan {
+        redactor: config.redactor(),
+        redacted_params: config.log_redacted_params.clone().into(),
+    })
+}
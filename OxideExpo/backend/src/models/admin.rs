@@ -4,7 +4,14 @@ use sqlx::FromRow;
 use sqlx::Type;
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+use crate::models::application::RejectionReasonCode;
+use crate::models::company::{CompanyProfile, UsageCounts};
+use crate::models::export::ExportType;
+use crate::models::job::{Job, JobCompleteness};
+use crate::models::profile::DisabilityCategory;
+use crate::models::user::UserResponse;
 
 // ============================================================================
 // ENUMS
@@ -281,6 +288,18 @@ pub struct AuditLogFilterParams {
     pub offset: Option<i64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExportAuditFilterParams {
+    pub omil_id: Option<Uuid>,
+    pub company_id: Option<Uuid>,
+    pub exported_by: Option<Uuid>,
+    pub export_type: Option<ExportType>,
+    /// Export ID from a file's watermark, or its beginning
+    pub export_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
 // ============================================================================
 // V11: USER MANAGEMENT DTOs
 // ============================================================================
@@ -291,7 +310,7 @@ use crate::models::user::{AccountStatus, UserType};
 #[ts(export)]
 pub struct UserListItem {
     pub id: Uuid,
-    pub email: String,
+    pub email: Option<String>,
     pub first_name: String,
     pub last_name: String,
     pub user_type: UserType,
@@ -304,7 +323,7 @@ pub struct UserListItem {
 #[ts(export)]
 pub struct UserDetail {
     pub id: Uuid,
-    pub email: String,
+    pub email: Option<String>,
     pub first_name: String,
     pub last_name: String,
     pub user_type: UserType,
@@ -317,6 +336,8 @@ pub struct UserDetail {
     pub company_name: Option<String>,
     pub omil_id: Option<Uuid>,
     pub omil_name: Option<String>,
+    /// Internal notes, pinned first
+    pub notes: Vec<AdminNote>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -332,7 +353,86 @@ pub struct AdminImpersonationResponse {
     pub impersonation_token: String,
     pub expires_at: DateTime<Utc>,
     pub user_id: Uuid,
-    pub user_email: String,
+    pub user_email: Option<String>,
+}
+
+/// Hours an admin-issued password reset token stays valid
+pub const ADMIN_PASSWORD_RESET_HOURS: i64 = 24;
+
+/// Body for admin-issued password reset and verification tokens
+#[derive(Debug, Default, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueUserTokenRequest {
+    /// Email the link as usual (the default), or return the token once so
+    /// support can hand it over another way
+    pub deliver: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum TokenDelivery {
+    Email,
+    OutOfBand,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct IssuedUserToken {
+    pub user_id: Uuid,
+    pub delivery: TokenDelivery,
+    /// Only set for out-of-band delivery. It is not stored and cannot be
+    /// shown again.
+    pub token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// V13: DUPLICATE ACCOUNT DTOs
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct DuplicateUserEntry {
+    pub id: Uuid,
+    pub email: Option<String>,
+    pub first_name: String,
+    pub last_name: String,
+    pub account_status: AccountStatus,
+    pub applications_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Accounts sharing the same national ID (RUT)
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct DuplicateUserGroup {
+    pub national_id: String,
+    pub users: Vec<DuplicateUserEntry>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MergeUsersRequest {
+    /// Account that survives the merge
+    pub primary_user_id: Uuid,
+    /// Account whose data is moved to the primary and then deactivated
+    pub duplicate_user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct MergeUsersResponse {
+    pub primary_user_id: Uuid,
+    pub duplicate_user_id: Uuid,
+    pub applications_moved: Vec<Uuid>,
+    /// Later application of a pair where both accounts applied to the same job
+    pub applications_removed: Vec<Uuid>,
+    pub saved_jobs_moved: i64,
+    pub followups_moved: i64,
+    pub managed_records_moved: i64,
+    pub skills_moved: i64,
+    pub education_moved: i64,
+    pub experience_moved: i64,
 }
 
 // ============================================================================
@@ -355,14 +455,289 @@ pub struct RejectOmilRequest {
 // V11: SYSTEM SETTINGS DTOs
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SettingValueType {
+    Int,
+    Bool,
+    String,
+    Json,
+}
+
+/// Shape and constraints of a system setting. Only keys listed in
+/// SETTING_DEFINITIONS can be read or written.
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[ts(export)]
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub value_type: SettingValueType,
+    /// Inclusive bounds for int settings
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    /// Accepted values for string settings; empty means any string
+    pub allowed_values: &'static [&'static str],
+    pub description: &'static str,
+    /// Whether running servers only pick up a change after a restart
+    pub requires_restart: bool,
+    /// JSON used when the setting has never been stored
+    #[serde(skip)]
+    #[ts(skip)]
+    pub default: &'static str,
+}
+
+pub const SETTING_DEFINITIONS: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: "account_deletion_grace_days",
+        value_type: SettingValueType::Int,
+        min: Some(1),
+        max: Some(365),
+        allowed_values: &[],
+        description: "Days a deleted account can be restored before it is scrubbed",
+        requires_restart: false,
+        default: "30",
+    },
+    SettingDefinition {
+        key: "auto_approve_companies",
+        value_type: SettingValueType::Bool,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Auto-approve company registrations",
+        requires_restart: false,
+        default: "false",
+    },
+    SettingDefinition {
+        key: "auto_approve_jobs",
+        value_type: SettingValueType::Bool,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Auto-approve job postings",
+        requires_restart: false,
+        default: "false",
+    },
+    SettingDefinition {
+        key: "job_automation_enabled",
+        value_type: SettingValueType::Bool,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Run company automation rules on new applications",
+        requires_restart: false,
+        default: "true",
+    },
+    SettingDefinition {
+        key: "maintenance_message",
+        value_type: SettingValueType::String,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Message shown to users while maintenance mode is on",
+        requires_restart: false,
+        default: "\"\"",
+    },
+    SettingDefinition {
+        key: "maintenance_mode",
+        value_type: SettingValueType::String,
+        min: None,
+        max: None,
+        allowed_values: &["off", "read_only", "full"],
+        description: "Maintenance mode: off, read_only (only admins can write) or full",
+        requires_restart: false,
+        default: "\"off\"",
+    },
+    SettingDefinition {
+        key: "max_applications_per_day",
+        value_type: SettingValueType::Int,
+        min: Some(0),
+        max: Some(1000),
+        allowed_values: &[],
+        description: "Applications a job seeker may send per day (0 = unlimited)",
+        requires_restart: false,
+        default: "20",
+    },
+    SettingDefinition {
+        key: "max_applications_per_seeker",
+        value_type: SettingValueType::Int,
+        min: Some(1),
+        max: Some(1000),
+        allowed_values: &[],
+        description: "Maximum pending applications per job seeker",
+        requires_restart: false,
+        default: "50",
+    },
+    SettingDefinition {
+        key: "max_omil_applications_per_day",
+        value_type: SettingValueType::Int,
+        min: Some(0),
+        max: Some(10000),
+        allowed_values: &[],
+        description: "Applications an OMIL may send per day on behalf of its job seekers (0 = unlimited)",
+        requires_restart: false,
+        default: "200",
+    },
+    SettingDefinition {
+        key: "moderation_age_limit_severity",
+        value_type: SettingValueType::String,
+        min: None,
+        max: None,
+        allowed_values: &["off", "warn", "block"],
+        description: "How job postings with age limits outside the allowed range are screened",
+        requires_restart: false,
+        default: "\"warn\"",
+    },
+    SettingDefinition {
+        key: "moderation_contact_info_severity",
+        value_type: SettingValueType::String,
+        min: None,
+        max: None,
+        allowed_values: &["off", "warn", "block"],
+        description: "How emails, phone numbers and URLs in job descriptions are screened",
+        requires_restart: false,
+        default: "\"block\"",
+    },
+    SettingDefinition {
+        key: "moderation_max_age_min",
+        value_type: SettingValueType::Int,
+        min: Some(15),
+        max: Some(100),
+        allowed_values: &[],
+        description: "Highest minimum age a job posting may require",
+        requires_restart: false,
+        default: "18",
+    },
+    SettingDefinition {
+        key: "moderation_min_age_max",
+        value_type: SettingValueType::Int,
+        min: Some(15),
+        max: Some(100),
+        allowed_values: &[],
+        description: "Lowest maximum age a job posting may set",
+        requires_restart: false,
+        default: "65",
+    },
+    SettingDefinition {
+        key: "require_email_verification",
+        value_type: SettingValueType::Bool,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Require email verification for new accounts",
+        requires_restart: false,
+        default: "true",
+    },
+    SettingDefinition {
+        key: "review_edited_jobs",
+        value_type: SettingValueType::Bool,
+        min: None,
+        max: None,
+        allowed_values: &[],
+        description: "Send active jobs back to approval when they are edited",
+        requires_restart: false,
+        default: "false",
+    },
+    SettingDefinition {
+        key: "stale_job_nudge_interval_days",
+        value_type: SettingValueType::Int,
+        min: Some(1),
+        max: Some(90),
+        allowed_values: &[],
+        description: "Days between nudges about the same stale job",
+        requires_restart: false,
+        default: "7",
+    },
+    SettingDefinition {
+        key: "stale_job_nudges_before_pause",
+        value_type: SettingValueType::Int,
+        min: Some(1),
+        max: Some(10),
+        allowed_values: &[],
+        description: "Unanswered nudges after which a stale job is paused",
+        requires_restart: false,
+        default: "2",
+    },
+    SettingDefinition {
+        key: "stale_job_pending_days",
+        value_type: SettingValueType::Int,
+        min: Some(1),
+        max: Some(365),
+        allowed_values: &[],
+        description: "Days applications can wait for review before the company is nudged",
+        requires_restart: false,
+        default: "14",
+    },
+];
+
+impl SettingDefinition {
+    pub fn find(key: &str) -> Option<&'static SettingDefinition> {
+        SETTING_DEFINITIONS.iter().find(|d| d.key == key)
+    }
+
+    pub fn default_value(&self) -> serde_json::Value {
+        serde_json::from_str(self.default).expect("setting defaults are valid JSON")
+    }
+
+    /// Checks a value against the definition, describing the first problem found
+    pub fn validate_value(&self, value: &serde_json::Value) -> Result<(), String> {
+        match self.value_type {
+            SettingValueType::Int => {
+                let n = value
+                    .as_i64()
+                    .ok_or_else(|| format!("{} must be an integer", self.key))?;
+                if let Some(min) = self.min.filter(|min| n < *min) {
+                    return Err(format!("{} must be at least {}", self.key, min));
+                }
+                if let Some(max) = self.max.filter(|max| n > *max) {
+                    return Err(format!("{} must be at most {}", self.key, max));
+                }
+            }
+            SettingValueType::Bool => {
+                if !value.is_boolean() {
+                    return Err(format!("{} must be true or false", self.key));
+                }
+            }
+            SettingValueType::String => {
+                let s = value
+                    .as_str()
+                    .ok_or_else(|| format!("{} must be a string", self.key))?;
+                if !self.allowed_values.is_empty() && !self.allowed_values.contains(&s) {
+                    return Err(format!(
+                        "{} must be one of: {}",
+                        self.key,
+                        self.allowed_values.join(", ")
+                    ));
+                }
+            }
+            SettingValueType::Json => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
 #[ts(export)]
 pub struct SystemSetting {
     pub key: String,
     #[ts(type = "any")]
     pub value: serde_json::Value,
-    pub description: Option<String>,
-    pub updated_at: DateTime<Utc>,
+    pub definition: SettingDefinition,
+    /// None while the setting still has its default value
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct SettingHistoryEntry {
+    pub id: Uuid,
+    pub key: String,
+    #[ts(type = "any")]
+    pub old_value: Option<serde_json::Value>,
+    #[ts(type = "any")]
+    pub new_value: serde_json::Value,
+    pub changed_by: Option<Uuid>,
+    pub changed_by_name: Option<String>,
+    pub changed_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -376,6 +751,30 @@ pub struct SettingUpdate {
     pub value: serde_json::Value,
 }
 
+// ============================================================================
+// V13: JWT KEY DTOs
+// ============================================================================
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct JwtKeyInfo {
+    /// Key ID carried in token headers (SHA256 fingerprint of the secret)
+    pub kid: String,
+    /// Signs new tokens; the others only verify
+    pub primary: bool,
+    /// Approximate distinct tokens seen in the usage window
+    pub recent_tokens: i64,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct JwtKeysResponse {
+    pub window_hours: i64,
+    pub keys: Vec<JwtKeyInfo>,
+    /// Tokens issued before kid headers existed
+    pub tokens_without_kid: i64,
+}
+
 // ============================================================================
 // V12: REPORTING DTOs
 // ============================================================================
@@ -417,9 +816,20 @@ pub struct CompanyTrendsReport {
     pub active_companies: i64,
     pub pending_companies: i64,
     pub new_companies_period: i64,
+    /// Each company counted once, under its primary industry
+    pub by_primary_industry: Vec<IndustryCompanyCount>,
     pub trend: Vec<TrendDataPoint>,
 }
 
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct IndustryCompanyCount {
+    /// None groups companies without an industry
+    pub industry_id: Option<Uuid>,
+    pub industry_name: Option<String>,
+    pub count: i64,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
 pub struct JobTrendsReport {
@@ -437,6 +847,8 @@ pub struct ApplicationTrendsReport {
     pub new_applications_period: i64,
     pub by_status: Vec<ApplicationStatusCount>,
     pub trend: Vec<TrendDataPoint>,
+    /// Rejected applications platform-wide, by reason
+    pub rejection_reasons: Vec<RejectionReasonCount>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -446,6 +858,258 @@ pub struct ApplicationStatusCount {
     pub count: i64,
 }
 
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RejectionReasonCount {
+    /// None for rejections made without a reason
+    pub reason: Option<RejectionReasonCode>,
+    pub count: i64,
+}
+
+// ============================================================================
+// V13: INCLUSION REPORT DTOs
+// ============================================================================
+
+/// Cells counting fewer people than this are suppressed to avoid re-identification
+pub const MIN_REPORTABLE_CELL: i64 = 5;
+
+/// Count as reported, or None under MIN_REPORTABLE_CELL. Every aggregate
+/// about people goes through this rule, directly or via SuppressedCount.
+pub fn reportable(count: i64) -> Option<i64> {
+    (count >= MIN_REPORTABLE_CELL).then_some(count)
+}
+
+/// A people count that is hidden (null) when it falls under MIN_REPORTABLE_CELL
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SuppressedCount {
+    pub value: Option<i64>,
+    pub suppressed: bool,
+}
+
+impl SuppressedCount {
+    pub fn from_count(count: i64) -> Self {
+        let value = reportable(count);
+        Self {
+            value,
+            suppressed: value.is_none(),
+        }
+    }
+
+    /// Share of `self` over `base`, only when both cells are reportable
+    pub fn rate_over(&self, base: &SuppressedCount) -> Option<f64> {
+        match (self.value, base.value) {
+            (Some(part), Some(total)) if total > 0 => {
+                Some((part as f64 / total as f64 * 10_000.0).round() / 10_000.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Suppress one column of a breakdown whose total is reported, or can be
+    /// added up from another breakdown. Hiding the small cells alone is not
+    /// enough there, since the total minus the shown cells gives them back:
+    /// the smallest shown cells are hidden too until at least two cells are
+    /// hidden and together they count MIN_REPORTABLE_CELL people. Hidden
+    /// zeros need no cover, as nobody can be recovered from them.
+    pub fn column(counts: &[i64]) -> Vec<SuppressedCount> {
+        let mut cells: Vec<SuppressedCount> = counts
+            .iter()
+            .map(|&count| Self::from_count(count))
+            .collect();
+
+        // Shown cells, largest first so the smallest pops off the end
+        let mut shown: Vec<usize> = (0..counts.len())
+            .filter(|&i| !cells[i].suppressed)
+            .collect();
+        shown.sort_by_key(|&i| std::cmp::Reverse(counts[i]));
+
+        let hidden = |cells: &[SuppressedCount]| {
+            let mut n = 0;
+            let mut sum = 0;
+            for (cell, count) in cells.iter().zip(counts) {
+                if cell.suppressed {
+                    n += 1;
+                    sum += count;
+                }
+            }
+            (n, sum)
+        };
+
+        loop {
+            let (n, sum) = hidden(&cells);
+            if sum == 0 || (n >= 2 && sum >= MIN_REPORTABLE_CELL) {
+                break;
+            }
+            let Some(smallest) = shown.pop() else {
+                break;
+            };
+            cells[smallest] = SuppressedCount {
+                value: None,
+                suppressed: true,
+            };
+        }
+
+        cells
+    }
+}
+
+/// Funnel for one disability category or region
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct InclusionFunnelRow {
+    pub group: String,
+    /// Registered seekers with a disability record in this group
+    pub seekers: SuppressedCount,
+    /// Applications submitted in the period (suppressed with `applied`)
+    pub applications: SuppressedCount,
+    pub applied: SuppressedCount,
+    pub interviewing: SuppressedCount,
+    pub offered: SuppressedCount,
+    pub hired: SuppressedCount,
+    /// interviewing / applied
+    pub interview_rate: Option<f64>,
+    /// offered / interviewing
+    pub offer_rate: Option<f64>,
+    /// hired / offered
+    pub hire_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct InclusionReport {
+    pub from_date: DateTime<Utc>,
+    pub to_date: DateTime<Utc>,
+    pub seekers_with_disability: SuppressedCount,
+    pub applications: SuppressedCount,
+    pub by_category: Vec<InclusionFunnelRow>,
+    pub by_region: Vec<InclusionFunnelRow>,
+    pub active_jobs: i64,
+    pub active_jobs_with_accommodation: i64,
+    pub accommodation_share: Option<f64>,
+}
+
+// ============================================================================
+// V13: MODERATION THROUGHPUT DTOs
+// ============================================================================
+
+/// Time from submission to an approve/reject decision for one entity type
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ModerationTurnaround {
+    /// company, job or omil
+    pub entity_type: String,
+    pub decisions: i64,
+    pub approvals: i64,
+    pub rejections: i64,
+    /// Hours from (re)submission to decision; None without decisions
+    pub median_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+/// Decisions taken by one admin in the period
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ReviewerDecisionCount {
+    pub admin_id: Uuid,
+    pub admin_name: String,
+    pub approvals: i64,
+    pub rejections: i64,
+}
+
+/// Items waiting for review right now, bucketed by how long they have waited
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ModerationQueueAge {
+    pub entity_type: String,
+    pub pending: i64,
+    pub under_1_day: i64,
+    pub from_1_to_3_days: i64,
+    pub from_3_to_7_days: i64,
+    pub over_7_days: i64,
+    pub oldest_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ModerationReport {
+    pub from_date: DateTime<Utc>,
+    pub to_date: DateTime<Utc>,
+    pub turnaround: Vec<ModerationTurnaround>,
+    pub by_admin: Vec<ReviewerDecisionCount>,
+    pub queue: Vec<ModerationQueueAge>,
+}
+
+// ============================================================================
+// V13: COHORT RETENTION DTOs
+// ============================================================================
+
+/// Days after registration at which cohorts are checked for activity
+pub const COHORT_RETENTION_WINDOWS: [i32; 3] = [30, 60, 90];
+
+/// Registration months covered, the current one included
+pub const COHORT_RETENTION_MONTHS: i32 = 12;
+
+/// Job seekers who registered in one calendar month
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CohortRetentionRow {
+    /// Registration month, YYYY-MM in the platform time zone
+    pub cohort: String,
+    pub registered: i64,
+    /// Seekers active (signed in, applied or updated their profile) at least
+    /// each window's days after registering; None until the whole cohort is
+    /// that old
+    pub retained: Vec<Option<i64>>,
+    /// retained / registered, per window
+    pub retention_rates: Vec<Option<f64>>,
+    /// Seekers hired through an application or placed by an OMIL
+    pub placed: i64,
+    pub placement_rate: Option<f64>,
+}
+
+/// All cohorts together; each window only counts the cohorts that reached it
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CohortRetentionTotals {
+    pub registered: i64,
+    pub retained: Vec<i64>,
+    pub retention_rates: Vec<Option<f64>>,
+    pub placed: i64,
+    pub placement_rate: Option<f64>,
+}
+
+/// Cohort heatmap: one row per registration month, oldest first, and one
+/// retention column per window
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CohortRetentionReport {
+    pub windows: Vec<i32>,
+    pub cohorts: Vec<CohortRetentionRow>,
+    pub totals: CohortRetentionTotals,
+}
+
+/// One company's line in the usage report
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct CompanyUsageRow {
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub usage: UsageCounts,
+}
+
+/// Usage of every company with any metered action in the month, by name
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct UsageReport {
+    /// YYYY-MM
+    pub month: String,
+    /// When the month was last totalled; None if it never was
+    pub aggregated_at: Option<DateTime<Utc>>,
+    pub companies: Vec<CompanyUsageRow>,
+    pub totals: UsageCounts,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
 pub struct CompanyDashboard {
@@ -454,6 +1118,10 @@ pub struct CompanyDashboard {
     pub applications_by_status: Vec<ApplicationStatusCount>,
     pub trend: Vec<TrendDataPoint>,
     pub top_jobs: Vec<TopJobPerformance>,
+    /// Jobs with rejected applications, by reason
+    pub rejection_reasons: Vec<JobRejectionReasons>,
+    /// Open jobs scoring under INCOMPLETE_JOB_THRESHOLD, with what they miss
+    pub incomplete_jobs: Vec<JobCompleteness>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -462,5 +1130,680 @@ pub struct TopJobPerformance {
     pub job_id: Uuid,
     pub title: String,
     pub applications_count: i64,
+    /// Applicants who also applied to an earlier posting of the job
+    pub repeat_applicants_count: i64,
     pub status: String,
 }
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct JobRejectionReasons {
+    pub job_id: Uuid,
+    pub title: String,
+    pub reasons: Vec<RejectionReasonCount>,
+}
+
+// ============================================================================
+// V13: ORPHANED COMPANY DTOs
+// ============================================================================
+
+/// Days a pending company must have waited before it can count as orphaned
+pub const DEFAULT_ORPHANED_COMPANY_DAYS: i64 = 14;
+
+/// Rejection reason recorded on companies dismissed as orphaned
+pub const ORPHANED_COMPANY_REJECTION_REASON: &str =
+    "Registration was never completed: the account owner did not verify their email or log in";
+
+#[derive(Debug, Deserialize)]
+pub struct OrphanedCompanyParams {
+    /// Minimum age of the registration; defaults to DEFAULT_ORPHANED_COMPANY_DAYS
+    pub older_than_days: Option<i64>,
+}
+
+/// Pending company whose members never verified their email or logged in
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct OrphanedCompany {
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub registered_at: DateTime<Utc>,
+    pub owner_id: Option<Uuid>,
+    pub owner_email: Option<String>,
+    pub owner_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct DismissOrphanedCompaniesRequest {
+    #[validate(length(min = 1, max = 500, message = "Must provide 1-500 company IDs"))]
+    pub company_ids: Vec<Uuid>,
+    /// Same threshold the list was fetched with
+    pub older_than_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct DismissOrphanedCompaniesResponse {
+    pub dismissed: Vec<Uuid>,
+    /// Companies that no longer look orphaned, e.g. the owner has since logged in
+    pub skipped: Vec<Uuid>,
+}
+
+// ============================================================================
+// V13: CONTENT SCREENING DTOs
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "moderation_rule_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ModerationRuleType {
+    /// Case-insensitive whole-word phrase
+    BannedPhrase,
+    Regex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "moderation_severity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ModerationSeverity {
+    /// Submitted, with the finding shown to moderators
+    Warn,
+    /// Kept in draft until the company fixes it
+    Block,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct ModerationRule {
+    pub id: Uuid,
+    pub rule_type: ModerationRuleType,
+    pub pattern: String,
+    pub severity: ModerationSeverity,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateModerationRuleRequest {
+    pub rule_type: ModerationRuleType,
+    #[validate(length(min = 1, max = 500))]
+    pub pattern: String,
+    pub severity: ModerationSeverity,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateModerationRuleRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub pattern: Option<String>,
+    pub severity: Option<ModerationSeverity>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+// ============================================================================
+// FOLLOWUP TYPE REFERENCE DTOs
+// ============================================================================
+
+fn validate_followup_type_code(code: &str) -> Result<(), ValidationError> {
+    let mut chars = code.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_followup_type_code").with_message(
+            "Code must be lowercase letters, digits and underscores, starting with a letter"
+                .into(),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateFollowupTypeRequest {
+    #[validate(
+        length(min = 1, max = 50),
+        custom(function = "validate_followup_type_code")
+    )]
+    pub code: String,
+    #[validate(length(min = 1, max = 255))]
+    pub label_es: String,
+    pub is_active: Option<bool>,
+    pub counts_as_intervention: Option<bool>,
+}
+
+/// The code can't change once followups may have been recorded with it
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateFollowupTypeRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub label_es: Option<String>,
+    pub is_active: Option<bool>,
+    pub counts_as_intervention: Option<bool>,
+}
+
+/// Which check produced a screening finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ScreeningCheck {
+    BannedPhrase,
+    Regex,
+    ContactInfo,
+    AgeLimit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ScreeningFinding {
+    /// Job field the finding refers to
+    pub field: String,
+    pub check: ScreeningCheck,
+    pub severity: ModerationSeverity,
+    /// Set for findings from admin-defined rules
+    pub rule_id: Option<Uuid>,
+    /// Offending text as it appears in the field
+    pub matched: Option<String>,
+    pub message: String,
+}
+
+/// Job awaiting approval with the warnings found when it was submitted.
+/// The full posting is fetched with the review endpoint.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct PendingJob {
+    pub id: Uuid,
+    pub title: String,
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub created_at: DateTime<Utc>,
+    pub completeness_percentage: i32,
+    /// Whole hours since the job last entered the queue
+    pub waiting_hours: i64,
+    pub screening_findings: Vec<ScreeningFinding>,
+    /// Jobs this one matched when the company forced it through the
+    /// duplicate check
+    pub possible_duplicate_of: Vec<Uuid>,
+}
+
+/// Skill or language a job asks for, resolved to its name
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JobReviewRequirement {
+    pub id: Uuid,
+    pub name: String,
+    /// None for preferred skills, which have no minimum
+    pub minimum_proficiency: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JobReviewCompany {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub profile: CompanyProfile,
+    /// Times any of the company's jobs was rejected, this one included
+    pub prior_job_rejections: i64,
+}
+
+/// Everything a moderator needs to decide on a job, in one response
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct JobReview {
+    pub job: Job,
+    /// False when the job already left the queue; it can still be reviewed
+    /// for audit but not approved or rejected
+    pub pending_approval: bool,
+    pub required_skills: Vec<JobReviewRequirement>,
+    pub preferred_skills: Vec<JobReviewRequirement>,
+    pub required_languages: Vec<JobReviewRequirement>,
+    pub disability_accommodations: Vec<DisabilityCategory>,
+    pub company: JobReviewCompany,
+    pub posted_by: UserResponse,
+    pub screening_findings: Vec<ScreeningFinding>,
+    pub possible_duplicate_of: Vec<Uuid>,
+    /// Internal notes on the job, pinned first
+    pub notes: Vec<AdminNote>,
+}
+
+// ============================================================================
+// V13: DATA RETENTION DTOs
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "retention_entity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RetentionEntity {
+    Applications,
+    OmilFollowups,
+    /// Admin audit log entries
+    AuditLogs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "retention_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum RetentionAction {
+    /// Scrub free text but keep the row for statistics
+    Anonymize,
+    Delete,
+    /// Move to an archive table
+    Archive,
+}
+
+impl RetentionEntity {
+    /// Actions a policy on this entity may take
+    pub fn allowed_actions(self) -> &'static [RetentionAction] {
+        match self {
+            RetentionEntity::Applications => &[RetentionAction::Anonymize, RetentionAction::Delete],
+            RetentionEntity::OmilFollowups => &[RetentionAction::Delete],
+            RetentionEntity::AuditLogs => &[RetentionAction::Archive],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct RetentionPolicy {
+    pub id: Uuid,
+    pub entity_type: RetentionEntity,
+    pub action: RetentionAction,
+    /// Records older than this are acted on
+    pub age_months: i32,
+    pub is_enabled: bool,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct UpdateRetentionPolicyRequest {
+    pub action: Option<RetentionAction>,
+    #[validate(range(min = 1, max = 1200, message = "Age must be between 1 and 1200 months"))]
+    pub age_months: Option<i32>,
+    pub is_enabled: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, TS)]
+#[ts(export)]
+pub struct RunRetentionRequest {
+    /// Only count the rows each policy would act on
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What one policy did, or would do on a dry run
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct RetentionOutcome {
+    pub policy_id: Uuid,
+    pub entity_type: RetentionEntity,
+    pub action: RetentionAction,
+    pub age_months: i32,
+    pub affected_rows: i64,
+    pub duration_ms: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RetentionRunResponse {
+    pub dry_run: bool,
+    pub outcomes: Vec<RetentionOutcome>,
+}
+
+/// Recorded outcome of a policy applied by the monthly job or an admin
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct RetentionRun {
+    pub id: Uuid,
+    pub policy_id: Option<Uuid>,
+    pub entity_type: RetentionEntity,
+    pub action: RetentionAction,
+    pub age_months: i32,
+    pub affected_rows: i64,
+    pub duration_ms: i64,
+    pub error: Option<String>,
+    /// None for the scheduled run
+    pub triggered_by: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetentionRunParams {
+    pub entity_type: Option<RetentionEntity>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+// ============================================================================
+// STALE JOBS
+// ============================================================================
+
+/// What one weekly stale job run did
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct StaleJobRun {
+    pub id: Uuid,
+    pub ran_at: DateTime<Utc>,
+    /// Companies emailed about jobs with applications waiting for review
+    pub nudged_jobs: i32,
+    /// Jobs paused after their nudges went unanswered
+    pub paused_jobs: i32,
+    /// Applicants of the paused jobs told their application is on hold
+    pub notified_applicants: i32,
+}
+
+// ============================================================================
+// COUNTER MAINTENANCE
+// ============================================================================
+
+/// A job whose stored applications_count did not match its applications
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct CounterDrift {
+    pub job_id: Uuid,
+    pub stored: i32,
+    pub actual: i32,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RecountApplicationsResponse {
+    /// Jobs whose counter was corrected; empty when nothing had drifted
+    pub corrected: Vec<CounterDrift>,
+}
+
+// ============================================================================
+// JOB TRANSFER
+// ============================================================================
+
+/// Moves a job, with its applicants, to another company
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct TransferJobRequest {
+    pub company_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct JobTransferResponse {
+    pub job_id: Uuid,
+    pub from_company_id: Uuid,
+    pub to_company_id: Uuid,
+    /// Applications that moved with the job, notes included
+    pub applications_moved: i64,
+    /// Applicants emailed about the new hiring company; withdrawn
+    /// applications are not
+    pub applicants_notified: i64,
+}
+
+// ============================================================================
+// LIVE QUEUE COUNTS
+// ============================================================================
+
+/// Items waiting for a moderator, as pushed over GET /api/admin/events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct PendingQueueCounts {
+    pub companies: i64,
+    pub jobs: i64,
+    pub omils: i64,
+    pub flags: i64,
+}
+
+// ============================================================================
+// DOMAIN EVENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "aggregate_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AggregateType {
+    Application,
+    Job,
+    Invitation,
+    /// An OMIL's record of a job seeker it manages
+    ManagedJobSeeker,
+    User,
+}
+
+/// Something that happened to an entity, as written in the transaction of
+/// the change
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct DomainEvent {
+    pub id: i64,
+    pub aggregate_type: AggregateType,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    /// Personal data fields are redacted
+    #[ts(type = "Record<string, unknown>")]
+    pub payload: serde_json::Value,
+    /// None for events raised by the platform itself
+    pub actor_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DomainEventsQuery {
+    pub aggregate_type: AggregateType,
+    pub aggregate_id: Uuid,
+}
+
+// ============================================================================
+// BACKFILLS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "backfill_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BackfillStatus {
+    /// Registered but never started
+    Pending,
+    Running,
+    Paused,
+    Completed,
+    /// A batch failed; starting it again resumes from the last good batch
+    Failed,
+}
+
+/// Progress of an out-of-band data migration
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export)]
+pub struct BackfillJob {
+    pub name: String,
+    pub status: BackfillStatus,
+    /// Where the next batch starts, in the backfill's own terms
+    pub cursor: Option<String>,
+    pub batch_size: i32,
+    /// Rows looked at so far
+    pub processed: i64,
+    pub last_error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// INTERNAL NOTES
+// ============================================================================
+
+/// What an internal note is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "admin_note_entity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AdminNoteEntity {
+    Company,
+    User,
+    Job,
+    Omil,
+}
+
+/// Note staff keep on an entity. Only ever returned by admin endpoints.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct AdminNote {
+    pub id: Uuid,
+    pub entity_type: AdminNoteEntity,
+    pub entity_id: Uuid,
+    /// None once the author is no longer an admin
+    pub author_admin_id: Option<Uuid>,
+    pub author_name: Option<String>,
+    pub body: String,
+    pub is_pinned: bool,
+    /// Admins mentioned in the note
+    pub mentions: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminNoteQuery {
+    pub entity_type: AdminNoteEntity,
+    pub entity_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAdminNoteRequest {
+    pub entity_type: AdminNoteEntity,
+    pub entity_id: Uuid,
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+    pub is_pinned: Option<bool>,
+    /// Admin IDs to notify
+    #[serde(default)]
+    pub mentions: Vec<Uuid>,
+}
+
+/// Mentions are added to the existing ones; already mentioned admins aren't
+/// notified again
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateAdminNoteRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub body: Option<String>,
+    pub is_pinned: Option<bool>,
+    #[serde(default)]
+    pub mentions: Vec<Uuid>,
+}
+
+/// A note the admin was mentioned in, their in-app notification of it
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct AdminNoteMention {
+    pub note: AdminNote,
+    pub mentioned_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminNoteMentionsQuery {
+    pub unread_only: Option<bool>,
+}
+
+/// GET /api/admin/companies/{id}
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct AdminCompanyDetail {
+    pub profile: CompanyProfile,
+    /// Internal notes, pinned first
+    pub notes: Vec<AdminNote>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suppressed_count_threshold() {
+        assert_eq!(SuppressedCount::from_count(0).value, None);
+        assert!(SuppressedCount::from_count(4).suppressed);
+        assert_eq!(SuppressedCount::from_count(5).value, Some(5));
+        assert!(!SuppressedCount::from_count(5).suppressed);
+    }
+
+    #[test]
+    fn test_setting_defaults_match_definitions() {
+        for definition in SETTING_DEFINITIONS {
+            assert_eq!(
+                definition.validate_value(&definition.default_value()),
+                Ok(()),
+                "default for {}",
+                definition.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_setting_value_validation() {
+        let max_apps = SettingDefinition::find("max_applications_per_seeker").unwrap();
+        assert!(max_apps.validate_value(&serde_json::json!(20)).is_ok());
+        assert!(max_apps.validate_value(&serde_json::json!("tweny")).is_err());
+        assert!(max_apps.validate_value(&serde_json::json!(2.5)).is_err());
+        assert!(max_apps.validate_value(&serde_json::json!(0)).is_err());
+        assert!(max_apps.validate_value(&serde_json::json!(1001)).is_err());
+
+        let maintenance = SettingDefinition::find("maintenance_mode").unwrap();
+        assert!(maintenance.validate_value(&serde_json::json!("read_only")).is_ok());
+        assert!(maintenance.validate_value(&serde_json::json!(true)).is_err());
+        assert!(maintenance.validate_value(&serde_json::json!("on")).is_err());
+
+        let choice = SettingDefinition {
+            key: "choice",
+            value_type: SettingValueType::String,
+            min: None,
+            max: None,
+            allowed_values: &["a", "b"],
+            description: "",
+            requires_restart: false,
+            default: "\"a\"",
+        };
+        assert!(choice.validate_value(&serde_json::json!("b")).is_ok());
+        assert!(choice.validate_value(&serde_json::json!("c")).is_err());
+
+        assert!(SettingDefinition::find("unknown_key").is_none());
+    }
+
+    #[test]
+    fn test_rate_over_requires_reportable_cells() {
+        let applied = SuppressedCount::from_count(20);
+        let interviewing = SuppressedCount::from_count(5);
+        let hired = SuppressedCount::from_count(3);
+
+        assert_eq!(interviewing.rate_over(&applied), Some(0.25));
+        assert_eq!(hired.rate_over(&interviewing), None);
+        assert_eq!(applied.rate_over(&hired), None);
+    }
+
+    #[test]
+    fn test_column_suppression_hides_a_complementary_cell() {
+        let values = |counts: &[i64]| -> Vec<Option<i64>> {
+            SuppressedCount::column(counts)
+                .iter()
+                .map(|cell| cell.value)
+                .collect()
+        };
+
+        // The 2 could be worked out from the total; the 6 goes with it
+        assert_eq!(values(&[7, 2, 6]), vec![Some(7), None, None]);
+        // Two hidden cells that together count too few people
+        assert_eq!(values(&[1, 9, 2, 8]), vec![None, Some(9), None, None]);
+        // Hidden zeros reveal nobody
+        assert_eq!(values(&[0, 6, 7]), vec![None, Some(6), Some(7)]);
+        assert_eq!(values(&[10, 20]), vec![Some(10), Some(20)]);
+        assert_eq!(values(&[3]), vec![None]);
+    }
+}
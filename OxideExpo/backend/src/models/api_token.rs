@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use ts_rs::TS;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// Read access to the caller's OMIL: the OMIL GET endpoints and exports
+pub const SCOPE_OMIL_READ: &str = "omil:read";
+
+/// Scopes a token can be issued with
+pub const API_TOKEN_SCOPES: &[&str] = &[SCOPE_OMIL_READ];
+
+// ============================================================================
+// API TOKEN MODEL
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Leading characters of the token, for recognizing it in the list
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// REQUEST/RESPONSE DTOs
+// ============================================================================
+
+fn validate_scopes(scopes: &[String]) -> Result<(), ValidationError> {
+    if scopes.is_empty() {
+        return Err(ValidationError::new("scopes_required"));
+    }
+    if scopes
+        .iter()
+        .any(|s| !API_TOKEN_SCOPES.contains(&s.as_str()))
+    {
+        return Err(ValidationError::new("unknown_scope"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateApiTokenRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_scopes"))]
+    pub scopes: Vec<String>,
+    /// Days until the token expires; omit for a token that lasts until revoked
+    #[validate(range(min = 1, max = 365, message = "Expiry must be 1-365 days"))]
+    pub expires_in_days: Option<i64>,
+}
+
+/// Returned once, at creation; the plaintext token can't be retrieved again
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreatedApiToken {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub api_token: ApiToken,
+    pub token: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_must_be_known() {
+        let request = |scopes: &[&str]| CreateApiTokenRequest {
+            name: "Dashboard municipal".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            expires_in_days: Some(90),
+        };
+
+        assert!(request(&["omil:read"]).validate().is_ok());
+        assert!(request(&[]).validate().is_err());
+        assert!(request(&["omil:read", "omil:write"]).validate().is_err());
+    }
+}
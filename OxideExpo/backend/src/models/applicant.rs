@@ -1,12 +1,15 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Type};
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
-use super::application::ApplicationStatus;
-use super::profile::JobSeekerProfile;
+use super::application::{ApplicationDocumentWithUrl, ApplicationStatus};
+use super::matching::SalaryFit;
+use super::profile::{JobSeekerProfile, SeekerAvailability, UserSkill};
+use crate::utils::normalize::{self, Normalize};
 
 // ============================================================================
 // APPLICATION STATUS HISTORY
@@ -51,6 +54,11 @@ pub struct ApplicantFilterQuery {
     pub applied_to: Option<DateTime<Utc>>,
     /// Filter applicants who have a CV uploaded
     pub has_cv: Option<bool>,
+    /// Comma-separated tag IDs; only applicants carrying all of them are listed
+    pub tags: Option<String>,
+    /// Only applicants who also applied to an earlier posting of a reposted
+    /// job when true, only the others when false
+    pub applied_to_previous: Option<bool>,
     /// Sort field
     pub sort_by: Option<ApplicantSortField>,
     /// Sort direction
@@ -96,12 +104,116 @@ pub struct ApplicantDetailResponse {
     pub reviewed_at: Option<DateTime<Utc>>,
     pub interview_date: Option<DateTime<Utc>>,
     pub interview_notes: Option<String>,
+    /// Office address or video call link
+    pub interview_location: Option<String>,
     pub offer_date: Option<DateTime<Utc>>,
     pub offer_details: Option<String>,
+    /// The applicant's account is suspended
+    pub is_on_hold: bool,
     pub profile: Option<JobSeekerProfile>,
+    pub skills: Vec<UserSkill>,
+    /// Cached match score; None when there is none or the company hides scores
     pub match_score: Option<i32>,
+    pub match_score_computed_at: Option<DateTime<Utc>>,
+    /// The profile or job changed since the score was computed, or it is old
+    pub match_score_is_stale: bool,
+    /// How the applicant's salary expectation compares with the job's range;
+    /// None unless they show it and both sides use the same currency
+    pub salary_expectation_vs_range: Option<SalaryFit>,
     pub cv_url: Option<String>,
+    /// Extra documents the applicant attached, with short-lived download URLs
+    pub documents: Vec<ApplicationDocumentWithUrl>,
+    /// Empty when the applicant keeps their references to themselves
+    pub references: Vec<ApplicantReference>,
     pub status_history: Vec<StatusHistoryWithUser>,
+    /// The profile differs from what it was when the seeker applied
+    pub profile_changed_since_application: bool,
+    /// What changed, per section; None for applications made before the
+    /// profile was fingerprinted
+    pub profile_changes: Option<ProfileChanges>,
+}
+
+/// A reference of the applicant as companies see it, without the referee's
+/// contact email
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicantReference {
+    pub id: Uuid,
+    pub referee_name: String,
+    pub referee_organization: Option<String>,
+    pub relationship: String,
+    /// Short-lived download link of the recommendation letter
+    pub letter_download_url: Option<String>,
+    /// Verified by the applicant's OMIL
+    pub is_verified: bool,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+/// How a seeker's profile moved since they applied. Counts are entries
+/// added or removed; a section is modified when any of its entries was
+/// added or edited afterwards.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ProfileChanges {
+    pub experience_added: i32,
+    pub experience_removed: i32,
+    pub experience_modified: bool,
+    pub education_added: i32,
+    pub education_removed: i32,
+    pub education_modified: bool,
+    pub skills_added: i32,
+    pub skills_removed: i32,
+    pub skills_modified: bool,
+    /// Profile completeness now minus at apply time
+    pub completeness_change: i32,
+}
+
+impl ProfileChanges {
+    /// Anything at all changed
+    pub fn any(&self) -> bool {
+        *self != ProfileChanges::default()
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicantDetailQuery {
+    /// Recompute the match score now instead of showing the cached one
+    pub recalculate: Option<bool>,
+}
+
+/// A rescore of every applicant of a job, waiting for the background worker
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchScoreRecalculation {
+    pub job_id: Uuid,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum MatchRecomputeState {
+    /// Nothing waiting; the job's scores are as fresh as they get
+    Idle,
+    /// Waiting for the background worker
+    Queued,
+    /// The worker is rescoring, `processed_seekers` of `total_seekers` so far
+    Running,
+}
+
+/// Where the background rescore of a job's match scores stands
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchRecomputeStatus {
+    pub job_id: Uuid,
+    pub state: MatchRecomputeState,
+    /// Queued because what the job requires changed, rather than on request
+    pub requirements_changed: bool,
+    pub total_seekers: Option<i32>,
+    pub processed_seekers: i32,
+    pub requested_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
 }
 
 // ============================================================================
@@ -116,20 +228,149 @@ pub struct ApplicantListItem {
     pub status: ApplicationStatus,
     pub applied_at: DateTime<Utc>,
     pub applicant_name: String,
-    pub applicant_email: String,
+    pub applicant_email: Option<String>,
+    /// Cached match score; None when there is none or the company hides scores
     pub match_score: Option<i32>,
+    pub match_score_computed_at: Option<DateTime<Utc>>,
+    /// The profile or job changed since the score was computed, or it is old
+    pub match_score_is_stale: bool,
     pub has_cv: bool,
+    /// The applicant's account is suspended
+    pub is_on_hold: bool,
+    /// The applicant also applied to an earlier posting of this job
+    pub applied_to_previous: bool,
+    pub tags: Vec<AssignedTag>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct PaginatedApplicants {
     pub applicants: Vec<ApplicantListItem>,
+    /// False when the company has turned match scores off
+    pub show_match_scores: bool,
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
 }
 
+// ============================================================================
+// CANDIDATE COMPARISON
+// ============================================================================
+
+/// Fewest and most applications compared at once
+pub const MIN_COMPARED_APPLICATIONS: usize = 2;
+pub const MAX_COMPARED_APPLICATIONS: usize = 5;
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompareApplicantsQuery {
+    /// Comma-separated application IDs, in the order the columns are shown
+    pub ids: String,
+}
+
+/// Application IDs from the comma-separated `ids` parameter, deduplicated,
+/// checked against the number of candidates that can be compared
+pub fn parse_compared_ids(value: &str) -> Result<Vec<Uuid>, String> {
+    let mut ids = Vec::new();
+    for part in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let id = Uuid::parse_str(part).map_err(|_| format!("Invalid application ID: {}", part))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if !(MIN_COMPARED_APPLICATIONS..=MAX_COMPARED_APPLICATIONS).contains(&ids.len()) {
+        return Err(format!(
+            "Compare between {} and {} applications",
+            MIN_COMPARED_APPLICATIONS, MAX_COMPARED_APPLICATIONS
+        ));
+    }
+    Ok(ids)
+}
+
+/// Shortlisted candidates side by side. Every candidate has the same fields,
+/// so each field is a row of the comparison and each candidate a column.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicantComparison {
+    pub job_id: Uuid,
+    /// The job's required skills, the rows of the skills section
+    pub required_skills: Vec<ComparedSkillRequirement>,
+    /// False when the company hides match scores; the scores are then None
+    pub show_match_scores: bool,
+    /// In the order the IDs were given
+    pub candidates: Vec<ComparedCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ComparedSkillRequirement {
+    pub skill_id: Uuid,
+    pub name: String,
+    pub minimum_proficiency: i32,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ComparedCandidate {
+    pub application_id: Uuid,
+    pub applicant_id: Uuid,
+    pub applicant_name: String,
+    pub status: ApplicationStatus,
+    pub applied_at: DateTime<Utc>,
+    /// Cached score per component; None when there is none or scores are hidden
+    pub match_score: Option<ComparedMatchScore>,
+    pub years_of_experience: i32,
+    /// Highest education level on the profile, e.g. `technical`
+    pub highest_education: Option<String>,
+    /// Names of the required skills the candidate meets, endorsements counted
+    /// as in match scores
+    pub matched_required_skills: Vec<String>,
+    /// Names of the required skills the candidate lacks or has below the level
+    pub missing_required_skills: Vec<String>,
+    pub languages: Vec<ComparedLanguage>,
+    /// None unless the seeker shows their salary expectation to companies
+    pub salary_expectation: Option<ComparedSalaryExpectation>,
+    pub availability: Option<SeekerAvailability>,
+    /// None unless the seeker shares the date
+    pub available_from: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ComparedMatchScore {
+    pub total_score: i32,
+    pub skills_score: i32,
+    pub preferred_skills_score: i32,
+    pub languages_score: i32,
+    pub location_score: i32,
+    pub experience_score: i32,
+    pub education_score: i32,
+    pub accommodations_score: i32,
+    pub computed_at: DateTime<Utc>,
+    /// The profile or job changed since the score was computed, or it is old
+    pub is_stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ComparedLanguage {
+    pub name: String,
+    /// basic, intermediate, advanced, fluent or native
+    pub proficiency: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ComparedSalaryExpectation {
+    #[ts(type = "string | null")]
+    #[serde(with = "rust_decimal::serde::str_option")]
+    pub min: Option<Decimal>,
+    #[ts(type = "string | null")]
+    #[serde(with = "rust_decimal::serde::str_option")]
+    pub max: Option<Decimal>,
+    pub currency: String,
+}
+
 // ============================================================================
 // BULK STATUS UPDATE
 // ============================================================================
@@ -138,7 +379,7 @@ pub struct PaginatedApplicants {
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct BulkStatusUpdateRequest {
     /// Application IDs to update
-    #[validate(length(min = 1, max = 100, message = "Must provide 1-100 application IDs"))]
+    #[validate(length(min = 1, max = 50000, message = "Must provide 1-50000 application IDs"))]
     pub application_ids: Vec<Uuid>,
     /// New status
     pub status: ApplicationStatus,
@@ -152,6 +393,58 @@ pub struct BulkStatusUpdateRequest {
 pub struct BulkStatusUpdateResponse {
     pub updated_count: i32,
     pub failed_ids: Vec<Uuid>,
+    /// Applications left untouched because the move is not allowed from their status
+    pub skipped: Vec<SkippedStatusUpdate>,
+    /// One entry per UPDATE issued; empty when the work was queued
+    pub chunks: Vec<BulkChunkSummary>,
+    /// Set when the selection was too large to update within the request
+    pub operation: Option<BulkOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct BulkChunkSummary {
+    pub chunk: i32,
+    pub requested: i32,
+    pub updated: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "bulk_operation_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum BulkOperationStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// Progress of a bulk status update processed in the background
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct BulkOperation {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub target_status: ApplicationStatus,
+    pub status: BulkOperationStatus,
+    pub total: i32,
+    pub processed: i32,
+    pub updated_count: i32,
+    pub failed_ids: Vec<Uuid>,
+    pub skipped_count: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SkippedStatusUpdate {
+    pub application_id: Uuid,
+    pub current_status: ApplicationStatus,
+    pub allowed_statuses: Vec<ApplicationStatus>,
 }
 
 // ============================================================================
@@ -166,6 +459,264 @@ pub struct CvDownloadResponse {
     pub content_type: String,
 }
 
+// ============================================================================
+// APPLICANT TAGS
+// ============================================================================
+
+/// Tag definitions a company may have
+pub const MAX_APPLICATION_TAGS: i64 = 30;
+
+pub const DEFAULT_TAG_COLOR: &str = "#6B7280";
+
+fn validate_tag_color(value: &str) -> Result<(), ValidationError> {
+    let valid = value.len() == 7
+        && value.starts_with('#')
+        && value[1..].chars().all(|c| c.is_ascii_hexdigit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_color")
+            .with_message("Color must be a hex value like #1A2B3C".into()))
+    }
+}
+
+/// Company-defined label for applicants; internal to the company
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicationTag {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A tag as shown on an applicant
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AssignedTag {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateApplicationTagRequest {
+    #[validate(length(min = 1, max = 50, message = "Tag name must be 1-50 characters"))]
+    pub name: String,
+    /// Defaults to grey
+    #[validate(custom(function = "validate_tag_color"))]
+    pub color: Option<String>,
+}
+
+impl Normalize for CreateApplicationTagRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.name);
+        normalize::trim_opt(&mut self.color);
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateApplicationTagRequest {
+    #[validate(length(min = 1, max = 50, message = "Tag name must be 1-50 characters"))]
+    pub name: Option<String>,
+    #[validate(custom(function = "validate_tag_color"))]
+    pub color: Option<String>,
+}
+
+impl Normalize for UpdateApplicationTagRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.name);
+        normalize::trim_opt(&mut self.color);
+    }
+}
+
+/// Tag IDs from the comma-separated `tags` filter, deduplicated
+pub fn parse_tag_filter(value: &str) -> Result<Vec<Uuid>, String> {
+    let mut ids = Vec::new();
+    for part in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let id = Uuid::parse_str(part).map_err(|_| format!("Invalid tag ID: {}", part))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+// ============================================================================
+// FUNNEL AUTOMATION RULES
+// ============================================================================
+
+/// Automation rules a job may have
+pub const MAX_AUTOMATION_RULES_PER_JOB: i64 = 5;
+
+/// Longest experience a rule condition may ask about
+pub const MAX_CONDITION_YEARS_EXPERIENCE: i32 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "automation_trigger", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum AutomationTrigger {
+    OnApplicationSubmitted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "automation_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum AutomationAction {
+    /// Move the application to `target_status`
+    SetStatus,
+    /// Put the company tag `tag_id` on the application
+    AddTag,
+    /// Email the company member `notify_user_id`
+    NotifyMember,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum Comparator {
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Eq,
+}
+
+impl Comparator {
+    pub fn holds(&self, actual: i32, expected: i32) -> bool {
+        match self {
+            Comparator::Lt => actual < expected,
+            Comparator::Lte => actual <= expected,
+            Comparator::Gt => actual > expected,
+            Comparator::Gte => actual >= expected,
+            Comparator::Eq => actual == expected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct NumericCondition {
+    pub op: Comparator,
+    pub value: i32,
+}
+
+/// What an application must meet for a rule to act; every criterion given
+/// must hold, and an empty condition matches every application
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AutomationCondition {
+    /// Applicant's match score for the job, 0-100
+    pub match_score: Option<NumericCondition>,
+    /// Whether the application or the applicant's profile has a CV
+    pub has_cv: Option<bool>,
+    /// Whole years across the applicant's work experience
+    pub years_experience: Option<NumericCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobAutomationRule {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub condition: AutomationCondition,
+    pub action: AutomationAction,
+    pub target_status: Option<ApplicationStatus>,
+    pub tag_id: Option<Uuid>,
+    pub notify_user_id: Option<Uuid>,
+    pub is_enabled: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Creates a rule, or replaces one entirely on update
+#[derive(Debug, Deserialize, Validate, TS)]
+#[validate(schema(function = "validate_automation_rule"))]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AutomationRuleRequest {
+    #[validate(length(min = 1, max = 100, message = "Rule name must be 1-100 characters"))]
+    pub name: String,
+    /// Defaults to on_application_submitted
+    pub trigger: Option<AutomationTrigger>,
+    #[serde(default)]
+    pub condition: AutomationCondition,
+    pub action: AutomationAction,
+    pub target_status: Option<ApplicationStatus>,
+    pub tag_id: Option<Uuid>,
+    pub notify_user_id: Option<Uuid>,
+    /// Defaults to true
+    pub is_enabled: Option<bool>,
+}
+
+impl Normalize for AutomationRuleRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.name);
+    }
+}
+
+fn validate_automation_rule(req: &AutomationRuleRequest) -> Result<(), ValidationError> {
+    if let Some(score) = req.condition.match_score {
+        if !(0..=100).contains(&score.value) {
+            return Err(ValidationError::new("invalid_condition")
+                .with_message("Match score conditions must be between 0 and 100".into()));
+        }
+    }
+    if let Some(years) = req.condition.years_experience {
+        if !(0..=MAX_CONDITION_YEARS_EXPERIENCE).contains(&years.value) {
+            return Err(ValidationError::new("invalid_condition").with_message(
+                format!(
+                    "Experience conditions must be between 0 and {} years",
+                    MAX_CONDITION_YEARS_EXPERIENCE
+                )
+                .into(),
+            ));
+        }
+    }
+
+    let params_match = match req.action {
+        AutomationAction::SetStatus => {
+            req.target_status.is_some() && req.tag_id.is_none() && req.notify_user_id.is_none()
+        }
+        AutomationAction::AddTag => {
+            req.tag_id.is_some() && req.target_status.is_none() && req.notify_user_id.is_none()
+        }
+        AutomationAction::NotifyMember => {
+            req.notify_user_id.is_some() && req.target_status.is_none() && req.tag_id.is_none()
+        }
+    };
+    if !params_match {
+        return Err(ValidationError::new("invalid_action").with_message(
+            "set_status needs only target_status, add_tag only tag_id and notify_member only notify_user_id".into(),
+        ));
+    }
+
+    // Rules run on submission, so the status has to be one a new application can move to
+    if let Some(status) = req.target_status {
+        if !ApplicationStatus::Submitted
+            .company_transitions()
+            .contains(&status)
+        {
+            return Err(ValidationError::new("invalid_target_status").with_message(
+                format!("A new application cannot be moved to {:?}", status).into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // EXCEL EXPORT
 // ============================================================================
@@ -178,3 +729,46 @@ pub struct ExportApplicantsQuery {
     /// Include contact info in export
     pub include_contact: Option<bool>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag_filter() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        assert_eq!(
+            parse_tag_filter(&format!("{}, {},{}", a, b, a)).unwrap(),
+            vec![a, b]
+        );
+        assert!(parse_tag_filter("").unwrap().is_empty());
+        assert!(parse_tag_filter("bilingual").is_err());
+    }
+
+    #[test]
+    fn test_parse_compared_ids() {
+        let ids: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+        let list = |ids: &[Uuid]| {
+            ids.iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!(parse_compared_ids(&list(&ids[..2])).unwrap(), ids[..2]);
+        assert_eq!(parse_compared_ids(&list(&ids[..5])).unwrap(), ids[..5]);
+        assert!(parse_compared_ids(&list(&ids[..1])).is_err());
+        assert!(parse_compared_ids(&list(&ids)).is_err());
+        // Repeating an ID doesn't make another candidate
+        assert!(parse_compared_ids(&list(&[ids[0], ids[0]])).is_err());
+        assert!(parse_compared_ids(&format!("{},nope", ids[0])).is_err());
+    }
+
+    #[test]
+    fn test_validate_tag_color() {
+        assert!(validate_tag_color("#1a2B3c").is_ok());
+        assert!(validate_tag_color("1A2B3C").is_err());
+        assert!(validate_tag_color("#12345").is_err());
+        assert!(validate_tag_color("#GGGGGG").is_err());
+    }
+}
@@ -3,10 +3,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Type};
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use super::job::{Job, PublicJobListing};
 use super::profile::JobSeekerProfile;
+use crate::utils::normalize::{self, Normalize};
 
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0002_create_enums.sql)
@@ -20,13 +21,105 @@ pub enum ApplicationStatus {
     Submitted,
     UnderReview,
     Shortlisted,
-    Interviewed,
-    Offered,
-    Accepted,
+    InterviewScheduled,
+    OfferExtended,
+    Hired,
     Rejected,
     Withdrawn,
 }
 
+impl ApplicationStatus {
+    pub const ALL: [ApplicationStatus; 8] = [
+        ApplicationStatus::Submitted,
+        ApplicationStatus::UnderReview,
+        ApplicationStatus::Shortlisted,
+        ApplicationStatus::InterviewScheduled,
+        ApplicationStatus::OfferExtended,
+        ApplicationStatus::Hired,
+        ApplicationStatus::Rejected,
+        ApplicationStatus::Withdrawn,
+    ];
+
+    /// Every status a company or job seeker can move an application to from here.
+    /// Hired, rejected and withdrawn are terminal.
+    pub fn allowed_transitions(&self) -> &'static [ApplicationStatus] {
+        use ApplicationStatus::*;
+
+        match self {
+            Submitted => &[UnderReview, Rejected, Withdrawn],
+            UnderReview => &[Shortlisted, InterviewScheduled, Rejected, Withdrawn],
+            Shortlisted => &[InterviewScheduled, Rejected, Withdrawn],
+            InterviewScheduled => &[OfferExtended, Rejected, Withdrawn],
+            OfferExtended => &[Hired, Rejected, Withdrawn],
+            Hired | Rejected | Withdrawn => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: ApplicationStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Spanish label shown to people, e.g. in application notes
+    pub fn label(self) -> &'static str {
+        match self {
+            ApplicationStatus::Submitted => "Recibida",
+            ApplicationStatus::UnderReview => "En revisión",
+            ApplicationStatus::Shortlisted => "Preseleccionada",
+            ApplicationStatus::InterviewScheduled => "Entrevista agendada",
+            ApplicationStatus::OfferExtended => "Oferta enviada",
+            ApplicationStatus::Hired => "Contratada",
+            ApplicationStatus::Rejected => "Rechazada",
+            ApplicationStatus::Withdrawn => "Retirada",
+        }
+    }
+
+    /// Transitions available to the hiring company; only the seeker can withdraw
+    pub fn company_transitions(&self) -> Vec<ApplicationStatus> {
+        self.allowed_transitions()
+            .iter()
+            .copied()
+            .filter(|status| *status != ApplicationStatus::Withdrawn)
+            .collect()
+    }
+
+    /// Every status an application can be moved to `target` from
+    pub fn transition_sources(target: ApplicationStatus) -> Vec<ApplicationStatus> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|status| status.can_transition_to(target))
+            .collect()
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.allowed_transitions().is_empty()
+    }
+}
+
+/// How an application came in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "application_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ApplicationSource {
+    Direct,
+    /// Submitted while accepting an invitation
+    Invitation,
+}
+
+/// Why a company did not select a candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "rejection_reason_code", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum RejectionReasonCode {
+    ProfileMismatch,
+    Experience,
+    Location,
+    PositionFilled,
+    Other,
+}
+
 // ============================================================================
 // CORE APPLICATION STRUCT
 // ============================================================================
@@ -51,6 +144,8 @@ pub struct JobApplication {
     // Interview
     pub interview_date: Option<DateTime<Utc>>,
     pub interview_notes: Option<String>,
+    /// Office address or video call link
+    pub interview_location: Option<String>,
 
     // Offer
     pub offer_date: Option<DateTime<Utc>>,
@@ -60,11 +155,31 @@ pub struct JobApplication {
     // Withdrawal
     pub withdrawal_reason: Option<String>,
 
+    // Rejection
+    pub rejection_reason_code: Option<RejectionReasonCode>,
+    pub rejection_feedback: Option<String>,
+    /// The company chose to show the reason and feedback to the candidate
+    pub rejection_feedback_shared: bool,
+
+    /// The applicant's account is suspended
+    pub is_on_hold: bool,
+
     // Metadata
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl JobApplication {
+    /// Candidate view: unless the company shared them, the reason and
+    /// feedback are withheld and the seeker only sees the rejected status
+    pub fn hide_unshared_rejection(&mut self) {
+        if !self.rejection_feedback_shared {
+            self.rejection_reason_code = None;
+            self.rejection_feedback = None;
+        }
+    }
+}
+
 // ============================================================================
 // APPLICATION NOTES
 // ============================================================================
@@ -96,6 +211,10 @@ pub struct CreateApplicationRequest {
     #[validate(url(message = "Invalid resume URL"))]
     #[validate(length(max = 500, message = "Resume URL too long"))]
     pub resume_url: Option<String>,
+
+    /// One of the seeker's cover letter templates, filled in for the job;
+    /// instead of cover_letter
+    pub template_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -108,8 +227,45 @@ pub struct UpdateApplicationStatusRequest {
     #[validate(length(max = 2000, message = "Interview notes too long"))]
     pub interview_notes: Option<String>,
 
+    /// Office address or video call link
+    #[validate(length(max = 500, message = "Interview location too long"))]
+    pub interview_location: Option<String>,
+
     #[validate(length(max = 2000, message = "Offer details too long"))]
     pub offer_details: Option<String>,
+
+    /// Only when rejecting
+    pub rejection_reason_code: Option<RejectionReasonCode>,
+
+    /// Only when rejecting
+    #[validate(length(max = 2000, message = "Feedback too long"))]
+    pub feedback_text: Option<String>,
+
+    /// Show the reason and feedback to the candidate (default false)
+    pub share_feedback_with_candidate: Option<bool>,
+}
+
+impl UpdateApplicationStatusRequest {
+    pub fn has_rejection_details(&self) -> bool {
+        self.rejection_reason_code.is_some()
+            || self.feedback_text.is_some()
+            || self.share_feedback_with_candidate.is_some()
+    }
+}
+
+/// Moves a scheduled interview; the calendar event keeps its UID
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RescheduleInterviewRequest {
+    pub interview_date: DateTime<Utc>,
+
+    /// Office address or video call link; unchanged when omitted
+    #[validate(length(max = 500, message = "Interview location too long"))]
+    pub interview_location: Option<String>,
+
+    /// Unchanged when omitted
+    #[validate(length(max = 2000, message = "Interview notes too long"))]
+    pub interview_notes: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -158,6 +314,18 @@ pub struct FullApplicationResponse {
     pub notes: Vec<ApplicationNote>,
 }
 
+/// One status change as the candidate sees it; who made it is not shown
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicationTimelineEntry {
+    pub previous_status: Option<ApplicationStatus>,
+    pub status: ApplicationStatus,
+    /// On the rejected entry, when the company shared it
+    pub rejection_reason_code: Option<RejectionReasonCode>,
+    pub rejection_feedback: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Application note with creator info
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
@@ -166,3 +334,355 @@ pub struct ApplicationNoteWithCreator {
     pub creator_name: String,
     pub creator_email: String,
 }
+
+// ============================================================================
+// V13: APPLICATION DRAFTS
+// ============================================================================
+
+/// Drafts a job seeker can hold at once; saving another evicts the oldest
+pub const MAX_APPLICATION_DRAFTS: i64 = 20;
+
+/// Minimum seconds between two saves of the same draft
+pub const DRAFT_SAVE_INTERVAL_SECONDS: i64 = 5;
+
+const MAX_DRAFT_ANSWERS_BYTES: usize = 20_000;
+
+/// Screening answers must be a JSON object of reasonable size
+fn validate_draft_answers(answers: &serde_json::Value) -> Result<(), ValidationError> {
+    if !answers.is_object() {
+        return Err(ValidationError::new("answers_not_object")
+            .with_message("Answers must be an object keyed by question".into()));
+    }
+    if answers.to_string().len() > MAX_DRAFT_ANSWERS_BYTES {
+        return Err(ValidationError::new("answers_too_large")
+            .with_message("Answers too long".into()));
+    }
+
+    Ok(())
+}
+
+/// Autosaved application, visible only to the job seeker
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicationDraft {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub job_id: Uuid,
+    pub cover_letter: Option<String>,
+    #[ts(type = "Record<string, unknown>")]
+    pub answers: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SaveApplicationDraftRequest {
+    #[validate(length(max = 5000, message = "Cover letter too long"))]
+    pub cover_letter: Option<String>,
+
+    #[validate(custom(function = "validate_draft_answers"))]
+    #[ts(type = "Record<string, unknown> | null")]
+    pub answers: Option<serde_json::Value>,
+}
+
+impl CreateApplicationRequest {
+    /// Take fields the seeker left out of the submission from their draft;
+    /// anything sent explicitly wins
+    pub fn fill_from_draft(&mut self, draft: &ApplicationDraft) {
+        if self.cover_letter.is_none() {
+            self.cover_letter = draft.cover_letter.clone();
+        }
+    }
+}
+
+// ============================================================================
+// V13: APPLICATION DOCUMENTS
+// ============================================================================
+
+/// Extra documents a seeker can attach to one application
+pub const MAX_APPLICATION_DOCUMENTS: i64 = 3;
+
+/// Largest document accepted, in bytes
+pub const MAX_APPLICATION_DOCUMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// PDF, JPEG, PNG and DOCX
+pub const APPLICATION_DOCUMENT_CONTENT_TYPES: [&str; 4] = [
+    "application/pdf",
+    "image/jpeg",
+    "image/png",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+];
+
+/// Minutes a company's signed document download URL stays valid
+pub const APPLICATION_DOCUMENT_URL_MINUTES: u64 = 15;
+
+impl ApplicationStatus {
+    /// Documents can be added until the company starts shortlisting
+    pub fn accepts_documents(&self) -> bool {
+        matches!(self, ApplicationStatus::Submitted | ApplicationStatus::UnderReview)
+    }
+}
+
+/// Storage folder for an application's documents
+pub fn application_documents_folder(application_id: Uuid) -> String {
+    format!("applications/{}/docs", application_id)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicationDocument {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    #[serde(skip_serializing)]
+    #[ts(skip)]
+    pub file_key: String,
+    pub label: String,
+    pub original_filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub uploaded_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A document as the hiring company sees it, with a short-lived download link
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplicationDocumentWithUrl {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub document: ApplicationDocument,
+    pub download_url: String,
+}
+
+// ============================================================================
+// V13: COVER LETTER TEMPLATES
+// ============================================================================
+
+/// Templates a job seeker can keep
+pub const MAX_COVER_LETTER_TEMPLATES: i64 = 10;
+
+/// A reusable cover letter. `{{job_title}}` and `{{company_name}}` in the body
+/// are filled in from the job when applying with it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CoverLetterTemplate {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub body: String,
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateCoverLetterTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Template name must be 1-100 characters"))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 5000, message = "Template must be 1-5000 characters"))]
+    pub body: String,
+}
+
+impl Normalize for CreateCoverLetterTemplateRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.name);
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateCoverLetterTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Template name must be 1-100 characters"))]
+    pub name: Option<String>,
+
+    #[validate(length(min = 1, max = 5000, message = "Template must be 1-5000 characters"))]
+    pub body: Option<String>,
+}
+
+impl Normalize for UpdateCoverLetterTemplateRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.name);
+    }
+}
+
+/// A submitted application, with anything the seeker should know about how
+/// it was built
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SubmitApplicationResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub application: JobApplication,
+    /// Unknown placeholders left as written in a template's cover letter,
+    /// a nudge to check the match score when recent applications were
+    /// mostly rejected as a profile mismatch, a notice when the job pays
+    /// less than the seeker expects, and how the seeker's application to an
+    /// earlier posting of a reposted job ended
+    pub warnings: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApplicationStatus::{self, *};
+    use super::{ApplicationDraft, CreateApplicationRequest, JobApplication, RejectionReasonCode};
+
+    const ALL: [ApplicationStatus; 8] = [
+        Submitted,
+        UnderReview,
+        Shortlisted,
+        InterviewScheduled,
+        OfferExtended,
+        Hired,
+        Rejected,
+        Withdrawn,
+    ];
+
+    #[test]
+    fn test_transition_matrix() {
+        let legal = [
+            (Submitted, UnderReview),
+            (Submitted, Rejected),
+            (Submitted, Withdrawn),
+            (UnderReview, Shortlisted),
+            (UnderReview, InterviewScheduled),
+            (UnderReview, Rejected),
+            (UnderReview, Withdrawn),
+            (Shortlisted, InterviewScheduled),
+            (Shortlisted, Rejected),
+            (Shortlisted, Withdrawn),
+            (InterviewScheduled, OfferExtended),
+            (InterviewScheduled, Rejected),
+            (InterviewScheduled, Withdrawn),
+            (OfferExtended, Hired),
+            (OfferExtended, Rejected),
+            (OfferExtended, Withdrawn),
+        ];
+
+        for from in ALL {
+            for to in ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    legal.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_terminal_states() {
+        for status in ALL {
+            assert_eq!(
+                status.is_terminal(),
+                matches!(status, Hired | Rejected | Withdrawn)
+            );
+        }
+    }
+
+    #[test]
+    fn test_company_cannot_withdraw() {
+        for status in ALL {
+            assert!(!status.company_transitions().contains(&Withdrawn));
+        }
+        assert_eq!(OfferExtended.company_transitions(), vec![Hired, Rejected]);
+    }
+
+    fn draft(cover_letter: Option<&str>) -> ApplicationDraft {
+        ApplicationDraft {
+            id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            job_id: uuid::Uuid::new_v4(),
+            cover_letter: cover_letter.map(str::to_string),
+            answers: serde_json::json!({}),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn request(cover_letter: Option<&str>) -> CreateApplicationRequest {
+        CreateApplicationRequest {
+            job_id: uuid::Uuid::new_v4(),
+            cover_letter: cover_letter.map(str::to_string),
+            resume_url: None,
+            template_id: None,
+        }
+    }
+
+    #[test]
+    fn test_draft_fills_omitted_cover_letter() {
+        let mut payload = request(None);
+        payload.fill_from_draft(&draft(Some("Borrador guardado")));
+        assert_eq!(payload.cover_letter.as_deref(), Some("Borrador guardado"));
+    }
+
+    #[test]
+    fn test_explicit_cover_letter_wins_over_draft() {
+        let mut payload = request(Some("Versión final"));
+        payload.fill_from_draft(&draft(Some("Borrador guardado")));
+        assert_eq!(payload.cover_letter.as_deref(), Some("Versión final"));
+
+        // An explicitly empty letter is still explicit
+        let mut payload = request(Some(""));
+        payload.fill_from_draft(&draft(Some("Borrador guardado")));
+        assert_eq!(payload.cover_letter.as_deref(), Some(""));
+    }
+
+    fn rejected_application(shared: bool) -> JobApplication {
+        JobApplication {
+            id: uuid::Uuid::new_v4(),
+            job_id: uuid::Uuid::new_v4(),
+            applicant_id: uuid::Uuid::new_v4(),
+            status: Rejected,
+            cover_letter: None,
+            resume_url: None,
+            applied_at: chrono::Utc::now(),
+            reviewed_at: None,
+            reviewed_by: None,
+            interview_date: None,
+            interview_notes: None,
+            interview_location: None,
+            offer_date: None,
+            offer_details: None,
+            response_date: None,
+            withdrawal_reason: None,
+            rejection_reason_code: Some(RejectionReasonCode::Experience),
+            rejection_feedback: Some("Buscamos al menos dos años en bodega".to_string()),
+            rejection_feedback_shared: shared,
+            is_on_hold: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_unshared_rejection_is_hidden_from_candidate() {
+        let mut application = rejected_application(false);
+        application.hide_unshared_rejection();
+        assert_eq!(application.rejection_reason_code, None);
+        assert_eq!(application.rejection_feedback, None);
+
+        let mut application = rejected_application(true);
+        application.hide_unshared_rejection();
+        assert_eq!(
+            application.rejection_reason_code,
+            Some(RejectionReasonCode::Experience)
+        );
+        assert!(application.rejection_feedback.is_some());
+    }
+
+    #[test]
+    fn test_documents_only_while_under_consideration() {
+        for status in ALL {
+            assert_eq!(
+                status.accepts_documents(),
+                matches!(status, Submitted | UnderReview)
+            );
+        }
+    }
+}
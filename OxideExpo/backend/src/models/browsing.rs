@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::job::JobStatus;
+
+// ============================================================================
+// RECENTLY VIEWED JOBS
+// ============================================================================
+
+/// A job the seeker viewed, as it is now
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RecentlyViewedJob {
+    pub job_id: Uuid,
+    pub title: String,
+    pub company_name: String,
+    pub company_logo_url: Option<String>,
+    /// Viewed jobs may have closed since
+    pub status: JobStatus,
+    pub viewed_at: DateTime<Utc>,
+    pub has_applied: bool,
+    pub is_saved: bool,
+}
+
+// ============================================================================
+// RECENT SEARCHES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RecentSearch {
+    pub id: Uuid,
+    /// Query parameters of GET /api/jobs, without pagination
+    #[ts(type = "Record<string, any>")]
+    pub filters: serde_json::Value,
+    pub searched_at: DateTime<Utc>,
+}
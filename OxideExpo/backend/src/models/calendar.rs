@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+// ============================================================================
+// CALENDAR TOKENS
+// ============================================================================
+
+/// A member's secret token for subscribing to the company interview feed
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CalendarToken {
+    /// Only returned when the token is created; revoke it to get a new one
+    pub token: Option<String>,
+    /// URL to subscribe to, with the token in it; only returned with the token
+    pub feed_url: Option<String>,
+    /// Leading characters of the token, for recognizing it later
+    pub token_prefix: String,
+    pub created_at: DateTime<Utc>,
+    /// Last time a calendar app read the feed
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// REQUEST DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarFeedQuery {
+    /// Calendar token, for calendar apps that can't send a JWT
+    pub token: Option<String>,
+}
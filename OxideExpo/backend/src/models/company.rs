@@ -3,9 +3,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Type};
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use super::user::UserResponse;
+use crate::utils::normalize::{self, validate_phone, Normalize};
+use crate::utils::rut::validate_rut;
 
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0002_create_enums.sql)
@@ -20,6 +22,10 @@ pub enum OrganizationStatus {
     Active,
     Suspended,
     Rejected,
+    /// Wound down for deletion; jobs closed and members deactivated, can be reopened
+    Closing,
+    /// Purged; the profile is anonymized
+    Deleted,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
@@ -89,8 +95,13 @@ pub struct UpdateCompanyProfileRequest {
     #[validate(length(max = 255, message = "Legal name too long"))]
     pub legal_name: Option<String>,
     #[validate(length(max = 50, message = "Tax ID too long"))]
+    #[validate(custom(function = "validate_rut"))]
     pub tax_id: Option<String>,
+    /// Deprecated: sets the primary industry; use `industries` instead
     pub industry_id: Option<Uuid>,
+    /// Replaces the company's industries (1-3, exactly one primary)
+    #[validate(custom(function = "validate_company_industries"))]
+    pub industries: Option<Vec<CompanyIndustryInput>>,
     pub company_size: Option<String>,
     #[validate(range(min = 1800, max = 2100, message = "Founded year must be between 1800 and 2100"))]
     pub founded_year: Option<i32>,
@@ -99,6 +110,7 @@ pub struct UpdateCompanyProfileRequest {
     #[validate(length(max = 500, message = "Address too long"))]
     pub address: Option<String>,
     #[validate(length(max = 20, message = "Phone number too long"))]
+    #[validate(custom(function = "validate_phone"))]
     pub phone: Option<String>,
     #[validate(url(message = "Invalid website URL"))]
     #[validate(length(max = 500, message = "Website URL too long"))]
@@ -127,6 +139,76 @@ pub struct UpdateCompanyProfileRequest {
     pub benefits: Option<String>,
 }
 
+impl Normalize for UpdateCompanyProfileRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.company_name);
+        normalize::trim_opt(&mut self.legal_name);
+        normalize::rut_opt(&mut self.tax_id);
+        normalize::trim_opt(&mut self.company_size);
+        normalize::trim_opt(&mut self.address);
+        normalize::phone_opt(&mut self.phone);
+        normalize::trim_opt(&mut self.website_url);
+        normalize::trim_opt(&mut self.linkedin_url);
+        normalize::trim_opt(&mut self.video_url);
+        normalize::trim_opt(&mut self.logo_url);
+        normalize::trim_opt(&mut self.cover_image_url);
+        normalize::trim_opt(&mut self.description);
+        normalize::trim_opt(&mut self.mission);
+        normalize::trim_opt(&mut self.vision);
+        normalize::trim_opt(&mut self.culture);
+        normalize::trim_opt(&mut self.benefits);
+    }
+}
+
+// ============================================================================
+// V13: COMPANY INDUSTRIES
+// ============================================================================
+
+/// Companies may span up to this many industries, one of them primary
+pub const MAX_COMPANY_INDUSTRIES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyIndustryInput {
+    pub industry_id: Uuid,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyIndustry {
+    pub industry_id: Uuid,
+    pub industry_name: String,
+    pub is_primary: bool,
+}
+
+/// Validate a replacement industry list: 1-3 distinct industries with
+/// exactly one marked primary
+pub fn validate_company_industries(
+    industries: &[CompanyIndustryInput],
+) -> Result<(), ValidationError> {
+    if industries.is_empty() || industries.len() > MAX_COMPANY_INDUSTRIES {
+        return Err(ValidationError::new("industries_count")
+            .with_message("A company must have between 1 and 3 industries".into()));
+    }
+
+    let mut ids: Vec<Uuid> = industries.iter().map(|i| i.industry_id).collect();
+    ids.sort();
+    ids.dedup();
+    if ids.len() != industries.len() {
+        return Err(ValidationError::new("industries_duplicate")
+            .with_message("Each industry can only be listed once".into()));
+    }
+
+    if industries.iter().filter(|i| i.is_primary).count() != 1 {
+        return Err(ValidationError::new("industries_primary")
+            .with_message("Exactly one industry must be marked as primary".into()));
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // COMPANY MEMBERS
 // ============================================================================
@@ -167,6 +249,7 @@ pub struct PublicCompanyProfile {
     pub id: Uuid,
     pub company_name: String,
     pub industry_id: Option<Uuid>,
+    pub industries: Vec<CompanyIndustry>,
     pub company_size: Option<String>,
     pub founded_year: Option<i32>,
     pub region_id: Option<Uuid>,
@@ -191,6 +274,7 @@ impl From<CompanyProfile> for PublicCompanyProfile {
             id: profile.id,
             company_name: profile.company_name,
             industry_id: profile.industry_id,
+            industries: Vec::new(),
             company_size: profile.company_size,
             founded_year: profile.founded_year,
             region_id: profile.region_id,
@@ -211,6 +295,13 @@ impl From<CompanyProfile> for PublicCompanyProfile {
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PublicCompanyListQuery {
+    /// Matches companies listing this industry, primary or not
+    pub industry_id: Option<Uuid>,
+}
+
 /// Company member with full user details
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
@@ -228,7 +319,329 @@ pub struct CompanyMemberWithUser {
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct FullCompanyProfileResponse {
-    pub profile: CompanyProfile,
+    pub profile: OwnCompanyProfile,
     pub members: Vec<CompanyMemberWithUser>,
     pub current_user_role: MemberRole,
 }
+
+// ============================================================================
+// V13: PENDING PROFILE CHANGE DTOs
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "profile_change_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ProfileChangeStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PendingProfileChange {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub field_name: String,
+    pub old_value: Option<String>,
+    pub new_value: String,
+    pub status: ProfileChangeStatus,
+    pub requested_by: Option<Uuid>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The company's own view of its profile: pending values are shown in place
+/// of the approved ones and listed in `pending_changes`
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OwnCompanyProfile {
+    #[serde(flatten)]
+    pub profile: CompanyProfile,
+    pub industries: Vec<CompanyIndustry>,
+    pub has_pending_changes: bool,
+    pub pending_changes: Vec<PendingProfileChange>,
+}
+
+impl OwnCompanyProfile {
+    pub fn new(
+        mut profile: CompanyProfile,
+        industries: Vec<CompanyIndustry>,
+        pending_changes: Vec<PendingProfileChange>,
+    ) -> Self {
+        for change in &pending_changes {
+            profile.set_sensitive_field(&change.field_name, change.new_value.clone());
+        }
+
+        Self {
+            profile,
+            industries,
+            has_pending_changes: !pending_changes.is_empty(),
+            pending_changes,
+        }
+    }
+}
+
+impl CompanyProfile {
+    /// Sets one of the fields that, once the company is active, only change
+    /// after admin review: company_name, legal_name or tax_id
+    pub fn set_sensitive_field(&mut self, field: &str, value: String) {
+        match field {
+            "company_name" => self.company_name = value,
+            "legal_name" => self.legal_name = Some(value),
+            "tax_id" => self.tax_id = Some(value),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RejectProfileChangeRequest {
+    #[validate(length(min = 10, max = 1000))]
+    pub rejection_reason: String,
+}
+
+// ============================================================================
+// V13: BENCHMARK DTOs
+// ============================================================================
+
+/// Which set of jobs a platform benchmark was computed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum BenchmarkScope {
+    /// Active jobs with the same industry and region
+    Bucket,
+    /// All active jobs, used when the bucket is too small to stay anonymous
+    Global,
+    /// Not even the global pool has enough jobs
+    InsufficientData,
+}
+
+/// Anonymous platform percentiles over the last 30 days
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PlatformBenchmark {
+    pub scope: BenchmarkScope,
+    pub jobs_count: i64,
+    pub views_median: Option<f64>,
+    pub views_p75: Option<f64>,
+    pub applications_median: Option<f64>,
+    pub applications_p75: Option<f64>,
+    /// Applications per view, only over jobs that had views
+    pub conversion_median: Option<f64>,
+    pub conversion_p75: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobBenchmark {
+    pub job_id: Uuid,
+    pub title: String,
+    pub industry_id: Option<Uuid>,
+    pub region_id: Option<Uuid>,
+    pub views: i64,
+    pub applications: i64,
+    /// None when the job had no views in the period
+    pub conversion_rate: Option<f64>,
+    pub benchmark: PlatformBenchmark,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyBenchmarksResponse {
+    pub period_days: i64,
+    pub jobs: Vec<JobBenchmark>,
+}
+
+// ============================================================================
+// V13: COMPANY SETTINGS
+// ============================================================================
+
+/// Preferences that change how the company's own views behave
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanySettings {
+    /// Show match scores in the applicant list and detail
+    pub show_match_scores: bool,
+    /// Include the company's jobs in the public syndication feeds
+    pub syndication_enabled: bool,
+    /// Whether exports may include applicants' contact details; only
+    /// administrators change it
+    pub allow_contact_export: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateCompanySettingsRequest {
+    pub show_match_scores: Option<bool>,
+    pub syndication_enabled: Option<bool>,
+}
+
+// ============================================================================
+// V13: COMPANY DATA EXPORTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "data_export_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum DataExportStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// Progress of a company data export built in the background
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyDataExport {
+    pub id: Uuid,
+    pub status: DataExportStatus,
+    /// Known once the export has started
+    pub total_jobs: Option<i32>,
+    pub processed_jobs: i32,
+    pub file_size: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The file can be downloaded until then
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Signed ZIP download link, valid until expires_at
+    pub download_url: Option<String>,
+}
+
+// ============================================================================
+// V13: COMPANY DELETION
+// ============================================================================
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct DeleteCompanyQuery {
+    /// Purge a wound-down company before its waiting period is over
+    pub force: Option<bool>,
+}
+
+/// Where a company deletion stands after a step
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyDeletionResponse {
+    pub company_id: Uuid,
+    /// Closing after winding down, deleted after the purge
+    pub status: OrganizationStatus,
+    /// When the company can be purged without force; None once purged
+    pub purge_after: Option<DateTime<Utc>>,
+    pub jobs_closed: i64,
+    pub members_deactivated: i64,
+    pub applicants_notified: i64,
+    pub jobs_archived: i64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReopenCompanyResponse {
+    pub company_id: Uuid,
+    pub status: OrganizationStatus,
+    pub jobs_reopened: i64,
+    pub members_reactivated: i64,
+}
+
+// ============================================================================
+// USAGE METERING
+// ============================================================================
+
+/// Metered action of a company, one `usage_events` row each time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "usage_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum UsageEventType {
+    /// A job went active, on approval, reactivation or scheduled publication
+    JobPublished,
+    /// Recommended candidates were listed for a job
+    CandidateSearch,
+    InvitationSent,
+    CvDownload,
+}
+
+/// Query parameters for usage reports
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    /// YYYY-MM; the current month in the platform time zone by default
+    pub month: Option<String>,
+}
+
+/// Totals of one month, per metered action
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UsageCounts {
+    pub job_published: i64,
+    pub candidate_search: i64,
+    pub invitation_sent: i64,
+    pub cv_download: i64,
+}
+
+impl UsageCounts {
+    pub fn add(&mut self, event_type: UsageEventType, total: i64) {
+        let counter = match event_type {
+            UsageEventType::JobPublished => &mut self.job_published,
+            UsageEventType::CandidateSearch => &mut self.candidate_search,
+            UsageEventType::InvitationSent => &mut self.invitation_sent,
+            UsageEventType::CvDownload => &mut self.cv_download,
+        };
+        *counter += total;
+    }
+}
+
+/// A company's usage in one month, as of the last nightly aggregation
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyUsage {
+    /// YYYY-MM
+    pub month: String,
+    /// When the month was last totalled; None if it never was
+    pub aggregated_at: Option<DateTime<Utc>>,
+    pub usage: UsageCounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn industries(primaries: &[bool]) -> Vec<CompanyIndustryInput> {
+        primaries
+            .iter()
+            .map(|&is_primary| CompanyIndustryInput {
+                industry_id: Uuid::new_v4(),
+                is_primary,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_company_industries_count() {
+        assert!(validate_company_industries(&industries(&[true])).is_ok());
+        assert!(validate_company_industries(&industries(&[true, false, false])).is_ok());
+        assert!(validate_company_industries(&industries(&[])).is_err());
+        assert!(validate_company_industries(&industries(&[true, false, false, false])).is_err());
+    }
+
+    #[test]
+    fn test_company_industries_single_primary() {
+        assert!(validate_company_industries(&industries(&[false, false])).is_err());
+        assert!(validate_company_industries(&industries(&[true, true])).is_err());
+    }
+
+    #[test]
+    fn test_company_industries_no_duplicates() {
+        let mut list = industries(&[true, false]);
+        list[1].industry_id = list[0].industry_id;
+        assert!(validate_company_industries(&list).is_err());
+    }
+}
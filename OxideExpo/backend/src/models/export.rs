@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Which spreadsheet an export produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "export_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ExportType {
+    /// An OMIL's managed job seekers
+    ManagedSeekers,
+    /// A company's applicants to one job
+    Applicants,
+}
+
+/// One generated export, as recorded when the file was made
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ExportAuditEntry {
+    pub id: Uuid,
+    /// Export ID printed in the file's watermark
+    pub short_id: String,
+    pub export_type: ExportType,
+    pub exported_by: Option<Uuid>,
+    pub exported_by_name: String,
+    pub omil_id: Option<Uuid>,
+    pub company_id: Option<Uuid>,
+    pub organization_name: String,
+    /// Filters the export was made with
+    #[ts(type = "any")]
+    pub filters: serde_json::Value,
+    pub row_count: i32,
+    /// Whether emails and phone numbers were in the file
+    pub included_contact: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ExportAuditQuery {
+    pub export_type: Option<ExportType>,
+    /// Export ID from a file's watermark, or its beginning
+    pub export_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Whether an organization's exports may carry contact details
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ExportSettings {
+    pub allow_contact_export: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateExportSettingsRequest {
+    pub allow_contact_export: bool,
+}
@@ -17,6 +17,7 @@ pub enum FileType {
     ProfileImage,
     CompanyLogo,
     CompanyCover,
+    OmilLogo,
 }
 
 // ============================================================================
@@ -64,6 +65,16 @@ pub struct FileDeleteResponse {
     pub message: String,
 }
 
+// ============================================================================
+// QUERY PARAMETERS
+// ============================================================================
+
+/// GET /api/files/download: the signed token from a download link
+#[derive(Debug, Deserialize)]
+pub struct FileDownloadQuery {
+    pub token: String,
+}
+
 // ============================================================================
 // FILE CONSTRAINTS
 // ============================================================================
@@ -76,6 +87,7 @@ impl FileType {
             FileType::ProfileImage => 5 * 1024 * 1024, // 5 MB
             FileType::CompanyLogo => 5 * 1024 * 1024,  // 5 MB
             FileType::CompanyCover => 10 * 1024 * 1024, // 10 MB
+            FileType::OmilLogo => 5 * 1024 * 1024,     // 5 MB
         }
     }
 
@@ -92,6 +104,8 @@ impl FileType {
                 "image/png",
                 "image/webp",
             ],
+            // Embedded in spreadsheet exports, which cannot hold WebP
+            FileType::OmilLogo => vec!["image/jpeg", "image/png"],
         }
     }
 
@@ -102,6 +116,7 @@ impl FileType {
             FileType::ProfileImage => "profile-images",
             FileType::CompanyLogo => "company-logos",
             FileType::CompanyCover => "company-covers",
+            FileType::OmilLogo => "omil-logos",
         }
     }
 
@@ -112,6 +127,7 @@ impl FileType {
             FileType::ProfileImage => "Profile Image",
             FileType::CompanyLogo => "Company Logo",
             FileType::CompanyCover => "Company Cover",
+            FileType::OmilLogo => "OMIL Logo",
         }
     }
 }
@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Type};
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
+use super::omil::JobPartnerOmil;
 use super::profile::DisabilityCategory;
+use crate::utils::validation::validate_publish_at;
 
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0002_create_enums.sql)
@@ -32,10 +34,13 @@ pub enum JobType {
 pub enum JobStatus {
     Draft,
     PendingApproval,
+    Scheduled,
     Active,
     Paused,
     Closed,
     Rejected,
+    /// The company was deleted; kept so applicants can still read it
+    ArchivedDeleted,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
@@ -48,6 +53,73 @@ pub enum WorkModality {
     Hybrid,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "shift_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ShiftType {
+    FullTime,
+    PartTime,
+    /// Rotating or fixed shifts, spelled out in schedule_details
+    Shifts,
+    Flexible,
+}
+
+impl ShiftType {
+    pub fn label(self) -> &'static str {
+        match self {
+            ShiftType::FullTime => "Jornada completa",
+            ShiftType::PartTime => "Jornada parcial",
+            ShiftType::Shifts => "Por turnos",
+            ShiftType::Flexible => "Horario flexible",
+        }
+    }
+}
+
+/// Human-readable schedule of a job, e.g. "Jornada parcial · 20 horas
+/// semanales · Mañanas". None when the job says nothing about its schedule.
+pub fn schedule_display(
+    shift_type: Option<ShiftType>,
+    weekly_hours: Option<i32>,
+    schedule_details: Option<&str>,
+) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(shift_type) = shift_type {
+        parts.push(shift_type.label().to_string());
+    }
+    match weekly_hours {
+        Some(1) => parts.push("1 hora semanal".to_string()),
+        Some(hours) => parts.push(format!("{} horas semanales", hours)),
+        None => {}
+    }
+    if let Some(details) = schedule_details.map(str::trim).filter(|d| !d.is_empty()) {
+        parts.push(details.to_string());
+    }
+
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+/// Shift work must say what the shifts are
+fn validate_shift_details(
+    shift_type: Option<ShiftType>,
+    schedule_details: Option<&str>,
+) -> Result<(), ValidationError> {
+    let has_details = schedule_details.is_some_and(|d| !d.trim().is_empty());
+    if shift_type == Some(ShiftType::Shifts) && !has_details {
+        return Err(ValidationError::new("shift_details_required")
+            .with_message("Shift work requires schedule details".into()));
+    }
+    Ok(())
+}
+
+fn validate_create_schedule(req: &CreateJobRequest) -> Result<(), ValidationError> {
+    validate_shift_details(req.shift_type, req.schedule_details.as_deref())
+}
+
+fn validate_update_schedule(req: &UpdateJobRequest) -> Result<(), ValidationError> {
+    validate_shift_details(req.shift_type, req.schedule_details.as_deref())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "../frontend/src/types/")]
@@ -85,7 +157,9 @@ pub struct Job {
 
     // Work Arrangement
     pub work_modality: WorkModality,
-    pub work_schedule: Option<String>,
+    pub schedule_details: Option<String>,
+    pub shift_type: Option<ShiftType>,
+    pub weekly_hours: Option<i32>,
 
     // Location
     pub region_id: Option<Uuid>,
@@ -117,6 +191,7 @@ pub struct Job {
 
     // Counts
     pub vacancies: i32,
+    /// Applications that are not withdrawn, see services::counters
     pub applications_count: i32,
 
     // Status & Approval
@@ -125,6 +200,10 @@ pub struct Job {
     pub approved_by: Option<Uuid>,
     pub rejection_reason: Option<String>,
 
+    // Publication
+    pub publish_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+
     // Metadata
     pub completeness_percentage: i32,
     pub is_featured: Option<bool>,
@@ -180,6 +259,7 @@ pub struct JobDisabilityAccommodation {
 // ============================================================================
 
 #[derive(Debug, Deserialize, Validate, TS)]
+#[validate(schema(function = "validate_create_schedule"))]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct CreateJobRequest {
     // Core Information
@@ -201,8 +281,15 @@ pub struct CreateJobRequest {
     // Work Arrangement
     pub work_modality: WorkModality,
 
-    #[validate(length(max = 50, message = "Work schedule too long"))]
-    pub work_schedule: Option<String>,
+    /// Free-text schedule, e.g. "Lunes a viernes, 08:00 a 17:00"
+    #[validate(length(max = 200, message = "Schedule details too long"))]
+    pub schedule_details: Option<String>,
+
+    pub shift_type: Option<ShiftType>,
+
+    // Chilean labor law caps the ordinary working week at 45 hours
+    #[validate(range(min = 1, max = 45, message = "Weekly hours must be 1-45"))]
+    pub weekly_hours: Option<i32>,
 
     // Location
     pub region_id: Option<Uuid>,
@@ -254,11 +341,22 @@ pub struct CreateJobRequest {
     #[validate(range(min = 1, max = 1000, message = "Vacancies must be 1-1000"))]
     pub vacancies: i32,
 
+    // Publication (goes live automatically after approval)
+    #[validate(custom(function = "validate_publish_at"))]
+    pub publish_at: Option<DateTime<Utc>>,
+
     // Skills and Languages
     pub required_skills: Option<Vec<RequiredSkillInput>>,
     pub preferred_skills: Option<Vec<Uuid>>,
     pub required_languages: Option<Vec<RequiredLanguageInput>>,
     pub disability_accommodations: Option<Vec<DisabilityCategory>>,
+
+    /// Earlier posting of the same vacancy, closed or rejected, that this
+    /// job reposts
+    pub reposted_from_job_id: Option<Uuid>,
+
+    /// Create the job even though it looks like a duplicate of another one
+    pub force: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -278,6 +376,7 @@ pub struct RequiredLanguageInput {
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
+#[validate(schema(function = "validate_update_schedule"))]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct UpdateJobRequest {
     // All fields optional (same validation as CreateJobRequest)
@@ -297,8 +396,13 @@ pub struct UpdateJobRequest {
 
     pub work_modality: Option<WorkModality>,
 
-    #[validate(length(max = 50, message = "Work schedule too long"))]
-    pub work_schedule: Option<String>,
+    #[validate(length(max = 200, message = "Schedule details too long"))]
+    pub schedule_details: Option<String>,
+
+    pub shift_type: Option<ShiftType>,
+
+    #[validate(range(min = 1, max = 45, message = "Weekly hours must be 1-45"))]
+    pub weekly_hours: Option<i32>,
 
     pub region_id: Option<Uuid>,
     pub municipality_id: Option<Uuid>,
@@ -332,6 +436,8 @@ pub struct UpdateJobRequest {
     #[validate(length(max = 5000, message = "Benefits too long"))]
     pub benefits: Option<String>,
 
+    /// Only until the job is published; after that the deadline endpoint
+    /// changes it
     pub application_deadline: Option<NaiveDate>,
 
     #[validate(email(message = "Invalid contact email"))]
@@ -345,11 +451,17 @@ pub struct UpdateJobRequest {
     #[validate(range(min = 1, max = 1000, message = "Vacancies must be 1-1000"))]
     pub vacancies: Option<i32>,
 
+    #[validate(custom(function = "validate_publish_at"))]
+    pub publish_at: Option<DateTime<Utc>>,
+
     // Skills and Languages (if provided, replace existing)
     pub required_skills: Option<Vec<RequiredSkillInput>>,
     pub preferred_skills: Option<Vec<Uuid>>,
     pub required_languages: Option<Vec<RequiredLanguageInput>>,
     pub disability_accommodations: Option<Vec<DisabilityCategory>>,
+
+    /// Save the edit even though the job then looks like a duplicate of another one
+    pub force: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -359,12 +471,72 @@ pub struct UpdateJobStatusRequest {
 
     #[validate(length(max = 2000, message = "Rejection reason too long"))]
     pub rejection_reason: Option<String>,
+
+    /// Drop the scheduled publish time so a scheduled job can be activated now
+    pub clear_publish_at: Option<bool>,
+
+    /// Submit for approval even though the job looks like a duplicate
+    pub force: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateJobDeadlineRequest {
+    pub application_deadline: NaiveDate,
 }
 
 // ============================================================================
 // RESPONSE DTOs
 // ============================================================================
 
+/// Another open job of the same company that a posting may duplicate
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct DuplicateJobMatch {
+    pub job_id: Uuid,
+    pub title: String,
+    pub status: JobStatus,
+    /// Same title, region and job type
+    pub exact_match: bool,
+    /// Trigram similarity of the normalized descriptions (0-1)
+    pub description_similarity: f32,
+}
+
+/// Something a job posting can add to raise its completeness score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum CompletenessItem {
+    /// A description of at least MIN_DESCRIPTION_CHARS characters
+    Description,
+    Salary,
+    Skills,
+    Languages,
+    Accommodations,
+    /// An application deadline that has not passed
+    Deadline,
+    Benefits,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MissingCompletenessItem {
+    pub item: CompletenessItem,
+    /// Points the score gains once the item is added
+    pub points: i32,
+}
+
+/// Completeness score of a job and what it is missing, most valuable first
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobCompleteness {
+    pub job_id: Uuid,
+    pub title: String,
+    /// 0-100
+    pub score: i32,
+    pub missing: Vec<MissingCompletenessItem>,
+}
+
 /// Public job listing (filtered for public viewing)
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
@@ -385,7 +557,11 @@ pub struct PublicJobListing {
 
     // Work Arrangement
     pub work_modality: WorkModality,
-    pub work_schedule: Option<String>,
+    pub schedule_details: Option<String>,
+    pub shift_type: Option<ShiftType>,
+    pub weekly_hours: Option<i32>,
+    /// The schedule fields above as one line, see `schedule_display`
+    pub schedule_display: Option<String>,
 
     // Location
     pub region_id: Option<Uuid>,
@@ -417,6 +593,43 @@ pub struct PublicJobListing {
     pub company_logo_url: Option<String>,
 }
 
+/// Public job listing as returned by search, with the commute distance when
+/// a location filter is active (null for remote-allowed jobs)
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PublicJobSearchResult {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub job: PublicJobListing,
+    pub distance_km: Option<f64>,
+}
+
+/// Public job page, with the OMIL the job is co-published with
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PublicJobDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub job: PublicJobListing,
+    pub partner_omil: Option<JobPartnerOmil>,
+}
+
+/// One entry of a job's deadline change log
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobDeadlineChange {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub previous_deadline: NaiveDate,
+    pub new_deadline: NaiveDate,
+    pub changed_by: Option<Uuid>,
+    /// The job had been closed by its deadline and is open again
+    pub reopened: bool,
+    /// Seekers who saved or recently viewed the job, told about an extension
+    pub notified_seekers: i32,
+    pub changed_at: DateTime<Utc>,
+}
+
 /// Job with application count (company view)
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
@@ -436,19 +649,105 @@ pub struct FullJobResponse {
     pub disability_accommodations: Vec<JobDisabilityAccommodation>,
 }
 
+/// One thing a company member did on a job, newest first in the feed
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobActivityEntry {
+    pub actor_id: Uuid,
+    /// Name of the member who acted
+    pub actor: String,
+    /// status_changed, interview_scheduled, note_added, invitation_sent or bulk_update
+    pub action: String,
+    pub target_applicant_id: Option<Uuid>,
+    /// Name of the candidate acted on; None for bulk updates
+    pub target_applicant: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub summary: Option<String>,
+}
+
+/// Page of a job's activity feed
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobActivityResponse {
+    pub entries: Vec<JobActivityEntry>,
+    /// Entries matching `since`, across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// Entries by other members since the caller last marked the feed seen
+    pub unseen_count: i64,
+}
+
 // ============================================================================
 // QUERY PARAMETERS
 // ============================================================================
 
-/// Paginated response for public job listings
+/// Keys of the public job listing that `fields` can name
+pub const PUBLIC_JOB_LIST_SECTIONS: &[&str] = &[
+    "jobs",
+    "total",
+    "page",
+    "per_page",
+    "total_pages",
+    "next_cursor",
+];
+
+/// Paginated response for public job listings. `fields` drops keys from the
+/// serialized response, the type describes the full one.
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct PublicJobListResponse {
-    pub jobs: Vec<PublicJobListing>,
+    pub jobs: Vec<PublicJobSearchResult>,
     pub total: i64,
     pub page: i64,
     pub per_page: i64,
     pub total_pages: i64,
+    /// Pass as `cursor` to get the next page; null on the last page and
+    /// when sorting by distance
+    pub next_cursor: Option<String>,
+}
+
+/// Position after the last job of a public listing page, in the listing's
+/// order: featured first, then newest published, then id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicJobCursor {
+    pub is_featured: bool,
+    pub published_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl PublicJobCursor {
+    /// Opaque token handed to clients
+    pub fn encode(&self) -> String {
+        hex::encode(format!(
+            "{}|{}|{}",
+            u8::from(self.is_featured),
+            self.published_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            self.id
+        ))
+    }
+
+    /// None when the token wasn't made by `encode`
+    pub fn decode(token: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(token).ok()?).ok()?;
+        let mut parts = decoded.split('|');
+        let is_featured = match parts.next()? {
+            "0" => false,
+            "1" => true,
+            _ => return None,
+        };
+        let published_at = DateTime::parse_from_rfc3339(parts.next()?).ok()?;
+        let id = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            is_featured,
+            published_at: published_at.with_timezone(&Utc),
+            id,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -459,11 +758,76 @@ pub struct PublicJobListQuery {
     pub work_area_id: Option<Uuid>,
     pub job_type: Option<JobType>,
     pub work_modality: Option<WorkModality>,
+    pub shift_type: Option<ShiftType>,
+    /// Jobs of at most this many weekly hours; jobs without hours are left out
+    pub weekly_hours_max: Option<i32>,
     pub is_remote_allowed: Option<bool>,
     pub search: Option<String>,
-    // Pagination - supports both page/per_page and limit/offset
+    // Commute filter - from a municipality, or the seeker's own with near_me
+    pub near_municipality_id: Option<Uuid>,
+    pub near_me: Option<bool>,
+    pub radius_km: Option<f64>,
+    // Pagination - `cursor` with `per_page` or `limit` is preferred: pages
+    // stay stable while jobs are published. page/per_page and limit/offset
+    // still work.
+    /// `next_cursor` of the previous page; takes precedence over page and offset
+    pub cursor: Option<String>,
     pub page: Option<i64>,
     pub per_page: Option<i64>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Comma-separated response keys to return, e.g. "jobs,total"; all when absent
+    pub fields: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobActivityQuery {
+    /// Only entries after this time, for polling
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_display() {
+        assert_eq!(
+            schedule_display(Some(ShiftType::PartTime), Some(20), Some(" Mañanas ")),
+            Some("Jornada parcial · 20 horas semanales · Mañanas".to_string())
+        );
+        assert_eq!(
+            schedule_display(None, Some(1), None),
+            Some("1 hora semanal".to_string())
+        );
+        assert_eq!(
+            schedule_display(None, None, Some("part time AM")),
+            Some("part time AM".to_string())
+        );
+        assert_eq!(schedule_display(None, None, Some("  ")), None);
+    }
+
+    #[test]
+    fn test_public_job_cursor_round_trip() {
+        let cursor = PublicJobCursor {
+            is_featured: true,
+            published_at: DateTime::from_timestamp(1_760_000_000, 123_456_000).unwrap(),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(PublicJobCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(PublicJobCursor::decode("not a cursor"), None);
+        assert_eq!(PublicJobCursor::decode(&hex::encode("2|x|y")), None);
+    }
+
+    #[test]
+    fn test_shift_work_requires_details() {
+        assert!(validate_shift_details(Some(ShiftType::Shifts), Some("Turnos 4x4")).is_ok());
+        assert!(validate_shift_details(Some(ShiftType::Shifts), Some(" ")).is_err());
+        assert!(validate_shift_details(Some(ShiftType::Shifts), None).is_err());
+        assert!(validate_shift_details(Some(ShiftType::FullTime), None).is_ok());
+        assert!(validate_shift_details(None, None).is_ok());
+    }
 }
@@ -6,8 +6,8 @@ use ts_rs::TS;
 use uuid::Uuid;
 use validator::Validate;
 
-use super::job::{JobType, PublicJobListing, WorkModality};
-use super::profile::JobSeekerProfile;
+use super::job::{JobType, PublicJobListing, ShiftType, WorkModality};
+use super::profile::{JobSeekerProfile, UserSkill};
 
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0011_create_matching_tables.sql)
@@ -34,6 +34,21 @@ pub enum AlertFrequency {
     Never,
 }
 
+/// Where a seeker's salary expectation falls against a job's salary range,
+/// with the job's amounts brought to a month (`salary_expectation_fit` in
+/// 0077_salary_fit.sql)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "salary_fit", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum SalaryFit {
+    /// Even the most they expect is less than the job's minimum
+    Below,
+    Within,
+    /// Even the least they expect is more than the job pays at most
+    Above,
+}
+
 // ============================================================================
 // CORE STRUCTS
 // ============================================================================
@@ -52,6 +67,7 @@ pub struct JobSeekerPreferences {
     pub preferred_region_ids: Vec<Uuid>,
     #[sqlx(skip)]
     pub preferred_industry_ids: Vec<Uuid>,
+    pub preferred_shift_types: Vec<ShiftType>,
     pub willing_to_relocate: bool,
 
     // Salary Expectations
@@ -63,14 +79,24 @@ pub struct JobSeekerPreferences {
     pub salary_expectation_max: Option<Decimal>,
     pub salary_currency: String,
 
+    // Commute
+    pub preferred_max_commute_km: Option<i32>,
+
     // Privacy Controls
     pub profile_visibility: ProfileVisibility,
     pub show_disability_info: bool,
+    /// Companies see the references of the seeker's applications
+    pub show_references: bool,
+    /// Companies comparing candidates see the salary expectation
+    pub show_salary_expectation: bool,
 
     // Alert Settings
     pub email_job_alerts: bool,
     pub alert_frequency: AlertFrequency,
 
+    /// Keep recently viewed jobs and recent searches
+    pub track_browsing: bool,
+
     // Timestamps
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -123,8 +149,11 @@ pub struct MatchScoreBreakdown {
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct SkillsMatchDetail {
+    /// Required and preferred skills together
     pub score: i32,
     pub max_score: i32,
+    /// Part of the score from preferred skills
+    pub preferred_score: i32,
     pub matched_required: Vec<MatchedSkill>,
     pub missing_required: Vec<MissingSkill>,
     pub matched_preferred: Vec<Uuid>,
@@ -143,6 +172,8 @@ pub struct MatchedSkill {
 pub struct MissingSkill {
     pub skill_id: Uuid,
     pub required_proficiency: i32,
+    /// Set when the user has the skill below the required level
+    pub user_proficiency: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -167,6 +198,8 @@ pub struct MatchedLanguage {
 pub struct MissingLanguage {
     pub language_id: Uuid,
     pub required_proficiency: i32,
+    /// Set when the user speaks the language below the required level
+    pub user_proficiency: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -211,6 +244,44 @@ pub struct AccommodationsMatchDetail {
     pub matching_categories: Vec<String>,
 }
 
+// ============================================================================
+// MATCH TIPS DTOs
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum MatchTipKind {
+    AddSkill,
+    ImproveSkill,
+    AddLanguage,
+    ImproveLanguage,
+    EnableRelocation,
+    AddEducation,
+}
+
+/// Actionable suggestion derived from a match score breakdown
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchTip {
+    pub kind: MatchTipKind,
+    pub message: String,
+    /// Points the component score would gain if the tip is followed
+    pub potential_points: i32,
+    pub skill_id: Option<Uuid>,
+    pub language_id: Option<Uuid>,
+    /// Proficiency levels missing for improve_skill / improve_language
+    pub proficiency_gap: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchTipsResponse {
+    pub job_id: Uuid,
+    pub match_score: i32,
+    pub tips: Vec<MatchTip>,
+}
+
 // ============================================================================
 // RESPONSE DTOs
 // ============================================================================
@@ -233,6 +304,9 @@ pub struct RecommendedCandidate {
     pub match_score: i32,
     pub score_breakdown: MatchScoreBreakdown,
     pub has_applied: bool,
+    /// Rejected for an earlier posting of this job, when it is a repost
+    pub rejected_before: bool,
+    pub skills: Vec<UserSkill>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -250,6 +324,8 @@ pub struct RecommendedJobsResponse {
     pub jobs: Vec<RecommendedJob>,
     pub total_count: i64,
     pub has_more: bool,
+    /// How `jobs` is ordered; stable across requests, so offsets can be paged
+    pub ordering: String,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -258,8 +334,37 @@ pub struct RecommendedCandidatesResponse {
     pub candidates: Vec<RecommendedCandidate>,
     pub total_count: i64,
     pub has_more: bool,
+    /// How `candidates` is ordered; stable across requests, so offsets can be paged
+    pub ordering: String,
 }
 
+/// Ranking points a recommended job gains when its shift type is one the
+/// seeker prefers. The reported match score is left as is.
+pub const SHIFT_PREFERENCE_BOOST: i32 = 5;
+
+/// Ordering of recommended jobs. Ties on score go to the most recently
+/// published job, then to the lowest job ID.
+pub const RECOMMENDED_JOBS_ORDERING: &str =
+    "match_score + shift preference boost desc, published_at desc, id asc";
+
+/// Ordering of recommended jobs with `diversify=true`
+pub const RECOMMENDED_JOBS_DIVERSIFIED_ORDERING: &str =
+    "match_score + shift preference boost desc, published_at desc, id asc; at most 3 jobs per company in the top 10";
+
+/// Ranking points a recommended candidate gains when actively looking for
+/// work. The reported match score is left as is.
+pub const ACTIVELY_LOOKING_BOOST: i32 = 10;
+
+/// Ranking points a recommended candidate loses when they were rejected for
+/// an earlier posting of a reposted job. The reported match score is left
+/// as is.
+pub const EARLIER_REJECTION_PENALTY: i32 = 10;
+
+/// Ordering of recommended candidates. Applicants come first; ties on score go
+/// to the most recently updated profile, then to the lowest user ID.
+pub const RECOMMENDED_CANDIDATES_ORDERING: &str =
+    "has_applied desc, match_score + availability boost - earlier rejection penalty desc, profile_updated_at desc, user_id asc";
+
 // ============================================================================
 // REQUEST DTOs
 // ============================================================================
@@ -272,6 +377,8 @@ pub struct UpdatePreferencesRequest {
     pub preferred_job_types: Option<Vec<JobType>>,
     pub preferred_region_ids: Option<Vec<Uuid>>,
     pub preferred_industry_ids: Option<Vec<Uuid>>,
+    /// Jobs with one of these shift types rank a little higher
+    pub preferred_shift_types: Option<Vec<ShiftType>>,
     pub willing_to_relocate: Option<bool>,
 
     // Salary Expectations
@@ -283,13 +390,26 @@ pub struct UpdatePreferencesRequest {
     #[validate(length(min = 3, max = 3, message = "Currency must be 3 characters"))]
     pub salary_currency: Option<String>,
 
+    // Commute
+    #[validate(range(
+        min = 1,
+        max = 500,
+        message = "Commute distance must be between 1 and 500 km"
+    ))]
+    pub preferred_max_commute_km: Option<i32>,
+
     // Privacy Controls
     pub profile_visibility: Option<ProfileVisibility>,
     pub show_disability_info: Option<bool>,
+    pub show_references: Option<bool>,
+    pub show_salary_expectation: Option<bool>,
 
     // Alert Settings
     pub email_job_alerts: Option<bool>,
     pub alert_frequency: Option<AlertFrequency>,
+
+    /// Turning this off also forgets the browsing already kept
+    pub track_browsing: Option<bool>,
 }
 
 // ============================================================================
@@ -303,6 +423,10 @@ pub struct RecommendedJobsQuery {
     pub exclude_applied: Option<bool>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Demote jobs so no company has more than 3 in the top 10
+    pub diversify: Option<bool>,
+    /// Only jobs whose salary range fits the seeker's expectation this way
+    pub salary_fit: Option<SalaryFit>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -312,4 +436,145 @@ pub struct RecommendedCandidatesQuery {
     pub include_applied_only: Option<bool>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Only candidates showing a salary expectation that fits the job this way
+    pub salary_fit: Option<SalaryFit>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SkillGapQuery {
+    /// Defaults to the work area of the seeker's most recent experience
+    pub work_area_id: Option<Uuid>,
+    /// Only jobs in the seeker's region
+    pub in_my_region: Option<bool>,
+}
+
+// ============================================================================
+// MATCH SCORE HISTORY
+// ============================================================================
+
+/// Scored component of a match, as stored per history point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum MatchScoreComponent {
+    Skills,
+    Languages,
+    Location,
+    Experience,
+    Education,
+    PreferredSkills,
+    Accommodations,
+}
+
+/// Component that moved the most since the previous point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchScoreComponentChange {
+    pub component: MatchScoreComponent,
+    /// Points gained, negative when lost
+    pub change: i32,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchScoreHistoryPoint {
+    pub total_score: i32,
+    pub skills_score: i32,
+    pub languages_score: i32,
+    pub location_score: i32,
+    pub experience_score: i32,
+    pub education_score: i32,
+    pub preferred_skills_score: i32,
+    pub accommodations_score: i32,
+    pub recorded_at: DateTime<Utc>,
+    /// None for the first point and when no component changed
+    pub biggest_change: Option<MatchScoreComponentChange>,
+}
+
+impl MatchScoreHistoryPoint {
+    fn components(&self) -> [(MatchScoreComponent, i32); 7] {
+        [
+            (MatchScoreComponent::Skills, self.skills_score),
+            (MatchScoreComponent::Languages, self.languages_score),
+            (MatchScoreComponent::Location, self.location_score),
+            (MatchScoreComponent::Experience, self.experience_score),
+            (MatchScoreComponent::Education, self.education_score),
+            (MatchScoreComponent::PreferredSkills, self.preferred_skills_score),
+            (MatchScoreComponent::Accommodations, self.accommodations_score),
+        ]
+    }
+
+    /// The component with the largest change from `previous`; on a tie the
+    /// one listed first in the score breakdown
+    pub fn biggest_change_since(&self, previous: &Self) -> Option<MatchScoreComponentChange> {
+        self.components()
+            .into_iter()
+            .zip(previous.components())
+            .map(|((component, now), (_, before))| MatchScoreComponentChange {
+                component,
+                change: now - before,
+            })
+            .filter(|c| c.change != 0)
+            .reduce(|best, c| if c.change.abs() > best.change.abs() { c } else { best })
+    }
+}
+
+/// GET /api/jobs/{id}/match-score/history, oldest point first
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MatchScoreHistoryResponse {
+    pub job_id: Uuid,
+    pub points: Vec<MatchScoreHistoryPoint>,
+}
+
+// ============================================================================
+// SKILL GAP ANALYSIS
+// ============================================================================
+
+/// Skill asked for by active jobs of the work area that the seeker lacks, or
+/// has below what some of those jobs require
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SkillGap {
+    pub skill_id: Uuid,
+    pub skill_name: String,
+    /// The seeker's level as counted by matching (endorsements included),
+    /// None when they don't list the skill
+    pub seeker_proficiency: Option<i32>,
+    /// Level that meets every job still requiring more, None when the skill
+    /// is only preferred
+    pub required_proficiency: Option<i32>,
+    /// Active jobs requiring the skill above the seeker's level
+    pub jobs_requiring: i64,
+    /// Active jobs preferring the skill, when the seeker doesn't list it
+    pub jobs_preferring: i64,
+    /// Average match score points each of those jobs would gain
+    pub average_score_uplift: f64,
+}
+
+/// Skill asked for by the work area's active jobs that the seeker already
+/// has at every required level
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CoveredSkill {
+    pub skill_id: Uuid,
+    pub skill_name: String,
+    pub jobs_requiring: i64,
+    pub jobs_preferring: i64,
+}
+
+/// GET /api/me/skill-gap, gaps ranked by the number of jobs they keep out of
+/// reach
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SkillGapResponse {
+    pub work_area_id: Uuid,
+    /// Set when narrowed to the seeker's region
+    pub region_id: Option<Uuid>,
+    pub active_jobs: i64,
+    pub gaps: Vec<SkillGap>,
+    pub covered: Vec<CoveredSkill>,
+    /// When the market side was computed; it's cached for a few hours
+    pub generated_at: DateTime<Utc>,
 }
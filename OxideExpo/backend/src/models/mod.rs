@@ -29,3 +29,27 @@ pub mod omil;
 pub mod applicant;
 pub mod saved_job;
 pub mod file;
+
+// V13: Personal API tokens
+pub mod api_token;
+
+// V13: Public platform statistics
+pub mod stats;
+
+// V13: Job seeker onboarding checklist
+pub mod onboarding;
+
+// V13: Export watermarking and audit
+pub mod export;
+
+// V13: Recently viewed jobs and recent searches
+pub mod browsing;
+
+// V13: Per-event notification preferences
+pub mod notification;
+
+// V13: Interview calendar feeds
+pub mod calendar;
+
+// V13: Capabilities behind authorization checks
+pub mod permissions;
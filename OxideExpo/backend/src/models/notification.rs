@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::user::UserType;
+
+// ============================================================================
+// EVENTS AND CHANNELS
+// ============================================================================
+
+/// Something the platform tells a user about. Events live only in code and
+/// are stored by code in notification_preferences; adding one means adding a
+/// variant here, the user types it applies to and its defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum NotificationEvent {
+    /// A company invited the seeker to apply
+    JobInvitation,
+    /// An application was hired, closed, put on hold or moved to another
+    /// company
+    ApplicationStatus,
+    /// Job recommendations and profile tips
+    Digest,
+    /// A new application met one of the company's automation rules
+    NewApplication,
+    /// Referral requests between companies and OMILs
+    ReferralRequest,
+    /// A changed company profile field was reviewed
+    ProfileReview,
+    /// A job's applicants have been waiting for review too long
+    StaleJob,
+    /// A company data export is ready to download
+    DataExport,
+    /// Another OMIL asked to transfer a seeker
+    OmilTransfer,
+    /// A followup with a managed seeker is due
+    FollowupReminder,
+    NewMessage,
+    /// Platform news
+    Announcement,
+    /// Password and email changes, always sent
+    Security,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 13] = [
+        NotificationEvent::JobInvitation,
+        NotificationEvent::ApplicationStatus,
+        NotificationEvent::Digest,
+        NotificationEvent::NewApplication,
+        NotificationEvent::ReferralRequest,
+        NotificationEvent::ProfileReview,
+        NotificationEvent::StaleJob,
+        NotificationEvent::DataExport,
+        NotificationEvent::OmilTransfer,
+        NotificationEvent::FollowupReminder,
+        NotificationEvent::NewMessage,
+        NotificationEvent::Announcement,
+        NotificationEvent::Security,
+    ];
+
+    /// Code stored in notification_preferences.event_type
+    pub fn code(self) -> &'static str {
+        match self {
+            NotificationEvent::JobInvitation => "job_invitation",
+            NotificationEvent::ApplicationStatus => "application_status",
+            NotificationEvent::Digest => "digest",
+            NotificationEvent::NewApplication => "new_application",
+            NotificationEvent::ReferralRequest => "referral_request",
+            NotificationEvent::ProfileReview => "profile_review",
+            NotificationEvent::StaleJob => "stale_job",
+            NotificationEvent::DataExport => "data_export",
+            NotificationEvent::OmilTransfer => "omil_transfer",
+            NotificationEvent::FollowupReminder => "followup_reminder",
+            NotificationEvent::NewMessage => "new_message",
+            NotificationEvent::Announcement => "announcement",
+            NotificationEvent::Security => "security",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.code() == code)
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            NotificationEvent::JobInvitation => "Invitaciones a postular",
+            NotificationEvent::ApplicationStatus => "Estado de tus postulaciones",
+            NotificationEvent::Digest => "Recomendaciones de ofertas",
+            NotificationEvent::NewApplication => "Postulaciones destacadas",
+            NotificationEvent::ReferralRequest => "Solicitudes de derivación",
+            NotificationEvent::ProfileReview => "Revisión del perfil de empresa",
+            NotificationEvent::StaleJob => "Ofertas con postulaciones sin revisar",
+            NotificationEvent::DataExport => "Exportaciones de datos",
+            NotificationEvent::OmilTransfer => "Solicitudes de transferencia",
+            NotificationEvent::FollowupReminder => "Recordatorios de seguimiento",
+            NotificationEvent::NewMessage => "Mensajes nuevos",
+            NotificationEvent::Announcement => "Novedades de EmpleosInclusivos",
+            NotificationEvent::Security => "Seguridad de la cuenta",
+        }
+    }
+
+    /// Whether users of this type ever get the event
+    pub fn applies_to(self, user_type: UserType) -> bool {
+        use UserType::*;
+        match self {
+            NotificationEvent::JobInvitation
+            | NotificationEvent::ApplicationStatus
+            | NotificationEvent::Digest => user_type == JobSeeker,
+            NotificationEvent::NewApplication
+            | NotificationEvent::ProfileReview
+            | NotificationEvent::StaleJob
+            | NotificationEvent::DataExport => user_type == CompanyMember,
+            NotificationEvent::ReferralRequest => matches!(user_type, CompanyMember | OmilMember),
+            NotificationEvent::OmilTransfer | NotificationEvent::FollowupReminder => {
+                user_type == OmilMember
+            }
+            NotificationEvent::NewMessage
+            | NotificationEvent::Announcement
+            | NotificationEvent::Security => true,
+        }
+    }
+
+    /// Security emails go out whatever the user chose
+    pub fn is_configurable(self) -> bool {
+        self != NotificationEvent::Security
+    }
+
+    /// Channels a user of this type gets until they choose otherwise
+    pub fn defaults(self, user_type: UserType) -> ChannelSettings {
+        match self {
+            // Seekers only get platform news by email if they ask for it;
+            // staff of companies and OMILs need to hear about changes
+            NotificationEvent::Announcement => ChannelSettings {
+                email: user_type != UserType::JobSeeker,
+                in_app: true,
+            },
+            _ => ChannelSettings {
+                email: true,
+                in_app: true,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum NotificationChannel {
+    Email,
+    InApp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelSettings {
+    pub email: bool,
+    pub in_app: bool,
+}
+
+impl ChannelSettings {
+    pub fn get(self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Email => self.email,
+            NotificationChannel::InApp => self.in_app,
+        }
+    }
+}
+
+// ============================================================================
+// PREFERENCES
+// ============================================================================
+
+/// One row of the preferences matrix
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct NotificationPreference {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub email: bool,
+    pub in_app: bool,
+    /// False for security notifications, which are always sent
+    pub configurable: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct NotificationPreferenceUpdate {
+    pub event: NotificationEvent,
+    /// Unchanged when omitted
+    pub email: Option<bool>,
+    /// Unchanged when omitted
+    pub in_app: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateNotificationPreferencesRequest {
+    pub preferences: Vec<NotificationPreferenceUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UnsubscribeResponse {
+    pub event: NotificationEvent,
+    pub title: String,
+    /// Always false once unsubscribed
+    pub email: bool,
+}
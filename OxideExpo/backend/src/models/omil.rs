@@ -1,13 +1,19 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Type};
 use ts_rs::TS;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
+use super::admin::SuppressedCount;
+use super::application::ApplicationStatus;
 use super::company::OrganizationStatus;
-use super::job::PublicJobListing;
-use super::profile::JobSeekerProfile;
+use super::job::WorkModality;
+use super::matching::MatchScoreBreakdown;
+use super::profile::{JobSeekerProfile, ProfileReference, UserSkill};
+use crate::utils::normalize::{self, validate_phone, Normalize};
+use crate::utils::rut::validate_rut;
 
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0012_create_omil_tables.sql)
@@ -23,21 +29,28 @@ pub enum OmilRole {
     Advisor,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
-#[sqlx(type_name = "followup_type", rename_all = "snake_case")]
-#[serde(rename_all = "snake_case")]
+/// Followup category from the admin-maintained `followup_types` table
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
-pub enum FollowupType {
-    InitialRegistration,
-    ProfileUpdate,
-    JobApplication,
-    InterviewScheduled,
-    InterviewCompleted,
-    Placement,
-    FollowUpCall,
-    GeneralNote,
+pub struct FollowupType {
+    pub code: String,
+    pub label_es: String,
+    /// Inactive types remain on old followups but can't be chosen for new ones
+    pub is_active: bool,
+    /// Counted as an intervention in OMIL statistics and the monthly report
+    pub counts_as_intervention: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
+/// Types the system records followups with itself; they can't be deleted
+pub const SYSTEM_FOLLOWUP_TYPES: &[&str] = &[
+    "initial_registration",
+    "job_application",
+    "placement",
+    "general_note",
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
 #[sqlx(type_name = "placement_outcome", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -50,6 +63,19 @@ pub enum PlacementOutcome {
     Withdrawn,
 }
 
+impl PlacementOutcome {
+    /// Spanish label shown to people, e.g. in followups
+    pub fn label(self) -> &'static str {
+        match self {
+            PlacementOutcome::Pending => "Pendiente",
+            PlacementOutcome::Placed => "Colocado",
+            PlacementOutcome::NotPlaced => "No colocado",
+            PlacementOutcome::DeclinedOffer => "Rechazó la oferta",
+            PlacementOutcome::Withdrawn => "Retirado",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
 #[sqlx(type_name = "invitation_status", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -57,9 +83,58 @@ pub enum PlacementOutcome {
 pub enum InvitationStatus {
     Pending,
     Viewed,
+    /// Accepted without applying (yet)
+    Accepted,
     Applied,
     Declined,
     Expired,
+    /// The job was deleted before the seeker answered
+    JobNoLongerAvailable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "omil_transfer_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum OmilTransferStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "referral_request_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ReferralRequestStatus {
+    Pending,
+    Accepted,
+    Declined,
+    /// The OMIL stepped back after accepting
+    Withdrawn,
+}
+
+/// Advisor actions that may need a coordinator's or director's approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "omil_action_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum OmilActionType {
+    /// Marking the seeker as placed
+    Placement,
+    /// Deactivating the managed record
+    Deactivation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "pending_action_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum PendingActionStatus {
+    Pending,
+    Approved,
+    Rejected,
 }
 
 // ============================================================================
@@ -91,6 +166,7 @@ pub struct OmilMember {
     pub omil_id: Uuid,
     pub user_id: Uuid,
     pub role: OmilRole,
+    pub branch_id: Option<Uuid>,
     pub is_active: bool,
     pub joined_at: DateTime<Utc>,
     pub left_at: Option<DateTime<Utc>>,
@@ -105,6 +181,7 @@ pub struct OmilManagedJobSeeker {
     pub omil_id: Uuid,
     pub job_seeker_id: Uuid,
     pub assigned_advisor_id: Option<Uuid>,
+    pub branch_id: Option<Uuid>,
     pub registered_by: Uuid,
     pub placement_outcome: PlacementOutcome,
     pub placed_at: Option<DateTime<Utc>>,
@@ -123,7 +200,8 @@ pub struct JobSeekerFollowup {
     pub created_by: Uuid,
     pub omil_id: Option<Uuid>,
     pub application_id: Option<Uuid>,
-    pub followup_type: FollowupType,
+    /// Code of a `FollowupType`
+    pub followup_type: String,
     pub title: Option<String>,
     pub content: String,
     pub is_private: bool,
@@ -136,7 +214,8 @@ pub struct JobSeekerFollowup {
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct JobInvitation {
     pub id: Uuid,
-    pub job_id: Uuid,
+    /// None once the job has been deleted
+    pub job_id: Option<Uuid>,
     pub job_seeker_id: Uuid,
     pub invited_by: Uuid,
     pub company_id: Uuid,
@@ -145,6 +224,8 @@ pub struct JobInvitation {
     pub viewed_at: Option<DateTime<Utc>>,
     pub responded_at: Option<DateTime<Utc>>,
     pub expires_at: DateTime<Utc>,
+    /// The application submitted when accepting
+    pub application_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -160,20 +241,6 @@ pub struct OmilApplication {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
-#[ts(export, export_to = "../frontend/src/types/")]
-pub struct NotificationPreferences {
-    pub user_id: Uuid,
-    pub email_job_alerts: bool,
-    pub email_application_updates: bool,
-    pub email_invitations: bool,
-    pub email_messages: bool,
-    pub email_marketing: bool,
-    pub digest_frequency: crate::models::matching::AlertFrequency,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
-
 // ============================================================================
 // REQUEST DTOs
 // ============================================================================
@@ -220,9 +287,12 @@ pub struct UpdateOmilMemberRequest {
 
 #[derive(Debug, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
+#[validate(schema(function = "validate_offline_registration"))]
 pub struct RegisterJobSeekerOnBehalfRequest {
+    /// Left out for beneficiaries without an email; they are then managed
+    /// offline and identified by their national ID
     #[validate(email(message = "Invalid email"))]
-    pub email: String,
+    pub email: Option<String>,
 
     #[validate(length(min = 1, max = 100, message = "First name required"))]
     pub first_name: String,
@@ -231,14 +301,62 @@ pub struct RegisterJobSeekerOnBehalfRequest {
     pub last_name: String,
 
     #[validate(length(max = 50, message = "Phone too long"))]
+    #[validate(custom(function = "validate_phone"))]
     pub phone: Option<String>,
 
+    #[validate(length(max = 50, message = "National ID too long"))]
+    #[validate(custom(function = "validate_rut"))]
+    pub national_id: Option<String>,
+
     #[validate(length(max = 500, message = "Notes too long"))]
     pub notes: Option<String>,
 
     pub assign_to_self: Option<bool>,
 }
 
+fn validate_offline_registration(
+    request: &RegisterJobSeekerOnBehalfRequest,
+) -> Result<(), ValidationError> {
+    let has_national_id = request.national_id.as_deref().is_some_and(|n| !n.is_empty());
+    if request.email.is_none() && !has_national_id {
+        return Err(ValidationError::new("national_id")
+            .with_message("National ID is required when registering without an email".into()));
+    }
+    Ok(())
+}
+
+impl Normalize for RegisterJobSeekerOnBehalfRequest {
+    fn normalize(&mut self) {
+        normalize::email_opt(&mut self.email);
+        normalize::trim(&mut self.first_name);
+        normalize::trim(&mut self.last_name);
+        normalize::phone_opt(&mut self.phone);
+        normalize::rut_opt(&mut self.national_id);
+        normalize::trim_opt(&mut self.notes);
+    }
+}
+
+/// Email a seeker managed offline can now be reached at
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ClaimInviteRequest {
+    #[validate(email(message = "Invalid email"))]
+    pub email: String,
+}
+
+impl Normalize for ClaimInviteRequest {
+    fn normalize(&mut self) {
+        normalize::email(&mut self.email);
+    }
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ClaimInviteResponse {
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct ApplyOnBehalfRequest {
@@ -247,11 +365,14 @@ pub struct ApplyOnBehalfRequest {
     #[validate(length(max = 5000, message = "Cover letter too long"))]
     pub cover_letter: Option<String>,
 
+    /// One of the seeker's cover letter templates, instead of cover_letter
+    pub template_id: Option<Uuid>,
+
     #[validate(length(max = 2000, message = "Internal notes too long"))]
     pub internal_notes: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Validate, TS)]
+#[derive(Debug, Serialize, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct UpdatePlacementRequest {
     pub outcome: PlacementOutcome,
@@ -264,7 +385,9 @@ pub struct UpdatePlacementRequest {
 #[derive(Debug, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct CreateFollowupRequest {
-    pub followup_type: FollowupType,
+    /// Code of an active followup type
+    #[validate(length(min = 1, max = 50, message = "Followup type required"))]
+    pub followup_type: String,
 
     #[validate(length(max = 255, message = "Title too long"))]
     pub title: Option<String>,
@@ -279,6 +402,10 @@ pub struct CreateFollowupRequest {
 #[derive(Debug, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct UpdateFollowupRequest {
+    /// Code of an active followup type
+    #[validate(length(min = 1, max = 50, message = "Followup type required"))]
+    pub followup_type: Option<String>,
+
     #[validate(length(max = 255, message = "Title too long"))]
     pub title: Option<String>,
 
@@ -300,23 +427,47 @@ pub struct SendJobInvitationRequest {
     pub expires_in_days: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+/// Something the company should know about an invitation that was still sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "../frontend/src/types/")]
-pub struct RespondToInvitationRequest {
-    pub accept: bool,
+pub enum InvitationWarning {
+    /// The seeker has said they are not available for work
+    SeekerNotAvailable,
+}
 
-    pub cover_letter: Option<String>,
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SendJobInvitationResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub invitation: JobInvitation,
+    pub warnings: Vec<InvitationWarning>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
-pub struct UpdateNotificationPreferencesRequest {
-    pub email_job_alerts: Option<bool>,
-    pub email_application_updates: Option<bool>,
-    pub email_invitations: Option<bool>,
-    pub email_messages: Option<bool>,
-    pub email_marketing: Option<bool>,
-    pub digest_frequency: Option<crate::models::matching::AlertFrequency>,
+pub struct RespondToInvitationResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub invitation: JobInvitation,
+    /// Why accepting with apply_immediately didn't create an application.
+    /// The invitation is accepted all the same.
+    pub application_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RespondToInvitationRequest {
+    pub accept: bool,
+
+    /// When accepting, also apply to the job
+    #[serde(default)]
+    pub apply_immediately: bool,
+
+    /// Used when applying
+    #[validate(length(max = 5000, message = "Cover letter too long"))]
+    pub cover_letter: Option<String>,
 }
 
 // ============================================================================
@@ -333,6 +484,40 @@ pub struct OmilDashboardStats {
     pub pending_placements: i64,
     pub new_registrations_this_month: i64,
     pub total_applications_submitted: i64,
+    /// Followups this month whose type counts as an intervention
+    pub interventions_this_month: i64,
+    /// Per branch, with placements and applications this year; the
+    /// unassigned bucket comes last
+    pub branches: Vec<OmilBranchStats>,
+}
+
+/// Months a stats trend covers by default, and at most
+pub const DEFAULT_TREND_MONTHS: i32 = 12;
+pub const MAX_TREND_MONTHS: i32 = 36;
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilStatsTrendsQuery {
+    pub months: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MonthlyCount {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub count: i64,
+}
+
+/// Monthly series for the OMIL dashboard, oldest month first and ending with
+/// the current month. Months without activity are included with a zero count.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilStatsTrends {
+    pub months: i32,
+    pub registrations: Vec<MonthlyCount>,
+    pub placements: Vec<MonthlyCount>,
+    pub applications_submitted: Vec<MonthlyCount>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -343,6 +528,17 @@ pub struct OmilMemberWithUser {
     pub user_email: String,
 }
 
+/// A new member, with anything the adding member should double-check
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AddOmilMemberResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub member: OmilMemberWithUser,
+    /// Set when the OMIL already applied to jobs on the new member's behalf
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct OmilOrganizationWithMembers {
@@ -356,9 +552,11 @@ pub struct ManagedJobSeekerSummary {
     pub id: Uuid,
     pub job_seeker_id: Uuid,
     pub user_name: String,
-    pub user_email: String,
+    pub user_email: Option<String>,
     pub placement_outcome: PlacementOutcome,
     pub assigned_advisor_name: Option<String>,
+    pub branch_id: Option<Uuid>,
+    pub branch_name: Option<String>,
     pub followups_count: i64,
     pub applications_count: i64,
     pub registered_at: DateTime<Utc>,
@@ -371,15 +569,38 @@ pub struct ManagedJobSeekerDetail {
     pub managed: OmilManagedJobSeeker,
     pub profile: Option<JobSeekerProfile>,
     pub user_name: String,
-    pub user_email: String,
+    pub user_email: Option<String>,
     pub recent_followups: Vec<JobSeekerFollowup>,
+    pub skills: Vec<UserSkill>,
+}
+
+/// The terms of a job as they were when the invitation was sent
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct InvitationJobSnapshot {
+    pub title: String,
+    /// Opening of the description, up to 300 characters
+    pub description: String,
+    #[ts(type = "string | null")]
+    #[serde(with = "rust_decimal::serde::str_option")]
+    pub salary_min: Option<Decimal>,
+    #[ts(type = "string | null")]
+    #[serde(with = "rust_decimal::serde::str_option")]
+    pub salary_max: Option<Decimal>,
+    pub salary_currency: Option<String>,
+    pub salary_period: Option<String>,
+    pub work_modality: WorkModality,
+    pub region_id: Option<Uuid>,
+    pub region_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct JobInvitationWithDetails {
     pub invitation: JobInvitation,
-    pub job: PublicJobListing,
+    pub job: InvitationJobSnapshot,
+    /// The company has edited these terms since sending the invitation
+    pub job_changed: bool,
     pub company_name: String,
 }
 
@@ -399,6 +620,7 @@ pub struct FollowupWithCreator {
 pub struct ManagedJobSeekersQuery {
     pub placement_outcome: Option<PlacementOutcome>,
     pub assigned_to_me: Option<bool>,
+    pub branch_id: Option<Uuid>,
     pub search: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
@@ -407,20 +629,122 @@ pub struct ManagedJobSeekersQuery {
 #[derive(Debug, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct FollowupsQuery {
-    pub followup_type: Option<FollowupType>,
+    pub followup_type: Option<String>,
     pub include_private: Option<bool>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+/// Order of invitation lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum InvitationSort {
+    /// Soonest response deadline first, newest first among equal deadlines
+    #[default]
+    ExpiresAt,
+    /// Newest first
+    CreatedAt,
+}
+
 #[derive(Debug, Deserialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct InvitationsQuery {
     pub status: Option<InvitationStatus>,
+    /// Company side only: whether the invitation produced an application
+    pub has_application: Option<bool>,
+    pub sort: Option<InvitationSort>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+/// Invitations per status, for the list tabs. Counts every status whatever
+/// the status filter, but honour the other filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct InvitationStatusCounts {
+    pub pending: i64,
+    pub viewed: i64,
+    pub accepted: i64,
+    pub applied: i64,
+    pub declined: i64,
+    pub expired: i64,
+    pub job_no_longer_available: i64,
+}
+
+impl InvitationStatusCounts {
+    pub fn from_rows(rows: impl IntoIterator<Item = (InvitationStatus, i64)>) -> Self {
+        let mut counts = Self::default();
+        for (status, count) in rows {
+            *counts.slot(status) += count;
+        }
+        counts
+    }
+
+    fn slot(&mut self, status: InvitationStatus) -> &mut i64 {
+        match status {
+            InvitationStatus::Pending => &mut self.pending,
+            InvitationStatus::Viewed => &mut self.viewed,
+            InvitationStatus::Accepted => &mut self.accepted,
+            InvitationStatus::Applied => &mut self.applied,
+            InvitationStatus::Declined => &mut self.declined,
+            InvitationStatus::Expired => &mut self.expired,
+            InvitationStatus::JobNoLongerAvailable => &mut self.job_no_longer_available,
+        }
+    }
+
+    /// Invitations matching the status filter, all of them without one
+    pub fn matching(&self, status: Option<InvitationStatus>) -> i64 {
+        match status {
+            Some(InvitationStatus::Pending) => self.pending,
+            Some(InvitationStatus::Viewed) => self.viewed,
+            Some(InvitationStatus::Accepted) => self.accepted,
+            Some(InvitationStatus::Applied) => self.applied,
+            Some(InvitationStatus::Declined) => self.declined,
+            Some(InvitationStatus::Expired) => self.expired,
+            Some(InvitationStatus::JobNoLongerAvailable) => self.job_no_longer_available,
+            None => {
+                self.pending
+                    + self.viewed
+                    + self.accepted
+                    + self.applied
+                    + self.declined
+                    + self.expired
+                    + self.job_no_longer_available
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MyInvitationsResponse {
+    pub data: Vec<JobInvitationWithDetails>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub counts: InvitationStatusCounts,
+}
+
+/// An invitation as the inviting company sees it
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobInvitationForCompany {
+    pub invitation: JobInvitation,
+    pub seeker_name: String,
+    pub job_title: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobInvitationsResponse {
+    pub data: Vec<JobInvitationForCompany>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub counts: InvitationStatusCounts,
+}
+
 // ============================================================================
 // V10: IMPERSONATION, EXPORT, APPLICATIONS
 // ============================================================================
@@ -454,6 +778,8 @@ pub struct OmilImpersonationSession {
 pub struct OmilApplicationsQuery {
     pub job_seeker_id: Option<Uuid>,
     pub status: Option<super::application::ApplicationStatus>,
+    /// Applications of the branch's job seekers
+    pub branch_id: Option<Uuid>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -464,6 +790,8 @@ pub struct OmilApplicationsQuery {
 pub struct ExportManagedSeekersQuery {
     pub placement_outcome: Option<PlacementOutcome>,
     pub include_contact: Option<bool>,
+    /// Render with a saved export template instead of the default layout
+    pub template_id: Option<Uuid>,
 }
 
 /// OMIL application with job and seeker details
@@ -479,6 +807,10 @@ pub struct OmilApplicationWithDetails {
     pub status: super::application::ApplicationStatus,
     pub applied_at: DateTime<Utc>,
     pub submitted_by: Uuid,
+    /// Always visible to the OMIL, to coach the job seeker
+    pub rejection_reason_code: Option<super::application::RejectionReasonCode>,
+    /// Only when the company shared it with the candidate
+    pub rejection_feedback: Option<String>,
 }
 
 /// Paginated list of OMIL applications
@@ -488,3 +820,801 @@ pub struct OmilApplicationsResponse {
     pub applications: Vec<OmilApplicationWithDetails>,
     pub total: i64,
 }
+
+// ============================================================================
+// V13: INTER-OMIL TRANSFERS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilTransfer {
+    pub id: Uuid,
+    pub job_seeker_id: Uuid,
+    pub source_omil_id: Uuid,
+    pub target_omil_id: Uuid,
+    pub source_managed_id: Uuid,
+    pub reason: Option<String>,
+    pub status: OmilTransferStatus,
+    pub requested_by: Uuid,
+    pub responded_by: Option<Uuid>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateOmilTransferRequest {
+    pub target_omil_id: Uuid,
+
+    #[validate(length(max = 1000, message = "Reason too long"))]
+    pub reason: Option<String>,
+}
+
+/// Query parameters for the transfers list
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilTransfersQuery {
+    /// "incoming" or "outgoing"; both when omitted
+    pub direction: Option<String>,
+    pub status: Option<OmilTransferStatus>,
+}
+
+/// Transfer with names for display
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilTransferWithDetails {
+    pub transfer: OmilTransfer,
+    pub job_seeker_name: String,
+    pub source_omil_name: String,
+    pub target_omil_name: String,
+    pub requested_by_name: String,
+    /// "incoming" or "outgoing" from the caller's organization
+    pub direction: String,
+}
+
+/// Result of accepting a transfer
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AcceptOmilTransferResponse {
+    pub transfer: OmilTransfer,
+    pub managed_job_seeker: OmilManagedJobSeeker,
+    pub followups_copied: i64,
+}
+
+// ============================================================================
+// V13: OMIL DIRECTORY AND REFERRAL REQUESTS
+// ============================================================================
+
+/// Upper bounds of the seeker count buckets shown in the directory
+const SEEKER_COUNT_BUCKETS: [(i64, &str); 4] = [
+    (9, "0-9"),
+    (49, "10-49"),
+    (199, "50-199"),
+    (i64::MAX, "200+"),
+];
+
+/// Bucket label for an OMIL's active seeker count; exact counts stay private
+pub fn seeker_count_bucket(count: i64) -> &'static str {
+    SEEKER_COUNT_BUCKETS
+        .iter()
+        .find(|(max, _)| count <= *max)
+        .map(|(_, label)| *label)
+        .unwrap_or("0-9")
+}
+
+/// Query parameters for the OMIL directory
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilDirectoryQuery {
+    pub region_id: Option<Uuid>,
+    pub municipality_id: Option<Uuid>,
+}
+
+/// An active OMIL as companies see it, with aggregate figures only
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilDirectoryEntry {
+    pub id: Uuid,
+    pub organization_name: String,
+    pub region_id: Option<Uuid>,
+    pub region_name: Option<String>,
+    pub municipality_id: Option<Uuid>,
+    pub municipality_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    /// e.g. "10-49"
+    pub active_seekers: String,
+    pub placements_this_year: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralRequest {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub omil_id: Uuid,
+    pub job_id: Uuid,
+    pub message: String,
+    pub status: ReferralRequestStatus,
+    pub response_message: Option<String>,
+    pub requested_by: Uuid,
+    pub responded_by: Option<Uuid>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateReferralRequest {
+    pub omil_id: Uuid,
+    pub job_id: Uuid,
+
+    #[validate(length(min = 1, max = 2000, message = "Message must be 1-2000 characters"))]
+    pub message: String,
+}
+
+impl Normalize for CreateReferralRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.message);
+    }
+}
+
+/// Body for accepting or declining a referral request
+#[derive(Debug, Default, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RespondReferralRequest {
+    #[validate(length(max = 2000, message = "Message too long"))]
+    pub message: Option<String>,
+}
+
+impl Normalize for RespondReferralRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.message);
+    }
+}
+
+/// Query parameters for the referral request lists
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralRequestsQuery {
+    pub status: Option<ReferralRequestStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralRequestHistoryEntry {
+    pub status: ReferralRequestStatus,
+    pub changed_by_name: String,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Referral request with names for display, as listed to either side
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralRequestWithDetails {
+    pub request: ReferralRequest,
+    pub company_name: String,
+    pub omil_name: String,
+    pub job_title: String,
+    pub requested_by_name: String,
+}
+
+/// A single referral request with its status history
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralRequestDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub details: ReferralRequestWithDetails,
+    pub history: Vec<ReferralRequestHistoryEntry>,
+}
+
+/// A managed seeker scored against the job of an accepted referral request
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ReferralCandidate {
+    /// Use with POST /api/me/omil/job-seekers/{id}/apply
+    pub managed_id: Uuid,
+    pub job_seeker_id: Uuid,
+    pub job_seeker_name: String,
+    pub match_score: i32,
+    pub score_breakdown: MatchScoreBreakdown,
+    pub has_applied: bool,
+}
+
+/// OMIL a job is co-published with, shown on the public job page
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobPartnerOmil {
+    pub omil_id: Uuid,
+    pub organization_name: String,
+}
+
+/// Body for naming or clearing a job's partner OMIL
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SetJobPartnerRequest {
+    /// Must have accepted a referral request for the job; null ends the
+    /// partnership
+    pub omil_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct JobPartnership {
+    pub job_id: Uuid,
+    pub partner_omil: Option<JobPartnerOmil>,
+}
+
+/// Applications to a partner job in one status, counted over all applicants
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PartnerJobStatusCount {
+    pub status: ApplicationStatus,
+    pub count: i64,
+}
+
+/// Application to a partner job by one of the OMIL's own managed seekers
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PartnerJobApplicant {
+    pub application_id: Uuid,
+    pub managed_id: Uuid,
+    pub job_seeker_id: Uuid,
+    pub job_seeker_name: String,
+    pub status: ApplicationStatus,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// What the partner OMIL sees of a co-published job's applicants
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PartnerJobApplicants {
+    pub job_id: Uuid,
+    pub job_title: String,
+    pub company_name: String,
+    pub total_applications: i64,
+    pub status_counts: Vec<PartnerJobStatusCount>,
+    pub applicants: Vec<PartnerJobApplicant>,
+}
+
+// ============================================================================
+// V13: EXPORT TEMPLATES
+// ============================================================================
+
+/// Columns an export template may use, as (key, header) in suggested order
+pub const OMIL_EXPORT_COLUMNS: [(&str, &str); 23] = [
+    ("full_name", "Name"),
+    ("first_name", "First Name"),
+    ("last_name", "Last Name"),
+    ("national_id", "RUT"),
+    ("email", "Email"),
+    ("phone", "Phone"),
+    ("date_of_birth", "Date of Birth"),
+    ("gender", "Gender"),
+    ("region", "Region"),
+    ("municipality", "Municipality"),
+    ("profile_completeness", "Profile Completeness"),
+    ("placement_outcome", "Placement Status"),
+    ("placed_at", "Placed At"),
+    ("placed_job_title", "Placed Job"),
+    ("placed_company_name", "Placed Company"),
+    ("advisor_name", "Advisor"),
+    ("branch", "Branch"),
+    ("registered_at", "Registered At"),
+    ("is_active", "Active"),
+    ("followup_count", "Followups"),
+    ("last_followup_at", "Last Followup"),
+    ("applications_count", "Applications Submitted"),
+    ("notes", "Notes"),
+];
+
+/// Header of an export column, or None for unknown keys
+pub fn export_column_header(key: &str) -> Option<&'static str> {
+    OMIL_EXPORT_COLUMNS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, header)| *header)
+}
+
+/// Validate a template's columns: known keys, each used once
+pub fn validate_export_columns(columns: &[String]) -> Result<(), ValidationError> {
+    for (i, column) in columns.iter().enumerate() {
+        if export_column_header(column).is_none() {
+            return Err(ValidationError::new("unknown_export_column")
+                .with_message(format!("Unknown export column: {}", column).into()));
+        }
+        if columns[..i].contains(column) {
+            return Err(ValidationError::new("duplicate_export_column")
+                .with_message(format!("Export column listed twice: {}", column).into()));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilExportTemplate {
+    pub id: Uuid,
+    pub omil_id: Uuid,
+    pub name: String,
+    pub columns: Vec<String>,
+    pub header_title: Option<String>,
+    pub include_logo: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateExportTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 23, message = "Choose between 1 and 23 columns"))]
+    #[validate(custom(function = "validate_export_columns"))]
+    pub columns: Vec<String>,
+    #[validate(length(max = 200, message = "Header title too long"))]
+    pub header_title: Option<String>,
+    pub include_logo: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateExportTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+    #[validate(length(min = 1, max = 23, message = "Choose between 1 and 23 columns"))]
+    #[validate(custom(function = "validate_export_columns"))]
+    pub columns: Option<Vec<String>>,
+    #[validate(length(max = 200, message = "Header title too long"))]
+    pub header_title: Option<String>,
+    pub include_logo: Option<bool>,
+}
+
+impl Normalize for CreateExportTemplateRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.name);
+        normalize::trim_opt(&mut self.header_title);
+    }
+}
+
+impl Normalize for UpdateExportTemplateRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.name);
+        normalize::trim_opt(&mut self.header_title);
+    }
+}
+
+/// An export column offered to template editors
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ExportColumnInfo {
+    pub key: String,
+    pub header: String,
+}
+
+// ============================================================================
+// MONTHLY SENCE REPORT
+// ============================================================================
+
+/// Query parameters for the monthly SENCE report
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MonthlyReportQuery {
+    pub year: i32,
+    pub month: i32,
+}
+
+/// One counter of the monthly report, split by gender and by disability status.
+/// Seekers with no gender on their profile, or who preferred not to say, are
+/// counted as `gender_not_specified`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MonthlyReportCounter {
+    pub total: i64,
+    pub male: i64,
+    pub female: i64,
+    pub non_binary: i64,
+    pub gender_not_specified: i64,
+    pub with_disability: i64,
+    pub without_disability: i64,
+}
+
+/// Counters an OMIL reports to SENCE for one calendar month
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilMonthlyReport {
+    pub omil_id: Uuid,
+    pub organization_name: String,
+    pub year: i32,
+    pub month: i32,
+    /// Zone the month boundaries were taken in
+    pub timezone: String,
+    /// Seekers registered by the OMIL during the month
+    pub new_registrations: MonthlyReportCounter,
+    /// Seekers registered by the end of the month and still active with the OMIL
+    pub total_active: MonthlyReportCounter,
+    /// Applications the OMIL submitted on behalf of seekers during the month
+    pub intermediated: MonthlyReportCounter,
+    /// Seekers marked as placed during the month
+    pub placed: MonthlyReportCounter,
+    /// Followups recorded during the month whose type counts as an intervention
+    pub interventions: MonthlyReportCounter,
+    /// Per branch, with placements and applications during the month; the
+    /// unassigned bucket comes last
+    pub branches: Vec<OmilBranchStats>,
+}
+
+// ============================================================================
+// KIOSK SESSIONS
+// ============================================================================
+
+/// Body for opening a kiosk on a shared device
+#[derive(Debug, Default, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateKioskSessionRequest {
+    /// Where the kiosk is used, e.g. the job fair's name
+    #[validate(length(max = 100, message = "Label must be at most 100 characters"))]
+    pub label: Option<String>,
+}
+
+impl Normalize for CreateKioskSessionRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.label);
+    }
+}
+
+/// A kiosk session with how many seekers were registered through it
+#[derive(Debug, Clone, Serialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilKioskSession {
+    pub id: Uuid,
+    pub omil_id: Uuid,
+    pub label: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_by_name: Option<String>,
+    pub registrations: i64,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for opening a kiosk. The token is only shown once.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct KioskSessionResponse {
+    pub kiosk_token: String,
+    pub session: OmilKioskSession,
+}
+
+// ============================================================================
+// V13: SKILL ENDORSEMENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "endorser_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum EndorserType {
+    OmilMember,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SkillEndorsement {
+    pub id: Uuid,
+    pub user_skill_id: Uuid,
+    pub endorsed_by: Uuid,
+    pub endorser_type: EndorserType,
+    pub omil_id: Uuid,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct EndorseSkillRequest {
+    #[validate(length(max = 1000, message = "Comment too long"))]
+    pub comment: Option<String>,
+}
+
+impl Normalize for EndorseSkillRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.comment);
+    }
+}
+
+// ============================================================================
+// PROFILE REFERENCES
+// ============================================================================
+
+/// A managed job seeker's reference as their OMIL sees it, referee email
+/// included
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ManagedProfileReference {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub reference: ProfileReference,
+    pub verified_by_name: Option<String>,
+    /// Short-lived download link of the recommendation letter
+    pub letter_download_url: Option<String>,
+}
+
+// ============================================================================
+// PENDING ACTIONS (FOUR-EYES APPROVAL)
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct DeactivateManagedJobSeekerRequest {
+    #[validate(length(max = 1000, message = "Reason too long"))]
+    pub reason: Option<String>,
+}
+
+impl Normalize for DeactivateManagedJobSeekerRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.reason);
+    }
+}
+
+/// An advisor's placement or deactivation held for approval
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PendingOmilAction {
+    pub id: Uuid,
+    pub omil_id: Uuid,
+    pub managed_id: Uuid,
+    pub action_type: OmilActionType,
+    /// The request as the advisor sent it
+    #[ts(type = "any")]
+    pub payload: serde_json::Value,
+    pub status: PendingActionStatus,
+    pub requested_by: Uuid,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Pending action with names for display
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PendingOmilActionWithDetails {
+    pub action: PendingOmilAction,
+    pub job_seeker_name: String,
+    pub requested_by_name: String,
+}
+
+/// Query parameters for the pending actions queue
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PendingOmilActionsQuery {
+    /// Defaults to pending
+    pub status: Option<PendingActionStatus>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RejectPendingActionRequest {
+    #[validate(length(max = 1000, message = "Reason too long"))]
+    pub reason: Option<String>,
+}
+
+/// Result of approving an action: the record as the action left it
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApprovePendingActionResponse {
+    pub action: PendingOmilAction,
+    pub managed_job_seeker: OmilManagedJobSeeker,
+}
+
+/// Whether advisors' placements and deactivations need approval
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApprovalSettings {
+    pub require_placement_approval: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateApprovalSettingsRequest {
+    pub require_placement_approval: bool,
+}
+
+// ============================================================================
+// COMPANY INSIGHTS
+// ============================================================================
+
+/// Hiring signals of a company in the OMIL's region, aggregates only
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyInsight {
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub active_jobs_with_accommodations: i64,
+    /// Candidates with a registered disability hired in the last 12 months
+    pub disability_hires_last_12_months: SuppressedCount,
+    /// Days from application to hire, over all hires in the last 12 months
+    pub avg_days_to_hire: Option<f64>,
+    /// Whether the company has hired one of this OMIL's job seekers
+    pub has_hired_our_seekers: bool,
+    pub inclusiveness_score: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CompanyInsightsResponse {
+    /// How `inclusiveness_score` is computed
+    pub score_formula: String,
+    pub generated_at: DateTime<Utc>,
+    /// Highest inclusiveness score first
+    pub companies: Vec<CompanyInsight>,
+}
+
+// ============================================================================
+// BRANCHES
+// ============================================================================
+
+/// An attention point of the OMIL. Members and managed job seekers can be
+/// assigned to one.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilBranch {
+    pub id: Uuid,
+    pub omil_id: Uuid,
+    pub name: String,
+    pub address: Option<String>,
+    pub municipality_id: Option<Uuid>,
+    /// Inactive branches can't be assigned
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CreateOmilBranchRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(length(max = 255, message = "Address too long"))]
+    pub address: Option<String>,
+    pub municipality_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct UpdateOmilBranchRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+    #[validate(length(max = 255, message = "Address too long"))]
+    pub address: Option<String>,
+    pub municipality_id: Option<Uuid>,
+    /// Deactivating requires the branch's active job seekers and members to
+    /// be reassigned or unassigned first
+    pub is_active: Option<bool>,
+}
+
+impl Normalize for CreateOmilBranchRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.name);
+        normalize::trim_opt(&mut self.address);
+    }
+}
+
+impl Normalize for UpdateOmilBranchRequest {
+    fn normalize(&mut self) {
+        normalize::trim_opt(&mut self.name);
+        normalize::trim_opt(&mut self.address);
+    }
+}
+
+/// Body for assigning a member or a managed job seeker to a branch
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AssignBranchRequest {
+    /// None unassigns
+    pub branch_id: Option<Uuid>,
+}
+
+/// Why a branch can't be deactivated or deleted yet, if it can't
+pub fn branch_in_use_message(active_seekers: i64, active_members: i64) -> Option<String> {
+    let mut in_use = Vec::new();
+    for (count, one, many) in [
+        (active_seekers, "job seeker", "job seekers"),
+        (active_members, "member", "members"),
+    ] {
+        match count {
+            0 => {}
+            1 => in_use.push(format!("1 {}", one)),
+            _ => in_use.push(format!("{} {}", count, many)),
+        }
+    }
+    if in_use.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Reassign or unassign the branch's {} first",
+        in_use.join(" and ")
+    ))
+}
+
+/// One branch's numbers. The entry without a branch counts the job seekers,
+/// and their placements and applications, not assigned to any branch.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilBranchStats {
+    /// None for the unassigned bucket
+    pub branch_id: Option<Uuid>,
+    pub branch_name: Option<String>,
+    /// Active job seekers
+    pub seekers: i64,
+    /// Job seekers placed during the period
+    pub placements: i64,
+    /// Applications the OMIL submitted during the period, by the seeker's branch
+    pub applications: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_export_columns() {
+        assert!(validate_export_columns(&columns(&["notes", "full_name"])).is_ok());
+        assert!(validate_export_columns(&columns(&["full_name", "salary"])).is_err());
+        assert!(validate_export_columns(&columns(&["email", "email"])).is_err());
+    }
+
+    #[test]
+    fn test_invitation_status_counts() {
+        let counts = InvitationStatusCounts::from_rows([
+            (InvitationStatus::Pending, 4),
+            (InvitationStatus::Accepted, 9),
+            (InvitationStatus::Expired, 1),
+        ]);
+
+        assert_eq!(counts.pending, 4);
+        assert_eq!(counts.declined, 0);
+        assert_eq!(counts.matching(Some(InvitationStatus::Accepted)), 9);
+        assert_eq!(counts.matching(Some(InvitationStatus::Viewed)), 0);
+        assert_eq!(counts.matching(None), 14);
+    }
+
+    #[test]
+    fn test_seeker_count_bucket() {
+        assert_eq!(seeker_count_bucket(0), "0-9");
+        assert_eq!(seeker_count_bucket(9), "0-9");
+        assert_eq!(seeker_count_bucket(10), "10-49");
+        assert_eq!(seeker_count_bucket(199), "50-199");
+        assert_eq!(seeker_count_bucket(5000), "200+");
+    }
+
+    #[test]
+    fn test_branch_in_use_message() {
+        assert_eq!(branch_in_use_message(0, 0), None);
+        assert_eq!(
+            branch_in_use_message(3, 1).as_deref(),
+            Some("Reassign or unassign the branch's 3 job seekers and 1 member first")
+        );
+        assert_eq!(
+            branch_in_use_message(0, 2).as_deref(),
+            Some("Reassign or unassign the branch's 2 members first")
+        );
+    }
+
+    #[test]
+    fn test_export_column_keys_are_unique() {
+        for (i, (key, _)) in OMIL_EXPORT_COLUMNS.iter().enumerate() {
+            assert!(!OMIL_EXPORT_COLUMNS[..i].iter().any(|(k, _)| k == key));
+        }
+    }
+}
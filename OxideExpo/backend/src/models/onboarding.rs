@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Skills a seeker needs before the skills step counts as done
+pub const ONBOARDING_MIN_SKILLS: i64 = 3;
+
+/// A step of the job seeker onboarding checklist, in the order it is shown.
+/// Steps live only in code; adding one means adding a variant here and its
+/// check in `is_done`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum OnboardingStep {
+    VerifyEmail,
+    /// Phone, date of birth, region and municipality
+    BasicInfo,
+    WorkExperience,
+    /// At least ONBOARDING_MIN_SKILLS skills
+    Skills,
+    Cv,
+    /// Work modality, job type, region or salary expectation
+    Preferences,
+}
+
+impl OnboardingStep {
+    pub const ALL: [OnboardingStep; 6] = [
+        OnboardingStep::VerifyEmail,
+        OnboardingStep::BasicInfo,
+        OnboardingStep::WorkExperience,
+        OnboardingStep::Skills,
+        OnboardingStep::Cv,
+        OnboardingStep::Preferences,
+    ];
+
+    /// Code used in URLs and stored in dismissed_steps
+    pub fn code(self) -> &'static str {
+        match self {
+            OnboardingStep::VerifyEmail => "verify_email",
+            OnboardingStep::BasicInfo => "basic_info",
+            OnboardingStep::WorkExperience => "work_experience",
+            OnboardingStep::Skills => "skills",
+            OnboardingStep::Cv => "cv",
+            OnboardingStep::Preferences => "preferences",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|step| step.code() == code)
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            OnboardingStep::VerifyEmail => "Verifica tu correo electrónico",
+            OnboardingStep::BasicInfo => "Completa tus datos básicos",
+            OnboardingStep::WorkExperience => "Agrega una experiencia laboral",
+            OnboardingStep::Skills => "Agrega 3 habilidades",
+            OnboardingStep::Cv => "Sube tu CV",
+            OnboardingStep::Preferences => "Define tus preferencias de empleo",
+        }
+    }
+
+    /// Required steps can't be dismissed, and all of them must be done for
+    /// the onboarding to be completed. First-time job seekers may have no
+    /// work experience to add.
+    pub fn is_required(self) -> bool {
+        !matches!(
+            self,
+            OnboardingStep::WorkExperience | OnboardingStep::Preferences
+        )
+    }
+
+    pub fn is_done(self, facts: &OnboardingFacts) -> bool {
+        match self {
+            OnboardingStep::VerifyEmail => facts.email_verified,
+            OnboardingStep::BasicInfo => facts.has_basic_info,
+            OnboardingStep::WorkExperience => facts.experience_count > 0,
+            OnboardingStep::Skills => facts.skill_count >= ONBOARDING_MIN_SKILLS,
+            OnboardingStep::Cv => facts.has_cv,
+            OnboardingStep::Preferences => facts.has_preferences,
+        }
+    }
+}
+
+/// The profile data the steps are checked against
+#[derive(Debug, Clone, Default)]
+pub struct OnboardingFacts {
+    pub email_verified: bool,
+    pub has_basic_info: bool,
+    pub experience_count: i64,
+    pub skill_count: i64,
+    pub has_cv: bool,
+    pub has_preferences: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OnboardingStepStatus {
+    pub step: OnboardingStep,
+    pub title: String,
+    pub required: bool,
+    pub completed: bool,
+    pub dismissed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OnboardingChecklist {
+    pub steps: Vec<OnboardingStepStatus>,
+    /// Share of steps completed or dismissed, 0-100
+    pub progress_percentage: i32,
+    /// When every required step was first done; stays set afterwards
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl OnboardingChecklist {
+    pub fn build(
+        facts: &OnboardingFacts,
+        dismissed: &[String],
+        completed_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let steps: Vec<OnboardingStepStatus> = OnboardingStep::ALL
+            .into_iter()
+            .map(|step| {
+                let completed = step.is_done(facts);
+                OnboardingStepStatus {
+                    step,
+                    title: step.title().to_string(),
+                    required: step.is_required(),
+                    completed,
+                    dismissed: !completed
+                        && !step.is_required()
+                        && dismissed.iter().any(|code| code == step.code()),
+                }
+            })
+            .collect();
+
+        let settled = steps.iter().filter(|s| s.completed || s.dismissed).count();
+        let progress_percentage = (settled * 100 / steps.len()) as i32;
+
+        OnboardingChecklist {
+            steps,
+            progress_percentage,
+            completed_at,
+        }
+    }
+
+    pub fn required_steps_done(&self) -> bool {
+        self.steps.iter().all(|s| !s.required || s.completed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete_facts() -> OnboardingFacts {
+        OnboardingFacts {
+            email_verified: true,
+            has_basic_info: true,
+            experience_count: 1,
+            skill_count: 3,
+            has_cv: true,
+            has_preferences: true,
+        }
+    }
+
+    #[test]
+    fn test_step_codes_round_trip() {
+        for step in OnboardingStep::ALL {
+            assert_eq!(OnboardingStep::from_code(step.code()), Some(step));
+            assert_eq!(
+                serde_json::to_value(step).unwrap(),
+                serde_json::json!(step.code())
+            );
+        }
+        assert_eq!(OnboardingStep::from_code("unknown"), None);
+    }
+
+    #[test]
+    fn test_step_detection() {
+        let facts = complete_facts();
+        assert!(OnboardingStep::ALL.iter().all(|s| s.is_done(&facts)));
+
+        let facts = OnboardingFacts {
+            skill_count: 2,
+            ..complete_facts()
+        };
+        assert!(!OnboardingStep::Skills.is_done(&facts));
+
+        let empty = OnboardingFacts::default();
+        assert!(OnboardingStep::ALL.iter().all(|s| !s.is_done(&empty)));
+    }
+
+    #[test]
+    fn test_progress_counts_dismissed_optional_steps() {
+        let facts = OnboardingFacts {
+            email_verified: true,
+            skill_count: 5,
+            ..Default::default()
+        };
+        let dismissed = vec!["preferences".to_string(), "cv".to_string()];
+        let checklist = OnboardingChecklist::build(&facts, &dismissed, None);
+
+        // Verify email, skills and the dismissed preferences; a required
+        // step can't be dismissed
+        assert_eq!(checklist.progress_percentage, 50);
+        let cv = checklist
+            .steps
+            .iter()
+            .find(|s| s.step == OnboardingStep::Cv)
+            .unwrap();
+        assert!(!cv.dismissed);
+        assert!(!checklist.required_steps_done());
+    }
+
+    #[test]
+    fn test_required_steps_done_without_optional_ones() {
+        let facts = OnboardingFacts {
+            experience_count: 0,
+            has_preferences: false,
+            ..complete_facts()
+        };
+        let checklist = OnboardingChecklist::build(&facts, &[], None);
+        assert!(checklist.required_steps_done());
+        assert_eq!(checklist.progress_percentage, 66);
+    }
+}
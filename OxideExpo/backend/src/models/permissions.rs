@@ -0,0 +1,319 @@
+//! What a session may do, as one set of capabilities.
+//!
+//! `resolve` is the single place that maps a user type, company role, OMIL
+//! role, admin level and the kind of session onto capabilities; handlers
+//! check a capability instead of comparing user types and roles themselves.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::models::admin::AdminRole;
+use crate::models::company::MemberRole;
+use crate::models::omil::OmilRole;
+use crate::models::user::UserType;
+
+/// A protected action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Edit one's own seeker profile, CV and preferences
+    ManageOwnProfile,
+    /// Apply to, save and get matched with jobs
+    ApplyToJobs,
+    /// Delete the account, change its credentials and tokens
+    ManageOwnAccount,
+    /// See the company's jobs and applicants, and annotate and tag applicants
+    ViewCompanyJobs,
+    /// Create, edit, close and configure the company's jobs
+    PostJobs,
+    /// Move applicants through the hiring process
+    ManageApplicants,
+    /// Export applicants, managed seekers or reports as spreadsheets
+    ExportData,
+    /// Register and follow up the OMIL's managed seekers
+    ManageOmilSeekers,
+    /// Manage the OMIL's advisors, reports and approvals
+    SuperviseOmil,
+    /// Change the OMIL's settings, branches and senior roles
+    AdministerOmil,
+    /// Use the admin panel
+    AccessAdminPanel,
+    /// Review jobs, companies and reported content
+    ModerateContent,
+    /// Platform settings and admin accounts
+    AdministerPlatform,
+}
+
+impl Capability {
+    /// Completes "This session can't ..." in a refusal
+    pub fn action(self) -> &'static str {
+        match self {
+            Capability::ManageOwnProfile => "edit a job seeker profile",
+            Capability::ApplyToJobs => "apply to jobs",
+            Capability::ManageOwnAccount => "manage the account",
+            Capability::ViewCompanyJobs => "view the company's jobs and applicants",
+            Capability::PostJobs => "publish or edit the company's jobs",
+            Capability::ManageApplicants => "manage applicants",
+            Capability::ExportData => "export data",
+            Capability::ManageOmilSeekers => "manage the OMIL's job seekers",
+            Capability::SuperviseOmil => "supervise the OMIL",
+            Capability::AdministerOmil => "administer the OMIL",
+            Capability::AccessAdminPanel => "use the admin panel",
+            Capability::ModerateContent => "moderate content",
+            Capability::AdministerPlatform => "administer the platform",
+        }
+    }
+}
+
+/// Active company membership
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompanyMembership {
+    pub company_id: Uuid,
+    pub role: MemberRole,
+}
+
+/// Active membership of an active OMIL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OmilMembership {
+    pub omil_id: Uuid,
+    pub role: OmilRole,
+}
+
+/// Who is acting, and through what kind of session
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    pub user_id: Uuid,
+    pub user_type: UserType,
+    pub company: Option<CompanyMembership>,
+    pub omil: Option<OmilMembership>,
+    pub admin_role: Option<AdminRole>,
+    /// Staff acting as a seeker through an impersonation token
+    pub impersonated: bool,
+    /// An OMIL kiosk token
+    pub kiosk: bool,
+}
+
+fn company_capabilities(role: MemberRole) -> &'static [Capability] {
+    match role {
+        MemberRole::Owner | MemberRole::Admin => &[
+            Capability::ViewCompanyJobs,
+            Capability::ExportData,
+            Capability::PostJobs,
+            Capability::ManageApplicants,
+        ],
+        MemberRole::Member => &[Capability::ViewCompanyJobs, Capability::ExportData],
+    }
+}
+
+fn omil_capabilities(role: OmilRole) -> &'static [Capability] {
+    match role {
+        OmilRole::Director => &[
+            Capability::ManageOmilSeekers,
+            Capability::ExportData,
+            Capability::SuperviseOmil,
+            Capability::AdministerOmil,
+        ],
+        OmilRole::Coordinator => &[
+            Capability::ManageOmilSeekers,
+            Capability::ExportData,
+            Capability::SuperviseOmil,
+        ],
+        OmilRole::Advisor => &[Capability::ManageOmilSeekers, Capability::ExportData],
+    }
+}
+
+fn admin_capabilities(role: AdminRole) -> &'static [Capability] {
+    match role {
+        AdminRole::SuperAdmin => &[
+            Capability::AccessAdminPanel,
+            Capability::ExportData,
+            Capability::ModerateContent,
+            Capability::AdministerPlatform,
+        ],
+        AdminRole::Moderator => &[
+            Capability::AccessAdminPanel,
+            Capability::ExportData,
+            Capability::ModerateContent,
+        ],
+        AdminRole::Analyst => &[Capability::AccessAdminPanel, Capability::ExportData],
+    }
+}
+
+/// Everything the session may do.
+///
+/// Company roles only count in a company member session. OMIL staff are
+/// added by email whatever their account type, so an OMIL membership counts
+/// on its own, as does an admin row. An impersonation session gets the
+/// seeker's everyday capabilities and nothing on the account itself, and a
+/// kiosk only registers seekers for its OMIL.
+pub fn resolve(context: &AuthContext) -> HashSet<Capability> {
+    let mut capabilities = HashSet::new();
+
+    if context.impersonated {
+        if context.user_type == UserType::JobSeeker {
+            capabilities.extend([Capability::ManageOwnProfile, Capability::ApplyToJobs]);
+        }
+        return capabilities;
+    }
+
+    if context.kiosk {
+        if context.omil.is_some() {
+            capabilities.insert(Capability::ManageOmilSeekers);
+        }
+        return capabilities;
+    }
+
+    capabilities.insert(Capability::ManageOwnAccount);
+
+    if context.user_type == UserType::JobSeeker {
+        capabilities.extend([Capability::ManageOwnProfile, Capability::ApplyToJobs]);
+    }
+    if let (UserType::CompanyMember, Some(membership)) = (context.user_type, context.company) {
+        capabilities.extend(company_capabilities(membership.role));
+    }
+    if let Some(membership) = context.omil {
+        capabilities.extend(omil_capabilities(membership.role));
+    }
+    if let Some(role) = context.admin_role {
+        capabilities.extend(admin_capabilities(role));
+    }
+
+    capabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(user_type: UserType) -> AuthContext {
+        AuthContext {
+            user_id: Uuid::new_v4(),
+            user_type,
+            company: None,
+            omil: None,
+            admin_role: None,
+            impersonated: false,
+            kiosk: false,
+        }
+    }
+
+    fn company(role: MemberRole) -> AuthContext {
+        AuthContext {
+            company: Some(CompanyMembership {
+                company_id: Uuid::new_v4(),
+                role,
+            }),
+            ..context(UserType::CompanyMember)
+        }
+    }
+
+    fn omil(role: OmilRole) -> AuthContext {
+        AuthContext {
+            omil: Some(OmilMembership {
+                omil_id: Uuid::new_v4(),
+                role,
+            }),
+            ..context(UserType::OmilMember)
+        }
+    }
+
+    #[test]
+    fn test_job_seekers() {
+        let capabilities = resolve(&context(UserType::JobSeeker));
+        assert_eq!(
+            capabilities,
+            HashSet::from([
+                Capability::ManageOwnAccount,
+                Capability::ManageOwnProfile,
+                Capability::ApplyToJobs,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_company_roles() {
+        let member = resolve(&company(MemberRole::Member));
+        assert!(member.contains(&Capability::ViewCompanyJobs));
+        assert!(member.contains(&Capability::ExportData));
+        assert!(!member.contains(&Capability::PostJobs));
+        assert!(!member.contains(&Capability::ManageApplicants));
+
+        for role in [MemberRole::Owner, MemberRole::Admin] {
+            let capabilities = resolve(&company(role));
+            assert!(capabilities.contains(&Capability::PostJobs));
+            assert!(capabilities.contains(&Capability::ManageApplicants));
+            assert!(!capabilities.contains(&Capability::ApplyToJobs));
+        }
+
+        // A company member account without an active membership
+        let removed = resolve(&context(UserType::CompanyMember));
+        assert_eq!(removed, HashSet::from([Capability::ManageOwnAccount]));
+
+        // A membership held by another kind of account
+        let seeker = resolve(&AuthContext {
+            user_type: UserType::JobSeeker,
+            ..company(MemberRole::Owner)
+        });
+        assert!(!seeker.contains(&Capability::ViewCompanyJobs));
+    }
+
+    #[test]
+    fn test_omil_roles_are_cumulative() {
+        let advisor = resolve(&omil(OmilRole::Advisor));
+        let coordinator = resolve(&omil(OmilRole::Coordinator));
+        let director = resolve(&omil(OmilRole::Director));
+
+        assert!(advisor.contains(&Capability::ManageOmilSeekers));
+        assert!(!advisor.contains(&Capability::SuperviseOmil));
+        assert!(advisor.is_subset(&coordinator));
+        assert!(coordinator.contains(&Capability::SuperviseOmil));
+        assert!(!coordinator.contains(&Capability::AdministerOmil));
+        assert!(coordinator.is_subset(&director));
+        assert!(director.contains(&Capability::AdministerOmil));
+    }
+
+    #[test]
+    fn test_admin_levels() {
+        let admin = |role| {
+            resolve(&AuthContext {
+                admin_role: Some(role),
+                ..context(UserType::Admin)
+            })
+        };
+
+        let analyst = admin(AdminRole::Analyst);
+        assert!(analyst.contains(&Capability::AccessAdminPanel));
+        assert!(!analyst.contains(&Capability::ModerateContent));
+        let moderator = admin(AdminRole::Moderator);
+        assert!(moderator.contains(&Capability::ModerateContent));
+        assert!(!moderator.contains(&Capability::AdministerPlatform));
+        assert!(admin(AdminRole::SuperAdmin).contains(&Capability::AdministerPlatform));
+
+        assert!(!resolve(&context(UserType::Admin)).contains(&Capability::AccessAdminPanel));
+    }
+
+    #[test]
+    fn test_impersonation_and_kiosk_sessions_are_restricted() {
+        let impersonated = resolve(&AuthContext {
+            impersonated: true,
+            ..context(UserType::JobSeeker)
+        });
+        assert_eq!(
+            impersonated,
+            HashSet::from([Capability::ManageOwnProfile, Capability::ApplyToJobs])
+        );
+
+        // Staff roles never carry over into an impersonation
+        let impersonated_director = resolve(&AuthContext {
+            impersonated: true,
+            ..omil(OmilRole::Director)
+        });
+        assert!(impersonated_director.is_empty());
+
+        let kiosk = resolve(&AuthContext {
+            kiosk: true,
+            ..omil(OmilRole::Director)
+        });
+        assert_eq!(kiosk, HashSet::from([Capability::ManageOmilSeekers]));
+    }
+}
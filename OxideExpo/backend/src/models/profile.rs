@@ -5,6 +5,9 @@ use ts_rs::TS;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::utils::normalize::{self, validate_phone, Normalize};
+use crate::utils::rut::validate_rut;
+
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0002_create_enums.sql)
 // ============================================================================
@@ -32,6 +35,20 @@ pub enum MaritalStatus {
     DomesticPartnership,
 }
 
+/// Whether a seeker is looking for work. Companies see only this status,
+/// never the date the seeker is available from unless they share it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "seeker_availability", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum SeekerAvailability {
+    ActivelyLooking,
+    #[default]
+    OpenToOffers,
+    /// Left out of candidate search
+    NotAvailable,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
 #[sqlx(type_name = "disability_category", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -118,14 +135,20 @@ pub struct JobSeekerProfile {
     pub profile_image_url: Option<String>,
     pub cv_url: Option<String>,
     pub completeness_percentage: i32,
+    pub availability: SeekerAvailability,
+    /// Null in company views unless the seeker shares it
+    pub available_from: Option<NaiveDate>,
+    pub share_available_from: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
+#[validate(schema(function = "validate_available_from_update"))]
 pub struct UpdateProfileRequest {
     #[validate(length(max = 20, message = "Phone number too long"))]
+    #[validate(custom(function = "validate_phone"))]
     pub phone: Option<String>,
     pub date_of_birth: Option<NaiveDate>,
     pub gender: Option<Gender>,
@@ -133,6 +156,7 @@ pub struct UpdateProfileRequest {
     #[validate(length(max = 100, message = "Nationality too long"))]
     pub nationality: Option<String>,
     #[validate(length(max = 50, message = "National ID too long"))]
+    #[validate(custom(function = "validate_rut"))]
     pub national_id: Option<String>,
     pub region_id: Option<Uuid>,
     pub municipality_id: Option<Uuid>,
@@ -142,6 +166,34 @@ pub struct UpdateProfileRequest {
     pub bio: Option<String>,
     #[validate(length(max = 200, message = "Professional headline too long"))]
     pub professional_headline: Option<String>,
+    /// Availability fields left out keep their current value
+    pub availability: Option<SeekerAvailability>,
+    pub available_from: Option<NaiveDate>,
+    /// Remove the available-from date
+    pub clear_available_from: Option<bool>,
+    /// Show the available-from date to companies
+    pub share_available_from: Option<bool>,
+}
+
+fn validate_available_from_update(
+    request: &UpdateProfileRequest,
+) -> Result<(), validator::ValidationError> {
+    if request.available_from.is_some() && request.clear_available_from == Some(true) {
+        return Err(validator::ValidationError::new("available_from")
+            .with_message("Cannot set and clear the available-from date at once".into()));
+    }
+    Ok(())
+}
+
+impl Normalize for UpdateProfileRequest {
+    fn normalize(&mut self) {
+        normalize::phone_opt(&mut self.phone);
+        normalize::trim_opt(&mut self.nationality);
+        normalize::rut_opt(&mut self.national_id);
+        normalize::trim_opt(&mut self.address);
+        normalize::trim_opt(&mut self.bio);
+        normalize::trim_opt(&mut self.professional_headline);
+    }
 }
 
 // ============================================================================
@@ -327,6 +379,8 @@ pub struct UserSkill {
     pub skill_id: Uuid,
     pub proficiency_level: i32,
     pub years_of_experience: Option<i32>,
+    /// Endorsed by a third party rather than only self-reported
+    pub is_verified: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -432,10 +486,94 @@ pub struct UpdatePortfolioRequest {
     pub completion_date: Option<NaiveDate>,
 }
 
+// ============================================================================
+// REFERENCES
+// ============================================================================
+
+/// Most references a job seeker can keep on their profile
+pub const MAX_PROFILE_REFERENCES: i64 = 5;
+
+/// Largest recommendation letter accepted, in bytes
+pub const MAX_REFERENCE_LETTER_BYTES: usize = 5 * 1024 * 1024;
+
+/// How long letter download links stay valid
+pub const REFERENCE_LETTER_URL_MINUTES: u64 = 15;
+
+pub fn reference_letters_folder(user_id: Uuid) -> String {
+    format!("users/{}/references", user_id)
+}
+
+/// A referee from a previous employer or training program, optionally with
+/// their recommendation letter
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ProfileReference {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub referee_name: String,
+    pub referee_organization: Option<String>,
+    pub relationship: String,
+    /// Only for the job seeker and their OMIL, never shown to companies
+    pub contact_email: Option<String>,
+    #[serde(skip_serializing)]
+    #[ts(skip)]
+    pub letter_file_key: Option<String>,
+    pub letter_filename: Option<String>,
+    /// The managing OMIL called the referee
+    pub is_verified: bool,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Creates or replaces a reference; the letter is uploaded separately
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ProfileReferenceRequest {
+    #[validate(length(min = 1, max = 200, message = "Referee name is required"))]
+    pub referee_name: String,
+    #[validate(length(max = 200, message = "Organization too long"))]
+    pub referee_organization: Option<String>,
+    #[validate(length(min = 1, max = 100, message = "Relationship is required"))]
+    pub relationship: String,
+    #[validate(email(message = "Invalid email format"))]
+    pub contact_email: Option<String>,
+}
+
+impl Normalize for ProfileReferenceRequest {
+    fn normalize(&mut self) {
+        normalize::trim(&mut self.referee_name);
+        normalize::trim_opt(&mut self.referee_organization);
+        normalize::trim(&mut self.relationship);
+        normalize::email_opt(&mut self.contact_email);
+    }
+}
+
 // ============================================================================
 // COMPOSITE RESPONSE TYPES
 // ============================================================================
 
+/// Sections of the full profile that `fields` can name
+pub const FULL_PROFILE_SECTIONS: &[&str] = &[
+    "profile",
+    "disability",
+    "education",
+    "experience",
+    "skills",
+    "languages",
+    "portfolio",
+];
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct FullProfileQuery {
+    /// Comma-separated sections to return, e.g. "profile,skills"; all when absent
+    pub fields: Option<String>,
+}
+
+/// The whole profile. `fields` drops sections from the serialized response,
+/// the type describes the full one.
 #[derive(Debug, Serialize, TS)]
 #[ts(export, export_to = "../frontend/src/types/")]
 pub struct FullProfileResponse {
@@ -447,3 +585,124 @@ pub struct FullProfileResponse {
     pub languages: Vec<UserLanguage>,
     pub portfolio: Vec<PortfolioItem>,
 }
+
+// ============================================================================
+// CV PARSING
+// ============================================================================
+
+/// How sure the parser is about a suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum SuggestionConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// Why a CV produced no suggestions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum CvParseFailure {
+    /// Only PDFs can be read; Word documents are stored but not parsed
+    UnsupportedFormat,
+    /// The PDF is damaged or encrypted
+    Unreadable,
+    /// The PDF has no text layer, as with scanned or photographed CVs
+    NoText,
+    /// There is text, but nothing in it looked like profile data
+    NothingRecognized,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SuggestedValue {
+    pub value: String,
+    pub confidence: SuggestionConfidence,
+}
+
+/// A work experience found in the CV. Fields the parser could not find are
+/// left empty for the seeker to fill in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct WorkExperienceSuggestion {
+    pub company_name: Option<String>,
+    pub position_title: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub is_current: bool,
+    pub confidence: SuggestionConfidence,
+}
+
+/// An education entry found in the CV
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct EducationSuggestion {
+    pub institution_name: Option<String>,
+    pub degree_title: Option<String>,
+    pub level: Option<EducationLevel>,
+    pub status: Option<EducationStatus>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub confidence: SuggestionConfidence,
+}
+
+/// Profile data suggested from a CV. Nothing here is saved until the seeker
+/// accepts it through apply-suggestions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CvSuggestions {
+    pub phone: Option<SuggestedValue>,
+    pub headline: Option<SuggestedValue>,
+    pub work_experiences: Vec<WorkExperienceSuggestion>,
+    pub education: Vec<EducationSuggestion>,
+    /// Set when there are no suggestions, saying why
+    pub reason: Option<CvParseFailure>,
+}
+
+impl CvSuggestions {
+    pub fn failed(reason: CvParseFailure) -> Self {
+        CvSuggestions {
+            reason: Some(reason),
+            ..Default::default()
+        }
+    }
+}
+
+/// The suggestions the seeker accepted, possibly edited
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplySuggestionsRequest {
+    #[validate(length(max = 20, message = "Phone number too long"))]
+    #[validate(custom(function = "validate_phone"))]
+    pub phone: Option<String>,
+    #[validate(length(max = 200, message = "Professional headline too long"))]
+    pub professional_headline: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub work_experiences: Vec<CreateWorkExperienceRequest>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub education: Vec<CreateEducationRequest>,
+}
+
+/// Entries accepted per section in one apply-suggestions call
+pub const MAX_APPLIED_SUGGESTIONS: usize = 20;
+
+impl Normalize for ApplySuggestionsRequest {
+    fn normalize(&mut self) {
+        normalize::phone_opt(&mut self.phone);
+        normalize::trim_opt(&mut self.professional_headline);
+    }
+}
+
+/// What apply-suggestions saved
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ApplySuggestionsResponse {
+    pub phone: Option<String>,
+    pub professional_headline: Option<String>,
+    pub work_experiences: Vec<WorkExperience>,
+    pub education: Vec<EducationRecord>,
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use ts_rs::TS;
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 /// Country reference data
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
@@ -141,3 +142,148 @@ pub struct SkillWithCategory {
     pub name: String,
     pub is_active: bool,
 }
+
+// ============================================================================
+// REFERENCE DATA SEEDING
+// ============================================================================
+
+/// Reference data to create or update. Rows are matched by natural key:
+/// their name, the name within the category for skills, and the name and
+/// country for institutions. Rows missing from the document are left alone.
+#[derive(Debug, Clone, Default, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct ReferenceSeed {
+    #[serde(default)]
+    #[validate(nested)]
+    pub skill_categories: Vec<SeedSkillCategory>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub languages: Vec<SeedLanguage>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub industries: Vec<SeedListItem>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub work_areas: Vec<SeedListItem>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub institutions: Vec<SeedInstitution>,
+}
+
+/// A skill category with the names of its skills
+#[derive(Debug, Clone, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct SeedSkillCategory {
+    #[validate(length(min = 1, max = 100, message = "Category name must be 1-100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub sort_order: Option<i32>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_skill_names"))]
+    pub skills: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct SeedLanguage {
+    #[validate(length(min = 1, max = 100, message = "Language name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(length(equal = 2, message = "ISO code must be 2 characters"))]
+    pub iso_code: Option<String>,
+}
+
+/// An industry or work area
+#[derive(Debug, Clone, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct SeedListItem {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub sort_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct SeedInstitution {
+    #[validate(length(min = 1, max = 200, message = "Institution name must be 1-200 characters"))]
+    pub name: String,
+    #[validate(length(max = 50, message = "Institution type too long"))]
+    pub institution_type: Option<String>,
+    /// ISO code of a country in the countries table
+    #[validate(length(equal = 2, message = "Country ISO code must be 2 characters"))]
+    pub country_iso: Option<String>,
+}
+
+fn validate_skill_names(names: &[String]) -> Result<(), ValidationError> {
+    if names
+        .iter()
+        .any(|name| name.trim().is_empty() || name.chars().count() > 100)
+    {
+        return Err(ValidationError::new("invalid_skill_name")
+            .with_message("Skill names must be 1-100 characters".into()));
+    }
+    Ok(())
+}
+
+/// What seeding did to one kind of reference data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct SeedCounts {
+    pub created: i32,
+    pub updated: i32,
+    pub unchanged: i32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[ts(export)]
+pub struct ReferenceSeedReport {
+    pub skill_categories: SeedCounts,
+    pub skills: SeedCounts,
+    pub languages: SeedCounts,
+    pub industries: SeedCounts,
+    pub work_areas: SeedCounts,
+    pub institutions: SeedCounts,
+}
+
+// ============================================================================
+// INSTITUTION CURATION
+// ============================================================================
+
+/// Folds duplicate institution entries into a canonical one
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export)]
+pub struct MergeInstitutionsRequest {
+    pub canonical_id: Uuid,
+    #[validate(length(min = 1, max = 50, message = "Merge 1-50 institutions at a time"))]
+    pub duplicate_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct MergeInstitutionsReport {
+    /// Education records moved from a duplicate to the canonical entry
+    pub records_repointed: i64,
+    /// Free-text education records that now match the canonical entry
+    pub records_linked: i64,
+    pub institutions_deactivated: i64,
+}
+
+/// Query parameters for the unmatched institution names report
+#[derive(Debug, Deserialize)]
+pub struct UnmatchedInstitutionsQuery {
+    pub limit: Option<i64>,
+}
+
+/// A free-text institution name that no reference entry matches
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct UnmatchedInstitutionName {
+    /// The most common spelling
+    pub name: String,
+    pub record_count: i64,
+    /// Spellings differing only in case or surrounding spaces
+    pub spelling_count: i64,
+    /// Closest active institution by trigram similarity, if any is close
+    pub suggested_institution_id: Option<Uuid>,
+    pub suggested_institution_name: Option<String>,
+}
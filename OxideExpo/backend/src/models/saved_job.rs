@@ -28,6 +28,12 @@ pub struct SavedJob {
 pub struct SavedJobWithDetails {
     pub saved_job: SavedJob,
     pub job: PublicJobListing,
+    /// Match score when the job was saved
+    pub match_score_at_save: Option<i32>,
+    /// Latest computed match score
+    pub current_match_score: Option<i32>,
+    /// Badge: the match score rose since the job was saved
+    pub score_improved: bool,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
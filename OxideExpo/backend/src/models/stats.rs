@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Platform-wide numbers for the public landing page. Counts under the
+/// aggregation threshold are null.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct PublicPlatformStats {
+    pub active_jobs: Option<i64>,
+    pub active_companies: Option<i64>,
+    /// Rounded to the nearest 10
+    pub registered_job_seekers: Option<i64>,
+    /// People placed since January 1st, through an OMIL or a hired application
+    pub placements_this_year: Option<i64>,
+    /// Active jobs listing at least one disability accommodation
+    pub jobs_with_accommodations: Option<i64>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// How many job seekers are registered in an area, never the exact number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum SeekerCountBucket {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "1-50")]
+    UpTo50,
+    #[serde(rename = "51-200")]
+    UpTo200,
+    #[serde(rename = "200+")]
+    Over200,
+}
+
+impl SeekerCountBucket {
+    pub fn from_count(count: i64) -> Self {
+        match count {
+            i64::MIN..=0 => Self::None,
+            1..=50 => Self::UpTo50,
+            51..=200 => Self::UpTo200,
+            _ => Self::Over200,
+        }
+    }
+}
+
+/// Coverage of one municipality on the landing page map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MunicipalityCoverage {
+    pub municipality_id: Uuid,
+    pub name: String,
+    /// Null for municipalities without known coordinates
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// An approved, active OMIL of this municipality is on the platform
+    pub has_active_omil: bool,
+    /// Active jobs located in the municipality
+    pub active_jobs: i64,
+    /// Job seekers whose profile is in the municipality
+    pub registered_seekers: SeekerCountBucket,
+}
+
+/// Sums over a region or the whole country
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct CoverageTotals {
+    pub municipalities: i64,
+    pub municipalities_with_active_omil: i64,
+    pub active_jobs: i64,
+    pub registered_seekers: SeekerCountBucket,
+}
+
+/// Municipalities of one region, by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RegionCoverage {
+    pub region_id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub totals: CoverageTotals,
+    pub municipalities: Vec<MunicipalityCoverage>,
+}
+
+/// Response of GET /api/stats/omil-coverage, consumed by the public site.
+/// Regions come in their reference order and only active regions and
+/// municipalities are listed. Fields are only ever added to this shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct OmilCoverage {
+    pub regions: Vec<RegionCoverage>,
+    pub totals: CoverageTotals,
+    pub generated_at: DateTime<Utc>,
+}
@@ -5,6 +5,9 @@ use ts_rs::TS;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::utils::normalize::{self, Normalize};
+use crate::utils::rut::validate_rut;
+
 // ============================================================================
 // ENUMS (matching PostgreSQL enums from 0002_create_enums.sql)
 // ============================================================================
@@ -29,6 +32,17 @@ pub enum AccountStatus {
     Active,
     Suspended,
     Deactivated,
+    /// An admin forced a password reset; login is blocked until it is done
+    PasswordResetRequired,
+    /// A job seeker an OMIL registered without an email; login is blocked
+    /// until the person claims the account
+    ManagedOffline,
+    /// The user asked to delete the account; it is hidden until it is
+    /// restored or scrubbed at the end of the grace period
+    PendingDeletion,
+    /// A job seeker an OMIL registered with an email; login is blocked until
+    /// the person sets a first password from the advisor's invite
+    Invited,
 }
 
 // ============================================================================
@@ -42,7 +56,7 @@ pub struct User {
     pub email: String,
     #[serde(skip_serializing)]
     #[ts(skip)]
-    pub password_hash: String,
+    pub password_hash: Option<String>,
     pub first_name: String,
     pub last_name: String,
     pub user_type: UserType,
@@ -50,6 +64,9 @@ pub struct User {
     pub email_verified_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing)]
+    #[ts(skip)]
+    pub token_version: i32,
 }
 
 impl User {
@@ -109,6 +126,13 @@ pub struct RegisterJobSeekerRequest {
     pub first_name: String,
     #[validate(length(min = 1, max = 100, message = "Last name is required"))]
     pub last_name: String,
+    /// Links the registration to an account an OMIL has been managing
+    /// offline for the same person
+    #[validate(length(max = 50, message = "National ID too long"))]
+    #[validate(custom(function = "validate_rut"))]
+    pub national_id: Option<String>,
+    /// Bot check for the active `CaptchaMode`, see GET /api/auth/challenge
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -124,6 +148,8 @@ pub struct RegisterCompanyRequest {
     pub last_name: String,
     #[validate(length(min = 1, message = "Company name is required"))]
     pub company_name: String,
+    /// Bot check for the active `CaptchaMode`, see GET /api/auth/challenge
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -139,6 +165,8 @@ pub struct RegisterOmilRequest {
     pub last_name: String,
     #[validate(length(min = 1, max = 255, message = "Organization name is required"))]
     pub organization_name: String,
+    /// Bot check for the active `CaptchaMode`, see GET /api/auth/challenge
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -162,6 +190,8 @@ pub struct RefreshRequest {
 pub struct ForgotPasswordRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
+    /// Bot check for the active `CaptchaMode`, see GET /api/auth/challenge
+    pub captcha_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, TS)]
@@ -187,6 +217,61 @@ pub struct ResendVerificationRequest {
     pub email: String,
 }
 
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RequestEmailChangeRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub new_email: String,
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub current_password: String,
+}
+
+/// Token from either link of an email change: confirm or cancel
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct EmailChangeTokenRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+}
+
+/// Takes over an account an OMIL managed offline, from the emailed invitation
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct ClaimAccountRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    pub password: String,
+}
+
+/// Sets the first password of an account an OMIL registered, from the
+/// emailed setup link
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct SetupPasswordRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub current_password: String,
+}
+
+/// Restores an account in its deletion grace period, from the emailed link or
+/// by the signed-in user confirming
+#[derive(Debug, Deserialize, Validate, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct RestoreAccountRequest {
+    /// Token from the email; without it the caller must be signed in
+    #[validate(length(min = 1, message = "Token must not be empty"))]
+    pub token: Option<String>,
+}
+
 // ============================================================================
 // RESPONSE DTOs
 // ============================================================================
@@ -216,6 +301,35 @@ pub struct MessageResponse {
     pub message: String,
 }
 
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct EmailChangeRequestedResponse {
+    pub new_email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The signed-in user, with the pending deletion if there is one
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct MeResponse {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub user: UserResponse,
+    /// When the account is scrubbed unless it is restored first
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
+}
+
+/// Deletion scheduled; every other session was signed out and this one
+/// continues with the new tokens
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AccountDeletionScheduledResponse {
+    pub deletion_scheduled_at: DateTime<Utc>,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub tokens: TokenResponse,
+}
+
 impl MessageResponse {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
@@ -223,3 +337,86 @@ impl MessageResponse {
         }
     }
 }
+
+// ============================================================================
+// ABUSE PROTECTION
+// ============================================================================
+
+/// Check that registration and password reset requests must pass, from
+/// `CAPTCHA_MODE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum CaptchaMode {
+    Disabled,
+    /// hCaptcha widget token verified server-side
+    Hcaptcha,
+    /// Cloudflare Turnstile widget token verified server-side
+    Turnstile,
+    /// Nonce for a challenge from GET /api/auth/challenge, for environments
+    /// without a captcha provider
+    ProofOfWork,
+}
+
+impl std::str::FromStr for CaptchaMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "disabled" | "none" => Ok(Self::Disabled),
+            "hcaptcha" => Ok(Self::Hcaptcha),
+            "turnstile" => Ok(Self::Turnstile),
+            "pow" | "proof_of_work" => Ok(Self::ProofOfWork),
+            _ => Err(()),
+        }
+    }
+}
+
+/// GET /api/auth/challenge: what the client must send as `captcha_token`.
+/// In proof-of-work mode the token is `{challenge}:{nonce}` for any nonce
+/// whose SHA-256 of the whole token starts with `difficulty` zero bits.
+#[derive(Debug, Serialize, TS)]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub struct AuthChallenge {
+    pub mode: CaptchaMode,
+    pub challenge: Option<String>,
+    pub difficulty: Option<u32>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// NORMALIZATION
+// ============================================================================
+
+impl Normalize for RegisterJobSeekerRequest {
+    fn normalize(&mut self) {
+        normalize::email(&mut self.email);
+        normalize::trim(&mut self.first_name);
+        normalize::trim(&mut self.last_name);
+        normalize::rut_opt(&mut self.national_id);
+    }
+}
+
+impl Normalize for RequestEmailChangeRequest {
+    fn normalize(&mut self) {
+        normalize::email(&mut self.new_email);
+    }
+}
+
+impl Normalize for RegisterCompanyRequest {
+    fn normalize(&mut self) {
+        normalize::email(&mut self.email);
+        normalize::trim(&mut self.first_name);
+        normalize::trim(&mut self.last_name);
+        normalize::trim(&mut self.company_name);
+    }
+}
+
+impl Normalize for RegisterOmilRequest {
+    fn normalize(&mut self) {
+        normalize::email(&mut self.email);
+        normalize::trim(&mut self.first_name);
+        normalize::trim(&mut self.last_name);
+        normalize::trim(&mut self.organization_name);
+    }
+}
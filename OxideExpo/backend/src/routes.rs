@@ -0,0 +1,1285 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, patch, post, put},
+    Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::{
+    handlers::{self, auth, profile},
+    models::{
+        application::MAX_APPLICATION_DOCUMENT_BYTES, profile::MAX_REFERENCE_LETTER_BYTES,
+    },
+    middleware::{
+        compression_layer, enforce_maintenance_mode, optional_auth, require_admin, require_auth, require_omil,
+        require_omil_coordinator_or_above, require_omil_director, skip_compression, trace_layer,
+        with_overload_protection,
+    },
+    AppState,
+};
+
+/// Request body limit for document uploads, leaving room for the multipart framing
+const DOCUMENT_UPLOAD_BODY_LIMIT: usize = MAX_APPLICATION_DOCUMENT_BYTES + 64 * 1024;
+
+/// Request body limit for recommendation letters, with the same margin
+const REFERENCE_LETTER_BODY_LIMIT: usize = MAX_REFERENCE_LETTER_BYTES + 64 * 1024;
+
+/// Builds the full API router. Shared by the server binary and the integration tests.
+pub fn create_router(app_state: AppState) -> Router {
+    // Reference data routes (public)
+    let reference_routes = Router::new()
+        .route("/api/reference/countries", get(handlers::list_countries))
+        .route("/api/reference/regions", get(handlers::list_regions))
+        .route(
+            "/api/reference/municipalities",
+            get(handlers::list_municipalities),
+        )
+        .route("/api/reference/industries", get(handlers::list_industries))
+        .route("/api/reference/work-areas", get(handlers::list_work_areas))
+        .route(
+            "/api/reference/position-levels",
+            get(handlers::list_position_levels),
+        )
+        .route(
+            "/api/reference/career-fields",
+            get(handlers::list_career_fields),
+        )
+        .route(
+            "/api/reference/institutions",
+            get(handlers::list_institutions),
+        )
+        .route("/api/reference/languages", get(handlers::list_languages))
+        .route(
+            "/api/reference/skill-categories",
+            get(handlers::list_skill_categories),
+        )
+        .route("/api/reference/skills", get(handlers::list_skills));
+
+    // Auth routes (public)
+    let auth_public_routes = Router::new()
+        // Registration
+        .route("/api/auth/challenge", get(auth::get_challenge))
+        .route("/api/auth/register", post(auth::register_job_seeker))
+        .route("/api/auth/register/company", post(auth::register_company))
+        .route("/api/auth/register/omil", post(auth::register_omil))
+        // Taking over an account an OMIL managed offline
+        .route("/api/auth/claim-account", post(auth::claim_account))
+        // First password for an account an OMIL registered with an email
+        .route("/api/auth/setup-password", post(auth::setup_password))
+        // Login/Token
+        .route("/api/auth/login", post(auth::login))
+        .route("/api/auth/refresh", post(auth::refresh))
+        // Password reset
+        .route("/api/auth/password/forgot", post(auth::forgot_password))
+        .route("/api/auth/password/reset", post(auth::reset_password))
+        // Email verification
+        .route("/api/auth/email/verify", post(auth::verify_email))
+        .route("/api/auth/email/resend", post(auth::resend_verification))
+        // Email change
+        .route(
+            "/api/auth/email/confirm-change",
+            post(auth::confirm_email_change),
+        )
+        .route(
+            "/api/auth/email/cancel-change",
+            post(auth::cancel_email_change),
+        );
+
+    // Auth routes (protected - require valid JWT)
+    let auth_protected_routes = Router::new()
+        .route("/api/auth/me", get(auth::me))
+        .route("/api/auth/logout", post(auth::logout))
+        .route(
+            "/api/me/email/change-request",
+            post(auth::request_email_change),
+        )
+        .route("/api/me/account", delete(auth::delete_account))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // Auth routes (token or signed in)
+    let auth_optional_routes = Router::new()
+        .route("/api/auth/account/restore", post(auth::restore_account))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            optional_auth,
+        ));
+
+    // V3: Job Seeker Profile routes (protected)
+    let profile_routes = Router::new()
+        // Profile basics
+        .route("/api/me/profile", get(profile::get_profile).put(profile::update_profile))
+        .route("/api/me/profile/full", get(profile::get_full_profile))
+        .route("/api/me/profile/apply-suggestions", post(profile::apply_suggestions))
+        // Onboarding checklist
+        .route("/api/me/onboarding", get(handlers::onboarding::get_onboarding))
+        .route(
+            "/api/me/onboarding/{step}/dismiss",
+            post(handlers::onboarding::dismiss_onboarding_step),
+        )
+        // Disability info
+        .route("/api/me/disability", get(profile::get_disability).put(profile::update_disability))
+        // Education
+        .route("/api/me/education", get(profile::list_education).post(profile::create_education))
+        .route("/api/me/education/{id}", put(profile::update_education).delete(profile::delete_education))
+        // Work experience
+        .route("/api/me/experience", get(profile::list_experiences).post(profile::create_experience))
+        .route("/api/me/experience/{id}", put(profile::update_experience).delete(profile::delete_experience))
+        // Skills
+        .route("/api/me/skills", get(profile::list_skills).post(profile::create_skill))
+        .route("/api/me/skills/{id}", put(profile::update_skill).delete(profile::delete_skill))
+        // Languages
+        .route("/api/me/languages", get(profile::list_languages).post(profile::create_language))
+        .route("/api/me/languages/{id}", put(profile::update_language).delete(profile::delete_language))
+        // Portfolio
+        .route("/api/me/portfolio", get(profile::list_portfolio).post(profile::create_portfolio))
+        .route("/api/me/portfolio/{id}", put(profile::update_portfolio).delete(profile::delete_portfolio))
+        // References
+        .route("/api/me/references", get(profile::list_references).post(profile::create_reference))
+        .route("/api/me/references/{id}", put(profile::update_reference).delete(profile::delete_reference))
+        .route(
+            "/api/me/references/{id}/letter",
+            post(profile::upload_reference_letter)
+                .delete(profile::delete_reference_letter)
+                .layer(DefaultBodyLimit::max(REFERENCE_LETTER_BODY_LIMIT)),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V4: Company Profile routes (protected)
+    let company_routes = Router::new()
+        .route("/api/me/company", delete(handlers::company::delete_company))
+        .route(
+            "/api/me/company/profile",
+            get(handlers::company::get_company_profile)
+                .put(handlers::company::update_company_profile),
+        )
+        .route(
+            "/api/me/company/full",
+            get(handlers::company::get_full_company_profile),
+        )
+        .route(
+            "/api/me/company/members",
+            get(handlers::company::list_members),
+        )
+        .route(
+            "/api/me/company/members/{id}",
+            put(handlers::company::update_member).delete(handlers::company::remove_member),
+        )
+        // V12: Company Dashboard
+        .route(
+            "/api/me/company/dashboard",
+            get(handlers::company::get_company_dashboard),
+        )
+        // V13: Company Benchmarks
+        .route(
+            "/api/me/company/benchmarks",
+            get(handlers::company::get_company_benchmarks),
+        )
+        .route(
+            "/api/me/company/usage",
+            get(handlers::company::get_company_usage),
+        )
+        .route(
+            "/api/me/company/settings",
+            get(handlers::company::get_company_settings)
+                .put(handlers::company::update_company_settings),
+        )
+        // V13: Company data exports
+        .route(
+            "/api/me/company/data-export",
+            post(handlers::company::request_data_export),
+        )
+        .route(
+            "/api/me/company/data-export/{id}",
+            get(handlers::company::get_data_export),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V4: Public company routes (no auth required)
+    let company_public_routes = Router::new()
+        .route("/api/companies", get(handlers::company::list_public_companies))
+        .route(
+            "/api/companies/{id}",
+            get(handlers::company::get_public_company),
+        );
+
+    // V5: Job Management routes (protected - company members)
+    let job_routes = Router::new()
+        .route(
+            "/api/me/jobs",
+            get(handlers::jobs::list_company_jobs).post(handlers::jobs::create_job),
+        )
+        .route(
+            "/api/me/jobs/{id}",
+            get(handlers::jobs::get_job_with_applications)
+                .put(handlers::jobs::update_job)
+                .delete(handlers::jobs::delete_job),
+        )
+        .route(
+            "/api/me/jobs/{id}/status",
+            patch(handlers::jobs::update_job_status),
+        )
+        .route(
+            "/api/me/jobs/{id}/deadline",
+            patch(handlers::jobs::update_job_deadline),
+        )
+        .route(
+            "/api/me/jobs/{id}/completeness",
+            get(handlers::jobs::get_job_completeness),
+        )
+        .route(
+            "/api/me/jobs/{id}/applications",
+            get(handlers::jobs::list_job_applications),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applications/{app_id}",
+            put(handlers::jobs::update_application_status),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applications/{app_id}/interview",
+            put(handlers::jobs::reschedule_interview),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applications/{app_id}/notes",
+            post(handlers::jobs::add_application_note),
+        )
+        // V13: Job activity feed
+        .route(
+            "/api/me/jobs/{id}/activity",
+            get(handlers::jobs::get_job_activity),
+        )
+        .route(
+            "/api/me/jobs/{id}/activity/seen",
+            post(handlers::jobs::mark_job_activity_seen),
+        )
+        // V13: Funnel automation rules
+        .route(
+            "/api/me/jobs/{id}/automation-rules",
+            get(handlers::jobs::list_automation_rules)
+                .post(handlers::jobs::create_automation_rule),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/automation-rules/{rule_id}",
+            put(handlers::jobs::update_automation_rule)
+                .delete(handlers::jobs::delete_automation_rule),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V5: Application routes (protected - job seekers)
+    let application_routes = Router::new()
+        .route(
+            "/api/me/applications",
+            get(handlers::applications::list_my_applications)
+                .post(handlers::applications::submit_application),
+        )
+        .route(
+            "/api/me/applications/{id}",
+            get(handlers::applications::get_application),
+        )
+        .route(
+            "/api/me/applications/{id}/withdraw",
+            patch(handlers::applications::withdraw_application),
+        )
+        .route(
+            "/api/me/applications/{id}/timeline",
+            get(handlers::applications::get_application_timeline),
+        )
+        .route(
+            "/api/me/applications/{id}/documents",
+            get(handlers::applications::list_application_documents)
+                .post(handlers::applications::upload_application_document)
+                .layer(DefaultBodyLimit::max(DOCUMENT_UPLOAD_BODY_LIMIT)),
+        )
+        .route(
+            "/api/me/applications/{id}/documents/{document_id}",
+            delete(handlers::applications::delete_application_document),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/application-draft",
+            get(handlers::applications::get_application_draft)
+                .put(handlers::applications::save_application_draft)
+                .delete(handlers::applications::delete_application_draft),
+        )
+        .route(
+            "/api/me/cover-letter-templates",
+            get(handlers::applications::list_cover_letter_templates)
+                .post(handlers::applications::create_cover_letter_template),
+        )
+        .route(
+            "/api/me/cover-letter-templates/{id}",
+            put(handlers::applications::update_cover_letter_template)
+                .delete(handlers::applications::delete_cover_letter_template),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V5: Public job listings (no auth)
+    let job_public_routes = Router::new()
+        .route("/api/jobs", get(handlers::applications::list_public_jobs))
+        .route("/api/jobs/{id}", get(handlers::applications::get_public_job))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            optional_auth,
+        ));
+
+    // V6: Admin dashboard routes (protected - admin only)
+    let admin_routes = Router::new()
+        // Dashboard & analytics
+        .route(
+            "/api/admin/dashboard/stats",
+            get(handlers::admin::get_dashboard_stats),
+        )
+        .route("/api/admin/events", get(handlers::admin::stream_admin_events))
+        // Company management (super admin only)
+        .route(
+            "/api/admin/companies/pending",
+            get(handlers::admin::list_pending_companies),
+        )
+        .route(
+            "/api/admin/companies/orphaned",
+            get(handlers::admin::list_orphaned_companies),
+        )
+        .route(
+            "/api/admin/companies/orphaned/dismiss",
+            post(handlers::admin::dismiss_orphaned_companies),
+        )
+        .route(
+            "/api/admin/companies/{id}/approve",
+            patch(handlers::admin::approve_company),
+        )
+        .route(
+            "/api/admin/companies/{id}/reject",
+            patch(handlers::admin::reject_company),
+        )
+        .route(
+            "/api/admin/companies/{id}",
+            get(handlers::admin::get_company_detail).delete(handlers::admin::delete_company),
+        )
+        .route(
+            "/api/admin/companies/{id}/reopen",
+            post(handlers::admin::reopen_company),
+        )
+        .route(
+            "/api/admin/companies/{id}/pending-changes",
+            get(handlers::admin::list_company_pending_changes),
+        )
+        .route(
+            "/api/admin/companies/{id}/pending-changes/{change_id}/approve",
+            patch(handlers::admin::approve_company_profile_change),
+        )
+        .route(
+            "/api/admin/companies/{id}/pending-changes/{change_id}/reject",
+            patch(handlers::admin::reject_company_profile_change),
+        )
+        // Job moderation (moderator or above)
+        .route(
+            "/api/admin/jobs/pending",
+            get(handlers::admin::list_pending_jobs),
+        )
+        .route(
+            "/api/admin/jobs/{id}/review",
+            get(handlers::admin::get_job_review),
+        )
+        .route(
+            "/api/admin/jobs/{id}/approve",
+            patch(handlers::admin::approve_job),
+        )
+        .route(
+            "/api/admin/jobs/{id}/reject",
+            patch(handlers::admin::reject_job),
+        )
+        .route(
+            "/api/admin/jobs/{id}/transfer",
+            post(handlers::admin::transfer_job),
+        )
+        // V13: Content screening rules
+        .route(
+            "/api/admin/moderation-rules",
+            get(handlers::admin::list_moderation_rules)
+                .post(handlers::admin::create_moderation_rule),
+        )
+        .route(
+            "/api/admin/moderation-rules/{id}",
+            put(handlers::admin::update_moderation_rule)
+                .delete(handlers::admin::delete_moderation_rule),
+        )
+        // OMIL followup type taxonomy
+        .route(
+            "/api/admin/reference/followup-types",
+            get(handlers::admin::list_followup_types).post(handlers::admin::create_followup_type),
+        )
+        .route(
+            "/api/admin/reference/followup-types/{code}",
+            put(handlers::admin::update_followup_type)
+                .delete(handlers::admin::delete_followup_type),
+        )
+        .route(
+            "/api/admin/reference/seed",
+            post(handlers::admin::seed_reference_data),
+        )
+        .route(
+            "/api/admin/reference/institutions/merge",
+            post(handlers::admin::merge_institutions),
+        )
+        .route(
+            "/api/admin/reference/institutions/unmatched",
+            get(handlers::admin::list_unmatched_institutions),
+        )
+        // V11: User management
+        .route("/api/admin/users", get(handlers::admin::list_users))
+        .route(
+            "/api/admin/users/duplicates",
+            get(handlers::admin::list_duplicate_users),
+        )
+        .route(
+            "/api/admin/users/merge",
+            post(handlers::admin::merge_users),
+        )
+        .route(
+            "/api/admin/users/{id}",
+            get(handlers::admin::get_user_detail),
+        )
+        .route(
+            "/api/admin/users/{id}/status",
+            patch(handlers::admin::update_user_status),
+        )
+        .route(
+            "/api/admin/users/{id}/impersonate",
+            get(handlers::admin::impersonate_user),
+        )
+        .route(
+            "/api/admin/users/{id}/force-password-reset",
+            post(handlers::admin::force_password_reset),
+        )
+        .route(
+            "/api/admin/users/{id}/resend-verification",
+            post(handlers::admin::admin_resend_verification),
+        )
+        // V11: OMIL approvals
+        .route(
+            "/api/admin/omils/pending",
+            get(handlers::admin::list_pending_omils),
+        )
+        .route(
+            "/api/admin/omils/{id}/approve",
+            patch(handlers::admin::approve_omil),
+        )
+        .route(
+            "/api/admin/omils/{id}/reject",
+            patch(handlers::admin::reject_omil),
+        )
+        // V11: Audit log viewer
+        .route(
+            "/api/admin/audit-logs",
+            get(handlers::admin::list_audit_logs),
+        )
+        // V13: Domain event log
+        .route(
+            "/api/admin/domain-events",
+            get(handlers::admin::list_domain_events),
+        )
+        // V13: Export audit and contact export restrictions
+        .route("/api/admin/exports", get(handlers::admin::list_exports))
+        .route(
+            "/api/admin/companies/{id}/export-settings",
+            put(handlers::admin::update_company_export_settings),
+        )
+        // V11: System settings
+        .route(
+            "/api/admin/settings",
+            get(handlers::admin::get_settings).put(handlers::admin::update_settings),
+        )
+        .route(
+            "/api/admin/settings/{key}/history",
+            get(handlers::admin::get_setting_history),
+        )
+        // V13: Data retention
+        .route(
+            "/api/admin/retention/policies",
+            get(handlers::admin::list_retention_policies),
+        )
+        .route(
+            "/api/admin/retention/policies/{id}",
+            put(handlers::admin::update_retention_policy),
+        )
+        .route(
+            "/api/admin/retention/runs",
+            get(handlers::admin::list_retention_runs).post(handlers::admin::run_retention),
+        )
+        .route(
+            "/api/admin/stale-jobs/runs",
+            get(handlers::admin::list_stale_job_runs),
+        )
+        .route(
+            "/api/admin/maintenance/recount-applications",
+            post(handlers::admin::recount_applications),
+        )
+        // V13: Out-of-band data migrations
+        .route("/api/admin/backfills", get(handlers::admin::list_backfills))
+        .route(
+            "/api/admin/backfills/{name}/start",
+            post(handlers::admin::start_backfill),
+        )
+        .route(
+            "/api/admin/backfills/{name}/pause",
+            post(handlers::admin::pause_backfill),
+        )
+        // V13: JWT key rotation
+        .route(
+            "/api/admin/security/jwt-keys",
+            get(handlers::admin::list_jwt_keys),
+        )
+        // V12: Reporting
+        .route(
+            "/api/admin/reports/users",
+            get(handlers::admin::report_users),
+        )
+        .route(
+            "/api/admin/reports/companies",
+            get(handlers::admin::report_companies),
+        )
+        .route(
+            "/api/admin/reports/jobs",
+            get(handlers::admin::report_jobs),
+        )
+        .route(
+            "/api/admin/reports/applications",
+            get(handlers::admin::report_applications),
+        )
+        .route(
+            "/api/admin/reports/inclusion",
+            get(handlers::admin::report_inclusion),
+        )
+        .route(
+            "/api/admin/reports/moderation",
+            get(handlers::admin::report_moderation),
+        )
+        .route(
+            "/api/admin/reports/retention",
+            get(handlers::admin::report_cohort_retention),
+        )
+        .route(
+            "/api/admin/reports/export/{report_type}",
+            get(handlers::admin::export_report).layer(middleware::map_response(skip_compression)),
+        )
+        // Internal notes
+        .route(
+            "/api/admin/notes",
+            get(handlers::admin::list_notes).post(handlers::admin::create_note),
+        )
+        .route(
+            "/api/admin/notes/mentions",
+            get(handlers::admin::list_note_mentions),
+        )
+        .route(
+            "/api/admin/notes/{id}",
+            patch(handlers::admin::update_note).delete(handlers::admin::delete_note),
+        )
+        .route(
+            "/api/admin/notes/{id}/read",
+            post(handlers::admin::mark_note_mention_read),
+        )
+        .route("/api/admin/usage", get(handlers::admin::get_usage_report))
+        .route(
+            "/api/admin/usage/export",
+            get(handlers::admin::export_usage_report)
+                .layer(middleware::map_response(skip_compression)),
+        )
+        // Require authentication first, then admin privileges
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_admin,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V7: Matching routes (protected - job seekers)
+    let matching_routes = Router::new()
+        .route(
+            "/api/me/recommended-jobs",
+            get(handlers::matching::get_recommended_jobs),
+        )
+        .route(
+            "/api/me/preferences",
+            get(handlers::matching::get_preferences).put(handlers::matching::update_preferences),
+        )
+        .route("/api/me/skill-gap", get(handlers::matching::get_skill_gap))
+        .route(
+            "/api/me/jobs/{id}/recommended-candidates",
+            get(handlers::matching::get_recommended_candidates),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V7: Job match score route (protected - job seekers)
+    let match_score_routes = Router::new()
+        .route(
+            "/api/jobs/{id}/match-score",
+            get(handlers::matching::get_job_match_score),
+        )
+        .route(
+            "/api/jobs/{id}/match-score/tips",
+            get(handlers::matching::get_job_match_tips),
+        )
+        .route(
+            "/api/jobs/{id}/match-score/history",
+            get(handlers::matching::get_job_match_score_history),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V8: OMIL routes (protected - any OMIL member)
+    let omil_routes = Router::new()
+        // Organization management
+        .route(
+            "/api/me/omil",
+            get(handlers::omil::get_omil_organization),
+        )
+        .route(
+            "/api/me/omil/stats",
+            get(handlers::omil::get_omil_stats),
+        )
+        .route(
+            "/api/me/omil/stats/trends",
+            get(handlers::omil::get_omil_stats_trends),
+        )
+        .route(
+            "/api/me/omil/companies/insights",
+            get(handlers::omil::get_company_insights),
+        )
+        // Member listing (any member can view)
+        .route(
+            "/api/me/omil/members",
+            get(handlers::omil::list_omil_members),
+        )
+        .route(
+            "/api/me/omil/branches",
+            get(handlers::omil::list_branches),
+        )
+        // Managed job seekers
+        .route(
+            "/api/me/omil/job-seekers",
+            get(handlers::omil::list_managed_job_seekers)
+                .post(handlers::omil::register_job_seeker_on_behalf),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}",
+            get(handlers::omil::get_managed_job_seeker),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/placement",
+            put(handlers::omil::update_placement),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/deactivate",
+            post(handlers::omil::deactivate_managed_job_seeker),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/apply",
+            post(handlers::omil::apply_on_behalf),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/cover-letter-templates",
+            get(handlers::omil::list_managed_cover_letter_templates),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/claim-invite",
+            post(handlers::omil::send_claim_invite),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/send-credentials-invite",
+            post(handlers::omil::send_credentials_invite),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/applications/{app_id}/documents",
+            post(handlers::omil::attach_document_on_behalf)
+                .layer(DefaultBodyLimit::max(DOCUMENT_UPLOAD_BODY_LIMIT)),
+        )
+        // Followups
+        .route(
+            "/api/me/omil/job-seekers/{id}/followups",
+            get(handlers::omil::list_followups).post(handlers::omil::create_followup),
+        )
+        .route(
+            "/api/me/omil/followups/{id}",
+            put(handlers::omil::update_followup).delete(handlers::omil::delete_followup),
+        )
+        .route(
+            "/api/me/omil/followup-types",
+            get(handlers::omil::list_followup_types),
+        )
+        // V10: Impersonation
+        .route(
+            "/api/me/omil/job-seekers/{id}/impersonate",
+            get(handlers::omil::generate_impersonation),
+        )
+        // V10: Export managed seekers
+        .route(
+            "/api/me/omil/job-seekers/export",
+            get(handlers::omil::export_managed_seekers).layer(middleware::map_response(skip_compression)),
+        )
+        .route(
+            "/api/me/omil/export-settings",
+            get(handlers::omil::get_export_settings).put(handlers::omil::update_export_settings),
+        )
+        .route(
+            "/api/me/omil/approval-settings",
+            get(handlers::omil::get_approval_settings).put(handlers::omil::update_approval_settings),
+        )
+        // V10: List all OMIL applications
+        .route(
+            "/api/me/omil/applications",
+            get(handlers::omil::list_omil_applications),
+        )
+        // V13: Transfers
+        .route(
+            "/api/me/omil/transfers",
+            get(handlers::omil::list_transfers),
+        )
+        // V13: Skill endorsements
+        .route(
+            "/api/me/omil/job-seekers/{id}/skills/{user_skill_id}/endorse",
+            post(handlers::omil::endorse_skill).delete(handlers::omil::remove_skill_endorsement),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/references",
+            get(handlers::omil::list_managed_references),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/references/{reference_id}/verify",
+            post(handlers::omil::verify_reference),
+        )
+        // V13: Referral requests from companies
+        .route(
+            "/api/me/omil/referral-requests",
+            get(handlers::referrals::list_omil_referral_requests),
+        )
+        .route(
+            "/api/me/omil/referral-requests/{id}",
+            get(handlers::referrals::get_omil_referral_request),
+        )
+        .route(
+            "/api/me/omil/referral-requests/{id}/candidates",
+            get(handlers::referrals::list_referral_candidates),
+        )
+        .route(
+            "/api/me/omil/partner-jobs/{id}/applicants",
+            get(handlers::applicants::list_partner_applicants),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_omil,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V8: OMIL coordinator+ routes (protected - coordinator or director only)
+    let omil_coordinator_routes = Router::new()
+        .route(
+            "/api/me/omil/members",
+            post(handlers::omil::add_omil_member),
+        )
+        .route(
+            "/api/me/omil/members/{id}",
+            put(handlers::omil::update_omil_member),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/advisor",
+            put(handlers::omil::assign_advisor),
+        )
+        .route(
+            "/api/me/omil/members/{id}/branch",
+            put(handlers::omil::assign_member_branch),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/branch",
+            put(handlers::omil::assign_job_seeker_branch),
+        )
+        // V13: Export templates
+        .route(
+            "/api/me/omil/export-templates",
+            get(handlers::omil::list_export_templates).post(handlers::omil::create_export_template),
+        )
+        .route(
+            "/api/me/omil/export-templates/columns",
+            get(handlers::omil::list_export_columns),
+        )
+        .route(
+            "/api/me/omil/export-templates/{id}",
+            get(handlers::omil::get_export_template)
+                .put(handlers::omil::update_export_template)
+                .delete(handlers::omil::delete_export_template),
+        )
+        // Kiosk sessions for shared devices
+        .route(
+            "/api/me/omil/pending-actions",
+            get(handlers::omil::list_pending_actions),
+        )
+        .route(
+            "/api/me/omil/pending-actions/{id}/approve",
+            post(handlers::omil::approve_pending_action),
+        )
+        .route(
+            "/api/me/omil/pending-actions/{id}/reject",
+            post(handlers::omil::reject_pending_action),
+        )
+        .route(
+            "/api/me/omil/kiosk-sessions",
+            get(handlers::omil::list_kiosk_sessions).post(handlers::omil::create_kiosk_session),
+        )
+        .route(
+            "/api/me/omil/kiosk-sessions/{id}",
+            delete(handlers::omil::revoke_kiosk_session),
+        )
+        // SENCE monthly report
+        .route(
+            "/api/me/omil/reports/monthly",
+            get(handlers::omil::get_monthly_report),
+        )
+        .route(
+            "/api/me/omil/reports/monthly/export",
+            get(handlers::omil::export_monthly_report).layer(middleware::map_response(skip_compression)),
+        )
+        // V13: Answering referral requests
+        .route(
+            "/api/me/omil/referral-requests/{id}/accept",
+            post(handlers::referrals::accept_referral_request),
+        )
+        .route(
+            "/api/me/omil/referral-requests/{id}/decline",
+            post(handlers::referrals::decline_referral_request),
+        )
+        .route(
+            "/api/me/omil/referral-requests/{id}/withdraw",
+            post(handlers::referrals::withdraw_referral_request),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_omil_coordinator_or_above,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V8: OMIL director routes (protected - director only)
+    let omil_director_routes = Router::new()
+        .route(
+            "/api/me/omil",
+            put(handlers::omil::update_omil_organization),
+        )
+        .route(
+            "/api/me/omil/members/{id}",
+            delete(handlers::omil::remove_omil_member),
+        )
+        .route(
+            "/api/me/omil/branches",
+            post(handlers::omil::create_branch),
+        )
+        .route(
+            "/api/me/omil/branches/{id}",
+            put(handlers::omil::update_branch).delete(handlers::omil::delete_branch),
+        )
+        .route(
+            "/api/me/omil/job-seekers/{id}/transfer",
+            post(handlers::omil::request_transfer),
+        )
+        .route(
+            "/api/me/omil/transfers/{id}/accept",
+            post(handlers::omil::accept_transfer),
+        )
+        .route(
+            "/api/me/omil/transfers/{id}/decline",
+            post(handlers::omil::decline_transfer),
+        )
+        .route(
+            "/api/me/omil/transfers/{id}/cancel",
+            post(handlers::omil::cancel_transfer),
+        )
+        // V13: Export audit and contact export restrictions
+        .route("/api/me/omil/exports", get(handlers::omil::list_exports))
+        // V13: Logo for branded exports
+        .route(
+            "/api/me/omil/logo",
+            put(handlers::files::upload_omil_logo).delete(handlers::files::delete_omil_logo),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_omil_director,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V8: Job invitation routes - company side (protected - company members)
+    let invitation_company_routes = Router::new()
+        .route(
+            "/api/me/jobs/{job_id}/invitations",
+            get(handlers::invitations::list_job_invitations)
+                .post(handlers::invitations::send_job_invitation),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: OMIL directory and referral requests - company side (protected - company members)
+    let referral_company_routes = Router::new()
+        .route("/api/omils", get(handlers::referrals::list_omil_directory))
+        .route(
+            "/api/me/company/referral-requests",
+            get(handlers::referrals::list_company_referral_requests)
+                .post(handlers::referrals::create_referral_request),
+        )
+        .route(
+            "/api/me/company/referral-requests/{id}",
+            get(handlers::referrals::get_company_referral_request),
+        )
+        // V13: Co-published jobs
+        .route(
+            "/api/me/jobs/{id}/partner-omil",
+            put(handlers::referrals::set_job_partner),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V8: Job invitation routes - job seeker side (protected - job seekers)
+    let invitation_seeker_routes = Router::new()
+        .route(
+            "/api/me/invitations",
+            get(handlers::invitations::list_my_invitations),
+        )
+        .route(
+            "/api/me/invitations/{id}",
+            get(handlers::invitations::get_invitation),
+        )
+        .route(
+            "/api/me/invitations/{id}/respond",
+            post(handlers::invitations::respond_to_invitation),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V9: Enhanced Applicant Management routes (protected - company members)
+    let applicant_routes = Router::new()
+        .route(
+            "/api/me/jobs/{id}/applicants",
+            get(handlers::applicants::list_applicants),
+        )
+        .route(
+            "/api/me/jobs/{id}/applicants/compare",
+            get(handlers::applicants::compare_applicants),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applicants/{app_id}/detail",
+            get(handlers::applicants::get_applicant_detail),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applicants/{app_id}/cv",
+            get(handlers::applicants::get_applicant_cv),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applicants/{app_id}/history",
+            get(handlers::applicants::get_applicant_history),
+        )
+        .route(
+            "/api/me/jobs/{id}/applicants/bulk-status",
+            post(handlers::applicants::bulk_status_update),
+        )
+        .route(
+            "/api/me/jobs/{id}/bulk-operations/{op_id}",
+            get(handlers::applicants::get_bulk_operation),
+        )
+        .route(
+            "/api/me/jobs/{id}/applicants/export",
+            get(handlers::applicants::export_applicants).layer(middleware::map_response(skip_compression)),
+        )
+        .route(
+            "/api/me/jobs/{id}/match-scores/recalculate",
+            post(handlers::applicants::recalculate_match_scores),
+        )
+        .route(
+            "/api/me/jobs/{id}/match-recompute-status",
+            get(handlers::applicants::get_match_recompute_status),
+        )
+        // V13: Applicant tags
+        .route(
+            "/api/me/company/tags",
+            get(handlers::applicants::list_tags).post(handlers::applicants::create_tag),
+        )
+        .route(
+            "/api/me/company/tags/{id}",
+            put(handlers::applicants::update_tag).delete(handlers::applicants::delete_tag),
+        )
+        .route(
+            "/api/me/jobs/{job_id}/applicants/{app_id}/tags/{tag_id}",
+            post(handlers::applicants::assign_tag).delete(handlers::applicants::unassign_tag),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V9: Saved Jobs routes (protected - job seekers)
+    let saved_jobs_routes = Router::new()
+        .route(
+            "/api/me/saved-jobs",
+            get(handlers::saved_jobs::list_saved_jobs),
+        )
+        .route(
+            "/api/me/saved-jobs/{job_id}",
+            post(handlers::saved_jobs::save_job).delete(handlers::saved_jobs::unsave_job),
+        )
+        .route(
+            "/api/me/saved-jobs/{job_id}/check",
+            get(handlers::saved_jobs::check_job_saved),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: Recently viewed jobs and recent searches (protected - job seekers)
+    let browsing_routes = Router::new()
+        .route(
+            "/api/me/recently-viewed-jobs",
+            get(handlers::browsing::list_recently_viewed_jobs),
+        )
+        .route(
+            "/api/me/recent-searches",
+            get(handlers::browsing::list_recent_searches)
+                .delete(handlers::browsing::clear_recent_searches),
+        )
+        .route(
+            "/api/me/recent-searches/{id}",
+            delete(handlers::browsing::delete_recent_search),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: Notification preferences (protected - any authenticated user)
+    let notification_routes = Router::new()
+        .route(
+            "/api/me/notification-preferences",
+            get(handlers::notifications::get_notification_preferences)
+                .put(handlers::notifications::update_notification_preferences),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: One-click unsubscribe from email links (public - signed token)
+    let notification_public_routes = Router::new().route(
+        "/api/notifications/unsubscribe",
+        post(handlers::notifications::unsubscribe),
+    );
+
+    // V9: File upload routes - Job seeker files (protected)
+    let file_seeker_routes = Router::new()
+        .route(
+            "/api/me/profile/cv",
+            put(handlers::files::upload_cv).delete(handlers::files::delete_cv),
+        )
+        .route("/api/me/profile/cv/parse", post(handlers::files::parse_cv))
+        .route(
+            "/api/me/profile/image",
+            put(handlers::files::upload_profile_image).delete(handlers::files::delete_profile_image),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V9: File upload routes - Company files (protected)
+    let file_company_routes = Router::new()
+        .route(
+            "/api/me/company/logo",
+            put(handlers::files::upload_company_logo).delete(handlers::files::delete_company_logo),
+        )
+        .route(
+            "/api/me/company/cover",
+            put(handlers::files::upload_company_cover).delete(handlers::files::delete_company_cover),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V9: File download route (protected - any authenticated user)
+    let file_download_routes = Router::new()
+        .route(
+            "/api/files/{id}",
+            get(handlers::files::download_file).layer(middleware::map_response(skip_compression)),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V9: Download links of storage drivers without presigned URLs (public -
+    // signed token)
+    let file_link_routes = Router::new().route(
+        "/api/files/download",
+        get(handlers::files::download_stored_file)
+            .layer(middleware::map_response(skip_compression)),
+    );
+
+    // V13: Personal API token management (protected)
+    let api_token_routes = Router::new()
+        .route(
+            "/api/me/api-tokens",
+            get(handlers::api_tokens::list_api_tokens)
+                .post(handlers::api_tokens::create_api_token),
+        )
+        .route(
+            "/api/me/api-tokens/{id}",
+            delete(handlers::api_tokens::revoke_api_token),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: Interview calendar feeds
+    let calendar_routes = Router::new()
+        .route(
+            "/api/me/company/calendar-token",
+            get(handlers::calendar::get_calendar_token)
+                .delete(handlers::calendar::revoke_calendar_token),
+        )
+        .route(
+            "/api/me/interviews.ics",
+            get(handlers::calendar::get_seeker_interviews_feed),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    // V13: Company interview feed (calendar token or signed in)
+    let calendar_optional_routes = Router::new()
+        .route(
+            "/api/me/company/interviews.ics",
+            get(handlers::calendar::get_company_interviews_feed),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            optional_auth,
+        ));
+
+    // V13: Landing page statistics (public)
+    let stats_public_routes = Router::new()
+        .route("/api/stats/public", get(handlers::stats::get_public_stats))
+        .route(
+            "/api/stats/omil-coverage",
+            get(handlers::stats::get_omil_coverage),
+        );
+
+    // V13: Job syndication feeds for aggregators (public)
+    let feed_public_routes = Router::new()
+        .route("/api/feeds/jobs.xml", get(handlers::feeds::get_jobs_xml_feed))
+        .route("/api/feeds/jobs.json", get(handlers::feeds::get_jobs_json_feed));
+
+    // Build router (V1-V9)
+    let api_routes = Router::new()
+        // Merge reference data routes
+        .merge(reference_routes)
+        // Merge auth routes
+        .merge(auth_public_routes)
+        .merge(auth_protected_routes)
+        .merge(auth_optional_routes)
+        // Merge V3 profile routes
+        .merge(profile_routes)
+        // Merge V4 company routes
+        .merge(company_routes)
+        .merge(company_public_routes)
+        // Merge V5 job and application routes
+        .merge(job_routes)
+        .merge(application_routes)
+        .merge(job_public_routes)
+        // Merge V6 admin routes
+        .merge(admin_routes)
+        // Merge V7 matching routes
+        .merge(matching_routes)
+        .merge(match_score_routes)
+        // Merge V8 OMIL routes
+        .merge(omil_routes)
+        .merge(omil_coordinator_routes)
+        .merge(omil_director_routes)
+        // Merge V8 invitation routes
+        .merge(invitation_company_routes)
+        .merge(invitation_seeker_routes)
+        // Merge V13 OMIL directory and referral request routes
+        .merge(referral_company_routes)
+        // Merge V9 applicant management routes
+        .merge(applicant_routes)
+        // Merge V9 saved jobs routes
+        .merge(saved_jobs_routes)
+        // Merge V13 browsing history routes
+        .merge(browsing_routes)
+        // Merge V13 notification preference routes
+        .merge(notification_routes)
+        .merge(notification_public_routes)
+        // Merge V9 file upload routes
+        .merge(file_seeker_routes)
+        .merge(file_company_routes)
+        .merge(file_download_routes)
+        .merge(file_link_routes)
+        // Merge V13 API token routes
+        .merge(api_token_routes)
+        // Merge V13 interview calendar routes
+        .merge(calendar_routes)
+        .merge(calendar_optional_routes)
+        // Merge V13 public statistics routes
+        .merge(stats_public_routes)
+        // Merge V13 job syndication feed routes
+        .merge(feed_public_routes)
+        // Maintenance mode applies to the whole API but not to health checks
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            enforce_maintenance_mode,
+        ));
+
+    Router::new()
+        // Health check routes, outside the concurrency limit so they answer
+        // even while the API is shedding load
+        .route("/api/health", get(handlers::health))
+        .route("/api/health/ready", get(handlers::readiness))
+        .merge(with_overload_protection(api_routes, &app_state.config))
+        // Middleware layers
+        .layer(compression_layer())
+        .layer(trace_layer(&app_state.config))
+        .layer(CorsLayer::permissive())
+        // Application state
+        .with_state(app_state)
+}
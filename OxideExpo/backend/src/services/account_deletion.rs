@@ -0,0 +1,367 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::user::{AccountStatus, UserType};
+use crate::services::suspension;
+use crate::utils::jwt::{create_refresh_token, hash_token};
+
+/// Setting holding the days between a deletion request and the scrub
+pub const GRACE_DAYS_SETTING: &str = "account_deletion_grace_days";
+
+/// Accounts scrubbed per scheduler run
+const SCRUB_BATCH_SIZE: i64 = 50;
+
+/// A deletion waiting out its grace period
+#[derive(Debug)]
+pub struct ScheduledDeletion {
+    /// Emailed to the user; restores the account without logging in
+    pub restore_token: String,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+// ============================================================================
+// SCHEDULE / RESTORE
+// ============================================================================
+
+/// Mark the account for deletion after `grace_days`. It leaves search at once
+/// since only active accounts are listed; everything it owns is paused like
+/// on suspension, and every session, API token and calendar token is revoked.
+pub async fn schedule(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    user_type: UserType,
+    grace_days: i64,
+) -> Result<ScheduledDeletion> {
+    let status = sqlx::query_scalar!(
+        r#"SELECT account_status as "account_status: AccountStatus" FROM users WHERE id = $1 FOR UPDATE"#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if status == AccountStatus::PendingDeletion {
+        return Err(AppError::ValidationError(
+            "Account deletion is already scheduled".to_string(),
+        ));
+    }
+
+    let restore_token = create_refresh_token(); // Reuse secure token generation
+    let scheduled_for = Utc::now() + Duration::days(grace_days);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO account_deletions (user_id, prior_status, restore_token_hash, scheduled_for)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        user_id,
+        status as AccountStatus,
+        hash_token(&restore_token),
+        scheduled_for
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET account_status = $2, token_version = token_version + 1, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        user_id,
+        AccountStatus::PendingDeletion as AccountStatus
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE api_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM calendar_tokens WHERE user_id = $1", user_id)
+        .execute(&mut **tx)
+        .await?;
+
+    suspension::suspend(tx, user_id, user_type).await?;
+
+    Ok(ScheduledDeletion {
+        restore_token,
+        scheduled_for,
+    })
+}
+
+/// The account a restore token belongs to, locked for the restore. Tokens stop
+/// working once the grace period is over.
+pub async fn user_for_restore_token(
+    tx: &mut Transaction<'_, Postgres>,
+    token: &str,
+) -> Result<Uuid> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT user_id FROM account_deletions
+        WHERE restore_token_hash = $1 AND scheduled_for > NOW()
+        FOR UPDATE
+        "#,
+        hash_token(token)
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))
+}
+
+/// Cancel a scheduled deletion, putting back the account status and whatever
+/// scheduling it paused
+pub async fn restore(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<()> {
+    let prior_status = sqlx::query_scalar!(
+        r#"
+        DELETE FROM account_deletions
+        WHERE user_id = $1 AND scheduled_for > NOW()
+        RETURNING prior_status as "prior_status: AccountStatus"
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::ValidationError("Account is not scheduled for deletion".to_string())
+    })?;
+
+    sqlx::query!(
+        "UPDATE users SET account_status = $2, updated_at = NOW() WHERE id = $1",
+        user_id,
+        prior_status as AccountStatus
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    suspension::reactivate(tx, user_id).await?;
+
+    Ok(())
+}
+
+/// When the account's deletion is due, if it is scheduled
+pub async fn scheduled_for(
+    db: impl sqlx::PgExecutor<'_>,
+    user_id: Uuid,
+) -> Result<Option<DateTime<Utc>>> {
+    Ok(sqlx::query_scalar!(
+        "SELECT scheduled_for FROM account_deletions WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(db)
+    .await?)
+}
+
+// ============================================================================
+// SCRUB
+// ============================================================================
+
+/// Scrub accounts whose grace period is over, one transaction each,
+/// returning how many were scrubbed
+pub async fn scrub_due(db: &PgPool) -> Result<usize> {
+    let due = sqlx::query_scalar!(
+        r#"
+        SELECT user_id FROM account_deletions
+        WHERE scheduled_for <= NOW()
+        ORDER BY scheduled_for
+        LIMIT $1
+        "#,
+        SCRUB_BATCH_SIZE
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut scrubbed = 0;
+    for user_id in due {
+        let mut tx = db.begin().await?;
+        // Restored or taken by another run in the meantime
+        let still_due = sqlx::query_scalar!(
+            r#"
+            DELETE FROM account_deletions
+            WHERE user_id = $1 AND scheduled_for <= NOW()
+            RETURNING user_id
+            "#,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if still_due.is_none() {
+            continue;
+        }
+
+        scrub(&mut tx, user_id).await?;
+        tx.commit().await?;
+        tracing::info!(user_id = %user_id, "Scrubbed deleted account");
+        scrubbed += 1;
+    }
+
+    Ok(scrubbed)
+}
+
+/// Remove the personal data of an account. The user row stays, deactivated
+/// and nameless, so applications and history others depend on keep their
+/// status and dates for statistics, with their free text scrubbed.
+async fn scrub(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET email = NULL,
+            first_name = 'Cuenta',
+            last_name = 'eliminada',
+            password_hash = NULL,
+            email_verified_at = NULL,
+            account_status = $2,
+            token_version = token_version + 1,
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        user_id,
+        AccountStatus::Deactivated as AccountStatus
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE job_seeker_profiles
+        SET phone = NULL,
+            date_of_birth = NULL,
+            gender = NULL,
+            marital_status = NULL,
+            nationality = NULL,
+            national_id = NULL,
+            address = NULL,
+            bio = NULL,
+            professional_headline = NULL,
+            profile_image_url = NULL,
+            cv_url = NULL,
+            cv_file_id = NULL,
+            profile_image_file_id = NULL,
+            available_from = NULL
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Rows that only describe the person go entirely; stored files are queued
+    // for removal from storage
+    sqlx::query!(
+        r#"
+        WITH
+            education AS (DELETE FROM education_records WHERE user_id = $1),
+            experience AS (DELETE FROM work_experiences WHERE user_id = $1),
+            skills AS (DELETE FROM user_skills WHERE user_id = $1),
+            languages AS (DELETE FROM user_languages WHERE user_id = $1),
+            portfolio AS (DELETE FROM portfolio_items WHERE user_id = $1),
+            profile_refs AS (DELETE FROM profile_references WHERE user_id = $1),
+            disabilities AS (DELETE FROM job_seeker_disabilities WHERE user_id = $1),
+            preferences AS (DELETE FROM job_seeker_preferences WHERE user_id = $1),
+            files AS (DELETE FROM uploaded_files WHERE user_id = $1 RETURNING storage_path),
+            queued_files AS (
+                INSERT INTO application_document_deletions (file_key)
+                SELECT storage_path FROM files
+                ON CONFLICT (file_key) DO NOTHING
+            ),
+            templates AS (DELETE FROM cover_letter_templates WHERE user_id = $1),
+            drafts AS (DELETE FROM application_drafts WHERE user_id = $1),
+            saved AS (DELETE FROM saved_jobs WHERE user_id = $1),
+            invitations AS (DELETE FROM job_invitations WHERE job_seeker_id = $1),
+            scores AS (DELETE FROM job_match_scores WHERE user_id = $1),
+            score_history AS (DELETE FROM job_match_score_history WHERE user_id = $1),
+            email_changes AS (DELETE FROM pending_email_changes WHERE user_id = $1),
+            verification AS (DELETE FROM email_verification_tokens WHERE user_id = $1),
+            password_resets AS (DELETE FROM password_reset_tokens WHERE user_id = $1),
+            calendar AS (DELETE FROM calendar_tokens WHERE user_id = $1)
+        DELETE FROM refresh_tokens WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let application_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE job_applications
+        SET
+            cover_letter = NULL,
+            resume_url = NULL,
+            interview_notes = NULL,
+            offer_details = NULL,
+            withdrawal_reason = NULL,
+            rejection_feedback = NULL,
+            anonymized_at = NOW()
+        WHERE applicant_id = $1
+        RETURNING id
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM application_notes WHERE application_id = ANY($1)",
+        &application_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM application_documents WHERE application_id = ANY($1)",
+        &application_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // OMIL followups about the person keep their type and date for reports
+    sqlx::query!(
+        r#"
+        UPDATE job_seeker_followups
+        SET title = NULL, content = '', updated_at = NOW()
+        WHERE job_seeker_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE company_members SET is_active = false, updated_at = NOW() WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE omil_members SET is_active = false, left_at = COALESCE(left_at, NOW()), updated_at = NOW()
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Paused applications stay on hold; the paused company and jobs stay paused
+    sqlx::query!(
+        "DELETE FROM suspension_cascades WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
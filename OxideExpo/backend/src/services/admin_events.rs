@@ -0,0 +1,135 @@
+//! Live moderation queue counts for the admin panel. Paths that add to or
+//! clear a moderation queue call [`publish`], which recounts the queues and
+//! broadcasts the counts to every open admin event stream.
+
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use axum::response::sse::Event;
+use futures::{stream, Stream, StreamExt};
+use sqlx::PgPool;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::error::Result;
+use crate::models::admin::PendingQueueCounts;
+use crate::AppState;
+
+/// Updates buffered for each stream. A stream further behind than this is
+/// ended rather than holding back the others; its client reconnects and
+/// starts over from a snapshot.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Counts as of one change, numbered for the SSE `id` field
+#[derive(Debug, Clone)]
+pub struct QueueCountsUpdate {
+    pub id: u64,
+    pub counts: PendingQueueCounts,
+}
+
+/// Fan-out of queue count updates to the connected admins
+#[derive(Clone)]
+pub struct AdminEvents {
+    sender: broadcast::Sender<QueueCountsUpdate>,
+    last_id: Arc<AtomicU64>,
+}
+
+impl Default for AdminEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdminEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            last_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<QueueCountsUpdate> {
+        self.sender.subscribe()
+    }
+
+    /// ID of the latest update, which a snapshot taken now is current with
+    pub fn last_id(&self) -> u64 {
+        self.last_id.load(Ordering::SeqCst)
+    }
+
+    fn send(&self, counts: PendingQueueCounts) {
+        let id = self.last_id.fetch_add(1, Ordering::SeqCst) + 1;
+        // Nobody listening is not an error
+        let _ = self.sender.send(QueueCountsUpdate { id, counts });
+    }
+}
+
+/// Current size of each moderation queue
+pub async fn pending_counts(db: &PgPool) -> Result<PendingQueueCounts> {
+    let counts = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM company_profiles WHERE status = 'pending_approval') as "companies!",
+            (SELECT COUNT(*) FROM jobs WHERE status = 'pending_approval') as "jobs!",
+            (SELECT COUNT(*) FROM omil_organizations WHERE status = 'pending_approval') as "omils!",
+            (SELECT COUNT(*) FROM flagged_content WHERE status = 'pending') as "flags!"
+        "#
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(PendingQueueCounts {
+        companies: counts.companies,
+        jobs: counts.jobs,
+        omils: counts.omils,
+        flags: counts.flags,
+    })
+}
+
+/// Recounts the queues and pushes the counts to connected admins. Call it
+/// once a change is committed; with no admin connected it does nothing. A
+/// failure is logged, never returned, since the change itself went through.
+pub async fn publish(state: &AppState) {
+    if state.admin_events.sender.receiver_count() == 0 {
+        return;
+    }
+
+    match pending_counts(&state.db).await {
+        Ok(counts) => state.admin_events.send(counts),
+        Err(e) => tracing::warn!("Failed to publish admin queue counts: {}", e),
+    }
+}
+
+fn counts_event(name: &str, id: u64, counts: &PendingQueueCounts) -> Event {
+    Event::default()
+        .event(name)
+        .id(id.to_string())
+        .data(serde_json::json!(counts).to_string())
+}
+
+/// A `snapshot` event with the counts at connection time, then a
+/// `queue_counts` event each time they change
+pub fn event_stream(
+    snapshot_id: u64,
+    snapshot: PendingQueueCounts,
+    receiver: broadcast::Receiver<QueueCountsUpdate>,
+) -> impl Stream<Item = std::result::Result<Event, Infallible>> {
+    let first = counts_event("snapshot", snapshot_id, &snapshot);
+
+    let updates = stream::unfold((receiver, snapshot), |(mut receiver, last)| async move {
+        loop {
+            match receiver.recv().await {
+                // A change elsewhere left these queues as they were
+                Ok(update) if update.counts == last => continue,
+                Ok(update) => {
+                    let event = counts_event("queue_counts", update.id, &update.counts);
+                    return Some((Ok(event), (receiver, update.counts)));
+                }
+                Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    stream::once(async move { Ok(first) }).chain(updates)
+}
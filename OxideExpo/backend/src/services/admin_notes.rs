@@ -0,0 +1,331 @@
+//! Internal notes staff keep on companies, users, jobs and OMILs.
+//!
+//! Notes are for admins only: they're read through the admin endpoints and
+//! the admin detail views, and no public or owner-facing response embeds an
+//! `AdminNote`. Mentioning an admin in a note leaves them an unread mention,
+//! listed as their in-app notifications until they read it.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::{
+    Admin, AdminNote, AdminNoteEntity, AdminNoteMention, AdminRole, CreateAdminNoteRequest,
+    UpdateAdminNoteRequest,
+};
+
+/// Authors edit and delete their own notes; super admins any note
+pub fn can_edit(admin: &Admin, note: &AdminNote) -> bool {
+    admin.admin_role == AdminRole::SuperAdmin || note.author_admin_id == Some(admin.id)
+}
+
+async fn entity_exists(db: &PgPool, entity_type: AdminNoteEntity, entity_id: Uuid) -> Result<bool> {
+    let exists = match entity_type {
+        AdminNoteEntity::Company => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM company_profiles WHERE id = $1) as "exists!""#,
+                entity_id
+            )
+            .fetch_one(db)
+            .await?
+        }
+        AdminNoteEntity::User => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) as "exists!""#,
+                entity_id
+            )
+            .fetch_one(db)
+            .await?
+        }
+        AdminNoteEntity::Job => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1) as "exists!""#,
+                entity_id
+            )
+            .fetch_one(db)
+            .await?
+        }
+        AdminNoteEntity::Omil => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM omil_organizations WHERE id = $1) as "exists!""#,
+                entity_id
+            )
+            .fetch_one(db)
+            .await?
+        }
+    };
+    Ok(exists)
+}
+
+/// The notes with the given IDs, in that order
+async fn load_notes(db: &PgPool, note_ids: &[Uuid]) -> Result<Vec<AdminNote>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            n.id as "id!",
+            n.entity_type as "entity_type!: AdminNoteEntity",
+            n.entity_id as "entity_id!",
+            n.author_admin_id,
+            u.first_name || ' ' || u.last_name as "author_name?",
+            n.body as "body!",
+            n.is_pinned as "is_pinned!",
+            COALESCE(
+                (SELECT array_agg(m.admin_id ORDER BY m.created_at, m.admin_id)
+                 FROM admin_note_mentions m WHERE m.note_id = n.id),
+                '{}'
+            ) as "mentions!",
+            n.created_at as "created_at!",
+            n.updated_at as "updated_at!"
+        FROM admin_notes n
+        LEFT JOIN admins a ON a.id = n.author_admin_id
+        LEFT JOIN users u ON u.id = a.user_id
+        WHERE n.id = ANY($1)
+        "#,
+        note_ids
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut notes: HashMap<Uuid, AdminNote> = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.id,
+                AdminNote {
+                    id: row.id,
+                    entity_type: row.entity_type,
+                    entity_id: row.entity_id,
+                    author_admin_id: row.author_admin_id,
+                    author_name: row.author_name,
+                    body: row.body,
+                    is_pinned: row.is_pinned,
+                    mentions: row.mentions,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                },
+            )
+        })
+        .collect();
+
+    Ok(note_ids.iter().filter_map(|id| notes.remove(id)).collect())
+}
+
+pub async fn get_note(db: &PgPool, note_id: Uuid) -> Result<AdminNote> {
+    load_notes(db, &[note_id])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound("Note not found".to_string()))
+}
+
+/// Notes on the entity, pinned ones first, then newest first
+pub async fn notes_for(
+    db: &PgPool,
+    entity_type: AdminNoteEntity,
+    entity_id: Uuid,
+) -> Result<Vec<AdminNote>> {
+    let note_ids = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM admin_notes
+        WHERE entity_type = $1 AND entity_id = $2
+        ORDER BY is_pinned DESC, created_at DESC, id
+        "#,
+        entity_type as AdminNoteEntity,
+        entity_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    load_notes(db, &note_ids).await
+}
+
+/// Records the mentions of the note, skipping its author and admins already
+/// mentioned. Every mentioned ID must be an admin.
+async fn mention(
+    tx: &mut sqlx::PgConnection,
+    note_id: Uuid,
+    author_admin_id: Uuid,
+    admin_ids: &[Uuid],
+) -> Result<()> {
+    if admin_ids.is_empty() {
+        return Ok(());
+    }
+
+    let known = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM admins WHERE id = ANY($1)"#,
+        admin_ids
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let mut distinct = admin_ids.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    if known != distinct.len() as i64 {
+        return Err(AppError::ValidationError(
+            "Mentions must be admin IDs".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO admin_note_mentions (note_id, admin_id)
+        SELECT $1, admin_id FROM UNNEST($2::uuid[]) AS admin_id
+        WHERE admin_id <> $3
+        ON CONFLICT (note_id, admin_id) DO NOTHING
+        "#,
+        note_id,
+        &distinct,
+        author_admin_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create(
+    db: &PgPool,
+    author: &Admin,
+    request: CreateAdminNoteRequest,
+) -> Result<AdminNote> {
+    if !entity_exists(db, request.entity_type, request.entity_id).await? {
+        return Err(AppError::NotFound(
+            "The entity to annotate was not found".to_string(),
+        ));
+    }
+
+    let mut tx = db.begin().await?;
+
+    let note_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO admin_notes (entity_type, entity_id, author_admin_id, body, is_pinned)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+        request.entity_type as AdminNoteEntity,
+        request.entity_id,
+        author.id,
+        request.body.trim(),
+        request.is_pinned.unwrap_or(false)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    mention(&mut tx, note_id, author.id, &request.mentions).await?;
+
+    tx.commit().await?;
+
+    get_note(db, note_id).await
+}
+
+/// Edits the note as `editor`, who must be allowed to
+pub async fn update(
+    db: &PgPool,
+    editor: &Admin,
+    note_id: Uuid,
+    request: UpdateAdminNoteRequest,
+) -> Result<AdminNote> {
+    let note = get_note(db, note_id).await?;
+    if !can_edit(editor, &note) {
+        return Err(AppError::ForbiddenError(
+            "Only the author or a super admin can edit this note".to_string(),
+        ));
+    }
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE admin_notes
+        SET body = COALESCE($2, body),
+            is_pinned = COALESCE($3, is_pinned)
+        WHERE id = $1
+        "#,
+        note_id,
+        request.body.as_deref().map(str::trim),
+        request.is_pinned
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Mentions notify on the author's behalf, also when a super admin edits
+    let author_admin_id = note.author_admin_id.unwrap_or(editor.id);
+    mention(&mut tx, note_id, author_admin_id, &request.mentions).await?;
+
+    tx.commit().await?;
+
+    get_note(db, note_id).await
+}
+
+/// Deletes the note as `editor`, who must be allowed to
+pub async fn delete(db: &PgPool, editor: &Admin, note_id: Uuid) -> Result<()> {
+    let note = get_note(db, note_id).await?;
+    if !can_edit(editor, &note) {
+        return Err(AppError::ForbiddenError(
+            "Only the author or a super admin can delete this note".to_string(),
+        ));
+    }
+
+    sqlx::query!("DELETE FROM admin_notes WHERE id = $1", note_id)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Notes the admin was mentioned in, newest mention first
+pub async fn mentions_for(
+    db: &PgPool,
+    admin_id: Uuid,
+    unread_only: bool,
+) -> Result<Vec<AdminNoteMention>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT note_id, created_at, read_at
+        FROM admin_note_mentions
+        WHERE admin_id = $1 AND (NOT $2 OR read_at IS NULL)
+        ORDER BY created_at DESC, note_id
+        "#,
+        admin_id,
+        unread_only
+    )
+    .fetch_all(db)
+    .await?;
+
+    let note_ids: Vec<Uuid> = rows.iter().map(|row| row.note_id).collect();
+    let notes = load_notes(db, &note_ids).await?;
+
+    Ok(rows
+        .into_iter()
+        .zip(notes)
+        .map(|(row, note)| AdminNoteMention {
+            note,
+            mentioned_at: row.created_at,
+            read_at: row.read_at,
+        })
+        .collect())
+}
+
+/// Marks the admin's mention in the note read
+pub async fn mark_mention_read(db: &PgPool, admin_id: Uuid, note_id: Uuid) -> Result<()> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE admin_note_mentions
+        SET read_at = COALESCE(read_at, NOW())
+        WHERE note_id = $1 AND admin_id = $2
+        "#,
+        note_id,
+        admin_id
+    )
+    .execute(db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            "You were not mentioned in this note".to_string(),
+        ));
+    }
+
+    Ok(())
+}
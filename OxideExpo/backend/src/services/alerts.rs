@@ -0,0 +1,259 @@
+//! Job alert emails.
+//!
+//! A seeker with email_job_alerts on is due an alert once their
+//! alert_frequency has gone by since `last_alert_sent_at`: on every run for
+//! instant alerts, after a day or a week for the digests. The alert lists the
+//! jobs published since then that score at least `job_alert_min_score`
+//! against the seeker, best match first, using the cached match scores and
+//! caching those it has to compute. Each run handles up to
+//! `job_alert_batch_size` seekers, longest waiting first; a seeker whose
+//! alert fails keeps their timestamp and is tried again on the next run.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::notification::{NotificationChannel, NotificationEvent};
+use crate::services::matching::{self, MatchingService};
+use crate::services::notifications;
+use crate::utils::redact;
+use crate::AppState;
+
+/// Jobs listed in one alert
+pub const MAX_ALERT_JOBS: usize = 10;
+
+/// New jobs fetched and scored at a time; a seeker's jobs are paged through
+/// until all published since their last alert have been seen
+const CANDIDATE_PAGE_SIZE: i64 = 50;
+
+/// How far back a seeker's first alert looks
+const FIRST_ALERT_DAYS: i64 = 7;
+
+/// A new job in an alert
+#[derive(Debug, Clone)]
+pub struct AlertJob {
+    pub id: Uuid,
+    pub title: String,
+    pub company_name: String,
+    pub match_score: i32,
+}
+
+/// What a run did, for the log
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AlertRun {
+    /// Seekers whose alert was due
+    pub seekers: usize,
+    /// Alerts emailed
+    pub sent: usize,
+    /// Seekers left for the next run after an error
+    pub failed: usize,
+}
+
+struct DueSeeker {
+    user_id: Uuid,
+    email: String,
+    first_name: String,
+    last_alert_sent_at: Option<DateTime<Utc>>,
+}
+
+/// Emails the alerts due as of `now`
+pub async fn run(state: &AppState, now: DateTime<Utc>) -> Result<AlertRun> {
+    let due = sqlx::query_as!(
+        DueSeeker,
+        r#"
+        SELECT jp.user_id, u.email as "email!", u.first_name, jp.last_alert_sent_at
+        FROM job_seeker_preferences jp
+        JOIN users u ON u.id = jp.user_id
+        WHERE jp.email_job_alerts
+          AND jp.alert_frequency <> 'never'
+          AND u.user_type = 'job_seeker'
+          AND u.account_status = 'active'
+          AND u.email IS NOT NULL
+          AND (
+              jp.last_alert_sent_at IS NULL
+              OR jp.last_alert_sent_at <= $1::timestamptz - CASE jp.alert_frequency
+                  WHEN 'daily' THEN INTERVAL '1 day'
+                  WHEN 'weekly' THEN INTERVAL '7 days'
+                  ELSE INTERVAL '0'
+              END
+          )
+        ORDER BY jp.last_alert_sent_at NULLS FIRST, jp.user_id
+        LIMIT $2
+        "#,
+        now,
+        state.config.job_alert_batch_size
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut run = AlertRun {
+        seekers: due.len(),
+        ..AlertRun::default()
+    };
+
+    // Seekers who turned digest emails off count as done, or they would
+    // hold the front of every batch
+    let due_ids: Vec<Uuid> = due.iter().map(|seeker| seeker.user_id).collect();
+    let wanted = notifications::filter_recipients(
+        &state.db,
+        &due_ids,
+        NotificationEvent::Digest,
+        NotificationChannel::Email,
+    )
+    .await?;
+    let unwanted: Vec<Uuid> = due_ids
+        .into_iter()
+        .filter(|id| !wanted.contains(id))
+        .collect();
+    mark_sent(state, &unwanted, now).await;
+
+    for seeker in due.iter().filter(|seeker| wanted.contains(&seeker.user_id)) {
+        match alert(state, seeker, now).await {
+            Ok(sent) => {
+                if sent {
+                    run.sent += 1;
+                }
+                mark_sent(state, &[seeker.user_id], now).await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to send job alert to {}: {}", seeker.user_id, e);
+                run.failed += 1;
+            }
+        }
+    }
+
+    Ok(run)
+}
+
+/// Scores the seeker's new jobs and emails the ones that match, if any.
+/// True when an email went out.
+async fn alert(state: &AppState, seeker: &DueSeeker, now: DateTime<Utc>) -> Result<bool> {
+    let since = seeker
+        .last_alert_sent_at
+        .unwrap_or(now - Duration::days(FIRST_ALERT_DAYS));
+
+    let mut jobs = Vec::new();
+    let mut after: Option<(DateTime<Utc>, Uuid)> = None;
+    loop {
+        let candidates = sqlx::query!(
+            r#"
+            SELECT j.id, j.title, cp.company_name, j.published_at as "published_at!"
+            FROM jobs j
+            JOIN company_profiles cp ON cp.id = j.company_id
+            WHERE j.status = 'active'
+              AND j.approved_at IS NOT NULL
+              AND j.application_deadline >= CURRENT_DATE
+              AND j.published_at > $2
+              AND j.published_at <= $3
+              AND ($4::timestamptz IS NULL OR (j.published_at, j.id) > ($4, $5))
+              AND NOT EXISTS (
+                  SELECT 1 FROM job_applications ja
+                  WHERE ja.job_id = j.id AND ja.applicant_id = $1
+              )
+            ORDER BY j.published_at, j.id
+            LIMIT $6
+            "#,
+            seeker.user_id,
+            since,
+            now,
+            after.map(|(published_at, _)| published_at),
+            after.map(|(_, id)| id),
+            CANDIDATE_PAGE_SIZE
+        )
+        .fetch_all(&state.db)
+        .await?;
+        let Some(last) = candidates.last() else {
+            break;
+        };
+        after = Some((last.published_at, last.id));
+        let last_page = candidates.len() < CANDIDATE_PAGE_SIZE as usize;
+
+        let job_ids: Vec<Uuid> = candidates.iter().map(|c| c.id).collect();
+        let scores = match_scores(state, seeker.user_id, &job_ids, now).await?;
+        for candidate in candidates {
+            let match_score = scores[&candidate.id];
+            if match_score >= state.config.job_alert_min_score {
+                jobs.push(AlertJob {
+                    id: candidate.id,
+                    title: candidate.title,
+                    company_name: candidate.company_name,
+                    match_score,
+                });
+            }
+        }
+        if last_page {
+            break;
+        }
+    }
+    if jobs.is_empty() {
+        return Ok(false);
+    }
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.match_score));
+    jobs.truncate(MAX_ALERT_JOBS);
+
+    let (email, name) = (seeker.email.clone(), seeker.first_name.clone());
+    notifications::email_now(
+        state,
+        seeker.user_id,
+        NotificationEvent::Digest,
+        move |mailer| async move { mailer.send_job_alert_digest(&email, &name, &jobs).await },
+    )
+    .await
+}
+
+/// The seeker's score for each job: the cached one while it is fresh,
+/// otherwise computed and cached
+async fn match_scores(
+    state: &AppState,
+    user_id: Uuid,
+    job_ids: &[Uuid],
+    now: DateTime<Utc>,
+) -> Result<HashMap<Uuid, i32>> {
+    let mut scores: HashMap<Uuid, i32> = sqlx::query!(
+        r#"
+        SELECT job_id, total_score, is_stale, computed_at
+        FROM job_match_scores
+        WHERE user_id = $1 AND job_id = ANY($2)
+        "#,
+        user_id,
+        job_ids
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .filter(|cached| !matching::is_score_stale(cached.is_stale, cached.computed_at, now))
+    .map(|cached| (cached.job_id, cached.total_score))
+    .collect();
+
+    for &job_id in job_ids {
+        if scores.contains_key(&job_id) {
+            continue;
+        }
+        let breakdown = MatchingService::calculate_match_score(&state.db, job_id, user_id).await?;
+        MatchingService::save_match_score(&state.db, job_id, user_id, &breakdown).await?;
+        scores.insert(job_id, breakdown.total_score);
+    }
+
+    Ok(scores)
+}
+
+/// A failure is only logged: the seekers are due again on the next run,
+/// where an instant alert may repeat jobs already sent
+async fn mark_sent(state: &AppState, user_ids: &[Uuid], now: DateTime<Utc>) {
+    if let Err(e) = sqlx::query!(
+        "UPDATE job_seeker_preferences SET last_alert_sent_at = $2 WHERE user_id = ANY($1)",
+        user_ids,
+        now
+    )
+    .execute(&state.db)
+    .await
+    {
+        tracing::error!(
+            "Failed to record job alerts sent to {} seekers: {}",
+            user_ids.len(),
+            redact::db_error(&e)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::time::Duration;
+
+use axum::extract::Multipart;
+use bytes::Bytes;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::application::{
+    application_documents_folder, ApplicationDocument, ApplicationDocumentWithUrl,
+    ApplicationStatus, APPLICATION_DOCUMENT_CONTENT_TYPES, APPLICATION_DOCUMENT_URL_MINUTES,
+    MAX_APPLICATION_DOCUMENTS, MAX_APPLICATION_DOCUMENT_BYTES,
+};
+use crate::services::storage::StorageBackend;
+use crate::AppState;
+
+/// Queued storage objects removed per scheduler run
+const PURGE_BATCH_SIZE: i64 = 100;
+
+// ============================================================================
+// UPLOAD
+// ============================================================================
+
+/// A document read from a multipart upload, before it is stored
+struct DocumentUpload {
+    label: Option<String>,
+    filename: String,
+    content_type: String,
+    data: Bytes,
+}
+
+/// Reads the `file` field and optional `label` field of an upload
+async fn read_upload(multipart: &mut Multipart) -> Result<DocumentUpload> {
+    let mut label = None;
+    let mut file = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::ValidationError(format!("Failed to read upload: {}", e)))?
+    {
+        match field.name() {
+            Some("label") => {
+                let text = field.text().await.map_err(|e| {
+                    AppError::ValidationError(format!("Failed to read label: {}", e))
+                })?;
+                label = Some(text.trim().to_string()).filter(|l| !l.is_empty());
+            }
+            Some("file") => {
+                let filename = field
+                    .file_name()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                let content_type = field
+                    .content_type()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+
+                if !APPLICATION_DOCUMENT_CONTENT_TYPES.contains(&content_type.as_str()) {
+                    return Err(AppError::ValidationError(
+                        "Invalid file type. Allowed types: PDF, JPEG, PNG, DOCX".to_string(),
+                    ));
+                }
+
+                let data = field.bytes().await.map_err(|e| {
+                    AppError::ValidationError(format!("Failed to read file: {}", e))
+                })?;
+
+                if data.len() > MAX_APPLICATION_DOCUMENT_BYTES {
+                    return Err(AppError::ValidationError(format!(
+                        "File too large. Maximum size: {} MB",
+                        MAX_APPLICATION_DOCUMENT_BYTES / 1024 / 1024
+                    )));
+                }
+
+                file = Some((filename, content_type, data));
+            }
+            _ => {}
+        }
+    }
+
+    let (filename, content_type, data) =
+        file.ok_or_else(|| AppError::ValidationError("No file provided".to_string()))?;
+
+    if label.as_ref().is_some_and(|l| l.chars().count() > 100) {
+        return Err(AppError::ValidationError(
+            "Label must be at most 100 characters".to_string(),
+        ));
+    }
+
+    Ok(DocumentUpload {
+        label,
+        filename,
+        content_type,
+        data,
+    })
+}
+
+/// Checks the application takes documents and has room for another
+async fn check_room(
+    conn: &mut PgConnection,
+    application_id: Uuid,
+    status: ApplicationStatus,
+) -> Result<()> {
+    if !status.accepts_documents() {
+        return Err(AppError::ValidationError(
+            "Documents can only be attached while the application is submitted or under review"
+                .to_string(),
+        ));
+    }
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM application_documents WHERE application_id = $1"#,
+        application_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if count >= MAX_APPLICATION_DOCUMENTS {
+        return Err(AppError::ValidationError(format!(
+            "An application can have at most {} documents",
+            MAX_APPLICATION_DOCUMENTS
+        )));
+    }
+
+    Ok(())
+}
+
+/// Stores a document on an application after checking the application still
+/// takes documents and has room for another. Callers check who may attach.
+/// The check runs again with the application locked once the file is stored,
+/// so concurrent uploads can't go over the limit; a file turned away then is
+/// queued for deletion.
+pub async fn attach(
+    state: &AppState,
+    application_id: Uuid,
+    status: ApplicationStatus,
+    uploaded_by: Uuid,
+    multipart: &mut Multipart,
+) -> Result<ApplicationDocument> {
+    // Fails early, before reading the upload
+    check_room(&mut *state.db.acquire().await?, application_id, status).await?;
+
+    let upload = read_upload(multipart).await?;
+
+    let stored = state
+        .storage
+        .upload(
+            &application_documents_folder(application_id),
+            &upload.filename,
+            &upload.content_type,
+            upload.data,
+        )
+        .await?;
+
+    let label = upload
+        .label
+        .unwrap_or_else(|| upload.filename.chars().take(100).collect());
+
+    let mut tx = state.db.begin().await?;
+
+    let status = sqlx::query_scalar!(
+        r#"
+        SELECT status as "status: ApplicationStatus"
+        FROM job_applications
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        application_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Err(e) = check_room(&mut tx, application_id, status).await {
+        tx.rollback().await?;
+        sqlx::query!(
+            "INSERT INTO application_document_deletions (file_key) VALUES ($1) ON CONFLICT DO NOTHING",
+            stored.storage_path
+        )
+        .execute(&state.db)
+        .await?;
+        return Err(e);
+    }
+
+    let document = sqlx::query_as!(
+        ApplicationDocument,
+        r#"
+        INSERT INTO application_documents
+            (application_id, file_key, label, original_filename, content_type, size, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, application_id, file_key, label, original_filename, content_type, size,
+                  uploaded_by, created_at
+        "#,
+        application_id,
+        stored.storage_path,
+        label,
+        upload.filename,
+        upload.content_type,
+        stored.file_size,
+        uploaded_by
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(document)
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+pub async fn list(db: &PgPool, application_id: Uuid) -> Result<Vec<ApplicationDocument>> {
+    let documents = sqlx::query_as!(
+        ApplicationDocument,
+        r#"
+        SELECT id, application_id, file_key, label, original_filename, content_type, size,
+               uploaded_by, created_at
+        FROM application_documents
+        WHERE application_id = $1
+        ORDER BY created_at, id
+        "#,
+        application_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(documents)
+}
+
+/// Documents of an application with presigned download URLs for the company
+pub async fn list_with_download_urls(
+    db: &PgPool,
+    storage: &dyn StorageBackend,
+    application_id: Uuid,
+) -> Result<Vec<ApplicationDocumentWithUrl>> {
+    let documents = list(db, application_id).await?;
+    if documents.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let expires_in = Duration::from_secs(APPLICATION_DOCUMENT_URL_MINUTES * 60);
+
+    let mut with_urls = Vec::with_capacity(documents.len());
+    for document in documents {
+        let download_url = storage.presign_get(&document.file_key, expires_in).await?;
+        with_urls.push(ApplicationDocumentWithUrl {
+            document,
+            download_url,
+        });
+    }
+
+    Ok(with_urls)
+}
+
+/// Removes a document; its file is queued for deletion from storage
+pub async fn delete(db: &PgPool, application_id: Uuid, document_id: Uuid) -> Result<()> {
+    let result = sqlx::query!(
+        "DELETE FROM application_documents WHERE id = $1 AND application_id = $2",
+        document_id,
+        application_id
+    )
+    .execute(db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Document not found".to_string()));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// STORAGE CLEANUP
+// ============================================================================
+
+/// Deletes the stored files of removed documents, however they were removed.
+/// Files that fail to delete stay queued for the next run.
+pub async fn purge_deleted(db: &PgPool, storage: &dyn StorageBackend) -> Result<u64> {
+    let file_keys = sqlx::query_scalar!(
+        r#"
+        SELECT file_key FROM application_document_deletions
+        ORDER BY queued_at
+        LIMIT $1
+        "#,
+        PURGE_BATCH_SIZE
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut purged = 0;
+    for file_key in file_keys {
+        if let Err(e) = storage.delete(&file_key).await {
+            tracing::warn!("Failed to delete application document {}: {}", file_key, e);
+            continue;
+        }
+
+        sqlx::query!(
+            "DELETE FROM application_document_deletions WHERE file_key = $1",
+            file_key
+        )
+        .execute(db)
+        .await?;
+        purged += 1;
+    }
+
+    Ok(purged)
+}
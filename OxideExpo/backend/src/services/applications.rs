@@ -0,0 +1,357 @@
+//! Submitting job applications. Direct submissions and invitations accepted
+//! with `apply_immediately` both go through here, so the same rules apply to
+//! every application: active job, open deadline, one application per job,
+//! verified email, a complete enough profile and no membership in the hiring
+//! company.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use redis::aio::ConnectionManager;
+use serde_json::json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::AggregateType;
+use crate::models::application::{
+    ApplicationSource, ApplicationStatus, JobApplication, RejectionReasonCode,
+};
+use crate::models::matching::SalaryFit;
+use crate::services::{
+    automation, counters, events, limits, memberships, profile_fingerprint, settings,
+};
+use crate::AppState;
+
+/// Profile completeness a seeker needs before applying
+pub const MIN_COMPLETENESS_TO_APPLY: i32 = 50;
+
+/// An application about to be submitted
+pub struct NewApplication {
+    pub job_id: Uuid,
+    pub applicant_id: Uuid,
+    pub cover_letter: Option<String>,
+    pub resume_url: Option<String>,
+    pub source: ApplicationSource,
+}
+
+/// Checks that the seeker may apply to the job. Every failed rule is a
+/// validation error whose message tells the seeker what is missing.
+pub async fn check_can_apply(
+    conn: &mut PgConnection,
+    applicant_id: Uuid,
+    job_id: Uuid,
+) -> Result<()> {
+    let deadline = sqlx::query_scalar!(
+        "SELECT application_deadline FROM jobs WHERE id = $1 AND status = 'active'",
+        job_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Job not found or not active".to_string()))?;
+
+    if deadline < Utc::now().date_naive() {
+        return Err(AppError::ValidationError(
+            "Application deadline has passed".to_string(),
+        ));
+    }
+
+    let already_applied = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM job_applications WHERE job_id = $1 AND applicant_id = $2) as "exists!"
+        "#,
+        job_id,
+        applicant_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if already_applied {
+        return Err(AppError::ValidationError(
+            "You have already applied to this job".to_string(),
+        ));
+    }
+
+    let applicant = sqlx::query!(
+        r#"
+        SELECT
+            u.email_verified_at IS NOT NULL as "email_verified!",
+            p.completeness_percentage as "completeness?"
+        FROM users u
+        LEFT JOIN job_seeker_profiles p ON p.user_id = u.id
+        WHERE u.id = $1
+        "#,
+        applicant_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if !applicant.email_verified {
+        return Err(AppError::ValidationError(
+            "You must verify your email before applying for jobs".to_string(),
+        ));
+    }
+
+    match applicant.completeness {
+        Some(completeness) if completeness < MIN_COMPLETENESS_TO_APPLY => {
+            Err(AppError::ValidationError(format!(
+                "Your profile must be at least {}% complete to apply for jobs",
+                MIN_COMPLETENESS_TO_APPLY
+            )))
+        }
+        Some(_) => Ok(()),
+        None => Err(AppError::ValidationError(
+            "You must complete your profile before applying for jobs".to_string(),
+        )),
+    }
+}
+
+/// Checks and creates the application with a fingerprint of the seeker's
+/// profile, consuming their draft for the job. Runs on the caller's transaction; automation rules run once it is
+/// committed, see [`run_automation`].
+pub async fn submit(conn: &mut PgConnection, new: &NewApplication) -> Result<JobApplication> {
+    memberships::check_not_self_dealing(&mut *conn, new.applicant_id, new.job_id).await?;
+    check_can_apply(conn, new.applicant_id, new.job_id).await?;
+    let fingerprint = profile_fingerprint::take(conn, new.applicant_id).await?;
+
+    let application = sqlx::query_as!(
+        JobApplication,
+        r#"
+        INSERT INTO job_applications (job_id, applicant_id, cover_letter, resume_url, status, source, profile_fingerprint)
+        VALUES ($1, $2, $3, $4, 'submitted', $5, $6)
+        RETURNING
+            id, job_id, applicant_id,
+            status as "status: ApplicationStatus",
+            cover_letter, resume_url, applied_at,
+            reviewed_at, reviewed_by,
+            interview_date, interview_notes, interview_location,
+            offer_date, offer_details, response_date,
+            withdrawal_reason,
+            rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+            rejection_feedback, rejection_feedback_shared, is_on_hold,
+            created_at, updated_at
+        "#,
+        new.job_id,
+        new.applicant_id,
+        new.cover_letter,
+        new.resume_url,
+        new.source as ApplicationSource,
+        fingerprint.to_json(),
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| match e {
+        // Lost a race with another submission for the same job
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::ValidationError("You have already applied to this job".to_string())
+        }
+        e => e.into(),
+    })?;
+
+    // The draft is consumed by the submission
+    sqlx::query!(
+        "DELETE FROM application_drafts WHERE user_id = $1 AND job_id = $2",
+        new.applicant_id,
+        new.job_id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    counters::application_added(conn, new.job_id).await?;
+    events::emit(
+        conn,
+        AggregateType::Application,
+        application.id,
+        "submitted",
+        Some(new.applicant_id),
+        json!({ "job_id": new.job_id, "source": new.source }),
+    )
+    .await?;
+
+    Ok(application)
+}
+
+/// Runs the company's automation rules on a committed application, which may
+/// move or tag it right away. The application is already submitted, so a
+/// failure here is logged rather than returned.
+pub async fn run_automation(
+    state: &AppState,
+    application: JobApplication,
+) -> Result<JobApplication> {
+    match automation::on_application_submitted(state, application.id).await {
+        Ok(0) => Ok(application),
+        Ok(_) => {
+            let application = sqlx::query_as!(
+                JobApplication,
+                r#"
+                SELECT
+                    id, job_id, applicant_id,
+                    status as "status: ApplicationStatus",
+                    cover_letter, resume_url, applied_at,
+                    reviewed_at, reviewed_by,
+                    interview_date, interview_notes, interview_location,
+                    offer_date, offer_details, response_date,
+                    withdrawal_reason,
+                    rejection_reason_code as "rejection_reason_code: RejectionReasonCode",
+                    rejection_feedback, rejection_feedback_shared, is_on_hold,
+                    created_at, updated_at
+                FROM job_applications
+                WHERE id = $1
+                "#,
+                application.id,
+            )
+            .fetch_one(&state.db)
+            .await?;
+
+            Ok(application)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Automation rules failed for application {}: {}",
+                application.id,
+                e
+            );
+            Ok(application)
+        }
+    }
+}
+
+/// Setting capping how many applications a seeker may send per day
+pub const APPLICATIONS_PER_DAY_SETTING: &str = "max_applications_per_day";
+
+/// Setting capping how many applications an OMIL may send per day on behalf
+/// of all its seekers
+pub const OMIL_APPLICATIONS_PER_DAY_SETTING: &str = "max_omil_applications_per_day";
+
+/// Prior applications looked at for the profile mismatch notice
+pub const MISMATCH_WINDOW: i64 = 20;
+
+/// Share of those, in percent, rejected as a profile mismatch above which
+/// the seeker is nudged to check their match score
+pub const MISMATCH_NOTICE_PERCENT: i64 = 80;
+
+pub const MISMATCH_NOTICE: &str = "Many of your recent applications were rejected because your profile did not match the job. Reviewing the match score before applying can help you focus on the offers that fit you best.";
+
+pub const SALARY_NOTICE: &str =
+    "The most this job pays is below the salary you expect. Your application was sent anyway.";
+
+/// Daily quota key of a seeker's own applications, which OMIL submissions
+/// on their behalf count against too
+pub fn seeker_daily_key(applicant_id: Uuid, day: NaiveDate) -> String {
+    limits::daily_key("applications", applicant_id, day)
+}
+
+/// Daily quota key of an OMIL's submissions on behalf of its seekers
+pub fn omil_daily_key(omil_id: Uuid, day: NaiveDate) -> String {
+    limits::daily_key("omil_applications", omil_id, day)
+}
+
+/// Daily quota taken for a submission, to give back if it then fails
+#[must_use]
+pub struct DailySlots {
+    keys: Vec<String>,
+}
+
+impl DailySlots {
+    pub async fn give_back(self, redis: &mut ConnectionManager) {
+        for key in &self.keys {
+            limits::give_back(redis, key).await;
+        }
+    }
+}
+
+/// Takes today's application from the seeker's quota and, for submissions
+/// made by an OMIL, from the organization's. A cap of 0 is unlimited. When
+/// either is used up nothing is taken and the error says when it resets.
+pub async fn take_daily_slots(
+    state: &AppState,
+    applicant_id: Uuid,
+    omil_id: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<DailySlots> {
+    let mut redis = state.redis.clone();
+    let window = limits::day_window(&state.db, &state.config.platform_timezone, now).await?;
+    let ttl = window.ttl_seconds(now);
+
+    let mut quotas = vec![(
+        seeker_daily_key(applicant_id, window.day),
+        settings::get_int(&state.db, &mut redis, APPLICATIONS_PER_DAY_SETTING).await?,
+        "The job seeker has reached the daily application limit",
+    )];
+    if let Some(omil_id) = omil_id {
+        quotas.push((
+            omil_daily_key(omil_id, window.day),
+            settings::get_int(&state.db, &mut redis, OMIL_APPLICATIONS_PER_DAY_SETTING).await?,
+            "Your organization has reached the daily application limit",
+        ));
+    }
+
+    let mut slots = DailySlots { keys: Vec::new() };
+    for (key, limit, message) in quotas {
+        if limit <= 0 {
+            continue;
+        }
+        if !limits::take(&mut redis, &key, limit, ttl).await {
+            slots.give_back(&mut redis).await;
+            return Err(AppError::QuotaExceeded {
+                message: message.to_string(),
+                resets_at: window.resets_at,
+            });
+        }
+        slots.keys.push(key);
+    }
+    Ok(slots)
+}
+
+/// Notice for a seeker whose last applications were mostly rejected as a
+/// profile mismatch. Only a nudge: it never stops the submission.
+pub async fn mismatch_notice(conn: &mut PgConnection, applicant_id: Uuid) -> Result<Option<String>> {
+    let recent = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) as "total!",
+            COUNT(*) FILTER (WHERE rejection_reason_code = 'profile_mismatch') as "mismatched!"
+        FROM (
+            SELECT rejection_reason_code
+            FROM job_applications
+            WHERE applicant_id = $1
+            ORDER BY applied_at DESC
+            LIMIT $2
+        ) recent
+        "#,
+        applicant_id,
+        MISMATCH_WINDOW,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let notice = recent.total >= MISMATCH_WINDOW
+        && recent.mismatched * 100 > recent.total * MISMATCH_NOTICE_PERCENT;
+    Ok(notice.then(|| MISMATCH_NOTICE.to_string()))
+}
+
+/// Notice for a seeker applying to a job that pays at most less than their
+/// minimum expected salary, in the same currency. Never stops the submission.
+pub async fn salary_notice(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    applicant_id: Uuid,
+) -> Result<Option<String>> {
+    let fit = sqlx::query_scalar!(
+        r#"
+        SELECT salary_expectation_fit(
+            j.salary_min, j.salary_max, j.salary_currency, j.salary_period,
+            p.salary_expectation_min, p.salary_expectation_max,
+            COALESCE(p.salary_currency, 'CLP')
+        ) as "fit: SalaryFit"
+        FROM jobs j
+        JOIN job_seeker_preferences p ON p.user_id = $2
+        WHERE j.id = $1
+        "#,
+        job_id,
+        applicant_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .flatten();
+
+    Ok((fit == Some(SalaryFit::Above)).then(|| SALARY_NOTICE.to_string()))
+}
@@ -0,0 +1,99 @@
+//! Capability checks for handlers, on top of `models::permissions`.
+
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::middleware::AuthUser;
+use crate::models::admin::AdminRole;
+use crate::models::company::MemberRole;
+use crate::models::omil::OmilRole;
+use crate::models::permissions::{
+    self, AuthContext, Capability, CompanyMembership, OmilMembership,
+};
+use crate::models::user::UserType;
+
+/// The session's user with their active memberships and admin level
+pub async fn load_context(db: &PgPool, auth_user: &AuthUser) -> Result<AuthContext> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            u.user_type as "user_type: UserType",
+            cm.company_id as "company_id?",
+            cm.role as "company_role?: MemberRole",
+            om.omil_id as "omil_id?",
+            om.role as "omil_role?: OmilRole",
+            a.admin_role as "admin_role?: AdminRole"
+        FROM users u
+        LEFT JOIN company_members cm ON cm.user_id = u.id AND cm.is_active = true
+        LEFT JOIN LATERAL (
+            SELECT m.omil_id, m.role
+            FROM omil_members m
+            JOIN omil_organizations o ON o.id = m.omil_id
+            WHERE m.user_id = u.id AND m.is_active = true AND o.status = 'active'
+            -- Members of several OMILs act for the one where they rank highest,
+            -- the same one the OMIL middleware picks
+            ORDER BY m.role ASC, m.joined_at ASC, m.id ASC
+            LIMIT 1
+        ) om ON true
+        LEFT JOIN admins a ON a.user_id = u.id
+        WHERE u.id = $1
+        LIMIT 1
+        "#,
+        auth_user.id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::AuthenticationError("User not found".to_string()))?;
+
+    Ok(AuthContext {
+        user_id: auth_user.id,
+        user_type: row.user_type,
+        company: row
+            .company_id
+            .zip(row.company_role)
+            .map(|(company_id, role)| CompanyMembership { company_id, role }),
+        omil: row
+            .omil_id
+            .zip(row.omil_role)
+            .map(|(omil_id, role)| OmilMembership { omil_id, role }),
+        admin_role: row.admin_role,
+        impersonated: auth_user.impersonator_id.is_some(),
+        kiosk: auth_user.kiosk.is_some(),
+    })
+}
+
+/// Everything the session may do
+pub async fn capabilities(db: &PgPool, auth_user: &AuthUser) -> Result<HashSet<Capability>> {
+    let context = load_context(db, auth_user).await?;
+    Ok(permissions::resolve(&context))
+}
+
+/// Refuses unless `capability` is among the session's capabilities
+pub fn require(capabilities: &HashSet<Capability>, capability: Capability) -> Result<()> {
+    if !capabilities.contains(&capability) {
+        return Err(AppError::ForbiddenError(format!(
+            "This session can't {}",
+            capability.action()
+        )));
+    }
+    Ok(())
+}
+
+/// The company the session acts for, once it may do `capability` there
+pub async fn require_company(
+    db: &PgPool,
+    auth_user: &AuthUser,
+    capability: Capability,
+) -> Result<Uuid> {
+    let context = load_context(db, auth_user).await?;
+    require(&permissions::resolve(&context), capability)?;
+
+    // Company capabilities are only granted along with the membership
+    context
+        .company
+        .map(|membership| membership.company_id)
+        .ok_or_else(|| AppError::ForbiddenError("User is not a member of any company".to_string()))
+}
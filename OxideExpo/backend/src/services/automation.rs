@@ -0,0 +1,648 @@
+//! Company funnel automation: per-job rules evaluated when an application is
+//! submitted. Rule CRUD lives here so every read goes through the same
+//! condition decoding; the job handlers only check permissions.
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::AggregateType;
+use crate::models::applicant::{
+    AutomationAction, AutomationCondition, AutomationRuleRequest, AutomationTrigger,
+    JobAutomationRule,
+};
+use crate::models::application::ApplicationStatus;
+use crate::models::notification::NotificationEvent;
+use crate::services::events;
+use crate::services::matching::MatchingService;
+use crate::services::notifications;
+use crate::services::settings;
+use crate::AppState;
+
+/// Platform-wide kill switch; while false no rule runs
+pub const AUTOMATION_ENABLED_SETTING: &str = "job_automation_enabled";
+
+/// Prefix of the application note left by every automated action
+pub const NOTE_PREFIX: &str = "Regla automática";
+
+// ============================================================================
+// CONDITIONS
+// ============================================================================
+
+/// What rule conditions are evaluated against. The match score is only
+/// looked up when a rule asks for it.
+#[derive(Debug, Clone, Copy)]
+pub struct ApplicantFacts {
+    pub match_score: Option<i32>,
+    pub has_cv: bool,
+    pub years_experience: i32,
+}
+
+/// Whether every criterion of `condition` holds for the applicant
+pub fn condition_matches(condition: &AutomationCondition, facts: &ApplicantFacts) -> bool {
+    let score_ok = match (condition.match_score, facts.match_score) {
+        (None, _) => true,
+        (Some(c), Some(score)) => c.op.holds(score, c.value),
+        (Some(_), None) => false,
+    };
+    let cv_ok = condition.has_cv.is_none_or(|wanted| wanted == facts.has_cv);
+    let years_ok = condition
+        .years_experience
+        .is_none_or(|c| c.op.holds(facts.years_experience, c.value));
+
+    score_ok && cv_ok && years_ok
+}
+
+// ============================================================================
+// RULE STORAGE
+// ============================================================================
+
+/// A stored rule before its condition JSON is decoded
+struct RuleRow {
+    id: Uuid,
+    job_id: Uuid,
+    name: String,
+    trigger: AutomationTrigger,
+    condition: serde_json::Value,
+    action: AutomationAction,
+    target_status: Option<ApplicationStatus>,
+    tag_id: Option<Uuid>,
+    notify_user_id: Option<Uuid>,
+    is_enabled: bool,
+    created_by: Option<Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RuleRow {
+    /// None when the stored condition cannot be decoded. Conditions are
+    /// validated on write, so this only trips on rows edited by hand; such a
+    /// rule is skipped rather than read as the empty condition, which would
+    /// match every applicant.
+    fn into_rule(self) -> Option<JobAutomationRule> {
+        let condition = match serde_json::from_value(self.condition) {
+            Ok(condition) => condition,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping automation rule {}: unreadable condition: {}",
+                    self.id,
+                    e
+                );
+                return None;
+            }
+        };
+
+        Some(JobAutomationRule {
+            id: self.id,
+            job_id: self.job_id,
+            name: self.name,
+            trigger: self.trigger,
+            condition,
+            action: self.action,
+            target_status: self.target_status,
+            tag_id: self.tag_id,
+            notify_user_id: self.notify_user_id,
+            is_enabled: self.is_enabled,
+            created_by: self.created_by,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+
+    /// For rows just written from a validated request
+    fn into_written_rule(self) -> Result<JobAutomationRule> {
+        let id = self.id;
+        self.into_rule().ok_or_else(|| {
+            AppError::InternalError(format!("Automation rule {} was stored unreadable", id))
+        })
+    }
+}
+
+pub async fn list_rules(db: &PgPool, job_id: Uuid) -> Result<Vec<JobAutomationRule>> {
+    let rows = sqlx::query_as!(
+        RuleRow,
+        r#"
+        SELECT id, job_id, name,
+               trigger as "trigger: AutomationTrigger",
+               condition,
+               action as "action: AutomationAction",
+               target_status as "target_status: ApplicationStatus",
+               tag_id, notify_user_id, is_enabled, created_by, created_at, updated_at
+        FROM job_automation_rules
+        WHERE job_id = $1
+        ORDER BY created_at, id
+        "#,
+        job_id,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(RuleRow::into_rule).collect())
+}
+
+/// Checks a rule's tag and notified member belong to the job's company
+async fn verify_rule_targets(
+    db: &PgPool,
+    company_id: Uuid,
+    payload: &AutomationRuleRequest,
+) -> Result<()> {
+    if let Some(tag_id) = payload.tag_id {
+        let tag_exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM application_tags WHERE id = $1 AND company_id = $2) as "exists!""#,
+            tag_id,
+            company_id,
+        )
+        .fetch_one(db)
+        .await?;
+
+        if !tag_exists {
+            return Err(AppError::ValidationError("Tag not found".to_string()));
+        }
+    }
+
+    if let Some(user_id) = payload.notify_user_id {
+        let is_member = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM company_members
+                WHERE user_id = $1 AND company_id = $2 AND is_active = true
+            ) as "exists!"
+            "#,
+            user_id,
+            company_id,
+        )
+        .fetch_one(db)
+        .await?;
+
+        if !is_member {
+            return Err(AppError::ValidationError(
+                "Only active members of the company can be notified".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn condition_json(condition: &AutomationCondition) -> Result<serde_json::Value> {
+    serde_json::to_value(condition)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode condition: {}", e)))
+}
+
+pub async fn create_rule(
+    db: &PgPool,
+    company_id: Uuid,
+    job_id: Uuid,
+    created_by: Uuid,
+    payload: &AutomationRuleRequest,
+) -> Result<JobAutomationRule> {
+    verify_rule_targets(db, company_id, payload).await?;
+
+    let mut tx = db.begin().await?;
+
+    // Lock the job so concurrent creates cannot both pass the limit
+    sqlx::query!("SELECT id FROM jobs WHERE id = $1 FOR UPDATE", job_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM job_automation_rules WHERE job_id = $1"#,
+        job_id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if count >= crate::models::applicant::MAX_AUTOMATION_RULES_PER_JOB {
+        return Err(AppError::ValidationError(format!(
+            "A job can have at most {} automation rules",
+            crate::models::applicant::MAX_AUTOMATION_RULES_PER_JOB
+        )));
+    }
+
+    let row = sqlx::query_as!(
+        RuleRow,
+        r#"
+        INSERT INTO job_automation_rules (
+            job_id, name, trigger, condition, action,
+            target_status, tag_id, notify_user_id, is_enabled, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id, job_id, name,
+                  trigger as "trigger: AutomationTrigger",
+                  condition,
+                  action as "action: AutomationAction",
+                  target_status as "target_status: ApplicationStatus",
+                  tag_id, notify_user_id, is_enabled, created_by, created_at, updated_at
+        "#,
+        job_id,
+        payload.name,
+        payload
+            .trigger
+            .unwrap_or(AutomationTrigger::OnApplicationSubmitted) as AutomationTrigger,
+        condition_json(&payload.condition)?,
+        payload.action as AutomationAction,
+        payload.target_status as Option<ApplicationStatus>,
+        payload.tag_id,
+        payload.notify_user_id,
+        payload.is_enabled.unwrap_or(true),
+        created_by,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    row.into_written_rule()
+}
+
+/// Replaces every field of a rule
+pub async fn update_rule(
+    db: &PgPool,
+    company_id: Uuid,
+    job_id: Uuid,
+    rule_id: Uuid,
+    payload: &AutomationRuleRequest,
+) -> Result<JobAutomationRule> {
+    verify_rule_targets(db, company_id, payload).await?;
+
+    let row = sqlx::query_as!(
+        RuleRow,
+        r#"
+        UPDATE job_automation_rules
+        SET name = $3, trigger = $4, condition = $5, action = $6,
+            target_status = $7, tag_id = $8, notify_user_id = $9, is_enabled = $10
+        WHERE id = $1 AND job_id = $2
+        RETURNING id, job_id, name,
+                  trigger as "trigger: AutomationTrigger",
+                  condition,
+                  action as "action: AutomationAction",
+                  target_status as "target_status: ApplicationStatus",
+                  tag_id, notify_user_id, is_enabled, created_by, created_at, updated_at
+        "#,
+        rule_id,
+        job_id,
+        payload.name,
+        payload
+            .trigger
+            .unwrap_or(AutomationTrigger::OnApplicationSubmitted) as AutomationTrigger,
+        condition_json(&payload.condition)?,
+        payload.action as AutomationAction,
+        payload.target_status as Option<ApplicationStatus>,
+        payload.tag_id,
+        payload.notify_user_id,
+        payload.is_enabled.unwrap_or(true),
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Automation rule not found".to_string()))?;
+
+    row.into_written_rule()
+}
+
+pub async fn delete_rule(db: &PgPool, job_id: Uuid, rule_id: Uuid) -> Result<()> {
+    let result = sqlx::query!(
+        "DELETE FROM job_automation_rules WHERE id = $1 AND job_id = $2",
+        rule_id,
+        job_id,
+    )
+    .execute(db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Automation rule not found".to_string()));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// EVALUATION
+// ============================================================================
+
+/// An application as the rules see it
+struct SubmittedApplication {
+    job_id: Uuid,
+    applicant_id: Uuid,
+    status: ApplicationStatus,
+    job_title: String,
+    /// Acts for rules whose creator is gone
+    posted_by: Uuid,
+    has_cv: bool,
+    years_experience: i32,
+}
+
+/// Email to send once the rule actions are committed
+struct MemberNotification {
+    user_id: Uuid,
+    email: String,
+    first_name: String,
+    rule_name: String,
+}
+
+/// Cached match score of the applicant, computed and cached when missing
+async fn match_score(db: &PgPool, job_id: Uuid, user_id: Uuid) -> Result<i32> {
+    if let Some(cached) = MatchingService::get_cached_score(db, job_id, user_id).await? {
+        return Ok(cached.total_score);
+    }
+
+    let breakdown = MatchingService::calculate_match_score(db, job_id, user_id).await?;
+    MatchingService::save_match_score(db, job_id, user_id, &breakdown).await?;
+    Ok(breakdown.total_score)
+}
+
+async fn add_rule_note(
+    conn: &mut PgConnection,
+    application_id: Uuid,
+    author: Uuid,
+    rule_name: &str,
+    detail: &str,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO application_notes (application_id, created_by, note_text, is_important)
+        VALUES ($1, $2, $3, false)
+        "#,
+        application_id,
+        author,
+        format!("{}: {}\n{}", NOTE_PREFIX, rule_name, detail),
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Runs the job's enabled on_application_submitted rules against a freshly
+/// submitted application, in creation order, returning how many acted.
+/// Status changes follow the status state machine, so a rule whose move is
+/// no longer allowed (e.g. an earlier rule rejected the application) is
+/// skipped. Each action leaves a note naming its rule.
+pub async fn on_application_submitted(state: &AppState, application_id: Uuid) -> Result<usize> {
+    let db = &state.db;
+
+    if !settings::get_bool(db, &mut state.redis.clone(), AUTOMATION_ENABLED_SETTING).await? {
+        return Ok(0);
+    }
+
+    let application = sqlx::query_as!(
+        SubmittedApplication,
+        r#"
+        SELECT ja.job_id, ja.applicant_id,
+               ja.status as "status: ApplicationStatus",
+               j.title as job_title, j.posted_by,
+               (ja.resume_url IS NOT NULL OR jsp.cv_file_id IS NOT NULL) as "has_cv!",
+               COALESCE((
+                   SELECT SUM(EXTRACT(YEAR FROM AGE(COALESCE(we.end_date, CURRENT_DATE), we.start_date)))::INTEGER
+                   FROM work_experiences we
+                   WHERE we.user_id = ja.applicant_id
+               ), 0) as "years_experience!"
+        FROM job_applications ja
+        JOIN jobs j ON j.id = ja.job_id
+        LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = ja.applicant_id
+        WHERE ja.id = $1
+        "#,
+        application_id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Application not found".to_string()))?;
+
+    let rules: Vec<JobAutomationRule> = list_rules(db, application.job_id)
+        .await?
+        .into_iter()
+        .filter(|r| r.is_enabled && r.trigger == AutomationTrigger::OnApplicationSubmitted)
+        .collect();
+    if rules.is_empty() {
+        return Ok(0);
+    }
+
+    let score = if rules.iter().any(|r| r.condition.match_score.is_some()) {
+        Some(match_score(db, application.job_id, application.applicant_id).await?)
+    } else {
+        None
+    };
+    let facts = ApplicantFacts {
+        match_score: score,
+        has_cv: application.has_cv,
+        years_experience: application.years_experience,
+    };
+
+    let mut status = application.status;
+    let mut applied = 0;
+    let mut notifications = Vec::new();
+    let mut tx = db.begin().await?;
+
+    for rule in rules
+        .iter()
+        .filter(|r| condition_matches(&r.condition, &facts))
+    {
+        let author = rule.created_by.unwrap_or(application.posted_by);
+
+        let detail = match rule.action {
+            AutomationAction::SetStatus => {
+                let Some(target) = rule.target_status else {
+                    continue;
+                };
+                if !status.can_transition_to(target) {
+                    tracing::debug!(
+                        "Automation rule {} skipped: cannot move {:?} to {:?}",
+                        rule.id,
+                        status,
+                        target
+                    );
+                    continue;
+                }
+
+                sqlx::query!(
+                    r#"
+                    UPDATE job_applications
+                    SET status = $1, reviewed_at = NOW(), reviewed_by = $2
+                    WHERE id = $3
+                    "#,
+                    target as ApplicationStatus,
+                    author,
+                    application_id,
+                )
+                .execute(&mut *tx)
+                .await?;
+                events::emit(
+                    &mut tx,
+                    AggregateType::Application,
+                    application_id,
+                    "status_changed",
+                    None,
+                    serde_json::json!({
+                        "from": status,
+                        "to": target,
+                        "automation_rule_id": rule.id,
+                    }),
+                )
+                .await?;
+
+                let detail = format!("Estado cambiado de {} a {}", status.label(), target.label());
+                status = target;
+                detail
+            }
+            AutomationAction::AddTag => {
+                let Some(tag_id) = rule.tag_id else { continue };
+                let tag_name = sqlx::query_scalar!(
+                    r#"
+                    WITH assigned AS (
+                        INSERT INTO application_tag_assignments (application_id, tag_id, assigned_by)
+                        VALUES ($1, $2, $3)
+                        ON CONFLICT (application_id, tag_id) DO NOTHING
+                    )
+                    SELECT name FROM application_tags WHERE id = $2
+                    "#,
+                    application_id,
+                    tag_id,
+                    author,
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                format!("Etiqueta agregada: {}", tag_name)
+            }
+            AutomationAction::NotifyMember => {
+                let Some(user_id) = rule.notify_user_id else {
+                    continue;
+                };
+                // Members who left the company since the rule was set up are not told
+                let Some(member) = sqlx::query!(
+                    r#"
+                    SELECT u.email as "email!", u.first_name
+                    FROM company_members cm
+                    JOIN users u ON u.id = cm.user_id
+                    JOIN jobs j ON j.company_id = cm.company_id
+                    WHERE cm.user_id = $1 AND cm.is_active = true AND j.id = $2
+                    "#,
+                    user_id,
+                    application.job_id,
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+                else {
+                    continue;
+                };
+
+                let detail = format!("Notificación enviada a {}", member.email);
+                notifications.push(MemberNotification {
+                    user_id,
+                    email: member.email,
+                    first_name: member.first_name,
+                    rule_name: rule.name.clone(),
+                });
+                detail
+            }
+        };
+
+        add_rule_note(&mut tx, application_id, author, &rule.name, &detail).await?;
+        applied += 1;
+    }
+
+    tx.commit().await?;
+
+    for notification in notifications {
+        let job_id = application.job_id;
+        let job_title = application.job_title.clone();
+        notifications::email(
+            state,
+            notification.user_id,
+            NotificationEvent::NewApplication,
+            move |email| async move {
+                email
+                    .send_automation_rule_notification_email(
+                        &notification.email,
+                        &notification.first_name,
+                        &notification.rule_name,
+                        job_id,
+                        &job_title,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::applicant::{Comparator, NumericCondition};
+
+    fn facts(match_score: Option<i32>, has_cv: bool, years_experience: i32) -> ApplicantFacts {
+        ApplicantFacts {
+            match_score,
+            has_cv,
+            years_experience,
+        }
+    }
+
+    #[test]
+    fn test_empty_condition_matches_everyone() {
+        let condition = AutomationCondition::default();
+        assert!(condition_matches(&condition, &facts(None, false, 0)));
+        assert!(condition_matches(&condition, &facts(Some(90), true, 10)));
+    }
+
+    #[test]
+    fn test_every_criterion_must_hold() {
+        // "match >= 80 and a CV"
+        let condition = AutomationCondition {
+            match_score: Some(NumericCondition {
+                op: Comparator::Gte,
+                value: 80,
+            }),
+            has_cv: Some(true),
+            years_experience: None,
+        };
+        assert!(condition_matches(&condition, &facts(Some(80), true, 0)));
+        assert!(!condition_matches(&condition, &facts(Some(79), true, 0)));
+        assert!(!condition_matches(&condition, &facts(Some(95), false, 0)));
+        assert!(!condition_matches(&condition, &facts(None, true, 0)));
+
+        let condition = AutomationCondition {
+            match_score: None,
+            has_cv: Some(false),
+            years_experience: Some(NumericCondition {
+                op: Comparator::Lt,
+                value: 2,
+            }),
+        };
+        assert!(condition_matches(&condition, &facts(None, false, 1)));
+        assert!(!condition_matches(&condition, &facts(None, false, 2)));
+        assert!(!condition_matches(&condition, &facts(None, true, 1)));
+    }
+
+    #[test]
+    fn test_comparators() {
+        assert!(Comparator::Lt.holds(29, 30));
+        assert!(!Comparator::Lt.holds(30, 30));
+        assert!(Comparator::Lte.holds(30, 30));
+        assert!(Comparator::Gt.holds(31, 30));
+        assert!(!Comparator::Gt.holds(30, 30));
+        assert!(Comparator::Gte.holds(30, 30));
+        assert!(Comparator::Eq.holds(30, 30));
+        assert!(!Comparator::Eq.holds(31, 30));
+    }
+
+    #[test]
+    fn test_condition_rejects_unknown_criteria() {
+        let parsed: std::result::Result<AutomationCondition, _> =
+            serde_json::from_value(serde_json::json!({ "salary": { "op": "gt", "value": 1 } }));
+        assert!(parsed.is_err());
+
+        let parsed: AutomationCondition = serde_json::from_value(serde_json::json!({
+            "match_score": { "op": "lt", "value": 30 }
+        }))
+        .unwrap();
+        assert_eq!(
+            parsed.match_score,
+            Some(NumericCondition {
+                op: Comparator::Lt,
+                value: 30
+            })
+        );
+    }
+}
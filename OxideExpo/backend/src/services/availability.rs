@@ -0,0 +1,51 @@
+//! Keeps seekers' availability current. A hire doesn't change it on its own;
+//! the seeker is asked to update it instead.
+
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::notification::NotificationEvent;
+use crate::services::notifications;
+use crate::AppState;
+
+/// Emails the seekers of the given applications that are now hired, asking
+/// them to update their availability. Seekers already marked not_available,
+/// and seekers managed offline without an email, are left alone. Emails are
+/// sent in the background to those who want application updates.
+pub async fn prompt_after_hire(state: &AppState, application_ids: &[Uuid]) -> Result<()> {
+    let hires = sqlx::query!(
+        r#"
+        SELECT u.id, u.email as "email!", u.first_name, j.title as job_title
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        JOIN jobs j ON j.id = ja.job_id
+        LEFT JOIN job_seeker_profiles p ON p.user_id = ja.applicant_id
+        WHERE ja.id = ANY($1)
+          AND ja.status = 'hired'
+          AND u.email IS NOT NULL
+          AND COALESCE(p.availability, 'open_to_offers') <> 'not_available'
+        "#,
+        application_ids
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for hire in hires {
+        notifications::email(
+            state,
+            hire.id,
+            NotificationEvent::ApplicationStatus,
+            move |email| async move {
+                email
+                    .send_availability_prompt_email(
+                        &hire.email,
+                        &hire.first_name,
+                        &hire.job_title,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+//! Out-of-band data migrations. Schema changes stay in `sqlx::migrate!`;
+//! moving data over large tables is a registered [`Backfill`] that the
+//! scheduler runs a batch at a time once an admin starts it.
+//!
+//! Each batch runs in one transaction with the job's row locked: the batch's
+//! writes, the new cursor and the progress count are committed together, so
+//! a worker that dies mid-batch leaves nothing half done and the next one
+//! resumes from the last committed cursor. A failing batch is rolled back
+//! and the backfill stops as failed until it is started again.
+
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use sqlx::{Acquire, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::{BackfillJob, BackfillStatus};
+
+/// How long one scheduler tick keeps running batches
+const TICK_BUDGET: Duration = Duration::from_secs(8);
+
+/// Work done by one batch
+pub struct Batch {
+    /// Rows looked at
+    pub processed: i64,
+    /// Where the next batch starts; None once there is nothing left
+    pub cursor: Option<String>,
+}
+
+/// A data migration run in batches. `process_batch` gets the cursor it
+/// returned last time, None on the first batch, and runs on the
+/// transaction that also records its progress.
+pub trait Backfill: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn default_batch_size(&self) -> i32 {
+        1000
+    }
+
+    fn process_batch<'a>(
+        &'a self,
+        conn: &'a mut PgConnection,
+        cursor: Option<&'a str>,
+        batch_size: i64,
+    ) -> BoxFuture<'a, Result<Batch>>;
+}
+
+/// Every backfill the worker knows how to run
+pub static BACKFILLS: &[&dyn Backfill] = &[&StatusHistoryBackfill];
+
+pub fn find(name: &str) -> Option<&'static dyn Backfill> {
+    BACKFILLS.iter().copied().find(|b| b.name() == name)
+}
+
+/// Adds the job row of a backfill that doesn't have one yet, as pending
+pub async fn register(db: &PgPool, backfill: &dyn Backfill) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO backfill_jobs (name, batch_size)
+        VALUES ($1, $2)
+        ON CONFLICT (name) DO NOTHING
+        "#,
+        backfill.name(),
+        backfill.default_batch_size(),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list(db: &PgPool) -> Result<Vec<BackfillJob>> {
+    for backfill in BACKFILLS {
+        register(db, *backfill).await?;
+    }
+
+    let jobs = sqlx::query_as!(
+        BackfillJob,
+        r#"
+        SELECT
+            name, status as "status: BackfillStatus", cursor, batch_size, processed,
+            last_error, started_at, completed_at, updated_at
+        FROM backfill_jobs
+        ORDER BY name
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Moves a backfill to `to` when it is in one of `from`, or fails with a
+/// conflict carrying `conflict_message`
+pub async fn transition(
+    db: &PgPool,
+    name: &str,
+    from: &[BackfillStatus],
+    to: BackfillStatus,
+    conflict_message: &str,
+) -> Result<BackfillJob> {
+    let backfill =
+        find(name).ok_or_else(|| AppError::NotFound("Backfill not found".to_string()))?;
+    register(db, backfill).await?;
+
+    sqlx::query_as!(
+        BackfillJob,
+        r#"
+        UPDATE backfill_jobs
+        SET
+            status = $2,
+            last_error = CASE WHEN $2 = 'running'::backfill_status THEN NULL ELSE last_error END,
+            started_at = CASE
+                WHEN $2 = 'running'::backfill_status THEN COALESCE(started_at, NOW())
+                ELSE started_at
+            END
+        WHERE name = $1 AND status = ANY($3)
+        RETURNING
+            name, status as "status: BackfillStatus", cursor, batch_size, processed,
+            last_error, started_at, completed_at, updated_at
+        "#,
+        name,
+        to as BackfillStatus,
+        from as &[BackfillStatus],
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::ConflictError(conflict_message.to_string()))
+}
+
+/// Runs the next batch of a running backfill, returning the status it was
+/// left in, or None when it isn't running or another worker holds it
+pub async fn run_batch(db: &PgPool, backfill: &dyn Backfill) -> Result<Option<BackfillStatus>> {
+    let mut tx = db.begin().await?;
+
+    let Some(job) = sqlx::query!(
+        r#"
+        SELECT cursor, batch_size
+        FROM backfill_jobs
+        WHERE name = $1 AND status = 'running'
+        FOR UPDATE SKIP LOCKED
+        "#,
+        backfill.name(),
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut savepoint = (&mut tx).begin().await?;
+    let outcome = backfill
+        .process_batch(&mut savepoint, job.cursor.as_deref(), job.batch_size.into())
+        .await;
+
+    let status = match outcome {
+        Ok(batch) => {
+            savepoint.commit().await?;
+            let status = if batch.cursor.is_some() {
+                BackfillStatus::Running
+            } else {
+                BackfillStatus::Completed
+            };
+            sqlx::query!(
+                r#"
+                UPDATE backfill_jobs
+                SET
+                    status = $2,
+                    cursor = COALESCE($3, cursor),
+                    processed = processed + $4,
+                    completed_at = CASE WHEN $2 = 'completed'::backfill_status THEN NOW() END
+                WHERE name = $1
+                "#,
+                backfill.name(),
+                status as BackfillStatus,
+                batch.cursor,
+                batch.processed,
+            )
+            .execute(&mut *tx)
+            .await?;
+            status
+        }
+        Err(e) => {
+            savepoint.rollback().await?;
+            tracing::error!("Backfill {} failed: {}", backfill.name(), e);
+            sqlx::query!(
+                "UPDATE backfill_jobs SET status = 'failed', last_error = $2 WHERE name = $1",
+                backfill.name(),
+                e.to_string(),
+            )
+            .execute(&mut *tx)
+            .await?;
+            BackfillStatus::Failed
+        }
+    };
+
+    tx.commit().await?;
+    Ok(Some(status))
+}
+
+/// Runs batches of every running backfill for up to one tick's budget,
+/// returning how many batches ran
+pub async fn run_pending(db: &PgPool) -> Result<usize> {
+    let started = Instant::now();
+    let mut batches = 0;
+
+    for backfill in BACKFILLS {
+        while started.elapsed() < TICK_BUDGET {
+            match run_batch(db, *backfill).await? {
+                Some(BackfillStatus::Running) => batches += 1,
+                Some(_) => {
+                    batches += 1;
+                    break;
+                }
+                None => break,
+            }
+        }
+    }
+
+    Ok(batches)
+}
+
+// ============================================================================
+// BACKFILLS
+// ============================================================================
+
+/// Applications whose status changed before status changes were logged have
+/// no history, so their timeline is empty. Each gets one row recording the
+/// status it has now, dated when it was last reviewed. The cursor is the
+/// last application ID looked at.
+pub struct StatusHistoryBackfill;
+
+impl Backfill for StatusHistoryBackfill {
+    fn name(&self) -> &'static str {
+        "application_status_history"
+    }
+
+    fn process_batch<'a>(
+        &'a self,
+        conn: &'a mut PgConnection,
+        cursor: Option<&'a str>,
+        batch_size: i64,
+    ) -> BoxFuture<'a, Result<Batch>> {
+        Box::pin(async move {
+            let after = cursor
+                .map(str::parse::<Uuid>)
+                .transpose()
+                .map_err(|_| AppError::InternalError("Invalid backfill cursor".to_string()))?;
+
+            let batch = sqlx::query!(
+                r#"
+                WITH batch AS (
+                    SELECT id FROM job_applications
+                    WHERE $1::uuid IS NULL OR id > $1
+                    ORDER BY id
+                    LIMIT $2
+                ),
+                inserted AS (
+                    INSERT INTO application_status_history (
+                        application_id, previous_status, new_status, changed_by, created_at
+                    )
+                    SELECT
+                        a.id, NULL, a.status, COALESCE(a.reviewed_by, a.applicant_id),
+                        COALESCE(a.reviewed_at, a.updated_at)
+                    FROM job_applications a
+                    JOIN batch b ON b.id = a.id
+                    WHERE a.status <> 'submitted'
+                    AND NOT EXISTS (
+                        SELECT 1 FROM application_status_history h WHERE h.application_id = a.id
+                    )
+                )
+                SELECT
+                    (SELECT COUNT(*) FROM batch) as "processed!",
+                    (SELECT id FROM batch ORDER BY id DESC LIMIT 1) as last_id
+                "#,
+                after,
+                batch_size,
+            )
+            .fetch_one(&mut *conn)
+            .await?;
+
+            Ok(Batch {
+                processed: batch.processed,
+                cursor: batch.last_id.map(|id| id.to_string()),
+            })
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::company::{BenchmarkScope, PlatformBenchmark};
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Days of activity covered by benchmarks
+pub const PERIOD_DAYS: i32 = 30;
+
+/// Buckets with fewer active jobs fall back to the global benchmark so that
+/// no single company's numbers can be inferred from the percentiles
+pub const MIN_BUCKET_JOBS: i64 = 5;
+
+/// Percentiles scan every active job, so they are only recomputed every 6 hours
+const CACHE_TTL_SECONDS: u64 = 6 * 3600;
+
+const GLOBAL_CACHE_KEY: &str = "benchmarks:global";
+
+/// Redis key for an (industry, region) bucket; jobs without one share the "none" bucket
+pub fn bucket_cache_key(industry_id: Option<Uuid>, region_id: Option<Uuid>) -> String {
+    let part = |id: Option<Uuid>| id.map(|id| id.to_string()).unwrap_or_else(|| "none".into());
+    format!(
+        "benchmarks:industry:{}:region:{}",
+        part(industry_id),
+        part(region_id)
+    )
+}
+
+// ============================================================================
+// BENCHMARKS
+// ============================================================================
+
+/// Platform benchmark for jobs in the given industry and region, falling back
+/// to the global benchmark when the bucket is too small
+pub async fn benchmark_for(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    industry_id: Option<Uuid>,
+    region_id: Option<Uuid>,
+) -> Result<PlatformBenchmark> {
+    let bucket_key = bucket_cache_key(industry_id, region_id);
+    let bucket = cached_or_compute(db, redis, &bucket_key, Some((industry_id, region_id))).await?;
+
+    if bucket.jobs_count >= MIN_BUCKET_JOBS {
+        return Ok(bucket);
+    }
+
+    let global = cached_or_compute(db, redis, GLOBAL_CACHE_KEY, None).await?;
+    Ok(choose_benchmark(bucket, global))
+}
+
+/// Picks the bucket benchmark when it is large enough, otherwise the global one.
+/// When neither is, percentiles are withheld entirely.
+pub fn choose_benchmark(bucket: PlatformBenchmark, global: PlatformBenchmark) -> PlatformBenchmark {
+    if bucket.jobs_count >= MIN_BUCKET_JOBS {
+        return bucket;
+    }
+
+    if global.jobs_count >= MIN_BUCKET_JOBS {
+        return PlatformBenchmark {
+            scope: BenchmarkScope::Global,
+            ..global
+        };
+    }
+
+    PlatformBenchmark {
+        scope: BenchmarkScope::InsufficientData,
+        jobs_count: global.jobs_count,
+        views_median: None,
+        views_p75: None,
+        applications_median: None,
+        applications_p75: None,
+        conversion_median: None,
+        conversion_p75: None,
+    }
+}
+
+/// Reads a benchmark from Redis, computing and storing it on a miss.
+/// Redis failures are logged and the benchmark is computed directly.
+async fn cached_or_compute(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    key: &str,
+    bucket: Option<(Option<Uuid>, Option<Uuid>)>,
+) -> Result<PlatformBenchmark> {
+    match redis.get::<_, Option<String>>(key).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(benchmark) => return Ok(benchmark),
+            Err(e) => tracing::warn!("Discarding unreadable benchmark cache {}: {}", key, e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read benchmark cache {}: {}", key, e),
+    }
+
+    let benchmark = compute_benchmark(db, bucket).await?;
+
+    if let Ok(json) = serde_json::to_string(&benchmark) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(key, json, CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache benchmark {}: {}", key, e);
+        }
+    }
+
+    Ok(benchmark)
+}
+
+/// Median and p75 of views, applications and view→application conversion
+/// over active jobs, either platform-wide or for one (industry, region) bucket
+async fn compute_benchmark(
+    db: &PgPool,
+    bucket: Option<(Option<Uuid>, Option<Uuid>)>,
+) -> Result<PlatformBenchmark> {
+    let (industry_id, region_id) = bucket.unwrap_or_default();
+
+    let row = sqlx::query!(
+        r#"
+        WITH job_stats AS (
+            SELECT
+                COALESCE((
+                    SELECT SUM(v.views) FROM job_view_stats v
+                    WHERE v.job_id = j.id AND v.view_date > CURRENT_DATE - $4::int
+                ), 0)::float8 AS views,
+                (
+                    SELECT COUNT(*) FROM job_applications a
+                    WHERE a.job_id = j.id AND a.applied_at >= NOW() - make_interval(days => $4)
+                )::float8 AS applications
+            FROM jobs j
+            WHERE j.status = 'active'
+            AND ($1::bool OR (j.industry_id IS NOT DISTINCT FROM $2 AND j.region_id IS NOT DISTINCT FROM $3))
+        )
+        SELECT
+            COUNT(*) AS "jobs_count!",
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY views) AS views_median,
+            percentile_cont(0.75) WITHIN GROUP (ORDER BY views) AS views_p75,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY applications) AS applications_median,
+            percentile_cont(0.75) WITHIN GROUP (ORDER BY applications) AS applications_p75,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY applications / views)
+                FILTER (WHERE views > 0) AS conversion_median,
+            percentile_cont(0.75) WITHIN GROUP (ORDER BY applications / views)
+                FILTER (WHERE views > 0) AS conversion_p75
+        FROM job_stats
+        "#,
+        bucket.is_none(),
+        industry_id,
+        region_id,
+        PERIOD_DAYS,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(PlatformBenchmark {
+        scope: if bucket.is_some() {
+            BenchmarkScope::Bucket
+        } else {
+            BenchmarkScope::Global
+        },
+        jobs_count: row.jobs_count,
+        views_median: row.views_median,
+        views_p75: row.views_p75,
+        applications_median: row.applications_median,
+        applications_p75: row.applications_p75,
+        conversion_median: row.conversion_median,
+        conversion_p75: row.conversion_p75,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn benchmark(scope: BenchmarkScope, jobs_count: i64, views_median: f64) -> PlatformBenchmark {
+        PlatformBenchmark {
+            scope,
+            jobs_count,
+            views_median: Some(views_median),
+            views_p75: Some(views_median * 2.0),
+            applications_median: Some(3.0),
+            applications_p75: Some(6.0),
+            conversion_median: Some(0.1),
+            conversion_p75: Some(0.2),
+        }
+    }
+
+    #[test]
+    fn test_bucket_cache_keys_are_separate() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+
+        let keys = [
+            bucket_cache_key(Some(a), Some(b)),
+            bucket_cache_key(Some(b), Some(a)),
+            bucket_cache_key(Some(a), None),
+            bucket_cache_key(None, Some(a)),
+            bucket_cache_key(None, None),
+            GLOBAL_CACHE_KEY.to_string(),
+        ];
+
+        for (i, key) in keys.iter().enumerate() {
+            for other in &keys[i + 1..] {
+                assert_ne!(key, other);
+            }
+        }
+        assert_eq!(
+            bucket_cache_key(Some(a), Some(b)),
+            bucket_cache_key(Some(a), Some(b))
+        );
+    }
+
+    #[test]
+    fn test_large_bucket_is_used() {
+        let chosen = choose_benchmark(
+            benchmark(BenchmarkScope::Bucket, MIN_BUCKET_JOBS, 10.0),
+            benchmark(BenchmarkScope::Global, 100, 50.0),
+        );
+        assert_eq!(chosen.scope, BenchmarkScope::Bucket);
+        assert_eq!(chosen.views_median, Some(10.0));
+    }
+
+    #[test]
+    fn test_small_bucket_falls_back_to_global() {
+        let chosen = choose_benchmark(
+            benchmark(BenchmarkScope::Bucket, MIN_BUCKET_JOBS - 1, 10.0),
+            benchmark(BenchmarkScope::Global, 100, 50.0),
+        );
+        assert_eq!(chosen.scope, BenchmarkScope::Global);
+        assert_eq!(chosen.jobs_count, 100);
+        assert_eq!(chosen.views_median, Some(50.0));
+    }
+
+    #[test]
+    fn test_small_platform_withholds_percentiles() {
+        let chosen = choose_benchmark(
+            benchmark(BenchmarkScope::Bucket, 1, 10.0),
+            benchmark(BenchmarkScope::Global, 3, 50.0),
+        );
+        assert_eq!(chosen.scope, BenchmarkScope::InsufficientData);
+        assert_eq!(chosen.views_median, None);
+        assert_eq!(chosen.conversion_p75, None);
+    }
+}
@@ -0,0 +1,289 @@
+//! What a seeker browsed: the jobs they viewed and the job searches they
+//! ran, kept short and only while their `track_browsing` preference is on.
+
+use serde_json::{Map, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::browsing::{RecentSearch, RecentlyViewedJob};
+use crate::models::job::{JobStatus, PublicJobListQuery};
+
+/// Viewed jobs kept per seeker; older views are pruned
+pub const VIEW_HISTORY_CAP: i64 = 100;
+
+/// Viewed jobs returned by GET /api/me/recently-viewed-jobs
+pub const RECENTLY_VIEWED_LIMIT: i64 = 20;
+
+/// Distinct searches kept per seeker
+pub const RECENT_SEARCHES_CAP: i64 = 10;
+
+/// Whether the seeker lets us keep their browsing; on unless they opted out
+pub async fn tracks_browsing(db: &PgPool, user_id: Uuid) -> Result<bool> {
+    let tracked = sqlx::query_scalar!(
+        "SELECT track_browsing FROM job_seeker_preferences WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(tracked.unwrap_or(true))
+}
+
+/// Moves the job to the front of the seeker's history, pruning views past
+/// the cap
+pub async fn record_view(db: &PgPool, user_id: Uuid, job_id: Uuid) -> Result<()> {
+    if !tracks_browsing(db, user_id).await? {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO job_view_history (user_id, job_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, job_id) DO UPDATE SET viewed_at = NOW()
+        "#,
+        user_id,
+        job_id
+    )
+    .execute(db)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM job_view_history
+        WHERE user_id = $1
+        AND job_id NOT IN (
+            SELECT job_id FROM job_view_history
+            WHERE user_id = $1
+            ORDER BY viewed_at DESC
+            LIMIT $2
+        )
+        "#,
+        user_id,
+        VIEW_HISTORY_CAP
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// The filters of a job search, keyed like its query parameters. None when
+/// nothing is filtered: browsing every job isn't worth remembering.
+pub fn search_filters(params: &PublicJobListQuery) -> Option<Value> {
+    let mut filters = Map::new();
+    let mut add = |key: &str, value: Option<Value>| {
+        if let Some(value) = value {
+            filters.insert(key.to_string(), value);
+        }
+    };
+
+    add("region_id", params.region_id.map(|v| v.to_string().into()));
+    add(
+        "industry_id",
+        params.industry_id.map(|v| v.to_string().into()),
+    );
+    add(
+        "work_area_id",
+        params.work_area_id.map(|v| v.to_string().into()),
+    );
+    add(
+        "job_type",
+        params.job_type.and_then(|v| serde_json::to_value(v).ok()),
+    );
+    add(
+        "work_modality",
+        params
+            .work_modality
+            .and_then(|v| serde_json::to_value(v).ok()),
+    );
+    add(
+        "shift_type",
+        params.shift_type.and_then(|v| serde_json::to_value(v).ok()),
+    );
+    add("weekly_hours_max", params.weekly_hours_max.map(Value::from));
+    add(
+        "is_remote_allowed",
+        params.is_remote_allowed.map(Value::from),
+    );
+    add(
+        "search",
+        params
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(Value::from),
+    );
+    add(
+        "near_municipality_id",
+        params.near_municipality_id.map(|v| v.to_string().into()),
+    );
+    add(
+        "near_me",
+        params.near_me.filter(|near| *near).map(Value::from),
+    );
+    add("radius_km", params.radius_km.map(Value::from));
+
+    (!filters.is_empty()).then_some(Value::Object(filters))
+}
+
+/// Keeps the search as the seeker's latest, pruning searches past the cap.
+/// Repeating a search moves it to the front instead of adding it again.
+pub async fn record_search(db: &PgPool, user_id: Uuid, filters: Value) -> Result<()> {
+    if !tracks_browsing(db, user_id).await? {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO recent_job_searches (user_id, filters)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, filters) DO UPDATE SET searched_at = NOW()
+        "#,
+        user_id,
+        filters
+    )
+    .execute(db)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM recent_job_searches
+        WHERE user_id = $1
+        AND id NOT IN (
+            SELECT id FROM recent_job_searches
+            WHERE user_id = $1
+            ORDER BY searched_at DESC
+            LIMIT $2
+        )
+        "#,
+        user_id,
+        RECENT_SEARCHES_CAP
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Last viewed jobs first, with their current status and whether the seeker
+/// already applied to or saved them
+pub async fn recently_viewed(db: &PgPool, user_id: Uuid) -> Result<Vec<RecentlyViewedJob>> {
+    let jobs = sqlx::query_as!(
+        RecentlyViewedJob,
+        r#"
+        SELECT
+            j.id as job_id,
+            j.title,
+            c.company_name,
+            c.logo_url as company_logo_url,
+            j.status as "status: JobStatus",
+            h.viewed_at,
+            (ja.id IS NOT NULL) as "has_applied!",
+            (sj.id IS NOT NULL) as "is_saved!"
+        FROM job_view_history h
+        JOIN jobs j ON j.id = h.job_id
+        JOIN company_profiles c ON c.id = j.company_id
+        LEFT JOIN job_applications ja ON ja.job_id = h.job_id AND ja.applicant_id = h.user_id
+        LEFT JOIN saved_jobs sj ON sj.job_id = h.job_id AND sj.user_id = h.user_id
+        WHERE h.user_id = $1
+        ORDER BY h.viewed_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        RECENTLY_VIEWED_LIMIT
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Latest searches first
+pub async fn recent_searches(db: &PgPool, user_id: Uuid) -> Result<Vec<RecentSearch>> {
+    let searches = sqlx::query_as!(
+        RecentSearch,
+        r#"
+        SELECT id, filters, searched_at
+        FROM recent_job_searches
+        WHERE user_id = $1
+        ORDER BY searched_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(searches)
+}
+
+/// Forgets everything kept about the seeker's browsing
+pub async fn clear(db: &PgPool, user_id: Uuid) -> Result<()> {
+    sqlx::query!("DELETE FROM job_view_history WHERE user_id = $1", user_id)
+        .execute(db)
+        .await?;
+    sqlx::query!(
+        "DELETE FROM recent_job_searches WHERE user_id = $1",
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job::WorkModality;
+    use serde_json::json;
+
+    fn query() -> PublicJobListQuery {
+        PublicJobListQuery {
+            region_id: None,
+            industry_id: None,
+            work_area_id: None,
+            job_type: None,
+            work_modality: None,
+            shift_type: None,
+            weekly_hours_max: None,
+            is_remote_allowed: None,
+            search: None,
+            near_municipality_id: None,
+            near_me: None,
+            radius_km: None,
+            cursor: None,
+            page: None,
+            per_page: None,
+            limit: None,
+            offset: None,
+            fields: None,
+        }
+    }
+
+    #[test]
+    fn test_unfiltered_search_is_not_kept() {
+        let mut params = query();
+        params.page = Some(3);
+        params.fields = Some("jobs".to_string());
+        params.search = Some("   ".to_string());
+        params.near_me = Some(false);
+        assert_eq!(search_filters(&params), None);
+    }
+
+    #[test]
+    fn test_filters_keep_query_parameter_names() {
+        let mut params = query();
+        params.search = Some(" bodega ".to_string());
+        params.work_modality = Some(WorkModality::Remote);
+        params.weekly_hours_max = Some(30);
+        params.per_page = Some(50);
+
+        assert_eq!(
+            search_filters(&params),
+            Some(json!({ "search": "bodega", "work_modality": "remote", "weekly_hours_max": 30 }))
+        );
+    }
+}
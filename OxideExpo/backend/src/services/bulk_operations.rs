@@ -0,0 +1,275 @@
+use serde_json::json;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::AggregateType;
+use crate::models::applicant::{BulkOperation, BulkOperationStatus};
+use crate::models::application::ApplicationStatus;
+use crate::services::events;
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Applications moved by a single UPDATE
+pub const BULK_CHUNK_SIZE: usize = 1000;
+
+/// Larger selections are queued for the background worker instead of being
+/// updated within the request
+pub const ASYNC_THRESHOLD: usize = 5000;
+
+/// A running operation whose heartbeat is older than this is assumed to have
+/// lost its worker and is picked up again
+const STALE_AFTER_SECONDS: f64 = 300.0;
+
+// ============================================================================
+// CHUNKED UPDATES
+// ============================================================================
+
+/// Index range of chunk number `chunk` within `len` IDs, or None past the end
+pub fn chunk_range(chunk: usize, len: usize) -> Option<std::ops::Range<usize>> {
+    let start = chunk * BULK_CHUNK_SIZE;
+    (start < len).then(|| start..(start + BULK_CHUNK_SIZE).min(len))
+}
+
+/// Moves every application in `ids` that can still legally reach `target` in
+/// one statement, returning the IDs that were updated. Applications changed
+/// by someone else since validation are left alone.
+pub async fn update_chunk(
+    tx: &mut Transaction<'_, Postgres>,
+    job_id: Uuid,
+    ids: &[Uuid],
+    target: ApplicationStatus,
+    reviewed_by: Uuid,
+) -> Result<Vec<Uuid>> {
+    let sources = ApplicationStatus::transition_sources(target);
+
+    let updated = sqlx::query_scalar!(
+        r#"
+        UPDATE job_applications
+        SET status = $1, reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = ANY($3) AND job_id = $4 AND status = ANY($5)
+        RETURNING id
+        "#,
+        target as ApplicationStatus,
+        reviewed_by,
+        ids,
+        job_id,
+        &sources as &[ApplicationStatus],
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    events::emit_many(
+        tx,
+        AggregateType::Application,
+        &updated,
+        "status_changed",
+        Some(reviewed_by),
+        json!({ "to": target, "bulk": true }),
+    )
+    .await?;
+
+    Ok(updated)
+}
+
+// ============================================================================
+// BACKGROUND OPERATIONS
+// ============================================================================
+
+/// Queues a status update over already validated application IDs
+pub async fn enqueue(
+    db: &PgPool,
+    job_id: Uuid,
+    requested_by: Uuid,
+    target: ApplicationStatus,
+    application_ids: &[Uuid],
+    failed_ids: &[Uuid],
+    skipped_count: i32,
+) -> Result<BulkOperation> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO bulk_operations (
+            job_id, requested_by, target_status, application_ids, total, failed_ids, skipped_count
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+        job_id,
+        requested_by,
+        target as ApplicationStatus,
+        application_ids,
+        application_ids.len() as i32,
+        failed_ids,
+        skipped_count,
+    )
+    .fetch_one(db)
+    .await?;
+
+    get_operation(db, job_id, id).await
+}
+
+pub async fn get_operation(db: &PgPool, job_id: Uuid, operation_id: Uuid) -> Result<BulkOperation> {
+    sqlx::query_as!(
+        BulkOperation,
+        r#"
+        SELECT
+            id, job_id, target_status as "target_status: ApplicationStatus",
+            status as "status: BulkOperationStatus",
+            total, processed, updated_count, failed_ids, skipped_count, error,
+            created_at, started_at, completed_at
+        FROM bulk_operations
+        WHERE id = $1 AND job_id = $2
+        "#,
+        operation_id,
+        job_id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Bulk operation not found".to_string()))
+}
+
+/// Claims the oldest queued operation, or a running one whose worker died
+async fn claim_next(db: &PgPool) -> Result<Option<Uuid>> {
+    let id = sqlx::query_scalar!(
+        r#"
+        UPDATE bulk_operations
+        SET status = 'running', started_at = COALESCE(started_at, NOW()), heartbeat_at = NOW()
+        WHERE id = (
+            SELECT id FROM bulk_operations
+            WHERE status = 'queued'
+            OR (status = 'running' AND heartbeat_at < NOW() - make_interval(secs => $1))
+            ORDER BY created_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id
+        "#,
+        STALE_AFTER_SECONDS,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Processes the remaining chunks of an operation. Each chunk's update and
+/// the progress counters are committed together, so stopping at any point
+/// never repeats or loses a chunk.
+pub async fn run_operation(db: &PgPool, operation_id: Uuid) -> Result<()> {
+    loop {
+        let mut tx = db.begin().await?;
+
+        let op = sqlx::query!(
+            r#"
+            SELECT
+                job_id, requested_by, target_status as "target_status: ApplicationStatus",
+                application_ids, next_chunk
+            FROM bulk_operations
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            operation_id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let Some(range) = chunk_range(op.next_chunk as usize, op.application_ids.len()) else {
+            sqlx::query!(
+                r#"
+                UPDATE bulk_operations
+                SET status = 'done', completed_at = NOW(), heartbeat_at = NOW()
+                WHERE id = $1
+                "#,
+                operation_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(());
+        };
+
+        let chunk = &op.application_ids[range];
+        let updated =
+            update_chunk(&mut tx, op.job_id, chunk, op.target_status, op.requested_by).await?;
+        let missed: Vec<Uuid> = chunk
+            .iter()
+            .copied()
+            .filter(|id| !updated.contains(id))
+            .collect();
+
+        sqlx::query!(
+            r#"
+            UPDATE bulk_operations
+            SET
+                next_chunk = next_chunk + 1,
+                processed = processed + $2,
+                updated_count = updated_count + $3,
+                failed_ids = failed_ids || $4,
+                heartbeat_at = NOW()
+            WHERE id = $1
+            "#,
+            operation_id,
+            chunk.len() as i32,
+            updated.len() as i32,
+            &missed,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+    }
+}
+
+/// Runs every pending operation, returning how many were completed
+pub async fn process_pending(db: &PgPool) -> Result<usize> {
+    let mut completed = 0;
+
+    while let Some(operation_id) = claim_next(db).await? {
+        if let Err(e) = run_operation(db, operation_id).await {
+            tracing::error!("Bulk operation {} failed: {}", operation_id, e);
+            sqlx::query!(
+                r#"
+                UPDATE bulk_operations
+                SET status = 'failed', error = $2, completed_at = NOW()
+                WHERE id = $1
+                "#,
+                operation_id,
+                e.to_string(),
+            )
+            .execute(db)
+            .await?;
+            continue;
+        }
+        completed += 1;
+    }
+
+    Ok(completed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejection_sources_exclude_terminal_statuses() {
+        let sources = ApplicationStatus::transition_sources(ApplicationStatus::Rejected);
+        assert_eq!(sources.len(), 5);
+        assert!(!sources.contains(&ApplicationStatus::Hired));
+        assert!(!sources.contains(&ApplicationStatus::Rejected));
+        assert!(!sources.contains(&ApplicationStatus::Withdrawn));
+    }
+
+    #[test]
+    fn test_chunk_boundaries() {
+        let size = BULK_CHUNK_SIZE;
+
+        assert_eq!(chunk_range(0, 0), None);
+        assert_eq!(chunk_range(0, size), Some(0..size));
+        assert_eq!(chunk_range(1, size), None);
+        assert_eq!(chunk_range(1, size + 1), Some(size..size + 1));
+        assert_eq!(chunk_range(2, size * 2 + 1), Some(size * 2..size * 2 + 1));
+        assert_eq!(chunk_range(3, size * 3), None);
+    }
+}
@@ -0,0 +1,373 @@
+//! iCalendar (RFC 5545) feeds of scheduled interviews, for recruiters and
+//! seekers to subscribe to from Outlook, Google Calendar and the like.
+//! Interviews are the applications with an interview_date; the feed only
+//! carries the ones that haven't ended.
+//!
+//! Calendar apps can't send a JWT, so company members can also read their
+//! feed with a secret calendar token in the URL. A member has at most one;
+//! it's stored hashed, like API tokens, and revoking it breaks the URL.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::models::calendar::CalendarToken;
+use crate::utils::jwt;
+
+/// Timezone of the times in the feeds, described by VTIMEZONE below
+pub const CALENDAR_TIMEZONE: &str = "America/Santiago";
+
+/// Interviews have no end time; calendars show them this long
+pub const INTERVIEW_MINUTES: i32 = 60;
+
+/// Characters of the calendar token kept in clear for display ("cal_" plus 8)
+const TOKEN_PREFIX_LEN: usize = 12;
+
+/// Chile's rules since 2023: standard time (-04) from the first Sunday on or
+/// after April 2, daylight time (-03) from the first Sunday on or after
+/// September 2, both at local midnight
+const VTIMEZONE: &[&str] = &[
+    "BEGIN:VTIMEZONE",
+    "TZID:America/Santiago",
+    "BEGIN:STANDARD",
+    "DTSTART:19700405T000000",
+    "TZOFFSETFROM:-0300",
+    "TZOFFSETTO:-0400",
+    "TZNAME:-04",
+    "RRULE:FREQ=YEARLY;BYMONTH=4;BYMONTHDAY=2,3,4,5,6,7,8;BYDAY=SU",
+    "END:STANDARD",
+    "BEGIN:DAYLIGHT",
+    "DTSTART:19700906T000000",
+    "TZOFFSETFROM:-0400",
+    "TZOFFSETTO:-0300",
+    "TZNAME:-03",
+    "RRULE:FREQ=YEARLY;BYMONTH=9;BYMONTHDAY=2,3,4,5,6,7,8;BYDAY=SU",
+    "END:DAYLIGHT",
+    "END:VTIMEZONE",
+];
+
+/// One interview as a VEVENT
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Application the interview belongs to; the UID is derived from it so
+    /// rescheduling updates the event instead of adding another
+    pub application_id: Uuid,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// Wall-clock time in CALENDAR_TIMEZONE
+    pub starts_at: NaiveDateTime,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// FEEDS
+// ============================================================================
+
+/// Interviews of the company's applicants. Every active member sees all of
+/// them, as in the applicant views.
+pub async fn company_interviews(db: &PgPool, company_id: Uuid) -> Result<Vec<CalendarEvent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            ja.id,
+            ja.interview_date AT TIME ZONE $2 as "starts_at!",
+            ja.interview_location,
+            ja.interview_notes,
+            ja.updated_at,
+            u.first_name,
+            u.last_name,
+            j.title as job_title
+        FROM job_applications ja
+        JOIN jobs j ON j.id = ja.job_id
+        JOIN users u ON u.id = ja.applicant_id
+        WHERE j.company_id = $1
+        AND ja.interview_date + make_interval(mins => $3) > NOW()
+        AND ja.status NOT IN ('rejected', 'withdrawn')
+        ORDER BY ja.interview_date
+        "#,
+        company_id,
+        CALENDAR_TIMEZONE,
+        INTERVIEW_MINUTES
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CalendarEvent {
+            application_id: row.id,
+            summary: format!(
+                "Entrevista: {} - {}",
+                short_name(&row.first_name, &row.last_name),
+                row.job_title
+            ),
+            description: row.interview_notes,
+            location: row.interview_location,
+            starts_at: row.starts_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+/// The seeker's own interviews
+pub async fn seeker_interviews(db: &PgPool, user_id: Uuid) -> Result<Vec<CalendarEvent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            ja.id,
+            ja.interview_date AT TIME ZONE $2 as "starts_at!",
+            ja.interview_location,
+            ja.updated_at,
+            j.title as job_title,
+            cp.company_name
+        FROM job_applications ja
+        JOIN jobs j ON j.id = ja.job_id
+        JOIN company_profiles cp ON cp.id = j.company_id
+        WHERE ja.applicant_id = $1
+        AND ja.interview_date + make_interval(mins => $3) > NOW()
+        AND ja.status NOT IN ('rejected', 'withdrawn')
+        ORDER BY ja.interview_date
+        "#,
+        user_id,
+        CALENDAR_TIMEZONE,
+        INTERVIEW_MINUTES
+    )
+    .fetch_all(db)
+    .await?;
+
+    // Interview notes are the company's own, so seekers don't get them
+    Ok(rows
+        .into_iter()
+        .map(|row| CalendarEvent {
+            application_id: row.id,
+            summary: format!("Entrevista: {} - {}", row.job_title, row.company_name),
+            description: None,
+            location: row.interview_location,
+            starts_at: row.starts_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+/// "María G." - the candidate's first name and last initial
+fn short_name(first_name: &str, last_name: &str) -> String {
+    match last_name.trim().chars().next() {
+        Some(initial) => format!("{} {}.", first_name.trim(), initial.to_uppercase()),
+        None => first_name.trim().to_string(),
+    }
+}
+
+// ============================================================================
+// ICALENDAR
+// ============================================================================
+
+/// The feed as an iCalendar document named `name`
+pub fn render(name: &str, events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//EmpleosInclusivos//Entrevistas//ES".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+        format!("X-WR-TIMEZONE:{}", CALENDAR_TIMEZONE),
+    ];
+    lines.extend(VTIMEZONE.iter().map(|line| line.to_string()));
+
+    for event in events {
+        let ends_at = event.starts_at + chrono::Duration::minutes(INTERVIEW_MINUTES as i64);
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:interview-{}@empleosinclusivos.cl",
+            event.application_id
+        ));
+        lines.push(format!("DTSTAMP:{}", utc_stamp(now)));
+        lines.push(format!("LAST-MODIFIED:{}", utc_stamp(event.updated_at)));
+        lines.push(format!(
+            "DTSTART;TZID={}:{}",
+            CALENDAR_TIMEZONE,
+            event.starts_at.format("%Y%m%dT%H%M%S")
+        ));
+        lines.push(format!(
+            "DTEND;TZID={}:{}",
+            CALENDAR_TIMEZONE,
+            ends_at.format("%Y%m%dT%H%M%S")
+        ));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = event.location.as_deref().filter(|l| !l.trim().is_empty()) {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = event
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("STATUS:CONFIRMED".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn utc_stamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value: backslashes, semicolons, commas and line breaks
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                escaped.push_str("\\n");
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into lines of at most 75 octets, continuation
+/// lines starting with a space, each ending in CRLF. Never splits a
+/// multi-byte character.
+fn fold(line: &str) -> String {
+    const LIMIT: usize = 75;
+    let mut folded = String::with_capacity(line.len() + line.len() / LIMIT * 3 + 2);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LIMIT {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the limit
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+// ============================================================================
+// CALENDAR TOKENS
+// ============================================================================
+
+/// The member's calendar token. Creates one when they have none, which is the
+/// only time the token itself is returned.
+pub async fn get_or_create_token(
+    db: &PgPool,
+    config: &Config,
+    user_id: Uuid,
+) -> Result<CalendarToken> {
+    let token = jwt::create_calendar_token();
+    let created = sqlx::query!(
+        r#"
+        INSERT INTO calendar_tokens (user_id, token_prefix, token_hash)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO NOTHING
+        RETURNING created_at
+        "#,
+        user_id,
+        &token[..TOKEN_PREFIX_LEN],
+        jwt::hash_token(&token)
+    )
+    .fetch_optional(db)
+    .await?;
+
+    if let Some(created) = created {
+        return Ok(CalendarToken {
+            token_prefix: token[..TOKEN_PREFIX_LEN].to_string(),
+            feed_url: Some(format!(
+                "{}/api/me/company/interviews.ics?token={}",
+                config.app_base_url, token
+            )),
+            token: Some(token),
+            created_at: created.created_at,
+            last_used_at: None,
+        });
+    }
+
+    let existing = sqlx::query!(
+        "SELECT token_prefix, created_at, last_used_at FROM calendar_tokens WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(CalendarToken {
+        token: None,
+        token_prefix: existing.token_prefix,
+        feed_url: None,
+        created_at: existing.created_at,
+        last_used_at: existing.last_used_at,
+    })
+}
+
+/// Deletes the member's calendar token, returning whether they had one
+pub async fn revoke_token(db: &PgPool, user_id: Uuid) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM calendar_tokens WHERE user_id = $1", user_id)
+        .execute(db)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// The user a calendar token belongs to, while their account is active
+pub async fn user_for_token(db: &PgPool, token: &str) -> Result<Option<Uuid>> {
+    let user_id = sqlx::query_scalar!(
+        r#"
+        UPDATE calendar_tokens t SET last_used_at = NOW()
+        FROM users u
+        WHERE t.token_hash = $1
+          AND u.id = t.user_id
+          AND u.account_status = 'active'
+        RETURNING t.user_id
+        "#,
+        jwt::hash_token(token)
+    )
+    .fetch_optional(db)
+    .await?;
+    Ok(user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(
+            escape_text("Av. Providencia 123, of. 4; piso 2\\B\r\nTocar timbre\n"),
+            "Av. Providencia 123\\, of. 4\\; piso 2\\\\B\\nTocar timbre\\n"
+        );
+    }
+
+    #[test]
+    fn test_fold_keeps_lines_short_and_characters_whole() {
+        let line = format!("SUMMARY:{}", "ñ".repeat(100));
+        let folded = fold(&line);
+        assert!(folded.ends_with("\r\n"));
+        for (i, part) in folded.trim_end_matches("\r\n").split("\r\n").enumerate() {
+            assert!(part.len() <= 75);
+            assert_eq!(part.starts_with(' '), i > 0);
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+
+    #[test]
+    fn test_short_name() {
+        assert_eq!(short_name("María", "González"), "María G.");
+        assert_eq!(short_name("José", "émile"), "José É.");
+        assert_eq!(short_name("Ana", " "), "Ana");
+    }
+}
@@ -0,0 +1,450 @@
+//! Removing a company in two phases. Winding it down closes its active jobs,
+//! deactivates its members and hides it, and can be undone by reopening it.
+//! Purging it, once the waiting period is over, anonymizes the profile and
+//! archives its jobs: applicants keep reading their applications, with the
+//! company shown as "Empresa eliminada". Every step is logged with a snapshot
+//! of the company row as it was before.
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::company::OrganizationStatus;
+use crate::models::notification::NotificationEvent;
+use crate::services::notifications;
+use crate::AppState;
+
+/// Days between winding a company down and purging it without force
+pub const PURGE_AFTER_DAYS: i64 = 30;
+
+/// Shown in place of a purged company's name
+pub const DELETED_COMPANY_NAME: &str = "Empresa eliminada";
+
+/// Someone with a live application to a job closed by the wind-down
+pub struct ClosedJobApplicant {
+    pub user_id: Uuid,
+    pub email: String,
+    pub first_name: String,
+    pub job_title: String,
+}
+
+pub struct WindDown {
+    pub company_name: String,
+    pub jobs_closed: i64,
+    pub members_deactivated: i64,
+    pub applicants: Vec<ClosedJobApplicant>,
+    pub purge_after: DateTime<Utc>,
+}
+
+pub struct Reopened {
+    pub status: OrganizationStatus,
+    pub jobs_reopened: i64,
+    pub members_reactivated: i64,
+}
+
+pub struct Purge {
+    pub jobs_archived: i64,
+    /// Logo and cover objects, removed from storage once the purge commits
+    pub storage_paths: Vec<String>,
+}
+
+// ============================================================================
+// PHASE ONE: WIND DOWN / REOPEN
+// ============================================================================
+
+/// Closes the company's active jobs, deactivates its members and marks it
+/// closing, remembering what changed so it can be reopened
+pub async fn wind_down(
+    conn: &mut PgConnection,
+    company_id: Uuid,
+    requested_by: Uuid,
+) -> Result<WindDown> {
+    let (status, snapshot) = lock(&mut *conn, company_id).await?;
+    if matches!(
+        status,
+        OrganizationStatus::Closing | OrganizationStatus::Deleted
+    ) {
+        return Err(AppError::ValidationError(
+            "Company is already being deleted".to_string(),
+        ));
+    }
+
+    let closed_job_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE jobs SET status = 'closed', updated_at = NOW()
+        WHERE company_id = $1 AND status = 'active'
+        RETURNING id
+        "#,
+        company_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let member_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE company_members SET is_active = false, updated_at = NOW()
+        WHERE company_id = $1 AND is_active = true
+        RETURNING id
+        "#,
+        company_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let wound_down_at = sqlx::query_scalar!(
+        r#"
+        INSERT INTO company_deletions
+            (company_id, prior_status, closed_job_ids, deactivated_member_ids, requested_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING wound_down_at
+        "#,
+        company_id,
+        status as OrganizationStatus,
+        &closed_job_ids,
+        &member_ids,
+        requested_by
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let company_name = sqlx::query_scalar!(
+        r#"
+        UPDATE company_profiles SET status = 'closing', updated_at = NOW()
+        WHERE id = $1
+        RETURNING company_name
+        "#,
+        company_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let applicants = sqlx::query_as!(
+        ClosedJobApplicant,
+        r#"
+        SELECT u.id as user_id, u.email as "email!", u.first_name, j.title as job_title
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        JOIN jobs j ON j.id = ja.job_id
+        WHERE ja.job_id = ANY($1)
+          AND ja.status NOT IN ('hired', 'rejected', 'withdrawn')
+          -- Seekers managed offline have no email to notify
+          AND u.email IS NOT NULL
+        ORDER BY j.title, ja.applied_at
+        "#,
+        &closed_job_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let wind_down = WindDown {
+        company_name,
+        jobs_closed: closed_job_ids.len() as i64,
+        members_deactivated: member_ids.len() as i64,
+        applicants,
+        purge_after: wound_down_at + Duration::days(PURGE_AFTER_DAYS),
+    };
+
+    record(
+        &mut *conn,
+        company_id,
+        "wind_down",
+        requested_by,
+        snapshot,
+        json!({
+            "closed_job_ids": closed_job_ids,
+            "deactivated_member_ids": member_ids,
+            "applicants_notified": wind_down.applicants.len(),
+        }),
+    )
+    .await?;
+
+    Ok(wind_down)
+}
+
+/// Undoes a wind-down: the company gets its prior status back, and the jobs
+/// and members it closed come back unless they changed in the meantime
+pub async fn reopen(
+    conn: &mut PgConnection,
+    company_id: Uuid,
+    performed_by: Uuid,
+) -> Result<Reopened> {
+    let (status, snapshot) = lock(&mut *conn, company_id).await?;
+    if status != OrganizationStatus::Closing {
+        return Err(AppError::ValidationError(
+            "Only companies that are winding down can be reopened".to_string(),
+        ));
+    }
+
+    let deletion = sqlx::query!(
+        r#"
+        DELETE FROM company_deletions
+        WHERE company_id = $1
+        RETURNING
+            prior_status as "prior_status: OrganizationStatus",
+            closed_job_ids,
+            deactivated_member_ids
+        "#,
+        company_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE company_profiles
+        SET status = $2, updated_at = NOW()
+        WHERE id = $1
+        "#,
+        company_id,
+        deletion.prior_status as OrganizationStatus
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let jobs_reopened = sqlx::query!(
+        r#"
+        UPDATE jobs SET status = 'active', updated_at = NOW()
+        WHERE id = ANY($1) AND company_id = $2 AND status = 'closed'
+        "#,
+        &deletion.closed_job_ids,
+        company_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected() as i64;
+
+    let members_reactivated = sqlx::query!(
+        r#"
+        UPDATE company_members SET is_active = true, updated_at = NOW()
+        WHERE id = ANY($1) AND company_id = $2 AND is_active = false
+        "#,
+        &deletion.deactivated_member_ids,
+        company_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected() as i64;
+
+    record(
+        &mut *conn,
+        company_id,
+        "reopen",
+        performed_by,
+        snapshot,
+        json!({
+            "jobs_reopened": jobs_reopened,
+            "members_reactivated": members_reactivated,
+        }),
+    )
+    .await?;
+
+    Ok(Reopened {
+        status: deletion.prior_status,
+        jobs_reopened,
+        members_reactivated,
+    })
+}
+
+/// Tells the applicants of the closed jobs, in the background
+pub fn notify_applicants(
+    state: &AppState,
+    company_name: &str,
+    applicants: Vec<ClosedJobApplicant>,
+) {
+    for applicant in applicants {
+        let company_name = company_name.to_string();
+        notifications::email(
+            state,
+            applicant.user_id,
+            NotificationEvent::ApplicationStatus,
+            move |email| async move {
+                email
+                    .send_job_closed_email(
+                        &applicant.email,
+                        &applicant.first_name,
+                        &applicant.job_title,
+                        &company_name,
+                    )
+                    .await
+            },
+        );
+    }
+}
+
+// ============================================================================
+// PHASE TWO: PURGE
+// ============================================================================
+
+/// Anonymizes a wound-down company and archives all its jobs. Refused before
+/// the waiting period is over unless forced.
+pub async fn purge(
+    conn: &mut PgConnection,
+    company_id: Uuid,
+    performed_by: Uuid,
+    force: bool,
+) -> Result<Purge> {
+    let (status, snapshot) = lock(&mut *conn, company_id).await?;
+    if status != OrganizationStatus::Closing {
+        return Err(AppError::ValidationError(
+            "Company must be wound down before it is purged".to_string(),
+        ));
+    }
+
+    let wound_down_at = sqlx::query_scalar!(
+        "SELECT wound_down_at FROM company_deletions WHERE company_id = $1",
+        company_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    let purge_after = wound_down_at + Duration::days(PURGE_AFTER_DAYS);
+    if !force && purge_after > Utc::now() {
+        return Err(AppError::ValidationError(format!(
+            "Company can be purged after {}; use force to purge it now",
+            purge_after.to_rfc3339()
+        )));
+    }
+
+    let files = sqlx::query!(
+        r#"
+        DELETE FROM uploaded_files
+        WHERE id IN (
+            SELECT logo_file_id FROM company_profiles WHERE id = $1
+            UNION
+            SELECT cover_file_id FROM company_profiles WHERE id = $1
+        )
+        RETURNING storage_path
+        "#,
+        company_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE company_profiles
+        SET company_name = $2,
+            legal_name = NULL,
+            tax_id = NULL,
+            address = NULL,
+            phone = NULL,
+            website_url = NULL,
+            linkedin_url = NULL,
+            video_url = NULL,
+            logo_url = NULL,
+            cover_image_url = NULL,
+            logo_file_id = NULL,
+            cover_file_id = NULL,
+            description = NULL,
+            mission = NULL,
+            vision = NULL,
+            culture = NULL,
+            benefits = NULL,
+            is_featured = false,
+            can_search_candidates = false,
+            status = 'deleted',
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        company_id,
+        DELETED_COMPANY_NAME
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Contact details pointed at the company; the rest of the posting stays
+    // for the applicants who read it
+    let jobs_archived = sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET status = 'archived_deleted',
+            contact_email = NULL,
+            application_url = NULL,
+            is_featured = false,
+            updated_at = NOW()
+        WHERE company_id = $1
+        "#,
+        company_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected() as i64;
+
+    sqlx::query!(
+        r#"
+        UPDATE company_deletions SET purged_by = $2, purged_at = NOW()
+        WHERE company_id = $1
+        "#,
+        company_id,
+        performed_by
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    record(
+        &mut *conn,
+        company_id,
+        "purge",
+        performed_by,
+        snapshot,
+        json!({
+            "forced": force,
+            "jobs_archived": jobs_archived,
+            "files_deleted": files.len(),
+        }),
+    )
+    .await?;
+
+    Ok(Purge {
+        jobs_archived,
+        storage_paths: files.into_iter().map(|f| f.storage_path).collect(),
+    })
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Locks the company, returning its status and the row as it is now
+async fn lock(conn: &mut PgConnection, company_id: Uuid) -> Result<(OrganizationStatus, Value)> {
+    let company = sqlx::query!(
+        r#"
+        SELECT c.status as "status: OrganizationStatus", to_jsonb(c) as "snapshot!"
+        FROM company_profiles c
+        WHERE c.id = $1
+        FOR UPDATE
+        "#,
+        company_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
+
+    Ok((company.status, company.snapshot))
+}
+
+async fn record(
+    conn: &mut PgConnection,
+    company_id: Uuid,
+    action: &str,
+    performed_by: Uuid,
+    snapshot: Value,
+    details: Value,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO company_deletion_events (company_id, action, performed_by, snapshot, details)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        company_id,
+        action,
+        performed_by,
+        snapshot,
+        details
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,721 @@
+//! Self-service export of everything a company has on the platform: its job
+//! postings, the applicants of each job with their notes, and its profile and
+//! members. Exports are queued and built by a background worker into a ZIP
+//! that is uploaded to storage as it is written, then downloadable through a
+//! signed URL for EXPORT_VALID_DAYS.
+//!
+//! Applicant personal data is included only for people who applied to the
+//! company's jobs; talent search and recommendation data is never exported.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use object_store::WriteMultipart;
+use rust_xlsxwriter::{Format, Workbook};
+use sqlx::PgPool;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::{AppError, Result};
+use crate::models::company::{CompanyDataExport, DataExportStatus};
+use crate::models::notification::NotificationEvent;
+use crate::services::notifications;
+use crate::services::storage::StorageBackend;
+use crate::AppState;
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Days a finished export can be downloaded before its file is removed
+pub const EXPORT_VALID_DAYS: i64 = 7;
+
+/// A running export whose heartbeat is older than this is assumed to have
+/// lost its worker and is picked up again
+const STALE_AFTER_SECONDS: f64 = 600.0;
+
+/// Upload parts sent to storage at once
+const MAX_CONCURRENT_PARTS: usize = 2;
+
+const JOB_COLUMNS: [&str; 35] = [
+    "ID",
+    "Title",
+    "Status",
+    "Job Type",
+    "Work Modality",
+    "Shift Type",
+    "Weekly Hours",
+    "Schedule Details",
+    "Industry",
+    "Work Area",
+    "Position Level",
+    "Region",
+    "Municipality",
+    "Remote Allowed",
+    "Description",
+    "Responsibilities",
+    "Education Level",
+    "Min Years Experience",
+    "Max Years Experience",
+    "Min Age",
+    "Max Age",
+    "Min Salary",
+    "Max Salary",
+    "Salary Currency",
+    "Salary Period",
+    "Benefits",
+    "Application Deadline",
+    "Contact Email",
+    "Application URL",
+    "Vacancies",
+    "Applications",
+    "Views",
+    "Posted By",
+    "Created At",
+    "Published At",
+];
+
+const APPLICANT_COLUMNS: [&str; 19] = [
+    "Application ID",
+    "First Name",
+    "Last Name",
+    "Email",
+    "Phone",
+    "Status",
+    "Applied At",
+    "Reviewed At",
+    "Cover Letter",
+    "Resume URL",
+    "Tags",
+    "Interview Date",
+    "Interview Notes",
+    "Offer Date",
+    "Offer Details",
+    "Rejection Reason",
+    "Rejection Feedback",
+    "Withdrawal Reason",
+    "Notes",
+];
+
+// ============================================================================
+// QUEUE
+// ============================================================================
+
+/// Queues an export of the company's data. A company has at most one export
+/// queued or running at a time.
+pub async fn enqueue(db: &PgPool, company_id: Uuid, requested_by: Uuid) -> Result<Uuid> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO company_data_exports (company_id, requested_by)
+        VALUES ($1, $2)
+        RETURNING id
+        "#,
+        company_id,
+        requested_by,
+    )
+    .fetch_one(db)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::ConflictError("A data export is already in progress".to_string())
+        }
+        _ => AppError::DatabaseError(e),
+    })
+}
+
+/// The export with a download URL while its file is available
+pub async fn get_export(
+    db: &PgPool,
+    storage: &dyn StorageBackend,
+    company_id: Uuid,
+    export_id: Uuid,
+) -> Result<CompanyDataExport> {
+    let export = sqlx::query!(
+        r#"
+        SELECT
+            id, status as "status: DataExportStatus", total_jobs, processed_jobs,
+            storage_path, file_size, error, created_at, started_at, completed_at, expires_at
+        FROM company_data_exports
+        WHERE id = $1 AND company_id = $2
+        "#,
+        export_id,
+        company_id,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Data export not found".to_string()))?;
+
+    let remaining = export
+        .expires_at
+        .map(|expires_at| (expires_at - Utc::now()).num_seconds())
+        .filter(|&seconds| seconds > 0);
+    let download_url = match (&export.storage_path, remaining) {
+        (Some(path), Some(seconds)) => Some(
+            storage
+                .presign_get(path, Duration::from_secs(seconds as u64))
+                .await?,
+        ),
+        _ => None,
+    };
+
+    Ok(CompanyDataExport {
+        id: export.id,
+        status: export.status,
+        total_jobs: export.total_jobs,
+        processed_jobs: export.processed_jobs,
+        file_size: export.file_size,
+        error: export.error,
+        created_at: export.created_at,
+        started_at: export.started_at,
+        completed_at: export.completed_at,
+        expires_at: export.expires_at,
+        download_url,
+    })
+}
+
+// ============================================================================
+// WORKER
+// ============================================================================
+
+/// Claims the oldest queued export, or a running one whose worker died
+async fn claim_next(db: &PgPool) -> Result<Option<Uuid>> {
+    let id = sqlx::query_scalar!(
+        r#"
+        UPDATE company_data_exports
+        SET status = 'running', started_at = COALESCE(started_at, NOW()), heartbeat_at = NOW(),
+            processed_jobs = 0
+        WHERE id = (
+            SELECT id FROM company_data_exports
+            WHERE status = 'queued'
+            OR (status = 'running' AND heartbeat_at < NOW() - make_interval(secs => $1))
+            ORDER BY created_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id
+        "#,
+        STALE_AFTER_SECONDS,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(id)
+}
+
+/// Builds the export's ZIP and uploads it. A reclaimed export starts over
+/// with a fresh file.
+pub async fn run_export(db: &PgPool, storage: &dyn StorageBackend, export_id: Uuid) -> Result<()> {
+    let company_id = sqlx::query_scalar!(
+        "SELECT company_id FROM company_data_exports WHERE id = $1",
+        export_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    let storage_path = format!("company-exports/{}/{}.zip", company_id, Uuid::new_v4());
+    let mut upload = storage.start_upload(&storage_path).await?;
+    let file_size = match write_archive(db, export_id, company_id, &mut upload).await {
+        Ok(file_size) => file_size,
+        Err(e) => {
+            if let Err(abort_err) = upload.abort().await {
+                tracing::warn!("Failed to abort data export upload: {}", abort_err);
+            }
+            return Err(e);
+        }
+    };
+    upload.finish().await.map_err(upload_err)?;
+
+    sqlx::query!(
+        r#"
+        UPDATE company_data_exports
+        SET status = 'done', storage_path = $2, file_size = $3, completed_at = NOW(),
+            expires_at = NOW() + make_interval(days => $4), heartbeat_at = NOW()
+        WHERE id = $1
+        "#,
+        export_id,
+        storage_path,
+        file_size as i64,
+        EXPORT_VALID_DAYS as i32,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Writes the archive to `upload` one job at a time, recording progress after
+/// each, and returns its size
+async fn write_archive(
+    db: &PgPool,
+    export_id: Uuid,
+    company_id: Uuid,
+    upload: &mut WriteMultipart,
+) -> Result<usize> {
+    let flushed = Arc::new(Mutex::new(Vec::new()));
+    let mut zip = ZipWriter::new(ArchiveBuffer::new(flushed.clone()));
+    zip.set_flush_on_finish_file(true);
+    let mut file_size = 0;
+
+    let jobs = sqlx::query!(
+        "SELECT id, title FROM jobs WHERE company_id = $1 ORDER BY created_at",
+        company_id
+    )
+    .fetch_all(db)
+    .await?;
+    sqlx::query!(
+        "UPDATE company_data_exports SET total_jobs = $2, heartbeat_at = NOW() WHERE id = $1",
+        export_id,
+        jobs.len() as i32
+    )
+    .execute(db)
+    .await?;
+
+    add_file(
+        &mut zip,
+        "company.json",
+        &company_json(db, company_id).await?,
+    )?;
+    add_file(&mut zip, "jobs.xlsx", &jobs_workbook(db, company_id).await?)?;
+
+    for job in &jobs {
+        let name = format!(
+            "applicants/{}-{}.xlsx",
+            file_slug(&job.title),
+            &job.id.to_string()[..8]
+        );
+        add_file(&mut zip, &name, &applicants_workbook(db, job.id).await?)?;
+
+        // Entries are flushed once the writer moves past them
+        let chunk = std::mem::take(&mut *flushed.lock().unwrap());
+        file_size += chunk.len();
+        upload
+            .wait_for_capacity(MAX_CONCURRENT_PARTS)
+            .await
+            .map_err(upload_err)?;
+        upload.write(&chunk);
+
+        sqlx::query!(
+            r#"
+            UPDATE company_data_exports
+            SET processed_jobs = processed_jobs + 1, heartbeat_at = NOW()
+            WHERE id = $1
+            "#,
+            export_id
+        )
+        .execute(db)
+        .await?;
+    }
+
+    let mut buffer = zip.finish().map_err(zip_err)?;
+    buffer.flush().map_err(write_err)?;
+    let chunk = std::mem::take(&mut *flushed.lock().unwrap());
+    file_size += chunk.len();
+    upload.write(&chunk);
+
+    Ok(file_size)
+}
+
+/// Runs every pending export, emailing whoever requested each one that it is
+/// ready. Returns how many were completed.
+pub async fn process_pending(state: &AppState) -> Result<usize> {
+    let db = &state.db;
+    let mut completed = 0;
+
+    while let Some(export_id) = claim_next(db).await? {
+        if let Err(e) = run_export(db, state.storage.as_ref(), export_id).await {
+            tracing::error!("Company data export {} failed: {}", export_id, e);
+            sqlx::query!(
+                r#"
+                UPDATE company_data_exports
+                SET status = 'failed', error = $2, completed_at = NOW()
+                WHERE id = $1
+                "#,
+                export_id,
+                e.to_string(),
+            )
+            .execute(db)
+            .await?;
+            continue;
+        }
+        completed += 1;
+
+        let recipient = sqlx::query!(
+            r#"
+            SELECT e.requested_by, u.email as "email?", u.first_name, cp.company_name
+            FROM company_data_exports e
+            JOIN users u ON u.id = e.requested_by
+            JOIN company_profiles cp ON cp.id = e.company_id
+            WHERE e.id = $1
+            "#,
+            export_id
+        )
+        .fetch_one(db)
+        .await?;
+        if let Some(to) = recipient.email {
+            notifications::email(
+                state,
+                recipient.requested_by,
+                NotificationEvent::DataExport,
+                move |email| async move {
+                    email
+                        .send_data_export_ready_email(
+                            &to,
+                            &recipient.first_name,
+                            &recipient.company_name,
+                        )
+                        .await
+                },
+            );
+        }
+    }
+
+    Ok(completed)
+}
+
+/// Deletes the files of exports past their download window. Files that fail
+/// to delete are retried on the next run.
+pub async fn purge_expired(db: &PgPool, storage: &dyn StorageBackend) -> Result<u64> {
+    let expired = sqlx::query!(
+        r#"
+        SELECT id, storage_path as "storage_path!"
+        FROM company_data_exports
+        WHERE expires_at < NOW() AND storage_path IS NOT NULL
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut purged = 0;
+    for export in expired {
+        if let Err(e) = storage.delete(&export.storage_path).await {
+            tracing::warn!("Failed to delete data export {}: {}", export.id, e);
+            continue;
+        }
+
+        sqlx::query!(
+            "UPDATE company_data_exports SET storage_path = NULL WHERE id = $1",
+            export.id
+        )
+        .execute(db)
+        .await?;
+        purged += 1;
+    }
+
+    Ok(purged)
+}
+
+// ============================================================================
+// ARCHIVE CONTENTS
+// ============================================================================
+
+/// Company profile and member list as pretty-printed JSON
+async fn company_json(db: &PgPool, company_id: Uuid) -> Result<Vec<u8>> {
+    let data = sqlx::query_scalar!(
+        r#"
+        SELECT jsonb_build_object(
+            'company', to_jsonb(cp),
+            'members', COALESCE((
+                SELECT jsonb_agg(jsonb_build_object(
+                    'user_id', u.id,
+                    'first_name', u.first_name,
+                    'last_name', u.last_name,
+                    'email', u.email,
+                    'role', cm.role,
+                    'job_title', cm.job_title,
+                    'is_active', cm.is_active,
+                    'joined_at', cm.joined_at
+                ) ORDER BY cm.joined_at)
+                FROM company_members cm
+                JOIN users u ON u.id = cm.user_id
+                WHERE cm.company_id = cp.id
+            ), '[]'::jsonb),
+            'exported_at', NOW()
+        ) as "data!"
+        FROM company_profiles cp
+        WHERE cp.id = $1
+        "#,
+        company_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    serde_json::to_vec_pretty(&data)
+        .map_err(|e| AppError::InternalError(format!("Failed to serialize company: {}", e)))
+}
+
+/// Every posting of the company, in any status, with all its fields
+async fn jobs_workbook(db: &PgPool, company_id: Uuid) -> Result<Vec<u8>> {
+    let rows = sqlx::query_scalar!(
+        r#"
+        SELECT ARRAY[
+            j.id::text, j.title, j.status::text, j.job_type::text, j.work_modality::text,
+            j.shift_type::text, j.weekly_hours::text, j.schedule_details,
+            i.name, wa.name, pl.name, r.name, m.name,
+            CASE WHEN j.is_remote_allowed THEN 'yes' ELSE 'no' END,
+            j.description, j.responsibilities, j.education_level,
+            j.years_experience_min::text, j.years_experience_max::text,
+            j.age_min::text, j.age_max::text,
+            j.salary_min::text, j.salary_max::text, j.salary_currency, j.salary_period,
+            j.benefits, j.application_deadline::text, j.contact_email, j.application_url,
+            j.vacancies::text, j.applications_count::text, j.views_count::text,
+            u.email, to_char(j.created_at, 'YYYY-MM-DD HH24:MI'),
+            to_char(j.published_at, 'YYYY-MM-DD HH24:MI')
+        ] as "cells!: Vec<Option<String>>"
+        FROM jobs j
+        JOIN users u ON u.id = j.posted_by
+        LEFT JOIN industries i ON i.id = j.industry_id
+        LEFT JOIN work_areas wa ON wa.id = j.work_area_id
+        LEFT JOIN position_levels pl ON pl.id = j.position_level_id
+        LEFT JOIN regions r ON r.id = j.region_id
+        LEFT JOIN municipalities m ON m.id = j.municipality_id
+        WHERE j.company_id = $1
+        ORDER BY j.created_at
+        "#,
+        company_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    write_workbook(&JOB_COLUMNS, &rows)
+}
+
+/// The applicants of one job with their review history and notes
+async fn applicants_workbook(db: &PgPool, job_id: Uuid) -> Result<Vec<u8>> {
+    let rows = sqlx::query_scalar!(
+        r#"
+        SELECT ARRAY[
+            ja.id::text, u.first_name, u.last_name, u.email, jsp.phone, ja.status::text,
+            to_char(ja.applied_at, 'YYYY-MM-DD HH24:MI'),
+            to_char(ja.reviewed_at, 'YYYY-MM-DD HH24:MI'),
+            ja.cover_letter, ja.resume_url,
+            (
+                SELECT string_agg(at.name, ', ' ORDER BY at.name)
+                FROM application_tag_assignments ata
+                JOIN application_tags at ON at.id = ata.tag_id
+                WHERE ata.application_id = ja.id
+            ),
+            to_char(ja.interview_date, 'YYYY-MM-DD HH24:MI'), ja.interview_notes,
+            to_char(ja.offer_date, 'YYYY-MM-DD HH24:MI'), ja.offer_details,
+            ja.rejection_reason_code::text, ja.rejection_feedback, ja.withdrawal_reason,
+            (
+                SELECT string_agg(
+                    to_char(n.created_at, 'YYYY-MM-DD HH24:MI') || ' ' || nu.first_name || ' '
+                        || nu.last_name || ': ' || n.note_text,
+                    E'\n' ORDER BY n.created_at
+                )
+                FROM application_notes n
+                JOIN users nu ON nu.id = n.created_by
+                WHERE n.application_id = ja.id
+            )
+        ] as "cells!: Vec<Option<String>>"
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        LEFT JOIN job_seeker_profiles jsp ON jsp.user_id = ja.applicant_id
+        WHERE ja.job_id = $1
+        ORDER BY ja.applied_at
+        "#,
+        job_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    write_workbook(&APPLICANT_COLUMNS, &rows)
+}
+
+fn write_workbook(headers: &[&str], rows: &[Vec<Option<String>>]) -> Result<Vec<u8>> {
+    let xlsx_err =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    let header_format = Format::new().set_bold();
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(xlsx_err)?;
+    }
+    for (row_idx, cells) in rows.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            if let Some(value) = cell {
+                worksheet
+                    .write_string((row_idx + 1) as u32, col as u16, value)
+                    .map_err(xlsx_err)?;
+            }
+        }
+    }
+
+    workbook.save_to_buffer().map_err(xlsx_err)
+}
+
+fn add_file<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, contents: &[u8]) -> Result<()> {
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(zip_err)?;
+    zip.write_all(contents).map_err(write_err)
+}
+
+/// Job title reduced to something safe in a file name
+fn file_slug(title: &str) -> String {
+    let slug: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .take(50)
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+fn zip_err(e: zip::result::ZipError) -> AppError {
+    AppError::InternalError(format!("Failed to write export: {}", e))
+}
+
+fn write_err(e: io::Error) -> AppError {
+    AppError::InternalError(format!("Failed to write export: {}", e))
+}
+
+fn upload_err(e: object_store::Error) -> AppError {
+    AppError::InternalError(format!("Failed to upload export: {}", e))
+}
+
+// ============================================================================
+// STREAMING
+// ============================================================================
+
+/// Seekable target for the ZIP writer that hands finished entries on while
+/// the archive is still being written. The writer only seeks back within the
+/// entry it is writing, so everything before its last flush is final and is
+/// moved to `flushed`, to be uploaded and dropped.
+struct ArchiveBuffer {
+    flushed: Arc<Mutex<Vec<u8>>>,
+    /// Bytes of the entry being written
+    pending: Vec<u8>,
+    /// Archive offset of `pending[0]`
+    start: u64,
+    position: u64,
+}
+
+impl ArchiveBuffer {
+    fn new(flushed: Arc<Mutex<Vec<u8>>>) -> Self {
+        Self {
+            flushed,
+            pending: Vec::new(),
+            start: 0,
+            position: 0,
+        }
+    }
+
+    fn end(&self) -> u64 {
+        self.start + self.pending.len() as u64
+    }
+}
+
+impl Write for ArchiveBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let offset = (self.position - self.start) as usize;
+        let overlap = buf.len().min(self.pending.len() - offset);
+        self.pending[offset..offset + overlap].copy_from_slice(&buf[..overlap]);
+        self.pending.extend_from_slice(&buf[overlap..]);
+        self.position += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed.lock().unwrap().append(&mut self.pending);
+        self.start = self.position;
+        Ok(())
+    }
+}
+
+/// Only there because the ZIP writer wants it before flushing finished
+/// entries; it never reads back what it wrote
+impl Read for ArchiveBuffer {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the export archive is write-only",
+        ))
+    }
+}
+
+impl Seek for ArchiveBuffer {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.end().checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        };
+        match target {
+            Some(target) if target >= self.start && target <= self.end() => {
+                self.position = target;
+                Ok(target)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot seek into data already uploaded",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_archive_buffer_streams_a_valid_zip() {
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+        let mut zip = ZipWriter::new(ArchiveBuffer::new(flushed.clone()));
+        zip.set_flush_on_finish_file(true);
+
+        let mut streamed = Vec::new();
+        for i in 0..3 {
+            add_file(
+                &mut zip,
+                &format!("file-{}.txt", i),
+                format!("contents {}", i).repeat(100).as_bytes(),
+            )
+            .unwrap();
+            streamed.append(&mut flushed.lock().unwrap());
+        }
+        // The second file is finished and handed on once the third starts
+        assert!(!streamed.is_empty());
+        zip.finish().unwrap().flush().unwrap();
+        streamed.append(&mut flushed.lock().unwrap());
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(streamed)).unwrap();
+        assert_eq!(archive.len(), 3);
+        let mut contents = String::new();
+        archive
+            .by_name("file-2.txt")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "contents 2".repeat(100));
+    }
+
+    #[test]
+    fn test_archive_buffer_refuses_to_seek_into_flushed_data() {
+        let mut buffer = ArchiveBuffer::new(Arc::new(Mutex::new(Vec::new())));
+        buffer.write_all(b"header").unwrap();
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+        buffer.write_all(b"HEAD").unwrap();
+        buffer.seek(SeekFrom::End(0)).unwrap();
+        buffer.flush().unwrap();
+
+        assert_eq!(*buffer.flushed.lock().unwrap(), b"HEADer");
+        assert!(buffer.seek(SeekFrom::Start(2)).is_err());
+        assert_eq!(buffer.stream_position().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_file_slug() {
+        assert_eq!(
+            file_slug("Operario/a de bodega (turno noche)"),
+            "Operario-a-de-bodega--turno-noche"
+        );
+        assert_eq!(file_slug("¡Cajero!"), "Cajero");
+    }
+}
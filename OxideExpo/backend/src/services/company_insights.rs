@@ -0,0 +1,214 @@
+//! Company hiring signals for OMIL outreach: which active companies in the
+//! OMIL's region actually hire people with disabilities. Only aggregates
+//! leave the database, and disability-related counts go through
+//! SuppressedCount like the admin inclusion report.
+
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::admin::SuppressedCount;
+use crate::models::omil::{CompanyInsight, CompanyInsightsResponse};
+
+pub const CACHE_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+/// How far back hires are looked at
+pub const HIRING_WINDOW_MONTHS: i32 = 12;
+
+pub const ACCOMMODATING_JOB_POINTS: i64 = 2;
+pub const DISABILITY_HIRE_POINTS: i64 = 1;
+pub const PRIOR_HIRE_POINTS: i64 = 5;
+
+/// How `inclusiveness_score` is computed, sent with every list
+pub const INCLUSIVENESS_SCORE_FORMULA: &str = "2 points per active job offering accommodations, \
+     plus 1 point per candidate with a registered disability hired in the last 12 months \
+     (counts under the reporting threshold are hidden and add nothing), \
+     plus 5 points if the company has hired one of this OMIL's job seekers";
+
+pub fn cache_key(omil_id: Uuid, month: &str) -> String {
+    format!("omil:{}:company_insights:{}", omil_id, month)
+}
+
+/// Suppressed hire counts add nothing, so the ordering can't reveal them
+pub fn inclusiveness_score(
+    accommodating_jobs: i64,
+    disability_hires: &SuppressedCount,
+    hired_our_seekers: bool,
+) -> i64 {
+    accommodating_jobs * ACCOMMODATING_JOB_POINTS
+        + disability_hires.value.unwrap_or(0) * DISABILITY_HIRE_POINTS
+        + if hired_our_seekers {
+            PRIOR_HIRE_POINTS
+        } else {
+            0
+        }
+}
+
+/// Insights for the OMIL from Redis, computed and kept for a day on a miss.
+/// Each month gets its own entry. Redis failures are logged and the insights
+/// computed directly.
+pub async fn insights(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    omil_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<CompanyInsightsResponse> {
+    let key = cache_key(omil_id, &now.format("%Y-%m").to_string());
+
+    match redis.get::<_, Option<String>>(&key).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(insights) => return Ok(insights),
+            Err(e) => tracing::warn!(
+                "Discarding unreadable company insights cache {}: {}",
+                key,
+                e
+            ),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read company insights cache {}: {}", key, e),
+    }
+
+    let insights = compute_insights(db, omil_id, now).await?;
+
+    if let Ok(json) = serde_json::to_string(&insights) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(&key, json, CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache company insights {}: {}", key, e);
+        }
+    }
+
+    Ok(insights)
+}
+
+/// An OMIL without a region of its own uses its municipality's. Hires are
+/// dated by their move to hired, as in the public statistics.
+async fn compute_insights(
+    db: &PgPool,
+    omil_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<CompanyInsightsResponse> {
+    let rows = sqlx::query!(
+        r#"
+        WITH omil_region AS (
+            SELECT COALESCE(o.region_id, m.region_id) AS region_id
+            FROM omil_organizations o
+            LEFT JOIN municipalities m ON m.id = o.municipality_id
+            WHERE o.id = $1
+        ),
+        companies AS (
+            SELECT c.id, c.company_name
+            FROM company_profiles c
+            JOIN omil_region r ON r.region_id = c.region_id
+            WHERE c.status = 'active'
+        ),
+        hires AS (
+            SELECT
+                j.company_id,
+                ja.applicant_id,
+                ja.applied_at,
+                COALESCE(
+                    (
+                        SELECT MAX(h.created_at)
+                        FROM application_status_history h
+                        WHERE h.application_id = ja.id AND h.new_status = 'hired'
+                    ),
+                    ja.updated_at
+                ) AS hired_at
+            FROM job_applications ja
+            JOIN jobs j ON j.id = ja.job_id
+            JOIN companies c ON c.id = j.company_id
+            WHERE ja.status = 'hired'
+        )
+        SELECT
+            c.id AS "company_id!",
+            c.company_name AS "company_name!",
+            (
+                SELECT COUNT(*) FROM jobs j
+                WHERE j.company_id = c.id
+                  AND j.status = 'active'
+                  AND EXISTS (
+                      SELECT 1 FROM job_disability_accommodations a WHERE a.job_id = j.id
+                  )
+            ) AS "accommodating_jobs!",
+            (
+                SELECT COUNT(DISTINCT h.applicant_id) FROM hires h
+                WHERE h.company_id = c.id
+                  AND h.hired_at >= $2::timestamptz - make_interval(months => $3)
+                  AND EXISTS (
+                      SELECT 1 FROM job_seeker_disabilities d WHERE d.user_id = h.applicant_id
+                  )
+            ) AS "disability_hires!",
+            (
+                SELECT AVG(EXTRACT(EPOCH FROM h.hired_at - h.applied_at) / 86400)::float8
+                FROM hires h
+                WHERE h.company_id = c.id AND h.hired_at >= $2::timestamptz - make_interval(months => $3)
+            ) AS avg_days_to_hire,
+            EXISTS (
+                SELECT 1 FROM hires h
+                JOIN omil_managed_job_seekers s
+                    ON s.job_seeker_id = h.applicant_id
+                   AND s.omil_id = $1
+                   AND s.transferred_at IS NULL
+                WHERE h.company_id = c.id
+            ) AS "hired_our_seekers!"
+        FROM companies c
+        "#,
+        omil_id,
+        now,
+        HIRING_WINDOW_MONTHS,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut companies: Vec<CompanyInsight> = rows
+        .into_iter()
+        .map(|row| {
+            let disability_hires = SuppressedCount::from_count(row.disability_hires);
+            CompanyInsight {
+                inclusiveness_score: inclusiveness_score(
+                    row.accommodating_jobs,
+                    &disability_hires,
+                    row.hired_our_seekers,
+                ),
+                company_id: row.company_id,
+                company_name: row.company_name,
+                active_jobs_with_accommodations: row.accommodating_jobs,
+                disability_hires_last_12_months: disability_hires,
+                avg_days_to_hire: row
+                    .avg_days_to_hire
+                    .map(|days| (days * 10.0).round() / 10.0),
+                has_hired_our_seekers: row.hired_our_seekers,
+            }
+        })
+        .collect();
+
+    companies.sort_by(|a, b| {
+        b.inclusiveness_score
+            .cmp(&a.inclusiveness_score)
+            .then_with(|| a.company_name.cmp(&b.company_name))
+    });
+
+    Ok(CompanyInsightsResponse {
+        score_formula: INCLUSIVENESS_SCORE_FORMULA.to_string(),
+        generated_at: now,
+        companies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suppressed_hires_add_nothing_to_the_score() {
+        let hidden = SuppressedCount::from_count(4);
+        let shown = SuppressedCount::from_count(6);
+
+        assert_eq!(inclusiveness_score(0, &hidden, false), 0);
+        assert_eq!(inclusiveness_score(3, &hidden, true), 11);
+        assert_eq!(inclusiveness_score(1, &shown, false), 8);
+    }
+}
@@ -0,0 +1,261 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::job::{CompletenessItem, JobCompleteness, MissingCompletenessItem};
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Points for each item; they add up to 100
+pub const COMPLETENESS_WEIGHTS: [(CompletenessItem, i32); 7] = [
+    (CompletenessItem::Description, 25),
+    (CompletenessItem::Salary, 20),
+    (CompletenessItem::Skills, 20),
+    (CompletenessItem::Accommodations, 15),
+    (CompletenessItem::Languages, 10),
+    (CompletenessItem::Deadline, 5),
+    (CompletenessItem::Benefits, 5),
+];
+
+/// Shorter descriptions rarely say enough about the day-to-day work
+pub const MIN_DESCRIPTION_CHARS: i32 = 300;
+
+/// Jobs scoring below this get their checklist on the company dashboard
+pub const INCOMPLETE_JOB_THRESHOLD: i32 = 70;
+
+/// Jobs recalculated per statement by the background task
+const BATCH_SIZE: i64 = 500;
+
+// ============================================================================
+// SCORING
+// ============================================================================
+
+/// What a job has, as far as completeness is concerned
+#[derive(Debug, Clone)]
+pub struct JobFacts {
+    pub job_id: Uuid,
+    pub title: String,
+    pub description_chars: i32,
+    pub has_salary: bool,
+    pub has_skills: bool,
+    pub has_languages: bool,
+    pub has_accommodations: bool,
+    pub deadline_open: bool,
+    pub has_benefits: bool,
+}
+
+impl JobFacts {
+    fn has(&self, item: CompletenessItem) -> bool {
+        match item {
+            CompletenessItem::Description => self.description_chars >= MIN_DESCRIPTION_CHARS,
+            CompletenessItem::Salary => self.has_salary,
+            CompletenessItem::Skills => self.has_skills,
+            CompletenessItem::Languages => self.has_languages,
+            CompletenessItem::Accommodations => self.has_accommodations,
+            CompletenessItem::Deadline => self.deadline_open,
+            CompletenessItem::Benefits => self.has_benefits,
+        }
+    }
+}
+
+/// Score a job from its facts. Missing items keep the weight order, so the
+/// most valuable ones come first.
+pub fn job_completeness(facts: &JobFacts) -> JobCompleteness {
+    let mut score = 0;
+    let mut missing = Vec::new();
+
+    for (item, points) in COMPLETENESS_WEIGHTS {
+        if facts.has(item) {
+            score += points;
+        } else {
+            missing.push(MissingCompletenessItem { item, points });
+        }
+    }
+
+    JobCompleteness {
+        job_id: facts.job_id,
+        title: facts.title.clone(),
+        score,
+        missing,
+    }
+}
+
+// ============================================================================
+// RECALCULATION
+// ============================================================================
+
+async fn load_facts(conn: &mut PgConnection, job_ids: &[Uuid]) -> Result<Vec<JobFacts>> {
+    let facts = sqlx::query_as!(
+        JobFacts,
+        r#"
+        SELECT
+            j.id as "job_id!",
+            j.title as "title!",
+            char_length(btrim(j.description)) as "description_chars!",
+            (j.salary_min IS NOT NULL OR j.salary_max IS NOT NULL) as "has_salary!",
+            (
+                EXISTS (SELECT 1 FROM job_required_skills s WHERE s.job_id = j.id)
+                OR EXISTS (SELECT 1 FROM job_preferred_skills s WHERE s.job_id = j.id)
+            ) as "has_skills!",
+            EXISTS (
+                SELECT 1 FROM job_required_languages l WHERE l.job_id = j.id
+            ) as "has_languages!",
+            EXISTS (
+                SELECT 1 FROM job_disability_accommodations a WHERE a.job_id = j.id
+            ) as "has_accommodations!",
+            (j.application_deadline >= CURRENT_DATE) as "deadline_open!",
+            (COALESCE(btrim(j.benefits), '') <> '') as "has_benefits!"
+        FROM jobs j
+        WHERE j.id = ANY($1)
+        ORDER BY j.created_at, j.id
+        "#,
+        job_ids,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(facts)
+}
+
+/// Score the given jobs without storing the scores, for reads
+pub async fn score(conn: &mut PgConnection, job_ids: &[Uuid]) -> Result<Vec<JobCompleteness>> {
+    Ok(load_facts(conn, job_ids)
+        .await?
+        .iter()
+        .map(job_completeness)
+        .collect())
+}
+
+/// Score the given jobs and store the scores in jobs.completeness_percentage.
+/// Call after anything that changes a job or its skills, languages or
+/// accommodations, inside the same transaction.
+pub async fn recalculate(
+    conn: &mut PgConnection,
+    job_ids: &[Uuid],
+) -> Result<Vec<JobCompleteness>> {
+    let scored = score(&mut *conn, job_ids).await?;
+
+    let ids: Vec<Uuid> = scored.iter().map(|c| c.job_id).collect();
+    let scores: Vec<i32> = scored.iter().map(|c| c.score).collect();
+
+    // Unchanged rows are skipped so updated_at only moves on real changes
+    sqlx::query!(
+        r#"
+        UPDATE jobs j
+        SET completeness_percentage = s.score
+        FROM UNNEST($1::uuid[], $2::int[]) AS s(id, score)
+        WHERE j.id = s.id AND j.completeness_percentage <> s.score
+        "#,
+        &ids,
+        &scores,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(scored)
+}
+
+/// Recalculate every job that is not closed or rejected, catching scores
+/// that drift without an edit (e.g. a deadline passing). Returns how many
+/// jobs were scored.
+pub async fn recalculate_open_jobs(db: &PgPool) -> Result<usize> {
+    let mut conn = db.acquire().await?;
+    let mut after = Uuid::nil();
+    let mut scored = 0;
+
+    loop {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM jobs
+            WHERE status NOT IN ('closed', 'rejected') AND id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+            after,
+            BATCH_SIZE,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let Some(last) = ids.last() else {
+            return Ok(scored);
+        };
+        after = *last;
+        scored += recalculate(&mut conn, &ids).await?.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> JobFacts {
+        JobFacts {
+            job_id: Uuid::new_v4(),
+            title: "Operario de bodega".to_string(),
+            description_chars: MIN_DESCRIPTION_CHARS,
+            has_salary: true,
+            has_skills: true,
+            has_languages: true,
+            has_accommodations: true,
+            deadline_open: true,
+            has_benefits: true,
+        }
+    }
+
+    #[test]
+    fn test_weights_add_up_to_100() {
+        let total: i32 = COMPLETENESS_WEIGHTS.iter().map(|(_, points)| points).sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn test_complete_job_scores_100() {
+        let completeness = job_completeness(&facts());
+        assert_eq!(completeness.score, 100);
+        assert!(completeness.missing.is_empty());
+    }
+
+    #[test]
+    fn test_missing_items_lose_their_points() {
+        let completeness = job_completeness(&JobFacts {
+            description_chars: MIN_DESCRIPTION_CHARS - 1,
+            has_benefits: false,
+            has_skills: false,
+            ..facts()
+        });
+
+        assert_eq!(completeness.score, 100 - 25 - 20 - 5);
+        let missing: Vec<(CompletenessItem, i32)> = completeness
+            .missing
+            .iter()
+            .map(|m| (m.item, m.points))
+            .collect();
+        assert_eq!(
+            missing,
+            vec![
+                (CompletenessItem::Description, 25),
+                (CompletenessItem::Skills, 20),
+                (CompletenessItem::Benefits, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bare_job_scores_zero() {
+        let completeness = job_completeness(&JobFacts {
+            description_chars: 0,
+            has_salary: false,
+            has_skills: false,
+            has_languages: false,
+            has_accommodations: false,
+            deadline_open: false,
+            has_benefits: false,
+            ..facts()
+        });
+        assert_eq!(completeness.score, 0);
+        assert_eq!(completeness.missing.len(), COMPLETENESS_WEIGHTS.len());
+    }
+}
@@ -0,0 +1,437 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use redis::aio::ConnectionManager;
+use regex::{Regex, RegexBuilder};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::admin::{
+    ModerationRule, ModerationRuleType, ModerationSeverity, ScreeningCheck, ScreeningFinding,
+};
+use crate::services::settings;
+
+/// Whether editing an active job sends it back to the moderation queue
+pub const REVIEW_EDITED_JOBS_SETTING: &str = "review_edited_jobs";
+
+/// Admin regexes run against every submission, so their compiled size is capped
+const RULE_SIZE_LIMIT: usize = 1 << 20;
+
+static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}")
+        .expect("Failed to compile EMAIL_REGEX")
+});
+
+/// Chilean mobile/Santiago numbers and anything written in international format.
+/// Amounts like 1.500.000 don't match.
+static PHONE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+?56[\s.-]?)?\(?[29]\)?[\s.-]?\d{4}[\s.-]?\d{4}\b|\+\d[\d\s.-]{7,}\d")
+        .expect("Failed to compile PHONE_REGEX")
+});
+
+static URL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:https?://|www\.)\S+").expect("Failed to compile URL_REGEX"));
+
+/// Fields of a job that are screened on submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobContent<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub responsibilities: Option<&'a str>,
+    pub benefits: Option<&'a str>,
+    pub age_min: Option<i32>,
+    pub age_max: Option<i32>,
+}
+
+impl<'a> JobContent<'a> {
+    fn text_fields(&self) -> impl Iterator<Item = (&'static str, &'a str)> {
+        [
+            ("title", Some(self.title)),
+            ("description", Some(self.description)),
+            ("responsibilities", self.responsibilities),
+            ("benefits", self.benefits),
+        ]
+        .into_iter()
+        .filter_map(|(field, text)| text.map(|t| (field, t)))
+    }
+}
+
+/// Settings for the built-in checks; a severity of None turns the check off
+#[derive(Debug, Clone, Copy)]
+pub struct ScreeningPolicy {
+    pub contact_info: Option<ModerationSeverity>,
+    pub age_limit: Option<ModerationSeverity>,
+    pub max_age_min: i32,
+    pub min_age_max: i32,
+}
+
+impl ScreeningPolicy {
+    pub async fn load(db: &PgPool, redis: &mut ConnectionManager) -> Result<Self> {
+        let contact_info =
+            settings::get_string(db, redis, "moderation_contact_info_severity").await?;
+        let age_limit = settings::get_string(db, redis, "moderation_age_limit_severity").await?;
+
+        Ok(Self {
+            contact_info: parse_severity(&contact_info),
+            age_limit: parse_severity(&age_limit),
+            max_age_min: settings::get_int(db, redis, "moderation_max_age_min").await? as i32,
+            min_age_max: settings::get_int(db, redis, "moderation_min_age_max").await? as i32,
+        })
+    }
+}
+
+fn parse_severity(value: &str) -> Option<ModerationSeverity> {
+    match value {
+        "warn" => Some(ModerationSeverity::Warn),
+        "block" => Some(ModerationSeverity::Block),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct CompiledRule {
+    pub id: Uuid,
+    pub rule_type: ModerationRuleType,
+    pub severity: ModerationSeverity,
+    pub pattern: String,
+    pub description: Option<String>,
+    pub regex: Regex,
+}
+
+/// Build the matcher for a rule. Banned phrases match case-insensitively on
+/// word boundaries so "edad" doesn't flag "calidad".
+pub fn compile_rule(
+    rule_type: ModerationRuleType,
+    pattern: &str,
+) -> std::result::Result<Regex, regex::Error> {
+    let source = match rule_type {
+        ModerationRuleType::BannedPhrase => {
+            let phrase = pattern.trim();
+            let is_word = |c: char| c.is_alphanumeric() || c == '_';
+            let start = if phrase.starts_with(is_word) {
+                r"\b"
+            } else {
+                ""
+            };
+            let end = if phrase.ends_with(is_word) { r"\b" } else { "" };
+            format!("(?i){}{}{}", start, regex::escape(phrase), end)
+        }
+        ModerationRuleType::Regex => pattern.to_string(),
+    };
+
+    RegexBuilder::new(&source)
+        .size_limit(RULE_SIZE_LIMIT)
+        .build()
+}
+
+// ============================================================================
+// RULE CACHE
+// ============================================================================
+
+/// Row count and latest update of moderation_rules; any create, edit or
+/// delete changes it
+type RulesFingerprint = (i64, Option<DateTime<Utc>>);
+
+struct RuleCache {
+    fingerprint: RulesFingerprint,
+    rules: Arc<Vec<CompiledRule>>,
+}
+
+static RULE_CACHE: Lazy<RwLock<Option<RuleCache>>> = Lazy::new(|| RwLock::new(None));
+
+/// Active rules, compiled once and reused until the table changes. Checking
+/// the fingerprint on every call picks up admin edits on all servers without
+/// a restart.
+pub async fn active_rules(db: &PgPool) -> Result<Arc<Vec<CompiledRule>>> {
+    let current = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!", MAX(updated_at) as updated_at FROM moderation_rules"#
+    )
+    .fetch_one(db)
+    .await?;
+    let fingerprint = (current.count, current.updated_at);
+
+    if let Some(cache) = RULE_CACHE.read().expect("rule cache poisoned").as_ref() {
+        if cache.fingerprint == fingerprint {
+            return Ok(cache.rules.clone());
+        }
+    }
+
+    let rows = sqlx::query_as!(
+        ModerationRule,
+        r#"
+        SELECT
+            id,
+            rule_type as "rule_type: ModerationRuleType",
+            pattern,
+            severity as "severity: ModerationSeverity",
+            description,
+            is_active,
+            created_by,
+            created_at,
+            updated_at
+        FROM moderation_rules
+        WHERE is_active = true
+        ORDER BY created_at
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let rules: Vec<CompiledRule> = rows
+        .into_iter()
+        .filter_map(|rule| match compile_rule(rule.rule_type, &rule.pattern) {
+            Ok(regex) => Some(CompiledRule {
+                id: rule.id,
+                rule_type: rule.rule_type,
+                severity: rule.severity,
+                pattern: rule.pattern,
+                description: rule.description,
+                regex,
+            }),
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping moderation rule {} that fails to compile: {}",
+                    rule.id,
+                    e
+                );
+                None
+            }
+        })
+        .collect();
+    let rules = Arc::new(rules);
+
+    *RULE_CACHE.write().expect("rule cache poisoned") = Some(RuleCache {
+        fingerprint,
+        rules: rules.clone(),
+    });
+
+    Ok(rules)
+}
+
+// ============================================================================
+// SCREENING
+// ============================================================================
+
+/// Screen a job with the current rules and settings
+pub async fn screen_job(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    content: &JobContent<'_>,
+) -> Result<Vec<ScreeningFinding>> {
+    let policy = ScreeningPolicy::load(db, redis).await?;
+    let rules = active_rules(db).await?;
+
+    Ok(screen(content, &rules, &policy))
+}
+
+/// Every finding for the job, at most one per rule and field
+pub fn screen(
+    content: &JobContent<'_>,
+    rules: &[CompiledRule],
+    policy: &ScreeningPolicy,
+) -> Vec<ScreeningFinding> {
+    let mut findings = Vec::new();
+
+    for rule in rules {
+        for (field, text) in content.text_fields() {
+            let Some(found) = rule.regex.find(text) else {
+                continue;
+            };
+            let (check, default_message) = match rule.rule_type {
+                ModerationRuleType::BannedPhrase => (
+                    ScreeningCheck::BannedPhrase,
+                    format!("Contains the banned phrase \"{}\"", rule.pattern.trim()),
+                ),
+                ModerationRuleType::Regex => (
+                    ScreeningCheck::Regex,
+                    "Contains text that is not allowed".to_string(),
+                ),
+            };
+            findings.push(ScreeningFinding {
+                field: field.to_string(),
+                check,
+                severity: rule.severity,
+                rule_id: Some(rule.id),
+                matched: Some(found.as_str().to_string()),
+                message: rule.description.clone().unwrap_or(default_message),
+            });
+        }
+    }
+
+    // Contact details let applicants bypass the platform
+    if let Some(severity) = policy.contact_info {
+        for (regex, what) in [
+            (&*EMAIL_REGEX, "an email address"),
+            (&*PHONE_REGEX, "a phone number"),
+            (&*URL_REGEX, "a URL"),
+        ] {
+            if let Some(found) = regex.find(content.description) {
+                findings.push(ScreeningFinding {
+                    field: "description".to_string(),
+                    check: ScreeningCheck::ContactInfo,
+                    severity,
+                    rule_id: None,
+                    matched: Some(found.as_str().to_string()),
+                    message: format!(
+                        "Description contains {}; applications must go through the platform",
+                        what
+                    ),
+                });
+            }
+        }
+    }
+
+    if let Some(severity) = policy.age_limit {
+        if let Some(age_min) = content.age_min.filter(|a| *a > policy.max_age_min) {
+            findings.push(ScreeningFinding {
+                field: "age_min".to_string(),
+                check: ScreeningCheck::AgeLimit,
+                severity,
+                rule_id: None,
+                matched: Some(age_min.to_string()),
+                message: format!("Minimum age can't be above {}", policy.max_age_min),
+            });
+        }
+        if let Some(age_max) = content.age_max.filter(|a| *a < policy.min_age_max) {
+            findings.push(ScreeningFinding {
+                field: "age_max".to_string(),
+                check: ScreeningCheck::AgeLimit,
+                severity,
+                rule_id: None,
+                matched: Some(age_max.to_string()),
+                message: format!("Maximum age can't be below {}", policy.min_age_max),
+            });
+        }
+    }
+
+    findings
+}
+
+pub fn is_blocking(finding: &ScreeningFinding) -> bool {
+    finding.severity == ModerationSeverity::Block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(description: &str) -> JobContent<'_> {
+        JobContent {
+            title: "Vendedor",
+            description,
+            responsibilities: None,
+            benefits: None,
+            age_min: None,
+            age_max: None,
+        }
+    }
+
+    fn policy() -> ScreeningPolicy {
+        ScreeningPolicy {
+            contact_info: Some(ModerationSeverity::Block),
+            age_limit: Some(ModerationSeverity::Warn),
+            max_age_min: 18,
+            min_age_max: 65,
+        }
+    }
+
+    fn rule(
+        rule_type: ModerationRuleType,
+        pattern: &str,
+        severity: ModerationSeverity,
+    ) -> CompiledRule {
+        CompiledRule {
+            id: Uuid::new_v4(),
+            rule_type,
+            severity,
+            pattern: pattern.to_string(),
+            description: None,
+            regex: compile_rule(rule_type, pattern).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_banned_phrase_matches_whole_words_ignoring_case() {
+        let rules = [rule(
+            ModerationRuleType::BannedPhrase,
+            "buena presencia",
+            ModerationSeverity::Block,
+        )];
+
+        let findings = screen(&content("Se requiere BUENA PRESENCIA."), &rules, &policy());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].check, ScreeningCheck::BannedPhrase);
+        assert_eq!(findings[0].field, "description");
+        assert_eq!(findings[0].matched.as_deref(), Some("BUENA PRESENCIA"));
+
+        let findings = screen(&content("Muy buena presenciales"), &rules, &policy());
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_regex_rule_checks_every_text_field() {
+        let rules = [rule(
+            ModerationRuleType::Regex,
+            r"(?i)sexo\s*:\s*(masculino|femenino)",
+            ModerationSeverity::Warn,
+        )];
+        let mut job = content("Atención de público");
+        job.benefits = Some("Sexo: masculino");
+
+        let findings = screen(&job, &rules, &policy());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].field, "benefits");
+        assert_eq!(findings[0].check, ScreeningCheck::Regex);
+        assert_eq!(findings[0].severity, ModerationSeverity::Warn);
+    }
+
+    #[test]
+    fn test_invalid_regex_is_rejected() {
+        assert!(compile_rule(ModerationRuleType::Regex, "(unclosed").is_err());
+        // Phrases are literal text
+        assert!(compile_rule(ModerationRuleType::BannedPhrase, "(unclosed").is_ok());
+    }
+
+    #[test]
+    fn test_contact_info_in_description() {
+        for description in [
+            "Envía tu CV a rrhh@empresa.cl",
+            "Llamar al +56 9 1234 5678",
+            "Llamar al 9 8765 4321",
+            "Más información en www.empresa.cl",
+            "Postula en https://empresa.cl/trabajos",
+        ] {
+            let findings = screen(&content(description), &[], &policy());
+            assert_eq!(findings.len(), 1, "{}", description);
+            assert_eq!(findings[0].check, ScreeningCheck::ContactInfo);
+            assert_eq!(findings[0].severity, ModerationSeverity::Block);
+        }
+
+        let salary = content("Sueldo de $1.500.000 líquidos, 45 horas semanales");
+        assert!(screen(&salary, &[], &policy()).is_empty());
+
+        let off = ScreeningPolicy {
+            contact_info: None,
+            ..policy()
+        };
+        assert!(screen(&content("rrhh@empresa.cl"), &[], &off).is_empty());
+    }
+
+    #[test]
+    fn test_age_limits_outside_policy() {
+        let mut job = content("Atención de público");
+        job.age_min = Some(18);
+        job.age_max = Some(65);
+        assert!(screen(&job, &[], &policy()).is_empty());
+
+        job.age_min = Some(25);
+        job.age_max = Some(35);
+        let findings = screen(&job, &[], &policy());
+        let fields: Vec<&str> = findings.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, vec!["age_min", "age_max"]);
+        assert!(findings.iter().all(|f| f.check == ScreeningCheck::AgeLimit));
+        assert!(!findings.iter().any(is_blocking));
+    }
+}
@@ -0,0 +1,136 @@
+//! Denormalized counters kept next to the rows they count. Each change is
+//! applied in the same transaction as the row change, with a relative
+//! UPDATE so concurrent writers never overwrite each other.
+//!
+//! jobs.applications_count counts a job's applications that are not
+//! withdrawn. Before migration 0049 a trigger counted every application row,
+//! withdrawn ones included, and withdrawals were never subtracted.
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::admin::CounterDrift;
+
+/// Drifted jobs locked and fixed per transaction by recount_applications
+const RECOUNT_BATCH_SIZE: usize = 500;
+
+/// A new application was inserted for `job_id`
+pub async fn application_added(conn: &mut PgConnection, job_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE jobs SET applications_count = applications_count + 1 WHERE id = $1",
+        job_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Applications that counted towards their jobs were withdrawn or deleted;
+/// `job_ids` has one entry per application
+pub async fn applications_removed(
+    conn: &mut PgConnection,
+    job_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    if job_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE jobs j
+        SET applications_count = j.applications_count - removed.count
+        FROM (
+            SELECT job_id, COUNT(*)::INTEGER as count
+            FROM UNNEST($1::uuid[]) as job_id
+            GROUP BY job_id
+        ) removed
+        WHERE j.id = removed.job_id
+        "#,
+        job_ids
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Recomputes one job's applications_count, returning the corrected value
+pub async fn recount_job(conn: &mut PgConnection, job_id: Uuid) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE jobs
+        SET applications_count = (
+            SELECT COUNT(*)::INTEGER FROM job_applications
+            WHERE job_id = $1 AND status <> 'withdrawn'
+        )
+        WHERE id = $1
+        RETURNING applications_count
+        "#,
+        job_id
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Recomputes applications_count from the applications themselves and fixes
+/// every job that drifted, returning what was corrected. Drifted jobs are
+/// found without locks, then locked and re-checked RECOUNT_BATCH_SIZE at a
+/// time, so an application committed while this runs is counted either here
+/// or by its own increment afterwards, never both or neither, and jobs that
+/// were right all along are never locked.
+pub async fn recount_applications(db: &PgPool) -> Result<Vec<CounterDrift>, sqlx::Error> {
+    let drifted = sqlx::query_scalar!(
+        r#"
+        SELECT j.id FROM jobs j
+        WHERE j.applications_count <> (
+            SELECT COUNT(*) FROM job_applications ja
+            WHERE ja.job_id = j.id AND ja.status <> 'withdrawn'
+        )
+        ORDER BY j.id
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut corrected = Vec::new();
+    for batch in drifted.chunks(RECOUNT_BATCH_SIZE) {
+        let mut tx = db.begin().await?;
+
+        sqlx::query!(
+            "SELECT id FROM jobs WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+            batch
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let drifts = sqlx::query_as!(
+            CounterDrift,
+            r#"
+            WITH actual AS (
+                SELECT j.id,
+                       j.applications_count as stored,
+                       (
+                           SELECT COUNT(*) FROM job_applications ja
+                           WHERE ja.job_id = j.id AND ja.status <> 'withdrawn'
+                       )::INTEGER as count
+                FROM jobs j
+                WHERE j.id = ANY($1)
+            )
+            UPDATE jobs j
+            SET applications_count = actual.count
+            FROM actual
+            WHERE j.id = actual.id AND j.applications_count <> actual.count
+            RETURNING j.id as job_id, actual.stored as "stored!", actual.count as "actual!"
+            "#,
+            batch
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        corrected.extend(drifts);
+    }
+
+    Ok(corrected)
+}
@@ -0,0 +1,200 @@
+//! Cover letter templates. A template is expanded against the job being
+//! applied to and the application stores the resulting text, so later edits
+//! to the template never reach past applications.
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::application::CoverLetterTemplate;
+
+/// Longest cover letter an application accepts, in characters
+const MAX_COVER_LETTER_CHARS: usize = 5000;
+
+/// A template filled in for one job
+#[derive(Debug)]
+pub struct ExpandedCoverLetter {
+    pub text: String,
+    /// Placeholders the template used that aren't known, left as written
+    pub unknown_placeholders: Vec<String>,
+}
+
+impl ExpandedCoverLetter {
+    /// One warning per unknown placeholder, for the submission response
+    pub fn warnings(&self) -> Vec<String> {
+        self.unknown_placeholders
+            .iter()
+            .map(|placeholder| format!("Unknown placeholder {} was left as is", placeholder))
+            .collect()
+    }
+}
+
+/// Replaces `{{job_title}}` and `{{company_name}}` with the job's values.
+/// Values are inserted verbatim and never expanded themselves; unknown
+/// placeholders stay in the text as written.
+pub fn expand(body: &str, job_title: &str, company_name: &str) -> ExpandedCoverLetter {
+    let mut text = String::with_capacity(body.len());
+    let mut unknown_placeholders: Vec<String> = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = &after[..end];
+
+        // "{{ {{job_title}}": the first braces are just text
+        if name.contains("{{") {
+            text.push_str(&rest[..start + 2]);
+            rest = after;
+            continue;
+        }
+
+        text.push_str(&rest[..start]);
+        match name.trim() {
+            "job_title" => text.push_str(job_title),
+            "company_name" => text.push_str(company_name),
+            _ => {
+                let placeholder = &rest[start..start + end + 4];
+                text.push_str(placeholder);
+                if !unknown_placeholders.iter().any(|p| p == placeholder) {
+                    unknown_placeholders.push(placeholder.to_string());
+                }
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    text.push_str(rest);
+
+    ExpandedCoverLetter {
+        text,
+        unknown_placeholders,
+    }
+}
+
+/// A user's templates, most used first
+pub async fn list_templates(db: &PgPool, user_id: Uuid) -> Result<Vec<CoverLetterTemplate>> {
+    let templates = sqlx::query_as!(
+        CoverLetterTemplate,
+        r#"
+        SELECT id, user_id, name, body, use_count, last_used_at, created_at, updated_at
+        FROM cover_letter_templates
+        WHERE user_id = $1
+        ORDER BY last_used_at DESC NULLS LAST, use_count DESC, created_at DESC
+        "#,
+        user_id,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(templates)
+}
+
+/// Expands one of the user's templates for the job and records the use.
+/// Runs on the submission's transaction so a refused application doesn't
+/// count as a use.
+pub async fn use_template(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    template_id: Uuid,
+    job_id: Uuid,
+) -> Result<ExpandedCoverLetter> {
+    let body = sqlx::query_scalar!(
+        "SELECT body FROM cover_letter_templates WHERE id = $1 AND user_id = $2",
+        template_id,
+        user_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Cover letter template not found".to_string()))?;
+
+    let job = sqlx::query!(
+        r#"
+        SELECT j.title, c.company_name
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        WHERE j.id = $1
+        "#,
+        job_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::ValidationError("Job not found or not active".to_string()))?;
+
+    let expanded = expand(&body, &job.title, &job.company_name);
+    if expanded.text.chars().count() > MAX_COVER_LETTER_CHARS {
+        return Err(AppError::ValidationError(
+            "Cover letter too long once the template is filled in".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE cover_letter_templates
+        SET use_count = use_count + 1, last_used_at = NOW()
+        WHERE id = $1
+        "#,
+        template_id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+
+    #[test]
+    fn test_expand_fills_known_placeholders() {
+        let expanded = expand(
+            "Postulo a {{job_title}} en {{ company_name }}. Me encantaría trabajar en {{company_name}}.",
+            "Cajero/a",
+            "Supermercados Sur",
+        );
+        assert_eq!(
+            expanded.text,
+            "Postulo a Cajero/a en Supermercados Sur. Me encantaría trabajar en Supermercados Sur."
+        );
+        assert!(expanded.unknown_placeholders.is_empty());
+        assert!(expanded.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_expand_leaves_unknown_placeholders_literal() {
+        let expanded = expand(
+            "Hola {{recruiter}}, {{recruiter}} y {{}}",
+            "Cajero/a",
+            "Sur",
+        );
+        assert_eq!(expanded.text, "Hola {{recruiter}}, {{recruiter}} y {{}}");
+        assert_eq!(expanded.unknown_placeholders, ["{{recruiter}}", "{{}}"]);
+        assert_eq!(expanded.warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_expand_inserts_values_verbatim() {
+        // Values are never expanded or escaped
+        let expanded = expand(
+            "{{job_title}} @ {{company_name}}",
+            "{{company_name}} & <b>Ventas</b>",
+            "O'Higgins \"SpA\"",
+        );
+        assert_eq!(
+            expanded.text,
+            "{{company_name}} & <b>Ventas</b> @ O'Higgins \"SpA\""
+        );
+    }
+
+    #[test]
+    fn test_expand_handles_stray_braces() {
+        let expanded = expand("{{ {{job_title}} }} {{job_title", "Bodeguero", "Sur");
+        assert_eq!(expanded.text, "{{ Bodeguero }} {{job_title");
+        assert!(expanded.unknown_placeholders.is_empty());
+
+        let expanded = expand("Sin marcadores, sólo texto ñandú", "x", "y");
+        assert_eq!(expanded.text, "Sin marcadores, sólo texto ñandú");
+    }
+}
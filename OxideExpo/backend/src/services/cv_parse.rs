@@ -0,0 +1,875 @@
+use chrono::{Datelike, NaiveDate};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::models::profile::{
+    CvParseFailure, CvSuggestions, EducationLevel, EducationStatus, EducationSuggestion,
+    SuggestedValue, SuggestionConfidence, WorkExperienceSuggestion,
+};
+use crate::utils::normalize::{self, validate_phone};
+
+/// Below this many letters the PDF is taken to be a scan without a text layer
+pub const MIN_TEXT_LETTERS: usize = 50;
+
+/// Lines longer than this are prose, never an entry heading
+const MAX_HEADING_CHARS: usize = 120;
+
+// ============================================================================
+// TEXT EXTRACTION
+// ============================================================================
+
+/// Extracts the text layer of a PDF. pdf-extract panics on some malformed
+/// files, so panics are caught and reported as an unreadable file.
+pub fn extract_text(pdf: &[u8]) -> Result<String, CvParseFailure> {
+    let text = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(pdf))
+        .map_err(|_| CvParseFailure::Unreadable)?
+        .map_err(|_| CvParseFailure::Unreadable)?;
+
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_TEXT_LETTERS {
+        return Err(CvParseFailure::NoText);
+    }
+    Ok(text)
+}
+
+// ============================================================================
+// SECTIONS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    /// Lines before the first heading: name, contact details, headline
+    Top,
+    Experience,
+    Education,
+    Other,
+}
+
+const EXPERIENCE_HEADERS: &[&str] = &[
+    "experiencia",
+    "experiencia laboral",
+    "experiencia profesional",
+    "experiencia de trabajo",
+    "antecedentes laborales",
+    "historial laboral",
+    "experience",
+    "work experience",
+    "professional experience",
+    "employment history",
+    "work history",
+];
+
+const EDUCATION_HEADERS: &[&str] = &[
+    "educacion",
+    "formacion",
+    "formacion academica",
+    "antecedentes academicos",
+    "estudios",
+    "education",
+    "academic background",
+    "academic history",
+];
+
+const OTHER_HEADERS: &[&str] = &[
+    "perfil",
+    "perfil profesional",
+    "resumen",
+    "sobre mi",
+    "objetivo",
+    "objetivo profesional",
+    "habilidades",
+    "competencias",
+    "conocimientos",
+    "idiomas",
+    "cursos",
+    "certificaciones",
+    "capacitaciones",
+    "referencias",
+    "intereses",
+    "datos personales",
+    "contacto",
+    "profile",
+    "summary",
+    "about me",
+    "objective",
+    "skills",
+    "languages",
+    "courses",
+    "certifications",
+    "references",
+    "interests",
+    "personal information",
+    "contact",
+];
+
+/// Lowercases and drops Spanish accents, so headings and keywords match
+/// however the CV spells them
+fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' | 'ü' => 'u',
+            other => other,
+        })
+        .collect()
+}
+
+fn section_header(line: &str) -> Option<Section> {
+    let folded = fold(line.trim().trim_end_matches(':').trim());
+    if EXPERIENCE_HEADERS.contains(&folded.as_str()) {
+        Some(Section::Experience)
+    } else if EDUCATION_HEADERS.contains(&folded.as_str()) {
+        Some(Section::Education)
+    } else if OTHER_HEADERS.contains(&folded.as_str()) {
+        Some(Section::Other)
+    } else {
+        None
+    }
+}
+
+/// Non-empty lines of each section, in order
+fn split_sections(text: &str) -> Vec<(Section, Vec<&str>)> {
+    let mut sections = vec![(Section::Top, Vec::new())];
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match section_header(line) {
+            Some(section) => sections.push((section, Vec::new())),
+            None => sections.last_mut().expect("starts with Top").1.push(line),
+        }
+    }
+    sections
+}
+
+// ============================================================================
+// DATES
+// ============================================================================
+
+const MONTHS: &[(&str, u32)] = &[
+    ("enero", 1),
+    ("ene", 1),
+    ("january", 1),
+    ("jan", 1),
+    ("febrero", 2),
+    ("february", 2),
+    ("feb", 2),
+    ("marzo", 3),
+    ("march", 3),
+    ("mar", 3),
+    ("abril", 4),
+    ("april", 4),
+    ("abr", 4),
+    ("apr", 4),
+    ("mayo", 5),
+    ("may", 5),
+    ("junio", 6),
+    ("june", 6),
+    ("jun", 6),
+    ("julio", 7),
+    ("july", 7),
+    ("jul", 7),
+    ("agosto", 8),
+    ("august", 8),
+    ("ago", 8),
+    ("aug", 8),
+    ("septiembre", 9),
+    ("setiembre", 9),
+    ("september", 9),
+    ("sept", 9),
+    ("sep", 9),
+    ("octubre", 10),
+    ("october", 10),
+    ("oct", 10),
+    ("noviembre", 11),
+    ("november", 11),
+    ("nov", 11),
+    ("diciembre", 12),
+    ("december", 12),
+    ("dic", 12),
+    ("dec", 12),
+];
+
+/// A month (by name or number) and year, with the capture names suffixed
+fn date_pattern(suffix: &str) -> String {
+    let mut names: Vec<&str> = MONTHS.iter().map(|(name, _)| *name).collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    format!(
+        r"(?:(?P<month_name{s}>{names})\.?\s*(?:de\s+|del\s+)?|(?P<month_num{s}>0?[1-9]|1[0-2])\s*[/.\-]\s*)?(?P<year{s}>(?:19|20)\d{{2}})",
+        s = suffix,
+        names = names.join("|"),
+    )
+}
+
+/// "Marzo 2019 - Presente", "03/2019 – 12/2021", "2015 a 2019"
+static DATE_RANGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)\b{start}\s*(?:-|–|—|hasta|until|to|a)\s*(?:(?P<current>(?:la\s+)?actualidad|a\s+la\s+fecha|la\s+fecha|presente|present|current|now|hoy|today|actual)|{end})\b",
+        start = date_pattern("1"),
+        end = date_pattern("2"),
+    ))
+    .expect("Failed to compile DATE_RANGE")
+});
+
+/// A lone year, taken as the end of studies when there is no range
+static LONE_YEAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:19|20)\d{2}\b").expect("Failed to compile LONE_YEAR"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DateRange {
+    start: NaiveDate,
+    /// None when the entry runs to the present
+    end: Option<NaiveDate>,
+    /// Whether the start month was given rather than assumed
+    precise: bool,
+}
+
+/// Reads one end of a range. A missing month is January for a start and
+/// December for an end.
+fn captured_date(caps: &Captures, suffix: &str, is_end: bool) -> Option<(NaiveDate, bool)> {
+    let year: i32 = caps
+        .name(&format!("year{}", suffix))?
+        .as_str()
+        .parse()
+        .ok()?;
+    let month = if let Some(name) = caps.name(&format!("month_name{}", suffix)) {
+        let name = fold(name.as_str());
+        MONTHS.iter().find(|(n, _)| *n == name).map(|(_, m)| *m)
+    } else if let Some(number) = caps.name(&format!("month_num{}", suffix)) {
+        number.as_str().parse().ok()
+    } else {
+        None
+    };
+
+    let precise = month.is_some();
+    let month = month.unwrap_or(if is_end { 12 } else { 1 });
+    NaiveDate::from_ymd_opt(year, month, 1).map(|date| (date, precise))
+}
+
+/// The first date range on a line, and the line with the range cut out
+fn find_range(line: &str) -> Option<(DateRange, String)> {
+    let caps = DATE_RANGE.captures(line)?;
+    let (start, precise) = captured_date(&caps, "1", false)?;
+    let end = if caps.name("current").is_some() {
+        None
+    } else {
+        Some(captured_date(&caps, "2", true)?.0)
+    };
+    if end.is_some_and(|end| end < start) {
+        return None;
+    }
+
+    let whole = caps.get(0).expect("group 0 always matches");
+    let rest = format!("{} {}", &line[..whole.start()], &line[whole.end()..]);
+    Some((
+        DateRange {
+            start,
+            end,
+            precise,
+        },
+        rest,
+    ))
+}
+
+// ============================================================================
+// ENTRY HEADINGS
+// ============================================================================
+
+const COMPANY_SUFFIXES: &[&str] = &[
+    "spa", "s.a", "sa", "ltda", "limitada", "eirl", "inc", "llc", "ltd", "corp", "gmbh",
+];
+
+const COMPANY_PREFIXES: &[&str] = &[
+    "banco",
+    "municipalidad",
+    "ministerio",
+    "hospital",
+    "clinica",
+    "empresa",
+    "empresas",
+    "constructora",
+    "comercial",
+    "sociedad",
+    "servicios",
+    "inversiones",
+    "supermercado",
+    "supermercados",
+];
+
+const INSTITUTION_WORDS: &[&str] = &[
+    "universidad",
+    "university",
+    "instituto",
+    "institute",
+    "liceo",
+    "colegio",
+    "college",
+    "school",
+    "escuela",
+    "academia",
+    "duoc",
+    "inacap",
+    "cft",
+    "centro de formacion tecnica",
+    "pontificia",
+];
+
+fn words(text: &str) -> Vec<String> {
+    fold(text)
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| c == ',' || c == '.' || c == '(' || c == ')')
+                .to_string()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn looks_like_company(text: &str) -> bool {
+    let words = words(text);
+    words
+        .first()
+        .is_some_and(|w| COMPANY_PREFIXES.contains(&w.as_str()))
+        || words.iter().any(|w| COMPANY_SUFFIXES.contains(&w.as_str()))
+}
+
+fn looks_like_institution(text: &str) -> bool {
+    let folded = fold(text);
+    INSTITUTION_WORDS.iter().any(|word| {
+        folded
+            .split(|c: char| !c.is_alphanumeric() && c != ' ')
+            .any(|part| part.contains(word))
+    })
+}
+
+/// Bullets and sentences are descriptions, not headings
+fn is_heading_line(line: &str) -> bool {
+    !line.starts_with(['•', '-', '*', '·', '▪', '‣', '–', '○'])
+        && line.chars().count() <= MAX_HEADING_CHARS
+        && section_header(line).is_none()
+}
+
+/// Strips the separators left around a cut-out date range
+fn clean(text: &str) -> Option<String> {
+    let cleaned = text
+        .trim_matches(|c: char| c.is_whitespace() || "-–—|,·•()[]:".contains(c))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// Splits "Title at Company", "Title | Company", "Title - Company" or
+/// "Title, Company" into its two parts
+fn split_heading(heading: &str) -> Option<(String, String)> {
+    for separator in [" at ", " @ ", " | ", " – ", " — ", " - ", ", "] {
+        if let Some((left, right)) = heading.split_once(separator) {
+            if let (Some(left), Some(right)) = (clean(left), clean(right)) {
+                return Some((left, right));
+            }
+        }
+    }
+    None
+}
+
+/// The heading lines of an entry as a (first, second) pair, where the second
+/// part is the organization unless only the first one looks like it
+fn heading_parts(
+    lines: &[String],
+    is_organization: fn(&str) -> bool,
+) -> (Option<String>, Option<String>) {
+    let (first, second) = match lines {
+        [] => return (None, None),
+        [only] => match split_heading(only) {
+            Some((first, second)) => (first, second),
+            None => {
+                return if is_organization(only) {
+                    (None, Some(only.clone()))
+                } else {
+                    (Some(only.clone()), None)
+                };
+            }
+        },
+        [.., first, second] => (first.clone(), second.clone()),
+    };
+
+    if is_organization(&first) && !is_organization(&second) {
+        (Some(second), Some(first))
+    } else {
+        (Some(first), Some(second))
+    }
+}
+
+/// One entry of a section: the heading lines and the dates found for it
+struct Entry {
+    heading: Vec<String>,
+    range: Option<DateRange>,
+    /// Year of a lone year line, when there was no range
+    year: Option<i32>,
+}
+
+/// Groups the lines of a section into entries around their dates. The heading
+/// is the text next to the dates or, failing that, up to two heading lines
+/// before them, or the line right after them.
+fn entries(lines: &[&str], allow_lone_year: bool) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let dated = find_range(line)
+            .map(|(range, rest)| (Some(range), None, rest))
+            .or_else(|| {
+                let year = LONE_YEAR.find(line).filter(|_| allow_lone_year)?;
+                let rest = format!("{} {}", &line[..year.start()], &line[year.end()..]);
+                Some((None, year.as_str().parse().ok(), rest))
+            });
+
+        let Some((range, year, rest)) = dated else {
+            if is_heading_line(line) {
+                pending.push(line.to_string());
+            } else {
+                pending.clear();
+            }
+            i += 1;
+            continue;
+        };
+
+        let heading = match clean(&rest) {
+            Some(rest) => vec![rest],
+            None if !pending.is_empty() => {
+                let from = pending.len().saturating_sub(2);
+                pending[from..].to_vec()
+            }
+            None => match lines.get(i + 1).filter(|next| is_heading_line(next)) {
+                Some(next) if find_range(next).is_none() => {
+                    i += 1;
+                    vec![next.to_string()]
+                }
+                _ => Vec::new(),
+            },
+        };
+
+        entries.push(Entry {
+            heading,
+            range,
+            year,
+        });
+        pending.clear();
+        i += 1;
+    }
+
+    entries
+}
+
+fn confidence(found: usize) -> SuggestionConfidence {
+    match found {
+        3 => SuggestionConfidence::High,
+        2 => SuggestionConfidence::Medium,
+        _ => SuggestionConfidence::Low,
+    }
+}
+
+fn work_experiences(lines: &[&str]) -> Vec<WorkExperienceSuggestion> {
+    entries(lines, false)
+        .into_iter()
+        .filter_map(|entry| {
+            let range = entry.range?;
+            let (position_title, company_name) = heading_parts(&entry.heading, looks_like_company);
+            if position_title.is_none() && company_name.is_none() {
+                return None;
+            }
+            let found = [
+                position_title.is_some(),
+                company_name.is_some(),
+                range.precise,
+            ]
+            .iter()
+            .filter(|found| **found)
+            .count();
+
+            Some(WorkExperienceSuggestion {
+                company_name,
+                position_title,
+                start_date: Some(range.start),
+                end_date: range.end,
+                is_current: range.end.is_none(),
+                confidence: confidence(found),
+            })
+        })
+        .collect()
+}
+
+/// Level implied by the degree or institution wording
+fn education_level(text: &str) -> Option<EducationLevel> {
+    let folded = fold(text);
+    let has = |keywords: &[&str]| keywords.iter().any(|k| folded.contains(k));
+
+    if has(&["doctorado", "doctor en", "phd", "ph.d"]) {
+        Some(EducationLevel::Postgraduate)
+    } else if has(&[
+        "magister",
+        "master",
+        "mba",
+        "maestria",
+        "diplomado",
+        "postitulo",
+    ]) {
+        Some(EducationLevel::Graduate)
+    } else if has(&[
+        "ingenieria",
+        "ingeniero",
+        "licenciatura",
+        "licenciado",
+        "bachelor",
+        "universidad",
+        "university",
+    ]) {
+        Some(EducationLevel::Undergraduate)
+    } else if has(&[
+        "tecnico",
+        "technician",
+        "instituto",
+        "duoc",
+        "inacap",
+        "cft",
+    ]) {
+        Some(EducationLevel::Technical)
+    } else if has(&[
+        "ensenanza media",
+        "educacion media",
+        "liceo",
+        "high school",
+        "secundaria",
+    ]) {
+        Some(EducationLevel::Secondary)
+    } else if has(&[
+        "ensenanza basica",
+        "educacion basica",
+        "primaria",
+        "primary school",
+    ]) {
+        Some(EducationLevel::Primary)
+    } else {
+        None
+    }
+}
+
+fn education(lines: &[&str], today: NaiveDate) -> Vec<EducationSuggestion> {
+    entries(lines, true)
+        .into_iter()
+        .filter_map(|entry| {
+            let (degree_title, institution_name) =
+                heading_parts(&entry.heading, looks_like_institution);
+            if degree_title.is_none() && institution_name.is_none() {
+                return None;
+            }
+
+            let (start_date, end_date, precise) = match (entry.range, entry.year) {
+                (Some(range), _) => (Some(range.start), range.end, range.precise),
+                (None, Some(year)) => (None, NaiveDate::from_ymd_opt(year, 12, 1), false),
+                (None, None) => return None,
+            };
+            let status = match end_date {
+                Some(end) if end.year() < today.year() || end <= today => {
+                    EducationStatus::Completed
+                }
+                _ => EducationStatus::InProgress,
+            };
+            let found = [
+                degree_title.is_some(),
+                institution_name.is_some(),
+                start_date.is_some() || precise,
+            ]
+            .iter()
+            .filter(|found| **found)
+            .count();
+
+            Some(EducationSuggestion {
+                level: education_level(&entry.heading.join(" ")),
+                institution_name,
+                degree_title,
+                status: Some(status),
+                start_date,
+                end_date,
+                confidence: confidence(found),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// CONTACT DETAILS
+// ============================================================================
+
+/// Chilean mobile (9 XXXX XXXX) and Santiago landline (2 XXXX XXXX) numbers,
+/// with or without the +56 prefix
+static PHONE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\s?56[\s.\-]?)?\(?[29]\)?[\s.\-]?\d{4}[\s.\-]?\d{4}\b")
+        .expect("Failed to compile PHONE")
+});
+
+const PHONE_LABELS: &[&str] = &[
+    "tel", "fono", "celular", "cel", "movil", "phone", "mobile", "whatsapp",
+];
+
+fn phone(text: &str) -> Option<SuggestedValue> {
+    text.lines().find_map(|line| {
+        let found = PHONE.find(line)?;
+        // Part of a longer number, such as an ID or an account
+        let before = line[..found.start()].chars().last();
+        if before.is_some_and(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let value = normalize::phone(found.as_str());
+        validate_phone(&value).ok()?;
+
+        let labelled = words(line)
+            .iter()
+            .any(|w| PHONE_LABELS.iter().any(|label| w.starts_with(label)));
+        let confidence = if labelled || found.as_str().starts_with('+') {
+            SuggestionConfidence::High
+        } else {
+            SuggestionConfidence::Medium
+        };
+        Some(SuggestedValue { value, confidence })
+    })
+}
+
+/// A person's name: two to five capitalized words
+fn looks_like_name(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    (2..=5).contains(&words.len())
+        && words.iter().all(|w| {
+            w.chars().next().is_some_and(|c| c.is_uppercase())
+                && w.chars()
+                    .all(|c| c.is_alphabetic() || c == '-' || c == '\'')
+        })
+}
+
+/// The line under the name at the top of the CV, when it reads like a title
+/// rather than contact details or prose
+fn headline(top: &[&str]) -> Option<SuggestedValue> {
+    let mut candidates = top.iter().filter(|line| {
+        let folded = fold(line);
+        !line.contains('@')
+            && !folded.contains("http")
+            && !folded.contains("www.")
+            && !folded.contains("linkedin")
+            && PHONE.find(line).is_none()
+            && (3..=100).contains(&line.chars().count())
+            && line.chars().filter(|c| c.is_ascii_digit()).count() < 4
+    });
+
+    // Without a name first this is not the usual CV header
+    if !looks_like_name(candidates.next()?) {
+        return None;
+    }
+    let line = candidates.next()?;
+    if line.ends_with('.') {
+        return None;
+    }
+    let value = clean(line)?;
+    let confidence = if value.chars().count() <= 60 {
+        SuggestionConfidence::Medium
+    } else {
+        SuggestionConfidence::Low
+    };
+    Some(SuggestedValue { value, confidence })
+}
+
+// ============================================================================
+// SUGGESTIONS
+// ============================================================================
+
+/// Suggests profile data from the text of a CV. `today` decides whether
+/// studies ending in the future are still in progress.
+pub fn suggest(text: &str, today: NaiveDate) -> CvSuggestions {
+    let sections = split_sections(text);
+
+    let mut suggestions = CvSuggestions {
+        phone: phone(text),
+        ..Default::default()
+    };
+    for (section, lines) in &sections {
+        match section {
+            Section::Top => suggestions.headline = headline(lines),
+            Section::Experience => suggestions.work_experiences.extend(work_experiences(lines)),
+            Section::Education => suggestions.education.extend(education(lines, today)),
+            Section::Other => {}
+        }
+    }
+
+    if suggestions == CvSuggestions::default() {
+        suggestions.reason = Some(CvParseFailure::NothingRecognized);
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 15).unwrap()
+    }
+
+    #[test]
+    fn test_date_ranges() {
+        let (range, rest) = find_range("Marzo 2019 - Presente").unwrap();
+        assert_eq!(range.start, date(2019, 3));
+        assert_eq!(range.end, None);
+        assert!(range.precise);
+        assert!(clean(&rest).is_none());
+
+        let (range, _) = find_range("03/2017 – 12/2018").unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (date(2017, 3), Some(date(2018, 12)))
+        );
+
+        let (range, rest) = find_range("Cajera, Supermercados Líder (2015 a 2017)").unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (date(2015, 1), Some(date(2017, 12)))
+        );
+        assert!(!range.precise);
+        assert_eq!(clean(&rest).unwrap(), "Cajera, Supermercados Líder");
+
+        let (range, _) = find_range("Jan 2020 to Aug 2022").unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (date(2020, 1), Some(date(2022, 8)))
+        );
+
+        assert!(find_range("Teléfono: +56 9 8765 4321").is_none());
+        assert!(find_range("2021 - 2019").is_none());
+    }
+
+    #[test]
+    fn test_spanish_chronological_cv() {
+        let suggestions = suggest(
+            include_str!("../../tests/fixtures/cv/es_chronological.txt"),
+            today(),
+        );
+
+        assert_eq!(
+            suggestions.phone,
+            Some(SuggestedValue {
+                value: "+56987654321".to_string(),
+                confidence: SuggestionConfidence::High,
+            })
+        );
+        assert_eq!(suggestions.headline.unwrap().value, "Analista de Datos");
+
+        let experiences = &suggestions.work_experiences;
+        assert_eq!(experiences.len(), 3);
+        assert_eq!(
+            experiences[0].position_title.as_deref(),
+            Some("Analista de Datos")
+        );
+        assert_eq!(experiences[0].company_name.as_deref(), Some("Banco Estado"));
+        assert_eq!(experiences[0].start_date, Some(date(2019, 3)));
+        assert!(experiences[0].is_current);
+        assert_eq!(experiences[0].confidence, SuggestionConfidence::High);
+
+        // Company first, recognized by its legal suffix
+        assert_eq!(
+            experiences[1].position_title.as_deref(),
+            Some("Asistente de Ventas")
+        );
+        assert_eq!(
+            experiences[1].company_name.as_deref(),
+            Some("Comercial Andes SpA")
+        );
+        assert_eq!(experiences[1].end_date, Some(date(2019, 2)));
+
+        // Year-only dates are less certain
+        assert_eq!(experiences[2].position_title.as_deref(), Some("Cajera"));
+        assert_eq!(
+            experiences[2].company_name.as_deref(),
+            Some("Supermercados Líder")
+        );
+        assert_eq!(experiences[2].confidence, SuggestionConfidence::Medium);
+
+        let education = &suggestions.education;
+        assert_eq!(education.len(), 2);
+        assert_eq!(
+            education[0].degree_title.as_deref(),
+            Some("Ingeniería en Estadística")
+        );
+        assert_eq!(
+            education[0].institution_name.as_deref(),
+            Some("Universidad de Valparaíso")
+        );
+        assert_eq!(education[0].level, Some(EducationLevel::Undergraduate));
+        assert_eq!(education[0].status, Some(EducationStatus::Completed));
+        assert_eq!(
+            education[1].institution_name.as_deref(),
+            Some("Liceo Eduardo de la Barra")
+        );
+        assert_eq!(education[1].level, Some(EducationLevel::Secondary));
+        assert_eq!(education[1].end_date, Some(date(2011, 12)));
+        assert_eq!(suggestions.reason, None);
+    }
+
+    #[test]
+    fn test_english_cv_with_dates_first() {
+        let suggestions = suggest(
+            include_str!("../../tests/fixtures/cv/en_dates_first.txt"),
+            today(),
+        );
+
+        assert_eq!(suggestions.phone.unwrap().value, "+56223456789");
+        assert_eq!(suggestions.headline.unwrap().value, "Software Developer");
+
+        let experiences = &suggestions.work_experiences;
+        assert_eq!(experiences.len(), 2);
+        assert_eq!(
+            experiences[0].position_title.as_deref(),
+            Some("Backend Developer")
+        );
+        assert_eq!(
+            experiences[0].company_name.as_deref(),
+            Some("Acme Chile SpA")
+        );
+        assert_eq!(experiences[0].start_date, Some(date(2020, 1)));
+        assert_eq!(experiences[0].end_date, Some(date(2022, 8)));
+        assert!(!experiences[0].is_current);
+        assert_eq!(experiences[1].position_title.as_deref(), Some("Intern"));
+        assert_eq!(experiences[1].company_name.as_deref(), Some("Startup Labs"));
+
+        let education = &suggestions.education;
+        assert_eq!(education.len(), 1);
+        assert_eq!(education[0].level, Some(EducationLevel::Graduate));
+        assert_eq!(education[0].status, Some(EducationStatus::InProgress));
+        assert_eq!(
+            education[0].institution_name.as_deref(),
+            Some("Universidad de Chile")
+        );
+    }
+
+    #[test]
+    fn test_text_without_profile_data() {
+        let suggestions = suggest(
+            "Carta de presentación\n\nEstimados señores, les escribo por el cargo.",
+            today(),
+        );
+        assert_eq!(suggestions.reason, Some(CvParseFailure::NothingRecognized));
+        assert!(suggestions.work_experiences.is_empty());
+    }
+
+    #[test]
+    fn test_non_pdf_bytes_are_unreadable() {
+        assert_eq!(
+            extract_text(b"not a pdf at all"),
+            Err(CvParseFailure::Unreadable)
+        );
+    }
+}
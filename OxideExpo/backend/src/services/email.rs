@@ -1,7 +1,11 @@
 use crate::config::Config;
+use crate::services::alerts::AlertJob;
+use crate::utils::redact::Redactor;
+use chrono::{DateTime, Utc};
 use lettre::{
-    message::header::ContentType, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
-    AsyncTransport, Message, Tokio1Executor,
+    message::header::{ContentType, HeaderName, HeaderValue},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
 };
 
 #[derive(Clone)]
@@ -9,6 +13,11 @@ pub struct EmailService {
     mailer: AsyncSmtpTransport<Tokio1Executor>,
     from_address: String,
     frontend_url: String,
+    /// Base URL of this API, for the one-click unsubscribe header
+    api_url: String,
+    /// Set on copies used for emails the recipient can opt out of
+    unsubscribe_token: Option<String>,
+    redactor: Redactor,
 }
 
 impl EmailService {
@@ -34,9 +43,21 @@ impl EmailService {
             mailer,
             from_address: config.smtp_from.clone(),
             frontend_url: config.frontend_url.clone(),
+            api_url: config.app_base_url.clone(),
+            unsubscribe_token: None,
+            redactor: config.redactor(),
         })
     }
 
+    /// A copy whose emails end with an unsubscribe link for the token and
+    /// carry the one-click unsubscribe header (RFC 8058)
+    pub fn with_unsubscribe_token(&self, token: String) -> Self {
+        Self {
+            unsubscribe_token: Some(token),
+            ..self.clone()
+        }
+    }
+
     pub async fn send_verification_email(
         &self,
         to: &str,
@@ -95,6 +116,164 @@ El equipo de EmpleosInclusivos"#,
             .await
     }
 
+    pub async fn send_email_change_confirmation_email(
+        &self,
+        to: &str,
+        name: &str,
+        token: &str,
+    ) -> Result<(), EmailError> {
+        let confirm_url = format!(
+            "{}/auth/confirm-email-change?token={}",
+            self.frontend_url, token
+        );
+
+        let body = format!(
+            r#"Hola {},
+
+Recibimos una solicitud para usar esta dirección como correo de acceso a tu cuenta de EmpleosInclusivos.
+
+Para confirmar el cambio, haz clic en el siguiente enlace:
+{}
+
+Este enlace expirará en 24 horas. Hasta que lo confirmes, seguirás ingresando con tu correo anterior.
+
+Si no solicitaste este cambio, puedes ignorar este correo.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, confirm_url
+        );
+
+        self.send_email(to, "Confirma tu nuevo correo - EmpleosInclusivos", &body)
+            .await
+    }
+
+    pub async fn send_email_change_requested_email(
+        &self,
+        to: &str,
+        name: &str,
+        new_email: &str,
+        cancel_token: &str,
+    ) -> Result<(), EmailError> {
+        let cancel_url = format!(
+            "{}/auth/cancel-email-change?token={}",
+            self.frontend_url, cancel_token
+        );
+
+        let body = format!(
+            r#"Hola {},
+
+Se solicitó cambiar el correo de acceso de tu cuenta de EmpleosInclusivos a {}.
+
+El cambio solo se aplicará cuando se confirme desde la nueva dirección. Si no fuiste tú, cancela la solicitud con el siguiente enlace y cambia tu contraseña:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, new_email, cancel_url
+        );
+
+        self.send_email(
+            to,
+            "Solicitud de cambio de correo - EmpleosInclusivos",
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_account_deletion_scheduled_email(
+        &self,
+        to: &str,
+        name: &str,
+        scheduled_for: DateTime<Utc>,
+        restore_token: &str,
+    ) -> Result<(), EmailError> {
+        let restore_url = format!(
+            "{}/auth/restore-account?token={}",
+            self.frontend_url, restore_token
+        );
+
+        let body = format!(
+            r#"Hola {},
+
+Recibimos tu solicitud para eliminar tu cuenta de EmpleosInclusivos. Tu perfil ya no es visible para las empresas y tus postulaciones quedaron en pausa.
+
+La cuenta y tus datos personales se eliminarán definitivamente el {}. Hasta entonces puedes recuperarla con el siguiente enlace o iniciando sesión:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name,
+            scheduled_for.format("%d-%m-%Y"),
+            restore_url
+        );
+
+        self.send_email(
+            to,
+            "Eliminación de cuenta programada - EmpleosInclusivos",
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_account_claim_invitation_email(
+        &self,
+        to: &str,
+        name: &str,
+        omil_name: &str,
+        token: &str,
+        valid_days: i64,
+    ) -> Result<(), EmailError> {
+        let claim_url = format!("{}/auth/claim-account?token={}", self.frontend_url, token);
+
+        let body = format!(
+            r#"Hola {},
+
+{} te ha acompañado en tu búsqueda de empleo y registró tu información en EmpleosInclusivos.
+
+Ahora puedes ingresar tú mismo a tu cuenta. Para crear tu contraseña, haz clic en el siguiente enlace:
+{}
+
+Tus postulaciones y tu historial se mantendrán. Este enlace expirará en {} días.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, omil_name, claim_url, valid_days
+        );
+
+        self.send_email(to, "Activa tu cuenta - EmpleosInclusivos", &body)
+            .await
+    }
+
+    pub async fn send_password_setup_email(
+        &self,
+        to: &str,
+        name: &str,
+        omil_name: &str,
+        token: &str,
+        valid_days: i64,
+    ) -> Result<(), EmailError> {
+        let setup_url = format!("{}/auth/setup-password?token={}", self.frontend_url, token);
+
+        let body = format!(
+            r#"Hola {},
+
+{} creó tu cuenta en EmpleosInclusivos.
+
+Para ingresar por primera vez, crea tu contraseña en el siguiente enlace:
+{}
+
+Este enlace expirará en {} días. Si no esperabas este correo, puedes ignorarlo.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, omil_name, setup_url, valid_days
+        );
+
+        self.send_email(to, "Crea tu contraseña - EmpleosInclusivos", &body)
+            .await
+    }
+
     pub async fn send_application_received_email(
         &self,
         to: &str,
@@ -120,13 +299,547 @@ El equipo de EmpleosInclusivos"#,
             .await
     }
 
+    pub async fn send_job_invitation_email(
+        &self,
+        to: &str,
+        name: &str,
+        company_name: &str,
+        job_id: uuid::Uuid,
+        job_title: &str,
+        message: Option<&str>,
+    ) -> Result<(), EmailError> {
+        let job_url = format!("{}/jobs/{}", self.frontend_url, job_id);
+        let message = message
+            .map(|m| format!("\nMensaje de la empresa: {}\n", m))
+            .unwrap_or_default();
+
+        let body = format!(
+            r#"Hola {},
+
+{} te invita a postular al puesto de {}.
+{}
+Puedes revisar la oferta y postular en el siguiente enlace:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, job_title, message, job_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Invitación a postular: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_job_deadline_extended_email(
+        &self,
+        to: &str,
+        name: &str,
+        company_name: &str,
+        job_id: uuid::Uuid,
+        job_title: &str,
+        deadline: chrono::NaiveDate,
+    ) -> Result<(), EmailError> {
+        let job_url = format!("{}/jobs/{}", self.frontend_url, job_id);
+
+        let body = format!(
+            r#"Hola {},
+
+La oferta {} de {}, que revisaste hace poco, recibirá postulaciones
+por más tiempo: el nuevo plazo para postular es el {}.
+
+Puedes revisar la oferta y postular en el siguiente enlace:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name,
+            job_title,
+            company_name,
+            deadline.format("%d-%m-%Y"),
+            job_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Más plazo para postular: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_availability_prompt_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_title: &str,
+    ) -> Result<(), EmailError> {
+        let profile_url = format!("{}/profile", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+¡Felicitaciones! Fuiste seleccionado/a para el puesto de {}.
+
+Si ya no estás buscando empleo, actualiza tu disponibilidad en tu perfil para que las empresas dejen de enviarte invitaciones:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, job_title, profile_url
+        );
+
+        self.send_email(to, "Actualiza tu disponibilidad", &body)
+            .await
+    }
+
+    pub async fn send_profile_ready_email(&self, to: &str, name: &str) -> Result<(), EmailError> {
+        let jobs_url = format!("{}/jobs", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+¡Tu perfil está listo! Completaste los pasos necesarios para que las empresas puedan conocerte.
+
+Desde ahora recibirás recomendaciones de ofertas según tu perfil. También puedes buscar ofertas aquí:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, jobs_url
+        );
+
+        self.send_email(to, "Tu perfil está listo - EmpleosInclusivos", &body)
+            .await
+    }
+
+    pub async fn send_data_export_ready_email(
+        &self,
+        to: &str,
+        name: &str,
+        company_name: &str,
+    ) -> Result<(), EmailError> {
+        let export_url = format!("{}/company/settings", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+La exportación de datos de {} está lista. Incluye sus ofertas, los postulantes de cada oferta con sus notas y el perfil de la empresa.
+
+Puedes descargarla durante los próximos 7 días desde:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, export_url
+        );
+
+        self.send_email(to, "Tu exportación de datos está lista", &body)
+            .await
+    }
+
+    pub async fn send_automation_rule_notification_email(
+        &self,
+        to: &str,
+        name: &str,
+        rule_name: &str,
+        job_id: uuid::Uuid,
+        job_title: &str,
+    ) -> Result<(), EmailError> {
+        let applicants_url = format!("{}/company/jobs/{}/applicants", self.frontend_url, job_id);
+
+        let body = format!(
+            r#"Hola {},
+
+Una nueva postulación al puesto de {} cumplió la regla automática "{}".
+
+Puedes revisar a los postulantes en:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, job_title, rule_name, applicants_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Nueva postulación destacada: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_omil_transfer_request_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_seeker_name: &str,
+        source_omil_name: &str,
+    ) -> Result<(), EmailError> {
+        let transfers_url = format!("{}/omil/transfers", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+{} ha solicitado transferir a {} a tu OMIL.
+
+Puedes revisar y aceptar la transferencia en el siguiente enlace:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, source_omil_name, job_seeker_name, transfers_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Solicitud de transferencia: {}", job_seeker_name),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_referral_request_email(
+        &self,
+        to: &str,
+        name: &str,
+        company_name: &str,
+        job_title: &str,
+    ) -> Result<(), EmailError> {
+        let requests_url = format!("{}/omil/referral-requests", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+{} solicita a tu OMIL que le derive candidatos para el puesto de {}.
+
+Puedes revisar la solicitud y aceptarla o rechazarla en el siguiente enlace:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, job_title, requests_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Solicitud de derivación: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_referral_request_answered_email(
+        &self,
+        to: &str,
+        name: &str,
+        omil_name: &str,
+        job_title: &str,
+        accepted: bool,
+        message: Option<&str>,
+    ) -> Result<(), EmailError> {
+        let requests_url = format!("{}/company/referral-requests", self.frontend_url);
+        let outcome = if accepted {
+            "aceptó tu solicitud y comenzará a derivar candidatos"
+        } else {
+            "no pudo aceptar tu solicitud"
+        };
+        let message = message
+            .map(|m| format!("\nMensaje de la OMIL: {}\n", m))
+            .unwrap_or_default();
+
+        let body = format!(
+            r#"Hola {},
+
+{} {} para el puesto de {}.
+{}
+Puedes revisar el historial de la solicitud en el siguiente enlace:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, omil_name, outcome, job_title, message, requests_url
+        );
+
+        let subject = if accepted {
+            format!("Solicitud de derivación aceptada: {}", job_title)
+        } else {
+            format!("Solicitud de derivación rechazada: {}", job_title)
+        };
+
+        self.send_email(to, &subject, &body).await
+    }
+
+    pub async fn send_company_change_rejected_email(
+        &self,
+        to: &str,
+        name: &str,
+        company_name: &str,
+        field_name: &str,
+        new_value: &str,
+        reason: &str,
+    ) -> Result<(), EmailError> {
+        let field_label = match field_name {
+            "company_name" => "nombre de la empresa",
+            "legal_name" => "razón social",
+            "tax_id" => "RUT",
+            other => other,
+        };
+
+        let body = format!(
+            r#"Hola {},
+
+El cambio solicitado en el perfil de {} no fue aprobado.
+
+Campo: {}
+Valor solicitado: {}
+Motivo: {}
+
+Tu perfil sigue mostrando el valor aprobado anteriormente. Si necesitas
+corregir el dato, puedes enviar un nuevo cambio desde tu perfil de empresa.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, field_label, new_value, reason
+        );
+
+        self.send_email(
+            to,
+            &format!("Cambio de perfil no aprobado: {}", company_name),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_job_transferred_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_title: &str,
+        old_company_name: &str,
+        new_company_name: &str,
+    ) -> Result<(), EmailError> {
+        let body = format!(
+            r#"Hola {},
+
+El aviso {} al que postulaste ahora es gestionado por {} en lugar de {}.
+
+Tu postulación sigue vigente y {} podrá revisar tu perfil y los documentos
+que enviaste. Si no deseas continuar con esta empresa, puedes retirar tu
+postulación desde tu panel de control.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, job_title, new_company_name, old_company_name, new_company_name
+        );
+
+        self.send_email(
+            to,
+            &format!("Cambio de empresa en tu postulación: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_job_closed_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_title: &str,
+        company_name: &str,
+    ) -> Result<(), EmailError> {
+        let body = format!(
+            r#"Hola {},
+
+{} cerró el aviso {} al que postulaste, por lo que ya no recibirá
+postulaciones ni avanzará en el proceso de selección.
+
+Tu postulación seguirá visible en tu panel de control. Te invitamos a
+revisar otras ofertas publicadas en EmpleosInclusivos.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, job_title
+        );
+
+        self.send_email(
+            to,
+            &format!("Aviso cerrado: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_stale_job_nudge_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_id: uuid::Uuid,
+        job_title: &str,
+        pending_applications: i64,
+        pauses_next: bool,
+    ) -> Result<(), EmailError> {
+        let applicants_url = format!("{}/company/jobs/{}/applicants", self.frontend_url, job_id);
+        let consequence = if pauses_next {
+            "Si no hay actividad antes del próximo recordatorio, pausaremos la oferta\nhasta que la reactives."
+        } else {
+            "Revisar las postulaciones a tiempo ayuda a que las personas sigan\nconfiando en tu empresa."
+        };
+
+        let body = format!(
+            r#"Hola {},
+
+Tu oferta {} tiene {} postulaciones esperando revisión desde hace
+varios días, y nadie de tu empresa ha revisado sus postulantes.
+
+{}
+
+Puedes revisar los postulantes en:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, job_title, pending_applications, consequence, applicants_url
+        );
+
+        self.send_email(
+            to,
+            &format!("Postulaciones sin revisar: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_stale_job_paused_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_id: uuid::Uuid,
+        job_title: &str,
+    ) -> Result<(), EmailError> {
+        let job_url = format!("{}/company/jobs/{}", self.frontend_url, job_id);
+
+        let body = format!(
+            r#"Hola {},
+
+Pausamos tu oferta {} porque sus postulaciones llevan semanas sin
+revisarse y no hubo respuesta a nuestros recordatorios. Avisamos a los
+postulantes que su postulación está en pausa.
+
+Puedes reactivar la oferta en cualquier momento desde:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, job_title, job_url
+        );
+
+        self.send_email(to, &format!("Oferta pausada: {}", job_title), &body)
+            .await
+    }
+
+    pub async fn send_application_on_hold_email(
+        &self,
+        to: &str,
+        name: &str,
+        job_title: &str,
+        company_name: &str,
+    ) -> Result<(), EmailError> {
+        let body = format!(
+            r#"Hola {},
+
+{} pausó el aviso {} al que postulaste, por lo que tu postulación
+queda en pausa hasta que la empresa lo reactive.
+
+Tu postulación sigue visible en tu panel de control. Mientras tanto, te
+invitamos a revisar otras ofertas publicadas en EmpleosInclusivos.
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, company_name, job_title
+        );
+
+        self.send_email(
+            to,
+            &format!("Postulación en pausa: {}", job_title),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn send_job_alert_digest(
+        &self,
+        to: &str,
+        name: &str,
+        jobs: &[AlertJob],
+    ) -> Result<(), EmailError> {
+        let listing: String = jobs
+            .iter()
+            .map(|job| {
+                format!(
+                    "- {} en {} ({}% de coincidencia)\n  {}/jobs/{}\n",
+                    job.title, job.company_name, job.match_score, self.frontend_url, job.id
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let preferences_url = format!("{}/profile", self.frontend_url);
+
+        let body = format!(
+            r#"Hola {},
+
+Se publicaron nuevas ofertas que coinciden con tu perfil:
+
+{}
+Puedes cambiar la frecuencia de estas alertas o desactivarlas en tus
+preferencias de búsqueda:
+{}
+
+Saludos,
+El equipo de EmpleosInclusivos"#,
+            name, listing, preferences_url
+        );
+
+        let subject = match jobs.len() {
+            1 => "1 oferta nueva para ti - EmpleosInclusivos".to_string(),
+            count => format!("{} ofertas nuevas para ti - EmpleosInclusivos", count),
+        };
+        self.send_email(to, &subject, &body).await
+    }
+
     async fn send_email(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError> {
-        let email = Message::builder()
+        let mut builder = Message::builder()
             .from(self.from_address.parse().map_err(|_| EmailError::InvalidFromAddress)?)
             .to(to.parse().map_err(|_| EmailError::InvalidToAddress)?)
             .subject(subject)
-            .header(ContentType::TEXT_PLAIN)
-            .body(body.to_string())
+            .header(ContentType::TEXT_PLAIN);
+        let mut body = body.to_string();
+
+        if let Some(token) = &self.unsubscribe_token {
+            let one_click_url = format!(
+                "{}/api/notifications/unsubscribe?token={}",
+                self.api_url, token
+            );
+            builder = builder
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                    format!("<{}>", one_click_url),
+                ))
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                    "List-Unsubscribe=One-Click".to_string(),
+                ));
+            body.push_str(&format!(
+                "\n\n--\nSi no quieres recibir más correos como este, puedes darte de baja aquí:\n{}/notifications/unsubscribe?token={}",
+                self.frontend_url, token
+            ));
+        }
+
+        let email = builder
+            .body(body)
             .map_err(|e| EmailError::BuildError(e.to_string()))?;
 
         self.mailer
@@ -134,7 +847,7 @@ El equipo de EmpleosInclusivos"#,
             .await
             .map_err(|e| EmailError::SendError(e.to_string()))?;
 
-        tracing::info!("Email sent to {}: {}", to, subject);
+        tracing::info!("Email sent to {}: {}", self.redactor.email(to), subject);
         Ok(())
     }
 }
@@ -0,0 +1,129 @@
+//! Append-only log of domain events. Each event is emitted on the connection
+//! of the change it describes, so a rolled back change leaves no event
+//! behind. Support reads one entity's stream through the admin API, with
+//! personal data in the payloads redacted.
+
+use serde_json::Value;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::admin::AggregateType;
+use crate::utils::redact::REDACTED;
+
+/// Payload fields that may hold personal data or free text written about a
+/// person, at any depth
+const PII_FIELDS: &[&str] = &[
+    "email",
+    "phone",
+    "rut",
+    "first_name",
+    "last_name",
+    "address",
+    "cover_letter",
+    "message",
+    "notes",
+    "internal_notes",
+    "reason",
+    "feedback",
+];
+
+/// Records `event_type` on the aggregate, by `actor_id` when a user caused it
+pub async fn emit(
+    conn: &mut PgConnection,
+    aggregate_type: AggregateType,
+    aggregate_id: Uuid,
+    event_type: &str,
+    actor_id: Option<Uuid>,
+    payload: Value,
+) -> Result<(), sqlx::Error> {
+    emit_many(
+        conn,
+        aggregate_type,
+        &[aggregate_id],
+        event_type,
+        actor_id,
+        payload,
+    )
+    .await
+}
+
+/// Records the same event on several aggregates, e.g. after a bulk update
+pub async fn emit_many(
+    conn: &mut PgConnection,
+    aggregate_type: AggregateType,
+    aggregate_ids: &[Uuid],
+    event_type: &str,
+    actor_id: Option<Uuid>,
+    payload: Value,
+) -> Result<(), sqlx::Error> {
+    if aggregate_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO domain_events (aggregate_type, aggregate_id, event_type, actor_id, payload)
+        SELECT $1::aggregate_type, id, $3::varchar, $4::uuid, $5::jsonb
+        FROM UNNEST($2::uuid[]) WITH ORDINALITY AS ids(id, position)
+        ORDER BY position
+        "#,
+        aggregate_type as AggregateType,
+        aggregate_ids,
+        event_type,
+        actor_id,
+        payload,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// The payload with the values of personal data fields replaced
+pub fn redact_payload(payload: Value) -> Value {
+    match payload {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if value.is_null() || !PII_FIELDS.contains(&key.as_str()) {
+                        redact_payload(value)
+                    } else {
+                        Value::String(REDACTED.to_string())
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact_payload).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redacts_personal_fields_at_any_depth() {
+        let payload = json!({
+            "status": "rejected",
+            "feedback": "Le faltó experiencia",
+            "reason": null,
+            "cascade": { "job_ids": ["a"], "notes": "Llamar al 912345678" },
+            "contacts": [{ "email": "fernanda@example.cl" }],
+        });
+
+        assert_eq!(
+            redact_payload(payload),
+            json!({
+                "status": "rejected",
+                "feedback": REDACTED,
+                "reason": null,
+                "cascade": { "job_ids": ["a"], "notes": REDACTED },
+                "contacts": [{ "email": REDACTED }],
+            })
+        );
+    }
+}
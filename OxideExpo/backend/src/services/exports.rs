@@ -0,0 +1,310 @@
+use chrono::{DateTime, Utc};
+use rust_xlsxwriter::{DocProperties, Format, Workbook};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::export::{ExportAuditEntry, ExportType};
+
+/// Organization an export is made for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportOwner {
+    Omil(Uuid),
+    Company(Uuid),
+}
+
+impl ExportOwner {
+    fn omil_id(self) -> Option<Uuid> {
+        match self {
+            ExportOwner::Omil(id) => Some(id),
+            ExportOwner::Company(_) => None,
+        }
+    }
+
+    fn company_id(self) -> Option<Uuid> {
+        match self {
+            ExportOwner::Company(id) => Some(id),
+            ExportOwner::Omil(_) => None,
+        }
+    }
+}
+
+/// Who made a file and when, printed into it so a forwarded copy can be
+/// traced back to its audit row
+#[derive(Debug, Clone)]
+pub struct Watermark {
+    pub export_id: Uuid,
+    pub exported_by_name: String,
+    pub organization_name: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl Watermark {
+    /// Export ID as printed in the file
+    pub fn short_id(&self) -> String {
+        short_id(self.export_id)
+    }
+
+    pub fn text(&self) -> String {
+        format!(
+            "Exported by {} ({}) on {} UTC - Export ID {}",
+            self.exported_by_name,
+            self.organization_name,
+            self.exported_at.format("%Y-%m-%d %H:%M"),
+            self.short_id()
+        )
+    }
+
+    /// Writes the watermark on `row` of the first worksheet and into the
+    /// workbook's document properties
+    pub fn stamp(&self, workbook: &mut Workbook, row: u32) -> Result<()> {
+        let xlsx_err =
+            |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+        let properties = DocProperties::new()
+            .set_author(&self.exported_by_name)
+            .set_company(&self.organization_name)
+            .set_comment(self.text())
+            .set_custom_property("Export ID", self.short_id().as_str());
+        workbook.set_properties(&properties);
+
+        let format = Format::new().set_italic().set_font_color("#808080");
+        workbook
+            .worksheet_from_index(0)
+            .map_err(xlsx_err)?
+            .write_string_with_format(row, 0, self.text(), &format)
+            .map_err(xlsx_err)?;
+
+        Ok(())
+    }
+}
+
+/// First 8 hex digits of an export ID, upper-cased
+pub fn short_id(export_id: Uuid) -> String {
+    export_id.simple().to_string()[..8].to_uppercase()
+}
+
+/// An export in progress: the watermark to print and whether contact details
+/// may go in the file
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub owner: ExportOwner,
+    pub exported_by: Uuid,
+    pub include_contact: bool,
+    pub watermark: Watermark,
+}
+
+/// Starts an export by `user_id` for `owner`. Contact details are left out
+/// when the organization disallows them, whatever the caller asked for.
+pub async fn begin(
+    db: &PgPool,
+    owner: ExportOwner,
+    user_id: Uuid,
+    include_contact: Option<bool>,
+) -> Result<Export> {
+    let (organization_name, allow_contact_export) = match owner {
+        ExportOwner::Omil(omil_id) => {
+            let org = sqlx::query!(
+                "SELECT organization_name, allow_contact_export FROM omil_organizations WHERE id = $1",
+                omil_id
+            )
+            .fetch_one(db)
+            .await?;
+            (org.organization_name, org.allow_contact_export)
+        }
+        ExportOwner::Company(company_id) => {
+            let company = sqlx::query!(
+                "SELECT company_name, allow_contact_export FROM company_profiles WHERE id = $1",
+                company_id
+            )
+            .fetch_one(db)
+            .await?;
+            (company.company_name, company.allow_contact_export)
+        }
+    };
+
+    let exported_by_name = sqlx::query_scalar!(
+        r#"SELECT (first_name || ' ' || last_name) as "name!" FROM users WHERE id = $1"#,
+        user_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(Export {
+        owner,
+        exported_by: user_id,
+        include_contact: allow_contact_export && include_contact.unwrap_or(true),
+        watermark: Watermark {
+            export_id: Uuid::new_v4(),
+            exported_by_name,
+            organization_name,
+            exported_at: Utc::now(),
+        },
+    })
+}
+
+impl Export {
+    /// Records the finished export under the ID printed in its watermark
+    pub async fn record(
+        &self,
+        db: &PgPool,
+        export_type: ExportType,
+        filters: serde_json::Value,
+        row_count: usize,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO export_audit
+                (id, export_type, exported_by, omil_id, company_id, exported_by_name,
+                 organization_name, filters, row_count, included_contact, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            self.watermark.export_id,
+            export_type as ExportType,
+            self.exported_by,
+            self.owner.omil_id(),
+            self.owner.company_id(),
+            self.watermark.exported_by_name,
+            self.watermark.organization_name,
+            filters,
+            row_count as i32,
+            self.include_contact,
+            self.watermark.exported_at
+        )
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// AUDIT LIST
+// ============================================================================
+
+/// Which audit rows to list; unset fields don't filter
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub omil_id: Option<Uuid>,
+    pub company_id: Option<Uuid>,
+    pub exported_by: Option<Uuid>,
+    pub export_type: Option<ExportType>,
+    /// Beginning of the export ID, as printed in a file
+    pub export_id: Option<String>,
+}
+
+/// Newest exports first, with the total matching the filter
+pub async fn list(
+    db: &PgPool,
+    filter: &AuditFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<ExportAuditEntry>, i64)> {
+    let export_id = filter
+        .export_id
+        .as_deref()
+        .map(|id| id.trim().to_lowercase())
+        .filter(|id| !id.is_empty());
+
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM export_audit
+        WHERE ($1::uuid IS NULL OR omil_id = $1)
+        AND ($2::uuid IS NULL OR company_id = $2)
+        AND ($3::uuid IS NULL OR exported_by = $3)
+        AND ($4::export_type IS NULL OR export_type = $4)
+        AND ($5::text IS NULL OR replace(id::text, '-', '') LIKE $5 || '%')
+        "#,
+        filter.omil_id,
+        filter.company_id,
+        filter.exported_by,
+        filter.export_type as Option<ExportType>,
+        export_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, export_type as "export_type: ExportType", exported_by, exported_by_name,
+               omil_id, company_id, organization_name, filters, row_count,
+               included_contact, created_at
+        FROM export_audit
+        WHERE ($1::uuid IS NULL OR omil_id = $1)
+        AND ($2::uuid IS NULL OR company_id = $2)
+        AND ($3::uuid IS NULL OR exported_by = $3)
+        AND ($4::export_type IS NULL OR export_type = $4)
+        AND ($5::text IS NULL OR replace(id::text, '-', '') LIKE $5 || '%')
+        ORDER BY created_at DESC
+        LIMIT $6 OFFSET $7
+        "#,
+        filter.omil_id,
+        filter.company_id,
+        filter.exported_by,
+        filter.export_type as Option<ExportType>,
+        export_id,
+        limit,
+        offset
+    )
+    .fetch_all(db)
+    .await?;
+
+    let entries = rows
+        .into_iter()
+        .map(|row| ExportAuditEntry {
+            id: row.id,
+            short_id: short_id(row.id),
+            export_type: row.export_type,
+            exported_by: row.exported_by,
+            exported_by_name: row.exported_by_name,
+            omil_id: row.omil_id,
+            company_id: row.company_id,
+            organization_name: row.organization_name,
+            filters: row.filters,
+            row_count: row.row_count,
+            included_contact: row.included_contact,
+            created_at: row.created_at,
+        })
+        .collect();
+
+    Ok((entries, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn watermark() -> Watermark {
+        Watermark {
+            export_id: Uuid::parse_str("3f2a9c1e-0000-4000-8000-000000000000").unwrap(),
+            exported_by_name: "Pedro Soto".to_string(),
+            organization_name: "OMIL Maipú".to_string(),
+            exported_at: Utc.with_ymd_and_hms(2024, 3, 15, 14, 5, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_short_id_is_first_eight_hex_digits() {
+        assert_eq!(watermark().short_id(), "3F2A9C1E");
+    }
+
+    #[test]
+    fn test_watermark_text() {
+        assert_eq!(
+            watermark().text(),
+            "Exported by Pedro Soto (OMIL Maipú) on 2024-03-15 14:05 UTC - Export ID 3F2A9C1E"
+        );
+    }
+
+    #[test]
+    fn test_stamp_needs_a_worksheet() {
+        assert!(watermark().stamp(&mut Workbook::new(), 3).is_err());
+
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet();
+        watermark().stamp(&mut workbook, 3).unwrap();
+        assert!(!workbook.save_to_buffer().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+//! Curation of the institutions reference table. Education records link to
+//! an institution when their free-text name matches one exactly (see the
+//! `match_institution` SQL function); admins merge duplicate entries and
+//! work through the names nothing matches yet.
+
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::{AppError, Result};
+use crate::models::reference::{
+    MergeInstitutionsReport, MergeInstitutionsRequest, UnmatchedInstitutionName,
+};
+
+/// Default and largest size of the unmatched names report
+pub const DEFAULT_UNMATCHED_LIMIT: i64 = 50;
+pub const MAX_UNMATCHED_LIMIT: i64 = 200;
+
+/// Repoints education records from the duplicates to the canonical entry and
+/// deactivates the duplicates, which keep pointing at it so their names still
+/// resolve. Run it in a transaction.
+pub async fn merge(
+    conn: &mut PgConnection,
+    request: &MergeInstitutionsRequest,
+) -> Result<MergeInstitutionsReport> {
+    let mut duplicate_ids = request.duplicate_ids.clone();
+    duplicate_ids.sort();
+    duplicate_ids.dedup();
+
+    if duplicate_ids.contains(&request.canonical_id) {
+        return Err(AppError::ValidationError(
+            "The canonical institution can't also be a duplicate".to_string(),
+        ));
+    }
+
+    let canonical_active = sqlx::query_scalar!(
+        "SELECT is_active FROM institutions WHERE id = $1 FOR UPDATE",
+        request.canonical_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Institution not found".to_string()))?;
+
+    if !canonical_active {
+        return Err(AppError::ValidationError(
+            "The canonical institution must be active".to_string(),
+        ));
+    }
+
+    let found = sqlx::query_scalar!(
+        "SELECT id FROM institutions WHERE id = ANY($1) FOR UPDATE",
+        &duplicate_ids,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if let Some(missing) = duplicate_ids.iter().find(|id| !found.contains(id)) {
+        return Err(AppError::NotFound(format!(
+            "Institution {} not found",
+            missing
+        )));
+    }
+
+    let records_repointed = sqlx::query!(
+        "UPDATE education_records SET institution_id = $1 WHERE institution_id = ANY($2)",
+        request.canonical_id,
+        &duplicate_ids,
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    // Entries merged into a duplicate earlier end up at the canonical one too
+    sqlx::query!(
+        "UPDATE institutions SET merged_into_id = $1 WHERE merged_into_id = ANY($2)",
+        request.canonical_id,
+        &duplicate_ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let institutions_deactivated = sqlx::query!(
+        r#"
+        UPDATE institutions
+        SET is_active = false, merged_into_id = $1
+        WHERE id = ANY($2)
+        "#,
+        request.canonical_id,
+        &duplicate_ids,
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    // Records typed with a duplicate's name now resolve to the canonical entry
+    let records_linked = sqlx::query!(
+        r#"
+        UPDATE education_records
+        SET institution_id = $1
+        WHERE institution_id IS NULL
+        AND institution_name IS NOT NULL
+        AND match_institution(institution_name) = $1
+        "#,
+        request.canonical_id,
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    Ok(MergeInstitutionsReport {
+        records_repointed: records_repointed as i64,
+        records_linked: records_linked as i64,
+        institutions_deactivated: institutions_deactivated as i64,
+    })
+}
+
+/// Free-text institution names without a reference entry, most used first,
+/// each with the closest active institution as a merge or rename candidate
+pub async fn unmatched_names(db: &PgPool, limit: i64) -> Result<Vec<UnmatchedInstitutionName>> {
+    let names = sqlx::query_as!(
+        UnmatchedInstitutionName,
+        r#"
+        WITH spellings AS (
+            SELECT
+                LOWER(btrim(institution_name)) AS key,
+                btrim(institution_name) AS spelling,
+                COUNT(*) AS records
+            FROM education_records
+            WHERE institution_id IS NULL
+            AND institution_name IS NOT NULL
+            AND btrim(institution_name) <> ''
+            GROUP BY 1, 2
+        ),
+        names AS (
+            SELECT
+                key,
+                (array_agg(spelling ORDER BY records DESC, spelling))[1] AS name,
+                SUM(records)::bigint AS record_count,
+                COUNT(*) AS spelling_count
+            FROM spellings
+            GROUP BY key
+            ORDER BY record_count DESC, name
+            LIMIT $1
+        )
+        SELECT
+            n.name as "name!",
+            n.record_count as "record_count!",
+            n.spelling_count as "spelling_count!",
+            s.id as "suggested_institution_id?",
+            s.name as "suggested_institution_name?"
+        FROM names n
+        LEFT JOIN LATERAL (
+            SELECT i.id, i.name
+            FROM institutions i
+            WHERE i.is_active AND LOWER(i.name) % n.key
+            ORDER BY similarity(LOWER(i.name), n.key) DESC, i.name
+            LIMIT 1
+        ) s ON true
+        ORDER BY n.record_count DESC, n.name
+        "#,
+        limit,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(names)
+}
@@ -0,0 +1,250 @@
+//! Application deadlines of published jobs.
+//!
+//! The hourly sweep closes active jobs once their deadline has passed,
+//! stamping `jobs.deadline_closed_at`. Rather than reposting a job that
+//! underperformed, its company can move the deadline: that of an active job,
+//! or that of a job the sweep closed within `REOPEN_WINDOW_DAYS` of its
+//! deadline, which reopens it. A deadline change isn't a content change, so
+//! it doesn't go back through moderation, and a shorter deadline leaves the
+//! applications already made as they are. Extensions are announced to the
+//! seekers who saved the job or viewed it in the last `RECENT_VIEW_DAYS`.
+
+use chrono::{Duration, NaiveDate};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::AggregateType;
+use crate::models::job::{JobDeadlineChange, JobStatus};
+use crate::models::notification::NotificationEvent;
+use crate::services::{completeness, events, notifications};
+use crate::AppState;
+
+/// Furthest a deadline can be set, in days from today
+pub const MAX_DAYS_AHEAD: i64 = 90;
+
+/// Days after its deadline that a job closed by it can still be reopened
+pub const REOPEN_WINDOW_DAYS: i64 = 30;
+
+/// Days a view of the job counts for the audience of an extension
+pub const RECENT_VIEW_DAYS: i32 = 14;
+
+/// Closes the active jobs whose deadline has passed
+pub async fn close_expired(db: &PgPool) -> Result<Vec<Uuid>> {
+    let mut tx = db.begin().await?;
+
+    let closed = sqlx::query_scalar!(
+        r#"
+        UPDATE jobs
+        SET status = 'closed', deadline_closed_at = NOW()
+        WHERE status = 'active' AND application_deadline < CURRENT_DATE
+        RETURNING id
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    events::emit_many(
+        &mut tx,
+        AggregateType::Job,
+        &closed,
+        "closed",
+        None,
+        json!({ "from": JobStatus::Active, "cause": "deadline" }),
+    )
+    .await?;
+
+    // A passed deadline no longer counts towards completeness
+    completeness::recalculate(&mut tx, &closed).await?;
+
+    tx.commit().await?;
+
+    Ok(closed)
+}
+
+/// Checks the new deadline against today's date and the current deadline
+fn validate_deadline(today: NaiveDate, current: NaiveDate, new_deadline: NaiveDate) -> Result<()> {
+    if new_deadline <= today {
+        return Err(AppError::ValidationError(
+            "The application deadline must be in the future".to_string(),
+        ));
+    }
+    if new_deadline > today + Duration::days(MAX_DAYS_AHEAD) {
+        return Err(AppError::ValidationError(format!(
+            "The application deadline can be at most {} days from today",
+            MAX_DAYS_AHEAD
+        )));
+    }
+    if new_deadline == current {
+        return Err(AppError::ValidationError(
+            "The job already has that application deadline".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Moves the deadline of the company's job to `new_deadline`, reopening the
+/// job when the sweep had closed it, and logs the change
+pub async fn change_deadline(
+    state: &AppState,
+    company_id: Uuid,
+    job_id: Uuid,
+    changed_by: Uuid,
+    new_deadline: NaiveDate,
+) -> Result<JobDeadlineChange> {
+    let mut tx = state.db.begin().await?;
+
+    let job = sqlx::query!(
+        r#"
+        SELECT
+            j.title,
+            j.status as "status: JobStatus",
+            j.application_deadline,
+            j.deadline_closed_at,
+            cp.company_name,
+            CURRENT_DATE as "today!"
+        FROM jobs j
+        JOIN company_profiles cp ON cp.id = j.company_id
+        WHERE j.id = $1 AND j.company_id = $2
+        FOR UPDATE OF j
+        "#,
+        job_id,
+        company_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    let reopens = match job.status {
+        JobStatus::Active => false,
+        JobStatus::Closed
+            if job.deadline_closed_at.is_some()
+                && job.application_deadline
+                    >= job.today - Duration::days(REOPEN_WINDOW_DAYS) =>
+        {
+            true
+        }
+        _ => {
+            return Err(AppError::ValidationError(format!(
+                "Only the deadline of an active job, or of one closed by its deadline in the last {} days, can be changed",
+                REOPEN_WINDOW_DAYS
+            )))
+        }
+    };
+    validate_deadline(job.today, job.application_deadline, new_deadline)?;
+
+    sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET
+            application_deadline = $2,
+            status = CASE WHEN $3 THEN 'active'::job_status ELSE status END,
+            deadline_closed_at = NULL
+        WHERE id = $1
+        "#,
+        job_id,
+        new_deadline,
+        reopens,
+    )
+    .execute(&mut *tx)
+    .await?;
+    completeness::recalculate(&mut tx, &[job_id]).await?;
+
+    // Seekers who already applied don't need telling, nor anyone about a
+    // shorter deadline
+    let audience = if new_deadline > job.application_deadline {
+        sqlx::query!(
+            r#"
+            SELECT u.id, u.email as "email!", u.first_name
+            FROM users u
+            WHERE u.user_type = 'job_seeker'
+            AND u.account_status = 'active'
+            AND u.email IS NOT NULL
+            AND (
+                EXISTS (SELECT 1 FROM saved_jobs s WHERE s.job_id = $1 AND s.user_id = u.id)
+                OR EXISTS (
+                    SELECT 1 FROM job_view_history h
+                    WHERE h.job_id = $1 AND h.user_id = u.id
+                    AND h.viewed_at >= NOW() - make_interval(days => $2)
+                )
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM job_applications ja
+                WHERE ja.job_id = $1 AND ja.applicant_id = u.id
+            )
+            ORDER BY u.id
+            "#,
+            job_id,
+            RECENT_VIEW_DAYS,
+        )
+        .fetch_all(&mut *tx)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    let change = sqlx::query_as!(
+        JobDeadlineChange,
+        r#"
+        INSERT INTO job_deadline_changes
+            (job_id, previous_deadline, new_deadline, changed_by, reopened, notified_seekers)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id, job_id, previous_deadline, new_deadline, changed_by, reopened,
+            notified_seekers, changed_at
+        "#,
+        job_id,
+        job.application_deadline,
+        new_deadline,
+        changed_by,
+        reopens,
+        audience.len() as i32,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    events::emit(
+        &mut tx,
+        AggregateType::Job,
+        job_id,
+        "deadline_changed",
+        Some(changed_by),
+        json!({
+            "from": job.application_deadline,
+            "to": new_deadline,
+            "reopened": reopens,
+        }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    for seeker in audience {
+        let (email, name, company_name, title) = (
+            seeker.email,
+            seeker.first_name,
+            job.company_name.clone(),
+            job.title.clone(),
+        );
+        notifications::email(
+            state,
+            seeker.id,
+            NotificationEvent::Digest,
+            move |mailer| async move {
+                mailer
+                    .send_job_deadline_extended_email(
+                        &email,
+                        &name,
+                        &company_name,
+                        job_id,
+                        &title,
+                        new_deadline,
+                    )
+                    .await
+            },
+        );
+    }
+
+    Ok(change)
+}
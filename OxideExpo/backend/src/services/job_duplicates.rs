@@ -0,0 +1,120 @@
+use serde_json::json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::job::{DuplicateJobMatch, JobStatus, JobType};
+
+/// Descriptions at least this similar (pg_trgm, after normalization) are
+/// reported even when the titles differ
+pub const DESCRIPTION_SIMILARITY_THRESHOLD: f32 = 0.7;
+
+/// The posting being checked
+#[derive(Debug, Clone, Copy)]
+pub struct JobFingerprint<'a> {
+    pub company_id: Uuid,
+    /// The job itself when it already exists
+    pub job_id: Option<Uuid>,
+    pub title: &'a str,
+    pub description: &'a str,
+    pub region_id: Option<Uuid>,
+    pub job_type: JobType,
+}
+
+/// The company's other non-terminal jobs this one may duplicate: exact
+/// title, region and type matches first, then by description similarity.
+///
+/// Must run inside a transaction, since the trigram threshold is set locally
+/// so the `%` operator can use the description index.
+pub async fn find_duplicates(
+    conn: &mut PgConnection,
+    job: &JobFingerprint<'_>,
+) -> Result<Vec<DuplicateJobMatch>> {
+    sqlx::query!(
+        "SELECT set_config('pg_trgm.similarity_threshold', $1, true)",
+        DESCRIPTION_SIMILARITY_THRESHOLD.to_string()
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let matches = sqlx::query_as!(
+        DuplicateJobMatch,
+        r#"
+        WITH candidate AS (
+            SELECT
+                id,
+                title,
+                status,
+                lower(btrim(title)) = lower(btrim($3))
+                    AND region_id IS NOT DISTINCT FROM $4
+                    AND job_type = $5 AS exact_match,
+                similarity(
+                    normalize_job_description(description),
+                    normalize_job_description($6)
+                ) AS description_similarity
+            FROM jobs
+            WHERE company_id = $1
+              AND id IS DISTINCT FROM $2
+              AND status NOT IN ('closed', 'rejected')
+              AND (
+                  lower(btrim(title)) = lower(btrim($3))
+                  OR normalize_job_description(description) % normalize_job_description($6)
+              )
+        )
+        SELECT
+            id as "job_id!",
+            title as "title!",
+            status as "status!: JobStatus",
+            exact_match as "exact_match!",
+            description_similarity as "description_similarity!"
+        FROM candidate
+        WHERE exact_match OR description_similarity >= $7
+        ORDER BY exact_match DESC, description_similarity DESC
+        "#,
+        job.company_id,
+        job.job_id,
+        job.title,
+        job.region_id,
+        job.job_type as JobType,
+        job.description,
+        DESCRIPTION_SIMILARITY_THRESHOLD,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(matches)
+}
+
+/// 409 listing the jobs the posting matched, so the client can ask the user
+/// whether to go ahead with force=true
+pub fn duplicate_conflict(matches: &[DuplicateJobMatch]) -> AppError {
+    AppError::ConflictWithDetails {
+        message: "This job looks like a duplicate of another open job; \
+                  send force=true to continue anyway"
+            .to_string(),
+        details: json!({ "duplicates": matches }),
+    }
+}
+
+/// Remember that the job went ahead despite the matches, for moderators
+pub async fn record_override(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    matches: &[DuplicateJobMatch],
+    user_id: Uuid,
+) -> Result<()> {
+    let duplicate_ids: Vec<Uuid> = matches.iter().map(|m| m.job_id).collect();
+    sqlx::query!(
+        r#"
+        INSERT INTO job_duplicate_overrides (job_id, duplicate_job_ids, overridden_by)
+        VALUES ($1, $2, $3)
+        "#,
+        job_id,
+        &duplicate_ids,
+        user_id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,506 @@
+//! Syndication feeds of active job postings for aggregators: an XML feed in
+//! the common job-board format and schema.org JobPosting objects as JSON.
+//! Both are generated together by the scheduler and served from Redis, so
+//! a feed request never reaches Postgres.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::job::{JobType, WorkModality};
+
+pub const XML_CACHE_KEY: &str = "feeds:jobs:xml";
+pub const JSON_CACHE_KEY: &str = "feeds:jobs:json";
+
+/// The scheduler regenerates the feeds every 30 minutes; they are sent with
+/// the same max-age
+pub const REFRESH_INTERVAL_SECONDS: u64 = 30 * 60;
+
+/// Kept long enough that a few failed regenerations still leave a feed to
+/// serve, but not so long that a stale one outlives a broken scheduler
+const CACHE_TTL_SECONDS: u64 = 6 * 60 * 60;
+
+/// Most recently published jobs included in a feed
+pub const MAX_FEED_JOBS: i64 = 5000;
+
+const PUBLISHER: &str = "EmpleosInclusivos";
+const COUNTRY_CODE: &str = "CL";
+
+// ============================================================================
+// GENERATION
+// ============================================================================
+
+/// A job as it appears in the feeds
+#[derive(Debug, Clone)]
+pub struct FeedJob {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub company_name: String,
+    pub company_logo_url: Option<String>,
+    pub region: Option<String>,
+    pub municipality: Option<String>,
+    pub job_type: JobType,
+    pub work_modality: WorkModality,
+    pub salary_min: Option<f64>,
+    pub salary_max: Option<f64>,
+    pub salary_currency: Option<String>,
+    pub salary_period: Option<String>,
+    pub published_at: DateTime<Utc>,
+    pub application_deadline: NaiveDate,
+}
+
+/// Active, approved jobs still open for applications, newest first, from
+/// companies that have not opted out of syndication
+pub async fn fetch_feed_jobs(db: &PgPool) -> Result<Vec<FeedJob>> {
+    let jobs = sqlx::query_as!(
+        FeedJob,
+        r#"
+        SELECT
+            j.id,
+            j.title,
+            j.description,
+            c.company_name,
+            c.logo_url as company_logo_url,
+            r.name as "region?",
+            m.name as "municipality?",
+            j.job_type as "job_type: JobType",
+            j.work_modality as "work_modality: WorkModality",
+            j.salary_min::FLOAT8 as salary_min,
+            j.salary_max::FLOAT8 as salary_max,
+            j.salary_currency,
+            j.salary_period,
+            COALESCE(j.published_at, j.created_at) as "published_at!",
+            j.application_deadline
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        LEFT JOIN regions r ON r.id = j.region_id
+        LEFT JOIN municipalities m ON m.id = j.municipality_id
+        WHERE j.status = 'active'
+          AND j.approved_at IS NOT NULL
+          AND j.application_deadline >= CURRENT_DATE
+          AND c.syndication_enabled
+        ORDER BY COALESCE(j.published_at, j.created_at) DESC, j.id
+        LIMIT $1
+        "#,
+        MAX_FEED_JOBS
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Regenerates both feeds and stores them in Redis, returning how many jobs
+/// they contain
+pub async fn refresh(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    frontend_url: &str,
+) -> Result<usize> {
+    let jobs = fetch_feed_jobs(db).await?;
+    let generated_at = Utc::now();
+
+    let xml = render_xml(&jobs, frontend_url, generated_at);
+    let json = render_json(&jobs, frontend_url, generated_at);
+
+    let result: std::result::Result<(), redis::RedisError> = redis::pipe()
+        .set_ex(XML_CACHE_KEY, xml, CACHE_TTL_SECONDS)
+        .set_ex(JSON_CACHE_KEY, json, CACHE_TTL_SECONDS)
+        .query_async(redis)
+        .await;
+    result.map_err(|e| AppError::InternalError(format!("Failed to store job feeds: {}", e)))?;
+
+    Ok(jobs.len())
+}
+
+/// A stored feed, or None until the scheduler has generated one
+pub async fn cached(redis: &mut ConnectionManager, key: &str) -> Option<String> {
+    match redis.get::<_, Option<String>>(key).await {
+        Ok(feed) => feed,
+        Err(e) => {
+            tracing::warn!("Failed to read job feed {}: {}", key, e);
+            None
+        }
+    }
+}
+
+fn job_url(frontend_url: &str, id: Uuid) -> String {
+    format!("{}/jobs/{}", frontend_url.trim_end_matches('/'), id)
+}
+
+// ============================================================================
+// XML
+// ============================================================================
+
+/// Escapes text for XML content and attributes. Characters XML 1.0 does not
+/// allow at all, such as most control characters, are dropped.
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < '\u{20}' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn push_element(xml: &mut String, name: &str, value: &str) {
+    xml.push_str("    <");
+    xml.push_str(name);
+    xml.push('>');
+    xml.push_str(&xml_escape(value));
+    xml.push_str("</");
+    xml.push_str(name);
+    xml.push_str(">\n");
+}
+
+pub fn render_xml(jobs: &[FeedJob], frontend_url: &str, generated_at: DateTime<Utc>) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<source>\n");
+    xml.push_str(&format!("  <publisher>{}</publisher>\n", PUBLISHER));
+    xml.push_str(&format!(
+        "  <publisherurl>{}</publisherurl>\n",
+        xml_escape(frontend_url)
+    ));
+    xml.push_str(&format!(
+        "  <lastBuildDate>{}</lastBuildDate>\n",
+        generated_at.to_rfc2822()
+    ));
+
+    for job in jobs {
+        xml.push_str("  <job>\n");
+        push_element(&mut xml, "referencenumber", &job.id.to_string());
+        push_element(&mut xml, "title", &job.title);
+        push_element(&mut xml, "company", &job.company_name);
+        push_element(&mut xml, "url", &job_url(frontend_url, job.id));
+        push_element(&mut xml, "date", &job.published_at.to_rfc2822());
+        push_element(
+            &mut xml,
+            "expirationdate",
+            &job.application_deadline.to_string(),
+        );
+        push_element(&mut xml, "description", &strip_html(&job.description));
+        if let Some(municipality) = &job.municipality {
+            push_element(&mut xml, "city", municipality);
+        }
+        if let Some(region) = &job.region {
+            push_element(&mut xml, "state", region);
+        }
+        push_element(&mut xml, "country", COUNTRY_CODE);
+        push_element(&mut xml, "jobtype", employment_type(job.job_type));
+        if job.work_modality == WorkModality::Remote {
+            push_element(&mut xml, "remotetype", "remote");
+        }
+        if let Some(salary) = salary_text(job) {
+            push_element(&mut xml, "salary", &salary);
+        }
+        xml.push_str("  </job>\n");
+    }
+
+    xml.push_str("</source>\n");
+    xml
+}
+
+/// "500000 - 700000 CLP monthly", or None when the job shows no salary
+fn salary_text(job: &FeedJob) -> Option<String> {
+    let range = match (job.salary_min, job.salary_max) {
+        (Some(min), Some(max)) if min == max => format!("{}", min),
+        (Some(min), Some(max)) => format!("{} - {}", min, max),
+        (Some(min), None) => format!("{}+", min),
+        (None, Some(max)) => format!("up to {}", max),
+        (None, None) => return None,
+    };
+
+    let mut text = range;
+    if let Some(currency) = &job.salary_currency {
+        text.push(' ');
+        text.push_str(currency);
+    }
+    if let Some(period) = &job.salary_period {
+        text.push(' ');
+        text.push_str(period);
+    }
+    Some(text)
+}
+
+// ============================================================================
+// HTML STRIPPING
+// ============================================================================
+
+/// Plain text from a job description that may contain HTML. Tags are
+/// removed, block-level ones become line breaks, common entities are
+/// decoded and blank lines are dropped.
+pub fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            // A lone '<' is text, not a tag
+            text.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let tag = rest[start + 1..start + end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if matches!(
+            tag.as_str(),
+            "br" | "p"
+                | "div"
+                | "li"
+                | "ul"
+                | "ol"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "tr"
+        ) {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    let decoded = decode_entities(&text);
+
+    let lines: Vec<String> = decoded
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.join("\n")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        // Last, so "&amp;lt;" stays the literal text "&lt;"
+        .replace("&amp;", "&")
+}
+
+// ============================================================================
+// SCHEMA.ORG JSON
+// ============================================================================
+
+/// schema.org employmentType for a job type
+fn employment_type(job_type: JobType) -> &'static str {
+    match job_type {
+        JobType::FullTime => "FULL_TIME",
+        JobType::PartTime => "PART_TIME",
+        JobType::Contract | JobType::Freelance => "CONTRACTOR",
+        JobType::Internship => "INTERN",
+        JobType::Temporary => "TEMPORARY",
+    }
+}
+
+/// schema.org unitText for a stored salary period
+fn salary_unit(period: Option<&str>) -> &'static str {
+    match period {
+        Some("hourly") => "HOUR",
+        Some("daily") => "DAY",
+        Some("weekly") => "WEEK",
+        Some("yearly") => "YEAR",
+        _ => "MONTH",
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonFeed {
+    pub generated_at: DateTime<Utc>,
+    pub jobs: Vec<JobPosting>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobPosting {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    identifier: PropertyValue,
+    title: String,
+    description: String,
+    url: String,
+    date_posted: String,
+    valid_through: String,
+    employment_type: &'static str,
+    hiring_organization: Organization,
+    job_location: Place,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job_location_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_salary: Option<MonetaryAmount>,
+}
+
+#[derive(Debug, Serialize)]
+struct PropertyValue {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    name: &'static str,
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Organization {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logo: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Place {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    address: PostalAddress,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostalAddress {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address_locality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address_region: Option<String>,
+    address_country: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct MonetaryAmount {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    currency: String,
+    value: QuantitativeValue,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuantitativeValue {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_value: Option<f64>,
+    unit_text: &'static str,
+}
+
+fn job_posting(job: &FeedJob, frontend_url: &str) -> JobPosting {
+    let base_salary =
+        (job.salary_min.is_some() || job.salary_max.is_some()).then(|| MonetaryAmount {
+            kind: "MonetaryAmount",
+            currency: job
+                .salary_currency
+                .clone()
+                .unwrap_or_else(|| "CLP".to_string()),
+            value: QuantitativeValue {
+                kind: "QuantitativeValue",
+                min_value: job.salary_min,
+                max_value: job.salary_max,
+                unit_text: salary_unit(job.salary_period.as_deref()),
+            },
+        });
+
+    JobPosting {
+        context: "https://schema.org",
+        kind: "JobPosting",
+        identifier: PropertyValue {
+            kind: "PropertyValue",
+            name: PUBLISHER,
+            value: job.id.to_string(),
+        },
+        title: job.title.clone(),
+        description: strip_html(&job.description),
+        url: job_url(frontend_url, job.id),
+        date_posted: job.published_at.to_rfc3339(),
+        // Applications are accepted through the whole deadline day
+        valid_through: format!("{}T23:59:59", job.application_deadline),
+        employment_type: employment_type(job.job_type),
+        hiring_organization: Organization {
+            kind: "Organization",
+            name: job.company_name.clone(),
+            logo: job.company_logo_url.clone(),
+        },
+        job_location: Place {
+            kind: "Place",
+            address: PostalAddress {
+                kind: "PostalAddress",
+                address_locality: job.municipality.clone(),
+                address_region: job.region.clone(),
+                address_country: COUNTRY_CODE,
+            },
+        },
+        job_location_type: (job.work_modality == WorkModality::Remote).then_some("TELECOMMUTE"),
+        base_salary,
+    }
+}
+
+pub fn render_json(jobs: &[FeedJob], frontend_url: &str, generated_at: DateTime<Utc>) -> String {
+    let feed = JsonFeed {
+        generated_at,
+        jobs: jobs
+            .iter()
+            .map(|job| job_posting(job, frontend_url))
+            .collect(),
+    };
+    // Only strings, numbers and timestamps, so serialization cannot fail
+    serde_json::to_string(&feed).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_escape_handles_markup_and_control_characters() {
+        assert_eq!(
+            xml_escape(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;"
+        );
+        assert_eq!(xml_escape("a\u{0}b\u{1b}c\td\u{FFFF}"), "abc\td");
+        assert_eq!(xml_escape("Ñandú ]]> café"), "Ñandú ]]&gt; café");
+    }
+
+    #[test]
+    fn test_strip_html_keeps_text_and_line_breaks() {
+        assert_eq!(
+            strip_html("<p>Bodega <b>central</b></p><p></p><ul><li>Turno&nbsp;noche</li><li>A &amp; B</li></ul>"),
+            "Bodega central\nTurno noche\nA & B"
+        );
+        assert_eq!(strip_html("Sueldo < 500 y &amp;lt;"), "Sueldo < 500 y &lt;");
+        assert_eq!(strip_html("  texto   plano  "), "texto plano");
+    }
+
+    #[test]
+    fn test_employment_type_covers_job_types() {
+        assert_eq!(employment_type(JobType::FullTime), "FULL_TIME");
+        assert_eq!(employment_type(JobType::Freelance), "CONTRACTOR");
+        assert_eq!(salary_unit(Some("hourly")), "HOUR");
+        assert_eq!(salary_unit(None), "MONTH");
+    }
+}
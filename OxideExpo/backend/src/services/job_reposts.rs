@@ -0,0 +1,152 @@
+//! Reposts of a vacancy.
+//!
+//! A company that closes a job and posts it again can link the new job to
+//! the old one through `jobs.reposted_from_job_id`. People who applied to an
+//! earlier posting are then flagged to the company, warned when they apply
+//! again, and ranked a little lower among recommended candidates when they
+//! were rejected. Links are followed back up to `MAX_PREDECESSORS` postings.
+
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::application::ApplicationStatus;
+use crate::models::job::JobStatus;
+
+/// Earlier postings of a vacancy looked at, following the repost links
+pub const MAX_PREDECESSORS: i32 = 3;
+
+/// Checks that the job a new posting reposts is another job of the same
+/// company that is no longer open
+pub async fn validate_predecessor(
+    conn: &mut PgConnection,
+    company_id: Uuid,
+    predecessor_id: Uuid,
+) -> Result<()> {
+    let status = sqlx::query_scalar!(
+        r#"
+        SELECT status as "status: JobStatus"
+        FROM jobs
+        WHERE id = $1 AND company_id = $2
+        "#,
+        predecessor_id,
+        company_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| {
+        AppError::ValidationError(
+            "A job can only be a repost of another job of your company".to_string(),
+        )
+    })?;
+
+    if !matches!(status, JobStatus::Closed | JobStatus::Rejected) {
+        return Err(AppError::ValidationError(
+            "Only closed or rejected jobs can be reposted".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Earlier postings of the job, the one it reposts first, up to
+/// `MAX_PREDECESSORS` of them
+pub async fn predecessors(conn: impl PgExecutor<'_>, job_id: Uuid) -> Result<Vec<Uuid>> {
+    let ids = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE chain AS (
+            SELECT reposted_from_job_id AS id, 1 AS depth
+            FROM jobs
+            WHERE id = $1 AND reposted_from_job_id IS NOT NULL
+            UNION ALL
+            SELECT j.reposted_from_job_id, c.depth + 1
+            FROM chain c
+            JOIN jobs j ON j.id = c.id
+            WHERE j.reposted_from_job_id IS NOT NULL AND c.depth < $2
+        )
+        SELECT id as "id!" FROM chain ORDER BY depth
+        "#,
+        job_id,
+        MAX_PREDECESSORS,
+    )
+    .fetch_all(conn)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Applicants to the job who also applied to one of its earlier postings
+pub async fn count_repeat_applicants(db: &PgPool, job_id: Uuid) -> Result<i64> {
+    let earlier = predecessors(db, job_id).await?;
+    if earlier.is_empty() {
+        return Ok(0);
+    }
+
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM job_applications ja
+        WHERE ja.job_id = $1
+        AND EXISTS (
+            SELECT 1 FROM job_applications p
+            WHERE p.applicant_id = ja.applicant_id AND p.job_id = ANY($2)
+        )
+        "#,
+        job_id,
+        &earlier,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(count)
+}
+
+/// How the seeker's application to an earlier posting ended, as the end of
+/// "You applied to an earlier posting of this job in March 2025 and ..."
+fn outcome(status: ApplicationStatus) -> &'static str {
+    match status {
+        ApplicationStatus::Hired => "were hired",
+        ApplicationStatus::Rejected => "were not selected",
+        ApplicationStatus::Withdrawn => "withdrew your application",
+        _ => "the posting closed before a decision was made",
+    }
+}
+
+/// Notice for a seeker applying to a repost of a job they already applied
+/// to, with how their latest earlier application ended. Never stops the
+/// submission.
+pub async fn previous_application_notice(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    applicant_id: Uuid,
+    timezone: &str,
+) -> Result<Option<String>> {
+    let earlier = predecessors(&mut *conn, job_id).await?;
+    if earlier.is_empty() {
+        return Ok(None);
+    }
+
+    let previous = sqlx::query!(
+        r#"
+        SELECT
+            status as "status: ApplicationStatus",
+            to_char(applied_at AT TIME ZONE $3, 'FMMonth YYYY') as "applied_in!"
+        FROM job_applications
+        WHERE applicant_id = $1 AND job_id = ANY($2)
+        ORDER BY applied_at DESC
+        LIMIT 1
+        "#,
+        applicant_id,
+        &earlier,
+        timezone,
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(previous.map(|previous| {
+        format!(
+            "You applied to an earlier posting of this job in {} and {}.",
+            previous.applied_in,
+            outcome(previous.status)
+        )
+    }))
+}
@@ -0,0 +1,190 @@
+//! Moving a job posting, with its applicants, to another company, for jobs
+//! posted under the wrong legal entity or companies that restructure.
+//! Applications, their notes, invitations and saved jobs point at the job and
+//! move with it. Tags are the old company's own labels and stay behind.
+
+use serde_json::json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::services::counters;
+
+/// Someone with a live application to a transferred job
+pub struct AffectedApplicant {
+    pub user_id: Uuid,
+    pub email: String,
+    pub first_name: String,
+}
+
+/// What a transfer changed, for the audit log and the applicant emails
+pub struct JobTransfer {
+    pub job_title: String,
+    pub from_company_id: Uuid,
+    pub from_company_name: String,
+    pub to_company_name: String,
+    pub applications_moved: i64,
+    pub applicants: Vec<AffectedApplicant>,
+    /// Before/after snapshot of everything the transfer touched
+    pub audit_details: serde_json::Value,
+}
+
+/// Moves the job to `to_company_id`, posted by that company's owner. Jobs
+/// pending approval are refused so a moderator never reviews a posting whose
+/// company changed under them.
+pub async fn transfer(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    to_company_id: Uuid,
+) -> Result<JobTransfer> {
+    let job = sqlx::query!(
+        r#"
+        SELECT j.title, j.company_id, j.posted_by, j.status::text as "status!",
+               j.applications_count, c.company_name
+        FROM jobs j
+        JOIN company_profiles c ON c.id = j.company_id
+        WHERE j.id = $1
+        FOR UPDATE OF j
+        "#,
+        job_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+    if job.status == "pending_approval" {
+        return Err(AppError::ValidationError(
+            "Jobs pending approval cannot be transferred; approve or reject them first".to_string(),
+        ));
+    }
+    if job.company_id == to_company_id {
+        return Err(AppError::ValidationError(
+            "The job already belongs to this company".to_string(),
+        ));
+    }
+
+    let target = sqlx::query!(
+        r#"
+        SELECT c.company_name, c.status::text as "status!",
+            (
+                SELECT m.user_id FROM company_members m
+                WHERE m.company_id = c.id AND m.role = 'owner' AND m.is_active
+                ORDER BY m.joined_at
+                LIMIT 1
+            ) as owner_id
+        FROM company_profiles c
+        WHERE c.id = $1
+        "#,
+        to_company_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Company not found".to_string()))?;
+
+    if target.status != "active" {
+        return Err(AppError::ValidationError(
+            "Jobs can only be transferred to an active company".to_string(),
+        ));
+    }
+    let owner_id = target.owner_id.ok_or_else(|| {
+        AppError::ValidationError("The company has no active owner to post the job".to_string())
+    })?;
+
+    sqlx::query!(
+        "UPDATE jobs SET company_id = $2, posted_by = $3, updated_at = NOW() WHERE id = $1",
+        job_id,
+        to_company_id,
+        owner_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Referral requests name the company that asked for them
+    let referral_requests = sqlx::query!(
+        "UPDATE omil_referral_requests SET company_id = $2 WHERE job_id = $1",
+        job_id,
+        to_company_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    let tag_assignments = sqlx::query!(
+        r#"
+        DELETE FROM application_tag_assignments ta
+        USING application_tags t, job_applications ja
+        WHERE t.id = ta.tag_id
+          AND ja.id = ta.application_id
+          AND ja.job_id = $1
+          AND t.company_id = $2
+        "#,
+        job_id,
+        job.company_id
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    let moved = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) as "applications!",
+            (
+                SELECT COUNT(*) FROM application_notes n
+                JOIN job_applications a ON a.id = n.application_id
+                WHERE a.job_id = $1
+            ) as "notes!"
+        FROM job_applications
+        WHERE job_id = $1
+        "#,
+        job_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let applications_count = counters::recount_job(&mut *conn, job_id).await?;
+
+    let applicants = sqlx::query_as!(
+        AffectedApplicant,
+        r#"
+        SELECT u.id as user_id, u.email as "email!", u.first_name
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        WHERE ja.job_id = $1 AND ja.status <> 'withdrawn'
+          -- Seekers managed offline have no email to notify
+          AND u.email IS NOT NULL
+        ORDER BY ja.applied_at
+        "#,
+        job_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let audit_details = json!({
+        "before": {
+            "company_id": job.company_id,
+            "posted_by": job.posted_by,
+            "applications_count": job.applications_count,
+        },
+        "after": {
+            "company_id": to_company_id,
+            "posted_by": owner_id,
+            "applications_count": applications_count,
+        },
+        "applications_moved": moved.applications,
+        "notes_moved": moved.notes,
+        "referral_requests_moved": referral_requests,
+        "tag_assignments_removed": tag_assignments,
+        "applicants_notified": applicants.len(),
+    });
+
+    Ok(JobTransfer {
+        job_title: job.title,
+        from_company_id: job.company_id,
+        from_company_name: job.company_name,
+        to_company_name: target.company_name,
+        applications_moved: moved.applications,
+        applicants,
+        audit_details,
+    })
+}
@@ -0,0 +1,89 @@
+//! Usage quotas counted in Redis, for features that cap how often something
+//! may be done. Counters fail open: when Redis is unavailable the action is
+//! let through and the failure logged.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// Seconds a daily counter outlives its day, so a slow clock doesn't drop
+/// it early
+const DAILY_KEY_GRACE_SECONDS: i64 = 3600;
+
+/// Calendar day a daily quota counts in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayWindow {
+    pub day: NaiveDate,
+    /// When the next day starts and the quota resets
+    pub resets_at: DateTime<Utc>,
+}
+
+impl DayWindow {
+    /// Seconds a counter for this day has to live at `now`
+    pub fn ttl_seconds(&self, now: DateTime<Utc>) -> i64 {
+        (self.resets_at - now).num_seconds().max(0) + DAILY_KEY_GRACE_SECONDS
+    }
+}
+
+/// The day `now` falls on in `timezone`, which Postgres resolves with its
+/// own zone data as the reports do
+pub async fn day_window(db: &PgPool, timezone: &str, now: DateTime<Utc>) -> Result<DayWindow> {
+    let window = sqlx::query!(
+        r#"
+        SELECT
+            ($2::timestamptz AT TIME ZONE $1)::date as "day!",
+            ((($2::timestamptz AT TIME ZONE $1)::date + 1)::timestamp AT TIME ZONE $1) as "resets_at!"
+        "#,
+        timezone,
+        now,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(DayWindow {
+        day: window.day,
+        resets_at: window.resets_at,
+    })
+}
+
+/// Key of the `quota` counter of `subject` for one day
+pub fn daily_key(quota: &str, subject: Uuid, day: NaiveDate) -> String {
+    format!("limits:{}:{}:{}", quota, subject, day)
+}
+
+/// Takes one use of the counter at `key`, which expires `ttl_seconds` after
+/// its first use. Once `limit` uses are taken it returns false and takes
+/// nothing.
+pub async fn take(redis: &mut ConnectionManager, key: &str, limit: i64, ttl_seconds: i64) -> bool {
+    let used: i64 = match redis.incr(key, 1).await {
+        Ok(used) => used,
+        Err(e) => {
+            tracing::warn!("Failed to count quota {}: {}", key, e);
+            return true;
+        }
+    };
+    if used == 1 {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.expire(key, ttl_seconds).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to expire quota {}: {}", key, e);
+        }
+    }
+
+    if used > limit {
+        give_back(redis, key).await;
+        return false;
+    }
+    true
+}
+
+/// Returns a use taken for an action that then didn't happen
+pub async fn give_back(redis: &mut ConnectionManager, key: &str) {
+    let result: std::result::Result<i64, redis::RedisError> = redis.incr(key, -1).await;
+    if let Err(e) = result {
+        tracing::warn!("Failed to give back quota {}: {}", key, e);
+    }
+}
@@ -0,0 +1,79 @@
+//! Platform-wide maintenance mode, read on every API request.
+//!
+//! The mode lives in the `maintenance_mode` setting and is cached for a few
+//! seconds only, so flipping it in the database takes effect across servers
+//! without a restart even when the admin endpoint isn't used.
+
+use serde_json::Value;
+
+use crate::services::settings;
+use crate::AppState;
+
+/// How long servers keep using a cached mode
+const CACHE_TTL_SECONDS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceMode {
+    Off,
+    /// Reads keep working; writes are rejected for everyone but admins
+    ReadOnly,
+    /// Only health checks and the admin settings endpoint answer
+    Full,
+}
+
+impl MaintenanceMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaintenanceMode::Off => "off",
+            MaintenanceMode::ReadOnly => "read_only",
+            MaintenanceMode::Full => "full",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "read_only" => MaintenanceMode::ReadOnly,
+            "full" => MaintenanceMode::Full,
+            _ => MaintenanceMode::Off,
+        }
+    }
+}
+
+/// The current mode. When the setting can't be read the API stays up:
+/// failing closed would turn a Redis or database hiccup into an outage.
+pub async fn current(state: &AppState) -> MaintenanceMode {
+    let mut redis = state.redis.clone();
+    match settings::get_value_with_ttl(&state.db, &mut redis, "maintenance_mode", CACHE_TTL_SECONDS)
+        .await
+    {
+        Ok(Value::String(mode)) => MaintenanceMode::parse(&mode),
+        Ok(value) => {
+            tracing::warn!("Unexpected maintenance_mode value {}", value);
+            MaintenanceMode::Off
+        }
+        Err(e) => {
+            tracing::error!("Failed to read maintenance mode: {}", e);
+            MaintenanceMode::Off
+        }
+    }
+}
+
+/// The operator's message for users, when one is set
+pub async fn message(state: &AppState) -> Option<String> {
+    let mut redis = state.redis.clone();
+    match settings::get_value_with_ttl(
+        &state.db,
+        &mut redis,
+        "maintenance_message",
+        CACHE_TTL_SECONDS,
+    )
+    .await
+    {
+        Ok(Value::String(message)) if !message.trim().is_empty() => Some(message),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::error!("Failed to read maintenance message: {}", e);
+            None
+        }
+    }
+}
@@ -1,21 +1,46 @@
-use sqlx::PgPool;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use redis::aio::ConnectionManager;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::error::{AppError, Result};
 use crate::models::matching::*;
+use crate::services::limits;
 
 // ============================================================================
 // MATCH SCORE WEIGHTS (out of 100 total)
 // ============================================================================
 
-const SKILLS_WEIGHT: i32 = 35;
+pub const SKILLS_WEIGHT: i32 = 35;
 const LANGUAGES_WEIGHT: i32 = 15;
 const LOCATION_WEIGHT: i32 = 15;
 const EXPERIENCE_WEIGHT: i32 = 15;
 const EDUCATION_WEIGHT: i32 = 10;
-const PREFERRED_SKILLS_WEIGHT: i32 = 5;
+pub const PREFERRED_SKILLS_WEIGHT: i32 = 5;
 const ACCOMMODATIONS_WEIGHT: i32 = 5;
 
+const _: () = assert!(
+    SKILLS_WEIGHT
+        + LANGUAGES_WEIGHT
+        + LOCATION_WEIGHT
+        + EXPERIENCE_WEIGHT
+        + EDUCATION_WEIGHT
+        + PREFERRED_SKILLS_WEIGHT
+        + ACCOMMODATIONS_WEIGHT
+        == 100
+);
+
+/// Proficiency levels an endorsed skill is credited above its self-reported level
+pub const ENDORSEMENT_PROFICIENCY_BONUS: i32 = 1;
+
+/// Points a score must move from the last history point to record a new one
+pub const MATCH_SCORE_HISTORY_THRESHOLD: i32 = 2;
+
+/// History points kept per job and seeker; older ones are pruned
+pub const MAX_MATCH_SCORE_HISTORY: i64 = 50;
+
 // ============================================================================
 // EDUCATION LEVEL ORDERING
 // ============================================================================
@@ -48,30 +73,71 @@ fn language_proficiency_to_int(proficiency: &str) -> i32 {
     }
 }
 
+fn education_level_label(level: &str) -> &'static str {
+    match level.to_lowercase().as_str() {
+        "primary" => "educación básica",
+        "secondary" => "educación media",
+        "technical" => "técnico",
+        "undergraduate" => "universitario",
+        "graduate" => "postítulo",
+        "postgraduate" => "postgrado",
+        _ => "el nivel requerido",
+    }
+}
+
+fn language_proficiency_label(level: i32) -> &'static str {
+    match level {
+        1 => "básico",
+        2 => "intermedio",
+        3 => "avanzado",
+        4 => "fluido",
+        _ => "nativo",
+    }
+}
+
 // ============================================================================
 // INTERNAL DATA STRUCTURES
 // ============================================================================
 
+#[derive(Debug, Clone)]
 struct UserSkillData {
     skill_id: Uuid,
     proficiency_level: i32,
+    is_endorsed: bool,
 }
 
+impl UserSkillData {
+    /// Proficiency counted against job requirements. An endorsement vouches
+    /// for the skill, so it covers a requirement one level above the
+    /// self-reported level.
+    fn effective_proficiency(&self) -> i32 {
+        if self.is_endorsed {
+            self.proficiency_level + ENDORSEMENT_PROFICIENCY_BONUS
+        } else {
+            self.proficiency_level
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 struct UserLanguageData {
     language_id: Uuid,
     proficiency: String,
 }
 
+#[derive(Debug, Clone)]
 struct JobRequiredSkillData {
     skill_id: Uuid,
     minimum_proficiency: i32,
 }
 
+#[derive(Debug, Clone)]
 struct JobRequiredLanguageData {
     language_id: Uuid,
     minimum_proficiency: i32,
 }
 
+#[derive(Debug, Clone)]
 struct JobLocationData {
     region_id: Option<Uuid>,
     municipality_id: Option<Uuid>,
@@ -79,28 +145,66 @@ struct JobLocationData {
     work_modality: String,
 }
 
+#[derive(Debug, Clone, Default)]
 struct UserLocationData {
     region_id: Option<Uuid>,
     municipality_id: Option<Uuid>,
 }
 
+#[derive(Debug, Clone, Default)]
 struct UserExperienceData {
     total_years: i32,
 }
 
+#[derive(Debug, Clone, Default)]
 struct UserEducationData {
     highest_level: Option<String>,
 }
 
+#[derive(Debug, Clone, Default)]
 struct UserDisabilityData {
     categories: Vec<String>,
     requires_accommodations: bool,
 }
 
+#[derive(Debug, Clone)]
 struct JobAccommodationData {
     categories: Vec<String>,
 }
 
+/// A seeker's half of the match inputs
+#[derive(Debug, Clone, Default)]
+struct UserMatchData {
+    skills: Vec<UserSkillData>,
+    languages: Vec<UserLanguageData>,
+    location: UserLocationData,
+    experience: UserExperienceData,
+    education: UserEducationData,
+    disability: UserDisabilityData,
+    willing_to_relocate: bool,
+}
+
+/// Everything a match score is computed from, fetched up front so the
+/// scoring itself needs no database
+#[derive(Debug, Clone)]
+struct MatchInputs {
+    user_skills: Vec<UserSkillData>,
+    user_languages: Vec<UserLanguageData>,
+    user_location: UserLocationData,
+    user_experience: UserExperienceData,
+    user_education: UserEducationData,
+    user_disability: UserDisabilityData,
+    willing_to_relocate: bool,
+    job_required_skills: Vec<JobRequiredSkillData>,
+    job_preferred_skills: Vec<Uuid>,
+    job_required_languages: Vec<JobRequiredLanguageData>,
+    job_location: JobLocationData,
+    job_experience_min: Option<i32>,
+    job_experience_max: Option<i32>,
+    job_education: Option<String>,
+    job_accommodations: JobAccommodationData,
+}
+
 // ============================================================================
 // MATCHING SERVICE
 // ============================================================================
@@ -114,85 +218,113 @@ impl MatchingService {
         job_id: Uuid,
         user_id: Uuid,
     ) -> Result<MatchScoreBreakdown> {
-        // Fetch all required data in parallel
+        let mut scores = Self::calculate_match_scores(db, job_id, &[user_id]).await?;
+        scores
+            .remove(&user_id)
+            .ok_or_else(|| AppError::InternalError("Match score missing".to_string()))
+    }
+
+    /// Match scores of several seekers against one job, keyed by seeker.
+    /// The job is fetched once and each kind of seeker data in one query for
+    /// all of them, so listings don't query per seeker.
+    pub async fn calculate_match_scores(
+        db: &PgPool,
+        job_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MatchScoreBreakdown>> {
         let (
-            user_skills,
-            user_languages,
+            mut users,
             job_required_skills,
             job_preferred_skills,
             job_required_languages,
             job_location,
-            user_location,
-            user_experience,
-            user_education,
-            user_disability,
             job_accommodations,
-            user_preferences,
+            (job_experience_min, job_experience_max),
+            job_education,
         ) = tokio::try_join!(
-            Self::get_user_skills(db, user_id),
-            Self::get_user_languages(db, user_id),
+            Self::get_users_data(db, user_ids),
             Self::get_job_required_skills(db, job_id),
             Self::get_job_preferred_skills(db, job_id),
             Self::get_job_required_languages(db, job_id),
             Self::get_job_location(db, job_id),
-            Self::get_user_location(db, user_id),
-            Self::get_user_experience(db, user_id),
-            Self::get_user_education(db, user_id),
-            Self::get_user_disability(db, user_id),
             Self::get_job_accommodations(db, job_id),
-            Self::get_user_preferences(db, user_id),
+            Self::get_job_experience_requirements(db, job_id),
+            Self::get_job_education_requirement(db, job_id),
         )?;
 
-        // Get job experience requirements
-        let job_experience = Self::get_job_experience_requirements(db, job_id).await?;
-        let job_education = Self::get_job_education_requirement(db, job_id).await?;
+        Ok(user_ids
+            .iter()
+            .map(|user_id| {
+                let user = users.remove(user_id).unwrap_or_default();
+                let breakdown = Self::score(&MatchInputs {
+                    user_skills: user.skills,
+                    user_languages: user.languages,
+                    user_location: user.location,
+                    user_experience: user.experience,
+                    user_education: user.education,
+                    user_disability: user.disability,
+                    willing_to_relocate: user.willing_to_relocate,
+                    job_required_skills: job_required_skills.clone(),
+                    job_preferred_skills: job_preferred_skills.clone(),
+                    job_required_languages: job_required_languages.clone(),
+                    job_location: job_location.clone(),
+                    job_experience_min,
+                    job_experience_max,
+                    job_education: job_education.clone(),
+                    job_accommodations: job_accommodations.clone(),
+                });
+                (*user_id, breakdown)
+            })
+            .collect())
+    }
 
-        // Calculate each component
-        let skills_detail = Self::calculate_skills_score(
-            &user_skills,
-            &job_required_skills,
-            &job_preferred_skills,
+    /// Scores the fetched data. The total is the sum of the components, whose
+    /// maximums add up to 100.
+    fn score(inputs: &MatchInputs) -> MatchScoreBreakdown {
+        let skills = Self::calculate_skills_score(
+            &inputs.user_skills,
+            &inputs.job_required_skills,
+            &inputs.job_preferred_skills,
         );
-
-        let languages_detail =
-            Self::calculate_languages_score(&user_languages, &job_required_languages);
-
-        let location_detail = Self::calculate_location_score(
-            &user_location,
-            &job_location,
-            user_preferences.as_ref().map(|p| p.willing_to_relocate).unwrap_or(false),
+        let languages =
+            Self::calculate_languages_score(&inputs.user_languages, &inputs.job_required_languages);
+        let location = Self::calculate_location_score(
+            &inputs.user_location,
+            &inputs.job_location,
+            inputs.willing_to_relocate,
+        );
+        let experience = Self::calculate_experience_score(
+            &inputs.user_experience,
+            inputs.job_experience_min,
+            inputs.job_experience_max,
+        );
+        let education =
+            Self::calculate_education_score(&inputs.user_education, &inputs.job_education);
+        let accommodations = Self::calculate_accommodations_score(
+            &inputs.user_disability,
+            &inputs.job_accommodations,
         );
 
-        let experience_detail =
-            Self::calculate_experience_score(&user_experience, job_experience.0, job_experience.1);
-
-        let education_detail =
-            Self::calculate_education_score(&user_education, &job_education);
-
-        let accommodations_detail =
-            Self::calculate_accommodations_score(&user_disability, &job_accommodations);
-
-        // Calculate total score
-        let total_score = skills_detail.score
-            + languages_detail.score
-            + location_detail.score
-            + experience_detail.score
-            + education_detail.score
-            + skills_detail.score // preferred skills is part of skills_detail
-            + accommodations_detail.score;
-
-        Ok(MatchScoreBreakdown {
-            total_score: total_score.min(100),
-            skills: skills_detail,
-            languages: languages_detail,
-            location: location_detail,
-            experience: experience_detail,
-            education: education_detail,
-            accommodations: accommodations_detail,
-        })
+        // Preferred skills are part of the skills component
+        let total_score = skills.score
+            + languages.score
+            + location.score
+            + experience.score
+            + education.score
+            + accommodations.score;
+
+        MatchScoreBreakdown {
+            total_score,
+            skills,
+            languages,
+            location,
+            experience,
+            education,
+            accommodations,
+        }
     }
 
-    // Calculate skills score (35 points max)
+    // Calculate skills score (35 points max, plus 5 for preferred skills)
     fn calculate_skills_score(
         user_skills: &[UserSkillData],
         job_required_skills: &[JobRequiredSkillData],
@@ -208,7 +340,7 @@ impl MatchingService {
                 .iter()
                 .find(|s| s.skill_id == required.skill_id)
             {
-                if user_skill.proficiency_level >= required.minimum_proficiency {
+                if user_skill.effective_proficiency() >= required.minimum_proficiency {
                     matched_required.push(MatchedSkill {
                         skill_id: required.skill_id,
                         required_proficiency: required.minimum_proficiency,
@@ -218,12 +350,14 @@ impl MatchingService {
                     missing_required.push(MissingSkill {
                         skill_id: required.skill_id,
                         required_proficiency: required.minimum_proficiency,
+                        user_proficiency: Some(user_skill.proficiency_level),
                     });
                 }
             } else {
                 missing_required.push(MissingSkill {
                     skill_id: required.skill_id,
                     required_proficiency: required.minimum_proficiency,
+                    user_proficiency: None,
                 });
             }
         }
@@ -237,10 +371,10 @@ impl MatchingService {
 
         // Calculate score
         let required_score = if job_required_skills.is_empty() {
-            SKILLS_WEIGHT - PREFERRED_SKILLS_WEIGHT // Full score if no requirements
+            SKILLS_WEIGHT // Full score if no requirements
         } else {
             let ratio = matched_required.len() as f64 / job_required_skills.len() as f64;
-            ((SKILLS_WEIGHT - PREFERRED_SKILLS_WEIGHT) as f64 * ratio) as i32
+            (SKILLS_WEIGHT as f64 * ratio) as i32
         };
 
         let preferred_score = if job_preferred_skills.is_empty() {
@@ -252,7 +386,8 @@ impl MatchingService {
 
         SkillsMatchDetail {
             score: required_score + preferred_score,
-            max_score: SKILLS_WEIGHT,
+            max_score: SKILLS_WEIGHT + PREFERRED_SKILLS_WEIGHT,
+            preferred_score,
             matched_required,
             missing_required,
             matched_preferred,
@@ -283,12 +418,14 @@ impl MatchingService {
                     missing.push(MissingLanguage {
                         language_id: required.language_id,
                         required_proficiency: required.minimum_proficiency,
+                        user_proficiency: Some(user_proficiency),
                     });
                 }
             } else {
                 missing.push(MissingLanguage {
                     language_id: required.language_id,
                     required_proficiency: required.minimum_proficiency,
+                    user_proficiency: None,
                 });
             }
         }
@@ -463,10 +600,11 @@ impl MatchingService {
 
         let job_provides = !job_accommodations.categories.is_empty();
 
-        let score = if user_disability.categories.is_empty() {
+        // No accommodations needed, or all of them provided
+        let score = if user_disability.categories.is_empty()
+            || matching_categories.len() == user_disability.categories.len()
+        {
             ACCOMMODATIONS_WEIGHT
-        } else if matching_categories.len() == user_disability.categories.len() {
-            ACCOMMODATIONS_WEIGHT // All accommodations provided
         } else if !matching_categories.is_empty() {
             // Partial match
             let ratio = matching_categories.len() as f64 / user_disability.categories.len() as f64;
@@ -495,7 +633,10 @@ impl MatchingService {
         let skills = sqlx::query_as!(
             UserSkillData,
             r#"
-            SELECT skill_id, proficiency_level
+            SELECT skill_id, proficiency_level,
+                   EXISTS(
+                       SELECT 1 FROM skill_endorsements e WHERE e.user_skill_id = user_skills.id
+                   ) as "is_endorsed!"
             FROM user_skills
             WHERE user_id = $1
             "#,
@@ -507,24 +648,15 @@ impl MatchingService {
         Ok(skills)
     }
 
-    async fn get_user_languages(db: &PgPool, user_id: Uuid) -> Result<Vec<UserLanguageData>> {
-        let languages = sqlx::query!(
-            r#"
-            SELECT language_id, proficiency as "proficiency: String"
-            FROM user_languages
-            WHERE user_id = $1
-            "#,
-            user_id
-        )
-        .fetch_all(db)
-        .await?;
-
-        Ok(languages
-            .into_iter()
-            .map(|r| UserLanguageData {
-                language_id: r.language_id,
-                proficiency: r.proficiency,
-            })
+    /// The seeker's proficiency per skill as counted against job requirements
+    pub async fn effective_skill_proficiencies(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<HashMap<Uuid, i32>> {
+        Ok(Self::get_user_skills(db, user_id)
+            .await?
+            .iter()
+            .map(|s| (s.skill_id, s.effective_proficiency()))
             .collect())
     }
 
@@ -603,116 +735,6 @@ impl MatchingService {
         })
     }
 
-    async fn get_user_location(db: &PgPool, user_id: Uuid) -> Result<UserLocationData> {
-        let location = sqlx::query!(
-            r#"
-            SELECT region_id, municipality_id
-            FROM job_seeker_profiles
-            WHERE user_id = $1
-            "#,
-            user_id
-        )
-        .fetch_optional(db)
-        .await?;
-
-        Ok(match location {
-            Some(l) => UserLocationData {
-                region_id: l.region_id,
-                municipality_id: l.municipality_id,
-            },
-            None => UserLocationData {
-                region_id: None,
-                municipality_id: None,
-            },
-        })
-    }
-
-    async fn get_user_experience(db: &PgPool, user_id: Uuid) -> Result<UserExperienceData> {
-        // Calculate total years from work experiences
-        let result = sqlx::query!(
-            r#"
-            SELECT COALESCE(
-                SUM(
-                    EXTRACT(YEAR FROM AGE(
-                        COALESCE(end_date, CURRENT_DATE),
-                        start_date
-                    ))
-                )::INTEGER,
-                0
-            ) as "total_years!"
-            FROM work_experiences
-            WHERE user_id = $1
-            "#,
-            user_id
-        )
-        .fetch_one(db)
-        .await?;
-
-        Ok(UserExperienceData {
-            total_years: result.total_years,
-        })
-    }
-
-    async fn get_user_education(db: &PgPool, user_id: Uuid) -> Result<UserEducationData> {
-        // Get highest education level
-        let result = sqlx::query!(
-            r#"
-            SELECT level as "level: String"
-            FROM education_records
-            WHERE user_id = $1
-            ORDER BY
-                CASE level
-                    WHEN 'postgraduate' THEN 7
-                    WHEN 'graduate' THEN 6
-                    WHEN 'undergraduate' THEN 5
-                    WHEN 'technical' THEN 4
-                    WHEN 'secondary' THEN 3
-                    WHEN 'primary' THEN 2
-                    WHEN 'none' THEN 1
-                    ELSE 0
-                END DESC
-            LIMIT 1
-            "#,
-            user_id
-        )
-        .fetch_optional(db)
-        .await?;
-
-        Ok(UserEducationData {
-            highest_level: result.map(|r| r.level),
-        })
-    }
-
-    async fn get_user_disability(db: &PgPool, user_id: Uuid) -> Result<UserDisabilityData> {
-        let disabilities = sqlx::query!(
-            r#"
-            SELECT
-                category as "category: String",
-                requires_accommodations
-            FROM job_seeker_disabilities
-            WHERE user_id = $1
-            "#,
-            user_id
-        )
-        .fetch_all(db)
-        .await?;
-
-        if disabilities.is_empty() {
-            return Ok(UserDisabilityData {
-                categories: Vec::new(),
-                requires_accommodations: false,
-            });
-        }
-
-        let requires = disabilities.iter().any(|d| d.requires_accommodations);
-        let categories: Vec<String> = disabilities.into_iter().map(|d| d.category).collect();
-
-        Ok(UserDisabilityData {
-            categories,
-            requires_accommodations: requires,
-        })
-    }
-
     async fn get_job_accommodations(db: &PgPool, job_id: Uuid) -> Result<JobAccommodationData> {
         let accommodations = sqlx::query!(
             r#"
@@ -765,61 +787,172 @@ impl MatchingService {
         Ok(result.education_level)
     }
 
-    async fn get_user_preferences(
+    /// The seeker data of every listed user, one query per kind of data.
+    /// Users without a row in a table get the same defaults as an empty profile.
+    async fn get_users_data(
         db: &PgPool,
-        user_id: Uuid,
-    ) -> Result<Option<JobSeekerPreferences>> {
-        let result = sqlx::query!(
-            r#"
-            SELECT
-                user_id,
-                willing_to_relocate,
-                salary_expectation_min,
-                salary_expectation_max,
-                COALESCE(salary_currency, 'CLP') as "salary_currency!",
-                profile_visibility as "profile_visibility: ProfileVisibility",
-                show_disability_info,
-                email_job_alerts,
-                alert_frequency as "alert_frequency: AlertFrequency",
-                created_at,
-                updated_at
-            FROM job_seeker_preferences
-            WHERE user_id = $1
-            "#,
-            user_id
-        )
-        .fetch_optional(db)
-        .await?;
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, UserMatchData>> {
+        let (skills, languages, locations, experience, education, disabilities, relocation) = tokio::try_join!(
+            sqlx::query!(
+                r#"
+                SELECT user_id, skill_id, proficiency_level,
+                       EXISTS(
+                           SELECT 1 FROM skill_endorsements e WHERE e.user_skill_id = user_skills.id
+                       ) as "is_endorsed!"
+                FROM user_skills
+                WHERE user_id = ANY($1)
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            sqlx::query!(
+                r#"
+                SELECT user_id, language_id, proficiency as "proficiency: String"
+                FROM user_languages
+                WHERE user_id = ANY($1)
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            sqlx::query!(
+                r#"
+                SELECT user_id, region_id, municipality_id
+                FROM job_seeker_profiles
+                WHERE user_id = ANY($1)
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            // Total years from work experiences
+            sqlx::query!(
+                r#"
+                SELECT
+                    user_id,
+                    COALESCE(
+                        SUM(
+                            EXTRACT(YEAR FROM AGE(
+                                COALESCE(end_date, CURRENT_DATE),
+                                start_date
+                            ))
+                        )::INTEGER,
+                        0
+                    ) as "total_years!"
+                FROM work_experiences
+                WHERE user_id = ANY($1)
+                GROUP BY user_id
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            // Highest education level
+            sqlx::query!(
+                r#"
+                SELECT DISTINCT ON (user_id) user_id, level as "level: String"
+                FROM education_records
+                WHERE user_id = ANY($1)
+                ORDER BY
+                    user_id,
+                    CASE level
+                        WHEN 'postgraduate' THEN 7
+                        WHEN 'graduate' THEN 6
+                        WHEN 'undergraduate' THEN 5
+                        WHEN 'technical' THEN 4
+                        WHEN 'secondary' THEN 3
+                        WHEN 'primary' THEN 2
+                        WHEN 'none' THEN 1
+                        ELSE 0
+                    END DESC
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            sqlx::query!(
+                r#"
+                SELECT
+                    user_id,
+                    category as "category: String",
+                    requires_accommodations
+                FROM job_seeker_disabilities
+                WHERE user_id = ANY($1)
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+            sqlx::query!(
+                r#"
+                SELECT user_id, willing_to_relocate
+                FROM job_seeker_preferences
+                WHERE user_id = ANY($1)
+                "#,
+                user_ids
+            )
+            .fetch_all(db),
+        )?;
 
-        Ok(result.map(|r| JobSeekerPreferences {
-            user_id: r.user_id,
-            preferred_work_modalities: Vec::new(), // Would need separate query
-            preferred_job_types: Vec::new(),
-            preferred_region_ids: Vec::new(),
-            preferred_industry_ids: Vec::new(),
-            willing_to_relocate: r.willing_to_relocate.unwrap_or(false),
-            salary_expectation_min: r.salary_expectation_min,
-            salary_expectation_max: r.salary_expectation_max,
-            salary_currency: r.salary_currency,
-            profile_visibility: r.profile_visibility,
-            show_disability_info: r.show_disability_info,
-            email_job_alerts: r.email_job_alerts,
-            alert_frequency: r.alert_frequency,
-            created_at: r.created_at,
-            updated_at: r.updated_at,
-        }))
+        let mut users: HashMap<Uuid, UserMatchData> = HashMap::new();
+        for r in skills {
+            users
+                .entry(r.user_id)
+                .or_default()
+                .skills
+                .push(UserSkillData {
+                    skill_id: r.skill_id,
+                    proficiency_level: r.proficiency_level,
+                    is_endorsed: r.is_endorsed,
+                });
+        }
+        for r in languages {
+            users
+                .entry(r.user_id)
+                .or_default()
+                .languages
+                .push(UserLanguageData {
+                    language_id: r.language_id,
+                    proficiency: r.proficiency,
+                });
+        }
+        for r in locations {
+            users.entry(r.user_id).or_default().location = UserLocationData {
+                region_id: r.region_id,
+                municipality_id: r.municipality_id,
+            };
+        }
+        for r in experience {
+            users.entry(r.user_id).or_default().experience.total_years = r.total_years;
+        }
+        for r in education {
+            users.entry(r.user_id).or_default().education.highest_level = Some(r.level);
+        }
+        for r in disabilities {
+            let disability = &mut users.entry(r.user_id).or_default().disability;
+            disability.requires_accommodations |= r.requires_accommodations;
+            disability.categories.push(r.category);
+        }
+        for r in relocation {
+            users.entry(r.user_id).or_default().willing_to_relocate =
+                r.willing_to_relocate.unwrap_or(false);
+        }
+
+        Ok(users)
     }
 
     // ============================================================================
     // CACHE MANAGEMENT
     // ============================================================================
 
+    /// Caches the score, recording a history point when it moved by more
+    /// than MATCH_SCORE_HISTORY_THRESHOLD since the last one
     pub async fn save_match_score(
         db: &PgPool,
         job_id: Uuid,
         user_id: Uuid,
         breakdown: &MatchScoreBreakdown,
     ) -> Result<()> {
+        let mut tx = db.begin().await?;
+
+        // The upsert locks the cached row, so saves of the same pair don't
+        // race on the history
         sqlx::query!(
             r#"
             INSERT INTO job_match_scores (
@@ -846,20 +979,145 @@ impl MatchingService {
             job_id,
             user_id,
             breakdown.total_score,
-            breakdown.skills.score,
+            breakdown.skills.score - breakdown.skills.preferred_score,
+            breakdown.languages.score,
+            breakdown.location.score,
+            breakdown.experience.score,
+            breakdown.education.score,
+            breakdown.skills.preferred_score,
+            breakdown.accommodations.score
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Self::record_score_history(&mut tx, job_id, user_id, breakdown).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Compared with the last recorded point rather than the cached score, so
+    /// a score creeping up a point at a time still shows up
+    async fn record_score_history(
+        conn: &mut PgConnection,
+        job_id: Uuid,
+        user_id: Uuid,
+        breakdown: &MatchScoreBreakdown,
+    ) -> Result<()> {
+        let last_total = sqlx::query_scalar!(
+            r#"
+            SELECT total_score
+            FROM job_match_score_history
+            WHERE job_id = $1 AND user_id = $2
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+            job_id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if let Some(last_total) = last_total {
+            if (breakdown.total_score - last_total).abs() <= MATCH_SCORE_HISTORY_THRESHOLD {
+                return Ok(());
+            }
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO job_match_score_history (
+                job_id, user_id, total_score,
+                skills_score, languages_score, location_score,
+                experience_score, education_score, preferred_skills_score,
+                accommodations_score
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            job_id,
+            user_id,
+            breakdown.total_score,
+            breakdown.skills.score - breakdown.skills.preferred_score,
             breakdown.languages.score,
             breakdown.location.score,
             breakdown.experience.score,
             breakdown.education.score,
-            breakdown.skills.matched_preferred.len() as i32, // Preferred skills contribution
+            breakdown.skills.preferred_score,
             breakdown.accommodations.score
         )
-        .execute(db)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM job_match_score_history
+            WHERE id IN (
+                SELECT id
+                FROM job_match_score_history
+                WHERE job_id = $1 AND user_id = $2
+                ORDER BY recorded_at DESC
+                OFFSET $3
+            )
+            "#,
+            job_id,
+            user_id,
+            MAX_MATCH_SCORE_HISTORY
+        )
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
     }
 
+    /// The seeker's recorded scores for a job, oldest first, each with the
+    /// component that changed most since the point before
+    pub async fn get_score_history(
+        db: &PgPool,
+        job_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<MatchScoreHistoryPoint>> {
+        let mut points: Vec<MatchScoreHistoryPoint> = sqlx::query!(
+            r#"
+            SELECT
+                total_score, skills_score, languages_score, location_score,
+                experience_score, education_score, preferred_skills_score,
+                accommodations_score, recorded_at
+            FROM job_match_score_history
+            WHERE job_id = $1 AND user_id = $2
+            ORDER BY recorded_at
+            "#,
+            job_id,
+            user_id
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|row| MatchScoreHistoryPoint {
+            total_score: row.total_score,
+            skills_score: row.skills_score,
+            languages_score: row.languages_score,
+            location_score: row.location_score,
+            experience_score: row.experience_score,
+            education_score: row.education_score,
+            preferred_skills_score: row.preferred_skills_score,
+            accommodations_score: row.accommodations_score,
+            recorded_at: row.recorded_at,
+            biggest_change: None,
+        })
+        .collect();
+
+        let changes: Vec<_> = points
+            .windows(2)
+            .map(|pair| pair[1].biggest_change_since(&pair[0]))
+            .collect();
+        for (point, change) in points.iter_mut().skip(1).zip(changes) {
+            point.biggest_change = change;
+        }
+
+        Ok(points)
+    }
+
     pub async fn get_cached_score(
         db: &PgPool,
         job_id: Uuid,
@@ -902,3 +1160,1264 @@ impl MatchingService {
         Ok(result)
     }
 }
+
+// ============================================================================
+// MATCH TIPS
+// ============================================================================
+
+/// Most tips returned for a single job
+pub const MAX_MATCH_TIPS: usize = 5;
+
+/// Points a proportional component gains when one more of `total` items is met,
+/// using the same rounding as the score calculation
+fn next_item_points(weight: i32, matched: usize, total: usize) -> i32 {
+    if total == 0 || matched >= total {
+        return 0;
+    }
+    let points = |m: usize| (weight as f64 * (m as f64 / total as f64)) as i32;
+    points(matched + 1) - points(matched)
+}
+
+/// Turns a score breakdown into the most valuable improvements a job seeker can
+/// make, sorted by potential points and capped at MAX_MATCH_TIPS. Skill and
+/// language names come from the lookups; unknown IDs get a generic label.
+pub fn generate_match_tips(
+    breakdown: &MatchScoreBreakdown,
+    skill_names: &HashMap<Uuid, String>,
+    language_names: &HashMap<Uuid, String>,
+) -> Vec<MatchTip> {
+    let mut tips = Vec::new();
+
+    // Required skills
+    let skills = &breakdown.skills;
+    let required_total = skills.matched_required.len() + skills.missing_required.len();
+    let skill_points = next_item_points(
+        SKILLS_WEIGHT,
+        skills.matched_required.len(),
+        required_total,
+    );
+    for missing in &skills.missing_required {
+        let name = skill_names
+            .get(&missing.skill_id)
+            .map(String::as_str)
+            .unwrap_or("una habilidad requerida");
+        let (kind, message, gap) = match missing.user_proficiency {
+            Some(current) => (
+                MatchTipKind::ImproveSkill,
+                format!(
+                    "Mejora tu nivel en «{}» de {}/5 a {}/5",
+                    name, current, missing.required_proficiency
+                ),
+                Some(missing.required_proficiency - current),
+            ),
+            None => (
+                MatchTipKind::AddSkill,
+                format!(
+                    "Agrega «{}» a tus habilidades con nivel {}/5 o superior",
+                    name, missing.required_proficiency
+                ),
+                None,
+            ),
+        };
+        tips.push(MatchTip {
+            kind,
+            message,
+            potential_points: skill_points,
+            skill_id: Some(missing.skill_id),
+            language_id: None,
+            proficiency_gap: gap,
+        });
+    }
+
+    // Required languages
+    let languages = &breakdown.languages;
+    let language_points = next_item_points(
+        LANGUAGES_WEIGHT,
+        languages.matched.len(),
+        languages.matched.len() + languages.missing.len(),
+    );
+    for missing in &languages.missing {
+        let name = language_names
+            .get(&missing.language_id)
+            .map(String::as_str)
+            .unwrap_or("un idioma requerido");
+        let required = language_proficiency_label(missing.required_proficiency);
+        let (kind, message, gap) = match missing.user_proficiency {
+            Some(current) => {
+                let gap = missing.required_proficiency - current;
+                (
+                    MatchTipKind::ImproveLanguage,
+                    format!(
+                        "Tu nivel de {} es {} y el empleo pide {} ({} {} más)",
+                        name,
+                        language_proficiency_label(current),
+                        required,
+                        gap,
+                        if gap == 1 { "nivel" } else { "niveles" }
+                    ),
+                    Some(gap),
+                )
+            }
+            None => (
+                MatchTipKind::AddLanguage,
+                format!("Agrega {} a tus idiomas con nivel {} o superior", name, required),
+                None,
+            ),
+        };
+        tips.push(MatchTip {
+            kind,
+            message,
+            potential_points: language_points,
+            skill_id: None,
+            language_id: Some(missing.language_id),
+            proficiency_gap: gap,
+        });
+    }
+
+    // Relocation only counts when nothing else about the location matches
+    let location = &breakdown.location;
+    if !location.willing_to_relocate
+        && !location.is_remote_compatible
+        && !location.is_same_region
+        && !location.is_same_municipality
+    {
+        let relocate_score = (LOCATION_WEIGHT as f64 * 0.6) as i32;
+        tips.push(MatchTip {
+            kind: MatchTipKind::EnableRelocation,
+            message: "Indica en tus preferencias que estás dispuesto/a a cambiarte de ciudad"
+                .to_string(),
+            potential_points: relocate_score - location.score,
+            skill_id: None,
+            language_id: None,
+            proficiency_gap: None,
+        });
+    }
+
+    // Education
+    let education = &breakdown.education;
+    if let (false, Some(required)) = (education.meets_requirement, &education.required_level) {
+        let message = match &education.user_level {
+            None => format!(
+                "Agrega tu formación académica; el empleo pide nivel {}",
+                education_level_label(required)
+            ),
+            Some(_) => format!(
+                "Si tienes estudios de nivel {} o superior, regístralos en tu perfil",
+                education_level_label(required)
+            ),
+        };
+        tips.push(MatchTip {
+            kind: MatchTipKind::AddEducation,
+            message,
+            potential_points: education.max_score - education.score,
+            skill_id: None,
+            language_id: None,
+            proficiency_gap: None,
+        });
+    }
+
+    tips.retain(|tip| tip.potential_points > 0);
+    // Stable sort keeps skills before languages on ties
+    tips.sort_by_key(|tip| std::cmp::Reverse(tip.potential_points));
+    tips.truncate(MAX_MATCH_TIPS);
+    tips
+}
+
+// ============================================================================
+// RECOMMENDATION DIVERSITY
+// ============================================================================
+
+/// Positions at the top of the recommendations where diversity is enforced
+pub const DIVERSIFY_TOP_N: usize = 10;
+
+/// Most items per group allowed in the top positions
+pub const DIVERSIFY_MAX_PER_GROUP: usize = 3;
+
+/// Reorders already sorted items so no group has more than
+/// DIVERSIFY_MAX_PER_GROUP of the first DIVERSIFY_TOP_N positions. Extras are
+/// demoted to just after the top, keeping their relative order, and the rest
+/// keeps its place. The result stays deterministic for a deterministic input.
+pub fn diversify_top<T, K, F>(items: Vec<T>, group: F) -> Vec<T>
+where
+    K: Eq + std::hash::Hash,
+    F: Fn(&T) -> K,
+{
+    let mut top = Vec::with_capacity(DIVERSIFY_TOP_N);
+    let mut demoted = Vec::new();
+    let mut per_group: HashMap<K, usize> = HashMap::new();
+    let mut rest = items.into_iter();
+
+    for item in rest.by_ref() {
+        let count = per_group.entry(group(&item)).or_insert(0);
+        if *count < DIVERSIFY_MAX_PER_GROUP {
+            *count += 1;
+            top.push(item);
+        } else {
+            demoted.push(item);
+        }
+        if top.len() == DIVERSIFY_TOP_N {
+            break;
+        }
+    }
+
+    top.extend(demoted);
+    top.extend(rest);
+    top
+}
+
+// ============================================================================
+// APPLICANT SCORE RECALCULATION
+// ============================================================================
+
+/// Cached scores older than this count as stale even when nothing they depend
+/// on was flagged as changed
+pub const MATCH_SCORE_MAX_AGE_DAYS: i64 = 7;
+
+/// Synchronous recalculations a company may ask for per hour, to protect the
+/// database; whole jobs go through the background worker instead
+pub const RECALCULATIONS_PER_HOUR: i64 = 30;
+
+/// Whether a cached score should be shown as out of date
+pub fn is_score_stale(is_stale: bool, computed_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    is_stale || now - computed_at > Duration::days(MATCH_SCORE_MAX_AGE_DAYS)
+}
+
+fn recalculation_key(company_id: Uuid, hour: i64) -> String {
+    format!("match_recalc:{}:{}", company_id, hour)
+}
+
+/// Takes one of the company's recalculations for the current hour, returning
+/// false once they are used up. Redis failures are logged and let it through.
+pub async fn take_recalculation_slot(redis: &mut ConnectionManager, company_id: Uuid) -> bool {
+    let key = recalculation_key(company_id, Utc::now().timestamp() / 3600);
+    limits::take(redis, &key, RECALCULATIONS_PER_HOUR, 3600).await
+}
+
+/// Seekers rescored per batch, after which the worker records its progress
+pub const RECALCULATION_BATCH_SIZE: i64 = 50;
+
+/// What a job requires of seekers, as far as match scores depend on it.
+/// Two snapshots that compare equal give the same scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobRequirements {
+    pub required_skills: Vec<String>,
+    pub preferred_skills: Vec<Uuid>,
+    pub required_languages: Vec<String>,
+    pub accommodations: Vec<String>,
+    pub years_experience_min: Option<i32>,
+    pub years_experience_max: Option<i32>,
+    pub education_level: Option<String>,
+    pub region_id: Option<Uuid>,
+    pub municipality_id: Option<Uuid>,
+    pub is_remote_allowed: Option<bool>,
+    pub work_modality: String,
+}
+
+impl JobRequirements {
+    pub async fn load(conn: &mut PgConnection, job_id: Uuid) -> Result<Option<Self>> {
+        let requirements = sqlx::query_as!(
+            JobRequirements,
+            r#"
+            SELECT
+                ARRAY(
+                    SELECT skill_id::text || ':' || minimum_proficiency
+                    FROM job_required_skills WHERE job_id = j.id ORDER BY 1
+                ) as "required_skills!",
+                ARRAY(
+                    SELECT skill_id FROM job_preferred_skills WHERE job_id = j.id ORDER BY 1
+                ) as "preferred_skills!",
+                ARRAY(
+                    SELECT language_id::text || ':' || minimum_proficiency
+                    FROM job_required_languages WHERE job_id = j.id ORDER BY 1
+                ) as "required_languages!",
+                ARRAY(
+                    SELECT disability_category::text
+                    FROM job_disability_accommodations WHERE job_id = j.id ORDER BY 1
+                ) as "accommodations!",
+                j.years_experience_min,
+                j.years_experience_max,
+                j.education_level,
+                j.region_id,
+                j.municipality_id,
+                j.is_remote_allowed,
+                j.work_modality::text as "work_modality!"
+            FROM jobs j
+            WHERE j.id = $1
+            "#,
+            job_id,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(requirements)
+    }
+}
+
+/// Queues a rescore of the job for the background worker, flagging its
+/// scores stale right away so they show as such until the worker runs.
+/// Requests after a requirement change go first and also cover seekers who
+/// saved the job or were scored for it before; otherwise only applicants are
+/// rescored. Asking again restarts a request that is under way.
+pub async fn queue_recalculation(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    requested_by: Uuid,
+    requirements_changed: bool,
+) -> Result<DateTime<Utc>> {
+    sqlx::query!(
+        r#"UPDATE job_match_scores SET is_stale = TRUE WHERE job_id = $1"#,
+        job_id,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let requested_at = sqlx::query_scalar!(
+        r#"
+        INSERT INTO match_score_recalculations (job_id, requested_by, requirements_changed)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (job_id) DO UPDATE SET
+            requested_by = EXCLUDED.requested_by,
+            requested_at = NOW(),
+            requirements_changed =
+                match_score_recalculations.requirements_changed OR EXCLUDED.requirements_changed,
+            total_seekers = NULL,
+            processed_seekers = 0,
+            started_at = NULL
+        RETURNING requested_at
+        "#,
+        job_id,
+        requested_by,
+        requirements_changed,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(requested_at)
+}
+
+/// Queues a rescore when what the job requires of seekers no longer matches
+/// `before`, loaded earlier in the same transaction. Edits that leave the
+/// requirements alone, like a reworded title, keep the scores. Returns
+/// whether a rescore was queued.
+pub async fn queue_if_requirements_changed(
+    conn: &mut PgConnection,
+    job_id: Uuid,
+    before: Option<JobRequirements>,
+    requested_by: Uuid,
+) -> Result<bool> {
+    if JobRequirements::load(conn, job_id).await? == before {
+        return Ok(false);
+    }
+    queue_recalculation(conn, job_id, requested_by, true).await?;
+    Ok(true)
+}
+
+/// Queues a rescore of every applicant of the job
+pub async fn request_job_recalculation(
+    db: &PgPool,
+    job_id: Uuid,
+    requested_by: Uuid,
+) -> Result<DateTime<Utc>> {
+    let mut tx = db.begin().await?;
+    let requested_at = queue_recalculation(&mut tx, job_id, requested_by, false).await?;
+    tx.commit().await?;
+    Ok(requested_at)
+}
+
+/// Seekers a request still has to rescore: those without a fresh score since
+/// it was made. Applicants and seekers who saved the job come first, then
+/// anyone scored for it before.
+async fn pending_seekers(
+    db: &PgPool,
+    job_id: Uuid,
+    requested_at: DateTime<Utc>,
+    requirements_changed: bool,
+    limit: Option<i64>,
+) -> Result<Vec<Uuid>> {
+    let seekers = sqlx::query_scalar!(
+        r#"
+        WITH targets AS (
+            SELECT applicant_id AS user_id, 0 AS priority
+            FROM job_applications WHERE job_id = $1
+            UNION ALL
+            SELECT user_id, 0 FROM saved_jobs WHERE job_id = $1 AND $3
+            UNION ALL
+            SELECT user_id, 1 FROM job_match_scores WHERE job_id = $1 AND $3
+        )
+        SELECT t.user_id as "user_id!"
+        FROM targets t
+        WHERE NOT EXISTS (
+            SELECT 1 FROM job_match_scores s
+            WHERE s.job_id = $1 AND s.user_id = t.user_id
+            AND s.computed_at >= $2 AND NOT s.is_stale
+        )
+        GROUP BY t.user_id
+        ORDER BY MIN(t.priority), t.user_id
+        LIMIT $4
+        "#,
+        job_id,
+        requested_at,
+        requirements_changed,
+        limit,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(seekers)
+}
+
+/// Rescores up to `batch_size` seekers of the first request in the queue,
+/// requirement changes first and then oldest first, and records the progress.
+/// Returns None when the queue is empty, or whether the request is done.
+/// A request made again while its job is being rescored starts over.
+pub async fn process_recalculation_batch(db: &PgPool, batch_size: i64) -> Result<Option<bool>> {
+    let Some(request) = sqlx::query!(
+        r#"
+        SELECT job_id, requested_at, requirements_changed, total_seekers, processed_seekers
+        FROM match_score_recalculations
+        ORDER BY requirements_changed DESC, requested_at
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(db)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let total = match request.total_seekers {
+        Some(total) => total,
+        None => {
+            let total = pending_seekers(
+                db,
+                request.job_id,
+                request.requested_at,
+                request.requirements_changed,
+                None,
+            )
+            .await?
+            .len() as i32;
+            sqlx::query!(
+                r#"
+                UPDATE match_score_recalculations
+                SET total_seekers = $3, started_at = NOW()
+                WHERE job_id = $1 AND requested_at = $2
+                "#,
+                request.job_id,
+                request.requested_at,
+                total,
+            )
+            .execute(db)
+            .await?;
+            total
+        }
+    };
+
+    let batch = pending_seekers(
+        db,
+        request.job_id,
+        request.requested_at,
+        request.requirements_changed,
+        Some(batch_size),
+    )
+    .await?;
+
+    let mut rescored = 0;
+    for user_id in &batch {
+        let saved = async {
+            let breakdown =
+                MatchingService::calculate_match_score(db, request.job_id, *user_id).await?;
+            MatchingService::save_match_score(db, request.job_id, *user_id, &breakdown).await
+        }
+        .await;
+        match saved {
+            Ok(()) => rescored += 1,
+            Err(e) => {
+                // Dropped rather than retried, so one bad job cannot block the queue
+                tracing::error!(
+                    "Failed to recalculate match scores for job {}: {}",
+                    request.job_id,
+                    e
+                );
+                finish_recalculation(db, request.job_id, request.requested_at).await?;
+                return Ok(Some(false));
+            }
+        }
+    }
+
+    let processed = (request.processed_seekers + rescored).min(total);
+    if batch.len() < batch_size as usize || processed >= total {
+        finish_recalculation(db, request.job_id, request.requested_at).await?;
+        return Ok(Some(true));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE match_score_recalculations
+        SET processed_seekers = $3
+        WHERE job_id = $1 AND requested_at = $2
+        "#,
+        request.job_id,
+        request.requested_at,
+        processed,
+    )
+    .execute(db)
+    .await?;
+    Ok(Some(false))
+}
+
+async fn finish_recalculation(
+    db: &PgPool,
+    job_id: Uuid,
+    requested_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM match_score_recalculations WHERE job_id = $1 AND requested_at = $2"#,
+        job_id,
+        requested_at,
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Works through the recalculation queue batch by batch, returning how many
+/// jobs were rescored
+pub async fn process_recalculations(db: &PgPool) -> Result<usize> {
+    let mut done = 0;
+    while let Some(finished) = process_recalculation_batch(db, RECALCULATION_BATCH_SIZE).await? {
+        if finished {
+            done += 1;
+        }
+    }
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_point(skills: i32, languages: i32, location: i32) -> MatchScoreHistoryPoint {
+        MatchScoreHistoryPoint {
+            total_score: skills + languages + location,
+            skills_score: skills,
+            languages_score: languages,
+            location_score: location,
+            experience_score: 0,
+            education_score: 0,
+            preferred_skills_score: 0,
+            accommodations_score: 0,
+            recorded_at: Utc::now(),
+            biggest_change: None,
+        }
+    }
+
+    #[test]
+    fn test_biggest_component_change() {
+        let before = history_point(10, 5, 15);
+
+        let change = history_point(20, 10, 15).biggest_change_since(&before).unwrap();
+        assert_eq!(change.component, MatchScoreComponent::Skills);
+        assert_eq!(change.change, 10);
+
+        // Losses count by size too
+        let change = history_point(12, 5, 0).biggest_change_since(&before).unwrap();
+        assert_eq!(change.component, MatchScoreComponent::Location);
+        assert_eq!(change.change, -15);
+
+        // Ties go to the component listed first
+        let change = history_point(13, 8, 15).biggest_change_since(&before).unwrap();
+        assert_eq!(change.component, MatchScoreComponent::Skills);
+
+        assert!(before.biggest_change_since(&before).is_none());
+    }
+
+    fn breakdown() -> MatchScoreBreakdown {
+        MatchScoreBreakdown {
+            total_score: 100,
+            skills: SkillsMatchDetail {
+                score: SKILLS_WEIGHT + PREFERRED_SKILLS_WEIGHT,
+                max_score: SKILLS_WEIGHT + PREFERRED_SKILLS_WEIGHT,
+                preferred_score: PREFERRED_SKILLS_WEIGHT,
+                matched_required: Vec::new(),
+                missing_required: Vec::new(),
+                matched_preferred: Vec::new(),
+            },
+            languages: LanguagesMatchDetail {
+                score: LANGUAGES_WEIGHT,
+                max_score: LANGUAGES_WEIGHT,
+                matched: Vec::new(),
+                missing: Vec::new(),
+            },
+            location: LocationMatchDetail {
+                score: LOCATION_WEIGHT,
+                max_score: LOCATION_WEIGHT,
+                is_same_region: true,
+                is_same_municipality: true,
+                is_remote_compatible: false,
+                willing_to_relocate: false,
+            },
+            experience: ExperienceMatchDetail {
+                score: EXPERIENCE_WEIGHT,
+                max_score: EXPERIENCE_WEIGHT,
+                user_years: 5,
+                required_min: None,
+                required_max: None,
+                is_within_range: true,
+            },
+            education: EducationMatchDetail {
+                score: EDUCATION_WEIGHT,
+                max_score: EDUCATION_WEIGHT,
+                user_level: None,
+                required_level: None,
+                meets_requirement: true,
+            },
+            accommodations: AccommodationsMatchDetail {
+                score: ACCOMMODATIONS_WEIGHT,
+                max_score: ACCOMMODATIONS_WEIGHT,
+                user_needs_accommodations: false,
+                job_provides_accommodations: false,
+                matching_categories: Vec::new(),
+            },
+        }
+    }
+
+    fn missing_skill(user_proficiency: Option<i32>) -> MissingSkill {
+        MissingSkill {
+            skill_id: Uuid::new_v4(),
+            required_proficiency: 4,
+            user_proficiency,
+        }
+    }
+
+    #[test]
+    fn test_endorsement_covers_one_level_gap() {
+        let skill_id = Uuid::new_v4();
+        let required = [JobRequiredSkillData {
+            skill_id,
+            minimum_proficiency: 4,
+        }];
+        let user_skill = |proficiency_level, is_endorsed| UserSkillData {
+            skill_id,
+            proficiency_level,
+            is_endorsed,
+        };
+
+        let self_reported =
+            MatchingService::calculate_skills_score(&[user_skill(3, false)], &required, &[]);
+        assert!(self_reported.matched_required.is_empty());
+
+        let endorsed =
+            MatchingService::calculate_skills_score(&[user_skill(3, true)], &required, &[]);
+        assert_eq!(endorsed.matched_required.len(), 1);
+        assert_eq!(endorsed.matched_required[0].user_proficiency, 3);
+        assert!(endorsed.score > self_reported.score);
+
+        // Only one level is covered
+        let endorsed_far_below =
+            MatchingService::calculate_skills_score(&[user_skill(2, true)], &required, &[]);
+        assert!(endorsed_far_below.matched_required.is_empty());
+    }
+
+    #[test]
+    fn test_perfect_match_has_no_tips() {
+        let tips = generate_match_tips(&breakdown(), &HashMap::new(), &HashMap::new());
+        assert!(tips.is_empty());
+    }
+
+    #[test]
+    fn test_next_item_points_follows_score_rounding() {
+        // 30 points over 3 skills: 0 -> 10 -> 20 -> 30
+        assert_eq!(next_item_points(30, 0, 3), 10);
+        assert_eq!(next_item_points(30, 2, 3), 10);
+        // 15 points over 4 languages: 0 -> 3 -> 7 -> 11 -> 15
+        assert_eq!(next_item_points(15, 0, 4), 3);
+        assert_eq!(next_item_points(15, 1, 4), 4);
+        assert_eq!(next_item_points(15, 4, 4), 0);
+    }
+
+    #[test]
+    fn test_missing_skills_resolve_names() {
+        let mut b = breakdown();
+        let add = missing_skill(None);
+        let improve = missing_skill(Some(2));
+        b.skills.missing_required = vec![add.clone(), improve.clone()];
+        b.skills.score = PREFERRED_SKILLS_WEIGHT;
+
+        let names = HashMap::from([(add.skill_id, "Rust".to_string())]);
+        let tips = generate_match_tips(&b, &names, &HashMap::new());
+
+        assert_eq!(tips.len(), 2);
+        assert_eq!(tips[0].kind, MatchTipKind::AddSkill);
+        assert!(tips[0].message.contains("«Rust»"));
+        // Two required skills, none met: the first is worth half of 35 points
+        assert_eq!(tips[0].potential_points, 17);
+        assert_eq!(tips[1].kind, MatchTipKind::ImproveSkill);
+        assert_eq!(tips[1].proficiency_gap, Some(2));
+        assert!(tips[1].message.contains("una habilidad requerida"));
+    }
+
+    #[test]
+    fn test_language_gap_is_reported() {
+        let mut b = breakdown();
+        let language_id = Uuid::new_v4();
+        b.languages.missing = vec![MissingLanguage {
+            language_id,
+            required_proficiency: 4,
+            user_proficiency: Some(2),
+        }];
+        b.languages.score = 0;
+
+        let names = HashMap::from([(language_id, "Inglés".to_string())]);
+        let tips = generate_match_tips(&b, &HashMap::new(), &names);
+
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips[0].kind, MatchTipKind::ImproveLanguage);
+        assert_eq!(tips[0].potential_points, LANGUAGES_WEIGHT);
+        assert_eq!(tips[0].proficiency_gap, Some(2));
+        assert_eq!(
+            tips[0].message,
+            "Tu nivel de Inglés es intermedio y el empleo pide fluido (2 niveles más)"
+        );
+    }
+
+    #[test]
+    fn test_relocation_only_when_location_does_not_match() {
+        let mut b = breakdown();
+        b.location.is_same_region = false;
+        b.location.is_same_municipality = false;
+        b.location.score = 0;
+
+        let tips = generate_match_tips(&b, &HashMap::new(), &HashMap::new());
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips[0].kind, MatchTipKind::EnableRelocation);
+        assert_eq!(tips[0].potential_points, 9);
+
+        b.location.is_remote_compatible = true;
+        b.location.score = LOCATION_WEIGHT;
+        assert!(generate_match_tips(&b, &HashMap::new(), &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_education_gain_is_remaining_points() {
+        let mut b = breakdown();
+        b.education.required_level = Some("undergraduate".to_string());
+        b.education.user_level = Some("secondary".to_string());
+        b.education.meets_requirement = false;
+        b.education.score = 5;
+
+        let tips = generate_match_tips(&b, &HashMap::new(), &HashMap::new());
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips[0].kind, MatchTipKind::AddEducation);
+        assert_eq!(tips[0].potential_points, 5);
+        assert!(tips[0].message.contains("universitario"));
+    }
+
+    #[test]
+    fn test_tips_sorted_by_points_and_capped() {
+        let mut b = breakdown();
+        // 6 required skills, none met: 5 points each
+        b.skills.missing_required = (0..6).map(|_| missing_skill(None)).collect();
+        // Education missing entirely: 10 points
+        b.education.required_level = Some("technical".to_string());
+        b.education.meets_requirement = false;
+        b.education.score = 0;
+        // Relocation: 9 points
+        b.location.is_same_region = false;
+        b.location.is_same_municipality = false;
+        b.location.score = 0;
+
+        let tips = generate_match_tips(&b, &HashMap::new(), &HashMap::new());
+
+        assert_eq!(tips.len(), MAX_MATCH_TIPS);
+        assert_eq!(tips[0].kind, MatchTipKind::AddEducation);
+        assert_eq!(tips[0].potential_points, 10);
+        assert_eq!(tips[1].kind, MatchTipKind::EnableRelocation);
+        assert!(tips[2..].iter().all(|t| t.kind == MatchTipKind::AddSkill));
+        assert!(tips
+            .windows(2)
+            .all(|w| w[0].potential_points >= w[1].potential_points));
+    }
+
+    #[test]
+    fn test_diversify_caps_groups_in_top() {
+        // Items are (company, rank); company "a" dominates the sorted input
+        let items: Vec<(char, usize)> = "aaaaabbaaccdeffa".chars().zip(0..).collect();
+
+        let ranked = diversify_top(items, |item| item.0);
+        let top: Vec<char> = ranked[..DIVERSIFY_TOP_N].iter().map(|i| i.0).collect();
+        assert_eq!(top, ['a', 'a', 'a', 'b', 'b', 'c', 'c', 'd', 'e', 'f']);
+
+        // Demoted items come right after the top, in their original order
+        let ranks: Vec<usize> = ranked.iter().map(|i| i.1).collect();
+        assert_eq!(
+            ranks,
+            [0, 1, 2, 5, 6, 9, 10, 11, 12, 13, 3, 4, 7, 8, 14, 15]
+        );
+    }
+
+    #[test]
+    fn test_diversify_keeps_short_lists() {
+        let items = vec![('a', 0), ('a', 1), ('a', 2), ('a', 3), ('b', 4)];
+        let ranks: Vec<usize> = diversify_top(items, |item| item.0)
+            .iter()
+            .map(|i| i.1)
+            .collect();
+        assert_eq!(ranks, [0, 1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_old_scores_are_stale() {
+        let now = Utc::now();
+        let fresh = now - Duration::days(MATCH_SCORE_MAX_AGE_DAYS - 1);
+        let old = now - Duration::days(MATCH_SCORE_MAX_AGE_DAYS + 1);
+
+        assert!(!is_score_stale(false, fresh, now));
+        assert!(is_score_stale(true, fresh, now));
+        assert!(is_score_stale(false, old, now));
+    }
+
+    // ========================================================================
+    // SCORING
+    // ========================================================================
+
+    fn id(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    /// Job without requirements on site somewhere unknown, and a seeker with
+    /// an empty profile
+    fn no_requirements() -> MatchInputs {
+        MatchInputs {
+            user_skills: Vec::new(),
+            user_languages: Vec::new(),
+            user_location: UserLocationData {
+                region_id: None,
+                municipality_id: None,
+            },
+            user_experience: UserExperienceData { total_years: 0 },
+            user_education: UserEducationData {
+                highest_level: None,
+            },
+            user_disability: UserDisabilityData {
+                categories: Vec::new(),
+                requires_accommodations: false,
+            },
+            willing_to_relocate: false,
+            job_required_skills: Vec::new(),
+            job_preferred_skills: Vec::new(),
+            job_required_languages: Vec::new(),
+            job_location: JobLocationData {
+                region_id: None,
+                municipality_id: None,
+                is_remote_allowed: false,
+                work_modality: "on_site".to_string(),
+            },
+            job_experience_min: None,
+            job_experience_max: None,
+            job_education: None,
+            job_accommodations: JobAccommodationData {
+                categories: Vec::new(),
+            },
+        }
+    }
+
+    fn user_skill(n: u128, proficiency_level: i32) -> UserSkillData {
+        UserSkillData {
+            skill_id: id(n),
+            proficiency_level,
+            is_endorsed: false,
+        }
+    }
+
+    fn required_skill(n: u128, minimum_proficiency: i32) -> JobRequiredSkillData {
+        JobRequiredSkillData {
+            skill_id: id(n),
+            minimum_proficiency,
+        }
+    }
+
+    fn component_scores(b: &MatchScoreBreakdown) -> [i32; 6] {
+        [
+            b.skills.score,
+            b.languages.score,
+            b.location.score,
+            b.experience.score,
+            b.education.score,
+            b.accommodations.score,
+        ]
+    }
+
+    fn max_scores(b: &MatchScoreBreakdown) -> [i32; 6] {
+        [
+            b.skills.max_score,
+            b.languages.max_score,
+            b.location.max_score,
+            b.experience.max_score,
+            b.education.max_score,
+            b.accommodations.max_score,
+        ]
+    }
+
+    #[test]
+    fn test_score_fixtures() {
+        // Scores the handlers returned before preferred skills stopped being
+        // counted twice were 100 and 82 for these
+        let b = MatchingService::score(&no_requirements());
+        assert_eq!(component_scores(&b), [40, 15, 0, 15, 10, 5]);
+        assert_eq!(b.total_score, 85);
+        assert_eq!(max_scores(&b).iter().sum::<i32>(), 100);
+
+        let mut partial = no_requirements();
+        partial.job_required_skills = vec![required_skill(1, 3), required_skill(2, 3)];
+        partial.job_preferred_skills = vec![id(3)];
+        partial.user_skills = vec![user_skill(1, 4), user_skill(3, 4)];
+        partial.job_location.is_remote_allowed = true;
+        partial.user_experience.total_years = 5;
+        partial.job_experience_min = Some(7);
+        partial.job_education = Some("technical".to_string());
+        let b = MatchingService::score(&partial);
+        assert_eq!(component_scores(&b), [22, 15, 15, 7, 0, 5]);
+        assert_eq!(b.total_score, 64);
+
+        let mut perfect = partial;
+        perfect.user_skills.push(user_skill(2, 3));
+        perfect.user_experience.total_years = 7;
+        perfect.user_education.highest_level = Some("undergraduate".to_string());
+        assert_eq!(MatchingService::score(&perfect).total_score, 100);
+    }
+
+    #[test]
+    fn test_skills_score() {
+        let score =
+            |user: &[UserSkillData], required: &[JobRequiredSkillData], preferred: &[Uuid]| {
+                MatchingService::calculate_skills_score(user, required, preferred)
+            };
+
+        // Nothing asked for is full marks
+        let detail = score(&[], &[], &[]);
+        assert_eq!((detail.score, detail.preferred_score), (40, 5));
+        assert_eq!(detail.max_score, SKILLS_WEIGHT + PREFERRED_SKILLS_WEIGHT);
+
+        // Required skills count by share met, rounded down
+        let required = [required_skill(1, 3), required_skill(2, 3), required_skill(3, 3)];
+        let detail = score(&[user_skill(1, 3), user_skill(2, 5)], &required, &[]);
+        assert_eq!(detail.score, 23 + 5);
+        assert_eq!(detail.matched_required.len(), 2);
+        assert_eq!(detail.missing_required[0].user_proficiency, None);
+
+        // A level too low is missing with the seeker's level
+        let detail = score(&[user_skill(1, 2)], &required[..1], &[]);
+        assert_eq!(detail.score, 5);
+        assert_eq!(detail.missing_required[0].user_proficiency, Some(2));
+
+        // Preferred skills count at any level
+        let detail = score(&[user_skill(4, 1)], &[], &[id(4), id(5)]);
+        assert_eq!((detail.score, detail.preferred_score), (35 + 2, 2));
+        assert_eq!(detail.matched_preferred, vec![id(4)]);
+
+        let detail = score(&[user_skill(1, 3), user_skill(4, 1)], &required[..1], &[id(4)]);
+        assert_eq!(detail.score, detail.max_score);
+    }
+
+    #[test]
+    fn test_languages_score() {
+        let language = |n: u128, proficiency: &str| UserLanguageData {
+            language_id: id(n),
+            proficiency: proficiency.to_string(),
+        };
+        let required = |n: u128, minimum_proficiency| JobRequiredLanguageData {
+            language_id: id(n),
+            minimum_proficiency,
+        };
+
+        let detail = MatchingService::calculate_languages_score(&[], &[]);
+        assert_eq!((detail.score, detail.max_score), (15, LANGUAGES_WEIGHT));
+
+        let detail =
+            MatchingService::calculate_languages_score(&[language(1, "fluent")], &[required(1, 4)]);
+        assert_eq!(detail.score, 15);
+        assert_eq!(detail.matched[0].user_proficiency, 4);
+
+        let detail = MatchingService::calculate_languages_score(
+            &[language(1, "intermediate")],
+            &[required(1, 3), required(2, 1)],
+        );
+        assert_eq!(detail.score, 0);
+        assert_eq!(detail.missing[0].user_proficiency, Some(2));
+        assert_eq!(detail.missing[1].user_proficiency, None);
+
+        // Unknown levels count as basic
+        let detail = MatchingService::calculate_languages_score(
+            &[language(1, "Native"), language(2, "bilingual")],
+            &[required(1, 5), required(2, 2)],
+        );
+        assert_eq!(detail.score, 7);
+        assert_eq!(detail.missing[0].user_proficiency, Some(1));
+    }
+
+    #[test]
+    fn test_location_score() {
+        let (region, municipality) = (Some(id(1)), Some(id(2)));
+        let user = |region_id, municipality_id| UserLocationData {
+            region_id,
+            municipality_id,
+        };
+        let job = |region_id, municipality_id, is_remote_allowed, modality: &str| JobLocationData {
+            region_id,
+            municipality_id,
+            is_remote_allowed,
+            work_modality: modality.to_string(),
+        };
+
+        let cases = [
+            (user(region, municipality), job(region, municipality, false, "on_site"), false, 15),
+            (user(region, None), job(region, municipality, false, "on_site"), false, 12),
+            (user(None, None), job(None, None, true, "on_site"), false, 15),
+            (user(None, None), job(None, None, false, "remote"), false, 15),
+            (user(region, None), job(Some(id(3)), None, false, "hybrid"), true, 9),
+            (user(region, None), job(Some(id(3)), None, false, "hybrid"), false, 0),
+            // Unknown locations never match each other
+            (user(None, None), job(None, None, false, "on_site"), false, 0),
+        ];
+        for (i, (user, job, relocate, expected)) in cases.iter().enumerate() {
+            let detail = MatchingService::calculate_location_score(user, job, *relocate);
+            assert_eq!(detail.score, *expected, "case {}", i);
+            assert_eq!(detail.max_score, LOCATION_WEIGHT);
+        }
+    }
+
+    #[test]
+    fn test_experience_score() {
+        let cases = [
+            (0, None, None, 15, true),
+            (5, Some(3), Some(6), 15, true),
+            (5, Some(5), Some(5), 15, true),
+            // Up to two years short is half
+            (3, Some(5), None, 7, false),
+            (2, Some(5), None, 0, false),
+            // Overqualified keeps most of it
+            (8, None, Some(5), 10, false),
+            (9, Some(1), Some(5), 7, false),
+        ];
+        for (years, min, max, expected, within) in cases {
+            let detail = MatchingService::calculate_experience_score(
+                &UserExperienceData { total_years: years },
+                min,
+                max,
+            );
+            assert_eq!(
+                (detail.score, detail.is_within_range),
+                (expected, within),
+                "{} years for {:?}-{:?}",
+                years,
+                min,
+                max
+            );
+        }
+    }
+
+    #[test]
+    fn test_education_score() {
+        let cases = [
+            (None, None, 10, true),
+            (Some("secondary"), None, 10, true),
+            (Some("undergraduate"), Some("technical"), 10, true),
+            (Some("technical"), Some("technical"), 10, true),
+            // Partial credit by rank
+            (Some("secondary"), Some("undergraduate"), 5, false),
+            (Some("primary"), Some("technical"), 3, false),
+            (Some("none"), Some("primary"), 0, false),
+            (None, Some("technical"), 0, false),
+        ];
+        for (user, required, expected, meets) in cases {
+            let detail = MatchingService::calculate_education_score(
+                &UserEducationData {
+                    highest_level: user.map(str::to_string),
+                },
+                &required.map(str::to_string),
+            );
+            assert_eq!(
+                (detail.score, detail.meets_requirement),
+                (expected, meets),
+                "{:?} for {:?}",
+                user,
+                required
+            );
+        }
+    }
+
+    #[test]
+    fn test_accommodations_score() {
+        let strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let score = |needs: bool, user: &[&str], job: &[&str]| {
+            MatchingService::calculate_accommodations_score(
+                &UserDisabilityData {
+                    categories: strings(user),
+                    requires_accommodations: needs,
+                },
+                &JobAccommodationData {
+                    categories: strings(job),
+                },
+            )
+        };
+
+        let detail = score(false, &["visual"], &["physical"]);
+        assert_eq!(detail.score, 5);
+        assert!(!detail.user_needs_accommodations);
+        assert!(detail.job_provides_accommodations);
+
+        assert_eq!(score(true, &[], &[]).score, 5);
+        assert_eq!(score(true, &["visual"], &["visual", "physical"]).score, 5);
+        let detail = score(true, &["visual", "hearing"], &["visual"]);
+        assert_eq!(detail.score, 2);
+        assert_eq!(detail.matching_categories, strings(&["visual"]));
+        assert_eq!(score(true, &["visual"], &["physical"]).score, 1);
+        assert_eq!(score(true, &["visual"], &[]).score, 0);
+    }
+
+    // ========================================================================
+    // SCORING PROPERTIES
+    // ========================================================================
+
+    use proptest::collection::{btree_map, btree_set, vec};
+    use proptest::option;
+    use proptest::prelude::*;
+    use proptest::sample::select;
+
+    const LANGUAGE_LEVELS: &[&str] = &["basic", "intermediate", "advanced", "fluent", "native"];
+    const EDUCATION_LEVELS: &[&str] = &[
+        "none",
+        "primary",
+        "secondary",
+        "technical",
+        "undergraduate",
+        "graduate",
+        "postgraduate",
+    ];
+    const CATEGORIES: &[&str] = &["visual", "hearing", "physical", "intellectual"];
+
+    prop_compose! {
+        /// IDs come from small pools so seekers and jobs overlap often
+        fn arb_seeker()(
+            skills in btree_map(0..8u128, (1..=5i32, any::<bool>()), 0..6),
+            languages in btree_map(0..4u128, select(LANGUAGE_LEVELS), 0..3),
+            region in option::of(0..3u128),
+            municipality in option::of(0..3u128),
+            willing_to_relocate in any::<bool>(),
+            total_years in 0..40i32,
+            highest_level in option::of(select(EDUCATION_LEVELS)),
+            requires_accommodations in any::<bool>(),
+            categories in btree_set(select(CATEGORIES), 0..3),
+        ) -> MatchInputs {
+            let mut inputs = no_requirements();
+            inputs.user_skills = skills
+                .into_iter()
+                .map(|(n, (proficiency_level, is_endorsed))| UserSkillData {
+                    skill_id: id(n),
+                    proficiency_level,
+                    is_endorsed,
+                })
+                .collect();
+            inputs.user_languages = languages
+                .into_iter()
+                .map(|(n, level)| UserLanguageData {
+                    language_id: id(n),
+                    proficiency: level.to_string(),
+                })
+                .collect();
+            inputs.user_location = UserLocationData {
+                region_id: region.map(id),
+                municipality_id: municipality.map(|n| id(100 + n)),
+            };
+            inputs.willing_to_relocate = willing_to_relocate;
+            inputs.user_experience.total_years = total_years;
+            inputs.user_education.highest_level = highest_level.map(str::to_string);
+            inputs.user_disability = UserDisabilityData {
+                categories: categories.into_iter().map(str::to_string).collect(),
+                requires_accommodations,
+            };
+            inputs
+        }
+    }
+
+    prop_compose! {
+        fn arb_inputs()(
+            seeker in arb_seeker(),
+            required_skills in btree_map(0..8u128, 1..=5i32, 0..6),
+            preferred_skills in btree_set(0..8u128, 0..4),
+            required_languages in btree_map(0..4u128, 1..=5i32, 0..3),
+            region in option::of(0..3u128),
+            municipality in option::of(0..3u128),
+            is_remote_allowed in any::<bool>(),
+            work_modality in select(&["on_site", "hybrid", "remote"][..]),
+            experience in (option::of(0..20i32), option::of(0..20i32)),
+            education in option::of(select(EDUCATION_LEVELS)),
+            accommodations in vec(select(CATEGORIES), 0..3),
+        ) -> MatchInputs {
+            let mut inputs = seeker;
+            inputs.job_required_skills = required_skills
+                .into_iter()
+                .map(|(n, minimum_proficiency)| required_skill(n, minimum_proficiency))
+                .collect();
+            inputs.job_preferred_skills = preferred_skills.into_iter().map(id).collect();
+            inputs.job_required_languages = required_languages
+                .into_iter()
+                .map(|(n, minimum_proficiency)| JobRequiredLanguageData {
+                    language_id: id(n),
+                    minimum_proficiency,
+                })
+                .collect();
+            inputs.job_location = JobLocationData {
+                region_id: region.map(id),
+                municipality_id: municipality.map(|n| id(100 + n)),
+                is_remote_allowed,
+                work_modality: work_modality.to_string(),
+            };
+            (inputs.job_experience_min, inputs.job_experience_max) = experience;
+            inputs.job_education = education.map(str::to_string);
+            inputs.job_accommodations.categories =
+                accommodations.into_iter().map(str::to_string).collect();
+            inputs
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_total_is_sum_of_components_within_100(inputs in arb_inputs()) {
+            let b = MatchingService::score(&inputs);
+
+            prop_assert!((0..=100).contains(&b.total_score), "{}", b.total_score);
+            prop_assert_eq!(b.total_score, component_scores(&b).iter().sum::<i32>());
+            prop_assert_eq!(max_scores(&b).iter().sum::<i32>(), 100);
+            for (score, max) in component_scores(&b).into_iter().zip(max_scores(&b)) {
+                prop_assert!((0..=max).contains(&score), "{} of {}", score, max);
+            }
+            prop_assert!((0..=PREFERRED_SKILLS_WEIGHT).contains(&b.skills.preferred_score));
+        }
+
+        #[test]
+        fn prop_adding_a_matching_skill_never_lowers_the_score(inputs in arb_inputs()) {
+            let before = MatchingService::score(&inputs).total_score;
+
+            let job_skills = inputs
+                .job_required_skills
+                .iter()
+                .map(|s| s.skill_id)
+                .chain(inputs.job_preferred_skills.iter().copied());
+            for skill_id in job_skills {
+                if inputs.user_skills.iter().any(|s| s.skill_id == skill_id) {
+                    continue;
+                }
+                let mut more = inputs.clone();
+                more.user_skills.push(UserSkillData {
+                    skill_id,
+                    proficiency_level: 5,
+                    is_endorsed: false,
+                });
+                let after = MatchingService::score(&more).total_score;
+                prop_assert!(after >= before, "{} -> {} adding {}", before, after, skill_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Membership lookups behind the conflict-of-interest checks. Nothing stops
+//! one person from holding a seeker account and a company or OMIL
+//! membership at once; these keep them from acting on both sides of the
+//! same application.
+
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+/// Whether the user is an active member of the company that owns the job
+pub async fn is_member_of_job_company(
+    conn: impl PgExecutor<'_>,
+    user_id: Uuid,
+    job_id: Uuid,
+) -> Result<bool> {
+    let member = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM company_members m
+            JOIN jobs j ON j.company_id = m.company_id
+            WHERE j.id = $2 AND m.user_id = $1 AND m.is_active
+        ) as "member!"
+        "#,
+        user_id,
+        job_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(member)
+}
+
+/// Applications the OMIL sent on the user's behalf
+pub async fn applications_through_omil(
+    conn: impl PgExecutor<'_>,
+    user_id: Uuid,
+    omil_id: Uuid,
+) -> Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM omil_applications oa
+        JOIN job_applications ja ON ja.id = oa.application_id
+        WHERE ja.applicant_id = $1 AND oa.omil_id = $2
+        "#,
+        user_id,
+        omil_id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(count)
+}
+
+/// Rejects an application from a member of the company hiring for the job
+pub async fn check_not_self_dealing(
+    conn: impl PgExecutor<'_>,
+    applicant_id: Uuid,
+    job_id: Uuid,
+) -> Result<()> {
+    if is_member_of_job_company(conn, applicant_id, job_id).await? {
+        return Err(AppError::ConflictOfInterest {
+            code: "company_member_applicant",
+            message: "Members of the hiring company cannot apply to its jobs".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Rejects an OMIL member applying on behalf of their own seeker account
+pub fn check_not_own_behalf(member_user_id: Uuid, job_seeker_id: Uuid) -> Result<()> {
+    if member_user_id == job_seeker_id {
+        return Err(AppError::ConflictOfInterest {
+            code: "self_managed_applicant",
+            message: "OMIL members cannot apply on behalf of themselves".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Warning for a user who becomes an OMIL member after the OMIL applied on
+/// their behalf. Logged and returned, never blocking.
+pub async fn omil_membership_warning(
+    conn: impl PgExecutor<'_>,
+    user_id: Uuid,
+    omil_id: Uuid,
+) -> Result<Option<String>> {
+    let applications = applications_through_omil(conn, user_id, omil_id).await?;
+    if applications == 0 {
+        return Ok(None);
+    }
+
+    tracing::warn!(
+        "User {} joined OMIL {} which has {} applications on their behalf",
+        user_id,
+        omil_id,
+        applications
+    );
+    Ok(Some(format!(
+        "This user has {} applications submitted by this OMIL on their behalf",
+        applications
+    )))
+}
@@ -0,0 +1,276 @@
+//! Usage metering of companies, for future cost-sharing.
+//!
+//! Handlers record metered actions after the work they describe is done.
+//! Recording never fails the request: a failed write is logged and the
+//! action goes unmetered. A nightly task totals the events per company and
+//! calendar month (in the platform time zone) into `usage_monthly_summaries`,
+//! which is what companies and admins read.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_xlsxwriter::{Format, Workbook};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::{CompanyUsageRow, UsageReport};
+use crate::models::company::{CompanyUsage, UsageCounts, UsageEventType};
+
+/// Records `quantity` uses of `event_type` by the company. Failures are
+/// only logged.
+pub async fn record(
+    db: &PgPool,
+    company_id: Uuid,
+    event_type: UsageEventType,
+    quantity: i32,
+    metadata: Value,
+) {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO usage_events (company_id, event_type, quantity, metadata)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        company_id,
+        event_type as UsageEventType,
+        quantity,
+        metadata,
+    )
+    .execute(db)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to record {:?} usage of company {}: {}",
+            event_type,
+            company_id,
+            e
+        );
+    }
+}
+
+/// Records a publication for each job, e.g. after the scheduled ones went
+/// live. Failures are only logged.
+pub async fn record_jobs_published(db: &PgPool, job_ids: &[Uuid]) {
+    if job_ids.is_empty() {
+        return;
+    }
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO usage_events (company_id, event_type, metadata)
+        SELECT company_id, 'job_published', jsonb_build_object('job_id', id)
+        FROM jobs
+        WHERE id = ANY($1)
+        "#,
+        job_ids,
+    )
+    .execute(db)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to record {} job publication(s): {}",
+            job_ids.len(),
+            e
+        );
+    }
+}
+
+/// First day of the month asked for as YYYY-MM, or of the current month in
+/// `timezone`
+pub async fn parse_month(db: &PgPool, month: Option<&str>, timezone: &str) -> Result<NaiveDate> {
+    match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError("Month must be given as YYYY-MM".to_string())),
+        None => {
+            let current = sqlx::query_scalar!(
+                r#"SELECT date_trunc('month', NOW() AT TIME ZONE $1)::date as "month!""#,
+                timezone,
+            )
+            .fetch_one(db)
+            .await?;
+            Ok(current)
+        }
+    }
+}
+
+/// Totals the events of the month into its summaries, replacing whatever an
+/// earlier run wrote, so running it again never counts an event twice.
+/// Returns the number of summary rows written.
+pub async fn aggregate_month(db: &PgPool, month: NaiveDate, timezone: &str) -> Result<u64> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM usage_monthly_summaries WHERE month = $1",
+        month
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let written = sqlx::query!(
+        r#"
+        INSERT INTO usage_monthly_summaries (company_id, month, event_type, total)
+        SELECT company_id, $1::date, event_type, SUM(quantity)
+        FROM usage_events
+        WHERE occurred_at >= $1::date::timestamp AT TIME ZONE $2
+          AND occurred_at < ($1::date + INTERVAL '1 month') AT TIME ZONE $2
+        GROUP BY company_id, event_type
+        "#,
+        month,
+        timezone,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+
+    Ok(written)
+}
+
+/// Nightly run: the current month so far, and the previous one so events of
+/// its last evening are included once it has ended
+pub async fn aggregate_recent(db: &PgPool, timezone: &str) -> Result<u64> {
+    let current = parse_month(db, None, timezone).await?;
+    let previous = current
+        .checked_sub_months(chrono::Months::new(1))
+        .unwrap_or(current);
+
+    let mut written = aggregate_month(db, previous, timezone).await?;
+    written += aggregate_month(db, current, timezone).await?;
+    Ok(written)
+}
+
+fn month_label(month: NaiveDate) -> String {
+    month.format("%Y-%m").to_string()
+}
+
+/// The company's summaries for the month
+pub async fn company_usage(
+    db: &PgPool,
+    company_id: Uuid,
+    month: NaiveDate,
+) -> Result<CompanyUsage> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT event_type as "event_type: UsageEventType", total, aggregated_at
+        FROM usage_monthly_summaries
+        WHERE company_id = $1 AND month = $2
+        "#,
+        company_id,
+        month,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut usage = UsageCounts::default();
+    let mut aggregated_at: Option<DateTime<Utc>> = None;
+    for row in rows {
+        usage.add(row.event_type, row.total);
+        aggregated_at = aggregated_at.max(Some(row.aggregated_at));
+    }
+
+    Ok(CompanyUsage {
+        month: month_label(month),
+        aggregated_at,
+        usage,
+    })
+}
+
+/// Every company's summaries for the month, with the platform totals
+pub async fn usage_report(db: &PgPool, month: NaiveDate) -> Result<UsageReport> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            s.company_id,
+            cp.company_name,
+            s.event_type as "event_type: UsageEventType",
+            s.total,
+            s.aggregated_at
+        FROM usage_monthly_summaries s
+        JOIN company_profiles cp ON cp.id = s.company_id
+        WHERE s.month = $1
+        ORDER BY cp.company_name, s.company_id
+        "#,
+        month,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut companies: Vec<CompanyUsageRow> = Vec::new();
+    let mut totals = UsageCounts::default();
+    let mut aggregated_at: Option<DateTime<Utc>> = None;
+    for row in rows {
+        if companies.last().map(|c| c.company_id) != Some(row.company_id) {
+            companies.push(CompanyUsageRow {
+                company_id: row.company_id,
+                company_name: row.company_name,
+                usage: UsageCounts::default(),
+            });
+        }
+        if let Some(company) = companies.last_mut() {
+            company.usage.add(row.event_type, row.total);
+        }
+        totals.add(row.event_type, row.total);
+        aggregated_at = aggregated_at.max(Some(row.aggregated_at));
+    }
+
+    Ok(UsageReport {
+        month: month_label(month),
+        aggregated_at,
+        companies,
+        totals,
+    })
+}
+
+/// The usage report as a spreadsheet, one row per company and a total row
+pub fn render_usage_report(report: &UsageReport) -> Result<Vec<u8>> {
+    let xlsx_err =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet
+        .set_name(format!("Usage {}", report.month))
+        .map_err(xlsx_err)?;
+    let header_format = Format::new().set_bold();
+
+    let headers = [
+        "Company",
+        "Jobs Published",
+        "Candidate Searches",
+        "Invitations Sent",
+        "CV Downloads",
+    ];
+    for (col, title) in headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *title, &header_format)
+            .map_err(xlsx_err)?;
+    }
+
+    let total_row = ("Total".to_string(), &report.totals);
+    let rows = report
+        .companies
+        .iter()
+        .map(|c| (c.company_name.clone(), &c.usage))
+        .chain([total_row]);
+    for (i, (name, usage)) in rows.enumerate() {
+        let line = (i + 1) as u32;
+        worksheet.write_string(line, 0, &name).map_err(xlsx_err)?;
+        let counts = [
+            usage.job_published,
+            usage.candidate_search,
+            usage.invitation_sent,
+            usage.cv_download,
+        ];
+        for (j, count) in counts.iter().enumerate() {
+            worksheet
+                .write_number(line, (j + 1) as u16, *count as f64)
+                .map_err(xlsx_err)?;
+        }
+    }
+
+    workbook
+        .save_to_buffer()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))
+}
@@ -1,3 +1,54 @@
+pub mod account_deletion;
+pub mod admin_events;
+pub mod admin_notes;
+pub mod alerts;
+pub mod application_documents;
+pub mod applications;
+pub mod authz;
+pub mod automation;
+pub mod availability;
+pub mod backfill;
+pub mod benchmarks;
+pub mod browsing;
+pub mod bulk_operations;
+pub mod calendar;
+pub mod captcha;
+pub mod company_deletion;
+pub mod company_export;
+pub mod company_insights;
+pub mod completeness;
+pub mod content_screening;
+pub mod counters;
+pub mod cover_letters;
+pub mod cv_parse;
 pub mod email;
+pub mod events;
+pub mod exports;
+pub mod institutions;
+pub mod job_deadlines;
+pub mod job_duplicates;
+pub mod job_feed;
+pub mod job_reposts;
+pub mod job_transfer;
+pub mod limits;
+pub mod maintenance;
 pub mod matching;
+pub mod memberships;
+pub mod metering;
+pub mod notifications;
+pub mod omil_export;
+pub mod omil_coverage;
+pub mod omil_monthly_report;
+pub mod omil_stats;
+pub mod onboarding;
+pub mod profile_fingerprint;
+pub mod profile_references;
+pub mod public_stats;
+pub mod reference_seed;
+pub mod retention;
+pub mod scheduler;
+pub mod settings;
+pub mod skill_gap;
+pub mod stale_jobs;
 pub mod storage;
+pub mod suspension;
@@ -0,0 +1,357 @@
+//! Who gets told about what. Every email about a NotificationEvent is sent
+//! through `email`, which asks `should_send` first: the user's stored choice
+//! for the event, or the default for their user type until they make one.
+//! Security emails (password and email changes) don't go through here and
+//! can't be turned off.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::models::notification::{
+    ChannelSettings, NotificationChannel, NotificationEvent, NotificationPreference,
+    NotificationPreferenceUpdate,
+};
+use crate::models::user::UserType;
+use crate::services::email::{EmailError, EmailService};
+use crate::utils::jwt;
+use crate::AppState;
+
+/// The stored choice, or the defaults for the user type when there is none.
+/// Events that don't apply to the user type can't have been turned off.
+fn resolve(
+    event: NotificationEvent,
+    user_type: UserType,
+    stored: Option<ChannelSettings>,
+) -> ChannelSettings {
+    match stored {
+        Some(settings) if event.is_configurable() && event.applies_to(user_type) => settings,
+        _ => event.defaults(user_type),
+    }
+}
+
+/// Whether the user wants to hear about `event` on `channel`. Users that no
+/// longer exist don't.
+pub async fn should_send(
+    db: &PgPool,
+    user_id: Uuid,
+    event: NotificationEvent,
+    channel: NotificationChannel,
+) -> Result<bool> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            u.user_type as "user_type: UserType",
+            p.email as "email?",
+            p.in_app as "in_app?"
+        FROM users u
+        LEFT JOIN notification_preferences p ON p.user_id = u.id AND p.event_type = $2
+        WHERE u.id = $1
+        "#,
+        user_id,
+        event.code()
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(false);
+    };
+    let stored = row
+        .email
+        .zip(row.in_app)
+        .map(|(email, in_app)| ChannelSettings { email, in_app });
+
+    Ok(resolve(event, row.user_type, stored).get(channel))
+}
+
+/// The users among `user_ids` that want to hear about `event` on `channel`,
+/// in the order given. For batches like the digest, where one query per
+/// user would be too many.
+pub async fn filter_recipients(
+    db: &PgPool,
+    user_ids: &[Uuid],
+    event: NotificationEvent,
+    channel: NotificationChannel,
+) -> Result<Vec<Uuid>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            u.id,
+            u.user_type as "user_type: UserType",
+            p.email as "email?",
+            p.in_app as "in_app?"
+        FROM users u
+        LEFT JOIN notification_preferences p ON p.user_id = u.id AND p.event_type = $2
+        WHERE u.id = ANY($1)
+        "#,
+        user_ids,
+        event.code()
+    )
+    .fetch_all(db)
+    .await?;
+
+    let wanted: HashMap<Uuid, bool> = rows
+        .into_iter()
+        .map(|row| {
+            let stored = row
+                .email
+                .zip(row.in_app)
+                .map(|(email, in_app)| ChannelSettings { email, in_app });
+            (row.id, resolve(event, row.user_type, stored).get(channel))
+        })
+        .collect();
+
+    Ok(user_ids
+        .iter()
+        .copied()
+        .filter(|id| wanted.get(id).copied().unwrap_or(false))
+        .collect())
+}
+
+/// Emails `user_id` about `event` in the background, unless they turned the
+/// event's emails off. `send` gets an email service whose emails carry the
+/// link to unsubscribe from the event.
+pub fn email<F, Fut>(state: &AppState, user_id: Uuid, event: NotificationEvent, send: F)
+where
+    F: FnOnce(EmailService) -> Fut + Send + 'static,
+    Fut: Future<Output = std::result::Result<(), EmailError>> + Send,
+{
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = email_now(&state, user_id, event, send).await {
+            tracing::error!("Failed to send {} email: {}", event.code(), e);
+        }
+    });
+}
+
+/// Like `email`, but waits for the email to go out, for batches that count
+/// their failures. False when the user turned the event's emails off.
+pub async fn email_now<F, Fut>(
+    state: &AppState,
+    user_id: Uuid,
+    event: NotificationEvent,
+    send: F,
+) -> Result<bool>
+where
+    F: FnOnce(EmailService) -> Fut,
+    Fut: Future<Output = std::result::Result<(), EmailError>>,
+{
+    if !should_send(&state.db, user_id, event, NotificationChannel::Email).await? {
+        return Ok(false);
+    }
+
+    let token = jwt::create_unsubscribe_token(user_id, event.code(), &state.config)
+        .map_err(|e| AppError::InternalError(format!("Unsubscribe token: {}", e)))?;
+    send(state.email.with_unsubscribe_token(token))
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+    Ok(true)
+}
+
+// ============================================================================
+// PREFERENCES
+// ============================================================================
+
+/// The user's full preferences matrix: every event for their user type,
+/// security included. The first read stores the defaults.
+pub async fn preferences(db: &PgPool, user_id: Uuid) -> Result<Vec<NotificationPreference>> {
+    let user_type = seed(db, user_id).await?;
+
+    let stored: HashMap<String, ChannelSettings> = sqlx::query!(
+        "SELECT event_type, email, in_app FROM notification_preferences WHERE user_id = $1",
+        user_id
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| {
+        (
+            row.event_type,
+            ChannelSettings {
+                email: row.email,
+                in_app: row.in_app,
+            },
+        )
+    })
+    .collect();
+
+    Ok(NotificationEvent::ALL
+        .into_iter()
+        .filter(|event| event.applies_to(user_type))
+        .map(|event| {
+            let settings = resolve(event, user_type, stored.get(event.code()).copied());
+            NotificationPreference {
+                event,
+                title: event.title().to_string(),
+                email: settings.email,
+                in_app: settings.in_app,
+                configurable: event.is_configurable(),
+            }
+        })
+        .collect())
+}
+
+/// Applies the changes, all or none. Events that don't apply to the user,
+/// and security notifications, can't be changed.
+pub async fn update(
+    db: &PgPool,
+    user_id: Uuid,
+    updates: &[NotificationPreferenceUpdate],
+) -> Result<Vec<NotificationPreference>> {
+    let user_type = seed(db, user_id).await?;
+
+    for update in updates {
+        if !update.event.is_configurable() {
+            return Err(AppError::ValidationError(format!(
+                "{} notifications are always sent",
+                update.event.code()
+            )));
+        }
+        if !update.event.applies_to(user_type) {
+            return Err(AppError::ValidationError(format!(
+                "{} notifications don't apply to this account",
+                update.event.code()
+            )));
+        }
+    }
+
+    let mut tx = db.begin().await?;
+    for update in updates {
+        sqlx::query!(
+            r#"
+            UPDATE notification_preferences
+            SET email = COALESCE($3, email),
+                in_app = COALESCE($4, in_app)
+            WHERE user_id = $1 AND event_type = $2
+            "#,
+            user_id,
+            update.event.code(),
+            update.email,
+            update.in_app
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    preferences(db, user_id).await
+}
+
+/// Turns off the emails of the event an unsubscribe link was sent for
+pub async fn unsubscribe(
+    db: &PgPool,
+    config: &Config,
+    token: &str,
+) -> Result<NotificationEvent> {
+    let invalid = || AppError::ValidationError("Invalid or expired unsubscribe link".to_string());
+
+    let claims = jwt::verify_unsubscribe_token(token, config).map_err(|_| invalid())?;
+    let user_id = claims.user_id().map_err(|_| invalid())?;
+    let event = NotificationEvent::from_code(&claims.event)
+        .filter(|event| event.is_configurable())
+        .ok_or_else(invalid)?;
+
+    let user_type = seed(db, user_id).await?;
+    if !event.applies_to(user_type) {
+        return Err(invalid());
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE notification_preferences SET email = false
+        WHERE user_id = $1 AND event_type = $2
+        "#,
+        user_id,
+        event.code()
+    )
+    .execute(db)
+    .await?;
+
+    Ok(event)
+}
+
+/// Stores the defaults for every configurable event of the user's type that
+/// has no row yet, returning the user type
+async fn seed(db: &PgPool, user_id: Uuid) -> Result<UserType> {
+    let user_type = sqlx::query_scalar!(
+        r#"SELECT user_type as "user_type: UserType" FROM users WHERE id = $1"#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let (events, defaults): (Vec<_>, Vec<_>) = NotificationEvent::ALL
+        .into_iter()
+        .filter(|event| event.is_configurable() && event.applies_to(user_type))
+        .map(|event| (event.code().to_string(), event.defaults(user_type)))
+        .unzip();
+    let emails: Vec<bool> = defaults.iter().map(|d| d.email).collect();
+    let in_apps: Vec<bool> = defaults.iter().map(|d| d.in_app).collect();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_preferences (user_id, event_type, email, in_app)
+        SELECT $1, e.event_type, e.email, e.in_app
+        FROM UNNEST($2::varchar[], $3::bool[], $4::bool[]) AS e(event_type, email, in_app)
+        ON CONFLICT (user_id, event_type) DO NOTHING
+        "#,
+        user_id,
+        &events,
+        &emails,
+        &in_apps
+    )
+    .execute(db)
+    .await?;
+
+    Ok(user_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_choice_wins_over_defaults() {
+        let off = ChannelSettings {
+            email: false,
+            in_app: true,
+        };
+        assert_eq!(
+            resolve(NotificationEvent::Digest, UserType::JobSeeker, Some(off)),
+            off
+        );
+        assert!(resolve(NotificationEvent::Digest, UserType::JobSeeker, None).email);
+    }
+
+    #[test]
+    fn test_security_and_foreign_events_ignore_stored_choice() {
+        let off = ChannelSettings {
+            email: false,
+            in_app: false,
+        };
+        assert!(resolve(NotificationEvent::Security, UserType::JobSeeker, Some(off)).email);
+        // Companies have no application_status setting to turn off
+        assert!(
+            resolve(
+                NotificationEvent::ApplicationStatus,
+                UserType::CompanyMember,
+                Some(off)
+            )
+            .email
+        );
+    }
+
+    #[test]
+    fn test_announcement_defaults_per_user_type() {
+        let event = NotificationEvent::Announcement;
+        assert!(!event.defaults(UserType::JobSeeker).email);
+        assert!(event.defaults(UserType::CompanyMember).email);
+        assert!(event.defaults(UserType::JobSeeker).in_app);
+    }
+}
@@ -0,0 +1,198 @@
+//! OMIL coverage of the country for the landing page map: per municipality,
+//! whether its OMIL is on the platform, how many jobs are open there and
+//! roughly how many job seekers live there. Seeker numbers only ever leave
+//! as a `SeekerCountBucket`, also in the region and national totals.
+
+use chrono::Utc;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+
+use crate::error::Result;
+use crate::models::stats::{
+    CoverageTotals, MunicipalityCoverage, OmilCoverage, RegionCoverage, SeekerCountBucket,
+};
+
+/// Also sent as the response's max-age, so browsers and CDNs agree with Redis
+pub const CACHE_TTL_SECONDS: u64 = 60 * 60;
+
+pub const CACHE_KEY: &str = "stats:omil-coverage";
+
+/// Coverage from Redis, computed and stored on a miss.
+/// Redis failures are logged and the coverage computed directly.
+pub async fn omil_coverage(db: &PgPool, redis: &mut ConnectionManager) -> Result<OmilCoverage> {
+    match redis.get::<_, Option<String>>(CACHE_KEY).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(coverage) => return Ok(coverage),
+            Err(e) => tracing::warn!("Discarding unreadable OMIL coverage cache: {}", e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read OMIL coverage cache: {}", e),
+    }
+
+    let coverage = compute_omil_coverage(db).await?;
+
+    if let Ok(json) = serde_json::to_string(&coverage) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(CACHE_KEY, json, CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache OMIL coverage: {}", e);
+        }
+    }
+
+    Ok(coverage)
+}
+
+/// Running sums behind `CoverageTotals`, with the exact seeker count kept
+/// until it is bucketed
+#[derive(Default)]
+struct Totals {
+    municipalities: i64,
+    municipalities_with_active_omil: i64,
+    active_jobs: i64,
+    registered_seekers: i64,
+}
+
+impl Totals {
+    fn add(&mut self, has_active_omil: bool, active_jobs: i64, registered_seekers: i64) {
+        self.municipalities += 1;
+        self.municipalities_with_active_omil += i64::from(has_active_omil);
+        self.active_jobs += active_jobs;
+        self.registered_seekers += registered_seekers;
+    }
+
+    fn publish(&self) -> CoverageTotals {
+        CoverageTotals {
+            municipalities: self.municipalities,
+            municipalities_with_active_omil: self.municipalities_with_active_omil,
+            active_jobs: self.active_jobs,
+            registered_seekers: SeekerCountBucket::from_count(self.registered_seekers),
+        }
+    }
+}
+
+/// Coverage straight from the database, without the cache
+pub async fn compute_omil_coverage(db: &PgPool) -> Result<OmilCoverage> {
+    let rows = sqlx::query!(
+        r#"
+        WITH jobs_by_municipality AS (
+            SELECT municipality_id, COUNT(*) AS active_jobs
+            FROM jobs
+            WHERE status = 'active' AND municipality_id IS NOT NULL
+            GROUP BY municipality_id
+        ),
+        seekers_by_municipality AS (
+            SELECT p.municipality_id, COUNT(*) AS registered_seekers
+            FROM job_seeker_profiles p
+            JOIN users u ON u.id = p.user_id
+            WHERE p.municipality_id IS NOT NULL
+              AND u.user_type = 'job_seeker'
+              AND u.account_status IN ('pending_verification', 'active', 'managed_offline')
+            GROUP BY p.municipality_id
+        ),
+        omils_by_municipality AS (
+            SELECT DISTINCT municipality_id
+            FROM omil_organizations
+            WHERE status = 'active' AND municipality_id IS NOT NULL
+        )
+        SELECT
+            r.id AS region_id,
+            r.name AS region_name,
+            r.code AS region_code,
+            m.id AS municipality_id,
+            m.name AS municipality_name,
+            m.latitude,
+            m.longitude,
+            o.municipality_id IS NOT NULL AS "has_active_omil!",
+            COALESCE(j.active_jobs, 0) AS "active_jobs!",
+            COALESCE(s.registered_seekers, 0) AS "registered_seekers!"
+        FROM municipalities m
+        JOIN regions r ON r.id = m.region_id
+        LEFT JOIN jobs_by_municipality j ON j.municipality_id = m.id
+        LEFT JOIN seekers_by_municipality s ON s.municipality_id = m.id
+        LEFT JOIN omils_by_municipality o ON o.municipality_id = m.id
+        WHERE m.is_active AND r.is_active
+        ORDER BY r.sort_order, r.name, m.name
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut regions: Vec<(RegionCoverage, Totals)> = Vec::new();
+    let mut national = Totals::default();
+
+    for row in rows {
+        if regions.last().map(|(r, _)| r.region_id) != Some(row.region_id) {
+            regions.push((
+                RegionCoverage {
+                    region_id: row.region_id,
+                    name: row.region_name.clone(),
+                    code: row.region_code.clone(),
+                    totals: Totals::default().publish(),
+                    municipalities: Vec::new(),
+                },
+                Totals::default(),
+            ));
+        }
+        let (region, totals) = regions.last_mut().expect("region was just pushed");
+
+        totals.add(row.has_active_omil, row.active_jobs, row.registered_seekers);
+        national.add(row.has_active_omil, row.active_jobs, row.registered_seekers);
+        region.municipalities.push(MunicipalityCoverage {
+            municipality_id: row.municipality_id,
+            name: row.municipality_name,
+            latitude: row.latitude,
+            longitude: row.longitude,
+            has_active_omil: row.has_active_omil,
+            active_jobs: row.active_jobs,
+            registered_seekers: SeekerCountBucket::from_count(row.registered_seekers),
+        });
+    }
+
+    Ok(OmilCoverage {
+        regions: regions
+            .into_iter()
+            .map(|(region, totals)| RegionCoverage {
+                totals: totals.publish(),
+                ..region
+            })
+            .collect(),
+        totals: national.publish(),
+        generated_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeker_count_buckets() {
+        assert_eq!(SeekerCountBucket::from_count(0), SeekerCountBucket::None);
+        assert_eq!(SeekerCountBucket::from_count(1), SeekerCountBucket::UpTo50);
+        assert_eq!(SeekerCountBucket::from_count(50), SeekerCountBucket::UpTo50);
+        assert_eq!(
+            SeekerCountBucket::from_count(51),
+            SeekerCountBucket::UpTo200
+        );
+        assert_eq!(
+            SeekerCountBucket::from_count(200),
+            SeekerCountBucket::UpTo200
+        );
+        assert_eq!(
+            SeekerCountBucket::from_count(201),
+            SeekerCountBucket::Over200
+        );
+    }
+
+    #[test]
+    fn test_totals_bucket_the_sum() {
+        let mut totals = Totals::default();
+        totals.add(true, 3, 40);
+        totals.add(false, 0, 40);
+        let published = totals.publish();
+        assert_eq!(published.municipalities, 2);
+        assert_eq!(published.municipalities_with_active_omil, 1);
+        assert_eq!(published.active_jobs, 3);
+        assert_eq!(published.registered_seekers, SeekerCountBucket::UpTo200);
+    }
+}
@@ -0,0 +1,388 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_xlsxwriter::{Format, Image, Workbook};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::omil::{export_column_header, OmilExportTemplate, PlacementOutcome};
+use crate::services::exports::Watermark;
+
+/// Height in points of the title row, and the box the logo is scaled into
+const TITLE_ROW_HEIGHT: f64 = 48.0;
+const LOGO_MAX_WIDTH_PX: u32 = 160;
+const LOGO_MAX_HEIGHT_PX: u32 = 60;
+
+/// Columns holding contact details, left out when contact export is off
+const CONTACT_COLUMNS: [&str; 2] = ["email", "phone"];
+
+// ============================================================================
+// DATA
+// ============================================================================
+
+/// Every exportable field of one managed job seeker
+#[derive(Debug, Clone)]
+pub struct ExportRow {
+    pub first_name: String,
+    pub last_name: String,
+    pub national_id: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub gender: Option<String>,
+    pub region: Option<String>,
+    pub municipality: Option<String>,
+    pub profile_completeness: Option<i32>,
+    pub placement_outcome: PlacementOutcome,
+    pub placed_at: Option<DateTime<Utc>>,
+    pub placed_job_title: Option<String>,
+    pub placed_company_name: Option<String>,
+    pub advisor_name: Option<String>,
+    pub branch: Option<String>,
+    pub registered_at: DateTime<Utc>,
+    pub is_active: bool,
+    pub followup_count: i64,
+    pub last_followup_at: Option<DateTime<Utc>>,
+    pub applications_count: i64,
+    pub notes: Option<String>,
+}
+
+pub async fn fetch_rows(
+    db: &PgPool,
+    omil_id: Uuid,
+    placement_outcome: Option<PlacementOutcome>,
+) -> Result<Vec<ExportRow>> {
+    let rows = sqlx::query_as!(
+        ExportRow,
+        r#"
+        SELECT
+            u.first_name, u.last_name, p.national_id, u.email, p.phone,
+            p.date_of_birth, p.gender::text as gender,
+            r.name as "region?", m.name as "municipality?",
+            p.completeness_percentage as "profile_completeness?",
+            mjs.placement_outcome as "placement_outcome: PlacementOutcome",
+            mjs.placed_at,
+            j.title as "placed_job_title?",
+            c.company_name as "placed_company_name?",
+            (a.first_name || ' ' || a.last_name) as advisor_name,
+            b.name as "branch?",
+            mjs.registered_at, mjs.is_active,
+            (
+                SELECT COUNT(*) FROM job_seeker_followups f
+                WHERE f.job_seeker_id = mjs.job_seeker_id AND f.omil_id = mjs.omil_id
+            ) as "followup_count!",
+            (
+                SELECT MAX(f.followup_date) FROM job_seeker_followups f
+                WHERE f.job_seeker_id = mjs.job_seeker_id AND f.omil_id = mjs.omil_id
+            ) as last_followup_at,
+            (
+                SELECT COUNT(*) FROM omil_applications oa
+                JOIN job_applications ja ON ja.id = oa.application_id
+                WHERE oa.omil_id = mjs.omil_id AND ja.applicant_id = mjs.job_seeker_id
+            ) as "applications_count!",
+            mjs.notes
+        FROM omil_managed_job_seekers mjs
+        JOIN users u ON u.id = mjs.job_seeker_id
+        LEFT JOIN job_seeker_profiles p ON p.user_id = mjs.job_seeker_id
+        LEFT JOIN regions r ON r.id = p.region_id
+        LEFT JOIN municipalities m ON m.id = p.municipality_id
+        LEFT JOIN jobs j ON j.id = mjs.placed_job_id
+        LEFT JOIN company_profiles c ON c.id = j.company_id
+        LEFT JOIN users a ON a.id = mjs.assigned_advisor_id
+        LEFT JOIN omil_branches b ON b.id = mjs.branch_id
+        WHERE mjs.omil_id = $1
+        AND ($2::placement_outcome IS NULL OR mjs.placement_outcome = $2)
+        ORDER BY mjs.registered_at DESC
+        "#,
+        omil_id,
+        placement_outcome as Option<PlacementOutcome>,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows)
+}
+
+// ============================================================================
+// LAYOUT
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+/// A template applied to rows, ready to be written out
+#[derive(Debug, Clone)]
+pub struct ExportLayout {
+    pub title: Option<String>,
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<ExportCell>>,
+}
+
+fn text(value: Option<&str>) -> ExportCell {
+    match value {
+        Some(value) if !value.is_empty() => ExportCell::Text(value.to_string()),
+        _ => ExportCell::Empty,
+    }
+}
+
+fn timestamp(value: Option<DateTime<Utc>>) -> ExportCell {
+    value.map_or(ExportCell::Empty, |v| {
+        ExportCell::Text(v.format("%Y-%m-%d %H:%M").to_string())
+    })
+}
+
+/// Value of column `key` for a row; unknown keys are left empty
+pub fn cell(row: &ExportRow, key: &str) -> ExportCell {
+    match key {
+        "full_name" => ExportCell::Text(format!("{} {}", row.first_name, row.last_name)),
+        "first_name" => text(Some(&row.first_name)),
+        "last_name" => text(Some(&row.last_name)),
+        "national_id" => text(row.national_id.as_deref()),
+        "email" => text(row.email.as_deref()),
+        "phone" => text(row.phone.as_deref()),
+        "date_of_birth" => row.date_of_birth.map_or(ExportCell::Empty, |d| {
+            ExportCell::Text(d.format("%Y-%m-%d").to_string())
+        }),
+        "gender" => text(row.gender.as_deref()),
+        "region" => text(row.region.as_deref()),
+        "municipality" => text(row.municipality.as_deref()),
+        "profile_completeness" => row
+            .profile_completeness
+            .map_or(ExportCell::Empty, |v| ExportCell::Number(v as f64)),
+        "placement_outcome" => ExportCell::Text(format!("{:?}", row.placement_outcome)),
+        "placed_at" => timestamp(row.placed_at),
+        "placed_job_title" => text(row.placed_job_title.as_deref()),
+        "placed_company_name" => text(row.placed_company_name.as_deref()),
+        "advisor_name" => text(row.advisor_name.as_deref()),
+        "branch" => text(row.branch.as_deref()),
+        "registered_at" => timestamp(Some(row.registered_at)),
+        "is_active" => ExportCell::Text(if row.is_active { "Yes" } else { "No" }.to_string()),
+        "followup_count" => ExportCell::Number(row.followup_count as f64),
+        "last_followup_at" => timestamp(row.last_followup_at),
+        "applications_count" => ExportCell::Number(row.applications_count as f64),
+        "notes" => text(row.notes.as_deref()),
+        _ => ExportCell::Empty,
+    }
+}
+
+/// Lays rows out in the template's column order. Columns saved before a key
+/// was retired are skipped rather than failing the export, and so are
+/// contact columns unless `include_contact` is set.
+pub fn build_layout(
+    template: &OmilExportTemplate,
+    rows: &[ExportRow],
+    include_contact: bool,
+) -> ExportLayout {
+    let columns: Vec<(&str, &'static str)> = template
+        .columns
+        .iter()
+        .filter(|key| include_contact || !CONTACT_COLUMNS.contains(&key.as_str()))
+        .filter_map(|key| export_column_header(key).map(|header| (key.as_str(), header)))
+        .collect();
+
+    ExportLayout {
+        title: template.header_title.clone(),
+        headers: columns.iter().map(|(_, header)| *header).collect(),
+        rows: rows
+            .iter()
+            .map(|row| columns.iter().map(|(key, _)| cell(row, key)).collect())
+            .collect(),
+    }
+}
+
+// ============================================================================
+// RENDERING
+// ============================================================================
+
+/// Writes the layout to an .xlsx file: logo and title on the first row when
+/// present, then a blank row, the header row and the data, and the
+/// watermark below a blank row after that
+pub fn render(
+    layout: &ExportLayout,
+    logo: Option<&[u8]>,
+    watermark: &Watermark,
+) -> Result<Vec<u8>> {
+    let xlsx_err =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    let header_format = Format::new().set_bold();
+    let title_format = Format::new().set_bold().set_font_size(16);
+
+    let has_banner = layout.title.is_some() || logo.is_some();
+    if has_banner {
+        worksheet
+            .set_row_height(0, TITLE_ROW_HEIGHT)
+            .map_err(xlsx_err)?;
+    }
+
+    let mut title_col = 0u16;
+    if let Some(logo) = logo {
+        match Image::new_from_buffer(logo) {
+            Ok(image) => {
+                let image = image.set_scale_to_size(LOGO_MAX_WIDTH_PX, LOGO_MAX_HEIGHT_PX, true);
+                worksheet.insert_image(0, 0, &image).map_err(xlsx_err)?;
+                title_col = 1;
+            }
+            // A broken logo should not cost the municipality its report
+            Err(e) => tracing::warn!("Skipping unreadable export logo: {}", e),
+        }
+    }
+
+    if let Some(ref title) = layout.title {
+        worksheet
+            .write_string_with_format(0, title_col, title, &title_format)
+            .map_err(xlsx_err)?;
+    }
+
+    let header_row = if has_banner { 2 } else { 0 };
+    for (col, header) in layout.headers.iter().enumerate() {
+        worksheet
+            .write_string_with_format(header_row, col as u16, *header, &header_format)
+            .map_err(xlsx_err)?;
+    }
+
+    for (i, cells) in layout.rows.iter().enumerate() {
+        let row = header_row + 1 + i as u32;
+        for (col, value) in cells.iter().enumerate() {
+            let col = col as u16;
+            match value {
+                ExportCell::Text(s) => worksheet.write_string(row, col, s).map_err(xlsx_err)?,
+                ExportCell::Number(n) => worksheet.write_number(row, col, *n).map_err(xlsx_err)?,
+                ExportCell::Empty => continue,
+            };
+        }
+    }
+
+    let last_row = header_row + layout.rows.len() as u32;
+    watermark.stamp(&mut workbook, last_row + 2)?;
+
+    workbook
+        .save_to_buffer()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest valid PNG: one white pixel
+    const PIXEL_PNG: [u8; 69] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90,
+        0x77, 0x53, 0xde, 0x00, 0x00, 0x00, 0x0c, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8,
+        0xff, 0xff, 0x3f, 0x00, 0x05, 0xfe, 0x02, 0xfe, 0x0d, 0xef, 0x46, 0xb8, 0x00, 0x00, 0x00,
+        0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    fn row() -> ExportRow {
+        ExportRow {
+            first_name: "María".to_string(),
+            last_name: "González".to_string(),
+            national_id: Some("12345678-5".to_string()),
+            email: Some("maria@example.cl".to_string()),
+            phone: None,
+            date_of_birth: NaiveDate::from_ymd_opt(1990, 5, 17),
+            gender: Some("female".to_string()),
+            region: Some("Región Metropolitana".to_string()),
+            municipality: Some("Maipú".to_string()),
+            profile_completeness: Some(80),
+            placement_outcome: PlacementOutcome::Placed,
+            placed_at: None,
+            placed_job_title: Some("Asistente administrativa".to_string()),
+            placed_company_name: Some("Test SpA".to_string()),
+            advisor_name: Some("Pedro Soto".to_string()),
+            branch: Some("Sucursal Centro".to_string()),
+            registered_at: Utc::now(),
+            is_active: true,
+            followup_count: 3,
+            last_followup_at: None,
+            applications_count: 2,
+            notes: None,
+        }
+    }
+
+    fn template(columns: &[&str], title: Option<&str>) -> OmilExportTemplate {
+        OmilExportTemplate {
+            id: Uuid::new_v4(),
+            omil_id: Uuid::new_v4(),
+            name: "Reporte mensual".to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            header_title: title.map(str::to_string),
+            include_logo: false,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_layout_follows_template_column_order() {
+        let template = template(
+            &[
+                "placed_company_name",
+                "national_id",
+                "full_name",
+                "followup_count",
+                "phone",
+            ],
+            Some("OMIL Maipú - Marzo"),
+        );
+        let layout = build_layout(&template, &[row()], true);
+
+        assert_eq!(layout.title.as_deref(), Some("OMIL Maipú - Marzo"));
+        assert_eq!(
+            layout.headers,
+            vec!["Placed Company", "RUT", "Name", "Followups", "Phone"]
+        );
+        assert_eq!(
+            layout.rows[0],
+            vec![
+                ExportCell::Text("Test SpA".to_string()),
+                ExportCell::Text("12345678-5".to_string()),
+                ExportCell::Text("María González".to_string()),
+                ExportCell::Number(3.0),
+                ExportCell::Empty,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_skips_retired_columns() {
+        let layout = build_layout(&template(&["email", "retired_key"], None), &[row()], true);
+        assert_eq!(layout.headers, vec!["Email"]);
+        assert_eq!(layout.rows[0].len(), 1);
+    }
+
+    #[test]
+    fn test_layout_without_contact_drops_contact_columns() {
+        let template = template(&["full_name", "email", "phone", "national_id"], None);
+        let layout = build_layout(&template, &[row()], false);
+        assert_eq!(layout.headers, vec!["Name", "RUT"]);
+        assert_eq!(layout.rows[0].len(), 2);
+    }
+
+    #[test]
+    fn test_render_with_and_without_logo() {
+        let layout = build_layout(&template(&["full_name"], Some("Reporte")), &[row()], true);
+        let watermark = Watermark {
+            export_id: Uuid::new_v4(),
+            exported_by_name: "Pedro Soto".to_string(),
+            organization_name: "OMIL Maipú".to_string(),
+            exported_at: Utc::now(),
+        };
+
+        assert!(!render(&layout, None, &watermark).unwrap().is_empty());
+        assert!(!render(&layout, Some(&PIXEL_PNG), &watermark)
+            .unwrap()
+            .is_empty());
+        // Unreadable logos are skipped
+        assert!(!render(&layout, Some(b"not an image"), &watermark)
+            .unwrap()
+            .is_empty());
+    }
+}
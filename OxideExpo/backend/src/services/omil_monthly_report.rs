@@ -0,0 +1,365 @@
+use rust_xlsxwriter::{Format, Workbook};
+use sqlx::PgPool;
+
+use crate::error::{AppError, Result};
+use crate::models::omil::{MonthlyReportCounter, OmilMonthlyReport, OmilOrganization};
+use crate::services::omil_stats;
+
+/// Sheet name of the official SENCE template
+pub const SHEET_NAME: &str = "Informe Mensual OMIL";
+
+/// Extra sheet with the per-branch breakdown, for OMILs with branches
+pub const BRANCHES_SHEET_NAME: &str = "Por sucursal";
+
+const BRANCH_HEADERS: [&str; 4] = ["Sucursal", "Activos", "Colocados", "Postulaciones"];
+
+/// Column headers of the official template, after the indicator column
+const COUNTER_HEADERS: [&str; 7] = [
+    "Hombres",
+    "Mujeres",
+    "No binario",
+    "Sin información de género",
+    "Con discapacidad",
+    "Sin discapacidad",
+    "Total",
+];
+
+// ============================================================================
+// DATA
+// ============================================================================
+
+fn validate_period(year: i32, month: i32) -> Result<()> {
+    if !(2000..=2100).contains(&year) {
+        return Err(AppError::ValidationError(
+            "Year must be between 2000 and 2100".to_string(),
+        ));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(AppError::ValidationError(
+            "Month must be between 1 and 12".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Computes the report for one calendar month in `timezone`. A seeker
+/// registered and placed in the same month counts in both counters, and the
+/// active total is as of the end of the month.
+/// Interventions are followups whose type has `counts_as_intervention` set.
+/// The per-branch breakdown covers the same month.
+pub async fn build(
+    db: &PgPool,
+    omil: &OmilOrganization,
+    year: i32,
+    month: i32,
+    timezone: &str,
+) -> Result<OmilMonthlyReport> {
+    validate_period(year, month)?;
+
+    let rows = sqlx::query!(
+        r#"
+        WITH bounds AS (
+            SELECT make_date($2, $3, 1)::timestamp AT TIME ZONE $4 AS starts_at,
+                   (make_date($2, $3, 1) + INTERVAL '1 month') AT TIME ZONE $4 AS ends_at
+        ),
+        events AS (
+            SELECT 'new_registrations' AS counter, mjs.job_seeker_id
+            FROM omil_managed_job_seekers mjs, bounds b
+            WHERE mjs.omil_id = $1
+            AND mjs.registered_at >= b.starts_at AND mjs.registered_at < b.ends_at
+
+            UNION ALL
+
+            SELECT 'total_active', mjs.job_seeker_id
+            FROM omil_managed_job_seekers mjs, bounds b
+            WHERE mjs.omil_id = $1
+            AND mjs.registered_at < b.ends_at
+            -- Not yet deactivated or transferred out when the month ended
+            AND COALESCE(LEAST(mjs.deactivated_at, mjs.transferred_at) >= b.ends_at, true)
+            -- Nor transferred in afterwards, keeping the source's registration date
+            AND NOT EXISTS (
+                SELECT 1 FROM omil_transfers t
+                WHERE t.target_omil_id = mjs.omil_id
+                AND t.job_seeker_id = mjs.job_seeker_id
+                AND t.status = 'accepted'
+                AND t.responded_at >= b.ends_at
+            )
+
+            UNION ALL
+
+            SELECT 'intermediated', ja.applicant_id
+            FROM omil_applications oa
+            JOIN job_applications ja ON ja.id = oa.application_id, bounds b
+            WHERE oa.omil_id = $1
+            AND oa.created_at >= b.starts_at AND oa.created_at < b.ends_at
+
+            UNION ALL
+
+            SELECT 'placed', mjs.job_seeker_id
+            FROM omil_managed_job_seekers mjs, bounds b
+            WHERE mjs.omil_id = $1
+            AND mjs.placement_outcome = 'placed'
+            AND mjs.placed_at >= b.starts_at AND mjs.placed_at < b.ends_at
+
+            UNION ALL
+
+            SELECT 'interventions', f.job_seeker_id
+            FROM job_seeker_followups f
+            JOIN followup_types t ON t.code = f.followup_type, bounds b
+            WHERE f.omil_id = $1
+            AND t.counts_as_intervention
+            AND f.followup_date >= b.starts_at AND f.followup_date < b.ends_at
+        )
+        SELECT
+            e.counter as "counter!",
+            COUNT(*) as "total!",
+            COUNT(*) FILTER (WHERE p.gender = 'male') as "male!",
+            COUNT(*) FILTER (WHERE p.gender = 'female') as "female!",
+            COUNT(*) FILTER (WHERE p.gender = 'non_binary') as "non_binary!",
+            COUNT(*) FILTER (WHERE p.gender IS NULL OR p.gender = 'prefer_not_to_say') as "gender_not_specified!",
+            COUNT(d.user_id) as "with_disability!",
+            COUNT(*) FILTER (WHERE d.user_id IS NULL) as "without_disability!"
+        FROM events e
+        LEFT JOIN job_seeker_profiles p ON p.user_id = e.job_seeker_id
+        LEFT JOIN job_seeker_disabilities d ON d.user_id = e.job_seeker_id
+        GROUP BY e.counter
+        "#,
+        omil.id,
+        year,
+        month,
+        timezone
+    )
+    .fetch_all(db)
+    .await?;
+
+    let bounds = sqlx::query!(
+        r#"
+        SELECT make_date($1, $2, 1)::timestamp AT TIME ZONE $3 AS "starts_at!",
+               (make_date($1, $2, 1) + INTERVAL '1 month') AT TIME ZONE $3 AS "ends_at!"
+        "#,
+        year,
+        month,
+        timezone
+    )
+    .fetch_one(db)
+    .await?;
+    let branches =
+        omil_stats::branch_breakdown(db, omil.id, bounds.starts_at, bounds.ends_at).await?;
+
+    let mut report = OmilMonthlyReport {
+        omil_id: omil.id,
+        organization_name: omil.organization_name.clone(),
+        year,
+        month,
+        timezone: timezone.to_string(),
+        new_registrations: MonthlyReportCounter::default(),
+        total_active: MonthlyReportCounter::default(),
+        intermediated: MonthlyReportCounter::default(),
+        placed: MonthlyReportCounter::default(),
+        interventions: MonthlyReportCounter::default(),
+        branches,
+    };
+
+    for row in rows {
+        let counter = MonthlyReportCounter {
+            total: row.total,
+            male: row.male,
+            female: row.female,
+            non_binary: row.non_binary,
+            gender_not_specified: row.gender_not_specified,
+            with_disability: row.with_disability,
+            without_disability: row.without_disability,
+        };
+        match row.counter.as_str() {
+            "new_registrations" => report.new_registrations = counter,
+            "total_active" => report.total_active = counter,
+            "intermediated" => report.intermediated = counter,
+            "placed" => report.placed = counter,
+            "interventions" => report.interventions = counter,
+            _ => {}
+        }
+    }
+
+    Ok(report)
+}
+
+// ============================================================================
+// LAYOUT
+// ============================================================================
+
+fn counter_values(counter: &MonthlyReportCounter) -> [i64; 7] {
+    [
+        counter.male,
+        counter.female,
+        counter.non_binary,
+        counter.gender_not_specified,
+        counter.with_disability,
+        counter.without_disability,
+        counter.total,
+    ]
+}
+
+/// Indicator rows in template order, followed by the totals row
+pub fn rows(report: &OmilMonthlyReport) -> Vec<(&'static str, [i64; 7])> {
+    let mut rows = vec![
+        (
+            "Nuevas inscripciones",
+            counter_values(&report.new_registrations),
+        ),
+        ("Total activos", counter_values(&report.total_active)),
+        (
+            "Intermediados (postulaciones)",
+            counter_values(&report.intermediated),
+        ),
+        ("Colocados", counter_values(&report.placed)),
+        ("Intervenciones", counter_values(&report.interventions)),
+    ];
+
+    let mut totals = [0i64; 7];
+    for (_, values) in &rows {
+        for (total, value) in totals.iter_mut().zip(values) {
+            *total += value;
+        }
+    }
+    rows.push(("Total", totals));
+
+    rows
+}
+
+// ============================================================================
+// RENDERING
+// ============================================================================
+
+/// Writes the report in the layout of the official template: organization and
+/// period on the first two rows, then the header row, one row per indicator
+/// and the totals row
+pub fn render(report: &OmilMonthlyReport) -> Result<Vec<u8>> {
+    let xlsx_err =
+        |e: rust_xlsxwriter::XlsxError| AppError::InternalError(format!("Excel error: {}", e));
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(SHEET_NAME).map_err(xlsx_err)?;
+
+    let bold = Format::new().set_bold();
+
+    worksheet
+        .write_string_with_format(0, 0, "Oficina Municipal de Información Laboral", &bold)
+        .map_err(xlsx_err)?;
+    worksheet
+        .write_string(0, 1, &report.organization_name)
+        .map_err(xlsx_err)?;
+    worksheet
+        .write_string_with_format(1, 0, "Período", &bold)
+        .map_err(xlsx_err)?;
+    worksheet
+        .write_string(1, 1, format!("{:02}/{}", report.month, report.year))
+        .map_err(xlsx_err)?;
+
+    let header_row = 3;
+    worksheet
+        .write_string_with_format(header_row, 0, "Indicador", &bold)
+        .map_err(xlsx_err)?;
+    for (col, header) in COUNTER_HEADERS.iter().enumerate() {
+        worksheet
+            .write_string_with_format(header_row, col as u16 + 1, *header, &bold)
+            .map_err(xlsx_err)?;
+    }
+
+    let rows = rows(report);
+    let last = rows.len() - 1;
+    for (i, (label, values)) in rows.iter().enumerate() {
+        let row = header_row + 1 + i as u32;
+        let format = if i == last {
+            bold.clone()
+        } else {
+            Format::new()
+        };
+        worksheet
+            .write_string_with_format(row, 0, *label, &format)
+            .map_err(xlsx_err)?;
+        for (col, value) in values.iter().enumerate() {
+            worksheet
+                .write_number_with_format(row, col as u16 + 1, *value as f64, &format)
+                .map_err(xlsx_err)?;
+        }
+    }
+
+    worksheet.set_column_width(0, 32).map_err(xlsx_err)?;
+
+    // The unassigned bucket alone means the OMIL has no branches
+    if report.branches.iter().any(|b| b.branch_id.is_some()) {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(BRANCHES_SHEET_NAME).map_err(xlsx_err)?;
+        for (col, header) in BRANCH_HEADERS.iter().enumerate() {
+            sheet
+                .write_string_with_format(0, col as u16, *header, &bold)
+                .map_err(xlsx_err)?;
+        }
+        for (i, branch) in report.branches.iter().enumerate() {
+            let row = i as u32 + 1;
+            let name = branch.branch_name.as_deref().unwrap_or("Sin sucursal");
+            sheet.write_string(row, 0, name).map_err(xlsx_err)?;
+            for (col, value) in [branch.seekers, branch.placements, branch.applications]
+                .iter()
+                .enumerate()
+            {
+                sheet
+                    .write_number(row, col as u16 + 1, *value as f64)
+                    .map_err(xlsx_err)?;
+            }
+        }
+        sheet.set_column_width(0, 32).map_err(xlsx_err)?;
+    }
+
+    workbook
+        .save_to_buffer()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate Excel: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn counter(male: i64, female: i64, with_disability: i64) -> MonthlyReportCounter {
+        MonthlyReportCounter {
+            total: male + female,
+            male,
+            female,
+            non_binary: 0,
+            gender_not_specified: 0,
+            with_disability,
+            without_disability: male + female - with_disability,
+        }
+    }
+
+    #[test]
+    fn test_totals_row_sums_each_column() {
+        let report = OmilMonthlyReport {
+            omil_id: Uuid::new_v4(),
+            organization_name: "OMIL Valparaíso".to_string(),
+            year: 2024,
+            month: 3,
+            timezone: "America/Santiago".to_string(),
+            new_registrations: counter(2, 1, 1),
+            total_active: counter(5, 4, 2),
+            intermediated: counter(0, 3, 0),
+            placed: counter(1, 0, 1),
+            interventions: counter(2, 2, 0),
+            branches: Vec::new(),
+        };
+
+        let rows = rows(&report);
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[5], ("Total", [10, 10, 0, 0, 4, 16, 20]));
+    }
+
+    #[test]
+    fn test_validate_period() {
+        assert!(validate_period(2024, 1).is_ok());
+        assert!(validate_period(2024, 13).is_err());
+        assert!(validate_period(2024, 0).is_err());
+        assert!(validate_period(1999, 6).is_err());
+    }
+}
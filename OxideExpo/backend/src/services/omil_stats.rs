@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::omil::{MonthlyCount, OmilBranchStats, OmilStatsTrends};
+
+pub const TRENDS_CACHE_TTL_SECONDS: u64 = 10 * 60;
+
+pub fn trends_cache_key(omil_id: Uuid, months: i32) -> String {
+    format!("omil:{}:stats_trends:{}", omil_id, months)
+}
+
+/// Monthly trends of an OMIL from Redis, computed and stored on a miss.
+/// Redis failures are logged and the trends computed directly.
+pub async fn trends(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    omil_id: Uuid,
+    months: i32,
+) -> Result<OmilStatsTrends> {
+    let key = trends_cache_key(omil_id, months);
+
+    match redis.get::<_, Option<String>>(&key).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(trends) => return Ok(trends),
+            Err(e) => tracing::warn!("Discarding unreadable OMIL trends cache {}: {}", key, e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read OMIL trends cache {}: {}", key, e),
+    }
+
+    let trends = compute_trends(db, omil_id, months).await?;
+
+    if let Ok(json) = serde_json::to_string(&trends) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(&key, json, TRENDS_CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache OMIL trends {}: {}", key, e);
+        }
+    }
+
+    Ok(trends)
+}
+
+/// The last `months` calendar months up to and including the current one,
+/// oldest first. Months without activity are counted as zero. Each series
+/// counts what the matching dashboard number counts for a single month.
+async fn compute_trends(db: &PgPool, omil_id: Uuid, months: i32) -> Result<OmilStatsTrends> {
+    let (registrations, placements, applications_submitted) = tokio::try_join!(
+        // Like new_registrations_this_month, seekers transferred away since
+        // count at the receiving OMIL
+        sqlx::query_as!(
+            MonthlyCount,
+            r#"
+            SELECT m.month::date AS "month!", COUNT(s.id) AS "count!"
+            FROM generate_series(
+                date_trunc('month', CURRENT_DATE) - make_interval(months => $2 - 1),
+                date_trunc('month', CURRENT_DATE),
+                INTERVAL '1 month'
+            ) AS m(month)
+            LEFT JOIN omil_managed_job_seekers s
+                ON s.omil_id = $1
+               AND s.transferred_at IS NULL
+               AND s.registered_at >= m.month
+               AND s.registered_at < m.month + INTERVAL '1 month'
+            GROUP BY m.month
+            ORDER BY m.month
+            "#,
+            omil_id,
+            months
+        )
+        .fetch_all(db),
+        sqlx::query_as!(
+            MonthlyCount,
+            r#"
+            SELECT m.month::date AS "month!", COUNT(s.id) AS "count!"
+            FROM generate_series(
+                date_trunc('month', CURRENT_DATE) - make_interval(months => $2 - 1),
+                date_trunc('month', CURRENT_DATE),
+                INTERVAL '1 month'
+            ) AS m(month)
+            LEFT JOIN omil_managed_job_seekers s
+                ON s.omil_id = $1
+               AND s.placement_outcome = 'placed'
+               AND s.placed_at >= m.month
+               AND s.placed_at < m.month + INTERVAL '1 month'
+            GROUP BY m.month
+            ORDER BY m.month
+            "#,
+            omil_id,
+            months
+        )
+        .fetch_all(db),
+        sqlx::query_as!(
+            MonthlyCount,
+            r#"
+            SELECT m.month::date AS "month!", COUNT(a.id) AS "count!"
+            FROM generate_series(
+                date_trunc('month', CURRENT_DATE) - make_interval(months => $2 - 1),
+                date_trunc('month', CURRENT_DATE),
+                INTERVAL '1 month'
+            ) AS m(month)
+            LEFT JOIN omil_applications a
+                ON a.omil_id = $1
+               AND a.created_at >= m.month
+               AND a.created_at < m.month + INTERVAL '1 month'
+            GROUP BY m.month
+            ORDER BY m.month
+            "#,
+            omil_id,
+            months
+        )
+        .fetch_all(db),
+    )?;
+
+    Ok(OmilStatsTrends {
+        months,
+        registrations,
+        placements,
+        applications_submitted,
+    })
+}
+
+/// Numbers per branch, branches by name and the unassigned bucket last.
+/// Seekers are those active at `ends_at`; placements and applications are
+/// those between `starts_at` and `ends_at`, applications counted under the
+/// seeker's current branch. Inactive branches are left out once they have
+/// nothing to count.
+pub async fn branch_breakdown(
+    db: &PgPool,
+    omil_id: Uuid,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> Result<Vec<OmilBranchStats>> {
+    let branches = sqlx::query_as!(
+        OmilBranchStats,
+        r#"
+        WITH events AS (
+            SELECT mjs.branch_id, 'seeker' AS kind
+            FROM omil_managed_job_seekers mjs
+            WHERE mjs.omil_id = $1
+            AND mjs.is_active = true
+            AND mjs.transferred_at IS NULL
+            AND mjs.registered_at < $3
+
+            UNION ALL
+
+            SELECT mjs.branch_id, 'placement'
+            FROM omil_managed_job_seekers mjs
+            WHERE mjs.omil_id = $1
+            AND mjs.placement_outcome = 'placed'
+            AND mjs.placed_at >= $2 AND mjs.placed_at < $3
+
+            UNION ALL
+
+            SELECT mjs.branch_id, 'application'
+            FROM omil_applications oa
+            JOIN job_applications ja ON ja.id = oa.application_id
+            LEFT JOIN omil_managed_job_seekers mjs
+                ON mjs.omil_id = oa.omil_id AND mjs.job_seeker_id = ja.applicant_id
+            WHERE oa.omil_id = $1
+            AND oa.created_at >= $2 AND oa.created_at < $3
+        ),
+        buckets AS (
+            SELECT id, name, is_active FROM omil_branches WHERE omil_id = $1
+            UNION ALL
+            SELECT NULL, NULL, true
+        )
+        SELECT
+            b.id as "branch_id?",
+            b.name as "branch_name?",
+            COUNT(*) FILTER (WHERE e.kind = 'seeker') as "seekers!",
+            COUNT(*) FILTER (WHERE e.kind = 'placement') as "placements!",
+            COUNT(*) FILTER (WHERE e.kind = 'application') as "applications!"
+        FROM buckets b
+        LEFT JOIN events e ON e.branch_id IS NOT DISTINCT FROM b.id
+        GROUP BY b.id, b.name, b.is_active
+        HAVING b.is_active OR COUNT(e.kind) > 0
+        ORDER BY b.id IS NULL, b.name
+        "#,
+        omil_id,
+        starts_at,
+        ends_at
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(branches)
+}
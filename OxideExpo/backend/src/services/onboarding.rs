@@ -0,0 +1,191 @@
+//! Job seeker onboarding checklist. Step completion is computed from the
+//! profile on every read; the first read that finds every required step done
+//! stamps the onboarding as completed and sends the "profile ready" email.
+
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::notification::{NotificationChannel, NotificationEvent};
+use crate::models::onboarding::{OnboardingChecklist, OnboardingFacts, OnboardingStep};
+use crate::services::notifications;
+use crate::AppState;
+
+/// Loads the profile data the checklist steps are checked against
+pub async fn load_facts(db: &sqlx::PgPool, user_id: Uuid) -> Result<OnboardingFacts> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            u.email_verified_at IS NOT NULL as "email_verified!",
+            COALESCE(
+                p.phone IS NOT NULL
+                    AND p.date_of_birth IS NOT NULL
+                    AND p.region_id IS NOT NULL
+                    AND p.municipality_id IS NOT NULL,
+                false
+            ) as "has_basic_info!",
+            (SELECT COUNT(*) FROM work_experiences we WHERE we.user_id = u.id) as "experience_count!",
+            (SELECT COUNT(*) FROM user_skills us WHERE us.user_id = u.id) as "skill_count!",
+            p.cv_url IS NOT NULL as "has_cv!",
+            EXISTS(
+                SELECT 1 FROM job_seeker_preferences jp
+                WHERE jp.user_id = u.id
+                  AND (
+                      COALESCE(cardinality(jp.preferred_work_modalities), 0) > 0
+                      OR COALESCE(cardinality(jp.preferred_job_types), 0) > 0
+                      OR COALESCE(cardinality(jp.preferred_region_ids), 0) > 0
+                      OR jp.salary_expectation_min IS NOT NULL
+                  )
+            ) as "has_preferences!"
+        FROM users u
+        LEFT JOIN job_seeker_profiles p ON p.user_id = u.id
+        WHERE u.id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    Ok(OnboardingFacts {
+        email_verified: row.email_verified,
+        has_basic_info: row.has_basic_info,
+        experience_count: row.experience_count,
+        skill_count: row.skill_count,
+        has_cv: row.has_cv,
+        has_preferences: row.has_preferences,
+    })
+}
+
+/// The seeker's checklist. Completes the onboarding the first time every
+/// required step is found done.
+pub async fn checklist(state: &AppState, user_id: Uuid) -> Result<OnboardingChecklist> {
+    let facts = load_facts(&state.db, user_id).await?;
+    let saved = sqlx::query!(
+        "SELECT dismissed_steps, completed_at FROM user_onboarding_state WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    let (dismissed, completed_at) = saved
+        .map(|s| (s.dismissed_steps, s.completed_at))
+        .unwrap_or_default();
+
+    let mut checklist = OnboardingChecklist::build(&facts, &dismissed, completed_at);
+    if checklist.completed_at.is_none() && checklist.required_steps_done() {
+        checklist.completed_at = complete(state, user_id).await?;
+    }
+
+    Ok(checklist)
+}
+
+/// Stamps the onboarding as completed and, only for the request that did so,
+/// emails the seeker that their profile is ready
+async fn complete(
+    state: &AppState,
+    user_id: Uuid,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    let stamped = sqlx::query_scalar!(
+        r#"
+        INSERT INTO user_onboarding_state (user_id, completed_at)
+        VALUES ($1, NOW())
+        ON CONFLICT (user_id) DO UPDATE SET completed_at = NOW()
+        WHERE user_onboarding_state.completed_at IS NULL
+        RETURNING completed_at as "completed_at!"
+        "#,
+        user_id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some(completed_at) = stamped else {
+        // Completed concurrently by another request
+        let completed_at = sqlx::query_scalar!(
+            "SELECT completed_at FROM user_onboarding_state WHERE user_id = $1",
+            user_id
+        )
+        .fetch_one(&state.db)
+        .await?;
+        return Ok(completed_at);
+    };
+
+    let recipient = sqlx::query!(
+        r#"SELECT email as "email?", first_name FROM users WHERE id = $1"#,
+        user_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if let Some(to) = recipient.email {
+        notifications::email(
+            state,
+            user_id,
+            NotificationEvent::Digest,
+            move |email| async move {
+                email
+                    .send_profile_ready_email(&to, &recipient.first_name)
+                    .await
+            },
+        );
+    }
+
+    Ok(Some(completed_at))
+}
+
+/// Skips an optional step. Dismissing a step twice is a no-op.
+pub async fn dismiss(
+    state: &AppState,
+    user_id: Uuid,
+    step: OnboardingStep,
+) -> Result<OnboardingChecklist> {
+    if step.is_required() {
+        return Err(AppError::ValidationError(format!(
+            "The {} step is required and can't be dismissed",
+            step.code()
+        )));
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO user_onboarding_state (user_id, dismissed_steps)
+        VALUES ($1, ARRAY[$2::varchar])
+        ON CONFLICT (user_id) DO UPDATE
+        SET dismissed_steps = array_append(user_onboarding_state.dismissed_steps, $2::varchar)
+        WHERE NOT ($2::varchar = ANY(user_onboarding_state.dismissed_steps))
+        "#,
+        user_id,
+        step.code()
+    )
+    .execute(&state.db)
+    .await?;
+
+    checklist(state, user_id).await
+}
+
+/// Job seekers the recommendation digest goes to: onboarding completed,
+/// active account with an email, job alerts not turned off, and digest
+/// emails wanted
+pub async fn digest_recipients(db: &sqlx::PgPool) -> Result<Vec<Uuid>> {
+    let recipients = sqlx::query_scalar!(
+        r#"
+        SELECT u.id
+        FROM user_onboarding_state os
+        JOIN users u ON u.id = os.user_id
+        LEFT JOIN job_seeker_preferences jp ON jp.user_id = u.id
+        WHERE os.completed_at IS NOT NULL
+          AND u.user_type = 'job_seeker'
+          AND u.account_status = 'active'
+          AND u.email IS NOT NULL
+          AND COALESCE(jp.email_job_alerts, true)
+        ORDER BY os.completed_at
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    notifications::filter_recipients(
+        db,
+        &recipients,
+        NotificationEvent::Digest,
+        NotificationChannel::Email,
+    )
+    .await
+}
@@ -0,0 +1,196 @@
+//! Profile fingerprints taken when a seeker applies. A fingerprint keeps the
+//! entry count and latest edit of each profile section plus completeness,
+//! which is enough to tell a recruiter which sections moved since the
+//! application without keeping a copy of the profile.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::applicant::ProfileChanges;
+
+/// Entries in one profile section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionFingerprint {
+    pub count: i32,
+    /// Latest created_at or updated_at of its entries
+    pub last_updated_at: Option<DateTime<Utc>>,
+}
+
+/// As stored in job_applications.profile_fingerprint
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileFingerprint {
+    pub experience: SectionFingerprint,
+    pub education: SectionFingerprint,
+    pub skills: SectionFingerprint,
+    pub completeness: i32,
+}
+
+impl ProfileFingerprint {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// None for a missing or unreadable stored fingerprint
+    pub fn from_json(value: Option<serde_json::Value>) -> Option<Self> {
+        value.and_then(|value| serde_json::from_value(value).ok())
+    }
+}
+
+/// Added and removed entries, and whether any entry was added or edited
+fn section_changes(then: &SectionFingerprint, now: &SectionFingerprint) -> (i32, i32, bool) {
+    let added = (now.count - then.count).max(0);
+    let removed = (then.count - now.count).max(0);
+    let modified = match (then.last_updated_at, now.last_updated_at) {
+        (Some(then), Some(now)) => now > then,
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+    (added, removed, modified)
+}
+
+/// Compares the fingerprint taken at apply time with the current one
+pub fn compare(then: &ProfileFingerprint, now: &ProfileFingerprint) -> ProfileChanges {
+    let (experience_added, experience_removed, experience_modified) =
+        section_changes(&then.experience, &now.experience);
+    let (education_added, education_removed, education_modified) =
+        section_changes(&then.education, &now.education);
+    let (skills_added, skills_removed, skills_modified) =
+        section_changes(&then.skills, &now.skills);
+
+    ProfileChanges {
+        experience_added,
+        experience_removed,
+        experience_modified,
+        education_added,
+        education_removed,
+        education_modified,
+        skills_added,
+        skills_removed,
+        skills_modified,
+        completeness_change: now.completeness - then.completeness,
+    }
+}
+
+/// Fingerprint of the seeker's profile as it is now
+pub async fn take(conn: &mut PgConnection, user_id: Uuid) -> Result<ProfileFingerprint> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM work_experiences WHERE user_id = $1) as "experience_count!",
+            (SELECT MAX(GREATEST(created_at, updated_at)) FROM work_experiences WHERE user_id = $1) as experience_updated_at,
+            (SELECT COUNT(*) FROM education_records WHERE user_id = $1) as "education_count!",
+            (SELECT MAX(GREATEST(created_at, updated_at)) FROM education_records WHERE user_id = $1) as education_updated_at,
+            (SELECT COUNT(*) FROM user_skills WHERE user_id = $1) as "skills_count!",
+            (SELECT MAX(GREATEST(created_at, updated_at)) FROM user_skills WHERE user_id = $1) as skills_updated_at,
+            (SELECT completeness_percentage FROM job_seeker_profiles WHERE user_id = $1) as completeness
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(ProfileFingerprint {
+        experience: SectionFingerprint {
+            count: row.experience_count as i32,
+            last_updated_at: row.experience_updated_at,
+        },
+        education: SectionFingerprint {
+            count: row.education_count as i32,
+            last_updated_at: row.education_updated_at,
+        },
+        skills: SectionFingerprint {
+            count: row.skills_count as i32,
+            last_updated_at: row.skills_updated_at,
+        },
+        completeness: row.completeness.unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn at(minutes: i64) -> Option<DateTime<Utc>> {
+        Some(Utc.with_ymd_and_hms(2024, 5, 2, 10, 0, 0).unwrap() + Duration::minutes(minutes))
+    }
+
+    fn fingerprint() -> ProfileFingerprint {
+        ProfileFingerprint {
+            experience: SectionFingerprint {
+                count: 2,
+                last_updated_at: at(0),
+            },
+            education: SectionFingerprint {
+                count: 1,
+                last_updated_at: at(0),
+            },
+            skills: SectionFingerprint {
+                count: 4,
+                last_updated_at: at(0),
+            },
+            completeness: 70,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_profile_has_no_changes() {
+        let changes = compare(&fingerprint(), &fingerprint());
+        assert_eq!(changes, ProfileChanges::default());
+        assert!(!changes.any());
+    }
+
+    #[test]
+    fn test_added_entries_are_counted_and_mark_the_section() {
+        let mut now = fingerprint();
+        now.skills = SectionFingerprint {
+            count: 7,
+            last_updated_at: at(5),
+        };
+
+        let changes = compare(&fingerprint(), &now);
+        assert_eq!(changes.skills_added, 3);
+        assert!(changes.skills_modified);
+        assert!(!changes.experience_modified);
+        assert!(changes.any());
+    }
+
+    #[test]
+    fn test_edit_without_count_change_is_a_modification() {
+        let mut now = fingerprint();
+        now.experience.last_updated_at = at(30);
+        now.completeness = 85;
+
+        let changes = compare(&fingerprint(), &now);
+        assert_eq!(changes.experience_added, 0);
+        assert!(changes.experience_modified);
+        assert_eq!(changes.completeness_change, 15);
+    }
+
+    #[test]
+    fn test_removed_entries() {
+        let mut now = fingerprint();
+        now.education = SectionFingerprint::default();
+
+        let changes = compare(&fingerprint(), &now);
+        assert_eq!(changes.education_removed, 1);
+        assert!(!changes.education_modified);
+    }
+
+    #[test]
+    fn test_fingerprint_round_trips_through_json() {
+        let stored = fingerprint().to_json();
+        assert_eq!(
+            ProfileFingerprint::from_json(Some(stored)),
+            Some(fingerprint())
+        );
+        assert_eq!(ProfileFingerprint::from_json(None), None);
+        assert_eq!(
+            ProfileFingerprint::from_json(Some(serde_json::json!("x"))),
+            None
+        );
+    }
+}
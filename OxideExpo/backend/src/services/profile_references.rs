@@ -0,0 +1,223 @@
+//! Referees and recommendation letters on job seeker profiles.
+//!
+//! Companies see the references of their applicants without the referees'
+//! contact emails, and only while the seeker shows them; the OMIL managing
+//! the seeker sees everything, including who verified each reference.
+
+use std::time::Duration;
+
+use axum::extract::Multipart;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::applicant::ApplicantReference;
+use crate::models::omil::ManagedProfileReference;
+use crate::models::profile::{
+    reference_letters_folder, ProfileReference, MAX_REFERENCE_LETTER_BYTES,
+    REFERENCE_LETTER_URL_MINUTES,
+};
+use crate::services::storage::StorageBackend;
+use crate::AppState;
+
+/// The seeker's reference, or 404 when it's someone else's
+pub async fn find(db: &PgPool, user_id: Uuid, reference_id: Uuid) -> Result<ProfileReference> {
+    sqlx::query_as!(
+        ProfileReference,
+        r#"
+        SELECT id, user_id, referee_name, referee_organization, relationship, contact_email,
+               letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+               created_at, updated_at
+        FROM profile_references
+        WHERE id = $1 AND user_id = $2
+        "#,
+        reference_id,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Reference not found".to_string()))
+}
+
+// ============================================================================
+// LETTERS
+// ============================================================================
+
+/// Stores the PDF in the `file` field of an upload as the reference's letter.
+/// A letter it replaces is queued for deletion from storage.
+pub async fn attach_letter(
+    state: &AppState,
+    reference: &ProfileReference,
+    multipart: &mut Multipart,
+) -> Result<ProfileReference> {
+    let mut file = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::ValidationError(format!("Failed to read upload: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field
+            .file_name()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "carta.pdf".to_string());
+        if field.content_type() != Some("application/pdf") {
+            return Err(AppError::ValidationError(
+                "Invalid file type. Letters must be PDF files".to_string(),
+            ));
+        }
+
+        let data = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::ValidationError(format!("Failed to read file: {}", e)))?;
+        if data.len() > MAX_REFERENCE_LETTER_BYTES {
+            return Err(AppError::ValidationError(format!(
+                "File too large. Maximum size: {} MB",
+                MAX_REFERENCE_LETTER_BYTES / 1024 / 1024
+            )));
+        }
+
+        file = Some((filename, data));
+    }
+
+    let (filename, data) =
+        file.ok_or_else(|| AppError::ValidationError("No file provided".to_string()))?;
+
+    let stored = state
+        .storage
+        .upload(
+            &reference_letters_folder(reference.user_id),
+            &filename,
+            "application/pdf",
+            data,
+        )
+        .await?;
+
+    let reference = sqlx::query_as!(
+        ProfileReference,
+        r#"
+        UPDATE profile_references
+        SET letter_file_key = $2, letter_filename = $3
+        WHERE id = $1
+        RETURNING id, user_id, referee_name, referee_organization, relationship, contact_email,
+                  letter_file_key, letter_filename, is_verified, verified_by, verified_at,
+                  created_at, updated_at
+        "#,
+        reference.id,
+        stored.storage_path,
+        filename.chars().take(255).collect::<String>()
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(reference)
+}
+
+/// Short-lived download link of a letter, when there is one
+async fn letter_url(
+    storage: &dyn StorageBackend,
+    file_key: Option<&str>,
+) -> Result<Option<String>> {
+    let Some(file_key) = file_key else {
+        return Ok(None);
+    };
+    let expires_in = Duration::from_secs(REFERENCE_LETTER_URL_MINUTES * 60);
+
+    Ok(Some(storage.presign_get(file_key, expires_in).await?))
+}
+
+// ============================================================================
+// VIEWS
+// ============================================================================
+
+/// The applicant's references as companies see them; empty when the seeker
+/// hides them
+pub async fn for_company(
+    db: &PgPool,
+    storage: &dyn StorageBackend,
+    user_id: Uuid,
+) -> Result<Vec<ApplicantReference>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.id, r.referee_name, r.referee_organization, r.relationship,
+               r.letter_file_key, r.is_verified, r.verified_at
+        FROM profile_references r
+        LEFT JOIN job_seeker_preferences p ON p.user_id = r.user_id
+        WHERE r.user_id = $1 AND COALESCE(p.show_references, true)
+        ORDER BY r.is_verified DESC, r.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut references = Vec::with_capacity(rows.len());
+    for row in rows {
+        references.push(ApplicantReference {
+            id: row.id,
+            referee_name: row.referee_name,
+            referee_organization: row.referee_organization,
+            relationship: row.relationship,
+            letter_download_url: letter_url(storage, row.letter_file_key.as_deref()).await?,
+            is_verified: row.is_verified,
+            verified_at: row.verified_at,
+        });
+    }
+
+    Ok(references)
+}
+
+/// The seeker's references as their OMIL sees them, whatever the seeker
+/// shows companies
+pub async fn for_omil(
+    db: &PgPool,
+    storage: &dyn StorageBackend,
+    user_id: Uuid,
+) -> Result<Vec<ManagedProfileReference>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            r.id, r.user_id, r.referee_name, r.referee_organization, r.relationship,
+            r.contact_email, r.letter_file_key, r.letter_filename, r.is_verified,
+            r.verified_by, r.verified_at, r.created_at, r.updated_at,
+            v.first_name || ' ' || v.last_name as verified_by_name
+        FROM profile_references r
+        LEFT JOIN users v ON v.id = r.verified_by
+        WHERE r.user_id = $1
+        ORDER BY r.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut references = Vec::with_capacity(rows.len());
+    for row in rows {
+        let letter_download_url = letter_url(storage, row.letter_file_key.as_deref()).await?;
+        references.push(ManagedProfileReference {
+            reference: ProfileReference {
+                id: row.id,
+                user_id: row.user_id,
+                referee_name: row.referee_name,
+                referee_organization: row.referee_organization,
+                relationship: row.relationship,
+                contact_email: row.contact_email,
+                letter_file_key: row.letter_file_key,
+                letter_filename: row.letter_filename,
+                is_verified: row.is_verified,
+                verified_by: row.verified_by,
+                verified_at: row.verified_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            verified_by_name: row.verified_by_name,
+            letter_download_url,
+        });
+    }
+
+    Ok(references)
+}
@@ -0,0 +1,123 @@
+use chrono::Utc;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use sqlx::PgPool;
+
+use crate::error::Result;
+use crate::models::admin::reportable;
+use crate::models::stats::PublicPlatformStats;
+
+/// Also sent as the response's max-age, so browsers and CDNs agree with Redis
+pub const CACHE_TTL_SECONDS: u64 = 15 * 60;
+
+pub const CACHE_KEY: &str = "stats:public";
+
+/// Rounds half up to the nearest 10 (1235 -> 1240, 1234 -> 1230)
+pub fn round_to_nearest_ten(count: i64) -> i64 {
+    (count + 5) / 10 * 10
+}
+
+/// Public statistics from Redis, computed and stored on a miss.
+/// Redis failures are logged and the statistics computed directly.
+pub async fn public_stats(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+) -> Result<PublicPlatformStats> {
+    match redis.get::<_, Option<String>>(CACHE_KEY).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(stats) => return Ok(stats),
+            Err(e) => tracing::warn!("Discarding unreadable public stats cache: {}", e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read public stats cache: {}", e),
+    }
+
+    let stats = compute_public_stats(db).await?;
+
+    if let Ok(json) = serde_json::to_string(&stats) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(CACHE_KEY, json, CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache public stats: {}", e);
+        }
+    }
+
+    Ok(stats)
+}
+
+async fn compute_public_stats(db: &PgPool) -> Result<PublicPlatformStats> {
+    // A person hired through the platform who is also followed by an OMIL
+    // is one placement, not two
+    let row = sqlx::query!(
+        r#"
+        WITH placed AS (
+            SELECT job_seeker_id AS user_id
+            FROM omil_managed_job_seekers
+            WHERE placement_outcome = 'placed'
+              AND placed_at >= date_trunc('year', NOW())
+            UNION
+            SELECT ja.applicant_id
+            FROM job_applications ja
+            WHERE ja.status = 'hired'
+              AND COALESCE(
+                  (
+                      SELECT MAX(h.created_at)
+                      FROM application_status_history h
+                      WHERE h.application_id = ja.id AND h.new_status = 'hired'
+                  ),
+                  ja.updated_at
+              ) >= date_trunc('year', NOW())
+        )
+        SELECT
+            (SELECT COUNT(*) FROM jobs WHERE status = 'active') AS "active_jobs!",
+            (SELECT COUNT(*) FROM company_profiles WHERE status = 'active') AS "active_companies!",
+            (
+                SELECT COUNT(*) FROM users
+                WHERE user_type = 'job_seeker'
+                  AND account_status IN ('pending_verification', 'active', 'managed_offline')
+            ) AS "registered_job_seekers!",
+            (SELECT COUNT(*) FROM placed) AS "placements_this_year!",
+            (
+                SELECT COUNT(*) FROM jobs j
+                WHERE j.status = 'active'
+                  AND EXISTS (
+                      SELECT 1 FROM job_disability_accommodations a WHERE a.job_id = j.id
+                  )
+            ) AS "jobs_with_accommodations!"
+        "#
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(PublicPlatformStats {
+        active_jobs: reportable(row.active_jobs),
+        active_companies: reportable(row.active_companies),
+        registered_job_seekers: reportable(row.registered_job_seekers).map(round_to_nearest_ten),
+        placements_this_year: reportable(row.placements_this_year),
+        jobs_with_accommodations: reportable(row.jobs_with_accommodations),
+        generated_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::admin::MIN_REPORTABLE_CELL;
+
+    #[test]
+    fn test_round_to_nearest_ten() {
+        assert_eq!(round_to_nearest_ten(1234), 1230);
+        assert_eq!(round_to_nearest_ten(1235), 1240);
+        assert_eq!(round_to_nearest_ten(1240), 1240);
+        assert_eq!(round_to_nearest_ten(5), 10);
+    }
+
+    #[test]
+    fn test_counts_under_threshold_are_withheld() {
+        assert_eq!(reportable(0), None);
+        assert_eq!(reportable(MIN_REPORTABLE_CELL - 1), None);
+        assert_eq!(reportable(MIN_REPORTABLE_CELL), Some(MIN_REPORTABLE_CELL));
+        // Rounding applies only to what is published
+        assert_eq!(reportable(4).map(round_to_nearest_ten), None);
+        assert_eq!(reportable(6).map(round_to_nearest_ten), Some(10));
+    }
+}
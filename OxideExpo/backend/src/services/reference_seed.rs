@@ -0,0 +1,319 @@
+//! Seeding of reference data (skills, languages, industries, work areas and
+//! institutions) from a JSON document, for fresh deployments and staging
+//! refreshes. Rows are upserted by natural key and never deleted, so
+//! re-seeding the same document changes nothing.
+
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+use crate::models::reference::{
+    ReferenceSeed, ReferenceSeedReport, SeedCounts, SeedInstitution, SeedLanguage, SeedListItem,
+    SeedSkillCategory,
+};
+
+/// The canonical reference data every deployment starts from
+pub const CANONICAL_SEED: &str = include_str!("../../fixtures/reference_seed.json");
+
+/// Parses the canonical seed document
+pub fn canonical_seed() -> Result<ReferenceSeed> {
+    parse(CANONICAL_SEED)
+}
+
+/// Parses a seed document
+pub fn parse(document: &str) -> Result<ReferenceSeed> {
+    serde_json::from_str(document)
+        .map_err(|e| AppError::ValidationError(format!("Invalid reference seed document: {}", e)))
+}
+
+enum Outcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+impl SeedCounts {
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Created => self.created += 1,
+            Outcome::Updated => self.updated += 1,
+            Outcome::Unchanged => self.unchanged += 1,
+        }
+    }
+}
+
+/// Upsert outcome of an `INSERT ... ON CONFLICT DO UPDATE ... WHERE changed
+/// RETURNING (xmax = 0)`: no row when nothing changed, and xmax is 0 only
+/// for freshly inserted rows
+fn upsert_outcome(inserted: Option<bool>) -> Outcome {
+    match inserted {
+        Some(true) => Outcome::Created,
+        Some(false) => Outcome::Updated,
+        None => Outcome::Unchanged,
+    }
+}
+
+/// Creates or updates everything in the document. Run it in a transaction
+/// so a failing row leaves the data as it was.
+pub async fn seed(conn: &mut PgConnection, seed: &ReferenceSeed) -> Result<ReferenceSeedReport> {
+    let mut report = ReferenceSeedReport::default();
+
+    for category in &seed.skill_categories {
+        seed_skill_category(&mut *conn, category, &mut report).await?;
+    }
+    for language in &seed.languages {
+        report
+            .languages
+            .record(seed_language(&mut *conn, language).await?);
+    }
+    for industry in &seed.industries {
+        report
+            .industries
+            .record(seed_industry(&mut *conn, industry).await?);
+    }
+    for work_area in &seed.work_areas {
+        report
+            .work_areas
+            .record(seed_work_area(&mut *conn, work_area).await?);
+    }
+    for institution in &seed.institutions {
+        report
+            .institutions
+            .record(seed_institution(&mut *conn, institution).await?);
+    }
+
+    Ok(report)
+}
+
+async fn seed_skill_category(
+    conn: &mut PgConnection,
+    category: &SeedSkillCategory,
+    report: &mut ReferenceSeedReport,
+) -> Result<()> {
+    let name = category.name.trim();
+    let upserted = sqlx::query!(
+        r#"
+        INSERT INTO skill_categories (name, description, sort_order)
+        VALUES ($1, $2, COALESCE($3, 0))
+        ON CONFLICT (name) DO UPDATE
+        SET
+            description = COALESCE($2, skill_categories.description),
+            sort_order = COALESCE($3, skill_categories.sort_order)
+        WHERE (skill_categories.description, skill_categories.sort_order)
+            IS DISTINCT FROM (COALESCE($2, skill_categories.description), COALESCE($3, skill_categories.sort_order))
+        RETURNING id, (xmax = 0) as "inserted!"
+        "#,
+        name,
+        category.description,
+        category.sort_order,
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let category_id = match &upserted {
+        Some(row) => row.id,
+        None => {
+            sqlx::query_scalar!("SELECT id FROM skill_categories WHERE name = $1", name)
+                .fetch_one(&mut *conn)
+                .await?
+        }
+    };
+    report
+        .skill_categories
+        .record(upsert_outcome(upserted.map(|row| row.inserted)));
+
+    for skill in &category.skills {
+        let created = sqlx::query_scalar!(
+            r#"
+            INSERT INTO skills (category_id, name)
+            VALUES ($1, $2)
+            ON CONFLICT (category_id, name) DO NOTHING
+            RETURNING id
+            "#,
+            category_id,
+            skill.trim(),
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        report.skills.record(match created {
+            Some(_) => Outcome::Created,
+            None => Outcome::Unchanged,
+        });
+    }
+
+    Ok(())
+}
+
+async fn seed_language(conn: &mut PgConnection, language: &SeedLanguage) -> Result<Outcome> {
+    let iso_code = language.iso_code.as_deref().map(str::to_lowercase);
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO languages (name, iso_code)
+        VALUES ($1, $2)
+        ON CONFLICT (name) DO UPDATE
+        SET iso_code = COALESCE($2, languages.iso_code)
+        WHERE languages.iso_code IS DISTINCT FROM COALESCE($2, languages.iso_code)
+        RETURNING (xmax = 0) as "inserted!"
+        "#,
+        language.name.trim(),
+        iso_code as Option<String>,
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(upsert_outcome(inserted))
+}
+
+async fn seed_industry(conn: &mut PgConnection, industry: &SeedListItem) -> Result<Outcome> {
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO industries (name, description, sort_order)
+        VALUES ($1, $2, COALESCE($3, 0))
+        ON CONFLICT (name) DO UPDATE
+        SET
+            description = COALESCE($2, industries.description),
+            sort_order = COALESCE($3, industries.sort_order)
+        WHERE (industries.description, industries.sort_order)
+            IS DISTINCT FROM (COALESCE($2, industries.description), COALESCE($3, industries.sort_order))
+        RETURNING (xmax = 0) as "inserted!"
+        "#,
+        industry.name.trim(),
+        industry.description,
+        industry.sort_order,
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(upsert_outcome(inserted))
+}
+
+async fn seed_work_area(conn: &mut PgConnection, work_area: &SeedListItem) -> Result<Outcome> {
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO work_areas (name, description, sort_order)
+        VALUES ($1, $2, COALESCE($3, 0))
+        ON CONFLICT (name) DO UPDATE
+        SET
+            description = COALESCE($2, work_areas.description),
+            sort_order = COALESCE($3, work_areas.sort_order)
+        WHERE (work_areas.description, work_areas.sort_order)
+            IS DISTINCT FROM (COALESCE($2, work_areas.description), COALESCE($3, work_areas.sort_order))
+        RETURNING (xmax = 0) as "inserted!"
+        "#,
+        work_area.name.trim(),
+        work_area.description,
+        work_area.sort_order,
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(upsert_outcome(inserted))
+}
+
+/// Institutions have no unique constraint, so the match on name and country
+/// is done here
+async fn seed_institution(
+    conn: &mut PgConnection,
+    institution: &SeedInstitution,
+) -> Result<Outcome> {
+    let country_id: Option<Uuid> = match &institution.country_iso {
+        Some(iso) => Some(
+            sqlx::query_scalar!(
+                "SELECT id FROM countries WHERE iso_code = $1",
+                iso.to_uppercase()
+            )
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "Unknown country {} for institution {}",
+                    iso, institution.name
+                ))
+            })?,
+        ),
+        None => None,
+    };
+    let name = institution.name.trim();
+
+    let existing = sqlx::query!(
+        r#"
+        SELECT id, institution_type
+        FROM institutions
+        WHERE name = $1 AND country_id IS NOT DISTINCT FROM $2
+        ORDER BY created_at
+        LIMIT 1
+        FOR UPDATE
+        "#,
+        name,
+        country_id as Option<Uuid>,
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match existing {
+        None => {
+            sqlx::query!(
+                "INSERT INTO institutions (name, country_id, institution_type) VALUES ($1, $2, $3)",
+                name,
+                country_id as Option<Uuid>,
+                institution.institution_type,
+            )
+            .execute(&mut *conn)
+            .await?;
+            Ok(Outcome::Created)
+        }
+        Some(row)
+            if institution.institution_type.is_some()
+                && row.institution_type != institution.institution_type =>
+        {
+            sqlx::query!(
+                "UPDATE institutions SET institution_type = $2 WHERE id = $1",
+                row.id,
+                institution.institution_type,
+            )
+            .execute(&mut *conn)
+            .await?;
+            Ok(Outcome::Updated)
+        }
+        Some(_) => Ok(Outcome::Unchanged),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[test]
+    fn test_canonical_seed_is_valid() {
+        let seed = canonical_seed().unwrap();
+        seed.validate().unwrap();
+        // Enough for the startup validation gate
+        let skills: usize = seed.skill_categories.iter().map(|c| c.skills.len()).sum();
+        assert!(skills >= 10);
+        assert!(seed.languages.len() >= 5);
+        assert!(!seed.industries.is_empty());
+        assert!(!seed.work_areas.is_empty());
+        assert!(!seed.institutions.is_empty());
+    }
+
+    #[test]
+    fn test_missing_sections_default_to_empty() {
+        let seed = parse(r#"{ "languages": [{ "name": "Rapanui" }] }"#).unwrap();
+        assert_eq!(seed.languages.len(), 1);
+        assert!(seed.skill_categories.is_empty());
+        assert!(seed.institutions.is_empty());
+
+        assert!(parse(r#"{ "languages": "Rapanui" }"#).is_err());
+    }
+
+    #[test]
+    fn test_blank_skill_names_are_rejected() {
+        let seed = parse(
+            r#"{ "skill_categories": [{ "name": "Oficios", "skills": ["Soldadura", " "] }] }"#,
+        )
+        .unwrap();
+        assert!(seed.validate().is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use std::time::Instant;
+
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::admin::{RetentionAction, RetentionEntity, RetentionOutcome, RetentionPolicy};
+use crate::services::counters;
+use crate::utils::redact;
+
+/// Rows handled per statement, so a large backlog never holds locks for long
+const RETENTION_BATCH_SIZE: i64 = 500;
+
+// ============================================================================
+// RUNNER
+// ============================================================================
+
+/// Apply every enabled retention policy.
+///
+/// Each policy works through its due rows in batches until none are left,
+/// and its outcome is recorded in retention_runs. A failing batch stops that
+/// policy only; batches already committed stay applied. A dry run counts the
+/// due rows and changes nothing, not even retention_runs.
+pub async fn apply_policies(
+    db: &PgPool,
+    dry_run: bool,
+    triggered_by: Option<Uuid>,
+) -> Result<Vec<RetentionOutcome>, sqlx::Error> {
+    let policies = sqlx::query_as!(
+        RetentionPolicy,
+        r#"
+        SELECT
+            id,
+            entity_type as "entity_type: RetentionEntity",
+            action as "action: RetentionAction",
+            age_months,
+            is_enabled,
+            updated_by,
+            updated_at
+        FROM retention_policies
+        WHERE is_enabled = true
+        ORDER BY entity_type
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut outcomes = Vec::with_capacity(policies.len());
+    for policy in policies {
+        let started_at = Utc::now();
+        let timer = Instant::now();
+
+        let (affected_rows, error) = if dry_run {
+            (count_due(db, &policy).await?, None)
+        } else {
+            let mut affected = 0;
+            let mut error = None;
+            loop {
+                match apply_batch(db, &policy).await {
+                    Ok(done) => {
+                        affected += done;
+                        if done < RETENTION_BATCH_SIZE {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Retention policy {:?} failed after {} row(s): {}",
+                            policy.entity_type,
+                            affected,
+                            redact::db_error(&e)
+                        );
+                        error = Some(redact::db_error(&e));
+                        break;
+                    }
+                }
+            }
+            (affected, error)
+        };
+
+        let outcome = RetentionOutcome {
+            policy_id: policy.id,
+            entity_type: policy.entity_type,
+            action: policy.action,
+            age_months: policy.age_months,
+            affected_rows,
+            duration_ms: timer.elapsed().as_millis() as i64,
+            error,
+        };
+
+        if !dry_run {
+            sqlx::query!(
+                r#"
+                INSERT INTO retention_runs (
+                    policy_id, entity_type, action, age_months, affected_rows,
+                    duration_ms, error, triggered_by, started_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+                outcome.policy_id,
+                outcome.entity_type as RetentionEntity,
+                outcome.action as RetentionAction,
+                outcome.age_months,
+                outcome.affected_rows,
+                outcome.duration_ms,
+                outcome.error,
+                triggered_by,
+                started_at
+            )
+            .execute(db)
+            .await?;
+        }
+
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
+// ============================================================================
+// POLICY ACTIONS
+// ============================================================================
+
+/// Rows the policy would act on right now
+async fn count_due(db: &PgPool, policy: &RetentionPolicy) -> Result<i64, sqlx::Error> {
+    let count = match (policy.entity_type, policy.action) {
+        (RetentionEntity::Applications, RetentionAction::Anonymize) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) FROM job_applications
+                WHERE anonymized_at IS NULL
+                AND applied_at < NOW() - make_interval(months => $1)
+                "#,
+                policy.age_months
+            )
+            .fetch_one(db)
+            .await?
+        }
+        (RetentionEntity::Applications, _) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) FROM job_applications
+                WHERE applied_at < NOW() - make_interval(months => $1)
+                "#,
+                policy.age_months
+            )
+            .fetch_one(db)
+            .await?
+        }
+        (RetentionEntity::OmilFollowups, _) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) FROM job_seeker_followups
+                WHERE created_at < NOW() - make_interval(months => $1)
+                "#,
+                policy.age_months
+            )
+            .fetch_one(db)
+            .await?
+        }
+        (RetentionEntity::AuditLogs, _) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) FROM admin_audit_logs
+                WHERE created_at < NOW() - make_interval(months => $1)
+                "#,
+                policy.age_months
+            )
+            .fetch_one(db)
+            .await?
+        }
+    };
+
+    Ok(count.unwrap_or(0))
+}
+
+/// Act on one batch of due rows, returning how many were handled.
+/// Policies only ever hold an action their entity allows.
+async fn apply_batch(db: &PgPool, policy: &RetentionPolicy) -> Result<i64, sqlx::Error> {
+    let handled = match (policy.entity_type, policy.action) {
+        (RetentionEntity::Applications, RetentionAction::Anonymize) => {
+            anonymize_applications(db, policy.age_months).await?
+        }
+        (RetentionEntity::Applications, _) => delete_applications(db, policy.age_months).await?,
+        (RetentionEntity::OmilFollowups, _) => delete_followups(db, policy.age_months).await?,
+        (RetentionEntity::AuditLogs, _) => archive_audit_logs(db, policy.age_months).await?,
+    };
+
+    Ok(handled as i64)
+}
+
+/// Scrub the free text of old applications and everything written about
+/// them, keeping status and date columns for statistics. Attached documents
+/// are deleted; their files are queued for removal from storage.
+async fn anonymize_applications(db: &PgPool, age_months: i32) -> Result<usize, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let ids = sqlx::query_scalar!(
+        r#"
+        UPDATE job_applications
+        SET
+            cover_letter = NULL,
+            resume_url = NULL,
+            interview_notes = NULL,
+            offer_details = NULL,
+            withdrawal_reason = NULL,
+            rejection_feedback = NULL,
+            anonymized_at = NOW()
+        WHERE id IN (
+            SELECT id FROM job_applications
+            WHERE anonymized_at IS NULL
+            AND applied_at < NOW() - make_interval(months => $1)
+            ORDER BY applied_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id
+        "#,
+        age_months,
+        RETENTION_BATCH_SIZE
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM application_notes WHERE application_id = ANY($1)",
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE application_status_history SET notes = NULL WHERE application_id = ANY($1)",
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE omil_applications SET internal_notes = NULL WHERE application_id = ANY($1)",
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM application_documents WHERE application_id = ANY($1)",
+        &ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(ids.len())
+}
+
+/// Delete old applications outright. Notes and documents go with them
+/// through cascades, and document files are queued for removal from storage.
+/// The jobs' application counters are lowered in the same transaction.
+async fn delete_applications(db: &PgPool, age_months: i32) -> Result<usize, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM job_applications
+        WHERE id IN (
+            SELECT id FROM job_applications
+            WHERE applied_at < NOW() - make_interval(months => $1)
+            ORDER BY applied_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING job_id, status <> 'withdrawn' as "counted!"
+        "#,
+        age_months,
+        RETENTION_BATCH_SIZE
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let counted_job_ids: Vec<Uuid> = deleted
+        .iter()
+        .filter(|row| row.counted)
+        .map(|row| row.job_id)
+        .collect();
+    counters::applications_removed(&mut tx, &counted_job_ids).await?;
+
+    tx.commit().await?;
+
+    Ok(deleted.len())
+}
+
+async fn delete_followups(db: &PgPool, age_months: i32) -> Result<usize, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM job_seeker_followups
+        WHERE id IN (
+            SELECT id FROM job_seeker_followups
+            WHERE created_at < NOW() - make_interval(months => $1)
+            ORDER BY created_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        "#,
+        age_months,
+        RETENTION_BATCH_SIZE
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Move old audit log entries to audit_logs_archive in a single statement
+async fn archive_audit_logs(db: &PgPool, age_months: i32) -> Result<usize, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        WITH moved AS (
+            DELETE FROM admin_audit_logs
+            WHERE id IN (
+                SELECT id FROM admin_audit_logs
+                WHERE created_at < NOW() - make_interval(months => $1)
+                ORDER BY created_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, admin_id, action_type, entity_type, entity_id, details, ip_address, created_at
+        )
+        INSERT INTO audit_logs_archive (
+            id, admin_id, action_type, entity_type, entity_id, details, ip_address, created_at
+        )
+        SELECT id, admin_id, action_type, entity_type, entity_id, details, ip_address, created_at
+        FROM moved
+        "#,
+        age_months,
+        RETENTION_BATCH_SIZE
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
@@ -0,0 +1,392 @@
+use redis::aio::ConnectionManager;
+use sqlx::PgPool;
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+use uuid::Uuid;
+
+use crate::services::{
+    account_deletion, alerts, application_documents, backfill, bulk_operations, company_export,
+    completeness, job_deadlines, job_feed, matching, metering, retention, stale_jobs,
+};
+use crate::utils::redact;
+use crate::AppState;
+
+// ============================================================================
+// SCHEDULER
+// ============================================================================
+
+/// Every minute, at second 0
+const PUBLISH_SCHEDULED_JOBS_CRON: &str = "0 * * * * *";
+
+/// Every hour, at minute 5
+const EXPIRED_JOBS_CRON: &str = "0 5 * * * *";
+
+/// Every 10 seconds
+const BULK_OPERATIONS_CRON: &str = "*/10 * * * * *";
+
+/// Every hour, on the hour
+const APPLICATION_DRAFTS_CRON: &str = "0 0 * * * *";
+
+/// Every day at 04:15
+const JOB_COMPLETENESS_CRON: &str = "0 15 4 * * *";
+
+/// Every 5 minutes, at second 30
+const APPLICATION_DOCUMENT_PURGE_CRON: &str = "30 */5 * * * *";
+
+/// Every minute, at second 45
+const MATCH_SCORE_RECALCULATION_CRON: &str = "45 * * * * *";
+
+/// First day of every month at 03:30
+const RETENTION_CRON: &str = "0 30 3 1 * *";
+
+/// Every 30 minutes, at minute 0 and 30
+const JOB_FEED_CRON: &str = "0 */30 * * * *";
+
+/// Every minute, at second 15
+const COMPANY_DATA_EXPORTS_CRON: &str = "15 * * * * *";
+
+/// Every hour, at minute 10
+const ACCOUNT_DELETIONS_CRON: &str = "0 10 * * * *";
+
+/// Every Monday at 09:00, when companies are likely to act on a nudge
+const STALE_JOBS_CRON: &str = "0 0 9 * * Mon";
+
+/// Every 10 seconds
+const BACKFILLS_CRON: &str = "*/10 * * * * *";
+
+/// Every day at 01:40
+const USAGE_AGGREGATION_CRON: &str = "0 40 1 * * *";
+
+/// Every 15 minutes, at second 20, which is as soon as instant alerts go out
+const JOB_ALERTS_CRON: &str = "20 */15 * * * *";
+
+/// Start the background task scheduler.
+/// The returned scheduler must be kept alive for tasks to keep running.
+pub async fn start(state: AppState) -> Result<JobScheduler, JobSchedulerError> {
+    let scheduler = JobScheduler::new().await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(PUBLISH_SCHEDULED_JOBS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match publish_due_jobs(&db).await {
+                    Ok(published) if !published.is_empty() => {
+                        tracing::info!("Published {} scheduled job(s)", published.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!(
+                        "Failed to publish scheduled jobs: {}",
+                        redact::db_error(&e)
+                    ),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(EXPIRED_JOBS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match job_deadlines::close_expired(&db).await {
+                    Ok(closed) if !closed.is_empty() => {
+                        tracing::info!("Closed {} job(s) past their deadline", closed.len());
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to close expired jobs: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(BULK_OPERATIONS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match bulk_operations::process_pending(&db).await {
+                    Ok(completed) if completed > 0 => {
+                        tracing::info!("Completed {} bulk operation(s)", completed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to process bulk operations: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(APPLICATION_DRAFTS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match delete_stale_application_drafts(&db).await {
+                    Ok(deleted) if deleted > 0 => {
+                        tracing::info!("Deleted {} stale application draft(s)", deleted);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!(
+                        "Failed to delete application drafts: {}",
+                        redact::db_error(&e)
+                    ),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(JOB_COMPLETENESS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match completeness::recalculate_open_jobs(&db).await {
+                    Ok(scored) => tracing::info!("Recalculated completeness of {} job(s)", scored),
+                    Err(e) => tracing::error!("Failed to recalculate job completeness: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(MATCH_SCORE_RECALCULATION_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match matching::process_recalculations(&db).await {
+                    Ok(rescored) if rescored > 0 => {
+                        tracing::info!("Recalculated match scores for {} job(s)", rescored);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to recalculate match scores: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(RETENTION_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match retention::apply_policies(&db, false, None).await {
+                    Ok(outcomes) => {
+                        for outcome in outcomes {
+                            tracing::info!(
+                                "Retention {:?} {:?}: {} row(s) in {} ms",
+                                outcome.action,
+                                outcome.entity_type,
+                                outcome.affected_rows,
+                                outcome.duration_ms
+                            );
+                        }
+                    }
+                    Err(e) => tracing::error!("Failed to apply retention policies: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(ACCOUNT_DELETIONS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                match account_deletion::scrub_due(&db).await {
+                    Ok(scrubbed) if scrubbed > 0 => {
+                        tracing::info!("Scrubbed {} deleted account(s)", scrubbed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to scrub deleted accounts: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let stale_jobs_state = state.clone();
+    scheduler
+        .add(Job::new_async(STALE_JOBS_CRON, move |_, _| {
+            let state = stale_jobs_state.clone();
+            Box::pin(async move {
+                match stale_jobs::run(&state, chrono::Utc::now()).await {
+                    Ok(run) => tracing::info!(
+                        "Stale jobs: nudged {}, paused {}, notified {} applicant(s)",
+                        run.nudged_jobs,
+                        run.paused_jobs,
+                        run.notified_applicants
+                    ),
+                    Err(e) => tracing::error!("Failed to process stale jobs: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    scheduler
+        .add(Job::new_async(BACKFILLS_CRON, move |_, _| {
+            let db = db.clone();
+            Box::pin(async move {
+                if let Err(e) = backfill::run_pending(&db).await {
+                    tracing::error!("Failed to run backfills: {}", e);
+                }
+            })
+        })?)
+        .await?;
+
+    let (db, timezone) = (state.db.clone(), state.config.platform_timezone.clone());
+    scheduler
+        .add(Job::new_async(USAGE_AGGREGATION_CRON, move |_, _| {
+            let (db, timezone) = (db.clone(), timezone.clone());
+            Box::pin(async move {
+                match metering::aggregate_recent(&db, &timezone).await {
+                    Ok(written) => tracing::info!("Wrote {} usage summary row(s)", written),
+                    Err(e) => tracing::error!("Failed to aggregate company usage: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    let alerts_state = state.clone();
+    scheduler
+        .add(Job::new_async(JOB_ALERTS_CRON, move |_, _| {
+            let state = alerts_state.clone();
+            Box::pin(async move {
+                match alerts::run(&state, chrono::Utc::now()).await {
+                    Ok(run) if run.seekers > 0 => tracing::info!(
+                        "Job alerts: sent {} to {} seeker(s), {} failed",
+                        run.sent,
+                        run.seekers,
+                        run.failed
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to send job alerts: {}", e),
+                }
+            })
+        })?)
+        .await?;
+
+    // Generated once at startup too, so the feeds are not missing until the
+    // first scheduled run
+    let (db, redis, frontend_url) = (
+        state.db.clone(),
+        state.redis.clone(),
+        state.config.frontend_url.clone(),
+    );
+    tokio::spawn(refresh_job_feeds(db.clone(), redis.clone(), frontend_url.clone()));
+    scheduler
+        .add(Job::new_async(JOB_FEED_CRON, move |_, _| {
+            Box::pin(refresh_job_feeds(
+                db.clone(),
+                redis.clone(),
+                frontend_url.clone(),
+            ))
+        })?)
+        .await?;
+
+    let export_state = state.clone();
+    scheduler
+        .add(Job::new_async(COMPANY_DATA_EXPORTS_CRON, move |_, _| {
+            let state = export_state.clone();
+            Box::pin(async move {
+                match company_export::process_pending(&state).await {
+                    Ok(completed) if completed > 0 => {
+                        tracing::info!("Completed {} company data export(s)", completed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to run company data exports: {}", e),
+                }
+                match company_export::purge_expired(&state.db, state.storage.as_ref()).await {
+                    Ok(purged) if purged > 0 => {
+                        tracing::info!("Removed {} expired company data export(s)", purged);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to remove expired data exports: {}", e)
+                    }
+                }
+            })
+        })?)
+        .await?;
+
+    let db = state.db.clone();
+    let storage = state.storage.clone();
+    scheduler
+        .add(Job::new_async(APPLICATION_DOCUMENT_PURGE_CRON, move |_, _| {
+            let db = db.clone();
+            let storage = storage.clone();
+            Box::pin(async move {
+                match application_documents::purge_deleted(&db, storage.as_ref()).await {
+                    Ok(purged) if purged > 0 => {
+                        tracing::info!("Purged {} application document file(s)", purged);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to purge application documents: {}", e)
+                    }
+                }
+            })
+        })?)
+        .await?;
+
+    scheduler.start().await?;
+
+    Ok(scheduler)
+}
+
+// ============================================================================
+// TASKS
+// ============================================================================
+
+/// Activate scheduled jobs whose publish_at has passed.
+/// Stamps published_at exactly like a direct activation so the job becomes
+/// visible in public listings and eligible for alerts from that moment, and
+/// meters the publication.
+pub async fn publish_due_jobs(db: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+    let published = sqlx::query_scalar!(
+        r#"
+        UPDATE jobs
+        SET
+            status = 'active'::job_status,
+            published_at = NOW(),
+            updated_at = NOW()
+        WHERE status = 'scheduled'::job_status
+        AND publish_at <= NOW()
+        RETURNING id
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    metering::record_jobs_published(db, &published).await;
+
+    Ok(published)
+}
+
+/// Delete drafts for jobs that no longer take applications.
+/// Paused jobs keep their drafts since they may be reopened; drafts of deleted
+/// jobs go with the job itself.
+pub async fn delete_stale_application_drafts(db: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM application_drafts d
+        USING jobs j
+        WHERE j.id = d.job_id
+        AND (
+            j.status NOT IN ('active'::job_status, 'paused'::job_status)
+            OR j.application_deadline < CURRENT_DATE
+        )
+        "#
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Regenerate the job syndication feeds; failures keep the previous feeds
+/// until their cache entries expire
+async fn refresh_job_feeds(db: PgPool, mut redis: ConnectionManager, frontend_url: String) {
+    match job_feed::refresh(&db, &mut redis, &frontend_url).await {
+        Ok(jobs) => tracing::info!("Regenerated job feeds with {} job(s)", jobs),
+        Err(e) => tracing::error!("Failed to regenerate job feeds: {}", e),
+    }
+}
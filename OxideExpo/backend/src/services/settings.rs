@@ -0,0 +1,99 @@
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde_json::Value;
+use sqlx::PgPool;
+
+use crate::error::{AppError, Result};
+use crate::models::admin::SettingDefinition;
+
+/// Settings are read on hot paths, so values are cached briefly; updates
+/// through the admin endpoint invalidate the cache immediately
+const CACHE_TTL_SECONDS: u64 = 300;
+
+pub fn cache_key(key: &str) -> String {
+    format!("settings:{}", key)
+}
+
+/// Current value of a defined setting, falling back to its default when it
+/// has never been stored. Redis failures are logged and the database is read.
+pub async fn get_value(db: &PgPool, redis: &mut ConnectionManager, key: &str) -> Result<Value> {
+    get_value_with_ttl(db, redis, key, CACHE_TTL_SECONDS).await
+}
+
+/// Like `get_value`, for settings that must pick up changes made outside the
+/// admin endpoint sooner than the default cache lifetime
+pub async fn get_value_with_ttl(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    key: &str,
+    ttl_seconds: u64,
+) -> Result<Value> {
+    let definition = SettingDefinition::find(key)
+        .ok_or_else(|| AppError::InternalError(format!("Unknown setting {}", key)))?;
+    let cache_key = cache_key(key);
+
+    match redis.get::<_, Option<String>>(&cache_key).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(value) => return Ok(value),
+            Err(e) => tracing::warn!("Discarding unreadable setting cache {}: {}", key, e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read setting cache {}: {}", key, e),
+    }
+
+    let stored = sqlx::query_scalar!("SELECT value FROM system_settings WHERE key = $1", key)
+        .fetch_optional(db)
+        .await?;
+
+    // Rows written before values were validated may not match the definition
+    let value = match stored {
+        Some(value) if definition.validate_value(&value).is_ok() => value,
+        Some(value) => {
+            tracing::warn!("Ignoring invalid stored value for {}: {}", key, value);
+            definition.default_value()
+        }
+        None => definition.default_value(),
+    };
+
+    let result: std::result::Result<(), redis::RedisError> = redis
+        .set_ex(&cache_key, value.to_string(), ttl_seconds)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!("Failed to cache setting {}: {}", key, e);
+    }
+
+    Ok(value)
+}
+
+pub async fn get_int(db: &PgPool, redis: &mut ConnectionManager, key: &str) -> Result<i64> {
+    get_value(db, redis, key)
+        .await?
+        .as_i64()
+        .ok_or_else(|| AppError::InternalError(format!("Setting {} is not an integer", key)))
+}
+
+pub async fn get_bool(db: &PgPool, redis: &mut ConnectionManager, key: &str) -> Result<bool> {
+    get_value(db, redis, key)
+        .await?
+        .as_bool()
+        .ok_or_else(|| AppError::InternalError(format!("Setting {} is not a boolean", key)))
+}
+
+pub async fn get_string(db: &PgPool, redis: &mut ConnectionManager, key: &str) -> Result<String> {
+    match get_value(db, redis, key).await? {
+        Value::String(s) => Ok(s),
+        _ => Err(AppError::InternalError(format!(
+            "Setting {} is not a string",
+            key
+        ))),
+    }
+}
+
+/// Drops cached values so the next read sees the database
+pub async fn invalidate(redis: &mut ConnectionManager, keys: &[&str]) {
+    for key in keys {
+        let result: std::result::Result<(), redis::RedisError> = redis.del(cache_key(key)).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to invalidate setting cache {}: {}", key, e);
+        }
+    }
+}
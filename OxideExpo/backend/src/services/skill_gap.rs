@@ -0,0 +1,299 @@
+//! Skill gap of a job seeker against a work area: which skills the active
+//! jobs there ask for and the seeker lacks, ranked by how many jobs ask.
+//!
+//! What the market demands doesn't depend on the seeker, so the demand of a
+//! work area (and optionally region) is computed once and cached in Redis;
+//! only the overlay of the seeker's own skills runs per request.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::matching::{CoveredSkill, SkillGap, SkillGapResponse};
+use crate::services::matching::{MatchingService, PREFERRED_SKILLS_WEIGHT, SKILLS_WEIGHT};
+
+pub const CACHE_TTL_SECONDS: u64 = 6 * 60 * 60;
+
+/// Gaps (and covered skills) returned per analysis
+pub const SKILL_GAP_LIMIT: usize = 15;
+
+/// One entry per work area and region, `all` when not narrowed to a region
+pub fn cache_key(work_area_id: Uuid, region_id: Option<Uuid>) -> String {
+    match region_id {
+        Some(region_id) => format!("skill-gap:{}:{}", work_area_id, region_id),
+        None => format!("skill-gap:{}:all", work_area_id),
+    }
+}
+
+/// Jobs requiring a skill at one minimum proficiency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LevelDemand {
+    minimum_proficiency: i32,
+    jobs: i64,
+    /// Sum over those jobs of the skill's share of their requirements, i.e.
+    /// of the part of the skills weight the skill is worth in each
+    share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SkillDemand {
+    skill_id: Uuid,
+    skill_name: String,
+    required: Vec<LevelDemand>,
+    preferred_jobs: i64,
+    preferred_share: f64,
+}
+
+/// Seeker-independent part of an analysis, as cached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkAreaDemand {
+    active_jobs: i64,
+    skills: Vec<SkillDemand>,
+    generated_at: DateTime<Utc>,
+}
+
+/// Demand from Redis, computed and stored on a miss.
+/// Redis failures are logged and the demand computed directly.
+async fn work_area_demand(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    work_area_id: Uuid,
+    region_id: Option<Uuid>,
+) -> Result<WorkAreaDemand> {
+    let key = cache_key(work_area_id, region_id);
+    match redis.get::<_, Option<String>>(&key).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(demand) => return Ok(demand),
+            Err(e) => tracing::warn!("Discarding unreadable skill demand cache {}: {}", key, e),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read skill demand cache {}: {}", key, e),
+    }
+
+    let demand = compute_work_area_demand(db, work_area_id, region_id).await?;
+
+    if let Ok(json) = serde_json::to_string(&demand) {
+        let result: std::result::Result<(), redis::RedisError> =
+            redis.set_ex(&key, json, CACHE_TTL_SECONDS).await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache skill demand {}: {}", key, e);
+        }
+    }
+
+    Ok(demand)
+}
+
+fn demand_entry<'a>(
+    skills: &'a mut Vec<SkillDemand>,
+    index: &mut HashMap<Uuid, usize>,
+    skill_id: Uuid,
+    skill_name: String,
+) -> &'a mut SkillDemand {
+    let i = *index.entry(skill_id).or_insert_with(|| {
+        skills.push(SkillDemand {
+            skill_id,
+            skill_name,
+            required: Vec::new(),
+            preferred_jobs: 0,
+            preferred_share: 0.0,
+        });
+        skills.len() - 1
+    });
+    &mut skills[i]
+}
+
+async fn compute_work_area_demand(
+    db: &PgPool,
+    work_area_id: Uuid,
+    region_id: Option<Uuid>,
+) -> Result<WorkAreaDemand> {
+    let active_jobs = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM jobs
+        WHERE status = 'active'
+          AND work_area_id = $1
+          AND ($2::uuid IS NULL OR region_id = $2)
+        "#,
+        work_area_id,
+        region_id,
+    )
+    .fetch_one(db)
+    .await?;
+
+    let required = sqlx::query!(
+        r#"
+        WITH area_requirements AS (
+            SELECT r.skill_id, r.minimum_proficiency,
+                   COUNT(*) OVER (PARTITION BY r.job_id) AS skills_in_job
+            FROM job_required_skills r
+            JOIN jobs j ON j.id = r.job_id
+            WHERE j.status = 'active'
+              AND j.work_area_id = $1
+              AND ($2::uuid IS NULL OR j.region_id = $2)
+        )
+        SELECT
+            a.skill_id,
+            s.name as skill_name,
+            a.minimum_proficiency,
+            COUNT(*) as "jobs!",
+            SUM(1.0 / a.skills_in_job)::float8 as "share!"
+        FROM area_requirements a
+        JOIN skills s ON s.id = a.skill_id
+        GROUP BY a.skill_id, s.name, a.minimum_proficiency
+        ORDER BY a.skill_id, a.minimum_proficiency
+        "#,
+        work_area_id,
+        region_id,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let preferred = sqlx::query!(
+        r#"
+        WITH area_preferences AS (
+            SELECT p.skill_id, COUNT(*) OVER (PARTITION BY p.job_id) AS skills_in_job
+            FROM job_preferred_skills p
+            JOIN jobs j ON j.id = p.job_id
+            WHERE j.status = 'active'
+              AND j.work_area_id = $1
+              AND ($2::uuid IS NULL OR j.region_id = $2)
+        )
+        SELECT
+            a.skill_id,
+            s.name as skill_name,
+            COUNT(*) as "jobs!",
+            SUM(1.0 / a.skills_in_job)::float8 as "share!"
+        FROM area_preferences a
+        JOIN skills s ON s.id = a.skill_id
+        GROUP BY a.skill_id, s.name
+        "#,
+        work_area_id,
+        region_id,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut skills: Vec<SkillDemand> = Vec::new();
+    let mut index: HashMap<Uuid, usize> = HashMap::new();
+    for row in required {
+        let skill = demand_entry(&mut skills, &mut index, row.skill_id, row.skill_name);
+        skill.required.push(LevelDemand {
+            minimum_proficiency: row.minimum_proficiency,
+            jobs: row.jobs,
+            share: row.share,
+        });
+    }
+    for row in preferred {
+        let skill = demand_entry(&mut skills, &mut index, row.skill_id, row.skill_name);
+        skill.preferred_jobs = row.jobs;
+        skill.preferred_share = row.share;
+    }
+
+    Ok(WorkAreaDemand {
+        active_jobs,
+        skills,
+        generated_at: Utc::now(),
+    })
+}
+
+/// Splits the demand into what the seeker covers and what they lack, given
+/// their effective proficiency per skill. A skill is covered when the seeker
+/// meets every job's requirement for it; otherwise the gap counts only the
+/// jobs still out of reach, and the uplift is the average match score a job
+/// listing it would gain, per the matching weights.
+fn overlay(
+    demand: &WorkAreaDemand,
+    proficiencies: &HashMap<Uuid, i32>,
+) -> (Vec<SkillGap>, Vec<CoveredSkill>) {
+    let mut gaps = Vec::new();
+    let mut covered = Vec::new();
+
+    for skill in &demand.skills {
+        let proficiency = proficiencies.get(&skill.skill_id).copied();
+        let level = proficiency.unwrap_or(0);
+
+        let unmet: Vec<&LevelDemand> = skill
+            .required
+            .iter()
+            .filter(|d| d.minimum_proficiency > level)
+            .collect();
+        let jobs_requiring: i64 = unmet.iter().map(|d| d.jobs).sum();
+        let mut points: f64 = unmet.iter().map(|d| d.share).sum::<f64>() * SKILLS_WEIGHT as f64;
+
+        // Preferred skills count at any level
+        let jobs_preferring = if proficiency.is_none() {
+            points += skill.preferred_share * PREFERRED_SKILLS_WEIGHT as f64;
+            skill.preferred_jobs
+        } else {
+            0
+        };
+
+        if jobs_requiring == 0 && jobs_preferring == 0 {
+            covered.push(CoveredSkill {
+                skill_id: skill.skill_id,
+                skill_name: skill.skill_name.clone(),
+                jobs_requiring: skill.required.iter().map(|d| d.jobs).sum(),
+                jobs_preferring: skill.preferred_jobs,
+            });
+            continue;
+        }
+
+        let affected = (jobs_requiring + jobs_preferring) as f64;
+        gaps.push(SkillGap {
+            skill_id: skill.skill_id,
+            skill_name: skill.skill_name.clone(),
+            seeker_proficiency: proficiency,
+            required_proficiency: unmet.iter().map(|d| d.minimum_proficiency).max(),
+            jobs_requiring,
+            jobs_preferring,
+            average_score_uplift: (points / affected * 10.0).round() / 10.0,
+        });
+    }
+
+    gaps.sort_by(|a, b| {
+        b.jobs_requiring
+            .cmp(&a.jobs_requiring)
+            .then(b.jobs_preferring.cmp(&a.jobs_preferring))
+            .then(b.average_score_uplift.total_cmp(&a.average_score_uplift))
+            .then_with(|| a.skill_name.cmp(&b.skill_name))
+    });
+    gaps.truncate(SKILL_GAP_LIMIT);
+
+    covered.sort_by(|a, b| {
+        (b.jobs_requiring + b.jobs_preferring)
+            .cmp(&(a.jobs_requiring + a.jobs_preferring))
+            .then_with(|| a.skill_name.cmp(&b.skill_name))
+    });
+    covered.truncate(SKILL_GAP_LIMIT);
+
+    (gaps, covered)
+}
+
+/// The seeker's skill gap against the active jobs of the work area, only
+/// those of the region when one is given
+pub async fn skill_gap(
+    db: &PgPool,
+    redis: &mut ConnectionManager,
+    user_id: Uuid,
+    work_area_id: Uuid,
+    region_id: Option<Uuid>,
+) -> Result<SkillGapResponse> {
+    let demand = work_area_demand(db, redis, work_area_id, region_id).await?;
+    let proficiencies = MatchingService::effective_skill_proficiencies(db, user_id).await?;
+    let (gaps, covered) = overlay(&demand, &proficiencies);
+
+    Ok(SkillGapResponse {
+        work_area_id,
+        region_id,
+        active_jobs: demand.active_jobs,
+        gaps,
+        covered,
+        generated_at: demand.generated_at,
+    })
+}
@@ -0,0 +1,230 @@
+//! Nudges for companies that stopped reviewing a job's applicants.
+//!
+//! A job is stale when it's active, has applications waiting for review
+//! longer than `stale_job_pending_days`, and its company hasn't looked at its
+//! applicants for as long. The weekly run emails the company's owners and
+//! admins about each stale job; once `stale_job_nudges_before_pause` nudges
+//! went unanswered the next run pauses the job and tells the applicants
+//! still waiting. Any activity on the job's applicants clears the count.
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::admin::StaleJobRun;
+use crate::models::notification::NotificationEvent;
+use crate::services::{notifications, settings};
+use crate::AppState;
+
+pub const PENDING_DAYS_SETTING: &str = "stale_job_pending_days";
+pub const NUDGE_INTERVAL_DAYS_SETTING: &str = "stale_job_nudge_interval_days";
+pub const NUDGES_BEFORE_PAUSE_SETTING: &str = "stale_job_nudges_before_pause";
+
+/// Stamps company activity on the job's applicants, which answers any
+/// nudges about it. Jobs that were paused for inactivity and are no longer
+/// paused stop counting as such.
+pub async fn record_activity(db: &PgPool, job_id: Uuid) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET
+            last_company_activity_at = NOW(),
+            stale_nudge_count = 0,
+            last_stale_nudge_at = NULL,
+            stale_paused_at = CASE WHEN status = 'paused' THEN stale_paused_at END
+        WHERE id = $1
+        "#,
+        job_id
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Nudges the companies of stale jobs and pauses the ones whose nudges went
+/// unanswered, as of `now`. The counts are stored for the admin report.
+pub async fn run(state: &AppState, now: DateTime<Utc>) -> Result<StaleJobRun> {
+    let mut redis = state.redis.clone();
+    let pending_days = settings::get_int(&state.db, &mut redis, PENDING_DAYS_SETTING).await?;
+    let interval_days =
+        settings::get_int(&state.db, &mut redis, NUDGE_INTERVAL_DAYS_SETTING).await?;
+    let nudges_before_pause =
+        settings::get_int(&state.db, &mut redis, NUDGES_BEFORE_PAUSE_SETTING).await?;
+
+    let stale_before = now - Duration::days(pending_days);
+    let nudged_before = now - Duration::days(interval_days);
+
+    let mut tx = state.db.begin().await?;
+
+    let jobs = sqlx::query!(
+        r#"
+        SELECT
+            j.id, j.title, j.stale_nudge_count,
+            cp.company_name,
+            (
+                SELECT COUNT(*) FROM job_applications ja
+                WHERE ja.job_id = j.id AND ja.status = 'submitted' AND ja.applied_at <= $1
+            ) as "pending!"
+        FROM jobs j
+        JOIN company_profiles cp ON cp.id = j.company_id
+        WHERE j.status = 'active'
+        AND COALESCE(j.last_company_activity_at, j.published_at, j.created_at) <= $1
+        AND (j.last_stale_nudge_at IS NULL OR j.last_stale_nudge_at <= $2)
+        AND EXISTS (
+            SELECT 1 FROM job_applications ja
+            WHERE ja.job_id = j.id AND ja.status = 'submitted' AND ja.applied_at <= $1
+        )
+        ORDER BY j.id
+        FOR UPDATE OF j
+        "#,
+        stale_before,
+        nudged_before
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let (to_pause, to_nudge): (Vec<_>, Vec<_>) = jobs
+        .into_iter()
+        .partition(|job| i64::from(job.stale_nudge_count) >= nudges_before_pause);
+
+    let nudged_ids: Vec<Uuid> = to_nudge.iter().map(|job| job.id).collect();
+    sqlx::query!(
+        r#"
+        UPDATE jobs
+        SET stale_nudge_count = stale_nudge_count + 1, last_stale_nudge_at = $2
+        WHERE id = ANY($1)
+        "#,
+        &nudged_ids,
+        now
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let paused_ids: Vec<Uuid> = to_pause.iter().map(|job| job.id).collect();
+    sqlx::query!(
+        "UPDATE jobs SET status = 'paused', stale_paused_at = $2 WHERE id = ANY($1)",
+        &paused_ids,
+        now
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Everyone still in the process is told, not only the unreviewed
+    let applicants = sqlx::query!(
+        r#"
+        SELECT ja.job_id, u.id as user_id, u.email as "email!", u.first_name
+        FROM job_applications ja
+        JOIN users u ON u.id = ja.applicant_id
+        WHERE ja.job_id = ANY($1)
+        AND ja.status NOT IN ('hired', 'rejected', 'withdrawn')
+        AND u.email IS NOT NULL
+        "#,
+        &paused_ids
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let run = sqlx::query_as!(
+        StaleJobRun,
+        r#"
+        INSERT INTO stale_job_runs (ran_at, nudged_jobs, paused_jobs, notified_applicants)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, ran_at, nudged_jobs, paused_jobs, notified_applicants
+        "#,
+        now,
+        nudged_ids.len() as i32,
+        paused_ids.len() as i32,
+        applicants.len() as i32
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let job_ids: Vec<Uuid> = nudged_ids.iter().chain(&paused_ids).copied().collect();
+    let managers = sqlx::query!(
+        r#"
+        SELECT j.id as job_id, u.id as user_id, u.email as "email!", u.first_name
+        FROM jobs j
+        JOIN company_members cm ON cm.company_id = j.company_id
+        JOIN users u ON u.id = cm.user_id
+        WHERE j.id = ANY($1)
+        AND cm.is_active = true AND cm.role IN ('owner', 'admin')
+        AND u.email IS NOT NULL
+        "#,
+        &job_ids
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for job in &to_nudge {
+        let pauses_next = i64::from(job.stale_nudge_count) + 1 >= nudges_before_pause;
+        for manager in managers.iter().filter(|m| m.job_id == job.id) {
+            let (email, name, title) = (
+                manager.email.clone(),
+                manager.first_name.clone(),
+                job.title.clone(),
+            );
+            let (job_id, pending) = (job.id, job.pending);
+            notifications::email(
+                state,
+                manager.user_id,
+                NotificationEvent::StaleJob,
+                move |mailer| async move {
+                    mailer
+                        .send_stale_job_nudge_email(
+                            &email,
+                            &name,
+                            job_id,
+                            &title,
+                            pending,
+                            pauses_next,
+                        )
+                        .await
+                },
+            );
+        }
+    }
+
+    for job in &to_pause {
+        for manager in managers.iter().filter(|m| m.job_id == job.id) {
+            let (email, name, title) = (
+                manager.email.clone(),
+                manager.first_name.clone(),
+                job.title.clone(),
+            );
+            let job_id = job.id;
+            notifications::email(
+                state,
+                manager.user_id,
+                NotificationEvent::StaleJob,
+                move |mailer| async move {
+                    mailer
+                        .send_stale_job_paused_email(&email, &name, job_id, &title)
+                        .await
+                },
+            );
+        }
+        for applicant in applicants.iter().filter(|a| a.job_id == job.id) {
+            let (email, name, title, company_name) = (
+                applicant.email.clone(),
+                applicant.first_name.clone(),
+                job.title.clone(),
+                job.company_name.clone(),
+            );
+            notifications::email(
+                state,
+                applicant.user_id,
+                NotificationEvent::ApplicationStatus,
+                move |mailer| async move {
+                    mailer
+                        .send_application_on_hold_email(&email, &name, &title, &company_name)
+                        .await
+                },
+            );
+        }
+    }
+
+    Ok(run)
+}
@@ -1,23 +1,272 @@
+//! File storage behind the `StorageBackend` trait, with one driver per
+//! `STORAGE_DRIVER` value: S3-compatible object storage (MinIO, S3, R2), a
+//! directory on the local filesystem for development without MinIO, and
+//! process memory for tests.
+//!
+//! Keys are `/`-separated paths such as `cvs/{uuid}.pdf`. The local and
+//! in-memory drivers cannot presign URLs, so their download links point at
+//! GET /api/files/download with a short-lived signed token and the API
+//! streams the file itself.
+
+use axum::http::Method;
 use bytes::Bytes;
-use object_store::aws::AmazonS3Builder;
+use futures::future::BoxFuture;
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::local::LocalFileSystem;
+use object_store::memory::InMemory;
 use object_store::path::Path as ObjectPath;
-use object_store::{ObjectStore, PutPayload};
+use object_store::signer::Signer;
+use object_store::{ObjectStore, PutPayload, WriteMultipart};
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
-use crate::error::AppError;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::utils::jwt;
+
+/// Largest object stored in one `put`, the largest upload any endpoint
+/// accepts. Multipart uploads (data exports) are written piece by piece and
+/// are not bound by it.
+pub const MAX_OBJECT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Storage implementation, from `STORAGE_DRIVER`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageDriver {
+    /// S3-compatible object storage configured by the `S3_*` variables
+    S3,
+    /// Files under `STORAGE_LOCAL_DIR`
+    Local,
+    /// Process memory; everything is lost on restart
+    Memory,
+}
+
+impl std::str::FromStr for StorageDriver {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "s3" => Ok(Self::S3),
+            "local" => Ok(Self::Local),
+            "memory" => Ok(Self::Memory),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Object storage used by every file feature
+pub trait StorageBackend: Send + Sync {
+    /// Stores `data` under `key`, replacing any object already there
+    fn put<'a>(&'a self, key: &'a str, data: Bytes) -> BoxFuture<'a, Result<()>>;
+
+    /// Starts a multipart upload to `key`, for files written piece by piece
+    /// that are too large to hold in memory. Nothing is stored until the
+    /// upload is finished.
+    fn start_upload<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<WriteMultipart>>;
 
-/// Storage service for file uploads
-/// Uses object_store crate for S3/MinIO/R2 compatibility
-#[derive(Clone)]
-pub struct StorageService {
-    store: Arc<dyn ObjectStore>,
+    /// An object's content
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Bytes>>;
+
+    /// An object's size in bytes, or None when there is no object at `key`
+    fn head<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<u64>>>;
+
+    /// Removes an object
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// URL that lets someone without credentials download the object until
+    /// `expires_in` has passed
+    fn presign_get<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>>;
+
+    /// URL that lets someone without credentials upload the object until
+    /// `expires_in` has passed
+    fn presign_put<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>>;
+}
+
+impl dyn StorageBackend {
+    /// Stores a file under a new key in `folder` and returns where it went
+    pub async fn upload(
+        &self,
+        folder: &str,
+        filename: &str,
+        content_type: &str,
+        data: Bytes,
+    ) -> Result<StorageResult> {
+        // Generate unique path; anything but a plain extension is dropped
+        let file_id = Uuid::new_v4();
+        let extension = filename
+            .rsplit_once('.')
+            .map(|(_, extension)| extension)
+            .filter(|extension| {
+                !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+            .unwrap_or("bin");
+        let storage_path = format!("{}/{}.{}", folder, file_id, extension);
+        let file_size = data.len() as i64;
+
+        self.put(&storage_path, data).await?;
+
+        Ok(StorageResult {
+            storage_path,
+            file_size,
+            content_type: content_type.to_string(),
+        })
+    }
+}
+
+/// Builds the driver selected by `config.storage_driver`
+pub fn from_config(config: &Arc<Config>) -> Result<Arc<dyn StorageBackend>> {
+    Ok(match config.storage_driver {
+        StorageDriver::S3 => Arc::new(S3Storage::new(
+            &config.s3_endpoint,
+            &config.s3_bucket,
+            &config.s3_access_key,
+            &config.s3_secret_key,
+            &config.s3_region,
+            Some(config.s3_public_url.clone()),
+        )?),
+        StorageDriver::Local => Arc::new(LocalStorage::new(
+            &config.storage_local_dir,
+            config.clone(),
+        )?),
+        StorageDriver::Memory => Arc::new(MemoryStorage::new(config.clone())),
+    })
+}
+
+/// Result of a successful upload
+#[derive(Debug, Clone)]
+pub struct StorageResult {
+    pub storage_path: String,
+    pub file_size: i64,
+    pub content_type: String,
+}
+
+// ============================================================================
+// KEYS
+// ============================================================================
+
+/// Object path for `key`. Keys that could leave the storage root or name
+/// something other than a file (absolute paths, `.` and `..` segments,
+/// empty segments, backslashes, control characters) are rejected.
+pub fn object_path(key: &str) -> Result<ObjectPath> {
+    let invalid = || AppError::ValidationError("Invalid storage key".to_string());
+
+    if key.is_empty()
+        || key.starts_with('/')
+        || key.ends_with('/')
+        || key.contains('\\')
+        || key.chars().any(char::is_control)
+    {
+        return Err(invalid());
+    }
+
+    ObjectPath::parse(key).map_err(|_| invalid())
+}
+
+fn check_size(data: &Bytes) -> Result<()> {
+    if data.len() > MAX_OBJECT_BYTES {
+        return Err(AppError::ValidationError(format!(
+            "File too large. Maximum size: {} MB",
+            MAX_OBJECT_BYTES / 1024 / 1024
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// OBJECT STORE OPERATIONS
+// ============================================================================
+
+async fn put_object(store: &dyn ObjectStore, key: &str, data: Bytes) -> Result<()> {
+    store
+        .put(&object_path(key)?, PutPayload::from_bytes(data))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to upload file: {}", e)))?;
+    Ok(())
+}
+
+async fn start_object_upload(store: &dyn ObjectStore, key: &str) -> Result<WriteMultipart> {
+    let upload = store
+        .put_multipart(&object_path(key)?)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to start upload: {}", e)))?;
+
+    Ok(WriteMultipart::new(upload))
+}
+
+async fn get_object(store: &dyn ObjectStore, key: &str) -> Result<Bytes> {
+    let result = store
+        .get(&object_path(key)?)
+        .await
+        .map_err(|e| AppError::NotFound(format!("File not found: {}", e)))?;
+
+    let data = result
+        .bytes()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to read file: {}", e)))?;
+
+    Ok(data)
+}
+
+async fn head_object(store: &dyn ObjectStore, key: &str) -> Result<Option<u64>> {
+    match store.head(&object_path(key)?).await {
+        Ok(meta) => Ok(Some(meta.size as u64)),
+        Err(object_store::Error::NotFound { .. }) => Ok(None),
+        Err(e) => Err(AppError::InternalError(format!(
+            "Failed to read file: {}",
+            e
+        ))),
+    }
+}
+
+async fn delete_object(store: &dyn ObjectStore, key: &str) -> Result<()> {
+    store
+        .delete(&object_path(key)?)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to delete file: {}", e)))?;
+    Ok(())
+}
+
+/// GET /api/files/download link for drivers the API serves files from
+fn served_download_url(config: &Config, key: &str, expires_in: Duration) -> Result<String> {
+    object_path(key)?;
+    let expires_in = chrono::Duration::from_std(expires_in)
+        .map_err(|e| AppError::InternalError(format!("Failed to sign file URL: {}", e)))?;
+    let token = jwt::create_file_download_token(key, expires_in, config)
+        .map_err(|e| AppError::InternalError(format!("Failed to sign file URL: {}", e)))?;
+
+    Ok(format!(
+        "{}/api/files/download?token={}",
+        config.app_base_url, token
+    ))
+}
+
+fn presign_put_unsupported(driver: &str) -> Result<String> {
+    Err(AppError::InternalError(format!(
+        "The {} storage driver does not support presigned uploads",
+        driver
+    )))
+}
+
+// ============================================================================
+// S3 DRIVER
+// ============================================================================
+
+/// S3-compatible object storage (S3, MinIO, R2) through object_store
+pub struct S3Storage {
+    store: Arc<AmazonS3>,
     bucket: String,
     public_url_base: Option<String>,
 }
 
-impl StorageService {
-    /// Create a new storage service
+impl S3Storage {
     pub fn new(
         endpoint: &str,
         bucket: &str,
@@ -25,7 +274,7 @@ impl StorageService {
         secret_key: &str,
         region: &str,
         public_url_base: Option<String>,
-    ) -> Result<Self, AppError> {
+    ) -> Result<Self> {
         let store = AmazonS3Builder::new()
             .with_endpoint(endpoint)
             .with_bucket_name(bucket)
@@ -43,94 +292,203 @@ impl StorageService {
         })
     }
 
-    /// Upload a file and return the storage path
-    pub async fn upload(
-        &self,
-        folder: &str,
-        filename: &str,
-        content_type: &str,
-        data: Bytes,
-    ) -> Result<StorageResult, AppError> {
-        // Generate unique path
-        let file_id = Uuid::new_v4();
-        let extension = filename
-            .rsplit('.')
-            .next()
-            .unwrap_or("bin");
-        let storage_path = format!("{}/{}.{}", folder, file_id, extension);
-        let object_path = ObjectPath::from(storage_path.clone());
+    /// Generate a public URL for a file (if public_url_base is configured)
+    pub fn get_public_url(&self, storage_path: &str) -> Option<String> {
+        self.public_url_base
+            .as_ref()
+            .map(|base| format!("{}/{}/{}", base, self.bucket, storage_path))
+    }
 
-        // Upload file
-        let payload = PutPayload::from_bytes(data.clone());
-        self.store
-            .put(&object_path, payload)
+    async fn signed_url(&self, method: Method, key: &str, expires_in: Duration) -> Result<String> {
+        let url = self
+            .store
+            .signed_url(method, &object_path(key)?, expires_in)
             .await
-            .map_err(|e| AppError::InternalError(format!("Failed to upload file: {}", e)))?;
+            .map_err(|e| AppError::InternalError(format!("Failed to sign file URL: {}", e)))?;
 
-        Ok(StorageResult {
-            storage_path,
-            file_size: data.len() as i64,
-            content_type: content_type.to_string(),
-        })
+        Ok(url.to_string())
     }
+}
 
-    /// Delete a file
-    pub async fn delete(&self, storage_path: &str) -> Result<(), AppError> {
-        let object_path = ObjectPath::from(storage_path.to_string());
-        self.store
-            .delete(&object_path)
-            .await
-            .map_err(|e| AppError::InternalError(format!("Failed to delete file: {}", e)))?;
-        Ok(())
+impl StorageBackend for S3Storage {
+    fn put<'a>(&'a self, key: &'a str, data: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(put_object(self.store.as_ref(), key, data))
     }
 
-    /// Get a file's content
-    pub async fn get(&self, storage_path: &str) -> Result<Bytes, AppError> {
-        let object_path = ObjectPath::from(storage_path.to_string());
-        let result = self
-            .store
-            .get(&object_path)
-            .await
-            .map_err(|e| AppError::NotFound(format!("File not found: {}", e)))?;
+    fn start_upload<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<WriteMultipart>> {
+        Box::pin(start_object_upload(self.store.as_ref(), key))
+    }
 
-        let data = result
-            .bytes()
-            .await
-            .map_err(|e| AppError::InternalError(format!("Failed to read file: {}", e)))?;
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Bytes>> {
+        Box::pin(get_object(self.store.as_ref(), key))
+    }
 
-        Ok(data)
+    fn head<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<u64>>> {
+        Box::pin(head_object(self.store.as_ref(), key))
     }
 
-    /// Generate a public URL for a file (if public_url_base is configured)
-    pub fn get_public_url(&self, storage_path: &str) -> Option<String> {
-        self.public_url_base.as_ref().map(|base| {
-            format!("{}/{}/{}", base, self.bucket, storage_path)
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(delete_object(self.store.as_ref(), key))
+    }
+
+    fn presign_get<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(self.signed_url(Method::GET, key, expires_in))
+    }
+
+    fn presign_put<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(self.signed_url(Method::PUT, key, expires_in))
+    }
+}
+
+// ============================================================================
+// LOCAL FILESYSTEM DRIVER
+// ============================================================================
+
+/// Files under a directory on the local filesystem, for development without
+/// object storage. Downloads are streamed by the API.
+pub struct LocalStorage {
+    store: Arc<LocalFileSystem>,
+    config: Arc<Config>,
+}
+
+impl LocalStorage {
+    /// Driver rooted at `root`, which is created if missing
+    pub fn new(root: &str, config: Arc<Config>) -> Result<Self> {
+        std::fs::create_dir_all(root).map_err(|e| {
+            AppError::InternalError(format!("Failed to create storage directory: {}", e))
+        })?;
+        let store = LocalFileSystem::new_with_prefix(root)
+            .map_err(|e| AppError::InternalError(format!("Failed to create storage: {}", e)))?;
+
+        Ok(Self {
+            store: Arc::new(store),
+            config,
+        })
+    }
+}
+
+impl StorageBackend for LocalStorage {
+    fn put<'a>(&'a self, key: &'a str, data: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            check_size(&data)?;
+            put_object(self.store.as_ref(), key, data).await
         })
     }
 
-    /// Generate a presigned URL for temporary access
-    /// Note: object_store doesn't directly support presigned URLs,
-    /// so we return a direct URL or implement via signed URLs later
-    pub fn get_download_url(&self, storage_path: &str) -> String {
-        // For now, return the internal path - actual presigned URLs
-        // would need additional implementation
-        if let Some(url) = self.get_public_url(storage_path) {
-            url
-        } else {
-            format!("/api/files/download/{}", storage_path)
+    fn start_upload<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<WriteMultipart>> {
+        Box::pin(start_object_upload(self.store.as_ref(), key))
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Bytes>> {
+        Box::pin(get_object(self.store.as_ref(), key))
+    }
+
+    fn head<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<u64>>> {
+        Box::pin(head_object(self.store.as_ref(), key))
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(delete_object(self.store.as_ref(), key))
+    }
+
+    fn presign_get<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move { served_download_url(&self.config, key, expires_in) })
+    }
+
+    fn presign_put<'a>(&'a self, _key: &'a str, _: Duration) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async { presign_put_unsupported("local") })
+    }
+}
+
+// ============================================================================
+// IN-MEMORY DRIVER
+// ============================================================================
+
+/// Objects kept in process memory, for tests. Downloads are streamed by the
+/// API like the local driver's.
+pub struct MemoryStorage {
+    store: Arc<InMemory>,
+    config: Arc<Config>,
+}
+
+impl MemoryStorage {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            store: Arc::new(InMemory::new()),
+            config,
         }
     }
 }
 
-/// Result of a successful upload
-#[derive(Debug, Clone)]
-pub struct StorageResult {
-    pub storage_path: String,
-    pub file_size: i64,
-    pub content_type: String,
+impl StorageBackend for MemoryStorage {
+    fn put<'a>(&'a self, key: &'a str, data: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            check_size(&data)?;
+            put_object(self.store.as_ref(), key, data).await
+        })
+    }
+
+    fn start_upload<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<WriteMultipart>> {
+        Box::pin(start_object_upload(self.store.as_ref(), key))
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Bytes>> {
+        Box::pin(get_object(self.store.as_ref(), key))
+    }
+
+    fn head<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<u64>>> {
+        Box::pin(head_object(self.store.as_ref(), key))
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(delete_object(self.store.as_ref(), key))
+    }
+
+    fn presign_get<'a>(
+        &'a self,
+        key: &'a str,
+        expires_in: Duration,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move { served_download_url(&self.config, key, expires_in) })
+    }
+
+    fn presign_put<'a>(&'a self, _key: &'a str, _: Duration) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async { presign_put_unsupported("in-memory") })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    // Tests would go here
+    use super::*;
+
+    #[test]
+    fn test_object_path_rejects_escaping_keys() {
+        for key in [
+            "",
+            "../etc/passwd",
+            "cvs/../../etc/passwd",
+            "/etc/passwd",
+            "cvs/./file.pdf",
+            "cvs//file.pdf",
+            "cvs/",
+            "cvs\\..\\file.pdf",
+            "cvs/file\0.pdf",
+        ] {
+            assert!(object_path(key).is_err(), "{:?} was accepted", key);
+        }
+
+        assert!(object_path("cvs/0b7c2f7e-1111-4c7e-9a53-4f1d2c3b4a5e.pdf").is_ok());
+    }
 }
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::user::UserType;
+
+/// Resources touched by suspending or reactivating one user
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SuspensionCascade {
+    pub company_id: Option<Uuid>,
+    pub job_ids: Vec<Uuid>,
+    pub application_ids: Vec<Uuid>,
+}
+
+/// Pause everything the user owns, recording prior statuses.
+///
+/// A company is only suspended when the user is its sole active owner; its
+/// active jobs are paused with it. A job seeker's open applications are put
+/// on hold.
+pub async fn suspend(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    user_type: UserType,
+) -> Result<SuspensionCascade> {
+    let mut cascade = SuspensionCascade::default();
+
+    match user_type {
+        UserType::CompanyMember => {
+            let company = sqlx::query!(
+                r#"
+                SELECT c.id, c.status::text as "status!"
+                FROM company_members m
+                JOIN company_profiles c ON c.id = m.company_id
+                WHERE m.user_id = $1 AND m.role = 'owner' AND m.is_active = true
+                  AND c.status <> 'suspended'
+                  AND NOT EXISTS (
+                      SELECT 1 FROM company_members o
+                      WHERE o.company_id = m.company_id AND o.role = 'owner'
+                        AND o.is_active = true AND o.user_id <> m.user_id
+                  )
+                FOR UPDATE OF c
+                "#,
+                user_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?;
+
+            let Some(company) = company else {
+                return Ok(cascade);
+            };
+
+            sqlx::query!(
+                "UPDATE company_profiles SET status = 'suspended', updated_at = NOW() WHERE id = $1",
+                company.id
+            )
+            .execute(&mut **tx)
+            .await?;
+            record(tx, user_id, "company", &[company.id], Some(&company.status)).await?;
+            cascade.company_id = Some(company.id);
+
+            cascade.job_ids = sqlx::query_scalar!(
+                r#"
+                UPDATE jobs SET status = 'paused', updated_at = NOW()
+                WHERE company_id = $1 AND status = 'active'
+                RETURNING id
+                "#,
+                company.id
+            )
+            .fetch_all(&mut **tx)
+            .await?;
+            record(tx, user_id, "job", &cascade.job_ids, Some("active")).await?;
+        }
+        UserType::JobSeeker => {
+            cascade.application_ids = sqlx::query_scalar!(
+                r#"
+                UPDATE job_applications SET is_on_hold = true, updated_at = NOW()
+                WHERE applicant_id = $1 AND is_on_hold = false
+                  AND status NOT IN ('hired', 'rejected', 'withdrawn')
+                RETURNING id
+                "#,
+                user_id
+            )
+            .fetch_all(&mut **tx)
+            .await?;
+            record(tx, user_id, "application", &cascade.application_ids, None).await?;
+        }
+        _ => {}
+    }
+
+    Ok(cascade)
+}
+
+/// Undo a suspension using the recorded prior statuses. Anything an admin
+/// changed in the meantime is left as it is now.
+pub async fn reactivate(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<SuspensionCascade> {
+    let company_id = sqlx::query_scalar!(
+        r#"
+        UPDATE company_profiles c
+        SET status = s.prior_status::organization_status, updated_at = NOW()
+        FROM suspension_cascades s
+        WHERE s.user_id = $1 AND s.entity_type = 'company' AND s.entity_id = c.id
+          AND c.status = 'suspended'
+        RETURNING c.id
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let job_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE jobs j
+        SET status = s.prior_status::job_status, updated_at = NOW()
+        FROM suspension_cascades s
+        WHERE s.user_id = $1 AND s.entity_type = 'job' AND s.entity_id = j.id
+          AND j.status = 'paused'
+        RETURNING j.id
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let application_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE job_applications a
+        SET is_on_hold = false, updated_at = NOW()
+        FROM suspension_cascades s
+        WHERE s.user_id = $1 AND s.entity_type = 'application' AND s.entity_id = a.id
+        RETURNING a.id
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM suspension_cascades WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(SuspensionCascade {
+        company_id,
+        job_ids,
+        application_ids,
+    })
+}
+
+async fn record(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    entity_type: &str,
+    entity_ids: &[Uuid],
+    prior_status: Option<&str>,
+) -> Result<()> {
+    if entity_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO suspension_cascades (user_id, entity_type, entity_id, prior_status)
+        SELECT $1, $2, id, $4 FROM UNNEST($3::uuid[]) AS id
+        ON CONFLICT (user_id, entity_type, entity_id) DO NOTHING
+        "#,
+        user_id,
+        entity_type,
+        entity_ids,
+        prior_status
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
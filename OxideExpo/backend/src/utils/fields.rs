@@ -0,0 +1,139 @@
+//! Sparse fieldsets: `?fields=profile,skills` limits a response to the named
+//! top-level sections. Response types keep every section; the unselected ones
+//! are dropped once serialized.
+
+use axum::http::{HeaderMap, HeaderValue};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The sections a client asked for, checked against those the endpoint has
+#[derive(Debug, Clone, Default)]
+pub struct FieldSelection {
+    /// None when every section is wanted
+    selected: Option<Vec<&'static str>>,
+    /// Requested names the endpoint doesn't know
+    unknown: Vec<String>,
+}
+
+impl FieldSelection {
+    /// Parses a comma-separated `fields` parameter. Unknown names are
+    /// remembered for the warning header; if none of the names are known the
+    /// whole response is sent.
+    pub fn parse(fields: Option<&str>, known: &[&'static str]) -> Self {
+        let mut selected = Vec::new();
+        let mut unknown = Vec::new();
+
+        for name in fields.unwrap_or_default().split(',').map(str::trim) {
+            if name.is_empty() {
+                continue;
+            }
+            match known.iter().find(|k| k.eq_ignore_ascii_case(name)) {
+                Some(k) if !selected.contains(k) => selected.push(*k),
+                Some(_) => {}
+                None => unknown.push(name.to_string()),
+            }
+        }
+
+        Self {
+            selected: (!selected.is_empty()).then_some(selected),
+            unknown,
+        }
+    }
+
+    /// Whether a section goes in the response
+    pub fn includes(&self, section: &str) -> bool {
+        self.selected
+            .as_ref()
+            .is_none_or(|selected| selected.contains(&section))
+    }
+
+    /// The serialized response with only the selected top-level keys
+    pub fn retain<T: Serialize>(&self, response: &T) -> Value {
+        let mut value = serde_json::to_value(response).unwrap_or_default();
+        if let Value::Object(map) = &mut value {
+            map.retain(|key, _| self.includes(key));
+        }
+        value
+    }
+
+    /// A `Warning` header listing the names that were ignored, if any
+    pub fn warning_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if self.unknown.is_empty() {
+            return headers;
+        }
+
+        // Header values must be visible ASCII; anything else is dropped
+        let names: String = self
+            .unknown
+            .join(", ")
+            .chars()
+            .filter(|c| c.is_ascii_graphic() || *c == ' ')
+            .filter(|c| !matches!(c, '"' | '\\'))
+            .collect();
+        let warning = format!("299 - \"Unknown fields ignored: {}\"", names);
+        if let Ok(value) = HeaderValue::from_str(&warning) {
+            headers.insert(axum::http::header::WARNING, value);
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN: &[&str] = &["profile", "skills", "education"];
+
+    #[test]
+    fn test_no_fields_selects_everything() {
+        let fields = FieldSelection::parse(None, KNOWN);
+        assert!(fields.includes("profile"));
+        assert!(fields.includes("education"));
+        assert!(fields.warning_headers().is_empty());
+
+        let fields = FieldSelection::parse(Some(" , "), KNOWN);
+        assert!(fields.includes("skills"));
+    }
+
+    #[test]
+    fn test_named_fields_only() {
+        let fields = FieldSelection::parse(Some("profile, Skills,profile"), KNOWN);
+        assert!(fields.includes("profile"));
+        assert!(fields.includes("skills"));
+        assert!(!fields.includes("education"));
+        assert!(fields.warning_headers().is_empty());
+    }
+
+    #[test]
+    fn test_retain_keeps_selected_keys() {
+        let response = serde_json::json!({ "profile": {}, "skills": [], "education": [] });
+
+        let fields = FieldSelection::parse(Some("skills"), KNOWN);
+        assert_eq!(
+            fields.retain(&response),
+            serde_json::json!({ "skills": [] })
+        );
+
+        let fields = FieldSelection::parse(None, KNOWN);
+        assert_eq!(fields.retain(&response), response);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_ignored_with_warning() {
+        let fields = FieldSelection::parse(Some("skills,salario,\"años\""), KNOWN);
+        assert!(fields.includes("skills"));
+        assert!(!fields.includes("profile"));
+
+        let headers = fields.warning_headers();
+        assert_eq!(
+            headers.get("warning").unwrap(),
+            "299 - \"Unknown fields ignored: salario, aos\""
+        );
+
+        // Nothing usable left: the full response
+        let fields = FieldSelection::parse(Some("salario"), KNOWN);
+        assert!(fields.includes("profile"));
+        assert!(!fields.warning_headers().is_empty());
+    }
+}
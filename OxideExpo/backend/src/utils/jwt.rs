@@ -1,13 +1,77 @@
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{
+    decode, decode_header, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header,
+    TokenData, Validation,
+};
 use rand::Rng;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::config::Config;
 use crate::models::user::UserType;
 
+// ============================================================================
+// SIGNING KEYS
+// ============================================================================
+
+/// Key ID for a secret: first 16 hex chars of its SHA256, safe to expose
+pub fn key_id(secret: &str) -> String {
+    hash_token(secret)[..16].to_string()
+}
+
+/// Header for newly issued tokens, always signed with the primary secret
+fn signing_header(config: &Config) -> Header {
+    Header {
+        kid: Some(key_id(&config.jwt_secret)),
+        ..Header::default()
+    }
+}
+
+/// Primary secret first, then the verification-only ones
+fn verification_secrets(config: &Config) -> impl Iterator<Item = &String> {
+    std::iter::once(&config.jwt_secret).chain(config.jwt_previous_secrets.iter())
+}
+
+/// Key ID from a token header, without verifying the signature
+pub fn token_key_id(token: &str) -> Option<String> {
+    decode_header(token).ok().and_then(|header| header.kid)
+}
+
+/// Decodes a token against the configured keys. Tokens with a `kid` go straight
+/// to that key and unknown kids are rejected; tokens without one (issued before
+/// rotation support) try every key in order.
+fn decode_with_rotation<T: DeserializeOwned>(
+    token: &str,
+    config: &Config,
+) -> Result<TokenData<T>, jsonwebtoken::errors::Error> {
+    let validation = Validation::default();
+
+    match decode_header(token)?.kid {
+        Some(kid) => {
+            let secret = verification_secrets(config)
+                .find(|secret| key_id(secret) == kid)
+                .ok_or_else(|| jsonwebtoken::errors::Error::from(ErrorKind::InvalidKeyFormat))?;
+
+            decode::<T>(token, &DecodingKey::from_secret(secret.as_bytes()), &validation)
+        }
+        None => {
+            let mut last_error = jsonwebtoken::errors::Error::from(ErrorKind::InvalidSignature);
+            for secret in verification_secrets(config) {
+                match decode::<T>(token, &DecodingKey::from_secret(secret.as_bytes()), &validation) {
+                    Ok(data) => return Ok(data),
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(last_error)
+        }
+    }
+}
+
+// ============================================================================
+// ACCESS TOKENS
+// ============================================================================
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     /// Subject (user ID as UUID string)
@@ -22,6 +86,9 @@ pub struct Claims {
     pub iat: usize,
     /// JWT ID for blacklisting
     pub jti: String,
+    /// User's token_version at issue time; tokens from before versioning count as 0
+    #[serde(default)]
+    pub ver: i32,
 }
 
 impl Claims {
@@ -35,13 +102,12 @@ pub fn create_access_token(
     user_id: Uuid,
     email: &str,
     user_type: UserType,
+    token_version: i32,
     config: &Config,
 ) -> Result<(String, i64), jsonwebtoken::errors::Error> {
     let now = Utc::now();
-    let expiration = now
-        .checked_add_signed(Duration::seconds(config.jwt_access_expiry))
-        .expect("valid timestamp")
-        .timestamp();
+    // JWT_ACCESS_EXPIRY is bounded when the config is loaded
+    let expiration = now.timestamp().saturating_add(config.jwt_access_expiry);
 
     let jti = Uuid::new_v4().to_string();
 
@@ -59,10 +125,11 @@ pub fn create_access_token(
         exp: expiration as usize,
         iat: now.timestamp() as usize,
         jti,
+        ver: token_version,
     };
 
     let token = encode(
-        &Header::default(),
+        &signing_header(config),
         &claims,
         &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
     )?;
@@ -72,11 +139,7 @@ pub fn create_access_token(
 
 /// Verifies a JWT access token and returns the claims
 pub fn verify_access_token(token: &str, config: &Config) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
-        &Validation::default(),
-    )?;
+    let token_data = decode_with_rotation::<Claims>(token, config)?;
 
     Ok(token_data.claims)
 }
@@ -88,6 +151,22 @@ pub fn create_refresh_token() -> String {
     hex::encode(bytes)
 }
 
+/// Prefix of personal API tokens, which are opaque rather than JWTs
+pub const API_TOKEN_PREFIX: &str = "oxp_";
+
+/// Generates a personal API token ("oxp_" followed by 64 hex characters)
+pub fn create_api_token() -> String {
+    format!("{}{}", API_TOKEN_PREFIX, create_refresh_token())
+}
+
+/// Prefix of calendar feed tokens, which are opaque like API tokens
+pub const CALENDAR_TOKEN_PREFIX: &str = "cal_";
+
+/// Generates a calendar feed token ("cal_" followed by 64 hex characters)
+pub fn create_calendar_token() -> String {
+    format!("{}{}", CALENDAR_TOKEN_PREFIX, create_refresh_token())
+}
+
 /// Hashes a token using SHA256 for secure storage
 pub fn hash_token(token: &str) -> String {
     let mut hasher = Sha256::new();
@@ -158,7 +237,7 @@ pub fn create_impersonation_token(
     };
 
     let token = encode(
-        &Header::default(),
+        &signing_header(config),
         &claims,
         &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
     )?;
@@ -171,11 +250,7 @@ pub fn verify_impersonation_token(
     token: &str,
     config: &Config,
 ) -> Result<ImpersonationClaims, jsonwebtoken::errors::Error> {
-    let token_data = decode::<ImpersonationClaims>(
-        token,
-        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
-        &Validation::default(),
-    )?;
+    let token_data = decode_with_rotation::<ImpersonationClaims>(token, config)?;
 
     // Verify scope
     if token_data.claims.scope != "profile_edit" {
@@ -187,9 +262,389 @@ pub fn verify_impersonation_token(
     Ok(token_data.claims)
 }
 
+// ============================================================================
+// KIOSK TOKENS
+// ============================================================================
+
+/// Scope claim of kiosk tokens
+pub const KIOSK_SCOPE: &str = "omil:kiosk";
+
+/// Hours a kiosk token stays valid, enough for a full job fair day
+pub const KIOSK_TOKEN_HOURS: i64 = 8;
+
+/// Claims for kiosk tokens - a shared device registering walk-ins for an OMIL
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KioskClaims {
+    /// Subject (the OMIL member who opened the kiosk)
+    pub sub: String,
+    /// The OMIL organization the kiosk is bound to
+    pub omil_id: String,
+    /// The kiosk session, recorded on every registration
+    pub kiosk_session: String,
+    /// Token scope - always "omil:kiosk"
+    pub scope: String,
+    /// Expiration time (Unix timestamp)
+    pub exp: usize,
+    /// Issued at (Unix timestamp)
+    pub iat: usize,
+    /// JWT ID for blacklisting
+    pub jti: String,
+    /// Opener's token_version at issue time
+    pub ver: i32,
+}
+
+impl KioskClaims {
+    pub fn user_id(&self) -> Result<Uuid, uuid::Error> {
+        Uuid::parse_str(&self.sub)
+    }
+
+    pub fn omil_id(&self) -> Result<Uuid, uuid::Error> {
+        Uuid::parse_str(&self.omil_id)
+    }
+
+    pub fn kiosk_session_id(&self) -> Result<Uuid, uuid::Error> {
+        Uuid::parse_str(&self.kiosk_session)
+    }
+}
+
+/// Creates a kiosk token for an OMIL member, valid for 8 hours
+pub fn create_kiosk_token(
+    user_id: Uuid,
+    omil_id: Uuid,
+    kiosk_session_id: Uuid,
+    token_version: i32,
+    config: &Config,
+) -> Result<(String, Uuid, chrono::DateTime<Utc>), jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let expires_at = now
+        .checked_add_signed(Duration::hours(KIOSK_TOKEN_HOURS))
+        .expect("valid timestamp");
+
+    let jti = Uuid::new_v4();
+
+    let claims = KioskClaims {
+        sub: user_id.to_string(),
+        omil_id: omil_id.to_string(),
+        kiosk_session: kiosk_session_id.to_string(),
+        scope: KIOSK_SCOPE.to_string(),
+        exp: expires_at.timestamp() as usize,
+        iat: now.timestamp() as usize,
+        jti: jti.to_string(),
+        ver: token_version,
+    };
+
+    let token = encode(
+        &signing_header(config),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )?;
+
+    Ok((token, jti, expires_at))
+}
+
+/// Verifies a kiosk token and returns the claims
+pub fn verify_kiosk_token(
+    token: &str,
+    config: &Config,
+) -> Result<KioskClaims, jsonwebtoken::errors::Error> {
+    let token_data = decode_with_rotation::<KioskClaims>(token, config)?;
+
+    if token_data.claims.scope != KIOSK_SCOPE {
+        return Err(jsonwebtoken::errors::Error::from(ErrorKind::InvalidToken));
+    }
+
+    Ok(token_data.claims)
+}
+
+// ============================================================================
+// UNSUBSCRIBE TOKENS
+// ============================================================================
+
+/// Scope claim of unsubscribe tokens
+pub const UNSUBSCRIBE_SCOPE: &str = "email:unsubscribe";
+
+/// Days an unsubscribe link keeps working; old emails are still read
+pub const UNSUBSCRIBE_TOKEN_DAYS: i64 = 365;
+
+/// Claims for unsubscribe tokens - one-click opt-out from one kind of email
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribeClaims {
+    /// Subject (the recipient's user ID)
+    pub sub: String,
+    /// NotificationEvent code whose emails are turned off
+    pub event: String,
+    /// Token scope - always "email:unsubscribe"
+    pub scope: String,
+    /// Expiration time (Unix timestamp)
+    pub exp: usize,
+    /// Issued at (Unix timestamp)
+    pub iat: usize,
+}
+
+impl UnsubscribeClaims {
+    pub fn user_id(&self) -> Result<Uuid, uuid::Error> {
+        Uuid::parse_str(&self.sub)
+    }
+}
+
+/// Creates the unsubscribe token for an email about `event`
+pub fn create_unsubscribe_token(
+    user_id: Uuid,
+    event: &str,
+    config: &Config,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let expires_at = now
+        .checked_add_signed(Duration::days(UNSUBSCRIBE_TOKEN_DAYS))
+        .expect("valid timestamp");
+
+    let claims = UnsubscribeClaims {
+        sub: user_id.to_string(),
+        event: event.to_string(),
+        scope: UNSUBSCRIBE_SCOPE.to_string(),
+        exp: expires_at.timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+
+    encode(
+        &signing_header(config),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+}
+
+/// Verifies an unsubscribe token and returns the claims
+pub fn verify_unsubscribe_token(
+    token: &str,
+    config: &Config,
+) -> Result<UnsubscribeClaims, jsonwebtoken::errors::Error> {
+    let token_data = decode_with_rotation::<UnsubscribeClaims>(token, config)?;
+
+    if token_data.claims.scope != UNSUBSCRIBE_SCOPE {
+        return Err(jsonwebtoken::errors::Error::from(ErrorKind::InvalidToken));
+    }
+
+    Ok(token_data.claims)
+}
+
+// ============================================================================
+// FILE DOWNLOAD TOKENS
+// ============================================================================
+
+/// Scope claim of file download tokens
+pub const FILE_DOWNLOAD_SCOPE: &str = "file:download";
+
+/// Claims for file download tokens - temporary links to a stored object on
+/// storage drivers that cannot presign URLs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileDownloadClaims {
+    /// Subject (the storage key)
+    pub sub: String,
+    /// Token scope - always "file:download"
+    pub scope: String,
+    /// Expiration time (Unix timestamp)
+    pub exp: usize,
+    /// Issued at (Unix timestamp)
+    pub iat: usize,
+}
+
+/// Creates a token for downloading the object at `key` until `expires_in`
+/// has passed
+pub fn create_file_download_token(
+    key: &str,
+    expires_in: Duration,
+    config: &Config,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let expires_at = now.checked_add_signed(expires_in).expect("valid timestamp");
+
+    let claims = FileDownloadClaims {
+        sub: key.to_string(),
+        scope: FILE_DOWNLOAD_SCOPE.to_string(),
+        exp: expires_at.timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+
+    encode(
+        &signing_header(config),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+}
+
+/// Verifies a file download token and returns the claims
+pub fn verify_file_download_token(
+    token: &str,
+    config: &Config,
+) -> Result<FileDownloadClaims, jsonwebtoken::errors::Error> {
+    let token_data = decode_with_rotation::<FileDownloadClaims>(token, config)?;
+
+    if token_data.claims.scope != FILE_DOWNLOAD_SCOPE {
+        return Err(jsonwebtoken::errors::Error::from(ErrorKind::InvalidToken));
+    }
+
+    Ok(token_data.claims)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::user::CaptchaMode;
+    use crate::services::storage::StorageDriver;
+
+    fn test_config(secret: &str, previous: &[&str]) -> Config {
+        Config {
+            app_env: "test".to_string(),
+            app_port: 3000,
+            app_base_url: "http://localhost:3000".to_string(),
+            frontend_url: "http://localhost:3001".to_string(),
+            database_url: String::new(),
+            database_max_connections: 1,
+            redis_url: String::new(),
+            jwt_secret: secret.to_string(),
+            jwt_previous_secrets: previous.iter().map(|s| s.to_string()).collect(),
+            jwt_access_expiry: 900,
+            jwt_refresh_expiry: 604800,
+            google_client_id: None,
+            google_client_secret: None,
+            linkedin_client_id: None,
+            linkedin_client_secret: None,
+            s3_endpoint: String::new(),
+            s3_access_key: String::new(),
+            s3_secret_key: String::new(),
+            s3_bucket: String::new(),
+            s3_region: String::new(),
+            s3_public_url: String::new(),
+            storage_driver: StorageDriver::Memory,
+            storage_local_dir: String::new(),
+            smtp_host: String::new(),
+            smtp_port: 1025,
+            smtp_user: None,
+            smtp_password: None,
+            smtp_from: String::new(),
+            public_stats_enabled: true,
+            platform_timezone: "America/Santiago".to_string(),
+            job_alert_batch_size: 200,
+            job_alert_min_score: 60,
+            max_concurrent_requests: 10,
+            request_timeout_secs: 15,
+            log_pii: false,
+            log_redacted_params: Vec::new(),
+            captcha_mode: CaptchaMode::Disabled,
+            captcha_secret: None,
+            captcha_verify_url: None,
+            pow_difficulty: 18,
+        }
+    }
+
+    #[test]
+    fn test_token_carries_primary_kid() {
+        let config = test_config("new-secret", &["old-secret"]);
+        let (token, _) =
+            create_access_token(Uuid::new_v4(), "a@test.cl", UserType::JobSeeker, 0, &config).unwrap();
+
+        assert_eq!(token_key_id(&token), Some(key_id("new-secret")));
+    }
+
+    #[test]
+    fn test_old_token_validates_after_rotation() {
+        let user_id = Uuid::new_v4();
+        let before = test_config("old-secret", &[]);
+        let (token, _) =
+            create_access_token(user_id, "a@test.cl", UserType::JobSeeker, 0, &before).unwrap();
+
+        let after = test_config("new-secret", &["old-secret"]);
+        let claims = verify_access_token(&token, &after).unwrap();
+        assert_eq!(claims.user_id().unwrap(), user_id);
+
+        // Once the old secret is dropped the token stops working
+        let dropped = test_config("new-secret", &[]);
+        assert!(verify_access_token(&token, &dropped).is_err());
+    }
+
+    #[test]
+    fn test_token_carries_version() {
+        let config = test_config("secret", &[]);
+        let (token, _) =
+            create_access_token(Uuid::new_v4(), "a@test.cl", UserType::JobSeeker, 3, &config)
+                .unwrap();
+
+        assert_eq!(verify_access_token(&token, &config).unwrap().ver, 3);
+    }
+
+    #[test]
+    fn test_token_without_version_defaults_to_zero() {
+        let config = test_config("secret", &[]);
+        let claims = serde_json::json!({
+            "sub": Uuid::new_v4().to_string(),
+            "email": "a@test.cl",
+            "user_type": "job_seeker",
+            "exp": Utc::now().timestamp() + 600,
+            "iat": Utc::now().timestamp(),
+            "jti": Uuid::new_v4().to_string(),
+        });
+        let token = encode(
+            &signing_header(&config),
+            &claims,
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+
+        assert_eq!(verify_access_token(&token, &config).unwrap().ver, 0);
+    }
+
+    #[test]
+    fn test_unknown_kid_is_rejected() {
+        let config = test_config("secret", &[]);
+        let claims = Claims {
+            sub: Uuid::new_v4().to_string(),
+            email: "a@test.cl".to_string(),
+            user_type: "job_seeker".to_string(),
+            exp: (Utc::now().timestamp() + 600) as usize,
+            iat: Utc::now().timestamp() as usize,
+            jti: Uuid::new_v4().to_string(),
+            ver: 0,
+        };
+        let header = Header {
+            kid: Some("0000000000000000".to_string()),
+            ..Header::default()
+        };
+        // Correct secret, but the kid does not match any configured key
+        let token = encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+
+        assert!(verify_access_token(&token, &config).is_err());
+    }
+
+    #[test]
+    fn test_legacy_token_without_kid_still_validates() {
+        let config = test_config("new-secret", &["old-secret"]);
+        let claims = Claims {
+            sub: Uuid::new_v4().to_string(),
+            email: "a@test.cl".to_string(),
+            user_type: "job_seeker".to_string(),
+            exp: (Utc::now().timestamp() + 600) as usize,
+            iat: Utc::now().timestamp() as usize,
+            jti: Uuid::new_v4().to_string(),
+            ver: 0,
+        };
+        let token =
+            encode(&Header::default(), &claims, &EncodingKey::from_secret(b"old-secret")).unwrap();
+
+        assert!(verify_access_token(&token, &config).is_ok());
+    }
+
+    #[test]
+    fn test_impersonation_token_follows_rotation() {
+        let before = test_config("old-secret", &[]);
+        let (token, jti, _) =
+            create_impersonation_token(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), &before)
+                .unwrap();
+        assert_eq!(token_key_id(&token), Some(key_id("old-secret")));
+
+        let after = test_config("new-secret", &["old-secret"]);
+        let claims = verify_impersonation_token(&token, &after).unwrap();
+        assert_eq!(claims.jti_uuid().unwrap(), jti);
+    }
 
     #[test]
     fn test_create_refresh_token_length() {
@@ -218,4 +673,66 @@ mod tests {
         let hash = hash_token(token);
         assert_eq!(hash.len(), 64); // SHA256 produces 32 bytes = 64 hex chars
     }
+
+    #[test]
+    fn test_kiosk_token_is_not_an_access_token() {
+        let config = test_config("secret", &[]);
+        let (token, jti, _) =
+            create_kiosk_token(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), 2, &config)
+                .unwrap();
+
+        let claims = verify_kiosk_token(&token, &config).unwrap();
+        assert_eq!(claims.jti, jti.to_string());
+        assert_eq!(claims.scope, KIOSK_SCOPE);
+        assert_eq!(claims.ver, 2);
+
+        // Neither a session nor an impersonation token
+        assert!(verify_access_token(&token, &config).is_err());
+        assert!(verify_impersonation_token(&token, &config).is_err());
+
+        // And session tokens are not kiosk tokens
+        let (access, _) =
+            create_access_token(Uuid::new_v4(), "a@test.cl", UserType::OmilMember, 0, &config)
+                .unwrap();
+        assert!(verify_kiosk_token(&access, &config).is_err());
+    }
+
+    #[test]
+    fn test_unsubscribe_token_validation() {
+        let user_id = Uuid::new_v4();
+        let config = test_config("secret", &[]);
+        let token = create_unsubscribe_token(user_id, "digest", &config).unwrap();
+
+        let claims = verify_unsubscribe_token(&token, &config).unwrap();
+        assert_eq!(claims.user_id().unwrap(), user_id);
+        assert_eq!(claims.event, "digest");
+        assert_eq!(claims.scope, UNSUBSCRIBE_SCOPE);
+
+        // Signed with another secret, or tampered with
+        assert!(verify_unsubscribe_token(&token, &test_config("other", &[])).is_err());
+        let mut tampered = token.clone();
+        tampered.pop();
+        assert!(verify_unsubscribe_token(&tampered, &config).is_err());
+
+        // Expired
+        let expired = encode(
+            &signing_header(&config),
+            &UnsubscribeClaims {
+                sub: user_id.to_string(),
+                event: "digest".to_string(),
+                scope: UNSUBSCRIBE_SCOPE.to_string(),
+                exp: (Utc::now().timestamp() - 3600) as usize,
+                iat: (Utc::now().timestamp() - 7200) as usize,
+            },
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        assert!(verify_unsubscribe_token(&expired, &config).is_err());
+
+        // Not a session token, and session tokens don't unsubscribe
+        assert!(verify_access_token(&token, &config).is_err());
+        let (access, _) =
+            create_access_token(user_id, "a@test.cl", UserType::JobSeeker, 0, &config).unwrap();
+        assert!(verify_unsubscribe_token(&access, &config).is_err());
+    }
 }
@@ -1,5 +1,9 @@
+pub mod fields;
 pub mod jwt;
+pub mod normalize;
 pub mod password;
+pub mod redact;
+pub mod rut;
 pub mod validation;
 
 pub use jwt::*;
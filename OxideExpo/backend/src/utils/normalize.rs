@@ -0,0 +1,144 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use validator::ValidationError;
+
+use super::rut;
+
+/// Canonicalizes request fields before validation so the same value always
+/// reaches the database in the same shape.
+pub trait Normalize {
+    fn normalize(&mut self);
+}
+
+/// Trims surrounding whitespace in place
+pub fn trim(value: &mut String) {
+    let trimmed = value.trim();
+    if trimmed.len() != value.len() {
+        *value = trimmed.to_string();
+    }
+}
+
+pub fn trim_opt(value: &mut Option<String>) {
+    if let Some(v) = value.as_mut() {
+        trim(v);
+    }
+}
+
+/// Trims and lowercases an email address
+pub fn email(value: &mut String) {
+    *value = value.trim().to_lowercase();
+}
+
+/// Like `email`, and a blank address becomes None
+pub fn email_opt(value: &mut Option<String>) {
+    *value = value
+        .take()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty());
+}
+
+/// Chilean numbers become "+56" plus the 9-digit national number. Anything
+/// else keeps its digits (and a leading "+") for validate_phone to judge.
+pub fn phone(value: &str) -> String {
+    let has_plus = value.trim_start().starts_with('+');
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    match (has_plus, digits.len()) {
+        (_, 11) if digits.starts_with("56") => format!("+{}", digits),
+        (false, 9) => format!("+56{}", digits),
+        (true, _) => format!("+{}", digits),
+        (false, _) => digits,
+    }
+}
+
+pub fn phone_opt(value: &mut Option<String>) {
+    if let Some(v) = value.as_mut() {
+        *v = if v.trim().is_empty() { String::new() } else { phone(v) };
+    }
+}
+
+pub fn rut_opt(value: &mut Option<String>) {
+    if let Some(v) = value.as_mut() {
+        *v = rut::normalize(v);
+    }
+}
+
+static CHILEAN_PHONE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\+56\d{9}$").expect("Failed to compile CHILEAN_PHONE_REGEX"));
+
+static INTERNATIONAL_PHONE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\+[1-9]\d{7,14}$").expect("Failed to compile INTERNATIONAL_PHONE_REGEX")
+});
+
+/// Validator for normalized phone numbers: +56 and 9 digits, or another E.164 number
+/// Empty values pass so optional fields can be cleared.
+pub fn validate_phone(value: &str) -> Result<(), ValidationError> {
+    let valid = if value.starts_with("+56") {
+        CHILEAN_PHONE_REGEX.is_match(value)
+    } else {
+        value.is_empty() || INTERNATIONAL_PHONE_REGEX.is_match(value)
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_phone").with_message("Invalid phone number".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim() {
+        let mut name = "  María ".to_string();
+        trim(&mut name);
+        assert_eq!(name, "María");
+
+        let mut none: Option<String> = None;
+        trim_opt(&mut none);
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn test_email_lowercased() {
+        let mut value = " Maria.Perez@Example.CL ".to_string();
+        email(&mut value);
+        assert_eq!(value, "maria.perez@example.cl");
+
+        let mut value = Some(" Maria.Perez@Example.CL ".to_string());
+        email_opt(&mut value);
+        assert_eq!(value.as_deref(), Some("maria.perez@example.cl"));
+
+        let mut blank = Some("   ".to_string());
+        email_opt(&mut blank);
+        assert_eq!(blank, None);
+    }
+
+    #[test]
+    fn test_phone_chilean_formats() {
+        assert_eq!(phone("912345678"), "+56912345678");
+        assert_eq!(phone("9 1234 5678"), "+56912345678");
+        assert_eq!(phone("+56 9 1234 5678"), "+56912345678");
+        assert_eq!(phone("56912345678"), "+56912345678");
+        assert_eq!(phone("(2) 2345-6789"), "+56223456789");
+    }
+
+    #[test]
+    fn test_phone_validation() {
+        assert!(validate_phone("+56912345678").is_ok());
+        assert!(validate_phone("+5491123456789").is_ok());
+        assert!(validate_phone("").is_ok());
+        assert!(validate_phone(&phone("12345")).is_err());
+        assert!(validate_phone(&phone("+56 9 1234")).is_err());
+        assert!(validate_phone(&phone("9123456789012345")).is_err());
+    }
+
+    #[test]
+    fn test_rut_opt() {
+        let mut value = Some(" 12.345.678-5".to_string());
+        rut_opt(&mut value);
+        assert_eq!(value.as_deref(), Some("12345678-5"));
+    }
+}
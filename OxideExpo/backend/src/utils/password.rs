@@ -22,7 +22,17 @@ pub fn hash_password(password: &str) -> Result<String, PasswordError> {
         .map_err(|e| PasswordError::HashError(e.to_string()))
 }
 
-pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError> {
+/// Checks `password` against a stored hash. An account without a hash, or
+/// with an empty one, never verifies, whatever password is given.
+pub fn verify_password(password: &str, hash: Option<&str>) -> Result<bool, PasswordError> {
+    let hash = match hash {
+        Some(hash) if !hash.is_empty() => hash,
+        _ => return Ok(false),
+    };
+    if password.is_empty() {
+        return Ok(false);
+    }
+
     let parsed_hash =
         PasswordHash::new(hash).map_err(|e| PasswordError::VerifyError(e.to_string()))?;
 
@@ -30,3 +40,34 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError
         .verify_password(password.as_bytes(), &parsed_hash)
         .is_ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_round_trip() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(verify_password("correct horse", Some(&hash)).unwrap());
+        assert!(!verify_password("wrong horse", Some(&hash)).unwrap());
+    }
+
+    #[test]
+    fn test_missing_or_empty_hash_never_verifies() {
+        assert!(!verify_password("", None).unwrap());
+        assert!(!verify_password("anything", None).unwrap());
+        assert!(!verify_password("", Some("")).unwrap());
+        assert!(!verify_password("anything", Some("")).unwrap());
+    }
+
+    #[test]
+    fn test_empty_password_never_verifies() {
+        let hash = hash_password("").unwrap();
+        assert!(!verify_password("", Some(&hash)).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_hash_is_an_error() {
+        assert!(verify_password("anything", Some("not-a-phc-string")).is_err());
+    }
+}
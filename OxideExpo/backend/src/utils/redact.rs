@@ -0,0 +1,132 @@
+//! Masking of personal data written to logs. Unless `Config::log_pii` is
+//! set, emails, RUTs and the values of sensitive query parameters are logged
+//! masked. Database errors are always logged without the values they carry.
+
+use crate::utils::rut;
+
+/// Stands in for a removed value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Masks personal data for the logs, see `Config::redactor`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Redactor {
+    log_pii: bool,
+}
+
+impl Redactor {
+    /// With `log_pii` set, values are logged as is
+    pub fn new(log_pii: bool) -> Self {
+        Self { log_pii }
+    }
+
+    /// An email for the logs: "fernanda@example.cl" -> "f***@example.cl"
+    pub fn email(self, email: &str) -> String {
+        if self.log_pii {
+            email.to_string()
+        } else {
+            mask_email(email)
+        }
+    }
+
+    /// A RUT for the logs: "12.345.678-5" -> "**.***.**-5"
+    pub fn rut(self, rut: &str) -> String {
+        if self.log_pii {
+            rut.to_string()
+        } else {
+            mask_rut(rut)
+        }
+    }
+
+    /// A request URI for the logs, with the values of `params` replaced
+    pub fn uri(self, uri: &axum::http::Uri, params: &[String]) -> String {
+        match uri.query() {
+            Some(query) if !self.log_pii => {
+                format!("{}?{}", uri.path(), redact_query(query, params))
+            }
+            _ => uri.to_string(),
+        }
+    }
+}
+
+/// A database error for the logs. Postgres puts the offending values in the
+/// message and detail, e.g. "Key (email)=(...) already exists", so only the
+/// error code, constraint and table are kept.
+pub fn db_error(err: &sqlx::Error) -> String {
+    match err {
+        sqlx::Error::Database(db) => {
+            let mut description = format!(
+                "database error {} ({:?})",
+                db.code().as_deref().unwrap_or("unknown"),
+                db.kind()
+            );
+            if let Some(constraint) = db.constraint() {
+                description.push_str(&format!(" on constraint {}", constraint));
+            }
+            if let Some(table) = db.table() {
+                description.push_str(&format!(" in table {}", table));
+            }
+            description
+        }
+        other => other.to_string(),
+    }
+}
+
+fn mask_email(email: &str) -> String {
+    match email.trim().split_once('@') {
+        Some((local, domain)) => {
+            let first = local.chars().next().map(String::from).unwrap_or_default();
+            format!("{}***@{}", first, domain)
+        }
+        None => REDACTED.to_string(),
+    }
+}
+
+fn mask_rut(value: &str) -> String {
+    match rut::normalize(value).split_once('-') {
+        Some((_, dv)) if dv.len() == 1 => format!("**.***.**-{}", dv),
+        _ => REDACTED.to_string(),
+    }
+}
+
+/// Replaces the values of the named parameters, matched case-insensitively
+fn redact_query(query: &str, params: &[String]) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if params.iter().any(|p| p.eq_ignore_ascii_case(name)) => {
+                format!("{}={}", name, REDACTED)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(mask_email("fernanda.rojas@example.cl"), "f***@example.cl");
+        assert_eq!(mask_email("@example.cl"), "***@example.cl");
+        assert_eq!(mask_email("not an email"), REDACTED);
+    }
+
+    #[test]
+    fn test_mask_rut() {
+        assert_eq!(mask_rut("12.345.678-5"), "**.***.**-5");
+        assert_eq!(mask_rut("7654321k"), "**.***.**-K");
+        assert_eq!(mask_rut("abc"), REDACTED);
+    }
+
+    #[test]
+    fn test_redact_query() {
+        let params = vec!["email".to_string(), "search".to_string()];
+        assert_eq!(
+            redact_query("search=ana%40example.cl&page=2&Email=x", &params),
+            "search=[REDACTED]&page=2&Email=[REDACTED]"
+        );
+        assert_eq!(redact_query("page=2&flag", &params), "page=2&flag");
+    }
+}
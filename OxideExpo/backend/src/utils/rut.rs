@@ -0,0 +1,107 @@
+//! Chilean RUT (Rol Único Tributario) parsing and check-digit validation
+
+use validator::ValidationError;
+
+/// Computes the modulo-11 check digit for a RUT body
+/// Digits are weighted 2..=7 from the right, cycling
+pub fn check_digit(body: u32) -> char {
+    let mut sum = 0;
+    let mut weight = 2;
+    let mut n = body;
+
+    while n > 0 {
+        sum += (n % 10) * weight;
+        n /= 10;
+        weight = if weight == 7 { 2 } else { weight + 1 };
+    }
+
+    match 11 - (sum % 11) {
+        11 => '0',
+        10 => 'K',
+        d => char::from_digit(d, 10).expect("single digit"),
+    }
+}
+
+/// Canonical form "12345678-K": no dots or spaces, uppercase K, hyphen before the
+/// check digit. Input that doesn't look like a RUT is returned trimmed and unchanged.
+pub fn normalize(rut: &str) -> String {
+    let compact: String = rut
+        .chars()
+        .filter(|c| !matches!(c, '.' | ' ' | '-'))
+        .collect::<String>()
+        .to_uppercase();
+
+    if compact.len() < 2 || !compact[..compact.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+        return rut.trim().to_string();
+    }
+
+    let (body, dv) = compact.split_at(compact.len() - 1);
+    format!("{}-{}", body.trim_start_matches('0'), dv)
+}
+
+/// Whether a RUT (any common formatting) has a correct check digit
+pub fn is_valid(rut: &str) -> bool {
+    let canonical = normalize(rut);
+    let Some((body, dv)) = canonical.split_once('-') else {
+        return false;
+    };
+
+    if body.is_empty() || body.len() > 8 {
+        return false;
+    }
+
+    let mut dv_chars = dv.chars();
+    match (body.parse::<u32>(), dv_chars.next(), dv_chars.next()) {
+        (Ok(n), Some(dv), None) => check_digit(n) == dv,
+        _ => false,
+    }
+}
+
+/// Validator for `#[validate(custom(function = "validate_rut"))]`
+/// Empty values pass so optional fields can be cleared.
+pub fn validate_rut(rut: &str) -> Result<(), ValidationError> {
+    if rut.is_empty() || is_valid(rut) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_rut").with_message("Invalid RUT".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_digit() {
+        assert_eq!(check_digit(12_345_678), '5');
+        assert_eq!(check_digit(11_111_111), '1');
+        assert_eq!(check_digit(10_000_013), 'K');
+        assert_eq!(check_digit(6_000_005), '0');
+    }
+
+    #[test]
+    fn test_normalize_formats() {
+        assert_eq!(normalize("12.345.678-5"), "12345678-5");
+        assert_eq!(normalize(" 12345678-5 "), "12345678-5");
+        assert_eq!(normalize("123456785"), "12345678-5");
+        assert_eq!(normalize("10.000.013-k"), "10000013-K");
+        assert_eq!(normalize("not a rut"), "not a rut");
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid("12.345.678-5"));
+        assert!(is_valid("10000013-k"));
+        assert!(!is_valid("12.345.678-9"));
+        assert!(!is_valid("12345678-X"));
+        assert!(!is_valid("123456789-0"));
+        assert!(!is_valid(""));
+    }
+
+    #[test]
+    fn test_validate_rut() {
+        assert!(validate_rut("").is_ok());
+        assert!(validate_rut("11.111.111-1").is_ok());
+        assert!(validate_rut("11.111.111-2").is_err());
+    }
+}
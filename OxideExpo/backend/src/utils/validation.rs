@@ -1,5 +1,7 @@
+use chrono::{DateTime, Duration, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use validator::ValidationError;
 
 /// Regex pattern for valid company sizes
 /// Valid values: '1-10', '11-50', '51-200', '201-500', '500+'
@@ -7,3 +9,56 @@ pub static COMPANY_SIZE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^(1-10|11-50|51-200|201-500|500\+)$")
         .expect("Failed to compile COMPANY_SIZE_REGEX")
 });
+
+/// Mask an email address for display to users who don't own it
+/// e.g. "juan.perez@example.com" -> "j*********@example.com"
+pub fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            let mut chars = local.chars();
+            let first = chars.next().map(String::from).unwrap_or_default();
+            let hidden = "*".repeat(chars.count().max(1));
+            format!("{}{}@{}", first, hidden, domain)
+        }
+        None => "***".to_string(),
+    }
+}
+
+/// How far ahead a job posting may be scheduled for publication
+pub const MAX_PUBLISH_AHEAD_DAYS: i64 = 90;
+
+/// Validate a scheduled publish time: in the future, at most 90 days out
+pub fn validate_publish_at(publish_at: &DateTime<Utc>) -> Result<(), ValidationError> {
+    let now = Utc::now();
+
+    if *publish_at <= now {
+        return Err(ValidationError::new("publish_at_past")
+            .with_message("Publish time must be in the future".into()));
+    }
+    if *publish_at > now + Duration::days(MAX_PUBLISH_AHEAD_DAYS) {
+        return Err(ValidationError::new("publish_at_too_far")
+            .with_message("Publish time must be within 90 days".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_publish_at() {
+        let now = Utc::now();
+        assert!(validate_publish_at(&(now + Duration::hours(1))).is_ok());
+        assert!(validate_publish_at(&(now - Duration::minutes(1))).is_err());
+        assert!(validate_publish_at(&(now + Duration::days(91))).is_err());
+    }
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(mask_email("juan.perez@example.com"), "j*********@example.com");
+        assert_eq!(mask_email("a@example.com"), "a*@example.com");
+        assert_eq!(mask_email("not-an-email"), "***");
+    }
+}
@@ -0,0 +1,313 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, TestResponse, TestUser, TEST_PASSWORD};
+use empleos_inclusivos_backend::services::account_deletion;
+use empleos_inclusivos_backend::utils::hash_token;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+async fn login(app: &TestApp, user: &TestUser) -> TestResponse {
+    app.post(
+        "/api/auth/login",
+        None,
+        json!({ "email": user.email, "password": TEST_PASSWORD }),
+    )
+    .await
+}
+
+async fn delete_account(app: &TestApp, user: &TestUser) -> TestResponse {
+    app.request(
+        Method::DELETE,
+        "/api/me/account",
+        Some(user),
+        Some(json!({ "current_password": TEST_PASSWORD })),
+    )
+    .await
+}
+
+/// The user as the tokens from a response sign in
+fn with_tokens(user: &TestUser, body: &Value) -> TestUser {
+    TestUser {
+        id: user.id,
+        email: user.email.clone(),
+        user_type: user.user_type,
+        token: body["access_token"].as_str().unwrap().to_string(),
+    }
+}
+
+async fn account_status(app: &TestApp, user: &TestUser) -> String {
+    sqlx::query_scalar("SELECT account_status::text FROM users WHERE id = $1")
+        .bind(user.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn is_on_hold(app: &TestApp, application_id: uuid::Uuid) -> bool {
+    sqlx::query_scalar("SELECT is_on_hold FROM job_applications WHERE id = $1")
+        .bind(application_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_deletion_hides_the_seeker_immediately(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+
+    let other_session = login(&app, &seeker).await;
+    assert_eq!(other_session.status, StatusCode::OK);
+    sqlx::query(
+        "INSERT INTO calendar_tokens (user_id, token_prefix, token_hash) VALUES ($1, 'cal_', $2)",
+    )
+    .bind(seeker.id)
+    .bind(hash_token("calendar-token"))
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let candidates_url = format!("/api/me/jobs/{}/recommended-candidates?limit=100", job_id);
+    let res = app.get(&candidates_url, Some(&company.owner)).await;
+    assert_eq!(res.body["candidates"].as_array().unwrap().len(), 1);
+
+    let res = delete_account(&app, &seeker).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body["deletion_scheduled_at"].is_string());
+    let current = with_tokens(&seeker, &res.body);
+
+    // Gone from search and invitations, applications on hold
+    let res = app.get(&candidates_url, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["candidates"], json!([]));
+    let job_id = app.create_active_job(&company).await;
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+    assert!(is_on_hold(&app, application_id).await);
+
+    // Only the session that asked stays signed in
+    let res = app.get("/api/auth/me", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let calendar_tokens: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM calendar_tokens WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(calendar_tokens, 0);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": other_session.body["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app.get("/api/auth/me", Some(&current)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["account_status"], "pending_deletion");
+    assert!(res.body["deletion_scheduled_at"].is_string());
+
+    // Logging in during the grace period works and shows the pending state
+    let res = login(&app, &seeker).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["user"]["account_status"], "pending_deletion");
+
+    let res = delete_account(&app, &current).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_restore_puts_the_account_back(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+
+    let res = delete_account(&app, &seeker).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let current = with_tokens(&seeker, &res.body);
+
+    // The emailed token can't be read back; swap in a known one
+    sqlx::query("UPDATE account_deletions SET restore_token_hash = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(hash_token("restore-me"))
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app.post("/api/auth/account/restore", None, json!({})).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+    let res = app
+        .post(
+            "/api/auth/account/restore",
+            None,
+            json!({ "token": "wrong" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/auth/account/restore",
+            None,
+            json!({ "token": "restore-me" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(account_status(&app, &seeker).await, "active");
+    assert!(!is_on_hold(&app, application_id).await);
+
+    let res = app.get("/api/auth/me", Some(&current)).await;
+    assert_eq!(res.body["account_status"], "active");
+    assert_eq!(res.body["deletion_scheduled_at"], Value::Null);
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/recommended-candidates?limit=100", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.body["candidates"].as_array().unwrap().len(), 1);
+
+    // Signed in, confirming is enough
+    let res = delete_account(&app, &current).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let current = with_tokens(&seeker, &res.body);
+    let res = app
+        .post("/api/auth/account/restore", Some(&current), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(account_status(&app, &seeker).await, "active");
+
+    let res = app
+        .post("/api/auth/account/restore", Some(&current), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_scrub_after_the_grace_period(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+    sqlx::query("UPDATE job_applications SET cover_letter = 'Hola' WHERE id = $1")
+        .bind(application_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO uploaded_files (user_id, file_type, original_filename, storage_path)
+        VALUES ($1, 'cv', 'cv.pdf', 'cvs/scrubbed-cv.pdf')
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, followup_type, title, content)
+        VALUES ($1, $1, 'profile_update', 'Llamada', 'Vive en Maipú con su hermana')
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = delete_account(&app, &seeker).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Nothing is due yet
+    assert_eq!(account_deletion::scrub_due(app.db()).await.unwrap(), 0);
+
+    sqlx::query(
+        "UPDATE account_deletions SET scheduled_for = NOW() - INTERVAL '1 minute' WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    assert_eq!(account_deletion::scrub_due(app.db()).await.unwrap(), 1);
+
+    let user: (Option<String>, String, String) =
+        sqlx::query_as("SELECT email, first_name, account_status::text FROM users WHERE id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(
+        user,
+        (None, "Cuenta".to_string(), "deactivated".to_string())
+    );
+
+    let profile: (Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT professional_headline, cv_url FROM job_seeker_profiles WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(profile, (None, None));
+    let experiences: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM work_experiences WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(experiences, 0);
+
+    // The application stays for the company's history, without free text
+    let application: (Option<String>, bool, String) = sqlx::query_as(
+        "SELECT cover_letter, anonymized_at IS NOT NULL, status::text FROM job_applications WHERE id = $1",
+    )
+    .bind(application_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(application, (None, true, "submitted".to_string()));
+
+    // Stored files are queued for removal from storage
+    let queued: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM application_document_deletions WHERE file_key = 'cvs/scrubbed-cv.pdf')",
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(queued);
+
+    // OMIL followups keep their type without the free text
+    let followup: (Option<String>, String, String) = sqlx::query_as(
+        "SELECT title, content, followup_type FROM job_seeker_followups WHERE job_seeker_id = $1",
+    )
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(
+        followup,
+        (None, String::new(), "profile_update".to_string())
+    );
+
+    let res = login(&app, &seeker).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app
+        .post("/api/auth/account/restore", Some(&seeker), json!({}))
+        .await;
+    assert_ne!(res.status, StatusCode::OK);
+    assert_eq!(account_deletion::scrub_due(app.db()).await.unwrap(), 0);
+}
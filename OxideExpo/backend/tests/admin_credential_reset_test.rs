@@ -0,0 +1,218 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser, TEST_PASSWORD};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+async fn login(app: &TestApp, user: &TestUser, password: &str) -> common::TestResponse {
+    app.post(
+        "/api/auth/login",
+        None,
+        json!({ "email": user.email, "password": password }),
+    )
+    .await
+}
+
+async fn force_reset(
+    app: &TestApp,
+    admin: &TestUser,
+    user: &TestUser,
+    body: Value,
+) -> common::TestResponse {
+    app.post(
+        &format!("/api/admin/users/{}/force-password-reset", user.id),
+        Some(admin),
+        body,
+    )
+    .await
+}
+
+async fn account_status(app: &TestApp, user: &TestUser) -> String {
+    sqlx::query_scalar("SELECT account_status::text FROM users WHERE id = $1")
+        .bind(user.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Admin with a role below super admin
+async fn create_moderator(app: &TestApp) -> TestUser {
+    let admin = app.create_admin().await;
+    sqlx::query("UPDATE admins SET admin_role = 'moderator' WHERE user_id = $1")
+        .bind(admin.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    admin
+}
+
+#[sqlx::test]
+async fn test_forced_reset_blocks_login_until_completed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = login(&app, &seeker, TEST_PASSWORD).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let refresh_token = res.body["refresh_token"].clone();
+
+    let res = force_reset(&app, &admin, &seeker, json!({ "deliver": false })).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let reset_token = res.body["token"].as_str().unwrap().to_string();
+    assert_eq!(
+        account_status(&app, &seeker).await,
+        "password_reset_required"
+    );
+
+    // The old password, sessions and refresh tokens all stop working
+    let res = login(&app, &seeker, TEST_PASSWORD).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app.get("/api/auth/me", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+
+    let res = app
+        .post(
+            "/api/auth/password/reset",
+            None,
+            json!({ "token": reset_token, "new_password": "NuevaClave2024" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(account_status(&app, &seeker).await, "active");
+
+    let res = login(&app, &seeker, "NuevaClave2024").await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let logged: Value = sqlx::query_scalar(
+        "SELECT details FROM admin_audit_logs WHERE action_type = 'force_password_reset' AND entity_id = $1",
+    )
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(logged, json!({ "delivery": "out_of_band" }));
+}
+
+#[sqlx::test]
+async fn test_reset_token_is_shown_once(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+
+    // Emailed tokens are never returned
+    let res = force_reset(&app, &admin, &seeker, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["delivery"], "email");
+    assert_eq!(res.body["token"], Value::Null);
+
+    let res = force_reset(&app, &admin, &seeker, json!({ "deliver": false })).await;
+    assert_eq!(res.body["delivery"], "out_of_band");
+    let first = res.body["token"].as_str().unwrap().to_string();
+
+    // Issuing again replaces the token that was shown
+    let res = force_reset(&app, &admin, &seeker, json!({ "deliver": false })).await;
+    let second = res.body["token"].as_str().unwrap().to_string();
+    assert_ne!(first, second);
+
+    let stored: String =
+        sqlx::query_scalar("SELECT token_hash FROM password_reset_tokens WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_ne!(stored, second);
+
+    let reset = |token: String| {
+        let app = &app;
+        async move {
+            app.post(
+                "/api/auth/password/reset",
+                None,
+                json!({ "token": token, "new_password": "NuevaClave2024" }),
+            )
+            .await
+        }
+    };
+    assert_eq!(reset(first).await.status, StatusCode::BAD_REQUEST);
+    assert_eq!(reset(second.clone()).await.status, StatusCode::OK);
+    assert_eq!(reset(second).await.status, StatusCode::BAD_REQUEST);
+
+    // Verification tokens work the same way for unverified accounts
+    let uri = format!("/api/admin/users/{}/resend-verification", seeker.id);
+    let res = app
+        .post(&uri, Some(&admin), json!({ "deliver": false }))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE users SET email_verified_at = NULL, account_status = 'pending_verification' WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app
+        .post(&uri, Some(&admin), json!({ "deliver": false }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .post(
+            "/api/auth/email/verify",
+            None,
+            json!({ "token": res.body["token"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(account_status(&app, &seeker).await, "active");
+}
+
+#[sqlx::test]
+async fn test_only_super_admins_reset_admins(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let super_admin = app.create_admin().await;
+    let moderator = create_moderator(&app).await;
+    let other_admin = app.create_admin().await;
+
+    let res = force_reset(&app, &moderator, &other_admin, json!({ "deliver": false })).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app
+        .post(
+            &format!("/api/admin/users/{}/resend-verification", other_admin.id),
+            Some(&moderator),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    assert_eq!(account_status(&app, &other_admin).await, "active");
+
+    // Moderators still handle regular accounts
+    let seeker = app.create_job_seeker().await;
+    let res = force_reset(&app, &moderator, &seeker, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = force_reset(&app, &super_admin, &other_admin, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        account_status(&app, &other_admin).await,
+        "password_reset_required"
+    );
+
+    let res = app
+        .post(
+            &format!(
+                "/api/admin/users/{}/force-password-reset",
+                uuid::Uuid::new_v4()
+            ),
+            Some(&super_admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    body::{Body, BodyDataStream},
+    http::{Request, StatusCode},
+};
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::services::admin_events;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+/// An open `GET /api/admin/events` stream
+struct EventStream {
+    body: BodyDataStream,
+    buffer: String,
+}
+
+/// One server-sent event
+#[derive(Debug)]
+struct SseEvent {
+    event: String,
+    id: String,
+    data: Value,
+}
+
+async fn connect(app: &TestApp, user: &TestUser, last_event_id: Option<&str>) -> EventStream {
+    let mut request =
+        Request::get("/api/admin/events").header("authorization", format!("Bearer {}", user.token));
+    if let Some(id) = last_event_id {
+        request = request.header("last-event-id", id);
+    }
+    let response = app
+        .router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    EventStream {
+        body: response.into_body().into_data_stream(),
+        buffer: String::new(),
+    }
+}
+
+impl EventStream {
+    /// The next event, or None once the server ends the stream
+    async fn next(&mut self) -> Option<SseEvent> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let raw: String = self.buffer.drain(..end + 2).collect();
+                let mut event = SseEvent {
+                    event: String::new(),
+                    id: String::new(),
+                    data: Value::Null,
+                };
+                for line in raw.lines() {
+                    if let Some(value) = line.strip_prefix("event: ") {
+                        event.event = value.to_string();
+                    } else if let Some(value) = line.strip_prefix("id: ") {
+                        event.id = value.to_string();
+                    } else if let Some(value) = line.strip_prefix("data: ") {
+                        event.data = serde_json::from_str(value).unwrap();
+                    }
+                }
+                // Keep-alive comments carry no event
+                if event.event.is_empty() {
+                    continue;
+                }
+                return Some(event);
+            }
+
+            let chunk = tokio::time::timeout(Duration::from_secs(5), self.body.next())
+                .await
+                .expect("no event within 5 seconds")?
+                .unwrap();
+            self.buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+}
+
+#[sqlx::test]
+async fn test_admin_events_push_queue_counts(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app.get("/api/admin/events", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let mut first = connect(&app, &admin, None).await;
+    let snapshot = first.next().await.unwrap();
+    assert_eq!(snapshot.event, "snapshot");
+    assert_eq!(
+        snapshot.data,
+        json!({ "companies": 0, "jobs": 0, "omils": 0, "flags": 0 })
+    );
+    let mut second = connect(&app, &admin, None).await;
+    assert_eq!(second.next().await.unwrap().event, "snapshot");
+
+    // A company submits a job for review
+    let res = app
+        .post(
+            "/api/me/jobs",
+            Some(&company.owner),
+            json!({
+                "title": "Asistente de bodega",
+                "description": "Recepción y despacho de mercadería, control de inventario",
+                "job_type": "full_time",
+                "work_modality": "on_site",
+                "application_deadline": "2099-12-31",
+                "vacancies": 1,
+                "force": true,
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let job_id = res.body["id"].as_str().unwrap().to_string();
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", job_id),
+            Some(&company.owner),
+            json!({ "status": "pending_approval" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Every connected admin hears about it
+    for stream in [&mut first, &mut second] {
+        let update = stream.next().await.unwrap();
+        assert_eq!(update.event, "queue_counts");
+        assert_eq!(update.data["jobs"], 1);
+        assert_eq!(update.data["companies"], 0);
+    }
+
+    // A new OMIL registration lands in its queue
+    let res = app
+        .post(
+            "/api/auth/register/omil",
+            None,
+            json!({
+                "email": "director@omil-sse.cl",
+                "password": "SecurePass123",
+                "first_name": "Rosa",
+                "last_name": "Muñoz",
+                "organization_name": "OMIL Puerto Montt",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let update = first.next().await.unwrap();
+    assert_eq!(update.data["omils"], 1);
+    assert_eq!(update.data["jobs"], 1);
+
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/approve", job_id),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let update = first.next().await.unwrap();
+    assert_eq!(update.data["jobs"], 0);
+    let last_id = update.id;
+
+    // Reconnecting just starts over from a snapshot
+    let mut reconnected = connect(&app, &admin, Some(&last_id)).await;
+    let snapshot = reconnected.next().await.unwrap();
+    assert_eq!(snapshot.event, "snapshot");
+    assert_eq!(snapshot.id, last_id);
+    assert_eq!(
+        snapshot.data,
+        json!({ "companies": 0, "jobs": 0, "omils": 1, "flags": 0 })
+    );
+}
+
+#[sqlx::test]
+async fn test_slow_admin_event_consumer_is_disconnected(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let mut stream = connect(&app, &admin, None).await;
+    assert_eq!(stream.next().await.unwrap().event, "snapshot");
+
+    // Far more changes than the stream buffers while nobody reads it
+    for _ in 0..40 {
+        sqlx::query(
+            "UPDATE company_profiles SET status = CASE WHEN status = 'active' THEN 'pending_approval'::organization_status ELSE 'active'::organization_status END WHERE id = $1",
+        )
+        .bind(company.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+        admin_events::publish(&app.state).await;
+    }
+
+    // The publisher never waited on the reader, which is now cut off
+    assert!(stream.next().await.is_none());
+
+    // Other streams carry on
+    let mut fresh = connect(&app, &admin, None).await;
+    assert_eq!(fresh.next().await.unwrap().event, "snapshot");
+    sqlx::query("UPDATE company_profiles SET status = 'pending_approval' WHERE id = $1")
+        .bind(company.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    admin_events::publish(&app.state).await;
+    assert_eq!(fresh.next().await.unwrap().data["companies"], 1);
+}
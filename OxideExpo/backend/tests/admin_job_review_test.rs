@@ -0,0 +1,177 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn reference_ids(app: &TestApp, table: &str, limit: i64) -> Vec<Uuid> {
+    sqlx::query_scalar(&format!("SELECT id FROM {} ORDER BY name LIMIT $1", table))
+        .bind(limit)
+        .fetch_all(app.db())
+        .await
+        .unwrap()
+}
+
+/// Creates a job with `extra` fields and submits it for approval
+async fn submit_job(app: &TestApp, company: &TestCompany, extra: Value) -> String {
+    let mut job = json!({
+        "title": "Asistente de bodega",
+        "description": "Recepción y despacho de mercadería, control de inventario",
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1,
+        "force": true
+    });
+    for (key, value) in extra.as_object().unwrap() {
+        job[key] = value.clone();
+    }
+
+    let res = app.post("/api/me/jobs", Some(&company.owner), job).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let job_id = res.body["id"].as_str().unwrap().to_string();
+
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", job_id),
+            Some(&company.owner),
+            json!({ "status": "pending_approval" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    job_id
+}
+
+#[sqlx::test]
+async fn test_review_returns_job_with_context(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let skills = reference_ids(&app, "skills", 3).await;
+    let languages = reference_ids(&app, "languages", 1).await;
+
+    // An earlier posting of the same company was turned down
+    let rejected = submit_job(&app, &company, json!({ "title": "Guardia de seguridad" })).await;
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/reject", rejected),
+            Some(&admin),
+            json!({ "rejection_reason": "Descripción insuficiente del cargo" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let job_id = submit_job(
+        &app,
+        &company,
+        json!({
+            "required_skills": [
+                { "skill_id": skills[0], "minimum_proficiency": 3 },
+                { "skill_id": skills[1], "minimum_proficiency": 2 }
+            ],
+            "preferred_skills": [skills[2]],
+            "required_languages": [{ "language_id": languages[0], "minimum_proficiency": 4 }],
+            "disability_accommodations": ["visual", "hearing"]
+        }),
+    )
+    .await;
+
+    let res = app
+        .get(
+            &format!("/api/admin/jobs/{}/review", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app
+        .get(&format!("/api/admin/jobs/{}/review", job_id), Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let review = &res.body;
+    assert_eq!(review["job"]["id"], job_id);
+    assert_eq!(
+        review["job"]["description"],
+        "Recepción y despacho de mercadería, control de inventario"
+    );
+    assert_eq!(review["pending_approval"], true);
+
+    let required = review["required_skills"].as_array().unwrap();
+    assert_eq!(required.len(), 2);
+    assert_eq!(required[0]["id"], skills[0].to_string());
+    assert_eq!(required[0]["minimum_proficiency"], 3);
+    assert!(required[0]["name"]
+        .as_str()
+        .is_some_and(|name| !name.is_empty()));
+    assert_eq!(review["preferred_skills"][0]["id"], skills[2].to_string());
+    assert_eq!(
+        review["preferred_skills"][0]["minimum_proficiency"],
+        Value::Null
+    );
+    assert_eq!(
+        review["required_languages"][0]["id"],
+        languages[0].to_string()
+    );
+    assert_eq!(review["required_languages"][0]["minimum_proficiency"], 4);
+    assert_eq!(
+        review["disability_accommodations"],
+        json!(["visual", "hearing"])
+    );
+
+    assert_eq!(review["company"]["id"], company.id.to_string());
+    assert_eq!(review["company"]["company_name"], "Test SpA");
+    assert_eq!(review["company"]["status"], "active");
+    assert_eq!(review["company"]["prior_job_rejections"], 1);
+
+    assert_eq!(review["posted_by"]["id"], company.owner.id.to_string());
+    assert_eq!(review["posted_by"]["email"], company.owner.email);
+    assert_eq!(review["posted_by"]["email_verified"], true);
+    assert_eq!(review["screening_findings"], json!([]));
+    assert_eq!(review["possible_duplicate_of"], json!([]));
+
+    // The queue itself only carries the summary
+    let res = app.get("/api/admin/jobs/pending", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let pending = res.body.as_array().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0]["id"], job_id);
+    assert_eq!(pending[0]["company_name"], "Test SpA");
+    assert_eq!(pending[0]["waiting_hours"], 0);
+    assert!(pending[0]["completeness_percentage"].is_i64());
+    assert!(pending[0].get("description").is_none());
+}
+
+#[sqlx::test]
+async fn test_decided_job_is_reviewable_but_flagged(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = submit_job(&app, &company, json!({})).await;
+
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/approve", job_id),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .get(&format!("/api/admin/jobs/{}/review", job_id), Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["pending_approval"], false);
+    assert_eq!(res.body["job"]["status"], "active");
+    assert_eq!(res.body["company"]["prior_job_rejections"], 0);
+
+    let res = app
+        .get(
+            &format!("/api/admin/jobs/{}/review", Uuid::new_v4()),
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
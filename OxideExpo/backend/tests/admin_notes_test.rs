@@ -0,0 +1,285 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn admin_id(app: &TestApp, user: &TestUser) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM admins WHERE user_id = $1")
+        .bind(user.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn moderator(app: &TestApp) -> TestUser {
+    let user = app.create_admin().await;
+    sqlx::query("UPDATE admins SET admin_role = 'moderator' WHERE user_id = $1")
+        .bind(user.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    user
+}
+
+async fn note(
+    app: &TestApp,
+    author: &TestUser,
+    entity_type: &str,
+    entity_id: Uuid,
+    body: &str,
+) -> Value {
+    let res = app
+        .post(
+            "/api/admin/notes",
+            Some(author),
+            json!({ "entity_type": entity_type, "entity_id": entity_id, "body": body }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+fn bodies(notes: &Value) -> Vec<&str> {
+    notes
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["body"].as_str().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_notes_are_scoped_to_their_entity_and_admin_only(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let first = note(&app, &admin, "company", company.id, "Llamó el 12/03").await;
+    let pinned = note(
+        &app,
+        &admin,
+        "company",
+        company.id,
+        "Faltan documentos legales",
+    )
+    .await;
+    note(&app, &admin, "company", other.id, "Otra empresa").await;
+    note(&app, &admin, "job", job_id, "Revisar sueldo").await;
+    note(&app, &admin, "user", company.owner.id, "Prefiere teléfono").await;
+    assert_eq!(
+        first["author_admin_id"],
+        admin_id(&app, &admin).await.to_string()
+    );
+    assert!(first["author_name"].is_string());
+
+    let res = app
+        .patch(
+            &format!("/api/admin/notes/{}", pinned["id"].as_str().unwrap()),
+            Some(&admin),
+            json!({ "is_pinned": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Pinned first, then newest first
+    let uri = format!(
+        "/api/admin/notes?entity_type=company&entity_id={}",
+        company.id
+    );
+    let res = app.get(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        bodies(&res.body),
+        vec!["Faltan documentos legales", "Llamó el 12/03"]
+    );
+
+    // The admin detail views carry their entity's notes
+    let res = app
+        .get(
+            &format!("/api/admin/companies/{}", company.id),
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["profile"]["id"], company.id.to_string());
+    assert_eq!(
+        bodies(&res.body["notes"]),
+        vec!["Faltan documentos legales", "Llamó el 12/03"]
+    );
+    let res = app
+        .get(&format!("/api/admin/jobs/{}/review", job_id), Some(&admin))
+        .await;
+    assert_eq!(bodies(&res.body["notes"]), vec!["Revisar sueldo"]);
+    let res = app
+        .get(
+            &format!("/api/admin/users/{}", company.owner.id),
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(bodies(&res.body["notes"]), vec!["Prefiere teléfono"]);
+
+    // Owners see none of it
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    for uri in ["/api/me/company/profile", "/api/me/jobs"] {
+        let res = app.get(uri, Some(&company.owner)).await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        let body = res.body.to_string();
+        assert!(!body.contains("Faltan documentos"), "{}", uri);
+        assert!(!body.contains("Revisar sueldo"), "{}", uri);
+    }
+    let res = app
+        .get(&format!("/api/jobs/{}", job_id), Some(&company.owner))
+        .await;
+    assert!(!res.body.to_string().contains("Revisar sueldo"));
+
+    let res = app
+        .post(
+            "/api/admin/notes",
+            Some(&admin),
+            json!({ "entity_type": "omil", "entity_id": Uuid::new_v4(), "body": "?" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_only_the_author_or_a_super_admin_edits_a_note(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let author = moderator(&app).await;
+    let colleague = moderator(&app).await;
+    let super_admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let created = note(&app, &author, "company", company.id, "Primera versión").await;
+    let uri = format!("/api/admin/notes/{}", created["id"].as_str().unwrap());
+
+    let res = app
+        .patch(&uri, Some(&colleague), json!({ "body": "Cambiada" }))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app.delete(&uri, Some(&colleague)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app
+        .patch(&uri, Some(&author), json!({ "body": "Segunda versión" }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["body"], "Segunda versión");
+    assert_eq!(res.body["is_pinned"], false);
+
+    let res = app
+        .patch(&uri, Some(&super_admin), json!({ "is_pinned": true }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["body"], "Segunda versión");
+    assert_eq!(res.body["is_pinned"], true);
+    // Still the author's note
+    assert_eq!(
+        res.body["author_admin_id"],
+        admin_id(&app, &author).await.to_string()
+    );
+
+    let res = app.patch(&uri, Some(&author), json!({ "body": "" })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app.delete(&uri, Some(&super_admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app.delete(&uri, Some(&author)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_mentioned_admins_are_notified(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let author = app.create_admin().await;
+    let mentioned = moderator(&app).await;
+    let bystander = moderator(&app).await;
+    let author_id = admin_id(&app, &author).await;
+    let mentioned_id = admin_id(&app, &mentioned).await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app
+        .post(
+            "/api/admin/notes",
+            Some(&author),
+            json!({
+                "entity_type": "company",
+                "entity_id": company.id,
+                "body": "¿Puedes revisar sus documentos?",
+                "mentions": [mentioned_id, author_id],
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    // Authors aren't notified of their own notes
+    assert_eq!(res.body["mentions"], json!([mentioned_id]));
+    let note_uri = format!("/api/admin/notes/{}", res.body["id"].as_str().unwrap());
+
+    let res = app
+        .get(
+            "/api/admin/notes/mentions?unread_only=true",
+            Some(&mentioned),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let mentions = res.body.as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(
+        mentions[0]["note"]["body"],
+        "¿Puedes revisar sus documentos?"
+    );
+    assert_eq!(mentions[0]["note"]["entity_id"], company.id.to_string());
+    assert_eq!(mentions[0]["read_at"], Value::Null);
+
+    for admin in [&author, &bystander] {
+        let res = app.get("/api/admin/notes/mentions", Some(admin)).await;
+        assert_eq!(res.body, json!([]));
+    }
+
+    // Mentioning again on edit doesn't notify twice
+    let res = app
+        .patch(
+            &note_uri,
+            Some(&author),
+            json!({ "mentions": [mentioned_id] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app.get("/api/admin/notes/mentions", Some(&mentioned)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+
+    let res = app
+        .post(&format!("{}/read", note_uri), Some(&mentioned), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .get(
+            "/api/admin/notes/mentions?unread_only=true",
+            Some(&mentioned),
+        )
+        .await;
+    assert_eq!(res.body, json!([]));
+    let res = app.get("/api/admin/notes/mentions", Some(&mentioned)).await;
+    assert!(res.body[0]["read_at"].is_string());
+
+    // Only admins can be mentioned
+    let res = app
+        .post(
+            "/api/admin/notes",
+            Some(&author),
+            json!({
+                "entity_type": "company",
+                "entity_id": company.id,
+                "body": "Hola",
+                "mentions": [company.owner.id],
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+/// Creates a token for `user` and returns the creation response together
+/// with a TestUser that authenticates with it
+async fn create_token(app: &TestApp, user: &TestUser) -> (Value, TestUser) {
+    let res = app
+        .post(
+            "/api/me/api-tokens",
+            Some(user),
+            json!({ "name": "Dashboard municipal", "scopes": ["omil:read"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let as_token = TestUser {
+        id: user.id,
+        email: user.email.clone(),
+        user_type: user.user_type,
+        token: res.body["token"].as_str().unwrap().to_string(),
+    };
+    (res.body, as_token)
+}
+
+#[sqlx::test]
+async fn test_plaintext_token_is_shown_once(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+
+    let (created, _) = create_token(&app, &omil.director).await;
+    let token = created["token"].as_str().unwrap();
+    assert!(token.starts_with("oxp_"));
+    assert_eq!(created["token_prefix"], &token[..12]);
+
+    let res = app.get("/api/me/api-tokens", Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let tokens = res.body.as_array().unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert!(tokens[0].get("token").is_none());
+    assert!(tokens[0].get("token_hash").is_none());
+    assert!(!res.body.to_string().contains(token));
+}
+
+#[sqlx::test]
+async fn test_token_scope_limits_access(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (_, integration) = create_token(&app, &omil.director).await;
+
+    let res = app.get("/api/me/omil", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app
+        .get("/api/me/omil/job-seekers", Some(&integration))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Writes, other areas and token management are out of scope
+    let res = app
+        .put(
+            "/api/me/omil",
+            Some(&integration),
+            json!({ "organization_name": "Cambiado" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app.get("/api/auth/me", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app.get("/api/me/api-tokens", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let last_used_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT last_used_at FROM api_tokens WHERE user_id = $1")
+            .bind(omil.director.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(last_used_at.is_some());
+}
+
+#[sqlx::test]
+async fn test_omil_scope_requires_omil_member(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            "/api/me/api-tokens",
+            Some(&seeker),
+            json!({ "name": "Mi token", "scopes": ["omil:read"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app
+        .post(
+            "/api/me/api-tokens",
+            Some(&seeker),
+            json!({ "name": "Mi token", "scopes": ["admin"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_revocation_applies_immediately(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (created, integration) = create_token(&app, &omil.director).await;
+
+    let res = app.get("/api/me/omil", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let uri = format!("/api/me/api-tokens/{}", created["id"].as_str().unwrap());
+    let res = app.delete(&uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/me/omil", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+
+    let res = app.delete(&uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_expired_token_is_rejected(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (_, integration) = create_token(&app, &omil.director).await;
+
+    sqlx::query("UPDATE api_tokens SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app.get("/api/me/omil", Some(&integration)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+}
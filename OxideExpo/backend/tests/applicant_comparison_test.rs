@@ -0,0 +1,311 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn compare_uri(job_id: Uuid, ids: &[Uuid]) -> String {
+    let ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+    format!(
+        "/api/me/jobs/{}/applicants/compare?ids={}",
+        job_id,
+        ids.join(",")
+    )
+}
+
+/// Two required skills, by name: the first and second skill alphabetically
+async fn require_skills(app: &TestApp, job_id: Uuid) -> Vec<(Uuid, String)> {
+    let skills: Vec<(Uuid, String)> =
+        sqlx::query_as("SELECT id, name FROM skills ORDER BY name LIMIT 2")
+            .fetch_all(app.db())
+            .await
+            .unwrap();
+    for (skill_id, _) in &skills {
+        sqlx::query(
+            "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1, $2, 3)",
+        )
+        .bind(job_id)
+        .bind(skill_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    skills
+}
+
+async fn add_skill(app: &TestApp, seeker: &TestUser, skill_id: Uuid, level: i32) {
+    sqlx::query(
+        "INSERT INTO user_skills (user_id, skill_id, proficiency_level) VALUES ($1, $2, $3)",
+    )
+    .bind(seeker.id)
+    .bind(skill_id)
+    .bind(level)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn applicants(app: &TestApp, company: &TestCompany, count: usize) -> (Uuid, Vec<Uuid>) {
+    let job_id = app.create_active_job(company).await;
+    let mut ids = Vec::new();
+    for _ in 0..count {
+        let seeker = app.create_job_seeker().await;
+        ids.push(app.create_application(job_id, &seeker).await);
+    }
+    (job_id, ids)
+}
+
+#[sqlx::test]
+async fn test_only_the_jobs_applications_compare(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let (job_id, ids) = applicants(&app, &company, 6).await;
+    let (other_job_id, other_ids) = applicants(&app, &other, 1).await;
+
+    let res = app
+        .get(&compare_uri(job_id, &ids[..2]), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Between two and five distinct applications
+    for shortlist in [&ids[..1], &ids[..6], &[ids[0], ids[0]][..]] {
+        let res = app
+            .get(&compare_uri(job_id, shortlist), Some(&company.owner))
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "{:?}", shortlist);
+    }
+    let res = app
+        .get(
+            &format!(
+                "/api/me/jobs/{}/applicants/compare?ids={},nope",
+                job_id, ids[0]
+            ),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Another job's application, even the company's own, doesn't belong
+    let (second_job_id, second_ids) = applicants(&app, &company, 1).await;
+    let res = app
+        .get(
+            &compare_uri(job_id, &[ids[0], second_ids[0]]),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app
+        .get(
+            &compare_uri(second_job_id, &[ids[0], second_ids[0]]),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Nor can another company look
+    let res = app
+        .get(
+            &compare_uri(job_id, &[ids[0], other_ids[0]]),
+            Some(&other.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app
+        .get(
+            &compare_uri(other_job_id, &[ids[0], other_ids[0]]),
+            Some(&other.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let seeker = app.create_job_seeker().await;
+    let res = app
+        .get(&compare_uri(job_id, &ids[..2]), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_candidates_line_up_criterion_by_criterion(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let skills = require_skills(&app, job_id).await;
+
+    let first = app.create_job_seeker().await;
+    add_skill(&app, &first, skills[0].0, 4).await;
+    add_skill(&app, &first, skills[1].0, 2).await;
+    sqlx::query(
+        r#"
+        INSERT INTO user_languages (user_id, language_id, proficiency)
+        SELECT $1, id, 'native'::language_proficiency FROM languages WHERE name = 'Español'
+        UNION ALL
+        SELECT $1, id, 'basic'::language_proficiency FROM languages WHERE name = 'Inglés'
+        "#,
+    )
+    .bind(first.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO education_records (user_id, institution_name, level, status, start_date)
+        VALUES ($1, 'Liceo', 'secondary', 'completed', '2005-03-01'),
+               ($1, 'Inacap', 'technical', 'completed', '2010-03-01')
+        "#,
+    )
+    .bind(first.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_preferences
+        SET salary_expectation_min = 600000, salary_expectation_max = 800000
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(first.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let second = app.create_job_seeker().await;
+    let first_app = app.create_application(job_id, &first).await;
+    let second_app = app.create_application(job_id, &second).await;
+    sqlx::query(
+        r#"
+        INSERT INTO job_match_scores (
+            job_id, user_id, total_score, skills_score, languages_score, experience_score
+        )
+        VALUES ($1, $2, 42, 20, 10, 12)
+        "#,
+    )
+    .bind(job_id)
+    .bind(second.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // Columns come in the order asked for
+    let uri = compare_uri(job_id, &[second_app, first_app]);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let required: Vec<&str> = res.body["required_skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(required, vec![skills[0].1.as_str(), skills[1].1.as_str()]);
+
+    let candidates = res.body["candidates"].as_array().unwrap();
+    assert_eq!(candidates[0]["application_id"], json!(second_app));
+    assert_eq!(candidates[1]["application_id"], json!(first_app));
+
+    let scored = &candidates[0];
+    assert_eq!(scored["match_score"]["total_score"], 42);
+    assert_eq!(scored["match_score"]["skills_score"], 20);
+    assert_eq!(scored["match_score"]["location_score"], 0);
+    assert_eq!(scored["matched_required_skills"], json!([]));
+    assert_eq!(
+        scored["missing_required_skills"].as_array().unwrap().len(),
+        2
+    );
+    assert_eq!(scored["languages"], json!([]));
+    assert_eq!(scored["highest_education"], Value::Null);
+
+    let first_column = &candidates[1];
+    assert_eq!(first_column["match_score"], Value::Null);
+    assert_eq!(
+        first_column["matched_required_skills"],
+        json!([skills[0].1])
+    );
+    assert_eq!(
+        first_column["missing_required_skills"],
+        json!([skills[1].1])
+    );
+    assert_eq!(first_column["highest_education"], "technical");
+    assert!(first_column["years_of_experience"].as_i64().unwrap() >= 1);
+    assert_eq!(first_column["languages"][0]["name"], "Español");
+    assert_eq!(first_column["languages"][0]["proficiency"], "native");
+    assert_eq!(first_column["languages"][1]["name"], "Inglés");
+    assert_eq!(first_column["availability"], "open_to_offers");
+    // Salary expectations stay private until the seeker shows them
+    assert_eq!(first_column["salary_expectation"], Value::Null);
+
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&first),
+            json!({ "show_salary_expectation": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["show_salary_expectation"], true);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    let salary = &res.body["candidates"][1]["salary_expectation"];
+    assert_eq!(salary["min"], "600000.00");
+    assert_eq!(salary["max"], "800000.00");
+    assert_eq!(salary["currency"], "CLP");
+}
+
+#[sqlx::test]
+async fn test_comparison_respects_hidden_sections(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+    let seeker_app = app.create_application(job_id, &seeker).await;
+    let other_app = app.create_application(job_id, &other).await;
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_profiles
+        SET availability = 'actively_looking', available_from = '2030-03-01',
+            share_available_from = false
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO job_match_scores (job_id, user_id, total_score) VALUES ($1, $2, 80)")
+        .bind(job_id)
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let uri = compare_uri(job_id, &[seeker_app, other_app]);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.body["show_match_scores"], true);
+    let candidate = &res.body["candidates"][0];
+    assert_eq!(candidate["match_score"]["total_score"], 80);
+    assert_eq!(candidate["availability"], "actively_looking");
+    assert_eq!(candidate["available_from"], Value::Null);
+
+    sqlx::query("UPDATE job_seeker_profiles SET share_available_from = true WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app
+        .put(
+            "/api/me/company/settings",
+            Some(&company.owner),
+            json!({ "show_match_scores": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.body["show_match_scores"], false);
+    let candidate = &res.body["candidates"][0];
+    assert_eq!(candidate["match_score"], Value::Null);
+    assert_eq!(candidate["available_from"], "2030-03-01");
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn create_tag(app: &TestApp, company: &TestCompany, name: &str) -> Uuid {
+    let res = app
+        .post(
+            "/api/me/company/tags",
+            Some(&company.owner),
+            json!({ "name": name, "color": "#0EA5E9" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["id"].as_str().unwrap().parse().unwrap()
+}
+
+fn tag_uri(job_id: Uuid, app_id: Uuid, tag_id: Uuid) -> String {
+    format!(
+        "/api/me/jobs/{}/applicants/{}/tags/{}",
+        job_id, app_id, tag_id
+    )
+}
+
+async fn assign(app: &TestApp, company: &TestCompany, job_id: Uuid, app_id: Uuid, tag_id: Uuid) {
+    let res = app
+        .post(
+            &tag_uri(job_id, app_id, tag_id),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+/// Application IDs listed for a job, optionally filtered by tags
+async fn listed(app: &TestApp, company: &TestCompany, job_id: Uuid, tags: &[Uuid]) -> Vec<Uuid> {
+    let mut uri = format!("/api/me/jobs/{}/applicants", job_id);
+    if !tags.is_empty() {
+        let ids: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        uri = format!("{}?tags={}", uri, ids.join(","));
+    }
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["total"].as_u64().unwrap() as usize,
+        res.body["applicants"].as_array().unwrap().len()
+    );
+
+    let mut ids: Vec<Uuid> = res.body["applicants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["application_id"].as_str().unwrap().parse().unwrap())
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+    ids.sort();
+    ids
+}
+
+#[sqlx::test]
+async fn test_tags_are_scoped_to_the_company(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    let own_tag = create_tag(&app, &company, "Bilingüe").await;
+    let foreign_tag = create_tag(&app, &other, "Bilingüe").await;
+
+    // Company A cannot use company B's tag
+    let res = app
+        .post(
+            &tag_uri(job_id, app_id, foreign_tag),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Company B cannot tag company A's applicants, not even with its own tag
+    let res = app
+        .post(
+            &tag_uri(job_id, app_id, foreign_tag),
+            Some(&other.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Nor see or edit company A's definitions
+    let res = app.get("/api/me/company/tags", Some(&other.owner)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    let res = app
+        .put(
+            &format!("/api/me/company/tags/{}", own_tag),
+            Some(&other.owner),
+            json!({ "name": "Hackeado" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app
+        .post(
+            &tag_uri(job_id, app_id, own_tag),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["name"], "Bilingüe");
+
+    // Names are unique per company, ignoring case
+    let res = app
+        .post(
+            "/api/me/company/tags",
+            Some(&company.owner),
+            json!({ "name": "bilingüe" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    // The seeker never sees the company's labels
+    let res = app.get("/api/me/applications", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(!res.body.to_string().contains("Bilingüe"));
+}
+
+#[sqlx::test]
+async fn test_tag_filter_requires_all_tags(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let mut apps = Vec::new();
+    for _ in 0..3 {
+        let seeker = app.create_job_seeker().await;
+        apps.push(app.create_application(job_id, &seeker).await);
+    }
+
+    let bilingual = create_tag(&app, &company, "Bilingüe").await;
+    let second_round = create_tag(&app, &company, "Segunda ronda").await;
+    assign(&app, &company, job_id, apps[0], bilingual).await;
+    assign(&app, &company, job_id, apps[1], bilingual).await;
+    assign(&app, &company, job_id, apps[1], second_round).await;
+    assign(&app, &company, job_id, apps[2], second_round).await;
+
+    assert_eq!(
+        listed(&app, &company, job_id, &[]).await,
+        sorted(apps.clone())
+    );
+    assert_eq!(
+        listed(&app, &company, job_id, &[bilingual]).await,
+        sorted(vec![apps[0], apps[1]])
+    );
+    assert_eq!(
+        listed(&app, &company, job_id, &[bilingual, second_round]).await,
+        vec![apps[1]]
+    );
+
+    // Tags come back inline on each applicant
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    let both = res.body["applicants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["application_id"] == apps[1].to_string())
+        .unwrap();
+    let names: Vec<&str> = both["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Bilingüe", "Segunda ronda"]);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants?tags=bilingue", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_deleting_a_tag_removes_its_assignments(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    let tag = create_tag(&app, &company, "Reubicación").await;
+    assign(&app, &company, job_id, app_id, tag).await;
+
+    let res = app
+        .delete(
+            &format!("/api/me/company/tags/{}", tag),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let assignments: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM application_tag_assignments WHERE tag_id = $1")
+            .bind(tag)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(assignments, 0);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.body["applicants"][0]["tags"], json!([]));
+
+    // At most 30 definitions per company
+    for i in 0..30 {
+        create_tag(&app, &company, &format!("Etiqueta {}", i)).await;
+    }
+    let res = app
+        .post(
+            "/api/me/company/tags",
+            Some(&company.owner),
+            json!({ "name": "Una más" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn stored_count(app: &TestApp, job_id: Uuid) -> i32 {
+    sqlx::query_scalar("SELECT applications_count FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn counted_rows(app: &TestApp, job_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM job_applications WHERE job_id = $1 AND status <> 'withdrawn'",
+    )
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_concurrent_submissions_are_all_counted(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let mut seekers = Vec::new();
+    for _ in 0..50 {
+        seekers.push(app.create_job_seeker().await);
+    }
+
+    let app = Arc::new(app);
+    let submissions: Vec<_> = seekers
+        .into_iter()
+        .map(|seeker| {
+            let app = Arc::clone(&app);
+            tokio::spawn(async move {
+                app.post(
+                    "/api/me/applications",
+                    Some(&seeker),
+                    json!({ "job_id": job_id }),
+                )
+                .await
+                .status
+            })
+        })
+        .collect();
+
+    for submission in submissions {
+        assert_eq!(submission.await.unwrap(), StatusCode::OK);
+    }
+
+    assert_eq!(counted_rows(&app, job_id).await, 50);
+    assert_eq!(stored_count(&app, job_id).await, 50);
+}
+
+#[sqlx::test]
+async fn test_withdrawal_lowers_count(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(stored_count(&app, job_id).await, 1);
+
+    let uri = format!(
+        "/api/me/applications/{}/withdraw",
+        res.body["id"].as_str().unwrap()
+    );
+    let res = app
+        .patch(
+            &uri,
+            Some(&seeker),
+            json!({ "withdrawal_reason": "Acepté otra oferta" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(stored_count(&app, job_id).await, 0);
+
+    // A second withdrawal is rejected and must not lower the count again
+    let res = app.patch(&uri, Some(&seeker), json!({})).await;
+    assert_ne!(res.status, StatusCode::OK);
+    assert_eq!(stored_count(&app, job_id).await, 0);
+}
+
+#[sqlx::test]
+async fn test_recount_fixes_drift(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let drifted_job = app.create_active_job(&company).await;
+    let healthy_job = app.create_active_job(&company).await;
+    for _ in 0..2 {
+        let seeker = app.create_user(UserType::JobSeeker).await;
+        app.create_application(drifted_job, &seeker).await;
+        app.create_application(healthy_job, &seeker).await;
+    }
+
+    // A withdrawn application doesn't count: the trigger before migration
+    // 0049 counted it, which is one of the drifts a recount corrects
+    let seeker = app.create_user(UserType::JobSeeker).await;
+    sqlx::query(
+        "INSERT INTO job_applications (job_id, applicant_id, status) VALUES ($1, $2, 'withdrawn')",
+    )
+    .bind(drifted_job)
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query("UPDATE jobs SET applications_count = 7 WHERE id = $1")
+        .bind(drifted_job)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let uri = "/api/admin/maintenance/recount-applications";
+    let res = app.post(uri, Some(&company.owner), json!({})).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app.post(uri, Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let corrected = res.body["corrected"].as_array().unwrap();
+    assert_eq!(corrected.len(), 1);
+    assert_eq!(corrected[0]["job_id"], drifted_job.to_string());
+    assert_eq!(corrected[0]["stored"], 7);
+    assert_eq!(corrected[0]["actual"], 2);
+    assert_eq!(stored_count(&app, drifted_job).await, 2);
+    assert_eq!(stored_count(&app, healthy_job).await, 2);
+
+    let logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'recount_applications' AND entity_id = $1",
+    )
+    .bind(drifted_job)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(logged, 1);
+
+    let res = app.post(uri, Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body["corrected"].as_array().unwrap().is_empty());
+}
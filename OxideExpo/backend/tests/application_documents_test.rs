@@ -0,0 +1,300 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{FormPart, TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::storage::StorageDriver;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DOCX: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+async fn upload(app: &TestApp, user: &TestUser, app_id: Uuid, content_type: &str) -> StatusCode {
+    app.post_multipart(
+        &format!("/api/me/applications/{}/documents", app_id),
+        Some(user),
+        &[
+            FormPart {
+                name: "label",
+                filename: None,
+                content_type: None,
+                data: b"Certificado de estudios",
+            },
+            FormPart {
+                name: "file",
+                filename: Some("certificado.txt"),
+                content_type: Some(content_type),
+                data: b"not really a document",
+            },
+        ],
+    )
+    .await
+    .status
+}
+
+/// Document row as if uploaded earlier; storage isn't reachable in tests
+async fn seed_document(app: &TestApp, app_id: Uuid, uploaded_by: &TestUser, label: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO application_documents
+            (application_id, file_key, label, original_filename, content_type, size, uploaded_by)
+        VALUES ($1, 'applications/' || $1 || '/docs/' || gen_random_uuid() || '.pdf', $2,
+                'portafolio.pdf', 'application/pdf', 2048, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(app_id)
+    .bind(label)
+    .bind(uploaded_by.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn set_status(app: &TestApp, app_id: Uuid, status: &str) {
+    sqlx::query("UPDATE job_applications SET status = $1::application_status WHERE id = $2")
+        .bind(status)
+        .bind(app_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+async fn applicant_detail(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    app_id: Uuid,
+) -> common::TestResponse {
+    app.get(
+        &format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id),
+        Some(&company.owner),
+    )
+    .await
+}
+
+#[sqlx::test]
+async fn test_documents_only_while_submitted_or_under_review(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    // Inside the window the upload gets as far as checking the file itself
+    assert_eq!(
+        upload(&app, &seeker, app_id, "text/plain").await,
+        StatusCode::BAD_REQUEST
+    );
+
+    // Not someone else's application
+    let other_seeker = app.create_job_seeker().await;
+    assert_eq!(
+        upload(&app, &other_seeker, app_id, DOCX).await,
+        StatusCode::NOT_FOUND
+    );
+
+    for status in ["shortlisted", "rejected", "withdrawn"] {
+        set_status(&app, app_id, status).await;
+        let res = app
+            .post_multipart(
+                &format!("/api/me/applications/{}/documents", app_id),
+                Some(&seeker),
+                &[FormPart {
+                    name: "file",
+                    filename: Some("certificado.pdf"),
+                    content_type: Some("application/pdf"),
+                    data: b"%PDF-1.4",
+                }],
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", status);
+        assert!(res.body["error"].as_str().unwrap().contains("under review"));
+    }
+
+    // At most three documents per application
+    set_status(&app, app_id, "under_review").await;
+    for label in ["Uno", "Dos", "Tres"] {
+        seed_document(&app, app_id, &seeker, label).await;
+    }
+    let res = app
+        .post_multipart(
+            &format!("/api/me/applications/{}/documents", app_id),
+            Some(&seeker),
+            &[],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert!(res.body["error"].as_str().unwrap().contains("at most 3"));
+}
+
+#[sqlx::test]
+async fn test_concurrent_uploads_stay_within_the_limit(db: PgPool) {
+    let app =
+        TestApp::with_config(db, |config| config.storage_driver = StorageDriver::Memory).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+    for label in ["Uno", "Dos"] {
+        seed_document(&app, app_id, &seeker, label).await;
+    }
+
+    // Held until both uploads have passed the early check and stored their
+    // file; only one of them fits once they get the application in turn
+    let mut lock = app.db().begin().await.unwrap();
+    sqlx::query("SELECT 1 FROM job_applications WHERE id = $1 FOR UPDATE")
+        .bind(app_id)
+        .execute(&mut *lock)
+        .await
+        .unwrap();
+    let release = async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        lock.commit().await.unwrap();
+    };
+
+    let (first, second, ()) = tokio::join!(
+        upload(&app, &seeker, app_id, "application/pdf"),
+        upload(&app, &seeker, app_id, "application/pdf"),
+        release,
+    );
+    let mut statuses = vec![first, second];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::BAD_REQUEST]);
+
+    let (documents, queued): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT (SELECT COUNT(*) FROM application_documents WHERE application_id = $1),
+               (SELECT COUNT(*) FROM application_document_deletions)
+        "#,
+    )
+    .bind(app_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(documents, 3);
+    // The file turned away is cleaned up with removed documents
+    assert_eq!(queued, 1);
+}
+
+#[sqlx::test]
+async fn test_company_sees_documents_with_signed_urls(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other_company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    let res = applicant_detail(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["documents"].as_array().unwrap().len(), 0);
+
+    let document_id = seed_document(&app, app_id, &seeker, "Portafolio").await;
+    let file_key: String =
+        sqlx::query_scalar("SELECT file_key FROM application_documents WHERE id = $1")
+            .bind(document_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+
+    let res = applicant_detail(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let documents = res.body["documents"].as_array().unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0]["label"], "Portafolio");
+    assert_eq!(documents[0]["content_type"], "application/pdf");
+    assert!(documents[0].get("file_key").is_none());
+    let url = documents[0]["download_url"].as_str().unwrap();
+    assert!(url.contains(&file_key));
+    assert!(url.contains("X-Amz-Signature="));
+
+    // Other companies can't reach the applicant at all
+    let res = applicant_detail(&app, &other_company, job_id, app_id).await;
+    assert_ne!(res.status, StatusCode::OK);
+
+    // The seeker lists and removes their own document; the file is queued for storage cleanup
+    let uri = format!("/api/me/applications/{}/documents", app_id);
+    let res = app.get(&uri, Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["id"], document_id.to_string());
+
+    let res = app
+        .delete(&format!("{}/{}", uri, document_id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = applicant_detail(&app, &company, job_id, app_id).await;
+    assert_eq!(res.body["documents"].as_array().unwrap().len(), 0);
+
+    let queued: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM application_document_deletions WHERE file_key = $1)",
+    )
+    .bind(&file_key)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(queued);
+}
+
+#[sqlx::test]
+async fn test_omil_attaches_only_to_its_managed_seekers_applications(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+    let stranger = app.create_job_seeker().await;
+    let stranger_app_id = app.create_application(job_id, &stranger).await;
+
+    let managed_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let parts = [FormPart {
+        name: "file",
+        filename: Some("notas.txt"),
+        content_type: Some("text/plain"),
+        data: b"plain text",
+    }];
+
+    let res = app
+        .post_multipart(
+            &format!(
+                "/api/me/omil/job-seekers/{}/applications/{}/documents",
+                managed_id, stranger_app_id
+            ),
+            Some(&omil.director),
+            &parts,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Reaches file validation for the managed seeker's own application
+    let res = app
+        .post_multipart(
+            &format!(
+                "/api/me/omil/job-seekers/{}/applications/{}/documents",
+                managed_id, app_id
+            ),
+            Some(&omil.director),
+            &parts,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert!(res.body["error"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid file type"));
+}
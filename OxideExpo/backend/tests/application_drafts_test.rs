@@ -0,0 +1,243 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::services::scheduler::delete_stale_application_drafts;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn draft_uri(job_id: Uuid) -> String {
+    format!("/api/me/jobs/{}/application-draft", job_id)
+}
+
+/// Move a draft past the autosave throttle window
+async fn age_draft(app: &TestApp, user: &TestUser, job_id: Uuid, seconds: i32) {
+    sqlx::query(
+        r#"
+        UPDATE application_drafts
+        SET updated_at = NOW() - make_interval(secs => $3)
+        WHERE user_id = $1 AND job_id = $2
+        "#,
+    )
+    .bind(user.id)
+    .bind(job_id)
+    .bind(seconds as f64)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_draft_upsert_is_throttled(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador", "answers": { "q1": "Sí" } }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["answers"]["q1"], "Sí");
+
+    // Autosave spam right after a write is refused
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador 2" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+
+    age_draft(&app, &seeker, job_id, 6).await;
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador 2" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["cover_letter"], "Borrador 2");
+    assert_eq!(res.body["answers"], json!({}));
+
+    let res = app.delete(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app.get(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_draft_limit_evicts_oldest(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let mut job_ids = Vec::new();
+    for _ in 0..21 {
+        let job_id = app.create_active_job(&company).await;
+        let res = app
+            .put(
+                &draft_uri(job_id),
+                Some(&seeker),
+                json!({ "cover_letter": "x" }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK);
+        job_ids.push(job_id);
+    }
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM application_drafts WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(count, 20);
+
+    let res = app.get(&draft_uri(job_ids[0]), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app.get(&draft_uri(job_ids[20]), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_paused_job_keeps_draft_read_only(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    age_draft(&app, &seeker, job_id, 6).await;
+
+    let set_status = |status: &'static str| {
+        sqlx::query("UPDATE jobs SET status = $2::job_status WHERE id = $1")
+            .bind(job_id)
+            .bind(status)
+            .execute(app.db())
+    };
+    set_status("paused").await.unwrap();
+
+    // Still readable, and the cleanup leaves it alone
+    delete_stale_application_drafts(app.db()).await.unwrap();
+    let res = app.get(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["cover_letter"], "Borrador");
+
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador 2" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    // Saving resumes once the job reopens
+    set_status("active").await.unwrap();
+    let res = app
+        .put(
+            &draft_uri(job_id),
+            Some(&seeker),
+            json!({ "cover_letter": "Borrador 2" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["cover_letter"], "Borrador 2");
+
+    // Closing the job drops it
+    set_status("closed").await.unwrap();
+    delete_stale_application_drafts(app.db()).await.unwrap();
+    let res = app.get(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_drafts_are_for_job_seekers_only(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = app
+        .put(&draft_uri(job_id), Some(&company.owner), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_submit_consumes_draft(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    app.put(
+        &draft_uri(job_id),
+        Some(&seeker),
+        json!({ "cover_letter": "Desde el borrador" }),
+    )
+    .await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["cover_letter"], "Desde el borrador");
+
+    let res = app.get(&draft_uri(job_id), Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_submit_payload_wins_over_draft(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    app.put(
+        &draft_uri(job_id),
+        Some(&seeker),
+        json!({ "cover_letter": "Desde el borrador" }),
+    )
+    .await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id, "cover_letter": "Versión final" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["cover_letter"], "Versión final");
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM application_drafts WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(count, 0);
+}
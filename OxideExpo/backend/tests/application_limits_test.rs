@@ -0,0 +1,211 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use empleos_inclusivos_backend::error::AppError;
+use empleos_inclusivos_backend::services::{applications, limits};
+use redis::AsyncCommands;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn set_count(app: &TestApp, key: &str, count: i64) {
+    let mut redis = app.state.redis.clone();
+    let _: () = redis.set_ex(key, count, 600).await.unwrap();
+}
+
+async fn count(app: &TestApp, key: &str) -> i64 {
+    let mut redis = app.state.redis.clone();
+    let count: Option<i64> = redis.get(key).await.unwrap();
+    count.unwrap_or(0)
+}
+
+async fn today(app: &TestApp) -> NaiveDate {
+    limits::day_window(app.db(), &app.state.config.platform_timezone, Utc::now())
+        .await
+        .unwrap()
+        .day
+}
+
+async fn apply(app: &TestApp, seeker: &TestUser, job_id: Uuid) -> common::TestResponse {
+    app.post(
+        "/api/me/applications",
+        Some(seeker),
+        json!({ "job_id": job_id }),
+    )
+    .await
+}
+
+async fn manage(app: &TestApp, omil: &TestOmil, seeker: &TestUser) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn application_count(app: &TestApp, seeker: &TestUser) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM job_applications WHERE applicant_id = $1")
+        .bind(seeker.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+fn utc(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, hour, minute, 0).unwrap()
+}
+
+#[sqlx::test]
+async fn test_daily_cap_resets_at_platform_midnight(db: PgPool) {
+    let app =
+        TestApp::with_config(db, |c| c.platform_timezone = "America/Santiago".to_string()).await;
+    let seeker = app.create_job_seeker().await;
+
+    // Santiago is on UTC-3 in October, so its day changes at 03:00 UTC
+    let late = utc(2, 30);
+    let window = limits::day_window(app.db(), "America/Santiago", late)
+        .await
+        .unwrap();
+    assert_eq!(window.day, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+    assert_eq!(window.resets_at, utc(3, 0));
+
+    let key = applications::seeker_daily_key(seeker.id, window.day);
+    set_count(&app, &key, 20).await;
+
+    match applications::take_daily_slots(&app.state, seeker.id, None, late).await {
+        Err(AppError::QuotaExceeded { resets_at, .. }) => assert_eq!(resets_at, utc(3, 0)),
+        Err(e) => panic!("unexpected error: {:?}", e),
+        Ok(_) => panic!("cap was not enforced"),
+    }
+    assert_eq!(count(&app, &key).await, 20);
+
+    // Past local midnight the count starts over, though it's the same UTC date
+    let next_day = utc(3, 30);
+    let slots = applications::take_daily_slots(&app.state, seeker.id, None, next_day).await;
+    assert!(slots.is_ok());
+    let next_key =
+        applications::seeker_daily_key(seeker.id, NaiveDate::from_ymd_opt(2026, 10, 17).unwrap());
+    assert_eq!(count(&app, &next_key).await, 1);
+}
+
+#[sqlx::test]
+async fn test_capped_submission_returns_reset_time(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let key = applications::seeker_daily_key(seeker.id, today(&app).await);
+
+    set_count(&app, &key, 20).await;
+    let res = apply(&app, &seeker, job_id).await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.body["resets_at"].is_string());
+    assert!(res.headers.contains_key(header::RETRY_AFTER));
+    assert_eq!(application_count(&app, &seeker).await, 0);
+    assert_eq!(count(&app, &key).await, 20);
+
+    set_count(&app, &key, 0).await;
+    let res = apply(&app, &seeker, job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(count(&app, &key).await, 1);
+
+    // A rejected submission gives its slot back
+    let res = apply(&app, &seeker, job_id).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert_eq!(count(&app, &key).await, 1);
+}
+
+#[sqlx::test]
+async fn test_omil_submissions_count_against_seeker_and_organization(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company: TestCompany = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+    let day = today(&app).await;
+    let seeker_key = applications::seeker_daily_key(seeker.id, day);
+    let omil_key = applications::omil_daily_key(omil.id, day);
+    let uri = format!("/api/me/omil/job-seekers/{}/apply", managed_id);
+
+    let first_job = app.create_active_job(&company).await;
+    let res = app
+        .post(&uri, Some(&omil.director), json!({ "job_id": first_job }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(count(&app, &seeker_key).await, 1);
+    assert_eq!(count(&app, &omil_key).await, 1);
+
+    // The seeker's own cap holds however the application is sent
+    let second_job = app.create_active_job(&company).await;
+    set_count(&app, &seeker_key, 20).await;
+    let res = app
+        .post(&uri, Some(&omil.director), json!({ "job_id": second_job }))
+        .await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(count(&app, &omil_key).await, 1);
+
+    // A used-up organization cap doesn't take from the seeker's
+    set_count(&app, &seeker_key, 1).await;
+    set_count(&app, &omil_key, 200).await;
+    let res = app
+        .post(&uri, Some(&omil.director), json!({ "job_id": second_job }))
+        .await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(count(&app, &seeker_key).await, 1);
+
+    // The seeker can still apply on their own
+    let res = apply(&app, &seeker, second_job).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(count(&app, &seeker_key).await, 2);
+    assert_eq!(count(&app, &omil_key).await, 200);
+}
+
+#[sqlx::test]
+async fn test_mostly_mismatched_history_adds_notice(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+
+    let mut application_ids = Vec::new();
+    for _ in 0..applications::MISMATCH_WINDOW {
+        let job_id = app.create_active_job(&company).await;
+        application_ids.push(app.create_application(job_id, &seeker).await);
+    }
+    let reject = |ids: &[Uuid]| {
+        sqlx::query(
+            r#"
+            UPDATE job_applications
+            SET status = 'rejected', rejection_reason_code = 'profile_mismatch'
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(ids.to_vec())
+        .execute(app.db())
+    };
+
+    // Exactly 80% is not enough
+    reject(&application_ids[..16]).await.unwrap();
+    let job_id = app.create_active_job(&company).await;
+    let res = apply(&app, &seeker, job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([]));
+
+    // The submission above pushed the oldest out of the window, leaving 15
+    // mismatches in it; two more make 85%
+    let latest: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+    reject(&[latest, application_ids[19]]).await.unwrap();
+    let job_id = app.create_active_job(&company).await;
+    let res = apply(&app, &seeker, job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([applications::MISMATCH_NOTICE]));
+}
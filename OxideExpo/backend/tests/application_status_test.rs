@@ -0,0 +1,147 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Company owner and one of their jobs
+async fn create_job(app: &TestApp) -> (TestCompany, Uuid) {
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    (company, job_id)
+}
+
+async fn apply(app: &TestApp, job_id: Uuid, status: &str) -> (Uuid, TestUser) {
+    let applicant = app.create_job_seeker().await;
+    let app_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        WITH application AS (
+            INSERT INTO job_applications (job_id, applicant_id, status)
+            VALUES ($1, $2, $3::application_status)
+            RETURNING id
+        ), counted AS (
+            UPDATE jobs SET applications_count = applications_count + 1
+            WHERE id = $1 AND $3 <> 'withdrawn'
+        )
+        SELECT id FROM application
+        "#,
+    )
+    .bind(job_id)
+    .bind(applicant.id)
+    .bind(status)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    (app_id, applicant)
+}
+
+#[sqlx::test]
+async fn test_rejected_application_cannot_be_hired(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id) = create_job(&app).await;
+    let (app_id, _) = apply(&app, job_id, "rejected").await;
+
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", job_id, app_id),
+            Some(&company.owner),
+            json!({ "status": "hired" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(res.body["details"]["current_status"], "rejected");
+    assert_eq!(res.body["details"]["allowed_statuses"], json!([]));
+}
+
+#[sqlx::test]
+async fn test_legal_transition_reports_next_states(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id) = create_job(&app).await;
+    let (app_id, _) = apply(&app, job_id, "submitted").await;
+    let uri = format!("/api/me/jobs/{}/applications/{}", job_id, app_id);
+
+    // Skipping review is not allowed
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "status": "offer_extended" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        res.body["details"]["allowed_statuses"],
+        json!(["under_review", "rejected"])
+    );
+
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "status": "under_review" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "under_review");
+}
+
+#[sqlx::test]
+async fn test_bulk_update_skips_illegal_transitions(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id) = create_job(&app).await;
+    let (submitted_id, _) = apply(&app, job_id, "submitted").await;
+    let (withdrawn_id, _) = apply(&app, job_id, "withdrawn").await;
+    let (hired_id, _) = apply(&app, job_id, "hired").await;
+    let unknown_id = Uuid::new_v4();
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/applicants/bulk-status", job_id),
+            Some(&company.owner),
+            json!({
+                "application_ids": [submitted_id, withdrawn_id, hired_id, unknown_id],
+                "status": "rejected"
+            }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["updated_count"], 1);
+    assert_eq!(res.body["failed_ids"], json!([unknown_id]));
+
+    let skipped = res.body["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 2);
+    assert_eq!(skipped[0]["application_id"], json!(withdrawn_id));
+    assert_eq!(skipped[0]["current_status"], "withdrawn");
+    assert_eq!(skipped[1]["application_id"], json!(hired_id));
+    assert_eq!(skipped[1]["current_status"], "hired");
+
+    let statuses: Vec<String> = sqlx::query_scalar(
+        "SELECT status::text FROM job_applications WHERE id = ANY($1) ORDER BY status::text",
+    )
+    .bind(vec![submitted_id, withdrawn_id, hired_id])
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(statuses, vec!["hired", "rejected", "withdrawn"]);
+}
+
+#[sqlx::test]
+async fn test_withdrawn_application_cannot_be_withdrawn_again(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (_, job_id) = create_job(&app).await;
+    let (app_id, applicant) = apply(&app, job_id, "interview_scheduled").await;
+    let uri = format!("/api/me/applications/{}/withdraw", app_id);
+
+    let res = app.patch(&uri, Some(&applicant), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "withdrawn");
+
+    let res = app.patch(&uri, Some(&applicant), json!({})).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(res.body["details"]["current_status"], "withdrawn");
+}
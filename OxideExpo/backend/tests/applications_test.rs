@@ -1,255 +1,152 @@
 mod common;
 
-use empleos_inclusivos_backend::{handlers, middleware, AppState};
-use axum::{
-    body::Body,
-    http::{Request, StatusCode, header},
-    Router,
-    routing::{get, post},
-    middleware as axum_middleware,
-};
-use tower::ServiceExt;
+use axum::http::StatusCode;
+use common::TestApp;
 use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[sqlx::test]
+async fn test_submit_application_success(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({
+                "job_id": job_id,
+                "cover_letter": "I am very interested in this position."
+            }),
+        )
+        .await;
 
-async fn create_test_app(pool: sqlx::PgPool) -> Router {
-    let state = AppState { db: pool };
-
-    Router::new()
-        .route("/applications", post(handlers::create_application))
-        .route("/applications/my", get(handlers::my_applications))
-        .route_layer(axum_middleware::from_fn(middleware::require_auth))
-        .with_state(state)
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["job_id"], job_id.to_string());
+    assert_eq!(res.body["applicant_id"], seeker.id.to_string());
+    assert_eq!(res.body["status"], "submitted");
 }
 
-fn create_test_token(user_id: i32) -> String {
-    std::env::set_var("JWT_SECRET", "test-secret");
-    let (token, _) = empleos_inclusivos_backend::utils::create_jwt(
-        user_id,
-        "test@example.com",
-        "usuario"
-    ).unwrap();
-    token
-}
+#[sqlx::test]
+async fn test_submit_application_without_auth(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
 
-#[tokio::test]
-async fn test_create_application_success() {
-    let pool = common::setup_test_db().await;
-
-    let password_hash = bcrypt::hash("password", bcrypt::DEFAULT_COST).unwrap();
-    let user_id = common::create_test_user(&pool, "user@test.com", &password_hash).await;
-    let company_id = common::create_test_company(&pool).await;
-    let job_id = common::create_test_job(&pool, company_id, "Test Job", 1).await;
-
-    let token = create_test_token(user_id);
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "job_id": job_id,
-        "cover_letter": "I am very interested in this position."
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/applications")
-                .header("content-type", "application/json")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
-        )
-        .await
-        .unwrap();
+    let res = app
+        .post("/api/me/applications", None, json!({ "job_id": job_id }))
+        .await;
 
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+}
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let application: serde_json::Value = serde_json::from_slice(&body).unwrap();
+#[sqlx::test]
+async fn test_submit_application_as_company_member(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&company.owner),
+            json!({ "job_id": job_id }),
+        )
+        .await;
 
-    assert_eq!(application["job_id"], job_id);
-    assert_eq!(application["user_id"], user_id);
-    assert_eq!(application["status"], "pending");
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
 }
 
-#[tokio::test]
-async fn test_create_application_without_auth() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "job_id": 1,
-        "cover_letter": "Test"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/applications")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
+#[sqlx::test]
+async fn test_submit_application_duplicate(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    app.create_application(job_id, &seeker).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
         )
-        .await
-        .unwrap();
+        .await;
 
-    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
 }
 
-#[tokio::test]
-async fn test_create_application_duplicate() {
-    let pool = common::setup_test_db().await;
-
-    let password_hash = bcrypt::hash("password", bcrypt::DEFAULT_COST).unwrap();
-    let user_id = common::create_test_user(&pool, "user@test.com", &password_hash).await;
-    let company_id = common::create_test_company(&pool).await;
-    let job_id = common::create_test_job(&pool, company_id, "Test Job", 1).await;
-
-    let token = create_test_token(user_id);
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "job_id": job_id,
-        "cover_letter": "First application"
-    });
-
-    // First application
-    let _ = app
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/applications")
-                .header("content-type", "application/json")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
-        )
-        .await
-        .unwrap();
+#[sqlx::test]
+async fn test_submit_application_nonexistent_job(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
 
-    // Duplicate application
-    let payload2 = json!({
-        "job_id": job_id,
-        "cover_letter": "Second application (duplicate)"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/applications")
-                .header("content-type", "application/json")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::from(serde_json::to_string(&payload2).unwrap()))
-                .unwrap()
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": Uuid::new_v4() }),
         )
-        .await
-        .unwrap();
+        .await;
 
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
 }
 
-#[tokio::test]
-async fn test_create_application_nonexistent_job() {
-    let pool = common::setup_test_db().await;
-
-    let password_hash = bcrypt::hash("password", bcrypt::DEFAULT_COST).unwrap();
-    let user_id = common::create_test_user(&pool, "user@test.com", &password_hash).await;
-
-    let token = create_test_token(user_id);
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "job_id": 99999,
-        "cover_letter": "Application to non-existent job"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/applications")
-                .header("content-type", "application/json")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
-        )
+#[sqlx::test]
+async fn test_submit_application_requires_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    sqlx::query("UPDATE job_seeker_profiles SET completeness_percentage = 20 WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
-}
-
-#[tokio::test]
-async fn test_my_applications_empty() {
-    let pool = common::setup_test_db().await;
-
-    let password_hash = bcrypt::hash("password", bcrypt::DEFAULT_COST).unwrap();
-    let user_id = common::create_test_user(&pool, "user@test.com", &password_hash).await;
-
-    let token = create_test_token(user_id);
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/applications/my")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::empty())
-                .unwrap()
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
         )
-        .await
-        .unwrap();
+        .await;
 
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let applications: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+#[sqlx::test]
+async fn test_my_applications_empty(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
 
-    assert_eq!(applications.len(), 0);
-}
+    let res = app.get("/api/me/applications", Some(&seeker)).await;
 
-#[tokio::test]
-async fn test_my_applications_with_data() {
-    let pool = common::setup_test_db().await;
-
-    let password_hash = bcrypt::hash("password", bcrypt::DEFAULT_COST).unwrap();
-    let user_id = common::create_test_user(&pool, "user@test.com", &password_hash).await;
-    let company_id = common::create_test_company(&pool).await;
-    let job_id = common::create_test_job(&pool, company_id, "Test Job", 1).await;
-
-    // Create application directly in DB
-    sqlx::query!(
-        "INSERT INTO job_applications (job_id, user_id, cover_letter, status) VALUES ($1, $2, $3, 'pending')",
-        job_id,
-        user_id,
-        "Test cover letter"
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    let token = create_test_token(user_id);
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/applications/my")
-                .header(header::AUTHORIZATION, format!("Bearer {}", token))
-                .body(Body::empty())
-                .unwrap()
-        )
-        .await
-        .unwrap();
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body.as_array().unwrap().is_empty());
+}
 
-    assert_eq!(response.status(), StatusCode::OK);
+#[sqlx::test]
+async fn test_my_applications_with_data(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let application_id = app.create_application(job_id, &seeker).await;
+    app.create_application(job_id, &other).await;
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let applications: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    let res = app.get("/api/me/applications", Some(&seeker)).await;
 
+    assert_eq!(res.status, StatusCode::OK);
+    let applications = res.body.as_array().unwrap();
     assert_eq!(applications.len(), 1);
-    assert_eq!(applications[0]["job_id"], job_id);
-    assert_eq!(applications[0]["user_id"], user_id);
+    assert_eq!(
+        applications[0]["application"]["id"],
+        application_id.to_string()
+    );
+    assert_eq!(applications[0]["job"]["id"], job_id.to_string());
 }
@@ -1,195 +1,269 @@
 mod common;
 
-use empleos_inclusivos_backend::{handlers, models::*, AppState};
-use axum::{
-    body::Body,
-    http::{Request, StatusCode},
-    Router,
-    routing::post,
-};
-use tower::ServiceExt;
+use axum::http::StatusCode;
+use common::{TestApp, TEST_PASSWORD};
 use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_register_job_seeker_success(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            json!({
+                "email": " NewUser@Test.cl ",
+                "password": "SecurePass123",
+                "first_name": "New",
+                "last_name": "User"
+            }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body["access_token"].is_string());
+    assert!(res.body["refresh_token"].is_string());
+    assert_eq!(res.body["token_type"], "Bearer");
+    assert_eq!(res.body["user"]["email"], "newuser@test.cl");
+    assert_eq!(res.body["user"]["user_type"], "job_seeker");
+    assert_eq!(res.body["user"]["account_status"], "pending_verification");
+}
 
-async fn create_test_app(pool: sqlx::PgPool) -> Router {
-    let state = AppState { db: pool };
+#[sqlx::test]
+async fn test_register_duplicate_email(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let existing = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            json!({
+                "email": existing.email,
+                "password": "SecurePass123",
+                "first_name": "Second",
+                "last_name": "User"
+            }),
+        )
+        .await;
 
-    Router::new()
-        .route("/auth/register", post(handlers::register))
-        .route("/auth/login", post(handlers::login))
-        .with_state(state)
+    assert_eq!(res.status, StatusCode::CONFLICT);
 }
 
-#[tokio::test]
-async fn test_register_success() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "email": "newuser@test.com",
-        "password": "SecurePass123",
-        "nombre": "New",
-        "apellidos": "User",
-        "rut": "11111111-1"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/register")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
+#[sqlx::test]
+async fn test_register_rejects_short_password(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            json!({
+                "email": "short@test.cl",
+                "password": "short",
+                "first_name": "Short",
+                "last_name": "Password"
+            }),
         )
-        .await
-        .unwrap();
+        .await;
 
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_login_success(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let auth_response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": TEST_PASSWORD }),
+        )
+        .await;
 
-    assert!(auth_response["token"].is_string());
-    assert_eq!(auth_response["user"]["email"], "newuser@test.com");
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body["access_token"].is_string());
+    assert_eq!(res.body["user"]["id"], user.id.to_string());
 }
 
-#[tokio::test]
-async fn test_register_duplicate_email() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool.clone()).await;
-
-    // Create first user
-    let payload = json!({
-        "email": "duplicate@test.com",
-        "password": "SecurePass123",
-        "nombre": "First",
-        "apellidos": "User",
-        "rut": "11111111-1"
-    });
-
-    let _ = app
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/register")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
-        )
-        .await
-        .unwrap();
+#[sqlx::test]
+async fn test_login_wrong_password(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
 
-    // Try to create second user with same email
-    let payload2 = json!({
-        "email": "duplicate@test.com",
-        "password": "AnotherPass456",
-        "nombre": "Second",
-        "apellidos": "User",
-        "rut": "22222222-2"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/register")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload2).unwrap()))
-                .unwrap()
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": "WrongPassword123" }),
         )
-        .await
-        .unwrap();
+        .await;
 
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
 }
 
-#[tokio::test]
-async fn test_login_success() {
-    let pool = common::setup_test_db().await;
-
-    // Hash password for test user
-    let password_hash = bcrypt::hash("TestPassword123", bcrypt::DEFAULT_COST).unwrap();
-    common::create_test_user(&pool, "testuser@test.com", &password_hash).await;
-
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "email": "testuser@test.com",
-        "password": "TestPassword123"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/login")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
+#[sqlx::test]
+async fn test_login_nonexistent_user(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": "nobody@test.cl", "password": TEST_PASSWORD }),
         )
+        .await;
+
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_login_suspended_user(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+    sqlx::query("UPDATE users SET account_status = 'suspended' WHERE id = $1")
+        .bind(user.id)
+        .execute(app.db())
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let auth_response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": TEST_PASSWORD }),
+        )
+        .await;
 
-    assert!(auth_response["token"].is_string());
-    assert_eq!(auth_response["user"]["email"], "testuser@test.com");
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
 }
 
-#[tokio::test]
-async fn test_login_wrong_password() {
-    let pool = common::setup_test_db().await;
+#[sqlx::test]
+async fn test_me_requires_token(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
 
-    let password_hash = bcrypt::hash("CorrectPassword", bcrypt::DEFAULT_COST).unwrap();
-    common::create_test_user(&pool, "user@test.com", &password_hash).await;
+    let res = app.get("/api/auth/me", None).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
 
-    let app = create_test_app(pool).await;
+    let res = app.get("/api/auth/me", Some(&user)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["email"], user.email.as_str());
+}
 
-    let payload = json!({
-        "email": "user@test.com",
-        "password": "WrongPassword"
-    });
+#[sqlx::test]
+async fn test_refresh_rotates_token(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
 
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/login")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
+    let login = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": TEST_PASSWORD }),
         )
-        .await
-        .unwrap();
+        .await;
+    let refresh_token = login.body["refresh_token"].as_str().unwrap().to_string();
+
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_ne!(res.body["refresh_token"], refresh_token.as_str());
+
+    // The old refresh token was revoked by the rotation
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+}
 
-    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+#[sqlx::test]
+async fn test_refresh_rejected_after_suspension(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let user = app.create_job_seeker().await;
+
+    let login = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": TEST_PASSWORD }),
+        )
+        .await;
+    let refresh_token = login.body["refresh_token"].as_str().unwrap().to_string();
+
+    let res = app
+        .patch(
+            &format!("/api/admin/users/{}/status", user.id),
+            Some(&admin),
+            json!({ "status": "suspended", "reason": "Spam" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Suspending revokes the refresh tokens already handed out
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
 }
 
-#[tokio::test]
-async fn test_login_nonexistent_user() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool).await;
-
-    let payload = json!({
-        "email": "nonexistent@test.com",
-        "password": "SomePassword"
-    });
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/auth/login")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&payload).unwrap()))
-                .unwrap()
+#[sqlx::test]
+async fn test_refresh_checks_account_status(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+
+    let login = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": user.email, "password": TEST_PASSWORD }),
         )
+        .await;
+    let refresh_token = login.body["refresh_token"].as_str().unwrap().to_string();
+
+    // A status change that left the refresh token in place is still enforced
+    sqlx::query("UPDATE users SET account_status = 'suspended' WHERE id = $1")
+        .bind(user.id)
+        .execute(app.db())
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_logout_revokes_access_token(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+
+    let res = app.post("/api/auth/logout", Some(&user), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/auth/me", Some(&user)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
 }
@@ -0,0 +1,464 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::{
+    middleware::AuthUser,
+    models::{omil::OmilRole, user::UserType},
+    services::authz,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Who calls. Companies have owner, admin and member roles; a removed member
+/// keeps the company member account but loses the membership.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Persona {
+    Seeker,
+    /// Registered but never verified the email
+    Unverified,
+    /// An OMIL director acting as one of its managed seekers
+    Impersonated,
+    CompanyMember,
+    CompanyAdmin,
+    CompanyOwner,
+    RemovedCompanyMember,
+    OmilAdvisor,
+    OmilCoordinator,
+    OmilDirector,
+    Admin,
+}
+
+use Persona::*;
+
+const EVERYONE: &[Persona] = &[
+    Seeker,
+    Unverified,
+    Impersonated,
+    CompanyMember,
+    CompanyAdmin,
+    CompanyOwner,
+    RemovedCompanyMember,
+    OmilAdvisor,
+    OmilCoordinator,
+    OmilDirector,
+    Admin,
+];
+// Unverified seekers aren't held back from anything yet
+const SEEKERS: &[Persona] = &[Seeker, Unverified, Impersonated];
+const COMPANY: &[Persona] = &[CompanyMember, CompanyAdmin, CompanyOwner];
+const COMPANY_MANAGERS: &[Persona] = &[CompanyAdmin, CompanyOwner];
+const OMIL: &[Persona] = &[OmilAdvisor, OmilCoordinator, OmilDirector];
+const OMIL_SUPERVISORS: &[Persona] = &[OmilCoordinator, OmilDirector];
+const OMIL_DIRECTOR: &[Persona] = &[OmilDirector];
+const ADMIN: &[Persona] = &[Admin];
+// Impersonation sessions can't touch the account
+const ACCOUNT_HOLDERS: &[Persona] = &[
+    Seeker,
+    Unverified,
+    CompanyMember,
+    CompanyAdmin,
+    CompanyOwner,
+    RemovedCompanyMember,
+    OmilAdvisor,
+    OmilCoordinator,
+    OmilDirector,
+    Admin,
+];
+
+struct Endpoint {
+    method: Method,
+    uri: String,
+    body: Option<Value>,
+    allowed: &'static [Persona],
+}
+
+fn endpoint(
+    method: Method,
+    uri: String,
+    body: Option<Value>,
+    allowed: &'static [Persona],
+) -> Endpoint {
+    Endpoint {
+        method,
+        uri,
+        body,
+        allowed,
+    }
+}
+
+async fn add_company_member(app: &TestApp, company_id: Uuid, role: &str, active: bool) -> TestUser {
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role, is_active) VALUES ($1, $2, $3::member_role, $4)",
+    )
+    .bind(company_id)
+    .bind(member.id)
+    .bind(role)
+    .bind(active)
+    .execute(app.db())
+    .await
+    .unwrap();
+    member
+}
+
+async fn add_omil_member(app: &TestApp, omil_id: Uuid, role: &str) -> TestUser {
+    let member = app.create_user(UserType::OmilMember).await;
+    sqlx::query("INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role)")
+        .bind(omil_id)
+        .bind(member.id)
+        .bind(role)
+        .execute(app.db())
+        .await
+        .unwrap();
+    member
+}
+
+/// Session of `director` acting as a seeker their OMIL registered
+async fn impersonation_session(app: &TestApp, director: &TestUser) -> TestUser {
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(director),
+            json!({
+                "email": "gestionada@correo.cl",
+                "first_name": "Camila",
+                "last_name": "Rojas",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let managed_id: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+    let user_id: Uuid =
+        sqlx::query_scalar("SELECT job_seeker_id FROM omil_managed_job_seekers WHERE id = $1")
+            .bind(managed_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}/impersonate", managed_id),
+            Some(director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    TestUser {
+        id: user_id,
+        email: "gestionada@correo.cl".to_string(),
+        user_type: UserType::JobSeeker,
+        token: res.body["impersonation_token"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+    }
+}
+
+/// Every persona against every endpoint. Allowed calls may still fail for
+/// other reasons (a missing record, a bad request), but never with 401 or
+/// 403; denied calls get 403.
+#[sqlx::test]
+async fn test_authorization_matrix(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let omil = app.create_omil_with_director().await;
+    // Someone the director may add to the OMIL
+    let recruit = app.create_user(UserType::OmilMember).await;
+
+    let unverified = app.create_user(UserType::JobSeeker).await;
+    sqlx::query(
+        "UPDATE users SET account_status = 'pending_verification', email_verified_at = NULL WHERE id = $1",
+    )
+    .bind(unverified.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let personas = vec![
+        (Seeker, app.create_job_seeker().await),
+        (Unverified, unverified),
+        (
+            Impersonated,
+            impersonation_session(&app, &omil.director).await,
+        ),
+        (
+            CompanyMember,
+            add_company_member(&app, company.id, "member", true).await,
+        ),
+        (
+            CompanyAdmin,
+            add_company_member(&app, company.id, "admin", true).await,
+        ),
+        (
+            RemovedCompanyMember,
+            add_company_member(&app, company.id, "member", false).await,
+        ),
+        (OmilAdvisor, add_omil_member(&app, omil.id, "advisor").await),
+        (
+            OmilCoordinator,
+            add_omil_member(&app, omil.id, "coordinator").await,
+        ),
+        (Admin, app.create_admin().await),
+        (CompanyOwner, company.owner),
+        (OmilDirector, omil.director),
+    ];
+
+    // Records that don't exist, so that allowed writes change nothing
+    let missing = Uuid::new_v4();
+    let deadline = (Utc::now() + Duration::days(20)).date_naive();
+    let jobs = |path: &str| format!("/api/me/jobs/{}{}", job_id, path);
+
+    let endpoints = vec![
+        endpoint(Method::GET, "/api/auth/me".into(), None, EVERYONE),
+        endpoint(
+            Method::DELETE,
+            "/api/me/account".into(),
+            Some(json!({ "current_password": "" })),
+            ACCOUNT_HOLDERS,
+        ),
+        // Job seekers
+        endpoint(Method::GET, "/api/me/profile".into(), None, SEEKERS),
+        endpoint(
+            Method::PUT,
+            "/api/me/profile".into(),
+            Some(json!({})),
+            SEEKERS,
+        ),
+        endpoint(Method::GET, "/api/me/applications".into(), None, SEEKERS),
+        endpoint(Method::GET, "/api/me/saved-jobs".into(), None, SEEKERS),
+        endpoint(
+            Method::POST,
+            format!("/api/me/saved-jobs/{}", missing),
+            Some(json!({})),
+            SEEKERS,
+        ),
+        endpoint(
+            Method::GET,
+            "/api/me/recommended-jobs".into(),
+            None,
+            SEEKERS,
+        ),
+        endpoint(Method::GET, "/api/me/skill-gap".into(), None, SEEKERS),
+        endpoint(Method::GET, "/api/me/invitations".into(), None, SEEKERS),
+        endpoint(
+            Method::GET,
+            "/api/me/recently-viewed-jobs".into(),
+            None,
+            SEEKERS,
+        ),
+        // Company jobs
+        endpoint(Method::GET, "/api/me/jobs".into(), None, COMPANY),
+        endpoint(
+            Method::POST,
+            "/api/me/jobs".into(),
+            Some(json!({
+                "title": "",
+                "description": "Too short",
+                "job_type": "full_time",
+                "work_modality": "on_site",
+                "application_deadline": deadline,
+                "vacancies": 1,
+            })),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(Method::GET, jobs(""), None, COMPANY),
+        endpoint(
+            Method::PUT,
+            format!("/api/me/jobs/{}", missing),
+            Some(json!({})),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(
+            Method::DELETE,
+            format!("/api/me/jobs/{}", missing),
+            None,
+            COMPANY_MANAGERS,
+        ),
+        endpoint(
+            Method::PATCH,
+            format!("/api/me/jobs/{}/status", missing),
+            Some(json!({ "status": "paused" })),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(
+            Method::PATCH,
+            format!("/api/me/jobs/{}/deadline", missing),
+            Some(json!({ "application_deadline": deadline })),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(Method::GET, jobs("/completeness"), None, COMPANY),
+        endpoint(Method::GET, jobs("/applications"), None, COMPANY),
+        endpoint(
+            Method::PUT,
+            jobs(&format!("/applications/{}", missing)),
+            Some(json!({ "status": "under_review" })),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(
+            Method::POST,
+            jobs(&format!("/applications/{}/notes", missing)),
+            Some(json!({ "note_text": "Buen perfil" })),
+            COMPANY,
+        ),
+        endpoint(Method::GET, jobs("/activity"), None, COMPANY),
+        endpoint(
+            Method::GET,
+            jobs("/automation-rules"),
+            None,
+            COMPANY_MANAGERS,
+        ),
+        // Applicants
+        endpoint(Method::GET, jobs("/applicants"), None, COMPANY),
+        endpoint(Method::GET, jobs("/applicants/export"), None, COMPANY),
+        endpoint(
+            Method::POST,
+            format!("/api/me/jobs/{}/applicants/bulk-status", missing),
+            Some(json!({ "application_ids": [missing], "status": "under_review" })),
+            COMPANY_MANAGERS,
+        ),
+        endpoint(Method::GET, jobs("/recommended-candidates"), None, COMPANY),
+        endpoint(Method::GET, "/api/me/company/tags".into(), None, COMPANY),
+        endpoint(Method::GET, "/api/me/company/profile".into(), None, COMPANY),
+        // OMIL
+        endpoint(Method::GET, "/api/me/omil".into(), None, OMIL),
+        endpoint(Method::GET, "/api/me/omil/job-seekers".into(), None, OMIL),
+        endpoint(
+            Method::GET,
+            "/api/me/omil/job-seekers/export".into(),
+            None,
+            OMIL,
+        ),
+        endpoint(Method::GET, "/api/me/omil/members".into(), None, OMIL),
+        endpoint(Method::GET, "/api/me/omil/transfers".into(), None, OMIL),
+        endpoint(
+            Method::POST,
+            "/api/me/omil/members".into(),
+            Some(json!({ "email": recruit.email, "role": "coordinator" })),
+            OMIL_DIRECTOR,
+        ),
+        endpoint(
+            Method::GET,
+            "/api/me/omil/pending-actions".into(),
+            None,
+            OMIL_SUPERVISORS,
+        ),
+        endpoint(
+            Method::GET,
+            "/api/me/omil/reports/monthly".into(),
+            None,
+            OMIL_SUPERVISORS,
+        ),
+        endpoint(
+            Method::PUT,
+            "/api/me/omil/export-settings".into(),
+            Some(json!({ "allow_contact_export": false })),
+            OMIL_DIRECTOR,
+        ),
+        endpoint(
+            Method::GET,
+            "/api/me/omil/exports".into(),
+            None,
+            OMIL_DIRECTOR,
+        ),
+        // Admin panel
+        endpoint(
+            Method::GET,
+            "/api/admin/dashboard/stats".into(),
+            None,
+            ADMIN,
+        ),
+        endpoint(Method::GET, "/api/admin/users".into(), None, ADMIN),
+        endpoint(Method::GET, "/api/admin/jobs/pending".into(), None, ADMIN),
+        endpoint(Method::GET, "/api/admin/settings".into(), None, ADMIN),
+    ];
+
+    let mut mismatches = Vec::new();
+    for endpoint in &endpoints {
+        for (persona, user) in &personas {
+            let res = app
+                .request(
+                    endpoint.method.clone(),
+                    &endpoint.uri,
+                    Some(user),
+                    endpoint.body.clone(),
+                )
+                .await;
+            let allowed = endpoint.allowed.contains(persona);
+            let ok = if allowed {
+                res.status != StatusCode::UNAUTHORIZED && res.status != StatusCode::FORBIDDEN
+            } else {
+                res.status == StatusCode::FORBIDDEN
+            };
+            if !ok {
+                mismatches.push(format!(
+                    "{} {} as {:?}: expected {}, got {} {}",
+                    endpoint.method,
+                    endpoint.uri,
+                    persona,
+                    if allowed { "access" } else { "403" },
+                    res.status,
+                    res.body
+                ));
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{} authorization mismatch(es):\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+/// A member of several OMILs acts for the active one where they rank
+/// highest, both in the OMIL routes and in the session's capabilities
+#[sqlx::test]
+async fn test_several_omil_memberships_resolve_deterministically(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let advised = app.create_omil_with_director().await;
+    let directed = app.create_omil_with_director().await;
+    let left = app.create_omil_with_director().await;
+
+    let member = add_omil_member(&app, advised.id, "advisor").await;
+    for (omil_id, active, joined_days_ago) in [(directed.id, true, 1), (left.id, false, 30)] {
+        sqlx::query(
+            r#"
+            INSERT INTO omil_members (omil_id, user_id, role, is_active, joined_at)
+            VALUES ($1, $2, 'director', $3, NOW() - make_interval(days => $4))
+            "#,
+        )
+        .bind(omil_id)
+        .bind(member.id)
+        .bind(active)
+        .bind(joined_days_ago)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+
+    for _ in 0..3 {
+        let res = app.get("/api/me/omil", Some(&member)).await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        assert_eq!(res.body["organization"]["id"], json!(directed.id));
+    }
+
+    let auth_user = AuthUser {
+        id: member.id,
+        email: member.email.clone(),
+        user_type: "omil_member".to_string(),
+        jti: Uuid::new_v4().to_string(),
+        impersonator_id: None,
+        scopes: None,
+        kiosk: None,
+    };
+    let context = authz::load_context(app.db(), &auth_user).await.unwrap();
+    let membership = context.omil.unwrap();
+    assert_eq!(membership.omil_id, directed.id);
+    assert_eq!(membership.role, OmilRole::Director);
+}
@@ -0,0 +1,309 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::http::StatusCode;
+use common::TestApp;
+use empleos_inclusivos_backend::error::{AppError, Result};
+use empleos_inclusivos_backend::models::admin::BackfillStatus;
+use empleos_inclusivos_backend::services::backfill::{
+    self, Backfill, Batch, StatusHistoryBackfill,
+};
+use futures::future::BoxFuture;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Copies the numbers 1 to 20 into `backfill_test_rows`, failing once at 10
+/// while `fail` is set
+struct FlakyBackfill {
+    fail: AtomicBool,
+}
+
+impl Backfill for FlakyBackfill {
+    fn name(&self) -> &'static str {
+        "flaky_test_backfill"
+    }
+
+    fn default_batch_size(&self) -> i32 {
+        5
+    }
+
+    fn process_batch<'a>(
+        &'a self,
+        conn: &'a mut PgConnection,
+        cursor: Option<&'a str>,
+        batch_size: i64,
+    ) -> BoxFuture<'a, Result<Batch>> {
+        Box::pin(async move {
+            let after: i64 = cursor.map_or(0, |c| c.parse().unwrap());
+            let last = (after + batch_size).min(20);
+            if after >= last {
+                return Ok(Batch {
+                    processed: 0,
+                    cursor: None,
+                });
+            }
+
+            sqlx::query("INSERT INTO backfill_test_rows SELECT generate_series($1, $2)")
+                .bind(after + 1)
+                .bind(last)
+                .execute(&mut *conn)
+                .await?;
+
+            if after == 10 && self.fail.load(Ordering::SeqCst) {
+                return Err(AppError::InternalError("boom".to_string()));
+            }
+
+            Ok(Batch {
+                processed: last - after,
+                cursor: Some(last.to_string()),
+            })
+        })
+    }
+}
+
+async fn set_running(app: &TestApp, name: &str) {
+    sqlx::query("UPDATE backfill_jobs SET status = 'running' WHERE name = $1")
+        .bind(name)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+async fn run_to_end(app: &TestApp, backfill: &dyn Backfill) {
+    for _ in 0..20 {
+        match backfill::run_batch(app.db(), backfill).await.unwrap() {
+            Some(BackfillStatus::Running) => continue,
+            Some(BackfillStatus::Completed) => return,
+            other => panic!("Backfill stopped as {:?}", other),
+        }
+    }
+    panic!("Backfill did not complete");
+}
+
+#[sqlx::test]
+async fn test_failed_batch_rolls_back_and_resumes_from_cursor(db: PgPool) {
+    let app = TestApp::new(db).await;
+    sqlx::query("CREATE TABLE backfill_test_rows (n BIGINT PRIMARY KEY)")
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let flaky = FlakyBackfill {
+        fail: AtomicBool::new(true),
+    };
+    backfill::register(app.db(), &flaky).await.unwrap();
+
+    // Not started yet
+    assert_eq!(backfill::run_batch(app.db(), &flaky).await.unwrap(), None);
+
+    set_running(&app, flaky.name()).await;
+    for expected in [
+        BackfillStatus::Running,
+        BackfillStatus::Running,
+        BackfillStatus::Failed,
+    ] {
+        assert_eq!(
+            backfill::run_batch(app.db(), &flaky).await.unwrap(),
+            Some(expected)
+        );
+    }
+
+    let (status, cursor, processed, last_error): (String, Option<String>, i64, Option<String>) =
+        sqlx::query_as(
+            "SELECT status::text, cursor, processed, last_error FROM backfill_jobs WHERE name = $1",
+        )
+        .bind(flaky.name())
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(status, "failed");
+    assert_eq!(cursor.as_deref(), Some("10"));
+    assert_eq!(processed, 10);
+    assert!(last_error.unwrap().contains("boom"));
+
+    // The failed batch's rows went with it
+    let copied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM backfill_test_rows")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(copied, 10);
+
+    // A failed backfill waits to be started again
+    assert_eq!(backfill::run_batch(app.db(), &flaky).await.unwrap(), None);
+
+    flaky.fail.store(false, Ordering::SeqCst);
+    set_running(&app, flaky.name()).await;
+    run_to_end(&app, &flaky).await;
+
+    let (copied, processed): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT (SELECT COUNT(*) FROM backfill_test_rows), processed
+        FROM backfill_jobs WHERE name = $1
+        "#,
+    )
+    .bind(flaky.name())
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(copied, 20);
+    assert_eq!(processed, 20);
+}
+
+#[sqlx::test]
+async fn test_start_and_pause_endpoints(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+    let name = StatusHistoryBackfill.name();
+    let uri = |action: &str| format!("/api/admin/backfills/{}/{}", name, action);
+
+    let res = app.get("/api/admin/backfills", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let listed = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == name)
+        .unwrap()
+        .clone();
+    assert_eq!(listed["status"], "pending");
+    assert_eq!(listed["cursor"], json!(null));
+
+    let res = app.post(&uri("pause"), Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let res = app.post(&uri("start"), Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "running");
+    assert!(res.body["started_at"].is_string());
+
+    let res = app.post(&uri("start"), Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let res = app.post(&uri("pause"), Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "paused");
+
+    // Paused backfills are left alone by the worker
+    assert_eq!(
+        backfill::run_batch(app.db(), &StatusHistoryBackfill)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let res = app.post(&uri("start"), Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "running");
+
+    let res = app
+        .post(
+            "/api/admin/backfills/unknown/start",
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app.get("/api/admin/backfills", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_status_history_backfill_fills_missing_timelines(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Applications written straight with a later status have no history
+    let mut legacy = Vec::new();
+    for status in ["hired", "rejected", "under_review"] {
+        let seeker = app.create_job_seeker().await;
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO job_applications (job_id, applicant_id, status)
+            VALUES ($1, $2, $3::application_status)
+            RETURNING id
+            "#,
+        )
+        .bind(job_id)
+        .bind(seeker.id)
+        .bind(status)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+        legacy.push(id);
+    }
+
+    let seeker = app.create_job_seeker().await;
+    let submitted = app.create_application(job_id, &seeker).await;
+
+    let seeker = app.create_job_seeker().await;
+    let logged = app.create_application(job_id, &seeker).await;
+    sqlx::query("UPDATE job_applications SET status = 'under_review' WHERE id = $1")
+        .bind(logged)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app.get("/api/admin/backfills", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    sqlx::query("UPDATE backfill_jobs SET batch_size = 2 WHERE name = $1")
+        .bind(StatusHistoryBackfill.name())
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app
+        .post(
+            &format!(
+                "/api/admin/backfills/{}/start",
+                StatusHistoryBackfill.name()
+            ),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    run_to_end(&app, &StatusHistoryBackfill).await;
+
+    let history_of = |id: Uuid| {
+        sqlx::query_as::<_, (Option<String>, String)>(
+            r#"
+            SELECT previous_status::text, new_status::text
+            FROM application_status_history WHERE application_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(id)
+        .fetch_all(app.db())
+    };
+
+    assert_eq!(
+        history_of(legacy[0]).await.unwrap(),
+        vec![(None, "hired".to_string())]
+    );
+    assert_eq!(history_of(legacy[1]).await.unwrap().len(), 1);
+    assert_eq!(history_of(legacy[2]).await.unwrap().len(), 1);
+    assert!(history_of(submitted).await.unwrap().is_empty());
+    assert_eq!(
+        history_of(logged).await.unwrap(),
+        vec![(Some("submitted".to_string()), "under_review".to_string())]
+    );
+
+    let res = app.get("/api/admin/backfills", Some(&admin)).await;
+    let job = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["name"] == StatusHistoryBackfill.name())
+        .unwrap()
+        .clone();
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["processed"], 5);
+}
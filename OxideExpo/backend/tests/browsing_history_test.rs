@@ -0,0 +1,237 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::services::browsing;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Views and searches are written in the background; wait for them to land
+async fn wait_for(app: &TestApp, seeker: &TestUser, table: &str, count: i64) {
+    let query = format!("SELECT COUNT(*) FROM {} WHERE user_id = $1", table);
+    for _ in 0..50 {
+        let current: i64 = sqlx::query_scalar(&query)
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+        if current == count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("{} never reached {} rows", table, count);
+}
+
+#[sqlx::test]
+async fn test_viewing_a_job_again_moves_it_to_the_front(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let first = app.create_active_job(&company).await;
+    let second = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    for job_id in [first, second] {
+        let res = app
+            .get(&format!("/api/jobs/{}", job_id), Some(&seeker))
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        wait_for(
+            &app,
+            &seeker,
+            "job_view_history",
+            if job_id == first { 1 } else { 2 },
+        )
+        .await;
+    }
+
+    let res = app
+        .post(
+            &format!("/api/me/saved-jobs/{}", first),
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    browsing::record_view(app.db(), seeker.id, first)
+        .await
+        .unwrap();
+
+    let res = app.get("/api/me/recently-viewed-jobs", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let jobs = res.body.as_array().unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0]["job_id"], first.to_string());
+    assert_eq!(jobs[0]["is_saved"], true);
+    assert_eq!(jobs[0]["has_applied"], false);
+    assert_eq!(jobs[0]["status"], "active");
+    assert_eq!(jobs[1]["job_id"], second.to_string());
+
+    // Anonymous views aren't kept, and other users have no history
+    let res = app.get(&format!("/api/jobs/{}", second), None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app
+        .get("/api/me/recently-viewed-jobs", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_history_is_pruned_to_the_most_recent(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let mut jobs = Vec::new();
+    for _ in 0..browsing::VIEW_HISTORY_CAP + 1 {
+        let job_id = app.create_active_job(&company).await;
+        browsing::record_view(app.db(), seeker.id, job_id)
+            .await
+            .unwrap();
+        jobs.push(job_id);
+    }
+
+    let kept: Vec<Uuid> =
+        sqlx::query_scalar("SELECT job_id FROM job_view_history WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_all(app.db())
+            .await
+            .unwrap();
+    assert_eq!(kept.len() as i64, browsing::VIEW_HISTORY_CAP);
+    assert!(!kept.contains(&jobs[0]));
+
+    let res = app.get("/api/me/recently-viewed-jobs", Some(&seeker)).await;
+    assert_eq!(
+        res.body.as_array().unwrap().len() as i64,
+        browsing::RECENTLY_VIEWED_LIMIT
+    );
+    assert_eq!(res.body[0]["job_id"], jobs.last().unwrap().to_string());
+
+    for radius_km in 0..browsing::RECENT_SEARCHES_CAP + 2 {
+        browsing::record_search(app.db(), seeker.id, json!({ "radius_km": radius_km }))
+            .await
+            .unwrap();
+    }
+    // Repeating a search doesn't add a row
+    browsing::record_search(app.db(), seeker.id, json!({ "radius_km": 5 }))
+        .await
+        .unwrap();
+
+    let res = app.get("/api/me/recent-searches", Some(&seeker)).await;
+    let searches = res.body.as_array().unwrap();
+    assert_eq!(searches.len() as i64, browsing::RECENT_SEARCHES_CAP);
+    assert_eq!(searches[0]["filters"], json!({ "radius_km": 5 }));
+    assert!(searches
+        .iter()
+        .all(|s| s["filters"] != json!({ "radius_km": 0 })));
+}
+
+#[sqlx::test]
+async fn test_recent_searches_from_job_listing(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    // Unfiltered browsing isn't a search worth keeping
+    let res = app.get("/api/jobs?page=2", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    for search in ["bodega", "cajero"] {
+        let res = app
+            .get(
+                &format!("/api/jobs?search={}&work_modality=remote", search),
+                Some(&seeker),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        wait_for(
+            &app,
+            &seeker,
+            "recent_job_searches",
+            if search == "bodega" { 1 } else { 2 },
+        )
+        .await;
+    }
+
+    let res = app.get("/api/me/recent-searches", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body[0]["filters"],
+        json!({ "search": "cajero", "work_modality": "remote" })
+    );
+    let id = res.body[1]["id"].as_str().unwrap().to_string();
+
+    let res = app
+        .delete(&format!("/api/me/recent-searches/{}", id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .delete(&format!("/api/me/recent-searches/{}", id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app.delete("/api/me/recent-searches", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["deleted"], 1);
+}
+
+#[sqlx::test]
+async fn test_track_browsing_off_forgets_and_stops_recording(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    browsing::record_view(app.db(), seeker.id, job_id)
+        .await
+        .unwrap();
+    browsing::record_search(app.db(), seeker.id, json!({ "search": "bodega" }))
+        .await
+        .unwrap();
+
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "track_browsing": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["track_browsing"], false);
+    wait_for(&app, &seeker, "job_view_history", 0).await;
+    wait_for(&app, &seeker, "recent_job_searches", 0).await;
+
+    browsing::record_view(app.db(), seeker.id, job_id)
+        .await
+        .unwrap();
+    browsing::record_search(app.db(), seeker.id, json!({ "search": "bodega" }))
+        .await
+        .unwrap();
+    let res = app
+        .get(&format!("/api/jobs/{}", job_id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app.get("/api/jobs?search=bodega", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let res = app.get("/api/me/recently-viewed-jobs", Some(&seeker)).await;
+    assert_eq!(res.body, json!([]));
+    let res = app.get("/api/me/recent-searches", Some(&seeker)).await;
+    assert_eq!(res.body, json!([]));
+
+    // Turning it back on starts a fresh history
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "track_browsing": true }),
+        )
+        .await;
+    assert_eq!(res.body["track_browsing"], true);
+    browsing::record_view(app.db(), seeker.id, job_id)
+        .await
+        .unwrap();
+    wait_for(&app, &seeker, "job_view_history", 1).await;
+}
@@ -0,0 +1,239 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::models::application::ApplicationStatus;
+use empleos_inclusivos_backend::services::bulk_operations::{
+    self, ASYNC_THRESHOLD, BULK_CHUNK_SIZE,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Submitted applications from `count` fresh job seekers, inserted in one statement
+async fn seed_applications(app: &TestApp, job_id: Uuid, count: usize) -> Vec<Uuid> {
+    sqlx::query_scalar(
+        r#"
+        WITH seekers AS (
+            INSERT INTO users (email, password_hash, first_name, last_name, user_type, account_status)
+            SELECT uuid_generate_v4()::text || '@test.cl', '', 'Test', 'User', 'job_seeker', 'active'
+            FROM generate_series(1, $2)
+            RETURNING id
+        )
+        INSERT INTO job_applications (job_id, applicant_id, status)
+        SELECT $1, id, 'submitted' FROM seekers
+        RETURNING id
+        "#,
+    )
+    .bind(job_id)
+    .bind(count as i32)
+    .fetch_all(app.db())
+    .await
+    .unwrap()
+}
+
+async fn count_with_status(app: &TestApp, job_id: Uuid, status: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM job_applications WHERE job_id = $1 AND status::text = $2",
+    )
+    .bind(job_id)
+    .bind(status)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn setup(app: &TestApp, count: usize) -> (TestCompany, Uuid, Vec<Uuid>) {
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let ids = seed_applications(app, job_id, count).await;
+    (company, job_id, ids)
+}
+
+#[sqlx::test]
+async fn test_bulk_update_splits_at_chunk_boundary(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id, ids) = setup(&app, BULK_CHUNK_SIZE + 1).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/applicants/bulk-status", job_id),
+            Some(&company.owner),
+            json!({ "application_ids": ids, "status": "under_review" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["updated_count"], BULK_CHUNK_SIZE + 1);
+    assert_eq!(
+        res.body["chunks"],
+        json!([
+            { "chunk": 0, "requested": BULK_CHUNK_SIZE, "updated": BULK_CHUNK_SIZE },
+            { "chunk": 1, "requested": 1, "updated": 1 }
+        ])
+    );
+    assert!(res.body["operation"].is_null());
+    assert_eq!(
+        count_with_status(&app, job_id, "under_review").await,
+        (BULK_CHUNK_SIZE + 1) as i64
+    );
+}
+
+#[sqlx::test]
+async fn test_exact_chunk_uses_single_update(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id, ids) = setup(&app, BULK_CHUNK_SIZE).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/applicants/bulk-status", job_id),
+            Some(&company.owner),
+            json!({ "application_ids": ids, "status": "rejected" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["chunks"].as_array().unwrap().len(), 1);
+    assert_eq!(res.body["updated_count"], BULK_CHUNK_SIZE);
+}
+
+#[sqlx::test]
+async fn test_huge_selection_is_queued_and_polled(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id, ids) = setup(&app, ASYNC_THRESHOLD + 1).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/applicants/bulk-status", job_id),
+            Some(&company.owner),
+            json!({ "application_ids": ids, "status": "rejected" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::ACCEPTED);
+    assert_eq!(res.body["updated_count"], 0);
+    assert_eq!(res.body["operation"]["status"], "queued");
+    assert_eq!(res.body["operation"]["total"], ASYNC_THRESHOLD + 1);
+    assert_eq!(count_with_status(&app, job_id, "rejected").await, 0);
+
+    let op_id = res.body["operation"]["id"].as_str().unwrap().to_string();
+    assert_eq!(bulk_operations::process_pending(app.db()).await.unwrap(), 1);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/bulk-operations/{}", job_id, op_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "done");
+    assert_eq!(res.body["processed"], ASYNC_THRESHOLD + 1);
+    assert_eq!(res.body["updated_count"], ASYNC_THRESHOLD + 1);
+    assert_eq!(
+        count_with_status(&app, job_id, "rejected").await,
+        (ASYNC_THRESHOLD + 1) as i64
+    );
+
+    // Other companies cannot see the operation
+    let other = app.create_company_with_owner().await;
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/bulk-operations/{}", job_id, op_id),
+            Some(&other.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_worker_resumes_after_crash_mid_operation(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id, ids) = setup(&app, BULK_CHUNK_SIZE * 2 + 1).await;
+
+    let op = bulk_operations::enqueue(
+        app.db(),
+        job_id,
+        company.owner.id,
+        ApplicationStatus::Rejected,
+        &ids,
+        &[],
+        0,
+    )
+    .await
+    .unwrap();
+
+    // A worker committed the first chunk, then died before the next one
+    let mut tx = app.db().begin().await.unwrap();
+    bulk_operations::update_chunk(
+        &mut tx,
+        job_id,
+        &ids[..BULK_CHUNK_SIZE],
+        ApplicationStatus::Rejected,
+        company.owner.id,
+    )
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        UPDATE bulk_operations
+        SET status = 'running', started_at = NOW() - INTERVAL '1 hour',
+            heartbeat_at = NOW() - INTERVAL '1 hour',
+            next_chunk = 1, processed = $2, updated_count = $2
+        WHERE id = $1
+        "#,
+    )
+    .bind(op.id)
+    .bind(BULK_CHUNK_SIZE as i32)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+
+    assert_eq!(bulk_operations::process_pending(app.db()).await.unwrap(), 1);
+
+    let op = bulk_operations::get_operation(app.db(), job_id, op.id)
+        .await
+        .unwrap();
+    assert_eq!(op.processed, ids.len() as i32);
+    assert_eq!(op.updated_count, ids.len() as i32);
+    assert!(op.failed_ids.is_empty());
+    assert!(op.completed_at.is_some());
+
+    // The first chunk was not applied twice
+    let history: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM application_status_history WHERE application_id = ANY($1)",
+    )
+    .bind(&ids)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(history, ids.len() as i64);
+}
+
+#[sqlx::test]
+async fn test_fresh_running_operation_is_not_reclaimed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (company, job_id, ids) = setup(&app, 3).await;
+
+    let op = bulk_operations::enqueue(
+        app.db(),
+        job_id,
+        company.owner.id,
+        ApplicationStatus::Rejected,
+        &ids,
+        &[],
+        0,
+    )
+    .await
+    .unwrap();
+    sqlx::query(
+        "UPDATE bulk_operations SET status = 'running', heartbeat_at = NOW() WHERE id = $1",
+    )
+    .bind(op.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    assert_eq!(bulk_operations::process_pending(app.db()).await.unwrap(), 0);
+    assert_eq!(count_with_status(&app, job_id, "rejected").await, 0);
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use empleos_inclusivos_backend::models::user::CaptchaMode;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use wiremock::{
+    matchers::{body_string_contains, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn registration(email: &str, captcha_token: Option<&str>) -> Value {
+    json!({
+        "email": email,
+        "password": "SecurePass123",
+        "first_name": "Camila",
+        "last_name": "Rojas",
+        "captcha_token": captcha_token,
+    })
+}
+
+/// Finds a nonce the way a browser client would
+fn solve(challenge: &str, difficulty: u32) -> String {
+    (0u64..)
+        .map(|nonce| format!("{}:{}", challenge, nonce))
+        .find(|token| {
+            let hash = Sha256::digest(token.as_bytes());
+            let zero_bits: u32 = hash
+                .iter()
+                .scan(true, |counting, byte| {
+                    let bits = if *counting { byte.leading_zeros() } else { 0 };
+                    *counting = *counting && *byte == 0;
+                    Some(bits)
+                })
+                .sum();
+            zero_bits >= difficulty
+        })
+        .unwrap()
+}
+
+/// A siteverify endpoint answering `success` for `token` and rejecting
+/// anything else
+async fn provider(token: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_string_contains(format!("response={}", token)))
+        .and(body_string_contains("secret=provider-secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": false,
+            "error-codes": ["invalid-input-response"],
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[sqlx::test]
+async fn test_disabled_captcha_passes_through(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app.get("/api/auth/challenge", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["mode"], "disabled");
+    assert!(res.body["challenge"].is_null());
+
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            registration("sin-captcha@example.cl", None),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_provider_captcha_verification(db: PgPool) {
+    for mode in [CaptchaMode::Hcaptcha, CaptchaMode::Turnstile] {
+        let server = provider("widget-token-ok").await;
+        let app = TestApp::with_config(db.clone(), |c| {
+            c.captcha_mode = mode;
+            c.captcha_secret = Some("provider-secret".to_string());
+            c.captcha_verify_url = Some(server.uri());
+        })
+        .await;
+        let email = format!("{:?}@example.cl", mode).to_lowercase();
+
+        let res = app
+            .post("/api/auth/register", None, registration(&email, None))
+            .await;
+        assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+        assert_eq!(res.body["code"], "captcha_failed");
+
+        let res = app
+            .post(
+                "/api/auth/register",
+                None,
+                registration(&email, Some("widget-token-forged")),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+        assert_eq!(res.body["code"], "captcha_failed");
+
+        let res = app
+            .post(
+                "/api/auth/register",
+                None,
+                registration(&email, Some("widget-token-ok")),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    }
+
+    // Nothing was created by the rejected attempts
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&db)
+        .await
+        .unwrap();
+    assert_eq!(users, 2);
+}
+
+#[sqlx::test]
+async fn test_unreachable_provider_is_unavailable(db: PgPool) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let app = TestApp::with_config(db, |c| {
+        c.captcha_mode = CaptchaMode::Turnstile;
+        c.captcha_secret = Some("provider-secret".to_string());
+        c.captcha_verify_url = Some(server.uri());
+    })
+    .await;
+
+    let res = app
+        .post(
+            "/api/auth/password/forgot",
+            None,
+            json!({ "email": "alguien@example.cl", "captcha_token": "widget-token" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_proof_of_work_challenge(db: PgPool) {
+    let app = TestApp::with_config(db, |c| {
+        c.captcha_mode = CaptchaMode::ProofOfWork;
+        c.pow_difficulty = 8;
+    })
+    .await;
+
+    let res = app.get("/api/auth/challenge", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["mode"], "proof_of_work");
+    assert_eq!(res.body["difficulty"], 8);
+    assert!(res.body["expires_at"].is_string());
+    let challenge = res.body["challenge"].as_str().unwrap().to_string();
+
+    // A nonce that doesn't meet the difficulty is refused
+    let unsolved = (0..)
+        .map(|nonce| format!("{}:{}", challenge, nonce))
+        .find(|token| Sha256::digest(token.as_bytes())[0] != 0)
+        .unwrap();
+    let body = json!({
+        "email": "director@omil-pow.cl",
+        "password": "SecurePass123",
+        "first_name": "Rosa",
+        "last_name": "Muñoz",
+        "organization_name": "OMIL Osorno",
+        "captcha_token": unsolved,
+    });
+    let res = app
+        .post("/api/auth/register/omil", None, body.clone())
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    assert_eq!(res.body["code"], "captcha_failed");
+
+    let token = solve(&challenge, 8);
+    let mut solved = body.clone();
+    solved["captcha_token"] = json!(token);
+    let res = app.post("/api/auth/register/omil", None, solved).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Each challenge admits one request
+    let res = app
+        .post(
+            "/api/auth/password/forgot",
+            None,
+            json!({ "email": "director@omil-pow.cl", "captcha_token": token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    assert_eq!(res.body["code"], "captcha_failed");
+
+    // Challenges the server never issued are refused however well solved
+    let res = app
+        .post(
+            "/api/auth/register/company",
+            None,
+            json!({
+                "email": "rrhh@empresa-pow.cl",
+                "password": "SecurePass123",
+                "first_name": "Pedro",
+                "last_name": "Soto",
+                "company_name": "Empresa PoW SpA",
+                "captcha_token": solve("made-up-challenge", 8),
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let res = app.get("/api/auth/challenge", None).await;
+    let challenge = res.body["challenge"].as_str().unwrap();
+    let res = app
+        .post(
+            "/api/auth/password/forgot",
+            None,
+            json!({ "email": "director@omil-pow.cl", "captcha_token": solve(challenge, 8) }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
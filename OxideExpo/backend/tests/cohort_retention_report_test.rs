@@ -0,0 +1,244 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::{middleware::auth::last_active_key, models::user::UserType};
+use redis::AsyncCommands;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+/// Label of the cohort `months_ago` months before the current one
+async fn cohort_label(app: &TestApp, months_ago: i32) -> String {
+    sqlx::query_scalar(
+        "SELECT to_char(date_trunc('month', NOW() AT TIME ZONE $1) - make_interval(months => $2), 'YYYY-MM')",
+    )
+    .bind(&app.state.config.platform_timezone)
+    .bind(months_ago)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+/// Job seeker without a profile, registered early in the month
+/// `months_ago` months before the current one
+async fn seeker_registered(app: &TestApp, months_ago: i32) -> TestUser {
+    let seeker = app.create_user(UserType::JobSeeker).await;
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET created_at = (
+            date_trunc('month', NOW() AT TIME ZONE $2) - make_interval(months => $3) + INTERVAL '1 day'
+        ) AT TIME ZONE $2
+        WHERE id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(&app.state.config.platform_timezone)
+    .bind(months_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+    seeker
+}
+
+/// Runs `sql` with the seeker's id and the moment `days` after they registered
+async fn active_after(app: &TestApp, seeker: &TestUser, days: i32, sql: &str) {
+    let at: chrono::DateTime<chrono::Utc> = sqlx::query_scalar(
+        "SELECT created_at + make_interval(days => $2) FROM users WHERE id = $1",
+    )
+    .bind(seeker.id)
+    .bind(days)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    sqlx::query(sql)
+        .bind(seeker.id)
+        .bind(at)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+async fn last_active_at(app: &TestApp, user: &TestUser) -> Option<chrono::DateTime<chrono::Utc>> {
+    sqlx::query_scalar("SELECT last_active_at FROM users WHERE id = $1")
+        .bind(user.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_cohorts_count_retention_and_placements(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let omil = app.create_omil_with_director().await;
+
+    // A cohort old enough to have reached every window
+    let signed_in_once = seeker_registered(&app, 5).await;
+    active_after(
+        &app,
+        &signed_in_once,
+        10,
+        "INSERT INTO refresh_tokens (user_id, token_hash, expires_at, created_at) VALUES ($1, md5($1::text), $2 + INTERVAL '30 days', $2)",
+    )
+    .await;
+
+    let came_back = seeker_registered(&app, 5).await;
+    active_after(
+        &app,
+        &came_back,
+        45,
+        "INSERT INTO refresh_tokens (user_id, token_hash, expires_at, created_at) VALUES ($1, md5($1::text), $2 + INTERVAL '30 days', $2)",
+    )
+    .await;
+
+    let applied = seeker_registered(&app, 5).await;
+    sqlx::query(
+        r#"
+        INSERT INTO job_applications (job_id, applicant_id, status, applied_at)
+        SELECT $1, id, 'submitted', created_at + INTERVAL '65 days' FROM users WHERE id = $2
+        "#,
+    )
+    .bind(job_id)
+    .bind(applied.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let omil_placed = seeker_registered(&app, 5).await;
+    active_after(
+        &app,
+        &omil_placed,
+        100,
+        "UPDATE users SET last_active_at = $2 WHERE id = $1",
+    )
+    .await;
+    sqlx::query(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by, placement_outcome)
+        VALUES ($1, $2, $3, 'placed')
+        "#,
+    )
+    .bind(omil.id)
+    .bind(omil_placed.id)
+    .bind(omil.director.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let hired = seeker_registered(&app, 5).await;
+    sqlx::query(
+        r#"
+        INSERT INTO job_applications (job_id, applicant_id, status, applied_at)
+        SELECT $1, id, 'hired', created_at + INTERVAL '31 days' FROM users WHERE id = $2
+        "#,
+    )
+    .bind(job_id)
+    .bind(hired.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // Only job seekers are counted
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query("UPDATE users SET created_at = $2 WHERE id = $1")
+        .bind(member.id)
+        .bind(
+            sqlx::query_scalar::<_, chrono::DateTime<chrono::Utc>>(
+                "SELECT created_at FROM users WHERE id = $1",
+            )
+            .bind(hired.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap(),
+        )
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    // This month's cohort hasn't reached any window yet
+    seeker_registered(&app, 0).await;
+
+    let res = app.get("/api/admin/reports/retention", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["windows"], json!([30, 60, 90]));
+
+    let cohorts = res.body["cohorts"].as_array().unwrap();
+    assert_eq!(cohorts.len(), 12);
+    let cohort = |label: &str| -> &Value { cohorts.iter().find(|c| c["cohort"] == label).unwrap() };
+
+    let old = cohort(&cohort_label(&app, 5).await);
+    assert_eq!(old["registered"], 5);
+    assert_eq!(old["retained"], json!([4, 2, 1]));
+    assert_eq!(old["retention_rates"], json!([0.8, 0.4, 0.2]));
+    assert_eq!(old["placed"], 2);
+    assert_eq!(old["placement_rate"], 0.4);
+
+    let current = cohort(&cohort_label(&app, 0).await);
+    assert_eq!(current["registered"], 1);
+    assert_eq!(current["retained"], json!([null, null, null]));
+    assert_eq!(current["retention_rates"], json!([null, null, null]));
+    assert_eq!(cohorts.last().unwrap()["cohort"], current["cohort"]);
+
+    let empty = cohort(&cohort_label(&app, 4).await);
+    assert_eq!(empty["registered"], 0);
+    assert_eq!(empty["placement_rate"], json!(null));
+
+    // Immature cohorts are left out of the window totals
+    let totals = &res.body["totals"];
+    assert_eq!(totals["registered"], 6);
+    assert_eq!(totals["retained"], json!([4, 2, 1]));
+    assert_eq!(totals["retention_rates"], json!([0.8, 0.4, 0.2]));
+    assert_eq!(totals["placed"], 2);
+    assert_eq!(totals["placement_rate"], 0.3333);
+}
+
+#[sqlx::test]
+async fn test_retention_export_and_access(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    seeker_registered(&app, 3).await;
+
+    let res = app
+        .get("/api/admin/reports/export/retention", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.headers["content-type"],
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+
+    let seeker = app.create_job_seeker().await;
+    let res = app.get("/api/admin/reports/retention", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_last_active_is_written_at_most_once_an_hour(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    assert_eq!(last_active_at(&app, &seeker).await, None);
+
+    let res = app.get("/api/me/profile", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let first = last_active_at(&app, &seeker).await.unwrap();
+    assert!(chrono::Utc::now() - first < chrono::Duration::minutes(1));
+
+    // Within the debounce window the column isn't touched again
+    sqlx::query("UPDATE users SET last_active_at = '2020-01-01' WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    app.get("/api/me/profile", Some(&seeker)).await;
+    let stale = last_active_at(&app, &seeker).await.unwrap();
+    assert_eq!(stale.format("%Y-%m-%d").to_string(), "2020-01-01");
+
+    // Once the window has passed the next request writes it
+    let mut redis = app.state.redis.clone();
+    let _: () = redis.del(last_active_key(seeker.id)).await.unwrap();
+    app.get("/api/me/profile", Some(&seeker)).await;
+    assert!(last_active_at(&app, &seeker).await.unwrap() > first);
+}
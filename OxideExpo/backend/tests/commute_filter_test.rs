@@ -0,0 +1,268 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn municipality(app: &TestApp, name: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM municipalities WHERE name = $1")
+        .bind(name)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn distance_km(app: &TestApp, from: &str, to: &str) -> f64 {
+    sqlx::query_scalar(
+        r#"
+        SELECT haversine_km(a.latitude, a.longitude, b.latitude, b.longitude)
+        FROM municipalities a, municipalities b
+        WHERE a.name = $1 AND b.name = $2
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+/// Active job located in `municipality_name`
+async fn job_in(
+    app: &TestApp,
+    company: &TestCompany,
+    municipality_name: &str,
+    remote: bool,
+) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query("UPDATE jobs SET municipality_id = $2, is_remote_allowed = $3 WHERE id = $1")
+        .bind(job_id)
+        .bind(municipality(app, municipality_name).await)
+        .bind(remote)
+        .execute(app.db())
+        .await
+        .unwrap();
+    job_id
+}
+
+fn result_ids(body: &Value) -> Vec<String> {
+    body["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|job| job["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_haversine_matches_known_distances(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    // Straight-line distances between comuna centres
+    for (from, to, expected) in [
+        ("Santiago", "Providencia", 3.9),
+        ("Santiago", "Valparaíso", 99.4),
+        ("Santiago", "Concepción", 435.5),
+    ] {
+        let distance = distance_km(&app, from, to).await;
+        assert!(
+            (distance - expected).abs() < 1.0,
+            "{} - {}: {} km",
+            from,
+            to,
+            distance
+        );
+        assert!((distance - distance_km(&app, to, from).await).abs() < 1e-9);
+    }
+
+    assert_eq!(distance_km(&app, "Santiago", "Santiago").await, 0.0);
+}
+
+#[sqlx::test]
+async fn test_every_comuna_is_seeded_with_coordinates(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let (comunas, located, empty_regions): (i64, i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            COUNT(m.id),
+            COUNT(m.id) FILTER (WHERE m.latitude IS NOT NULL AND m.longitude IS NOT NULL),
+            COUNT(*) FILTER (WHERE m.id IS NULL)
+        FROM regions r
+        JOIN countries c ON c.id = r.country_id AND c.iso_code = 'CL'
+        LEFT JOIN municipalities m ON m.region_id = r.id
+        "#,
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(comunas, 346);
+    assert_eq!(located, comunas);
+    assert_eq!(empty_regions, 0);
+
+    let distance = distance_km(&app, "Arica", "Punta Arenas").await;
+    assert!((distance - 3857.2).abs() < 1.0, "{} km", distance);
+}
+
+#[sqlx::test]
+async fn test_near_filter_orders_by_distance_and_keeps_remote_jobs(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let nearby = job_in(&app, &company, "Providencia", false).await;
+    let local = job_in(&app, &company, "Santiago", false).await;
+    let far = job_in(&app, &company, "Valparaíso", false).await;
+    let remote = job_in(&app, &company, "Concepción", true).await;
+
+    let santiago = municipality(&app, "Santiago").await;
+    let res = app
+        .get(
+            &format!("/api/jobs?near_municipality_id={}&radius_km=30", santiago),
+            None,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 3);
+
+    let ids = result_ids(&res.body);
+    assert_eq!(
+        ids,
+        vec![local.to_string(), nearby.to_string(), remote.to_string()]
+    );
+    assert!(!ids.contains(&far.to_string()));
+
+    let jobs = res.body["jobs"].as_array().unwrap();
+    assert_eq!(jobs[0]["distance_km"], 0.0);
+    assert!((jobs[1]["distance_km"].as_f64().unwrap() - 3.9).abs() < 1.0);
+    assert!(jobs[2]["distance_km"].is_null());
+    assert_eq!(jobs[2]["is_remote_allowed"], true);
+
+    // A wider radius reaches the coast
+    let res = app
+        .get(
+            &format!("/api/jobs?near_municipality_id={}&radius_km=120", santiago),
+            None,
+        )
+        .await;
+    assert!(result_ids(&res.body).contains(&far.to_string()));
+
+    // Without the filter there is no distance
+    let res = app.get("/api/jobs", None).await;
+    assert_eq!(res.body["total"], 4);
+    assert!(res.body["jobs"][0]["distance_km"].is_null());
+}
+
+#[sqlx::test]
+async fn test_near_filter_rejects_invalid_radius(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let santiago = municipality(&app, "Santiago").await;
+
+    for radius in ["0", "-5", "501"] {
+        let res = app
+            .get(
+                &format!(
+                    "/api/jobs?near_municipality_id={}&radius_km={}",
+                    santiago, radius
+                ),
+                None,
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "radius {}", radius);
+    }
+
+    let res = app
+        .get(
+            &format!("/api/jobs?near_municipality_id={}", Uuid::new_v4()),
+            None,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_near_me_uses_profile_municipality(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let coastal = job_in(&app, &company, "Valparaíso", false).await;
+    job_in(&app, &company, "Santiago", false).await;
+
+    // Anonymous callers and seekers without a municipality cannot use near_me
+    let res = app.get("/api/jobs?near_me=true", None).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app.get("/api/jobs?near_me=true", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE job_seeker_profiles SET municipality_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(municipality(&app, "Valparaíso").await)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app
+        .get("/api/jobs?near_me=true&radius_km=20", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(result_ids(&res.body), vec![coastal.to_string()]);
+}
+
+#[sqlx::test]
+async fn test_recommendations_respect_preferred_commute(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let local = job_in(&app, &company, "Providencia", false).await;
+    let far = job_in(&app, &company, "Concepción", false).await;
+    let remote = job_in(&app, &company, "Concepción", true).await;
+
+    sqlx::query("UPDATE job_seeker_profiles SET municipality_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(municipality(&app, "Santiago").await)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let recommended = |body: &Value| -> Vec<String> {
+        body["jobs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["job"]["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // No preference: every job is recommended
+    let res = app.get("/api/me/recommended-jobs", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total_count"], 3);
+
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "preferred_max_commute_km": 25 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["preferred_max_commute_km"], 25);
+
+    let res = app.get("/api/me/recommended-jobs", Some(&seeker)).await;
+    let ids = recommended(&res.body);
+    assert!(ids.contains(&local.to_string()));
+    assert!(ids.contains(&remote.to_string()));
+    assert!(!ids.contains(&far.to_string()));
+
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "preferred_max_commute_km": 0 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
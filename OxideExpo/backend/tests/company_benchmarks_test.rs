@@ -0,0 +1,120 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::benchmarks::{self, MIN_BUCKET_JOBS};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Region of its own, so its bucket only holds jobs created here
+async fn create_region(app: &TestApp) -> Uuid {
+    sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO regions (country_id, name) SELECT id, $1 FROM countries LIMIT 1 RETURNING id",
+    )
+    .bind(format!("Region {}", Uuid::new_v4()))
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn create_active_job(app: &TestApp, company: &TestCompany, region_id: Option<Uuid>) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query("UPDATE jobs SET region_id = $2 WHERE id = $1")
+        .bind(job_id)
+        .bind(region_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    job_id
+}
+
+async fn add_views(app: &TestApp, job_id: Uuid, views: i32) {
+    sqlx::query(
+        "INSERT INTO job_view_stats (job_id, view_date, views) VALUES ($1, CURRENT_DATE, $2)",
+    )
+    .bind(job_id)
+    .bind(views)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn get_benchmarks(app: &TestApp, owner: &TestUser) -> Value {
+    let res = app.get("/api/me/company/benchmarks", Some(owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_small_bucket_falls_back_to_global_benchmark(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let region_id = create_region(&app).await;
+    let company = app.create_company_with_owner().await;
+
+    // Enough active jobs platform-wide for a global benchmark
+    let other_company = app.create_company_with_owner().await;
+    for _ in 0..MIN_BUCKET_JOBS {
+        create_active_job(&app, &other_company, None).await;
+    }
+
+    let job_id = create_active_job(&app, &company, Some(region_id)).await;
+    add_views(&app, job_id, 40).await;
+
+    let body = get_benchmarks(&app, &company.owner).await;
+    assert_eq!(body["period_days"], 30);
+
+    let job = &body["jobs"].as_array().unwrap()[0];
+    assert_eq!(job["job_id"], job_id.to_string());
+    assert_eq!(job["views"], 40);
+    assert_eq!(job["applications"], 0);
+    assert_eq!(job["conversion_rate"], 0.0);
+    assert_eq!(job["benchmark"]["scope"], "global");
+    assert!(job["benchmark"]["jobs_count"].as_i64().unwrap() >= MIN_BUCKET_JOBS);
+}
+
+#[sqlx::test]
+async fn test_full_bucket_uses_its_own_percentiles(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let region_id = create_region(&app).await;
+    let company = app.create_company_with_owner().await;
+
+    for views in [10, 20, 30, 40, 50] {
+        let job_id = create_active_job(&app, &company, Some(region_id)).await;
+        add_views(&app, job_id, views).await;
+    }
+
+    let body = get_benchmarks(&app, &company.owner).await;
+    let jobs = body["jobs"].as_array().unwrap();
+    assert_eq!(jobs.len(), 5);
+
+    let benchmark = &jobs[0]["benchmark"];
+    assert_eq!(benchmark["scope"], "bucket");
+    assert_eq!(benchmark["jobs_count"], 5);
+    assert_eq!(benchmark["views_median"], 30.0);
+    assert_eq!(benchmark["views_p75"], 40.0);
+    assert_eq!(benchmark["conversion_median"], 0.0);
+}
+
+#[sqlx::test]
+async fn test_bucket_cache_does_not_leak_between_regions(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let crowded_region = create_region(&app).await;
+    let empty_region = create_region(&app).await;
+    let company = app.create_company_with_owner().await;
+
+    for _ in 0..MIN_BUCKET_JOBS {
+        create_active_job(&app, &company, Some(crowded_region)).await;
+    }
+
+    let mut redis = app.state.redis.clone();
+    let crowded = benchmarks::benchmark_for(app.db(), &mut redis, None, Some(crowded_region))
+        .await
+        .unwrap();
+    let empty = benchmarks::benchmark_for(app.db(), &mut redis, None, Some(empty_region))
+        .await
+        .unwrap();
+
+    assert_eq!(crowded.jobs_count, MIN_BUCKET_JOBS);
+    assert_ne!(empty.scope, crowded.scope);
+}
@@ -0,0 +1,280 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::{
+    models::user::UserType,
+    services::{company_export, storage::StorageDriver},
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+type Archive = zip::ZipArchive<Cursor<Vec<u8>>>;
+
+fn read_entry(archive: &mut Archive, name: &str) -> Vec<u8> {
+    let mut contents = Vec::new();
+    archive
+        .by_name(name)
+        .unwrap_or_else(|_| panic!("{} missing from the export", name))
+        .read_to_end(&mut contents)
+        .unwrap();
+    contents
+}
+
+/// Cell text of a workbook written by rust_xlsxwriter, which keeps every
+/// string in the shared strings table
+fn workbook_text(xlsx: Vec<u8>) -> String {
+    let mut workbook = zip::ZipArchive::new(Cursor::new(xlsx)).unwrap();
+    String::from_utf8(read_entry(&mut workbook, "xl/sharedStrings.xml")).unwrap()
+}
+
+async fn add_company_member(app: &TestApp, company: &TestCompany, role: &str) -> common::TestUser {
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, $3::member_role)",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .bind(role)
+    .execute(app.db())
+    .await
+    .unwrap();
+    member
+}
+
+/// App whose exports are written to memory
+async fn app_with_memory_storage(db: PgPool) -> TestApp {
+    TestApp::with_config(db, |config| config.storage_driver = StorageDriver::Memory).await
+}
+
+#[sqlx::test]
+async fn test_company_data_export_end_to_end(db: PgPool) {
+    let app = app_with_memory_storage(db).await;
+    let company = app.create_company_with_owner().await;
+    let admin_member = add_company_member(&app, &company, "admin").await;
+
+    let cashier_job = app.create_active_job(&company).await;
+    sqlx::query(
+        "UPDATE jobs SET title = 'Cajero/a part-time', benefits = 'Colación' WHERE id = $1",
+    )
+    .bind(cashier_job)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let empty_job = app.create_active_job(&company).await;
+
+    let applicant = app.create_job_seeker().await;
+    let application_id = app.create_application(cashier_job, &applicant).await;
+    sqlx::query(
+        "INSERT INTO application_notes (application_id, created_by, note_text) VALUES ($1, $2, 'Buena entrevista telefónica')",
+    )
+    .bind(application_id)
+    .bind(company.owner.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    // Someone the company only found through candidate search
+    let searched = app.create_job_seeker().await;
+
+    // Only the owner may export
+    let res = app
+        .post(
+            "/api/me/company/data-export",
+            Some(&admin_member),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/me/company/data-export",
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    assert_eq!(res.body["status"], "queued");
+    assert!(res.body["download_url"].is_null());
+    let export_id = res.body["id"].as_str().unwrap().to_string();
+
+    // One export in progress at a time
+    let res = app
+        .post(
+            "/api/me/company/data-export",
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+
+    let completed = company_export::process_pending(&app.state).await.unwrap();
+    assert_eq!(completed, 1);
+
+    let uri = format!("/api/me/company/data-export/{}", export_id);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "done");
+    assert_eq!(res.body["total_jobs"], 2);
+    assert_eq!(res.body["processed_jobs"], 2);
+    assert!(res.body["expires_at"].is_string());
+    let download_url = res.body["download_url"].as_str().unwrap();
+    assert!(
+        download_url.contains("/api/files/download?token="),
+        "{}",
+        download_url
+    );
+
+    let res = app.get(&uri, Some(&admin_member)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    // The archive holds the profile, the jobs and one applicant workbook per job
+    let (storage_path, file_size): (String, i64) = sqlx::query_as(
+        "SELECT storage_path, file_size FROM company_data_exports WHERE id = $1::uuid",
+    )
+    .bind(&export_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    let zip_bytes = app.state.storage.get(&storage_path).await.unwrap().to_vec();
+    assert_eq!(zip_bytes.len() as i64, file_size);
+    let path = &download_url[download_url.find("/api/").unwrap()..];
+    let res = app.get(path, None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.bytes.to_vec(), zip_bytes);
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip_bytes)).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "applicants/Cajero-a-part-time-".to_string() + &cashier_job.to_string()[..8] + ".xlsx",
+            "applicants/Test-job-".to_string() + &empty_job.to_string()[..8] + ".xlsx",
+            "company.json".to_string(),
+            "jobs.xlsx".to_string(),
+        ]
+    );
+
+    let company_json: Value =
+        serde_json::from_slice(&read_entry(&mut archive, "company.json")).unwrap();
+    assert_eq!(company_json["company"]["company_name"], "Test SpA");
+    let member_emails: Vec<&str> = company_json["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["email"].as_str().unwrap())
+        .collect();
+    assert_eq!(member_emails.len(), 2);
+    assert!(member_emails.contains(&company.owner.email.as_str()));
+
+    let jobs = workbook_text(read_entry(&mut archive, "jobs.xlsx"));
+    assert!(jobs.contains("Cajero/a part-time"), "{}", jobs);
+    assert!(jobs.contains("Colación"), "{}", jobs);
+    assert!(jobs.contains(&empty_job.to_string()), "{}", jobs);
+
+    let applicants = workbook_text(read_entry(&mut archive, &names[0]));
+    assert!(applicants.contains(&applicant.email), "{}", applicants);
+    assert!(applicants.contains("submitted"), "{}", applicants);
+    assert!(
+        applicants.contains("Buena entrevista telefónica"),
+        "{}",
+        applicants
+    );
+
+    // People who never applied are not part of the export
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).unwrap();
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).unwrap();
+        let text = if entry.name().ends_with(".xlsx") {
+            workbook_text(contents)
+        } else {
+            String::from_utf8(contents).unwrap()
+        };
+        assert!(
+            !text.contains(&searched.email),
+            "{} leaks a non-applicant",
+            entry.name()
+        );
+    }
+
+    // A finished export lets the owner start another one
+    let res = app
+        .post(
+            "/api/me/company/data-export",
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_expired_company_data_export_is_removed(db: PgPool) {
+    let app = app_with_memory_storage(db).await;
+    let company = app.create_company_with_owner().await;
+    app.create_active_job(&company).await;
+
+    let res = app
+        .post(
+            "/api/me/company/data-export",
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    let export_id: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+
+    company_export::process_pending(&app.state).await.unwrap();
+    let storage_path: String =
+        sqlx::query_scalar("SELECT storage_path FROM company_data_exports WHERE id = $1")
+            .bind(export_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+
+    sqlx::query(
+        "UPDATE company_data_exports SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(export_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app
+        .get(
+            &format!("/api/me/company/data-export/{}", export_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "done");
+    assert!(res.body["download_url"].is_null());
+
+    assert_eq!(
+        company_export::purge_expired(app.db(), app.state.storage.as_ref())
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(app.state.storage.get(&storage_path).await.is_err());
+    let remaining: Option<String> =
+        sqlx::query_scalar("SELECT storage_path FROM company_data_exports WHERE id = $1")
+            .bind(export_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(remaining, None);
+
+    // Exports of other companies are not visible
+    let other = app.create_company_with_owner().await;
+    let res = app
+        .get(
+            &format!("/api/me/company/data-export/{}", export_id),
+            Some(&other.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+}
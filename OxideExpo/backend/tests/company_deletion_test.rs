@@ -0,0 +1,260 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn company_status(app: &TestApp, company: &TestCompany) -> String {
+    sqlx::query_scalar("SELECT status::text FROM company_profiles WHERE id = $1")
+        .bind(company.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn job_status(app: &TestApp, job_id: Uuid) -> String {
+    sqlx::query_scalar("SELECT status::text FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_wind_down_is_reversible(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    app.create_application(job_id, &seeker).await;
+
+    let uri = format!("/api/admin/companies/{}", company.id);
+    let res = app.delete(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "closing");
+    assert_eq!(res.body["jobs_closed"], 1);
+    assert_eq!(res.body["members_deactivated"], 1);
+    assert_eq!(res.body["applicants_notified"], 1);
+    assert!(res.body["purge_after"].is_string());
+
+    assert_eq!(job_status(&app, job_id).await, "closed");
+    let res = app
+        .get("/api/me/company/profile", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app.get(&format!("/api/jobs/{}", job_id), None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // The purge waits out its period unless forced
+    let res = app.delete(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let reopen = format!("/api/admin/companies/{}/reopen", company.id);
+    let res = app.post(&reopen, Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "active");
+    assert_eq!(res.body["jobs_reopened"], 1);
+    assert_eq!(res.body["members_reactivated"], 1);
+
+    assert_eq!(company_status(&app, &company).await, "active");
+    assert_eq!(job_status(&app, job_id).await, "active");
+    let res = app
+        .get("/api/me/company/profile", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app.post(&reopen, Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let events: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT action, snapshot->>'status'
+        FROM company_deletion_events
+        WHERE company_id = $1
+        ORDER BY created_at
+        "#,
+    )
+    .bind(company.id)
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            ("wind_down".to_string(), "active".to_string()),
+            ("reopen".to_string(), "closing".to_string()),
+        ]
+    );
+    let logged: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM admin_audit_logs
+        WHERE entity_id = $1 AND action_type IN ('wind_down_company', 'reopen_company')
+        "#,
+    )
+    .bind(company.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(logged, 2);
+}
+
+#[sqlx::test]
+async fn test_applications_stay_readable_after_purge(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let application_id = res.body["id"].as_str().unwrap().to_string();
+
+    let logo_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO uploaded_files (user_id, file_type, original_filename, storage_path)
+        VALUES ($1, 'company_logo', 'logo.png', 'company_logos/logo.png')
+        RETURNING id
+        "#,
+    )
+    .bind(company.owner.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        UPDATE company_profiles
+        SET logo_file_id = $2, logo_url = 'https://example.cl/logo.png', tax_id = '76.123.456-0'
+        WHERE id = $1
+        "#,
+    )
+    .bind(company.id)
+    .bind(logo_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // Purging needs a wind-down first
+    let uri = format!("/api/admin/companies/{}", company.id);
+    let res = app.delete(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .delete(&format!("{}?force=true", uri), Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "deleted");
+    assert_eq!(res.body["jobs_archived"], 1);
+    assert!(res.body["purge_after"].is_null());
+
+    assert_eq!(company_status(&app, &company).await, "deleted");
+    assert_eq!(job_status(&app, job_id).await, "archived_deleted");
+    let (tax_id, logo_file_id): (Option<String>, Option<Uuid>) =
+        sqlx::query_as("SELECT tax_id, logo_file_id FROM company_profiles WHERE id = $1")
+            .bind(company.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!((tax_id, logo_file_id), (None, None));
+    let files: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM uploaded_files WHERE id = $1")
+        .bind(logo_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(files, 0);
+
+    let res = app.get("/api/me/applications", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    assert_eq!(res.body[0]["job"]["title"], "Test job");
+    assert_eq!(res.body[0]["job"]["company_name"], "Empresa eliminada");
+    assert!(res.body[0]["job"]["company_logo_url"].is_null());
+
+    let res = app
+        .get(
+            &format!("/api/me/applications/{}", application_id),
+            Some(&seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["job"]["company_name"], "Empresa eliminada");
+
+    // Nothing to reopen or purge again
+    let res = app
+        .post(
+            &format!("/api/admin/companies/{}/reopen", company.id),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = app
+        .delete(&format!("{}?force=true", uri), Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let snapshot: String = sqlx::query_scalar(
+        r#"
+        SELECT snapshot->>'company_name' FROM company_deletion_events
+        WHERE company_id = $1 AND action = 'purge'
+        "#,
+    )
+    .bind(company.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(snapshot, "Test SpA");
+}
+
+#[sqlx::test]
+async fn test_owner_deletes_company_without_active_jobs(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app.delete("/api/me/company", Some(&member)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app.delete("/api/me/company", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+    assert_eq!(company_status(&app, &company).await, "active");
+
+    sqlx::query("UPDATE jobs SET status = 'closed' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app.delete("/api/me/company", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "closing");
+    assert_eq!(res.body["members_deactivated"], 2);
+
+    let res = app.get("/api/me/company/profile", Some(&member)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let performed_by: Uuid = sqlx::query_scalar(
+        "SELECT performed_by FROM company_deletion_events WHERE company_id = $1",
+    )
+    .bind(company.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(performed_by, company.owner.id);
+}
@@ -0,0 +1,268 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn industry_ids(app: &TestApp) -> Vec<Uuid> {
+    sqlx::query_scalar("SELECT id FROM industries ORDER BY sort_order, name LIMIT 4")
+        .fetch_all(app.db())
+        .await
+        .unwrap()
+}
+
+async fn set_industries(app: &TestApp, company: &TestCompany, industries: Value) -> StatusCode {
+    app.put(
+        "/api/me/company/profile",
+        Some(&company.owner),
+        json!({ "industries": industries }),
+    )
+    .await
+    .status
+}
+
+fn company_ids(body: &Value) -> Vec<String> {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_industries_are_capped_at_three(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let ids = industry_ids(&app).await;
+
+    let four: Vec<Value> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| json!({ "industry_id": id, "is_primary": i == 0 }))
+        .collect();
+    assert_eq!(
+        set_industries(&app, &company, json!(four)).await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        set_industries(&app, &company, json!([])).await,
+        StatusCode::BAD_REQUEST
+    );
+
+    assert_eq!(
+        set_industries(&app, &company, json!(four[..3])).await,
+        StatusCode::OK
+    );
+
+    let res = app
+        .get("/api/me/company/profile", Some(&company.owner))
+        .await;
+    let industries = res.body["industries"].as_array().unwrap();
+    assert_eq!(industries.len(), 3);
+    assert_eq!(industries[0]["industry_id"], ids[0].to_string());
+    assert_eq!(industries[0]["is_primary"], true);
+    // The deprecated column mirrors the primary industry
+    assert_eq!(res.body["industry_id"], ids[0].to_string());
+
+    // The database refuses a fourth row even outside the endpoint
+    let result =
+        sqlx::query("INSERT INTO company_industries (company_id, industry_id) VALUES ($1, $2)")
+            .bind(company.id)
+            .bind(ids[3])
+            .execute(app.db())
+            .await;
+    assert!(result.is_err());
+}
+
+#[sqlx::test]
+async fn test_exactly_one_primary_industry(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let ids = industry_ids(&app).await;
+
+    for industries in [
+        json!([
+            { "industry_id": ids[0], "is_primary": true },
+            { "industry_id": ids[1], "is_primary": true }
+        ]),
+        json!([{ "industry_id": ids[0] }, { "industry_id": ids[1] }]),
+        json!([
+            { "industry_id": ids[0], "is_primary": true },
+            { "industry_id": ids[0] }
+        ]),
+    ] {
+        assert_eq!(
+            set_industries(&app, &company, industries).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    assert_eq!(
+        set_industries(
+            &app,
+            &company,
+            json!([{ "industry_id": ids[0], "is_primary": true }])
+        )
+        .await,
+        StatusCode::OK
+    );
+    let result = sqlx::query(
+        "INSERT INTO company_industries (company_id, industry_id, is_primary) VALUES ($1, $2, true)",
+    )
+    .bind(company.id)
+    .bind(ids[1])
+    .execute(app.db())
+    .await;
+    assert!(result.is_err());
+}
+
+#[sqlx::test]
+async fn test_legacy_industry_id_replaces_primary(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let ids = industry_ids(&app).await;
+
+    set_industries(
+        &app,
+        &company,
+        json!([
+            { "industry_id": ids[0], "is_primary": true },
+            { "industry_id": ids[1] },
+            { "industry_id": ids[2] }
+        ]),
+    )
+    .await;
+
+    let res = app
+        .put(
+            "/api/me/company/profile",
+            Some(&company.owner),
+            json!({ "industry_id": ids[3] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["industry_id"], ids[3].to_string());
+
+    let industries = res.body["industries"].as_array().unwrap();
+    assert_eq!(industries.len(), 3);
+    assert_eq!(industries[0]["industry_id"], ids[3].to_string());
+    assert!(industries[1..].iter().all(|i| i["is_primary"] == false));
+}
+
+#[sqlx::test]
+async fn test_public_filter_matches_any_industry(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let ids = industry_ids(&app).await;
+
+    let conglomerate = app.create_company_with_owner().await;
+    set_industries(
+        &app,
+        &conglomerate,
+        json!([
+            { "industry_id": ids[0], "is_primary": true },
+            { "industry_id": ids[1] }
+        ]),
+    )
+    .await;
+    let specialist = app.create_company_with_owner().await;
+    set_industries(
+        &app,
+        &specialist,
+        json!([{ "industry_id": ids[1], "is_primary": true }]),
+    )
+    .await;
+
+    let res = app
+        .get(&format!("/api/companies?industry_id={}", ids[1]), None)
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let listed = company_ids(&res.body);
+    assert_eq!(listed.len(), 2);
+    assert!(listed.contains(&conglomerate.id.to_string()));
+
+    let res = app
+        .get(&format!("/api/companies?industry_id={}", ids[0]), None)
+        .await;
+    assert_eq!(company_ids(&res.body), vec![conglomerate.id.to_string()]);
+
+    let res = app
+        .get(&format!("/api/companies?industry_id={}", ids[2]), None)
+        .await;
+    assert!(company_ids(&res.body).is_empty());
+}
+
+#[sqlx::test]
+async fn test_job_inherits_primary_industry(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let ids = industry_ids(&app).await;
+    set_industries(
+        &app,
+        &company,
+        json!([
+            { "industry_id": ids[1] },
+            { "industry_id": ids[2], "is_primary": true }
+        ]),
+    )
+    .await;
+
+    let job = json!({
+        "title": "Analista de datos",
+        "description": "Análisis de datos para el área comercial",
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1
+    });
+    let res = app
+        .post("/api/me/jobs", Some(&company.owner), job.clone())
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["industry_id"], ids[2].to_string());
+
+    // An explicit industry is kept
+    let mut explicit = job;
+    explicit["industry_id"] = json!(ids[0]);
+    explicit["force"] = json!(true);
+    let res = app
+        .post("/api/me/jobs", Some(&company.owner), explicit)
+        .await;
+    assert_eq!(res.body["industry_id"], ids[0].to_string());
+}
+
+#[sqlx::test]
+async fn test_company_report_counts_primary_industry_only(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let ids = industry_ids(&app).await;
+
+    let company = app.create_company_with_owner().await;
+    set_industries(
+        &app,
+        &company,
+        json!([
+            { "industry_id": ids[0], "is_primary": true },
+            { "industry_id": ids[1] },
+            { "industry_id": ids[2] }
+        ]),
+    )
+    .await;
+
+    let res = app.get("/api/admin/reports/companies", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let groups = res.body["by_primary_industry"].as_array().unwrap();
+    let total: i64 = groups.iter().map(|g| g["count"].as_i64().unwrap()).sum();
+    assert_eq!(total, res.body["total_companies"].as_i64().unwrap());
+
+    let primary = groups
+        .iter()
+        .find(|g| g["industry_id"] == ids[0].to_string())
+        .expect("primary industry group");
+    assert_eq!(primary["count"], 1);
+    assert!(!groups
+        .iter()
+        .any(|g| g["industry_id"] == ids[1].to_string()));
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+async fn pending_changes(app: &TestApp, company: &TestCompany) -> Value {
+    let admin = app.create_admin().await;
+    let res = app
+        .get(
+            &format!("/api/admin/companies/{}/pending-changes", company.id),
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_sensitive_fields_wait_for_review_on_active_company(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app
+        .put(
+            "/api/me/company/profile",
+            Some(&company.owner),
+            json!({ "company_name": "Renamed SpA", "description": "Nueva descripción" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // The company sees its pending value, flagged
+    assert_eq!(res.body["company_name"], "Renamed SpA");
+    assert_eq!(res.body["description"], "Nueva descripción");
+    assert_eq!(res.body["has_pending_changes"], true);
+    let pending = res.body["pending_changes"].as_array().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0]["field_name"], "company_name");
+    assert_eq!(pending[0]["old_value"], "Test SpA");
+
+    // Non-sensitive fields apply immediately, sensitive ones do not
+    let row: (String, Option<String>) =
+        sqlx::query_as("SELECT company_name, description FROM company_profiles WHERE id = $1")
+            .bind(company.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(row.0, "Test SpA");
+    assert_eq!(row.1.as_deref(), Some("Nueva descripción"));
+
+    // Public views keep the approved name
+    let res = app
+        .get(&format!("/api/companies/{}", company.id), None)
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["company_name"], "Test SpA");
+    assert_eq!(res.body["description"], "Nueva descripción");
+}
+
+#[sqlx::test]
+async fn test_sensitive_fields_apply_immediately_before_approval(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    sqlx::query("UPDATE company_profiles SET status = 'pending_approval' WHERE id = $1")
+        .bind(company.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app
+        .put(
+            "/api/me/company/profile",
+            Some(&company.owner),
+            json!({ "legal_name": "Renamed Limitada" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["legal_name"], "Renamed Limitada");
+    assert_eq!(res.body["has_pending_changes"], false);
+}
+
+#[sqlx::test]
+async fn test_repeated_and_reverted_edits_replace_the_pending_change(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    for name in ["First Rename SpA", "Second Rename SpA"] {
+        app.put(
+            "/api/me/company/profile",
+            Some(&company.owner),
+            json!({ "company_name": name }),
+        )
+        .await;
+    }
+
+    let pending = pending_changes(&app, &company).await;
+    assert_eq!(pending.as_array().unwrap().len(), 1);
+    assert_eq!(pending[0]["new_value"], "Second Rename SpA");
+
+    let res = app
+        .put(
+            "/api/me/company/profile",
+            Some(&company.owner),
+            json!({ "company_name": "Test SpA" }),
+        )
+        .await;
+    assert_eq!(res.body["has_pending_changes"], false);
+    assert!(pending_changes(&app, &company)
+        .await
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test]
+async fn test_admin_approval_applies_change_and_audits(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let admin = app.create_admin().await;
+
+    app.put(
+        "/api/me/company/profile",
+        Some(&company.owner),
+        json!({ "company_name": "Renamed SpA" }),
+    )
+    .await;
+    let change_id = pending_changes(&app, &company).await[0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let res = app
+        .patch(
+            &format!(
+                "/api/admin/companies/{}/pending-changes/{}/approve",
+                company.id, change_id
+            ),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "approved");
+
+    let res = app
+        .get(&format!("/api/companies/{}", company.id), None)
+        .await;
+    assert_eq!(res.body["company_name"], "Renamed SpA");
+
+    let audits: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'approve_company_profile_change' AND entity_id = $1",
+    )
+    .bind(company.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(audits, 1);
+
+    // Already reviewed
+    let res = app
+        .patch(
+            &format!(
+                "/api/admin/companies/{}/pending-changes/{}/approve",
+                company.id, change_id
+            ),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_admin_rejection_keeps_approved_value(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let admin = app.create_admin().await;
+
+    app.put(
+        "/api/me/company/profile",
+        Some(&company.owner),
+        json!({ "company_name": "Renamed SpA" }),
+    )
+    .await;
+    let change_id = pending_changes(&app, &company).await[0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let res = app
+        .patch(
+            &format!(
+                "/api/admin/companies/{}/pending-changes/{}/reject",
+                company.id, change_id
+            ),
+            Some(&admin),
+            json!({ "rejection_reason": "El nombre no coincide con el SII" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "rejected");
+    assert_eq!(
+        res.body["rejection_reason"],
+        "El nombre no coincide con el SII"
+    );
+
+    let res = app
+        .get("/api/me/company/profile", Some(&company.owner))
+        .await;
+    assert_eq!(res.body["company_name"], "Test SpA");
+    assert_eq!(res.body["has_pending_changes"], false);
+}
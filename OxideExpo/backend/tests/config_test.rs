@@ -0,0 +1,27 @@
+use empleos_inclusivos_backend::config::{Config, MAX_TOKEN_EXPIRY_SECS};
+
+/// Token lifetimes that would overflow expiry timestamps are refused at
+/// startup rather than when a token is issued. The only test in this binary,
+/// since it changes the process environment.
+#[test]
+fn test_token_expiry_must_be_in_range() {
+    dotenvy::dotenv().ok();
+
+    for name in ["JWT_ACCESS_EXPIRY", "JWT_REFRESH_EXPIRY"] {
+        let previous = std::env::var(name).ok();
+
+        for value in ["0", "-60", &(MAX_TOKEN_EXPIRY_SECS + 1).to_string()] {
+            std::env::set_var(name, value);
+            let err = Config::from_env().err().unwrap();
+            assert!(err.to_string().contains(name), "{value}: {err}");
+        }
+
+        std::env::set_var(name, MAX_TOKEN_EXPIRY_SECS.to_string());
+        assert!(Config::from_env().is_ok());
+
+        match previous {
+            Some(value) => std::env::set_var(name, value),
+            None => std::env::remove_var(name),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn join_company(app: &TestApp, company: &TestCompany, user: &TestUser, active: bool) {
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role, is_active) VALUES ($1, $2, 'member', $3)",
+    )
+    .bind(company.id)
+    .bind(user.id)
+    .bind(active)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn manage(app: &TestApp, omil: &TestOmil, user_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(user_id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn apply(app: &TestApp, seeker: &TestUser, job_id: Uuid) -> common::TestResponse {
+    app.post(
+        "/api/me/applications",
+        Some(seeker),
+        json!({ "job_id": job_id }),
+    )
+    .await
+}
+
+async fn apply_on_behalf(
+    app: &TestApp,
+    omil: &TestOmil,
+    managed_id: Uuid,
+    job_id: Uuid,
+) -> common::TestResponse {
+    app.post(
+        &format!("/api/me/omil/job-seekers/{}/apply", managed_id),
+        Some(&omil.director),
+        json!({ "job_id": job_id }),
+    )
+    .await
+}
+
+#[sqlx::test]
+async fn test_company_member_cannot_apply_to_own_company(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let employer = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let own_job = app.create_active_job(&employer).await;
+    let other_job = app.create_active_job(&other).await;
+
+    let seeker = app.create_job_seeker().await;
+    join_company(&app, &employer, &seeker, true).await;
+
+    let res = apply(&app, &seeker, own_job).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    assert_eq!(res.body["code"], "company_member_applicant");
+
+    let res = apply(&app, &seeker, other_job).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // A former member may apply
+    let former = app.create_job_seeker().await;
+    join_company(&app, &employer, &former, false).await;
+    let res = apply(&app, &former, own_job).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_omil_cannot_apply_for_company_member(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let employer = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&employer).await;
+
+    let seeker = app.create_job_seeker().await;
+    join_company(&app, &employer, &seeker, true).await;
+    let managed_id = manage(&app, &omil, seeker.id).await;
+
+    let res = apply_on_behalf(&app, &omil, managed_id, job_id).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    assert_eq!(res.body["code"], "company_member_applicant");
+}
+
+#[sqlx::test]
+async fn test_omil_member_cannot_apply_on_own_behalf(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let managed_id = manage(&app, &omil, omil.director.id).await;
+
+    let res = apply_on_behalf(&app, &omil, managed_id, job_id).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    assert_eq!(res.body["code"], "self_managed_applicant");
+
+    let applications: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_applications WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(applications, 0);
+}
+
+#[sqlx::test]
+async fn test_adding_member_with_omil_applications_warns(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let seeker = app.create_job_seeker().await;
+    let managed_id = manage(&app, &omil, seeker.id).await;
+    let res = apply_on_behalf(&app, &omil, managed_id, job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app
+        .post(
+            "/api/me/omil/members",
+            Some(&omil.director),
+            json!({ "email": seeker.email, "role": "advisor" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["user_email"], seeker.email);
+    assert_eq!(res.body["warnings"].as_array().unwrap().len(), 1);
+
+    let newcomer = app.create_job_seeker().await;
+    let res = app
+        .post(
+            "/api/me/omil/members",
+            Some(&omil.director),
+            json!({ "email": newcomer.email, "role": "advisor" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([]));
+}
@@ -0,0 +1,237 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+async fn create_draft_job(app: &TestApp, company: &TestCompany, extra: Value) -> String {
+    let mut job = json!({
+        "title": "Vendedor de tienda",
+        "description": "Atención de clientes y reposición de productos en sala de ventas",
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1
+    });
+    for (key, value) in extra.as_object().unwrap() {
+        job[key] = value.clone();
+    }
+
+    let res = app.post("/api/me/jobs", Some(&company.owner), job).await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body["id"].as_str().unwrap().to_string()
+}
+
+async fn submit(app: &TestApp, company: &TestCompany, job_id: &str) -> common::TestResponse {
+    app.patch(
+        &format!("/api/me/jobs/{}/status", job_id),
+        Some(&company.owner),
+        json!({ "status": "pending_approval" }),
+    )
+    .await
+}
+
+async fn create_rule(app: &TestApp, admin: &TestUser, rule: Value) -> Value {
+    let res = app
+        .post("/api/admin/moderation-rules", Some(admin), rule)
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+async fn job_status(app: &TestApp, job_id: &str) -> String {
+    sqlx::query_scalar("SELECT status::text FROM jobs WHERE id = $1::uuid")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_moderation_rule_crud(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    let rule = create_rule(
+        &app,
+        &admin,
+        json!({ "rule_type": "banned_phrase", "pattern": "buena presencia", "severity": "block" }),
+    )
+    .await;
+    assert_eq!(rule["is_active"], true);
+
+    let res = app
+        .post(
+            "/api/admin/moderation-rules",
+            Some(&admin),
+            json!({ "rule_type": "banned_phrase", "pattern": "buena presencia", "severity": "warn" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let res = app
+        .post(
+            "/api/admin/moderation-rules",
+            Some(&admin),
+            json!({ "rule_type": "regex", "pattern": "(unclosed", "severity": "warn" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let uri = format!(
+        "/api/admin/moderation-rules/{}",
+        rule["id"].as_str().unwrap()
+    );
+    let res = app
+        .put(
+            &uri,
+            Some(&admin),
+            json!({ "severity": "warn", "is_active": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["severity"], "warn");
+    assert_eq!(res.body["is_active"], false);
+
+    let res = app.get("/api/admin/moderation-rules", Some(&admin)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+
+    let res = app.delete(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app.delete(&uri, Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_blocking_rule_keeps_job_in_draft(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    create_rule(
+        &app,
+        &admin,
+        json!({ "rule_type": "banned_phrase", "pattern": "buena presencia", "severity": "block" }),
+    )
+    .await;
+
+    let job_id = create_draft_job(
+        &app,
+        &company,
+        json!({ "responsibilities": "Se requiere Buena Presencia" }),
+    )
+    .await;
+
+    let res = submit(&app, &company, &job_id).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    let violations = res.body["details"]["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["field"], "responsibilities");
+    assert_eq!(violations[0]["check"], "banned_phrase");
+    assert_eq!(job_status(&app, &job_id).await, "draft");
+}
+
+#[sqlx::test]
+async fn test_warning_rule_reaches_moderation_queue(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    create_rule(
+        &app,
+        &admin,
+        json!({
+            "rule_type": "regex",
+            "pattern": "(?i)sexo\\s*:\\s*(masculino|femenino)",
+            "severity": "warn",
+            "description": "Gender requirement"
+        }),
+    )
+    .await;
+
+    let job_id = create_draft_job(
+        &app,
+        &company,
+        json!({ "description": "Bodeguero para centro de distribución. Sexo: masculino" }),
+    )
+    .await;
+
+    let res = submit(&app, &company, &job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "pending_approval");
+
+    let res = app.get("/api/admin/jobs/pending", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let pending = res.body.as_array().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0]["id"], job_id);
+    let findings = pending[0]["screening_findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["check"], "regex");
+    assert_eq!(findings[0]["message"], "Gender requirement");
+    assert_eq!(findings[0]["matched"], "Sexo: masculino");
+}
+
+#[sqlx::test]
+async fn test_contact_info_blocks_by_default(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let job_id = create_draft_job(
+        &app,
+        &company,
+        json!({ "description": "Envía tu CV directamente a seleccion@empresa.cl" }),
+    )
+    .await;
+
+    let res = submit(&app, &company, &job_id).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        res.body["details"]["violations"][0]["check"],
+        "contact_info"
+    );
+    assert_eq!(res.body["details"]["violations"][0]["field"], "description");
+}
+
+#[sqlx::test]
+async fn test_age_limits_warn_by_default(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let job_id = create_draft_job(&app, &company, json!({ "age_min": 25, "age_max": 35 })).await;
+
+    let res = submit(&app, &company, &job_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/admin/jobs/pending", Some(&admin)).await;
+    let findings = res.body[0]["screening_findings"].as_array().unwrap();
+    let fields: Vec<&str> = findings
+        .iter()
+        .map(|f| f["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["age_min", "age_max"]);
+
+    // Tightening the policy turns the same posting into a blocked one
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [{ "key": "moderation_age_limit_severity", "value": "block" }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let job_id = create_draft_job(
+        &app,
+        &company,
+        json!({
+            "title": "Cajero",
+            "description": "Cobro en caja y cuadratura de fin de turno",
+            "age_max": 40
+        }),
+    )
+    .await;
+    let res = submit(&app, &company, &job_id).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(res.body["details"]["violations"][0]["field"], "age_max");
+}
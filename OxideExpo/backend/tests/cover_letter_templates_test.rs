@@ -0,0 +1,252 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn create_template(app: &TestApp, seeker: &TestUser, name: &str, body: &str) -> Value {
+    let res = app
+        .post(
+            "/api/me/cover-letter-templates",
+            Some(seeker),
+            json!({ "name": name, "body": body }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_cover_letter_templates_are_capped_per_user(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    for i in 0..10 {
+        create_template(&app, &seeker, &format!("Plantilla {}", i), "Hola").await;
+    }
+    let res = app
+        .post(
+            "/api/me/cover-letter-templates",
+            Some(&seeker),
+            json!({ "name": "Una más", "body": "Hola" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // The cap is per user
+    let other = app.create_job_seeker().await;
+    let template = create_template(&app, &other, "Plantilla 0", "Hola").await;
+
+    // Names are unique per user, ignoring case
+    let res = app
+        .post(
+            "/api/me/cover-letter-templates",
+            Some(&other),
+            json!({ "name": "plantilla 0", "body": "Hola" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+
+    // Deleting frees a slot
+    let first = app
+        .get("/api/me/cover-letter-templates", Some(&seeker))
+        .await
+        .body[0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let uri = format!("/api/me/cover-letter-templates/{}", first);
+    assert_eq!(
+        app.delete(&uri, Some(&other)).await.status,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(app.delete(&uri, Some(&seeker)).await.status, StatusCode::OK);
+    create_template(&app, &seeker, "Una más", "Hola").await;
+
+    // Templates are private to their owner
+    let uri = format!(
+        "/api/me/cover-letter-templates/{}",
+        template["id"].as_str().unwrap()
+    );
+    let res = app.put(&uri, Some(&seeker), json!({ "body": "Mío" })).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+
+    let company = app.create_company_with_owner().await;
+    let res = app
+        .get("/api/me/cover-letter-templates", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_apply_with_cover_letter_template(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let other_job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let template = create_template(
+        &app,
+        &seeker,
+        "General",
+        "Me interesa el cargo de {{job_title}} en {{company_name}}. Saludos, {{firma}}",
+    )
+    .await;
+    let plain = create_template(&app, &seeker, "Corta", "Hola {{ company_name }}").await;
+    let template_id = template["id"].as_str().unwrap();
+
+    // A cover letter or a template, not both
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id, "template_id": template_id, "cover_letter": "Hola" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // Another seeker's template can't be used
+    let other = app.create_job_seeker().await;
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&other),
+            json!({ "job_id": job_id, "template_id": template_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id, "template_id": template_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let letter = "Me interesa el cargo de Test job en Test SpA. Saludos, {{firma}}";
+    assert_eq!(res.body["cover_letter"], letter);
+    assert_eq!(res.body["warnings"].as_array().unwrap().len(), 1);
+    assert!(res.body["warnings"][0]
+        .as_str()
+        .unwrap()
+        .contains("{{firma}}"));
+    let application_id: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+
+    // A refused application doesn't count as a use
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id, "template_id": plain["id"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // The used template now sorts first
+    let res = app
+        .get("/api/me/cover-letter-templates", Some(&seeker))
+        .await;
+    assert_eq!(res.body[0]["id"], template["id"]);
+    assert_eq!(res.body[0]["use_count"], 1);
+    assert!(res.body[0]["last_used_at"].is_string());
+    assert_eq!(res.body[1]["use_count"], 0);
+
+    // Editing the template later leaves the application as sent
+    let res = app
+        .put(
+            &format!("/api/me/cover-letter-templates/{}", template_id),
+            Some(&seeker),
+            json!({ "body": "Otro texto para {{job_title}}" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["use_count"], 1);
+
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT cover_letter FROM job_applications WHERE id = $1")
+            .bind(application_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(stored.as_deref(), Some(letter));
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": other_job_id, "template_id": template_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["cover_letter"], "Otro texto para Test job");
+    assert_eq!(res.body["warnings"], json!([]));
+}
+
+#[sqlx::test]
+async fn test_omil_applies_with_managed_seeker_template(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+    let managed_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let template = create_template(&app, &seeker, "General", "Postulo a {{job_title}}").await;
+    let someone_else = app.create_job_seeker().await;
+    let foreign = create_template(&app, &someone_else, "General", "No es mía").await;
+
+    let res = app
+        .get(
+            &format!(
+                "/api/me/omil/job-seekers/{}/cover-letter-templates",
+                managed_id
+            ),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    assert_eq!(res.body[0]["id"], template["id"]);
+
+    let uri = format!("/api/me/omil/job-seekers/{}/apply", managed_id);
+    let res = app
+        .post(
+            &uri,
+            Some(&omil.director),
+            json!({ "job_id": job_id, "template_id": foreign["id"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+
+    let res = app
+        .post(
+            &uri,
+            Some(&omil.director),
+            json!({ "job_id": job_id, "template_id": template["id"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT cover_letter FROM job_applications WHERE id = $1::uuid")
+            .bind(res.body["application_id"].as_str().unwrap())
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(stored.as_deref(), Some("Postulo a Test job"));
+}
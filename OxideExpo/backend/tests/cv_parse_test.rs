@@ -0,0 +1,263 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{FormPart, TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+/// A one-page PDF showing `lines` in Helvetica, one below the other. The
+/// text is written in Latin-1, which is what WinAnsiEncoding expects.
+fn pdf(lines: &[&str]) -> Vec<u8> {
+    let mut content = b"BT\n/F1 11 Tf\n14 TL\n50 780 Td\n".to_vec();
+    for line in lines {
+        content.push(b'(');
+        for c in line.chars() {
+            if matches!(c, '\\' | '(' | ')') {
+                content.push(b'\\');
+            }
+            content.push(u8::try_from(u32::from(c)).expect("Latin-1 text"));
+        }
+        content.extend_from_slice(b") Tj\nT*\n");
+    }
+    content.extend_from_slice(b"ET\n");
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 842] /Contents 4 0 R /Resources << /Font << /F1 5 0 R >> >> >>".to_string(),
+        format!("<< /Length {} >>\nstream\n", content.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{}", i + 1, object).as_bytes());
+        // The content stream is the one object that is not plain text
+        if i == 3 {
+            out.extend_from_slice(&content);
+            out.extend_from_slice(b"endstream");
+        }
+        out.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+const CV_LINES: &[&str] = &[
+    "Valentina Muñoz Araya",
+    "Técnico en Logística",
+    "Teléfono: +56 9 7654 3210",
+    "Experiencia laboral",
+    "Supervisora de Bodega",
+    "Distribuidora Sur SpA",
+    "Abril 2020 - Presente",
+    "Control de inventario y despacho de pedidos.",
+    "Educación",
+    "Técnico en Logística",
+    "Instituto Profesional Duoc UC",
+    "2016 - 2018",
+    "Habilidades",
+    "Excel, SAP, grúa horquilla",
+];
+
+async fn parse_upload(app: &TestApp, seeker: &TestUser, content_type: &str, data: &[u8]) -> Value {
+    let res = app
+        .post_multipart(
+            "/api/me/profile/cv/parse",
+            Some(seeker),
+            &[FormPart {
+                name: "file",
+                filename: Some("cv.pdf"),
+                content_type: Some(content_type),
+                data,
+            }],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+/// Entries the seeker has in a profile list such as /api/me/experience
+async fn count(app: &TestApp, seeker: &TestUser, uri: &str) -> usize {
+    let res = app.get(uri, Some(seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body.as_array().unwrap().len()
+}
+
+#[sqlx::test]
+async fn test_parse_suggests_and_apply_saves_only_accepted(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let suggestions = parse_upload(&app, &seeker, "application/pdf", &pdf(CV_LINES)).await;
+    assert_eq!(suggestions["reason"], Value::Null);
+    assert_eq!(suggestions["phone"]["value"], "+56976543210");
+    assert_eq!(suggestions["phone"]["confidence"], "high");
+    assert_eq!(suggestions["headline"]["value"], "Técnico en Logística");
+
+    let experience = &suggestions["work_experiences"][0];
+    assert_eq!(experience["position_title"], "Supervisora de Bodega");
+    assert_eq!(experience["company_name"], "Distribuidora Sur SpA");
+    assert_eq!(experience["start_date"], "2020-04-01");
+    assert_eq!(experience["is_current"], true);
+    assert_eq!(experience["confidence"], "high");
+
+    let education = &suggestions["education"][0];
+    assert_eq!(education["institution_name"], "Instituto Profesional Duoc UC");
+    assert_eq!(education["level"], "technical");
+    assert_eq!(education["status"], "completed");
+
+    // Parsing never saves anything
+    assert_eq!(count(&app, &seeker, "/api/me/experience").await, 1);
+
+    // The seeker keeps the experience, edits its title and skips the education
+    let res = app
+        .post(
+            "/api/me/profile/apply-suggestions",
+            Some(&seeker),
+            json!({
+                "phone": suggestions["phone"]["value"],
+                "work_experiences": [{
+                    "company_name": experience["company_name"],
+                    "position_title": "Jefa de Bodega",
+                    "is_current": true,
+                    "start_date": experience["start_date"],
+                }],
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["phone"], "+56976543210");
+    assert_eq!(res.body["work_experiences"][0]["position_title"], "Jefa de Bodega");
+    assert_eq!(res.body["education"], json!([]));
+
+    assert_eq!(count(&app, &seeker, "/api/me/experience").await, 2);
+    assert_eq!(count(&app, &seeker, "/api/me/education").await, 0);
+}
+
+#[sqlx::test]
+async fn test_unparseable_cvs_give_a_reason(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    // A scan has pages but no text layer
+    let body = parse_upload(&app, &seeker, "application/pdf", &pdf(&[])).await;
+    assert_eq!(body["reason"], "no_text");
+    assert_eq!(body["work_experiences"], json!([]));
+
+    let body = parse_upload(&app, &seeker, "application/pdf", b"%PDF-1.4 truncated").await;
+    assert_eq!(body["reason"], "unreadable");
+
+    let body = parse_upload(&app, &seeker, "application/msword", b"word document").await;
+    assert_eq!(body["reason"], "unsupported_format");
+
+    // Without a file the stored CV is used, and a stored Word CV is not read
+    let res = app
+        .post("/api/me/profile/cv/parse", Some(&seeker), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    sqlx::query(
+        r#"
+        WITH f AS (
+            INSERT INTO uploaded_files (user_id, file_type, original_filename, storage_path, content_type)
+            VALUES ($1, 'cv', 'cv.doc', 'cvs/missing.doc', 'application/msword')
+            RETURNING id
+        )
+        UPDATE job_seeker_profiles SET cv_file_id = (SELECT id FROM f) WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app
+        .post("/api/me/profile/cv/parse", Some(&seeker), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["reason"], "unsupported_format");
+
+    let res = app
+        .post_multipart(
+            "/api/me/profile/cv/parse",
+            Some(&seeker),
+            &[FormPart {
+                name: "file",
+                filename: Some("cv.png"),
+                content_type: Some("image/png"),
+                data: b"png",
+            }],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_apply_suggestions_is_all_or_nothing(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let valid = json!({
+        "company_name": "Distribuidora Sur SpA",
+        "position_title": "Supervisora de Bodega",
+        "is_current": true,
+        "start_date": "2020-04-01",
+    });
+    let res = app
+        .post(
+            "/api/me/profile/apply-suggestions",
+            Some(&seeker),
+            json!({
+                "professional_headline": "Técnico en Logística",
+                "work_experiences": [valid, { "company_name": "", "position_title": "Cajera", "is_current": false, "start_date": "2015-01-01" }],
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .post(
+            "/api/me/profile/apply-suggestions",
+            Some(&seeker),
+            json!({ "work_experiences": vec![valid.clone(); 21] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .post(
+            "/api/me/profile/apply-suggestions",
+            Some(&seeker),
+            json!({ "phone": "123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    assert_eq!(count(&app, &seeker, "/api/me/experience").await, 1);
+    let res = app.get("/api/me/profile", Some(&seeker)).await;
+    assert_ne!(res.body["professional_headline"], "Técnico en Logística");
+
+    let company = app.create_company_with_owner().await;
+    let res = app
+        .post(
+            "/api/me/profile/apply-suggestions",
+            Some(&company.owner),
+            json!({ "work_experiences": [valid] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::models::application::ApplicationSource;
+use empleos_inclusivos_backend::services::applications::{self, NewApplication};
+use serde_json::{json, Value};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+async fn event_count(db: impl PgExecutor<'_>, aggregate_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM domain_events WHERE aggregate_id = $1")
+        .bind(aggregate_id)
+        .fetch_one(db)
+        .await
+        .unwrap()
+}
+
+async fn stream(app: &TestApp, admin: &TestUser, aggregate_type: &str, id: Uuid) -> Vec<Value> {
+    let res = app
+        .get(
+            &format!(
+                "/api/admin/domain-events?aggregate_type={}&aggregate_id={}",
+                aggregate_type, id
+            ),
+            Some(admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body.as_array().unwrap().clone()
+}
+
+fn event_types(events: &[Value]) -> Vec<&str> {
+    events
+        .iter()
+        .map(|e| e["event_type"].as_str().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_application_history_is_one_redacted_stream(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let application_id: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+
+    let uri = format!("/api/me/jobs/{}/applications/{}", job_id, application_id);
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "status": "under_review" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // An illegal transition changes nothing and records nothing
+    let res = app
+        .put(&uri, Some(&company.owner), json!({ "status": "hired" }))
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({
+                "status": "rejected",
+                "rejection_reason_code": "experience",
+                "feedback_text": "Buscamos más años en bodega",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let events = stream(&app, &admin, "application", application_id).await;
+    assert_eq!(
+        event_types(&events),
+        vec!["submitted", "status_changed", "status_changed"]
+    );
+    assert_eq!(events[0]["actor_id"], json!(seeker.id));
+    assert_eq!(events[0]["payload"]["job_id"], json!(job_id));
+    assert_eq!(events[1]["actor_id"], json!(company.owner.id));
+    assert_eq!(events[1]["payload"]["from"], "submitted");
+    assert_eq!(events[1]["payload"]["to"], "under_review");
+
+    let rejected = &events[2]["payload"];
+    assert_eq!(rejected["to"], "rejected");
+    assert_eq!(rejected["rejection_reason_code"], "experience");
+    assert_eq!(rejected["feedback"], "[REDACTED]");
+
+    // The raw payload keeps the text for the record
+    let stored: String = sqlx::query_scalar(
+        "SELECT payload->>'feedback' FROM domain_events WHERE aggregate_id = $1 AND payload->>'to' = 'rejected'",
+    )
+    .bind(application_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(stored, "Buscamos más años en bodega");
+
+    let res = app
+        .get(
+            &format!(
+                "/api/admin/domain-events?aggregate_type=application&aggregate_id={}",
+                application_id
+            ),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_rolled_back_mutation_leaves_no_event(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+
+    let mut tx = app.db().begin().await.unwrap();
+    let application = applications::submit(
+        &mut tx,
+        &NewApplication {
+            job_id,
+            applicant_id: seeker.id,
+            cover_letter: Some("Me interesa el cargo".to_string()),
+            resume_url: None,
+            source: ApplicationSource::Direct,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(event_count(&mut *tx, application.id).await, 1);
+    assert_eq!(event_count(app.db(), application.id).await, 0);
+
+    tx.rollback().await.unwrap();
+
+    assert_eq!(event_count(app.db(), application.id).await, 0);
+}
+
+#[sqlx::test]
+async fn test_job_lifecycle_events(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    sqlx::query("UPDATE jobs SET status = 'pending_approval' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/approve", job_id),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", job_id),
+            Some(&company.owner),
+            json!({ "status": "closed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let events = stream(&app, &admin, "job", job_id).await;
+    assert_eq!(event_types(&events), vec!["approved", "closed"]);
+    assert_eq!(events[0]["actor_id"], json!(admin.id));
+    assert_eq!(events[0]["payload"]["status"], "active");
+    assert_eq!(events[1]["payload"]["from"], "active");
+
+    // Events can't be rewritten once recorded
+    let rewritten = sqlx::query("DELETE FROM domain_events WHERE aggregate_id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await;
+    assert!(rewritten.is_err());
+}
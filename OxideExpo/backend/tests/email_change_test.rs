@@ -0,0 +1,280 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser, TEST_PASSWORD};
+use empleos_inclusivos_backend::utils::jwt::hash_token;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Tokens for a user's open email change request. The real ones only go out
+/// by email, so the stored hashes are swapped for ones the test knows.
+struct ChangeTokens {
+    confirm: String,
+    cancel: String,
+}
+
+async fn request_change(app: &TestApp, user: &TestUser, new_email: &str) -> ChangeTokens {
+    let res = app
+        .post(
+            "/api/me/email/change-request",
+            Some(user),
+            json!({ "new_email": new_email, "current_password": TEST_PASSWORD }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["new_email"], new_email.to_lowercase());
+
+    let tokens = ChangeTokens {
+        confirm: format!("confirm-{}", Uuid::new_v4()),
+        cancel: format!("cancel-{}", Uuid::new_v4()),
+    };
+    sqlx::query(
+        r#"
+        UPDATE pending_email_changes
+        SET confirm_token_hash = $2, cancel_token_hash = $3
+        WHERE user_id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL
+        "#,
+    )
+    .bind(user.id)
+    .bind(hash_token(&tokens.confirm))
+    .bind(hash_token(&tokens.cancel))
+    .execute(app.db())
+    .await
+    .unwrap();
+    tokens
+}
+
+async fn confirm(app: &TestApp, token: &str) -> common::TestResponse {
+    app.post(
+        "/api/auth/email/confirm-change",
+        None,
+        json!({ "token": token }),
+    )
+    .await
+}
+
+async fn login(app: &TestApp, email: &str) -> common::TestResponse {
+    app.post(
+        "/api/auth/login",
+        None,
+        json!({ "email": email, "password": TEST_PASSWORD }),
+    )
+    .await
+}
+
+async fn current_email(app: &TestApp, user: &TestUser) -> String {
+    sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+fn new_address() -> String {
+    format!("nuevo-{}@test.cl", Uuid::new_v4())
+}
+
+#[sqlx::test]
+async fn test_confirmed_change_swaps_email_and_ends_sessions(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+
+    let res = login(&app, &user.email).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let refresh_token = res.body["refresh_token"].clone();
+
+    // The current password is required
+    let res = app
+        .post(
+            "/api/me/email/change-request",
+            Some(&user),
+            json!({ "new_email": new_address(), "current_password": "WrongPassword1" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+
+    let new_email = new_address();
+    let tokens = request_change(&app, &user, &new_email.to_uppercase()).await;
+
+    // Login keeps using the old email until the change is confirmed
+    assert_eq!(login(&app, &user.email).await.status, StatusCode::OK);
+    assert_eq!(
+        login(&app, &new_email).await.status,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let res = confirm(&app, &tokens.confirm).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(current_email(&app, &user).await, new_email);
+
+    let verified: bool =
+        sqlx::query_scalar("SELECT email_verified_at IS NOT NULL FROM users WHERE id = $1")
+            .bind(user.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(verified);
+
+    // Recorded in the user's event log, by the user
+    let events: Vec<(String, Option<Uuid>)> = sqlx::query_as(
+        "SELECT event_type, actor_id FROM domain_events WHERE aggregate_type = 'user' AND aggregate_id = $1",
+    )
+    .bind(user.id)
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(events, vec![("email_changed".to_string(), Some(user.id))]);
+
+    // Existing access and refresh tokens stop working
+    let res = app.get("/api/auth/me", Some(&user)).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+
+    assert_eq!(
+        login(&app, &user.email).await.status,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(login(&app, &new_email).await.status, StatusCode::OK);
+
+    // The token is single use and the confirmed row is the record of the change
+    let res = confirm(&app, &tokens.confirm).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let (old_email, recorded_new): (String, String) = sqlx::query_as(
+        "SELECT old_email, new_email FROM pending_email_changes WHERE user_id = $1 AND confirmed_at IS NOT NULL",
+    )
+    .bind(user.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(old_email, user.email);
+    assert_eq!(recorded_new, new_email);
+}
+
+#[sqlx::test]
+async fn test_new_email_registered_before_confirmation(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+
+    // Addresses already in use are rejected up front
+    let res = app
+        .post(
+            "/api/me/email/change-request",
+            Some(&user),
+            json!({ "new_email": other.email, "current_password": TEST_PASSWORD }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let new_email = new_address();
+    let tokens = request_change(&app, &user, &new_email).await;
+
+    // Someone else registers the address while the request is pending
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            json!({
+                "email": new_email,
+                "password": TEST_PASSWORD,
+                "first_name": "Otra",
+                "last_name": "Persona"
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = confirm(&app, &tokens.confirm).await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+    assert_eq!(current_email(&app, &user).await, user.email);
+    assert_eq!(login(&app, &user.email).await.status, StatusCode::OK);
+    let res = app.get("/api/auth/me", Some(&user)).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_cancel_voids_request(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+
+    let tokens = request_change(&app, &user, &new_address()).await;
+
+    let res = app
+        .post(
+            "/api/auth/email/cancel-change",
+            None,
+            json!({ "token": tokens.cancel }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = confirm(&app, &tokens.confirm).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert_eq!(current_email(&app, &user).await, user.email);
+
+    // A confirmed change cannot be cancelled afterwards
+    let tokens = request_change(&app, &user, &new_address()).await;
+    assert_eq!(confirm(&app, &tokens.confirm).await.status, StatusCode::OK);
+    let res = app
+        .post(
+            "/api/auth/email/cancel-change",
+            None,
+            json!({ "token": tokens.cancel }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_one_open_request_and_expiry(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_job_seeker().await;
+
+    // A second request replaces the first
+    let first = request_change(&app, &user, &new_address()).await;
+    let second_email = new_address();
+    let second = request_change(&app, &user, &second_email).await;
+
+    let open: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pending_email_changes WHERE user_id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL",
+    )
+    .bind(user.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(open, 1);
+    assert_eq!(
+        confirm(&app, &first.confirm).await.status,
+        StatusCode::BAD_REQUEST
+    );
+
+    // Requests lapse after 24 hours
+    let expires_in_hours: f64 = sqlx::query_scalar(
+        "SELECT EXTRACT(EPOCH FROM expires_at - created_at)::float8 / 3600 FROM pending_email_changes WHERE new_email = $1",
+    )
+    .bind(&second_email)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!((expires_in_hours - 24.0).abs() < 0.01);
+
+    sqlx::query(
+        "UPDATE pending_email_changes SET expires_at = NOW() - INTERVAL '1 minute' WHERE new_email = $1",
+    )
+    .bind(&second_email)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = confirm(&app, &second.confirm).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert_eq!(current_email(&app, &user).await, user.email);
+}
@@ -0,0 +1,251 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use common::{TestApp, TestOmil, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Downloads a spreadsheet and returns the text of its cells
+async fn download(app: &TestApp, uri: &str, user: &TestUser) -> String {
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", user.token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let mut workbook = zip::ZipArchive::new(Cursor::new(bytes.to_vec())).unwrap();
+    let mut text = String::new();
+    workbook
+        .by_name("xl/sharedStrings.xml")
+        .unwrap()
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+async fn add_member(app: &TestApp, omil: &TestOmil, role: &str) -> TestUser {
+    let member = app.create_user(UserType::OmilMember).await;
+    sqlx::query("INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role)")
+        .bind(omil.id)
+        .bind(member.id)
+        .bind(role)
+        .execute(app.db())
+        .await
+        .unwrap();
+    member
+}
+
+async fn register_seeker(app: &TestApp, omil: &TestOmil, email: &str) {
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({ "email": email, "first_name": "Rosa", "last_name": "Muñoz" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AuditRow {
+    id: Uuid,
+    exported_by: Option<Uuid>,
+    row_count: i32,
+    included_contact: bool,
+    filters: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+async fn audit_rows(app: &TestApp) -> Vec<AuditRow> {
+    sqlx::query_as(
+        "SELECT id, exported_by, row_count, included_contact, filters, created_at FROM export_audit ORDER BY created_at",
+    )
+    .fetch_all(app.db())
+    .await
+    .unwrap()
+}
+
+fn watermark(row: &AuditRow, organization: &str) -> String {
+    format!(
+        "Exported by Test User ({}) on {} UTC - Export ID {}",
+        organization,
+        row.created_at.format("%Y-%m-%d %H:%M"),
+        &row.id.simple().to_string()[..8].to_uppercase()
+    )
+}
+
+#[sqlx::test]
+async fn test_omil_export_is_watermarked_and_audited(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    register_seeker(&app, &omil, "rosa.munoz@test.cl").await;
+    register_seeker(&app, &omil, "ana.rojas@test.cl").await;
+
+    let text = download(
+        &app,
+        "/api/me/omil/job-seekers/export?placement_outcome=pending",
+        &omil.director,
+    )
+    .await;
+
+    let rows = audit_rows(&app).await;
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row.exported_by, Some(omil.director.id));
+    assert_eq!(row.row_count, 2);
+    assert!(row.included_contact);
+    assert_eq!(row.filters["placement_outcome"], "pending");
+
+    assert!(text.contains(&watermark(row, "OMIL Test")), "{}", text);
+    assert!(text.contains("rosa.munoz@test.cl"));
+
+    // Directors find the export by the ID printed in the file
+    let short_id = &row.id.simple().to_string()[..8].to_uppercase();
+    let res = app
+        .get(
+            &format!("/api/me/omil/exports?export_id={}", short_id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["total"], 1);
+    assert_eq!(res.body["data"][0]["short_id"], *short_id);
+    assert_eq!(res.body["data"][0]["export_type"], "managed_seekers");
+
+    // Other OMILs and advisors don't see it
+    let other = app.create_omil_with_director().await;
+    let res = app.get("/api/me/omil/exports", Some(&other.director)).await;
+    assert_eq!(res.body["total"], 0);
+    let advisor = add_member(&app, &omil, "advisor").await;
+    let res = app.get("/api/me/omil/exports", Some(&advisor)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_disabled_contact_export_is_forced_off(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    register_seeker(&app, &omil, "rosa.munoz@test.cl").await;
+    let advisor = add_member(&app, &omil, "advisor").await;
+
+    let res = app
+        .put(
+            "/api/me/omil/export-settings",
+            Some(&advisor),
+            json!({ "allow_contact_export": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let res = app
+        .put(
+            "/api/me/omil/export-settings",
+            Some(&omil.director),
+            json!({ "allow_contact_export": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["allow_contact_export"], false);
+
+    let res = app.get("/api/me/omil/export-settings", Some(&advisor)).await;
+    assert_eq!(res.body["allow_contact_export"], false);
+
+    let text = download(
+        &app,
+        "/api/me/omil/job-seekers/export?include_contact=true",
+        &advisor,
+    )
+    .await;
+    assert!(!text.contains("rosa.munoz@test.cl"), "{}", text);
+    assert!(!text.contains("<t>Email</t>"), "{}", text);
+
+    // Templates lose their contact columns too
+    let res = app
+        .post(
+            "/api/me/omil/export-templates",
+            Some(&omil.director),
+            json!({ "name": "Contactos", "columns": ["full_name", "email", "phone"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let template_id = res.body["id"].as_str().unwrap();
+    let text = download(
+        &app,
+        &format!("/api/me/omil/job-seekers/export?template_id={}", template_id),
+        &advisor,
+    )
+    .await;
+    assert!(!text.contains("rosa.munoz@test.cl"), "{}", text);
+    assert!(text.contains("Rosa Muñoz"), "{}", text);
+
+    let rows = audit_rows(&app).await;
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| !row.included_contact));
+    assert!(rows.iter().all(|row| row.exported_by == Some(advisor.id)));
+    assert_eq!(rows[0].filters["include_contact"], true);
+}
+
+#[sqlx::test]
+async fn test_company_export_watermark_and_admin_restriction(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    app.create_application(job_id, &seeker).await;
+    let uri = format!("/api/me/jobs/{}/applicants/export", job_id);
+
+    let text = download(&app, &uri, &company.owner).await;
+    let rows = audit_rows(&app).await;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].row_count, 1);
+    assert!(rows[0].included_contact);
+    assert!(text.contains(&watermark(&rows[0], "Test SpA")), "{}", text);
+    assert!(text.contains(&seeker.email));
+
+    // Only administrators restrict a company's exports
+    let res = app.get("/api/me/company/settings", Some(&company.owner)).await;
+    assert_eq!(res.body["allow_contact_export"], true);
+    let admin = app.create_admin().await;
+    let res = app
+        .put(
+            &format!("/api/admin/companies/{}/export-settings", company.id),
+            Some(&admin),
+            json!({ "allow_contact_export": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let text = download(&app, &format!("{}?include_contact=true", uri), &company.owner).await;
+    assert!(!text.contains(&seeker.email), "{}", text);
+
+    let res = app
+        .get(
+            &format!("/api/admin/exports?company_id={}", company.id),
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["total"], 2);
+    assert_eq!(res.body["data"][0]["included_contact"], false);
+    assert_eq!(res.body["data"][1]["included_contact"], true);
+    assert_eq!(res.body["data"][0]["organization_name"], "Test SpA");
+}
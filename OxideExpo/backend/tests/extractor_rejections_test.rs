@@ -0,0 +1,54 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_malformed_path_uuid_is_not_found(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app.get("/api/jobs/not-a-uuid", None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    assert_eq!(res.body["error"], "Resource not found");
+
+    // Same answer as a well-formed ID that doesn't exist
+    let admin = app.create_admin().await;
+    let res = app.get("/api/admin/users/123", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    assert!(res.body["error"].is_string());
+}
+
+#[sqlx::test]
+async fn test_malformed_query_uuid_names_the_parameter(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    let res = app
+        .get("/api/admin/audit-logs?admin_id=123&limit=10", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        res.body["error"],
+        "Invalid value for query parameter 'admin_id'"
+    );
+    assert_eq!(res.body["details"]["parameter"], "admin_id");
+
+    let res = app
+        .get("/api/admin/audit-logs?limit=10", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_malformed_query_enum_names_the_parameter(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let res = app.get("/api/jobs?work_modality=hovercraft", None).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(res.body["details"]["parameter"], "work_modality");
+    assert!(res.body["details"]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("hovercraft"));
+}
@@ -0,0 +1,271 @@
+mod common;
+
+use std::path::PathBuf;
+
+use axum::http::StatusCode;
+use bytes::Bytes;
+use common::{FormPart, TestApp, TestUser};
+use empleos_inclusivos_backend::{
+    error::AppError,
+    services::storage::{LocalStorage, StorageBackend, StorageDriver, MAX_OBJECT_BYTES},
+    utils::jwt,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const PDF: &[u8] = b"%PDF-1.4 file storage test";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n file storage test";
+
+fn file<'a>(filename: &'a str, content_type: &'a str, data: &'a [u8]) -> FormPart<'a> {
+    FormPart {
+        name: "file",
+        filename: Some(filename),
+        content_type: Some(content_type),
+        data,
+    }
+}
+
+/// Empty directory under the system temp dir, for the local driver
+fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("storage-test-{}", Uuid::new_v4()))
+}
+
+/// Path part of a download link, which the test router serves
+fn link_path(url: &str) -> &str {
+    &url[url.find("/api/").unwrap()..]
+}
+
+async fn storage_path(app: &TestApp, file_id: &Value) -> String {
+    sqlx::query_scalar("SELECT storage_path FROM uploaded_files WHERE id = $1::uuid")
+        .bind(file_id.as_str().unwrap())
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Uploads `data` to `uri` and checks GET /api/files/{id} streams it back
+async fn upload_and_download(
+    app: &TestApp,
+    uri: &str,
+    user: &TestUser,
+    part: FormPart<'_>,
+) -> Value {
+    let data = part.data;
+    let res = app.put_multipart(uri, Some(user), &[part]).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let download = app
+        .get(res.body["download_url"].as_str().unwrap(), Some(user))
+        .await;
+    assert_eq!(download.status, StatusCode::OK);
+    assert_eq!(&download.bytes[..], data);
+    res.body
+}
+
+/// Every file feature end to end, against whichever driver `app` uses
+async fn upload_suite(app: &TestApp) {
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    // CV, replaced and then deleted along with its object
+    let first = upload_and_download(
+        app,
+        "/api/me/profile/cv",
+        &seeker,
+        file("cv.pdf", "application/pdf", PDF),
+    )
+    .await;
+    let first_path = storage_path(app, &first["file_id"]).await;
+    assert_eq!(
+        app.state.storage.head(&first_path).await.unwrap(),
+        Some(PDF.len() as u64)
+    );
+
+    let second = upload_and_download(
+        app,
+        "/api/me/profile/cv",
+        &seeker,
+        file("cv-2024.pdf", "application/pdf", PDF),
+    )
+    .await;
+    let second_path = storage_path(app, &second["file_id"]).await;
+    assert_eq!(app.state.storage.head(&first_path).await.unwrap(), None);
+
+    let res = app.delete("/api/me/profile/cv", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(app.state.storage.head(&second_path).await.unwrap(), None);
+
+    // Images
+    upload_and_download(
+        app,
+        "/api/me/profile/image",
+        &seeker,
+        file("yo.png", "image/png", PNG),
+    )
+    .await;
+    upload_and_download(
+        app,
+        "/api/me/company/logo",
+        &company.owner,
+        file("logo.png", "image/png", PNG),
+    )
+    .await;
+
+    // Application documents and reference letters reach the company through
+    // download links
+    let job_id = app.create_active_job(&company).await;
+    let app_id = app.create_application(job_id, &seeker).await;
+    let res = app
+        .post_multipart(
+            &format!("/api/me/applications/{}/documents", app_id),
+            Some(&seeker),
+            &[
+                FormPart {
+                    name: "label",
+                    filename: None,
+                    content_type: None,
+                    data: b"Certificado",
+                },
+                file("certificado.pdf", "application/pdf", PDF),
+            ],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/me/references",
+            Some(&seeker),
+            json!({
+                "referee_name": "María Soto",
+                "referee_organization": "Panadería El Trigal",
+                "relationship": "Jefa directa",
+                "contact_email": "referee@example.cl"
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .post_multipart(
+            &format!(
+                "/api/me/references/{}/letter",
+                res.body["id"].as_str().unwrap()
+            ),
+            Some(&seeker),
+            &[file("carta.pdf", "application/pdf", PDF)],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    for url in [
+        &res.body["documents"][0]["download_url"],
+        &res.body["references"][0]["letter_download_url"],
+    ] {
+        let download = app.get(link_path(url.as_str().unwrap()), None).await;
+        assert_eq!(download.status, StatusCode::OK);
+        assert_eq!(&download.bytes[..], PDF);
+    }
+
+    // The driver itself refuses objects over the size limit
+    let too_large = Bytes::from(vec![0; MAX_OBJECT_BYTES + 1]);
+    assert!(matches!(
+        app.state.storage.put("cvs/too-large.pdf", too_large).await,
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[sqlx::test]
+async fn test_file_uploads_with_memory_storage(db: PgPool) {
+    let app =
+        TestApp::with_config(db, |config| config.storage_driver = StorageDriver::Memory).await;
+    upload_suite(&app).await;
+}
+
+#[sqlx::test]
+async fn test_file_uploads_with_local_storage(db: PgPool) {
+    let root = temp_root();
+    let app = TestApp::with_config(db, |config| {
+        config.storage_driver = StorageDriver::Local;
+        config.storage_local_dir = root.to_string_lossy().into_owned();
+    })
+    .await;
+    upload_suite(&app).await;
+
+    // Objects are plain files under the configured directory
+    let logo_path: String = sqlx::query_scalar(
+        "SELECT storage_path FROM uploaded_files WHERE file_type = 'company_logo'",
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(std::fs::read(root.join(&logo_path)).unwrap(), PNG);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[sqlx::test]
+async fn test_local_storage_rejects_path_traversal(db: PgPool) {
+    let parent = temp_root();
+    let root = parent.join("root");
+    let app = TestApp::with_config(db, |config| {
+        config.storage_driver = StorageDriver::Local;
+        config.storage_local_dir = root.to_string_lossy().into_owned();
+    })
+    .await;
+    let storage = LocalStorage::new(root.to_str().unwrap(), app.state.config.clone()).unwrap();
+
+    for key in [
+        "../escape.txt",
+        "cvs/../../escape.txt",
+        "/tmp/escape.txt",
+        "cvs\\..\\..\\escape.txt",
+    ] {
+        assert!(
+            matches!(
+                storage.put(key, Bytes::from_static(b"x")).await,
+                Err(AppError::ValidationError(_))
+            ),
+            "{} was stored",
+            key
+        );
+        assert!(storage.get(key).await.is_err());
+        assert!(storage
+            .presign_get(key, std::time::Duration::from_secs(60))
+            .await
+            .is_err());
+    }
+    assert!(!parent.join("escape.txt").exists());
+    assert!(!std::path::Path::new("/tmp/escape.txt").exists());
+
+    // A download link can't be forged to read outside the root either
+    std::fs::write(parent.join("secret.txt"), b"secret").unwrap();
+    let token = jwt::create_file_download_token(
+        "../secret.txt",
+        chrono::Duration::minutes(5),
+        &app.state.config,
+    )
+    .unwrap();
+    let res = app
+        .get(&format!("/api/files/download?token={}", token), None)
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert!(!res.bytes.starts_with(b"secret"));
+
+    // Nor can a link for one object be reused with a tampered token
+    let url = storage
+        .presign_get("cvs/real.pdf", std::time::Duration::from_secs(60))
+        .await
+        .unwrap();
+    let res = app.get(&format!("{}x", link_path(&url)), None).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    std::fs::remove_dir_all(&parent).unwrap();
+}
@@ -0,0 +1,377 @@
+mod common;
+
+use std::borrow::Cow;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Version of the migration that replaced the followup_type enum
+const FOLLOWUP_TYPES_MIGRATION: i64 = 53;
+
+const MARCH_2024: &str = "/api/me/omil/reports/monthly?year=2024&month=3";
+
+const LEGACY_TYPES: [&str; 8] = [
+    "initial_registration",
+    "profile_update",
+    "job_application",
+    "interview_scheduled",
+    "interview_completed",
+    "placement",
+    "follow_up_call",
+    "general_note",
+];
+
+/// Registers a seeker with the OMIL, returning the managed record ID
+async fn managed_seeker(app: &TestApp, omil: &TestOmil) -> Uuid {
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({
+                "email": format!("{}@test.cl", Uuid::new_v4()),
+                "first_name": "Rosa",
+                "last_name": "Muñoz",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn create_followup(
+    app: &TestApp,
+    omil: &TestOmil,
+    managed_id: Uuid,
+    followup_type: &str,
+) -> common::TestResponse {
+    app.post(
+        &format!("/api/me/omil/job-seekers/{}/followups", managed_id),
+        Some(&omil.director),
+        json!({ "followup_type": followup_type, "content": "Conversación con el usuario" }),
+    )
+    .await
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_migration_keeps_existing_followups(db: PgPool) {
+    // Schema as it was before the migration
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.migrations = Cow::Owned(
+        migrator
+            .migrations
+            .iter()
+            .filter(|m| m.version < FOLLOWUP_TYPES_MIGRATION)
+            .cloned()
+            .collect(),
+    );
+    migrator.run(&db).await.unwrap();
+
+    let user_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO users (email, password_hash, first_name, last_name, user_type)
+        VALUES ($1, 'x', 'Ana', 'Pérez', 'job_seeker')
+        RETURNING id
+        "#,
+    )
+    .bind(format!("{}@test.cl", Uuid::new_v4()))
+    .fetch_one(&db)
+    .await
+    .unwrap();
+
+    for followup_type in LEGACY_TYPES {
+        sqlx::query(
+            r#"
+            INSERT INTO job_seeker_followups (job_seeker_id, created_by, followup_type, content)
+            VALUES ($1, $1, $2::followup_type, $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(followup_type)
+        .execute(&db)
+        .await
+        .unwrap();
+    }
+
+    sqlx::migrate!("./migrations").run(&db).await.unwrap();
+
+    // Every followup kept its type, which is now a seeded reference row
+    let rows: Vec<(String, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT f.followup_type, f.content, t.is_active
+        FROM job_seeker_followups f
+        JOIN followup_types t ON t.code = f.followup_type
+        ORDER BY f.content
+        "#,
+    )
+    .fetch_all(&db)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), LEGACY_TYPES.len());
+    for (followup_type, content, is_active) in rows {
+        assert_eq!(followup_type, content);
+        assert!(is_active);
+    }
+
+    let seeded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM followup_types")
+        .fetch_one(&db)
+        .await
+        .unwrap();
+    assert_eq!(seeded, LEGACY_TYPES.len() as i64);
+
+    // Codes that don't exist are refused by the database too
+    let res = sqlx::query(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, followup_type, content)
+        VALUES ($1, $1, 'made_up', 'x')
+        "#,
+    )
+    .bind(user_id)
+    .execute(&db)
+    .await;
+    assert!(res.is_err());
+}
+
+#[sqlx::test]
+async fn test_followups_only_accept_active_types(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let admin = app.create_admin().await;
+    let managed_id = managed_seeker(&app, &omil).await;
+
+    // Clients sending the old enum strings keep working
+    let res = create_followup(&app, &omil, managed_id, "follow_up_call").await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["followup_type"], "follow_up_call");
+    let followup_id = res.body["id"].as_str().unwrap().to_string();
+
+    let res = create_followup(&app, &omil, managed_id, "made_up").await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/followup-types",
+            Some(&admin),
+            json!({
+                "code": "sence_training_referral",
+                "label_es": "Derivación a capacitación SENCE",
+                "counts_as_intervention": true,
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_active"], true);
+
+    let res = app
+        .get("/api/me/omil/followup-types", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t["code"] == "sence_training_referral"));
+
+    let res = create_followup(&app, &omil, managed_id, "sence_training_referral").await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Retired: no new followups, but the existing one stays and is still listed
+    let res = app
+        .put(
+            "/api/admin/reference/followup-types/follow_up_call",
+            Some(&admin),
+            json!({ "is_active": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_active"], false);
+
+    let res = create_followup(&app, &omil, managed_id, "follow_up_call").await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .get("/api/me/omil/followup-types", Some(&omil.director))
+        .await;
+    assert!(!res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t["code"] == "follow_up_call"));
+
+    // Editing the old followup without touching its type is still allowed,
+    // moving another followup onto the retired type isn't
+    let res = app
+        .put(
+            &format!("/api/me/omil/followups/{}", followup_id),
+            Some(&omil.director),
+            json!({ "followup_type": "follow_up_call", "content": "Llamada reagendada" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = create_followup(&app, &omil, managed_id, "general_note").await;
+    let note_id = res.body["id"].as_str().unwrap().to_string();
+    let res = app
+        .put(
+            &format!("/api/me/omil/followups/{}", note_id),
+            Some(&omil.director),
+            json!({ "followup_type": "follow_up_call" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .get(
+            &format!(
+                "/api/me/omil/job-seekers/{}/followups?followup_type=follow_up_call",
+                managed_id
+            ),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_admin_followup_type_management(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let omil = app.create_omil_with_director().await;
+
+    let res = app
+        .get("/api/admin/reference/followup-types", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app
+        .get("/api/admin/reference/followup-types", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body.as_array().unwrap().len(), LEGACY_TYPES.len());
+
+    let res = app
+        .post(
+            "/api/admin/reference/followup-types",
+            Some(&admin),
+            json!({ "code": "Con Espacios", "label_es": "Inválido" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/followup-types",
+            Some(&admin),
+            json!({ "code": "placement", "label_es": "Duplicado" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+
+    // Types the system writes itself can't go away
+    let res = app
+        .delete(
+            "/api/admin/reference/followup-types/general_note",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // Types in use can only be retired
+    let managed_id = managed_seeker(&app, &omil).await;
+    let res = create_followup(&app, &omil, managed_id, "profile_update").await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .delete(
+            "/api/admin/reference/followup-types/profile_update",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/followup-types",
+            Some(&admin),
+            json!({ "code": "workshop", "label_es": "Taller de apresto laboral" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["counts_as_intervention"], false);
+
+    let res = app
+        .delete("/api/admin/reference/followup-types/workshop", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .put(
+            "/api/admin/reference/followup-types/workshop",
+            Some(&admin),
+            json!({ "label_es": "Taller" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_interventions_follow_the_type_flag(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let admin = app.create_admin().await;
+    let managed_id = managed_seeker(&app, &omil).await;
+
+    // Registration adds an 'initial_registration' followup, which isn't one
+    for followup_type in ["follow_up_call", "interview_scheduled", "general_note"] {
+        let res = create_followup(&app, &omil, managed_id, followup_type).await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    }
+
+    let res = app.get("/api/me/omil/stats", Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["interventions_this_month"], 2);
+
+    // The same followups, moved into a past month for the report
+    sqlx::query(
+        "UPDATE job_seeker_followups SET followup_date = '2024-03-15 12:00:00+00' WHERE omil_id = $1",
+    )
+    .bind(omil.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app.get(MARCH_2024, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["interventions"]["total"], 2);
+
+    // Reclassifying a type changes the rollups, past followups included
+    for (code, counts) in [("general_note", true), ("follow_up_call", false)] {
+        let res = app
+            .put(
+                &format!("/api/admin/reference/followup-types/{}", code),
+                Some(&admin),
+                json!({ "counts_as_intervention": counts }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        assert_eq!(res.body["counts_as_intervention"], counts);
+    }
+    let res = app.get(MARCH_2024, Some(&omil.director)).await;
+    assert_eq!(res.body["interventions"]["total"], 2);
+
+    let res = app
+        .put(
+            "/api/admin/reference/followup-types/interview_scheduled",
+            Some(&admin),
+            json!({ "counts_as_intervention": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app.get(MARCH_2024, Some(&omil.director)).await;
+    assert_eq!(res.body["interventions"]["total"], 1);
+}
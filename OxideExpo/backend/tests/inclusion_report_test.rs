@@ -0,0 +1,156 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A region of its own, so its funnel row only holds this test's data
+async fn create_region(app: &TestApp) -> (Uuid, String) {
+    let name = format!("Región {}", Uuid::new_v4());
+    let id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO regions (country_id, name) SELECT id, $1 FROM countries LIMIT 1 RETURNING id",
+    )
+    .bind(&name)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    (id, name)
+}
+
+async fn create_seeker_with_disability(app: &TestApp, region_id: Uuid) -> Uuid {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query("UPDATE job_seeker_profiles SET region_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(region_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO job_seeker_disabilities (user_id, category) VALUES ($1, 'visual')")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    seeker.id
+}
+
+async fn apply(app: &TestApp, job_id: Uuid, applicant_id: Uuid, status: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO job_applications (job_id, applicant_id, status)
+        VALUES ($1, $2, $3::application_status)
+        "#,
+    )
+    .bind(job_id)
+    .bind(applicant_id)
+    .bind(status)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn fetch_report(app: &TestApp) -> Value {
+    let admin = app.create_admin().await;
+    let res = app.get("/api/admin/reports/inclusion", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+fn region_row<'a>(report: &'a Value, name: &str) -> &'a Value {
+    report["by_region"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["group"] == name)
+        .expect("region row")
+}
+
+#[sqlx::test]
+async fn test_inclusion_report_funnel_conversion(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region_id, region_name) = create_region(&app).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Six applicants, five of them reach the interview stage
+    for i in 0..6 {
+        let seeker_id = create_seeker_with_disability(&app, region_id).await;
+        let status = if i < 5 { "interview_scheduled" } else { "submitted" };
+        apply(&app, job_id, seeker_id, status).await;
+    }
+
+    let report = fetch_report(&app).await;
+    let row = region_row(&report, &region_name);
+
+    assert_eq!(row["seekers"]["value"], 6);
+    assert_eq!(row["applications"]["value"], 6);
+    assert_eq!(row["applied"]["value"], 6);
+    assert_eq!(row["interviewing"]["value"], 5);
+    assert_eq!(row["interview_rate"], 0.8333);
+
+    // Nobody got an offer: the empty cell is suppressed and so are the rates built on it
+    assert_eq!(row["offered"]["suppressed"], true);
+    assert!(row["offered"]["value"].is_null());
+    assert!(row["offer_rate"].is_null());
+    assert!(row["hire_rate"].is_null());
+}
+
+#[sqlx::test]
+async fn test_inclusion_report_suppresses_small_cells(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region_id, region_name) = create_region(&app).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    for _ in 0..2 {
+        let seeker_id = create_seeker_with_disability(&app, region_id).await;
+        apply(&app, job_id, seeker_id, "hired").await;
+    }
+
+    let report = fetch_report(&app).await;
+    let row = region_row(&report, &region_name);
+
+    for cell in ["seekers", "applications", "applied", "interviewing", "offered", "hired"] {
+        assert_eq!(row[cell]["suppressed"], true, "{} should be suppressed", cell);
+        assert!(row[cell]["value"].is_null());
+    }
+
+    // No group anywhere in the report exposes a people count under the threshold
+    let rows = report["by_region"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(report["by_category"].as_array().unwrap());
+    for row in rows {
+        for cell in ["seekers", "applied", "interviewing", "offered", "hired"] {
+            if let Some(value) = row[cell]["value"].as_i64() {
+                assert!(value >= 5);
+            }
+        }
+    }
+}
+
+#[sqlx::test]
+async fn test_inclusion_report_hides_cells_recoverable_from_totals(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let mut regions = Vec::new();
+    for seekers in [7, 2, 6] {
+        let (region_id, region_name) = create_region(&app).await;
+        for _ in 0..seekers {
+            create_seeker_with_disability(&app, region_id).await;
+        }
+        regions.push(region_name);
+    }
+
+    let report = fetch_report(&app).await;
+    assert_eq!(report["seekers_with_disability"]["value"], 15);
+
+    // Total minus the shown regions would give away the smallest one, so
+    // the next smallest is hidden with it
+    let seekers: Vec<&Value> = regions
+        .iter()
+        .map(|name| &region_row(&report, name)["seekers"]["value"])
+        .collect();
+    assert_eq!(seekers, vec![&json!(7), &Value::Null, &Value::Null]);
+}
@@ -0,0 +1,330 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn create_institution(app: &TestApp, name: &str) -> Uuid {
+    sqlx::query_scalar("INSERT INTO institutions (name) VALUES ($1) RETURNING id")
+        .bind(name)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn add_education(app: &TestApp, seeker: &TestUser, institution: Value) -> Value {
+    let mut body = json!({
+        "level": "undergraduate",
+        "status": "completed",
+        "start_date": "2015-03-01",
+        "end_date": "2019-12-20",
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(institution.as_object().unwrap().clone());
+
+    let res = app.post("/api/me/education", Some(seeker), body).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+async fn search(app: &TestApp, q: &str) -> Vec<String> {
+    let res = app
+        .get(&format!("/api/reference/institutions?q={}", q), None)
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_education_links_exact_institution_names(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let austral = create_institution(&app, "Universidad Técnica del Sur Austral").await;
+    let other = create_institution(&app, "Instituto Profesional del Maule").await;
+
+    // Case and surrounding spaces don't matter
+    let record = add_education(
+        &app,
+        &seeker,
+        json!({ "institution_name": "  universidad técnica del SUR austral " }),
+    )
+    .await;
+    assert_eq!(record["institution_id"], json!(austral));
+
+    // Anything short of an exact match stays free text
+    let record = add_education(
+        &app,
+        &seeker,
+        json!({ "institution_name": "U. Técnica del Sur Austral" }),
+    )
+    .await;
+    assert!(record["institution_id"].is_null());
+
+    // A chosen institution wins over the typed name
+    let record = add_education(
+        &app,
+        &seeker,
+        json!({ "institution_id": other, "institution_name": "Universidad Técnica del Sur Austral" }),
+    )
+    .await;
+    assert_eq!(record["institution_id"], json!(other));
+
+    // Updates resolve the same way
+    let res = app
+        .put(
+            &format!("/api/me/education/{}", record["id"].as_str().unwrap()),
+            Some(&seeker),
+            json!({
+                "institution_name": "INSTITUTO PROFESIONAL DEL MAULE",
+                "level": "technical",
+                "status": "completed",
+                "start_date": "2015-03-01",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["institution_id"], json!(other));
+}
+
+#[sqlx::test]
+async fn test_merge_institutions_repoints_records(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+    let canonical = create_institution(&app, "Universidad de Magallanes").await;
+    let duplicate = create_institution(&app, "Univ. de Magallanes").await;
+    let older_duplicate = create_institution(&app, "U. Magallanes").await;
+    let unrelated = create_institution(&app, "Universidad de Aysén").await;
+
+    let on_duplicate = add_education(&app, &seeker, json!({ "institution_id": duplicate })).await;
+    let on_unrelated = add_education(&app, &seeker, json!({ "institution_id": unrelated })).await;
+
+    // An earlier merge into what is now a duplicate
+    let res = app
+        .post(
+            "/api/admin/reference/institutions/merge",
+            Some(&admin),
+            json!({ "canonical_id": duplicate, "duplicate_ids": [older_duplicate] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Saved without going through the match, like records typed before the
+    // institution entries existed
+    let free_text: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO education_records (user_id, institution_name, level, status, start_date)
+        VALUES ($1, 'u. magallanes', 'undergraduate', 'completed', '2010-03-01')
+        RETURNING id
+        "#,
+    )
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let seeker_user = app.create_job_seeker().await;
+    let res = app
+        .post(
+            "/api/admin/reference/institutions/merge",
+            Some(&seeker_user),
+            json!({ "canonical_id": canonical, "duplicate_ids": [duplicate] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/institutions/merge",
+            Some(&admin),
+            json!({ "canonical_id": canonical, "duplicate_ids": [canonical, duplicate] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/institutions/merge",
+            Some(&admin),
+            json!({ "canonical_id": canonical, "duplicate_ids": [duplicate, Uuid::new_v4()] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/admin/reference/institutions/merge",
+            Some(&admin),
+            json!({ "canonical_id": canonical, "duplicate_ids": [duplicate, duplicate] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["records_repointed"], 1);
+    assert_eq!(res.body["records_linked"], 1);
+    assert_eq!(res.body["institutions_deactivated"], 1);
+
+    let institution_of = |id: Uuid| {
+        sqlx::query_scalar::<_, Option<Uuid>>(
+            "SELECT institution_id FROM education_records WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(app.db())
+    };
+    let on_duplicate: Uuid = on_duplicate["id"].as_str().unwrap().parse().unwrap();
+    let on_unrelated: Uuid = on_unrelated["id"].as_str().unwrap().parse().unwrap();
+    assert_eq!(institution_of(on_duplicate).await.unwrap(), Some(canonical));
+    assert_eq!(institution_of(free_text).await.unwrap(), Some(canonical));
+    assert_eq!(institution_of(on_unrelated).await.unwrap(), Some(unrelated));
+
+    let active: Vec<(Uuid, bool)> = sqlx::query_as(
+        "SELECT id, is_active FROM institutions WHERE id = ANY($1) ORDER BY created_at",
+    )
+    .bind(vec![canonical, duplicate, older_duplicate])
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(
+        active,
+        [
+            (canonical, true),
+            (duplicate, false),
+            (older_duplicate, false)
+        ]
+    );
+
+    // Names of merged entries, however old the merge, now resolve to the canonical one
+    let record = add_education(
+        &app,
+        &seeker,
+        json!({ "institution_name": "UNIV. DE MAGALLANES" }),
+    )
+    .await;
+    assert_eq!(record["institution_id"], json!(canonical));
+    assert_eq!(
+        search(&app, "magallanes").await,
+        ["Universidad de Magallanes"]
+    );
+
+    let actions: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'merge_institutions'",
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(actions, 2);
+}
+
+#[sqlx::test]
+async fn test_institution_autocomplete_ranking(db: PgPool) {
+    let app = TestApp::new(db).await;
+    create_institution(&app, "Universidad Andina del Sur").await;
+    create_institution(&app, "Andina Formación Técnica").await;
+    create_institution(&app, "Instituto Los Andes").await;
+    create_institution(&app, "Centro Pacífico").await;
+    let inactive = create_institution(&app, "Andina Antigua").await;
+    sqlx::query("UPDATE institutions SET is_active = false WHERE id = $1")
+        .bind(inactive)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    // Prefix matches first, then names with a matching word, then names
+    // with a similar one
+    assert_eq!(
+        search(&app, "andina").await,
+        [
+            "Andina Formación Técnica",
+            "Universidad Andina del Sur",
+            "Instituto Los Andes"
+        ]
+    );
+    // Accents, case and small typos are forgiven
+    assert_eq!(
+        search(&app, "FORMACION").await,
+        ["Andina Formación Técnica"]
+    );
+    let typo = search(&app, "andna").await;
+    assert!(
+        typo.contains(&"Andina Formación Técnica".to_string()),
+        "{:?}",
+        typo
+    );
+    assert!(
+        typo.contains(&"Universidad Andina del Sur".to_string()),
+        "{:?}",
+        typo
+    );
+    assert!(search(&app, "zzzz").await.is_empty());
+
+    for i in 0..20 {
+        create_institution(&app, &format!("Sede Regional {:02}", i)).await;
+    }
+    let results = search(&app, "sede").await;
+    assert_eq!(results.len(), 15);
+    assert_eq!(results[0], "Sede Regional 00");
+
+    // Without a search everything active is listed
+    let res = app.get("/api/reference/institutions", None).await;
+    assert_eq!(res.body["total"], 24);
+}
+
+#[sqlx::test]
+async fn test_unmatched_institution_names_report(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+    create_institution(&app, "Universidad de Valparaíso").await;
+
+    for name in [
+        "Universidad de Valparaiso",
+        "universidad de valparaiso",
+        "Universidad de Valparaiso ",
+        "Liceo Industrial de Talca",
+    ] {
+        add_education(&app, &seeker, json!({ "institution_name": name })).await;
+    }
+    // Linked records are not part of the report
+    add_education(
+        &app,
+        &seeker,
+        json!({ "institution_name": "Universidad de Valparaíso" }),
+    )
+    .await;
+
+    let res = app
+        .get("/api/admin/reference/institutions/unmatched", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    let res = app
+        .get("/api/admin/reference/institutions/unmatched", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let report = res.body.as_array().unwrap();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0]["name"], "Universidad de Valparaiso");
+    assert_eq!(report[0]["record_count"], 3);
+    assert_eq!(report[0]["spelling_count"], 2);
+    assert_eq!(
+        report[0]["suggested_institution_name"],
+        "Universidad de Valparaíso"
+    );
+    assert_eq!(report[1]["name"], "Liceo Industrial de Talca");
+    assert!(report[1]["suggested_institution_id"].is_null());
+
+    let res = app
+        .get(
+            "/api/admin/reference/institutions/unmatched?limit=1",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+}
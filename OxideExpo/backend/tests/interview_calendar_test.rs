@@ -0,0 +1,334 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Reads an iCalendar feed, signed in as `user` when given
+async fn feed(app: &TestApp, uri: &str, user: Option<&TestUser>) -> (StatusCode, String) {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(user) = user {
+        builder = builder.header("authorization", format!("Bearer {}", user.token));
+    }
+    let response = app
+        .router
+        .clone()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/calendar; charset=utf-8"
+        );
+    }
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+/// Content lines of a feed, unfolded
+fn unfold(ics: &str) -> Vec<String> {
+    ics.replace("\r\n ", "")
+        .split("\r\n")
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn property<'a>(lines: &'a [String], name: &str) -> Vec<&'a str> {
+    lines
+        .iter()
+        .filter_map(|line| line.strip_prefix(name))
+        .collect()
+}
+
+/// Application of a new seeker with an interview on `date`
+async fn schedule_interview(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    seeker: &TestUser,
+    date: &str,
+) -> Uuid {
+    let app_id = app.create_application(job_id, seeker).await;
+    let uri = format!("/api/me/jobs/{}/applications/{}", job_id, app_id);
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "status": "under_review" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({
+                "status": "interview_scheduled",
+                "interview_date": date,
+                "interview_location": "Av. Providencia 1234, of. 5; Santiago",
+                "interview_notes": "Preguntar por\nturnos de noche"
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    app_id
+}
+
+#[sqlx::test]
+async fn test_company_feed_structure(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    // Summer (UTC-3) and winter (UTC-4) interviews, both at 10:00 in Santiago
+    let summer = schedule_interview(&app, &company, job_id, &seeker, "2099-01-15T13:00:00Z").await;
+    let other = app.create_job_seeker().await;
+    schedule_interview(&app, &company, job_id, &other, "2099-07-15T14:00:00Z").await;
+    // Past interviews are left out
+    let past = app.create_job_seeker().await;
+    schedule_interview(&app, &company, job_id, &past, "2020-03-01T13:00:00Z").await;
+
+    let (status, ics) = feed(&app, "/api/me/company/interviews.ics", Some(&company.owner)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+    let lines = unfold(&ics);
+    assert_eq!(lines[0], "BEGIN:VCALENDAR");
+    assert!(lines.contains(&"VERSION:2.0".to_string()));
+    assert!(lines.contains(&"BEGIN:VTIMEZONE".to_string()));
+    assert!(lines.contains(&"TZID:America/Santiago".to_string()));
+    assert_eq!(property(&lines, "BEGIN:VEVENT").len(), 2);
+    assert_eq!(
+        property(&lines, "DTSTART;TZID=America/Santiago:"),
+        ["20990115T100000", "20990715T100000"]
+    );
+    assert_eq!(
+        property(&lines, "DTEND;TZID=America/Santiago:"),
+        ["20990115T110000", "20990715T110000"]
+    );
+    assert_eq!(
+        property(&lines, "UID:")[0],
+        format!("interview-{}@empleosinclusivos.cl", summer)
+    );
+    // First name and initial only, with commas and semicolons escaped
+    assert_eq!(
+        property(&lines, "SUMMARY:")[0],
+        "Entrevista: Test U. - Test job"
+    );
+    assert_eq!(
+        property(&lines, "LOCATION:")[0],
+        "Av. Providencia 1234\\, of. 5\\; Santiago"
+    );
+    assert_eq!(
+        property(&lines, "DESCRIPTION:")[0],
+        "Preguntar por\\nturnos de noche"
+    );
+}
+
+#[sqlx::test]
+async fn test_uid_stays_the_same_after_rescheduling(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let app_id = schedule_interview(&app, &company, job_id, &seeker, "2099-01-15T13:00:00Z").await;
+
+    let (_, ics) = feed(&app, "/api/me/company/interviews.ics", Some(&company.owner)).await;
+    let before = unfold(&ics);
+
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}/interview", job_id, app_id),
+            Some(&company.owner),
+            json!({
+                "interview_date": "2099-01-16T15:30:00Z",
+                "interview_location": "https://meet.example.cl/abc"
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.body["interview_location"],
+        "https://meet.example.cl/abc"
+    );
+    assert_eq!(
+        res.body["interview_notes"],
+        "Preguntar por\nturnos de noche"
+    );
+
+    let (_, ics) = feed(&app, "/api/me/company/interviews.ics", Some(&company.owner)).await;
+    let after = unfold(&ics);
+    assert_eq!(property(&after, "UID:"), property(&before, "UID:"));
+    assert_eq!(
+        property(&after, "DTSTART;TZID=America/Santiago:"),
+        ["20990116T123000"]
+    );
+    assert_eq!(
+        property(&after, "LOCATION:"),
+        ["https://meet.example.cl/abc"]
+    );
+
+    // Only scheduled interviews can be moved
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", job_id, app_id),
+            Some(&company.owner),
+            json!({ "status": "rejected" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}/interview", job_id, app_id),
+            Some(&company.owner),
+            json!({ "interview_date": "2099-01-17T15:30:00Z" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Rejected candidates' interviews leave the feed
+    let (_, ics) = feed(&app, "/api/me/company/interviews.ics", Some(&company.owner)).await;
+    assert!(!ics.contains("BEGIN:VEVENT"));
+}
+
+#[sqlx::test]
+async fn test_calendar_token_can_be_revoked(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    schedule_interview(&app, &company, job_id, &seeker, "2099-01-15T13:00:00Z").await;
+
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .get("/api/me/company/calendar-token", Some(&member))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let token = res.body["token"].as_str().unwrap().to_string();
+    let feed_url = res.body["feed_url"].as_str().unwrap();
+    assert!(feed_url.ends_with(&format!("/api/me/company/interviews.ics?token={}", token)));
+
+    // The token is only shown when created
+    let res = app
+        .get("/api/me/company/calendar-token", Some(&member))
+        .await;
+    assert_eq!(res.body["token"], json!(null));
+    assert_eq!(res.body["token_prefix"], &token[..12]);
+
+    // Calendar apps read the feed with the token alone
+    let uri = format!("/api/me/company/interviews.ics?token={}", token);
+    let (status, ics) = feed(&app, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ics.contains("BEGIN:VEVENT"));
+
+    let (status, _) = feed(&app, "/api/me/company/interviews.ics?token=cal_nope", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = feed(&app, "/api/me/company/interviews.ics", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let res = app
+        .delete("/api/me/company/calendar-token", Some(&member))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let (status, _) = feed(&app, &uri, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let res = app
+        .delete("/api/me/company/calendar-token", Some(&member))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // A new token works; it stops working when the member leaves
+    let res = app
+        .get("/api/me/company/calendar-token", Some(&member))
+        .await;
+    let new_token = res.body["token"].as_str().unwrap().to_string();
+    assert_ne!(new_token, token);
+    let uri = format!("/api/me/company/interviews.ics?token={}", new_token);
+    assert_eq!(feed(&app, &uri, None).await.0, StatusCode::OK);
+
+    sqlx::query("UPDATE company_members SET is_active = false WHERE user_id = $1")
+        .bind(member.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    assert_eq!(feed(&app, &uri, None).await.0, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_calendar_token_needs_an_active_account(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let res = app
+        .get("/api/me/company/calendar-token", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let uri = format!(
+        "/api/me/company/interviews.ics?token={}",
+        res.body["token"].as_str().unwrap()
+    );
+    assert_eq!(feed(&app, &uri, None).await.0, StatusCode::OK);
+
+    let set_status = |status: &'static str| {
+        sqlx::query("UPDATE users SET account_status = $2::account_status WHERE id = $1")
+            .bind(company.owner.id)
+            .bind(status)
+            .execute(app.db())
+    };
+    set_status("suspended").await.unwrap();
+    assert_eq!(feed(&app, &uri, None).await.0, StatusCode::UNAUTHORIZED);
+
+    set_status("active").await.unwrap();
+    assert_eq!(feed(&app, &uri, None).await.0, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_seeker_feed_has_own_interviews(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let app_id = schedule_interview(&app, &company, job_id, &seeker, "2099-01-15T13:00:00Z").await;
+    schedule_interview(&app, &company, job_id, &other, "2099-01-15T15:00:00Z").await;
+
+    let (status, ics) = feed(&app, "/api/me/interviews.ics", Some(&seeker)).await;
+    assert_eq!(status, StatusCode::OK);
+    let lines = unfold(&ics);
+    assert_eq!(
+        property(&lines, "UID:"),
+        [format!("interview-{}@empleosinclusivos.cl", app_id)]
+    );
+    assert!(property(&lines, "SUMMARY:")[0].starts_with("Entrevista: Test job - "));
+    assert_eq!(
+        property(&lines, "DTSTART;TZID=America/Santiago:"),
+        ["20990115T100000"]
+    );
+    // The company's notes stay with the company
+    assert!(property(&lines, "DESCRIPTION:").is_empty());
+
+    let (status, _) = feed(&app, "/api/me/interviews.ics", Some(&company.owner)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Invites the seeker to the job, returning the invitation ID
+async fn invite(app: &TestApp, company: &TestCompany, job_id: Uuid, seeker: &TestUser) -> Uuid {
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn set_invitation(app: &TestApp, id: Uuid, status: &str, expires_in_days: i32) {
+    sqlx::query(
+        "UPDATE job_invitations
+         SET status = $2::invitation_status, expires_at = NOW() + make_interval(days => $3)
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(expires_in_days)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+fn ids(body: &serde_json::Value) -> Vec<String> {
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|invitation| invitation["invitation"]["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_my_invitations_expiring_soonest_first(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let mut invitations = Vec::new();
+    for (status, days) in [
+        ("pending", 20),
+        ("accepted", 3),
+        ("pending", 9),
+        ("declined", 1),
+    ] {
+        let job_id = app.create_active_job(&company).await;
+        let id = invite(&app, &company, job_id, &seeker).await;
+        set_invitation(&app, id, status, days).await;
+        invitations.push(id.to_string());
+    }
+
+    let res = app.get("/api/me/invitations", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        ids(&res.body),
+        [
+            invitations[3].as_str(),
+            invitations[1].as_str(),
+            invitations[2].as_str(),
+            invitations[0].as_str()
+        ]
+    );
+    assert_eq!(res.body["total"], 4);
+    assert_eq!(res.body["data"][0]["job"]["title"], "Test job");
+
+    let res = app
+        .get("/api/me/invitations?sort=created_at", Some(&seeker))
+        .await;
+    assert_eq!(
+        ids(&res.body),
+        [
+            invitations[3].as_str(),
+            invitations[2].as_str(),
+            invitations[1].as_str(),
+            invitations[0].as_str()
+        ]
+    );
+
+    let res = app
+        .get("/api/me/invitations?status=pending&limit=1", Some(&seeker))
+        .await;
+    assert_eq!(ids(&res.body), [invitations[2].as_str()]);
+    assert_eq!(res.body["total"], 2);
+    assert_eq!(res.body["limit"], 1);
+}
+
+#[sqlx::test]
+async fn test_invitation_counts(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let mut seekers = Vec::new();
+    for status in ["pending", "pending", "accepted", "declined", "expired"] {
+        let seeker = app.create_job_seeker().await;
+        let id = invite(&app, &company, job_id, &seeker).await;
+        set_invitation(&app, id, status, 5).await;
+        seekers.push((seeker, id));
+    }
+    let (applicant, applied_invitation) = &seekers[2];
+    let application_id = app.create_application(job_id, applicant).await;
+    sqlx::query("UPDATE job_invitations SET application_id = $2 WHERE id = $1")
+        .bind(applied_invitation)
+        .bind(application_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let url = format!("/api/me/jobs/{}/invitations", job_id);
+    let res = app
+        .get(&format!("{}?status=pending", url), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["total"], 2);
+    assert_eq!(
+        res.body["counts"],
+        json!({
+            "pending": 2,
+            "viewed": 0,
+            "accepted": 1,
+            "applied": 0,
+            "declined": 1,
+            "expired": 1,
+            "job_no_longer_available": 0,
+        })
+    );
+    let name: String =
+        sqlx::query_scalar("SELECT first_name || ' ' || last_name FROM users WHERE id = $1")
+            .bind(applicant.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    let res = app
+        .get(
+            &format!("{}?has_application=true", url),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(ids(&res.body), [applied_invitation.to_string()]);
+    assert_eq!(res.body["data"][0]["seeker_name"], name.as_str());
+    assert_eq!(res.body["data"][0]["job_title"], "Test job");
+    assert_eq!(res.body["counts"]["pending"], 0);
+    assert_eq!(res.body["counts"]["accepted"], 1);
+
+    let res = app
+        .get(
+            &format!("{}?has_application=false", url),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.body["total"], 4);
+
+    // The seeker's own counts cover only their invitations
+    let (seeker, _) = &seekers[0];
+    let res = app.get("/api/me/invitations", Some(seeker)).await;
+    assert_eq!(res.body["counts"]["pending"], 1);
+    assert_eq!(res.body["total"], 1);
+}
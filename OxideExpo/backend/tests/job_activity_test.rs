@@ -0,0 +1,263 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, SecondsFormat, Utc};
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn add_member(app: &TestApp, company: &TestCompany) -> TestUser {
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    member
+}
+
+async fn log_status(app: &TestApp, application_id: Uuid, status: &str, by: Uuid, hours_ago: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO application_status_history (application_id, new_status, changed_by, created_at)
+        VALUES ($1, $2::application_status, $3, NOW() - make_interval(hours => $4::int))
+        "#,
+    )
+    .bind(application_id)
+    .bind(status)
+    .bind(by)
+    .bind(hours_ago as i32)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn activity(app: &TestApp, user: &TestUser, job_id: Uuid, query: &str) -> Value {
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/activity{}", job_id, query),
+            Some(user),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+fn actions(feed: &Value) -> Vec<&str> {
+    feed["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect()
+}
+
+async fn add_note(app: &TestApp, user: &TestUser, job_id: Uuid, application_id: Uuid) {
+    let res = app
+        .post(
+            &format!(
+                "/api/me/jobs/{}/applications/{}/notes",
+                job_id, application_id
+            ),
+            Some(user),
+            json!({ "note_text": "Llamar el lunes" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_feed_merges_company_actions_newest_first(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let member = add_member(&app, &company).await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+    let owner = &company.owner;
+
+    log_status(&app, application_id, "shortlisted", owner.id, 5).await;
+    sqlx::query(
+        r#"
+        INSERT INTO application_notes (application_id, created_by, note_text, created_at)
+        VALUES ($1, $2, 'Buen perfil para bodega', NOW() - INTERVAL '4 hours')
+        "#,
+    )
+    .bind(application_id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    log_status(&app, application_id, "interview_scheduled", owner.id, 3).await;
+
+    let invited = app.create_job_seeker().await;
+    sqlx::query(
+        r#"
+        INSERT INTO job_invitations (
+            job_id, job_seeker_id, invited_by, company_id, job_snapshot, expires_at, created_at
+        )
+        VALUES (
+            $1, $2, $3, $4, job_invitation_snapshot($1),
+            NOW() + INTERVAL '7 days', NOW() - INTERVAL '2 hours'
+        )
+        "#,
+    )
+    .bind(job_id)
+    .bind(invited.id)
+    .bind(owner.id)
+    .bind(company.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query(
+        r#"
+        INSERT INTO bulk_operations (
+            job_id, requested_by, target_status, application_ids, total, created_at
+        )
+        VALUES ($1, $2, 'rejected', ARRAY[$3]::uuid[], 1, NOW() - INTERVAL '1 hour')
+        "#,
+    )
+    .bind(job_id)
+    .bind(member.id)
+    .bind(application_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // The candidate's own withdrawal and changes they made are not company activity
+    log_status(&app, application_id, "withdrawn", owner.id, 0).await;
+    log_status(&app, application_id, "under_review", seeker.id, 0).await;
+
+    // Nor is what happens on other jobs
+    let other_job = app.create_active_job(&company).await;
+    let other_application = app.create_application(other_job, &seeker).await;
+    add_note(&app, owner, other_job, other_application).await;
+
+    let feed = activity(&app, &member, job_id, "").await;
+    assert_eq!(
+        actions(&feed),
+        vec![
+            "bulk_update",
+            "invitation_sent",
+            "interview_scheduled",
+            "note_added",
+            "status_changed",
+        ]
+    );
+    assert_eq!(feed["total"], 5);
+
+    let entries = feed["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["actor_id"], json!(member.id));
+    assert_eq!(entries[0]["actor"], "Test User");
+    assert_eq!(entries[0]["target_applicant"], json!(null));
+    assert_eq!(entries[0]["summary"], "1 → rejected");
+    assert_eq!(entries[1]["target_applicant_id"], json!(invited.id));
+    assert_eq!(entries[3]["summary"], "Buen perfil para bodega");
+    assert_eq!(entries[4]["target_applicant_id"], json!(seeker.id));
+    assert_eq!(entries[4]["target_applicant"], "Test User");
+    assert_eq!(entries[4]["summary"], "shortlisted");
+
+    let page = activity(&app, owner, job_id, "?limit=2&offset=1").await;
+    assert_eq!(
+        actions(&page),
+        vec!["invitation_sent", "interview_scheduled"]
+    );
+    assert_eq!(page["total"], 5);
+
+    // Only members of the owning company can read it
+    let outsider = app.create_company_with_owner().await;
+    let uri = format!("/api/me/jobs/{}/activity", job_id);
+    let res = app.get(&uri, Some(&outsider.owner)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app.get(&uri, Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_since_returns_only_newer_entries(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+
+    log_status(&app, application_id, "under_review", company.owner.id, 2).await;
+    add_note(&app, &company.owner, job_id, application_id).await;
+
+    let since = (Utc::now() - Duration::hours(1)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    let feed = activity(&app, &company.owner, job_id, &format!("?since={}", since)).await;
+    assert_eq!(actions(&feed), vec!["note_added"]);
+    assert_eq!(feed["total"], 1);
+
+    let newest = feed["entries"][0]["occurred_at"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let feed = activity(
+        &app,
+        &company.owner,
+        job_id,
+        &format!("?since={}", newest.replace('+', "%2B")),
+    )
+    .await;
+    assert!(actions(&feed).is_empty());
+    assert_eq!(feed["total"], 0);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/activity?since=yesterday", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[sqlx::test]
+async fn test_unseen_count_is_tracked_per_member(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let member = add_member(&app, &company).await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let application_id = app.create_application(job_id, &seeker).await;
+    let owner = &company.owner;
+    let seen_uri = format!("/api/me/jobs/{}/activity/seen", job_id);
+
+    add_note(&app, owner, job_id, application_id).await;
+
+    // Their own actions are never unseen
+    assert_eq!(activity(&app, owner, job_id, "").await["unseen_count"], 0);
+    assert_eq!(activity(&app, &member, job_id, "").await["unseen_count"], 1);
+
+    let res = app.post(&seen_uri, Some(&member), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body["last_seen_activity_at"].is_string());
+    assert_eq!(activity(&app, &member, job_id, "").await["unseen_count"], 0);
+
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", job_id, application_id),
+            Some(owner),
+            json!({ "status": "under_review" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    add_note(&app, owner, job_id, application_id).await;
+
+    let feed = activity(&app, &member, job_id, "").await;
+    assert_eq!(feed["unseen_count"], 2);
+    assert_eq!(feed["total"], 3);
+
+    // Another member marking the feed seen doesn't clear it for this one
+    add_note(&app, &member, job_id, application_id).await;
+    let res = app.post(&seen_uri, Some(owner), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(activity(&app, owner, job_id, "").await["unseen_count"], 0);
+    assert_eq!(activity(&app, &member, job_id, "").await["unseen_count"], 2);
+}
@@ -0,0 +1,284 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::{wait_for_messages, TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::alerts::{self, AlertRun};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Active job published `minutes_ago`
+async fn publish_job(app: &TestApp, company: &TestCompany, title: &str, minutes_ago: i32) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query(
+        "UPDATE jobs SET title = $2, published_at = NOW() - make_interval(mins => $3) WHERE id = $1",
+    )
+    .bind(job_id)
+    .bind(title)
+    .bind(minutes_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+    job_id
+}
+
+/// Requires skills the seekers don't have, so it scores below the default
+/// minimum
+async fn require_skills(app: &TestApp, job_id: Uuid) {
+    sqlx::query(
+        r#"
+        INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency)
+        SELECT $1, id, 3 FROM skills ORDER BY name LIMIT 3
+        "#,
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn set_alerts(
+    app: &TestApp,
+    seeker: &TestUser,
+    frequency: &str,
+    last_sent_hours_ago: Option<i32>,
+) {
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_preferences
+        SET alert_frequency = $2::alert_frequency,
+            last_alert_sent_at = NOW() - make_interval(hours => $3)
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(frequency)
+    .bind(last_sent_hours_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn last_sent(app: &TestApp, seeker: &TestUser) -> Option<DateTime<Utc>> {
+    sqlx::query_scalar("SELECT last_alert_sent_at FROM job_seeker_preferences WHERE user_id = $1")
+        .bind(seeker.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_alerts_follow_frequency_and_match_score(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    publish_job(&app, &company, "Bodeguero", 120).await;
+    let poor_match = publish_job(&app, &company, "Contador auditor", 90).await;
+    require_skills(&app, poor_match).await;
+    // Older than a week, so before any first alert
+    publish_job(&app, &company, "Recepcionista", 60 * 24 * 8).await;
+
+    let daily = app.create_job_seeker().await;
+    let instant = app.create_job_seeker().await;
+    let weekly = app.create_job_seeker().await;
+    let opted_out = app.create_job_seeker().await;
+    let applied = app.create_job_seeker().await;
+    set_alerts(&app, &daily, "daily", None).await;
+    // Alerted an hour ago, after the jobs above were published
+    set_alerts(&app, &instant, "instant", Some(1)).await;
+    set_alerts(&app, &weekly, "weekly", Some(48)).await;
+    sqlx::query("UPDATE job_seeker_preferences SET email_job_alerts = false WHERE user_id = $1")
+        .bind(opted_out.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let instant_job = publish_job(&app, &company, "Cajero", 30).await;
+    // Already applied to the jobs that match
+    let matching: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM jobs WHERE title IN ('Bodeguero', 'Cajero')")
+            .fetch_all(app.db())
+            .await
+            .unwrap();
+    for job_id in matching {
+        app.create_application(job_id, &applied).await;
+    }
+
+    let run = alerts::run(&app.state, Utc::now()).await.unwrap();
+    assert_eq!(
+        run,
+        AlertRun {
+            seekers: 3,
+            sent: 2,
+            failed: 0
+        }
+    );
+
+    let messages = wait_for_messages(&inbox, 2).await;
+    assert_eq!(messages.len(), 2);
+    let daily_alert = messages.iter().find(|m| m.contains(&daily.email)).unwrap();
+    assert!(daily_alert.contains("Bodeguero"));
+    assert!(daily_alert.contains("Cajero"));
+    assert!(!daily_alert.contains("Contador auditor"));
+    assert!(!daily_alert.contains("Recepcionista"));
+    assert!(daily_alert.contains("2 ofertas nuevas"));
+    let instant_alert = messages
+        .iter()
+        .find(|m| m.contains(&instant.email))
+        .unwrap();
+    assert!(instant_alert.contains(&instant_job.to_string()));
+    assert!(!instant_alert.contains("Bodeguero"));
+    assert!(!messages.iter().any(|m| m.contains(&weekly.email)));
+    assert!(!messages.iter().any(|m| m.contains(&opted_out.email)));
+
+    // Nothing matched the applicant, which still counts as handled
+    assert!(last_sent(&app, &applied).await.is_some());
+    assert!(last_sent(&app, &opted_out).await.is_none());
+    let weekly_before = last_sent(&app, &weekly).await;
+
+    // Daily and weekly seekers aren't due again yet; the instant one is, with
+    // nothing new
+    let run = alerts::run(&app.state, Utc::now()).await.unwrap();
+    assert_eq!(run.sent, 0);
+    assert_eq!(run.seekers, 1);
+    assert_eq!(last_sent(&app, &weekly).await, weekly_before);
+
+    // A day later the daily seeker gets only what was published since
+    publish_job(&app, &company, "Guardia", -60 * 12).await;
+    let run = alerts::run(&app.state, Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(run.sent, 3);
+    let messages = wait_for_messages(&inbox, 5).await;
+    assert_eq!(messages.len(), 5);
+    let daily_alert = messages[2..]
+        .iter()
+        .find(|m| m.contains(&daily.email))
+        .unwrap();
+    assert!(daily_alert.contains("Guardia"));
+    assert!(!daily_alert.contains("Bodeguero"));
+}
+
+#[sqlx::test]
+async fn test_a_failing_alert_does_not_stop_the_run(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    publish_job(&app, &company, "Bodeguero", 60).await;
+
+    let broken = app.create_job_seeker().await;
+    let seeker = app.create_job_seeker().await;
+    // An address the mailer refuses
+    sqlx::query("UPDATE users SET email = 'not an address' WHERE id = $1")
+        .bind(broken.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let updated_at = || {
+        sqlx::query_scalar::<_, DateTime<Utc>>(
+            "SELECT updated_at FROM job_seeker_preferences WHERE user_id = $1",
+        )
+        .bind(seeker.id)
+        .fetch_one(app.db())
+    };
+    let updated_before = updated_at().await.unwrap();
+
+    let run = alerts::run(&app.state, Utc::now()).await.unwrap();
+    assert_eq!(
+        run,
+        AlertRun {
+            seekers: 2,
+            sent: 1,
+            failed: 1
+        }
+    );
+    let messages = wait_for_messages(&inbox, 1).await;
+    assert!(messages[0].contains(&seeker.email));
+    assert!(last_sent(&app, &seeker).await.is_some());
+    // Not a change the seeker made to their preferences
+    assert_eq!(updated_at().await.unwrap(), updated_before);
+    // Tried again on the next run
+    assert!(last_sent(&app, &broken).await.is_none());
+
+    // The batch size bounds each run, longest waiting first
+    let mut state = app.state.clone();
+    let mut config = (*state.config).clone();
+    config.job_alert_batch_size = 1;
+    state.config = std::sync::Arc::new(config);
+    let run = alerts::run(&state, Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(run.seekers, 1);
+    assert_eq!(run.failed, 1);
+}
+
+#[sqlx::test]
+async fn test_alerts_page_through_every_new_job_using_cached_scores(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    set_alerts(&app, &seeker, "daily", Some(48)).await;
+
+    // The oldest new job matches; more than a page of newer ones don't
+    publish_job(&app, &company, "Bodeguero", 600).await;
+    let cached = publish_job(&app, &company, "Auditor", 500).await;
+    require_skills(&app, cached).await;
+    sqlx::query(
+        r#"
+        WITH copies AS (
+            INSERT INTO jobs (
+                company_id, posted_by, title, description, job_type, work_modality,
+                application_deadline, status, approved_at, approved_by, published_at
+            )
+            SELECT
+                company_id, posted_by, 'Contador ' || n, description, job_type, work_modality,
+                application_deadline, status, approved_at, approved_by,
+                published_at + make_interval(mins => n)
+            FROM jobs, generate_series(1, 60) n
+            WHERE id = $1
+            RETURNING id
+        )
+        INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency)
+        SELECT copies.id, rs.skill_id, rs.minimum_proficiency
+        FROM copies, job_required_skills rs
+        WHERE rs.job_id = $1
+        "#,
+    )
+    .bind(cached)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // A fresh cached score is used as is; a stale one is computed again
+    let stale: Uuid = sqlx::query_scalar("SELECT id FROM jobs WHERE title = 'Contador 7'")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO job_match_scores (job_id, user_id, total_score, is_stale)
+        VALUES ($1, $3, 95, false), ($2, $3, 95, true)
+        "#,
+    )
+    .bind(cached)
+    .bind(stale)
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let run = alerts::run(&app.state, Utc::now()).await.unwrap();
+    assert_eq!(run.sent, 1);
+    let messages = wait_for_messages(&inbox, 1).await;
+    assert!(messages[0].contains("Bodeguero"));
+    assert!(messages[0].contains("Auditor"));
+    assert!(!messages[0].contains("Contador"));
+
+    let (score, is_stale): (i32, bool) = sqlx::query_as(
+        "SELECT total_score, is_stale FROM job_match_scores WHERE job_id = $1 AND user_id = $2",
+    )
+    .bind(stale)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(score < 95);
+    assert!(!is_stale);
+}
@@ -0,0 +1,402 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn rules_uri(job_id: Uuid) -> String {
+    format!("/api/me/jobs/{}/automation-rules", job_id)
+}
+
+async fn create_rule(app: &TestApp, company: &TestCompany, job_id: Uuid, rule: Value) -> Value {
+    let res = app
+        .post(&rules_uri(job_id), Some(&company.owner), rule)
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+/// Caches a match score so rules see it without running the matcher
+async fn seed_match_score(app: &TestApp, job_id: Uuid, seeker: &TestUser, score: i32) {
+    sqlx::query("INSERT INTO job_match_scores (job_id, user_id, total_score) VALUES ($1, $2, $3)")
+        .bind(job_id)
+        .bind(seeker.id)
+        .bind(score)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+async fn apply(app: &TestApp, seeker: &TestUser, job_id: Uuid, resume_url: Option<&str>) -> Value {
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(seeker),
+            json!({ "job_id": job_id, "resume_url": resume_url }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+async fn application_status(app: &TestApp, application_id: &Value) -> String {
+    sqlx::query_scalar("SELECT status::text FROM job_applications WHERE id = $1::uuid")
+        .bind(application_id.as_str().unwrap())
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Notes on the application; rules run in one transaction, so by text rather than time
+async fn rule_notes(app: &TestApp, application_id: &Value) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT note_text FROM application_notes WHERE application_id = $1::uuid ORDER BY note_text",
+    )
+    .bind(application_id.as_str().unwrap())
+    .fetch_all(app.db())
+    .await
+    .unwrap()
+}
+
+fn reject_low_matches() -> Value {
+    json!({
+        "name": "Descartar match bajo",
+        "condition": { "match_score": { "op": "lt", "value": 30 } },
+        "action": "set_status",
+        "target_status": "rejected"
+    })
+}
+
+#[sqlx::test]
+async fn test_rule_management(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other_company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let rule = create_rule(&app, &company, job_id, reject_low_matches()).await;
+    assert_eq!(rule["trigger"], "on_application_submitted");
+    assert_eq!(rule["is_enabled"], true);
+    assert_eq!(rule["condition"]["match_score"]["value"], 30);
+    let rule_id = rule["id"].as_str().unwrap().to_string();
+
+    // Plain members and other companies cannot see or change the rules
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app.get(&rules_uri(job_id), Some(&member)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app
+        .get(&rules_uri(job_id), Some(&other_company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Conditions outside the schema and actions with the wrong parameters are refused
+    for invalid in [
+        json!({ "name": "x", "condition": { "salary": { "op": "gt", "value": 1 } }, "action": "set_status", "target_status": "rejected" }),
+        json!({ "name": "x", "condition": { "match_score": { "op": "gt", "value": 120 } }, "action": "set_status", "target_status": "rejected" }),
+        json!({ "name": "x", "action": "set_status" }),
+        json!({ "name": "x", "action": "set_status", "target_status": "rejected", "tag_id": Uuid::new_v4() }),
+        json!({ "name": "x", "action": "set_status", "target_status": "hired" }),
+    ] {
+        let res = app
+            .post(&rules_uri(job_id), Some(&company.owner), invalid.clone())
+            .await;
+        assert!(res.status.is_client_error(), "{} accepted", invalid);
+    }
+
+    // Tags and members have to be the company's own
+    let res = app
+        .post(
+            "/api/me/company/tags",
+            Some(&other_company.owner),
+            json!({ "name": "Ajeno" }),
+        )
+        .await;
+    let foreign_tag = res.body["id"].clone();
+    let res = app
+        .post(
+            &rules_uri(job_id),
+            Some(&company.owner),
+            json!({ "name": "x", "action": "add_tag", "tag_id": foreign_tag }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = app
+        .post(
+            &rules_uri(job_id),
+            Some(&company.owner),
+            json!({ "name": "x", "action": "notify_member", "notify_user_id": other_company.owner.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Updates replace the rule
+    let res = app
+        .put(
+            &format!("{}/{}", rules_uri(job_id), rule_id),
+            Some(&company.owner),
+            json!({
+                "name": "Avisar a la dueña",
+                "action": "notify_member",
+                "notify_user_id": company.owner.id,
+                "is_enabled": false
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["action"], "notify_member");
+    assert_eq!(res.body["target_status"], Value::Null);
+    assert_eq!(
+        res.body["condition"],
+        json!({ "match_score": null, "has_cv": null, "years_experience": null })
+    );
+    assert_eq!(res.body["is_enabled"], false);
+
+    // At most five rules per job
+    for _ in 0..4 {
+        create_rule(&app, &company, job_id, reject_low_matches()).await;
+    }
+    let res = app
+        .post(
+            &rules_uri(job_id),
+            Some(&company.owner),
+            reject_low_matches(),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .delete(
+            &format!("{}/{}", rules_uri(job_id), rule_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app.get(&rules_uri(job_id), Some(&company.owner)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 4);
+}
+
+#[sqlx::test]
+async fn test_rules_act_on_submission_within_state_machine(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = app
+        .post(
+            "/api/me/company/tags",
+            Some(&company.owner),
+            json!({ "name": "Con CV" }),
+        )
+        .await;
+    let tag_id = res.body["id"].clone();
+
+    create_rule(&app, &company, job_id, reject_low_matches()).await;
+    create_rule(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "name": "Avanzar buenos matches",
+            "condition": { "match_score": { "op": "gte", "value": 80 }, "has_cv": true },
+            "action": "set_status",
+            "target_status": "under_review"
+        }),
+    )
+    .await;
+    create_rule(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "name": "Etiquetar CV",
+            "condition": { "has_cv": true },
+            "action": "add_tag",
+            "tag_id": tag_id
+        }),
+    )
+    .await;
+    // Runs after the rejection rule, which leaves nothing to move for low matches
+    create_rule(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "name": "Revisar sin experiencia",
+            "condition": { "years_experience": { "op": "eq", "value": 0 } },
+            "action": "set_status",
+            "target_status": "under_review"
+        }),
+    )
+    .await;
+    create_rule(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "name": "Regla apagada",
+            "action": "notify_member",
+            "notify_user_id": company.owner.id,
+            "is_enabled": false
+        }),
+    )
+    .await;
+
+    let low = app.create_job_seeker().await;
+    seed_match_score(&app, job_id, &low, 20).await;
+    let application = apply(&app, &low, job_id, None).await;
+    assert_eq!(application["status"], "rejected");
+    assert_eq!(
+        application_status(&app, &application["id"]).await,
+        "rejected"
+    );
+    let notes = rule_notes(&app, &application["id"]).await;
+    assert_eq!(notes.len(), 1, "{:?}", notes);
+    assert!(notes[0].starts_with("Regla automática: Descartar match bajo"));
+    assert!(
+        notes[0].ends_with("Estado cambiado de Recibida a Rechazada"),
+        "{}",
+        notes[0]
+    );
+
+    let strong = app.create_job_seeker().await;
+    seed_match_score(&app, job_id, &strong, 85).await;
+    let application = apply(&app, &strong, job_id, Some("https://cv.example.cl/cv.pdf")).await;
+    assert_eq!(application["status"], "under_review");
+    let notes = rule_notes(&app, &application["id"]).await;
+    assert_eq!(notes.len(), 2, "{:?}", notes);
+    assert!(notes[0].starts_with("Regla automática: Avanzar buenos matches"));
+    assert!(notes[1].starts_with("Regla automática: Etiquetar CV"));
+    let tagged: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM application_tag_assignments WHERE application_id = $1::uuid AND tag_id = $2::uuid)",
+    )
+    .bind(application["id"].as_str().unwrap())
+    .bind(tag_id.as_str().unwrap())
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(tagged);
+
+    // The status move is recorded in the history like a manual one
+    let history: Vec<(String, String)> = sqlx::query_as(
+        "SELECT previous_status::text, new_status::text FROM application_status_history WHERE application_id = $1::uuid",
+    )
+    .bind(application["id"].as_str().unwrap())
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(
+        history,
+        vec![("submitted".to_string(), "under_review".to_string())]
+    );
+
+    // Without a cached score one is computed, cached and used
+    let unscored = app.create_job_seeker().await;
+    let application = apply(&app, &unscored, job_id, None).await;
+    let score: i32 = sqlx::query_scalar(
+        "SELECT total_score FROM job_match_scores WHERE job_id = $1 AND user_id = $2",
+    )
+    .bind(job_id)
+    .bind(unscored.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(application["status"] == "rejected", score < 30);
+}
+
+#[sqlx::test]
+async fn test_unreadable_rule_is_skipped(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let rule = create_rule(&app, &company, job_id, reject_low_matches()).await;
+    // Edited by hand into something the decoder no longer accepts
+    sqlx::query(
+        r#"UPDATE job_automation_rules SET condition = '{"salary": 1}' WHERE id = $1::uuid"#,
+    )
+    .bind(rule["id"].as_str().unwrap())
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app.get(&rules_uri(job_id), Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body, json!([]));
+
+    // Not read as the empty condition, which would reject everyone
+    let seeker = app.create_job_seeker().await;
+    seed_match_score(&app, job_id, &seeker, 90).await;
+    let application = apply(&app, &seeker, job_id, None).await;
+    assert_eq!(application["status"], "submitted");
+    assert!(rule_notes(&app, &application["id"]).await.is_empty());
+}
+
+#[sqlx::test]
+async fn test_kill_switch_and_apply_on_behalf(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+    create_rule(&app, &company, job_id, reject_low_matches()).await;
+
+    let set_enabled = |enabled: bool| {
+        app.put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [{ "key": "job_automation_enabled", "value": enabled }] }),
+        )
+    };
+
+    let res = set_enabled(false).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let seeker = app.create_job_seeker().await;
+    seed_match_score(&app, job_id, &seeker, 10).await;
+    let application = apply(&app, &seeker, job_id, None).await;
+    assert_eq!(application["status"], "submitted");
+    assert!(rule_notes(&app, &application["id"]).await.is_empty());
+
+    let res = set_enabled(true).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Applications an OMIL submits for its seekers go through the same rules
+    let managed_seeker = app.create_job_seeker().await;
+    seed_match_score(&app, job_id, &managed_seeker, 10).await;
+    let managed_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(managed_seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/apply", managed_id),
+            Some(&omil.director),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let application_id = res.body["application_id"].clone();
+    assert_eq!(application_status(&app, &application_id).await, "rejected");
+    assert_eq!(rule_notes(&app, &application_id).await.len(), 1);
+}
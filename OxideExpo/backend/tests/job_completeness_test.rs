@@ -0,0 +1,177 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::services::completeness::{
+    self, INCOMPLETE_JOB_THRESHOLD, MIN_DESCRIPTION_CHARS,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn any_skill(app: &TestApp) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 1")
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn stored_percentage(app: &TestApp, job_id: &str) -> i32 {
+    sqlx::query_scalar("SELECT completeness_percentage FROM jobs WHERE id = $1::uuid")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn post_job(app: &TestApp, company: &TestCompany, fields: Value) -> String {
+    let mut job = json!({
+        "title": "Operario de bodega",
+        "description": "Recepción y despacho de mercadería",
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1
+    });
+    for (key, value) in fields.as_object().unwrap() {
+        job[key] = value.clone();
+    }
+
+    let res = app.post("/api/me/jobs", Some(&company.owner), job).await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body["id"].as_str().unwrap().to_string()
+}
+
+fn missing_items(body: &Value) -> Vec<String> {
+    body["missing"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["item"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_created_job_is_scored_with_checklist(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = post_job(&app, &company, json!({})).await;
+
+    let res = app
+        .get(&format!("/api/me/jobs/{}/completeness", job_id), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    // Only the open deadline counts
+    assert_eq!(res.body["score"], 5);
+    assert_eq!(
+        missing_items(&res.body),
+        vec!["description", "salary", "skills", "accommodations", "languages", "benefits"]
+    );
+    assert_eq!(res.body["missing"][0]["points"], 25);
+    assert_eq!(stored_percentage(&app, &job_id).await, 5);
+
+    let dashboard = app
+        .get("/api/me/company/dashboard", Some(&company.owner))
+        .await;
+    assert_eq!(dashboard.status, StatusCode::OK);
+    let incomplete = dashboard.body["incomplete_jobs"].as_array().unwrap();
+    assert_eq!(incomplete.len(), 1);
+    assert_eq!(incomplete[0]["job_id"], job_id.as_str());
+}
+
+#[sqlx::test]
+async fn test_required_skills_raise_the_score(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let skill_id = any_skill(&app).await;
+
+    let job_id = post_job(
+        &app,
+        &company,
+        json!({
+            "description": "x".repeat(MIN_DESCRIPTION_CHARS as usize),
+            "salary_min": "600000",
+            "benefits": "Colación y movilización",
+            "required_skills": [{ "skill_id": skill_id, "minimum_proficiency": 3 }]
+        }),
+    )
+    .await;
+    assert_eq!(stored_percentage(&app, &job_id).await, 25 + 20 + 20 + 5 + 5);
+
+    // Junction rows changed outside the API are picked up on recalculation
+    let other_id = post_job(
+        &app,
+        &company,
+        json!({ "title": "Reponedor", "description": "Reposición de góndolas en sala" }),
+    )
+    .await;
+    assert_eq!(stored_percentage(&app, &other_id).await, 5);
+
+    sqlx::query(
+        "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1::uuid, $2, 3)",
+    )
+    .bind(&other_id)
+    .bind(skill_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let scored = completeness::recalculate_open_jobs(app.db()).await.unwrap();
+    assert_eq!(scored, 2);
+    assert_eq!(stored_percentage(&app, &other_id).await, 25);
+
+    let res = app
+        .get(&format!("/api/me/jobs/{}/completeness", other_id), Some(&company.owner))
+        .await;
+    assert_eq!(res.body["score"], 25);
+    assert!(!missing_items(&res.body).contains(&"skills".to_string()));
+    assert!(res.body["score"].as_i64().unwrap() < INCOMPLETE_JOB_THRESHOLD as i64);
+}
+
+#[sqlx::test]
+async fn test_completeness_of_other_companies_jobs_is_hidden(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let job_id = post_job(&app, &company, json!({})).await;
+
+    let res = app
+        .get(&format!("/api/me/jobs/{}/completeness", job_id), Some(&other.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let seeker = app.create_job_seeker().await;
+    let res = app
+        .get(&format!("/api/me/jobs/{}/completeness", job_id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_reads_leave_the_stored_score_and_deadline_changes_update_it(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    // Inserted directly, so never scored
+    let job_id = app.create_active_job(&company).await.to_string();
+    assert_eq!(stored_percentage(&app, &job_id).await, 0);
+
+    let uri = format!("/api/me/jobs/{}/completeness", job_id);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.body["score"], 5);
+    let dashboard = app
+        .get("/api/me/company/dashboard", Some(&company.owner))
+        .await;
+    assert_eq!(dashboard.body["incomplete_jobs"][0]["score"], 5);
+    assert_eq!(stored_percentage(&app, &job_id).await, 0);
+
+    let deadline = chrono::Utc::now().date_naive() + chrono::Duration::days(60);
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/deadline", job_id),
+            Some(&company.owner),
+            json!({ "application_deadline": deadline }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(stored_percentage(&app, &job_id).await, 5);
+}
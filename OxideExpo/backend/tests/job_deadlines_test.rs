@@ -0,0 +1,257 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, NaiveDate};
+use common::{wait_for_messages, TestApp, TestCompany};
+use empleos_inclusivos_backend::services::job_deadlines;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn today(app: &TestApp) -> NaiveDate {
+    sqlx::query_scalar("SELECT CURRENT_DATE")
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Moves the deadline, into the past too, as if time had gone by
+async fn set_deadline(app: &TestApp, job_id: Uuid, deadline: NaiveDate) {
+    let mut tx = app.db().begin().await.unwrap();
+    sqlx::query("ALTER TABLE jobs DISABLE TRIGGER jobs_application_deadline")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE jobs SET application_deadline = $2 WHERE id = $1")
+        .bind(job_id)
+        .bind(deadline)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("ALTER TABLE jobs ENABLE TRIGGER jobs_application_deadline")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+}
+
+async fn job_state(app: &TestApp, job_id: Uuid) -> (String, NaiveDate, bool) {
+    sqlx::query_as(
+        "SELECT status::text, application_deadline, deadline_closed_at IS NOT NULL FROM jobs WHERE id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn change_deadline(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    deadline: NaiveDate,
+) -> common::TestResponse {
+    app.patch(
+        &format!("/api/me/jobs/{}/deadline", job_id),
+        Some(&company.owner),
+        json!({ "application_deadline": deadline }),
+    )
+    .await
+}
+
+#[sqlx::test]
+async fn test_extending_a_job_closed_by_its_deadline_reopens_it(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let today = today(&app).await;
+    let expired = app.create_active_job(&company).await;
+    let long_expired = app.create_active_job(&company).await;
+    let open = app.create_active_job(&company).await;
+    set_deadline(&app, expired, today - Duration::days(2)).await;
+    set_deadline(&app, long_expired, today - Duration::days(45)).await;
+
+    let closed = job_deadlines::close_expired(app.db()).await.unwrap();
+    assert_eq!(closed.len(), 2);
+    assert!(closed.contains(&expired) && closed.contains(&long_expired));
+    assert_eq!(
+        job_state(&app, expired).await,
+        ("closed".to_string(), today - Duration::days(2), true)
+    );
+    assert_eq!(job_state(&app, open).await.0, "active");
+    let res = app.get(&format!("/api/jobs/{}", expired), None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let new_deadline = today + Duration::days(20);
+    let res = change_deadline(&app, &company, expired, new_deadline).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["reopened"], true);
+    assert_eq!(
+        res.body["previous_deadline"],
+        (today - Duration::days(2)).to_string()
+    );
+    assert_eq!(res.body["new_deadline"], new_deadline.to_string());
+    assert_eq!(res.body["changed_by"], company.owner.id.to_string());
+    assert_eq!(
+        job_state(&app, expired).await,
+        ("active".to_string(), new_deadline, false)
+    );
+    let res = app.get(&format!("/api/jobs/{}", expired), None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Reopening doesn't send the job back to moderation
+    let approved: bool =
+        sqlx::query_scalar("SELECT approved_at IS NOT NULL FROM jobs WHERE id = $1")
+            .bind(expired)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(approved);
+
+    // Too long ago to reopen
+    let res = change_deadline(&app, &company, long_expired, new_deadline).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert_eq!(job_state(&app, long_expired).await.0, "closed");
+
+    // Closed by hand, not by the deadline
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", open),
+            Some(&company.owner),
+            json!({ "status": "closed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = change_deadline(&app, &company, open, new_deadline).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let logged: Vec<(Uuid, bool)> =
+        sqlx::query_as("SELECT job_id, reopened FROM job_deadline_changes")
+            .fetch_all(app.db())
+            .await
+            .unwrap();
+    assert_eq!(logged, vec![(expired, true)]);
+}
+
+#[sqlx::test]
+async fn test_extensions_notify_seekers_who_saved_or_recently_viewed_the_job(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let today = today(&app).await;
+
+    let saver = app.create_job_seeker().await;
+    let viewer = app.create_job_seeker().await;
+    let old_viewer = app.create_job_seeker().await;
+    let applicant = app.create_job_seeker().await;
+    let stranger = app.create_job_seeker().await;
+    app.create_job_seeker().await;
+
+    for seeker in [&saver, &applicant] {
+        let res = app
+            .post(
+                &format!("/api/me/saved-jobs/{}", job_id),
+                Some(seeker),
+                json!({}),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    }
+    for (seeker, days_ago) in [(&viewer, 3), (&old_viewer, 20), (&applicant, 1)] {
+        sqlx::query(
+            "INSERT INTO job_view_history (user_id, job_id, viewed_at) VALUES ($1, $2, NOW() - make_interval(days => $3))",
+        )
+        .bind(seeker.id)
+        .bind(job_id)
+        .bind(days_ago)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    // Viewed a different job
+    let other_job = app.create_active_job(&company).await;
+    sqlx::query("INSERT INTO job_view_history (user_id, job_id) VALUES ($1, $2)")
+        .bind(stranger.id)
+        .bind(other_job)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let application_id = app.create_application(job_id, &applicant).await;
+
+    let res = change_deadline(&app, &company, job_id, today + Duration::days(60)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["reopened"], false);
+    assert_eq!(res.body["notified_seekers"], 2);
+
+    let messages = wait_for_messages(&inbox, 2).await;
+    assert_eq!(messages.len(), 2);
+    assert!(messages.iter().any(|m| m.contains(&saver.email)));
+    assert!(messages.iter().any(|m| m.contains(&viewer.email)));
+    for seeker in [&old_viewer, &applicant, &stranger] {
+        assert!(!messages.iter().any(|m| m.contains(&seeker.email)));
+    }
+
+    // Shortening tells no one and leaves the applications alone
+    let res = change_deadline(&app, &company, job_id, today + Duration::days(1)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["notified_seekers"], 0);
+    let status: String =
+        sqlx::query_scalar("SELECT status::text FROM job_applications WHERE id = $1")
+            .bind(application_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(status, "submitted");
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert_eq!(inbox.lock().unwrap().len(), 2);
+}
+
+#[sqlx::test]
+async fn test_deadline_changes_are_bounded(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let today = today(&app).await;
+    let current = today + Duration::days(30);
+
+    for deadline in [
+        today - Duration::days(1),
+        today,
+        today + Duration::days(91),
+        current,
+    ] {
+        let res = change_deadline(&app, &company, job_id, deadline).await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", deadline);
+    }
+    assert_eq!(job_state(&app, job_id).await.1, current);
+
+    let res = change_deadline(&app, &other, job_id, today + Duration::days(40)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let seeker = app.create_job_seeker().await;
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/deadline", job_id),
+            Some(&seeker),
+            json!({ "application_deadline": today + Duration::days(40) }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = change_deadline(&app, &company, job_id, today + Duration::days(90)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = change_deadline(&app, &company, job_id, today + Duration::days(1)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["previous_deadline"],
+        (today + Duration::days(90)).to_string()
+    );
+
+    // Paused jobs keep their deadline
+    sqlx::query("UPDATE jobs SET status = 'paused' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = change_deadline(&app, &company, job_id, today + Duration::days(10)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestResponse};
+use empleos_inclusivos_backend::services::job_duplicates::DESCRIPTION_SIMILARITY_THRESHOLD;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+const BASE_DESCRIPTION: &str = "Atención de clientes y reposición de productos en sala de ventas, \
+     manejo de caja y apoyo en inventarios mensuales";
+
+async fn post_job(app: &TestApp, company: &TestCompany, fields: Value) -> TestResponse {
+    let mut job = json!({
+        "title": "Vendedor de tienda",
+        "description": BASE_DESCRIPTION,
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1
+    });
+    for (key, value) in fields.as_object().unwrap() {
+        job[key] = value.clone();
+    }
+
+    app.post("/api/me/jobs", Some(&company.owner), job).await
+}
+
+async fn similarity(app: &TestApp, a: &str, b: &str) -> f32 {
+    sqlx::query_scalar(
+        "SELECT similarity(normalize_job_description($1), normalize_job_description($2))",
+    )
+    .bind(a)
+    .bind(b)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_exact_title_region_and_type_is_a_duplicate(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let res = post_job(&app, &company, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let original_id = res.body["id"].clone();
+
+    let res = post_job(
+        &app,
+        &company,
+        json!({
+            "title": "  VENDEDOR de tienda ",
+            "description": "Turnos de fin de semana en local de Providencia"
+        }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+    let duplicates = res.body["details"]["duplicates"].as_array().unwrap();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0]["job_id"], original_id);
+    assert_eq!(duplicates[0]["exact_match"], true);
+
+    // Same title for a different kind of contract is a separate vacancy
+    let res = post_job(
+        &app,
+        &company,
+        json!({
+            "job_type": "part_time",
+            "description": "Turnos de fin de semana en local de Providencia"
+        }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Other companies' jobs never count
+    let other = app.create_company_with_owner().await;
+    let res = post_job(&app, &other, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_description_similarity_threshold(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    post_job(&app, &company, json!({})).await;
+
+    let above = "Atención de clientes y reposición de mercadería en sala de ventas, \
+                 manejo de caja y apoyo en inventarios";
+    let below = "Atención de clientes y reposición de mercadería en sala, \
+                 manejo de caja y apoyo en inventarios";
+    assert!(similarity(&app, BASE_DESCRIPTION, above).await >= DESCRIPTION_SIMILARITY_THRESHOLD);
+    assert!(similarity(&app, BASE_DESCRIPTION, below).await < DESCRIPTION_SIMILARITY_THRESHOLD);
+
+    let res = post_job(
+        &app,
+        &company,
+        json!({ "title": "Reponedor", "description": above }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+    let duplicates = res.body["details"]["duplicates"].as_array().unwrap();
+    assert_eq!(duplicates[0]["exact_match"], false);
+
+    let res = post_job(
+        &app,
+        &company,
+        json!({ "title": "Reponedor", "description": below }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_closed_jobs_are_not_duplicates(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let res = post_job(&app, &company, json!({})).await;
+
+    sqlx::query("UPDATE jobs SET status = 'closed' WHERE id = $1::uuid")
+        .bind(res.body["id"].as_str().unwrap())
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = post_job(&app, &company, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_forced_duplicate_is_flagged_for_moderators(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let original = post_job(&app, &company, json!({})).await;
+    let original_id = original.body["id"].as_str().unwrap().to_string();
+
+    let res = post_job(&app, &company, json!({ "force": true })).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let copy_id = res.body["id"].as_str().unwrap().to_string();
+
+    // Submitting for approval checks again
+    let status_uri = format!("/api/me/jobs/{}/status", copy_id);
+    let res = app
+        .patch(
+            &status_uri,
+            Some(&company.owner),
+            json!({ "status": "pending_approval" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+    assert_eq!(res.body["details"]["duplicates"][0]["job_id"], original_id);
+
+    let res = app
+        .patch(
+            &status_uri,
+            Some(&company.owner),
+            json!({ "status": "pending_approval", "force": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let overrides: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_duplicate_overrides WHERE job_id = $1::uuid")
+            .bind(&copy_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(overrides, 2);
+
+    let res = app.get("/api/admin/jobs/pending", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["id"], copy_id);
+    assert_eq!(res.body[0]["possible_duplicate_of"], json!([original_id]));
+}
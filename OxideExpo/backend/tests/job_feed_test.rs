@@ -0,0 +1,108 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use common::TestApp;
+use empleos_inclusivos_backend::services::job_feed;
+use redis::AsyncCommands;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const ADVERSARIAL_TITLE: &str =
+    "Operario <script>alert('x')</script> & \"Ayudante\" ]]> \u{8}\u{1b}fin";
+
+async fn clear_feeds(app: &TestApp) {
+    let mut redis = app.state.redis.clone();
+    let _: () = redis
+        .del(&[job_feed::XML_CACHE_KEY, job_feed::JSON_CACHE_KEY])
+        .await
+        .unwrap();
+}
+
+async fn set_title(app: &TestApp, job_id: Uuid, title: &str) {
+    sqlx::query("UPDATE jobs SET title = $2 WHERE id = $1")
+        .bind(job_id)
+        .bind(title)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+// The feed keys are global, so this is the only test that reads through them
+#[sqlx::test]
+async fn test_feeds_escape_titles_and_skip_opted_out_companies(db: PgPool) {
+    let app = TestApp::new(db).await;
+    clear_feeds(&app).await;
+
+    // Nothing is generated until the scheduler runs
+    let res = app.get("/api/feeds/jobs.xml", None).await;
+    assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE);
+
+    let company = app.create_company_with_owner().await;
+    let listed = app.create_active_job(&company).await;
+    set_title(&app, listed, ADVERSARIAL_TITLE).await;
+
+    let opted_out = app.create_company_with_owner().await;
+    let res = app
+        .put(
+            "/api/me/company/settings",
+            Some(&opted_out.owner),
+            json!({ "syndication_enabled": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["syndication_enabled"], false);
+    assert_eq!(res.body["show_match_scores"], true);
+    let hidden = app.create_active_job(&opted_out).await;
+
+    let mut redis = app.state.redis.clone();
+    let frontend_url = &app.state.config.frontend_url;
+    job_feed::refresh(app.db(), &mut redis, frontend_url)
+        .await
+        .unwrap();
+
+    let res = app.get("/api/feeds/jobs.xml", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.headers[header::CONTENT_TYPE],
+        "application/xml; charset=utf-8"
+    );
+    assert_eq!(res.headers[header::CACHE_CONTROL], "public, max-age=1800");
+
+    let xml: String = redis.get(job_feed::XML_CACHE_KEY).await.unwrap();
+    assert!(xml.contains(
+        "<title>Operario &lt;script&gt;alert(&apos;x&apos;)&lt;/script&gt; &amp; &quot;Ayudante&quot; ]]&gt; fin</title>"
+    ));
+    assert!(!xml.contains("<script>"));
+    assert!(!xml.contains('\u{8}') && !xml.contains('\u{1b}'));
+    assert!(xml.contains(&format!("<referencenumber>{}</referencenumber>", listed)));
+    assert!(!xml.contains(&hidden.to_string()));
+
+    let res = app.get("/api/feeds/jobs.json", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.headers[header::CONTENT_TYPE], "application/json");
+
+    let postings = res.body["jobs"].as_array().unwrap();
+    let ids: Vec<&str> = postings
+        .iter()
+        .map(|p| p["identifier"]["value"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&listed.to_string().as_str()));
+    assert!(!ids.contains(&hidden.to_string().as_str()));
+
+    let posting = postings
+        .iter()
+        .find(|p| p["identifier"]["value"] == listed.to_string())
+        .unwrap();
+    assert_eq!(posting["@context"], "https://schema.org");
+    assert_eq!(posting["@type"], "JobPosting");
+    assert_eq!(posting["employmentType"], "FULL_TIME");
+    assert_eq!(
+        posting["url"],
+        format!("{}/jobs/{}", frontend_url.trim_end_matches('/'), listed)
+    );
+    // JSON carries the title as-is; escaping is the JSON encoder's job
+    assert_eq!(posting["title"], ADVERSARIAL_TITLE);
+
+    clear_feeds(&app).await;
+}
@@ -0,0 +1,305 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Invites the seeker to the job, returning the invitation ID
+async fn invite(app: &TestApp, company: &TestCompany, job_id: Uuid, seeker: &TestUser) -> String {
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id, "message": "Tu perfil calza con la oferta" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body["id"].as_str().unwrap().to_string()
+}
+
+#[sqlx::test]
+async fn test_edited_job_keeps_invited_terms(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    invite(&app, &company, job_id, &seeker).await;
+
+    let res = app.get("/api/me/invitations", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["data"][0]["job"]["title"], "Test job");
+    assert_eq!(res.body["data"][0]["job_changed"], false);
+
+    sqlx::query("UPDATE jobs SET title = 'Test job (turno noche)', salary_min = 550000 WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app.get("/api/me/invitations", Some(&seeker)).await;
+    assert_eq!(res.body["data"][0]["job"]["title"], "Test job");
+    assert_eq!(res.body["data"][0]["job"]["salary_min"], json!(null));
+    assert_eq!(res.body["data"][0]["job_changed"], true);
+}
+
+#[sqlx::test]
+async fn test_deleted_job_invitation_stays_readable(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let invitation_id = invite(&app, &company, job_id, &seeker).await;
+
+    let res = app
+        .delete(&format!("/api/me/jobs/{}", job_id), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let uri = format!("/api/me/invitations/{}", invitation_id);
+    let res = app.get(&uri, Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["invitation"]["status"], "job_no_longer_available");
+    assert_eq!(res.body["invitation"]["job_id"], json!(null));
+    assert_eq!(res.body["job"]["title"], "Test job");
+    assert_eq!(res.body["job_changed"], false);
+
+    let res = app
+        .post(&format!("{}/respond", uri), Some(&seeker), json!({ "accept": true }))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_closed_job_invitation_cannot_be_accepted(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let invitation_id = invite(&app, &company, job_id, &seeker).await;
+
+    sqlx::query("UPDATE jobs SET status = 'closed' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let uri = format!("/api/me/invitations/{}/respond", invitation_id);
+    let res = app.post(&uri, Some(&seeker), json!({ "accept": true })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let applications: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_applications WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(applications, 0);
+
+    // Declining is still possible
+    let res = app.post(&uri, Some(&seeker), json!({ "accept": false })).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "declined");
+}
+
+async fn respond(
+    app: &TestApp,
+    seeker: &TestUser,
+    invitation_id: &str,
+    body: serde_json::Value,
+) -> common::TestResponse {
+    app.post(
+        &format!("/api/me/invitations/{}/respond", invitation_id),
+        Some(seeker),
+        body,
+    )
+    .await
+}
+
+async fn application_count(app: &TestApp, job_id: Uuid) -> (i64, i32) {
+    let applications: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_applications WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    let counted: i32 = sqlx::query_scalar("SELECT applications_count FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    (applications, counted)
+}
+
+#[sqlx::test]
+async fn test_accepting_with_apply_immediately_creates_the_application(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let invitation_id = invite(&app, &company, job_id, &seeker).await;
+
+    let res = respond(
+        &app,
+        &seeker,
+        &invitation_id,
+        json!({ "accept": true, "apply_immediately": true, "cover_letter": "Me interesa el cargo" }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "applied");
+    assert_eq!(res.body["application_error"], json!(null));
+    let application_id = res.body["application_id"].as_str().unwrap().to_string();
+
+    let (cover_letter, source): (Option<String>, String) = sqlx::query_as(
+        "SELECT cover_letter, source::text FROM job_applications WHERE id = $1::uuid AND applicant_id = $2",
+    )
+    .bind(&application_id)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(cover_letter.as_deref(), Some("Me interesa el cargo"));
+    assert_eq!(source, "invitation");
+    assert_eq!(application_count(&app, job_id).await, (1, 1));
+
+    // The company sees which invitations turned into applications
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["data"][0]["invitation"]["application_id"],
+        application_id.as_str()
+    );
+
+    let res = respond(
+        &app,
+        &seeker,
+        &invitation_id,
+        json!({ "accept": true, "apply_immediately": true }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_blocked_application_keeps_the_acceptance(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let invitation_id = invite(&app, &company, job_id, &seeker).await;
+    sqlx::query("UPDATE users SET email_verified_at = NULL WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = respond(
+        &app,
+        &seeker,
+        &invitation_id,
+        json!({ "accept": true, "apply_immediately": true }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "accepted");
+    assert_eq!(res.body["application_id"], json!(null));
+    assert!(
+        res.body["application_error"]
+            .as_str()
+            .unwrap()
+            .contains("verify your email"),
+        "{}",
+        res.body
+    );
+    assert_eq!(application_count(&app, job_id).await, (0, 0));
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.body["data"][0]["invitation"]["status"], "accepted");
+    assert_eq!(
+        res.body["data"][0]["invitation"]["application_id"],
+        json!(null)
+    );
+
+    // An accepted invitation can't be declined, but the seeker can still apply through it
+    let res = respond(&app, &seeker, &invitation_id, json!({ "accept": false })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    sqlx::query("UPDATE users SET email_verified_at = NOW() WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = respond(
+        &app,
+        &seeker,
+        &invitation_id,
+        json!({ "accept": true, "apply_immediately": true }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "applied");
+    assert!(res.body["application_id"].is_string());
+    assert_eq!(application_count(&app, job_id).await, (1, 1));
+
+    // Accepting without the flag doesn't apply
+    let other_job = app.create_active_job(&company).await;
+    let other_invitation = invite(&app, &company, other_job, &seeker).await;
+    let res = respond(&app, &seeker, &other_invitation, json!({ "accept": true })).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "accepted");
+    assert_eq!(res.body["application_error"], json!(null));
+    assert_eq!(application_count(&app, other_job).await, (0, 0));
+}
+
+#[sqlx::test]
+async fn test_accepting_after_applying_does_not_duplicate_the_application(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+    let invitation_id = invite(&app, &company, job_id, &seeker).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = respond(
+        &app,
+        &seeker,
+        &invitation_id,
+        json!({ "accept": true, "apply_immediately": true }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "accepted");
+    assert_eq!(
+        res.body["application_error"],
+        "You have already applied to this job"
+    );
+    assert_eq!(application_count(&app, job_id).await, (1, 1));
+
+    let source: String =
+        sqlx::query_scalar("SELECT source::text FROM job_applications WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(source, "direct");
+}
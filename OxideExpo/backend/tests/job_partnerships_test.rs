@@ -0,0 +1,259 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Registers `seeker` with the OMIL
+async fn manage(app: &TestApp, omil: &TestOmil, seeker: &TestUser) {
+    sqlx::query(
+        "INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by) VALUES ($1, $2, $3)",
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+/// Referral request for the job, accepted by the OMIL, returning its ID
+async fn accepted_referral(
+    app: &TestApp,
+    company: &TestCompany,
+    omil: &TestOmil,
+    job_id: Uuid,
+) -> String {
+    let res = app
+        .post(
+            "/api/me/company/referral-requests",
+            Some(&company.owner),
+            json!({ "omil_id": omil.id, "job_id": job_id, "message": "Publiquemos juntos" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let request_id = res.body["id"].as_str().unwrap().to_string();
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/referral-requests/{}/accept", request_id),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    request_id
+}
+
+async fn set_partner(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    omil_id: Option<Uuid>,
+) -> common::TestResponse {
+    app.put(
+        &format!("/api/me/jobs/{}/partner-omil", job_id),
+        Some(&company.owner),
+        json!({ "omil_id": omil_id }),
+    )
+    .await
+}
+
+async fn public_partner(app: &TestApp, job_id: Uuid) -> Value {
+    let res = app.get(&format!("/api/jobs/{}", job_id), None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["id"], job_id.to_string());
+    res.body["partner_omil"].clone()
+}
+
+fn partner_applicants_uri(job_id: Uuid) -> String {
+    format!("/api/me/omil/partner-jobs/{}/applicants", job_id)
+}
+
+#[sqlx::test]
+async fn test_partner_must_have_accepted_a_referral(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // A pending request is not enough
+    let res = app
+        .post(
+            "/api/me/company/referral-requests",
+            Some(&company.owner),
+            json!({ "omil_id": omil.id, "job_id": job_id, "message": "Hola" }),
+        )
+        .await;
+    let request_id = res.body["id"].as_str().unwrap().to_string();
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert_eq!(public_partner(&app, job_id).await, Value::Null);
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/referral-requests/{}/accept", request_id),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Only the company that owns the job can name its partner
+    let other = app.create_company_with_owner().await;
+    let res = set_partner(&app, &other, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Nor a plain member of it
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/partner-omil", job_id),
+            Some(&member),
+            json!({ "omil_id": omil.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["partner_omil"]["omil_id"], omil.id.to_string());
+    let partner = public_partner(&app, job_id).await;
+    assert_eq!(partner["organization_name"], "OMIL Test");
+
+    // The company can end the partnership on its side too
+    let res = set_partner(&app, &company, job_id, None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["partner_omil"], Value::Null);
+    assert_eq!(public_partner(&app, job_id).await, Value::Null);
+}
+
+#[sqlx::test]
+async fn test_partner_omil_sees_only_its_own_seekers(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let other_omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let managed = app.create_job_seeker().await;
+    manage(&app, &omil, &managed).await;
+    let managed_app = app.create_application(job_id, &managed).await;
+    let managed_elsewhere = app.create_job_seeker().await;
+    manage(&app, &other_omil, &managed_elsewhere).await;
+    app.create_application(job_id, &managed_elsewhere).await;
+    let unmanaged = app.create_job_seeker().await;
+    let unmanaged_app = app.create_application(job_id, &unmanaged).await;
+    sqlx::query("UPDATE job_applications SET status = 'under_review' WHERE id = $1")
+        .bind(unmanaged_app)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    // Accepting a referral alone does not share applicants
+    accepted_referral(&app, &company, &omil, job_id).await;
+    let res = app
+        .get(&partner_applicants_uri(job_id), Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app
+        .get(&partner_applicants_uri(job_id), Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["total_applications"], 3);
+    assert_eq!(
+        res.body["status_counts"],
+        json!([
+            { "status": "submitted", "count": 2 },
+            { "status": "under_review", "count": 1 },
+        ])
+    );
+    let applicants = res.body["applicants"].as_array().unwrap();
+    assert_eq!(applicants.len(), 1);
+    assert_eq!(applicants[0]["application_id"], managed_app.to_string());
+    assert_eq!(applicants[0]["job_seeker_id"], managed.id.to_string());
+
+    let res = app
+        .get(&partner_applicants_uri(job_id), Some(&other_omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app
+        .get(&partner_applicants_uri(job_id), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_withdrawal_removes_access_and_badge(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    manage(&app, &omil, &seeker).await;
+    let application_id = app.create_application(job_id, &seeker).await;
+
+    let request_id = accepted_referral(&app, &company, &omil, job_id).await;
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let withdraw_uri = format!("/api/me/omil/referral-requests/{}/withdraw", request_id);
+    let res = app
+        .post(
+            &withdraw_uri,
+            Some(&omil.director),
+            json!({ "message": "Sin cupos para acompañar el proceso" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "withdrawn");
+
+    assert_eq!(public_partner(&app, job_id).await, Value::Null);
+    let res = app
+        .get(&partner_applicants_uri(job_id), Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Applications made during the partnership are untouched
+    let status: String =
+        sqlx::query_scalar("SELECT status::text FROM job_applications WHERE id = $1")
+            .bind(application_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(status, "submitted");
+
+    let res = app
+        .get(
+            &format!("/api/me/company/referral-requests/{}", request_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.body["history"].as_array().unwrap().len(), 3);
+
+    // The company cannot restore the badge without a new acceptance
+    let res = set_partner(&app, &company, job_id, Some(omil.id)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = app
+        .post(&withdraw_uri, Some(&omil.director), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,277 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn post_job(
+    app: &TestApp,
+    company: &TestCompany,
+    reposted_from: Uuid,
+) -> common::TestResponse {
+    app.post(
+        "/api/me/jobs",
+        Some(&company.owner),
+        json!({
+            "title": "Operario de bodega",
+            "description": "Recepción y despacho de mercadería",
+            "job_type": "full_time",
+            "work_modality": "on_site",
+            "application_deadline": "2099-12-31",
+            "vacancies": 1,
+            "reposted_from_job_id": reposted_from,
+        }),
+    )
+    .await
+}
+
+async fn set_status(app: &TestApp, job_id: Uuid, status: &str) {
+    sqlx::query("UPDATE jobs SET status = $2::job_status WHERE id = $1")
+        .bind(job_id)
+        .bind(status)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+/// Active repost of `predecessor`, which is closed first
+async fn repost(app: &TestApp, company: &TestCompany, predecessor: Uuid) -> Uuid {
+    set_status(app, predecessor, "closed").await;
+    let res = post_job(app, company, predecessor).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let job_id = res.body["id"].as_str().unwrap().parse().unwrap();
+    sqlx::query(
+        "UPDATE jobs SET status = 'active', approved_at = NOW(), approved_by = posted_by, published_at = NOW() WHERE id = $1",
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    job_id
+}
+
+async fn applied_with_status(app: &TestApp, job_id: Uuid, seeker: &TestUser, status: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO job_applications (job_id, applicant_id, status, applied_at)
+        VALUES ($1, $2, $3::application_status, '2025-03-10 15:00:00+00')
+        "#,
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .bind(status)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+/// Applicants listed for the job, as (applicant id, applied_to_previous)
+async fn applicants(app: &TestApp, company: &TestCompany, uri: &str) -> Vec<(String, bool)> {
+    let res = app.get(uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["applicants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| {
+            (
+                a["applicant_id"].as_str().unwrap().to_string(),
+                a["applied_to_previous"].as_bool().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_repost_must_be_a_closed_job_of_the_same_company(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other = app.create_company_with_owner().await;
+
+    let others_job = app.create_active_job(&other).await;
+    set_status(&app, others_job, "closed").await;
+    let res = post_job(&app, &company, others_job).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let open_job = app.create_active_job(&company).await;
+    let res = post_job(&app, &company, open_job).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = post_job(&app, &company, Uuid::new_v4()).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    set_status(&app, open_job, "closed").await;
+    let res = post_job(&app, &company, open_job).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let linked: Option<Uuid> =
+        sqlx::query_scalar("SELECT reposted_from_job_id FROM jobs WHERE id = $1::uuid")
+            .bind(res.body["id"].as_str().unwrap())
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(linked, Some(open_job));
+}
+
+#[sqlx::test]
+async fn test_returning_applicants_are_warned_and_flagged(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let original = app.create_active_job(&company).await;
+    let returning = app.create_job_seeker().await;
+    let newcomer = app.create_job_seeker().await;
+    applied_with_status(&app, original, &returning, "rejected").await;
+
+    let job_id = repost(&app, &company, original).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&returning),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["warnings"],
+        json!([
+            "You applied to an earlier posting of this job in March 2025 and were not selected."
+        ])
+    );
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&newcomer),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["warnings"], json!([]));
+
+    // The company sees who applied before, and can filter on it
+    let uri = format!("/api/me/jobs/{}/applicants", job_id);
+    let mut listed = applicants(&app, &company, &uri).await;
+    listed.sort();
+    let mut expected = vec![
+        (returning.id.to_string(), true),
+        (newcomer.id.to_string(), false),
+    ];
+    expected.sort();
+    assert_eq!(listed, expected);
+    let only_returning = format!("{}?applied_to_previous=true", uri);
+    assert_eq!(
+        applicants(&app, &company, &only_returning).await,
+        vec![(returning.id.to_string(), true)]
+    );
+    let only_new = format!("{}?applied_to_previous=false", uri);
+    assert_eq!(
+        applicants(&app, &company, &only_new).await,
+        vec![(newcomer.id.to_string(), false)]
+    );
+
+    let res = app
+        .get("/api/me/company/dashboard", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let stats = res.body["top_jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|j| j["job_id"] == json!(job_id))
+        .unwrap();
+    assert_eq!(stats["applications_count"], 2);
+    assert_eq!(stats["repeat_applicants_count"], 1);
+}
+
+#[sqlx::test]
+async fn test_chain_is_followed_up_to_three_postings(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    // Five postings of the same vacancy, each reposting the one before
+    let mut chain = vec![app.create_active_job(&company).await];
+    for _ in 0..4 {
+        let job_id = repost(&app, &company, *chain.last().unwrap()).await;
+        chain.push(job_id);
+    }
+    let current = chain[4];
+
+    // Three postings back is still the same vacancy, four is not
+    let three_back = app.create_job_seeker().await;
+    let four_back = app.create_job_seeker().await;
+    applied_with_status(&app, chain[1], &three_back, "hired").await;
+    applied_with_status(&app, chain[0], &four_back, "rejected").await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&three_back),
+            json!({ "job_id": current }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["warnings"],
+        json!(["You applied to an earlier posting of this job in March 2025 and were hired."])
+    );
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&four_back),
+            json!({ "job_id": current }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["warnings"], json!([]));
+
+    let uri = format!(
+        "/api/me/jobs/{}/applicants?applied_to_previous=true",
+        current
+    );
+    assert_eq!(
+        applicants(&app, &company, &uri).await,
+        vec![(three_back.id.to_string(), true)]
+    );
+}
+
+#[sqlx::test]
+async fn test_candidates_rejected_before_rank_lower(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let original = app.create_active_job(&company).await;
+    let other = app.create_job_seeker().await;
+    // Updated last, so ahead of `other` on an equal score
+    let rejected = app.create_job_seeker().await;
+    applied_with_status(&app, original, &rejected, "rejected").await;
+
+    let job_id = repost(&app, &company, original).await;
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/recommended-candidates", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let candidates: Vec<(Value, Value)> = res.body["candidates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            (
+                c["profile"]["user_id"].clone(),
+                c["rejected_before"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        candidates,
+        vec![
+            (json!(other.id), json!(false)),
+            (json!(rejected.id), json!(true))
+        ]
+    );
+}
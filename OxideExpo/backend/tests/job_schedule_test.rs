@@ -0,0 +1,201 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn post_job(app: &TestApp, company: &TestCompany, fields: Value) -> common::TestResponse {
+    let mut job = json!({
+        "title": "Operario de bodega",
+        "description": "Recepción y despacho de mercadería",
+        "job_type": "full_time",
+        "work_modality": "on_site",
+        "application_deadline": "2099-12-31",
+        "vacancies": 1,
+        // The postings in a test look alike on purpose
+        "force": true
+    });
+    for (key, value) in fields.as_object().unwrap() {
+        job[key] = value.clone();
+    }
+    app.post("/api/me/jobs", Some(&company.owner), job).await
+}
+
+/// Active job with the given schedule
+async fn schedule_job(
+    app: &TestApp,
+    company: &TestCompany,
+    shift_type: Option<&str>,
+    weekly_hours: Option<i32>,
+) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query(
+        "UPDATE jobs SET shift_type = $2::shift_type, weekly_hours = $3, schedule_details = 'Lunes a viernes' WHERE id = $1",
+    )
+    .bind(job_id)
+    .bind(shift_type)
+    .bind(weekly_hours)
+    .execute(app.db())
+    .await
+    .unwrap();
+    job_id
+}
+
+async fn listed(app: &TestApp, query: &str) -> Vec<Uuid> {
+    let res = app.get(&format!("/api/jobs?{}", query), None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let mut ids: Vec<Uuid> = res.body["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|j| j["id"].as_str().unwrap().parse().unwrap())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[sqlx::test]
+async fn test_schedule_validation(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let res = post_job(&app, &company, json!({ "weekly_hours": 46 })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = post_job(&app, &company, json!({ "weekly_hours": 0 })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Shift work has to say what the shifts are
+    let res = post_job(&app, &company, json!({ "shift_type": "shifts" })).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = post_job(
+        &app,
+        &company,
+        json!({ "shift_type": "shifts", "schedule_details": "  " }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = post_job(
+        &app,
+        &company,
+        json!({ "shift_type": "shifts", "weekly_hours": 42, "schedule_details": "Turnos 4x4, 08:00 a 20:00" }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["shift_type"], "shifts");
+    assert_eq!(res.body["weekly_hours"], 42);
+    assert_eq!(res.body["schedule_details"], "Turnos 4x4, 08:00 a 20:00");
+
+    // Other shift types do not need details
+    let res = post_job(
+        &app,
+        &company,
+        json!({ "shift_type": "part_time", "weekly_hours": 20 }),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["schedule_details"], Value::Null);
+}
+
+#[sqlx::test]
+async fn test_public_jobs_filter_and_display_schedule(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let full_time = schedule_job(&app, &company, Some("full_time"), Some(45)).await;
+    let part_time = schedule_job(&app, &company, Some("part_time"), Some(20)).await;
+    let unstructured = schedule_job(&app, &company, None, None).await;
+
+    let res = app.get(&format!("/api/jobs/{}", full_time), None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["shift_type"], "full_time");
+    assert_eq!(res.body["weekly_hours"], 45);
+    assert_eq!(
+        res.body["schedule_display"],
+        "Jornada completa · 45 horas semanales · Lunes a viernes"
+    );
+    let res = app.get(&format!("/api/jobs/{}", unstructured), None).await;
+    assert_eq!(res.body["schedule_display"], "Lunes a viernes");
+
+    let mut all = vec![full_time, part_time, unstructured];
+    all.sort();
+    assert_eq!(listed(&app, "").await, all);
+    assert_eq!(listed(&app, "shift_type=part_time").await, vec![part_time]);
+    // Jobs without weekly hours cannot be shown to fit a limit
+    assert_eq!(listed(&app, "weekly_hours_max=30").await, vec![part_time]);
+    assert_eq!(
+        listed(&app, "shift_type=full_time&weekly_hours_max=30").await,
+        Vec::<Uuid>::new()
+    );
+
+    let res = app.get("/api/jobs?shift_type=night", None).await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[sqlx::test]
+async fn test_backfill_heuristics(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    let cases = [
+        ("part time mañanas", Some("part_time")),
+        ("media jornada AM", Some("part_time")),
+        ("Medio tiempo, tardes", Some("part_time")),
+        ("Jornada completa", Some("full_time")),
+        ("Lunes a Viernes 9:00 a 18:00", Some("full_time")),
+        ("45 horas semanales", Some("full_time")),
+        ("Turnos rotativos", Some("shifts")),
+        ("Sistema 4x4", Some("shifts")),
+        ("Full time, turno noche", Some("shifts")),
+        ("9am-6pm flexible", Some("flexible")),
+        ("Horario a convenir", Some("flexible")),
+        ("Sábados y domingos", None),
+        ("", None),
+    ];
+    for (schedule, expected) in cases {
+        let inferred: Option<String> = sqlx::query_scalar("SELECT infer_shift_type($1)::text")
+            .bind(schedule)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+        assert_eq!(inferred.as_deref(), expected, "{:?}", schedule);
+    }
+}
+
+#[sqlx::test]
+async fn test_preferred_shift_types_boost_recommendations(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let full_time = schedule_job(&app, &company, Some("full_time"), Some(45)).await;
+    let part_time = schedule_job(&app, &company, Some("part_time"), Some(20)).await;
+    // Same score, so without a preference the newer job comes first
+    sqlx::query("UPDATE jobs SET published_at = NOW() - INTERVAL '1 day' WHERE id = $1")
+        .bind(part_time)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let first = |body: &Value| body["jobs"][0]["job"]["id"].as_str().unwrap().to_string();
+    let res = app.get("/api/me/recommended-jobs", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(first(&res.body), full_time.to_string());
+    let score = res.body["jobs"][0]["match_score"].clone();
+
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "preferred_shift_types": ["part_time"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["preferred_shift_types"], json!(["part_time"]));
+    let res = app.get("/api/me/preferences", Some(&seeker)).await;
+    assert_eq!(res.body["preferred_shift_types"], json!(["part_time"]));
+
+    let res = app.get("/api/me/recommended-jobs", Some(&seeker)).await;
+    assert_eq!(first(&res.body), part_time.to_string());
+    // The boost orders the list but does not change the score
+    assert_eq!(res.body["jobs"][0]["match_score"], score);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::services::scheduler::publish_due_jobs;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Job waiting for approval, to be published in an hour
+async fn create_pending_scheduled_job(app: &TestApp, company: &TestCompany) -> Uuid {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO jobs (
+            company_id, posted_by, title, description, job_type, work_modality,
+            application_deadline, status, publish_at
+        )
+        VALUES (
+            $1, $2, 'Test job', 'Test job description', 'full_time', 'on_site',
+            CURRENT_DATE + 30, 'pending_approval', $3
+        )
+        RETURNING id
+        "#,
+    )
+    .bind(company.id)
+    .bind(company.owner.id)
+    .bind(Utc::now() + Duration::hours(1))
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn approve(app: &TestApp, job_id: Uuid) -> Value {
+    let admin = app.create_admin().await;
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/approve", job_id),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_approval_schedules_then_publishes_job(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = create_pending_scheduled_job(&app, &company).await;
+
+    let body = approve(&app, job_id).await;
+    assert_eq!(body["status"], "scheduled");
+    assert!(body["published_at"].is_null());
+
+    // Not due yet: nothing happens
+    let published = publish_due_jobs(app.db()).await.unwrap();
+    assert!(!published.contains(&job_id));
+
+    // Time arrives
+    sqlx::query("UPDATE jobs SET publish_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let published = publish_due_jobs(app.db()).await.unwrap();
+    assert_eq!(published, vec![job_id]);
+
+    let row: (String, bool) =
+        sqlx::query_as("SELECT status::text, published_at IS NOT NULL FROM jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(row, ("active".to_string(), true));
+}
+
+#[sqlx::test]
+async fn test_scheduled_job_cannot_be_activated_early(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = create_pending_scheduled_job(&app, &company).await;
+    approve(&app, job_id).await;
+
+    let uri = format!("/api/me/jobs/{}/status", job_id);
+
+    let res = app
+        .patch(&uri, Some(&company.owner), json!({ "status": "active" }))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Clearing the publish time allows going live immediately
+    let res = app
+        .patch(
+            &uri,
+            Some(&company.owner),
+            json!({ "status": "active", "clear_publish_at": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "active");
+    assert!(res.body["publish_at"].is_null());
+    assert!(!res.body["published_at"].is_null());
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn transfer(app: &TestApp, job_id: Uuid, company: &TestCompany) -> common::TestResponse {
+    let admin = app.create_admin().await;
+    app.post(
+        &format!("/api/admin/jobs/{}/transfer", job_id),
+        Some(&admin),
+        json!({ "company_id": company.id }),
+    )
+    .await
+}
+
+async fn dashboard(app: &TestApp, company: &TestCompany) -> Value {
+    let res = app
+        .get("/api/me/company/dashboard", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+async fn withdraw(app: &TestApp, application_id: Uuid) {
+    sqlx::query("UPDATE job_applications SET status = 'withdrawn' WHERE id = $1")
+        .bind(application_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+#[sqlx::test]
+async fn test_transfer_moves_applications_and_recounts(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_company_with_owner().await;
+    let target = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&source).await;
+
+    let mut application_ids = Vec::new();
+    for _ in 0..3 {
+        let seeker = app.create_job_seeker().await;
+        application_ids.push(app.create_application(job_id, &seeker).await);
+    }
+    withdraw(&app, application_ids[2]).await;
+
+    sqlx::query(
+        "INSERT INTO application_notes (application_id, created_by, note_text) VALUES ($1, $2, 'Llamar el lunes')",
+    )
+    .bind(application_ids[0])
+    .bind(source.owner.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let tag_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO application_tags (company_id, name) VALUES ($1, 'bilingüe') RETURNING id",
+    )
+    .bind(source.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO application_tag_assignments (application_id, tag_id) VALUES ($1, $2)")
+        .bind(application_ids[0])
+        .bind(tag_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    // A drifted counter is corrected on the way
+    sqlx::query("UPDATE jobs SET applications_count = 99 WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let before = dashboard(&app, &source).await;
+    assert_eq!(before["active_jobs"], 1);
+
+    let res = transfer(&app, job_id, &target).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["from_company_id"], source.id.to_string());
+    assert_eq!(res.body["to_company_id"], target.id.to_string());
+    assert_eq!(res.body["applications_moved"], 3);
+
+    let (company_id, posted_by, applications_count): (Uuid, Uuid, i32) =
+        sqlx::query_as("SELECT company_id, posted_by, applications_count FROM jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(company_id, target.id);
+    assert_eq!(posted_by, target.owner.id);
+    assert_eq!(applications_count, 2);
+
+    let source_after = dashboard(&app, &source).await;
+    assert_eq!(source_after["active_jobs"], 0);
+    assert_eq!(source_after["total_applications"], 0);
+    let target_after = dashboard(&app, &target).await;
+    assert_eq!(target_after["active_jobs"], 1);
+    assert_eq!(
+        target_after["total_applications"],
+        before["total_applications"]
+    );
+
+    // Notes move with their applications; the old company's tags stay behind
+    let notes: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM application_notes WHERE application_id = $1")
+            .bind(application_ids[0])
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(notes, 1);
+    let tags: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM application_tag_assignments WHERE application_id = $1",
+    )
+    .bind(application_ids[0])
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(tags, 0);
+
+    let details: Value = sqlx::query_scalar(
+        "SELECT details FROM admin_audit_logs WHERE action_type = 'transfer_job' AND entity_id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(details["before"]["company_id"], source.id.to_string());
+    assert_eq!(details["before"]["applications_count"], 99);
+    assert_eq!(details["after"]["company_id"], target.id.to_string());
+    assert_eq!(details["after"]["posted_by"], target.owner.id.to_string());
+    assert_eq!(details["after"]["applications_count"], 2);
+    assert_eq!(details["notes_moved"], 1);
+    assert_eq!(details["tag_assignments_removed"], 1);
+}
+
+#[sqlx::test]
+async fn test_transfer_notifies_live_applicants(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_company_with_owner().await;
+    let target = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&source).await;
+    let empty_job_id = app.create_active_job(&source).await;
+
+    for i in 0..4 {
+        let seeker = app.create_job_seeker().await;
+        let application_id = app.create_application(job_id, &seeker).await;
+        if i == 0 {
+            withdraw(&app, application_id).await;
+        }
+    }
+
+    let res = transfer(&app, job_id, &target).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["applications_moved"], 4);
+    assert_eq!(res.body["applicants_notified"], 3);
+
+    let res = transfer(&app, empty_job_id, &target).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["applicants_notified"], 0);
+}
+
+#[sqlx::test]
+async fn test_transfer_blocked_cases(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_company_with_owner().await;
+    let target = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&source).await;
+
+    sqlx::query("UPDATE jobs SET status = 'pending_approval' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = transfer(&app, job_id, &target).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let company_id: Uuid = sqlx::query_scalar("SELECT company_id FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(company_id, source.id);
+
+    sqlx::query("UPDATE jobs SET status = 'active' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = transfer(&app, job_id, &source).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE company_profiles SET status = 'suspended' WHERE id = $1")
+        .bind(target.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = transfer(&app, job_id, &target).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .post(
+            &format!("/api/admin/jobs/{}/transfer", job_id),
+            Some(&source.owner),
+            json!({ "company_id": target.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,347 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn update(app: &TestApp, company: &TestCompany, job_id: Uuid, body: Value) -> Value {
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}", job_id),
+            Some(&company.owner),
+            body,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+async fn count(app: &TestApp, table: &str, job_id: Uuid) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {} WHERE job_id = $1", table))
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_empty_lists_clear_and_omitted_lists_stay(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let skills: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 2")
+        .fetch_all(app.db())
+        .await
+        .unwrap();
+    let language: Uuid = sqlx::query_scalar("SELECT id FROM languages ORDER BY name LIMIT 1")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+
+    let job = update(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "title": "Bodeguero con experiencia",
+            "required_skills": [{ "skill_id": skills[0], "minimum_proficiency": 3 }],
+            "preferred_skills": [skills[1]],
+            "required_languages": [{ "language_id": language, "minimum_proficiency": 2 }],
+            "disability_accommodations": ["visual"],
+        }),
+    )
+    .await;
+    assert_eq!(job["title"], "Bodeguero con experiencia");
+    for table in [
+        "job_required_skills",
+        "job_preferred_skills",
+        "job_required_languages",
+        "job_disability_accommodations",
+    ] {
+        assert_eq!(count(&app, table, job_id).await, 1, "{}", table);
+    }
+
+    // Other fields leave the lists alone
+    let job = update(&app, &company, job_id, json!({ "vacancies": 3 })).await;
+    assert_eq!(job["vacancies"], 3);
+    assert_eq!(job["title"], "Bodeguero con experiencia");
+    assert_eq!(count(&app, "job_required_skills", job_id).await, 1);
+    assert_eq!(count(&app, "job_required_languages", job_id).await, 1);
+
+    update(
+        &app,
+        &company,
+        job_id,
+        json!({ "required_skills": [], "preferred_skills": [] }),
+    )
+    .await;
+    assert_eq!(count(&app, "job_required_skills", job_id).await, 0);
+    assert_eq!(count(&app, "job_preferred_skills", job_id).await, 0);
+    assert_eq!(count(&app, "job_required_languages", job_id).await, 1);
+    assert_eq!(
+        count(&app, "job_disability_accommodations", job_id).await,
+        1
+    );
+
+    // Company members without a managing role can't edit
+    let member = app.create_user(UserType::CompanyMember).await;
+    sqlx::query(
+        "INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'member')",
+    )
+    .bind(company.id)
+    .bind(member.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}", job_id),
+            Some(&member),
+            json!({ "title": "Otro" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+async fn review_edited_jobs(app: &TestApp) {
+    let admin = app.create_admin().await;
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [{ "key": "review_edited_jobs", "value": true }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_edited_active_jobs_go_back_to_review_when_configured(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Off by default
+    let job = update(&app, &company, job_id, json!({ "title": "Cajero" })).await;
+    assert_eq!(job["status"], "active");
+
+    review_edited_jobs(&app).await;
+
+    // Resending what the job already has doesn't count
+    let job = update(&app, &company, job_id, json!({ "title": "Cajero" })).await;
+    assert_eq!(job["status"], "active");
+
+    // Content that fails screening is refused and the job stays live
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}", job_id),
+            Some(&company.owner),
+            json!({ "description": "Enviar CV a seleccion@empresa.cl" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY, "{}", res.body);
+    let status: String = sqlx::query_scalar("SELECT status::text FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(status, "active");
+
+    let job = update(
+        &app,
+        &company,
+        job_id,
+        json!({ "description": "Atención de caja y reposición en sala de ventas" }),
+    )
+    .await;
+    assert_eq!(job["status"], "pending_approval");
+    let res = app.get("/api/admin/jobs/pending", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|pending| pending["id"] == job_id.to_string()));
+    let res = app.get(&format!("/api/jobs/{}", job_id), None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // A job already waiting for review stays there, screened again
+    let job = update(
+        &app,
+        &company,
+        job_id,
+        json!({ "title": "Cajero part time" }),
+    )
+    .await;
+    assert_eq!(job["status"], "pending_approval");
+}
+
+#[sqlx::test]
+async fn test_ranges_are_checked_against_the_merged_job(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let uri = format!("/api/me/jobs/{}", job_id);
+
+    update(
+        &app,
+        &company,
+        job_id,
+        json!({ "salary_min": "500000", "age_min": 30, "years_experience_min": 2 }),
+    )
+    .await;
+
+    // Each edit sets only one end of a range
+    for body in [
+        json!({ "salary_max": "400000" }),
+        json!({ "age_max": 25 }),
+        json!({ "years_experience_max": 1 }),
+        json!({ "application_deadline": "2000-01-01" }),
+    ] {
+        let res = app.put(&uri, Some(&company.owner), body.clone()).await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    let job = update(&app, &company, job_id, json!({ "salary_max": "600000" })).await;
+    assert_eq!(job["salary_max"], "600000.00");
+}
+
+#[sqlx::test]
+async fn test_edits_outside_screened_content_also_go_back_to_review(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let salary_job = app.create_active_job(&company).await;
+    let skills_job = app.create_active_job(&company).await;
+    review_edited_jobs(&app).await;
+
+    let job = update(
+        &app,
+        &company,
+        salary_job,
+        json!({ "salary_min": "650000" }),
+    )
+    .await;
+    assert_eq!(job["status"], "pending_approval");
+
+    let skill: Uuid = sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 1")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    let job = update(
+        &app,
+        &company,
+        skills_job,
+        json!({ "preferred_skills": [skill] }),
+    )
+    .await;
+    assert_eq!(job["status"], "pending_approval");
+}
+
+#[sqlx::test]
+async fn test_published_deadline_is_only_changed_through_its_endpoint(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let uri = format!("/api/me/jobs/{}", job_id);
+
+    let current: chrono::NaiveDate =
+        sqlx::query_scalar("SELECT application_deadline FROM jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "application_deadline": "2099-12-31" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+    assert!(res.body.to_string().contains("/deadline"), "{}", res.body);
+
+    // Resending the current one is fine
+    update(
+        &app,
+        &company,
+        job_id,
+        json!({ "application_deadline": current, "vacancies": 3 }),
+    )
+    .await;
+
+    // Unpublished jobs still set it with the rest of the posting
+    sqlx::query("UPDATE jobs SET status = 'draft', published_at = NULL WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let job = update(
+        &app,
+        &company,
+        job_id,
+        json!({ "application_deadline": "2099-12-31" }),
+    )
+    .await;
+    assert_eq!(job["application_deadline"], "2099-12-31");
+}
+
+#[sqlx::test]
+async fn test_edits_waiting_for_review_are_screened_and_checked_for_duplicates(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let live_id = app.create_active_job(&company).await;
+    let job_id = app.create_active_job(&company).await;
+    update(
+        &app,
+        &company,
+        job_id,
+        json!({ "title": "Cajero", "description": "Atención de caja en sala de ventas" }),
+    )
+    .await;
+    sqlx::query("UPDATE jobs SET status = 'pending_approval' WHERE id = $1")
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let uri = format!("/api/me/jobs/{}", job_id);
+
+    let res = app
+        .put(
+            &uri,
+            Some(&company.owner),
+            json!({ "description": "Enviar CV a seleccion@empresa.cl" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY, "{}", res.body);
+
+    // Renaming it to the live job's title makes it a duplicate of that job
+    let res = app
+        .put(&uri, Some(&company.owner), json!({ "title": "Test job" }))
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+    assert_eq!(
+        res.body["details"]["duplicates"][0]["job_id"],
+        live_id.to_string()
+    );
+
+    let job = update(
+        &app,
+        &company,
+        job_id,
+        json!({ "title": "Test job", "force": true }),
+    )
+    .await;
+    assert_eq!(job["status"], "pending_approval");
+    let overridden: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_duplicate_overrides WHERE job_id = $1")
+            .bind(job_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(overridden, 1);
+}
@@ -1,226 +1,200 @@
 mod common;
 
-use empleos_inclusivos_backend::{handlers, AppState};
-use axum::{
-    body::Body,
-    http::{Request, StatusCode},
-    Router,
-    routing::get,
-};
-use tower::ServiceExt;
-
-async fn create_test_app(pool: sqlx::PgPool) -> Router {
-    let state = AppState { db: pool };
-
-    Router::new()
-        .route("/jobs", get(handlers::list_jobs))
-        .route("/jobs/:id", get(handlers::get_job))
-        .with_state(state)
-}
-
-#[tokio::test]
-async fn test_list_jobs_empty() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs")
-                .body(Body::empty())
-                .unwrap()
-        )
+use axum::http::StatusCode;
+use common::TestApp;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn set_title(app: &TestApp, job_id: Uuid, title: &str) {
+    sqlx::query("UPDATE jobs SET title = $1 WHERE id = $2")
+        .bind(title)
+        .bind(job_id)
+        .execute(app.db())
         .await
         .unwrap();
-
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
-
-    assert_eq!(job_list["total"], 0);
-    assert_eq!(job_list["jobs"].as_array().unwrap().len(), 0);
 }
 
-#[tokio::test]
-async fn test_list_jobs_with_data() {
-    let pool = common::setup_test_db().await;
+#[sqlx::test]
+async fn test_list_jobs_empty(db: PgPool) {
+    let app = TestApp::new(db).await;
 
-    // Create test data
-    let company_id = common::create_test_company(&pool).await;
-    common::create_test_job(&pool, company_id, "Backend Developer", 1).await;
-    common::create_test_job(&pool, company_id, "Frontend Developer", 1).await;
-
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs")
-                .body(Body::empty())
-                .unwrap()
-        )
-        .await
-        .unwrap();
+    let res = app.get("/api/jobs", None).await;
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
-
-    assert_eq!(job_list["total"], 2);
-    assert_eq!(job_list["jobs"].as_array().unwrap().len(), 2);
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 0);
+    assert!(res.body["jobs"].as_array().unwrap().is_empty());
 }
 
-#[tokio::test]
-async fn test_list_jobs_with_category_filter() {
-    let pool = common::setup_test_db().await;
+#[sqlx::test]
+async fn test_list_jobs_with_data(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    app.create_active_job(&company).await;
+    app.create_active_job(&company).await;
 
-    let company_id = common::create_test_company(&pool).await;
-    common::create_test_job(&pool, company_id, "Tech Job", 1).await;
-    common::create_test_job(&pool, company_id, "Sales Job", 2).await;
+    let res = app.get("/api/jobs", None).await;
 
-    let app = create_test_app(pool).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 2);
+    assert_eq!(res.body["jobs"].as_array().unwrap().len(), 2);
+}
 
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs?category_id=1")
-                .body(Body::empty())
-                .unwrap()
-        )
+#[sqlx::test]
+async fn test_list_jobs_hides_inactive(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    app.create_active_job(&company).await;
+    let draft = app.create_active_job(&company).await;
+    sqlx::query("UPDATE jobs SET status = 'draft' WHERE id = $1")
+        .bind(draft)
+        .execute(app.db())
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let res = app.get("/api/jobs", None).await;
+    assert_eq!(res.body["total"], 1);
 
-    assert_eq!(job_list["total"], 1);
-    assert_eq!(job_list["jobs"][0]["title"], "Tech Job");
+    let res = app.get(&format!("/api/jobs/{}", draft), None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
 }
 
-#[tokio::test]
-async fn test_list_jobs_with_search() {
-    let pool = common::setup_test_db().await;
-
-    let company_id = common::create_test_company(&pool).await;
-    common::create_test_job(&pool, company_id, "Rust Developer", 1).await;
-    common::create_test_job(&pool, company_id, "Python Developer", 1).await;
-
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs?search=Rust")
-                .body(Body::empty())
-                .unwrap()
-        )
-        .await
-        .unwrap();
+#[sqlx::test]
+async fn test_list_jobs_with_search(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let rust_job = app.create_active_job(&company).await;
+    let python_job = app.create_active_job(&company).await;
+    set_title(&app, rust_job, "Rust Developer").await;
+    set_title(&app, python_job, "Python Developer").await;
+
+    let res = app.get("/api/jobs?search=Rust", None).await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    let jobs = res.body["jobs"].as_array().unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["title"], "Rust Developer");
+}
 
-    assert_eq!(response.status(), StatusCode::OK);
+#[sqlx::test]
+async fn test_get_job_success(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let res = app.get(&format!("/api/jobs/{}", job_id), None).await;
 
-    assert_eq!(job_list["total"], 1);
-    assert_eq!(job_list["jobs"][0]["title"], "Rust Developer");
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["id"], job_id.to_string());
+    assert_eq!(res.body["title"], "Test job");
 }
 
-#[tokio::test]
-async fn test_get_job_success() {
-    let pool = common::setup_test_db().await;
-
-    let company_id = common::create_test_company(&pool).await;
-    let job_id = common::create_test_job(&pool, company_id, "Test Job", 1).await;
+#[sqlx::test]
+async fn test_get_job_not_found(db: PgPool) {
+    let app = TestApp::new(db).await;
 
-    let app = create_test_app(pool).await;
+    let res = app
+        .get(&format!("/api/jobs/{}", Uuid::new_v4()), None)
+        .await;
 
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri(&format!("/jobs/{}", job_id))
-                .body(Body::empty())
-                .unwrap()
-        )
-        .await
-        .unwrap();
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
 
-    assert_eq!(response.status(), StatusCode::OK);
+#[sqlx::test]
+async fn test_list_jobs_pagination(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    for _ in 0..15 {
+        app.create_active_job(&company).await;
+    }
 
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let res = app.get("/api/jobs?page=1&per_page=10", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 15);
+    assert_eq!(res.body["total_pages"], 2);
+    assert_eq!(res.body["jobs"].as_array().unwrap().len(), 10);
 
-    assert_eq!(job["title"], "Test Job");
-    assert_eq!(job["company_name"], "Test Company");
+    let res = app.get("/api/jobs?page=2&per_page=10", None).await;
+    assert_eq!(res.body["jobs"].as_array().unwrap().len(), 5);
 }
 
-#[tokio::test]
-async fn test_get_job_not_found() {
-    let pool = common::setup_test_db().await;
-    let app = create_test_app(pool).await;
-
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs/99999")
-                .body(Body::empty())
-                .unwrap()
+#[sqlx::test]
+async fn test_list_jobs_cursor_pagination(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let mut expected = Vec::new();
+    for hours_ago in 1..=5 {
+        let job_id = app.create_active_job(&company).await;
+        sqlx::query(
+            "UPDATE jobs SET published_at = NOW() - make_interval(hours => $2) WHERE id = $1",
         )
+        .bind(job_id)
+        .bind(hours_ago)
+        .execute(app.db())
         .await
         .unwrap();
-
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
-}
-
-#[tokio::test]
-async fn test_list_jobs_pagination() {
-    let pool = common::setup_test_db().await;
-
-    let company_id = common::create_test_company(&pool).await;
-    for i in 1..=25 {
-        common::create_test_job(&pool, company_id, &format!("Job {}", i), 1).await;
+        expected.push(job_id.to_string());
     }
-
-    let app = create_test_app(pool).await;
-
-    // Test first page
-    let response = app
-        .clone()
-        .oneshot(
-            Request::builder()
-                .uri("/jobs?page=1&per_page=10")
-                .body(Body::empty())
-                .unwrap()
-        )
+    // Featured jobs come first however old
+    sqlx::query("UPDATE jobs SET is_featured = true WHERE id = $1")
+        .bind(Uuid::parse_str(&expected[4]).unwrap())
+        .execute(app.db())
         .await
         .unwrap();
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
-
-    assert_eq!(job_list["total"], 25);
-    assert_eq!(job_list["jobs"].as_array().unwrap().len(), 10);
-    assert_eq!(job_list["page"], 1);
-    assert_eq!(job_list["total_pages"], 3);
-
-    // Test second page
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/jobs?page=2&per_page=10")
-                .body(Body::empty())
-                .unwrap()
+    expected.rotate_right(1);
+
+    let page_ids = |body: &serde_json::Value| -> Vec<String> {
+        body["jobs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let res = app.get("/api/jobs?per_page=2", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let mut seen = page_ids(&res.body);
+    let mut cursor = res.body["next_cursor"].as_str().unwrap().to_string();
+
+    // Published between page fetches: sorts before the cursor, so it neither
+    // shifts the following pages nor shows up in them
+    let newest = app.create_active_job(&company).await;
+
+    loop {
+        let res = app
+            .get(&format!("/api/jobs?per_page=2&cursor={}", cursor), None)
+            .await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+        seen.extend(page_ids(&res.body));
+        match res.body["next_cursor"].as_str() {
+            Some(next) => cursor = next.to_string(),
+            None => break,
+        }
+    }
+    assert_eq!(seen, expected);
+    assert!(!seen.contains(&newest.to_string()));
+
+    // The last page has no next cursor, even when it's full
+    let res = app.get("/api/jobs?per_page=6", None).await;
+    assert_eq!(res.body["jobs"].as_array().unwrap().len(), 6);
+    assert!(res.body["next_cursor"].is_null());
+
+    let res = app.get("/api/jobs?cursor=bogus", None).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let municipality: Uuid =
+        sqlx::query_scalar("SELECT id FROM municipalities WHERE latitude IS NOT NULL LIMIT 1")
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    let res = app
+        .get(
+            &format!(
+                "/api/jobs?near_municipality_id={}&cursor={}",
+                municipality, cursor
+            ),
+            None,
         )
-        .await
-        .unwrap();
-
-    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let job_list: serde_json::Value = serde_json::from_slice(&body).unwrap();
-
-    assert_eq!(job_list["page"], 2);
-    assert_eq!(job_list["jobs"].as_array().unwrap().len(), 10);
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
 }
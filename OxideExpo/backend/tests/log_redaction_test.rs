@@ -0,0 +1,114 @@
+mod common;
+
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil};
+use empleos_inclusivos_backend::error::AppError;
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::Level;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Log output written while the subscriber it was given to is the default
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn take(&self) -> String {
+        String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+    }
+}
+
+impl io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// A failed login, an OMIL seeker search by email and a duplicate-key
+/// database error logged with both Display and Debug, all involving `email`
+async fn log_activity(app: &TestApp, omil: &TestOmil, email: &str) {
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": email, "password": "WrongPassword1" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers?search={}", email),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let err = sqlx::query(
+        "INSERT INTO users (email, password_hash, first_name, last_name, user_type) VALUES ($1, 'x', 'A', 'B', 'job_seeker')",
+    )
+    .bind(email)
+    .execute(app.db())
+    .await
+    .unwrap_err();
+    let err = AppError::from(err);
+    tracing::error!("{}", err);
+    tracing::error!("{:?}", err);
+}
+
+#[sqlx::test]
+async fn test_logs_mask_personal_data_unless_enabled(db: PgPool) {
+    let logs = CapturedLogs::default();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::fmt()
+            .with_max_level(Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(logs.clone())
+            .finish(),
+    );
+
+    let app = TestApp::with_config(db.clone(), |config| config.log_pii = false).await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+
+    log_activity(&app, &omil, &seeker.email).await;
+    let output = logs.take();
+
+    let (local, domain) = seeker.email.split_once('@').unwrap();
+    let masked = format!("{}***@{}", &local[..1], domain);
+    assert!(!output.contains(&seeker.email), "{}", output);
+    assert!(!output.contains(local), "{}", output);
+    assert!(output.contains(&masked), "{}", output);
+    assert!(output.contains("search=[REDACTED]"), "{}", output);
+    assert!(output.contains("users_email_key"), "{}", output);
+    assert!(!output.contains("Key (email)"), "{}", output);
+
+    // With PII logging on, the same activity is logged as is
+    let app = TestApp::with_config(db, |config| config.log_pii = true).await;
+    log_activity(&app, &omil, &seeker.email).await;
+    let output = logs.take();
+
+    assert!(output.contains(&format!("search={}", seeker.email)), "{}", output);
+    assert!(output.contains(&format!("Failed login for {}", seeker.email)), "{}", output);
+    // Postgres echoes the duplicate value back; that detail is never logged
+    assert!(!output.contains("Key (email)"), "{}", output);
+    assert!(output.contains("users_email_key"), "{}", output);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, TestResponse, TestUser, TEST_PASSWORD};
+use serde_json::json;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+
+/// The mode is cached under a fixed Redis key shared by every test, so tests
+/// that switch it take turns
+static MAINTENANCE: Mutex<()> = Mutex::const_new(());
+
+async fn set_mode(app: &TestApp, admin: &TestUser, mode: &str, message: &str) {
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(admin),
+            json!({ "settings": [
+                { "key": "maintenance_mode", "value": mode },
+                { "key": "maintenance_message", "value": message }
+            ] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+async fn login(app: &TestApp, user: &TestUser) -> TestResponse {
+    app.post(
+        "/api/auth/login",
+        None,
+        json!({ "email": user.email, "password": TEST_PASSWORD }),
+    )
+    .await
+}
+
+async fn update_preferences(app: &TestApp, user: &TestUser) -> TestResponse {
+    app.put(
+        "/api/me/preferences",
+        Some(user),
+        json!({ "show_references": false }),
+    )
+    .await
+}
+
+fn assert_maintenance(res: &TestResponse, mode: &str) {
+    assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE, "{}", res.body);
+    assert_eq!(res.body["code"], "MAINTENANCE");
+    assert_eq!(res.body["mode"], mode);
+}
+
+#[sqlx::test]
+async fn test_read_only_blocks_writes(db: PgPool) {
+    let _turn = MAINTENANCE.lock().await;
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+
+    assert_eq!(
+        update_preferences(&app, &seeker).await.status,
+        StatusCode::OK
+    );
+
+    set_mode(&app, &admin, "read_only", "Volvemos a las 22:00").await;
+
+    let res = update_preferences(&app, &seeker).await;
+    assert_maintenance(&res, "read_only");
+    assert_eq!(res.body["message"], "Volvemos a las 22:00");
+    let res = app
+        .request(
+            Method::DELETE,
+            "/api/me/references/00000000-0000-0000-0000-000000000000",
+            Some(&seeker),
+            None,
+        )
+        .await;
+    assert_maintenance(&res, "read_only");
+
+    // Reads, and starting, renewing and ending sessions, keep working
+    assert_eq!(app.get("/api/jobs", None).await.status, StatusCode::OK);
+    assert_eq!(
+        app.get("/api/me/preferences", Some(&seeker)).await.status,
+        StatusCode::OK
+    );
+    let session = login(&app, &seeker).await;
+    assert_eq!(session.status, StatusCode::OK, "{}", session.body);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": session.body["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app.post("/api/auth/logout", Some(&seeker), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/health/ready", None).await;
+    assert_eq!(res.body["maintenance_mode"], "read_only");
+
+    // Switching it off takes effect straight away
+    set_mode(&app, &admin, "off", "").await;
+    let other = app.create_job_seeker().await;
+    assert_eq!(
+        update_preferences(&app, &other).await.status,
+        StatusCode::OK
+    );
+    let res = app.get("/api/health/ready", None).await;
+    assert_eq!(res.body["maintenance_mode"], "off");
+}
+
+#[sqlx::test]
+async fn test_full_blocks_everything_but_health_and_settings(db: PgPool) {
+    let _turn = MAINTENANCE.lock().await;
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seeker = app.create_job_seeker().await;
+
+    set_mode(&app, &admin, "full", "").await;
+
+    let res = app.get("/api/jobs", None).await;
+    assert_maintenance(&res, "full");
+    // No operator message, no message field
+    assert!(res.body.get("message").is_none());
+    assert_maintenance(&app.get("/api/me/preferences", Some(&seeker)).await, "full");
+    assert_maintenance(&update_preferences(&app, &seeker).await, "full");
+    // Admins don't bypass a full outage
+    assert_maintenance(
+        &app.get("/api/admin/dashboard/stats", Some(&admin)).await,
+        "full",
+    );
+
+    assert_eq!(app.get("/api/health", None).await.status, StatusCode::OK);
+    let res = app.get("/api/health/ready", None).await;
+    assert_eq!(res.body["maintenance_mode"], "full");
+    let res = app.get("/api/admin/settings", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // The settings endpoint still checks who's calling
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&seeker),
+            json!({ "settings": [{ "key": "maintenance_mode", "value": "off" }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    set_mode(&app, &admin, "off", "").await;
+    assert_eq!(app.get("/api/jobs", None).await.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_admins_can_sign_in_during_full_maintenance(db: PgPool) {
+    let _turn = MAINTENANCE.lock().await;
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    set_mode(&app, &admin, "full", "").await;
+
+    // An admin whose session ran out gets back in to switch it off
+    let session = login(&app, &admin).await;
+    assert_eq!(session.status, StatusCode::OK, "{}", session.body);
+    let res = app
+        .post(
+            "/api/auth/refresh",
+            None,
+            json!({ "refresh_token": session.body["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let renewed = TestUser {
+        id: admin.id,
+        email: admin.email.clone(),
+        user_type: admin.user_type,
+        token: res.body["access_token"].as_str().unwrap().to_string(),
+    };
+
+    set_mode(&app, &renewed, "off", "").await;
+    assert_eq!(app.get("/api/jobs", None).await.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_admins_bypass_read_only(db: PgPool) {
+    let _turn = MAINTENANCE.lock().await;
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    set_mode(&app, &admin, "read_only", "").await;
+
+    let uri = "/api/admin/maintenance/recount-applications";
+    let res = app.post(uri, Some(&admin), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Other users, and requests without a valid token, are still turned away
+    assert_maintenance(
+        &app.post(uri, Some(&company.owner), json!({})).await,
+        "read_only",
+    );
+    assert_maintenance(&app.post(uri, None, json!({})).await, "read_only");
+    let forged = TestUser {
+        id: admin.id,
+        email: admin.email.clone(),
+        user_type: admin.user_type,
+        token: "not-a-token".to_string(),
+    };
+    assert_maintenance(&app.post(uri, Some(&forged), json!({})).await, "read_only");
+
+    set_mode(&app, &admin, "off", "").await;
+}
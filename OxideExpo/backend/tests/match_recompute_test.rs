@@ -0,0 +1,282 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::matching;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn skills(app: &TestApp) -> Vec<Uuid> {
+    sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 2")
+        .fetch_all(app.db())
+        .await
+        .unwrap()
+}
+
+async fn update_job(app: &TestApp, company: &TestCompany, job_id: Uuid, body: Value) {
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}", job_id),
+            Some(&company.owner),
+            body,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+async fn recompute_status(app: &TestApp, company: &TestCompany, job_id: Uuid) -> Value {
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/match-recompute-status", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+/// Cached score of the seeker for the job, computed a day ago
+async fn scored_before(app: &TestApp, job_id: Uuid, seeker: &TestUser) {
+    sqlx::query(
+        r#"
+        INSERT INTO job_match_scores (job_id, user_id, total_score, computed_at)
+        VALUES ($1, $2, 50, NOW() - INTERVAL '1 day')
+        "#,
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+/// Whether the seeker's cached score for the job is fresh
+async fn is_fresh(app: &TestApp, job_id: Uuid, seeker: &TestUser) -> bool {
+    sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM job_match_scores
+            WHERE job_id = $1 AND user_id = $2 AND NOT is_stale
+            AND computed_at > NOW() - INTERVAL '1 hour'
+        )
+        "#,
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_only_requirement_changes_queue_a_rescore(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    app.create_application(job_id, &seeker).await;
+    let skills = skills(&app).await;
+
+    update_job(
+        &app,
+        &company,
+        job_id,
+        json!({ "required_skills": [{ "skill_id": skills[0], "minimum_proficiency": 3 }] }),
+    )
+    .await;
+    assert_eq!(
+        recompute_status(&app, &company, job_id).await["state"],
+        "queued"
+    );
+    assert_eq!(matching::process_recalculations(app.db()).await.unwrap(), 1);
+    assert!(is_fresh(&app, job_id, &seeker).await);
+
+    // Wording and benefits, or the same skills sent again, change no score
+    update_job(
+        &app,
+        &company,
+        job_id,
+        json!({
+            "title": "Operario de bodega",
+            "benefits": "Casino y bono de movilización",
+            "required_skills": [{ "skill_id": skills[0], "minimum_proficiency": 3 }],
+        }),
+    )
+    .await;
+    let status = recompute_status(&app, &company, job_id).await;
+    assert_eq!(status["state"], "idle");
+    assert!(is_fresh(&app, job_id, &seeker).await);
+    let title: String = sqlx::query_scalar("SELECT title FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(title, "Operario de bodega");
+
+    for change in [
+        json!({ "required_skills": [{ "skill_id": skills[0], "minimum_proficiency": 4 }] }),
+        json!({ "preferred_skills": [skills[1]] }),
+        json!({ "years_experience_min": 2 }),
+        json!({ "education_level": "technical" }),
+        json!({ "is_remote_allowed": true }),
+        json!({ "disability_accommodations": ["visual"] }),
+    ] {
+        update_job(&app, &company, job_id, change.clone()).await;
+        let status = recompute_status(&app, &company, job_id).await;
+        assert_eq!(status["state"], "queued", "{}", change);
+        assert_eq!(status["requirements_changed"], true);
+        assert!(!is_fresh(&app, job_id, &seeker).await);
+        matching::process_recalculations(app.db()).await.unwrap();
+    }
+
+    // Another company's job can't be edited
+    let other = app.create_company_with_owner().await;
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}", job_id),
+            Some(&other.owner),
+            json!({ "title": "Otro" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_rescore_goes_first_to_applicants_and_savers(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let skills = skills(&app).await;
+
+    let applicant = app.create_job_seeker().await;
+    app.create_application(job_id, &applicant).await;
+    let saver = app.create_job_seeker().await;
+    sqlx::query("INSERT INTO saved_jobs (user_id, job_id) VALUES ($1, $2)")
+        .bind(saver.id)
+        .bind(job_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let scored = [app.create_job_seeker().await, app.create_job_seeker().await];
+    for seeker in &scored {
+        scored_before(&app, job_id, seeker).await;
+    }
+    let unrelated = app.create_job_seeker().await;
+
+    // A rescore a company asked for earlier waits behind the requirement change
+    let other_job = app.create_active_job(&company).await;
+    let other_applicant = app.create_job_seeker().await;
+    app.create_application(other_job, &other_applicant).await;
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/match-scores/recalculate", other_job),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+
+    update_job(
+        &app,
+        &company,
+        job_id,
+        json!({ "required_skills": [{ "skill_id": skills[0], "minimum_proficiency": 2 }] }),
+    )
+    .await;
+
+    assert_eq!(
+        matching::process_recalculation_batch(app.db(), 2)
+            .await
+            .unwrap(),
+        Some(false)
+    );
+    assert!(is_fresh(&app, job_id, &applicant).await);
+    assert!(is_fresh(&app, job_id, &saver).await);
+    for seeker in &scored {
+        assert!(!is_fresh(&app, job_id, seeker).await);
+    }
+    assert!(!is_fresh(&app, other_job, &other_applicant).await);
+
+    assert_eq!(
+        matching::process_recalculation_batch(app.db(), 2)
+            .await
+            .unwrap(),
+        Some(true)
+    );
+    for seeker in &scored {
+        assert!(is_fresh(&app, job_id, seeker).await);
+    }
+    assert!(!is_fresh(&app, job_id, &unrelated).await);
+
+    // Then the company's own request
+    assert_eq!(
+        matching::process_recalculation_batch(app.db(), 2)
+            .await
+            .unwrap(),
+        Some(true)
+    );
+    assert!(is_fresh(&app, other_job, &other_applicant).await);
+    assert_eq!(
+        matching::process_recalculation_batch(app.db(), 2)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[sqlx::test]
+async fn test_status_follows_the_batches(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let skills = skills(&app).await;
+    for _ in 0..5 {
+        let seeker = app.create_job_seeker().await;
+        app.create_application(job_id, &seeker).await;
+    }
+
+    let status = recompute_status(&app, &company, job_id).await;
+    assert_eq!(status["state"], "idle");
+    assert_eq!(status["requested_at"], json!(null));
+
+    update_job(
+        &app,
+        &company,
+        job_id,
+        json!({ "required_skills": [{ "skill_id": skills[1], "minimum_proficiency": 1 }] }),
+    )
+    .await;
+    let status = recompute_status(&app, &company, job_id).await;
+    assert_eq!(status["state"], "queued");
+    assert_eq!(status["total_seekers"], json!(null));
+    assert_eq!(status["processed_seekers"], 0);
+
+    for processed in [2, 4] {
+        matching::process_recalculation_batch(app.db(), 2)
+            .await
+            .unwrap();
+        let status = recompute_status(&app, &company, job_id).await;
+        assert_eq!(status["state"], "running");
+        assert_eq!(status["total_seekers"], 5);
+        assert_eq!(status["processed_seekers"], processed);
+        assert!(status["started_at"].is_string());
+    }
+
+    matching::process_recalculation_batch(app.db(), 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        recompute_status(&app, &company, job_id).await["state"],
+        "idle"
+    );
+
+    let other = app.create_company_with_owner().await;
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/match-recompute-status", job_id),
+            Some(&other.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
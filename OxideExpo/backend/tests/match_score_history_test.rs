@@ -0,0 +1,217 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn match_score(app: &TestApp, job_id: Uuid, seeker: &TestUser) -> i64 {
+    let res = app
+        .get(&format!("/api/jobs/{}/match-score", job_id), Some(seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["match_score"].as_i64().unwrap()
+}
+
+async fn history(app: &TestApp, job_id: Uuid, seeker: &TestUser) -> Vec<Value> {
+    let res = app
+        .get(
+            &format!("/api/jobs/{}/match-score/history", job_id),
+            Some(seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["points"].as_array().unwrap().clone()
+}
+
+async fn history_len(app: &TestApp, job_id: Uuid, seeker: &TestUser) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM job_match_score_history WHERE job_id = $1 AND user_id = $2",
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn saved_entry(app: &TestApp, seeker: &TestUser) -> Value {
+    let res = app.get("/api/me/saved-jobs", Some(seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body["saved_jobs"][0].clone()
+}
+
+/// Active job requiring one skill, and that skill's ID
+async fn job_requiring_skill(app: &TestApp) -> (Uuid, Uuid) {
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let skill_id: Uuid = sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 1")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1, $2, 3)",
+    )
+    .bind(job_id)
+    .bind(skill_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    (job_id, skill_id)
+}
+
+#[sqlx::test]
+async fn test_history_records_only_real_changes(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (job_id, skill_id) = job_requiring_skill(&app).await;
+    let seeker = app.create_job_seeker().await;
+
+    // The first score is the baseline; recomputing the same score adds nothing
+    let before = match_score(&app, job_id, &seeker).await;
+    match_score(&app, job_id, &seeker).await;
+    assert_eq!(history_len(&app, job_id, &seeker).await, 1);
+
+    // Within the threshold of the last point nothing is recorded
+    let set_last_point = |total: i64| {
+        sqlx::query("UPDATE job_match_score_history SET total_score = $3 WHERE job_id = $1 AND user_id = $2")
+            .bind(job_id)
+            .bind(seeker.id)
+            .bind(total as i32)
+            .execute(app.db())
+    };
+    set_last_point(before - 2).await.unwrap();
+    match_score(&app, job_id, &seeker).await;
+    assert_eq!(history_len(&app, job_id, &seeker).await, 1);
+    set_last_point(before - 3).await.unwrap();
+    match_score(&app, job_id, &seeker).await;
+    assert_eq!(history_len(&app, job_id, &seeker).await, 2);
+
+    // Adding the required skill moves the score, and the skills component most
+    let res = app
+        .post(
+            "/api/me/skills",
+            Some(&seeker),
+            json!({ "skill_id": skill_id, "proficiency_level": 4 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let after = match_score(&app, job_id, &seeker).await;
+    assert!(after > before + 2, "{} -> {}", before, after);
+
+    let points = history(&app, job_id, &seeker).await;
+    assert_eq!(points.len(), 3);
+    assert!(points[0]["biggest_change"].is_null());
+    assert_eq!(points[2]["total_score"], after);
+    assert_eq!(points[2]["biggest_change"]["component"], "skills");
+    assert!(points[2]["biggest_change"]["change"].as_i64().unwrap() > 0);
+
+    // Seekers only see their own history
+    let other = app.create_job_seeker().await;
+    assert!(history(&app, job_id, &other).await.is_empty());
+    let company = app.create_company_with_owner().await;
+    let res = app
+        .get(
+            &format!("/api/jobs/{}/match-score/history", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    let res = app
+        .get(
+            &format!("/api/jobs/{}/match-score/history", Uuid::new_v4()),
+            Some(&seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_history_is_capped_with_oldest_pruned(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (job_id, skill_id) = job_requiring_skill(&app).await;
+    let seeker = app.create_job_seeker().await;
+    let before = match_score(&app, job_id, &seeker).await;
+
+    // A long history from earlier days
+    sqlx::query(
+        r#"
+        INSERT INTO job_match_score_history (
+            job_id, user_id, total_score, skills_score, languages_score, location_score,
+            experience_score, education_score, preferred_skills_score, accommodations_score,
+            recorded_at
+        )
+        SELECT $1, $2, $3, 0, 0, 0, 0, 0, 0, 0, NOW() - make_interval(days => n)
+        FROM generate_series(1, 60) AS n
+        "#,
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .bind(before as i32)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    app.post(
+        "/api/me/skills",
+        Some(&seeker),
+        json!({ "skill_id": skill_id, "proficiency_level": 4 }),
+    )
+    .await;
+    let after = match_score(&app, job_id, &seeker).await;
+
+    assert_eq!(history_len(&app, job_id, &seeker).await, 50);
+    let points = history(&app, job_id, &seeker).await;
+    assert_eq!(points.last().unwrap()["total_score"], after);
+    let oldest_days: f64 = sqlx::query_scalar(
+        "SELECT EXTRACT(DAY FROM NOW() - MIN(recorded_at))::float8 FROM job_match_score_history WHERE job_id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(oldest_days, 48.0);
+}
+
+#[sqlx::test]
+async fn test_saved_job_score_improved_badge(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (job_id, skill_id) = job_requiring_skill(&app).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .post(
+            &format!("/api/me/saved-jobs/{}", job_id),
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let entry = saved_entry(&app, &seeker).await;
+    let at_save = entry["match_score_at_save"].as_i64().unwrap();
+    assert_eq!(entry["current_match_score"], at_save);
+    assert_eq!(entry["score_improved"], false);
+
+    app.post(
+        "/api/me/skills",
+        Some(&seeker),
+        json!({ "skill_id": skill_id, "proficiency_level": 4 }),
+    )
+    .await;
+    let improved = match_score(&app, job_id, &seeker).await;
+
+    let entry = saved_entry(&app, &seeker).await;
+    assert_eq!(entry["match_score_at_save"], at_save);
+    assert_eq!(entry["current_match_score"], improved);
+    assert_eq!(entry["score_improved"], true);
+
+    // A lower score than at save time is no improvement
+    sqlx::query("UPDATE saved_jobs SET match_score_at_save = $2 WHERE job_id = $1")
+        .bind(job_id)
+        .bind(improved as i32 + 5)
+        .execute(app.db())
+        .await
+        .unwrap();
+    assert_eq!(saved_entry(&app, &seeker).await["score_improved"], false);
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn breakdown(app: &TestApp, job_id: Uuid, seeker: &TestUser) -> Value {
+    let res = app
+        .get(&format!("/api/jobs/{}/match-score", job_id), Some(seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["match_score"],
+        res.body["score_breakdown"]["total_score"]
+    );
+    res.body["score_breakdown"].clone()
+}
+
+/// Component scores in breakdown order, then the total
+fn scores(breakdown: &Value) -> [i64; 7] {
+    let score = |component: &str| breakdown[component]["score"].as_i64().unwrap();
+    [
+        score("skills"),
+        score("languages"),
+        score("location"),
+        score("experience"),
+        score("education"),
+        score("accommodations"),
+        breakdown["total_score"].as_i64().unwrap(),
+    ]
+}
+
+/// Seeker with exactly five years of experience and nothing else scored
+async fn seeker(app: &TestApp) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query(
+        "UPDATE work_experiences SET start_date = CURRENT_DATE - INTERVAL '5 years' WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    seeker
+}
+
+async fn skill_ids(app: &TestApp) -> Vec<Uuid> {
+    sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 3")
+        .fetch_all(app.db())
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_job_without_requirements(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = seeker(&app).await;
+
+    // On-site job without a location, so only location is missed
+    let breakdown = breakdown(&app, job_id, &seeker).await;
+    assert_eq!(scores(&breakdown), [40, 15, 0, 15, 10, 5, 85]);
+
+    let max_scores: i64 = [
+        "skills",
+        "languages",
+        "location",
+        "experience",
+        "education",
+        "accommodations",
+    ]
+    .iter()
+    .map(|c| breakdown[c]["max_score"].as_i64().unwrap())
+    .sum();
+    assert_eq!(max_scores, 100);
+}
+
+#[sqlx::test]
+async fn test_job_with_partial_match(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = seeker(&app).await;
+    let skills = skill_ids(&app).await;
+
+    // Two required skills of which the seeker has one, one preferred skill
+    // the seeker has, and more experience and education than they have
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET is_remote_allowed = true, years_experience_min = 7, education_level = 'technical'
+        WHERE id = $1
+        "#,
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    for skill_id in &skills[..2] {
+        sqlx::query(
+            "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1, $2, 3)",
+        )
+        .bind(job_id)
+        .bind(skill_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    sqlx::query("INSERT INTO job_preferred_skills (job_id, skill_id) VALUES ($1, $2)")
+        .bind(job_id)
+        .bind(skills[2])
+        .execute(app.db())
+        .await
+        .unwrap();
+    for skill_id in [skills[0], skills[2]] {
+        sqlx::query(
+            "INSERT INTO user_skills (user_id, skill_id, proficiency_level) VALUES ($1, $2, 4)",
+        )
+        .bind(seeker.id)
+        .bind(skill_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+
+    let breakdown = breakdown(&app, job_id, &seeker).await;
+    assert_eq!(scores(&breakdown), [22, 15, 15, 7, 0, 5, 64]);
+    assert_eq!(breakdown["skills"]["preferred_score"], 5);
+
+    // The cache keeps preferred skills apart from the required ones
+    let cached: (i32, i32, i32) = sqlx::query_as(
+        "SELECT total_score, skills_score, preferred_skills_score FROM job_match_scores WHERE job_id = $1 AND user_id = $2",
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(cached, (64, 17, 5));
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::services::matching;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn detail_uri(job_id: Uuid, app_id: Uuid) -> String {
+    format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id)
+}
+
+async fn recalculate(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    app_id: Uuid,
+) -> common::TestResponse {
+    app.get(
+        &format!("{}?recalculate=true", detail_uri(job_id, app_id)),
+        Some(&company.owner),
+    )
+    .await
+}
+
+async fn first_listed(app: &TestApp, company: &TestCompany, job_id: Uuid) -> Value {
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_setting_hides_match_scores(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    // Nothing is cached until a score is computed
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["show_match_scores"], true);
+    assert_eq!(body["applicants"][0]["match_score"], Value::Null);
+
+    let res = recalculate(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let score = res.body["match_score"].as_i64().unwrap();
+    assert_eq!(res.body["match_score_is_stale"], false);
+
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["applicants"][0]["match_score"], score);
+    assert!(body["applicants"][0]["match_score_computed_at"].is_string());
+
+    let res = app
+        .get("/api/me/company/settings", Some(&company.owner))
+        .await;
+    assert_eq!(
+        res.body,
+        json!({ "show_match_scores": true, "syndication_enabled": true, "allow_contact_export": true })
+    );
+    let res = app
+        .put(
+            "/api/me/company/settings",
+            Some(&company.owner),
+            json!({ "show_match_scores": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["show_match_scores"], false);
+
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["show_match_scores"], false);
+    assert_eq!(body["applicants"][0]["match_score"], Value::Null);
+    assert_eq!(
+        body["applicants"][0]["match_score_computed_at"],
+        Value::Null
+    );
+
+    let res = recalculate(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["match_score"], Value::Null);
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/match-scores/recalculate", job_id),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Leaving the field out keeps the setting
+    let res = app
+        .put("/api/me/company/settings", Some(&company.owner), json!({}))
+        .await;
+    assert_eq!(res.body["show_match_scores"], false);
+}
+
+#[sqlx::test]
+async fn test_recalculation_is_rate_limited_per_company(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    for _ in 0..matching::RECALCULATIONS_PER_HOUR {
+        let res = recalculate(&app, &company, job_id, app_id).await;
+        assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    }
+    let res = recalculate(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+
+    // Reading the cached score is not limited
+    let res = app
+        .get(&detail_uri(job_id, app_id), Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res.body["match_score"].is_number());
+
+    // Another company has its own allowance
+    let other = app.create_company_with_owner().await;
+    let other_job = app.create_active_job(&other).await;
+    let other_app = app.create_application(other_job, &seeker).await;
+    let res = recalculate(&app, &other, other_job, other_app).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_stale_scores_are_flagged(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    let res = recalculate(&app, &company, job_id, app_id).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["applicants"][0]["match_score_is_stale"], false);
+
+    // Old scores are stale even when nothing was flagged
+    sqlx::query(
+        "UPDATE job_match_scores SET computed_at = NOW() - INTERVAL '10 days' WHERE job_id = $1",
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["applicants"][0]["match_score_is_stale"], true);
+
+    let res = recalculate(&app, &company, job_id, app_id).await;
+    assert_eq!(res.body["match_score_is_stale"], false);
+
+    // Asking for the whole job flags the scores until the worker rescores them
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/match-scores/recalculate", job_id),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["applicants"][0]["match_score_is_stale"], true);
+
+    assert_eq!(matching::process_recalculations(app.db()).await.unwrap(), 1);
+    let body = first_listed(&app, &company, job_id).await;
+    assert_eq!(body["applicants"][0]["match_score_is_stale"], false);
+    let queued: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM match_score_recalculations")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(queued, 0);
+
+    // Another company cannot queue this job
+    let other = app.create_company_with_owner().await;
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/match-scores/recalculate", job_id),
+            Some(&other.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const BASE: &str = "2026-01-05T00:00:00Z";
+const RANGE: &str = "from_date=2026-01-01T00:00:00Z&to_date=2026-01-31T00:00:00Z";
+
+async fn admin_row_id(app: &TestApp, admin: &TestUser) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM admins WHERE user_id = $1")
+        .bind(admin.id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Company submitted at BASE and decided `hours` later
+async fn seed_company_decision(app: &TestApp, admin: &TestUser, hours: i32, action: &str) {
+    let company_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO company_profiles (company_name, status, approved_at, approved_by, created_at)
+        VALUES ('Seeded SpA', 'active', NOW(), $1, $2::timestamptz)
+        RETURNING id
+        "#,
+    )
+    .bind(admin.id)
+    .bind(BASE)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query(
+        r#"
+        INSERT INTO admin_audit_logs (admin_id, action_type, entity_type, entity_id, created_at)
+        VALUES ($1, $2, 'company', $3, $4::timestamptz + make_interval(hours => $5))
+        "#,
+    )
+    .bind(admin_row_id(app, admin).await)
+    .bind(action)
+    .bind(company_id)
+    .bind(BASE)
+    .bind(hours)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_turnaround_percentiles_and_reviewer_counts(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let alice = app.create_admin().await;
+    let bob = app.create_admin().await;
+
+    seed_company_decision(&app, &alice, 1, "approve_company").await;
+    seed_company_decision(&app, &alice, 2, "approve_company").await;
+    seed_company_decision(&app, &alice, 3, "reject_company").await;
+    seed_company_decision(&app, &bob, 4, "approve_company").await;
+    seed_company_decision(&app, &bob, 10, "approve_company").await;
+
+    let res = app
+        .get(&format!("/api/admin/reports/moderation?{}", RANGE), Some(&alice))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let turnaround = res.body["turnaround"].as_array().unwrap();
+    assert_eq!(turnaround.len(), 3);
+    let companies = &turnaround[0];
+    assert_eq!(companies["entity_type"], "company");
+    assert_eq!(companies["decisions"], 5);
+    assert_eq!(companies["approvals"], 4);
+    assert_eq!(companies["rejections"], 1);
+    assert_eq!(companies["median_hours"], 3.0);
+    // Interpolated between the 4th and 5th waits: 4 + 0.6 * (10 - 4)
+    assert_eq!(companies["p90_hours"], 7.6);
+
+    assert_eq!(turnaround[1]["entity_type"], "job");
+    assert_eq!(turnaround[1]["decisions"], 0);
+    assert_eq!(turnaround[1]["median_hours"], json!(null));
+
+    let by_admin = res.body["by_admin"].as_array().unwrap();
+    assert_eq!(by_admin.len(), 2);
+    assert_eq!(by_admin[0]["admin_id"], admin_row_id(&app, &alice).await.to_string());
+    assert_eq!(by_admin[0]["approvals"], 2);
+    assert_eq!(by_admin[0]["rejections"], 1);
+    assert_eq!(by_admin[1]["approvals"], 2);
+    assert_eq!(by_admin[1]["rejections"], 0);
+
+    // Decisions outside the range are left out
+    let res = app
+        .get(
+            "/api/admin/reports/moderation?from_date=2026-02-01T00:00:00Z&to_date=2026-02-28T00:00:00Z",
+            Some(&alice),
+        )
+        .await;
+    assert_eq!(res.body["turnaround"][0]["decisions"], 0);
+    assert_eq!(res.body["by_admin"].as_array().unwrap().len(), 0);
+}
+
+#[sqlx::test]
+async fn test_resubmitted_job_waits_from_latest_submission(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let admin = app.create_admin().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Rejected ten days after it was first posted
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET status = 'rejected', rejection_reason = 'Falta información',
+            created_at = NOW() - INTERVAL '10 days', submitted_at = NOW() - INTERVAL '10 days'
+        WHERE id = $1
+        "#,
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", job_id),
+            Some(&company.owner),
+            json!({ "status": "pending_approval" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/admin/reports/moderation", Some(&admin)).await;
+    let jobs = &res.body["queue"][1];
+    assert_eq!(jobs["entity_type"], "job");
+    assert_eq!(jobs["pending"], 1);
+    assert_eq!(jobs["under_1_day"], 1);
+
+    let res = app
+        .patch(&format!("/api/admin/jobs/{}/approve", job_id), Some(&admin), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/admin/reports/moderation", Some(&admin)).await;
+    let jobs = &res.body["turnaround"][1];
+    assert_eq!(jobs["decisions"], 1);
+    assert!(jobs["median_hours"].as_f64().unwrap() < 1.0);
+    assert_eq!(res.body["queue"][1]["pending"], 0);
+}
+
+#[sqlx::test]
+async fn test_queue_age_buckets(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    for age in ["2 hours", "2 days", "5 days", "9 days"] {
+        sqlx::query(
+            r#"
+            INSERT INTO company_profiles (company_name, status, created_at)
+            VALUES ('En revisión SpA', 'pending_approval', NOW() - $1::interval)
+            "#,
+        )
+        .bind(age)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO omil_organizations (organization_name, status, created_at)
+        VALUES ('OMIL Pendiente', 'pending_approval', NOW() - INTERVAL '30 hours')
+        "#,
+    )
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app.get("/api/admin/reports/moderation", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let companies = &res.body["queue"][0];
+    assert_eq!(companies["entity_type"], "company");
+    assert_eq!(companies["pending"], 4);
+    assert_eq!(companies["under_1_day"], 1);
+    assert_eq!(companies["from_1_to_3_days"], 1);
+    assert_eq!(companies["from_3_to_7_days"], 1);
+    assert_eq!(companies["over_7_days"], 1);
+    assert_eq!(companies["oldest_hours"].as_f64().unwrap().round(), 216.0);
+
+    let omils = &res.body["queue"][2];
+    assert_eq!(omils["entity_type"], "omil");
+    assert_eq!(omils["pending"], 1);
+    assert_eq!(omils["from_1_to_3_days"], 1);
+}
+
+#[sqlx::test]
+async fn test_moderation_export_and_access(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    seed_company_decision(&app, &admin, 6, "approve_company").await;
+
+    let res = app
+        .get(&format!("/api/admin/reports/export/moderation?{}", RANGE), Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.headers["content-type"],
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+
+    let seeker = app.create_job_seeker().await;
+    let res = app.get("/api/admin/reports/moderation", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
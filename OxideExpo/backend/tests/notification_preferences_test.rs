@@ -0,0 +1,243 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::{wait_for_messages, TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::{
+    models::notification::{NotificationChannel, NotificationEvent},
+    services::notifications,
+    utils::jwt,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn invite(app: &TestApp, company: &TestCompany, job_id: Uuid, seeker: &TestUser) {
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id, "message": "Tu perfil calza con la oferta" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+fn preference<'a>(body: &'a Value, event: &str) -> &'a Value {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["event"] == event)
+        .unwrap_or_else(|| panic!("no {} preference", event))
+}
+
+#[sqlx::test]
+async fn test_defaults_are_stored_on_first_read(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app.get("/api/me/notification-preferences", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let events: Vec<&str> = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        events,
+        [
+            "job_invitation",
+            "application_status",
+            "digest",
+            "new_message",
+            "announcement",
+            "security"
+        ]
+    );
+    assert_eq!(preference(&res.body, "job_invitation")["email"], true);
+    assert_eq!(preference(&res.body, "announcement")["email"], false);
+    assert_eq!(preference(&res.body, "announcement")["in_app"], true);
+    assert_eq!(preference(&res.body, "security")["configurable"], false);
+
+    // Every configurable event got a row; security never does
+    let stored: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM notification_preferences WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(stored, 5);
+
+    let res = app
+        .get("/api/me/notification-preferences", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(preference(&res.body, "new_application")["email"], true);
+    assert_eq!(preference(&res.body, "announcement")["email"], true);
+    assert!(res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|p| p["event"] != "job_invitation"));
+}
+
+#[sqlx::test]
+async fn test_update_changes_only_given_switches(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            "/api/me/notification-preferences",
+            Some(&seeker),
+            json!({ "preferences": [{ "event": "digest", "email": false }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(preference(&res.body, "digest")["email"], false);
+    assert_eq!(preference(&res.body, "digest")["in_app"], true);
+    assert_eq!(preference(&res.body, "job_invitation")["email"], true);
+
+    // Security emails can't be turned off, nor events of other user types
+    for event in ["security", "new_application"] {
+        let res = app
+            .put(
+                "/api/me/notification-preferences",
+                Some(&seeker),
+                json!({ "preferences": [
+                    { "event": "application_status", "email": false },
+                    { "event": event, "email": false }
+                ] }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    }
+
+    // Rejected requests change nothing
+    let res = app.get("/api/me/notification-preferences", Some(&seeker)).await;
+    assert_eq!(preference(&res.body, "application_status")["email"], true);
+}
+
+#[sqlx::test]
+async fn test_opted_out_invitation_is_not_emailed(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    let opted_out = app.create_job_seeker().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let res = app
+        .put(
+            "/api/me/notification-preferences",
+            Some(&opted_out),
+            json!({ "preferences": [{ "event": "job_invitation", "email": false }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    invite(&app, &company, job_id, &opted_out).await;
+    invite(&app, &company, job_id, &seeker).await;
+
+    wait_for_messages(&inbox, 1).await;
+    // Give a wrongly sent email time to arrive
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let messages = inbox.lock().unwrap().clone();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains(&seeker.email));
+    assert!(!messages[0].contains(&opted_out.email));
+    assert!(messages[0].contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+    assert!(messages[0].contains("/api/notifications/unsubscribe?token="));
+}
+
+#[sqlx::test]
+async fn test_unsubscribe_link_turns_off_the_event(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+
+    invite(&app, &company, job_id, &seeker).await;
+    let messages = wait_for_messages(&inbox, 1).await;
+    assert_eq!(messages.len(), 1);
+
+    // One-click unsubscribe posts to the URL in the header
+    let header = messages[0]
+        .lines()
+        .find(|line| line.starts_with("List-Unsubscribe:"))
+        .unwrap();
+    let url = header
+        .split('<')
+        .nth(1)
+        .and_then(|rest| rest.split('>').next())
+        .unwrap();
+    let path = &url[url.find("/api/").unwrap()..];
+
+    let res = app.post(path, None, json!({})).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["event"], "job_invitation");
+    assert_eq!(res.body["email"], false);
+
+    let res = app.get("/api/me/notification-preferences", Some(&seeker)).await;
+    assert_eq!(preference(&res.body, "job_invitation")["email"], false);
+    assert_eq!(preference(&res.body, "job_invitation")["in_app"], true);
+    assert_eq!(preference(&res.body, "application_status")["email"], true);
+
+    // Later invitations aren't emailed
+    let other_job_id = app.create_active_job(&company).await;
+    invite(&app, &company, other_job_id, &seeker).await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(inbox.lock().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_invalid_unsubscribe_links_are_rejected(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let company = app.create_company_with_owner().await;
+    let config = &app.state.config;
+
+    let res = app
+        .post("/api/notifications/unsubscribe?token=not-a-token", None, json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Security emails have no unsubscribe link to forge
+    let token = jwt::create_unsubscribe_token(seeker.id, "security", config).unwrap();
+    let uri = format!("/api/notifications/unsubscribe?token={}", token);
+    assert_eq!(app.post(&uri, None, json!({})).await.status, StatusCode::BAD_REQUEST);
+
+    // Nor do companies get invitations
+    let token =
+        jwt::create_unsubscribe_token(company.owner.id, "job_invitation", config).unwrap();
+    let uri = format!("/api/notifications/unsubscribe?token={}", token);
+    assert_eq!(app.post(&uri, None, json!({})).await.status, StatusCode::BAD_REQUEST);
+
+    // A session token isn't an unsubscribe link
+    let uri = format!("/api/notifications/unsubscribe?token={}", seeker.token);
+    assert_eq!(app.post(&uri, None, json!({})).await.status, StatusCode::BAD_REQUEST);
+
+    let token = jwt::create_unsubscribe_token(seeker.id, "digest", config).unwrap();
+    let uri = format!("/api/notifications/unsubscribe?token={}", token);
+    assert_eq!(app.post(&uri, None, json!({})).await.status, StatusCode::OK);
+    assert!(!notifications::should_send(
+        app.db(),
+        seeker.id,
+        NotificationEvent::Digest,
+        NotificationChannel::Email
+    )
+    .await
+    .unwrap());
+    assert!(notifications::should_send(
+        app.db(),
+        seeker.id,
+        NotificationEvent::Digest,
+        NotificationChannel::InApp
+    )
+    .await
+    .unwrap());
+}
@@ -0,0 +1,282 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil};
+use empleos_inclusivos_backend::utils::{jwt::hash_token, rut};
+use rand::Rng;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn random_rut() -> String {
+    let body: u32 = rand::thread_rng().gen_range(10_000_000..100_000_000);
+    format!("{}-{}", body, rut::check_digit(body))
+}
+
+/// Registers a beneficiary without an email, returning (managed_id, job_seeker_id)
+async fn register_offline(app: &TestApp, omil: &TestOmil, national_id: &str) -> (Uuid, Uuid) {
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({
+                "first_name": "Rosa",
+                "last_name": "Muñoz",
+                "national_id": national_id,
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let managed_id = res.body["id"].as_str().unwrap().parse().unwrap();
+    let job_seeker_id = res.body["job_seeker_id"].as_str().unwrap().parse().unwrap();
+    (managed_id, job_seeker_id)
+}
+
+/// Sends a claim invitation and swaps its token for one the test knows;
+/// the real one only goes out by email
+async fn invite(app: &TestApp, omil: &TestOmil, managed_id: Uuid, email: &str) -> String {
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/claim-invite", managed_id),
+            Some(&omil.director),
+            json!({ "email": email }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["email"], email.to_lowercase());
+
+    let token = format!("claim-{}", Uuid::new_v4());
+    sqlx::query("UPDATE account_claim_invitations SET token_hash = $2 WHERE email = $1")
+        .bind(email.to_lowercase())
+        .bind(hash_token(&token))
+        .execute(app.db())
+        .await
+        .unwrap();
+    token
+}
+
+#[sqlx::test]
+async fn test_offline_registration_needs_national_id_and_has_no_email(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({ "first_name": "Rosa", "last_name": "Muñoz" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // Any number of seekers can be without an email
+    let national_id = random_rut();
+    let (managed_id, job_seeker_id) = register_offline(&app, &omil, &national_id).await;
+    register_offline(&app, &omil, &random_rut()).await;
+
+    let (email, status): (Option<String>, String) =
+        sqlx::query_as("SELECT email, account_status::text FROM users WHERE id = $1")
+            .bind(job_seeker_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(email, None);
+    assert_eq!(status, "managed_offline");
+
+    // The national ID identifies the person on a second registration
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({ "first_name": "Rosa", "last_name": "Muñoz", "national_id": national_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .get("/api/me/omil/job-seekers", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let seeker = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["id"] == managed_id.to_string())
+        .unwrap();
+    assert!(seeker["user_email"].is_null());
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}", managed_id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body["user_email"].is_null());
+}
+
+#[sqlx::test]
+async fn test_claimed_account_keeps_history_and_can_log_in(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (managed_id, job_seeker_id) = register_offline(&app, &omil, &random_rut()).await;
+
+    // An address that already has an account can't be offered
+    let taken = app.create_job_seeker().await;
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/claim-invite", managed_id),
+            Some(&omil.director),
+            json!({ "email": taken.email }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+
+    let email = format!("Rosa-{}@Test.cl", Uuid::new_v4());
+    let token = invite(&app, &omil, managed_id, &email).await;
+
+    let res = app
+        .post(
+            "/api/auth/claim-account",
+            None,
+            json!({ "token": token, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["user"]["id"], job_seeker_id.to_string());
+    assert_eq!(res.body["user"]["email"], email.to_lowercase());
+    assert_eq!(res.body["user"]["account_status"], "active");
+    assert_eq!(res.body["user"]["email_verified"], true);
+
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": email, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // The OMIL still manages the same record, with the claim in its history
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}", managed_id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["user_email"], email.to_lowercase());
+    let titles: Vec<&str> = res.body["recent_followups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["title"].as_str().unwrap())
+        .collect();
+    assert!(titles.contains(&"Registro inicial"));
+    assert!(titles.contains(&"Cuenta activada"));
+
+    // Neither the token nor the invitation can be used twice
+    let res = app
+        .post(
+            "/api/auth/claim-account",
+            None,
+            json!({ "token": token, "password": "OtraClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/claim-invite", managed_id),
+            Some(&omil.director),
+            json!({ "email": format!("{}@test.cl", Uuid::new_v4()) }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_expired_claim_invitation_is_rejected(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (managed_id, job_seeker_id) = register_offline(&app, &omil, &random_rut()).await;
+
+    let email = format!("{}@test.cl", Uuid::new_v4());
+    let token = invite(&app, &omil, managed_id, &email).await;
+    sqlx::query("UPDATE account_claim_invitations SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app
+        .post(
+            "/api/auth/claim-account",
+            None,
+            json!({ "token": token, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let status: String = sqlx::query_scalar("SELECT account_status::text FROM users WHERE id = $1")
+        .bind(job_seeker_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(status, "managed_offline");
+}
+
+#[sqlx::test]
+async fn test_self_registration_does_not_take_over_offline_record(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let national_id = random_rut();
+    let (managed_id, job_seeker_id) = register_offline(&app, &omil, &national_id).await;
+
+    // Typed the way people write it: 12.345.678-9
+    let (body, dv) = national_id.split_once('-').unwrap();
+    let dotted = format!("{}.{}.{}-{}", &body[..2], &body[2..5], &body[5..], dv);
+
+    // Knowing someone's RUT is not enough to take over their history; the
+    // account is claimed through the OMIL's invitation
+    let email = format!("{}@test.cl", Uuid::new_v4());
+    let res = app
+        .post(
+            "/api/auth/register",
+            None,
+            json!({
+                "email": email,
+                "password": "NuevaClave123",
+                "first_name": "Rosa",
+                "last_name": "Muñoz Soto",
+                "national_id": dotted,
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+    assert!(res.body["error"].as_str().unwrap().contains("invitation"));
+
+    let status: String = sqlx::query_scalar("SELECT account_status::text FROM users WHERE id = $1")
+        .bind(job_seeker_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(status, "managed_offline");
+    let registered: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE email = $1)")
+            .bind(&email)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(!registered);
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}", managed_id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body["user_email"].is_null());
+}
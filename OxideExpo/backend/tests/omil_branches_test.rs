@@ -0,0 +1,398 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, TestOmil, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn add_member(app: &TestApp, omil: &TestOmil, role: &str) -> (TestUser, Uuid) {
+    let member = app.create_user(UserType::OmilMember).await;
+    let member_id = sqlx::query_scalar(
+        "INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role) RETURNING id",
+    )
+    .bind(omil.id)
+    .bind(member.id)
+    .bind(role)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    (member, member_id)
+}
+
+/// Managed record of a new seeker, with the seeker
+async fn manage(app: &TestApp, omil: &TestOmil) -> (Uuid, TestUser) {
+    let seeker = app.create_job_seeker().await;
+    let managed_id = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    (managed_id, seeker)
+}
+
+async fn apply_on_behalf(app: &TestApp, omil: &TestOmil, job_id: Uuid, seeker: &TestUser) {
+    let application_id = app.create_application(job_id, seeker).await;
+    sqlx::query(
+        "INSERT INTO omil_applications (application_id, omil_id, submitted_by) VALUES ($1, $2, $3)",
+    )
+    .bind(application_id)
+    .bind(omil.id)
+    .bind(omil.director.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn create_branch(app: &TestApp, omil: &TestOmil, name: &str) -> Uuid {
+    let res = app
+        .post(
+            "/api/me/omil/branches",
+            Some(&omil.director),
+            json!({ "name": name, "address": "Av. Pajaritos 1234" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_active"], true);
+    res.body["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn assign_seeker(
+    app: &TestApp,
+    user: &TestUser,
+    managed_id: Uuid,
+    branch_id: Option<Uuid>,
+) -> StatusCode {
+    app.put(
+        &format!("/api/me/omil/job-seekers/{}/branch", managed_id),
+        Some(user),
+        json!({ "branch_id": branch_id }),
+    )
+    .await
+    .status
+}
+
+fn ids(list: &Value, key: &str) -> Vec<String> {
+    let mut ids: Vec<String> = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item[key].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[sqlx::test]
+async fn test_branches_scope_seekers_and_applications(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (coordinator, _) = add_member(&app, &omil, "coordinator").await;
+    let (advisor, advisor_member_id) = add_member(&app, &omil, "advisor").await;
+
+    // Only directors manage branches
+    let res = app
+        .post(
+            "/api/me/omil/branches",
+            Some(&coordinator),
+            json!({ "name": "Sucursal Norte" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let north = create_branch(&app, &omil, "Sucursal Norte").await;
+    let south = create_branch(&app, &omil, "Sucursal Sur").await;
+    let res = app
+        .post(
+            "/api/me/omil/branches",
+            Some(&omil.director),
+            json!({ "name": "Sucursal Norte" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    // Everyone in the OMIL sees them, to pick one
+    let res = app.get("/api/me/omil/branches", Some(&advisor)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body.as_array().unwrap().len(), 2);
+
+    let (north_managed, north_seeker) = manage(&app, &omil).await;
+    let (south_managed, south_seeker) = manage(&app, &omil).await;
+    let (_, unassigned_seeker) = manage(&app, &omil).await;
+
+    // Coordinators assign, advisors don't
+    assert_eq!(
+        assign_seeker(&app, &advisor, north_managed, Some(north)).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        assign_seeker(&app, &coordinator, north_managed, Some(north)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        assign_seeker(&app, &coordinator, south_managed, Some(south)).await,
+        StatusCode::OK
+    );
+    let res = app
+        .put(
+            &format!("/api/me/omil/members/{}/branch", advisor_member_id),
+            Some(&coordinator),
+            json!({ "branch_id": north }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["branch_id"], north.to_string());
+
+    // Another OMIL's branch can't be assigned
+    let other = app.create_omil_with_director().await;
+    let foreign = create_branch(&app, &other, "Sucursal Norte").await;
+    assert_eq!(
+        assign_seeker(&app, &coordinator, north_managed, Some(foreign)).await,
+        StatusCode::BAD_REQUEST
+    );
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers?branch_id={}", north),
+            Some(&advisor),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        ids(&res.body, "job_seeker_id"),
+        vec![north_seeker.id.to_string()]
+    );
+    assert_eq!(res.body[0]["branch_name"], "Sucursal Norte");
+    let res = app.get("/api/me/omil/job-seekers", Some(&advisor)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 3);
+
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    for seeker in [&north_seeker, &south_seeker, &unassigned_seeker] {
+        apply_on_behalf(&app, &omil, job_id, seeker).await;
+    }
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/applications?branch_id={}", south),
+            Some(&advisor),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 1);
+    assert_eq!(
+        ids(&res.body["applications"], "job_seeker_id"),
+        vec![south_seeker.id.to_string()]
+    );
+    let res = app.get("/api/me/omil/applications", Some(&advisor)).await;
+    assert_eq!(res.body["total"], 3);
+}
+
+#[sqlx::test]
+async fn test_deactivating_a_branch_requires_reassignment(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (_, member_id) = add_member(&app, &omil, "advisor").await;
+    let branch = create_branch(&app, &omil, "Sucursal Centro").await;
+    let (first, _) = manage(&app, &omil).await;
+    let (second, _) = manage(&app, &omil).await;
+    let (inactive, _) = manage(&app, &omil).await;
+    for managed_id in [first, second, inactive] {
+        assert_eq!(
+            assign_seeker(&app, &omil.director, managed_id, Some(branch)).await,
+            StatusCode::OK
+        );
+    }
+    sqlx::query("UPDATE omil_managed_job_seekers SET is_active = false WHERE id = $1")
+        .bind(inactive)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let member_uri = format!("/api/me/omil/members/{}/branch", member_id);
+    let res = app
+        .put(
+            &member_uri,
+            Some(&omil.director),
+            json!({ "branch_id": branch }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let uri = format!("/api/me/omil/branches/{}", branch);
+    let res = app
+        .put(&uri, Some(&omil.director), json!({ "is_active": false }))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let message = res.body["error"].as_str().unwrap();
+    assert!(
+        message.contains("2 job seekers and 1 member"),
+        "{}",
+        message
+    );
+    let res = app
+        .request(Method::DELETE, &uri, Some(&omil.director), None)
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Renaming is fine meanwhile
+    let res = app
+        .put(
+            &uri,
+            Some(&omil.director),
+            json!({ "name": "Sucursal Plaza" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["name"], "Sucursal Plaza");
+    assert_eq!(res.body["address"], "Av. Pajaritos 1234");
+
+    let other_branch = create_branch(&app, &omil, "Sucursal Poniente").await;
+    assert_eq!(
+        assign_seeker(&app, &omil.director, first, Some(other_branch)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        assign_seeker(&app, &omil.director, second, None).await,
+        StatusCode::OK
+    );
+    let res = app
+        .put(&uri, Some(&omil.director), json!({ "is_active": false }))
+        .await;
+    let message = res.body["error"].as_str().unwrap();
+    assert!(message.contains("branch's 1 member first"), "{}", message);
+
+    let res = app
+        .put(
+            &member_uri,
+            Some(&omil.director),
+            json!({ "branch_id": null }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["branch_id"], Value::Null);
+    let res = app
+        .put(&uri, Some(&omil.director), json!({ "is_active": false }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_active"], false);
+
+    // Inactive branches take no one new
+    assert_eq!(
+        assign_seeker(&app, &omil.director, second, Some(branch)).await,
+        StatusCode::BAD_REQUEST
+    );
+
+    // Deleting leaves the inactive record unassigned
+    let res = app
+        .request(Method::DELETE, &uri, Some(&omil.director), None)
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let branch_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT branch_id FROM omil_managed_job_seekers WHERE id = $1")
+            .bind(inactive)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(branch_id, None);
+}
+
+#[sqlx::test]
+async fn test_stats_break_down_by_branch(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let north = create_branch(&app, &omil, "Sucursal Norte").await;
+    let south = create_branch(&app, &omil, "Sucursal Sur").await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Two seekers at the northern branch, one of them placed and applying
+    let (placed, placed_seeker) = manage(&app, &omil).await;
+    let (pending, _) = manage(&app, &omil).await;
+    for managed_id in [placed, pending] {
+        assign_seeker(&app, &omil.director, managed_id, Some(north)).await;
+    }
+    sqlx::query(
+        "UPDATE omil_managed_job_seekers SET placement_outcome = 'placed', placed_at = NOW() WHERE id = $1",
+    )
+    .bind(placed)
+    .execute(app.db())
+    .await
+    .unwrap();
+    apply_on_behalf(&app, &omil, job_id, &placed_seeker).await;
+
+    // One seeker at no branch, with two applications
+    let (_, unassigned_seeker) = manage(&app, &omil).await;
+    apply_on_behalf(&app, &omil, job_id, &unassigned_seeker).await;
+    let other_job_id = app.create_active_job(&company).await;
+    apply_on_behalf(&app, &omil, other_job_id, &unassigned_seeker).await;
+
+    let res = app.get("/api/me/omil/stats", Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["active_seekers"], 3);
+    assert_eq!(
+        res.body["branches"],
+        json!([
+            {
+                "branch_id": north, "branch_name": "Sucursal Norte",
+                "seekers": 2, "placements": 1, "applications": 1
+            },
+            {
+                "branch_id": south, "branch_name": "Sucursal Sur",
+                "seekers": 0, "placements": 0, "applications": 0
+            },
+            {
+                "branch_id": null, "branch_name": null,
+                "seekers": 1, "placements": 0, "applications": 2
+            }
+        ])
+    );
+
+    // The monthly report breaks the current month down the same way
+    let (year, month): (i32, i32) = sqlx::query_as(
+        r#"
+        SELECT EXTRACT(YEAR FROM NOW() AT TIME ZONE 'America/Santiago')::int,
+               EXTRACT(MONTH FROM NOW() AT TIME ZONE 'America/Santiago')::int
+        "#,
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    let res = app
+        .get(
+            &format!("/api/me/omil/reports/monthly?year={}&month={}", year, month),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let branches = res.body["branches"].as_array().unwrap();
+    assert_eq!(branches.len(), 3);
+    assert_eq!(branches[0]["placements"], 1);
+    assert_eq!(branches[2]["branch_id"], Value::Null);
+    assert_eq!(branches[2]["applications"], 2);
+
+    // An inactive branch drops out once it has nothing to count
+    let res = app
+        .put(
+            &format!("/api/me/omil/branches/{}", south),
+            Some(&omil.director),
+            json!({ "is_active": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app.get("/api/me/omil/stats", Some(&omil.director)).await;
+    let names: Vec<&Value> = res.body["branches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| &b["branch_name"])
+        .collect();
+    assert_eq!(names, vec![&json!("Sucursal Norte"), &Value::Null]);
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::Utc;
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use empleos_inclusivos_backend::services::company_insights;
+use redis::AsyncCommands;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn regions(app: &TestApp) -> (Uuid, Uuid) {
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM regions ORDER BY name LIMIT 2")
+        .fetch_all(app.db())
+        .await
+        .unwrap();
+    (ids[0], ids[1])
+}
+
+async fn company_in(app: &TestApp, region_id: Uuid) -> TestCompany {
+    let company = app.create_company_with_owner().await;
+    sqlx::query("UPDATE company_profiles SET region_id = $2 WHERE id = $1")
+        .bind(company.id)
+        .bind(region_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    company
+}
+
+/// A seeker with a disability record hired for a new job at the company
+async fn hire(app: &TestApp, company: &TestCompany, disability: bool) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    if disability {
+        sqlx::query(
+            "INSERT INTO job_seeker_disabilities (user_id, category) VALUES ($1, 'visual')",
+        )
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    let job_id = app.create_active_job(company).await;
+    let application_id = app.create_application(job_id, &seeker).await;
+    sqlx::query("UPDATE job_applications SET status = 'hired' WHERE id = $1")
+        .bind(application_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    seeker
+}
+
+async fn insights(app: &TestApp, omil: &TestOmil) -> Value {
+    // Cached for a day, so each check starts from fresh numbers
+    let mut redis = app.state.redis.clone();
+    let key = company_insights::cache_key(omil.id, &Utc::now().format("%Y-%m").to_string());
+    let _: () = redis.del(&key).await.unwrap();
+
+    let res = app
+        .get("/api/me/omil/companies/insights", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+fn entry<'a>(body: &'a Value, company: &TestCompany) -> &'a Value {
+    body["companies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["company_id"] == company.id.to_string())
+        .expect("company listed")
+}
+
+async fn omil_in(app: &TestApp, region_id: Uuid) -> TestOmil {
+    let omil = app.create_omil_with_director().await;
+    sqlx::query("UPDATE omil_organizations SET region_id = $2 WHERE id = $1")
+        .bind(omil.id)
+        .bind(region_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    omil
+}
+
+#[sqlx::test]
+async fn test_disability_hires_are_suppressed_below_threshold(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region, _) = regions(&app).await;
+    let omil = omil_in(&app, region).await;
+    let company = company_in(&app, region).await;
+
+    for _ in 0..4 {
+        hire(&app, &company, true).await;
+    }
+    hire(&app, &company, false).await;
+
+    let body = insights(&app, &omil).await;
+    let hires = &entry(&body, &company)["disability_hires_last_12_months"];
+    assert_eq!(hires["value"], Value::Null);
+    assert_eq!(hires["suppressed"], true);
+    // Hidden hires don't lift the company either
+    assert_eq!(entry(&body, &company)["inclusiveness_score"], 0);
+    assert!(entry(&body, &company)["avg_days_to_hire"].is_number());
+    assert!(body["score_formula"]
+        .as_str()
+        .unwrap()
+        .contains("accommodations"));
+
+    hire(&app, &company, true).await;
+    let body = insights(&app, &omil).await;
+    let hires = &entry(&body, &company)["disability_hires_last_12_months"];
+    assert_eq!(hires["value"], 5);
+    assert_eq!(hires["suppressed"], false);
+    assert_eq!(entry(&body, &company)["inclusiveness_score"], 5);
+}
+
+#[sqlx::test]
+async fn test_prior_hire_of_managed_seeker_is_flagged(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region, _) = regions(&app).await;
+    let omil = omil_in(&app, region).await;
+    let other_omil = omil_in(&app, region).await;
+    let hired_ours = company_in(&app, region).await;
+    let hired_others = company_in(&app, region).await;
+
+    let ours = hire(&app, &hired_ours, false).await;
+    let theirs = hire(&app, &hired_others, false).await;
+    for (managing, seeker) in [(&omil, &ours), (&other_omil, &theirs)] {
+        sqlx::query(
+            r#"
+            INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(managing.id)
+        .bind(seeker.id)
+        .bind(managing.director.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+
+    let body = insights(&app, &omil).await;
+    assert_eq!(entry(&body, &hired_ours)["has_hired_our_seekers"], true);
+    assert_eq!(entry(&body, &hired_others)["has_hired_our_seekers"], false);
+    // The prior hire ranks the company first
+    assert_eq!(
+        body["companies"][0]["company_id"],
+        hired_ours.id.to_string()
+    );
+
+    let body = insights(&app, &other_omil).await;
+    assert_eq!(entry(&body, &hired_ours)["has_hired_our_seekers"], false);
+    assert_eq!(entry(&body, &hired_others)["has_hired_our_seekers"], true);
+}
+
+#[sqlx::test]
+async fn test_only_active_companies_in_region_are_listed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region, other_region) = regions(&app).await;
+    let omil = omil_in(&app, region).await;
+    let local = company_in(&app, region).await;
+    // Active, but in another region
+    company_in(&app, other_region).await;
+    let suspended = company_in(&app, region).await;
+    sqlx::query("UPDATE company_profiles SET status = 'suspended' WHERE id = $1")
+        .bind(suspended.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let job_id = app.create_active_job(&local).await;
+    sqlx::query(
+        "INSERT INTO job_disability_accommodations (job_id, disability_category) VALUES ($1, 'visual')",
+    )
+    .bind(job_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let body = insights(&app, &omil).await;
+    let listed: Vec<&Value> = body["companies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| &c["company_id"])
+        .collect();
+    assert_eq!(listed, vec![&json!(local.id.to_string())]);
+    assert_eq!(entry(&body, &local)["active_jobs_with_accommodations"], 1);
+    assert_eq!(entry(&body, &local)["inclusiveness_score"], 2);
+    assert_eq!(entry(&body, &local)["avg_days_to_hire"], Value::Null);
+
+    // Only OMIL members can see the insights
+    let res = app
+        .get("/api/me/omil/companies/insights", Some(&local.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::{
+    models::stats::{MunicipalityCoverage, OmilCoverage, RegionCoverage, SeekerCountBucket},
+    services::omil_coverage,
+};
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Three active municipalities of the same region
+async fn municipalities(app: &TestApp) -> (Uuid, Vec<Uuid>) {
+    let region_id: Uuid = sqlx::query_scalar(
+        r#"
+        SELECT region_id FROM municipalities
+        WHERE is_active
+        GROUP BY region_id HAVING COUNT(*) >= 3
+        ORDER BY region_id LIMIT 1
+        "#,
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    let ids = sqlx::query_scalar(
+        "SELECT id FROM municipalities WHERE region_id = $1 AND is_active ORDER BY name LIMIT 3",
+    )
+    .bind(region_id)
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    (region_id, ids)
+}
+
+async fn omil_in(app: &TestApp, municipality_id: Uuid, status: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO omil_organizations (organization_name, municipality_id, status)
+        VALUES ('OMIL', $1, $2::organization_status)
+        "#,
+    )
+    .bind(municipality_id)
+    .bind(status)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn lives_in(app: &TestApp, seeker: &TestUser, municipality_id: Uuid) {
+    sqlx::query("UPDATE job_seeker_profiles SET municipality_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(municipality_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+fn region(coverage: &OmilCoverage, region_id: Uuid) -> &RegionCoverage {
+    coverage
+        .regions
+        .iter()
+        .find(|r| r.region_id == region_id)
+        .unwrap()
+}
+
+fn municipality(region: &RegionCoverage, municipality_id: Uuid) -> &MunicipalityCoverage {
+    region
+        .municipalities
+        .iter()
+        .find(|m| m.municipality_id == municipality_id)
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_coverage_counts_only_active_omils_and_jobs(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (region_id, ids) = municipalities(&app).await;
+    let (active, pending, rejected) = (ids[0], ids[1], ids[2]);
+
+    omil_in(&app, active, "active").await;
+    omil_in(&app, active, "rejected").await;
+    omil_in(&app, pending, "pending_approval").await;
+    omil_in(&app, rejected, "rejected").await;
+
+    let company = app.create_company_with_owner().await;
+    for status in ["active", "active", "closed"] {
+        let job_id = app.create_active_job(&company).await;
+        sqlx::query("UPDATE jobs SET municipality_id = $2, status = $3::job_status WHERE id = $1")
+            .bind(job_id)
+            .bind(active)
+            .bind(status)
+            .execute(app.db())
+            .await
+            .unwrap();
+    }
+
+    for _ in 0..2 {
+        let seeker = app.create_job_seeker().await;
+        lives_in(&app, &seeker, pending).await;
+    }
+
+    let coverage = omil_coverage::compute_omil_coverage(app.db())
+        .await
+        .unwrap();
+    let region = region(&coverage, region_id);
+
+    let with_omil = municipality(region, active);
+    assert!(with_omil.has_active_omil);
+    assert_eq!(with_omil.active_jobs, 2);
+    assert_eq!(with_omil.registered_seekers, SeekerCountBucket::None);
+
+    let waiting = municipality(region, pending);
+    assert!(!waiting.has_active_omil);
+    assert_eq!(waiting.active_jobs, 0);
+    assert_eq!(waiting.registered_seekers, SeekerCountBucket::UpTo50);
+
+    assert!(!municipality(region, rejected).has_active_omil);
+
+    assert_eq!(
+        region.totals.municipalities,
+        region.municipalities.len() as i64
+    );
+    assert_eq!(region.totals.municipalities_with_active_omil, 1);
+    assert_eq!(region.totals.active_jobs, 2);
+    assert_eq!(region.totals.registered_seekers, SeekerCountBucket::UpTo50);
+
+    assert_eq!(coverage.totals.municipalities_with_active_omil, 1);
+    assert_eq!(coverage.totals.active_jobs, 2);
+    assert_eq!(
+        coverage.totals.municipalities,
+        coverage
+            .regions
+            .iter()
+            .map(|r| r.totals.municipalities)
+            .sum::<i64>()
+    );
+}
+
+// The cache key is global, so this is the only test that reads through it
+#[sqlx::test]
+async fn test_coverage_is_public_and_cached(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let (_, ids) = municipalities(&app).await;
+    let mut redis = app.state.redis.clone();
+    let _: () = redis.del(omil_coverage::CACHE_KEY).await.unwrap();
+
+    let res = app.get("/api/stats/omil-coverage", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.headers[header::CACHE_CONTROL], "public, max-age=3600");
+    assert_eq!(res.body["totals"]["municipalities_with_active_omil"], 0);
+    assert_eq!(res.body["totals"]["registered_seekers"], "none");
+    let generated_at = res.body["generated_at"].clone();
+
+    // Served from Redis until the entry expires
+    omil_in(&app, ids[0], "active").await;
+    let res = app.get("/api/stats/omil-coverage", None).await;
+    assert_eq!(res.body["generated_at"], generated_at);
+    assert_eq!(res.body["totals"]["municipalities_with_active_omil"], 0);
+
+    let _: () = redis.del(omil_coverage::CACHE_KEY).await.unwrap();
+    let res = app.get("/api/stats/omil-coverage", None).await;
+    assert_eq!(res.body["totals"]["municipalities_with_active_omil"], 1);
+
+    let _: () = redis.del(omil_coverage::CACHE_KEY).await.unwrap();
+}
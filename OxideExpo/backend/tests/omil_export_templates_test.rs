@@ -0,0 +1,188 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_export_template_crud(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+
+    let res = app
+        .post(
+            "/api/me/omil/export-templates",
+            Some(&omil.director),
+            json!({
+                "name": "Reporte municipal",
+                "columns": ["national_id", "full_name", "placement_outcome"],
+                "header_title": "OMIL Test - Colocaciones",
+                "include_logo": true
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let id = res.body["id"].as_str().unwrap().to_string();
+    assert_eq!(
+        res.body["columns"],
+        json!(["national_id", "full_name", "placement_outcome"])
+    );
+
+    // Names are unique per OMIL
+    let res = app
+        .post(
+            "/api/me/omil/export-templates",
+            Some(&omil.director),
+            json!({ "name": "Reporte municipal", "columns": ["email"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let res = app
+        .put(
+            &format!("/api/me/omil/export-templates/{}", id),
+            Some(&omil.director),
+            json!({ "columns": ["email", "full_name"] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["columns"], json!(["email", "full_name"]));
+    assert_eq!(res.body["header_title"], "OMIL Test - Colocaciones");
+
+    let res = app
+        .get("/api/me/omil/export-templates", Some(&omil.director))
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+
+    let res = app
+        .delete(
+            &format!("/api/me/omil/export-templates/{}", id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let res = app
+        .get(
+            &format!("/api/me/omil/export-templates/{}", id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_unknown_columns_are_rejected_at_save(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+
+    for columns in [
+        json!(["full_name", "salary"]),
+        json!(["email", "email"]),
+        json!([]),
+    ] {
+        let res = app
+            .post(
+                "/api/me/omil/export-templates",
+                Some(&omil.director),
+                json!({ "name": "Invalida", "columns": columns }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", columns);
+    }
+
+    let res = app
+        .get(
+            "/api/me/omil/export-templates/columns",
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["key"] == "national_id"));
+}
+
+#[sqlx::test]
+async fn test_templates_are_scoped_to_their_omil(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let owner = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+
+    let res = app
+        .post(
+            "/api/me/omil/export-templates",
+            Some(&owner.director),
+            json!({ "name": "Privada", "columns": ["full_name"] }),
+        )
+        .await;
+    let id = res.body["id"].as_str().unwrap().to_string();
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/export-templates/{}", id),
+            Some(&other.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/export?template_id={}", id),
+            Some(&other.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_export_with_template(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .post(
+            "/api/me/omil/export-templates",
+            Some(&omil.director),
+            json!({
+                "name": "Con logo",
+                "columns": ["followup_count", "full_name", "municipality"],
+                "header_title": "Reporte",
+                "include_logo": true
+            }),
+        )
+        .await;
+    let id = res.body["id"].as_str().unwrap().to_string();
+
+    // No logo uploaded yet: the sheet is still produced
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/export?template_id={}", id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // The default export is unaffected
+    let res = app
+        .get("/api/me/omil/job-seekers/export", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
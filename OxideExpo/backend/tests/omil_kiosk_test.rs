@@ -0,0 +1,181 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TestApp, TestOmil, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Opens a kiosk as `user` and returns the session together with a TestUser
+/// that authenticates with the kiosk token
+async fn open_kiosk(app: &TestApp, user: &TestUser) -> (Value, TestUser) {
+    let res = app
+        .post(
+            "/api/me/omil/kiosk-sessions",
+            Some(user),
+            json!({ "label": "Feria Laboral Valparaíso" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let kiosk = TestUser {
+        id: user.id,
+        email: user.email.clone(),
+        user_type: user.user_type,
+        token: res.body["kiosk_token"].as_str().unwrap().to_string(),
+    };
+    (res.body["session"].clone(), kiosk)
+}
+
+async fn register_walk_in(app: &TestApp, kiosk: &TestUser, email: &str) -> common::TestResponse {
+    app.post(
+        "/api/me/omil/job-seekers",
+        Some(kiosk),
+        json!({
+            "email": email,
+            "first_name": "Matías",
+            "last_name": "Soto",
+        }),
+    )
+    .await
+}
+
+async fn add_member(app: &TestApp, omil: &TestOmil, role: &str) -> TestUser {
+    let member = app.create_user(UserType::OmilMember).await;
+    sqlx::query("INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role)")
+        .bind(omil.id)
+        .bind(member.id)
+        .bind(role)
+        .execute(app.db())
+        .await
+        .unwrap();
+    member
+}
+
+#[sqlx::test]
+async fn test_kiosk_token_scope(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let (session, kiosk) = open_kiosk(&app, &omil.director).await;
+    assert_eq!(session["label"], "Feria Laboral Valparaíso");
+
+    let res = register_walk_in(&app, &kiosk, "visita1@correo.cl").await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let managed_id: Uuid = res.body["id"].as_str().unwrap().parse().unwrap();
+
+    let res = app.get("/api/reference/regions", Some(&kiosk)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let forbidden = [
+        (Method::GET, "/api/me/omil"),
+        (Method::GET, "/api/me/omil/job-seekers"),
+        (Method::GET, "/api/me/omil/job-seekers/export"),
+        (Method::GET, "/api/me/omil/kiosk-sessions"),
+        (Method::POST, "/api/me/omil/kiosk-sessions"),
+        (Method::GET, "/api/auth/me"),
+        (Method::POST, "/api/auth/logout"),
+        (Method::GET, "/api/me/api-tokens"),
+        (Method::GET, "/api/me/profile"),
+    ];
+    for (method, uri) in forbidden {
+        let body = (method == Method::POST).then(|| json!({}));
+        let res = app.request(method.clone(), uri, Some(&kiosk), body).await;
+        assert_eq!(res.status, StatusCode::FORBIDDEN, "{} {}", method, uri);
+    }
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}/impersonate", managed_id),
+            Some(&kiosk),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    // The registration is attributed to the kiosk
+    let kiosk_session_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT kiosk_session_id FROM omil_managed_job_seekers WHERE id = $1")
+            .bind(managed_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(kiosk_session_id.unwrap().to_string(), session["id"]);
+
+    // Registering with the full session leaves no kiosk attribution
+    let res = register_walk_in(&app, &omil.director, "visita2@correo.cl").await;
+    assert_eq!(res.status, StatusCode::OK);
+    let kiosk_session_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT kiosk_session_id FROM omil_managed_job_seekers WHERE id = $1")
+            .bind(res.body["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(kiosk_session_id.is_none());
+
+    let res = app
+        .get("/api/me/omil/kiosk-sessions", Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["registrations"], 1);
+}
+
+#[sqlx::test]
+async fn test_kiosk_revocation_is_immediate(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let coordinator = add_member(&app, &omil, "coordinator").await;
+    let advisor = add_member(&app, &omil, "advisor").await;
+
+    // Advisors cannot open kiosks
+    let res = app
+        .post("/api/me/omil/kiosk-sessions", Some(&advisor), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let (session, kiosk) = open_kiosk(&app, &coordinator).await;
+    let (_, other_kiosk) = open_kiosk(&app, &coordinator).await;
+
+    let res = register_walk_in(&app, &kiosk, "antes@correo.cl").await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Another OMIL cannot see or revoke it
+    let other_omil = app.create_omil_with_director().await;
+    let uri = format!(
+        "/api/me/omil/kiosk-sessions/{}",
+        session["id"].as_str().unwrap()
+    );
+    let res = app.delete(&uri, Some(&other_omil.director)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // The director revokes the coordinator's kiosk mid-session
+    let res = app.delete(&uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = register_walk_in(&app, &kiosk, "despues@correo.cl").await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    let res = app.get("/api/reference/regions", Some(&kiosk)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Only the revoked kiosk stops working
+    let res = register_walk_in(&app, &other_kiosk, "despues@correo.cl").await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app
+        .get("/api/me/omil/kiosk-sessions", Some(&coordinator))
+        .await;
+    let listed = res.body.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_ne!(listed[0]["id"], session["id"]);
+
+    let res = app.delete(&uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // A kiosk is bound to its organization: once the opener leaves, it is useless
+    sqlx::query("UPDATE omil_members SET omil_id = $1 WHERE user_id = $2")
+        .bind(other_omil.id)
+        .bind(coordinator.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = register_walk_in(&app, &other_kiosk, "traslado@correo.cl").await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,317 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use common::{TestApp, TestOmil, TestUser};
+use empleos_inclusivos_backend::{config::Config, models::user::UserType, AppState};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const MARCH_2024: &str = "/api/me/omil/reports/monthly?year=2024&month=3";
+
+/// Seeker with the given profile gender, optionally with a registered disability
+async fn seeker(app: &TestApp, gender: Option<&str>, disability: bool) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query("UPDATE job_seeker_profiles SET gender = $1::gender WHERE user_id = $2")
+        .bind(gender)
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    if disability {
+        sqlx::query(
+            "INSERT INTO job_seeker_disabilities (user_id, category) VALUES ($1, 'visual')",
+        )
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    seeker
+}
+
+async fn manage(
+    app: &TestApp,
+    omil: &TestOmil,
+    seeker: &TestUser,
+    registered_at: &str,
+    placed_at: Option<&str>,
+) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers
+            (omil_id, job_seeker_id, registered_by, registered_at, placement_outcome, placed_at)
+        VALUES ($1, $2, $3, $4::timestamptz,
+                CASE WHEN $5::timestamptz IS NULL THEN 'pending' ELSE 'placed' END::placement_outcome,
+                $5::timestamptz)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .bind(registered_at)
+    .bind(placed_at)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn apply_on_behalf(
+    app: &TestApp,
+    omil: &TestOmil,
+    job_id: Uuid,
+    seeker: &TestUser,
+    created_at: &str,
+) {
+    let application_id = app.create_application(job_id, seeker).await;
+    sqlx::query(
+        r#"
+        INSERT INTO omil_applications (application_id, omil_id, submitted_by, created_at)
+        VALUES ($1, $2, $3, $4::timestamptz)
+        "#,
+    )
+    .bind(application_id)
+    .bind(omil.id)
+    .bind(omil.director.id)
+    .bind(created_at)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn add_member(app: &TestApp, omil: &TestOmil, role: &str) -> TestUser {
+    let member = app.create_user(UserType::OmilMember).await;
+    sqlx::query("INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role)")
+        .bind(omil.id)
+        .bind(member.id)
+        .bind(role)
+        .execute(app.db())
+        .await
+        .unwrap();
+    member
+}
+
+fn counter(
+    total: i64,
+    male: i64,
+    female: i64,
+    non_binary: i64,
+    gender_not_specified: i64,
+    with_disability: i64,
+) -> Value {
+    json!({
+        "total": total,
+        "male": male,
+        "female": female,
+        "non_binary": non_binary,
+        "gender_not_specified": gender_not_specified,
+        "with_disability": with_disability,
+        "without_disability": total - with_disability,
+    })
+}
+
+#[sqlx::test]
+async fn test_monthly_report_counters(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Registered and placed in March: counts in both counters
+    let ana = seeker(&app, Some("female"), true).await;
+    manage(
+        &app,
+        &omil,
+        &ana,
+        "2024-03-05 10:00:00+00",
+        Some("2024-03-20 15:00:00+00"),
+    )
+    .await;
+
+    // 23:00 on 31 March in Santiago, already April in UTC
+    let bruno = seeker(&app, Some("male"), false).await;
+    manage(&app, &omil, &bruno, "2024-04-01 02:00:00+00", None).await;
+
+    // 23:00 on 29 February in Santiago, already March in UTC
+    let carla = seeker(&app, None, false).await;
+    manage(&app, &omil, &carla, "2024-03-01 02:00:00+00", None).await;
+
+    // Registered earlier, placed in March and deactivated after that
+    let dani = seeker(&app, Some("non_binary"), false).await;
+    let dani_managed = manage(
+        &app,
+        &omil,
+        &dani,
+        "2024-01-10 12:00:00+00",
+        Some("2024-03-10 12:00:00+00"),
+    )
+    .await;
+    sqlx::query(
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET is_active = false, deactivated_at = '2024-03-12 12:00:00+00'
+        WHERE id = $1
+        "#,
+    )
+    .bind(dani_managed)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // Registered here in March, then transferred to another OMIL
+    let other_omil = app.create_omil_with_director().await;
+    let eva = seeker(&app, Some("prefer_not_to_say"), false).await;
+    let eva_managed = manage(&app, &omil, &eva, "2024-03-02 12:00:00+00", None).await;
+    sqlx::query(
+        r#"
+        UPDATE omil_managed_job_seekers
+        SET is_active = false, transferred_to_omil_id = $1, transferred_at = '2024-03-25 12:00:00+00'
+        WHERE id = $2
+        "#,
+    )
+    .bind(other_omil.id)
+    .bind(eva_managed)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    // Applications submitted on behalf of seekers, one of them in April
+    apply_on_behalf(&app, &omil, job_id, &ana, "2024-03-15 12:00:00+00").await;
+    apply_on_behalf(&app, &omil, job_id, &carla, "2024-03-16 12:00:00+00").await;
+    apply_on_behalf(&app, &omil, job_id, &bruno, "2024-04-10 12:00:00+00").await;
+
+    // Another OMIL's seeker never shows up
+    let stranger = seeker(&app, Some("male"), true).await;
+    manage(
+        &app,
+        &other_omil,
+        &stranger,
+        "2024-03-10 12:00:00+00",
+        Some("2024-03-12 12:00:00+00"),
+    )
+    .await;
+
+    let res = app.get(MARCH_2024, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["omil_id"], omil.id.to_string());
+    assert_eq!(res.body["timezone"], "America/Santiago");
+    assert_eq!(res.body["new_registrations"], counter(3, 1, 1, 0, 1, 1));
+    assert_eq!(res.body["total_active"], counter(3, 1, 1, 0, 1, 1));
+    assert_eq!(res.body["intermediated"], counter(2, 0, 1, 0, 1, 1));
+    assert_eq!(res.body["placed"], counter(2, 0, 1, 1, 0, 1));
+
+    let res = app
+        .get(
+            "/api/me/omil/reports/monthly?year=2024&month=2",
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["new_registrations"], counter(1, 0, 0, 0, 1, 0));
+    // Dani was still active when February ended
+    assert_eq!(res.body["total_active"], counter(2, 0, 0, 1, 1, 0));
+    assert_eq!(res.body["placed"], counter(0, 0, 0, 0, 0, 0));
+
+    // The other OMIL only sees its own seeker
+    let res = app.get(MARCH_2024, Some(&other_omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["new_registrations"], counter(1, 1, 0, 0, 0, 1));
+    assert_eq!(res.body["placed"], counter(1, 1, 0, 0, 0, 1));
+}
+
+#[sqlx::test]
+async fn test_transferred_seeker_counts_where_it_was_at_month_end(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+
+    let seeker = seeker(&app, Some("female"), false).await;
+    let managed_id = manage(&app, &source, &seeker, "2024-02-10 12:00:00+00", None).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/transfer", managed_id),
+            Some(&source.director),
+            json!({ "target_omil_id": target.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .post(
+            &format!(
+                "/api/me/omil/transfers/{}/accept",
+                res.body["id"].as_str().unwrap()
+            ),
+            Some(&target.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Moved today, so it was still the source's seeker when March ended
+    let res = app.get(MARCH_2024, Some(&source.director)).await;
+    assert_eq!(res.body["total_active"], counter(1, 0, 1, 0, 0, 0));
+    let res = app.get(MARCH_2024, Some(&target.director)).await;
+    assert_eq!(res.body["total_active"], counter(0, 0, 0, 0, 0, 0));
+}
+
+#[sqlx::test]
+async fn test_unknown_platform_timezone_fails_at_startup(db: PgPool) {
+    dotenvy::dotenv().ok();
+    let mut config = Config::from_env().unwrap();
+    config.platform_timezone = "Mars/Olympus".to_string();
+
+    let err = AppState::from_pool(config, db).await.err().unwrap();
+    assert!(err.to_string().contains("PLATFORM_TIMEZONE"), "{err}");
+}
+
+#[sqlx::test]
+async fn test_monthly_report_access_and_validation(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let coordinator = add_member(&app, &omil, "coordinator").await;
+    let advisor = add_member(&app, &omil, "advisor").await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app.get(MARCH_2024, Some(&coordinator)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get(MARCH_2024, Some(&advisor)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app.get(MARCH_2024, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app
+        .get(
+            "/api/me/omil/reports/monthly?year=2024&month=13",
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .get(
+            "/api/me/omil/reports/monthly?year=2024",
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let res = app
+        .get(
+            "/api/me/omil/reports/monthly/export?year=2024&month=3",
+            Some(&coordinator),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.headers[header::CONTENT_TYPE],
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+    assert_eq!(
+        res.headers[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"informe-sence-2024-03.xlsx\""
+    );
+}
@@ -0,0 +1,326 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil, TestUser};
+use empleos_inclusivos_backend::models::user::UserType;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn add_member(app: &TestApp, omil: &TestOmil, role: &str, first_name: &str) -> TestUser {
+    let member = app.create_user(UserType::OmilMember).await;
+    sqlx::query("INSERT INTO omil_members (omil_id, user_id, role) VALUES ($1, $2, $3::omil_role)")
+        .bind(omil.id)
+        .bind(member.id)
+        .bind(role)
+        .execute(app.db())
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET first_name = $2 WHERE id = $1")
+        .bind(member.id)
+        .bind(first_name)
+        .execute(app.db())
+        .await
+        .unwrap();
+    member
+}
+
+async fn manage_seeker(app: &TestApp, omil: &TestOmil) -> Uuid {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn require_approval(app: &TestApp, omil: &TestOmil) {
+    let res = app
+        .put(
+            "/api/me/omil/approval-settings",
+            Some(&omil.director),
+            json!({ "require_placement_approval": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["require_placement_approval"], true);
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Placement {
+    placement_outcome: String,
+    placed_job_id: Option<Uuid>,
+    notes: Option<String>,
+    placed: bool,
+    is_active: bool,
+}
+
+async fn placement(app: &TestApp, managed_id: Uuid) -> Placement {
+    sqlx::query_as(
+        r#"
+        SELECT placement_outcome::text, placed_job_id, notes, placed_at IS NOT NULL as placed, is_active
+        FROM omil_managed_job_seekers
+        WHERE id = $1
+        "#,
+    )
+    .bind(managed_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_advisor_placement_waits_for_approval(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let advisor = add_member(&app, &omil, "advisor", "Ana").await;
+    let coordinator = add_member(&app, &omil, "coordinator", "Carlos").await;
+    let managed_id = manage_seeker(&app, &omil).await;
+
+    // Only directors turn the rule on
+    let res = app
+        .put(
+            "/api/me/omil/approval-settings",
+            Some(&advisor),
+            json!({ "require_placement_approval": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    require_approval(&app, &omil).await;
+
+    let request = json!({ "outcome": "placed", "job_id": job_id, "notes": "Contrato indefinido" });
+    let uri = format!("/api/me/omil/job-seekers/{}/placement", managed_id);
+    let res = app.put(&uri, Some(&advisor), request.clone()).await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    assert_eq!(res.body["status"], "pending");
+    assert_eq!(res.body["action_type"], "placement");
+    let action_id = res.body["id"].as_str().unwrap().to_string();
+    assert_eq!(
+        placement(&app, managed_id).await.placement_outcome,
+        "pending"
+    );
+
+    // A second request for the same record is refused while one is open
+    let res = app.put(&uri, Some(&advisor), request.clone()).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    // Advisors don't see the queue
+    let res = app
+        .get("/api/me/omil/pending-actions", Some(&advisor))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app
+        .get("/api/me/omil/pending-actions", Some(&coordinator))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    assert_eq!(res.body[0]["requested_by_name"], "Ana User");
+
+    let approve = format!("/api/me/omil/pending-actions/{}/approve", action_id);
+    let res = app.post(&approve, Some(&coordinator), json!({})).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["action"]["status"], "approved");
+    assert_eq!(
+        res.body["action"]["reviewed_by"],
+        coordinator.id.to_string()
+    );
+    assert_eq!(
+        res.body["managed_job_seeker"]["placement_outcome"],
+        "placed"
+    );
+
+    // Same result as a direct placement, made under the approver's identity
+    let approved = placement(&app, managed_id).await;
+    let direct_id = manage_seeker(&app, &omil).await;
+    let res = app
+        .put(
+            &format!("/api/me/omil/job-seekers/{}/placement", direct_id),
+            Some(&coordinator),
+            request,
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let direct = placement(&app, direct_id).await;
+    assert_eq!(approved.placement_outcome, direct.placement_outcome);
+    assert_eq!(approved.placed_job_id, Some(job_id));
+    assert_eq!(approved.placed_job_id, direct.placed_job_id);
+    assert_eq!(approved.notes, direct.notes);
+    assert!(approved.placed && direct.placed);
+
+    let (created_by, content): (Uuid, String) = sqlx::query_as(
+        r#"
+        SELECT f.created_by, f.content
+        FROM job_seeker_followups f
+        JOIN omil_managed_job_seekers m ON m.job_seeker_id = f.job_seeker_id
+        WHERE m.id = $1 AND f.followup_type = 'placement'
+        "#,
+    )
+    .bind(managed_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(created_by, coordinator.id);
+    assert!(
+        content.starts_with("Estado actualizado a: Colocado."),
+        "{}",
+        content
+    );
+    assert!(
+        content.contains("Solicitado por Ana User, aprobado por Carlos User"),
+        "{}",
+        content
+    );
+
+    // Reviewed actions can't be reviewed again
+    let res = app.post(&approve, Some(&omil.director), json!({})).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_approval_conflicts_when_record_changed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let advisor = add_member(&app, &omil, "advisor", "Ana").await;
+    let managed_id = manage_seeker(&app, &omil).await;
+    require_approval(&app, &omil).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/deactivate", managed_id),
+            Some(&advisor),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    let action_id = res.body["id"].as_str().unwrap().to_string();
+
+    // The director records a placement in the meantime
+    let res = app
+        .put(
+            &format!("/api/me/omil/job-seekers/{}/placement", managed_id),
+            Some(&omil.director),
+            json!({ "outcome": "placed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/pending-actions/{}/approve", action_id),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+    assert!(placement(&app, managed_id).await.is_active);
+
+    // Still waiting, to be rejected or approved after a second look
+    let res = app
+        .get("/api/me/omil/pending-actions", Some(&omil.director))
+        .await;
+    assert_eq!(res.body[0]["action"]["id"], action_id);
+}
+
+#[sqlx::test]
+async fn test_rejected_deactivation_leaves_record_active(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let advisor = add_member(&app, &omil, "advisor", "Ana").await;
+    let managed_id = manage_seeker(&app, &omil).await;
+    require_approval(&app, &omil).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/deactivate", managed_id),
+            Some(&advisor),
+            json!({ "reason": "Se mudó de comuna" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::ACCEPTED, "{}", res.body);
+    assert_eq!(res.body["action_type"], "deactivation");
+    let action_id = res.body["id"].as_str().unwrap();
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/pending-actions/{}/reject", action_id),
+            Some(&omil.director),
+            json!({ "reason": "Sigue inscrito en la OMIL" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "rejected");
+    assert_eq!(res.body["review_notes"], "Sigue inscrito en la OMIL");
+    assert!(placement(&app, managed_id).await.is_active);
+
+    let res = app
+        .get(
+            "/api/me/omil/pending-actions?status=rejected",
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    let res = app
+        .get("/api/me/omil/pending-actions", Some(&omil.director))
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 0);
+}
+
+#[sqlx::test]
+async fn test_coordinators_and_unrestricted_organizations_bypass_the_queue(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let advisor = add_member(&app, &omil, "advisor", "Ana").await;
+    let coordinator = add_member(&app, &omil, "coordinator", "Carlos").await;
+    let first = manage_seeker(&app, &omil).await;
+    let second = manage_seeker(&app, &omil).await;
+
+    // Without the setting advisors act directly
+    let res = app
+        .put(
+            &format!("/api/me/omil/job-seekers/{}/placement", first),
+            Some(&advisor),
+            json!({ "outcome": "placed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(placement(&app, first).await.placement_outcome, "placed");
+
+    require_approval(&app, &omil).await;
+
+    // Outcomes other than placed never wait
+    let res = app
+        .put(
+            &format!("/api/me/omil/job-seekers/{}/placement", second),
+            Some(&advisor),
+            json!({ "outcome": "not_placed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // Coordinators act directly with the setting on
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/deactivate", second),
+            Some(&coordinator),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_active"], false);
+    assert!(!placement(&app, second).await.is_active);
+
+    let res = app
+        .get("/api/me/omil/pending-actions", Some(&coordinator))
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Registers `seeker` with the OMIL, returning the managed record ID
+async fn manage(app: &TestApp, omil: &TestOmil, seeker: &TestUser) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn request_referral(
+    app: &TestApp,
+    company: &TestCompany,
+    omil: &TestOmil,
+    job_id: Uuid,
+) -> String {
+    let res = app
+        .post(
+            "/api/me/company/referral-requests",
+            Some(&company.owner),
+            json!({
+                "omil_id": omil.id,
+                "job_id": job_id,
+                "message": "Buscamos operarios para nuestra bodega en Maipú"
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "pending");
+    res.body["id"].as_str().unwrap().to_string()
+}
+
+fn candidate_ids(body: &Value) -> Vec<String> {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["job_seeker_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_directory_shows_active_omils_with_aggregates(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+
+    for _ in 0..3 {
+        let seeker = app.create_job_seeker().await;
+        manage(&app, &omil, &seeker).await;
+    }
+    sqlx::query(
+        "UPDATE omil_managed_job_seekers SET placement_outcome = 'placed', placed_at = NOW() WHERE id IN (SELECT id FROM omil_managed_job_seekers WHERE omil_id = $1 LIMIT 1)",
+    )
+    .bind(omil.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query("INSERT INTO omil_organizations (organization_name) VALUES ('OMIL en revisión')")
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app.get("/api/omils", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let directory = res.body.as_array().unwrap();
+    assert_eq!(directory.len(), 1);
+    assert_eq!(directory[0]["id"], omil.id.to_string());
+    assert_eq!(directory[0]["active_seekers"], "0-9");
+    assert_eq!(directory[0]["placements_this_year"], 1);
+
+    let seeker = app.create_job_seeker().await;
+    let res = app.get("/api/omils", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app.get("/api/omils", Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_referral_requests_are_private_to_both_parties(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let other_company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let other_omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Another company can't request referrals for someone else's job
+    let res = app
+        .post(
+            "/api/me/company/referral-requests",
+            Some(&other_company.owner),
+            json!({ "omil_id": omil.id, "job_id": job_id, "message": "Hola" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let request_id = request_referral(&app, &company, &omil, job_id).await;
+
+    // A second open request for the same job and OMIL is refused
+    let res = app
+        .post(
+            "/api/me/company/referral-requests",
+            Some(&company.owner),
+            json!({ "omil_id": omil.id, "job_id": job_id, "message": "¿Novedades?" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let company_uri = format!("/api/me/company/referral-requests/{}", request_id);
+    let omil_uri = format!("/api/me/omil/referral-requests/{}", request_id);
+
+    assert_eq!(
+        app.get(&company_uri, Some(&other_company.owner))
+            .await
+            .status,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        app.get(&omil_uri, Some(&other_omil.director)).await.status,
+        StatusCode::NOT_FOUND
+    );
+    let res = app
+        .get("/api/me/omil/referral-requests", Some(&other_omil.director))
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 0);
+
+    let res = app
+        .post(
+            &format!("{}/accept", omil_uri),
+            Some(&other_omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app
+        .get("/api/me/omil/referral-requests", Some(&omil.director))
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+    assert_eq!(res.body[0]["company_name"], "Test SpA");
+    assert_eq!(res.body[0]["job_title"], "Test job");
+
+    let res = app
+        .post(
+            &format!("{}/decline", omil_uri),
+            Some(&omil.director),
+            json!({ "message": "Sin perfiles disponibles este mes" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "declined");
+
+    // Answered requests can't be answered again
+    let res = app
+        .post(
+            &format!("{}/accept", omil_uri),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // Both sides see the same history
+    for (uri, user) in [(&company_uri, &company.owner), (&omil_uri, &omil.director)] {
+        let res = app.get(uri, Some(user)).await;
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.body["request"]["status"], "declined");
+        let history = res.body["history"].as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0]["status"], "pending");
+        assert_eq!(history[1]["status"], "declined");
+        assert_eq!(history[1]["note"], "Sin perfiles disponibles este mes");
+    }
+}
+
+#[sqlx::test]
+async fn test_accepted_request_candidates_are_the_omils_own_seekers(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let other_omil = app.create_omil_with_director().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let managed_seeker = app.create_job_seeker().await;
+    let managed_id = manage(&app, &omil, &managed_seeker).await;
+    let transferred_seeker = app.create_job_seeker().await;
+    let transferred_id = manage(&app, &omil, &transferred_seeker).await;
+    sqlx::query(
+        "UPDATE omil_managed_job_seekers SET is_active = false, transferred_to_omil_id = $1, transferred_at = NOW() WHERE id = $2",
+    )
+    .bind(other_omil.id)
+    .bind(transferred_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let foreign_seeker = app.create_job_seeker().await;
+    manage(&app, &other_omil, &foreign_seeker).await;
+
+    let request_id = request_referral(&app, &company, &omil, job_id).await;
+    let candidates_uri = format!("/api/me/omil/referral-requests/{}/candidates", request_id);
+
+    // Nothing to browse until the request is accepted
+    let res = app.get(&candidates_uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/referral-requests/{}/accept", request_id),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["status"], "accepted");
+
+    let res = app.get(&candidates_uri, Some(&other_omil.director)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app.get(&candidates_uri, Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        candidate_ids(&res.body),
+        vec![managed_seeker.id.to_string()]
+    );
+    assert_eq!(res.body[0]["managed_id"], managed_id.to_string());
+    assert_eq!(res.body[0]["has_applied"], false);
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/apply", managed_id),
+            Some(&omil.director),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get(&candidates_uri, Some(&omil.director)).await;
+    assert_eq!(res.body[0]["has_applied"], true);
+}
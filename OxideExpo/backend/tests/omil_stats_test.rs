@@ -0,0 +1,241 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil, TestUser};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Managed seeker registered `registered_months_ago` calendar months ago
+/// (0 is the current month), placed `placed_months_ago` months ago if given
+async fn manage(
+    app: &TestApp,
+    omil: &TestOmil,
+    registered_months_ago: i32,
+    placed_months_ago: Option<i32>,
+) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query(
+        r#"
+        INSERT INTO omil_managed_job_seekers
+            (omil_id, job_seeker_id, registered_by, registered_at, placement_outcome, placed_at)
+        VALUES (
+            $1, $2, $3,
+            date_trunc('month', CURRENT_DATE) - make_interval(months => $4),
+            CASE WHEN $5::int IS NULL THEN 'pending' ELSE 'placed' END::placement_outcome,
+            date_trunc('month', CURRENT_DATE) - make_interval(months => $5::int)
+        )
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .bind(registered_months_ago)
+    .bind(placed_months_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+    seeker
+}
+
+async fn apply_on_behalf(
+    app: &TestApp,
+    omil: &TestOmil,
+    job_id: Uuid,
+    seeker: &TestUser,
+    months_ago: i32,
+) {
+    let application_id = app.create_application(job_id, seeker).await;
+    sqlx::query(
+        r#"
+        INSERT INTO omil_applications (application_id, omil_id, submitted_by, created_at)
+        VALUES ($1, $2, $3, date_trunc('month', CURRENT_DATE) - make_interval(months => $4))
+        "#,
+    )
+    .bind(application_id)
+    .bind(omil.id)
+    .bind(omil.director.id)
+    .bind(months_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn count(app: &TestApp, sql: &str, omil_id: Uuid) -> i64 {
+    sqlx::query_scalar(sql)
+        .bind(omil_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn trends(app: &TestApp, omil: &TestOmil, months: i32) -> Value {
+    let res = app
+        .get(
+            &format!("/api/me/omil/stats/trends?months={}", months),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+fn counts(series: &Value) -> Vec<i64> {
+    series
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| point["count"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_stats_match_per_query_counts(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let placed_now = manage(&app, &omil, 0, Some(0)).await;
+    manage(&app, &omil, 14, Some(13)).await;
+    let pending = manage(&app, &omil, 2, None).await;
+    let inactive = manage(&app, &omil, 0, None).await;
+    let transferred = manage(&app, &omil, 0, None).await;
+    manage(&app, &other, 0, Some(0)).await;
+    sqlx::query("UPDATE omil_managed_job_seekers SET is_active = false WHERE job_seeker_id = $1")
+        .bind(inactive.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE omil_managed_job_seekers SET transferred_at = NOW() WHERE job_seeker_id = $1",
+    )
+    .bind(transferred.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    apply_on_behalf(&app, &omil, job_id, &placed_now, 0).await;
+    apply_on_behalf(&app, &omil, job_id, &pending, 3).await;
+
+    let res = app.get("/api/me/omil/stats", Some(&omil.director)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // The counts the dashboard used to run one query at a time
+    let expected = [
+        (
+            "total_managed_seekers",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND transferred_at IS NULL",
+        ),
+        (
+            "active_seekers",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND is_active = true",
+        ),
+        (
+            "placed_this_month",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND placement_outcome = 'placed' AND placed_at >= DATE_TRUNC('month', CURRENT_DATE)",
+        ),
+        (
+            "placed_this_year",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND placement_outcome = 'placed' AND placed_at >= DATE_TRUNC('year', CURRENT_DATE)",
+        ),
+        (
+            "pending_placements",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND placement_outcome = 'pending' AND transferred_at IS NULL",
+        ),
+        (
+            "new_registrations_this_month",
+            "SELECT COUNT(*) FROM omil_managed_job_seekers WHERE omil_id = $1 AND transferred_at IS NULL AND registered_at >= DATE_TRUNC('month', CURRENT_DATE)",
+        ),
+        (
+            "total_applications_submitted",
+            "SELECT COUNT(*) FROM omil_applications WHERE omil_id = $1",
+        ),
+    ];
+    for (field, sql) in expected {
+        assert_eq!(
+            res.body[field],
+            count(&app, sql, omil.id).await,
+            "{}",
+            field
+        );
+    }
+    assert_eq!(res.body["total_managed_seekers"], 4);
+    assert_eq!(res.body["pending_placements"], 2);
+    assert_eq!(res.body["total_applications_submitted"], 2);
+}
+
+#[sqlx::test]
+async fn test_trends_fill_quiet_months_with_zeros(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let seeker = manage(&app, &omil, 4, Some(1)).await;
+    manage(&app, &omil, 4, None).await;
+    manage(&app, &omil, 0, None).await;
+    // Older than the window
+    manage(&app, &omil, 8, Some(7)).await;
+    apply_on_behalf(&app, &omil, job_id, &seeker, 2).await;
+
+    let body = trends(&app, &omil, 6).await;
+    assert_eq!(body["months"], 6);
+    assert_eq!(counts(&body["registrations"]), vec![0, 2, 0, 0, 0, 1]);
+    assert_eq!(counts(&body["placements"]), vec![0, 0, 0, 0, 1, 0]);
+    assert_eq!(
+        counts(&body["applications_submitted"]),
+        vec![0, 0, 0, 1, 0, 0]
+    );
+
+    // Consecutive months, oldest first, ending with the current one
+    let months: Vec<&str> = body["registrations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| point["month"].as_str().unwrap())
+        .collect();
+    let current: String =
+        sqlx::query_scalar("SELECT to_char(date_trunc('month', CURRENT_DATE), 'YYYY-MM-DD')")
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(months[5], current);
+    assert!(months.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(months.iter().all(|month| month.ends_with("-01")));
+
+    // An organization without any activity still gets every month
+    let quiet = app.create_omil_with_director().await;
+    let body = trends(&app, &quiet, 12).await;
+    assert_eq!(counts(&body["registrations"]), vec![0; 12]);
+    assert_eq!(counts(&body["placements"]), vec![0; 12]);
+
+    for months in [0, 37] {
+        let res = app
+            .get(
+                &format!("/api/me/omil/stats/trends?months={}", months),
+                Some(&omil.director),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[sqlx::test]
+async fn test_trends_are_cached_per_organization(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+
+    manage(&app, &omil, 0, None).await;
+    let body = trends(&app, &omil, 3).await;
+    assert_eq!(counts(&body["registrations"]), vec![0, 0, 1]);
+
+    // Served from the cache until it expires
+    manage(&app, &omil, 0, None).await;
+    let body = trends(&app, &omil, 3).await;
+    assert_eq!(counts(&body["registrations"]), vec![0, 0, 1]);
+
+    let body = trends(&app, &other, 3).await;
+    assert_eq!(counts(&body["registrations"]), vec![0, 0, 0]);
+}
@@ -0,0 +1,326 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Seeker managed by the OMIL, registered a while ago, with one shared and one private followup
+async fn create_managed_seeker(app: &TestApp, omil: &TestOmil) -> (Uuid, Uuid) {
+    let seeker = app.create_job_seeker().await;
+
+    let managed_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by, registered_at)
+        VALUES ($1, $2, $3, NOW() - INTERVAL '90 days')
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    for (content, is_private) in [("Shared note", false), ("Private note", true)] {
+        sqlx::query(
+            r#"
+            INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, content, is_private)
+            VALUES ($1, $2, $3, 'general_note', $4, $5)
+            "#,
+        )
+        .bind(seeker.id)
+        .bind(omil.director.id)
+        .bind(omil.id)
+        .bind(content)
+        .bind(is_private)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+
+    (seeker.id, managed_id)
+}
+
+async fn request_transfer(
+    app: &TestApp,
+    source: &TestOmil,
+    managed_id: Uuid,
+    target: Uuid,
+) -> Uuid {
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/transfer", managed_id),
+            Some(&source.director),
+            json!({ "target_omil_id": target, "reason": "Se mudó de comuna" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "pending");
+    res.body["id"].as_str().unwrap().parse().unwrap()
+}
+
+#[sqlx::test]
+async fn test_accept_transfer_moves_seeker_and_copies_shared_followups(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+    let (seeker_id, managed_id) = create_managed_seeker(&app, &source).await;
+
+    let transfer_id = request_transfer(&app, &source, managed_id, target.id).await;
+
+    // Shows up as incoming for the target
+    let res = app
+        .get(
+            "/api/me/omil/transfers?direction=incoming",
+            Some(&target.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["transfer"]["id"], json!(transfer_id));
+    assert_eq!(res.body[0]["direction"], "incoming");
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/transfers/{}/accept", transfer_id),
+            Some(&target.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let body = res.body;
+    assert_eq!(body["transfer"]["status"], "accepted");
+    assert_eq!(body["followups_copied"], 1);
+    assert_eq!(
+        body["managed_job_seeker"]["job_seeker_id"],
+        json!(seeker_id)
+    );
+
+    let source_registered_at: (bool, chrono::DateTime<chrono::Utc>) = sqlx::query_as(
+        "SELECT is_active, registered_at FROM omil_managed_job_seekers WHERE id = $1",
+    )
+    .bind(managed_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(!source_registered_at.0);
+    let target_registered_at: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(body["managed_job_seeker"]["registered_at"].clone()).unwrap();
+    assert_eq!(target_registered_at, source_registered_at.1);
+
+    // Private note stays behind
+    let target_managed_id = body["managed_job_seeker"]["id"].as_str().unwrap();
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}/followups", target_managed_id),
+            Some(&target.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let contents: Vec<&str> = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["followup"]["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, vec!["Shared note"]);
+
+    // The source still has both of its own notes
+    let source_followups: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM job_seeker_followups WHERE job_seeker_id = $1 AND omil_id = $2",
+    )
+    .bind(seeker_id)
+    .bind(source.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(source_followups, 2);
+}
+
+#[sqlx::test]
+async fn test_stats_after_transfer_do_not_double_count(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+    let (_, managed_id) = create_managed_seeker(&app, &source).await;
+
+    // Placed by the source before moving
+    sqlx::query(
+        "UPDATE omil_managed_job_seekers SET placement_outcome = 'placed', placed_at = NOW() WHERE id = $1",
+    )
+    .bind(managed_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let transfer_id = request_transfer(&app, &source, managed_id, target.id).await;
+    let res = app
+        .post(
+            &format!("/api/me/omil/transfers/{}/accept", transfer_id),
+            Some(&target.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let source_stats = app
+        .get("/api/me/omil/stats", Some(&source.director))
+        .await
+        .body;
+    assert_eq!(source_stats["total_managed_seekers"], 0);
+    assert_eq!(source_stats["active_seekers"], 0);
+    assert_eq!(source_stats["placed_this_month"], 1);
+
+    let target_stats = app
+        .get("/api/me/omil/stats", Some(&target.director))
+        .await
+        .body;
+    assert_eq!(target_stats["total_managed_seekers"], 1);
+    assert_eq!(target_stats["active_seekers"], 1);
+    assert_eq!(target_stats["placed_this_month"], 0);
+    assert_eq!(target_stats["pending_placements"], 1);
+    // Registration date travels, so it is not a new registration at the target
+    assert_eq!(target_stats["new_registrations_this_month"], 0);
+}
+
+#[sqlx::test]
+async fn test_cancel_pending_transfer(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+    let (_, managed_id) = create_managed_seeker(&app, &source).await;
+
+    let transfer_id = request_transfer(&app, &source, managed_id, target.id).await;
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/transfers/{}/cancel", transfer_id),
+            Some(&source.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["status"], "cancelled");
+
+    // Nothing left to accept
+    let res = app
+        .post(
+            &format!("/api/me/omil/transfers/{}/accept", transfer_id),
+            Some(&target.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+async fn accept(app: &TestApp, target: &TestOmil, transfer_id: Uuid) -> serde_json::Value {
+    let res = app
+        .post(
+            &format!("/api/me/omil/transfers/{}/accept", transfer_id),
+            Some(&target.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_source_cannot_act_for_transferred_seeker(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+    let (_, managed_id) = create_managed_seeker(&app, &source).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let transfer_id = request_transfer(&app, &source, managed_id, target.id).await;
+    accept(&app, &target, transfer_id).await;
+
+    let base = format!("/api/me/omil/job-seekers/{}", managed_id);
+    let director = Some(&source.director);
+    let responses = [
+        app.put(
+            &format!("{}/placement", base),
+            director,
+            json!({ "outcome": "placed", "job_id": job_id }),
+        )
+        .await,
+        app.post(
+            &format!("{}/apply", base),
+            director,
+            json!({ "job_id": job_id }),
+        )
+        .await,
+        app.get(&format!("{}/followups", base), director).await,
+        app.post(
+            &format!("{}/followups", base),
+            director,
+            json!({ "followup_type": "general_note", "content": "Llamada" }),
+        )
+        .await,
+    ];
+    for res in responses {
+        assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+    }
+
+    let row: (String, i64) = sqlx::query_as(
+        r#"
+        SELECT placement_outcome::text,
+            (SELECT COUNT(*) FROM job_applications WHERE job_id = $2)
+        FROM omil_managed_job_seekers WHERE id = $1
+        "#,
+    )
+    .bind(managed_id)
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(row, ("pending".to_string(), 0));
+}
+
+#[sqlx::test]
+async fn test_returning_seeker_is_unassigned(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let source = app.create_omil_with_director().await;
+    let target = app.create_omil_with_director().await;
+    let (_, managed_id) = create_managed_seeker(&app, &source).await;
+
+    // Assigned at the source before leaving
+    sqlx::query(
+        r#"
+        WITH branch AS (
+            INSERT INTO omil_branches (omil_id, name) VALUES ($1, 'Sede centro') RETURNING id
+        )
+        UPDATE omil_managed_job_seekers
+        SET assigned_advisor_id = $2, branch_id = (SELECT id FROM branch)
+        WHERE id = $3
+        "#,
+    )
+    .bind(source.id)
+    .bind(source.director.id)
+    .bind(managed_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let transfer_id = request_transfer(&app, &source, managed_id, target.id).await;
+    let body = accept(&app, &target, transfer_id).await;
+    let target_managed_id: Uuid = body["managed_job_seeker"]["id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    // And back again
+    let transfer_id = request_transfer(&app, &target, target_managed_id, source.id).await;
+    let body = accept(&app, &source, transfer_id).await;
+    let returned = &body["managed_job_seeker"];
+    assert_eq!(returned["id"], json!(managed_id));
+    assert_eq!(returned["is_active"], true);
+    assert!(returned["assigned_advisor_id"].is_null());
+    assert!(returned["branch_id"].is_null());
+}
@@ -0,0 +1,265 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::{models::user::UserType, services::onboarding};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn get_checklist(app: &TestApp, user: &TestUser) -> Value {
+    let res = app.get("/api/me/onboarding", Some(user)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+fn step<'a>(checklist: &'a Value, code: &str) -> &'a Value {
+    checklist["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["step"] == code)
+        .unwrap()
+}
+
+fn completed_steps(checklist: &Value) -> Vec<String> {
+    checklist["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|s| s["completed"] == true)
+        .map(|s| s["step"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn add_skills(app: &TestApp, user_id: Uuid, count: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO user_skills (user_id, skill_id, proficiency_level)
+        SELECT $1, id, 3 FROM skills
+        WHERE id NOT IN (SELECT skill_id FROM user_skills WHERE user_id = $1)
+        ORDER BY name
+        LIMIT $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(count)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_onboarding_steps_detected_from_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_user(UserType::JobSeeker).await;
+    sqlx::query("UPDATE users SET email_verified_at = NULL WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(checklist["steps"].as_array().unwrap().len(), 6);
+    assert!(completed_steps(&checklist).is_empty(), "{}", checklist);
+    assert_eq!(checklist["progress_percentage"], 0);
+    assert_eq!(step(&checklist, "cv")["required"], true);
+    assert_eq!(step(&checklist, "preferences")["required"], false);
+
+    sqlx::query("UPDATE users SET email_verified_at = NOW() WHERE id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(completed_steps(&checklist), ["verify_email"]);
+
+    // Basic info needs all of phone, date of birth, region and municipality
+    sqlx::query(
+        r#"
+        INSERT INTO job_seeker_profiles (user_id, phone, date_of_birth)
+        VALUES ($1, '+56911111111', '1990-05-01')
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "basic_info")["completed"], false);
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_profiles p
+        SET region_id = m.region_id, municipality_id = m.id
+        FROM (SELECT id, region_id FROM municipalities ORDER BY name LIMIT 1) m
+        WHERE p.user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(completed_steps(&checklist), ["verify_email", "basic_info"]);
+
+    sqlx::query(
+        r#"
+        INSERT INTO work_experiences (user_id, company_name, position_title, start_date)
+        VALUES ($1, 'Test Ltda', 'Operario', '2020-01-01')
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "work_experience")["completed"], true);
+
+    add_skills(&app, seeker.id, 2).await;
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "skills")["completed"], false);
+    add_skills(&app, seeker.id, 1).await;
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "skills")["completed"], true);
+
+    // The preferences row every seeker gets doesn't count until something is set
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "preferences")["completed"], false);
+    sqlx::query(
+        "UPDATE job_seeker_preferences SET preferred_job_types = '{full_time}' WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "preferences")["completed"], true);
+    assert_eq!(checklist["progress_percentage"], 83);
+    assert!(checklist["completed_at"].is_null());
+    assert!(onboarding::digest_recipients(app.db())
+        .await
+        .unwrap()
+        .is_empty());
+
+    // The CV is the last required step
+    sqlx::query(
+        "UPDATE job_seeker_profiles SET cv_url = 'https://example.cl/cv.pdf' WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(checklist["progress_percentage"], 100);
+    let completed_at = checklist["completed_at"].clone();
+    assert!(completed_at.is_string(), "{}", checklist);
+    assert_eq!(
+        onboarding::digest_recipients(app.db()).await.unwrap(),
+        vec![seeker.id]
+    );
+
+    // Completion is stamped once and stays even if a step is undone
+    sqlx::query("UPDATE job_seeker_profiles SET cv_url = NULL WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "cv")["completed"], false);
+    assert_eq!(checklist["completed_at"], completed_at);
+
+    // Seekers who turned job alerts off don't get the digest
+    sqlx::query("UPDATE job_seeker_preferences SET email_job_alerts = false WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    assert!(onboarding::digest_recipients(app.db())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test]
+async fn test_dismissing_optional_steps_is_persisted(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_user(UserType::JobSeeker).await;
+
+    let res = app
+        .post(
+            "/api/me/onboarding/preferences/dismiss",
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(step(&res.body, "preferences")["dismissed"], true);
+    // Verify email and the dismissed preferences
+    assert_eq!(res.body["progress_percentage"], 33);
+
+    // Dismissing again changes nothing
+    let res = app
+        .post(
+            "/api/me/onboarding/preferences/dismiss",
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = app
+        .post(
+            "/api/me/onboarding/work_experience/dismiss",
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let dismissed: Vec<String> =
+        sqlx::query_scalar("SELECT dismissed_steps FROM user_onboarding_state WHERE user_id = $1")
+            .bind(seeker.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(dismissed, ["preferences", "work_experience"]);
+
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "preferences")["dismissed"], true);
+    assert_eq!(step(&checklist, "work_experience")["dismissed"], true);
+    assert_eq!(checklist["progress_percentage"], 50);
+    assert!(checklist["completed_at"].is_null());
+
+    // A dismissed step that gets done shows as completed instead
+    sqlx::query(
+        r#"
+        INSERT INTO work_experiences (user_id, company_name, position_title, start_date)
+        VALUES ($1, 'Test Ltda', 'Operario', '2020-01-01')
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let checklist = get_checklist(&app, &seeker).await;
+    assert_eq!(step(&checklist, "work_experience")["completed"], true);
+    assert_eq!(step(&checklist, "work_experience")["dismissed"], false);
+
+    // Required steps can't be dismissed
+    let res = app
+        .post("/api/me/onboarding/cv/dismiss", Some(&seeker), json!({}))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/me/onboarding/unknown/dismiss",
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+
+    let company = app.create_company_with_owner().await;
+    let res = app.get("/api/me/onboarding", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+}
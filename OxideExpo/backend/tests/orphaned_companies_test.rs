@@ -0,0 +1,190 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+fn registration(email: &str, company_name: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "Password123!",
+        "first_name": "Rosa",
+        "last_name": "Pérez",
+        "company_name": company_name,
+    })
+}
+
+async fn register_company(app: &TestApp, email: &str, company_name: &str) -> Uuid {
+    let res = app
+        .post(
+            "/api/auth/register/company",
+            None,
+            registration(email, company_name),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    sqlx::query_scalar("SELECT id FROM company_profiles WHERE company_name = $1")
+        .bind(company_name)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn backdate(app: &TestApp, company_id: Uuid, days: i32) {
+    sqlx::query(
+        "UPDATE company_profiles SET created_at = NOW() - make_interval(days => $1) WHERE id = $2",
+    )
+    .bind(days)
+    .bind(company_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_failed_token_step_leaves_no_company_behind(db: PgPool) {
+    let app = TestApp::new(db).await;
+
+    // Make persisting the refresh token fail, as a broken insert would
+    sqlx::query(
+        r#"
+        CREATE FUNCTION fail_refresh_token() RETURNS TRIGGER AS $$
+        BEGIN
+            RAISE EXCEPTION 'refresh token storage unavailable';
+        END;
+        $$ LANGUAGE plpgsql
+        "#,
+    )
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        CREATE TRIGGER fail_refresh_token BEFORE INSERT ON refresh_tokens
+        FOR EACH ROW EXECUTE FUNCTION fail_refresh_token()
+        "#,
+    )
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .post(
+            "/api/auth/register/company",
+            None,
+            registration("ghost@empresa.cl", "Empresa Fantasma"),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(res.body["code"], "registration_failed");
+    assert_eq!(
+        res.body["error"],
+        "Registration failed and no account was created. Please try again."
+    );
+
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
+        .bind("ghost@empresa.cl")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    let companies: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM company_profiles WHERE company_name = $1")
+            .bind("Empresa Fantasma")
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!((users, companies), (0, 0));
+
+    // Nothing half-registered blocks a retry
+    sqlx::query("DROP TRIGGER fail_refresh_token ON refresh_tokens")
+        .execute(app.db())
+        .await
+        .unwrap();
+    register_company(&app, "ghost@empresa.cl", "Empresa Fantasma").await;
+}
+
+#[sqlx::test]
+async fn test_list_and_dismiss_orphaned_companies(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    // Owner never verified nor logged in
+    let orphan = register_company(&app, "nadie@empresa.cl", "Empresa Abandonada").await;
+    backdate(&app, orphan, 30).await;
+
+    // Owner logged in again after registering
+    let returning = register_company(&app, "vuelve@empresa.cl", "Empresa Activa").await;
+    backdate(&app, returning, 30).await;
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": "vuelve@empresa.cl", "password": "Password123!" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    // Too recent to call orphaned
+    let recent = register_company(&app, "nuevo@empresa.cl", "Empresa Reciente").await;
+    backdate(&app, recent, 3).await;
+
+    let res = app.get("/api/admin/companies/orphaned", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let listed = res.body.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["company_id"], orphan.to_string());
+    assert_eq!(listed[0]["owner_email"], "nadie@empresa.cl");
+
+    let res = app
+        .get(
+            "/api/admin/companies/orphaned?older_than_days=2",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.body.as_array().unwrap().len(), 2);
+
+    let res = app
+        .post(
+            "/api/admin/companies/orphaned/dismiss",
+            Some(&admin),
+            json!({ "company_ids": [orphan, returning, recent] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["dismissed"], json!([orphan]));
+    assert_eq!(res.body["skipped"].as_array().unwrap().len(), 2);
+
+    let (status, reason): (String, Option<String>) =
+        sqlx::query_as("SELECT status::text, rejection_reason FROM company_profiles WHERE id = $1")
+            .bind(orphan)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(status, "rejected");
+    assert!(reason.unwrap().contains("never completed"));
+
+    let logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'dismiss_orphaned_company' AND entity_id = $1",
+    )
+    .bind(orphan)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(logged, 1);
+
+    for id in [returning, recent] {
+        let status: String =
+            sqlx::query_scalar("SELECT status::text FROM company_profiles WHERE id = $1")
+                .bind(id)
+                .fetch_one(app.db())
+                .await
+                .unwrap();
+        assert_eq!(status, "pending_approval");
+    }
+
+    let res = app.get("/api/admin/companies/orphaned", Some(&admin)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 0);
+}
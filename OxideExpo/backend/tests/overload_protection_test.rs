@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use sqlx::PgPool;
+
+/// Waits until a request is blocked on a lock in this test's database,
+/// i.e. it holds its concurrency slot
+async fn wait_for_blocked_query(app: &TestApp) {
+    for _ in 0..100 {
+        let waiting: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM pg_locks l
+            JOIN pg_database d ON d.oid = l.database
+            WHERE NOT l.granted AND d.datname = current_database()
+            "#,
+        )
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+        if waiting > 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("request never blocked on the lock");
+}
+
+#[sqlx::test]
+async fn test_excess_requests_are_shed_and_slow_ones_time_out(db: PgPool) {
+    let app = Arc::new(
+        TestApp::with_config(db, |config| {
+            config.max_concurrent_requests = 1;
+            config.request_timeout_secs = 2;
+        })
+        .await,
+    );
+
+    // Keeps the regions listing waiting on the table lock, so it occupies
+    // the only slot until it times out
+    let mut lock = app.db().begin().await.unwrap();
+    sqlx::query("LOCK TABLE regions IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *lock)
+        .await
+        .unwrap();
+
+    let slow = {
+        let app = Arc::clone(&app);
+        tokio::spawn(async move { app.get("/api/reference/regions", None).await })
+    };
+    wait_for_blocked_query(&app).await;
+
+    let res = app.get("/api/reference/countries", None).await;
+    assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(res.body["error"], "Server is busy, try again shortly");
+
+    // Health checks are outside the limit
+    let res = app.get("/api/health", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = slow.await.unwrap();
+    assert_eq!(res.status, StatusCode::REQUEST_TIMEOUT);
+    assert_eq!(res.body["error"], "Request took too long to complete");
+    lock.rollback().await.unwrap();
+
+    // The slot is free again once the slow request is answered
+    let res = app.get("/api/reference/countries", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil};
+use empleos_inclusivos_backend::utils::jwt::hash_token;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Registers a beneficiary with an email, returning (managed_id, job_seeker_id)
+async fn register_with_email(app: &TestApp, omil: &TestOmil, email: &str) -> (Uuid, Uuid) {
+    let res = app
+        .post(
+            "/api/me/omil/job-seekers",
+            Some(&omil.director),
+            json!({
+                "email": email,
+                "first_name": "Rosa",
+                "last_name": "Muñoz",
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let managed_id = res.body["id"].as_str().unwrap().parse().unwrap();
+    let job_seeker_id = res.body["job_seeker_id"].as_str().unwrap().parse().unwrap();
+    (managed_id, job_seeker_id)
+}
+
+/// Sends a credentials invite and swaps its token for one the test knows;
+/// the real one only goes out by email
+async fn invite(app: &TestApp, omil: &TestOmil, managed_id: Uuid, job_seeker_id: Uuid) -> String {
+    let res = app
+        .post(
+            &format!(
+                "/api/me/omil/job-seekers/{}/send-credentials-invite",
+                managed_id
+            ),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let token = format!("setup-{}", Uuid::new_v4());
+    sqlx::query(
+        "UPDATE password_setup_tokens SET token_hash = $2 WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(job_seeker_id)
+    .bind(hash_token(&token))
+    .execute(app.db())
+    .await
+    .unwrap();
+    token
+}
+
+#[sqlx::test]
+async fn test_on_behalf_registration_has_no_password(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let email = format!("rosa-{}@test.com", Uuid::new_v4());
+    let (_, job_seeker_id) = register_with_email(&app, &omil, &email).await;
+
+    let (has_hash, status): (bool, String) = sqlx::query_as(
+        "SELECT password_hash IS NOT NULL, account_status::text FROM users WHERE id = $1",
+    )
+    .bind(job_seeker_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert!(!has_hash);
+    assert_eq!(status, "invited");
+
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": email, "password": "whatever123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    assert_eq!(res.body["code"], "password_setup_required");
+
+    // Password reset isn't a way around the invite
+    let res = app
+        .post("/api/auth/password/forgot", None, json!({ "email": email }))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let reset_tokens: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM password_reset_tokens WHERE user_id = $1")
+            .bind(job_seeker_id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(reset_tokens, 0);
+}
+
+#[sqlx::test]
+async fn test_setup_link_sets_password_and_activates(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let email = format!("rosa-{}@test.com", Uuid::new_v4());
+    let (managed_id, job_seeker_id) = register_with_email(&app, &omil, &email).await;
+
+    let token = invite(&app, &omil, managed_id, job_seeker_id).await;
+    let res = app
+        .post(
+            "/api/auth/setup-password",
+            None,
+            json!({ "token": token, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["user"]["account_status"], "active");
+    assert_eq!(res.body["user"]["email_verified"], true);
+    assert!(res.body["access_token"].is_string());
+
+    // The link works once
+    let res = app
+        .post(
+            "/api/auth/setup-password",
+            None,
+            json!({ "token": token, "password": "OtraClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let res = app
+        .post(
+            "/api/auth/login",
+            None,
+            json!({ "email": email, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // With a password set there is nothing left to invite to
+    let res = app
+        .post(
+            &format!(
+                "/api/me/omil/job-seekers/{}/send-credentials-invite",
+                managed_id
+            ),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_new_invite_replaces_open_link(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let email = format!("rosa-{}@test.com", Uuid::new_v4());
+    let (managed_id, job_seeker_id) = register_with_email(&app, &omil, &email).await;
+
+    let first = invite(&app, &omil, managed_id, job_seeker_id).await;
+    let second = invite(&app, &omil, managed_id, job_seeker_id).await;
+
+    let res = app
+        .post(
+            "/api/auth/setup-password",
+            None,
+            json!({ "token": first, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    sqlx::query("UPDATE password_setup_tokens SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app
+        .post(
+            "/api/auth/setup-password",
+            None,
+            json!({ "token": second, "password": "NuevaClave123" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn test_other_omil_cannot_send_invite(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+    let email = format!("rosa-{}@test.com", Uuid::new_v4());
+    let (managed_id, _) = register_with_email(&app, &omil, &email).await;
+
+    let res = app
+        .post(
+            &format!(
+                "/api/me/omil/job-seekers/{}/send-credentials-invite",
+                managed_id
+            ),
+            Some(&other.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND, "{}", res.body);
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn add_skills(app: &TestApp, seeker: &TestUser, count: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO user_skills (user_id, skill_id, proficiency_level)
+        SELECT $1, id, 3 FROM skills
+        WHERE id NOT IN (SELECT skill_id FROM user_skills WHERE user_id = $1)
+        ORDER BY name
+        LIMIT $2
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(count)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn detail(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    application_id: &str,
+) -> serde_json::Value {
+    let res = app
+        .get(
+            &format!(
+                "/api/me/jobs/{}/applicants/{}/detail",
+                job_id, application_id
+            ),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_detail_flags_profile_changes_since_application(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    add_skills(&app, &seeker, 1).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let application_id = res.body["id"].as_str().unwrap().to_string();
+
+    let body = detail(&app, &company, job_id, &application_id).await;
+    assert_eq!(body["profile_changed_since_application"], false);
+    assert_eq!(body["profile_changes"]["skills_added"], 0);
+
+    // Three more skills and an edited work experience
+    add_skills(&app, &seeker, 3).await;
+    sqlx::query("UPDATE work_experiences SET position_title = 'Jefe de bodega' WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let body = detail(&app, &company, job_id, &application_id).await;
+    assert_eq!(body["profile_changed_since_application"], true);
+    let changes = &body["profile_changes"];
+    assert_eq!(changes["skills_added"], 3);
+    assert_eq!(changes["skills_modified"], true);
+    assert_eq!(changes["experience_added"], 0);
+    assert_eq!(changes["experience_modified"], true);
+    assert_eq!(changes["education_modified"], false);
+}
+
+#[sqlx::test]
+async fn test_omil_application_and_unfingerprinted_application(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let managed_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/apply", managed_id),
+            Some(&omil.director),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let application_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM job_applications WHERE job_id = $1 AND applicant_id = $2",
+    )
+    .bind(job_id)
+    .bind(seeker.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query("DELETE FROM work_experiences WHERE user_id = $1")
+        .bind(seeker.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let body = detail(&app, &company, job_id, &application_id.to_string()).await;
+    assert_eq!(body["profile_changed_since_application"], true);
+    assert_eq!(body["profile_changes"]["experience_removed"], 1);
+
+    // Applications from before fingerprints carry no change signal
+    let other = app.create_job_seeker().await;
+    let old_application_id = app.create_application(job_id, &other).await;
+    let body = detail(&app, &company, job_id, &old_application_id.to_string()).await;
+    assert_eq!(body["profile_changed_since_application"], false);
+    assert!(body["profile_changes"].is_null());
+}
@@ -0,0 +1,301 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{FormPart, TestApp, TestOmil, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn add_reference(app: &TestApp, seeker: &TestUser, name: &str) -> Value {
+    let res = app
+        .post(
+            "/api/me/references",
+            Some(seeker),
+            json!({
+                "referee_name": name,
+                "referee_organization": "Panadería El Trigal",
+                "relationship": "Jefa directa",
+                "contact_email": "  Referee@Example.CL "
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+/// Registers the seeker with the OMIL, returning the managed record ID
+async fn manage(app: &TestApp, omil: &TestOmil, seeker: &TestUser) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+fn verify_uri(managed_id: Uuid, reference: &Value) -> String {
+    format!(
+        "/api/me/omil/job-seekers/{}/references/{}/verify",
+        managed_id,
+        reference["id"].as_str().unwrap()
+    )
+}
+
+/// Letter as if uploaded earlier; storage isn't reachable in tests
+async fn seed_letter(app: &TestApp, reference: &Value) -> String {
+    sqlx::query_scalar(
+        r#"
+        UPDATE profile_references
+        SET letter_file_key = 'users/' || user_id || '/references/' || gen_random_uuid() || '.pdf',
+            letter_filename = 'carta.pdf'
+        WHERE id = $1
+        RETURNING letter_file_key
+        "#,
+    )
+    .bind(Uuid::parse_str(reference["id"].as_str().unwrap()).unwrap())
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn is_queued_for_deletion(app: &TestApp, file_key: &str) -> bool {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM application_document_deletions WHERE file_key = $1)",
+    )
+    .bind(file_key)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_at_most_five_references(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+
+    let first = add_reference(&app, &seeker, "María Soto").await;
+    assert_eq!(first["contact_email"], "referee@example.cl");
+    assert_eq!(first["is_verified"], false);
+    assert!(first.get("letter_file_key").is_none());
+    for name in ["Uno", "Dos", "Tres", "Cuatro"] {
+        add_reference(&app, &seeker, name).await;
+    }
+
+    let res = app
+        .post(
+            "/api/me/references",
+            Some(&seeker),
+            json!({ "referee_name": "Seis", "relationship": "Profesor" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    assert!(res.body["error"].as_str().unwrap().contains("5 references"));
+
+    // Someone else's reference can't be touched
+    let uri = format!("/api/me/references/{}", first["id"].as_str().unwrap());
+    assert_eq!(
+        app.delete(&uri, Some(&other)).await.status,
+        StatusCode::NOT_FOUND
+    );
+
+    // Letters must be PDFs
+    let res = app
+        .post_multipart(
+            &format!("{}/letter", uri),
+            Some(&seeker),
+            &[FormPart {
+                name: "file",
+                filename: Some("carta.docx"),
+                content_type: Some("text/plain"),
+                data: b"not a letter",
+            }],
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    // Deleting a reference removes its letter and makes room for another
+    let file_key = seed_letter(&app, &first).await;
+    assert!(!is_queued_for_deletion(&app, &file_key).await);
+    let res = app.delete(&uri, Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert!(is_queued_for_deletion(&app, &file_key).await);
+
+    add_reference(&app, &seeker, "Seis").await;
+    let res = app.get("/api/me/references", Some(&seeker)).await;
+    assert_eq!(res.body.as_array().unwrap().len(), 5);
+}
+
+#[sqlx::test]
+async fn test_only_the_managing_omil_verifies(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+    let reference = add_reference(&app, &seeker, "María Soto").await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+
+    let res = app
+        .post(
+            &verify_uri(managed_id, &reference),
+            Some(&other.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    let res = app
+        .post(
+            &verify_uri(managed_id, &reference),
+            Some(&company.owner),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+    let res = app
+        .post(
+            &verify_uri(managed_id, &reference),
+            Some(&seeker),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    // Only references of the seeker the record is for
+    let stranger = app.create_job_seeker().await;
+    let stranger_reference = add_reference(&app, &stranger, "Pedro Rojas").await;
+    let res = app
+        .post(
+            &verify_uri(managed_id, &stranger_reference),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app
+        .post(
+            &verify_uri(managed_id, &reference),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["is_verified"], true);
+    assert_eq!(res.body["verified_by"], json!(omil.director.id));
+    assert!(res.body["verified_at"].is_string());
+
+    // The OMIL sees who verified it, and the referee's email
+    let res = app
+        .get(
+            &format!("/api/me/omil/job-seekers/{}/references", managed_id),
+            Some(&omil.director),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body[0]["verified_by_name"], "Test User");
+    assert_eq!(res.body[0]["contact_email"], "referee@example.cl");
+
+    // Changing who the referee is undoes the verification
+    let uri = format!("/api/me/references/{}", reference["id"].as_str().unwrap());
+    let res = app
+        .put(
+            &uri,
+            Some(&seeker),
+            json!({
+                "referee_name": "María Soto",
+                "referee_organization": "Panadería El Trigal",
+                "relationship": "Jefa directa",
+                "contact_email": "referee@example.cl"
+            }),
+        )
+        .await;
+    assert_eq!(res.body["is_verified"], true);
+    let res = app
+        .put(
+            &uri,
+            Some(&seeker),
+            json!({ "referee_name": "Otra Persona", "relationship": "Jefa directa" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["is_verified"], false);
+    assert_eq!(res.body["verified_at"], json!(null));
+
+    // Records no longer active can't verify
+    sqlx::query("UPDATE omil_managed_job_seekers SET is_active = false WHERE id = $1")
+        .bind(managed_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let res = app
+        .post(
+            &verify_uri(managed_id, &reference),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_companies_never_see_referee_emails(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    let verified = add_reference(&app, &seeker, "María Soto").await;
+    let with_letter = add_reference(&app, &seeker, "Pedro Rojas").await;
+    seed_letter(&app, &with_letter).await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+    let res = app
+        .post(
+            &verify_uri(managed_id, &verified),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let uri = format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let references = res.body["references"].as_array().unwrap();
+    assert_eq!(references.len(), 2);
+    // Verified references come first, with their badge
+    assert_eq!(references[0]["referee_name"], "María Soto");
+    assert_eq!(references[0]["is_verified"], true);
+    assert_eq!(references[0]["letter_download_url"], json!(null));
+    assert!(references[1]["letter_download_url"]
+        .as_str()
+        .unwrap()
+        .contains("X-Amz-Signature"));
+    for reference in references {
+        assert!(reference.get("contact_email").is_none());
+        assert!(reference.get("verified_by").is_none());
+    }
+    assert!(!res.body.to_string().contains("referee@example.cl"));
+
+    // Seekers can keep their references to themselves
+    let res = app
+        .put(
+            "/api/me/preferences",
+            Some(&seeker),
+            json!({ "show_references": false }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["show_references"], false);
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.body["references"], json!([]));
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn any_skill(db: &PgPool) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM skills WHERE is_active = true LIMIT 1")
+        .fetch_one(db)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_get_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app.get("/api/me/profile", Some(&seeker)).await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["user_id"], seeker.id.to_string());
+}
+
+#[sqlx::test]
+async fn test_profile_is_job_seeker_only(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    let res = app.get("/api/me/profile", Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    let res = app.get("/api/me/profile", None).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_update_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({
+                "phone": "9 1234 5678",
+                "bio": "  Desarrollador backend  ",
+                "professional_headline": "Rust developer"
+            }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["phone"], "+56912345678");
+    assert_eq!(res.body["bio"], "Desarrollador backend");
+    assert_eq!(res.body["professional_headline"], "Rust developer");
+
+    // Persisted
+    let res = app.get("/api/me/profile", Some(&seeker)).await;
+    assert_eq!(res.body["professional_headline"], "Rust developer");
+}
+
+#[sqlx::test]
+async fn test_update_profile_rejects_invalid_phone(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put("/api/me/profile", Some(&seeker), json!({ "phone": "123" }))
+        .await;
+
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_skills_crud(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let skill_id = any_skill(app.db()).await;
+
+    let res = app
+        .post(
+            "/api/me/skills",
+            Some(&seeker),
+            json!({ "skill_id": skill_id, "proficiency_level": 3 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let user_skill_id = res.body["id"].as_str().unwrap().to_string();
+
+    // Same skill twice
+    let res = app
+        .post(
+            "/api/me/skills",
+            Some(&seeker),
+            json!({ "skill_id": skill_id, "proficiency_level": 4 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::CONFLICT);
+
+    let res = app.get("/api/me/skills", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body.as_array().unwrap().len(), 1);
+
+    let res = app
+        .delete(&format!("/api/me/skills/{}", user_skill_id), Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app.get("/api/me/skills", Some(&seeker)).await;
+    assert!(res.body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn test_skills_are_isolated_between_users(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let other = app.create_job_seeker().await;
+    let skill_id = any_skill(app.db()).await;
+
+    let res = app
+        .post(
+            "/api/me/skills",
+            Some(&seeker),
+            json!({ "skill_id": skill_id, "proficiency_level": 3 }),
+        )
+        .await;
+    let user_skill_id = res.body["id"].as_str().unwrap().to_string();
+
+    let res = app
+        .delete(&format!("/api/me/skills/{}", user_skill_id), Some(&other))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app.get("/api/me/skills", Some(&other)).await;
+    assert!(res.body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn test_full_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app.get("/api/me/profile/full", Some(&seeker)).await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["profile"]["user_id"], seeker.id.to_string());
+    assert!(res.body["skills"].as_array().unwrap().is_empty());
+    assert!(res.body["disability"].is_null());
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use common::TestApp;
+use empleos_inclusivos_backend::{models::admin::MIN_REPORTABLE_CELL, services::public_stats};
+use redis::AsyncCommands;
+use serde_json::Value;
+use sqlx::PgPool;
+
+async fn clear_cache(app: &TestApp) {
+    let mut redis = app.state.redis.clone();
+    let _: () = redis.del(public_stats::CACHE_KEY).await.unwrap();
+}
+
+// The cache key is global, so this is the only test that reads through it
+#[sqlx::test]
+async fn test_stats_are_cached_and_small_counts_withheld(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    app.create_active_job(&company).await;
+    clear_cache(&app).await;
+
+    let res = app.get("/api/stats/public", None).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.headers[header::CACHE_CONTROL], "public, max-age=900");
+    assert_eq!(res.body["active_jobs"], Value::Null);
+    let generated_at = res.body["generated_at"].clone();
+
+    for _ in 0..MIN_REPORTABLE_CELL {
+        app.create_active_job(&company).await;
+    }
+
+    // Served from Redis until the entry expires
+    let res = app.get("/api/stats/public", None).await;
+    assert_eq!(res.body["active_jobs"], Value::Null);
+    assert_eq!(res.body["generated_at"], generated_at);
+
+    clear_cache(&app).await;
+    let res = app.get("/api/stats/public", None).await;
+    assert_eq!(res.body["active_jobs"], MIN_REPORTABLE_CELL + 1);
+    assert_eq!(res.body["jobs_with_accommodations"], Value::Null);
+
+    clear_cache(&app).await;
+}
+
+#[sqlx::test]
+async fn test_kill_switch_disables_endpoint(db: PgPool) {
+    let app = TestApp::with_config(db, |config| config.public_stats_enabled = false).await;
+
+    let res = app.get("/api/stats/public", None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
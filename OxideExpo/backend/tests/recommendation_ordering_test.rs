@@ -0,0 +1,192 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// `count` active jobs published at the same instant, so they tie on
+/// everything but their ID
+async fn tied_jobs(app: &TestApp, company: &TestCompany, count: usize) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    for _ in 0..count {
+        let job_id = app.create_active_job(company).await;
+        sqlx::query("UPDATE jobs SET published_at = '2024-05-01 12:00:00+00' WHERE id = $1")
+            .bind(job_id)
+            .execute(app.db())
+            .await
+            .unwrap();
+        ids.push(job_id);
+    }
+    ids
+}
+
+async fn recommended(app: &TestApp, seeker: &TestUser, params: &str) -> Value {
+    let res = app
+        .get(
+            &format!("/api/me/recommended-jobs?{}", params),
+            Some(seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+fn job_ids(body: &Value) -> Vec<Uuid> {
+    body["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|j| j["job"]["id"].as_str().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_tied_recommendations_keep_a_stable_order(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let mut tied = tied_jobs(&app, &company, 12).await;
+    let newest = app.create_active_job(&company).await;
+
+    let body = recommended(&app, &seeker, "limit=100").await;
+    assert_eq!(
+        body["ordering"],
+        "match_score + shift preference boost desc, published_at desc, id asc"
+    );
+    let scores: Vec<i64> = body["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|j| j["match_score"].as_i64().unwrap())
+        .collect();
+    assert!(scores.iter().all(|s| *s == scores[0]), "{:?}", scores);
+
+    // The newest job wins the tie on score, the rest fall back to their ID
+    let first = job_ids(&body);
+    tied.sort();
+    let mut expected = vec![newest];
+    expected.extend(&tied);
+    assert_eq!(first, expected);
+
+    for _ in 0..3 {
+        let body = recommended(&app, &seeker, "limit=100").await;
+        assert_eq!(job_ids(&body), first);
+    }
+
+    // Pages line up with the full list, without gaps or repeats
+    let mut paged = Vec::new();
+    for offset in [0, 5, 10] {
+        let body = recommended(&app, &seeker, &format!("limit=5&offset={}", offset)).await;
+        assert_eq!(body["total_count"], 13);
+        paged.extend(job_ids(&body));
+    }
+    assert_eq!(paged, first);
+}
+
+#[sqlx::test]
+async fn test_diversify_caps_jobs_per_company_in_top_ten(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let big = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    // The big company's jobs are newer, so they take the whole top otherwise
+    let big_jobs = tied_jobs(&app, &big, 8).await;
+    sqlx::query("UPDATE jobs SET published_at = '2024-06-01 12:00:00+00' WHERE id = ANY($1)")
+        .bind(&big_jobs)
+        .execute(app.db())
+        .await
+        .unwrap();
+    let mut rest = Vec::new();
+    for _ in 0..3 {
+        let company = app.create_company_with_owner().await;
+        rest.push(tied_jobs(&app, &company, 3).await);
+    }
+
+    let company_of = |id: &Uuid| match rest.iter().position(|jobs| jobs.contains(id)) {
+        Some(i) => i + 1,
+        None => 0,
+    };
+
+    let plain = job_ids(&recommended(&app, &seeker, "limit=100").await);
+    assert_eq!(
+        plain[..8].iter().filter(|id| company_of(id) == 0).count(),
+        8
+    );
+
+    let body = recommended(&app, &seeker, "limit=100&diversify=true").await;
+    assert!(body["ordering"]
+        .as_str()
+        .unwrap()
+        .contains("at most 3 jobs per company in the top 10"));
+    assert_eq!(body["total_count"], 17);
+
+    let diversified = job_ids(&body);
+    for company in 0..4 {
+        let in_top = diversified[..10]
+            .iter()
+            .filter(|id| company_of(id) == company)
+            .count();
+        assert!(
+            in_top <= 3,
+            "company {} has {} jobs in the top 10",
+            company,
+            in_top
+        );
+    }
+    // Nothing is dropped, and the demoted jobs follow right after the top
+    let mut all = diversified.clone();
+    all.sort();
+    let mut expected = plain.clone();
+    expected.sort();
+    assert_eq!(all, expected);
+    assert_eq!(&diversified[..3], &plain[..3]);
+    assert!(diversified[10..15].iter().all(|id| company_of(id) == 0));
+
+    let again = job_ids(&recommended(&app, &seeker, "limit=100&diversify=true").await);
+    assert_eq!(again, diversified);
+}
+
+#[sqlx::test]
+async fn test_tied_candidates_keep_a_stable_order(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let mut seekers = Vec::new();
+    for _ in 0..6 {
+        seekers.push(app.create_job_seeker().await.id);
+    }
+    sqlx::query("UPDATE job_seeker_profiles SET updated_at = '2024-05-01 12:00:00+00' WHERE user_id = ANY($1)")
+        .bind(&seekers)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let uri = format!("/api/me/jobs/{}/recommended-candidates?limit=100", job_id);
+    let candidate_ids = |body: &Value| -> Vec<Uuid> {
+        body["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["profile"]["user_id"].as_str().unwrap().parse().unwrap())
+            .collect()
+    };
+
+    let res = app.get(&uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["ordering"],
+        "has_applied desc, match_score + availability boost - earlier rejection penalty desc, profile_updated_at desc, user_id asc"
+    );
+    let first = candidate_ids(&res.body);
+    seekers.sort();
+    assert_eq!(first, seekers);
+
+    for _ in 0..3 {
+        let res = app.get(&uri, Some(&company.owner)).await;
+        assert_eq!(candidate_ids(&res.body), first);
+    }
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use empleos_inclusivos_backend::services::reference_seed;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+fn counts(created: i64, updated: i64, unchanged: i64) -> Value {
+    json!({ "created": created, "updated": updated, "unchanged": unchanged })
+}
+
+#[sqlx::test]
+async fn test_canonical_seed_is_idempotent(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let seed: Value = serde_json::from_str(reference_seed::CANONICAL_SEED).unwrap();
+
+    // The migrations already hold the canonical skills, languages,
+    // industries and work areas; only the institutions are new
+    let res = app
+        .post("/api/admin/reference/seed", Some(&admin), seed.clone())
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["skill_categories"], counts(0, 0, 7));
+    assert_eq!(res.body["skills"], counts(0, 0, 86));
+    assert_eq!(res.body["languages"], counts(0, 0, 16));
+    assert_eq!(res.body["industries"], counts(0, 0, 20));
+    assert_eq!(res.body["work_areas"], counts(0, 0, 15));
+    assert_eq!(res.body["institutions"], counts(20, 0, 0));
+
+    let res = app
+        .post("/api/admin/reference/seed", Some(&admin), seed)
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["skills"], counts(0, 0, 86));
+    assert_eq!(res.body["institutions"], counts(0, 0, 20));
+
+    let institutions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM institutions")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(institutions, 20);
+
+    let logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'seed_reference_data'",
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(logged, 2);
+}
+
+#[sqlx::test]
+async fn test_seed_matches_by_natural_key(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let skills_before: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM skills")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+
+    let seed = json!({
+        "skill_categories": [
+            // Existing category: Git is already in it, Terraform is new
+            { "name": "Habilidades Técnicas", "skills": ["Git", "Terraform"] },
+            // A name only has to be unique within its category
+            { "name": "Oficios", "description": "Oficios técnicos", "sort_order": 20, "skills": ["Git", "Soldadura"] }
+        ],
+        "languages": [
+            { "name": "Inglés", "iso_code": "EN" },
+            { "name": "Rapanui" }
+        ],
+        "industries": [
+            { "name": "Minería Sustentable", "description": "Minería con foco en sustentabilidad" }
+        ],
+        "institutions": [
+            { "name": "Universidad de Magallanes", "institution_type": "universidad", "country_iso": "CL" },
+            { "name": "Universidad de Magallanes", "country_iso": "CL" }
+        ]
+    });
+    let res = app
+        .post("/api/admin/reference/seed", Some(&admin), seed.clone())
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["skill_categories"], counts(1, 0, 1));
+    assert_eq!(res.body["skills"], counts(3, 0, 1));
+    assert_eq!(res.body["languages"], counts(1, 0, 1));
+    assert_eq!(res.body["industries"], counts(1, 0, 0));
+    assert_eq!(res.body["work_areas"], counts(0, 0, 0));
+    assert_eq!(res.body["institutions"], counts(1, 0, 1));
+
+    let skills_after: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM skills")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(skills_after, skills_before + 3);
+    let git_categories: Vec<String> = sqlx::query_scalar(
+        "SELECT c.name FROM skills s JOIN skill_categories c ON c.id = s.category_id WHERE s.name = 'Git' ORDER BY c.name",
+    )
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(git_categories, ["Habilidades Técnicas", "Oficios"]);
+
+    // Changed fields update the matching row instead of adding one
+    let res = app
+        .post(
+            "/api/admin/reference/seed",
+            Some(&admin),
+            json!({
+                "skill_categories": [{ "name": "Oficios", "sort_order": 5 }],
+                "industries": [{ "name": "Minería Sustentable", "description": "Minería responsable" }],
+                "institutions": [{ "name": "Universidad de Magallanes", "institution_type": "universidad_estatal", "country_iso": "CL" }]
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["skill_categories"], counts(0, 1, 0));
+    assert_eq!(res.body["industries"], counts(0, 1, 0));
+    assert_eq!(res.body["institutions"], counts(0, 1, 0));
+
+    let (description, sort_order): (Option<String>, i32) = sqlx::query_as(
+        "SELECT description, sort_order FROM skill_categories WHERE name = 'Oficios'",
+    )
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    // Fields left out of the document keep their value
+    assert_eq!(description.as_deref(), Some("Oficios técnicos"));
+    assert_eq!(sort_order, 5);
+    let institution_types: Vec<Option<String>> = sqlx::query_scalar(
+        "SELECT institution_type FROM institutions WHERE name = 'Universidad de Magallanes'",
+    )
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(institution_types, [Some("universidad_estatal".to_string())]);
+
+    // Nothing in a failing document is applied
+    let res = app
+        .post(
+            "/api/admin/reference/seed",
+            Some(&admin),
+            json!({
+                "languages": [{ "name": "Kawésqar" }],
+                "institutions": [{ "name": "Universidad Nacional", "country_iso": "XX" }]
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+    let kawesqar: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM languages WHERE name = 'Kawésqar'")
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(kawesqar, 0);
+
+    let seeker = app.create_job_seeker().await;
+    let res = app
+        .post("/api/admin/reference/seed", Some(&seeker), seed)
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestOmil, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const FEEDBACK: &str = "Buscamos al menos dos años de experiencia en bodega";
+
+struct Scenario {
+    company: TestCompany,
+    omil: TestOmil,
+    seeker: TestUser,
+    job_id: Uuid,
+    app_id: String,
+}
+
+/// A managed seeker whose OMIL applied on their behalf
+async fn omil_application(app: &TestApp) -> Scenario {
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+    let job_id = app.create_active_job(&company).await;
+
+    let managed_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+
+    let res = app
+        .post(
+            &format!("/api/me/omil/job-seekers/{}/apply", managed_id),
+            Some(&omil.director),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let app_id = res.body["application_id"].as_str().unwrap().to_string();
+
+    Scenario {
+        company,
+        omil,
+        seeker,
+        job_id,
+        app_id,
+    }
+}
+
+async fn reject(app: &TestApp, s: &Scenario, share: bool) {
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", s.job_id, s.app_id),
+            Some(&s.company.owner),
+            json!({
+                "status": "rejected",
+                "rejection_reason_code": "experience",
+                "feedback_text": FEEDBACK,
+                "share_feedback_with_candidate": share
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    // The company always sees everything it recorded
+    assert_eq!(res.body["rejection_reason_code"], "experience");
+    assert_eq!(res.body["rejection_feedback"], FEEDBACK);
+}
+
+async fn seeker_view(app: &TestApp, s: &Scenario) -> (Value, Value) {
+    let res = app
+        .get(&format!("/api/me/applications/{}", s.app_id), Some(&s.seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let detail = res.body["application"].clone();
+
+    let res = app
+        .get(
+            &format!("/api/me/applications/{}/timeline", s.app_id),
+            Some(&s.seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let rejected = res.body.as_array().unwrap().last().unwrap().clone();
+    assert_eq!(rejected["status"], "rejected");
+
+    (detail, rejected)
+}
+
+async fn omil_view(app: &TestApp, s: &Scenario) -> Value {
+    let res = app
+        .get("/api/me/omil/applications", Some(&s.omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body["applications"][0].clone()
+}
+
+#[sqlx::test]
+async fn test_unshared_rejection_visibility(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let s = omil_application(&app).await;
+    reject(&app, &s, false).await;
+
+    // The seeker only learns they were not selected
+    let (detail, timeline) = seeker_view(&app, &s).await;
+    assert_eq!(detail["status"], "rejected");
+    assert_eq!(detail["rejection_reason_code"], Value::Null);
+    assert_eq!(detail["rejection_feedback"], Value::Null);
+    assert_eq!(timeline["rejection_reason_code"], Value::Null);
+    assert_eq!(timeline["rejection_feedback"], Value::Null);
+
+    let res = app.get("/api/me/applications", Some(&s.seeker)).await;
+    assert_eq!(res.body[0]["application"]["rejection_reason_code"], Value::Null);
+
+    // The OMIL gets the code for coaching, but not the private feedback
+    let omil = omil_view(&app, &s).await;
+    assert_eq!(omil["rejection_reason_code"], "experience");
+    assert_eq!(omil["rejection_feedback"], Value::Null);
+}
+
+#[sqlx::test]
+async fn test_shared_rejection_visibility(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let s = omil_application(&app).await;
+    reject(&app, &s, true).await;
+
+    let (detail, timeline) = seeker_view(&app, &s).await;
+    assert_eq!(detail["rejection_reason_code"], "experience");
+    assert_eq!(detail["rejection_feedback"], FEEDBACK);
+    assert_eq!(timeline["rejection_reason_code"], "experience");
+    assert_eq!(timeline["rejection_feedback"], FEEDBACK);
+
+    let omil = omil_view(&app, &s).await;
+    assert_eq!(omil["rejection_reason_code"], "experience");
+    assert_eq!(omil["rejection_feedback"], FEEDBACK);
+}
+
+#[sqlx::test]
+async fn test_reason_only_accepted_when_rejecting(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let s = omil_application(&app).await;
+
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", s.job_id, s.app_id),
+            Some(&s.company.owner),
+            json!({ "status": "under_review", "rejection_reason_code": "location" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_rejection_reasons_are_aggregated(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let s = omil_application(&app).await;
+    reject(&app, &s, false).await;
+
+    // A rejection without a reason is counted separately
+    let other = app.create_job_seeker().await;
+    let other_app = app.create_application(s.job_id, &other).await;
+    let res = app
+        .put(
+            &format!("/api/me/jobs/{}/applications/{}", s.job_id, other_app),
+            Some(&s.company.owner),
+            json!({ "status": "rejected" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let res = app
+        .get("/api/me/company/dashboard", Some(&s.company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let jobs = res.body["rejection_reasons"].as_array().unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["job_id"], s.job_id.to_string());
+    let mut reasons = jobs[0]["reasons"].as_array().unwrap().clone();
+    reasons.sort_by_key(|r| r["reason"].to_string());
+    assert_eq!(
+        Value::Array(reasons),
+        json!([{ "reason": "experience", "count": 1 }, { "reason": null, "count": 1 }])
+    );
+
+    let res = app.get("/api/admin/reports/applications", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let experience = res.body["rejection_reasons"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["reason"] == "experience")
+        .unwrap();
+    assert_eq!(experience["count"], 1);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, Request, StatusCode},
+};
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+/// GETs `uri` with the given Accept-Encoding, returning the raw body
+async fn get_encoded(
+    app: &TestApp,
+    uri: &str,
+    user: Option<&TestUser>,
+    accept_encoding: &str,
+) -> (StatusCode, HeaderMap, Bytes) {
+    let mut builder = Request::builder()
+        .uri(uri)
+        .header("accept-encoding", accept_encoding);
+    if let Some(user) = user {
+        builder = builder.header("authorization", format!("Bearer {}", user.token));
+    }
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, bytes)
+}
+
+#[sqlx::test]
+async fn test_job_listing_is_compressed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    for _ in 0..10 {
+        app.create_active_job(&company).await;
+    }
+
+    let (status, headers, plain) = get_encoded(&app, "/api/jobs", None, "identity").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get("content-encoding").is_none());
+
+    for encoding in ["gzip", "br"] {
+        let (status, headers, compressed) = get_encoded(&app, "/api/jobs", None, encoding).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers.get("content-encoding").unwrap(), encoding);
+        assert!(
+            compressed.len() < plain.len(),
+            "{}: {} bytes compressed vs {} plain",
+            encoding,
+            compressed.len(),
+            plain.len()
+        );
+    }
+
+    // Small responses aren't worth compressing
+    let (status, headers, _) = get_encoded(&app, "/api/health", None, "gzip").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get("content-encoding").is_none());
+}
+
+#[sqlx::test]
+async fn test_excel_export_is_not_compressed(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    app.create_application(job_id, &seeker).await;
+
+    let (status, headers, body) = get_encoded(
+        &app,
+        &format!("/api/me/jobs/{}/applicants/export", job_id),
+        Some(&company.owner),
+        "gzip",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.len() > 1024);
+    assert!(headers.get("content-encoding").is_none());
+}
+
+#[sqlx::test]
+async fn test_full_profile_sparse_fields(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app.get("/api/me/profile/full", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    for section in [
+        "profile",
+        "disability",
+        "education",
+        "experience",
+        "skills",
+        "languages",
+        "portfolio",
+    ] {
+        assert!(res.body.get(section).is_some(), "missing {}", section);
+    }
+    assert!(res.headers.get("warning").is_none());
+
+    let res = app
+        .get("/api/me/profile/full?fields=profile,skills", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let keys: Vec<&str> = res
+        .body
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(keys.len(), 2, "{:?}", keys);
+    assert!(res.body["profile"].is_object());
+    assert!(res.body["skills"].is_array());
+
+    // Unknown names are ignored, not rejected
+    let res = app
+        .get("/api/me/profile/full?fields=skills,salary", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body, json!({ "skills": [] }));
+    assert_eq!(
+        res.headers.get("warning").unwrap(),
+        "299 - \"Unknown fields ignored: salary\""
+    );
+}
+
+#[sqlx::test]
+async fn test_job_listing_sparse_fields(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    app.create_active_job(&company).await;
+
+    let res = app.get("/api/jobs?fields=total", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body, json!({ "total": 1 }));
+
+    let res = app.get("/api/jobs?fields=jobs,bogus", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let body = res.body.as_object().unwrap();
+    assert_eq!(body.len(), 1);
+    assert_eq!(body["jobs"].as_array().map(Vec::len), Some(1));
+    assert!(res.headers.get("warning").is_some());
+
+    let res = app.get("/api/jobs", None).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    for key in ["jobs", "total", "page", "per_page", "total_pages"] {
+        assert_ne!(res.body[key], Value::Null, "missing {}", key);
+    }
+}
@@ -0,0 +1,426 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::services::retention;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn scalar(app: &TestApp, sql: &str, id: Uuid) -> i64 {
+    sqlx::query_scalar(sql)
+        .bind(id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn run(app: &TestApp, admin: &TestUser, dry_run: bool) -> Value {
+    let res = app
+        .post(
+            "/api/admin/retention/runs",
+            Some(admin),
+            json!({ "dry_run": dry_run }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+/// Rows a run reports for one entity type
+fn affected(run: &Value, entity_type: &str) -> i64 {
+    run["outcomes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|o| o["entity_type"] == entity_type)
+        .unwrap_or_else(|| panic!("no outcome for {}", entity_type))["affected_rows"]
+        .as_i64()
+        .unwrap()
+}
+
+async fn policy_id(app: &TestApp, admin: &TestUser, entity_type: &str) -> String {
+    let res = app.get("/api/admin/retention/policies", Some(admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["entity_type"] == entity_type)
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Application with free text, a note and an attached document, applied
+/// `years` ago
+async fn seed_application(app: &TestApp, years: i32) -> Uuid {
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let seeker = app.create_job_seeker().await;
+    let app_id = app.create_application(job_id, &seeker).await;
+
+    sqlx::query(
+        r#"
+        UPDATE job_applications
+        SET status = 'rejected',
+            cover_letter = 'Me interesa el cargo',
+            withdrawal_reason = 'Motivo personal',
+            rejection_feedback = 'Falta experiencia',
+            applied_at = NOW() - make_interval(years => $2),
+            reviewed_at = NOW() - make_interval(years => $2)
+        WHERE id = $1
+        "#,
+    )
+    .bind(app_id)
+    .bind(years)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO application_notes (application_id, created_by, note_text) VALUES ($1, $2, 'Buen perfil')",
+    )
+    .bind(app_id)
+    .bind(company.owner.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO application_documents (
+            application_id, file_key, label, original_filename, content_type, size, uploaded_by
+        )
+        VALUES ($1, $1::text || '/cert.pdf', 'Certificado', 'cert.pdf', 'application/pdf', 10, $2)
+        "#,
+    )
+    .bind(app_id)
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    app_id
+}
+
+async fn seed_followup(app: &TestApp, years: i32) -> Uuid {
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO job_seeker_followups (job_seeker_id, created_by, omil_id, followup_type, content, created_at)
+        VALUES ($1, $2, $3, 'general_note', 'Llamada de seguimiento', NOW() - make_interval(years => $4))
+        RETURNING id
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .bind(omil.id)
+    .bind(years)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+async fn seed_audit_log(app: &TestApp, admin: &TestUser, years: i32) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO admin_audit_logs (admin_id, action_type, entity_type, entity_id, details, created_at)
+        SELECT id, 'approve_company', 'company', uuid_generate_v4(), '{"notes": "ok"}', NOW() - make_interval(years => $2)
+        FROM admins WHERE user_id = $1
+        RETURNING id
+        "#,
+    )
+    .bind(admin.id)
+    .bind(years)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_old_applications_are_anonymized(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let old = seed_application(&app, 4).await;
+    let recent = seed_application(&app, 1).await;
+
+    let body = run(&app, &admin, false).await;
+    assert_eq!(affected(&body, "applications"), 1);
+
+    let row: (
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        String,
+        bool,
+        bool,
+    ) = sqlx::query_as(
+        r#"
+        SELECT cover_letter, withdrawal_reason, rejection_feedback, status::text,
+               reviewed_at IS NOT NULL, anonymized_at IS NOT NULL
+        FROM job_applications WHERE id = $1
+        "#,
+    )
+    .bind(old)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(row, (None, None, None, "rejected".to_string(), true, true));
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_notes WHERE application_id = $1",
+            old
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_documents WHERE application_id = $1",
+            old
+        )
+        .await,
+        0
+    );
+    // The file is queued for removal from storage
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_document_deletions WHERE file_key = $1::text || '/cert.pdf'",
+            old
+        )
+        .await,
+        1
+    );
+
+    let cover_letter: Option<String> =
+        sqlx::query_scalar("SELECT cover_letter FROM job_applications WHERE id = $1")
+            .bind(recent)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(cover_letter.as_deref(), Some("Me interesa el cargo"));
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_notes WHERE application_id = $1",
+            recent
+        )
+        .await,
+        1
+    );
+
+    // Anonymized applications are not counted again
+    let body = run(&app, &admin, false).await;
+    assert_eq!(affected(&body, "applications"), 0);
+
+    let res = app.get("/api/admin/retention/runs", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 6);
+    assert_eq!(res.body["data"][0]["triggered_by"], admin.id.to_string());
+}
+
+#[sqlx::test]
+async fn test_deleting_old_followups_and_applications(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let old_followup = seed_followup(&app, 6).await;
+    let recent_followup = seed_followup(&app, 4).await;
+
+    // The monthly job runs without an admin
+    let outcomes = retention::apply_policies(app.db(), false, None)
+        .await
+        .unwrap();
+    assert!(outcomes.iter().all(|o| o.error.is_none()));
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM job_seeker_followups WHERE id = $1",
+            old_followup
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM job_seeker_followups WHERE id = $1",
+            recent_followup
+        )
+        .await,
+        1
+    );
+    let res = app
+        .get(
+            "/api/admin/retention/runs?entity_type=omil_followups",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.body["total"], 1);
+    assert_eq!(res.body["data"][0]["affected_rows"], 1);
+    assert_eq!(res.body["data"][0]["action"], "delete");
+    assert_eq!(res.body["data"][0]["triggered_by"], Value::Null);
+
+    // Applications can be deleted instead, taking their documents along
+    let applications = policy_id(&app, &admin, "applications").await;
+    let uri = format!("/api/admin/retention/policies/{}", applications);
+    let res = app
+        .put(&uri, Some(&admin), json!({ "action": "archive" }))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+    let res = app
+        .put(
+            &uri,
+            Some(&admin),
+            json!({ "action": "delete", "age_months": 24 }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["action"], "delete");
+    assert_eq!(res.body["age_months"], 24);
+    assert_eq!(res.body["is_enabled"], true);
+
+    let old = seed_application(&app, 3).await;
+    let recent = seed_application(&app, 1).await;
+    let body = run(&app, &admin, false).await;
+    assert_eq!(affected(&body, "applications"), 1);
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM job_applications WHERE id = $1",
+            old
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM job_applications WHERE id = $1",
+            recent
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_document_deletions WHERE file_key = $1::text || '/cert.pdf'",
+            old
+        )
+        .await,
+        1
+    );
+
+    // Disabled policies are skipped
+    let res = app
+        .put(&uri, Some(&admin), json!({ "is_enabled": false }))
+        .await;
+    assert_eq!(res.body["action"], "delete");
+    let body = run(&app, &admin, true).await;
+    assert!(body["outcomes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|o| o["entity_type"] != "applications"));
+
+    let seeker = app.create_job_seeker().await;
+    let res = app
+        .get("/api/admin/retention/policies", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_old_audit_logs_are_archived(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let old = seed_audit_log(&app, &admin, 8).await;
+    let recent = seed_audit_log(&app, &admin, 6).await;
+
+    let body = run(&app, &admin, false).await;
+    assert_eq!(affected(&body, "audit_logs"), 1);
+
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM admin_audit_logs WHERE id = $1",
+            old
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM admin_audit_logs WHERE id = $1",
+            recent
+        )
+        .await,
+        1
+    );
+    let (action_type, details): (String, Value) =
+        sqlx::query_as("SELECT action_type, details FROM audit_logs_archive WHERE id = $1")
+            .bind(old)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(action_type, "approve_company");
+    assert_eq!(details, json!({ "notes": "ok" }));
+}
+
+#[sqlx::test]
+async fn test_dry_run_counts_without_changing_anything(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let application = seed_application(&app, 4).await;
+    let followup = seed_followup(&app, 6).await;
+    let audit_log = seed_audit_log(&app, &admin, 8).await;
+
+    let body = run(&app, &admin, true).await;
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(affected(&body, "applications"), 1);
+    assert_eq!(affected(&body, "omil_followups"), 1);
+    assert_eq!(affected(&body, "audit_logs"), 1);
+
+    let cover_letter: Option<String> =
+        sqlx::query_scalar("SELECT cover_letter FROM job_applications WHERE id = $1")
+            .bind(application)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert!(cover_letter.is_some());
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM application_documents WHERE application_id = $1",
+            application
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM job_seeker_followups WHERE id = $1",
+            followup
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        scalar(
+            &app,
+            "SELECT COUNT(*) FROM admin_audit_logs WHERE id = $1",
+            audit_log
+        )
+        .await,
+        1
+    );
+
+    let res = app.get("/api/admin/retention/runs", Some(&admin)).await;
+    assert_eq!(res.body["total"], 0);
+}
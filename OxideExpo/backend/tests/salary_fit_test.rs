@@ -0,0 +1,198 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::applications::SALARY_NOTICE;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Active job paying `min`-`max` per `period`
+async fn job_paying(
+    app: &TestApp,
+    company: &TestCompany,
+    min: i64,
+    max: i64,
+    currency: &str,
+    period: &str,
+) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET salary_min = $2, salary_max = $3, salary_currency = $4, salary_period = $5
+        WHERE id = $1
+        "#,
+    )
+    .bind(job_id)
+    .bind(min)
+    .bind(max)
+    .bind(currency)
+    .bind(period)
+    .execute(app.db())
+    .await
+    .unwrap();
+    job_id
+}
+
+/// Job seeker expecting `min`-`max` CLP a month
+async fn seeker_expecting(app: &TestApp, min: i64, max: i64, show: bool) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_preferences
+        SET
+            salary_expectation_min = $2, salary_expectation_max = $3, salary_currency = 'CLP',
+            show_salary_expectation = $4
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(min)
+    .bind(max)
+    .bind(show)
+    .execute(app.db())
+    .await
+    .unwrap();
+    seeker
+}
+
+async fn applicant_detail(
+    app: &TestApp,
+    company: &TestCompany,
+    job_id: Uuid,
+    seeker: &TestUser,
+) -> Value {
+    let app_id = app.create_application(job_id, seeker).await;
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_expectation_vs_range_is_below_within_or_above(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    // 4,500-5,500 an hour is about 819,000-1,001,000 a month
+    let job_id = job_paying(&app, &company, 4_500, 5_500, "CLP", "hourly").await;
+
+    for (min, max, expected) in [
+        (1_200_000, 1_500_000, "above"),
+        (900_000, 1_100_000, "within"),
+        (500_000, 700_000, "below"),
+    ] {
+        let seeker = seeker_expecting(&app, min, max, true).await;
+        let detail = applicant_detail(&app, &company, job_id, &seeker).await;
+        assert_eq!(detail["salary_expectation_vs_range"], expected);
+
+        // The seeker's own amounts are never part of it
+        let body = detail.to_string();
+        assert!(!body.contains(&min.to_string()) && !body.contains(&max.to_string()));
+    }
+}
+
+#[sqlx::test]
+async fn test_currency_mismatch_gives_no_indicator_or_warning(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = job_paying(&app, &company, 500, 800, "USD", "monthly").await;
+    let seeker = seeker_expecting(&app, 1_200_000, 1_500_000, true).await;
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": job_id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([]));
+
+    let app_id = res.body["id"].as_str().unwrap();
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants/{}/detail", job_id, app_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["salary_expectation_vs_range"], json!(null));
+}
+
+#[sqlx::test]
+async fn test_hidden_expectations_are_never_shown_or_filtered_on(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = job_paying(&app, &company, 800_000, 1_000_000, "CLP", "monthly").await;
+    let shown = seeker_expecting(&app, 900_000, 1_100_000, true).await;
+    let hidden = seeker_expecting(&app, 900_000, 1_100_000, false).await;
+
+    let detail = applicant_detail(&app, &company, job_id, &hidden).await;
+    assert_eq!(detail["salary_expectation_vs_range"], json!(null));
+
+    let res = app
+        .get(
+            &format!(
+                "/api/me/jobs/{}/recommended-candidates?limit=100&salary_fit=within",
+                job_id
+            ),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let candidates: Vec<Value> = res.body["candidates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["profile"]["user_id"].clone())
+        .collect();
+    assert_eq!(candidates, vec![json!(shown.id)]);
+}
+
+#[sqlx::test]
+async fn test_seeker_is_warned_and_can_filter_recommendations(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let low_job = job_paying(&app, &company, 500_000, 700_000, "CLP", "monthly").await;
+    // 12-15 million a year is 1-1.25 million a month
+    let fitting_job = job_paying(&app, &company, 12_000_000, 15_000_000, "CLP", "yearly").await;
+    let seeker = seeker_expecting(&app, 900_000, 1_100_000, false).await;
+
+    let res = app
+        .get("/api/me/recommended-jobs?salary_fit=within", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    let jobs: Vec<Value> = res.body["jobs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|j| j["job"]["id"].clone())
+        .collect();
+    assert_eq!(jobs, vec![json!(fitting_job)]);
+
+    // Applying still goes through, with a notice
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": low_job }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([SALARY_NOTICE]));
+
+    let res = app
+        .post(
+            "/api/me/applications",
+            Some(&seeker),
+            json!({ "job_id": fitting_job }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["warnings"], json!([]));
+}
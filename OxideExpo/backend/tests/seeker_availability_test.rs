@@ -0,0 +1,173 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Sets a seeker's availability directly; the profile's updated_at is pinned
+/// so candidates only differ by availability when ranked
+async fn set_availability(
+    app: &TestApp,
+    seeker: &TestUser,
+    availability: &str,
+    available_from: Option<&str>,
+    share_available_from: bool,
+) {
+    sqlx::query(
+        r#"
+        UPDATE job_seeker_profiles
+        SET availability = $2::seeker_availability,
+            available_from = $3::date,
+            share_available_from = $4
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(availability)
+    .bind(available_from)
+    .bind(share_available_from)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "UPDATE job_seeker_profiles SET updated_at = '2024-05-01 12:00:00+00' WHERE user_id = $1",
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn invite(app: &TestApp, company: &TestCompany, job_id: Uuid, seeker: &TestUser) -> Value {
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", job_id),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    res.body
+}
+
+#[sqlx::test]
+async fn test_availability_is_editable_on_the_profile(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app.get("/api/me/profile", Some(&seeker)).await;
+    assert_eq!(res.body["availability"], "open_to_offers");
+    assert_eq!(res.body["available_from"], Value::Null);
+    assert_eq!(res.body["share_available_from"], false);
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({
+                "professional_headline": "Operario de bodega",
+                "availability": "actively_looking",
+                "available_from": "2026-11-02",
+                "share_available_from": true
+            }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["availability"], "actively_looking");
+    assert_eq!(res.body["available_from"], "2026-11-02");
+    assert_eq!(res.body["share_available_from"], true);
+
+    // Leaving the fields out keeps them
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "professional_headline": "Operario de bodega" }),
+        )
+        .await;
+    assert_eq!(res.body["availability"], "actively_looking");
+    assert_eq!(res.body["available_from"], "2026-11-02");
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "available_from": "2026-12-01", "clear_available_from": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "availability": "not_available", "clear_available_from": true }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["availability"], "not_available");
+    assert_eq!(res.body["available_from"], Value::Null);
+}
+
+#[sqlx::test]
+async fn test_candidates_exclude_unavailable_and_boost_active_seekers(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+
+    // Identical profiles, so every seeker gets the same match score
+    let open = app.create_job_seeker().await;
+    let active = app.create_job_seeker().await;
+    let unavailable = app.create_job_seeker().await;
+    set_availability(&app, &open, "open_to_offers", Some("2026-11-02"), true).await;
+    set_availability(&app, &active, "actively_looking", Some("2026-11-02"), false).await;
+    set_availability(&app, &unavailable, "not_available", None, false).await;
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/recommended-candidates?limit=100", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        res.body["ordering"],
+        "has_applied desc, match_score + availability boost - earlier rejection penalty desc, profile_updated_at desc, user_id asc"
+    );
+
+    let candidates = res.body["candidates"].as_array().unwrap();
+    let ids: Vec<&str> = candidates
+        .iter()
+        .map(|c| c["profile"]["user_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, [active.id.to_string(), open.id.to_string()]);
+    assert_eq!(candidates[0]["match_score"], candidates[1]["match_score"]);
+
+    // Companies see the status, and the date only when the seeker shares it
+    assert_eq!(candidates[0]["profile"]["availability"], "actively_looking");
+    assert_eq!(candidates[0]["profile"]["available_from"], Value::Null);
+    assert_eq!(candidates[1]["profile"]["availability"], "open_to_offers");
+    assert_eq!(candidates[1]["profile"]["available_from"], "2026-11-02");
+}
+
+#[sqlx::test]
+async fn test_inviting_unavailable_seeker_warns(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let job_id = app.create_active_job(&company).await;
+    let available = app.create_job_seeker().await;
+    let unavailable = app.create_job_seeker().await;
+    set_availability(&app, &unavailable, "not_available", None, false).await;
+
+    let body = invite(&app, &company, job_id, &available).await;
+    assert_eq!(body["warnings"], json!([]));
+
+    // Still sent, with a warning for the company
+    let body = invite(&app, &company, job_id, &unavailable).await;
+    assert_eq!(body["warnings"], json!(["seeker_not_available"]));
+    assert_eq!(body["job_seeker_id"], unavailable.id.to_string());
+    assert_eq!(body["status"], "pending");
+}
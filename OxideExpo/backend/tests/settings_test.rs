@@ -0,0 +1,153 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use empleos_inclusivos_backend::services::settings;
+use serde_json::json;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn test_get_settings_returns_definitions(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    let res = app.get("/api/admin/settings", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let setting = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["key"] == "max_applications_per_seeker")
+        .expect("max_applications_per_seeker");
+    assert_eq!(setting["value"], 50);
+    assert_eq!(setting["definition"]["value_type"], "int");
+    assert_eq!(setting["definition"]["min"], 1);
+    assert_eq!(setting["definition"]["requires_restart"], false);
+}
+
+#[sqlx::test]
+async fn test_update_settings_rejects_wrong_type(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [{ "key": "max_applications_per_seeker", "value": "tweny" }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [{ "key": "maintenance_mode", "value": "yes" }] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_update_settings_enforces_range(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    for value in [0, 1001] {
+        let res = app
+            .put(
+                "/api/admin/settings",
+                Some(&admin),
+                json!({ "settings": [{ "key": "max_applications_per_seeker", "value": value }] }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::BAD_REQUEST, "value {}", value);
+    }
+}
+
+#[sqlx::test]
+async fn test_update_settings_rejects_unknown_key_without_partial_writes(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+
+    let res = app
+        .put(
+            "/api/admin/settings",
+            Some(&admin),
+            json!({ "settings": [
+                { "key": "maintenance_mode", "value": "read_only" },
+                { "key": "no_such_setting", "value": 1 }
+            ] }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let maintenance: serde_json::Value =
+        sqlx::query_scalar("SELECT value FROM system_settings WHERE key = 'maintenance_mode'")
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(maintenance, json!("off"));
+
+    let res = app
+        .get("/api/admin/settings/no_such_setting/history", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_update_settings_records_history_and_invalidates_cache(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let mut redis = app.state.redis.clone();
+
+    // Warm the cache with the seeded value
+    let before = settings::get_int(app.db(), &mut redis, "max_applications_per_seeker")
+        .await
+        .unwrap();
+    assert_eq!(before, 50);
+
+    for value in [20, 30] {
+        let res = app
+            .put(
+                "/api/admin/settings",
+                Some(&admin),
+                json!({ "settings": [{ "key": "max_applications_per_seeker", "value": value }] }),
+            )
+            .await;
+        assert_eq!(res.status, StatusCode::OK);
+    }
+
+    // Unchanged values are not recorded
+    app.put(
+        "/api/admin/settings",
+        Some(&admin),
+        json!({ "settings": [{ "key": "max_applications_per_seeker", "value": 30 }] }),
+    )
+    .await;
+
+    let after = settings::get_int(app.db(), &mut redis, "max_applications_per_seeker")
+        .await
+        .unwrap();
+    assert_eq!(after, 30);
+
+    let res = app
+        .get(
+            "/api/admin/settings/max_applications_per_seeker/history",
+            Some(&admin),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let history = res.body.as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["old_value"], 20);
+    assert_eq!(history[0]["new_value"], 30);
+    assert_eq!(history[1]["old_value"], 50);
+    assert_eq!(history[1]["new_value"], 20);
+    assert_eq!(history[0]["changed_by"], admin.id.to_string());
+    assert_eq!(history[0]["changed_by_name"], "Test User");
+}
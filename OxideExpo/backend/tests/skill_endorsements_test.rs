@@ -0,0 +1,197 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestOmil, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn any_skill(app: &TestApp) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM skills ORDER BY name LIMIT 1")
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+/// Adds a self-reported skill to the seeker, returning the user_skill ID
+async fn add_skill(app: &TestApp, seeker: &TestUser, skill_id: Uuid, level: i32) -> String {
+    let res = app
+        .post(
+            "/api/me/skills",
+            Some(seeker),
+            json!({ "skill_id": skill_id, "proficiency_level": level }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["is_verified"], false);
+    res.body["id"].as_str().unwrap().to_string()
+}
+
+/// Registers the seeker with the OMIL, returning the managed record ID
+async fn manage(app: &TestApp, omil: &TestOmil, seeker: &TestUser) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO omil_managed_job_seekers (omil_id, job_seeker_id, registered_by)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(omil.id)
+    .bind(seeker.id)
+    .bind(omil.director.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+fn endorse_uri(managed_id: Uuid, user_skill_id: &str) -> String {
+    format!(
+        "/api/me/omil/job-seekers/{}/skills/{}/endorse",
+        managed_id, user_skill_id
+    )
+}
+
+async fn is_verified(app: &TestApp, seeker: &TestUser, user_skill_id: &str) -> Value {
+    let res = app.get("/api/me/skills", Some(seeker)).await;
+    res.body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["id"] == user_skill_id)
+        .unwrap()["is_verified"]
+        .clone()
+}
+
+#[sqlx::test]
+async fn test_only_the_managing_omil_can_endorse(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let other = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+    let stranger = app.create_job_seeker().await;
+
+    let skill_id = any_skill(&app).await;
+    let user_skill = add_skill(&app, &seeker, skill_id, 3).await;
+    let stranger_skill = add_skill(&app, &stranger, skill_id, 3).await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+
+    // Another OMIL cannot reach the managed record
+    let res = app
+        .post(
+            &endorse_uri(managed_id, &user_skill),
+            Some(&other.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // A skill of someone the OMIL does not manage is out of scope
+    let res = app
+        .post(
+            &endorse_uri(managed_id, &stranger_skill),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let res = app
+        .post(
+            &endorse_uri(managed_id, &user_skill),
+            Some(&omil.director),
+            json!({ "comment": "Lo observamos en el taller de oficios" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["endorser_type"], "omil_member");
+    assert_eq!(is_verified(&app, &seeker, &user_skill).await, true);
+
+    let res = app
+        .delete(&endorse_uri(managed_id, &user_skill), Some(&omil.director))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(is_verified(&app, &seeker, &user_skill).await, false);
+}
+
+#[sqlx::test]
+async fn test_endorsement_covers_one_proficiency_level(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+
+    let skill_id = any_skill(&app).await;
+    let job_id = app.create_active_job(&company).await;
+    sqlx::query(
+        "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1, $2, 4)",
+    )
+    .bind(job_id)
+    .bind(skill_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+
+    let user_skill = add_skill(&app, &seeker, skill_id, 3).await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+
+    let uri = format!("/api/jobs/{}/match-score", job_id);
+    let before = app.get(&uri, Some(&seeker)).await;
+    assert_eq!(before.status, StatusCode::OK);
+    assert_eq!(
+        before.body["score_breakdown"]["skills"]["missing_required"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    app.post(
+        &endorse_uri(managed_id, &user_skill),
+        Some(&omil.director),
+        json!({}),
+    )
+    .await;
+
+    let after = app.get(&uri, Some(&seeker)).await;
+    let skills = &after.body["score_breakdown"]["skills"];
+    assert_eq!(skills["matched_required"].as_array().unwrap().len(), 1);
+    assert_eq!(skills["matched_required"][0]["user_proficiency"], 3);
+    assert!(
+        after.body["match_score"].as_i64().unwrap() > before.body["match_score"].as_i64().unwrap()
+    );
+}
+
+#[sqlx::test]
+async fn test_endorsements_removed_when_management_ends(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let omil = app.create_omil_with_director().await;
+    let seeker = app.create_job_seeker().await;
+
+    let user_skill = add_skill(&app, &seeker, any_skill(&app).await, 2).await;
+    let managed_id = manage(&app, &omil, &seeker).await;
+    app.post(
+        &endorse_uri(managed_id, &user_skill),
+        Some(&omil.director),
+        json!({}),
+    )
+    .await;
+    assert_eq!(is_verified(&app, &seeker, &user_skill).await, true);
+
+    sqlx::query("UPDATE omil_managed_job_seekers SET is_active = false WHERE id = $1")
+        .bind(managed_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    assert_eq!(is_verified(&app, &seeker, &user_skill).await, false);
+
+    // An inactive relationship cannot endorse again
+    let res = app
+        .post(
+            &endorse_uri(managed_id, &user_skill),
+            Some(&omil.director),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,316 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::skill_gap;
+use redis::AsyncCommands;
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn work_areas(app: &TestApp) -> (Uuid, Uuid) {
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM work_areas ORDER BY name LIMIT 2")
+        .fetch_all(app.db())
+        .await
+        .unwrap();
+    (ids[0], ids[1])
+}
+
+async fn skills(app: &TestApp, count: i64) -> Vec<Uuid> {
+    sqlx::query_scalar("SELECT id FROM skills ORDER BY name, id LIMIT $1")
+        .bind(count)
+        .fetch_all(app.db())
+        .await
+        .unwrap()
+}
+
+async fn regions(app: &TestApp) -> (Uuid, Uuid) {
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM regions ORDER BY sort_order LIMIT 2")
+        .fetch_all(app.db())
+        .await
+        .unwrap();
+    (ids[0], ids[1])
+}
+
+/// Active job of the work area requiring `required` (skill, level) and
+/// preferring `preferred`
+async fn job(
+    app: &TestApp,
+    company: &TestCompany,
+    work_area_id: Uuid,
+    region_id: Option<Uuid>,
+    required: &[(Uuid, i32)],
+    preferred: &[Uuid],
+) -> Uuid {
+    let job_id = app.create_active_job(company).await;
+    sqlx::query("UPDATE jobs SET work_area_id = $2, region_id = $3 WHERE id = $1")
+        .bind(job_id)
+        .bind(work_area_id)
+        .bind(region_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    for (skill_id, level) in required {
+        sqlx::query(
+            "INSERT INTO job_required_skills (job_id, skill_id, minimum_proficiency) VALUES ($1, $2, $3)",
+        )
+        .bind(job_id)
+        .bind(skill_id)
+        .bind(level)
+        .execute(app.db())
+        .await
+        .unwrap();
+    }
+    for skill_id in preferred {
+        sqlx::query("INSERT INTO job_preferred_skills (job_id, skill_id) VALUES ($1, $2)")
+            .bind(job_id)
+            .bind(skill_id)
+            .execute(app.db())
+            .await
+            .unwrap();
+    }
+    job_id
+}
+
+async fn has_skill(app: &TestApp, seeker: &TestUser, skill_id: Uuid, level: i32) {
+    sqlx::query(
+        r#"
+        INSERT INTO user_skills (user_id, skill_id, proficiency_level) VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, skill_id) DO UPDATE SET proficiency_level = $3
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(skill_id)
+    .bind(level)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn worked_in(app: &TestApp, seeker: &TestUser, work_area_id: Uuid) {
+    sqlx::query(
+        r#"
+        INSERT INTO work_experiences (user_id, company_name, position_title, work_area_id, start_date)
+        VALUES ($1, 'Acme', 'Analista', $2, CURRENT_DATE - 365)
+        "#,
+    )
+    .bind(seeker.id)
+    .bind(work_area_id)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn forget(app: &TestApp, work_area_id: Uuid, region_id: Option<Uuid>) {
+    let mut redis = app.state.redis.clone();
+    let _: () = redis
+        .del(skill_gap::cache_key(work_area_id, region_id))
+        .await
+        .unwrap();
+}
+
+fn gap_ids(body: &Value) -> Vec<Uuid> {
+    body["gaps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| serde_json::from_value(g["skill_id"].clone()).unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_gaps_are_ranked_by_jobs_out_of_reach(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let (area, other_area) = work_areas(&app).await;
+    let s = skills(&app, 5).await;
+    let (a, b, c, d, e) = (s[0], s[1], s[2], s[3], s[4]);
+
+    job(&app, &company, area, None, &[(a, 3), (b, 2)], &[e]).await;
+    job(&app, &company, area, None, &[(a, 3), (c, 4)], &[]).await;
+    job(&app, &company, area, None, &[(a, 2)], &[]).await;
+    job(&app, &company, area, None, &[(b, 4)], &[e]).await;
+    // Neither closed jobs nor other work areas count
+    let closed = job(&app, &company, area, None, &[(d, 1)], &[]).await;
+    sqlx::query("UPDATE jobs SET status = 'closed' WHERE id = $1")
+        .bind(closed)
+        .execute(app.db())
+        .await
+        .unwrap();
+    job(&app, &company, other_area, None, &[(d, 1)], &[]).await;
+
+    let seeker = app.create_job_seeker().await;
+    worked_in(&app, &seeker, area).await;
+    has_skill(&app, &seeker, b, 2).await;
+    has_skill(&app, &seeker, c, 5).await;
+    forget(&app, area, None).await;
+
+    let res = app.get("/api/me/skill-gap", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["work_area_id"], area.to_string());
+    assert_eq!(res.body["region_id"], Value::Null);
+    assert_eq!(res.body["active_jobs"], 4);
+    assert_eq!(gap_ids(&res.body), vec![a, b, e]);
+
+    let gaps = &res.body["gaps"];
+    // Half of two jobs' requirements and all of a third one's
+    assert_eq!(gaps[0]["jobs_requiring"], 3);
+    assert_eq!(gaps[0]["required_proficiency"], 3);
+    assert_eq!(gaps[0]["seeker_proficiency"], Value::Null);
+    assert_eq!(gaps[0]["average_score_uplift"], 23.3);
+    // Level 2 already meets the first job, not the fourth
+    assert_eq!(gaps[1]["jobs_requiring"], 1);
+    assert_eq!(gaps[1]["required_proficiency"], 4);
+    assert_eq!(gaps[1]["seeker_proficiency"], 2);
+    assert_eq!(gaps[1]["average_score_uplift"], 35.0);
+    assert_eq!(gaps[2]["jobs_requiring"], 0);
+    assert_eq!(gaps[2]["jobs_preferring"], 2);
+    assert_eq!(gaps[2]["required_proficiency"], Value::Null);
+    assert_eq!(gaps[2]["average_score_uplift"], 5.0);
+
+    assert_eq!(res.body["covered"].as_array().unwrap().len(), 1);
+    assert_eq!(res.body["covered"][0]["skill_id"], c.to_string());
+    assert_eq!(res.body["covered"][0]["jobs_requiring"], 1);
+
+    forget(&app, area, None).await;
+}
+
+#[sqlx::test]
+async fn test_overlay_uses_the_seekers_current_skills(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let (area, _) = work_areas(&app).await;
+    let s = skills(&app, 2).await;
+    let (a, b) = (s[0], s[1]);
+    job(&app, &company, area, None, &[(a, 3)], &[b]).await;
+    job(&app, &company, area, None, &[(a, 2)], &[]).await;
+    forget(&app, area, None).await;
+
+    let first = app.create_job_seeker().await;
+    let second = app.create_job_seeker().await;
+    has_skill(&app, &second, a, 2).await;
+    has_skill(&app, &second, b, 1).await;
+
+    let uri = format!("/api/me/skill-gap?work_area_id={}", area);
+    let res = app.get(&uri, Some(&first)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(gap_ids(&res.body), vec![a, b]);
+    assert_eq!(res.body["gaps"][0]["jobs_requiring"], 2);
+
+    // Same cached demand, another seeker's skills on top
+    let res = app.get(&uri, Some(&second)).await;
+    assert_eq!(gap_ids(&res.body), vec![a]);
+    assert_eq!(res.body["gaps"][0]["jobs_requiring"], 1);
+    assert_eq!(res.body["gaps"][0]["seeker_proficiency"], 2);
+    assert_eq!(res.body["covered"][0]["skill_id"], b.to_string());
+
+    // An endorsement counts a level higher, as in matching
+    let omil = app.create_omil_with_director().await;
+    sqlx::query(
+        r#"
+        INSERT INTO skill_endorsements (user_skill_id, endorsed_by, omil_id)
+        SELECT us.id, $2, $3 FROM user_skills us WHERE us.user_id = $1 AND us.skill_id = $4
+        "#,
+    )
+    .bind(second.id)
+    .bind(omil.director.id)
+    .bind(omil.id)
+    .bind(a)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app.get(&uri, Some(&second)).await;
+    assert_eq!(gap_ids(&res.body), Vec::<Uuid>::new());
+    assert_eq!(res.body["covered"].as_array().unwrap().len(), 2);
+
+    forget(&app, area, None).await;
+}
+
+#[sqlx::test]
+async fn test_regional_and_national_demand_are_cached_apart(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let (area, _) = work_areas(&app).await;
+    let (home, away) = regions(&app).await;
+    let s = skills(&app, 3).await;
+    let (a, b, c) = (s[0], s[1], s[2]);
+    job(&app, &company, area, Some(home), &[(a, 1)], &[]).await;
+    job(&app, &company, area, Some(away), &[(b, 1)], &[]).await;
+    job(&app, &company, area, Some(away), &[(b, 1)], &[]).await;
+    forget(&app, area, None).await;
+    forget(&app, area, Some(home)).await;
+
+    let seeker = app.create_job_seeker().await;
+    worked_in(&app, &seeker, area).await;
+    sqlx::query("UPDATE job_seeker_profiles SET region_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(home)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    let res = app
+        .get("/api/me/skill-gap?in_my_region=true", Some(&seeker))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["region_id"], home.to_string());
+    assert_eq!(res.body["active_jobs"], 1);
+    assert_eq!(gap_ids(&res.body), vec![a]);
+
+    let res = app.get("/api/me/skill-gap", Some(&seeker)).await;
+    assert_eq!(res.body["active_jobs"], 3);
+    assert_eq!(gap_ids(&res.body), vec![b, a]);
+
+    // Both are cached under their own key: dropping the regional entry
+    // brings a new job in there only
+    job(&app, &company, area, Some(home), &[(c, 1)], &[]).await;
+    forget(&app, area, Some(home)).await;
+    let res = app
+        .get("/api/me/skill-gap?in_my_region=true", Some(&seeker))
+        .await;
+    assert_eq!(res.body["active_jobs"], 2);
+    assert_eq!(gap_ids(&res.body), vec![a, c]);
+    let res = app.get("/api/me/skill-gap", Some(&seeker)).await;
+    assert_eq!(res.body["active_jobs"], 3);
+    assert_eq!(gap_ids(&res.body), vec![b, a]);
+
+    forget(&app, area, None).await;
+    forget(&app, area, Some(home)).await;
+}
+
+#[sqlx::test]
+async fn test_skill_gap_needs_a_work_area_and_region(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    // No experience to take the work area from
+    let res = app.get("/api/me/skill-gap", Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let res = app
+        .get(
+            &format!("/api/me/skill-gap?work_area_id={}", Uuid::new_v4()),
+            Some(&seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    // No region on the profile
+    let (area, _) = work_areas(&app).await;
+    let res = app
+        .get(
+            &format!("/api/me/skill-gap?work_area_id={}&in_my_region=true", area),
+            Some(&seeker),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+
+    let company = app.create_company_with_owner().await;
+    let res = app
+        .get(
+            &format!("/api/me/skill-gap?work_area_id={}", area),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
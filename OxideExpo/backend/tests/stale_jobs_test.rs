@@ -0,0 +1,212 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use common::{wait_for_messages, TestApp, TestCompany, TestUser};
+use empleos_inclusivos_backend::services::stale_jobs;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Active job with applications from two new seekers
+async fn job_with_applicants(app: &TestApp, company: &TestCompany) -> (Uuid, TestUser, TestUser) {
+    let job_id = app.create_active_job(company).await;
+    let first = app.create_job_seeker().await;
+    let second = app.create_job_seeker().await;
+    app.create_application(job_id, &first).await;
+    app.create_application(job_id, &second).await;
+    (job_id, first, second)
+}
+
+async fn nudge_state(app: &TestApp, job_id: Uuid) -> (String, i32, bool) {
+    sqlx::query_as(
+        "SELECT status::text, stale_nudge_count, stale_paused_at IS NOT NULL FROM jobs WHERE id = $1",
+    )
+    .bind(job_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_unanswered_nudges_pause_the_job(db: PgPool) {
+    let (app, inbox) = TestApp::with_inbox(db).await;
+    let company = app.create_company_with_owner().await;
+    let (job_id, first, second) = job_with_applicants(&app, &company).await;
+    // Candidates already turned down aren't told about the pause
+    let rejected = app.create_job_seeker().await;
+    let rejected_app = app.create_application(job_id, &rejected).await;
+    sqlx::query("UPDATE job_applications SET status = 'rejected' WHERE id = $1")
+        .bind(rejected_app)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    // Not stale yet
+    let run = stale_jobs::run(&app.state, Utc::now()).await.unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (0, 0));
+
+    let start = Utc::now() + Duration::days(15);
+    let run = stale_jobs::run(&app.state, start).await.unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (1, 0));
+    assert_eq!(
+        nudge_state(&app, job_id).await,
+        ("active".to_string(), 1, false)
+    );
+    let messages = wait_for_messages(&inbox, 1).await;
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains(&company.owner.email));
+    assert!(messages[0].contains("Subject: Postulaciones sin revisar: Test job"));
+
+    // Companies get a week to answer each nudge
+    let run = stale_jobs::run(&app.state, start + Duration::days(3))
+        .await
+        .unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (0, 0));
+
+    let run = stale_jobs::run(&app.state, start + Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (1, 0));
+    assert_eq!(
+        nudge_state(&app, job_id).await,
+        ("active".to_string(), 2, false)
+    );
+    wait_for_messages(&inbox, 2).await;
+
+    let run = stale_jobs::run(&app.state, start + Duration::days(14))
+        .await
+        .unwrap();
+    assert_eq!(
+        (run.nudged_jobs, run.paused_jobs, run.notified_applicants),
+        (0, 1, 2)
+    );
+    assert_eq!(
+        nudge_state(&app, job_id).await,
+        ("paused".to_string(), 2, true)
+    );
+
+    let messages = wait_for_messages(&inbox, 5).await;
+    assert_eq!(messages.len(), 5);
+    let paused = &messages[2..];
+    assert!(paused.iter().any(
+        |m| m.contains(&company.owner.email) && m.contains("Subject: Oferta pausada: Test job")
+    ));
+    assert!(paused.iter().any(|m| m.contains(&first.email)));
+    assert!(paused.iter().any(|m| m.contains(&second.email)));
+    assert!(!paused.iter().any(|m| m.contains(&rejected.email)));
+
+    // Paused jobs are left alone
+    let run = stale_jobs::run(&app.state, start + Duration::days(21))
+        .await
+        .unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (0, 0));
+
+    let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM stale_job_runs")
+        .fetch_one(app.db())
+        .await
+        .unwrap();
+    assert_eq!(runs, 6);
+}
+
+#[sqlx::test]
+async fn test_reviewing_applicants_answers_the_nudge(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let (job_id, _, _) = job_with_applicants(&app, &company).await;
+
+    let start = Utc::now() + Duration::days(15);
+    stale_jobs::run(&app.state, start).await.unwrap();
+    assert_eq!(nudge_state(&app, job_id).await.1, 1);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants", job_id),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(nudge_state(&app, job_id).await.1, 0);
+
+    // The applications still wait, so the count starts over from the visit
+    let visited = Utc::now();
+    let run = stale_jobs::run(&app.state, visited + Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!(run.nudged_jobs, 0);
+    let run = stale_jobs::run(&app.state, visited + Duration::days(15))
+        .await
+        .unwrap();
+    assert_eq!(run.nudged_jobs, 1);
+    assert_eq!(nudge_state(&app, job_id).await.1, 1);
+}
+
+#[sqlx::test]
+async fn test_reactivating_a_paused_job_starts_over(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+    let (job_id, _, _) = job_with_applicants(&app, &company).await;
+
+    let start = Utc::now() + Duration::days(15);
+    for week in 0..3 {
+        stale_jobs::run(&app.state, start + Duration::days(7 * week))
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        nudge_state(&app, job_id).await,
+        ("paused".to_string(), 2, true)
+    );
+
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", job_id),
+            Some(&company.owner),
+            json!({ "status": "active" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        nudge_state(&app, job_id).await,
+        ("active".to_string(), 0, false)
+    );
+
+    let run = stale_jobs::run(&app.state, Utc::now() + Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!((run.nudged_jobs, run.paused_jobs), (0, 0));
+}
+
+#[sqlx::test]
+async fn test_admins_list_stale_job_runs(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    job_with_applicants(&app, &company).await;
+
+    let start = Utc::now() + Duration::days(15);
+    stale_jobs::run(&app.state, start).await.unwrap();
+    stale_jobs::run(&app.state, start + Duration::days(7))
+        .await
+        .unwrap();
+
+    let res = app
+        .get("/api/admin/stale-jobs/runs?limit=1", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["total"], 2);
+    let runs = res.body["data"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0]["nudged_jobs"], 1);
+    let newest = runs[0]["ran_at"].as_str().unwrap().to_string();
+
+    let res = app
+        .get("/api/admin/stale-jobs/runs?limit=1&offset=1", Some(&admin))
+        .await;
+    assert!(res.body["data"][0]["ran_at"].as_str().unwrap() < newest.as_str());
+
+    let res = app
+        .get("/api/admin/stale-jobs/runs", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
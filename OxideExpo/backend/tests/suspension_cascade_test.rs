@@ -0,0 +1,189 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn set_status(app: &TestApp, admin: &TestUser, user_id: Uuid, status: &str) {
+    let res = app
+        .patch(
+            &format!("/api/admin/users/{}/status", user_id),
+            Some(admin),
+            json!({ "status": status, "reason": "Revisión de cuenta" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+async fn status_of(app: &TestApp, table: &str, id: Uuid) -> String {
+    sqlx::query_scalar(&format!("SELECT status::text FROM {} WHERE id = $1", table))
+        .bind(id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn set_job_status(app: &TestApp, job_id: Uuid, status: &str) {
+    sqlx::query("UPDATE jobs SET status = $2::job_status WHERE id = $1")
+        .bind(job_id)
+        .bind(status)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+async fn on_hold(app: &TestApp, application_id: Uuid) -> bool {
+    sqlx::query_scalar("SELECT is_on_hold FROM job_applications WHERE id = $1")
+        .bind(application_id)
+        .fetch_one(app.db())
+        .await
+        .unwrap()
+}
+
+async fn last_audit_details(app: &TestApp, user_id: Uuid) -> Value {
+    sqlx::query_scalar(
+        "SELECT details FROM admin_audit_logs WHERE entity_id = $1 ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(user_id)
+    .fetch_one(app.db())
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_owner_suspension_round_trip_restores_prior_statuses(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+
+    let active = app.create_active_job(&company).await;
+    let closed = app.create_active_job(&company).await;
+    set_job_status(&app, closed, "closed").await;
+    let draft = app.create_active_job(&company).await;
+    set_job_status(&app, draft, "draft").await;
+
+    set_status(&app, &admin, company.owner.id, "suspended").await;
+
+    assert_eq!(
+        status_of(&app, "company_profiles", company.id).await,
+        "suspended"
+    );
+    assert_eq!(status_of(&app, "jobs", active).await, "paused");
+    assert_eq!(status_of(&app, "jobs", closed).await, "closed");
+    assert_eq!(status_of(&app, "jobs", draft).await, "draft");
+
+    let res = app.get(&format!("/api/jobs/{}", active), None).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+
+    let details = last_audit_details(&app, company.owner.id).await;
+    assert_eq!(details["cascade"]["company_id"], company.id.to_string());
+    assert_eq!(details["cascade"]["job_ids"], json!([active]));
+
+    set_status(&app, &admin, company.owner.id, "active").await;
+
+    assert_eq!(
+        status_of(&app, "company_profiles", company.id).await,
+        "active"
+    );
+    assert_eq!(status_of(&app, "jobs", active).await, "active");
+    assert_eq!(status_of(&app, "jobs", closed).await, "closed");
+    assert_eq!(status_of(&app, "jobs", draft).await, "draft");
+
+    let details = last_audit_details(&app, company.owner.id).await;
+    assert_eq!(details["cascade"]["job_ids"], json!([active]));
+}
+
+#[sqlx::test]
+async fn test_reactivation_keeps_changes_made_while_suspended(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let first = app.create_active_job(&company).await;
+    let second = app.create_active_job(&company).await;
+
+    set_status(&app, &admin, company.owner.id, "suspended").await;
+    // Closed by an admin during the suspension
+    set_job_status(&app, second, "closed").await;
+    set_status(&app, &admin, company.owner.id, "active").await;
+
+    assert_eq!(status_of(&app, "jobs", first).await, "active");
+    assert_eq!(status_of(&app, "jobs", second).await, "closed");
+
+    // Nothing is left to restore a second time
+    let remaining: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM suspension_cascades WHERE user_id = $1")
+            .bind(company.owner.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[sqlx::test]
+async fn test_co_owned_company_is_not_suspended(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let job = app.create_active_job(&company).await;
+
+    let co_owner = app
+        .create_user(empleos_inclusivos_backend::models::user::UserType::CompanyMember)
+        .await;
+    sqlx::query("INSERT INTO company_members (company_id, user_id, role) VALUES ($1, $2, 'owner')")
+        .bind(company.id)
+        .bind(co_owner.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    set_status(&app, &admin, company.owner.id, "suspended").await;
+
+    assert_eq!(
+        status_of(&app, "company_profiles", company.id).await,
+        "active"
+    );
+    assert_eq!(status_of(&app, "jobs", job).await, "active");
+}
+
+#[sqlx::test]
+async fn test_seeker_suspension_holds_open_applications(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    let open_job = app.create_active_job(&company).await;
+    let open = app.create_application(open_job, &seeker).await;
+    let rejected_job = app.create_active_job(&company).await;
+    let rejected = app.create_application(rejected_job, &seeker).await;
+    sqlx::query("UPDATE job_applications SET status = 'rejected' WHERE id = $1")
+        .bind(rejected)
+        .execute(app.db())
+        .await
+        .unwrap();
+
+    set_status(&app, &admin, seeker.id, "suspended").await;
+
+    assert!(on_hold(&app, open).await);
+    assert!(!on_hold(&app, rejected).await);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/applicants", open_job),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["applicants"][0]["is_on_hold"], true);
+
+    set_status(&app, &admin, seeker.id, "active").await;
+
+    assert!(!on_hold(&app, open).await);
+    assert_eq!(status_of(&app, "job_applications", open).await, "submitted");
+    assert_eq!(
+        status_of(&app, "job_applications", rejected).await,
+        "rejected"
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::{
+    middleware::token_version_key, models::user::UserType, utils::create_access_token,
+};
+use redis::AsyncCommands;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Simulates the 60 second cache running out
+async fn expire_cached_version(app: &TestApp, user_id: Uuid) {
+    let mut redis = app.state.redis.clone();
+    let _: () = redis.del(token_version_key(user_id)).await.unwrap();
+}
+
+#[sqlx::test]
+async fn test_suspension_rejects_existing_tokens(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_user(UserType::JobSeeker).await;
+    let other = app.create_user(UserType::JobSeeker).await;
+    let admin = app.create_admin().await;
+
+    assert_eq!(
+        app.get("/api/auth/me", Some(&user)).await.status,
+        StatusCode::OK
+    );
+    assert_eq!(
+        app.get("/api/auth/me", Some(&other)).await.status,
+        StatusCode::OK
+    );
+
+    let res = app
+        .patch(
+            &format!("/api/admin/users/{}/status", user.id),
+            Some(&admin),
+            json!({ "status": "suspended", "reason": "Repeated policy violations" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    expire_cached_version(&app, user.id).await;
+
+    assert_eq!(
+        app.get("/api/auth/me", Some(&user)).await.status,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        app.get("/api/auth/me", Some(&other)).await.status,
+        StatusCode::OK
+    );
+}
+
+#[sqlx::test]
+async fn test_version_bump_applies_after_cache_expiry(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let user = app.create_user(UserType::JobSeeker).await;
+
+    // Caches version 0
+    assert_eq!(
+        app.get("/api/auth/me", Some(&user)).await.status,
+        StatusCode::OK
+    );
+
+    // Bumped without clearing the cache, e.g. from another instance whose Redis call failed
+    sqlx::query("UPDATE users SET token_version = token_version + 1 WHERE id = $1")
+        .bind(user.id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    assert_eq!(
+        app.get("/api/auth/me", Some(&user)).await.status,
+        StatusCode::OK
+    );
+
+    expire_cached_version(&app, user.id).await;
+    assert_eq!(
+        app.get("/api/auth/me", Some(&user)).await.status,
+        StatusCode::UNAUTHORIZED
+    );
+
+    // A token issued at the new version works
+    let (token, _) = create_access_token(
+        user.id,
+        &user.email,
+        UserType::JobSeeker,
+        1,
+        &app.state.config,
+    )
+    .unwrap();
+    let fresh = TestUser { token, ..user };
+    assert_eq!(
+        app.get("/api/auth/me", Some(&fresh)).await.status,
+        StatusCode::OK
+    );
+}
@@ -0,0 +1,271 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use common::{TestApp, TestCompany};
+use empleos_inclusivos_backend::services::{metering, scheduler::publish_due_jobs};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Metered events of the company, as (event_type, quantity) by type name
+async fn usage_events(app: &TestApp, company: &TestCompany) -> Vec<(String, i64)> {
+    sqlx::query_as(
+        r#"
+        SELECT event_type::text, SUM(quantity)
+        FROM usage_events
+        WHERE company_id = $1
+        GROUP BY event_type
+        ORDER BY event_type
+        "#,
+    )
+    .bind(company.id)
+    .fetch_all(app.db())
+    .await
+    .unwrap()
+}
+
+async fn set_status(app: &TestApp, job_id: Uuid, status: &str) {
+    sqlx::query("UPDATE jobs SET status = $2::job_status WHERE id = $1")
+        .bind(job_id)
+        .bind(status)
+        .execute(app.db())
+        .await
+        .unwrap();
+}
+
+/// Event of the company on `day`, noon in the platform time zone
+async fn event_on(
+    app: &TestApp,
+    company: &TestCompany,
+    event_type: &str,
+    quantity: i32,
+    day: &str,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO usage_events (company_id, event_type, quantity, occurred_at)
+        VALUES ($1, $2::usage_event_type, $3, ($4::date + TIME '12:00') AT TIME ZONE $5)
+        "#,
+    )
+    .bind(company.id)
+    .bind(event_type)
+    .bind(quantity)
+    .bind(day)
+    .bind(&app.state.config.platform_timezone)
+    .execute(app.db())
+    .await
+    .unwrap();
+}
+
+async fn aggregate(app: &TestApp, month: &str) -> u64 {
+    let month = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").unwrap();
+    metering::aggregate_month(app.db(), month, &app.state.config.platform_timezone)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_metered_actions_are_recorded(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let company = app.create_company_with_owner().await;
+    let seeker = app.create_job_seeker().await;
+
+    // Reactivated by the company, approved by an admin, published on schedule
+    let paused = app.create_active_job(&company).await;
+    set_status(&app, paused, "paused").await;
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", paused),
+            Some(&company.owner),
+            json!({ "status": "active" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let pending = app.create_active_job(&company).await;
+    set_status(&app, pending, "pending_approval").await;
+    let res = app
+        .patch(
+            &format!("/api/admin/jobs/{}/approve", pending),
+            Some(&admin),
+            json!({}),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let scheduled = app.create_active_job(&company).await;
+    sqlx::query(
+        "UPDATE jobs SET status = 'scheduled', publish_at = NOW() - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(scheduled)
+    .execute(app.db())
+    .await
+    .unwrap();
+    assert_eq!(publish_due_jobs(app.db()).await.unwrap(), vec![scheduled]);
+
+    let res = app
+        .get(
+            &format!("/api/me/jobs/{}/recommended-candidates", paused),
+            Some(&company.owner),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = app
+        .post(
+            &format!("/api/me/jobs/{}/invitations", paused),
+            Some(&company.owner),
+            json!({ "job_seeker_id": seeker.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let application_id = app.create_application(paused, &seeker).await;
+    let cv_uri = format!("/api/me/jobs/{}/applicants/{}/cv", paused, application_id);
+    // No CV yet, so nothing was downloaded
+    let res = app.get(&cv_uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+    sqlx::query(
+        r#"
+        WITH f AS (
+            INSERT INTO uploaded_files (user_id, file_type, original_filename, storage_path, content_type)
+            VALUES ($1, 'cv', 'cv.pdf', 'cvs/cv.pdf', 'application/pdf')
+            RETURNING id
+        )
+        UPDATE job_seeker_profiles SET cv_file_id = (SELECT id FROM f) WHERE user_id = $1
+        "#,
+    )
+    .bind(seeker.id)
+    .execute(app.db())
+    .await
+    .unwrap();
+    let res = app.get(&cv_uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    assert_eq!(
+        usage_events(&app, &company).await,
+        vec![
+            ("candidate_search".to_string(), 1),
+            ("cv_download".to_string(), 1),
+            ("invitation_sent".to_string(), 1),
+            ("job_published".to_string(), 3),
+        ]
+    );
+
+    // Closing or pausing again isn't a publication
+    let res = app
+        .patch(
+            &format!("/api/me/jobs/{}/status", paused),
+            Some(&company.owner),
+            json!({ "status": "closed" }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(
+        usage_events(&app, &company).await[3],
+        ("job_published".to_string(), 3)
+    );
+}
+
+#[sqlx::test]
+async fn test_aggregating_a_month_again_overwrites_it(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let company = app.create_company_with_owner().await;
+
+    event_on(&app, &company, "candidate_search", 1, "2025-03-01").await;
+    event_on(&app, &company, "candidate_search", 2, "2025-03-31").await;
+    event_on(&app, &company, "cv_download", 1, "2025-03-15").await;
+    // Other months stay out
+    event_on(&app, &company, "candidate_search", 5, "2025-02-28").await;
+    event_on(&app, &company, "candidate_search", 5, "2025-04-01").await;
+
+    let uri = "/api/me/company/usage?month=2025-03";
+    let res = app.get(uri, Some(&company.owner)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["aggregated_at"], Value::Null);
+    assert_eq!(res.body["usage"]["candidate_search"], 0);
+
+    assert_eq!(aggregate(&app, "2025-03").await, 2);
+    assert_eq!(aggregate(&app, "2025-03").await, 2);
+    let res = app.get(uri, Some(&company.owner)).await;
+    assert_eq!(res.body["month"], "2025-03");
+    assert!(res.body["aggregated_at"].is_string());
+    assert_eq!(
+        res.body["usage"],
+        json!({
+            "job_published": 0,
+            "candidate_search": 3,
+            "invitation_sent": 0,
+            "cv_download": 1,
+        })
+    );
+
+    // A late event is picked up by the next run, not added on top
+    event_on(&app, &company, "invitation_sent", 1, "2025-03-20").await;
+    assert_eq!(aggregate(&app, "2025-03").await, 3);
+    let res = app.get(uri, Some(&company.owner)).await;
+    assert_eq!(res.body["usage"]["candidate_search"], 3);
+    assert_eq!(res.body["usage"]["invitation_sent"], 1);
+
+    let res = app
+        .get("/api/me/company/usage?month=March", Some(&company.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_companies_only_see_their_own_usage(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let admin = app.create_admin().await;
+    let first = app.create_company_with_owner().await;
+    let second = app.create_company_with_owner().await;
+    event_on(&app, &first, "job_published", 2, "2025-05-10").await;
+    event_on(&app, &second, "cv_download", 4, "2025-05-11").await;
+    aggregate(&app, "2025-05").await;
+
+    let uri = "/api/me/company/usage?month=2025-05";
+    let res = app.get(uri, Some(&first.owner)).await;
+    assert_eq!(res.body["usage"]["job_published"], 2);
+    assert_eq!(res.body["usage"]["cv_download"], 0);
+    let res = app.get(uri, Some(&second.owner)).await;
+    assert_eq!(res.body["usage"]["job_published"], 0);
+    assert_eq!(res.body["usage"]["cv_download"], 4);
+
+    let seeker = app.create_job_seeker().await;
+    let res = app.get(uri, Some(&seeker)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+
+    // Admins see every company, with the totals
+    let res = app
+        .get("/api/admin/usage?month=2025-05", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let companies = res.body["companies"].as_array().unwrap();
+    assert_eq!(companies.len(), 2);
+    let row = |id: Uuid| -> &Value {
+        companies
+            .iter()
+            .find(|c| c["company_id"] == json!(id))
+            .unwrap()
+    };
+    assert_eq!(row(first.id)["usage"]["job_published"], 2);
+    assert_eq!(row(second.id)["usage"]["cv_download"], 4);
+    assert_eq!(res.body["totals"]["job_published"], 2);
+    assert_eq!(res.body["totals"]["cv_download"], 4);
+
+    let res = app
+        .get("/api/admin/usage/export?month=2025-05", Some(&admin))
+        .await;
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(
+        res.headers["content-type"],
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+
+    let res = app
+        .get("/api/admin/usage?month=2025-05", Some(&first.owner))
+        .await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN);
+}
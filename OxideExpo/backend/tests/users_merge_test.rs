@@ -0,0 +1,223 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TestUser};
+use empleos_inclusivos_backend::utils::rut;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Valid RUT in canonical form
+fn valid_rut(body: u32) -> String {
+    format!("{}-{}", body, rut::check_digit(body))
+}
+
+async fn seeker_with_rut(app: &TestApp, national_id: &str) -> TestUser {
+    let seeker = app.create_job_seeker().await;
+    sqlx::query("UPDATE job_seeker_profiles SET national_id = $2 WHERE user_id = $1")
+        .bind(seeker.id)
+        .bind(national_id)
+        .execute(app.db())
+        .await
+        .unwrap();
+    seeker
+}
+
+/// Application submitted `days_ago`
+async fn apply(app: &TestApp, job_id: Uuid, applicant: &TestUser, days_ago: i32) -> Uuid {
+    let application_id = app.create_application(job_id, applicant).await;
+    sqlx::query(
+        "UPDATE job_applications SET applied_at = NOW() - make_interval(days => $2) WHERE id = $1",
+    )
+    .bind(application_id)
+    .bind(days_ago)
+    .execute(app.db())
+    .await
+    .unwrap();
+    application_id
+}
+
+#[sqlx::test]
+async fn test_update_profile_rejects_taken_national_id(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let national_id = valid_rut(12_345_678);
+
+    let existing = seeker_with_rut(&app, &national_id).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "national_id": national_id }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::CONFLICT);
+    let message = res.body["error"].as_str().unwrap();
+    assert!(!message.contains(&existing.email));
+    assert!(message.contains(existing.email.split_once('@').unwrap().1));
+}
+
+#[sqlx::test]
+async fn test_update_profile_keeps_own_national_id(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let national_id = valid_rut(12_345_678);
+    let seeker = seeker_with_rut(&app, &national_id).await;
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "national_id": national_id }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_update_profile_normalizes_rut_and_phone(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let national_id = valid_rut(12_345_678);
+    let (body, dv) = national_id.split_once('-').unwrap();
+    let dotted = format!(
+        " {}.{}.{}-{} ",
+        &body[..2],
+        &body[2..5],
+        &body[5..],
+        dv.to_lowercase()
+    );
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "national_id": dotted, "phone": "9 1234 5678" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::OK);
+    assert_eq!(res.body["national_id"], national_id.as_str());
+    assert_eq!(res.body["phone"], "+56912345678");
+}
+
+#[sqlx::test]
+async fn test_update_profile_rejects_bad_check_digit(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+
+    let res = app
+        .put(
+            "/api/me/profile",
+            Some(&seeker),
+            json!({ "national_id": "12.345.678-9" }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_merge_users_repoints_data_and_keeps_earlier_application(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let national_id = valid_rut(12_345_678);
+
+    let primary = seeker_with_rut(&app, &national_id).await;
+    let duplicate = seeker_with_rut(&app, &national_id).await;
+    let admin = app.create_admin().await;
+
+    // Shows up as a duplicate group
+    let res = app.get("/api/admin/users/duplicates", Some(&admin)).await;
+    assert_eq!(res.status, StatusCode::OK);
+    let group = res
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|g| g["national_id"] == national_id.as_str())
+        .expect("duplicate group");
+    assert_eq!(group["users"].as_array().unwrap().len(), 2);
+
+    // Both applied to the shared job; the duplicate applied first
+    let company = app.create_company_with_owner().await;
+    let shared_job = app.create_active_job(&company).await;
+    let only_duplicate_job = app.create_active_job(&company).await;
+    let later_app = apply(&app, shared_job, &primary, 1).await;
+    let earlier_app = apply(&app, shared_job, &duplicate, 5).await;
+    let moved_app = apply(&app, only_duplicate_job, &duplicate, 2).await;
+
+    // Saved by both: the duplicate's row is skipped
+    for user_id in [primary.id, duplicate.id] {
+        sqlx::query("INSERT INTO saved_jobs (user_id, job_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(shared_job)
+            .execute(app.db())
+            .await
+            .unwrap();
+    }
+
+    let res = app
+        .post(
+            "/api/admin/users/merge",
+            Some(&admin),
+            json!({ "primary_user_id": primary.id, "duplicate_user_id": duplicate.id }),
+        )
+        .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["applications_removed"], json!([later_app]));
+    assert_eq!(res.body["saved_jobs_moved"], 0);
+
+    let applications: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM job_applications WHERE applicant_id = $1 ORDER BY applied_at",
+    )
+    .bind(primary.id)
+    .fetch_all(app.db())
+    .await
+    .unwrap();
+    assert_eq!(applications, vec![earlier_app, moved_app]);
+
+    let leftover: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM job_applications WHERE applicant_id = $1")
+            .bind(duplicate.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(leftover, 0);
+
+    let duplicate_status: String =
+        sqlx::query_scalar("SELECT account_status::text FROM users WHERE id = $1")
+            .bind(duplicate.id)
+            .fetch_one(app.db())
+            .await
+            .unwrap();
+    assert_eq!(duplicate_status, "deactivated");
+
+    let audit_entries: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM admin_audit_logs WHERE action_type = 'merge_users' AND entity_id = $1",
+    )
+    .bind(primary.id)
+    .fetch_one(app.db())
+    .await
+    .unwrap();
+    assert_eq!(audit_entries, 1);
+}
+
+#[sqlx::test]
+async fn test_merge_users_rejects_same_account(db: PgPool) {
+    let app = TestApp::new(db).await;
+    let seeker = app.create_job_seeker().await;
+    let admin = app.create_admin().await;
+
+    let res = app
+        .post(
+            "/api/admin/users/merge",
+            Some(&admin),
+            json!({ "primary_user_id": seeker.id, "duplicate_user_id": seeker.id }),
+        )
+        .await;
+
+    assert_eq!(res.status, StatusCode::BAD_REQUEST);
+}
@@ -140,7 +140,8 @@ test.describe('V5: Job Management', () => {
         job_type: 'full_time',
         industry_id: refData.industryId,
         work_modality: 'hybrid',
-        work_schedule: '9am-6pm flexible',
+        schedule_details: '9am-6pm flexible',
+        shift_type: 'flexible',
         region_id: refData.regionId,
         is_remote_allowed: true,
         education_level: 'Bachelor\'s degree in Computer Science',
@@ -322,7 +322,9 @@ export interface CompanyJob {
   responsibilities?: string;
   job_type: string;
   work_modality?: string;
-  work_schedule?: string;
+  schedule_details?: string;
+  shift_type?: 'full_time' | 'part_time' | 'shifts' | 'flexible';
+  weekly_hours?: number;
   region_id?: string;
   municipality_id?: string;
   is_remote_allowed: boolean;